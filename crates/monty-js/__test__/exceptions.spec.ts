@@ -147,6 +147,53 @@ fail()
   t.is(error.message, 'ValueError: from function')
 })
 
+test('exception cause', (t) => {
+  const code = `
+try:
+    1 / 0
+except ZeroDivisionError as e:
+    raise ValueError('bad value') from e
+`
+  const m = new Monty(code)
+  const error = t.throws(() => m.run(), isRuntimeError)
+  t.is(error.message, 'ValueError: bad value')
+  t.true(error.cause instanceof MontyRuntimeError)
+  t.is(error.cause!.message, 'ZeroDivisionError: division by zero')
+})
+
+test('exception context', (t) => {
+  const code = `
+try:
+    1 / 0
+except ZeroDivisionError:
+    raise ValueError('bad value')
+`
+  const m = new Monty(code)
+  const error = t.throws(() => m.run(), isRuntimeError)
+  t.is(error.cause!.message, 'ZeroDivisionError: division by zero')
+  t.true(error.display('traceback').includes('During handling of the above exception, another exception occurred:'))
+})
+
+test('exception from none has no cause', (t) => {
+  const code = `
+try:
+    1 / 0
+except ZeroDivisionError:
+    raise ValueError('bad value') from None
+`
+  const m = new Monty(code)
+  const error = t.throws(() => m.run(), isRuntimeError)
+  t.is(error.cause, undefined)
+})
+
+test('exception group', (t) => {
+  const m = new Monty("raise ExceptionGroup('eg', [ValueError('a'), TypeError('b')])")
+  const error = t.throws(() => m.run(), isRuntimeError)
+  t.is(error.message, 'ExceptionGroup: eg')
+  t.deepEqual(error.exceptions.map((e) => e.message), ['ValueError: a', 'TypeError: b'])
+  t.true(error.display('traceback').includes('Exception Group Traceback (most recent call last):'))
+})
+
 // =============================================================================
 // Display and str methods tests
 // =============================================================================
@@ -94,6 +94,28 @@ test('external function returns complex type', (t) => {
   t.is(nested.get('nested'), true)
 })
 
+test('external function as decorator', (t) => {
+  const code = `
+@register
+def double(x):
+    return x * 2
+
+double(21)
+`
+  const m = new Monty(code, { externalFunctions: ['register'] })
+
+  const registered: { __monty_type__: string; name: string }[] = []
+  const register = (func: { __monty_type__: string; name: string }) => {
+    registered.push(func)
+    return func
+  }
+
+  t.is(m.run({ externalFunctions: { register } }), 42)
+  t.is(registered.length, 1)
+  t.is(registered[0].__monty_type__, 'Function')
+  t.is(registered[0].name, 'double')
+})
+
 // =============================================================================
 // Multiple external functions tests
 // =============================================================================
@@ -0,0 +1,61 @@
+import test from 'ava'
+
+import { Monty, MontySnapshot, MontyRuntimeError } from '../wrapper'
+
+type Stream = 'stdout' | 'stderr'
+
+function makeCollector(): [Record<Stream, string[]>, (stream: Stream, text: string) => void] {
+  const output: Record<Stream, string[]> = { stdout: [], stderr: [] }
+  return [output, (stream, text) => output[stream].push(text)]
+}
+
+// =============================================================================
+// printCallback tests
+// =============================================================================
+
+test('print output reaches printCallback', (t) => {
+  const m = new Monty('print("hello", 1)')
+  const [output, printCallback] = makeCollector()
+  m.run({ printCallback })
+  t.is(output.stdout.join(''), 'hello 1\n')
+  t.deepEqual(output.stderr, [])
+})
+
+test('print file=sys.stderr uses the stderr stream', (t) => {
+  const code = `
+import sys
+print('result')
+print('warning', file=sys.stderr)
+sys.stderr.write('more')
+`
+  const m = new Monty(code)
+  const [output, printCallback] = makeCollector()
+  m.run({ printCallback })
+  t.is(output.stdout.join(''), 'result\n')
+  t.is(output.stderr.join(''), 'warning\nmore')
+})
+
+test('printCallback with start and resume', (t) => {
+  const code = `
+import sys
+print('before')
+x = func()
+print('after', x, file=sys.stderr)
+`
+  const m = new Monty(code, { externalFunctions: ['func'] })
+  const [output, printCallback] = makeCollector()
+  const progress = m.start({ printCallback })
+  t.true(progress instanceof MontySnapshot)
+  ;(progress as MontySnapshot).resume({ returnValue: 1, printCallback })
+  t.is(output.stdout.join(''), 'before\n')
+  t.is(output.stderr.join(''), 'after 1\n')
+})
+
+test('printCallback error is raised in the interpreter', (t) => {
+  const m = new Monty('print("hello")')
+  const printCallback = () => {
+    throw new Error('print failed')
+  }
+  const error = t.throws(() => m.run({ printCallback }), { instanceOf: MontyRuntimeError })
+  t.true(error?.message.includes('print failed'))
+})
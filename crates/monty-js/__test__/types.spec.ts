@@ -87,6 +87,53 @@ test('bytes result', (t) => {
   t.deepEqual([...result], [104, 101, 108, 108, 111])
 })
 
+// =============================================================================
+// Bytearray tests
+// =============================================================================
+
+test('bytearray roundtrip', (t) => {
+  const m = new Monty('x', { inputs: ['x'] })
+  const result = m.run({ inputs: { x: { __monty_type__: 'ByteArray', value: Buffer.from('hello') } } })
+  t.is(result.__monty_type__, 'ByteArray')
+  t.true(Buffer.isBuffer(result.value))
+  t.deepEqual([...result.value], [104, 101, 108, 108, 111])
+})
+
+test('bytearray input', (t) => {
+  const m = new Monty('x.append(33)\n(type(x).__name__, bytes(x))', { inputs: ['x'] })
+  const [name, value] = m.run({ inputs: { x: { __monty_type__: 'ByteArray', value: Buffer.from('hi') } } })
+  t.is(name, 'bytearray')
+  t.deepEqual([...value], [104, 105, 33])
+})
+
+test('bytearray output', (t) => {
+  const m = new Monty('bytearray(b"ab")')
+  const result = m.run()
+  t.is(result.__monty_type__, 'ByteArray')
+  t.deepEqual([...result.value], [97, 98])
+})
+
+// =============================================================================
+// Complex tests
+// =============================================================================
+
+test('complex roundtrip', (t) => {
+  const m = new Monty('x', { inputs: ['x'] })
+  const complex = { __monty_type__: 'Complex', real: 1.5, imag: -2 }
+  t.deepEqual(m.run({ inputs: { x: complex } }), complex)
+})
+
+test('complex input', (t) => {
+  const m = new Monty('(type(x).__name__, x.real, x.imag)', { inputs: ['x'] })
+  const result = m.run({ inputs: { x: { __monty_type__: 'Complex', real: 1, imag: 2 } } })
+  t.deepEqual([...result], ['complex', 1, 2])
+})
+
+test('complex output', (t) => {
+  const m = new Monty('(1 + 2j) * 1j')
+  t.deepEqual(m.run(), { __monty_type__: 'Complex', real: -2, imag: 1 })
+})
+
 // =============================================================================
 // List tests
 // =============================================================================
@@ -317,3 +364,61 @@ test('bigint in collection', (t) => {
   const result = m.run({ inputs: { x: [big, 42, big * 2n] } })
   t.deepEqual(result, [big, 42, big * 2n])
 })
+
+// =============================================================================
+// datetime tests
+// =============================================================================
+
+test('js date input', (t) => {
+  const m = new Monty('(x.year, x.month, x.day, x.hour, x.minute, x.utcoffset().total_seconds())', { inputs: ['x'] })
+  const result = m.run({ inputs: { x: new Date(Date.UTC(2024, 0, 15, 10, 20)) } })
+  t.deepEqual([...result], [2024, 1, 15, 10, 20, 0])
+})
+
+test('datetime output', (t) => {
+  const m = new Monty(
+    'from datetime import datetime, timedelta, timezone\ndatetime(2024, 1, 15, 12, tzinfo=timezone(timedelta(hours=2)))',
+  )
+  const result = m.run()
+  t.true(result instanceof Date)
+  t.is(result.toISOString(), '2024-01-15T10:00:00.000Z')
+})
+
+test('js date roundtrip', (t) => {
+  const date = new Date(Date.UTC(2024, 5, 1, 8, 30, 15, 250))
+  const m = new Monty('from datetime import timedelta\nx + timedelta(days=1)', { inputs: ['x'] })
+  t.is(m.run({ inputs: { x: date } }).getTime(), date.getTime() + 86_400_000)
+})
+
+test('date output', (t) => {
+  const m = new Monty('from datetime import date\ndate(2024, 2, 29)')
+  t.deepEqual(m.run(), { __monty_type__: 'Date', year: 2024, month: 2, day: 29 })
+})
+
+test('date input', (t) => {
+  const m = new Monty('x.isoformat()', { inputs: ['x'] })
+  t.is(m.run({ inputs: { x: { __monty_type__: 'Date', year: 2024, month: 2, day: 29 } } }), '2024-02-29')
+})
+
+test('time output', (t) => {
+  const m = new Monty('from datetime import time\ntime(13, 5, 9, 250)')
+  t.deepEqual(m.run(), {
+    __monty_type__: 'Time',
+    hour: 13,
+    minute: 5,
+    second: 9,
+    microsecond: 250,
+    utcOffset: null,
+  })
+})
+
+test('timedelta roundtrip', (t) => {
+  const m = new Monty('x * 2', { inputs: ['x'] })
+  const result = m.run({ inputs: { x: { __monty_type__: 'TimeDelta', days: 1, seconds: 3600, microseconds: 0 } } })
+  t.deepEqual(result, { __monty_type__: 'TimeDelta', days: 2, seconds: 7200, microseconds: 0 })
+})
+
+test('timezone output', (t) => {
+  const m = new Monty('from datetime import timedelta, timezone\ntimezone(timedelta(hours=-5), "EST")')
+  t.deepEqual(m.run(), { __monty_type__: 'TimeZone', utcOffset: -18000, name: 'EST' })
+})
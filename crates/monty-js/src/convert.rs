@@ -18,6 +18,8 @@
 //! - `MontyObject::Dict` ↔ `Map` (preserves key types and insertion order)
 //! - `MontyObject::Set` ↔ `Set`
 //! - `MontyObject::FrozenSet` ↔ `Set` (JS has no frozen set)
+//! - `MontyObject::DateTime` ↔ `Date` (naive datetimes are taken as UTC, microseconds are truncated
+//!   to milliseconds, and a JS `Date` always converts to an aware UTC datetime)
 //!
 //! ### Marked JS types (with `__monty_type__` property):
 //! - `MontyObject::Ellipsis` → `{ __monty_type__: 'Ellipsis' }`
@@ -25,7 +27,17 @@
 //! - `MontyObject::Exception` → `{ __monty_type__: 'Exception', excType, message }`
 //! - `MontyObject::Type` → `{ __monty_type__: 'Type', value }`
 //! - `MontyObject::BuiltinFunction` → `{ __monty_type__: 'BuiltinFunction', value }`
+//! - `MontyObject::Function` ↔ `{ __monty_type__: 'Function', name, id }`
+//! - `MontyObject::ByteArray` ↔ `{ __monty_type__: 'ByteArray', value }` (`value` is a `Buffer`)
+//! - `MontyObject::Complex` ↔ `{ __monty_type__: 'Complex', real, imag }`
+//! - `MontyObject::Date` ↔ `{ __monty_type__: 'Date', year, month, day }`
+//! - `MontyObject::Time` ↔ `{ __monty_type__: 'Time', hour, minute, second, microsecond, utcOffset }`
+//! - `MontyObject::TimeDelta` ↔ `{ __monty_type__: 'TimeDelta', days, seconds, microseconds }`
+//! - `MontyObject::TimeZone` ↔ `{ __monty_type__: 'TimeZone', utcOffset, name }`
+//! - `MontyObject::Decimal` ↔ `{ __monty_type__: 'Decimal', value }` (`value` is a `string`)
+//! - `MontyObject::Fraction` ↔ `{ __monty_type__: 'Fraction', numerator, denominator }` (as `BigInt`s)
 //! - `MontyObject::Dataclass` → `{ __monty_type__: 'Dataclass', name, fields, ... }`
+//! - `MontyObject::Enum` → its value
 //! - `MontyObject::Repr` → plain `string`
 //! - `MontyObject::Cycle` → placeholder `string`
 
@@ -39,6 +51,9 @@ use num_bigint::BigInt as NumBigInt;
 const JS_SAFE_INT_MIN: i64 = -(1_i64 << 53);
 const JS_SAFE_INT_MAX: i64 = 1_i64 << 53;
 
+/// Milliseconds in a day, the unit JS `Date` timestamps are counted in.
+const MS_PER_DAY: i64 = 86_400_000;
+
 /// Wrapper for returning an unknown JS value from napi functions.
 ///
 /// This allows `monty_to_js` to return dynamically typed JS values.
@@ -76,17 +91,22 @@ pub fn monty_to_js<'e>(obj: &MontyObject, env: &'e Env) -> Result<JsMontyObject<
         MontyObject::Int(i) => create_js_int(*i, env)?,
         MontyObject::BigInt(bi) => create_js_bigint(bi, env)?,
         MontyObject::Float(f) => env.create_double(*f)?.into_unknown(env)?,
+        MontyObject::Complex { real, imag } => create_js_complex(*real, *imag, env)?,
         MontyObject::String(s) => env.create_string(s)?.into_unknown(env)?,
         MontyObject::Bytes(bytes) => create_js_buffer(bytes, env)?,
+        MontyObject::ByteArray(bytes) => create_js_bytearray(bytes, env)?,
         MontyObject::List(items) => create_js_array(items, env)?.into_unknown(env)?,
         MontyObject::Tuple(items) => create_js_tuple(items, env)?,
         // NamedTuple is converted to a tuple (loses named access in JS)
         MontyObject::NamedTuple { values, .. } => create_js_tuple(values, env)?,
+        // Enum members are converted to their values (JS has no enums)
+        MontyObject::Enum { value, .. } => monty_to_js(value, env)?.0,
         MontyObject::Dict(pairs) => create_js_map(pairs, env)?,
         MontyObject::Set(items) | MontyObject::FrozenSet(items) => create_js_set(items, env)?,
         MontyObject::Exception { exc_type, arg } => create_js_exception(*exc_type, arg.as_deref(), env)?,
         MontyObject::Type(t) => create_js_type_marker(&t.to_string(), env)?,
         MontyObject::BuiltinFunction(f) => create_js_builtin_function_marker(&f.to_string(), env)?,
+        MontyObject::Function { name, id } => create_js_function(name, *id, env)?,
         MontyObject::Dataclass {
             name,
             type_id,
@@ -96,6 +116,39 @@ pub fn monty_to_js<'e>(obj: &MontyObject, env: &'e Env) -> Result<JsMontyObject<
             frozen,
         } => create_js_dataclass(name, *type_id, field_names, attrs, methods, *frozen, env)?,
         MontyObject::Path(p) => env.create_string(p)?.into_unknown(env)?,
+        MontyObject::Date { year, month, day } => create_js_date_marker(*year, *month, *day, env)?,
+        MontyObject::Time {
+            hour,
+            minute,
+            second,
+            microsecond,
+            utc_offset,
+        } => create_js_time(*hour, *minute, *second, *microsecond, *utc_offset, env)?,
+        MontyObject::DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            microsecond,
+            utc_offset,
+        } => {
+            let seconds = i64::from(*hour) * 3600 + i64::from(*minute) * 60 + i64::from(*second)
+                - i64::from(utc_offset.unwrap_or(0));
+            let ms =
+                days_from_civil(*year, *month, *day) * MS_PER_DAY + seconds * 1000 + i64::from(*microsecond / 1000);
+            // timestamps of years 1 to 9999 are well within f64's exact integer range
+            env.create_date(ms as f64)?.into_unknown(env)?
+        }
+        MontyObject::TimeDelta {
+            days,
+            seconds,
+            microseconds,
+        } => create_js_timedelta(*days, *seconds, *microseconds, env)?,
+        MontyObject::TimeZone { utc_offset, name } => create_js_timezone(*utc_offset, name.as_deref(), env)?,
+        MontyObject::Decimal(value) => create_js_decimal(value, env)?,
+        MontyObject::Fraction { numerator, denominator } => create_js_fraction(numerator, denominator, env)?,
         MontyObject::Repr(s) | MontyObject::Cycle(_, s) => env.create_string(s)?.into_unknown(env)?,
     };
     Ok(JsMontyObject(unknown))
@@ -161,6 +214,123 @@ fn create_js_buffer<'e>(bytes: &[u8], env: &'e Env) -> Result<Unknown<'e>> {
     buffer.into_unknown(env)
 }
 
+/// Creates a JS object representing a bytearray: `{ __monty_type__: 'ByteArray', value: Buffer }`.
+fn create_js_bytearray<'e>(bytes: &[u8], env: &'e Env) -> Result<Unknown<'e>> {
+    let mut obj = Object::new(env)?;
+    obj.set_named_property("__monty_type__", "ByteArray")?;
+    obj.set_named_property("value", create_js_buffer(bytes, env)?)?;
+    obj.into_unknown(env)
+}
+
+/// Creates a JS object representing a complex number: `{ __monty_type__: 'Complex', real, imag }`.
+fn create_js_complex(real: f64, imag: f64, env: &Env) -> Result<Unknown<'_>> {
+    let mut obj = Object::new(env)?;
+    obj.set_named_property("__monty_type__", "Complex")?;
+    obj.set_named_property("real", real)?;
+    obj.set_named_property("imag", imag)?;
+    obj.into_unknown(env)
+}
+
+/// Creates a JS object representing a date: `{ __monty_type__: 'Date', year, month, day }`.
+///
+/// A JS `Date` is an instant rather than a calendar day, so dates use a marker object instead.
+fn create_js_date_marker(year: i32, month: u8, day: u8, env: &Env) -> Result<Unknown<'_>> {
+    let mut obj = Object::new(env)?;
+    obj.set_named_property("__monty_type__", "Date")?;
+    obj.set_named_property("year", year)?;
+    obj.set_named_property("month", u32::from(month))?;
+    obj.set_named_property("day", u32::from(day))?;
+    obj.into_unknown(env)
+}
+
+/// Creates a JS object representing a time of day:
+/// `{ __monty_type__: 'Time', hour, minute, second, microsecond, utcOffset }`.
+///
+/// `utcOffset` is in seconds, or `null` for a naive time.
+fn create_js_time(
+    hour: u8,
+    minute: u8,
+    second: u8,
+    microsecond: u32,
+    utc_offset: Option<i32>,
+    env: &Env,
+) -> Result<Unknown<'_>> {
+    let mut obj = Object::new(env)?;
+    obj.set_named_property("__monty_type__", "Time")?;
+    obj.set_named_property("hour", u32::from(hour))?;
+    obj.set_named_property("minute", u32::from(minute))?;
+    obj.set_named_property("second", u32::from(second))?;
+    obj.set_named_property("microsecond", microsecond)?;
+    obj.set_named_property("utcOffset", utc_offset)?;
+    obj.into_unknown(env)
+}
+
+/// Creates a JS object representing a timedelta: `{ __monty_type__: 'TimeDelta', days, seconds, microseconds }`.
+fn create_js_timedelta(days: i32, seconds: i32, microseconds: i32, env: &Env) -> Result<Unknown<'_>> {
+    let mut obj = Object::new(env)?;
+    obj.set_named_property("__monty_type__", "TimeDelta")?;
+    obj.set_named_property("days", days)?;
+    obj.set_named_property("seconds", seconds)?;
+    obj.set_named_property("microseconds", microseconds)?;
+    obj.into_unknown(env)
+}
+
+/// Creates a JS object representing a fixed-offset timezone: `{ __monty_type__: 'TimeZone', utcOffset, name }`.
+///
+/// `name` is `null` unless the timezone was created with an explicit name.
+fn create_js_timezone<'e>(utc_offset: i32, name: Option<&str>, env: &'e Env) -> Result<Unknown<'e>> {
+    let mut obj = Object::new(env)?;
+    obj.set_named_property("__monty_type__", "TimeZone")?;
+    obj.set_named_property("utcOffset", utc_offset)?;
+    obj.set_named_property("name", name)?;
+    obj.into_unknown(env)
+}
+
+/// Creates a JS object representing a decimal: `{ __monty_type__: 'Decimal', value }`.
+///
+/// `value` is the decimal's string, which keeps all its digits.
+fn create_js_decimal<'e>(value: &str, env: &'e Env) -> Result<Unknown<'e>> {
+    let mut obj = Object::new(env)?;
+    obj.set_named_property("__monty_type__", "Decimal")?;
+    obj.set_named_property("value", value)?;
+    obj.into_unknown(env)
+}
+
+/// Creates a JS object representing a fraction: `{ __monty_type__: 'Fraction', numerator, denominator }`.
+fn create_js_fraction<'e>(numerator: &NumBigInt, denominator: &NumBigInt, env: &'e Env) -> Result<Unknown<'e>> {
+    let mut obj = Object::new(env)?;
+    obj.set_named_property("__monty_type__", "Fraction")?;
+    obj.set_named_property("numerator", create_js_bigint(numerator, env)?)?;
+    obj.set_named_property("denominator", create_js_bigint(denominator, env)?)?;
+    obj.into_unknown(env)
+}
+
+/// Returns the number of days from 1970-01-01 to the given proleptic Gregorian date.
+///
+/// Uses the era-based algorithm from Howard Hinnant's `chrono`-compatible date algorithms.
+fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Inverse of [`days_from_civil`]: returns the `(year, month, day)` that is `days` after 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = u8::try_from(day_of_year - (153 * mp + 2) / 5 + 1).expect("day is always 1 to 31");
+    let month = u8::try_from(if mp < 10 { mp + 3 } else { mp - 9 }).expect("month is always 1 to 12");
+    (year_of_era + era * 400 + i64::from(month <= 2), month, day)
+}
+
 /// Creates a native JS Array from Monty list items, recursively converting each element.
 fn create_js_array<'e>(items: &[MontyObject], env: &'e Env) -> Result<Array<'e>> {
     let mut arr = env.create_array(items.len().try_into().expect("array size overflows u32"))?;
@@ -271,6 +441,16 @@ fn create_js_builtin_function_marker<'e>(func_str: &str, env: &'e Env) -> Result
     obj.into_unknown(env)
 }
 
+/// Creates a JS object representing a handle to a Monty function.
+fn create_js_function<'e>(name: &str, id: u64, env: &'e Env) -> Result<Unknown<'e>> {
+    let mut obj = Object::new(env)?;
+    obj.set_named_property("__monty_type__", "Function")?;
+    obj.set_named_property("name", name)?;
+    // id as BigInt since it may exceed JS safe integer range
+    obj.set_named_property("id", BigInt::from(id))?;
+    obj.into_unknown(env)
+}
+
 /// Creates a JS object representing a dataclass instance.
 fn create_js_dataclass<'e>(
     name: &str,
@@ -432,6 +612,11 @@ pub fn js_to_monty(value: Unknown<'_>, env: Env) -> Result<MontyObject> {
                 return js_array_to_monty(obj, env);
             }
 
+            // Check if it's a Date
+            if is_js_date(&obj, env)? {
+                return js_date_to_monty(obj);
+            }
+
             // Check for __monty_type__ marker
             if let Some(monty_type) = get_string_property(&obj, "__monty_type__")? {
                 return js_marked_object_to_monty(&obj, &monty_type, env);
@@ -465,6 +650,46 @@ fn is_js_map(obj: &Object, env: Env) -> Result<bool> {
     obj.instanceof(map_constructor)
 }
 
+/// Checks if a JS object is an instance of Date.
+fn is_js_date(obj: &Object, env: Env) -> Result<bool> {
+    let global = env.get_global()?;
+    let date_constructor: Function<()> = global.get_named_property("Date")?;
+    obj.instanceof(date_constructor)
+}
+
+/// Converts a JS Date to an aware `MontyObject::DateTime` in UTC.
+fn js_date_to_monty(date: Object) -> Result<MontyObject> {
+    let get_time: Function<()> = date.get_named_property("getTime")?;
+    let ms: f64 = get_time.apply(date, ())?.coerce_to_number()?.get_double()?;
+    // JS dates span +/-8.64e15 ms, so any finite timestamp fits in an i64
+    if !ms.is_finite() {
+        return Err(Error::from_reason(
+            "Cannot convert an invalid JS Date to Monty datetime",
+        ));
+    }
+    #[expect(clippy::cast_possible_truncation, reason = "checked above that ms is finite")]
+    let ms = ms as i64;
+
+    let (year, month, day) = civil_from_days(ms.div_euclid(MS_PER_DAY));
+    let year = i32::try_from(year)
+        .ok()
+        .filter(|year| (1..=9999).contains(year))
+        .ok_or_else(|| Error::from_reason(format!("JS Date year {year} is out of range for Monty datetime")))?;
+    let ms_of_day = ms.rem_euclid(MS_PER_DAY);
+    let seconds = ms_of_day / 1000;
+    let to_u8 = |n: i64| u8::try_from(n).expect("time fields are always below 256");
+    Ok(MontyObject::DateTime {
+        year,
+        month,
+        day,
+        hour: to_u8(seconds / 3600),
+        minute: to_u8(seconds / 60 % 60),
+        second: to_u8(seconds % 60),
+        microsecond: u32::try_from(ms_of_day % 1000 * 1000).expect("microsecond is always below 1000000"),
+        utc_offset: Some(0),
+    })
+}
+
 /// Converts a JS Map to `MontyObject::Dict`.
 fn js_map_to_monty(map: Object, env: Env) -> Result<MontyObject> {
     // Get the entries iterator
@@ -562,15 +787,7 @@ fn js_marked_object_to_monty(obj: &Object, monty_type: &str, env: Env) -> Result
         "Dataclass" => {
             let name: String = obj.get_named_property("name")?;
 
-            // type_id is BigInt - access its public fields
-            let type_id_bigint: BigInt = obj.get_named_property("typeId")?;
-            let type_id = if type_id_bigint.words.is_empty() {
-                0u64
-            } else if type_id_bigint.sign_bit {
-                return Err(Error::from_reason("Dataclass typeId cannot be negative"));
-            } else {
-                type_id_bigint.words[0]
-            };
+            let type_id = get_u64_bigint_property(obj, "typeId", "Dataclass typeId")?;
 
             // field_names
             let field_names_arr: Array = obj.get_named_property("fieldNames")?;
@@ -612,6 +829,47 @@ fn js_marked_object_to_monty(obj: &Object, monty_type: &str, env: Env) -> Result
                 frozen,
             })
         }
+        "Function" => {
+            let name: String = obj.get_named_property("name")?;
+            let id = get_u64_bigint_property(obj, "id", "Function id")?;
+            Ok(MontyObject::Function { name, id })
+        }
+        "ByteArray" => {
+            let value: Unknown = obj.get_named_property("value")?;
+            let buffer: BufferSlice = BufferSlice::from_unknown(value)?;
+            Ok(MontyObject::ByteArray(buffer.to_vec()))
+        }
+        "Complex" => {
+            let real: f64 = obj.get_named_property("real")?;
+            let imag: f64 = obj.get_named_property("imag")?;
+            Ok(MontyObject::Complex { real, imag })
+        }
+        "Date" => Ok(MontyObject::Date {
+            year: obj.get_named_property("year")?,
+            month: get_u8_property(obj, "month")?,
+            day: get_u8_property(obj, "day")?,
+        }),
+        "Time" => Ok(MontyObject::Time {
+            hour: get_u8_property(obj, "hour")?,
+            minute: get_u8_property(obj, "minute")?,
+            second: get_u8_property(obj, "second")?,
+            microsecond: obj.get_named_property("microsecond")?,
+            utc_offset: obj.get_named_property("utcOffset")?,
+        }),
+        "TimeDelta" => Ok(MontyObject::TimeDelta {
+            days: obj.get_named_property("days")?,
+            seconds: obj.get_named_property("seconds")?,
+            microseconds: obj.get_named_property("microseconds")?,
+        }),
+        "TimeZone" => Ok(MontyObject::TimeZone {
+            utc_offset: obj.get_named_property("utcOffset")?,
+            name: obj.get_named_property("name")?,
+        }),
+        "Decimal" => Ok(MontyObject::Decimal(obj.get_named_property("value")?)),
+        "Fraction" => Ok(MontyObject::Fraction {
+            numerator: get_int_property(obj, "numerator", env)?,
+            denominator: get_int_property(obj, "denominator", env)?,
+        }),
         _ => {
             // Unknown marker type, treat as dict
             js_object_to_monty_dict(*obj, env)
@@ -656,3 +914,34 @@ fn get_string_property(obj: &Object, name: &str) -> Result<Option<String>> {
         Ok(None)
     }
 }
+
+/// Helper to get a small unsigned integer property, such as a month or an hour, from a JS object.
+fn get_u8_property(obj: &Object, name: &str) -> Result<u8> {
+    let value: u32 = obj.get_named_property(name)?;
+    u8::try_from(value).map_err(|_| Error::from_reason(format!("{name} {value} is out of range")))
+}
+
+/// Helper to get an integer property, a `number` or a `BigInt`, from a JS object.
+fn get_int_property(obj: &Object, name: &str, env: Env) -> Result<NumBigInt> {
+    let value: Unknown = obj.get_named_property(name)?;
+    match js_to_monty(value, env)? {
+        MontyObject::Int(i) => Ok(NumBigInt::from(i)),
+        MontyObject::BigInt(bi) => Ok(bi),
+        _ => Err(Error::from_reason(format!("{name} must be an integer"))),
+    }
+}
+
+/// Helper to get a non-negative `BigInt` property from a JS object as a `u64`.
+///
+/// `what` names the property in the error message if it's negative.
+fn get_u64_bigint_property(obj: &Object, name: &str, what: &str) -> Result<u64> {
+    // BigInt - access its public fields
+    let bigint: BigInt = obj.get_named_property(name)?;
+    if bigint.words.is_empty() {
+        Ok(0)
+    } else if bigint.sign_bit {
+        Err(Error::from_reason(format!("{what} cannot be negative")))
+    } else {
+        Ok(bigint.words[0])
+    }
+}
@@ -71,6 +71,37 @@ impl JsMontyException {
         self.0.traceback().iter().map(Frame::from_stack_frame).collect()
     }
 
+    /// Returns the exception's `__cause__`, set by `raise ... from cause`.
+    #[napi(getter)]
+    #[must_use]
+    pub fn cause(&self) -> Option<JsMontyException> {
+        self.0.cause().cloned().map(Self)
+    }
+
+    /// Returns the exception's `__context__`, the exception being handled when it was raised.
+    #[napi(getter)]
+    #[must_use]
+    pub fn context(&self) -> Option<JsMontyException> {
+        self.0.context().cloned().map(Self)
+    }
+
+    /// Returns the chained exception shown before this one in the traceback, if any.
+    ///
+    /// This is the cause if there is one, otherwise the context unless
+    /// `__suppress_context__` is set.
+    #[napi(getter)]
+    #[must_use]
+    pub fn displayed_context(&self) -> Option<JsMontyException> {
+        self.0.displayed_context().cloned().map(Self)
+    }
+
+    /// Returns the sub-exceptions of an exception group, empty for other exceptions.
+    #[napi(getter)]
+    #[must_use]
+    pub fn exceptions(&self) -> Vec<JsMontyException> {
+        self.0.exceptions().iter().cloned().map(Self).collect()
+    }
+
     /// Returns formatted exception string.
     ///
     /// @param format - Output format:
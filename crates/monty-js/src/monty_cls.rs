@@ -46,7 +46,7 @@
 use std::borrow::Cow;
 
 use monty::{
-    CollectStringPrint, ExcType, ExternalResult, LimitedTracker, MontyException, MontyObject, MontyRun, NoLimitTracker,
+    ExcType, ExternalResult, LimitedTracker, MontyException, MontyObject, MontyRun, NoLimitTracker, PrintWriter,
     ResourceTracker, RunProgress, Snapshot,
 };
 use monty_type_checking::{type_check, SourceFile};
@@ -105,6 +105,9 @@ pub struct RunOptions<'env> {
     /// Dict of external function callbacks.
     /// Keys are function names, values are callable functions.
     pub external_functions: Option<Object<'env>>,
+    /// Callback for print output, called with the stream (`'stdout'` or `'stderr'`) and the text.
+    #[napi(ts_type = "(stream: 'stdout' | 'stderr', text: string) => void")]
+    pub print_callback: Option<Unknown<'env>>,
 }
 
 /// Options for starting execution.
@@ -115,6 +118,9 @@ pub struct StartOptions<'env> {
     pub inputs: Option<Object<'env>>,
     /// Resource limits configuration.
     pub limits: Option<JsResourceLimits>,
+    /// Callback for print output, called with the stream (`'stdout'` or `'stderr'`) and the text.
+    #[napi(ts_type = "(stream: 'stdout' | 'stderr', text: string) => void")]
+    pub print_callback: Option<Unknown<'env>>,
 }
 
 #[napi]
@@ -192,6 +198,7 @@ impl Monty {
         let input_values = self.extract_input_values(options.as_ref().and_then(|opts| opts.inputs), *env)?;
 
         let external_functions = options.as_ref().and_then(|opts| opts.external_functions);
+        let mut print_output = JsPrint::new(env, options.as_ref().and_then(|opts| opts.print_callback));
 
         // If we have external functions declared, use the start/resume loop
         if !self.external_function_names.is_empty() {
@@ -200,11 +207,11 @@ impl Monty {
                 input_values,
                 options.as_ref().and_then(|opts| opts.limits),
                 external_functions,
+                &mut print_output,
             );
         }
 
         // No external functions - simple run
-        let mut print_output = CollectStringPrint::default();
 
         let result = if let Some(limits) = options.as_ref().and_then(|opts| opts.limits) {
             let tracker = LimitedTracker::new(limits.into());
@@ -226,14 +233,14 @@ impl Monty {
         input_values: Vec<MontyObject>,
         limits: Option<JsResourceLimits>,
         external_functions: Option<Object<'env>>,
+        print_output: &mut impl PrintWriter,
     ) -> Result<Either<JsMontyObject<'env>, JsMontyException>> {
-        let mut print_output = CollectStringPrint::default();
         let runner = self.runner.clone();
 
         // Helper macro to handle the execution loop for both tracker types
         macro_rules! run_loop {
             ($tracker:expr) => {{
-                let progress = runner.start(input_values, $tracker, &mut print_output);
+                let progress = runner.start(input_values, $tracker, print_output);
 
                 let mut progress = match progress {
                     Ok(p) => p,
@@ -260,7 +267,7 @@ impl Monty {
                                 &kwargs,
                             )?;
 
-                            progress = match state.run(return_value, &mut print_output) {
+                            progress = match state.run(return_value, print_output) {
                                 Ok(p) => p,
                                 Err(exc) => return Ok(Either::B(JsMontyException::new(exc))),
                             };
@@ -306,7 +313,7 @@ impl Monty {
 
         // Clone the runner since start() consumes it - allows reuse of the parsed code
         let runner = self.runner.clone();
-        let mut print_output = CollectStringPrint::default();
+        let mut print_output = JsPrint::new(env, options.and_then(|opts| opts.print_callback));
 
         // Start execution with appropriate tracker
         if let Some(limits) = options.and_then(|opts| opts.limits) {
@@ -496,6 +503,9 @@ pub struct ResumeOptions<'env> {
     /// An exception to raise in the interpreter.
     /// Format: { type: string, message: string }
     pub exception: Option<ExceptionInput>,
+    /// Callback for print output, called with the stream (`'stdout'` or `'stderr'`) and the text.
+    #[napi(ts_type = "(stream: 'stdout' | 'stderr', text: string) => void")]
+    pub print_callback: Option<Unknown<'env>>,
 }
 
 /// Input for raising an exception during resume.
@@ -585,7 +595,7 @@ impl MontySnapshot {
         let snapshot = std::mem::replace(&mut self.snapshot, EitherSnapshot::Done);
 
         // Resume execution based on the snapshot type
-        let mut print_output = CollectStringPrint::default();
+        let mut print_output = JsPrint::new(env, options.print_callback);
         match snapshot {
             EitherSnapshot::NoLimit(state) => {
                 let progress = match state.run(external_result, &mut print_output) {
@@ -841,6 +851,22 @@ fn call_external_function(
         js_args.push(kwargs_obj.raw());
     }
 
+    match call_js_function(env, &callable, &js_args, "External function") {
+        Ok(result) => Ok(ExternalResult::Return(js_to_monty(result, *env)?)),
+        Err(exc) => Ok(ExternalResult::Error(exc)),
+    }
+}
+
+/// Calls a JavaScript function with `this` set to `undefined`.
+///
+/// If the function throws, the JS exception is converted to a `MontyException`.
+/// `description` names the function in the generic errors used when that isn't possible.
+fn call_js_function<'env>(
+    env: &'env Env,
+    callable: &Unknown<'_>,
+    js_args: &[sys::napi_value],
+    description: &str,
+) -> std::result::Result<Unknown<'env>, MontyException> {
     // Get undefined for the 'this' argument
     let mut undefined_raw = std::ptr::null_mut();
     // SAFETY: [DH] - all arguments are valid and result is valid on success
@@ -875,27 +901,26 @@ fn call_external_function(
 
             if status != sys::Status::napi_ok {
                 // Failed to get the exception - return a generic error
-                let exc = MontyException::new(
+                return Err(MontyException::new(
                     ExcType::RuntimeError,
-                    Some("External function call failed and exception could not be retrieved".to_string()),
-                );
-                return Ok(ExternalResult::Error(exc));
+                    Some(format!(
+                        "{description} call failed and exception could not be retrieved"
+                    )),
+                ));
             }
             let exception_obj = Object::from_raw(env.raw(), exception_raw);
-            let exc = extract_js_exception(exception_obj);
-            return Ok(ExternalResult::Error(exc));
+            return Err(extract_js_exception(exception_obj));
         }
 
         // Generic error
-        let exc = MontyException::new(ExcType::RuntimeError, Some("External function call failed".to_string()));
-        return Ok(ExternalResult::Error(exc));
+        return Err(MontyException::new(
+            ExcType::RuntimeError,
+            Some(format!("{description} call failed")),
+        ));
     }
 
-    // Convert the result back to Monty format
     // SAFETY: [DH] - result_raw is valid on success
-    let result = unsafe { Unknown::from_raw_unchecked(env.raw(), result_raw) };
-    let monty_result = js_to_monty(result, *env)?;
-    Ok(ExternalResult::Return(monty_result))
+    Ok(unsafe { Unknown::from_raw_unchecked(env.raw(), result_raw) })
 }
 
 /// Extracts exception info from a JS exception object.
@@ -913,3 +938,55 @@ fn extract_js_exception(exception_obj: Object<'_>) -> MontyException {
 
     MontyException::new(exc_type, msg)
 }
+
+// =============================================================================
+// Print callback support
+// =============================================================================
+
+/// A `PrintWriter` that calls the `printCallback` option with the stream name
+/// (`'stdout'` or `'stderr'`) and the text of each write.
+///
+/// Output is discarded if no callback was provided.
+struct JsPrint<'env> {
+    env: &'env Env,
+    callback: Option<Unknown<'env>>,
+}
+
+impl<'env> JsPrint<'env> {
+    fn new(env: &'env Env, callback: Option<Unknown<'env>>) -> Self {
+        Self { env, callback }
+    }
+
+    /// Calls the callback with `stream` and `text`, converting a thrown JS error into an exception.
+    fn call(&self, stream: &str, text: &str) -> std::result::Result<(), MontyException> {
+        let Some(callback) = &self.callback else {
+            return Ok(());
+        };
+        let to_js = |s: &str| -> Result<sys::napi_value> {
+            Ok(monty_to_js(&MontyObject::String(s.to_owned()), self.env)?.raw())
+        };
+        let js_args = [to_js(stream), to_js(text)]
+            .into_iter()
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| MontyException::new(ExcType::RuntimeError, Some(e.reason.clone())))?;
+        call_js_function(self.env, callback, &js_args, "Print callback").map(|_| ())
+    }
+}
+
+impl PrintWriter for JsPrint<'_> {
+    fn stdout_write(&mut self, output: Cow<'_, str>) -> std::result::Result<(), MontyException> {
+        self.call("stdout", &output)
+    }
+
+    fn stdout_push(&mut self, end: char) -> std::result::Result<(), MontyException> {
+        self.call("stdout", end.encode_utf8(&mut [0; 4]))
+    }
+
+    fn stderr_write(&mut self, output: Cow<'_, str>) -> std::result::Result<(), MontyException> {
+        self.call("stderr", &output)
+    }
+
+    fn stderr_push(&mut self, end: char) -> std::result::Result<(), MontyException> {
+        self.call("stderr", end.encode_utf8(&mut [0; 4]))
+    }
+}
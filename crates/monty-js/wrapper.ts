@@ -133,6 +133,15 @@ export class MontyRuntimeError extends MontyError {
   private _native: NativeMontyException | null
   private _tracebackString: string | null
   private _frames: Frame[] | null
+  /**
+   * The exception this one was chained from: its `__cause__`, or its `__context__`
+   * unless `raise ... from ...` suppressed it.
+   */
+  cause?: MontyRuntimeError
+  /**
+   * The sub-exceptions of an `ExceptionGroup` or `BaseExceptionGroup`, empty for other exceptions.
+   */
+  exceptions: MontyRuntimeError[] = []
 
   constructor(
     nativeOrTypeName: NativeMontyException | string,
@@ -153,6 +162,11 @@ export class MontyRuntimeError extends MontyError {
       this._native = nativeOrTypeName
       this._tracebackString = null
       this._frames = null
+      const chained = nativeOrTypeName.displayedContext
+      if (chained) {
+        this.cause = new MontyRuntimeError(chained)
+      }
+      this.exceptions = nativeOrTypeName.exceptions.map((sub) => new MontyRuntimeError(sub))
     }
     this.name = 'MontyRuntimeError'
     if (Error.captureStackTrace) {
@@ -465,6 +479,8 @@ export interface RunMontyAsyncOptions {
   externalFunctions?: Record<string, (...args: unknown[]) => unknown>
   /** Resource limits. */
   limits?: ResourceLimits
+  /** Callback for print output, called with the stream (`'stdout'` or `'stderr'`) and the text. */
+  printCallback?: (stream: 'stdout' | 'stderr', text: string) => void
 }
 
 /**
@@ -497,9 +513,9 @@ export interface RunMontyAsyncOptions {
  * });
  */
 export async function runMontyAsync(montyRunner: Monty, options: RunMontyAsyncOptions = {}): Promise<JsMontyObject> {
-  const { inputs, externalFunctions = {}, limits } = options
+  const { inputs, externalFunctions = {}, limits, printCallback } = options
 
-  let progress: MontySnapshot | MontyComplete = montyRunner.start({ inputs, limits })
+  let progress: MontySnapshot | MontyComplete = montyRunner.start({ inputs, limits, printCallback })
 
   while (progress instanceof MontySnapshot) {
     const snapshot = progress
@@ -513,6 +529,7 @@ export async function runMontyAsync(montyRunner: Monty, options: RunMontyAsyncOp
           type: 'KeyError',
           message: `"External function '${funcName}' not found"`,
         },
+        printCallback,
       })
       continue
     }
@@ -527,7 +544,7 @@ export async function runMontyAsync(montyRunner: Monty, options: RunMontyAsyncOp
       }
 
       // Resume with the return value
-      progress = snapshot.resume({ returnValue: result })
+      progress = snapshot.resume({ returnValue: result, printCallback })
     } catch (error) {
       // External function threw an exception - convert to Monty exception
       const err = error as Error
@@ -538,6 +555,7 @@ export async function runMontyAsync(montyRunner: Monty, options: RunMontyAsyncOp
           type: excType,
           message: excMessage,
         },
+        printCallback,
       })
     }
   }
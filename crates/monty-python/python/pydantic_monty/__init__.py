@@ -11,6 +11,7 @@ from ._monty import (
     Monty,
     MontyComplete,
     MontyError,
+    MontyFunction,
     MontyFutureSnapshot,
     MontyRuntimeError,
     MontySnapshot,
@@ -31,6 +32,7 @@ __all__ = (
     'MontyComplete',
     'MontySnapshot',
     'MontyFutureSnapshot',
+    'MontyFunction',
     'MontyError',
     'MontySyntaxError',
     'MontyRuntimeError',
@@ -54,7 +56,7 @@ async def run_monty_async(
     inputs: dict[str, Any] | None = None,
     external_functions: dict[str, Callable[..., Any]] | None = None,
     limits: ResourceLimits | None = None,
-    print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+    print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
     os: AbstractOS | None = None,
 ) -> Any:
     """Run a Monty script with async external functions and optional OS access.
@@ -83,7 +83,7 @@ class Monty:
         inputs: dict[str, Any] | None = None,
         limits: ResourceLimits | None = None,
         external_functions: dict[str, Callable[..., Any]] | None = None,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
         os: Callable[[OsFunction, tuple[Any, ...]], Any] | None = None,
     ) -> Any:
         """
@@ -95,7 +95,8 @@ class Monty:
             inputs: Dict of input variable values (must match names from __init__)
             limits: Optional resource limits configuration
             external_functions: Dict of external function callbacks (must match names from __init__)
-            print_callback: Optional callback for print output
+            print_callback: Optional callback for print output, called with the stream
+                ('stdout' or 'stderr') and the text written
             os: Optional callback for OS calls.
                 Called with (function_name, args) where function_name is like 'Path.exists'
                 and args is a tuple of arguments. Must return the appropriate value for the
@@ -113,7 +114,7 @@ class Monty:
         *,
         inputs: dict[str, Any] | None = None,
         limits: ResourceLimits | None = None,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
     ) -> MontySnapshot | MontyFutureSnapshot | MontyComplete:
         """
         Start the code execution and return a progress object, or completion.
@@ -125,7 +126,8 @@ class Monty:
         Arguments:
             inputs: Dict of input variable values (must match names from __init__)
             limits: Optional resource limits configuration
-            print_callback: Optional callback for print output
+            print_callback: Optional callback for print output, called with the stream
+                ('stdout' or 'stderr') and the text written
 
         Returns:
             MontySnapshot if an external function call is pending,
@@ -286,7 +288,7 @@ class MontySnapshot:
     def load(
         data: bytes,
         *,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
         dataclass_registry: list[type] | None = None,
     ) -> 'MontySnapshot':
         """
@@ -377,7 +379,7 @@ class MontyFutureSnapshot:
     def load(
         data: bytes,
         *,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
         dataclass_registry: list[type] | None = None,
     ) -> 'MontyFutureSnapshot':
         """
@@ -411,6 +413,26 @@ class MontyComplete:
 
     def __repr__(self) -> str: ...
 
+@final
+class MontyFunction:
+    """A function defined in Monty code, passed to Python as a handle.
+
+    External functions receive these in place of Monty functions, e.g. a decorator
+    implemented as an external function receives the function it decorates.
+    The function can't be called from Python, but passing the handle back to Monty
+    (e.g. by returning it from the decorator) gives back the original function.
+    """
+
+    @property
+    def name(self) -> str:
+        """The function's name."""
+
+    @property
+    def id(self) -> int:
+        """The function's `id()` in Monty, which identifies it when the handle is passed back."""
+
+    def __repr__(self) -> str: ...
+
 class MontyError(Exception):
     """Base exception for all Monty interpreter errors.
 
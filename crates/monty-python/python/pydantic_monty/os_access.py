@@ -1,6 +1,8 @@
 from __future__ import annotations
 
+import secrets
 from abc import ABC, abstractmethod
+from datetime import datetime, timezone
 from pathlib import PurePosixPath
 from typing import TYPE_CHECKING, Any, Callable, Literal, NamedTuple, Protocol, Sequence, TypeAlias, TypeGuard
 
@@ -29,6 +31,9 @@ OsFunction = Literal[
     'Path.absolute',
     'os.getenv',
     'os.environ',
+    'datetime.now',
+    'random.seed',
+    'uuid.uuid4',
 ]
 
 
@@ -172,6 +177,12 @@ class AbstractOS(ABC):
                 return self.getenv(*args)
             case 'os.environ':
                 return self.get_environ()
+            case 'datetime.now':
+                return self.datetime_now(*args)
+            case 'random.seed':
+                return self.random_seed()
+            case 'uuid.uuid4':
+                return self.uuid4_bytes()
 
     @abstractmethod
     def path_exists(self, path: PurePosixPath) -> bool:
@@ -422,6 +433,43 @@ class AbstractOS(ABC):
         """
         raise NotImplementedError
 
+    def datetime_now(self, tz: timezone | None) -> datetime:
+        """Get the current time, for `datetime.now()`, `datetime.today()` and `date.today()`.
+
+        Override this to control the time Monty code sees, e.g. to make runs reproducible.
+        The default reads the host's clock.
+
+        Args:
+            tz: The timezone passed to `datetime.now()`, or None.
+
+        Returns:
+            The current time: an aware datetime, which Monty converts to the timezone
+            it needs, or a naive one, which Monty takes to be UTC.
+        """
+        return datetime.now(tz or timezone.utc)
+
+    def random_seed(self) -> int:
+        """Get a seed for the `random` module, used when Monty code doesn't seed it itself.
+
+        Override this to return a fixed seed, e.g. to make runs reproducible.
+        The default returns 128 random bits from the host.
+
+        Returns:
+            The seed, any int.
+        """
+        return secrets.randbits(128)
+
+    def uuid4_bytes(self) -> bytes:
+        """Get the random bytes of a UUID made by `uuid.uuid4()`.
+
+        Override this to return fixed bytes, e.g. to make runs reproducible.
+        The default returns 16 random bytes from the host.
+
+        Returns:
+            16 bytes, whose version and variant bits Monty sets.
+        """
+        return secrets.token_bytes(16)
+
 
 class AbstractFile(Protocol):
     """Protocol defining the interface for files used with OSAccess.
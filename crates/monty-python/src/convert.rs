@@ -5,18 +5,22 @@
 //! - `monty_to_py`: Convert Monty's `MontyObject` back to Python objects for output
 
 use ::monty::MontyObject;
+use indexmap::IndexMap;
 use monty::MontyException;
 use num_bigint::BigInt;
 use pyo3::{
-    exceptions::{PyBaseException, PyTypeError},
+    exceptions::{PyBaseException, PyRuntimeError, PyTypeError},
     prelude::*,
     sync::PyOnceLock,
-    types::{PyBool, PyBytes, PyDict, PyFloat, PyFrozenSet, PyInt, PyList, PySet, PyString, PyTuple},
+    types::{
+        PyBool, PyByteArray, PyBytes, PyComplex, PyDict, PyFloat, PyFrozenSet, PyInt, PyList, PySet, PyString, PyTuple,
+    },
 };
 
 use crate::{
     dataclass::{dataclass_to_monty, dataclass_to_py, is_dataclass},
     exceptions::{exc_monty_to_py, exc_to_monty_object},
+    function::PyMontyFunction,
 };
 
 /// Converts a Python object to Monty's `MontyObject` representation.
@@ -43,10 +47,17 @@ pub fn py_to_monty(obj: &Bound<'_, PyAny>) -> PyResult<MontyObject> {
         }
     } else if let Ok(float) = obj.cast::<PyFloat>() {
         Ok(MontyObject::Float(float.extract()?))
+    } else if let Ok(complex) = obj.cast::<PyComplex>() {
+        Ok(MontyObject::Complex {
+            real: complex.real(),
+            imag: complex.imag(),
+        })
     } else if let Ok(string) = obj.cast::<PyString>() {
         Ok(MontyObject::String(string.extract()?))
     } else if let Ok(bytes) = obj.cast::<PyBytes>() {
         Ok(MontyObject::Bytes(bytes.extract()?))
+    } else if let Ok(bytearray) = obj.cast::<PyByteArray>() {
+        Ok(MontyObject::ByteArray(bytearray.to_vec()))
     } else if let Ok(list) = obj.cast::<PyList>() {
         let items: PyResult<Vec<MontyObject>> = list.iter().map(|item| py_to_monty(&item)).collect();
         Ok(MontyObject::List(items?))
@@ -99,6 +110,17 @@ pub fn py_to_monty(obj: &Bound<'_, PyAny>) -> PyResult<MontyObject> {
         Ok(MontyObject::Ellipsis)
     } else if let Ok(exc) = obj.cast::<PyBaseException>() {
         Ok(exc_to_monty_object(exc))
+    } else if let Ok(func) = obj.cast::<PyMontyFunction>() {
+        Ok(func.get().to_monty())
+    } else if let Some(datetime) = datetime_to_monty(obj)? {
+        Ok(datetime)
+    } else if obj.is_instance(&decimal_class(obj.py(), "Decimal")?)? {
+        Ok(MontyObject::Decimal(obj.str()?.extract()?))
+    } else if obj.is_instance(&fractions_class(obj.py(), "Fraction")?)? {
+        Ok(MontyObject::Fraction {
+            numerator: obj.getattr("numerator")?.extract()?,
+            denominator: obj.getattr("denominator")?.extract()?,
+        })
     } else if is_dataclass(obj) {
         dataclass_to_monty(obj)
     } else if obj.is_instance(get_pure_posix_path(obj.py())?)? {
@@ -117,7 +139,15 @@ pub fn py_to_monty(obj: &Bound<'_, PyAny>) -> PyResult<MontyObject> {
 /// When a dataclass is converted and its class name is found in the registry,
 /// an instance of the original Python type is created (so `isinstance()` works).
 /// Otherwise, falls back to `PyMontyDataclass`.
+///
+/// Enum members convert to members of Python enum classes, which are cached in the registry
+/// too, see [`prepare_enum_classes`].
 pub fn monty_to_py(py: Python<'_>, obj: &MontyObject, dc_registry: &Bound<'_, PyDict>) -> PyResult<Py<PyAny>> {
+    prepare_enum_classes(py, obj, dc_registry)?;
+    monty_to_py_inner(py, obj, dc_registry)
+}
+
+fn monty_to_py_inner(py: Python<'_>, obj: &MontyObject, dc_registry: &Bound<'_, PyDict>) -> PyResult<Py<PyAny>> {
     match obj {
         MontyObject::None => Ok(py.None()),
         MontyObject::Ellipsis => Ok(py.Ellipsis()),
@@ -125,16 +155,22 @@ pub fn monty_to_py(py: Python<'_>, obj: &MontyObject, dc_registry: &Bound<'_, Py
         MontyObject::Int(i) => Ok(i.into_pyobject(py)?.clone().into_any().unbind()),
         MontyObject::BigInt(bi) => Ok(bi.into_pyobject(py)?.clone().into_any().unbind()),
         MontyObject::Float(f) => Ok(f.into_pyobject(py)?.clone().into_any().unbind()),
+        MontyObject::Complex { real, imag } => Ok(PyComplex::from_doubles(py, *real, *imag).into_any().unbind()),
         MontyObject::String(s) => Ok(PyString::new(py, s).into_any().unbind()),
         MontyObject::Bytes(b) => Ok(PyBytes::new(py, b).into_any().unbind()),
+        MontyObject::ByteArray(b) => Ok(PyByteArray::new(py, b).into_any().unbind()),
         MontyObject::List(items) => {
-            let py_items: PyResult<Vec<Py<PyAny>>> =
-                items.iter().map(|item| monty_to_py(py, item, dc_registry)).collect();
+            let py_items: PyResult<Vec<Py<PyAny>>> = items
+                .iter()
+                .map(|item| monty_to_py_inner(py, item, dc_registry))
+                .collect();
             Ok(PyList::new(py, py_items?)?.into_any().unbind())
         }
         MontyObject::Tuple(items) => {
-            let py_items: PyResult<Vec<Py<PyAny>>> =
-                items.iter().map(|item| monty_to_py(py, item, dc_registry)).collect();
+            let py_items: PyResult<Vec<Py<PyAny>>> = items
+                .iter()
+                .map(|item| monty_to_py_inner(py, item, dc_registry))
+                .collect();
             Ok(PyTuple::new(py, py_items?)?.into_any().unbind())
         }
         // NamedTuple - create a proper Python namedtuple using collections.namedtuple
@@ -166,38 +202,67 @@ pub fn monty_to_py(py: Python<'_>, obj: &MontyObject, dc_registry: &Bound<'_, Py
             // Convert values and instantiate using _make() which accepts an iterable
             // note `_make` might start with an underscore, but it's a public documented method
             // https://docs.python.org/3/library/collections.html#collections.somenamedtuple._make
-            let py_values: PyResult<Vec<Py<PyAny>>> =
-                values.iter().map(|item| monty_to_py(py, item, dc_registry)).collect();
+            let py_values: PyResult<Vec<Py<PyAny>>> = values
+                .iter()
+                .map(|item| monty_to_py_inner(py, item, dc_registry))
+                .collect();
             let instance = nt_type.call_method1("_make", (py_values?,))?;
             Ok(instance.into_any().unbind())
         }
         MontyObject::Dict(map) => {
             let dict = PyDict::new(py);
             for (k, v) in map {
-                dict.set_item(monty_to_py(py, k, dc_registry)?, monty_to_py(py, v, dc_registry)?)?;
+                dict.set_item(
+                    monty_to_py_inner(py, k, dc_registry)?,
+                    monty_to_py_inner(py, v, dc_registry)?,
+                )?;
             }
             Ok(dict.into_any().unbind())
         }
         MontyObject::Set(items) => {
             let set = PySet::empty(py)?;
             for item in items {
-                set.add(monty_to_py(py, item, dc_registry)?)?;
+                set.add(monty_to_py_inner(py, item, dc_registry)?)?;
             }
             Ok(set.into_any().unbind())
         }
         MontyObject::FrozenSet(items) => {
-            let py_items: PyResult<Vec<Py<PyAny>>> =
-                items.iter().map(|item| monty_to_py(py, item, dc_registry)).collect();
+            let py_items: PyResult<Vec<Py<PyAny>>> = items
+                .iter()
+                .map(|item| monty_to_py_inner(py, item, dc_registry))
+                .collect();
             Ok(PyFrozenSet::new(py, &py_items?)?.into_any().unbind())
         }
         // Return the exception instance as a value (not raised)
         MontyObject::Exception { exc_type, arg } => {
-            let exc = exc_monty_to_py(py, MontyException::new(*exc_type, arg.clone()));
+            let exc = exc_monty_to_py_inner(py, MontyException::new(*exc_type, arg.clone()));
             Ok(exc.into_value(py).into_any())
         }
-        // Return Python's built-in type object
-        MontyObject::Type(t) => import_builtins(py)?.getattr(py, t.to_string()),
+        // Return Python's built-in type object, or the class from the `datetime`, `enum`,
+        // `decimal` or `fractions` module
+        MontyObject::Type(t) => {
+            let type_name = t.to_string();
+            if let Some(name) = type_name.strip_prefix("datetime.") {
+                Ok(datetime_class(py, name)?.unbind())
+            } else if let Some(name) = type_name.strip_prefix("enum.") {
+                Ok(enum_class(py, name)?.unbind())
+            } else if let Some(name) = type_name.strip_prefix("decimal.") {
+                Ok(decimal_class(py, name)?.unbind())
+            } else if let Some(name) = type_name.strip_prefix("fractions.") {
+                Ok(fractions_class(py, name)?.unbind())
+            } else {
+                import_builtins(py)?.getattr(py, type_name)
+            }
+        }
         MontyObject::BuiltinFunction(f) => import_builtins(py)?.getattr(py, f.to_string()),
+        MontyObject::Function { name, id } => Ok(Py::new(
+            py,
+            PyMontyFunction {
+                name: name.clone(),
+                id: *id,
+            },
+        )?
+        .into_any()),
         // Dataclass - use registry to reconstruct original type if available
         MontyObject::Dataclass {
             name,
@@ -207,12 +272,68 @@ pub fn monty_to_py(py: Python<'_>, obj: &MontyObject, dc_registry: &Bound<'_, Py
             frozen,
             methods: _,
         } => dataclass_to_py(py, name, *type_id, field_names, attrs, *frozen, dc_registry),
+        MontyObject::Enum {
+            class_name,
+            class_id,
+            base,
+            name,
+            ..
+        } => enum_member_to_py(py, class_name, *class_id, &base.to_string(), name, dc_registry),
         // Path - convert to Python pathlib.Path
         MontyObject::Path(p) => {
             let pure_posix_path = get_pure_posix_path(py)?;
             let path_obj = pure_posix_path.call1((p,))?;
             Ok(path_obj.into_any().unbind())
         }
+        MontyObject::Date { year, month, day } => {
+            Ok(datetime_class(py, "date")?.call1((*year, *month, *day))?.unbind())
+        }
+        MontyObject::Time {
+            hour,
+            minute,
+            second,
+            microsecond,
+            utc_offset,
+        } => {
+            let tzinfo = offset_timezone(py, *utc_offset, None)?;
+            let time = datetime_class(py, "time")?.call1((*hour, *minute, *second, *microsecond, tzinfo))?;
+            Ok(time.unbind())
+        }
+        MontyObject::DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            microsecond,
+            utc_offset,
+        } => {
+            let tzinfo = offset_timezone(py, *utc_offset, None)?;
+            let datetime = datetime_class(py, "datetime")?.call1((
+                *year,
+                *month,
+                *day,
+                *hour,
+                *minute,
+                *second,
+                *microsecond,
+                tzinfo,
+            ))?;
+            Ok(datetime.unbind())
+        }
+        MontyObject::TimeDelta {
+            days,
+            seconds,
+            microseconds,
+        } => Ok(datetime_class(py, "timedelta")?
+            .call1((*days, *seconds, *microseconds))?
+            .unbind()),
+        MontyObject::TimeZone { utc_offset, name } => offset_timezone(py, Some(*utc_offset), name.as_deref()),
+        MontyObject::Decimal(value) => Ok(decimal_class(py, "Decimal")?.call1((value,))?.unbind()),
+        MontyObject::Fraction { numerator, denominator } => Ok(fractions_class(py, "Fraction")?
+            .call1((numerator, denominator))?
+            .unbind()),
         // Output-only types - convert to string representation
         MontyObject::Repr(s) => Ok(PyString::new(py, s).into_any().unbind()),
         MontyObject::Cycle(_, placeholder) => Ok(PyString::new(py, placeholder).into_any().unbind()),
@@ -225,6 +346,236 @@ pub fn import_builtins(py: Python<'_>) -> PyResult<&Py<PyModule>> {
     BUILTINS.get_or_try_init(py, || py.import("builtins").map(Bound::unbind))
 }
 
+/// Converts a `datetime` module object to a `MontyObject`, or returns `None` for other objects.
+///
+/// Aware datetimes and times keep only their current UTC offset, so any `tzinfo` works,
+/// but the offset must be a whole number of seconds.
+fn datetime_to_monty(obj: &Bound<'_, PyAny>) -> PyResult<Option<MontyObject>> {
+    let py = obj.py();
+    // `datetime` is a subclass of `date`, so it must be checked first
+    let object = if obj.is_instance(&datetime_class(py, "datetime")?)? {
+        MontyObject::DateTime {
+            year: obj.getattr("year")?.extract()?,
+            month: obj.getattr("month")?.extract()?,
+            day: obj.getattr("day")?.extract()?,
+            hour: obj.getattr("hour")?.extract()?,
+            minute: obj.getattr("minute")?.extract()?,
+            second: obj.getattr("second")?.extract()?,
+            microsecond: obj.getattr("microsecond")?.extract()?,
+            utc_offset: utc_offset_seconds(&obj.call_method0("utcoffset")?)?,
+        }
+    } else if obj.is_instance(&datetime_class(py, "date")?)? {
+        MontyObject::Date {
+            year: obj.getattr("year")?.extract()?,
+            month: obj.getattr("month")?.extract()?,
+            day: obj.getattr("day")?.extract()?,
+        }
+    } else if obj.is_instance(&datetime_class(py, "time")?)? {
+        MontyObject::Time {
+            hour: obj.getattr("hour")?.extract()?,
+            minute: obj.getattr("minute")?.extract()?,
+            second: obj.getattr("second")?.extract()?,
+            microsecond: obj.getattr("microsecond")?.extract()?,
+            utc_offset: utc_offset_seconds(&obj.call_method0("utcoffset")?)?,
+        }
+    } else if obj.is_instance(&datetime_class(py, "timedelta")?)? {
+        MontyObject::TimeDelta {
+            days: obj.getattr("days")?.extract()?,
+            seconds: obj.getattr("seconds")?.extract()?,
+            microseconds: obj.getattr("microseconds")?.extract()?,
+        }
+    } else if obj.is_instance(&datetime_class(py, "timezone")?)? {
+        let utc_offset = utc_offset_seconds(&obj.call_method1("utcoffset", (py.None(),))?)?.unwrap_or_default();
+        // Only keep names given explicitly, not the default `UTC+HH:MM` ones
+        let name: String = obj.call_method1("tzname", (py.None(),))?.extract()?;
+        let default_name: String = offset_timezone(py, Some(utc_offset), None)?
+            .call_method1(py, "tzname", (py.None(),))?
+            .extract(py)?;
+        MontyObject::TimeZone {
+            utc_offset,
+            name: (name != default_name).then_some(name),
+        }
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(object))
+}
+
+/// Converts the result of `utcoffset()` to seconds, keeping `None` for naive values.
+fn utc_offset_seconds(offset: &Bound<'_, PyAny>) -> PyResult<Option<i32>> {
+    if offset.is_none() {
+        return Ok(None);
+    }
+    let days: i32 = offset.getattr("days")?.extract()?;
+    let seconds: i32 = offset.getattr("seconds")?.extract()?;
+    let microseconds: i32 = offset.getattr("microseconds")?.extract()?;
+    if microseconds != 0 {
+        return Err(PyTypeError::new_err(
+            "Cannot convert a UTC offset with microseconds to Monty value",
+        ));
+    }
+    Ok(Some(days * 86_400 + seconds))
+}
+
+/// Creates a `datetime.timezone` with the given offset in seconds, or returns `None` for `None`.
+fn offset_timezone(py: Python<'_>, utc_offset: Option<i32>, name: Option<&str>) -> PyResult<Py<PyAny>> {
+    let Some(utc_offset) = utc_offset else {
+        return Ok(py.None());
+    };
+    let offset = datetime_class(py, "timedelta")?.call1((0, utc_offset))?;
+    let timezone = datetime_class(py, "timezone")?;
+    let tz = match name {
+        Some(name) => timezone.call1((offset, name))?,
+        None => timezone.call1((offset,))?,
+    };
+    Ok(tz.unbind())
+}
+
+/// Returns a class from the `datetime` module, which is imported once and cached.
+fn datetime_class<'py>(py: Python<'py>, name: &str) -> PyResult<Bound<'py, PyAny>> {
+    static DATETIME: PyOnceLock<Py<PyModule>> = PyOnceLock::new();
+
+    DATETIME
+        .get_or_try_init(py, || py.import("datetime").map(Bound::unbind))?
+        .bind(py)
+        .getattr(name)
+}
+
+/// Returns a class from the `enum` module, which is imported once and cached.
+fn enum_class<'py>(py: Python<'py>, name: &str) -> PyResult<Bound<'py, PyAny>> {
+    static ENUM: PyOnceLock<Py<PyModule>> = PyOnceLock::new();
+
+    ENUM.get_or_try_init(py, || py.import("enum").map(Bound::unbind))?
+        .bind(py)
+        .getattr(name)
+}
+
+/// Returns a class from the `decimal` module, which is imported once and cached.
+fn decimal_class<'py>(py: Python<'py>, name: &str) -> PyResult<Bound<'py, PyAny>> {
+    static DECIMAL: PyOnceLock<Py<PyModule>> = PyOnceLock::new();
+
+    DECIMAL
+        .get_or_try_init(py, || py.import("decimal").map(Bound::unbind))?
+        .bind(py)
+        .getattr(name)
+}
+
+/// Returns a class from the `fractions` module, which is imported once and cached.
+fn fractions_class<'py>(py: Python<'py>, name: &str) -> PyResult<Bound<'py, PyAny>> {
+    static FRACTIONS: PyOnceLock<Py<PyModule>> = PyOnceLock::new();
+
+    FRACTIONS
+        .get_or_try_init(py, || py.import("fractions").map(Bound::unbind))?
+        .bind(py)
+        .getattr(name)
+}
+
+/// The members of Monty enum classes found in a `MontyObject`, keyed by class id, name and
+/// base, in order of appearance.
+type EnumMembers<'a> = IndexMap<(u64, &'a str, String), Vec<(&'a str, &'a MontyObject)>>;
+
+/// Creates the Python enum classes for the Monty enum members in `obj`, so that members of
+/// the same Monty class convert to members of the same Python class.
+///
+/// Classes are created with the functional API, e.g. `Enum('Color', [('RED', 1), ...])`, and
+/// cached in `dc_registry` by the id, name and base of their Monty class, so they live as long
+/// as the `Monty` object or snapshot converting them. `MontyObject::Enum` doesn't carry the
+/// other members of its class, so a class has the members converted so far. Converting a
+/// member the cached class doesn't have replaces it with a class which does.
+fn prepare_enum_classes(py: Python<'_>, obj: &MontyObject, dc_registry: &Bound<'_, PyDict>) -> PyResult<()> {
+    let mut classes = EnumMembers::new();
+    collect_enum_members(obj, &mut classes);
+    for ((class_id, class_name, base), mut members) in classes {
+        let key = enum_class_key(py, class_name, class_id, &base)?;
+        let mut py_members: Vec<(Bound<'_, PyAny>, Py<PyAny>)> = Vec::new();
+        if let Some(cls) = dc_registry.get_item(&key)? {
+            let cached = cls.getattr("__members__")?;
+            let mut missing = Vec::new();
+            for (name, value) in members {
+                if !cached.contains(name)? {
+                    missing.push((name, value));
+                }
+            }
+            if missing.is_empty() {
+                continue;
+            }
+            for member in cls.try_iter()? {
+                let member = member?;
+                py_members.push((member.getattr("name")?, member.getattr("value")?.unbind()));
+            }
+            members = missing;
+        }
+        for (name, value) in members {
+            py_members.push((PyString::new(py, name).into_any(), monty_to_py(py, value, dc_registry)?));
+        }
+        let base = base.strip_prefix("enum.").unwrap_or(&base);
+        let cls = enum_class(py, base)?.call1((class_name, PyList::new(py, py_members)?))?;
+        dc_registry.set_item(key, cls)?;
+    }
+    Ok(())
+}
+
+/// Adds the enum members in `obj` to `classes`, including those in containers.
+fn collect_enum_members<'a>(obj: &'a MontyObject, classes: &mut EnumMembers<'a>) {
+    match obj {
+        MontyObject::List(items)
+        | MontyObject::Tuple(items)
+        | MontyObject::Set(items)
+        | MontyObject::FrozenSet(items)
+        | MontyObject::NamedTuple { values: items, .. } => {
+            for item in items {
+                collect_enum_members(item, classes);
+            }
+        }
+        MontyObject::Dict(pairs) | MontyObject::Dataclass { attrs: pairs, .. } => {
+            for (key, value) in pairs {
+                collect_enum_members(key, classes);
+                collect_enum_members(value, classes);
+            }
+        }
+        MontyObject::Enum {
+            class_name,
+            class_id,
+            base,
+            name,
+            value,
+        } => {
+            let members = classes
+                .entry((*class_id, class_name.as_str(), base.to_string()))
+                .or_default();
+            if !members.iter().any(|(member, _)| member == name) {
+                members.push((name.as_str(), value.as_ref()));
+            }
+            collect_enum_members(value, classes);
+        }
+        _ => {}
+    }
+}
+
+/// Returns the key of the Python class of a Monty enum class in the registry.
+///
+/// Dataclass types are keyed by integer ids, so these tuple keys can't clash with them.
+fn enum_class_key<'py>(py: Python<'py>, class_name: &str, class_id: u64, base: &str) -> PyResult<Bound<'py, PyAny>> {
+    Ok((class_id, class_name, base).into_pyobject(py)?.into_any())
+}
+
+/// Converts a Monty enum member to the member of the Python enum class created for its
+/// class by [`prepare_enum_classes`].
+fn enum_member_to_py(
+    py: Python<'_>,
+    class_name: &str,
+    class_id: u64,
+    base: &str,
+    name: &str,
+    dc_registry: &Bound<'_, PyDict>,
+) -> PyResult<Py<PyAny>> {
+    let key = enum_class_key(py, class_name, class_id, base)?;
+    let cls = dc_registry
+        .get_item(key)?
+        .ok_or_else(|| PyRuntimeError::new_err(format!("no class was created for enum '{class_name}'")))?;
+    Ok(cls.get_item(name)?.unbind())
+}
+
 /// Cached import of `collections.namedtuple` function.
 fn get_namedtuple(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
     static NAMEDTUPLE: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
@@ -18,7 +18,9 @@ use monty_type_checking::TypeCheckingDiagnostics;
 use pyo3::{
     PyClassInitializer, PyTypeCheck,
     exceptions::{self},
+    intern,
     prelude::*,
+    sync::PyOnceLock,
     types::{PyDict, PyList, PyString},
 };
 
@@ -363,18 +365,88 @@ impl PyFrame {
 /// Creates an appropriate Python exception type with the message.
 /// The traceback information is included in the exception message
 /// since PyO3 doesn't provide direct traceback manipulation.
+///
+/// The exception's cause and context are converted too and set as the Python
+/// exception's `__cause__` and `__context__`, as are the sub-exceptions of an
+/// exception group.
 pub fn exc_monty_to_py(py: Python<'_>, exc: MontyException) -> PyErr {
+    let cause = exc.cause().map(|cause| exc_monty_to_py(py, cause.clone()));
+    let context = exc.context().map(|context| exc_monty_to_py(py, context.clone()));
+    let suppress_context = exc.suppress_context();
+
     let exc_type = exc.exc_type();
-    let msg = exc.into_message().unwrap_or_default();
+    let py_err = if exc_type.is_group() {
+        let exceptions = exc
+            .exceptions()
+            .iter()
+            .map(|sub| exc_monty_to_py(py, sub.clone()))
+            .collect();
+        exc_group_monty_to_py(py, exc_type, exc.into_message().unwrap_or_default(), exceptions)
+    } else {
+        exc_monty_to_py_unchained(py, exc_type, exc.into_message().unwrap_or_default())
+    };
+    match set_exception_chain(py_err.value(py), cause, context, suppress_context) {
+        Ok(()) => py_err,
+        Err(e) => e,
+    }
+}
+
+/// Sets `__cause__`, `__context__` and `__suppress_context__` on a Python exception.
+fn set_exception_chain(
+    exc: &Bound<'_, exceptions::PyBaseException>,
+    cause: Option<PyErr>,
+    context: Option<PyErr>,
+    suppress_context: bool,
+) -> PyResult<()> {
+    let py = exc.py();
+    if let Some(context) = context {
+        exc.setattr(intern!(py, "__context__"), context.into_value(py))?;
+    }
+    if let Some(cause) = cause {
+        exc.setattr(intern!(py, "__cause__"), cause.into_value(py))?;
+    }
+    // Setting `__cause__` sets `__suppress_context__`, so this must come after it
+    exc.setattr(intern!(py, "__suppress_context__"), suppress_context)
+}
 
+/// Creates a Python exception group from its message and converted sub-exceptions.
+///
+/// `BaseExceptionGroup` itself returns an `ExceptionGroup` when all the sub-exceptions are
+/// `Exception`s. Exception groups don't exist before Python 3.11, so there (or if creating
+/// the group fails) a plain exception with the message is returned instead.
+fn exc_group_monty_to_py(py: Python<'_>, exc_type: ExcType, msg: String, exceptions: Vec<PyErr>) -> PyErr {
+    if let Ok(group_cls) = get_base_exception_group(py)
+        && let Ok(exceptions) = PyList::new(py, exceptions.into_iter().map(|exc| exc.into_value(py)))
+        && let Ok(group) = group_cls.call1((PyString::new(py, &msg), exceptions))
+    {
+        return PyErr::from_value(group);
+    }
+    exc_monty_to_py_unchained(py, exc_type, msg)
+}
+
+/// Creates the Python exception matching a Monty exception type, with the given message.
+fn exc_monty_to_py_unchained(py: Python<'_>, exc_type: ExcType, msg: String) -> PyErr {
     match exc_type {
         ExcType::Exception => exceptions::PyException::new_err(msg),
         ExcType::BaseException => exceptions::PyBaseException::new_err(msg),
         ExcType::SystemExit => exceptions::PySystemExit::new_err(msg),
         ExcType::KeyboardInterrupt => exceptions::PyKeyboardInterrupt::new_err(msg),
+        ExcType::GeneratorExit => exceptions::PyGeneratorExit::new_err(msg),
+        // Only reached when an exception group can't be created, see `exc_group_monty_to_py`
+        ExcType::BaseExceptionGroup => exceptions::PyBaseException::new_err(msg),
+        ExcType::ExceptionGroup => exceptions::PyException::new_err(msg),
         ExcType::ArithmeticError => exceptions::PyArithmeticError::new_err(msg),
         ExcType::OverflowError => exceptions::PyOverflowError::new_err(msg),
         ExcType::ZeroDivisionError => exceptions::PyZeroDivisionError::new_err(msg),
+        ExcType::DecimalException | ExcType::InvalidOperation | ExcType::DivisionByZero | ExcType::Overflow => {
+            if let Ok(exc_cls) = get_decimal_exception(py, &exc_type.to_string())
+                && let Ok(exc_instance) = exc_cls.call1((PyString::new(py, &msg),))
+            {
+                return PyErr::from_value(exc_instance);
+            }
+            // if creating the right exception fails, fallback to ArithmeticError which they're subclasses of
+            exceptions::PyArithmeticError::new_err(msg)
+        }
         ExcType::LookupError => exceptions::PyLookupError::new_err(msg),
         ExcType::IndexError => exceptions::PyIndexError::new_err(msg),
         ExcType::KeyError => exceptions::PyKeyError::new_err(msg),
@@ -393,6 +465,15 @@ pub fn exc_monty_to_py(py: Python<'_>, exc: MontyException) -> PyErr {
             exceptions::PyAttributeError::new_err(msg)
         }
         ExcType::MemoryError => exceptions::PyMemoryError::new_err(msg),
+        ExcType::PatternError => {
+            if let Ok(exc_cls) = get_re_error(py)
+                && let Ok(exc_instance) = exc_cls.call1((PyString::new(py, &msg),))
+            {
+                return PyErr::from_value(exc_instance);
+            }
+            // if creating the right exception fails, fallback to Exception which it's a subclass of
+            exceptions::PyException::new_err(msg)
+        }
         ExcType::NameError => exceptions::PyNameError::new_err(msg),
         ExcType::UnboundLocalError => exceptions::PyUnboundLocalError::new_err(msg),
         ExcType::StopIteration => exceptions::PyStopIteration::new_err(msg),
@@ -401,6 +482,18 @@ pub fn exc_monty_to_py(py: Python<'_>, exc: MontyException) -> PyErr {
         ExcType::TypeError => exceptions::PyTypeError::new_err(msg),
         ExcType::ValueError => exceptions::PyValueError::new_err(msg),
         ExcType::UnicodeDecodeError => exceptions::PyUnicodeDecodeError::new_err(msg),
+        ExcType::JSONDecodeError => {
+            // `JSONDecodeError.__init__` computes the message from the document, which we don't have,
+            // so the instance is created with the formatted message like a plain `ValueError`
+            if let Ok(exc_cls) = get_json_decode_error(py)
+                && let Ok(exc_instance) =
+                    exc_cls.call_method1(intern!(py, "__new__"), (exc_cls, PyString::new(py, &msg)))
+            {
+                return PyErr::from_value(exc_instance);
+            }
+            // if creating the right exception fails, fallback to ValueError which it's a subclass of
+            exceptions::PyValueError::new_err(msg)
+        }
         ExcType::ImportError => exceptions::PyImportError::new_err(msg),
         ExcType::ModuleNotFoundError => exceptions::PyModuleNotFoundError::new_err(msg),
         ExcType::OSError => exceptions::PyOSError::new_err(msg),
@@ -415,21 +508,80 @@ pub fn exc_monty_to_py(py: Python<'_>, exc: MontyException) -> PyErr {
 ///
 /// Used when resuming execution with an exception from Python.
 pub fn exc_py_to_monty(py: Python<'_>, py_err: &PyErr) -> MontyException {
-    let exc = py_err.value(py);
+    exc_value_to_monty(py_err.value(py), &mut Vec::new())
+}
+
+/// Converts a Python exception value to monty, including its `__cause__` and `__context__`
+/// and, for exception groups, its sub-exceptions.
+///
+/// `seen` holds the exceptions already converted further along the chain, so a cycle
+/// in the chain is cut rather than followed forever.
+fn exc_value_to_monty<'py>(
+    exc: &Bound<'py, exceptions::PyBaseException>,
+    seen: &mut Vec<Bound<'py, exceptions::PyBaseException>>,
+) -> MontyException {
+    let py = exc.py();
     let exc_type = py_err_to_exc_type(exc);
-    let arg = exc.str().ok().map(|s| s.to_string_lossy().into_owned());
+    let arg = exc_arg(exc, exc_type);
+    let suppress_context = exc
+        .getattr(intern!(py, "__suppress_context__"))
+        .and_then(|value| value.is_truthy())
+        .unwrap_or(false);
+
+    seen.push(exc.clone());
+    let mut chained = |attr: &Bound<'py, PyString>| {
+        let chained = exc
+            .getattr(attr)
+            .ok()?
+            .cast_into::<exceptions::PyBaseException>()
+            .ok()?;
+        if seen.iter().any(|other| other.is(&chained)) {
+            return None;
+        }
+        Some(Box::new(exc_value_to_monty(&chained, seen)))
+    };
+    let cause = chained(intern!(py, "__cause__"));
+    let context = chained(intern!(py, "__context__"));
+
+    let mut exceptions = Vec::new();
+    if exc_type.is_group()
+        && let Ok(sub_excs) = exc.getattr(intern!(py, "exceptions"))
+        && let Ok(sub_excs) = sub_excs.try_iter()
+    {
+        for sub_exc in sub_excs.flatten() {
+            if let Ok(sub_exc) = sub_exc.cast_into::<exceptions::PyBaseException>() {
+                exceptions.push(exc_value_to_monty(&sub_exc, &mut Vec::new()));
+            }
+        }
+    }
 
     MontyException::new(exc_type, arg)
+        .with_chain(cause, context, suppress_context)
+        .with_exceptions(exceptions)
 }
 
 /// Converts a Python exception to Monty's `MontyObject::Exception`.
 pub fn exc_to_monty_object(exc: &Bound<'_, exceptions::PyBaseException>) -> ::monty::MontyObject {
     let exc_type = py_err_to_exc_type(exc);
-    let arg = exc.str().ok().map(|s| s.to_string_lossy().into_owned());
+    let arg = exc_arg(exc, exc_type);
 
     ::monty::MontyObject::Exception { exc_type, arg }
 }
 
+/// Returns the message of a Python exception.
+///
+/// This is `str(exc)`, except for exception groups whose `str()` includes the number of
+/// sub-exceptions, so their `message` attribute is used instead.
+fn exc_arg(exc: &Bound<'_, exceptions::PyBaseException>, exc_type: ExcType) -> Option<String> {
+    let text = if exc_type.is_group() {
+        exc.getattr(intern!(exc.py(), "message"))
+            .and_then(|message| message.str())
+    } else {
+        exc.str()
+    };
+    text.ok().map(|s| s.to_string_lossy().into_owned())
+}
+
 /// Maps a Python exception type to Monty's `ExcType` enum.
 ///
 /// NOTE: order matters here as some exceptions are subclasses of others!
@@ -440,10 +592,12 @@ fn py_err_to_exc_type(exc: &Bound<'_, exceptions::PyBaseException>) -> ExcType {
         // put the most commonly used exceptions first
         if exceptions::PyTypeError::type_check(exc) {
             ExcType::TypeError
-        // ValueError hierarchy (check UnicodeDecodeError first as it's a subclass)
+        // ValueError hierarchy (check UnicodeDecodeError and JSONDecodeError first as they're subclasses)
         } else if exceptions::PyValueError::type_check(exc) {
             if exceptions::PyUnicodeDecodeError::type_check(exc) {
                 ExcType::UnicodeDecodeError
+            } else if is_json_decode_error(exc) {
+                ExcType::JSONDecodeError
             } else {
                 ExcType::ValueError
             }
@@ -462,7 +616,10 @@ fn py_err_to_exc_type(exc: &Bound<'_, exceptions::PyBaseException>) -> ExcType {
             }
         // ArithmeticError hierarchy
         } else if exceptions::PyArithmeticError::type_check(exc) {
-            if exceptions::PyZeroDivisionError::type_check(exc) {
+            // check the decimal exceptions first, as `DivisionByZero` is a `ZeroDivisionError`
+            if let Some(exc_type) = decimal_exc_type(exc) {
+                exc_type
+            } else if exceptions::PyZeroDivisionError::type_check(exc) {
                 ExcType::ZeroDivisionError
             } else if exceptions::PyOverflowError::type_check(exc) {
                 ExcType::OverflowError
@@ -510,6 +667,10 @@ fn py_err_to_exc_type(exc: &Bound<'_, exceptions::PyBaseException>) -> ExcType {
             ExcType::TimeoutError
         } else if exceptions::PyMemoryError::type_check(exc) {
             ExcType::MemoryError
+        } else if is_re_error(exc) {
+            ExcType::PatternError
+        } else if is_exception_group(exc) {
+            ExcType::ExceptionGroup
         } else {
             ExcType::Exception
         }
@@ -518,12 +679,32 @@ fn py_err_to_exc_type(exc: &Bound<'_, exceptions::PyBaseException>) -> ExcType {
         ExcType::SystemExit
     } else if exceptions::PyKeyboardInterrupt::type_check(exc) {
         ExcType::KeyboardInterrupt
+    } else if exceptions::PyGeneratorExit::type_check(exc) {
+        ExcType::GeneratorExit
+    } else if is_exception_group(exc) {
+        ExcType::BaseExceptionGroup
     // Catch-all for BaseException
     } else {
         ExcType::BaseException
     }
 }
 
+/// Cached lookup of the builtin `BaseExceptionGroup` class, which exists from Python 3.11.
+fn get_base_exception_group(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static BASE_EXCEPTION_GROUP: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    BASE_EXCEPTION_GROUP.import(py, "builtins", "BaseExceptionGroup")
+}
+
+/// Checks if an exception is an instance of `BaseExceptionGroup`.
+fn is_exception_group(exc: &Bound<'_, exceptions::PyBaseException>) -> bool {
+    if let Ok(group_cls) = get_base_exception_group(exc.py()) {
+        exc.is_instance(group_cls).unwrap_or(false)
+    } else {
+        false
+    }
+}
+
 /// Checks if an exception is an instance of `dataclasses.FrozenInstanceError`.
 ///
 /// Since `FrozenInstanceError` is not a built-in PyO3 exception type, we need to
@@ -535,3 +716,64 @@ fn is_frozen_instance_error(exc: &Bound<'_, exceptions::PyBaseException>) -> boo
         false
     }
 }
+
+/// Cached import of the `json.JSONDecodeError` exception class.
+fn get_json_decode_error(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static JSON_DECODE_ERROR: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    JSON_DECODE_ERROR.import(py, "json", "JSONDecodeError")
+}
+
+/// Checks if an exception is an instance of `json.JSONDecodeError`.
+fn is_json_decode_error(exc: &Bound<'_, exceptions::PyBaseException>) -> bool {
+    if let Ok(json_error_cls) = get_json_decode_error(exc.py()) {
+        exc.is_instance(json_error_cls).unwrap_or(false)
+    } else {
+        false
+    }
+}
+
+/// Returns an exception class from the `decimal` module, which is imported once and cached.
+fn get_decimal_exception<'py>(py: Python<'py>, name: &str) -> PyResult<Bound<'py, PyAny>> {
+    static DECIMAL: PyOnceLock<Py<PyModule>> = PyOnceLock::new();
+
+    DECIMAL
+        .get_or_try_init(py, || py.import("decimal").map(Bound::unbind))?
+        .bind(py)
+        .getattr(name)
+}
+
+/// Returns the Monty type of an exception from the `decimal` module, or `None` for other
+/// exceptions.
+///
+/// Signals Monty doesn't raise, like `decimal.Underflow`, map to the nearest base class.
+fn decimal_exc_type(exc: &Bound<'_, exceptions::PyBaseException>) -> Option<ExcType> {
+    [
+        ExcType::DivisionByZero,
+        ExcType::InvalidOperation,
+        ExcType::Overflow,
+        ExcType::DecimalException,
+    ]
+    .into_iter()
+    .find(|exc_type| {
+        get_decimal_exception(exc.py(), &exc_type.to_string())
+            .and_then(|exc_cls| exc.is_instance(&exc_cls))
+            .unwrap_or(false)
+    })
+}
+
+/// Cached import of the `re.error` exception class, named `PatternError` from Python 3.13.
+fn get_re_error(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static RE_ERROR: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    RE_ERROR.import(py, "re", "error")
+}
+
+/// Checks if an exception is an instance of `re.error`.
+fn is_re_error(exc: &Bound<'_, exceptions::PyBaseException>) -> bool {
+    if let Ok(re_error_cls) = get_re_error(exc.py()) {
+        exc.is_instance(re_error_cls).unwrap_or(false)
+    } else {
+        false
+    }
+}
@@ -0,0 +1,39 @@
+//! Handles for functions defined in Monty code.
+
+use monty::MontyObject;
+use pyo3::prelude::*;
+
+/// A function defined in Monty code, passed to Python as a handle.
+///
+/// External functions receive these in place of Monty functions, e.g. a decorator
+/// implemented as an external function receives the function it decorates.
+/// The function can't be called from Python, but passing the handle back to Monty
+/// (e.g. by returning it from the decorator) gives back the original function.
+#[pyclass(name = "MontyFunction", module = "pydantic_monty", frozen, eq, hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PyMontyFunction {
+    /// The function's name.
+    #[pyo3(get)]
+    pub name: String,
+    /// The function's `id()` in Monty, which identifies it when the handle is passed back.
+    #[pyo3(get)]
+    pub id: u64,
+}
+
+#[pymethods]
+impl PyMontyFunction {
+    fn __repr__(&self) -> String {
+        format!("<MontyFunction {}>", self.name)
+    }
+}
+
+impl PyMontyFunction {
+    /// Converts the handle back to a `MontyObject`.
+    #[must_use]
+    pub fn to_monty(&self) -> MontyObject {
+        MontyObject::Function {
+            name: self.name.clone(),
+            id: self.id,
+        }
+    }
+}
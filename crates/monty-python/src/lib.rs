@@ -8,6 +8,7 @@ mod convert;
 mod dataclass;
 mod exceptions;
 mod external;
+mod function;
 mod limits;
 mod monty_cls;
 
@@ -15,6 +16,7 @@ use std::sync::OnceLock;
 
 // Use `::monty` to refer to the external crate (not the pymodule)
 pub use exceptions::{MontyError, MontyRuntimeError, MontySyntaxError, MontyTypingError, PyFrame};
+pub use function::PyMontyFunction;
 pub use monty_cls::{PyMonty, PyMontyComplete, PyMontyFutureSnapshot, PyMontySnapshot};
 use pyo3::prelude::*;
 
@@ -53,6 +55,8 @@ mod _monty {
     #[pymodule_export]
     use super::PyMontyComplete as MontyComplete;
     #[pymodule_export]
+    use super::PyMontyFunction as MontyFunction;
+    #[pymodule_export]
     use super::PyMontyFutureSnapshot as MontyFutureSnapshot;
     #[pymodule_export]
     use super::PyMontySnapshot as MontySnapshot;
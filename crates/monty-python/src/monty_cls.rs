@@ -1119,6 +1119,8 @@ fn list_str(arg: Option<&Bound<'_, PyList>>, name: &str) -> PyResult<Vec<String>
 
 /// A `PrintWriter` implementation that calls a Python callback for each print output.
 ///
+/// The callback is called with the stream name (`'stdout'` or `'stderr'`) and the text.
+///
 /// This struct holds a GIL-independent `Py<PyAny>` reference to the callback,
 /// allowing it to be used across GIL release boundaries. The GIL is re-acquired
 /// briefly for each callback invocation.
@@ -1153,6 +1155,22 @@ impl PrintWriter for CallbackStringPrint {
         })
         .map_err(|e| Python::attach(|py| exc_py_to_monty(py, &e)))
     }
+
+    fn stderr_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        Python::attach(|py| {
+            self.0.bind(py).call1(("stderr", output.as_ref()))?;
+            Ok::<_, PyErr>(())
+        })
+        .map_err(|e| Python::attach(|py| exc_py_to_monty(py, &e)))
+    }
+
+    fn stderr_push(&mut self, end: char) -> Result<(), MontyException> {
+        Python::attach(|py| {
+            self.0.bind(py).call1(("stderr", end.to_string()))?;
+            Ok::<_, PyErr>(())
+        })
+        .map_err(|e| Python::attach(|py| exc_py_to_monty(py, &e)))
+    }
 }
 
 /// Serialization wrapper for `PyMonty` that includes all fields needed for reconstruction.
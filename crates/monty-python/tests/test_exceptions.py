@@ -1,3 +1,8 @@
+import decimal
+import json
+import re
+import sys
+
 import pytest
 from inline_snapshot import snapshot
 
@@ -26,6 +31,33 @@ def test_value_error():
     assert str(inner) == snapshot('bad value')
 
 
+def test_json_decode_error():
+    m = pydantic_monty.Monty("import json\njson.loads('[1,')")
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run()
+    inner = exc_info.value.exception()
+    assert isinstance(inner, json.JSONDecodeError)
+    assert str(inner) == snapshot('Expecting value: line 1 column 4 (char 3)')
+
+
+def test_re_error():
+    m = pydantic_monty.Monty("import re\nre.compile('(a')")
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run()
+    inner = exc_info.value.exception()
+    assert isinstance(inner, re.error)
+    assert str(inner) == snapshot('missing ), unterminated subpattern at position 0')
+
+
+def test_decimal_signal():
+    m = pydantic_monty.Monty('from decimal import Decimal\nDecimal(1) / 0')
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run()
+    inner = exc_info.value.exception()
+    assert isinstance(inner, decimal.DivisionByZero)
+    assert isinstance(inner, ZeroDivisionError)
+
+
 def test_type_error():
     m = pydantic_monty.Monty("'string' + 1")
     with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
@@ -173,6 +205,104 @@ fail()
     assert str(inner) == snapshot('from function')
 
 
+def test_exception_chain():
+    code = """
+try:
+    1 / 0
+except ZeroDivisionError as e:
+    raise ValueError('bad value') from e
+"""
+    m = pydantic_monty.Monty(code)
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run()
+    inner = exc_info.value.exception()
+    assert isinstance(inner, ValueError)
+    assert isinstance(inner.__cause__, ZeroDivisionError)
+    assert str(inner.__cause__) == snapshot('division by zero')
+    assert isinstance(inner.__context__, ZeroDivisionError)
+    assert inner.__suppress_context__ is True
+
+
+def test_exception_context():
+    code = """
+try:
+    1 / 0
+except ZeroDivisionError:
+    raise ValueError('bad value')
+"""
+    m = pydantic_monty.Monty(code)
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run()
+    inner = exc_info.value.exception()
+    assert inner.__cause__ is None
+    assert isinstance(inner.__context__, ZeroDivisionError)
+    assert inner.__suppress_context__ is False
+    assert exc_info.value.display() == snapshot("""\
+Traceback (most recent call last):
+  File "main.py", line 3, in <module>
+    1 / 0
+    ~~~~~
+ZeroDivisionError: division by zero
+
+During handling of the above exception, another exception occurred:
+
+Traceback (most recent call last):
+  File "main.py", line 5, in <module>
+    raise ValueError('bad value')
+ValueError: bad value\
+""")
+
+
+def test_external_exception_chain():
+    code = """
+try:
+    fail()
+except RuntimeError as e:
+    result = (type(e.__cause__) is KeyError, e.__cause__.args[0], e.__suppress_context__)
+result
+"""
+    m = pydantic_monty.Monty(code, external_functions=['fail'])
+
+    def fail() -> None:
+        try:
+            raise KeyError('missing')
+        except KeyError as e:
+            raise RuntimeError('lookup failed') from e
+
+    assert m.run(external_functions={'fail': fail}) == snapshot((True, 'missing', True))
+
+
+@pytest.mark.skipif(sys.version_info < (3, 11), reason='exception groups require Python 3.11+')
+def test_exception_group():
+    code = """
+raise ExceptionGroup('eg', [ValueError('a'), TypeError('b')])
+"""
+    m = pydantic_monty.Monty(code)
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run()
+    inner = exc_info.value.exception()
+    assert isinstance(inner, ExceptionGroup)  # noqa: F821
+    assert inner.message == snapshot('eg')
+    assert [repr(e) for e in inner.exceptions] == snapshot(["ValueError('a')", "TypeError('b')"])
+
+
+@pytest.mark.skipif(sys.version_info < (3, 11), reason='exception groups require Python 3.11+')
+def test_external_exception_group():
+    code = """
+try:
+    fail()
+except* KeyError as e:
+    result = (type(e) is ExceptionGroup, e.message, len(e.exceptions))
+result
+"""
+    m = pydantic_monty.Monty(code, external_functions=['fail'])
+
+    def fail() -> None:
+        raise ExceptionGroup('lookups failed', [KeyError('a'), KeyError('b')])  # noqa: F821
+
+    assert m.run(external_functions={'fail': fail}) == snapshot((True, 'lookups failed', 2))
+
+
 # === Display and str methods ===
 
 
@@ -190,6 +190,30 @@ caught
     assert result == snapshot(True)
 
 
+def test_external_function_as_decorator():
+    """Test that an external function can decorate a Monty function, receiving a handle to it."""
+    code = """
+@register
+def double(x):
+    return x * 2
+
+double(21)
+"""
+    m = pydantic_monty.Monty(code, external_functions=['register'])
+    registered: list[pydantic_monty.MontyFunction] = []
+
+    def register(func: pydantic_monty.MontyFunction) -> pydantic_monty.MontyFunction:
+        registered.append(func)
+        return func
+
+    result = m.run(external_functions={'register': register})
+    assert result == snapshot(42)
+    assert len(registered) == 1
+    assert isinstance(registered[0], pydantic_monty.MontyFunction)
+    assert registered[0].name == snapshot('double')
+    assert repr(registered[0]) == snapshot('<MontyFunction double>')
+
+
 def test_external_function_exception_type_preserved():
     """Test that various exception types are correctly preserved."""
     m = pydantic_monty.Monty('fail()', external_functions=['fail'])
@@ -5,6 +5,7 @@ with the right function name and arguments, and that return values from
 the host are properly converted and used by Monty code.
 """
 
+from datetime import date, datetime, timedelta, timezone
 from pathlib import PurePosixPath
 from typing import Any
 
@@ -817,3 +818,104 @@ Path('/tmp/mydir/file.txt').read_text()
             ('Path.read_text', (PurePosixPath('/tmp/mydir/file.txt'),)),
         ]
     )
+
+
+# =============================================================================
+# datetime.now() and date.today()
+# =============================================================================
+
+
+def test_datetime_now_yields_oscall():
+    """datetime.now() yields an OS call with the requested timezone."""
+    m = pydantic_monty.Monty('from datetime import datetime, timezone; datetime.now(timezone.utc)')
+    result = m.start()
+
+    assert isinstance(result, pydantic_monty.MontySnapshot)
+    assert result.is_os_function is True
+    assert result.function_name == snapshot('datetime.now')
+    assert result.args == snapshot((timezone.utc,))
+
+
+def test_datetime_now_resume():
+    """The host's time is converted to the timezone datetime.now() asked for."""
+    m = pydantic_monty.Monty(
+        'from datetime import datetime, timedelta, timezone; datetime.now(timezone(timedelta(hours=2)))'
+    )
+    snapshot_result = m.start()
+
+    assert isinstance(snapshot_result, pydantic_monty.MontySnapshot)
+    result = snapshot_result.resume(return_value=datetime(2024, 1, 15, 12, 30, tzinfo=timezone.utc))
+
+    assert isinstance(result, pydantic_monty.MontyComplete)
+    assert result.output == datetime(2024, 1, 15, 14, 30, tzinfo=timezone(timedelta(hours=2)))
+    assert result.output.utcoffset() == timedelta(hours=2)
+
+
+def test_date_today_callback():
+    """date.today() uses the time returned by the os callback."""
+    calls: list[tuple[str, tuple[Any, ...]]] = []
+
+    def os_handler(function_name: str, args: tuple[Any, ...], kwargs: dict[str, Any] | None = None) -> Any:
+        calls.append((function_name, args))
+        return datetime(2024, 12, 31, 23, 30, tzinfo=timezone(timedelta(hours=-1)))
+
+    m = pydantic_monty.Monty('from datetime import date; date.today()')
+    assert m.run(os=os_handler) == snapshot(date(2025, 1, 1))
+    assert calls == snapshot([('datetime.now', (None,))])
+
+
+# =============================================================================
+# random seeds
+# =============================================================================
+
+
+def test_random_seed_yields_oscall():
+    """Using the random module without seeding it asks the host for a seed."""
+    m = pydantic_monty.Monty('import random; random.random()')
+    result = m.start()
+
+    assert isinstance(result, pydantic_monty.MontySnapshot)
+    assert result.is_os_function is True
+    assert result.function_name == snapshot('random.seed')
+    assert result.args == snapshot(())
+
+
+def test_random_seed_resume():
+    """The host's seed gives the same numbers as seeding in code."""
+    m = pydantic_monty.Monty('import random; [random.randint(1, 6) for _ in range(5)]')
+    snapshot_result = m.start()
+
+    assert isinstance(snapshot_result, pydantic_monty.MontySnapshot)
+    result = snapshot_result.resume(return_value=42)
+
+    assert isinstance(result, pydantic_monty.MontyComplete)
+    seeded = pydantic_monty.Monty('import random; random.seed(42); [random.randint(1, 6) for _ in range(5)]')
+    assert result.output == seeded.run()
+
+
+# =============================================================================
+# uuid4 bytes
+# =============================================================================
+
+
+def test_uuid4_yields_oscall():
+    """uuid.uuid4() asks the host for random bytes."""
+    m = pydantic_monty.Monty('import uuid; uuid.uuid4()')
+    result = m.start()
+
+    assert isinstance(result, pydantic_monty.MontySnapshot)
+    assert result.is_os_function is True
+    assert result.function_name == snapshot('uuid.uuid4')
+    assert result.args == snapshot(())
+
+
+def test_uuid4_resume():
+    """The version and variant bits are set on the host's bytes."""
+    m = pydantic_monty.Monty('import uuid; str(uuid.uuid4())')
+    snapshot_result = m.start()
+
+    assert isinstance(snapshot_result, pydantic_monty.MontySnapshot)
+    result = snapshot_result.resume(return_value=bytes(16))
+
+    assert isinstance(result, pydantic_monty.MontyComplete)
+    assert result.output == snapshot('00000000-0000-4000-8000-000000000000')
@@ -5,20 +5,30 @@ from inline_snapshot import snapshot
 
 import pydantic_monty
 
-PrintCallback = Callable[[Literal['stdout'], str], None]
+PrintCallback = Callable[[Literal['stdout', 'stderr'], str], None]
 
 
 def make_print_collector() -> tuple[list[str], PrintCallback]:
     """Create a print callback that collects output into a list."""
     output: list[str] = []
 
-    def callback(stream: Literal['stdout'], text: str) -> None:
+    def callback(stream: Literal['stdout', 'stderr'], text: str) -> None:
         assert stream == 'stdout'
         output.append(text)
 
     return output, callback
 
 
+def make_stream_collector() -> tuple[dict[str, list[str]], PrintCallback]:
+    """Create a print callback that collects stdout and stderr output separately."""
+    output: dict[str, list[str]] = {'stdout': [], 'stderr': []}
+
+    def callback(stream: Literal['stdout', 'stderr'], text: str) -> None:
+        output[stream].append(text)
+
+    return output, callback
+
+
 def test_print_basic() -> None:
     m = pydantic_monty.Monty('print("hello")')
     output, callback = make_print_collector()
@@ -107,10 +117,37 @@ def test_print_mixed_types() -> None:
     assert ''.join(output) == snapshot('1 hello True None\n')
 
 
+def test_print_file_stderr() -> None:
+    code = """
+import sys
+print('result')
+print('warning', 1, file=sys.stderr)
+print('explicit', file=sys.stdout)
+"""
+    m = pydantic_monty.Monty(code)
+    output, callback = make_stream_collector()
+    m.run(print_callback=callback)
+    assert ''.join(output['stdout']) == snapshot('result\nexplicit\n')
+    assert ''.join(output['stderr']) == snapshot('warning 1\n')
+
+
+def test_sys_stderr_write() -> None:
+    code = """
+import sys
+sys.stdout.write('out')
+sys.stderr.write('err')
+sys.stderr.flush()
+"""
+    m = pydantic_monty.Monty(code)
+    output, callback = make_stream_collector()
+    m.run(print_callback=callback)
+    assert output == snapshot({'stdout': ['out'], 'stderr': ['err']})
+
+
 def make_error_callback(error: Exception) -> PrintCallback:
     """Create a print callback that raises an exception."""
 
-    def callback(stream: Literal['stdout'], text: str) -> None:
+    def callback(stream: Literal['stdout', 'stderr'], text: str) -> None:
         raise error
 
     return callback
@@ -183,7 +220,7 @@ for i in range(5):
     m = pydantic_monty.Monty(code)
     call_count = 0
 
-    def callback(stream: Literal['stdout'], text: str) -> None:
+    def callback(stream: Literal['stdout', 'stderr'], text: str) -> None:
         nonlocal call_count
         call_count += 1
         if call_count >= 3:
@@ -56,6 +56,32 @@ def test_bytes():
     assert m.run(inputs={'x': b'\x00\x01\x02'}) == snapshot(b'\x00\x01\x02')
 
 
+def test_bytearray():
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    result = m.run(inputs={'x': bytearray(b'hello')})
+    assert result == snapshot(bytearray(b'hello'))
+    assert type(result) is bytearray
+    assert m.run(inputs={'x': bytearray()}) == snapshot(bytearray(b''))
+
+
+def test_bytearray_output():
+    m = pydantic_monty.Monty('x.append(33)\nx', inputs=['x'])
+    assert m.run(inputs={'x': bytearray(b'hi')}) == snapshot(bytearray(b'hi!'))
+
+
+def test_complex():
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    result = m.run(inputs={'x': 1.5 - 2j})
+    assert result == snapshot(1.5 - 2j)
+    assert type(result) is complex
+    assert m.run(inputs={'x': 0j}) == snapshot(0j)
+
+
+def test_complex_output():
+    m = pydantic_monty.Monty('x * 1j', inputs=['x'])
+    assert m.run(inputs={'x': 1 + 2j}) == snapshot(-2 + 1j)
+
+
 def test_list():
     m = pydantic_monty.Monty('x', inputs=['x'])
     assert m.run(inputs={'x': [1, 2, 3]}) == snapshot([1, 2, 3])
@@ -551,3 +577,156 @@ def test_namedtuple_custom_missing_attr_error():
         m.run(inputs={'s': Simple(value=42)})
     # Monty uses the full qualified name (module.ClassName) for the type
     assert "AttributeError: 'test_types.Simple' object has no attribute 'nonexistent'" in str(exc_info.value)
+
+
+# === datetime ===
+
+
+def test_datetime_input_output():
+    from datetime import date, datetime, time, timedelta, timezone
+
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    tz = timezone(timedelta(hours=-3), 'BRT')
+    for value in [
+        date(2024, 2, 29),
+        time(12, 30, 5, 123),
+        time(8, 0, tzinfo=tz),
+        datetime(2024, 1, 15, 10, 20, 30, 456),
+        datetime(2024, 1, 15, 10, 20, tzinfo=timezone.utc),
+        timedelta(days=-1, seconds=5, microseconds=6),
+        tz,
+    ]:
+        result = m.run(inputs={'x': value})
+        assert result == value
+        assert type(result) is type(value)
+
+
+def test_datetime_aware_keeps_offset():
+    from datetime import datetime, timedelta, timezone
+
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    result = m.run(inputs={'x': datetime(2024, 6, 1, 12, tzinfo=timezone(timedelta(hours=2)))})
+    assert result.utcoffset() == timedelta(hours=2)
+    assert result.hour == 12
+
+
+def test_datetime_arithmetic():
+    from datetime import date, timedelta
+
+    m = pydantic_monty.Monty('from datetime import timedelta\nd + timedelta(days=1)', inputs=['d'])
+    assert m.run(inputs={'d': date(2024, 2, 28)}) == date(2024, 2, 29)
+    m = pydantic_monty.Monty('b - a', inputs=['a', 'b'])
+    assert m.run(inputs={'a': date(2024, 1, 1), 'b': date(2024, 3, 1)}) == timedelta(days=60)
+
+
+def test_return_datetime_type():
+    import datetime
+
+    m = pydantic_monty.Monty('import datetime\n(datetime.date, datetime.datetime, datetime.timedelta)')
+    assert m.run() == (datetime.date, datetime.datetime, datetime.timedelta)
+
+
+# === enum ===
+
+ENUM_CODE = """
+from enum import Enum, IntEnum, StrEnum, auto
+
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+    CRIMSON = 1
+
+class Level(IntEnum):
+    LOW = auto()
+    HIGH = auto()
+
+class Mode(StrEnum):
+    READ = auto()
+    WRITE = 'w'
+"""
+
+
+def test_enum_output():
+    import enum
+
+    m = pydantic_monty.Monty(ENUM_CODE + '(Color.GREEN, Color.CRIMSON, Level.HIGH, Mode.READ)')
+    color, crimson, level, mode = m.run()
+    assert isinstance(color, enum.Enum)
+    assert type(color).__name__ == 'Color'
+    assert (color.name, color.value) == ('GREEN', 2)
+    assert crimson is type(color).RED
+    # the Python class only has the members which were output
+    assert [member.name for member in type(color)] == ['GREEN', 'RED']
+    assert isinstance(level, enum.IntEnum)
+    assert level == 2
+    assert repr(level) == snapshot('<Level.HIGH: 2>')
+    assert isinstance(mode, enum.StrEnum)
+    assert mode == 'read'
+
+
+def test_enum_output_same_class():
+    m = pydantic_monty.Monty(ENUM_CODE + '[Color.RED, Color.GREEN, Color.RED]')
+    red, green, red_again = m.run()
+    assert type(red) is type(green)
+    assert red is red_again
+    assert m.run()[0] is red
+
+
+def test_enum_output_new_member():
+    m = pydantic_monty.Monty(ENUM_CODE + '[Color.RED, Color.GREEN][x]', inputs=['x'])
+    red = m.run(inputs={'x': 0})
+    assert [member.name for member in type(red)] == ['RED']
+    green = m.run(inputs={'x': 1})
+    assert [member.name for member in type(green)] == ['RED', 'GREEN']
+    assert m.run(inputs={'x': 0}) is type(green).RED
+
+
+def test_enum_output_classes_per_monty():
+    red = pydantic_monty.Monty(ENUM_CODE + 'Color.RED').run()
+    assert pydantic_monty.Monty(ENUM_CODE + 'Color.RED').run() is not red
+
+
+def test_return_enum_type():
+    import enum
+
+    m = pydantic_monty.Monty('import enum\n(enum.Enum, enum.IntEnum, enum.StrEnum)')
+    assert m.run() == (enum.Enum, enum.IntEnum, enum.StrEnum)
+
+
+# === decimal and fractions ===
+
+
+def test_decimal_fraction_input_output():
+    from decimal import Decimal
+    from fractions import Fraction
+
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    for value in [Decimal('1.50'), Decimal('-Infinity'), Fraction(-2, 3), Fraction(10**30, 7)]:
+        result = m.run(inputs={'x': value})
+        assert result == value
+        assert type(result) is type(value)
+    assert str(m.run(inputs={'x': Decimal('1.50')})) == '1.50'
+
+
+def test_decimal_nan_input():
+    from decimal import Decimal
+
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    assert m.run(inputs={'x': Decimal('NaN')}).is_nan()
+
+
+def test_decimal_fraction_arithmetic():
+    from decimal import Decimal
+    from fractions import Fraction
+
+    m = pydantic_monty.Monty('a + b', inputs=['a', 'b'])
+    assert m.run(inputs={'a': Decimal('0.1'), 'b': Decimal('0.2')}) == Decimal('0.3')
+    assert m.run(inputs={'a': Fraction(1, 3), 'b': 1}) == Fraction(4, 3)
+
+
+def test_return_decimal_fraction_type():
+    import decimal
+    import fractions
+
+    m = pydantic_monty.Monty('import decimal\nimport fractions\n(decimal.Decimal, fractions.Fraction)')
+    assert m.run() == (decimal.Decimal, fractions.Fraction)
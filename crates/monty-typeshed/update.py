@@ -22,12 +22,16 @@ ALLOWED_FUNCTIONS = {
     'all',
     'any',
     'bin',
+    'callable',
     'chr',
     'divmod',
+    'getattr',
+    'hasattr',
     'hash',
     'hex',
     'id',
     'isinstance',
+    'iter',
     'len',
     'max',
     'min',
@@ -37,6 +41,7 @@ ALLOWED_FUNCTIONS = {
     'print',
     'repr',
     'round',
+    'setattr',
     'sorted',
     'sum',
 }
@@ -62,6 +67,8 @@ ALLOWED_CLASSES = {
     'range',
     # Iterator types (these are classes, not functions)
     'enumerate',
+    'filter',
+    'map',
     'reversed',
     'zip',
     # Slicing
@@ -127,6 +127,49 @@ impl ArgValues {
         }
     }
 
+    /// Checks that two or three positional arguments were passed.
+    ///
+    /// On error, properly drops all contained values to maintain reference counts.
+    pub fn get_two_three_args(
+        self,
+        name: &str,
+        heap: &mut Heap<impl ResourceTracker>,
+    ) -> RunResult<(Value, Value, Option<Value>)> {
+        match self {
+            Self::Two(a, b) => Ok((a, b, None)),
+            Self::ArgsKargs { args, kwargs } if args.len() == 3 && kwargs.is_empty() => {
+                let [a, b, c] = <[Value; 3]>::try_from(args).expect("length checked above");
+                Ok((a, b, Some(c)))
+            }
+            other => {
+                let count = other.count();
+                other.drop_with_heap(heap);
+                if count < 2 {
+                    Err(ExcType::type_error_at_least(name, 2, count))
+                } else {
+                    Err(ExcType::type_error_at_most(name, 3, count))
+                }
+            }
+        }
+    }
+
+    /// Checks that exactly three positional arguments were passed, returning them as a tuple.
+    ///
+    /// On error, properly drops all contained values to maintain reference counts.
+    pub fn get_three_args(self, name: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<(Value, Value, Value)> {
+        match self {
+            Self::ArgsKargs { args, kwargs } if args.len() == 3 && kwargs.is_empty() => {
+                let [a, b, c] = <[Value; 3]>::try_from(args).expect("length checked above");
+                Ok((a, b, c))
+            }
+            other => {
+                let count = other.count();
+                other.drop_with_heap(heap);
+                Err(ExcType::type_error_arg_count(name, 3, count))
+            }
+        }
+    }
+
     /// Extracts two keyword-only arguments by name.
     ///
     /// Validates that no positional arguments are provided and only the specified
@@ -204,6 +247,47 @@ impl ArgValues {
         }
     }
 
+    /// Builds positional arguments from a Vec, using the compact variants for up to two arguments.
+    #[must_use]
+    pub fn from_vec(mut args: Vec<Value>) -> Self {
+        match args.len() {
+            0 => Self::Empty,
+            1 => Self::One(args.pop().expect("length checked above")),
+            2 => {
+                let b = args.pop().expect("length checked above");
+                let a = args.pop().expect("length checked above");
+                Self::Two(a, b)
+            }
+            _ => Self::ArgsKargs {
+                args,
+                kwargs: KwargsValues::Empty,
+            },
+        }
+    }
+
+    /// Inserts a value as the first positional argument.
+    ///
+    /// Used to pass `self` when calling methods of user-defined classes.
+    #[must_use]
+    pub fn prepend(self, first: Value) -> Self {
+        match self {
+            Self::Empty => Self::One(first),
+            Self::One(a) => Self::Two(first, a),
+            Self::Two(a, b) => Self::ArgsKargs {
+                args: vec![first, a, b],
+                kwargs: KwargsValues::Empty,
+            },
+            Self::Kwargs(kwargs) => Self::ArgsKargs {
+                args: vec![first],
+                kwargs,
+            },
+            Self::ArgsKargs { mut args, kwargs } => {
+                args.insert(0, first);
+                Self::ArgsKargs { args, kwargs }
+            }
+        }
+    }
+
     /// Converts the arguments into a Vec of MontyObjects.
     ///
     /// This is used when passing arguments to external functions.
@@ -261,6 +345,87 @@ impl<T: ResourceTracker> DropWithHeap<T> for ArgValues {
     }
 }
 
+/// Binds positional and keyword arguments to the parameters named `params`, of which the
+/// first `required` must be given.
+///
+/// # Errors
+/// Returns `TypeError` for missing, unexpected or duplicated arguments.
+pub(crate) fn bind_args<const N: usize>(
+    name: &str,
+    args: ArgValues,
+    params: [&str; N],
+    required: usize,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<[Option<Value>; N]> {
+    let bound = bind_args_vec(name, args, &params, required, heap, interns)?;
+    Ok(bound.try_into().expect("one value per parameter"))
+}
+
+/// Like [`bind_args`], for parameters only known at runtime, returning one value per parameter.
+pub(crate) fn bind_args_vec(
+    name: &str,
+    args: ArgValues,
+    params: &[&str],
+    required: usize,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Vec<Option<Value>>> {
+    let (positional, kwargs) = args.into_parts();
+    let mut bound: Vec<Option<Value>> = positional.map(Some).collect();
+    let mut error = None;
+    if bound.len() > params.len() {
+        error = Some(ExcType::type_error_too_many_positional(
+            name,
+            params.len(),
+            bound.len(),
+            0,
+        ));
+    }
+    bound.resize_with(bound.len().max(params.len()), || None);
+
+    for (key, value) in kwargs {
+        let Some(keyword_name) = key.as_either_str(heap) else {
+            key.drop_with_heap(heap);
+            value.drop_with_heap(heap);
+            error.get_or_insert_with(|| ExcType::type_error("keywords must be strings"));
+            continue;
+        };
+        key.drop_with_heap(heap);
+        let key_str = keyword_name.as_str(interns);
+        match params.iter().position(|param| *param == key_str) {
+            Some(index) if bound[index].is_none() => bound[index] = Some(value),
+            Some(_) => {
+                value.drop_with_heap(heap);
+                error.get_or_insert_with(|| ExcType::type_error_duplicate_arg(name, key_str));
+            }
+            None => {
+                value.drop_with_heap(heap);
+                error.get_or_insert_with(|| ExcType::type_error_unexpected_keyword(name, key_str));
+            }
+        }
+    }
+
+    if error.is_none() {
+        let missing: Vec<&str> = params[..required]
+            .iter()
+            .zip(&bound)
+            .filter(|(_, value)| value.is_none())
+            .map(|(param, _)| *param)
+            .collect();
+        if !missing.is_empty() {
+            error = Some(ExcType::type_error_missing_positional_with_names(name, &missing));
+        }
+    }
+    if let Some(error) = error {
+        for value in bound {
+            value.drop_with_heap(heap);
+        }
+        return Err(error);
+    }
+    Ok(bound)
+}
+
 /// Iterator over positional arguments without allocation.
 ///
 /// Supports iterating over `ArgValues::One/Two` without converting to Vec.
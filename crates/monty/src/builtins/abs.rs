@@ -9,13 +9,17 @@ use crate::{
     exception_private::{ExcType, RunResult, SimpleException},
     heap::{Heap, HeapData},
     resource::ResourceTracker,
-    types::{LongInt, PyTrait},
+    types::{
+        LongInt, PyTrait,
+        decimal::{self, UnaryOp},
+    },
     value::Value,
 };
 
 /// Implementation of the abs() builtin function.
 ///
-/// Returns the absolute value of a number. Works with integers, floats, and LongInts.
+/// Returns the absolute value of a number. Works with integers, floats, LongInts, complex numbers,
+/// timedeltas, decimals and fractions.
 /// For `i64::MIN`, which overflows on negation, promotes to LongInt.
 pub fn builtin_abs(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
     let value = args.get_one_arg("abs", heap)?;
@@ -34,17 +38,25 @@ pub fn builtin_abs(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) -> Ru
         }
         Value::Float(f) => Ok(Value::Float(f.abs())),
         Value::Bool(b) => Ok(Value::Int(i64::from(*b))),
-        Value::Ref(id) => {
-            if let HeapData::LongInt(li) = heap.get(*id) {
-                Ok(li.abs().into_value(heap)?)
-            } else {
-                Err(SimpleException::new_msg(
-                    ExcType::TypeError,
-                    format!("bad operand type for abs(): '{}'", value.py_type(heap)),
-                )
-                .into())
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => Ok(li.abs().into_value(heap)?),
+            // abs() of a complex number is its magnitude
+            HeapData::Complex(c) => Ok(Value::Float(c.abs())),
+            HeapData::TimeDelta(delta) => {
+                let delta = delta.abs()?;
+                Ok(delta.into_value(heap)?)
             }
-        }
+            HeapData::Decimal(decimal) => {
+                let decimal = decimal.clone();
+                decimal::unary_op(&decimal, UnaryOp::Abs, heap)
+            }
+            HeapData::Fraction(fraction) => Ok(fraction.abs().into_value(heap)?),
+            _ => Err(SimpleException::new_msg(
+                ExcType::TypeError,
+                format!("bad operand type for abs(): '{}'", value.py_type(heap)),
+            )
+            .into()),
+        },
         _ => Err(SimpleException::new_msg(
             ExcType::TypeError,
             format!("bad operand type for abs(): '{}'", value.py_type(heap)),
@@ -0,0 +1,158 @@
+//! Implementation of the getattr(), hasattr() and setattr() builtin functions.
+//!
+//! Attributes are looked up by `StringId`, so names built at runtime are mapped back to
+//! their interned ids. A name which was never interned can't name an attribute set from
+//! Monty code: `getattr()` and `hasattr()` treat it as missing, `setattr()` rejects it.
+
+use crate::{
+    args::ArgValues,
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult},
+    heap::{DropWithHeap, Heap, HeapData},
+    intern::{Interns, StringId},
+    resource::ResourceTracker,
+    types::{AttrCallResult, PyTrait, class},
+    value::Value,
+};
+
+/// Implementation of the getattr() builtin function.
+///
+/// Returns the named attribute of an object. With a default, returns the default
+/// instead of raising `AttributeError` if the attribute doesn't exist.
+pub fn builtin_getattr(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let (obj, name, default) = args.get_two_three_args("getattr", heap)?;
+    defer_drop!(obj, heap);
+    defer_drop!(name, heap);
+
+    let result = match attr_name_id(name, heap, interns) {
+        Ok(Some(name_id)) => getattr_by_id(obj, name_id, heap, interns),
+        Ok(None) => {
+            let Value::Ref(str_id) = name else {
+                unreachable!("interned attribute names always resolve")
+            };
+            let HeapData::Str(attr) = heap.get(*str_id) else {
+                unreachable!("attribute name was checked to be a string")
+            };
+            Err(missing_attr_error(obj, attr.as_str(), heap, interns))
+        }
+        Err(e) => {
+            default.drop_with_heap(heap);
+            return Err(e);
+        }
+    };
+
+    match (result, default) {
+        (Err(RunError::Exc(exc)), Some(default)) if exc.exc.exc_type() == ExcType::AttributeError => Ok(default),
+        (result, default) => {
+            default.drop_with_heap(heap);
+            result
+        }
+    }
+}
+
+/// Returns the attribute of `obj` named `name`, like `getattr(obj, name)`.
+pub(crate) fn getattr_by_name(
+    obj: &Value,
+    name: &str,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    match interns.find_str(name) {
+        Some(name_id) => getattr_by_id(obj, name_id, heap, interns),
+        None => Err(missing_attr_error(obj, name, heap, interns)),
+    }
+}
+
+/// Returns the attribute of `obj` with an interned name, rejecting attributes provided by the host.
+fn getattr_by_id(
+    obj: &Value,
+    name_id: StringId,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    match obj.py_getattr(name_id, heap, interns)? {
+        AttrCallResult::Value(value) => Ok(value),
+        AttrCallResult::OsCall(_, args) | AttrCallResult::ExternalCall(_, args) => {
+            args.drop_with_heap(heap);
+            Err(ExcType::not_implemented("getattr() of attributes provided by the host is not supported").into())
+        }
+    }
+}
+
+/// Implementation of the hasattr() builtin function.
+///
+/// Returns whether getting the named attribute of an object succeeds without `AttributeError`.
+pub fn builtin_hasattr(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let (obj, name) = args.get_two_args("hasattr", heap)?;
+    defer_drop!(obj, heap);
+    defer_drop!(name, heap);
+
+    let Some(name_id) = attr_name_id(name, heap, interns)? else {
+        return Ok(Value::Bool(false));
+    };
+    match obj.py_getattr(name_id, heap, interns) {
+        Ok(AttrCallResult::Value(value)) => {
+            value.drop_with_heap(heap);
+            Ok(Value::Bool(true))
+        }
+        Ok(AttrCallResult::OsCall(_, args) | AttrCallResult::ExternalCall(_, args)) => {
+            args.drop_with_heap(heap);
+            Ok(Value::Bool(true))
+        }
+        Err(RunError::Exc(exc)) if exc.exc.exc_type() == ExcType::AttributeError => Ok(Value::Bool(false)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Implementation of the setattr() builtin function.
+///
+/// Sets the named attribute of an object, like `obj.name = value`.
+pub fn builtin_setattr(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let (obj, name, value) = args.get_three_args("setattr", heap)?;
+    defer_drop!(obj, heap);
+    defer_drop!(name, heap);
+
+    let name_id = match attr_name_id(name, heap, interns) {
+        Ok(Some(name_id)) => name_id,
+        Ok(None) => {
+            value.drop_with_heap(heap);
+            return Err(ExcType::not_implemented(
+                "setattr() is only supported for attribute names which appear in the code",
+            )
+            .into());
+        }
+        Err(e) => {
+            value.drop_with_heap(heap);
+            return Err(e);
+        }
+    };
+    obj.py_set_attr(name_id, value, heap, interns)?;
+    Ok(Value::None)
+}
+
+/// Returns the `StringId` of an attribute name, or `None` if the name was never interned.
+///
+/// Raises `TypeError` if the name isn't a string, matching CPython.
+fn attr_name_id(name: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Option<StringId>> {
+    match name {
+        Value::InternString(name_id) => return Ok(Some(*name_id)),
+        Value::Ref(id) => {
+            if let HeapData::Str(s) = heap.get(*id) {
+                return Ok(interns.find_str(s.as_str()));
+            }
+        }
+        _ => {}
+    }
+    Err(ExcType::type_error(format!(
+        "attribute name must be string, not '{}'",
+        name.py_type(heap)
+    )))
+}
+
+/// Creates the `AttributeError` for a name which was never interned.
+fn missing_attr_error(obj: &Value, attr: &str, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunError {
+    match obj {
+        Value::Ref(heap_id) => class::attr_error_str(*heap_id, attr, heap, interns),
+        _ => ExcType::attribute_error(obj.py_type(heap), attr),
+    }
+}
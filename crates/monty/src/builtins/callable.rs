@@ -0,0 +1,40 @@
+//! Implementation of the callable() builtin function.
+
+use crate::{
+    args::ArgValues,
+    exception_private::RunResult,
+    heap::{DropWithHeap, Heap, HeapData},
+    resource::ResourceTracker,
+    value::Value,
+};
+
+/// Implementation of the callable() builtin function.
+///
+/// Returns whether the argument can be called: functions, builtins, classes and bound methods.
+pub fn builtin_callable(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let value = args.get_one_arg("callable", heap)?;
+    let result = is_callable(&value, heap);
+    value.drop_with_heap(heap);
+    Ok(Value::Bool(result))
+}
+
+/// Returns whether a value can be called.
+///
+/// Instances of user-defined classes are never callable, since `__call__` isn't supported.
+pub(crate) fn is_callable(value: &Value, heap: &Heap<impl ResourceTracker>) -> bool {
+    match value {
+        Value::Builtin(_) | Value::ModuleFunction(_) | Value::DefFunction(_) | Value::ExtFunction(_) => true,
+        Value::Ref(id) => matches!(
+            heap.get(*id),
+            HeapData::Closure(..)
+                | HeapData::FunctionDefaults(..)
+                | HeapData::BoundMethod(_)
+                | HeapData::Partial(_)
+                | HeapData::LruCacheWrapper(_)
+                | HeapData::CmpToKey(_)
+                | HeapData::ClassObject(_)
+                | HeapData::NamedTupleType(_)
+        ),
+        _ => false,
+    }
+}
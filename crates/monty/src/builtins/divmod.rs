@@ -8,9 +8,10 @@ use crate::{
     args::ArgValues,
     defer_drop,
     exception_private::{ExcType, RunResult, SimpleException},
-    heap::{Heap, HeapData},
+    expressions::Operator,
+    heap::{DropWithHeap, Heap, HeapData},
     resource::ResourceTracker,
-    types::{LongInt, PyTrait, allocate_tuple},
+    types::{LongInt, PyTrait, allocate_tuple, decimal, fraction},
     value::Value,
 };
 
@@ -25,6 +26,9 @@ pub fn builtin_divmod(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) ->
     defer_drop!(a, heap);
     defer_drop!(b, heap);
 
+    if let Some(result) = exact_divmod(a, b, heap)? {
+        return Ok(result);
+    }
     match (a, b) {
         (Value::Int(x), Value::Int(y)) => {
             if *y == 0 {
@@ -149,6 +153,34 @@ pub fn builtin_divmod(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) ->
     }
 }
 
+/// Computes `divmod()` as `(a // b, a % b)` when either operand is a decimal or a fraction.
+///
+/// Returns `Ok(None)` for other operands.
+fn exact_divmod(a: &Value, b: &Value, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Option<Value>> {
+    let quotient = match decimal::binary_op(a, b, &Operator::FloorDiv, heap)? {
+        Some(quotient) => quotient,
+        None => match fraction::binary_op(a, b, &Operator::FloorDiv, heap)? {
+            Some(quotient) => quotient,
+            None => return Ok(None),
+        },
+    };
+    let remainder = match decimal::binary_op(a, b, &Operator::Mod, heap) {
+        Ok(None) => fraction::binary_op(a, b, &Operator::Mod, heap),
+        result => result,
+    };
+    match remainder {
+        Ok(Some(remainder)) => Ok(Some(allocate_tuple(smallvec![quotient, remainder], heap)?)),
+        Ok(None) => {
+            quotient.drop_with_heap(heap);
+            Ok(None)
+        }
+        Err(e) => {
+            quotient.drop_with_heap(heap);
+            Err(e)
+        }
+    }
+}
+
 /// Computes Python-style floor division and modulo.
 ///
 /// Python's division rounds toward negative infinity (floor division),
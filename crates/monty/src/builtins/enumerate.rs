@@ -27,20 +27,7 @@ pub fn builtin_enumerate(
     defer_drop_mut!(iter, heap);
     defer_drop!(start, heap);
 
-    // Get start index (default 0)
-    let mut index: i64 = match start {
-        Some(Value::Int(n)) => *n,
-        Some(Value::Bool(b)) => i64::from(*b),
-        Some(v) => {
-            let type_name = v.py_type(heap);
-            return Err(SimpleException::new_msg(
-                ExcType::TypeError,
-                format!("'{type_name}' object cannot be interpreted as an integer"),
-            )
-            .into());
-        }
-        None => 0,
-    };
+    let mut index = enumerate_start(start.as_ref(), heap)?;
 
     let mut result: Vec<Value> = Vec::new();
 
@@ -54,3 +41,20 @@ pub fn builtin_enumerate(
     let heap_id = heap.allocate(HeapData::List(List::new(result)))?;
     Ok(Value::Ref(heap_id))
 }
+
+/// Returns the index of the first value of `enumerate()`, given its `start` argument (default 0).
+pub(crate) fn enumerate_start(start: Option<&Value>, heap: &Heap<impl ResourceTracker>) -> RunResult<i64> {
+    match start {
+        Some(Value::Int(n)) => Ok(*n),
+        Some(Value::Bool(b)) => Ok(i64::from(*b)),
+        Some(v) => {
+            let type_name = v.py_type(heap);
+            Err(SimpleException::new_msg(
+                ExcType::TypeError,
+                format!("'{type_name}' object cannot be interpreted as an integer"),
+            )
+            .into())
+        }
+        None => Ok(0),
+    }
+}
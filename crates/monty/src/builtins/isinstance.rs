@@ -5,9 +5,9 @@ use crate::{
     args::ArgValues,
     defer_drop,
     exception_private::{ExcType, RunResult},
-    heap::{Heap, HeapData},
+    heap::{Heap, HeapData, HeapId},
     resource::ResourceTracker,
-    types::{PyTrait, Type},
+    types::{PyTrait, Type, class::is_subclass},
     value::Value,
 };
 
@@ -20,8 +20,15 @@ pub fn builtin_isinstance(heap: &mut Heap<impl ResourceTracker>, args: ArgValues
     defer_drop!(classinfo, heap);
 
     let obj_type = obj.py_type(heap);
+    let obj_class = match obj {
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Instance(inst) => Some(inst.class_id()),
+            _ => None,
+        },
+        _ => None,
+    };
 
-    match isinstance_check(obj_type, classinfo, heap) {
+    match isinstance_check(obj, obj_type, obj_class, classinfo, heap) {
         Ok(result) => Ok(Value::Bool(result)),
         Err(()) => Err(ExcType::isinstance_arg2_error()),
     }
@@ -29,6 +36,8 @@ pub fn builtin_isinstance(heap: &mut Heap<impl ResourceTracker>, args: ArgValues
 
 /// Recursively checks if obj_type matches classinfo for isinstance().
 ///
+/// `obj_class` is the class of the object if it is an instance of a user-defined class.
+///
 /// Returns `Ok(true)` if the type matches, `Ok(false)` if it doesn't,
 /// or `Err(())` if classinfo is invalid (not a type or tuple of types).
 ///
@@ -37,7 +46,15 @@ pub fn builtin_isinstance(heap: &mut Heap<impl ResourceTracker>, args: ArgValues
 /// - Exception types: `isinstance(err, ValueError)`
 /// - Exception hierarchy: `isinstance(err, LookupError)` for KeyError/IndexError
 /// - Nested tuples: `isinstance(x, (int, (str, bytes)))`
-fn isinstance_check(obj_type: Type, classinfo: &Value, heap: &Heap<impl ResourceTracker>) -> Result<bool, ()> {
+/// - User-defined classes, including base classes: `isinstance(dog, Animal)`
+/// - Named tuple types from `collections.namedtuple()`: `isinstance(p, Point)`
+fn isinstance_check(
+    obj: &Value,
+    obj_type: Type,
+    obj_class: Option<HeapId>,
+    classinfo: &Value,
+    heap: &Heap<impl ResourceTracker>,
+) -> Result<bool, ()> {
     match classinfo {
         // Single type: isinstance(x, int)
         Value::Builtin(Builtins::Type(t)) => Ok(obj_type.is_instance_of(*t)),
@@ -49,18 +66,24 @@ fn isinstance_check(obj_type: Type, classinfo: &Value, heap: &Heap<impl Resource
         }
 
         // Tuple of types (possibly nested): isinstance(x, (int, (str, bytes)))
-        Value::Ref(id) => {
-            if let HeapData::Tuple(tuple) = heap.get(*id) {
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Tuple(tuple) => {
                 for v in tuple.as_vec() {
-                    if isinstance_check(obj_type, v, heap)? {
+                    if isinstance_check(obj, obj_type, obj_class, v, heap)? {
                         return Ok(true);
                     }
                 }
                 Ok(false)
-            } else {
-                Err(()) // Not a tuple - invalid
             }
-        }
+            // User-defined class: isinstance(dog, Animal)
+            HeapData::ClassObject(_) => Ok(obj_class.is_some_and(|class_id| is_subclass(class_id, *id, heap))),
+            // Named tuple type: isinstance(p, Point)
+            HeapData::NamedTupleType(t) => Ok(match obj {
+                Value::Ref(obj_id) => matches!(heap.get(*obj_id), HeapData::NamedTuple(nt) if t.is_type_of(nt)),
+                _ => false,
+            }),
+            _ => Err(()), // Not a tuple or class - invalid
+        },
         _ => Err(()), // Invalid classinfo
     }
 }
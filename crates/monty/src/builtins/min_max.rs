@@ -4,12 +4,11 @@ use std::cmp::Ordering;
 
 use crate::{
     args::ArgValues,
-    defer_drop, defer_drop_mut,
     exception_private::{ExcType, RunError, RunResult, SimpleException},
-    heap::{Heap, HeapGuard},
+    heap::{DropWithHeap, Heap},
     intern::Interns,
     resource::ResourceTracker,
-    types::{MontyIter, PyTrait},
+    types::{MontyIter, PyTrait, allocate_tuple},
     value::Value,
 };
 
@@ -17,7 +16,7 @@ use crate::{
 ///
 /// Returns the smallest item in an iterable or the smallest of two or more arguments.
 /// Supports two forms:
-/// - `min(iterable)` - returns smallest item from iterable
+/// - `min(iterable, *, default)` - returns smallest item from iterable
 /// - `min(arg1, arg2, ...)` - returns smallest of the arguments
 pub fn builtin_min(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
     builtin_min_max(heap, args, interns, true)
@@ -27,7 +26,7 @@ pub fn builtin_min(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, inter
 ///
 /// Returns the largest item in an iterable or the largest of two or more arguments.
 /// Supports two forms:
-/// - `max(iterable)` - returns largest item from iterable
+/// - `max(iterable, *, default)` - returns largest item from iterable
 /// - `max(arg1, arg2, ...)` - returns largest of the arguments
 pub fn builtin_max(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
     builtin_min_max(heap, args, interns, false)
@@ -36,91 +35,166 @@ pub fn builtin_max(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, inter
 /// Shared implementation for min() and max().
 ///
 /// When `is_min` is true, returns the minimum; otherwise returns the maximum.
+/// Key functions are called by the VM, which handles `min()` and `max()` calls with
+/// keyword arguments itself, so only `key=None` is supported here.
 fn builtin_min_max(
     heap: &mut Heap<impl ResourceTracker>,
     args: ArgValues,
     interns: &Interns,
     is_min: bool,
 ) -> RunResult<Value> {
+    let (iterable, key, default) = min_max_args(heap, args, interns, is_min)?;
+    if let Some(key) = key {
+        key.drop_with_heap(heap);
+        iterable.drop_with_heap(heap);
+        default.drop_with_heap(heap);
+        return Err(RunError::internal(
+            "min() and max() with a key function must be called through the VM",
+        ));
+    }
+    let mut iter = match MontyIter::new(iterable, heap, interns) {
+        Ok(iter) => iter,
+        Err(e) => {
+            default.drop_with_heap(heap);
+            return Err(e);
+        }
+    };
+    let best = select_min_max(&mut iter, heap, interns, is_min);
+    iter.drop_with_heap(heap);
+    match best {
+        Ok(Some(best)) => {
+            default.drop_with_heap(heap);
+            Ok(best)
+        }
+        Ok(None) => min_max_empty(default, is_min),
+        Err(e) => {
+            default.drop_with_heap(heap);
+            Err(e)
+        }
+    }
+}
+
+/// Parses the arguments of `min()` or `max()`.
+///
+/// Returns the iterable to choose from (a tuple of the arguments if there are several
+/// positional arguments), the key function (`None` if not given or `None`) and the default.
+pub(crate) fn min_max_args(
+    heap: &mut Heap<impl ResourceTracker>,
+    args: ArgValues,
+    interns: &Interns,
+    is_min: bool,
+) -> RunResult<(Value, Option<Value>, Option<Value>)> {
     let func_name = if is_min { "min" } else { "max" };
     let (positional, kwargs) = args.into_parts();
-    defer_drop_mut!(positional, heap);
-    let kwargs = kwargs.into_iter();
-    defer_drop!(kwargs, heap);
+    let mut positional: Vec<Value> = positional.collect();
+
+    let (key, default) =
+        match ArgValues::Kwargs(kwargs).extract_two_kwargs_only(func_name, "key", "default", heap, interns) {
+            Ok(kwargs) => kwargs,
+            Err(e) => {
+                positional.drop_with_heap(heap);
+                return Err(e);
+            }
+        };
+    let key = key.filter(|key| !matches!(key, Value::None));
 
-    // Check for unsupported kwargs (key, default not yet implemented)
-    if kwargs.len() > 0 {
-        return Err(SimpleException::new_msg(
+    let error = match positional.len() {
+        0 => SimpleException::new_msg(
             ExcType::TypeError,
-            format!("{func_name}() does not support keyword arguments yet"),
-        )
-        .into());
-    }
-
-    let Some(first_arg) = positional.next() else {
-        return Err(SimpleException::new_msg(
+            format!("{func_name} expected at least 1 argument, got 0"),
+        ),
+        1 => {
+            let iterable = positional.pop().expect("length checked above");
+            return Ok((iterable, key, default));
+        }
+        _ if default.is_some() => SimpleException::new_msg(
             ExcType::TypeError,
-            format!("{func_name}() expected at least 1 argument, got 0"),
-        )
-        .into());
+            format!("Cannot specify a default for {func_name}() with multiple positional arguments"),
+        ),
+        _ => match allocate_tuple(positional.into(), heap) {
+            Ok(args) => return Ok((args, key, None)),
+            Err(e) => {
+                key.drop_with_heap(heap);
+                return Err(e.into());
+            }
+        },
     };
+    positional.drop_with_heap(heap);
+    key.drop_with_heap(heap);
+    default.drop_with_heap(heap);
+    Err(error.into())
+}
 
-    // decide what to do based on remaining arguments
-    if positional.len() == 0 {
-        // Single argument: iterate over it
-        let iter = MontyIter::new(first_arg, heap, interns)?;
-        defer_drop_mut!(iter, heap);
-
-        let Some(result) = iter.for_next(heap, interns)? else {
-            return Err(SimpleException::new_msg(
-                ExcType::ValueError,
-                format!("{func_name}() iterable argument is empty"),
-            )
-            .into());
+/// Returns the smallest (if `is_min`) or largest of the remaining items of `iter`, or
+/// `None` if there are none.
+///
+/// Only the best item so far is kept, and the first of several equal items is chosen.
+fn select_min_max(
+    iter: &mut MontyIter,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+    is_min: bool,
+) -> RunResult<Option<Value>> {
+    let Some(mut best) = iter.for_next(heap, interns)? else {
+        return Ok(None);
+    };
+    loop {
+        let item = match iter.for_next(heap, interns) {
+            Ok(Some(item)) => item,
+            Ok(None) => return Ok(Some(best)),
+            Err(e) => {
+                best.drop_with_heap(heap);
+                return Err(e);
+            }
         };
-
-        let mut result_guard = HeapGuard::new(result, heap);
-        let (result, heap) = result_guard.as_parts_mut();
-
-        while let Some(item) = iter.for_next(heap, interns)? {
-            defer_drop_mut!(item, heap);
-
-            let Some(ordering) = result.py_cmp(item, heap, interns) else {
-                return Err(ord_not_supported(result, item, heap));
-            };
-
-            if (is_min && ordering == Ordering::Greater) || (!is_min && ordering == Ordering::Less) {
-                std::mem::swap(result, item);
+        match min_max_beats(&item, &best, heap, interns, is_min) {
+            Ok(true) => std::mem::replace(&mut best, item).drop_with_heap(heap),
+            Ok(false) => item.drop_with_heap(heap),
+            Err(e) => {
+                item.drop_with_heap(heap);
+                best.drop_with_heap(heap);
+                return Err(e);
             }
         }
+    }
+}
 
-        Ok(result_guard.into_inner())
-    } else {
-        // Multiple arguments: compare them directly
-        let mut result_guard = HeapGuard::new(first_arg, heap);
-        let (result, heap) = result_guard.as_parts_mut();
-
-        for item in positional {
-            defer_drop_mut!(item, heap);
-
-            let Some(ordering) = result.py_cmp(item, heap, interns) else {
-                return Err(ord_not_supported(result, item, heap));
-            };
-
-            if (is_min && ordering == Ordering::Greater) || (!is_min && ordering == Ordering::Less) {
-                std::mem::swap(result, item);
-            }
-        }
+/// Returns whether `item` replaces `best` as the result of `min()` (if `is_min`) or `max()`.
+///
+/// Like CPython, `min()` asks whether `item < best` and `max()` whether `item > best`,
+/// so the first of equal items wins.
+pub(crate) fn min_max_beats(
+    item: &Value,
+    best: &Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+    is_min: bool,
+) -> RunResult<bool> {
+    let Some(ordering) = item.py_cmp(best, heap, interns) else {
+        return Err(ord_not_supported(item, best, heap, is_min));
+    };
+    Ok(ordering == if is_min { Ordering::Less } else { Ordering::Greater })
+}
 
-        Ok(result_guard.into_inner())
+/// Returns the result of `min()` or `max()` without any items: `default`, or a
+/// `ValueError` if there's no default.
+pub(crate) fn min_max_empty(default: Option<Value>, is_min: bool) -> RunResult<Value> {
+    match default {
+        Some(default) => Ok(default),
+        None => Err(SimpleException::new_msg(
+            ExcType::ValueError,
+            format!("{}() iterable argument is empty", if is_min { "min" } else { "max" }),
+        )
+        .into()),
     }
 }
 
 #[cold]
-fn ord_not_supported(left: &Value, right: &Value, heap: &Heap<impl ResourceTracker>) -> RunError {
+fn ord_not_supported(left: &Value, right: &Value, heap: &Heap<impl ResourceTracker>, is_min: bool) -> RunError {
     let left_type = left.py_type(heap);
     let right_type = right.py_type(heap);
+    let op = if is_min { '<' } else { '>' };
     ExcType::type_error(format!(
-        "'<' not supported between instances of '{left_type}' and '{right_type}'"
+        "'{op}' not supported between instances of '{left_type}' and '{right_type}'"
     ))
 }
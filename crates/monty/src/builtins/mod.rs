@@ -6,7 +6,9 @@
 mod abs;
 mod all;
 mod any;
+mod attrs; // getattr, hasattr and setattr share implementation
 mod bin;
+mod callable;
 mod chr;
 mod divmod;
 mod enumerate;
@@ -35,8 +37,8 @@ use strum::{Display, EnumString, FromRepr, IntoStaticStr};
 
 use crate::{
     args::ArgValues,
-    exception_private::{ExcType, RunResult},
-    heap::Heap,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap},
     intern::Interns,
     io::PrintWriter,
     resource::ResourceTracker,
@@ -44,6 +46,12 @@ use crate::{
     value::Value,
 };
 
+pub(crate) use attrs::getattr_by_name;
+pub(crate) use callable::is_callable;
+pub(crate) use enumerate::enumerate_start;
+pub(crate) use min_max::{min_max_args, min_max_beats, min_max_empty};
+pub(crate) use sorted::{sort_values, sorted_args};
+
 /// Enumerates every interpreter-native Python builtins
 ///
 /// Uses strum derives for automatic `Display`, `FromStr`, and `AsRef<str>` implementations.
@@ -85,6 +93,10 @@ impl Builtins {
         match self {
             Self::Function(b) => write!(f, "<built-in function {b}>"),
             Self::ExcType(e) => write!(f, "<class '{e}'>"),
+            Self::Type(t @ (Type::Enum | Type::IntEnum | Type::StrEnum)) => {
+                let name = t.to_string();
+                write!(f, "<enum '{}'>", name.trim_start_matches("enum."))
+            }
             Self::Type(t) => write!(f, "<class '{t}'>"),
         }
     }
@@ -153,7 +165,7 @@ pub enum BuiltinsFunctions {
     // Breakpoint,
     // bytearray - handled by Type enum
     // bytes - handled by Type enum
-    // Callable,
+    Callable,
     Chr,
     // Classmethod,
     // Compile,
@@ -165,13 +177,13 @@ pub enum BuiltinsFunctions {
     Enumerate,
     // Eval,
     // Exec,
-    // Filter,
+    Filter,
     // float - handled by Type enum
     // Format,
     // frozenset - handled by Type enum
-    // Getattr,
+    Getattr,
     // Globals,
-    // Hasattr,
+    Hasattr,
     Hash,
     // Help,
     Hex,
@@ -184,7 +196,7 @@ pub enum BuiltinsFunctions {
     Len,
     // list - handled by Type enum
     // Locals,
-    // Map,
+    Map,
     Max,
     // memoryview - handled by Type enum
     Min,
@@ -201,13 +213,13 @@ pub enum BuiltinsFunctions {
     Reversed,
     Round,
     // set - handled by Type enum
-    // Setattr,
+    Setattr,
     // Slice,
     Sorted,
     // Staticmethod,
     // str - handled by Type enum
     Sum,
-    // Super,
+    Super,
     // tuple - handled by Type enum
     Type,
     // Vars,
@@ -232,14 +244,23 @@ impl BuiltinsFunctions {
             Self::All => all::builtin_all(heap, args, interns),
             Self::Any => any::builtin_any(heap, args, interns),
             Self::Bin => bin::builtin_bin(heap, args),
+            Self::Callable => callable::builtin_callable(heap, args),
             Self::Chr => chr::builtin_chr(heap, args),
             Self::Divmod => divmod::builtin_divmod(heap, args),
             Self::Enumerate => enumerate::builtin_enumerate(heap, args, interns),
+            Self::Getattr => attrs::builtin_getattr(heap, args, interns),
+            Self::Hasattr => attrs::builtin_hasattr(heap, args, interns),
             Self::Hash => hash::builtin_hash(heap, args, interns),
             Self::Hex => hex::builtin_hex(heap, args),
             Self::Id => id::builtin_id(heap, args),
             Self::Isinstance => isinstance::builtin_isinstance(heap, args),
             Self::Len => len::builtin_len(heap, args, interns),
+            // `map()` and `filter()` return iterators which call functions, so the VM
+            // intercepts them before we get here
+            Self::Filter | Self::Map => {
+                args.drop_with_heap(heap);
+                Err(RunError::internal("map() and filter() must be called through the VM"))
+            }
             Self::Max => min_max::builtin_max(heap, args, interns),
             Self::Min => min_max::builtin_min(heap, args, interns),
             Self::Next => next::builtin_next(heap, args, interns),
@@ -250,8 +271,14 @@ impl BuiltinsFunctions {
             Self::Repr => repr::builtin_repr(heap, args, interns),
             Self::Reversed => reversed::builtin_reversed(heap, args, interns),
             Self::Round => round::builtin_round(heap, args),
+            Self::Setattr => attrs::builtin_setattr(heap, args, interns),
             Self::Sorted => sorted::builtin_sorted(heap, args, interns),
             Self::Sum => sum::builtin_sum(heap, args, interns),
+            // `super()` needs the calling frame, so the VM intercepts it before we get here
+            Self::Super => {
+                args.drop_with_heap(heap);
+                Err(SimpleException::new_msg(ExcType::RuntimeError, "super(): no arguments").into())
+            }
             Self::Type => type_::builtin_type(heap, args),
             Self::Zip => zip::builtin_zip(heap, args, interns),
         }
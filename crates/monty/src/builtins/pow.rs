@@ -6,9 +6,10 @@ use num_traits::{Signed, ToPrimitive, Zero};
 use crate::{
     args::ArgValues,
     exception_private::{ExcType, RunError, RunResult, SimpleException},
+    expressions::Operator,
     heap::{Heap, HeapData},
     resource::{LARGE_RESULT_THRESHOLD, ResourceTracker},
-    types::{LongInt, PyTrait},
+    types::{LongInt, PyTrait, decimal, fraction},
     value::Value,
 };
 
@@ -145,6 +146,12 @@ fn checked_pow_i64(mut base: i64, mut exp: u32) -> Option<i64> {
 ///
 /// On overflow, promotes to LongInt instead of returning an error.
 fn two_arg_pow(base: &Value, exp: &Value, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    if let Some(value) = decimal::binary_op(base, exp, &Operator::Pow, heap)? {
+        return Ok(value);
+    }
+    if let Some(value) = fraction::binary_op(base, exp, &Operator::Pow, heap)? {
+        return Ok(value);
+    }
     match (base, exp) {
         (Value::Int(b), Value::Int(e)) => int_pow_int(*b, *e, heap),
         (Value::Int(b), Value::Ref(id)) => {
@@ -5,7 +5,8 @@ use crate::{
     exception_private::{ExcType, RunError, RunResult, SimpleException},
     heap::{Heap, HeapData},
     intern::Interns,
-    io::PrintWriter,
+    io::{PrintStream, PrintWriter},
+    modules::sys,
     resource::ResourceTracker,
     types::PyTrait,
     value::Value,
@@ -16,9 +17,8 @@ use crate::{
 /// Supports the following keyword arguments:
 /// - `sep`: separator between values (default: " ")
 /// - `end`: string appended after the last value (default: "\n")
+/// - `file`: `sys.stdout` or `sys.stderr` (default: `sys.stdout`)
 /// - `flush`: whether to flush the stream (accepted but ignored)
-///
-/// The `file` kwarg is not supported.
 pub fn builtin_print(
     heap: &mut Heap<impl ResourceTracker>,
     args: ArgValues,
@@ -29,7 +29,7 @@ pub fn builtin_print(
     let (positional, kwargs) = args.into_parts();
 
     // Extract kwargs first, consuming them - this handles cleanup on error
-    let (sep, end) = match extract_print_kwargs(kwargs, heap, interns) {
+    let (sep, end, stream) = match extract_print_kwargs(kwargs, heap, interns) {
         Ok(se) => se,
         Err(err) => {
             for value in positional {
@@ -45,35 +45,36 @@ pub fn builtin_print(
         if first {
             first = false;
         } else if let Some(sep) = &sep {
-            print.stdout_write(sep.as_str().into())?;
+            stream.write(print, sep.as_str().into())?;
         } else {
-            print.stdout_push(' ')?;
+            stream.push(print, ' ')?;
         }
-        print.stdout_write(value.py_str(heap, interns))?;
+        stream.write(print, value.py_str(heap, interns))?;
         value.drop_with_heap(heap);
     }
 
     // Append end string
     if let Some(end) = end {
-        print.stdout_write(end.into())?;
+        stream.write(print, end.into())?;
     } else {
-        print.stdout_push('\n')?;
+        stream.push(print, '\n')?;
     }
 
     Ok(Value::None)
 }
 
-/// Extracts sep and end kwargs from print() arguments.
+/// Extracts sep, end and file kwargs from print() arguments.
 ///
 /// Consumes the kwargs, dropping all values after extraction.
-/// Returns (sep, end, error) where error is Some if a kwarg error occurred.
+/// Returns (sep, end, stream), or the first kwarg error that occurred.
 fn extract_print_kwargs(
     kwargs: KwargsValues,
     heap: &mut Heap<impl ResourceTracker>,
     interns: &Interns,
-) -> RunResult<(Option<String>, Option<String>)> {
+) -> RunResult<(Option<String>, Option<String>, PrintStream)> {
     let mut sep: Option<String> = None;
     let mut end: Option<String> = None;
+    let mut stream = PrintStream::Stdout;
     let mut error: Option<RunError> = None;
 
     for (key, value) in kwargs {
@@ -103,9 +104,15 @@ fn extract_print_kwargs(
             },
             "flush" => {} // Accepted but ignored (we don't buffer output)
             "file" => {
-                error = Some(
-                    SimpleException::new_msg(ExcType::TypeError, "print() 'file' argument is not supported").into(),
-                );
+                let file_stream = match &value {
+                    Value::None => Some(PrintStream::Stdout),
+                    Value::Marker(marker) => sys::marker_stream(*marker),
+                    _ => None,
+                };
+                match file_stream {
+                    Some(file_stream) => stream = file_stream,
+                    None => error = Some(ExcType::attribute_error(value.py_type(heap), "write")),
+                }
             }
             _ => {
                 error = Some(ExcType::type_error_unexpected_keyword("print", key_str));
@@ -118,7 +125,7 @@ fn extract_print_kwargs(
     if let Some(error) = error {
         Err(error)
     } else {
-        Ok((sep, end))
+        Ok((sep, end, stream))
     }
 }
 
@@ -2,11 +2,11 @@
 
 use crate::{
     args::ArgValues,
-    exception_private::RunResult,
+    exception_private::{ExcType, RunResult},
     heap::{Heap, HeapData},
     intern::Interns,
     resource::ResourceTracker,
-    types::{List, MontyIter},
+    types::{List, MontyIter, PyTrait},
     value::Value,
 };
 
@@ -14,8 +14,16 @@ use crate::{
 ///
 /// Returns a list with elements in reverse order.
 /// Note: In Python this returns an iterator, but we return a list for simplicity.
+/// Iterators and generators aren't sequences, so they can't be reversed.
 pub fn builtin_reversed(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
     let value = args.get_one_arg("reversed", heap)?;
+    if let Value::Ref(id) = &value
+        && matches!(heap.get(*id), HeapData::Iter(_) | HeapData::Generator(_))
+    {
+        let err = ExcType::type_error(format!("'{}' object is not reversible", value.py_type(heap)));
+        value.drop_with_heap(heap);
+        return Err(err);
+    }
 
     // Collect all items
     let mut iter = MontyIter::new(value, heap, interns)?;
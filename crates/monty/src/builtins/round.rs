@@ -4,9 +4,9 @@ use crate::{
     args::ArgValues,
     defer_drop,
     exception_private::{ExcType, RunResult, SimpleException},
-    heap::Heap,
+    heap::{Heap, HeapData},
     resource::ResourceTracker,
-    types::PyTrait,
+    types::{PyTrait, decimal},
     value::Value,
 };
 
@@ -34,6 +34,9 @@ pub fn builtin_round(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) ->
         Some(Value::None) => None,
         Some(Value::Int(n)) => Some(*n),
         Some(Value::Bool(b)) => Some(i64::from(*b)),
+        Some(_) if decimal::is_decimal(number, heap) => {
+            return Err(ExcType::type_error("optional arg must be an integer"));
+        }
         Some(v) => {
             let type_name = v.py_type(heap);
             return Err(SimpleException::new_msg(
@@ -45,6 +48,14 @@ pub fn builtin_round(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) ->
         None => None,
     };
 
+    if let Value::Ref(id) = number {
+        match heap.get(*id) {
+            HeapData::Decimal(decimal) => return decimal.clone().py_round(digits, heap),
+            HeapData::Fraction(fraction) => return fraction.clone().py_round(digits, heap),
+            _ => {}
+        }
+    }
+
     match number {
         Value::Int(n) => {
             if let Some(d) = digits {
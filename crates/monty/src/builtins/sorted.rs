@@ -4,8 +4,8 @@ use std::cmp::Ordering;
 
 use crate::{
     args::ArgValues,
-    exception_private::{ExcType, RunResult, SimpleException},
-    heap::{Heap, HeapData},
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData},
     intern::Interns,
     resource::ResourceTracker,
     types::{List, MontyIter, PyTrait},
@@ -15,68 +15,122 @@ use crate::{
 /// Implementation of the sorted() builtin function.
 ///
 /// Returns a new sorted list from the items in an iterable.
-/// Note: Currently does not support key or reverse arguments.
+/// Key functions are called by the VM, which handles `sorted()` calls with keyword
+/// arguments itself, so only `key=None` is supported here.
 pub fn builtin_sorted(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
-    let (mut positional, kwargs) = args.into_parts();
-
-    // Check for unsupported kwargs
-    if !kwargs.is_empty() {
-        for (k, v) in kwargs {
-            k.drop_with_heap(heap);
-            v.drop_with_heap(heap);
-        }
-        for v in positional {
-            v.drop_with_heap(heap);
-        }
-        return Err(
-            SimpleException::new_msg(ExcType::TypeError, "sorted() does not support keyword arguments yet").into(),
-        );
+    let (mut items, key, reverse) = sorted_args(heap, args, interns)?;
+    if let Some(key) = key {
+        key.drop_with_heap(heap);
+        items.drop_with_heap(heap);
+        return Err(RunError::internal(
+            "sorted() with a key function must be called through the VM",
+        ));
+    }
+    if let Err(e) = sort_values(&mut items, None, reverse, heap, interns) {
+        items.drop_with_heap(heap);
+        return Err(e);
     }
+    let heap_id = heap.allocate(HeapData::List(List::new(items)))?;
+    Ok(Value::Ref(heap_id))
+}
+
+/// Parses the arguments of `sorted(iterable, /, *, key=None, reverse=False)`.
+///
+/// Returns the items of the iterable, the key function (`None` if not given or `None`)
+/// and whether to sort in descending order.
+pub(crate) fn sorted_args(
+    heap: &mut Heap<impl ResourceTracker>,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<(Vec<Value>, Option<Value>, bool)> {
+    let (mut positional, kwargs) = args.into_parts();
 
     let positional_len = positional.len();
     if positional_len != 1 {
-        for v in positional {
-            v.drop_with_heap(heap);
-        }
+        positional.drop_with_heap(heap);
+        kwargs.drop_with_heap(heap);
         return Err(SimpleException::new_msg(
             ExcType::TypeError,
             format!("sorted expected 1 argument, got {positional_len}"),
         )
         .into());
     }
+    let iterable = positional.next().expect("length checked above");
 
-    let iterable = positional.next().unwrap();
-    let mut iter = MontyIter::new(iterable, heap, interns)?;
-    let mut items: Vec<_> = iter.collect(heap, interns)?;
+    // `sorted()` reports bad keywords as `sort()`, like CPython
+    let (key, reverse) =
+        match ArgValues::Kwargs(kwargs).extract_two_kwargs_only("sort", "key", "reverse", heap, interns) {
+            Ok(kwargs) => kwargs,
+            Err(e) => {
+                iterable.drop_with_heap(heap);
+                return Err(e);
+            }
+        };
+    let reverse = reverse.is_some_and(|reverse| {
+        let flag = reverse.py_bool(heap, interns);
+        reverse.drop_with_heap(heap);
+        flag
+    });
+    let key = key.filter(|key| !matches!(key, Value::None));
+
+    let mut iter = match MontyIter::new(iterable, heap, interns) {
+        Ok(iter) => iter,
+        Err(e) => {
+            key.drop_with_heap(heap);
+            return Err(e);
+        }
+    };
+    let items: RunResult<Vec<Value>> = iter.collect(heap, interns);
     iter.drop_with_heap(heap);
+    match items {
+        Ok(items) => Ok((items, key, reverse)),
+        Err(e) => {
+            key.drop_with_heap(heap);
+            Err(e)
+        }
+    }
+}
 
-    // Sort using insertion sort (simple, stable, works with py_cmp)
-    // For small lists this is fine; for large lists we'd want a better algorithm
-    for i in 1..items.len() {
-        let mut j = i;
-        while j > 0 {
-            match items[j - 1].py_cmp(&items[j], heap, interns) {
-                Some(Ordering::Greater) => {
-                    items.swap(j - 1, j);
-                    j -= 1;
-                }
-                Some(_) => break,
-                None => {
-                    let left_type = items[j - 1].py_type(heap);
-                    let right_type = items[j].py_type(heap);
-                    for item in items {
-                        item.drop_with_heap(heap);
-                    }
-                    return Err(SimpleException::new_msg(
-                        ExcType::TypeError,
-                        format!("'<' not supported between instances of '{left_type}' and '{right_type}'"),
-                    )
-                    .into());
-                }
-            }
+/// Sorts values in place, comparing `keys` instead of the values themselves if given.
+///
+/// The sort is stable, also when `reverse` is true: equal values keep their order.
+/// On error (values which can't be compared), `items` is left unchanged.
+pub(crate) fn sort_values(
+    items: &mut Vec<Value>,
+    keys: Option<&[Value]>,
+    reverse: bool,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<()> {
+    let compared: &[Value] = keys.unwrap_or(items.as_slice());
+    let mut indices: Vec<usize> = (0..compared.len()).collect();
+    let mut sort_error: Option<RunError> = None;
+
+    indices.sort_by(|&a, &b| {
+        if sort_error.is_some() {
+            return Ordering::Equal;
         }
+        if let Some(ord) = compared[a].py_cmp(&compared[b], heap, interns) {
+            if reverse { ord.reverse() } else { ord }
+        } else {
+            sort_error = Some(ExcType::type_error(format!(
+                "'<' not supported between instances of '{}' and '{}'",
+                compared[a].py_type(heap),
+                compared[b].py_type(heap)
+            )));
+            Ordering::Equal
+        }
+    });
+    if let Some(err) = sort_error {
+        return Err(err);
     }
 
-    let heap_id = heap.allocate(HeapData::List(List::new(items)))?;
-    Ok(Value::Ref(heap_id))
+    // Rearrange items in sorted order using the index permutation
+    let mut unsorted: Vec<Option<Value>> = std::mem::take(items).into_iter().map(Some).collect();
+    items.extend(
+        indices
+            .into_iter()
+            .map(|i| unsorted[i].take().expect("index used once")),
+    );
+    Ok(())
 }
@@ -3,10 +3,11 @@
 use crate::{
     args::ArgValues,
     exception_private::{ExcType, RunResult, SimpleException},
+    expressions::Operator,
     heap::Heap,
     intern::Interns,
     resource::ResourceTracker,
-    types::{MontyIter, PyTrait, Type},
+    types::{MontyIter, PyTrait, Type, datetime, decimal, fraction},
     value::Value,
 };
 
@@ -45,8 +46,17 @@ pub fn builtin_sum(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, inter
         // Get item type before any operations (needed for error messages)
         let item_type = item.py_type(heap);
 
-        // Try to add the item to accumulator
-        let add_result = accumulator.py_add(&item, heap, interns);
+        // Try to add the item to accumulator, then timedelta, decimal and fraction addition
+        let add_result = match accumulator.py_add(&item, heap, interns) {
+            Ok(None) => match datetime::binary_op(&accumulator, &item, &Operator::Add, heap) {
+                Ok(None) => match decimal::binary_op(&accumulator, &item, &Operator::Add, heap) {
+                    Ok(None) => fraction::binary_op(&accumulator, &item, &Operator::Add, heap),
+                    result => result,
+                },
+                result => result,
+            },
+            result => result.map_err(Into::into),
+        };
         item.drop_with_heap(heap);
 
         match add_result {
@@ -64,7 +74,7 @@ pub fn builtin_sum(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, inter
             Err(e) => {
                 accumulator.drop_with_heap(heap);
                 iter.drop_with_heap(heap);
-                return Err(e.into());
+                return Err(e);
             }
         }
     }
@@ -2,15 +2,27 @@
 
 use super::Builtins;
 use crate::{
-    args::ArgValues, defer_drop, exception_private::RunResult, heap::Heap, resource::ResourceTracker, types::PyTrait,
+    args::ArgValues,
+    defer_drop,
+    exception_private::RunResult,
+    heap::{Heap, HeapData},
+    resource::ResourceTracker,
+    types::PyTrait,
     value::Value,
 };
 
 /// Implementation of the type() builtin function.
 ///
-/// Returns the type of an object.
+/// Returns the type of an object. Instances of user-defined classes return their class.
 pub fn builtin_type(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
     let value = args.get_one_arg("type", heap)?;
     defer_drop!(value, heap);
+    if let Value::Ref(id) = value
+        && let HeapData::Instance(inst) = heap.get(*id)
+    {
+        let class_id = inst.class_id();
+        heap.inc_ref(class_id);
+        return Ok(Value::Ref(class_id));
+    }
     Ok(Value::Builtin(Builtins::Type(value.py_type(heap))))
 }
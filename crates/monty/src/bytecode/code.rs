@@ -258,7 +258,7 @@ impl LocationEntry {
 /// 24: JUMP 50              # skip handler if no exception
 /// 30: <handler code>       # exception handler starts here
 /// ```
-/// Entry: `{ start: 0, end: 24, handler: 30, stack_depth: 0 }`
+/// Entry: `{ start: 0, end: 24, handler: 30, stack_depth: 0, exception_depth: 0 }`
 #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub struct ExceptionEntry {
     /// Start of protected bytecode range (inclusive).
@@ -276,17 +276,25 @@ pub struct ExceptionEntry {
     /// The VM pops values until the stack reaches this depth, then
     /// pushes the exception value.
     stack_depth: u16,
+
+    /// Number of exceptions this frame is handling when entering the try block.
+    ///
+    /// An exception raised inside an except or finally block leaves that block without
+    /// running its cleanup, so the VM drops the frame's handled exceptions above this
+    /// depth before jumping to the handler.
+    exception_depth: u16,
 }
 
 impl ExceptionEntry {
     /// Creates a new exception table entry.
     #[must_use]
-    pub fn new(start: u32, end: u32, handler: u32, stack_depth: u16) -> Self {
+    pub fn new(start: u32, end: u32, handler: u32, stack_depth: u16, exception_depth: u16) -> Self {
         Self {
             start,
             end,
             handler,
             stack_depth,
+            exception_depth,
         }
     }
 
@@ -302,6 +310,12 @@ impl ExceptionEntry {
         self.stack_depth
     }
 
+    /// Returns the number of handled exceptions to unwind to.
+    #[must_use]
+    pub fn exception_depth(&self) -> u16 {
+        self.exception_depth
+    }
+
     /// Returns true if the given bytecode offset is within this entry's protected range.
     #[must_use]
     pub fn contains(&self, offset: u32) -> bool {
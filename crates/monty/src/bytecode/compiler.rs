@@ -21,14 +21,15 @@ use crate::{
     exception_private::ExcType,
     exception_public::{MontyException, StackFrame},
     expressions::{
-        Callable, CmpOperator, Comprehension, Expr, ExprLoc, Identifier, Literal, NameScope, Node, Operator,
-        PreparedFunctionDef, PreparedNode, UnpackTarget,
+        Callable, CmpOperator, Comprehension, DeleteTarget, DictItem, Expr, ExprLoc, Identifier, Literal, NameScope,
+        Node, Operator, Pattern, PreparedFunctionDef, PreparedNode, UnpackTarget,
     },
     fstring::{ConversionFlag, FStringPart, FormatSpec, ParsedFormatSpec, encode_format_spec},
     function::Function,
-    intern::{Interns, StringId},
+    intern::{Interns, StaticStrings, StringId},
     modules::BuiltinModule,
-    parse::{CodeRange, ExceptHandler, Try},
+    parse::{CodeRange, ExceptHandler, MatchCase, Try},
+    types::Type,
     value::{EitherStr, Value},
 };
 
@@ -87,6 +88,13 @@ pub struct Compiler<'a> {
     /// clear the current exception (`ClearException`) and pop the exception
     /// value from the stack before jumping to the finally path or loop target.
     except_handler_depth: usize,
+
+    /// Number of exceptions being handled at the current point of the code.
+    ///
+    /// Counts enclosing except handlers and `finally` blocks running for an exception.
+    /// Recorded in exception table entries so the VM can drop exceptions whose
+    /// handling was cut short by another exception.
+    handled_exception_depth: usize,
 }
 
 /// Information about a loop for break/continue handling.
@@ -136,6 +144,27 @@ struct FinallyTarget {
     loop_depth_at_entry: usize,
 }
 
+/// Failure jumps of a `match` pattern, grouped by how many values they leave on the stack.
+///
+/// Patterns push intermediate values (unpacked items, looked-up attributes) while they
+/// match. Each failed check jumps into a ladder of `Pop`s at the point that removes exactly
+/// the values pushed so far, so every failure path reaches the next case with the same stack.
+struct PatternFailures {
+    /// Stack depth the ladder unwinds to.
+    base_depth: u16,
+    /// `jumps[n]` holds the failure jumps taken with `n` extra values above `base_depth`.
+    jumps: Vec<Vec<JumpLabel>>,
+}
+
+impl PatternFailures {
+    fn new(base_depth: u16) -> Self {
+        Self {
+            base_depth,
+            jumps: Vec::new(),
+        }
+    }
+}
+
 /// Result of module compilation: the module code and all compiled functions.
 pub struct CompileResult {
     /// The compiled module code.
@@ -155,6 +184,7 @@ impl<'a> Compiler<'a> {
             cell_base: 0,
             finally_targets: Vec::new(),
             except_handler_depth: 0,
+            handled_exception_depth: 0,
         }
     }
 
@@ -168,6 +198,7 @@ impl<'a> Compiler<'a> {
             cell_base,
             finally_targets: Vec::new(),
             except_handler_depth: 0,
+            handled_exception_depth: 0,
         }
     }
 
@@ -205,18 +236,26 @@ impl<'a> Compiler<'a> {
     ///
     /// The `functions` parameter receives any previously compiled functions, and
     /// any nested functions found in the body will be added to it.
+    ///
+    /// Class bodies (`is_class_body`) instead end by returning their local namespace
+    /// as a dict via `LoadLocals`, which `BuildClass` turns into the class attributes.
     fn compile_function_body(
         body: &[PreparedNode],
         interns: &Interns,
         functions: Vec<Function>,
         num_locals: u16,
         cell_base: u16,
+        is_class_body: bool,
     ) -> Result<(Code, Vec<Function>), CompileError> {
         let mut compiler = Compiler::new_with_cell_base(interns, functions, cell_base);
         compiler.compile_block(body)?;
 
-        // Implicit return None if no explicit return
-        compiler.code.emit(Opcode::LoadNone);
+        if is_class_body {
+            compiler.code.emit(Opcode::LoadLocals);
+        } else {
+            // Implicit return None if no explicit return
+            compiler.code.emit(Opcode::LoadNone);
+        }
         compiler.code.emit(Opcode::ReturnValue);
 
         Ok((compiler.code.build(num_locals), compiler.functions))
@@ -321,6 +360,11 @@ impl<'a> Compiler<'a> {
                     u16::try_from(name_id.index()).expect("name index exceeds u16"),
                 );
             }
+            Node::Delete(targets) => {
+                for target in targets {
+                    self.compile_delete_target(target)?;
+                }
+            }
             Node::If { test, body, or_else } => self.compile_if(test, body, or_else)?,
             Node::For {
                 target,
@@ -330,16 +374,29 @@ impl<'a> Compiler<'a> {
             } => self.compile_for(target, iter, body, or_else)?,
             Node::While { test, body, or_else } => self.compile_while(test, body, or_else)?,
             Node::Assert { test, msg } => self.compile_assert(test, msg.as_ref())?,
-            Node::Raise(expr) => {
-                if let Some(exc) = expr {
+            Node::Raise { exc, cause } => match (exc, cause) {
+                (Some(exc), Some(cause)) => {
+                    self.compile_expr(exc)?;
+                    self.compile_expr(cause)?;
+                    self.code.emit(Opcode::RaiseFrom);
+                }
+                (Some(exc), None) => {
                     self.compile_expr(exc)?;
                     self.code.emit(Opcode::Raise);
-                } else {
-                    self.code.emit(Opcode::Reraise);
                 }
-            }
+                (None, _) => self.code.emit(Opcode::Reraise),
+            },
             Node::FunctionDef(func_def) => self.compile_function_def(func_def)?,
+            Node::ClassDef { body, base } => self.compile_class_def(body, base.as_ref())?,
             Node::Try(try_block) => self.compile_try(try_block)?,
+            Node::Match { subject, cases } => self.compile_match(subject, cases)?,
+            Node::With {
+                context,
+                target,
+                manager,
+                body,
+                is_async,
+            } => self.compile_with(context, target.as_ref(), manager, body, *is_async)?,
             Node::Import { module_name, binding } => self.compile_import(*module_name, binding),
             Node::ImportFrom {
                 module_name,
@@ -361,74 +418,16 @@ impl<'a> Compiler<'a> {
     /// 2. Creating a Function struct with the compiled Code
     /// 3. Adding the Function to the compiler's functions vector
     /// 4. Emitting bytecode to evaluate defaults and create the function at runtime
+    ///
+    /// Decorator expressions are evaluated before all of this and applied to the new
+    /// function before it's stored.
     fn compile_function_def(&mut self, func_def: &PreparedFunctionDef) -> Result<(), CompileError> {
-        let func_pos = func_def.name.position;
-
-        // Check bytecode operand limits
-        if func_def.default_exprs.len() > MAX_CALL_ARGS {
-            return Err(CompileError::new(
-                format!("more than {MAX_CALL_ARGS} default parameter values"),
-                func_pos,
-            ));
-        }
-        if func_def.free_var_enclosing_slots.len() > MAX_CALL_ARGS {
-            return Err(CompileError::new(
-                format!("more than {MAX_CALL_ARGS} closure variables"),
-                func_pos,
-            ));
+        for decorator in &func_def.decorators {
+            self.compile_expr(decorator)?;
         }
 
-        // 1. Compile the function body recursively
-        // Take ownership of functions for the recursive compile, then restore
-        let functions = std::mem::take(&mut self.functions);
-        let cell_base = u16::try_from(func_def.signature.param_count()).expect("function parameter count exceeds u16");
-        let namespace_size = u16::try_from(func_def.namespace_size).expect("function namespace size exceeds u16");
-        let (body_code, mut functions) =
-            Self::compile_function_body(&func_def.body, self.interns, functions, namespace_size, cell_base)?;
-
-        // 2. Create the compiled Function and add to the vector
-        let func_id = functions.len();
-        let function = Function::new(
-            func_def.name,
-            func_def.signature.clone(),
-            func_def.namespace_size,
-            func_def.free_var_enclosing_slots.clone(),
-            func_def.cell_var_count,
-            func_def.cell_param_indices.clone(),
-            func_def.default_exprs.len(),
-            func_def.is_async,
-            body_code,
-        );
-        functions.push(function);
-
-        // Restore functions to self
-        self.functions = functions;
-
-        // 3. Compile and push default values (evaluated at definition time)
-        for default_expr in &func_def.default_exprs {
-            self.compile_expr(default_expr)?;
-        }
-        let defaults_count =
-            u8::try_from(func_def.default_exprs.len()).expect("function default argument count exceeds u8");
-        let func_id_u16 = u16::try_from(func_id).expect("function count exceeds u16");
-
-        // 4. Emit MakeFunction or MakeClosure (if has free vars)
-        if func_def.free_var_enclosing_slots.is_empty() {
-            // MakeFunction: func_id (u16) + defaults_count (u8)
-            self.code.emit_u16_u8(Opcode::MakeFunction, func_id_u16, defaults_count);
-        } else {
-            // Push captured cells from enclosing scope
-            for &slot in &func_def.free_var_enclosing_slots {
-                // Load the cell reference from the enclosing namespace
-                let slot_u16 = u16::try_from(slot.index()).expect("closure slot index exceeds u16");
-                self.code.emit_load_local(slot_u16);
-            }
-            let cell_count =
-                u8::try_from(func_def.free_var_enclosing_slots.len()).expect("closure cell count exceeds u8");
-            // MakeClosure: func_id (u16) + defaults_count (u8) + cell_count (u8)
-            self.code
-                .emit_u16_u8_u8(Opcode::MakeClosure, func_id_u16, defaults_count, cell_count);
-        }
+        self.compile_make_function(func_def, false)?;
+        self.compile_apply_decorators(&func_def.decorators);
 
         // 5. Store the function object to its name slot
         self.compile_store(&func_def.name);
@@ -443,6 +442,110 @@ impl<'a> Compiler<'a> {
     ///
     /// The lambda's `PreparedFunctionDef` already has `<lambda>` as its name.
     fn compile_lambda(&mut self, func_def: &PreparedFunctionDef) -> Result<(), CompileError> {
+        // NOTE: Unlike compile_function_def, we do NOT call compile_store here.
+        // The function object stays on the stack as an expression result.
+        self.compile_make_function(func_def, false)
+    }
+
+    /// Compiles a `yield from` expression.
+    ///
+    /// The bytecode structure:
+    /// ```text
+    ///   [evaluate iterable]
+    ///   GetIter
+    ///   LoadNone              # first value sent to the sub-iterator
+    /// loop:
+    ///   SendYieldFrom -> end  # push next value, or pop iter, push result and jump
+    ///   YieldValue
+    ///   Jump -> loop
+    /// end:
+    /// ```
+    fn compile_yield_from(&mut self, value: &ExprLoc, position: CodeRange) -> Result<(), CompileError> {
+        self.compile_expr(value)?;
+        self.code.set_location(position, None);
+        self.code.emit(Opcode::GetIter);
+        self.code.emit(Opcode::LoadNone);
+
+        let loop_start = self.code.current_offset();
+        let end_jump = self.code.emit_jump(Opcode::SendYieldFrom);
+        self.code.emit(Opcode::YieldValue);
+        self.code.emit_jump_to(Opcode::Jump, loop_start);
+
+        // SendYieldFrom replaces the iterator and sent value with the result when it jumps here
+        self.code.patch_jump(end_jump);
+        self.code.adjust_stack_depth(-1);
+        Ok(())
+    }
+
+    /// Compiles a class definition.
+    ///
+    /// The class body is compiled as a zero-argument function that returns its locals.
+    /// The emitted sequence is:
+    /// 1. Evaluate the decorator expressions
+    /// 2. `LoadConst` the class name
+    /// 3. Evaluate the base class expression (or `LoadNone` when there is no base)
+    /// 4. Create the body function and call it with no arguments
+    /// 5. `BuildClass` pops name, base and namespace dict, pushing the new class
+    /// 6. Apply the decorators to the class
+    /// 7. Store the class to its name slot
+    fn compile_class_def(&mut self, body: &PreparedFunctionDef, base: Option<&ExprLoc>) -> Result<(), CompileError> {
+        let position = body.name.position;
+
+        for decorator in &body.decorators {
+            self.compile_expr(decorator)?;
+        }
+
+        let name_const = self.code.add_const(Value::InternString(body.name.name_id));
+        self.code.emit_u16(Opcode::LoadConst, name_const);
+        match base {
+            Some(base) => self.compile_expr(base)?,
+            None => self.code.emit(Opcode::LoadNone),
+        }
+
+        self.compile_make_function(body, true)?;
+        self.code.set_location(position, None);
+        self.code.emit_u8(Opcode::CallFunction, 0);
+        self.code.emit(Opcode::BuildClass);
+        self.compile_apply_decorators(&body.decorators);
+
+        self.compile_store(&body.name);
+
+        Ok(())
+    }
+
+    /// Applies decorators to the function or class on top of the stack.
+    ///
+    /// The decorators have already been evaluated and sit below it on the stack, the last
+    /// one on top, so they're applied bottom-up. Each decorated value is kept on the stack
+    /// until its decorator returns: a decorator implemented by the host receives a handle
+    /// to the function, which stays valid if it's returned.
+    ///
+    /// ```text
+    ///   [deco, func]
+    ///   Dup; Rot3           -> [func, deco, func]
+    ///   CallFunction 1      -> [func, result]
+    ///   Rot2; Pop           -> [result]
+    /// ```
+    fn compile_apply_decorators(&mut self, decorators: &[ExprLoc]) {
+        for decorator in decorators.iter().rev() {
+            self.code.emit(Opcode::Dup);
+            self.code.emit(Opcode::Rot3);
+            self.code.set_location(decorator.position, None);
+            self.code.emit_u8(Opcode::CallFunction, 1);
+            self.code.emit(Opcode::Rot2);
+            self.code.emit(Opcode::Pop);
+        }
+    }
+
+    /// Compiles a function body and emits the bytecode that creates the function object.
+    ///
+    /// Shared by function definitions, lambdas and class bodies. Leaves the new function
+    /// (or closure) on the stack.
+    fn compile_make_function(
+        &mut self,
+        func_def: &PreparedFunctionDef,
+        is_class_body: bool,
+    ) -> Result<(), CompileError> {
         let func_pos = func_def.name.position;
 
         // Check bytecode operand limits
@@ -460,11 +563,18 @@ impl<'a> Compiler<'a> {
         }
 
         // 1. Compile the function body recursively
+        // Take ownership of functions for the recursive compile, then restore
         let functions = std::mem::take(&mut self.functions);
         let cell_base = u16::try_from(func_def.signature.param_count()).expect("function parameter count exceeds u16");
         let namespace_size = u16::try_from(func_def.namespace_size).expect("function namespace size exceeds u16");
-        let (body_code, mut functions) =
-            Self::compile_function_body(&func_def.body, self.interns, functions, namespace_size, cell_base)?;
+        let (body_code, mut functions) = Self::compile_function_body(
+            &func_def.body,
+            self.interns,
+            functions,
+            namespace_size,
+            cell_base,
+            is_class_body,
+        )?;
 
         // 2. Create the compiled Function and add to the vector
         let func_id = functions.len();
@@ -477,6 +587,7 @@ impl<'a> Compiler<'a> {
             func_def.cell_param_indices.clone(),
             func_def.default_exprs.len(),
             func_def.is_async,
+            func_def.is_generator,
             body_code,
         );
         functions.push(function);
@@ -499,6 +610,7 @@ impl<'a> Compiler<'a> {
         } else {
             // Push captured cells from enclosing scope
             for &slot in &func_def.free_var_enclosing_slots {
+                // Load the cell reference from the enclosing namespace
                 let slot_u16 = u16::try_from(slot.index()).expect("closure slot index exceeds u16");
                 self.code.emit_load_local(slot_u16);
             }
@@ -509,9 +621,6 @@ impl<'a> Compiler<'a> {
                 .emit_u16_u8_u8(Opcode::MakeClosure, func_id_u16, defaults_count, cell_count);
         }
 
-        // NOTE: Unlike compile_function_def, we do NOT call compile_store here.
-        // The function object stays on the stack as an expression result.
-
         Ok(())
     }
 
@@ -644,44 +753,65 @@ impl<'a> Compiler<'a> {
             }
 
             Expr::List(elements) => {
-                for elem in elements {
-                    self.compile_expr(elem)?;
-                }
-                self.code.emit_u16(
-                    Opcode::BuildList,
-                    u16::try_from(elements.len()).expect("elements count exceeds u16"),
-                );
+                self.compile_display(elements, Opcode::BuildList, Opcode::ListExtend, Opcode::ListAppend)?;
             }
 
             Expr::Tuple(elements) => {
-                for elem in elements {
-                    self.compile_expr(elem)?;
+                if elements.iter().any(|elem| matches!(elem.expr, Expr::Starred(_))) {
+                    // Build a list with the unpacked items, then convert it to a tuple
+                    self.compile_display(elements, Opcode::BuildList, Opcode::ListExtend, Opcode::ListAppend)?;
+                    self.code.emit(Opcode::ListToTuple);
+                } else {
+                    for elem in elements {
+                        self.compile_expr(elem)?;
+                    }
+                    self.code.emit_u16(
+                        Opcode::BuildTuple,
+                        u16::try_from(elements.len()).expect("elements count exceeds u16"),
+                    );
                 }
-                self.code.emit_u16(
-                    Opcode::BuildTuple,
-                    u16::try_from(elements.len()).expect("elements count exceeds u16"),
-                );
             }
 
-            Expr::Dict(pairs) => {
-                for (key, value) in pairs {
-                    self.compile_expr(key)?;
-                    self.compile_expr(value)?;
+            Expr::Dict(items) => {
+                // Pairs before the first `**mapping` are built in one go, the rest are added one by one
+                let pair_count = items
+                    .iter()
+                    .position(|item| matches!(item, DictItem::Unpack(_)))
+                    .unwrap_or(items.len());
+                let (pairs, rest) = items.split_at(pair_count);
+                for expr in pairs.iter().flat_map(DictItem::exprs) {
+                    self.compile_expr(expr)?;
                 }
                 self.code.emit_u16(
                     Opcode::BuildDict,
                     u16::try_from(pairs.len()).expect("pairs count exceeds u16"),
                 );
+                for item in rest {
+                    match item {
+                        DictItem::Pair(key, value) => {
+                            self.compile_expr(key)?;
+                            self.compile_expr(value)?;
+                            self.code.emit_u8(Opcode::DictSetItem, 0);
+                        }
+                        DictItem::Unpack(mapping) => {
+                            self.compile_expr(mapping)?;
+                            self.code.set_location(mapping.position, None);
+                            self.code.emit(Opcode::DictUpdate);
+                        }
+                    }
+                }
             }
 
             Expr::Set(elements) => {
-                for elem in elements {
-                    self.compile_expr(elem)?;
-                }
-                self.code.emit_u16(
-                    Opcode::BuildSet,
-                    u16::try_from(elements.len()).expect("elements count exceeds u16"),
-                );
+                self.compile_display(elements, Opcode::BuildSet, Opcode::SetUpdate, Opcode::SetAdd)?;
+            }
+
+            Expr::Starred(_) => {
+                // The parser only allows starred items directly inside list, tuple and set displays
+                return Err(CompileError::new(
+                    "can't use starred expression here",
+                    expr_loc.position,
+                ));
             }
 
             Expr::Subscript { object, index } => {
@@ -763,6 +893,34 @@ impl<'a> Compiler<'a> {
                 self.code.emit(Opcode::Await);
             }
 
+            Expr::Yield(value) => {
+                match value {
+                    Some(value) => self.compile_expr(value)?,
+                    None => self.code.emit(Opcode::LoadNone),
+                }
+                self.code.set_location(expr_loc.position, None);
+                self.code.emit(Opcode::YieldValue);
+            }
+
+            Expr::YieldFrom(value) => {
+                self.compile_yield_from(value, expr_loc.position)?;
+            }
+
+            Expr::Generator { func_def, iter } => {
+                // A generator expression is a call of its generator function with the
+                // outermost iterator, which is evaluated eagerly in the enclosing scope
+                self.compile_make_function(func_def, false)?;
+                self.compile_expr(iter)?;
+                self.code.set_location(iter.position, None);
+                self.code.emit(Opcode::GetIter);
+                self.code.set_location(expr_loc.position, None);
+                self.code.emit_u8(Opcode::CallFunction, 1);
+            }
+
+            Expr::GeneratorRaw { .. } => {
+                unreachable!("Expr::GeneratorRaw should not exist after prepare phase")
+            }
+
             Expr::Slice { lower, upper, step } => {
                 // Compile slice components: start, stop, step (push None for missing)
                 if let Some(lower) = lower {
@@ -2158,6 +2316,42 @@ impl<'a> Compiler<'a> {
         Ok(())
     }
 
+    /// Compiles the elements of a list or set display and builds the collection.
+    ///
+    /// Elements before the first starred item are pushed and built with `build` in one go.
+    /// Later elements are added to the collection one at a time: starred items with `extend`,
+    /// others with `append` (`ListAppend`/`SetAdd` with depth 0, since the collection is
+    /// directly below the value).
+    fn compile_display(
+        &mut self,
+        elements: &[ExprLoc],
+        build: Opcode,
+        extend: Opcode,
+        append: Opcode,
+    ) -> Result<(), CompileError> {
+        let prefix_len = elements
+            .iter()
+            .position(|elem| matches!(elem.expr, Expr::Starred(_)))
+            .unwrap_or(elements.len());
+        let (prefix, rest) = elements.split_at(prefix_len);
+        for elem in prefix {
+            self.compile_expr(elem)?;
+        }
+        self.code
+            .emit_u16(build, u16::try_from(prefix.len()).expect("elements count exceeds u16"));
+        for elem in rest {
+            if let Expr::Starred(value) = &elem.expr {
+                self.compile_expr(value)?;
+                self.code.set_location(elem.position, None);
+                self.code.emit(extend);
+            } else {
+                self.compile_expr(elem)?;
+                self.code.emit_u8(append, 0);
+            }
+        }
+        Ok(())
+    }
+
     /// Compiles storage of an unpack target - either a single identifier, nested tuple, or starred.
     ///
     /// For single identifiers: emits a simple store.
@@ -2395,6 +2589,7 @@ impl<'a> Compiler<'a> {
 
         // Record stack depth at try entry (for unwinding on exception)
         let stack_depth = self.code.stack_depth();
+        let exception_depth = self.exception_depth();
 
         // If there's a finally block, track returns/break/continue inside try/handlers/else
         if has_finally {
@@ -2428,7 +2623,11 @@ impl<'a> Compiler<'a> {
             // Compile exception handlers
             // handler_entry_depth = stack_depth + 1 (exception on stack)
             let handler_entry_depth = stack_depth + 1;
-            self.compile_exception_handlers(&try_block.handlers, &mut finally_jumps, handler_entry_depth)?;
+            if try_block.is_star {
+                self.compile_except_star_handlers(&try_block.handlers, &mut finally_jumps, handler_entry_depth)?;
+            } else {
+                self.compile_exception_handlers(&try_block.handlers, &mut finally_jumps, handler_entry_depth)?;
+            }
         } else {
             // No handlers - just reraise (this only happens with try-finally)
             self.code.emit(Opcode::Reraise);
@@ -2455,7 +2654,9 @@ impl<'a> Compiler<'a> {
             // The exception is already on the exception_stack from handle_exception,
             // so we can just pop from operand stack, run finally, then reraise.
             self.code.emit(Opcode::Pop); // Pop exception from operand stack
+            self.handled_exception_depth += 1;
             self.compile_block(&try_block.finally)?;
+            self.handled_exception_depth -= 1;
             self.code.emit(Opcode::Reraise); // Re-raise from exception_stack
             Some(cleanup_start)
         } else {
@@ -2547,6 +2748,7 @@ impl<'a> Compiler<'a> {
                 u32::try_from(try_end).expect("bytecode offset exceeds u32") + 3, // +3 to include the JUMP instruction
                 u32::try_from(handler_start).expect("bytecode offset exceeds u32"),
                 stack_depth,
+                exception_depth,
             ));
         }
 
@@ -2558,6 +2760,7 @@ impl<'a> Compiler<'a> {
                 u32::try_from(handler_dispatch_end).expect("bytecode offset exceeds u32"),
                 u32::try_from(cleanup_start).expect("bytecode offset exceeds u32"),
                 stack_depth,
+                exception_depth,
             ));
         }
 
@@ -2569,6 +2772,7 @@ impl<'a> Compiler<'a> {
                 u32::try_from(else_start).expect("bytecode offset exceeds u32"), // End at else_start (before else block)
                 u32::try_from(cleanup_start).expect("bytecode offset exceeds u32"),
                 stack_depth,
+                exception_depth,
             ));
         }
 
@@ -2580,6 +2784,7 @@ impl<'a> Compiler<'a> {
                 u32::try_from(else_end).expect("bytecode offset exceeds u32"),
                 u32::try_from(cleanup_start).expect("bytecode offset exceeds u32"),
                 stack_depth,
+                exception_depth,
             ));
         }
 
@@ -2662,16 +2867,18 @@ impl<'a> Compiler<'a> {
 
                 // Track that we're inside an except handler (for break/continue cleanup)
                 self.except_handler_depth += 1;
+                self.handled_exception_depth += 1;
 
                 // Compile handler body
                 self.compile_block(&handler.body)?;
 
                 // Exit except handler context
                 self.except_handler_depth -= 1;
+                self.handled_exception_depth -= 1;
 
                 // Delete exception variable (Python 3 behavior)
                 if let Some(name) = &handler.name {
-                    self.compile_delete(name);
+                    self.compile_unbind_exception_name(name);
                 }
 
                 // Clear current_exception
@@ -2705,16 +2912,18 @@ impl<'a> Compiler<'a> {
 
                 // Track that we're inside an except handler (for break/continue cleanup)
                 self.except_handler_depth += 1;
+                self.handled_exception_depth += 1;
 
                 // Compile handler body
                 self.compile_block(&handler.body)?;
 
                 // Exit except handler context
                 self.except_handler_depth -= 1;
+                self.handled_exception_depth -= 1;
 
                 // Delete exception variable
                 if let Some(name) = &handler.name {
-                    self.compile_delete(name);
+                    self.compile_unbind_exception_name(name);
                 }
 
                 // Clear current_exception
@@ -2731,26 +2940,800 @@ impl<'a> Compiler<'a> {
         Ok(())
     }
 
-    /// Compiles deletion of a variable.
+    /// Compiles the `except*` clauses of a try block.
+    ///
+    /// Each clause splits the part of the exception it matches off the unhandled rest and
+    /// runs its body with that part. Exceptions raised by the bodies are collected in a list,
+    /// and once every clause has run they're combined with whatever is still unhandled into
+    /// the exception to raise, if any:
+    /// ```text
+    /// DUP; BUILD_LIST 0; ROT2          # [exc, raised, rest]
+    /// for each clause:
+    ///   <exc_type>
+    ///   CHECK_EG_MATCH                 # [exc, raised, rest, match or None]
+    ///   DUP
+    ///   JUMP_IF_FALSE no_match
+    ///   <store name> or POP
+    ///   <handler body>                 # protected range -> body_raised
+    ///   <unbind name>
+    ///   CLEAR_EXCEPTION
+    ///   JUMP next
+    /// body_raised:                     # [exc, raised, rest, new]
+    ///   <unbind name>
+    ///   LIST_APPEND 1
+    ///   CLEAR_EXCEPTION
+    ///   JUMP next
+    /// no_match:
+    ///   POP
+    /// next:
+    /// LIST_APPEND 0
+    /// PREP_RERAISE_STAR                # [result or None]
+    /// DUP
+    /// JUMP_IF_FALSE no_exc
+    /// CLEAR_EXCEPTION
+    /// RAISE
+    /// no_exc:
+    ///   POP
+    ///   CLEAR_EXCEPTION
+    ///   JUMP to_finally
+    /// ```
+    ///
+    /// `handler_entry_depth` is the stack depth when entering handler dispatch
+    /// (i.e., base stack_depth + 1 for the exception value).
+    fn compile_except_star_handlers(
+        &mut self,
+        handlers: &[ExceptHandler<PreparedNode>],
+        finally_jumps: &mut Vec<JumpLabel>,
+        handler_entry_depth: u16,
+    ) -> Result<(), CompileError> {
+        // Stack: [exception] -> [exception, raised, rest]
+        self.code.emit(Opcode::Dup);
+        self.code.emit_u16(Opcode::BuildList, 0);
+        self.code.emit(Opcode::Rot2);
+        let clause_depth = handler_entry_depth + 2;
+        // Exceptions raised by a clause body are caught with just the original exception
+        // being handled, the clause's match having been dropped
+        let body_exception_depth = self.exception_depth() + 1;
+
+        for handler in handlers {
+            // The parser rejects `except*` clauses without an exception type
+            let Some(exc_type) = &handler.exc_type else {
+                continue;
+            };
+
+            self.compile_expr(exc_type)?;
+            // CheckEgMatch replaces rest with what's left unmatched and pushes the match,
+            // also making it the exception being handled
+            self.code.emit(Opcode::CheckEgMatch);
+            self.code.emit(Opcode::Dup);
+            let no_match_jump = self.code.emit_jump(Opcode::JumpIfFalse);
+            // Stack: [exception, raised, rest, match]
+            if let Some(name) = &handler.name {
+                self.compile_store(name);
+            } else {
+                self.code.emit(Opcode::Pop);
+            }
+
+            // Both the original exception and the match are being handled in the body
+            let body_start = self.code.current_offset();
+            self.handled_exception_depth += 2;
+            self.compile_block(&handler.body)?;
+            self.handled_exception_depth -= 2;
+            let body_end = self.code.current_offset();
+
+            if let Some(name) = &handler.name {
+                self.compile_unbind_exception_name(name);
+            }
+            self.code.emit(Opcode::ClearException);
+            let body_done_jump = self.code.emit_jump(Opcode::Jump);
+
+            // An exception raised by the body is collected: [exception, raised, rest, new]
+            let body_raised = self.code.current_offset();
+            self.code.set_stack_depth(clause_depth + 1);
+            if let Some(name) = &handler.name {
+                self.compile_unbind_exception_name(name);
+            }
+            self.code.emit_u8(Opcode::ListAppend, 1);
+            self.code.emit(Opcode::ClearException);
+            let raised_done_jump = self.code.emit_jump(Opcode::Jump);
+
+            // No match: pop the None left by CheckEgMatch
+            self.code.patch_jump(no_match_jump);
+            self.code.set_stack_depth(clause_depth + 1);
+            self.code.emit(Opcode::Pop);
+
+            self.code.patch_jump(body_done_jump);
+            self.code.patch_jump(raised_done_jump);
+
+            self.code.add_exception_entry(ExceptionEntry::new(
+                u32::try_from(body_start).expect("bytecode offset exceeds u32"),
+                u32::try_from(body_end).expect("bytecode offset exceeds u32"),
+                u32::try_from(body_raised).expect("bytecode offset exceeds u32"),
+                clause_depth,
+                body_exception_depth,
+            ));
+        }
+
+        // Stack: [exception, raised, rest] -> [result or None]
+        self.code.emit_u8(Opcode::ListAppend, 0);
+        self.code.emit(Opcode::PrepReraiseStar);
+        self.code.emit(Opcode::Dup);
+        let no_exc_jump = self.code.emit_jump(Opcode::JumpIfFalse);
+        self.code.emit(Opcode::ClearException);
+        self.code.emit(Opcode::Raise);
+
+        // Everything was handled
+        self.code.patch_jump(no_exc_jump);
+        self.code.set_stack_depth(handler_entry_depth);
+        self.code.emit(Opcode::Pop);
+        self.code.emit(Opcode::ClearException);
+        finally_jumps.push(self.code.emit_jump(Opcode::Jump));
+
+        Ok(())
+    }
+
+    /// Compiles a `with` (or `async with`) statement with a single context manager.
+    ///
+    /// The bytecode structure is:
+    /// ```text
+    /// <context>
+    /// DUP
+    /// STORE manager                  # hidden local
+    /// CALL_ATTR __enter__ 0          # followed by AWAIT for `async with`
+    /// <store target> or POP
+    /// <body>                         # protected range
+    /// JUMP normal_exit
+    /// handler:                       # exception pushed by VM
+    ///   <manager.__exit__(type(exc), exc, None)>
+    ///   JUMP_IF_TRUE suppress
+    ///   RERAISE
+    /// suppress:
+    ///   CLEAR_EXCEPTION
+    ///   POP
+    ///   JUMP end
+    /// <return/break/continue paths: manager.__exit__(None, None, None), then leave>
+    /// normal_exit:
+    ///   <manager.__exit__(None, None, None)>
+    /// end:
+    /// ```
+    ///
+    /// As with `finally` blocks, the exit call is emitted once per way out of the body.
+    /// Exceptions raised by `__enter__` or `__exit__` themselves propagate without a further
+    /// call to `__exit__`. The traceback argument is always `None`.
+    fn compile_with(
+        &mut self,
+        context: &ExprLoc,
+        target: Option<&UnpackTarget>,
+        manager: &Identifier,
+        body: &[PreparedNode],
+        is_async: bool,
+    ) -> Result<(), CompileError> {
+        let position = context.position;
+        let slot = u16::try_from(manager.namespace_id().index()).expect("local slot exceeds u16");
+        let enter = if is_async {
+            StaticStrings::DunderAenter
+        } else {
+            StaticStrings::DunderEnter
+        };
+
+        // Keep the manager in its hidden slot, then call `__enter__` on it
+        self.compile_expr(context)?;
+        self.code.emit(Opcode::Dup);
+        self.code.emit_store_local(slot);
+        self.code.set_location(position, None);
+        self.code.emit_u16_u8(Opcode::CallAttr, static_name_index(enter), 0);
+        if is_async {
+            self.code.emit(Opcode::Await);
+        }
+        match target {
+            Some(target) => self.compile_unpack_target(target),
+            None => self.code.emit(Opcode::Pop),
+        }
+
+        let stack_depth = self.code.stack_depth();
+        let exception_depth = self.exception_depth();
+
+        // Returns, breaks and continues in the body must call `__exit__` on their way out
+        self.finally_targets.push(FinallyTarget {
+            return_jumps: Vec::new(),
+            break_jumps: Vec::new(),
+            continue_jumps: Vec::new(),
+            loop_depth_at_entry: self.loop_stack.len(),
+        });
+
+        let body_start = self.code.current_offset();
+        self.compile_block(body)?;
+        let body_end = self.code.current_offset();
+        let normal_exit_jump = self.code.emit_jump(Opcode::Jump);
+
+        // === Exception path ===
+        let handler_start = self.code.current_offset();
+        // VM pushes exception onto stack when entering handler
+        self.code.adjust_stack_depth(1);
+        self.code.set_location(position, None);
+        // Stack: [exc]
+        self.code.emit(Opcode::Dup);
+        self.code.emit_load_local(slot);
+        self.code.emit(Opcode::Rot2);
+        // Stack: [exc, manager, exc]
+        self.code.emit(Opcode::Dup);
+        let type_idx = self.code.add_const(Value::Builtin(Builtins::Type(Type::Type)));
+        self.code.emit_u16(Opcode::LoadConst, type_idx);
+        self.code.emit(Opcode::Rot2);
+        self.code.emit_u8(Opcode::CallFunction, 1);
+        self.code.emit(Opcode::Rot2);
+        // Stack: [exc, manager, type(exc), exc]
+        self.code.emit(Opcode::LoadNone);
+        self.emit_exit_call(is_async);
+        // Stack: [exc, suppress]
+        let suppress_jump = self.code.emit_jump(Opcode::JumpIfTrue);
+        self.code.emit(Opcode::Reraise);
+
+        // A truthy result from `__exit__` swallows the exception
+        self.code.patch_jump(suppress_jump);
+        self.code.set_stack_depth(stack_depth + 1);
+        self.code.emit(Opcode::ClearException);
+        self.code.emit(Opcode::Pop);
+        self.code.emit_delete_local(slot);
+        let suppressed_jump = self.code.emit_jump(Opcode::Jump);
+
+        // === Exit with return/break/continue paths ===
+        let finally_target = self.finally_targets.pop().expect("finally_targets should not be empty");
+        if !finally_target.return_jumps.is_empty() {
+            for jump in finally_target.return_jumps {
+                self.code.patch_jump(jump);
+            }
+            // Return value is on stack
+            self.code.set_stack_depth(stack_depth + 1);
+            self.compile_exit_without_exception(manager, position, is_async);
+            self.compile_return();
+        }
+        if !finally_target.break_jumps.is_empty() {
+            for break_info in &finally_target.break_jumps {
+                self.code.patch_jump(break_info.jump);
+            }
+            // Break already popped the loop's iterator
+            self.code.set_stack_depth(stack_depth.saturating_sub(1));
+            self.compile_exit_without_exception(manager, position, is_async);
+            self.compile_control_flow_after_finally(&finally_target.break_jumps, true);
+        }
+        if !finally_target.continue_jumps.is_empty() {
+            for continue_info in &finally_target.continue_jumps {
+                self.code.patch_jump(continue_info.jump);
+            }
+            self.code.set_stack_depth(stack_depth);
+            self.compile_exit_without_exception(manager, position, is_async);
+            self.compile_control_flow_after_finally(&finally_target.continue_jumps, false);
+        }
+
+        // === Normal exit ===
+        self.code.patch_jump(normal_exit_jump);
+        self.code.set_stack_depth(stack_depth);
+        self.compile_exit_without_exception(manager, position, is_async);
+        self.code.patch_jump(suppressed_jump);
+
+        // +3 to include the JUMP instruction after the body
+        self.code.add_exception_entry(ExceptionEntry::new(
+            u32::try_from(body_start).expect("bytecode offset exceeds u32"),
+            u32::try_from(body_end).expect("bytecode offset exceeds u32") + 3,
+            u32::try_from(handler_start).expect("bytecode offset exceeds u32"),
+            stack_depth,
+            exception_depth,
+        ));
+
+        Ok(())
+    }
+
+    /// Returns the number of exceptions being handled, for an exception table entry.
+    fn exception_depth(&self) -> u16 {
+        u16::try_from(self.handled_exception_depth).expect("exception handler nesting exceeds u16")
+    }
+
+    /// Compiles `manager.__exit__(None, None, None)` for a `with` body left without an
+    /// exception, then clears the manager's hidden slot.
+    fn compile_exit_without_exception(&mut self, manager: &Identifier, position: CodeRange, is_async: bool) {
+        let slot = u16::try_from(manager.namespace_id().index()).expect("local slot exceeds u16");
+        self.code.set_location(position, None);
+        self.code.emit_load_local(slot);
+        self.code.emit(Opcode::LoadNone);
+        self.code.emit(Opcode::LoadNone);
+        self.code.emit(Opcode::LoadNone);
+        self.emit_exit_call(is_async);
+        self.code.emit(Opcode::Pop);
+        self.code.emit_delete_local(slot);
+    }
+
+    /// Emits the call of `__exit__` (or the awaited `__aexit__`) on the manager and its
+    /// three arguments at the top of the stack, leaving the result.
+    fn emit_exit_call(&mut self, is_async: bool) {
+        if is_async {
+            self.code
+                .emit_u16_u8(Opcode::CallAttr, static_name_index(StaticStrings::DunderAexit), 3);
+            self.code.emit(Opcode::Await);
+        } else {
+            self.code
+                .emit_u16_u8(Opcode::CallAttr, static_name_index(StaticStrings::DunderExit), 3);
+        }
+    }
+
+    /// Compiles deletion of a variable, as in `del x`.
+    ///
+    /// Deleting an unbound variable raises the same error as loading it.
     fn compile_delete(&mut self, target: &Identifier) {
         let slot = u16::try_from(target.namespace_id().index()).expect("local slot exceeds u16");
         match target.scope {
-            NameScope::Local | NameScope::LocalUnassigned => {
-                if let Ok(s) = u8::try_from(slot) {
-                    self.code.emit_u8(Opcode::DeleteLocal, s);
-                } else {
-                    // Wide variant not implemented yet
-                    todo!("DeleteLocalW for slot > 255");
+            NameScope::Local => {
+                self.code.register_local_name(slot, target.name_id);
+                self.code.register_assigned_local(slot);
+                self.code.emit_delete_local(slot);
+            }
+            NameScope::LocalUnassigned => {
+                self.code.register_local_name(slot, target.name_id);
+                self.code.emit_delete_local(slot);
+            }
+            NameScope::Global => {
+                self.code.emit_u16(Opcode::DeleteGlobal, slot);
+            }
+            NameScope::Cell => {
+                // Convert namespace slot to cells array index
+                let cell_index = slot.saturating_sub(self.cell_base);
+                self.code.register_local_name(cell_index, target.name_id);
+                self.code.emit_u16(Opcode::DeleteCell, cell_index);
+            }
+        }
+    }
+
+    /// Compiles a single target of a `del` statement.
+    fn compile_delete_target(&mut self, target: &DeleteTarget) -> Result<(), CompileError> {
+        match target {
+            DeleteTarget::Name(ident) => {
+                self.code.set_location(ident.position, None);
+                self.compile_delete(ident);
+            }
+            DeleteTarget::Subscript {
+                object,
+                index,
+                position,
+            } => {
+                // Stack order for DeleteSubscr: obj, index
+                self.compile_expr(object)?;
+                self.compile_expr(index)?;
+                self.code.set_location(*position, None);
+                self.code.emit(Opcode::DeleteSubscr);
+            }
+            DeleteTarget::Attr { object, attr, position } => {
+                self.compile_expr(object)?;
+                let name_id = attr.string_id().expect("DeleteAttr requires interned attr name");
+                self.code.set_location(*position, None);
+                self.code.emit_u16(
+                    Opcode::DeleteAttr,
+                    u16::try_from(name_id.index()).expect("name index exceeds u16"),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Unbinds the `as` variable of an except handler once the handler finishes.
+    ///
+    /// Like CPython, the variable is set to `None` before being deleted, so this can't
+    /// fail if the handler body already deleted it.
+    fn compile_unbind_exception_name(&mut self, name: &Identifier) {
+        self.code.emit(Opcode::LoadNone);
+        self.compile_store(name);
+        self.compile_delete(name);
+    }
+
+    // ========================================================================
+    // Pattern Matching Compilation
+    // ========================================================================
+
+    /// Compiles a `match` statement.
+    ///
+    /// The subject stays on the stack while the cases are tried. Each case matches a
+    /// copy of it, so a failed case leaves the stack unchanged and falls through to the
+    /// next one. Names are bound as soon as their sub-pattern matches, so a case that
+    /// fails part-way may leave some of them bound (PEP 634 allows this).
+    fn compile_match(&mut self, subject: &ExprLoc, cases: &[MatchCase<PreparedNode>]) -> Result<(), CompileError> {
+        self.compile_expr(subject)?;
+        let subject_depth = self.code.stack_depth();
+        let mut end_jumps = Vec::with_capacity(cases.len());
+
+        for (i, case) in cases.iter().enumerate() {
+            let is_last = i + 1 == cases.len();
+            if !is_last && case.guard.is_none() && case.pattern.is_irrefutable() {
+                return Err(self.unreachable_pattern_error(&case.pattern));
+            }
+            self.pattern_bindings(&case.pattern)?;
+            self.check_pattern(&case.pattern)?;
+
+            let mut failures = PatternFailures::new(subject_depth);
+            self.code.emit(Opcode::Dup);
+            self.compile_pattern(&case.pattern, &mut failures)?;
+            if let Some(guard) = &case.guard {
+                self.compile_expr(guard)?;
+                self.pattern_fail_jump(Opcode::JumpIfFalse, &mut failures);
+            }
+
+            // Matched: discard the subject and run the body
+            self.code.emit(Opcode::Pop);
+            self.compile_block(&case.body)?;
+            end_jumps.push(self.code.emit_jump(Opcode::Jump));
+            self.emit_pattern_failures(failures);
+        }
+
+        // No case matched
+        self.code.emit(Opcode::Pop);
+        for jump in end_jumps {
+            self.code.patch_jump(jump);
+        }
+        Ok(())
+    }
+
+    /// Compiles a pattern matched against the value on top of the stack.
+    ///
+    /// On success the value has been consumed and the pattern's names are bound.
+    /// On failure execution jumps to one of the labels recorded in `failures`.
+    fn compile_pattern(&mut self, pattern: &Pattern, failures: &mut PatternFailures) -> Result<(), CompileError> {
+        match pattern {
+            Pattern::Value(value) => {
+                self.compile_expr(value)?;
+                self.code.emit(Opcode::CompareEq);
+                self.pattern_fail_jump(Opcode::JumpIfFalse, failures);
+            }
+            Pattern::Singleton(value) => {
+                self.compile_expr(value)?;
+                self.code.emit(Opcode::CompareIs);
+                self.pattern_fail_jump(Opcode::JumpIfFalse, failures);
+            }
+            Pattern::As { pattern, name, .. } => {
+                if let Some(pattern) = pattern {
+                    if name.is_some() {
+                        self.code.emit(Opcode::Dup);
+                    }
+                    self.compile_pattern(pattern, failures)?;
+                }
+                match name {
+                    Some(name) => self.compile_store(name),
+                    // Wildcard: matches anything without binding it
+                    None if pattern.is_none() => self.code.emit(Opcode::Pop),
+                    None => {}
                 }
             }
-            NameScope::Global | NameScope::Cell => {
-                // Delete global/cell not commonly needed
-                // For now, just store Undefined
-                self.code.emit(Opcode::LoadNone);
-                self.compile_store(target);
+            Pattern::Star { name, .. } => match name {
+                Some(name) => self.compile_store(name),
+                None => self.code.emit(Opcode::Pop),
+            },
+            Pattern::Sequence { patterns, position } => self.compile_sequence_pattern(patterns, *position, failures)?,
+            Pattern::Mapping {
+                keys, patterns, rest, ..
+            } => self.compile_mapping_pattern(keys, patterns, rest.as_ref(), failures)?,
+            Pattern::Class {
+                cls,
+                patterns,
+                kwd_attrs,
+                kwd_patterns,
+                position,
+            } => self.compile_class_pattern(cls, patterns, kwd_attrs, kwd_patterns, *position, failures)?,
+            Pattern::Or { patterns, .. } => self.compile_or_pattern(patterns, failures)?,
+        }
+        Ok(())
+    }
+
+    /// Compiles a sequence pattern: checks the subject is a sequence of the right
+    /// length, unpacks it and matches each item against its sub-pattern.
+    fn compile_sequence_pattern(
+        &mut self,
+        patterns: &[Pattern],
+        position: CodeRange,
+        failures: &mut PatternFailures,
+    ) -> Result<(), CompileError> {
+        let star = patterns.iter().position(|p| matches!(p, Pattern::Star { .. }));
+        let min_len = patterns.len() - usize::from(star.is_some());
+
+        self.code.emit(Opcode::MatchSequence);
+        self.pattern_fail_jump(Opcode::JumpIfFalse, failures);
+
+        // A lone star pattern matches sequences of any length
+        if star.is_none() || min_len > 0 {
+            self.code.emit(Opcode::GetLen);
+            self.compile_literal(&Literal::Int(
+                i64::try_from(min_len).expect("pattern count exceeds i64"),
+            ));
+            self.code.emit(if star.is_some() {
+                Opcode::CompareGe
+            } else {
+                Opcode::CompareEq
+            });
+            self.pattern_fail_jump(Opcode::JumpIfFalse, failures);
+        }
+
+        let too_many = || CompileError::new("too many sub-patterns in sequence pattern", position);
+        match star {
+            Some(star) => {
+                let before = u8::try_from(star).map_err(|_| too_many())?;
+                let after = u8::try_from(patterns.len() - star - 1).map_err(|_| too_many())?;
+                self.code.emit_u8_u8(Opcode::UnpackEx, before, after);
+            }
+            None if patterns.is_empty() => self.code.emit(Opcode::Pop),
+            None => {
+                let count = u8::try_from(patterns.len()).map_err(|_| too_many())?;
+                self.code.emit_u8(Opcode::UnpackSequence, count);
+            }
+        }
+
+        for pattern in patterns {
+            self.compile_pattern(pattern, failures)?;
+        }
+        Ok(())
+    }
+
+    /// Compiles a mapping pattern: checks the subject is a mapping containing every key,
+    /// matches the values against their sub-patterns and optionally binds the
+    /// remaining items to the `**rest` name.
+    fn compile_mapping_pattern(
+        &mut self,
+        keys: &[ExprLoc],
+        patterns: &[Pattern],
+        rest: Option<&Identifier>,
+        failures: &mut PatternFailures,
+    ) -> Result<(), CompileError> {
+        self.code.emit(Opcode::MatchMapping);
+        self.pattern_fail_jump(Opcode::JumpIfFalse, failures);
+
+        if keys.is_empty() && rest.is_none() {
+            self.code.emit(Opcode::Pop);
+            return Ok(());
+        }
+
+        if !keys.is_empty() {
+            self.code.emit(Opcode::GetLen);
+            self.compile_literal(&Literal::Int(i64::try_from(keys.len()).expect("key count exceeds i64")));
+            self.code.emit(Opcode::CompareGe);
+            self.pattern_fail_jump(Opcode::JumpIfFalse, failures);
+        }
+
+        // Stack: [subject, keys]
+        for key in keys {
+            self.compile_expr(key)?;
+        }
+        let key_count = u16::try_from(keys.len())
+            .map_err(|_| CompileError::new("too many sub-patterns in mapping pattern", keys[0].position))?;
+        self.code.emit_u16(Opcode::BuildTuple, key_count);
+
+        if !keys.is_empty() {
+            // Stack: [subject, keys, values or None]
+            self.code.emit(Opcode::MatchKeys);
+            self.pattern_fail_if_none(failures);
+            let count = u8::try_from(keys.len())
+                .map_err(|_| CompileError::new("too many sub-patterns in mapping pattern", keys[0].position))?;
+            self.code.emit_u8(Opcode::UnpackSequence, count);
+            for pattern in patterns {
+                self.compile_pattern(pattern, failures)?;
+            }
+        }
+
+        match rest {
+            Some(rest) => {
+                // Stack: [subject, rest]
+                self.code.emit(Opcode::CopyDictWithoutKeys);
+                self.compile_store(rest);
+            }
+            None => self.code.emit(Opcode::Pop),
+        }
+        self.code.emit(Opcode::Pop);
+        Ok(())
+    }
+
+    /// Compiles a class pattern: checks the subject is an instance of the class,
+    /// looks up the positional (via `__match_args__`) and keyword attributes and
+    /// matches each against its sub-pattern.
+    fn compile_class_pattern(
+        &mut self,
+        cls: &ExprLoc,
+        patterns: &[Pattern],
+        kwd_attrs: &[StringId],
+        kwd_patterns: &[Pattern],
+        position: CodeRange,
+        failures: &mut PatternFailures,
+    ) -> Result<(), CompileError> {
+        for (i, attr) in kwd_attrs.iter().enumerate() {
+            if kwd_attrs[..i].contains(attr) {
+                return Err(CompileError::new(
+                    format!(
+                        "attribute name repeated in class pattern: {}",
+                        self.interns.get_str(*attr)
+                    ),
+                    kwd_patterns[i].position(),
+                ));
+            }
+        }
+        let too_many = || CompileError::new("too many sub-patterns in class pattern", position);
+
+        self.compile_expr(cls)?;
+        for attr in kwd_attrs {
+            let name_const = self.code.add_const(Value::InternString(*attr));
+            self.code.emit_u16(Opcode::LoadConst, name_const);
+        }
+        let kwd_count = u16::try_from(kwd_attrs.len()).map_err(|_| too_many())?;
+        self.code.emit_u16(Opcode::BuildTuple, kwd_count);
+
+        // Errors from `__match_args__` and the isinstance check point at the whole pattern
+        let nargs = u8::try_from(patterns.len()).map_err(|_| too_many())?;
+        self.code.set_location(position, None);
+        self.code.emit_u8(Opcode::MatchClass, nargs);
+        self.pattern_fail_if_none(failures);
+
+        // Stack: [attrs tuple]
+        let total = patterns.len() + kwd_patterns.len();
+        if total == 0 {
+            self.code.emit(Opcode::Pop);
+        } else {
+            let count = u8::try_from(total).map_err(|_| too_many())?;
+            self.code.emit_u8(Opcode::UnpackSequence, count);
+        }
+        for pattern in patterns.iter().chain(kwd_patterns) {
+            self.compile_pattern(pattern, failures)?;
+        }
+        Ok(())
+    }
+
+    /// Compiles an OR pattern: tries each alternative against a copy of the subject,
+    /// stopping at the first one that matches.
+    fn compile_or_pattern(
+        &mut self,
+        alternatives: &[Pattern],
+        failures: &mut PatternFailures,
+    ) -> Result<(), CompileError> {
+        let depth = self.code.stack_depth();
+        let mut success_jumps = Vec::with_capacity(alternatives.len());
+
+        for alternative in alternatives {
+            let mut alt_failures = PatternFailures::new(depth);
+            self.code.emit(Opcode::Dup);
+            self.compile_pattern(alternative, &mut alt_failures)?;
+            success_jumps.push(self.code.emit_jump(Opcode::Jump));
+            self.emit_pattern_failures(alt_failures);
+        }
+
+        // No alternative matched
+        self.pattern_fail_jump(Opcode::Jump, failures);
+
+        self.code.set_stack_depth(depth);
+        for jump in success_jumps {
+            self.code.patch_jump(jump);
+        }
+        self.code.emit(Opcode::Pop);
+        Ok(())
+    }
+
+    /// Emits a jump taken when the pattern fails, recording it with the number of
+    /// values it leaves above the failure base.
+    fn pattern_fail_jump(&mut self, op: Opcode, failures: &mut PatternFailures) {
+        let jump = self.code.emit_jump(op);
+        let extra = usize::from(self.code.stack_depth() - failures.base_depth);
+        if failures.jumps.len() <= extra {
+            failures.jumps.resize_with(extra + 1, Vec::new);
+        }
+        failures.jumps[extra].push(jump);
+    }
+
+    /// Emits a failure jump if the value on top of the stack is `None`, leaving it
+    /// on the stack otherwise.
+    fn pattern_fail_if_none(&mut self, failures: &mut PatternFailures) {
+        self.code.emit(Opcode::Dup);
+        self.code.emit(Opcode::LoadNone);
+        self.code.emit(Opcode::CompareIs);
+        self.pattern_fail_jump(Opcode::JumpIfTrue, failures);
+    }
+
+    /// Emits the `Pop` ladder the failure jumps land on. Each group of jumps enters
+    /// the ladder where the remaining `Pop`s remove exactly the values it left,
+    /// so the ladder always ends at `base_depth`.
+    fn emit_pattern_failures(&mut self, failures: PatternFailures) {
+        let PatternFailures { base_depth, jumps } = failures;
+        let max_extra = u16::try_from(jumps.len().saturating_sub(1)).expect("pattern stack depth exceeds u16");
+        self.code.set_stack_depth(base_depth + max_extra);
+        for (extra, group) in jumps.into_iter().enumerate().rev() {
+            for jump in group {
+                self.code.patch_jump(jump);
+            }
+            if extra > 0 {
+                self.code.emit(Opcode::Pop);
             }
         }
     }
+
+    /// Returns the names bound by a pattern, rejecting names bound more than once.
+    fn pattern_bindings(&self, pattern: &Pattern) -> Result<Vec<StringId>, CompileError> {
+        let mut names = Vec::new();
+        let mut duplicate = None;
+        pattern.for_each_binding(&mut |ident| {
+            if names.contains(&ident.name_id) {
+                duplicate.get_or_insert(ident);
+            } else {
+                names.push(ident.name_id);
+            }
+        });
+        match duplicate {
+            Some(ident) => Err(CompileError::new(
+                format!(
+                    "multiple assignments to name '{}' in pattern",
+                    self.interns.get_str(ident.name_id)
+                ),
+                ident.position,
+            )),
+            None => Ok(names),
+        }
+    }
+
+    /// Validates the OR patterns nested in a pattern: every alternative must bind the
+    /// same names, and only the last one may be irrefutable.
+    fn check_pattern(&self, pattern: &Pattern) -> Result<(), CompileError> {
+        match pattern {
+            Pattern::Or { patterns, position } => {
+                let expected = match patterns.first() {
+                    Some(first) => self.pattern_bindings(first)?,
+                    None => Vec::new(),
+                };
+                for (i, alternative) in patterns.iter().enumerate() {
+                    if i + 1 < patterns.len() && alternative.is_irrefutable() {
+                        return Err(self.unreachable_pattern_error(alternative));
+                    }
+                    let names = self.pattern_bindings(alternative)?;
+                    if names.len() != expected.len() || !names.iter().all(|name| expected.contains(name)) {
+                        return Err(CompileError::new(
+                            "alternative patterns bind different names",
+                            *position,
+                        ));
+                    }
+                    self.check_pattern(alternative)?;
+                }
+            }
+            Pattern::Sequence { patterns, .. } | Pattern::Mapping { patterns, .. } => {
+                for p in patterns {
+                    self.check_pattern(p)?;
+                }
+            }
+            Pattern::Class {
+                patterns, kwd_patterns, ..
+            } => {
+                for p in patterns.iter().chain(kwd_patterns) {
+                    self.check_pattern(p)?;
+                }
+            }
+            Pattern::As {
+                pattern: Some(pattern), ..
+            } => self.check_pattern(pattern)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Builds the error for an irrefutable pattern followed by further patterns.
+    fn unreachable_pattern_error(&self, pattern: &Pattern) -> CompileError {
+        match pattern {
+            Pattern::As {
+                pattern: None,
+                name: Some(name),
+                ..
+            } => CompileError::new(
+                format!(
+                    "name capture '{}' makes remaining patterns unreachable",
+                    self.interns.get_str(name.name_id)
+                ),
+                name.position,
+            ),
+            Pattern::As {
+                pattern: Some(pattern), ..
+            } => self.unreachable_pattern_error(pattern),
+            Pattern::Or { patterns, .. } => match patterns.iter().find(|p| p.is_irrefutable()) {
+                Some(irrefutable) => self.unreachable_pattern_error(irrefutable),
+                None => CompileError::new("wildcard makes remaining patterns unreachable", pattern.position()),
+            },
+            _ => CompileError::new("wildcard makes remaining patterns unreachable", pattern.position()),
+        }
+    }
 }
 
 /// Error that can occur during bytecode compilation.
@@ -2866,3 +3849,8 @@ fn cmp_operator_to_opcode(op: &CmpOperator) -> Opcode {
         CmpOperator::ModEq(_) => unreachable!("ModEq handled at call site"),
     }
 }
+
+/// Returns the operand used to refer to a static string, such as a dunder method name.
+fn static_name_index(name: StaticStrings) -> u16 {
+    u16::try_from(StringId::from(name).index()).expect("name index exceeds u16")
+}
@@ -70,7 +70,15 @@ pub enum Opcode {
     /// Store to closure cell. Operand: u16 slot.
     StoreCell,
     /// Delete local variable. Operand: u8 slot.
+    ///
+    /// Raises `UnboundLocalError` or `NameError` if the variable is not bound.
     DeleteLocal,
+    /// Delete local variable (wide). Operand: u16 slot.
+    DeleteLocalW,
+    /// Delete global variable. Operand: u16 slot.
+    DeleteGlobal,
+    /// Delete closure cell contents. Operand: u16 slot.
+    DeleteCell,
 
     // === Binary Operations (no operand) ===
     /// Add: a + b.
@@ -191,8 +199,8 @@ pub enum Opcode {
     BuildSlice,
     /// Pop iterable, pop list, extend list with iterable items.
     ///
-    /// Used for `*args` unpacking: builds a list of positional args,
-    /// then extends it with unpacked iterables.
+    /// Used for `*args` unpacking and starred items in list and tuple displays:
+    /// builds a list of the leading items, then extends it with unpacked iterables.
     ListExtend,
     /// Pop TOS (list), push tuple containing the same elements.
     ///
@@ -204,6 +212,15 @@ pub enum Opcode {
     /// Used for `**kwargs` unpacking. The func_name_id is used for error messages
     /// when the mapping contains non-string keys.
     DictMerge,
+    /// Pop iterable, pop set, add iterable items to set.
+    ///
+    /// Used for starred items in set displays like `{*a, *b}`.
+    SetUpdate,
+    /// Pop mapping, pop dict, update dict with mapping.
+    ///
+    /// Used for `**mapping` items in dict displays like `{**a, 'k': v}`.
+    /// Unlike `DictMerge`, keys can be of any hashable type.
+    DictUpdate,
 
     // === Comprehension Building ===
     /// Append TOS to list for comprehension. Operand: u8 depth (number of iterators).
@@ -320,6 +337,8 @@ pub enum Opcode {
 
     // === Iteration ===
     /// Convert TOS to iterator.
+    ///
+    /// Iterators and generators are their own iterators and are left as-is.
     GetIter,
     /// Advance iterator or jump to end. Operand: i16 offset.
     ForIter,
@@ -330,11 +349,23 @@ pub enum Opcode {
     /// Create closure. Operands: u16 func_id, u8 cell_count.
     MakeClosure,
 
+    // === Class Definition ===
+    /// Push the current frame's local namespace as a dict.
+    ///
+    /// Emitted at the end of class bodies, whose locals become the class attributes.
+    /// Only slots that have been assigned are included.
+    LoadLocals,
+    /// Create a class object.
+    ///
+    /// Stack: [..., name, base, namespace] -> [..., class]
+    /// `base` is `None` when the class has no explicit base class.
+    BuildClass,
+
     // === Exception Handling ===
     // Note: No SetupTry/PopExceptHandler - we use static exception_table
     /// Raise TOS as exception.
     Raise,
-    /// Raise TOS from TOS-1.
+    /// Raise TOS-1 with TOS as its cause (`raise exc from cause`).
     RaiseFrom,
     /// Re-raise current exception (bare `raise`).
     Reraise,
@@ -346,6 +377,20 @@ pub enum Opcode {
     /// Validates that exc_type is a valid exception type (ExcType or tuple of ExcTypes).
     /// If invalid, raises TypeError. If valid, pushes True if exception matches, else False.
     CheckExcMatch,
+    /// Split off the part of the exception matching an `except*` clause.
+    ///
+    /// Stack: [..., rest, exc_type] -> [..., rest, match]
+    /// `rest` is what earlier clauses didn't match (`None` once everything matched) and is
+    /// replaced by what this clause doesn't match. `match` is `None` if nothing matched,
+    /// otherwise it's pushed onto the exception stack as the exception being handled.
+    /// Raises TypeError if exc_type is invalid or an exception group type.
+    CheckEgMatch,
+    /// Work out the exception to raise after the clauses of an `except*` statement.
+    ///
+    /// Stack: [..., exception, raised] -> [..., result]
+    /// `raised` is a list of the exceptions raised by the clauses, followed by the unmatched
+    /// rest. `result` is `None` when there's nothing to raise.
+    PrepReraiseStar,
 
     // === Return ===
     /// Return TOS from function.
@@ -363,12 +408,58 @@ pub enum Opcode {
     /// Raises `RuntimeError` if coroutine/future has already been awaited.
     Await,
 
+    // === Generators ===
+    /// Suspend the current generator frame, yielding TOS to its caller.
+    ///
+    /// Stack: [..., value] -> [..., sent]
+    /// When the generator is resumed, the value passed to `send()` (or `None`)
+    /// is pushed in place of the yielded value.
+    YieldValue,
+    /// Advance the `yield from` iterator, or jump to end. Operand: i16 offset.
+    ///
+    /// Stack: [..., iter, sent] -> [..., iter, value] when the iterator yields a value.
+    /// Stack: [..., iter, sent] -> [..., result] (and jump) when it is exhausted, where
+    /// `result` is the generator's return value (or `None` for other iterators).
+    SendYieldFrom,
+
     // === Unpacking ===
     /// Unpack TOS into n values. Operand: u8 count.
     UnpackSequence,
     /// Unpack with *rest. Operands: u8 before, u8 after.
     UnpackEx,
 
+    // === Pattern Matching ===
+    /// Push whether TOS can match a sequence pattern (list, tuple or named tuple).
+    ///
+    /// Stack: [..., subject] -> [..., subject, bool]
+    MatchSequence,
+    /// Push whether TOS can match a mapping pattern (dict).
+    ///
+    /// Stack: [..., subject] -> [..., subject, bool]
+    MatchMapping,
+    /// Push the length of TOS, keeping TOS on the stack.
+    ///
+    /// Stack: [..., subject] -> [..., subject, len]
+    GetLen,
+    /// Look up the keys of a mapping pattern in the subject.
+    ///
+    /// Stack: [..., subject, keys] -> [..., subject, keys, values]
+    /// `keys` is a tuple; `values` is a tuple of the corresponding values, or `None`
+    /// if any key is missing. Raises `ValueError` for duplicate keys.
+    MatchKeys,
+    /// Build the `**rest` dict of a mapping pattern.
+    ///
+    /// Stack: [..., subject, keys] -> [..., subject, rest]
+    /// `rest` is a new dict with every item of the subject whose key is not in `keys`.
+    CopyDictWithoutKeys,
+    /// Match a class pattern. Operand: u8 positional sub-pattern count.
+    ///
+    /// Stack: [..., subject, cls, kw_names] -> [..., attrs]
+    /// `kw_names` is a tuple of keyword attribute names. `attrs` is a tuple of the
+    /// positional attributes (resolved via `__match_args__`) followed by the keyword
+    /// attributes, or `None` if the subject isn't an instance of `cls` or lacks an attribute.
+    MatchClass,
+
     // === Special ===
     /// No operation (for patching/alignment).
     Nop,
@@ -409,19 +500,21 @@ impl Opcode {
     pub const fn stack_effect(self) -> Option<i16> {
         use Opcode::{
             Await, BinaryAdd, BinaryAnd, BinaryDiv, BinaryFloorDiv, BinaryLShift, BinaryMatMul, BinaryMod, BinaryMul,
-            BinaryOr, BinaryPow, BinaryRShift, BinarySub, BinarySubscr, BinaryXor, BuildDict, BuildFString, BuildList,
-            BuildSet, BuildSlice, BuildTuple, CallAttr, CallAttrExtended, CallAttrKw, CallBuiltinFunction,
-            CallBuiltinType, CallFunction, CallFunctionExtended, CallFunctionKw, CheckExcMatch, ClearException,
-            CompareEq, CompareGe, CompareGt, CompareIn, CompareIs, CompareIsNot, CompareLe, CompareLt, CompareModEq,
-            CompareNe, CompareNotIn, DeleteAttr, DeleteLocal, DeleteSubscr, DictMerge, DictSetItem, Dup, ForIter,
-            FormatValue, GetIter, InplaceAdd, InplaceAnd, InplaceDiv, InplaceFloorDiv, InplaceLShift, InplaceMod,
+            BinaryOr, BinaryPow, BinaryRShift, BinarySub, BinarySubscr, BinaryXor, BuildClass, BuildDict, BuildFString,
+            BuildList, BuildSet, BuildSlice, BuildTuple, CallAttr, CallAttrExtended, CallAttrKw, CallBuiltinFunction,
+            CallBuiltinType, CallFunction, CallFunctionExtended, CallFunctionKw, CheckEgMatch, CheckExcMatch,
+            ClearException, CompareEq, CompareGe, CompareGt, CompareIn, CompareIs, CompareIsNot, CompareLe, CompareLt,
+            CompareModEq, CompareNe, CompareNotIn, CopyDictWithoutKeys, DeleteAttr, DeleteCell, DeleteGlobal,
+            DeleteLocal, DeleteLocalW, DeleteSubscr, DictMerge, DictSetItem, DictUpdate, Dup, ForIter, FormatValue,
+            GetIter, GetLen, InplaceAdd, InplaceAnd, InplaceDiv, InplaceFloorDiv, InplaceLShift, InplaceMod,
             InplaceMul, InplaceOr, InplacePow, InplaceRShift, InplaceSub, InplaceXor, Jump, JumpIfFalse,
             JumpIfFalseOrPop, JumpIfTrue, JumpIfTrueOrPop, ListAppend, ListExtend, ListToTuple, LoadAttr,
             LoadAttrImport, LoadCell, LoadConst, LoadFalse, LoadGlobal, LoadLocal, LoadLocal0, LoadLocal1, LoadLocal2,
-            LoadLocal3, LoadLocalW, LoadModule, LoadNone, LoadSmallInt, LoadTrue, MakeClosure, MakeFunction, Nop, Pop,
-            Raise, RaiseFrom, RaiseImportError, Reraise, ReturnValue, Rot2, Rot3, SetAdd, StoreAttr, StoreCell,
-            StoreGlobal, StoreLocal, StoreLocalW, StoreSubscr, UnaryInvert, UnaryNeg, UnaryNot, UnaryPos, UnpackEx,
-            UnpackSequence,
+            LoadLocal3, LoadLocalW, LoadLocals, LoadModule, LoadNone, LoadSmallInt, LoadTrue, MakeClosure,
+            MakeFunction, MatchClass, MatchKeys, MatchMapping, MatchSequence, Nop, Pop, PrepReraiseStar, Raise,
+            RaiseFrom, RaiseImportError, Reraise, ReturnValue, Rot2, Rot3, SendYieldFrom, SetAdd, SetUpdate, StoreAttr,
+            StoreCell, StoreGlobal, StoreLocal, StoreLocalW, StoreSubscr, UnaryInvert, UnaryNeg, UnaryNot, UnaryPos,
+            UnpackEx, UnpackSequence, YieldValue,
         };
         Some(match self {
             // Stack operations
@@ -436,7 +529,7 @@ impl Opcode {
             LoadLocal0 | LoadLocal1 | LoadLocal2 | LoadLocal3 => 1,
             LoadLocal | LoadLocalW | LoadGlobal | LoadCell => 1,
             StoreLocal | StoreLocalW | StoreGlobal | StoreCell => -1,
-            DeleteLocal => 0, // doesn't affect stack
+            DeleteLocal | DeleteLocalW | DeleteGlobal | DeleteCell => 0, // doesn't affect stack
 
             // Binary operations: pop 2, push 1 = -1
             BinaryAdd | BinarySub | BinaryMul | BinaryDiv | BinaryFloorDiv | BinaryMod | BinaryPow | BinaryAnd
@@ -463,8 +556,8 @@ impl Opcode {
             ListExtend => -1,
             // ListToTuple: pop 1, push 1 = 0
             ListToTuple => 0,
-            // DictMerge: pop 2, push 1 = -1
-            DictMerge => -1,
+            // DictMerge/SetUpdate/DictUpdate: pop 2, push 1 = -1
+            DictMerge | SetUpdate | DictUpdate => -1,
 
             // Comprehension building - pops value, no push (stores in collection below)
             ListAppend | SetAdd => -1,
@@ -494,15 +587,25 @@ impl Opcode {
             // Async/await
             Await => 0, // pop awaitable, push result
 
+            // Generators
+            YieldValue => 0,              // pop yielded value, push sent value
+            SendYieldFrom => return None, // pushes value or jumps (variable)
+
             // Function definition - push 1 (the function/closure)
             MakeFunction | MakeClosure => 1,
 
+            // Class definition
+            LoadLocals => 1,  // push namespace dict
+            BuildClass => -2, // pop name, base, namespace; push class
+
             // Exception handling
-            Raise => -1,         // pop exception
-            RaiseFrom => -2,     // pop exception, pop cause
-            Reraise => 0,        // no stack change (reads from exception_stack)
-            ClearException => 0, // clears exception_stack, no operand stack change
-            CheckExcMatch => 0,  // pop exc_type, push bool (net 0, but exc stays)
+            Raise => -1,           // pop exception
+            RaiseFrom => -2,       // pop exception, pop cause
+            Reraise => 0,          // no stack change (reads from exception_stack)
+            ClearException => 0,   // clears exception_stack, no operand stack change
+            CheckExcMatch => 0,    // pop exc_type, push bool (net 0, but exc stays)
+            CheckEgMatch => 0,     // pop rest and exc_type, push rest and match
+            PrepReraiseStar => -1, // pop exception and raised list, push result
 
             // Return
             ReturnValue => -1,
@@ -510,6 +613,11 @@ impl Opcode {
             // Unpacking - depends on operand
             UnpackSequence | UnpackEx => return None,
 
+            // Pattern matching
+            MatchSequence | MatchMapping | GetLen | MatchKeys => 1, // push result, keep subject
+            CopyDictWithoutKeys => 0,                               // pop keys, push rest dict
+            MatchClass => -2,                                       // pop subject, cls, names; push attrs
+
             // Special
             Nop => 0,
 
@@ -311,6 +311,7 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         self.frames.push(CallFrame::new_function(
             &func.code,
             self.stack.len(),
+            self.exception_stack.len(),
             namespace_idx,
             func_id,
             frame_cells,
@@ -616,9 +617,12 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
                 function_id: f.function_id,
                 ip: f.ip,
                 stack_base: f.stack_base,
+                exception_base: f.exception_base,
                 namespace_idx: f.namespace_idx,
                 cells: f.cells,
                 call_position: f.call_position,
+                is_init: f.is_init,
+                generator: f.generator,
             })
             .collect();
         let stack = std::mem::take(&mut self.stack);
@@ -672,10 +676,13 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
                         code,
                         ip: sf.ip,
                         stack_base: sf.stack_base,
+                        exception_base: sf.exception_base,
                         namespace_idx: sf.namespace_idx,
                         function_id: sf.function_id,
                         cells: sf.cells,
                         call_position: sf.call_position,
+                        is_init: sf.is_init,
+                        generator: sf.generator,
                     }
                 })
                 .collect();
@@ -735,6 +742,7 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         self.frames.push(CallFrame::new_function(
             &func.code,
             self.stack.len(),
+            self.exception_stack.len(),
             namespace_idx,
             func_id,
             frame_cells,
@@ -58,4 +58,14 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         obj.drop_with_heap(self.heap);
         result
     }
+
+    /// Deletes an attribute from an object.
+    ///
+    /// Returns an AttributeError if the attribute cannot be deleted.
+    pub(super) fn delete_attr(&mut self, name_id: StringId) -> Result<(), RunError> {
+        let obj = self.pop();
+        let result = obj.py_del_attr(name_id, self.heap, self.interns);
+        obj.drop_with_heap(self.heap);
+        result
+    }
 }
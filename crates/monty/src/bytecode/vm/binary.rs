@@ -3,12 +3,18 @@
 use super::VM;
 use crate::{
     defer_drop,
-    exception_private::{ExcType, RunError},
-    heap::HeapGuard,
+    exception_private::{ExcType, RunError, RunResult},
+    expressions::Operator,
+    heap::{Heap, HeapGuard},
     io::PrintWriter,
     resource::ResourceTracker,
-    types::PyTrait,
-    value::BitwiseOp,
+    types::{
+        PyTrait, counter, datetime, decimal,
+        dict_view::is_set_like_view,
+        r#enum, fraction,
+        set::{SetOp, set_operation},
+    },
+    value::{BitwiseOp, Value},
 };
 
 impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
@@ -24,23 +30,34 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         let lhs = this.pop();
         defer_drop!(lhs, this);
 
-        match lhs.py_add(rhs, this.heap, this.interns) {
+        let result = match lhs.py_add(rhs, this.heap, this.interns) {
+            Ok(None) => match stdlib_binary_op(lhs, rhs, &Operator::Add, this.heap) {
+                Ok(None) => counter::binary_op(lhs, rhs, &Operator::Add, this.heap, this.interns),
+                result => result,
+            },
+            result => result.map_err(Into::into),
+        };
+        match result {
             Ok(Some(v)) => {
                 this.push(v);
                 Ok(())
             }
             Ok(None) => {
+                if this.push_mixin_operands(lhs, rhs) {
+                    return this.binary_add();
+                }
                 let lhs_type = lhs.py_type(this.heap);
                 let rhs_type = rhs.py_type(this.heap);
                 Err(ExcType::binary_type_error("+", lhs_type, rhs_type))
             }
-            Err(e) => Err(e.into()),
+            Err(e) => Err(e),
         }
     }
 
     /// Binary subtraction with proper refcount handling.
     ///
-    /// Uses lazy type capture: only calls `py_type()` in error paths.
+    /// Keys and items dict views take the set difference instead, and counters subtract
+    /// counts. Uses lazy type capture: only calls `py_type()` in error paths.
     pub(super) fn binary_sub(&mut self) -> Result<(), RunError> {
         let this = self;
 
@@ -49,17 +66,30 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         let lhs = this.pop();
         defer_drop!(lhs, this);
 
-        match lhs.py_sub(rhs, this.heap) {
+        let result = match lhs.py_sub(rhs, this.heap) {
+            Ok(None) => match stdlib_binary_op(lhs, rhs, &Operator::Sub, this.heap) {
+                Ok(None) => match counter::binary_op(lhs, rhs, &Operator::Sub, this.heap, this.interns) {
+                    Ok(None) => this.dict_view_set_op(lhs, rhs, SetOp::Difference),
+                    result => result,
+                },
+                result => result,
+            },
+            result => result.map_err(Into::into),
+        };
+        match result {
             Ok(Some(v)) => {
                 this.push(v);
                 Ok(())
             }
             Ok(None) => {
+                if this.push_mixin_operands(lhs, rhs) {
+                    return this.binary_sub();
+                }
                 let lhs_type = lhs.py_type(this.heap);
                 let rhs_type = rhs.py_type(this.heap);
                 Err(ExcType::binary_type_error("-", lhs_type, rhs_type))
             }
-            Err(e) => Err(e.into()),
+            Err(e) => Err(e),
         }
     }
 
@@ -74,12 +104,19 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         let lhs = this.pop();
         defer_drop!(lhs, this);
 
-        match lhs.py_mult(rhs, this.heap, this.interns) {
+        let result = match lhs.py_mult(rhs, this.heap, this.interns) {
+            Ok(None) => stdlib_binary_op(lhs, rhs, &Operator::Mult, this.heap),
+            result => result,
+        };
+        match result {
             Ok(Some(v)) => {
                 this.push(v);
                 Ok(())
             }
             Ok(None) => {
+                if this.push_mixin_operands(lhs, rhs) {
+                    return this.binary_mult();
+                }
                 let lhs_type = lhs.py_type(this.heap);
                 let rhs_type = rhs.py_type(this.heap);
                 Err(ExcType::binary_type_error("*", lhs_type, rhs_type))
@@ -99,12 +136,19 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         let lhs = this.pop();
         defer_drop!(lhs, this);
 
-        match lhs.py_div(rhs, this.heap, this.interns) {
+        let result = match lhs.py_div(rhs, this.heap, this.interns) {
+            Ok(None) => stdlib_binary_op(lhs, rhs, &Operator::Div, this.heap),
+            result => result,
+        };
+        match result {
             Ok(Some(v)) => {
                 this.push(v);
                 Ok(())
             }
             Ok(None) => {
+                if this.push_mixin_operands(lhs, rhs) {
+                    return this.binary_div();
+                }
                 let lhs_type = lhs.py_type(this.heap);
                 let rhs_type = rhs.py_type(this.heap);
                 Err(ExcType::binary_type_error("/", lhs_type, rhs_type))
@@ -124,12 +168,19 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         let lhs = this.pop();
         defer_drop!(lhs, this);
 
-        match lhs.py_floordiv(rhs, this.heap) {
+        let result = match lhs.py_floordiv(rhs, this.heap) {
+            Ok(None) => stdlib_binary_op(lhs, rhs, &Operator::FloorDiv, this.heap),
+            result => result,
+        };
+        match result {
             Ok(Some(v)) => {
                 this.push(v);
                 Ok(())
             }
             Ok(None) => {
+                if this.push_mixin_operands(lhs, rhs) {
+                    return this.binary_floordiv();
+                }
                 let lhs_type = lhs.py_type(this.heap);
                 let rhs_type = rhs.py_type(this.heap);
                 Err(ExcType::binary_type_error("//", lhs_type, rhs_type))
@@ -140,6 +191,7 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
 
     /// Binary modulo with proper refcount handling.
     ///
+    /// `str` and `bytes` left operands do printf-style formatting instead.
     /// Uses lazy type capture: only calls `py_type()` in error paths.
     pub(super) fn binary_mod(&mut self) -> Result<(), RunError> {
         let this = self;
@@ -149,12 +201,22 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         let lhs = this.pop();
         defer_drop!(lhs, this);
 
-        match lhs.py_mod(rhs, this.heap) {
+        let result = match lhs.py_mod(rhs, this.heap) {
+            Ok(None) => match this.percent_format(lhs, rhs) {
+                Ok(None) => stdlib_binary_op(lhs, rhs, &Operator::Mod, this.heap),
+                result => result,
+            },
+            result => result,
+        };
+        match result {
             Ok(Some(v)) => {
                 this.push(v);
                 Ok(())
             }
             Ok(None) => {
+                if this.push_mixin_operands(lhs, rhs) {
+                    return this.binary_mod();
+                }
                 let lhs_type = lhs.py_type(this.heap);
                 let rhs_type = rhs.py_type(this.heap);
                 Err(ExcType::binary_type_error("%", lhs_type, rhs_type))
@@ -175,12 +237,19 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         let lhs = this.pop();
         defer_drop!(lhs, this);
 
-        match lhs.py_pow(rhs, this.heap) {
+        let result = match lhs.py_pow(rhs, this.heap) {
+            Ok(None) => stdlib_binary_op(lhs, rhs, &Operator::Pow, this.heap),
+            result => result,
+        };
+        match result {
             Ok(Some(v)) => {
                 this.push(v);
                 Ok(())
             }
             Ok(None) => {
+                if this.push_mixin_operands(lhs, rhs) {
+                    return this.binary_pow();
+                }
                 let lhs_type = lhs.py_type(this.heap);
                 let rhs_type = rhs.py_type(this.heap);
                 Err(ExcType::binary_type_error("** or pow()", lhs_type, rhs_type))
@@ -192,6 +261,8 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
     /// Binary bitwise operation on integers.
     ///
     /// Pops two values, performs the bitwise operation, and pushes the result.
+    /// `&`, `|` and `^` on keys and items dict views are set operations instead, and `&`
+    /// and `|` on counters take the minimum and maximum counts.
     pub(super) fn binary_bitwise(&mut self, op: BitwiseOp) -> Result<(), RunError> {
         let this = self;
 
@@ -200,9 +271,38 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         let lhs = this.pop();
         defer_drop!(lhs, this);
 
-        let result = lhs.py_bitwise(rhs, op, this.heap)?;
-        this.push(result);
-        Ok(())
+        let set_op = match op {
+            BitwiseOp::And => Some(SetOp::Intersection),
+            BitwiseOp::Or => Some(SetOp::Union),
+            BitwiseOp::Xor => Some(SetOp::SymmetricDifference),
+            BitwiseOp::LShift | BitwiseOp::RShift => None,
+        };
+        if let Some(set_op) = set_op
+            && let Some(result) = this.dict_view_set_op(lhs, rhs, set_op)?
+        {
+            this.push(result);
+            return Ok(());
+        }
+        let counter_op = match op {
+            BitwiseOp::And => Some(Operator::BitAnd),
+            BitwiseOp::Or => Some(Operator::BitOr),
+            _ => None,
+        };
+        if let Some(counter_op) = counter_op
+            && let Some(result) = counter::binary_op(lhs, rhs, &counter_op, this.heap, this.interns)?
+        {
+            this.push(result);
+            return Ok(());
+        }
+
+        match lhs.py_bitwise(rhs, op, this.heap) {
+            Ok(result) => {
+                this.push(result);
+                Ok(())
+            }
+            Err(_) if this.push_mixin_operands(lhs, rhs) => this.binary_bitwise(op),
+            Err(e) => Err(e),
+        }
     }
 
     /// In-place addition (uses py_iadd for mutable containers, falls back to py_add).
@@ -236,9 +336,78 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
             this.push(v);
             return Ok(());
         }
+        if let Some(v) = stdlib_binary_op(lhs, rhs, &Operator::Add, this.heap)? {
+            this.push(v);
+            return Ok(());
+        }
+        if let Some(v) = counter::binary_op(lhs, rhs, &Operator::Add, this.heap, this.interns)? {
+            this.push(v);
+            return Ok(());
+        }
+
+        if this.push_mixin_operands(lhs, rhs) {
+            return this.binary_add();
+        }
 
         let lhs_type = lhs.py_type(this.heap);
         let rhs_type = rhs.py_type(this.heap);
         Err(ExcType::binary_type_error("+=", lhs_type, rhs_type))
     }
+
+    /// Pushes the operands back with `IntEnum` and `StrEnum` members replaced by their
+    /// values, returning whether either operand was such a member.
+    ///
+    /// Operators which don't support their operands retry with these when it returns true.
+    fn push_mixin_operands(&mut self, lhs: &Value, rhs: &Value) -> bool {
+        match r#enum::mixin_operands(lhs, rhs, self.heap, self.interns) {
+            Some((lhs, rhs)) => {
+                self.push(lhs);
+                self.push(rhs);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replaces an `IntEnum` or `StrEnum` member with its value, for unary operators.
+    pub(super) fn mixin_operand(&mut self, value: Value) -> Value {
+        match r#enum::owned_mixin_value(&value, self.heap, self.interns) {
+            Some(inner) => {
+                value.drop_with_heap(self.heap);
+                inner
+            }
+            None => value,
+        }
+    }
+
+    /// Applies a set operator when either operand is a keys or items dict view.
+    ///
+    /// Returns `Ok(None)` for other operands, so the caller can report the usual TypeError.
+    fn dict_view_set_op(&mut self, lhs: &Value, rhs: &Value, op: SetOp) -> RunResult<Option<Value>> {
+        if !is_set_like_view(lhs, self.heap) && !is_set_like_view(rhs, self.heap) {
+            return Ok(None);
+        }
+        let lhs = lhs.clone_with_heap(self.heap);
+        let rhs = rhs.clone_with_heap(self.heap);
+        set_operation(lhs, rhs, op, self.heap, self.interns).map(Some)
+    }
+}
+
+/// Applies an arithmetic operator defined by a stdlib type: datetime arithmetic, decimals
+/// and fractions.
+///
+/// Returns `Ok(None)` when none of them supports the operands.
+fn stdlib_binary_op(
+    lhs: &Value,
+    rhs: &Value,
+    op: &Operator,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> RunResult<Option<Value>> {
+    if let Some(value) = datetime::binary_op(lhs, rhs, op, heap)? {
+        return Ok(Some(value));
+    }
+    if let Some(value) = decimal::binary_op(lhs, rhs, op, heap)? {
+        return Ok(Some(value));
+    }
+    fraction::binary_op(lhs, rhs, op, heap)
 }
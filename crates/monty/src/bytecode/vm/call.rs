@@ -4,7 +4,10 @@
 //! functions for executing function calls. The main entry points are the `exec_*`
 //! methods which are called from the VM's main dispatch loop.
 
-use super::{CallFrame, VM};
+use super::{
+    CallFrame, VM,
+    iterator::{method_iterable_args, module_function_iterable_args},
+};
 use crate::{
     args::{ArgValues, KwargsValues},
     asyncio::Coroutine,
@@ -13,14 +16,24 @@ use crate::{
     heap::{DropWithHeap, Heap, HeapData, HeapId},
     intern::{ExtFunctionId, FunctionId, Interns, StaticStrings, StringId},
     io::PrintWriter,
+    modules::{
+        ModuleFunctions, functools::FunctoolsFunctions, itertools::ItertoolsFunctions, json::JsonFunctions,
+        re::ReFunctions, sys, textwrap::TextwrapFunctions, uuid::UuidFunctions,
+    },
     os::OsFunction,
     resource::ResourceTracker,
     types::{
-        AttrCallResult, Dict, PyTrait, Type,
+        AttrCallResult, Dict, DictView, DictViewKind, PyTrait, Type,
+        bytearray::bytes_result_to_bytearray,
         bytes::{bytes_fromhex, call_bytes_method},
+        class::{self, ClassAttr},
+        datetime, decimal,
         dict::dict_fromkeys,
-        list::do_list_sort,
+        r#enum, fraction,
+        hashlib::call_hash_method,
+        re::call_pattern_method,
         str::call_str_method,
+        template::call_template_method,
     },
     value::{EitherStr, Value},
 };
@@ -37,6 +50,11 @@ pub(super) enum CallResult {
     FramePushed,
     /// External function call requested - VM should pause and return to caller.
     External(ExtFunctionId, ArgValues),
+    /// Call of a method implemented by the host, such as `__enter__` on a dataclass
+    /// returned by an external function - VM should yield `FrameExit::MethodCall` to host.
+    ///
+    /// The arguments start with the object the method is called on.
+    MethodCall(StringId, ArgValues),
     /// OS operation call requested - VM should yield `FrameExit::OsCall` to host.
     ///
     /// The host executes the OS operation and resumes the VM with the result.
@@ -79,7 +97,7 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         // Convert u8 to BuiltinsFunctions via FromRepr
         if let Some(builtin) = BuiltinsFunctions::from_repr(builtin_id) {
             let args = self.pop_n_args(arg_count);
-            builtin.call(self.heap, args, self.interns, self.print_writer)
+            self.call_builtin(Builtins::Function(builtin), args)
         } else {
             Err(RunError::internal("CallBuiltinFunction: invalid builtin_id"))
         }
@@ -93,7 +111,7 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         // Convert u8 to Type via callable_from_u8
         if let Some(t) = Type::callable_from_u8(type_id) {
             let args = self.pop_n_args(arg_count);
-            t.call(self.heap, args, self.interns)
+            self.call_builtin(Builtins::Type(t), args)
         } else {
             Err(RunError::internal("CallBuiltinType: invalid type_id"))
         }
@@ -256,19 +274,135 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
     /// For interned strings (`Value::InternString`), uses the unified `call_str_method`.
     /// For interned bytes (`Value::InternBytes`), uses the unified `call_bytes_method`.
     ///
+    /// Methods listed by a dataclass that came from the host are called on the host,
+    /// by returning `CallResult::MethodCall`.
+    ///
     /// Special handling: `list.sort(key=...)` is intercepted here to allow calling
-    /// builtin key functions with VM access.
+    /// key functions with VM access, and `str.format()` and `str.format_map()` so
+    /// fields can call `__str__` and `__repr__`. `dict.keys()`, `dict.values()` and
+    /// `dict.items()` are too, since their views need the dict's heap id. Generator
+    /// methods, and generator or function-calling iterator arguments to methods
+    /// consuming iterables, are also handled here, since advancing them runs Python code.
+    /// Methods of `sys.stdout` and `sys.stderr` need the print writer, so they are too.
+    /// Keys passed to dict and set methods may be instances calling `__hash__` and `__eq__`.
+    /// Pattern methods need the pattern's heap id for the matches they create, and `sub()`
+    /// and `subn()` of patterns may call a function for each match. Module attributes are
+    /// called through `call_function()`, since they may be types or functions run by the VM,
+    /// as is `chain.from_iterable()`.
     fn call_attr(&mut self, obj: Value, name_id: StringId, args: ArgValues) -> Result<CallResult, RunError> {
         let attr = EitherStr::Interned(name_id);
 
+        let args = match method_iterable_args(name_id) {
+            Some(which) if !self.is_user_defined(&obj) && self.has_vm_iterator_arg(&args) => {
+                match self.materialize_vm_iterator_args(args, which) {
+                    Ok(args) => args,
+                    Err(e) => {
+                        obj.drop_with_heap(self.heap);
+                        return Err(e);
+                    }
+                }
+            }
+            _ => args,
+        };
+
+        // Replacement fields may call `__str__` or `__repr__` on instances of user-defined classes
+        if (name_id == StaticStrings::Format || name_id == StaticStrings::FormatMap) && obj.is_str(self.heap) {
+            return self.call_str_format(obj, name_id, args).map(CallResult::Push);
+        }
+
         match obj {
             Value::Ref(heap_id) => {
+                // Attributes of user-defined classes, instances and `super()` may be Python functions
+                if class::has_class_attrs(self.heap.get(heap_id)) {
+                    return self.call_class_attr(heap_id, obj, name_id, args);
+                }
+                // Methods of host objects are run by the host
+                if let HeapData::Dataclass(dc) = self.heap.get(heap_id)
+                    && dc.methods().contains(self.interns.get_str(name_id))
+                {
+                    return Ok(CallResult::MethodCall(name_id, args.prepend(obj)));
+                }
+                // Generator methods resume the generator's frame
+                if matches!(self.heap.get(heap_id), HeapData::Generator(_)) {
+                    let result = self.call_generator_method(heap_id, name_id, args);
+                    obj.drop_with_heap(self.heap);
+                    return result.map(CallResult::Push);
+                }
+                // Exception group methods may call a Python function for each sub-exception
+                if (name_id == StaticStrings::Split || name_id == StaticStrings::Subgroup)
+                    && let HeapData::Exception(exc) = self.heap.get(heap_id)
+                    && exc.exc_type().is_group()
+                {
+                    let result = self.call_group_method(heap_id, name_id, args);
+                    obj.drop_with_heap(self.heap);
+                    return result.map(CallResult::Push);
+                }
+                // Dict views take over the reference to the dict itself
+                if let Some(kind) = DictViewKind::from_method(name_id)
+                    && matches!(self.heap.get(heap_id), HeapData::Dict(_))
+                {
+                    return DictView::call(obj, kind, args, self.heap).map(CallResult::Push);
+                }
+                // Module attributes may be types, or functions run by the VM, so they're called like any callable
+                if let HeapData::Module(module) = self.heap.get(heap_id) {
+                    let func = module
+                        .attrs()
+                        .get_by_str(self.interns.get_str(name_id), self.heap, self.interns)
+                        .map(Value::copy_for_extend);
+                    if let Some(func) = func {
+                        if let Value::Ref(id) = func {
+                            self.heap.inc_ref(id);
+                        }
+                        obj.drop_with_heap(self.heap);
+                        return self.call_function(func, args);
+                    }
+                }
+                // Pattern methods create matches referencing the pattern, and `sub()` may call a function
+                if matches!(self.heap.get(heap_id), HeapData::RePattern(_)) {
+                    let result = if name_id == StaticStrings::Sub || name_id == StaticStrings::Subn {
+                        self.call_pattern_sub(heap_id, name_id == StaticStrings::Subn, args)
+                    } else {
+                        call_pattern_method(self.heap, heap_id, &attr, args, self.interns)
+                    };
+                    obj.drop_with_heap(self.heap);
+                    return result.map(CallResult::Push);
+                }
+                // Hash and template methods read their arguments while the object stays on the heap
+                if matches!(self.heap.get(heap_id), HeapData::Hash(_)) {
+                    let result = call_hash_method(self.heap, heap_id, &attr, args, self.interns);
+                    obj.drop_with_heap(self.heap);
+                    return result.map(CallResult::Push);
+                }
+                if matches!(self.heap.get(heap_id), HeapData::Template(_)) {
+                    let result = call_template_method(self.heap, heap_id, &attr, args, self.interns);
+                    obj.drop_with_heap(self.heap);
+                    return result.map(CallResult::Push);
+                }
+                // `Random.seed()` without a seed asks the host for one
+                if name_id == StaticStrings::Seed && matches!(self.heap.get(heap_id), HeapData::Random(_)) {
+                    let result = self.call_random_seed(Some(heap_id), args);
+                    obj.drop_with_heap(self.heap);
+                    return result;
+                }
                 // Check for list.sort - needs special handling for key functions
                 if name_id == StaticStrings::Sort && matches!(self.heap.get(heap_id), HeapData::List(_)) {
-                    let result = do_list_sort(heap_id, args, self.heap, self.interns, self.print_writer);
-                    obj.drop_with_heap(self.heap);
-                    return result.map(|()| CallResult::Push(Value::None));
+                    return self.call_list_sort(obj, args);
                 }
+                // Keys of dict and set methods may be instances with `__hash__` and `__eq__`
+                let args = if matches!(
+                    self.heap.get(heap_id),
+                    HeapData::Dict(_) | HeapData::Set(_) | HeapData::FrozenSet(_)
+                ) {
+                    match self.resolve_key_args(heap_id, args) {
+                        Ok(args) => args,
+                        Err(e) => {
+                            obj.drop_with_heap(self.heap);
+                            return Err(e);
+                        }
+                    }
+                } else {
+                    args
+                };
                 // Call the method on the heap object using call_attr_raw to support OS/external calls
                 let result = self.heap.call_attr_raw(heap_id, &attr, args, self.interns);
                 obj.drop_with_heap(self.heap);
@@ -286,10 +420,28 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
                 call_bytes_method(b, name_id, args, self.heap, self.interns).map(CallResult::Push)
             }
             Value::Builtin(Builtins::Type(t)) => {
+                // `datetime.now()` and `date.today()` ask the host for the current time
+                if datetime::is_now_method(t, name_id) {
+                    let (request, args) = datetime::start_now(t, name_id, args, self.heap, self.interns)?;
+                    self.pending_now = Some(request);
+                    return Ok(CallResult::OsCall(OsFunction::DateTimeNow, args));
+                }
                 // Handle classmethods on type objects like dict.fromkeys()
                 call_type_method(t, name_id, args, self.heap, self.interns).map(CallResult::Push)
             }
+            Value::ModuleFunction(ModuleFunctions::Itertools(ItertoolsFunctions::Chain))
+                if name_id == StaticStrings::FromIterable =>
+            {
+                self.call_chain_from_iterable(args).map(CallResult::Push)
+            }
             _ => {
+                // `sys.stdout` and `sys.stderr` write to the host's print writer
+                if let Value::Marker(marker) = obj
+                    && let Some(stream) = sys::marker_stream(marker)
+                {
+                    return sys::call_stream_method(stream, name_id, args, self.heap, self.interns, self.print_writer)
+                        .map(CallResult::Push);
+                }
                 // Non-heap values without method support
                 let type_name = obj.py_type(self.heap);
                 args.drop_with_heap(self.heap);
@@ -298,22 +450,104 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         }
     }
 
+    /// Calls an attribute of a user-defined class, instance or `super()` proxy.
+    ///
+    /// Functions found on the class are called with the instance prepended to `args`.
+    /// `super().__init__()` with no `__init__` in the base classes calls `object.__init__`,
+    /// which does nothing.
+    fn call_class_attr(
+        &mut self,
+        heap_id: HeapId,
+        obj: Value,
+        name_id: StringId,
+        args: ArgValues,
+    ) -> Result<CallResult, RunError> {
+        let attr = class::lookup_attr(heap_id, name_id, self.heap, self.interns);
+        match attr {
+            Some(ClassAttr::Value(func)) => {
+                obj.drop_with_heap(self.heap);
+                self.call_function(func, args)
+            }
+            Some(ClassAttr::Method { func, self_obj }) => {
+                obj.drop_with_heap(self.heap);
+                self.call_function(func, args.prepend(self_obj))
+            }
+            None if name_id == StaticStrings::DunderInit
+                && matches!(self.heap.get(heap_id), HeapData::SuperProxy(_)) =>
+            {
+                obj.drop_with_heap(self.heap);
+                args.drop_with_heap(self.heap);
+                Ok(CallResult::Push(Value::None))
+            }
+            None => {
+                // `IntEnum` and `StrEnum` members have the methods of their values
+                if let Some(inner) = r#enum::owned_mixin_value(&obj, self.heap, self.interns) {
+                    obj.drop_with_heap(self.heap);
+                    return self.call_attr(inner, name_id, args);
+                }
+                let error = class::attr_error(heap_id, name_id, self.heap, self.interns);
+                obj.drop_with_heap(self.heap);
+                args.drop_with_heap(self.heap);
+                Err(error)
+            }
+        }
+    }
+
     /// Calls a callable value with the given arguments.
     ///
     /// Dispatches based on the callable type:
-    /// - `Value::Builtin`: calls builtin directly, returns `Push`
-    /// - `Value::ModuleFunction`: calls module function directly, returns `Push`
+    /// - `Value::Builtin`: calls builtin directly, returns `Push`; `sorted()`, `min()` and
+    ///   `max()` may also return a host call made by their key function
+    /// - `Value::ModuleFunction`: calls module function directly, returns `Push`; generator
+    ///   arguments of functions consuming whole iterables, like `math.fsum()`, are collected first;
+    ///   `json.dumps()`, `re.sub()` and `re.subn()` are run by the VM since they may call Python
+    ///   functions; the `itertools` functions and `functools.reduce()` are run by the VM too, and the
+    ///   `random` functions may return an OS call asking the host for a seed; `textwrap.indent()`
+    ///   may call its predicate, and `uuid.uuid4()` returns an OS call asking the host for random
+    ///   bytes
     /// - `Value::ExtFunction`: returns `External` for caller to execute
     /// - `Value::DefFunction`: pushes a new frame, returns `FramePushed`
-    /// - `Value::Ref`: checks for closure/function on heap
-    fn call_function(&mut self, callable: Value, args: ArgValues) -> Result<CallResult, RunError> {
+    /// - `Value::Ref`: checks for closure/function, class or bound method on heap
+    pub(super) fn call_function(&mut self, callable: Value, args: ArgValues) -> Result<CallResult, RunError> {
+        let args = match callable {
+            Value::ModuleFunction(mf) if self.has_vm_iterator_arg(&args) => match module_function_iterable_args(mf) {
+                Some(which) => self.materialize_vm_iterator_args(args, which)?,
+                None => args,
+            },
+            _ => args,
+        };
         match callable {
+            Value::Builtin(Builtins::Function(
+                builtin @ (BuiltinsFunctions::Sorted | BuiltinsFunctions::Min | BuiltinsFunctions::Max),
+            )) => self.call_key_builtin(builtin, args),
+            Value::Builtin(Builtins::Type(Type::Random)) => self.call_random_type(args),
             Value::Builtin(builtin) => {
-                let result = builtin.call(self.heap, args, self.interns, self.print_writer)?;
+                let result = self.call_builtin(builtin, args)?;
                 Ok(CallResult::Push(result))
             }
+            Value::ModuleFunction(ModuleFunctions::Json(JsonFunctions::Dumps)) => {
+                self.call_json_dumps(args).map(CallResult::Push)
+            }
+            Value::ModuleFunction(ModuleFunctions::Re(function @ (ReFunctions::Sub | ReFunctions::Subn))) => {
+                self.call_re_sub(function, args).map(CallResult::Push)
+            }
+            Value::ModuleFunction(ModuleFunctions::Itertools(function)) => {
+                self.call_itertools(function, args).map(CallResult::Push)
+            }
+            Value::ModuleFunction(ModuleFunctions::Functools(FunctoolsFunctions::Reduce)) => {
+                self.call_reduce(args).map(CallResult::Push)
+            }
+            Value::ModuleFunction(ModuleFunctions::Random(function)) => self.call_random(function, args),
+            Value::ModuleFunction(ModuleFunctions::Textwrap(TextwrapFunctions::Indent)) => {
+                self.call_indent(args).map(CallResult::Push)
+            }
+            Value::ModuleFunction(ModuleFunctions::Uuid(UuidFunctions::Uuid4)) => {
+                args.check_zero_args("uuid4", self.heap)?;
+                self.pending_uuid4 = true;
+                Ok(CallResult::OsCall(OsFunction::Uuid4, ArgValues::Empty))
+            }
             Value::ModuleFunction(mf) => {
-                let result = mf.call(self.heap, args)?;
+                let result = mf.call(self.heap, args, self.interns)?;
                 Ok(result.into())
             }
             Value::ExtFunction(ext_id) => {
@@ -335,7 +569,9 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         }
     }
 
-    /// Handles calling a heap-allocated callable (closure or function with defaults).
+    /// Handles calling a heap-allocated callable (closure, function with defaults,
+    /// user-defined class, bound method, named tuple type, or `functools` partial object
+    /// or cached function).
     ///
     /// Uses a two-phase approach to avoid borrow conflicts:
     /// 1. Copy data without incrementing refcounts
@@ -346,6 +582,49 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         callable: Value,
         args: ArgValues,
     ) -> Result<CallResult, RunError> {
+        match self.heap.get(heap_id) {
+            HeapData::ClassObject(_) => return self.call_class(heap_id, callable, args),
+            HeapData::BoundMethod(method) => {
+                let func = method.func().copy_for_extend();
+                let self_obj = method.self_obj().copy_for_extend();
+                for value in [&func, &self_obj] {
+                    if let Value::Ref(id) = value {
+                        self.heap.inc_ref(*id);
+                    }
+                }
+                callable.drop_with_heap(self.heap);
+                return self.call_function(func, args.prepend(self_obj));
+            }
+            HeapData::NamedTupleType(_) => {
+                let interns = self.interns;
+                let result = self.heap.with_entry_mut(heap_id, |heap, data| match data {
+                    HeapData::NamedTupleType(t) => t.call(args, heap, interns),
+                    _ => unreachable!("entry was checked to be a named tuple type"),
+                });
+                callable.drop_with_heap(self.heap);
+                return result.map(CallResult::Push);
+            }
+            HeapData::Partial(_) => {
+                let interns = self.interns;
+                let bound = self.heap.with_entry_mut(heap_id, |heap, data| match data {
+                    HeapData::Partial(partial) => partial.bind(args, heap, interns),
+                    _ => unreachable!("entry was checked to be a partial object"),
+                });
+                callable.drop_with_heap(self.heap);
+                let (func, args) = bound?;
+                return self.call_function(func, args);
+            }
+            HeapData::LruCacheWrapper(_) => return self.call_cached(heap_id, callable, args),
+            HeapData::CmpToKey(_) => {
+                callable.drop_with_heap(self.heap);
+                args.drop_with_heap(self.heap);
+                return Err(ExcType::type_error(
+                    "cmp_to_key() keys can only be used by sorted(), min(), max() and list.sort()",
+                ));
+            }
+            _ => {}
+        }
+
         // Phase 1: Copy data (func_id, cells, defaults) without refcount changes
         let (func_id, cells, defaults) = match self.heap.get(heap_id) {
             HeapData::Closure(fid, cells, defaults) => {
@@ -614,6 +893,8 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
     /// For async functions: binds arguments immediately but returns a Coroutine
     /// instead of pushing a frame. The coroutine stores the pre-bound namespace
     /// and will be executed when awaited.
+    ///
+    /// Generator functions are handled like async functions, returning a Generator.
     fn call_def_function(
         &mut self,
         func_id: FunctionId,
@@ -627,6 +908,9 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         if func.is_async {
             // Async function: create a Coroutine instead of pushing a frame
             self.create_coroutine(func_id, cells, defaults, args)
+        } else if func.is_generator {
+            // Generator function: create a Generator instead of pushing a frame
+            self.create_generator(func_id, cells, defaults, args)
         } else {
             // Sync function: push a new frame
             self.call_sync_function(func_id, cells, defaults, args)
@@ -645,6 +929,26 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         defaults: Vec<Value>,
         args: ArgValues,
     ) -> Result<CallResult, RunError> {
+        let (namespace, frame_cells) = self.bind_detached_namespace(func_id, cells, defaults, args)?;
+
+        // Create Coroutine on heap
+        let coroutine = Coroutine::new(func_id, namespace, frame_cells);
+        let coroutine_id = self.heap.allocate(HeapData::Coroutine(coroutine))?;
+
+        Ok(CallResult::Push(Value::Ref(coroutine_id)))
+    }
+
+    /// Binds a call's arguments into a namespace that isn't registered with `Namespaces`.
+    ///
+    /// Used by calls that don't run their function immediately (coroutines and generators).
+    /// Returns the namespace values together with the HeapIds of the cells for the frame.
+    pub(super) fn bind_detached_namespace(
+        &mut self,
+        func_id: FunctionId,
+        cells: &[HeapId],
+        defaults: Vec<Value>,
+        args: ArgValues,
+    ) -> Result<(Vec<Value>, Vec<HeapId>), RunError> {
         let func = self.interns.get_function(func_id);
 
         // 1. Create namespace vector (not registered with Namespaces)
@@ -673,7 +977,7 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
             default.drop_with_heap(self.heap);
         }
 
-        // Track created cell HeapIds for the coroutine
+        // Track created cell HeapIds for the frame
         let mut frame_cells: Vec<HeapId> = Vec::with_capacity(func.cell_var_count + cells.len());
 
         // 3. Create cells for variables captured by nested functions
@@ -706,11 +1010,7 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
             namespace.resize_with(func.namespace_size, || Value::Undefined);
         }
 
-        // 6. Create Coroutine on heap
-        let coroutine = Coroutine::new(func_id, namespace, frame_cells);
-        let coroutine_id = self.heap.allocate(HeapData::Coroutine(coroutine))?;
-
-        Ok(CallResult::Push(Value::Ref(coroutine_id)))
+        Ok((namespace, frame_cells))
     }
 
     /// Calls a sync function by pushing a new frame.
@@ -792,6 +1092,7 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         self.frames.push(CallFrame::new_function(
             code,
             self.stack.len(),
+            self.exception_stack.len(),
             namespace_idx,
             func_id,
             frame_cells,
@@ -816,6 +1117,13 @@ fn call_type_method(
     match (t, method_id) {
         (Type::Dict, m) if m == StaticStrings::Fromkeys => return dict_fromkeys(args, heap, interns),
         (Type::Bytes, m) if m == StaticStrings::Fromhex => return bytes_fromhex(args, heap, interns),
+        (Type::ByteArray, m) if m == StaticStrings::Fromhex => {
+            return bytes_result_to_bytearray(bytes_fromhex(args, heap, interns)?, heap);
+        }
+        (t, m) if datetime::is_class_method(t, m) => return datetime::call_class_method(t, m, args, heap, interns),
+        (Type::Decimal, m) if m == StaticStrings::FromFloat => return decimal::from_float(args, heap, interns),
+        (Type::Fraction, m) if m == StaticStrings::FromFloat => return fraction::from_float(args, heap, interns),
+        (Type::Fraction, m) if m == StaticStrings::FromDecimal => return fraction::from_decimal(args, heap, interns),
         _ => {}
     }
     // Other types or unknown methods - report actual type name, not 'type'
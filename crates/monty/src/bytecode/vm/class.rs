@@ -0,0 +1,1036 @@
+//! User-defined class support for the VM.
+//!
+//! Class bodies run as zero-argument functions which end in `LoadLocals`; `BuildClass`
+//! then turns the resulting namespace dict into a [`ClassObject`]. Special methods such
+//! as `__init__`, `__repr__` and `__eq__` are Python functions, so the builtins and
+//! operators that use them are routed through here, calling back into the run loop via
+//! [`VM::call_sync`].
+
+use ahash::AHashSet;
+
+use super::{FrameExit, VM, call::CallResult, iterator::builtin_iterable_args};
+use crate::{
+    args::ArgValues,
+    builtins::{Builtins, BuiltinsFunctions},
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, HeapData, HeapId},
+    intern::{FunctionId, StaticStrings},
+    io::PrintWriter,
+    namespace::NamespaceId,
+    resource::ResourceTracker,
+    types::{
+        ClassObject, Dict, FrozenSet, Instance, PyTrait, Set, SuperProxy, Type,
+        class::{class_lookup, class_name, function_id, is_instance_hashable, is_method_function, is_subclass},
+        r#enum::{self, EnumInfo, EnumKind},
+        str::allocate_string,
+    },
+    value::Value,
+};
+
+impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
+    /// Executes `LoadLocals`: pushes a dict of the class body's local variables.
+    ///
+    /// Class bodies have no parameters, so every local slot after the cell and free
+    /// variable slots is a class attribute. Unassigned slots are skipped.
+    pub(super) fn load_locals(&mut self) -> RunResult<()> {
+        let frame = self.current_frame();
+        let func_id = frame.function_id.expect("LoadLocals outside a class body");
+        let namespace_idx = frame.namespace_idx;
+        let func = self.interns.get_function(func_id);
+        let start = func.signature.total_slots() + func.cell_var_count + func.free_var_enclosing_slots.len();
+
+        let namespace = self.namespaces.get(namespace_idx);
+        let mut pairs = Vec::new();
+        for slot in start..func.namespace_size {
+            let value = namespace.get(NamespaceId::new(slot));
+            if matches!(value, Value::Undefined) {
+                continue;
+            }
+            let slot = u16::try_from(slot).expect("local slot exceeds u16");
+            if let Some(name_id) = func.code.local_name(slot) {
+                pairs.push((Value::InternString(name_id), value.copy_for_extend()));
+            }
+        }
+        for (_, value) in &pairs {
+            if let Value::Ref(id) = value {
+                self.heap.inc_ref(*id);
+            }
+        }
+
+        let dict = Dict::from_pairs(pairs, self.heap, self.interns)?;
+        let heap_id = self.heap.allocate(HeapData::Dict(dict))?;
+        self.push(Value::Ref(heap_id));
+        Ok(())
+    }
+
+    /// Executes `BuildClass`: pops the namespace dict, base and name, and pushes a new class.
+    ///
+    /// Defining `__eq__` without `__hash__` sets `__hash__ = None`, making instances
+    /// unhashable as in CPython. Classes deriving from `enum.Enum`, `enum.IntEnum` or
+    /// `enum.StrEnum` turn their attributes into members.
+    pub(super) fn build_class(&mut self) -> RunResult<()> {
+        let namespace = self.pop();
+        #[cfg_attr(not(feature = "ref-count-panic"), expect(unused_mut))]
+        let mut base = self.pop();
+        let name = self.pop();
+        let (Value::InternString(name_id), Value::Ref(namespace_id)) = (&name, &namespace) else {
+            namespace.drop_with_heap(self.heap);
+            base.drop_with_heap(self.heap);
+            name.drop_with_heap(self.heap);
+            return Err(RunError::internal("BuildClass: invalid class name or namespace"));
+        };
+        let (name_id, namespace_id) = (*name_id, *namespace_id);
+
+        let (base_id, enum_kind) = match &base {
+            Value::None | Value::Builtin(Builtins::Type(Type::Object)) => (None, None),
+            Value::Builtin(Builtins::Type(t)) if EnumKind::from_type(*t).is_some() => (None, EnumKind::from_type(*t)),
+            Value::Ref(id) if matches!(self.heap.get(*id), HeapData::ClassObject(_)) => {
+                let id = *id;
+                let HeapData::ClassObject(cls) = self.heap.get(id) else {
+                    unreachable!("base checked to be a class")
+                };
+                let enum_info = cls.enum_info();
+                if enum_info.is_some_and(|info| !info.members().is_empty()) {
+                    let error = ExcType::type_error(format!(
+                        "<enum '{}'> cannot extend {}",
+                        self.interns.get_str(name_id),
+                        base.py_repr(self.heap, self.interns)
+                    ));
+                    namespace.drop_with_heap(self.heap);
+                    base.drop_with_heap(self.heap);
+                    return Err(error);
+                }
+                let enum_kind = enum_info.map(EnumInfo::kind);
+                // The base's reference is transferred to the new class
+                #[cfg(feature = "ref-count-panic")]
+                base.dec_ref_forget();
+                (Some(id), enum_kind)
+            }
+            other => {
+                let error = match other {
+                    Value::Builtin(Builtins::Type(t)) => {
+                        ExcType::type_error(format!("subclassing '{t}' is not supported"))
+                    }
+                    Value::Builtin(Builtins::ExcType(e)) => {
+                        ExcType::type_error(format!("subclassing '{e}' is not supported"))
+                    }
+                    _ => ExcType::type_error("bases must be types"),
+                };
+                namespace.drop_with_heap(self.heap);
+                base.drop_with_heap(self.heap);
+                return Err(error);
+            }
+        };
+
+        // Copy the namespace entries into the class attributes
+        let HeapData::Dict(dict) = self.heap.get(namespace_id) else {
+            unreachable!("BuildClass: namespace must be a dict")
+        };
+        let pairs: Vec<(Value, Value)> = dict
+            .iter()
+            .map(|(k, v)| (k.copy_for_extend(), v.copy_for_extend()))
+            .collect();
+        let has_eq = dict.get_by_str("__eq__", self.heap, self.interns).is_some();
+        let has_hash = dict.get_by_str("__hash__", self.heap, self.interns).is_some();
+        for (key, value) in &pairs {
+            for v in [key, value] {
+                if let Value::Ref(id) = v {
+                    self.heap.inc_ref(*id);
+                }
+            }
+        }
+        namespace.drop_with_heap(self.heap);
+
+        let attrs = Dict::from_pairs(pairs, self.heap, self.interns);
+        let mut attrs = match attrs {
+            Ok(attrs) => attrs,
+            Err(e) => {
+                if let Some(id) = base_id {
+                    self.heap.dec_ref(id);
+                }
+                return Err(e);
+            }
+        };
+        if has_eq && !has_hash {
+            let key = Value::InternString(StaticStrings::DunderHash.into());
+            attrs.set(key, Value::None, self.heap, self.interns)?;
+        }
+
+        let class_id = self.heap.allocate(HeapData::ClassObject(ClassObject::new(
+            name_id, base_id, attrs, enum_kind,
+        )))?;
+        if enum_kind.is_some()
+            && let Err(e) = r#enum::create_members(class_id, self.heap, self.interns)
+        {
+            Value::Ref(class_id).drop_with_heap(self.heap);
+            return Err(e);
+        }
+        self.push(Value::Ref(class_id));
+        Ok(())
+    }
+
+    /// Calls a class, creating a new instance and running `__init__` if defined.
+    ///
+    /// When `__init__` is a Python function its frame is marked with `is_init`, so that
+    /// `ReturnValue` checks it returned `None` and replaces the result with the instance,
+    /// which is kept on the stack below the new frame.
+    pub(super) fn call_class(
+        &mut self,
+        class_id: HeapId,
+        callable: Value,
+        args: ArgValues,
+    ) -> Result<CallResult, RunError> {
+        // Calling an enum class looks up a member by value
+        if r#enum::enum_kind(class_id, self.heap).is_some() {
+            let result = r#enum::call_enum_class(class_id, args, self.heap, self.interns);
+            callable.drop_with_heap(self.heap);
+            return result.map(CallResult::Push);
+        }
+        let init = class_lookup(Some(class_id), "__init__", self.heap, self.interns).map(Value::copy_for_extend);
+
+        // The callable's reference to the class is transferred to the instance
+        let instance_id = match self.heap.allocate(HeapData::Instance(Instance::new(class_id))) {
+            Ok(id) => id,
+            Err(e) => {
+                callable.drop_with_heap(self.heap);
+                args.drop_with_heap(self.heap);
+                return Err(e.into());
+            }
+        };
+        #[cfg_attr(not(feature = "ref-count-panic"), expect(unused_mut))]
+        let mut callable = callable;
+        #[cfg(feature = "ref-count-panic")]
+        callable.dec_ref_forget();
+
+        let Some(init) = init else {
+            if matches!(args, ArgValues::Empty) {
+                return Ok(CallResult::Push(Value::Ref(instance_id)));
+            }
+            args.drop_with_heap(self.heap);
+            let name = self.interns.get_str(class_name(class_id, self.heap));
+            let error = ExcType::type_error(format!("{name}() takes no arguments"));
+            Value::Ref(instance_id).drop_with_heap(self.heap);
+            return Err(error);
+        };
+        if let Value::Ref(id) = &init {
+            self.heap.inc_ref(*id);
+        }
+
+        self.push(Value::Ref(instance_id));
+        self.heap.inc_ref(instance_id);
+        match self.call_function(init, args.prepend(Value::Ref(instance_id))) {
+            Ok(CallResult::FramePushed) => {
+                self.current_frame_mut().is_init = true;
+                Ok(CallResult::FramePushed)
+            }
+            Ok(CallResult::Push(result)) => {
+                let instance = self.pop();
+                if matches!(result, Value::None) {
+                    Ok(CallResult::Push(instance))
+                } else {
+                    let result_type = result.py_type(self.heap);
+                    result.drop_with_heap(self.heap);
+                    instance.drop_with_heap(self.heap);
+                    Err(ExcType::type_error_init_return(result_type))
+                }
+            }
+            Ok(CallResult::External(_, args) | CallResult::MethodCall(_, args) | CallResult::OsCall(_, args)) => {
+                args.drop_with_heap(self.heap);
+                self.abandon_os_request();
+                let instance = self.pop();
+                instance.drop_with_heap(self.heap);
+                Err(ExcType::type_error("__init__ must be a Python function"))
+            }
+            Err(e) => {
+                let instance = self.pop();
+                instance.drop_with_heap(self.heap);
+                Err(e)
+            }
+        }
+    }
+
+    /// Implements `super()`, in both the zero-argument and `super(cls, obj)` forms.
+    ///
+    /// The zero-argument form uses the first argument of the running function as the
+    /// instance, and finds the class that defined the running function by walking the
+    /// instance's class hierarchy.
+    fn call_super(&mut self, args: ArgValues) -> RunResult<Value> {
+        let (class_id, self_obj) = match args {
+            ArgValues::Empty => {
+                let no_args =
+                    || -> RunError { SimpleException::new_msg(ExcType::RuntimeError, "super(): no arguments").into() };
+                let frame = self.current_frame();
+                let Some(func_id) = frame.function_id else {
+                    return Err(no_args());
+                };
+                if self.interns.get_function(func_id).signature.total_slots() == 0 {
+                    return Err(no_args());
+                }
+                let self_obj = self
+                    .namespaces
+                    .get(frame.namespace_idx)
+                    .get(NamespaceId::new(0))
+                    .copy_for_extend();
+                let Value::Ref(self_id) = self_obj else {
+                    return Err(no_args());
+                };
+                let HeapData::Instance(inst) = self.heap.get(self_id) else {
+                    return Err(no_args());
+                };
+                let Some(class_id) = self.defining_class(inst.class_id(), func_id) else {
+                    return Err(
+                        SimpleException::new_msg(ExcType::RuntimeError, "super(): __class__ cell not found").into(),
+                    );
+                };
+                self.heap.inc_ref(class_id);
+                self.heap.inc_ref(self_id);
+                (class_id, self_obj)
+            }
+            ArgValues::Two(cls, obj) => {
+                let class_id = match &cls {
+                    Value::Ref(id) if matches!(self.heap.get(*id), HeapData::ClassObject(_)) => *id,
+                    _ => {
+                        let cls_type = cls.py_type(self.heap);
+                        cls.drop_with_heap(self.heap);
+                        obj.drop_with_heap(self.heap);
+                        return Err(ExcType::type_error(format!(
+                            "super() argument 1 must be a type, not {cls_type}"
+                        )));
+                    }
+                };
+                let is_valid = match &obj {
+                    Value::Ref(id) => match self.heap.get(*id) {
+                        HeapData::Instance(inst) => is_subclass(inst.class_id(), class_id, self.heap),
+                        _ => false,
+                    },
+                    _ => false,
+                };
+                if !is_valid {
+                    cls.drop_with_heap(self.heap);
+                    obj.drop_with_heap(self.heap);
+                    return Err(ExcType::type_error(
+                        "super(type, obj): obj must be an instance or subtype of type",
+                    ));
+                }
+                // The class reference is transferred to the proxy
+                #[cfg(feature = "ref-count-panic")]
+                {
+                    let mut cls = cls;
+                    cls.dec_ref_forget();
+                }
+                (class_id, obj)
+            }
+            ArgValues::One(arg) => {
+                arg.drop_with_heap(self.heap);
+                return Err(ExcType::type_error("super() with one argument is not supported"));
+            }
+            other => {
+                other.drop_with_heap(self.heap);
+                return Err(ExcType::type_error("super() takes 0 or 2 arguments"));
+            }
+        };
+        let proxy_id = self
+            .heap
+            .allocate(HeapData::SuperProxy(SuperProxy::new(class_id, self_obj)))?;
+        Ok(Value::Ref(proxy_id))
+    }
+
+    /// Finds the class in `class_id`'s hierarchy whose attributes contain the function `func_id`.
+    fn defining_class(&self, class_id: HeapId, func_id: FunctionId) -> Option<HeapId> {
+        let mut current = Some(class_id);
+        while let Some(id) = current {
+            let HeapData::ClassObject(cls) = self.heap.get(id) else {
+                return None;
+            };
+            if cls
+                .attrs()
+                .iter()
+                .any(|(_, v)| function_id(v, self.heap) == Some(func_id))
+            {
+                return Some(id);
+            }
+            current = cls.base();
+        }
+        None
+    }
+
+    /// Calls a Python callable from Rust and runs it to completion, returning its result.
+    ///
+    /// Used by builtins and operators that invoke special methods (e.g. `__repr__`, `__eq__`).
+    /// If a frame is pushed, the run loop is re-entered; `ReturnValue` returns from the
+    /// nested loop when the frame pushed here finishes, and unhandled exceptions stop
+    /// unwinding at that frame so the error is raised in the calling frame.
+    ///
+    /// External function calls and OS calls can't be suspended from inside a nested call,
+    /// so they raise a `RuntimeError`.
+    pub(super) fn call_sync(&mut self, callable: Value, args: ArgValues) -> RunResult<Value> {
+        self.call_sync_or(callable, args, sync_call_suspend_error)
+    }
+
+    /// Like [`call_sync`](Self::call_sync), but raises `suspend_error()` if the call tries to
+    /// suspend, for callers whose callables aren't special methods.
+    pub(super) fn call_sync_or(
+        &mut self,
+        callable: Value,
+        args: ArgValues,
+        suspend_error: fn() -> RunError,
+    ) -> RunResult<Value> {
+        let stack_len = self.stack.len();
+        let base = self.frames.len();
+        match self.call_function(callable, args)? {
+            CallResult::Push(value) => Ok(value),
+            CallResult::FramePushed => self.run_nested(base, stack_len, None)?.ok_or_else(suspend_error),
+            CallResult::External(_, args) | CallResult::MethodCall(_, args) | CallResult::OsCall(_, args) => {
+                args.drop_with_heap(self.heap);
+                // A key-ordered builtin or a call asking the host for the time, a seed or random bytes
+                // may have been waiting for this call
+                if let Some(call) = self.key_call.take() {
+                    self.abandon_key_call(call);
+                }
+                self.abandon_os_request();
+                Err(suspend_error())
+            }
+        }
+    }
+
+    /// Runs the frame pushed on top of `base` frames in a nested run loop until it returns.
+    ///
+    /// If `throw` is given, it is raised in the pushed frame before running it, at the
+    /// instruction just before the frame's IP (the `yield` a generator is suspended at).
+    ///
+    /// Returns `Ok(None)` if execution tried to suspend for an external function or OS
+    /// call; the frames and stack values above `base` and `stack_len` are unwound first.
+    pub(super) fn run_nested(
+        &mut self,
+        base: usize,
+        stack_len: usize,
+        throw: Option<Value>,
+    ) -> RunResult<Option<Value>> {
+        self.sync_call_bases.push(base);
+        let instruction_ip = self.instruction_ip;
+        let result = match throw {
+            Some(exc) => {
+                self.instruction_ip = self.current_frame().ip - 1;
+                let error = self.make_exception(exc, true);
+                match self.handle_exception(error) {
+                    Some(error) => Err(error),
+                    None => self.run(),
+                }
+            }
+            None => self.run(),
+        };
+        self.sync_call_bases.pop();
+        self.instruction_ip = instruction_ip;
+        match result {
+            Ok(FrameExit::Return(value)) => Ok(Some(value)),
+            Ok(exit) => {
+                if let FrameExit::ExternalCall { args, .. }
+                | FrameExit::MethodCall { args, .. }
+                | FrameExit::OsCall { args, .. } = exit
+                {
+                    args.drop_with_heap(self.heap);
+                }
+                self.abandon_os_request();
+                self.unwind_sync_call(base, stack_len);
+                Ok(None)
+            }
+            Err(e) => {
+                self.unwind_sync_call(base, stack_len);
+                Err(e)
+            }
+        }
+    }
+
+    /// Pops frames and stack values left behind by a `call_sync` that didn't return normally.
+    fn unwind_sync_call(&mut self, base: usize, stack_len: usize) {
+        while self.frames.len() > base {
+            self.pop_frame();
+        }
+        while self.stack.len() > stack_len {
+            let value = self.pop();
+            value.drop_with_heap(self.heap);
+        }
+    }
+
+    /// Returns the special method `name` if `value` is an instance whose class defines it
+    /// as a Python function. The returned value is owned.
+    pub(super) fn instance_dunder(&mut self, value: &Value, name: StaticStrings) -> Option<Value> {
+        let Value::Ref(id) = value else {
+            return None;
+        };
+        let HeapData::Instance(inst) = self.heap.get(*id) else {
+            return None;
+        };
+        let method = class_lookup(
+            Some(inst.class_id()),
+            self.interns.get_str(name.into()),
+            self.heap,
+            self.interns,
+        )?;
+        if !is_method_function(method, self.heap) {
+            return None;
+        }
+        let method = method.copy_for_extend();
+        if let Value::Ref(id) = &method {
+            self.heap.inc_ref(*id);
+        }
+        Some(method)
+    }
+
+    /// Calls the special method `name` on `value`, with `other` as an optional argument.
+    ///
+    /// `IntEnum` and `StrEnum` members have the comparison methods of `int` and `str`, unless
+    /// their class defines its own.
+    ///
+    /// Returns `Ok(None)` if `value` isn't an instance whose class defines `name`.
+    pub(super) fn call_dunder(
+        &mut self,
+        value: &Value,
+        name: StaticStrings,
+        other: Option<&Value>,
+    ) -> RunResult<Option<Value>> {
+        let Some(method) = self.instance_dunder(value, name) else {
+            return Ok(other.and_then(|other| r#enum::mixin_dunder(value, name, other, self.heap, self.interns)));
+        };
+        let self_obj = value.clone_with_heap(self.heap);
+        let args = match other {
+            Some(other) => ArgValues::Two(self_obj, other.clone_with_heap(self.heap)),
+            None => ArgValues::One(self_obj),
+        };
+        self.call_sync(method, args).map(Some)
+    }
+
+    /// Returns whether `value` is an instance of a user-defined class, or a list, tuple
+    /// or dict containing one, in which case `repr()`, `str()` and `==` must go through the VM.
+    pub(super) fn contains_instance(&self, value: &Value) -> bool {
+        let mut visited = AHashSet::new();
+        self.contains_instance_inner(value, &mut visited)
+    }
+
+    fn contains_instance_inner(&self, value: &Value, visited: &mut AHashSet<HeapId>) -> bool {
+        let Value::Ref(id) = value else {
+            return false;
+        };
+        if !visited.insert(*id) {
+            return false;
+        }
+        match self.heap.get(*id) {
+            HeapData::Instance(_) => true,
+            HeapData::List(list) => list.as_vec().iter().any(|v| self.contains_instance_inner(v, visited)),
+            HeapData::Tuple(tuple) => tuple.as_vec().iter().any(|v| self.contains_instance_inner(v, visited)),
+            HeapData::Dict(dict) => dict
+                .iter()
+                .any(|(k, v)| self.contains_instance_inner(k, visited) || self.contains_instance_inner(v, visited)),
+            _ => false,
+        }
+    }
+
+    /// Returns `repr(value)`, calling `__repr__` on instances of user-defined classes,
+    /// including those nested in lists, tuples and dicts.
+    pub(super) fn repr_value(&mut self, value: &Value) -> RunResult<String> {
+        let mut out = String::new();
+        let mut visited = AHashSet::new();
+        self.repr_value_inner(value, &mut out, &mut visited)?;
+        Ok(out)
+    }
+
+    /// Returns `str(value)`, calling `__str__` (or `__repr__`) on instances of
+    /// user-defined classes.
+    pub(super) fn str_value(&mut self, value: &Value) -> RunResult<String> {
+        if let Some(result) = self.call_dunder(value, StaticStrings::DunderStr, None)? {
+            return self.dunder_string_result(result, "__str__");
+        }
+        if let Value::Ref(id) = value
+            && let HeapData::Instance(inst) = self.heap.get(*id)
+            && let Some(s) = r#enum::member_str(inst, self.heap, self.interns)
+        {
+            return Ok(s);
+        }
+        if self.contains_instance(value) {
+            self.repr_value(value)
+        } else {
+            Ok(value.py_str(self.heap, self.interns).into_owned())
+        }
+    }
+
+    fn repr_value_inner(&mut self, value: &Value, out: &mut String, visited: &mut AHashSet<HeapId>) -> RunResult<()> {
+        if let Some(result) = self.call_dunder(value, StaticStrings::DunderRepr, None)? {
+            out.push_str(&self.dunder_string_result(result, "__repr__")?);
+            return Ok(());
+        }
+        if !self.contains_instance(value) {
+            out.push_str(&value.py_repr(self.heap, self.interns));
+            return Ok(());
+        }
+        let Value::Ref(id) = value else {
+            unreachable!("only heap values need the VM for repr")
+        };
+        let id = *id;
+        // Copy the items out so the heap isn't borrowed while calling `__repr__`
+        let (open, close, items, is_dict): (&str, &str, Vec<Value>, bool) = match self.heap.get(id) {
+            HeapData::List(list) => (
+                "[",
+                "]",
+                list.as_vec().iter().map(Value::copy_for_extend).collect(),
+                false,
+            ),
+            HeapData::Tuple(tuple) => (
+                "(",
+                ")",
+                tuple.as_vec().iter().map(Value::copy_for_extend).collect(),
+                false,
+            ),
+            HeapData::Dict(dict) => (
+                "{",
+                "}",
+                dict.iter()
+                    .flat_map(|(k, v)| [k.copy_for_extend(), v.copy_for_extend()])
+                    .collect::<Vec<_>>(),
+                true,
+            ),
+            _ => {
+                out.push_str(&value.py_repr(self.heap, self.interns));
+                return Ok(());
+            }
+        };
+        if !visited.insert(id) {
+            out.push_str(if is_dict {
+                "{...}"
+            } else if open == "[" {
+                "[...]"
+            } else {
+                "(...)"
+            });
+            return Ok(());
+        }
+        for item in &items {
+            if let Value::Ref(item_id) = item {
+                self.heap.inc_ref(*item_id);
+            }
+        }
+
+        out.push_str(open);
+        let mut result = Ok(());
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                out.push_str(if is_dict && i % 2 == 1 { ": " } else { ", " });
+            }
+            result = self.repr_value_inner(item, out, visited);
+            if result.is_err() {
+                break;
+            }
+        }
+        if open == "(" && items.len() == 1 {
+            out.push(',');
+        }
+        out.push_str(close);
+        visited.remove(&id);
+        items.drop_with_heap(self.heap);
+        result
+    }
+
+    /// Checks that the result of `__repr__` or `__str__` is a string and extracts it.
+    fn dunder_string_result(&mut self, result: Value, method: &str) -> RunResult<String> {
+        let s = match result.as_either_str(self.heap) {
+            Some(s) => Ok(s.as_str(self.interns).to_owned()),
+            None => Err(ExcType::type_error(format!(
+                "{method} returned non-string (type {})",
+                result.py_type(self.heap)
+            ))),
+        };
+        result.drop_with_heap(self.heap);
+        s
+    }
+
+    /// Equality which calls `__eq__` on instances of user-defined classes.
+    ///
+    /// Tries the left operand's `__eq__`, then the right operand's, and otherwise falls
+    /// back to the builtin comparison (identity for instances). Lists, tuples and dicts
+    /// holding instances are compared item by item, so `__eq__` is called on those too.
+    pub(super) fn eq_values(&mut self, lhs: &Value, rhs: &Value) -> RunResult<bool> {
+        if let Some(result) = self.call_dunder(lhs, StaticStrings::DunderEq, Some(rhs))? {
+            return Ok(self.truthy(result));
+        }
+        if let Some(result) = self.call_dunder(rhs, StaticStrings::DunderEq, Some(lhs))? {
+            return Ok(self.truthy(result));
+        }
+        if (self.contains_instance(lhs) || self.contains_instance(rhs))
+            && let Some(result) = self.eq_containers(lhs, rhs)?
+        {
+            return Ok(result);
+        }
+        Ok(lhs.py_eq(rhs, self.heap, self.interns))
+    }
+
+    /// Compares two lists, two tuples or two dicts item by item with [`Self::eq_values`],
+    /// returning `None` for other values.
+    ///
+    /// Like CPython, items which are the same object are equal without calling `__eq__`.
+    fn eq_containers(&mut self, lhs: &Value, rhs: &Value) -> RunResult<Option<bool>> {
+        let (&Value::Ref(lhs_id), &Value::Ref(rhs_id)) = (lhs, rhs) else {
+            return Ok(None);
+        };
+        let same_len = match (self.heap.get(lhs_id), self.heap.get(rhs_id)) {
+            (HeapData::List(a), HeapData::List(b)) => a.len() == b.len(),
+            (HeapData::Tuple(a), HeapData::Tuple(b)) => a.as_vec().len() == b.as_vec().len(),
+            (HeapData::Dict(a), HeapData::Dict(b)) => {
+                return if a.len() == b.len() {
+                    self.eq_dicts(lhs_id, rhs_id).map(Some)
+                } else {
+                    Ok(Some(false))
+                };
+            }
+            _ => return Ok(None),
+        };
+        if !same_len {
+            return Ok(Some(false));
+        }
+        let lhs_items = self.sequence_items(lhs).expect("checked to be a list or tuple");
+        let rhs_items = self.sequence_items(rhs).expect("checked to be a list or tuple");
+        let mut result = Ok(true);
+        for (a, b) in lhs_items.iter().zip(&rhs_items) {
+            if a.is(b) {
+                continue;
+            }
+            match self.eq_values(a, b) {
+                Ok(true) => {}
+                other => {
+                    result = other;
+                    break;
+                }
+            }
+        }
+        lhs_items.drop_with_heap(self.heap);
+        rhs_items.drop_with_heap(self.heap);
+        result.map(Some)
+    }
+
+    /// Compares two dicts of the same length, looking up each key of `lhs_id` in `rhs_id`
+    /// with `__hash__` and `__eq__`, and comparing the values with [`Self::eq_values`].
+    fn eq_dicts(&mut self, lhs_id: HeapId, rhs_id: HeapId) -> RunResult<bool> {
+        let HeapData::Dict(dict) = self.heap.get(lhs_id) else {
+            unreachable!("eq_dicts called with a non-dict")
+        };
+        let items: Vec<(Value, Value)> = dict
+            .iter()
+            .map(|(k, v)| (k.copy_for_extend(), v.copy_for_extend()))
+            .collect();
+        for (key, value) in &items {
+            for item in [key, value] {
+                if let Value::Ref(id) = item {
+                    self.heap.inc_ref(*id);
+                }
+            }
+        }
+        let mut result = Ok(true);
+        for (key, value) in &items {
+            let other = match self.dict_get(rhs_id, key) {
+                Ok(Some(other)) => other,
+                Ok(None) => {
+                    result = Ok(false);
+                    break;
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            };
+            let eq = if value.is(&other) {
+                Ok(true)
+            } else {
+                self.eq_values(value, &other)
+            };
+            other.drop_with_heap(self.heap);
+            match eq {
+                Ok(true) => {}
+                other => {
+                    result = other;
+                    break;
+                }
+            }
+        }
+        for (key, value) in items {
+            key.drop_with_heap(self.heap);
+            value.drop_with_heap(self.heap);
+        }
+        result
+    }
+
+    /// Converts a special method result to a bool, dropping it.
+    pub(super) fn truthy(&mut self, value: Value) -> bool {
+        let result = value.py_bool(self.heap, self.interns);
+        value.drop_with_heap(self.heap);
+        result
+    }
+
+    /// Returns whether `value` is an instance of a user-defined class.
+    pub(super) fn is_instance(&self, value: &Value) -> bool {
+        matches!(value, Value::Ref(id) if matches!(self.heap.get(*id), HeapData::Instance(_)))
+    }
+
+    /// Returns whether `value` is a user-defined class or an instance of one, whose methods
+    /// get their arguments unchanged.
+    pub(super) fn is_user_defined(&self, value: &Value) -> bool {
+        let Value::Ref(id) = value else {
+            return false;
+        };
+        matches!(self.heap.get(*id), HeapData::Instance(_) | HeapData::ClassObject(_))
+    }
+
+    /// Returns the type name of a value for error messages, using the class name for instances.
+    pub(super) fn type_name(&self, value: &Value) -> String {
+        match value {
+            Value::Ref(id) => match self.heap.get(*id) {
+                HeapData::Instance(inst) => self.interns.get_str(class_name(inst.class_id(), self.heap)).to_owned(),
+                _ => value.py_type(self.heap).to_string(),
+            },
+            _ => value.py_type(self.heap).to_string(),
+        }
+    }
+
+    /// Calls a builtin, routing builtins which depend on special methods of
+    /// user-defined classes (and `super()`, which needs the calling frame) through the VM.
+    ///
+    /// Builtins taking generators are routed through the VM too, since resuming a
+    /// generator runs its frame.
+    pub(super) fn call_builtin(&mut self, builtin: Builtins, args: ArgValues) -> RunResult<Value> {
+        match builtin {
+            Builtins::Function(BuiltinsFunctions::Super) => self.call_super(args),
+            Builtins::Function(BuiltinsFunctions::Repr) if self.first_arg_needs_vm(&args) => {
+                let value = args.get_one_arg("repr", self.heap)?;
+                let s = self.repr_value(&value);
+                value.drop_with_heap(self.heap);
+                allocate_string(s?, self.heap)
+            }
+            Builtins::Type(Type::Str) if self.first_arg_needs_vm(&args) => {
+                let value = args.get_one_arg("str", self.heap)?;
+                let s = self.str_value(&value);
+                value.drop_with_heap(self.heap);
+                allocate_string(s?, self.heap)
+            }
+            Builtins::Function(BuiltinsFunctions::Print) => {
+                let args = self.stringify_args(args)?;
+                builtin.call(self.heap, args, self.interns, self.print_writer)
+            }
+            Builtins::Function(BuiltinsFunctions::Hash) if self.first_arg_is_instance(&args) => {
+                let value = args.get_one_arg("hash", self.heap)?;
+                let result = self.hash_instance(&value);
+                value.drop_with_heap(self.heap);
+                result
+            }
+            // Tuples hash the instances in them with `__hash__`
+            Builtins::Function(BuiltinsFunctions::Hash) if self.first_arg_needs_vm(&args) => {
+                let value = args.get_one_arg("hash", self.heap)?;
+                if let Err(e) = self.prepare_hash(&value) {
+                    value.drop_with_heap(self.heap);
+                    return Err(e);
+                }
+                builtin.call(self.heap, ArgValues::One(value), self.interns, self.print_writer)
+            }
+            Builtins::Function(BuiltinsFunctions::Len) if self.first_arg_is_instance(&args) => {
+                let value = args.get_one_arg("len", self.heap)?;
+                let result = self.len_instance(&value);
+                value.drop_with_heap(self.heap);
+                result
+            }
+            // These return iterators which call Python functions for each value
+            Builtins::Function(BuiltinsFunctions::Map) => self.call_map(args),
+            Builtins::Function(BuiltinsFunctions::Filter) => self.call_filter(args),
+            Builtins::Type(Type::Iterator) if matches!(args, ArgValues::Two(..)) => self.call_iter_sentinel(args),
+            // Generators and function-calling iterators are advanced through the VM: these builtins
+            // advance them lazily, and builtins which consume whole iterables get a list of their values
+            Builtins::Function(BuiltinsFunctions::Next) if self.first_arg_is_vm_iterator(&args) => {
+                self.vm_iterator_next(args)
+            }
+            Builtins::Type(Type::Iterator) if self.first_arg_is_vm_iterator(&args) => {
+                args.get_one_arg("iter", self.heap)
+            }
+            Builtins::Function(BuiltinsFunctions::Sum) if self.first_arg_is_vm_iterator(&args) => {
+                self.vm_iterator_sum(args)
+            }
+            Builtins::Function(BuiltinsFunctions::Any) if self.first_arg_is_vm_iterator(&args) => {
+                self.vm_iterator_any_all(args, true)
+            }
+            Builtins::Function(BuiltinsFunctions::All) if self.first_arg_is_vm_iterator(&args) => {
+                self.vm_iterator_any_all(args, false)
+            }
+            Builtins::Function(BuiltinsFunctions::Enumerate) if self.first_arg_is_vm_iterator(&args) => {
+                self.call_enumerate(args)
+            }
+            Builtins::Function(BuiltinsFunctions::Zip) if self.has_vm_iterator_arg(&args) => self.call_zip(args),
+            _ => {
+                let args = match builtin_iterable_args(builtin) {
+                    Some(which) if self.has_vm_iterator_arg(&args) => self.materialize_vm_iterator_args(args, which)?,
+                    _ => args,
+                };
+                if let Builtins::Type(
+                    t @ (Type::Dict
+                    | Type::Counter
+                    | Type::OrderedDict
+                    | Type::DefaultDict
+                    | Type::Set
+                    | Type::FrozenSet),
+                ) = builtin
+                    && self.has_instance_arg(&args)
+                {
+                    return self.call_hashing_type(t, args);
+                }
+                builtin.call(self.heap, args, self.interns, self.print_writer)
+            }
+        }
+    }
+
+    /// Calls a dict or set type, like `dict()` or `set()`, with arguments holding instances,
+    /// which may define `__hash__` and `__eq__`.
+    ///
+    /// Sets are built from lists and tuples through the VM, so equal instances are only
+    /// added once. Other arguments have their instances hashed and are compared by identity.
+    fn call_hashing_type(&mut self, t: Type, args: ArgValues) -> RunResult<Value> {
+        if matches!(t, Type::Set | Type::FrozenSet)
+            && let ArgValues::One(iterable) = &args
+            && let Some(items) = self.sequence_items(iterable)
+        {
+            args.drop_with_heap(self.heap);
+            let set = self.build_set_from(items)?;
+            if t == Type::Set {
+                return Ok(set);
+            }
+            let &Value::Ref(set_id) = &set else {
+                unreachable!("build_set_from returns a set")
+            };
+            // The elements move to the frozenset, leaving an empty set to free
+            let frozen = self.heap.with_entry_mut(set_id, |_, data| {
+                let HeapData::Set(built) = data else {
+                    unreachable!("build_set_from returns a set")
+                };
+                FrozenSet::from_set(std::mem::replace(built, Set::new()))
+            });
+            set.drop_with_heap(self.heap);
+            return Ok(Value::Ref(self.heap.allocate(HeapData::FrozenSet(frozen))?));
+        }
+        let (positional, kwargs) = args.into_parts();
+        let values: Vec<Value> = positional.collect();
+        for value in &values {
+            if let Err(e) = self.prepare_hash(value) {
+                values.drop_with_heap(self.heap);
+                kwargs.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        }
+        let args = if kwargs.is_empty() {
+            ArgValues::from_vec(values)
+        } else {
+            ArgValues::ArgsKargs { args: values, kwargs }
+        };
+        Builtins::Type(t).call(self.heap, args, self.interns, self.print_writer)
+    }
+
+    fn first_arg_needs_vm(&self, args: &ArgValues) -> bool {
+        matches!(args, ArgValues::One(value) if self.contains_instance(value))
+    }
+
+    fn first_arg_is_instance(&self, args: &ArgValues) -> bool {
+        matches!(args, ArgValues::One(value) if self.is_instance(value))
+    }
+
+    /// Returns whether any positional argument is or contains an instance of a user-defined class.
+    pub(super) fn has_instance_arg(&self, args: &ArgValues) -> bool {
+        match args {
+            ArgValues::One(a) => self.contains_instance(a),
+            ArgValues::Two(a, b) => self.contains_instance(a) || self.contains_instance(b),
+            ArgValues::ArgsKargs { args, .. } => args.iter().any(|a| self.contains_instance(a)),
+            ArgValues::Empty | ArgValues::Kwargs(_) => false,
+        }
+    }
+
+    /// Replaces positional arguments that need the VM for `str()` with their string values.
+    fn stringify_args(&mut self, args: ArgValues) -> RunResult<ArgValues> {
+        if !self.has_instance_arg(&args) {
+            return Ok(args);
+        }
+        let (positional, kwargs) = args.into_parts();
+        let mut values: Vec<Value> = positional.collect();
+        let mut error = None;
+        for value in &mut values {
+            if !self.contains_instance(value) {
+                continue;
+            }
+            match self.str_value(value).and_then(|s| allocate_string(s, self.heap)) {
+                Ok(s) => std::mem::replace(value, s).drop_with_heap(self.heap),
+                Err(e) => {
+                    error = Some(e);
+                    break;
+                }
+            }
+        }
+        if let Some(error) = error {
+            values.drop_with_heap(self.heap);
+            kwargs.drop_with_heap(self.heap);
+            return Err(error);
+        }
+        Ok(ArgValues::ArgsKargs { args: values, kwargs })
+    }
+
+    /// Implements `hash()` for an instance, calling `__hash__` if the class defines it.
+    pub(super) fn hash_instance(&mut self, value: &Value) -> RunResult<Value> {
+        let Value::Ref(id) = value else {
+            unreachable!("hash_instance called with a non-instance")
+        };
+        let HeapData::Instance(inst) = self.heap.get(*id) else {
+            unreachable!("hash_instance called with a non-instance")
+        };
+        if !is_instance_hashable(inst.class_id(), self.heap, self.interns) {
+            return Err(ExcType::type_error(format!(
+                "unhashable type: '{}'",
+                self.type_name(value)
+            )));
+        }
+        match self.call_dunder(value, StaticStrings::DunderHash, None)? {
+            Some(Value::Int(i)) => Ok(Value::Int(i)),
+            Some(Value::Bool(b)) => Ok(Value::Int(i64::from(b))),
+            Some(other) => {
+                other.drop_with_heap(self.heap);
+                Err(ExcType::type_error("__hash__ method should return an integer"))
+            }
+            None => {
+                let hash = value.py_hash(self.heap, self.interns).expect("instance is hashable");
+                Ok(Value::Int(i64::from_ne_bytes(hash.to_ne_bytes())))
+            }
+        }
+    }
+
+    /// Implements `len()` for an instance by calling `__len__`, falling back to the length
+    /// of the value of a `StrEnum` member.
+    fn len_instance(&mut self, value: &Value) -> RunResult<Value> {
+        match self.call_dunder(value, StaticStrings::DunderLen, None)? {
+            Some(Value::Int(i)) if i >= 0 => Ok(Value::Int(i)),
+            Some(Value::Int(_)) => {
+                Err(SimpleException::new_msg(ExcType::ValueError, "__len__() should return >= 0").into())
+            }
+            Some(Value::Bool(b)) => Ok(Value::Int(i64::from(b))),
+            Some(other) => {
+                let type_name = self.type_name(&other);
+                other.drop_with_heap(self.heap);
+                Err(ExcType::type_error(format!(
+                    "'{type_name}' object cannot be interpreted as an integer"
+                )))
+            }
+            None => match value.py_len(self.heap, self.interns) {
+                Some(len) => Ok(Value::Int(i64::try_from(len).expect("len exceeds i64::MAX"))),
+                None => Err(ExcType::type_error(format!(
+                    "object of type '{}' has no len()",
+                    self.type_name(value)
+                ))),
+            },
+        }
+    }
+}
+
+/// Error raised when a special method tries to suspend execution for the host.
+pub(super) fn sync_call_suspend_error() -> RunError {
+    SimpleException::new_msg(
+        ExcType::RuntimeError,
+        "external functions and OS calls are not supported inside special methods",
+    )
+    .into()
+}
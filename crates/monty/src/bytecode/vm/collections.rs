@@ -1,15 +1,16 @@
-//! Collection building and unpacking helpers for the VM.
+//! Collection building, subscripting and unpacking helpers for the VM.
 
 use smallvec::SmallVec;
 
 use super::VM;
 use crate::{
-    exception_private::{ExcType, RunError, SimpleException},
-    heap::HeapData,
-    intern::StringId,
+    args::{ArgValues, KwargsValues},
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, HeapData, HeapId},
+    intern::{StaticStrings, StringId},
     io::PrintWriter,
     resource::ResourceTracker,
-    types::{Dict, List, PyTrait, Set, Slice, Type, allocate_tuple, slice::value_to_option_i64, str::allocate_char},
+    types::{Dict, List, MontyIter, PyTrait, Set, Slice, Type, allocate_tuple, r#enum, slice::value_to_option_i64},
     value::Value,
 };
 
@@ -35,8 +36,27 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
     }
 
     /// Builds a dict from the top 2n stack values (key/value pairs).
+    ///
+    /// Keys holding instances of user-defined classes are added through the VM, which
+    /// calls their `__hash__` and `__eq__`.
     pub(super) fn build_dict(&mut self, count: usize) -> Result<(), RunError> {
         let items = self.pop_n(count * 2);
+        if items.iter().step_by(2).any(|key| self.contains_instance(key)) {
+            let dict_id = self.heap.allocate(HeapData::Dict(Dict::new()))?;
+            let mut iter = items.into_iter();
+            while let (Some(key), Some(value)) = (iter.next(), iter.next()) {
+                match self.dict_set(dict_id, key, value) {
+                    Ok(old_value) => old_value.drop_with_heap(self.heap),
+                    Err(e) => {
+                        iter.for_each(|item| item.drop_with_heap(self.heap));
+                        Value::Ref(dict_id).drop_with_heap(self.heap);
+                        return Err(e);
+                    }
+                }
+            }
+            self.push(Value::Ref(dict_id));
+            return Ok(());
+        }
         let mut dict = Dict::new();
         // Use into_iter to consume items by value, avoiding clone and proper ownership transfer
         let mut iter = items.into_iter();
@@ -49,8 +69,16 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
     }
 
     /// Builds a set from the top n stack values.
+    ///
+    /// Instances of user-defined classes are added through the VM, which calls their
+    /// `__hash__` and `__eq__`.
     pub(super) fn build_set(&mut self, count: usize) -> Result<(), RunError> {
         let items = self.pop_n(count);
+        if items.iter().any(|item| self.contains_instance(item)) {
+            let set = self.build_set_from(items)?;
+            self.push(set);
+            return Ok(());
+        }
         let mut set = Set::new();
         for item in items {
             set.add(item, self.heap, self.interns)?;
@@ -90,59 +118,50 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
     /// Stack: [list, iterable] -> [list]
     /// Pops the iterable, extends the list in place, leaves list on stack.
     pub(super) fn list_extend(&mut self) -> Result<(), RunError> {
+        self.materialize_top_vm_iterator()?;
         let iterable = self.pop();
         let list_ref = self.pop();
 
         // Two-phase approach to avoid borrow conflicts:
-        // Phase 1: Copy items without refcount changes
-        let copied_items: Vec<Value> = match &iterable {
+        // Phase 1: Copy items of common containers without refcount changes
+        let copied_items: Option<Vec<Value>> = match &iterable {
             Value::Ref(id) => match self.heap.get(*id) {
-                HeapData::List(list) => list.as_vec().iter().map(Value::copy_for_extend).collect(),
-                HeapData::Tuple(tuple) => tuple.as_vec().iter().map(Value::copy_for_extend).collect(),
-                HeapData::Set(set) => set.storage().iter().map(Value::copy_for_extend).collect(),
-                HeapData::Dict(dict) => dict.iter().map(|(k, _)| Value::copy_for_extend(k)).collect(),
-                HeapData::Str(s) => {
-                    // Need to allocate strings for each character
-                    let chars: Vec<char> = s.as_str().chars().collect();
-                    let mut items = Vec::with_capacity(chars.len());
-                    for c in chars {
-                        items.push(allocate_char(c, self.heap)?);
-                    }
-                    items
+                HeapData::List(list) => Some(list.as_vec().iter().map(Value::copy_for_extend).collect()),
+                HeapData::Tuple(tuple) => Some(tuple.as_vec().iter().map(Value::copy_for_extend).collect()),
+                HeapData::Set(set) => Some(set.storage().iter().map(Value::copy_for_extend).collect()),
+                HeapData::Dict(dict) => Some(dict.iter().map(|(k, _)| Value::copy_for_extend(k)).collect()),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let items = if let Some(copied_items) = copied_items {
+            // Phase 2: Increment refcounts now that the borrow has ended
+            for item in &copied_items {
+                if let Value::Ref(id) = item {
+                    self.heap.inc_ref(*id);
                 }
-                _ => {
-                    let type_ = iterable.py_type(self.heap);
-                    iterable.drop_with_heap(self.heap);
+            }
+            iterable.drop_with_heap(self.heap);
+            copied_items
+        } else {
+            // Any other iterable (strings, ranges, iterators, ...) is iterated item by item
+            let type_ = iterable.py_type(self.heap);
+            match self.collect_iterable(iterable, None) {
+                Ok(Some(items)) => items,
+                Ok(None) => {
                     list_ref.drop_with_heap(self.heap);
                     return Err(ExcType::type_error_not_iterable(type_));
                 }
-            },
-            Value::InternString(id) => {
-                let s = self.interns.get_str(*id);
-                let chars: Vec<char> = s.chars().collect();
-                let mut items = Vec::with_capacity(chars.len());
-                for c in chars {
-                    items.push(allocate_char(c, self.heap)?);
+                Err(e) => {
+                    list_ref.drop_with_heap(self.heap);
+                    return Err(e);
                 }
-                items
-            }
-            _ => {
-                let type_ = iterable.py_type(self.heap);
-                iterable.drop_with_heap(self.heap);
-                list_ref.drop_with_heap(self.heap);
-                return Err(ExcType::type_error_not_iterable(type_));
             }
         };
 
-        // Phase 2: Increment refcounts now that the borrow has ended
-        for item in &copied_items {
-            if let Value::Ref(id) = item {
-                self.heap.inc_ref(*id);
-            }
-        }
-
-        // Check if any copied items are refs (for updating contains_refs)
-        let has_refs = copied_items.iter().any(|v| matches!(v, Value::Ref(_)));
+        // Check if any items are refs (for updating contains_refs)
+        let has_refs = items.iter().any(|v| matches!(v, Value::Ref(_)));
 
         // Extend the list
         if let Value::Ref(id) = &list_ref
@@ -152,7 +171,7 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
             if has_refs {
                 list.set_contains_refs();
             }
-            list.as_vec_mut().extend(copied_items);
+            list.as_vec_mut().extend(items);
         }
 
         // Mark potential cycle after the mutable borrow ends
@@ -160,7 +179,6 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
             self.heap.mark_potential_cycle();
         }
 
-        iterable.drop_with_heap(self.heap);
         self.push(list_ref);
         Ok(())
     }
@@ -302,6 +320,92 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         Ok(())
     }
 
+    /// Adds the items of an iterable to a set, for starred items in set displays.
+    ///
+    /// Stack: [set, iterable] -> [set]
+    pub(super) fn set_update(&mut self) -> Result<(), RunError> {
+        self.materialize_top_vm_iterator()?;
+        let iterable = self.pop();
+        let type_ = iterable.py_type(self.heap);
+        let Some(items) = self.collect_iterable(iterable, None)? else {
+            return Err(ExcType::type_error_not_iterable(type_));
+        };
+
+        let Value::Ref(set_id) = *self.peek() else {
+            items.drop_with_heap(self.heap);
+            return Err(RunError::internal("SetUpdate: expected set ref on stack"));
+        };
+
+        let mut items = items.into_iter();
+        let result: RunResult<()> = self.heap.with_entry_mut(set_id, |heap, data| {
+            let HeapData::Set(set) = data else {
+                return Err(RunError::internal("SetUpdate: expected set on heap"));
+            };
+            for item in items.by_ref() {
+                set.add(item, heap, self.interns)?;
+            }
+            Ok(())
+        });
+        // Drop any items left over after an unhashable item
+        items.drop_with_heap(self.heap);
+        result
+    }
+
+    /// Updates a dict with the items of a mapping, for `**mapping` items in dict displays.
+    ///
+    /// Stack: [dict, mapping] -> [dict]
+    /// Unlike `dict_merge`, keys don't need to be strings and later keys overwrite earlier ones.
+    pub(super) fn dict_update(&mut self) -> Result<(), RunError> {
+        let mapping = self.pop();
+
+        // Phase 1: Copy key-value pairs without refcount changes
+        let copied_items: Vec<(Value, Value)> = if let Value::Ref(id) = &mapping
+            && let HeapData::Dict(dict) = self.heap.get(*id)
+        {
+            dict.iter()
+                .map(|(k, v)| (Value::copy_for_extend(k), Value::copy_for_extend(v)))
+                .collect()
+        } else {
+            let type_ = mapping.py_type(self.heap);
+            mapping.drop_with_heap(self.heap);
+            return Err(ExcType::type_error_not_mapping(type_));
+        };
+
+        // Phase 2: Increment refcounts now that the borrow has ended
+        for (key, value) in &copied_items {
+            if let Value::Ref(id) = key {
+                self.heap.inc_ref(*id);
+            }
+            if let Value::Ref(id) = value {
+                self.heap.inc_ref(*id);
+            }
+        }
+        mapping.drop_with_heap(self.heap);
+
+        let Value::Ref(dict_id) = *self.peek() else {
+            for (key, value) in copied_items {
+                key.drop_with_heap(self.heap);
+                value.drop_with_heap(self.heap);
+            }
+            return Err(RunError::internal("DictUpdate: expected dict ref on stack"));
+        };
+
+        // Keys came from a dict so are hashable, and setting them can't fail
+        for (key, value) in copied_items {
+            let old_value = self.heap.with_entry_mut(dict_id, |heap, data| {
+                if let HeapData::Dict(dict) = data {
+                    dict.set(key, value, heap, self.interns)
+                } else {
+                    key.drop_with_heap(heap);
+                    value.drop_with_heap(heap);
+                    Err(RunError::internal("DictUpdate: expected dict on heap"))
+                }
+            })?;
+            old_value.drop_with_heap(self.heap);
+        }
+        Ok(())
+    }
+
     // ========================================================================
     // Comprehension Building
     // ========================================================================
@@ -350,16 +454,11 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
             return Err(RunError::internal("SetAdd: expected set ref on stack"));
         };
 
-        // Add to the set using with_entry_mut to avoid borrow conflicts
-        self.heap.with_entry_mut(set_id, |heap, data| {
-            if let HeapData::Set(set) = data {
-                set.add(value, heap, self.interns)
-            } else {
-                value.drop_with_heap(heap);
-                Err(RunError::internal("SetAdd: expected set on heap"))
-            }
-        })?;
-
+        if !matches!(self.heap.get(set_id), HeapData::Set(_)) {
+            value.drop_with_heap(self.heap);
+            return Err(RunError::internal("SetAdd: expected set on heap"));
+        }
+        self.set_insert(set_id, value)?;
         Ok(())
     }
 
@@ -381,16 +480,12 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
             return Err(RunError::internal("DictSetItem: expected dict ref on stack"));
         };
 
-        // Set item in the dict using with_entry_mut to avoid borrow conflicts
-        let old_value = self.heap.with_entry_mut(dict_id, |heap, data| {
-            if let HeapData::Dict(dict) = data {
-                dict.set(key, value, heap, self.interns)
-            } else {
-                key.drop_with_heap(heap);
-                value.drop_with_heap(heap);
-                Err(RunError::internal("DictSetItem: expected dict on heap"))
-            }
-        })?;
+        if !matches!(self.heap.get(dict_id), HeapData::Dict(_)) {
+            key.drop_with_heap(self.heap);
+            value.drop_with_heap(self.heap);
+            return Err(RunError::internal("DictSetItem: expected dict on heap"));
+        }
+        let old_value = self.dict_set(dict_id, key, value)?;
 
         // Drop old value if key already existed
         if let Some(old) = old_value {
@@ -401,102 +496,151 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
     }
 
     // ========================================================================
-    // Unpacking
+    // Subscripting
     // ========================================================================
 
-    /// Unpacks a sequence into n values on the stack.
+    /// Gets `obj[index]`, calling `__getitem__` on instances whose class defines it.
     ///
-    /// Supports lists, tuples, and strings. For strings, each character becomes
-    /// a separate single-character string.
-    pub(super) fn unpack_sequence(&mut self, count: usize) -> Result<(), RunError> {
-        let value = self.pop();
+    /// Missing keys of a `defaultdict` with a `default_factory` are inserted with the
+    /// result of calling the factory, which may be a Python function.
+    pub(super) fn binary_subscr(&mut self, obj: &Value, index: &Value) -> RunResult<Value> {
+        if let Some(result) = self.call_dunder(obj, StaticStrings::DunderGetitem, Some(index))? {
+            return Ok(result);
+        }
+        if !self.contains_instance(index) {
+            return self.subscr_item(obj, index);
+        }
+        let index = self.resolve_key(obj, index.clone_with_heap(self.heap))?;
+        let result = self.subscr_item(obj, &index);
+        index.drop_with_heap(self.heap);
+        result
+    }
 
-        // Copy values without incrementing refcounts (avoids borrow conflict with heap.get).
-        // For strings, we allocate new string values for each character.
-        let items: Vec<Value> = match &value {
-            // Interned strings (string literals stored inline, not on heap)
-            Value::InternString(string_id) => {
-                let s = self.interns.get_str(*string_id);
-                let str_len = s.chars().count();
-                if str_len != count {
-                    return Err(unpack_size_error(count, str_len));
-                }
-                // Allocate each character as a new string
-                let mut items = Vec::with_capacity(str_len);
-                for c in s.chars() {
-                    items.push(allocate_char(c, self.heap)?);
-                }
-                // Push items in reverse order so first item is on top
-                for item in items.into_iter().rev() {
-                    self.push(item);
-                }
-                return Ok(());
+    /// Gets `obj[index]` once `index` is resolved to a stored key of dicts.
+    fn subscr_item(&mut self, obj: &Value, index: &Value) -> RunResult<Value> {
+        let Some((dict_id, factory)) = self.default_factory(obj) else {
+            let result = obj.py_getitem(index, self.heap, self.interns);
+            // `IntEnum` members index sequences like their values
+            if result.is_err()
+                && let Some(inner) = r#enum::owned_mixin_value(index, self.heap, self.interns)
+            {
+                let retried = obj.py_getitem(&inner, self.heap, self.interns);
+                inner.drop_with_heap(self.heap);
+                return retried;
             }
-            // Heap-allocated sequences
-            Value::Ref(heap_id) => match self.heap.get(*heap_id) {
-                HeapData::List(list) => {
-                    let list_len = list.len();
-                    if list_len != count {
-                        value.drop_with_heap(self.heap);
-                        return Err(unpack_size_error(count, list_len));
-                    }
-                    list.as_vec().iter().map(Value::copy_for_extend).collect()
-                }
-                HeapData::Tuple(tuple) => {
-                    let tuple_len = tuple.as_vec().len();
-                    if tuple_len != count {
-                        value.drop_with_heap(self.heap);
-                        return Err(unpack_size_error(count, tuple_len));
-                    }
-                    tuple.as_vec().iter().map(Value::copy_for_extend).collect()
-                }
-                HeapData::Str(s) => {
-                    let str_len = s.as_str().chars().count();
-                    if str_len != count {
-                        value.drop_with_heap(self.heap);
-                        return Err(unpack_size_error(count, str_len));
-                    }
-                    // Collect characters first to avoid borrow conflict with heap
-                    let chars: Vec<char> = s.as_str().chars().collect();
-                    // Drop the original string value before allocating new ones
-                    value.drop_with_heap(self.heap);
-                    // Allocate each character as a new string
-                    let mut items = Vec::with_capacity(chars.len());
-                    for c in chars {
-                        items.push(allocate_char(c, self.heap)?);
-                    }
-                    // Push items in reverse order so first item is on top
-                    for item in items.into_iter().rev() {
-                        self.push(item);
-                    }
-                    return Ok(());
-                }
-                other => {
-                    let type_name = other.py_type(self.heap);
-                    value.drop_with_heap(self.heap);
-                    return Err(unpack_type_error(type_name));
-                }
-            },
-            // Non-iterable types
-            _ => {
-                let type_name = value.py_type(self.heap);
+            return result;
+        };
+
+        let found = self.heap.with_entry_mut(dict_id, |heap, data| {
+            let HeapData::Dict(dict) = data else {
+                unreachable!("default_factory only returns dicts")
+            };
+            dict.get(index, heap, self.interns)
+                .map(|value| value.map(|value| value.clone_with_heap(heap)))
+        });
+        match found {
+            Ok(None) => {}
+            Ok(Some(value)) => {
+                factory.drop_with_heap(self.heap);
+                return Ok(value);
+            }
+            Err(e) => {
+                factory.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        }
+
+        let value = self.call_sync(factory, ArgValues::Empty)?;
+        let key = index.clone_with_heap(self.heap);
+        let stored = value.clone_with_heap(self.heap);
+        let old_value = self.heap.with_entry_mut(dict_id, |heap, data| {
+            let HeapData::Dict(dict) = data else {
+                unreachable!("default_factory only returns dicts")
+            };
+            dict.set(key, stored, heap, self.interns)
+        });
+        match old_value {
+            Ok(old_value) => {
+                old_value.drop_with_heap(self.heap);
+                Ok(value)
+            }
+            Err(e) => {
                 value.drop_with_heap(self.heap);
-                return Err(unpack_type_error(type_name));
+                Err(e)
             }
-        };
+        }
+    }
 
-        // IMPORTANT: Increment refcounts BEFORE dropping the container.
-        // The container holds references to its items. If we drop the container first,
-        // it decrements the item refcounts, potentially freeing them before we can
-        // increment the refcounts for our copies.
-        for item in &items {
-            if let Value::Ref(id) = item {
-                self.heap.inc_ref(*id);
+    /// Sets `obj[index] = value`, calling `__setitem__` on instances whose class defines it.
+    pub(super) fn store_subscr(&mut self, mut obj: Value, index: Value, value: Value) -> RunResult<()> {
+        if let Some(method) = self.instance_dunder(&obj, StaticStrings::DunderSetitem) {
+            let args = ArgValues::ArgsKargs {
+                args: vec![obj, index, value],
+                kwargs: KwargsValues::Empty,
+            };
+            self.call_sync(method, args)?.drop_with_heap(self.heap);
+            return Ok(());
+        }
+        let result = match self.resolve_key(&obj, index) {
+            Ok(index) => obj.py_setitem(index, value, self.heap, self.interns),
+            Err(e) => {
+                value.drop_with_heap(self.heap);
+                Err(e)
             }
+        };
+        obj.drop_with_heap(self.heap);
+        result
+    }
+
+    /// Deletes `obj[index]`, calling `__delitem__` on instances whose class defines it.
+    pub(super) fn delete_subscr(&mut self, mut obj: Value, index: Value) -> RunResult<()> {
+        if let Some(method) = self.instance_dunder(&obj, StaticStrings::DunderDelitem) {
+            self.call_sync(method, ArgValues::Two(obj, index))?
+                .drop_with_heap(self.heap);
+            return Ok(());
         }
+        let result = self
+            .resolve_key(&obj, index)
+            .and_then(|index| obj.py_delitem(index, self.heap, self.interns));
+        obj.drop_with_heap(self.heap);
+        result
+    }
 
-        // Now safe to drop the original container
-        value.drop_with_heap(self.heap);
+    /// Returns the dict's id and a new reference to its factory if `obj` is a `defaultdict`
+    /// with a `default_factory`.
+    fn default_factory(&mut self, obj: &Value) -> Option<(HeapId, Value)> {
+        let Value::Ref(dict_id) = obj else {
+            return None;
+        };
+        let HeapData::Dict(dict) = self.heap.get(*dict_id) else {
+            return None;
+        };
+        let factory = dict.default_factory()?.copy_for_extend();
+        if let Value::Ref(factory_id) = &factory {
+            self.heap.inc_ref(*factory_id);
+        }
+        Some((*dict_id, factory))
+    }
+
+    // ========================================================================
+    // Unpacking
+    // ========================================================================
+
+    /// Unpacks an iterable into n values on the stack.
+    ///
+    /// Any iterable can be unpacked. Like CPython, at most one more item than
+    /// expected is read from iterators before reporting too many values.
+    pub(super) fn unpack_sequence(&mut self, count: usize) -> Result<(), RunError> {
+        let (items, sequence_len) = self.pop_unpack_items(Some(count + 1))?;
+        let actual = items.len();
+        if actual != count {
+            items.drop_with_heap(self.heap);
+            return Err(if actual < count {
+                unpack_too_few_error(count, actual)
+            } else {
+                unpack_too_many_error(count, sequence_len)
+            });
+        }
 
         // Push items in reverse order so first item is on top
         for item in items.into_iter().rev() {
@@ -505,7 +649,7 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         Ok(())
     }
 
-    /// Unpacks a sequence with a starred target.
+    /// Unpacks an iterable with a starred target.
     ///
     /// `before` is the number of targets before the star, `after` is the number after.
     /// The starred target collects all middle items into a list.
@@ -513,132 +657,83 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
     /// For example, `first, *rest, last = [1, 2, 3, 4, 5]` has before=1, after=1.
     /// After execution, the stack has: first (top), rest_list, last.
     pub(super) fn unpack_ex(&mut self, before: usize, after: usize) -> Result<(), RunError> {
-        let value = self.pop();
+        let (mut items, _) = self.pop_unpack_items(None)?;
         let min_items = before + after;
-
-        // Extract items from the sequence
-        let items: Vec<Value> = match &value {
-            Value::InternString(string_id) => {
-                let s = self.interns.get_str(*string_id);
-                // Collect chars once to avoid double iteration over UTF-8 data
-                let chars: Vec<char> = s.chars().collect();
-                if chars.len() < min_items {
-                    return Err(unpack_ex_too_few_error(min_items, chars.len()));
-                }
-                // Allocate each character as a new string
-                let mut items = Vec::with_capacity(chars.len());
-                for c in chars {
-                    items.push(allocate_char(c, self.heap)?);
-                }
-                // String items are newly allocated, push and return
-                self.push_unpack_ex_results(&items, before, after)?;
-                return Ok(());
-            }
-            Value::Ref(heap_id) => match self.heap.get(*heap_id) {
-                HeapData::List(list) => {
-                    let list_len = list.len();
-                    if list_len < min_items {
-                        value.drop_with_heap(self.heap);
-                        return Err(unpack_ex_too_few_error(min_items, list_len));
-                    }
-                    list.as_vec().iter().map(Value::copy_for_extend).collect()
-                }
-                HeapData::Tuple(tuple) => {
-                    let tuple_len = tuple.as_vec().len();
-                    if tuple_len < min_items {
-                        value.drop_with_heap(self.heap);
-                        return Err(unpack_ex_too_few_error(min_items, tuple_len));
-                    }
-                    tuple.as_vec().iter().map(Value::copy_for_extend).collect()
-                }
-                HeapData::Str(s) => {
-                    // Collect chars once to avoid double iteration over UTF-8 data
-                    let chars: Vec<char> = s.as_str().chars().collect();
-                    if chars.len() < min_items {
-                        value.drop_with_heap(self.heap);
-                        return Err(unpack_ex_too_few_error(min_items, chars.len()));
-                    }
-                    value.drop_with_heap(self.heap);
-                    let mut items = Vec::with_capacity(chars.len());
-                    for c in chars {
-                        items.push(allocate_char(c, self.heap)?);
-                    }
-                    // String items are newly allocated, push and return
-                    self.push_unpack_ex_results(&items, before, after)?;
-                    return Ok(());
-                }
-                other => {
-                    let type_name = other.py_type(self.heap);
-                    value.drop_with_heap(self.heap);
-                    return Err(unpack_type_error(type_name));
-                }
-            },
-            _ => {
-                let type_name = value.py_type(self.heap);
-                value.drop_with_heap(self.heap);
-                return Err(unpack_type_error(type_name));
-            }
-        };
-
-        // Increment refcounts BEFORE dropping the container.
-        // Items were copied with copy_for_extend (no refcount change).
-        // We need to increment for:
-        // - Each item going to the "before" targets
-        // - Each item going to the "middle" list
-        // - Each item going to the "after" targets
-        // That's one increment per item.
-        for item in &items {
-            if let Value::Ref(id) = item {
-                self.heap.inc_ref(*id);
-            }
+        if items.len() < min_items {
+            let actual = items.len();
+            items.drop_with_heap(self.heap);
+            return Err(unpack_ex_too_few_error(min_items, actual));
         }
 
-        // Drop the original container
-        value.drop_with_heap(self.heap);
+        let after_items = items.split_off(items.len() - after);
+        let middle = items.split_off(before);
+        let list_id = self.heap.allocate(HeapData::List(List::new(middle)))?;
 
-        // Now push the results
-        self.push_unpack_ex_results(&items, before, after)?;
+        // Push in reverse order so first item is on top
+        for item in after_items.into_iter().rev() {
+            self.push(item);
+        }
+        self.push(Value::Ref(list_id));
+        for item in items.into_iter().rev() {
+            self.push(item);
+        }
         Ok(())
     }
 
-    /// Helper to push unpacked items with starred target onto the stack.
+    /// Pops the value being unpacked and collects its items, reading at most `limit` items.
     ///
-    /// Takes a slice of items and creates the middle list.
-    fn push_unpack_ex_results(&mut self, items: &[Value], before: usize, after: usize) -> Result<(), RunError> {
-        let total = items.len();
-
-        // Collect results: before items, middle list, after items
-        let mut results = Vec::with_capacity(before + 1 + after);
+    /// Also returns the length of lists and tuples, since CPython only reports the
+    /// number of values for those types when there are too many.
+    fn pop_unpack_items(&mut self, limit: Option<usize>) -> Result<(Vec<Value>, Option<usize>), RunError> {
+        let is_vm_iterator = self.is_vm_iterator(self.peek());
+        self.materialize_top_vm_iterator()?;
+        let value = self.pop();
 
-        // Before items
-        for item in items.iter().take(before) {
-            results.push(item.copy_for_extend());
-        }
+        let sequence_len = match &value {
+            Value::Ref(id) if !is_vm_iterator => match self.heap.get(*id) {
+                HeapData::List(list) => Some(list.len()),
+                HeapData::Tuple(tuple) => Some(tuple.as_vec().len()),
+                _ => None,
+            },
+            _ => None,
+        };
 
-        // Middle items as a list (starred target)
-        // Items already have their refcount incremented in unpack_ex (for list/tuple)
-        // or are freshly allocated (for strings), so no additional inc_ref needed here.
-        let middle_start = before;
-        let middle_end = total - after;
-        let mut middle = Vec::with_capacity(middle_end - middle_start);
-        for item in &items[middle_start..middle_end] {
-            middle.push(item.copy_for_extend());
+        let type_ = value.py_type(self.heap);
+        match self.collect_iterable(value, limit)? {
+            Some(items) => Ok((items, sequence_len)),
+            None => Err(unpack_type_error(type_)),
         }
-        let middle_list = List::new(middle);
-        let list_id = self.heap.allocate(HeapData::List(middle_list))?;
-        results.push(Value::Ref(list_id));
+    }
 
-        // After items
-        for item in items.iter().skip(total - after) {
-            results.push(item.copy_for_extend());
-        }
+    /// Collects the items of an iterable, reading at most `limit` items.
+    ///
+    /// Supports everything `MontyIter` can iterate. Generators and function-calling iterators
+    /// must already have been materialized.
+    /// Returns `Ok(None)` if the value isn't iterable, so callers can raise their own error.
+    fn collect_iterable(&mut self, value: Value, limit: Option<usize>) -> RunResult<Option<Vec<Value>>> {
+        let Ok(mut iter) = MontyIter::new(value, self.heap, self.interns) else {
+            return Ok(None);
+        };
+        let mut items = Vec::new();
+        let result = loop {
+            if limit.is_some_and(|limit| items.len() >= limit) {
+                break Ok(());
+            }
+            match iter.for_next(self.heap, self.interns) {
+                Ok(Some(item)) => items.push(item),
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(e),
+            }
+        };
+        iter.drop_with_heap(self.heap);
 
-        // Push in reverse order so first item is on top
-        for item in results.into_iter().rev() {
-            self.push(item);
+        match result {
+            Ok(()) => Ok(Some(items)),
+            Err(e) => {
+                items.drop_with_heap(self.heap);
+                Err(e)
+            }
         }
-
-        Ok(())
     }
 }
 
@@ -648,16 +743,20 @@ fn unpack_ex_too_few_error(min_needed: usize, actual: usize) -> RunError {
     SimpleException::new_msg(ExcType::ValueError, message).into()
 }
 
-/// Creates the appropriate ValueError for unpacking size mismatches.
+/// Creates the ValueError for unpacking when there are too few values.
+fn unpack_too_few_error(expected: usize, actual: usize) -> RunError {
+    let message = format!("not enough values to unpack (expected {expected}, got {actual})");
+    SimpleException::new_msg(ExcType::ValueError, message).into()
+}
+
+/// Creates the ValueError for unpacking when there are too many values.
 ///
-/// Python uses different messages depending on whether there are too few or too many values:
-/// - Too few: "not enough values to unpack (expected X, got Y)"
-/// - Too many: "too many values to unpack (expected X, got Y)"
-fn unpack_size_error(expected: usize, actual: usize) -> RunError {
-    let message = if actual < expected {
-        format!("not enough values to unpack (expected {expected}, got {actual})")
-    } else {
-        format!("too many values to unpack (expected {expected}, got {actual})")
+/// The actual number of values is only known (and reported) for lists and tuples,
+/// other iterables are only read one item past the expected count.
+fn unpack_too_many_error(expected: usize, sequence_len: Option<usize>) -> RunError {
+    let message = match sequence_len {
+        Some(actual) => format!("too many values to unpack (expected {expected}, got {actual})"),
+        None => format!("too many values to unpack (expected {expected})"),
     };
     SimpleException::new_msg(ExcType::ValueError, message).into()
 }
@@ -1,46 +1,148 @@
 //! Comparison operation helpers for the VM.
 
+use std::cmp::Ordering;
+
 use super::VM;
 use crate::{
-    exception_private::{ExcType, RunError},
+    exception_private::{ExcType, RunError, RunResult},
+    expressions::CmpOperator,
+    heap::{DropWithHeap, HeapData},
+    intern::StaticStrings,
     io::PrintWriter,
     resource::ResourceTracker,
-    types::{LongInt, PyTrait},
+    types::{LongInt, PyTrait, datetime, decimal, fraction},
     value::Value,
 };
 
 impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
-    /// Equality comparison.
-    pub(super) fn compare_eq(&mut self) {
+    /// Equality comparison, calling `__eq__` on instances of user-defined classes.
+    pub(super) fn compare_eq(&mut self) -> RunResult<()> {
         let rhs = self.pop();
         let lhs = self.pop();
-        let result = lhs.py_eq(&rhs, self.heap, self.interns);
+        let result = self.eq_values(&lhs, &rhs);
         lhs.drop_with_heap(self.heap);
         rhs.drop_with_heap(self.heap);
-        self.push(Value::Bool(result));
+        self.push(Value::Bool(result?));
+        Ok(())
     }
 
     /// Inequality comparison.
-    pub(super) fn compare_ne(&mut self) {
+    ///
+    /// Calls `__ne__` on instances of user-defined classes if defined, otherwise
+    /// inverts the result of [`Self::eq_values`].
+    pub(super) fn compare_ne(&mut self) -> RunResult<()> {
         let rhs = self.pop();
         let lhs = self.pop();
-        let result = !lhs.py_eq(&rhs, self.heap, self.interns);
+        let result = self.ne_values(&lhs, &rhs);
         lhs.drop_with_heap(self.heap);
         rhs.drop_with_heap(self.heap);
-        self.push(Value::Bool(result));
+        self.push(Value::Bool(result?));
+        Ok(())
     }
 
-    /// Ordering comparison with a predicate.
-    pub(super) fn compare_ord<F>(&mut self, check: F)
-    where
-        F: FnOnce(std::cmp::Ordering) -> bool,
-    {
+    fn ne_values(&mut self, lhs: &Value, rhs: &Value) -> RunResult<bool> {
+        if let Some(result) = self.call_dunder(lhs, StaticStrings::DunderNe, Some(rhs))? {
+            return Ok(self.truthy(result));
+        }
+        if let Some(result) = self.call_dunder(rhs, StaticStrings::DunderNe, Some(lhs))? {
+            return Ok(self.truthy(result));
+        }
+        Ok(!self.eq_values(lhs, rhs)?)
+    }
+
+    /// Ordering comparison (`<`, `<=`, `>`, `>=`).
+    ///
+    /// Instances of user-defined classes use the matching special method (e.g. `__lt__`),
+    /// falling back to the reflected method of the right operand (e.g. `__gt__`). Comparing
+    /// instances which define neither raises `TypeError`.
+    pub(super) fn compare_ord(&mut self, op: &CmpOperator) -> RunResult<()> {
         let rhs = self.pop();
         let lhs = self.pop();
-        let result = lhs.py_cmp(&rhs, self.heap, self.interns).is_some_and(check);
+        let result = self.ord_values(&lhs, &rhs, op);
         lhs.drop_with_heap(self.heap);
         rhs.drop_with_heap(self.heap);
-        self.push(Value::Bool(result));
+        self.push(Value::Bool(result?));
+        Ok(())
+    }
+
+    pub(super) fn ord_values(&mut self, lhs: &Value, rhs: &Value, op: &CmpOperator) -> RunResult<bool> {
+        let (dunder, reflected, symbol, check): (_, _, _, fn(Ordering) -> bool) = match op {
+            CmpOperator::Lt => (StaticStrings::DunderLt, StaticStrings::DunderGt, "<", Ordering::is_lt),
+            CmpOperator::LtE => (StaticStrings::DunderLe, StaticStrings::DunderGe, "<=", Ordering::is_le),
+            CmpOperator::Gt => (StaticStrings::DunderGt, StaticStrings::DunderLt, ">", Ordering::is_gt),
+            CmpOperator::GtE => (StaticStrings::DunderGe, StaticStrings::DunderLe, ">=", Ordering::is_ge),
+            _ => return Err(RunError::internal("compare_ord: not an ordering operator")),
+        };
+        if let Some(result) = self.call_dunder(lhs, dunder, Some(rhs))? {
+            return Ok(self.truthy(result));
+        }
+        if let Some(result) = self.call_dunder(rhs, reflected, Some(lhs))? {
+            return Ok(self.truthy(result));
+        }
+        if (self.contains_instance(lhs) || self.contains_instance(rhs))
+            && let Some(result) = self.ord_sequences(lhs, rhs, op, check)?
+        {
+            return Ok(result);
+        }
+        if self.is_instance(lhs) || self.is_instance(rhs) {
+            return Err(ExcType::type_error(format!(
+                "'{symbol}' not supported between instances of '{}' and '{}'",
+                self.type_name(lhs),
+                self.type_name(rhs)
+            )));
+        }
+        datetime::check_orderable(lhs, rhs, symbol, self.heap)?;
+        decimal::check_orderable(lhs, rhs, symbol, self.heap)?;
+        fraction::check_orderable(lhs, rhs, symbol, self.heap)?;
+        Ok(lhs.py_cmp(rhs, self.heap, self.interns).is_some_and(check))
+    }
+
+    /// Orders two lists or two tuples item by item, returning `None` for other values.
+    ///
+    /// Like CPython, the first items which aren't equal (by [`Self::eq_values`]) are
+    /// compared with [`Self::ord_values`]; if there are none, the lengths are compared.
+    fn ord_sequences(
+        &mut self,
+        lhs: &Value,
+        rhs: &Value,
+        op: &CmpOperator,
+        check: fn(Ordering) -> bool,
+    ) -> RunResult<Option<bool>> {
+        let (&Value::Ref(lhs_id), &Value::Ref(rhs_id)) = (lhs, rhs) else {
+            return Ok(None);
+        };
+        let same_kind = matches!(
+            (self.heap.get(lhs_id), self.heap.get(rhs_id)),
+            (HeapData::List(_), HeapData::List(_)) | (HeapData::Tuple(_), HeapData::Tuple(_))
+        );
+        if !same_kind {
+            return Ok(None);
+        }
+        let lhs_items = self.sequence_items(lhs).expect("checked to be a list or tuple");
+        let rhs_items = self.sequence_items(rhs).expect("checked to be a list or tuple");
+        let mut result = Ok(None);
+        for (a, b) in lhs_items.iter().zip(&rhs_items) {
+            if a.is(b) {
+                continue;
+            }
+            match self.eq_values(a, b) {
+                Ok(true) => {}
+                Ok(false) => {
+                    result = self.ord_values(a, b, op).map(Some);
+                    break;
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        if matches!(result, Ok(None)) {
+            result = Ok(Some(check(lhs_items.len().cmp(&rhs_items.len()))));
+        }
+        lhs_items.drop_with_heap(self.heap);
+        rhs_items.drop_with_heap(self.heap);
+        result
     }
 
     /// Identity comparison (is/is not).
@@ -64,11 +166,21 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
     }
 
     /// Membership test (in/not in).
+    ///
+    /// Instances of user-defined classes are compared with `__eq__`, and hashed with
+    /// `__hash__` to look them up in dicts and sets.
     pub(super) fn compare_in(&mut self, negate: bool) -> Result<(), RunError> {
+        self.materialize_top_vm_iterator()?;
         let container = self.pop(); // container (rhs)
         let item = self.pop(); // item to find (lhs)
 
-        let result = container.py_contains(&item, self.heap, self.interns);
+        let result = if (self.contains_instance(&item) || self.contains_instance(&container))
+            && let Some(items) = self.sequence_items(&container)
+        {
+            self.sequence_contains(&item, items)
+        } else {
+            self.contains_key(&container, &item)
+        };
 
         item.drop_with_heap(self.heap);
         container.drop_with_heap(self.heap);
@@ -78,6 +190,47 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         Ok(())
     }
 
+    /// Returns owned copies of the items of a list or tuple, or `None` for other values.
+    pub(super) fn sequence_items(&mut self, container: &Value) -> Option<Vec<Value>> {
+        let Value::Ref(id) = container else {
+            return None;
+        };
+        let items: Vec<Value> = match self.heap.get(*id) {
+            HeapData::List(list) => list.as_vec().iter().map(Value::copy_for_extend).collect(),
+            HeapData::Tuple(tuple) => tuple.as_vec().iter().map(Value::copy_for_extend).collect(),
+            _ => return None,
+        };
+        for item in &items {
+            if let Value::Ref(item_id) = item {
+                self.heap.inc_ref(*item_id);
+            }
+        }
+        Some(items)
+    }
+
+    /// Membership test in a list or tuple when the item or the items may be instances of
+    /// user-defined classes, which may define `__eq__`.
+    ///
+    /// Like CPython, each item matches if it is the same object or compares equal.
+    fn sequence_contains(&mut self, item: &Value, items: Vec<Value>) -> RunResult<bool> {
+        let mut result = Ok(false);
+        for candidate in &items {
+            if candidate.is(item) {
+                result = Ok(true);
+                break;
+            }
+            match self.eq_values(candidate, item) {
+                Ok(false) => {}
+                other => {
+                    result = other;
+                    break;
+                }
+            }
+        }
+        items.drop_with_heap(self.heap);
+        result
+    }
+
     /// Modulo equality comparison: a % b == k
     ///
     /// This is an optimization for patterns like `x % 3 == 0`. The constant k
@@ -105,7 +258,10 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         } else {
             // Fallback: compute py_mod then compare with py_eq
             // This handles LongInt and other Ref types
-            let mod_value = lhs.py_mod(&rhs, self.heap);
+            let mod_value = match lhs.py_mod(&rhs, self.heap) {
+                Ok(None) => self.percent_format(&lhs, &rhs),
+                mod_value => mod_value,
+            };
             lhs.drop_with_heap(self.heap);
             rhs.drop_with_heap(self.heap);
 
@@ -1,14 +1,17 @@
 //! Exception handling helpers for the VM.
 
+use smallvec::smallvec;
+
 use super::VM;
 use crate::{
+    args::ArgValues,
     builtins::Builtins,
-    exception_private::{ExcType, ExceptionRaise, RawStackFrame, RunError, SimpleException},
-    heap::HeapData,
+    exception_private::{ExcType, ExceptionRaise, RawStackFrame, RunError, RunResult, SimpleException},
+    heap::{HeapData, HeapId},
     intern::{StaticStrings, StringId},
     io::PrintWriter,
     resource::ResourceTracker,
-    types::{PyTrait, Type},
+    types::{PyTrait, Type, allocate_tuple},
     value::Value,
 };
 
@@ -68,31 +71,55 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
     /// Takes ownership of the exception value and drops it properly.
     /// The `is_raise` flag indicates if this is from a `raise` statement (hide caret).
     pub(super) fn make_exception(&mut self, exc_value: Value, is_raise: bool) -> RunError {
-        let simple_exc = match &exc_value {
+        let exc = self.raised_exception(exc_value).unwrap_or_else(|| {
+            SimpleException::new_msg(ExcType::TypeError, "exceptions must derive from BaseException")
+        });
+        self.raise_at_current_position(exc, is_raise)
+    }
+
+    /// Creates a RunError for `raise exc from cause`.
+    ///
+    /// A `None` cause clears `__cause__`; either way the exception's context is suppressed.
+    /// Takes ownership of both values.
+    pub(super) fn make_exception_from(&mut self, exc_value: Value, cause_value: Value) -> RunError {
+        let Some(mut exc) = self.raised_exception(exc_value) else {
+            cause_value.drop_with_heap(self.heap);
+            let exc = SimpleException::new_msg(ExcType::TypeError, "exceptions must derive from BaseException");
+            return self.raise_at_current_position(exc, true);
+        };
+        let cause = if matches!(cause_value, Value::None) {
+            None
+        } else if let Some(cause) = self.raised_exception(cause_value) {
+            Some(cause)
+        } else {
+            let exc = SimpleException::new_msg(ExcType::TypeError, "exception causes must derive from BaseException");
+            return self.raise_at_current_position(exc, true);
+        };
+        exc.set_cause(cause);
+        self.raise_at_current_position(exc, true)
+    }
+
+    /// Extracts the exception from a raised value, taking ownership of the value.
+    ///
+    /// Exception types are instantiated with no message. Returns `None` if the value is
+    /// neither an exception instance nor an exception type.
+    fn raised_exception(&mut self, value: Value) -> Option<SimpleException> {
+        let exc = match &value {
             // Exception instance on heap
-            Value::Ref(heap_id) => {
-                if let HeapData::Exception(exc) = self.heap.get(*heap_id) {
-                    // Clone the exception
-                    let exc_clone = exc.clone();
-                    // Drop the value with proper heap cleanup
-                    exc_value.drop_with_heap(self.heap);
-                    exc_clone
-                } else {
-                    // Not an exception type
-                    exc_value.drop_with_heap(self.heap);
-                    SimpleException::new_msg(ExcType::TypeError, "exceptions must derive from BaseException")
-                }
-            }
+            Value::Ref(heap_id) => match self.heap.get(*heap_id) {
+                HeapData::Exception(exc) => Some(exc.clone()),
+                _ => None,
+            },
             // Exception type (e.g., `raise ValueError` instead of `raise ValueError()`)
-            // Instantiate with no message
-            Value::Builtin(Builtins::ExcType(exc_type)) => SimpleException::new_none(*exc_type),
-            // Invalid exception value
-            _ => {
-                exc_value.drop_with_heap(self.heap);
-                SimpleException::new_msg(ExcType::TypeError, "exceptions must derive from BaseException")
-            }
+            Value::Builtin(Builtins::ExcType(exc_type)) => Some(SimpleException::new_none(*exc_type)),
+            _ => None,
         };
+        value.drop_with_heap(self.heap);
+        exc
+    }
 
+    /// Wraps an exception raised at the current position in a RunError.
+    fn raise_at_current_position(&self, exc: SimpleException, is_raise: bool) -> RunError {
         // Create frame with appropriate hide_caret setting
         let frame = if is_raise {
             RawStackFrame::from_raise(self.current_position(), self.current_frame_name())
@@ -101,12 +128,34 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         };
 
         RunError::Exc(ExceptionRaise {
-            exc: simple_exc,
+            exc,
             frame: Some(frame),
             hide_caret: false,
         })
     }
 
+    /// Sets the exception's `__context__` to the exception currently being handled.
+    ///
+    /// Exceptions which already have a context keep it: they were raised while an
+    /// exception was being handled, or are being re-raised. Raising the exception
+    /// currently being handled doesn't make it its own context.
+    fn attach_context_to_error(&self, error: &mut RunError) {
+        let RunError::Exc(exc) = error else {
+            return;
+        };
+        if exc.exc.context().is_some() {
+            return;
+        }
+        let Some(Value::Ref(handled_id)) = self.exception_stack.last() else {
+            return;
+        };
+        if let HeapData::Exception(handled) = self.heap.get(*handled_id)
+            && *handled != exc.exc
+        {
+            exc.exc.set_context(handled.clone());
+        }
+    }
+
     /// Handles an exception by searching for a handler in the exception table.
     ///
     /// Returns:
@@ -121,6 +170,7 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
     pub(super) fn handle_exception(&mut self, mut error: RunError) -> Option<RunError> {
         // Ensure exception has initial frame info
         error = self.attach_frame_to_error(error);
+        self.attach_context_to_error(&mut error);
 
         // For uncatchable exceptions (ResourceError like RecursionError),
         // we still need to unwind the stack to collect all frames for the traceback
@@ -128,19 +178,6 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
             return Some(self.unwind_for_traceback(error));
         }
 
-        // Only catchable exceptions can be handled
-        let exc_info = match &error {
-            RunError::Exc(exc) => exc.clone(),
-            RunError::UncatchableExc(_) | RunError::Internal(_) => unreachable!(),
-        };
-
-        // Create exception value to push on stack
-        let exc_value = self.create_exception_value(&exc_info);
-        let exc_value = match exc_value {
-            Ok(v) => v,
-            Err(e) => return Some(e),
-        };
-
         // Search for handler in current and outer frames
         loop {
             let frame = self.current_frame();
@@ -151,6 +188,7 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
                 // Found a handler! Unwind stack and jump to it.
                 let handler_offset = usize::try_from(entry.handler()).expect("handler offset exceeds usize");
                 let target_stack_depth = frame.stack_base + entry.stack_depth() as usize;
+                let target_exception_depth = frame.exception_base + usize::from(entry.exception_depth());
 
                 // Unwind stack to target depth (drop excess values)
                 while self.stack.len() > target_stack_depth {
@@ -158,6 +196,21 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
                     value.drop_with_heap(self.heap);
                 }
 
+                // Drop exceptions whose handlers were left by this exception
+                while self.exception_stack.len() > target_exception_depth {
+                    let exc = self.exception_stack.pop().unwrap();
+                    exc.drop_with_heap(self.heap);
+                }
+
+                // Create exception value to push on stack, keeping its traceback so far
+                let RunError::Exc(exc_info) = &error else {
+                    unreachable!("only catchable exceptions can be handled")
+                };
+                let exc_value = match self.create_exception_value(exc_info) {
+                    Ok(v) => v,
+                    Err(e) => return Some(e),
+                };
+
                 // Push exception value onto stack (handler expects it)
                 let exc_for_stack = exc_value.clone_with_heap(self.heap);
                 self.push(exc_for_stack);
@@ -172,11 +225,27 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
                 return None; // Continue execution at handler
             }
 
+            // `StopIteration` escaping a generator would silently end the loop consuming it
+            if self.current_frame().generator.is_some()
+                && let RunError::Exc(exc) = &mut error
+                && exc.exc.exc_type() == ExcType::StopIteration
+            {
+                let runtime_error = SimpleException::new_msg(ExcType::RuntimeError, "generator raised StopIteration");
+                let mut stop_iteration = std::mem::replace(&mut exc.exc, runtime_error);
+                stop_iteration.set_traceback(exc.frame.as_ref());
+                exc.exc.set_cause(Some(stop_iteration));
+            }
+
+            // No handler in this frame - if it was entered via `call_sync`, hand the error
+            // back to the Rust caller, which raises it in the calling frame
+            if self.is_sync_call_frame() {
+                self.pop_frame_for_traceback(&mut error);
+                return Some(error);
+            }
+
             // No handler in this frame - pop frame and try outer
             if self.frames.len() <= 1 {
                 // No more frames - exception is unhandled
-                exc_value.drop_with_heap(self.heap);
-
                 // For spawned tasks, fail the task instead of propagating
                 if self.is_spawned_task() {
                     match self.handle_task_failure(error) {
@@ -194,28 +263,12 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
                 return Some(error);
             }
 
-            // Get the call site position before popping frame
-            // This is where the caller invoked the function that's failing
-            let call_position = self.current_frame().call_position;
-
-            // Pop this frame
-            self.pop_frame();
+            // Pop this frame, recording the call site in the traceback
+            self.pop_frame_for_traceback(&mut error);
 
-            // Add caller frame info to traceback (if we have call position)
-            if let Some(pos) = call_position {
-                let frame_name = self.current_frame_name();
-                match &mut error {
-                    RunError::Exc(exc) => exc.add_caller_frame(pos, frame_name),
-                    RunError::UncatchableExc(exc) => exc.add_caller_frame(pos, frame_name),
-                    RunError::Internal(_) => {}
-                }
-            }
-
-            // Update instruction_ip for the new frame
-            self.instruction_ip = self
-                .current_frame()
-                .call_position
-                .map_or(0, |p| p.start().line as usize);
+            // Update instruction_ip for the new frame: its IP is the return address just
+            // past the call instruction, so the instruction before it is still the call
+            self.instruction_ip = self.current_frame().ip - 1;
         }
     }
 
@@ -224,32 +277,47 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
     /// Used for uncatchable exceptions (like RecursionError) that can't be handled
     /// but still need a complete traceback showing all active call frames.
     fn unwind_for_traceback(&mut self, mut error: RunError) -> RunError {
-        // Pop frames and add caller frame info to the traceback
-        while self.frames.len() > 1 {
-            // Get the call site position before popping frame
-            let call_position = self.current_frame().call_position;
-
-            // Pop this frame (cleans up namespace, etc.)
-            self.pop_frame();
-
-            // Add caller frame info to traceback
-            if let Some(pos) = call_position {
-                let frame_name = self.current_frame_name();
-                match &mut error {
-                    RunError::Exc(exc) => exc.add_caller_frame(pos, frame_name),
-                    RunError::UncatchableExc(exc) => exc.add_caller_frame(pos, frame_name),
-                    RunError::Internal(_) => {}
-                }
-            }
+        // Pop frames and add caller frame info to the traceback, stopping at the
+        // frame that started a nested `call_sync` (its caller continues unwinding)
+        let floor = self.sync_call_bases.last().copied().unwrap_or(1);
+        while self.frames.len() > floor {
+            self.pop_frame_for_traceback(&mut error);
         }
         error
     }
 
+    /// Returns whether the current frame was pushed by `call_sync`.
+    fn is_sync_call_frame(&self) -> bool {
+        self.sync_call_bases.last() == Some(&(self.frames.len() - 1))
+    }
+
+    /// Pops the current frame and adds its call site to the error's traceback.
+    fn pop_frame_for_traceback(&mut self, error: &mut RunError) {
+        // Get the call site position before popping frame
+        // This is where the caller invoked the function that's failing
+        let call_position = self.current_frame().call_position;
+
+        // Pop this frame (cleans up namespace, etc.)
+        self.pop_frame();
+
+        // Add caller frame info to traceback (if we have call position)
+        if let Some(pos) = call_position {
+            let frame_name = self.current_frame_name();
+            match error {
+                RunError::Exc(exc) => exc.add_caller_frame(pos, frame_name),
+                RunError::UncatchableExc(exc) => exc.add_caller_frame(pos, frame_name),
+                RunError::Internal(_) => {}
+            }
+        }
+    }
+
     /// Creates an exception Value from exception info.
     ///
-    /// Allocates an Exception on the heap and returns a Value::Ref to it.
+    /// Allocates an Exception on the heap and returns a Value::Ref to it. The exception
+    /// keeps the traceback for display as part of a later exception's chain.
     fn create_exception_value(&mut self, exc: &ExceptionRaise) -> Result<Value, RunError> {
-        let exception = exc.exc.clone();
+        let mut exception = exc.exc.clone();
+        exception.set_traceback(exc.frame.as_ref());
         let heap_id = self.heap.allocate(HeapData::Exception(exception))?;
         Ok(Value::Ref(heap_id))
     }
@@ -290,3 +358,193 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
         }
     }
 }
+
+/// Exception group support: `except*` clauses and the `split()` and `subgroup()` methods.
+impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
+    /// Splits off the part of an exception which matches an `except*` clause.
+    ///
+    /// Stack: [..., rest, exc_type] -> [..., rest, match]
+    /// `rest` is the part of the exception not matched by earlier clauses, `None` once
+    /// everything has matched. An exception which isn't a group is wrapped in a group with
+    /// an empty message when it matches. The match is `None` if nothing matched, otherwise it
+    /// becomes the exception being handled.
+    pub(super) fn check_eg_match(&mut self) -> RunResult<()> {
+        let exc_type = self.pop();
+        let mut types = Vec::new();
+        let valid = self.collect_exc_types(&exc_type, &mut types);
+        exc_type.drop_with_heap(self.heap);
+        if !valid {
+            return Err(ExcType::except_invalid_type_error());
+        }
+        if types.iter().any(|exc_type| exc_type.is_group()) {
+            return Err(ExcType::except_star_group_type_error());
+        }
+
+        let rest = self.pop();
+        let exc = match &rest {
+            Value::Ref(heap_id) => match self.heap.get(*heap_id) {
+                HeapData::Exception(exc) => Some(exc.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+        let is_match = |exc: &SimpleException| types.iter().any(|t| exc.exc_type().is_subclass_of(*t));
+        let (matched, unmatched) = match exc {
+            Some(exc) if exc.exc_type().is_group() => exc.split(&mut |exc| Ok(is_match(exc)))?,
+            Some(exc) if is_match(&exc) => (Some(SimpleException::new_group(String::new(), vec![exc], true)), None),
+            _ => (None, None),
+        };
+        let Some(matched) = matched else {
+            self.push(rest);
+            self.push(Value::None);
+            return Ok(());
+        };
+        rest.drop_with_heap(self.heap);
+
+        let unmatched = self.allocate_exception(unmatched)?;
+        self.push(unmatched);
+        let matched = Value::Ref(self.heap.allocate(HeapData::Exception(matched))?);
+        self.exception_stack.push(matched.clone_with_heap(self.heap));
+        self.push(matched);
+        Ok(())
+    }
+
+    /// Works out what an `except*` statement raises once all its clauses have run.
+    ///
+    /// Stack: [..., exception, raised] -> [..., result]
+    /// `raised` is a list of the exceptions raised by the clauses followed by the unhandled
+    /// rest of the exception, with `None` for nothing unhandled. The result is `None` if
+    /// nothing should be raised. See [`SimpleException::prep_reraise_star`].
+    pub(super) fn prep_reraise_star(&mut self) -> RunResult<()> {
+        let raised = self.pop();
+        let exception = self.pop();
+        let exception_of = |value: &Value| match value {
+            Value::Ref(heap_id) => match self.heap.get(*heap_id) {
+                HeapData::Exception(exc) => Some(exc.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+        let orig = exception_of(&exception);
+        let raised_excs: Vec<SimpleException> = match &raised {
+            Value::Ref(heap_id) => match self.heap.get(*heap_id) {
+                HeapData::List(list) => list.as_vec().iter().filter_map(exception_of).collect(),
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+        raised.drop_with_heap(self.heap);
+        exception.drop_with_heap(self.heap);
+
+        let Some(orig) = orig else {
+            return Err(RunError::internal("PrepReraiseStar: expected exception on stack"));
+        };
+        let result = self.allocate_exception(orig.prep_reraise_star(raised_excs))?;
+        self.push(result);
+        Ok(())
+    }
+
+    /// Calls `split()` or `subgroup()` on an exception group.
+    ///
+    /// The condition is an exception type, a tuple of exception types, or a function called
+    /// with each exception and nested group. `split()` returns a `(match, rest)` tuple and
+    /// `subgroup()` just the matching part, with `None` for a part which would be empty.
+    pub(super) fn call_group_method(
+        &mut self,
+        heap_id: HeapId,
+        name_id: StringId,
+        args: ArgValues,
+    ) -> RunResult<Value> {
+        let is_split = name_id == StaticStrings::Split;
+        let name = if is_split {
+            "BaseExceptionGroup.split"
+        } else {
+            "BaseExceptionGroup.subgroup"
+        };
+        let condition = args.get_one_arg(name, self.heap)?;
+        let HeapData::Exception(exc) = self.heap.get(heap_id) else {
+            condition.drop_with_heap(self.heap);
+            return Err(RunError::internal("call_group_method: expected exception group"));
+        };
+        let exc = exc.clone();
+
+        let mut types = Vec::new();
+        let split = if self.collect_exc_types(&condition, &mut types) {
+            exc.split(&mut |exc| Ok(types.iter().any(|t| exc.exc_type().is_subclass_of(*t))))
+        } else if self.is_group_predicate(&condition) {
+            exc.split(&mut |exc| self.call_group_predicate(&condition, exc))
+        } else {
+            Err(ExcType::type_error_group_condition())
+        };
+        condition.drop_with_heap(self.heap);
+        let (matched, rest) = split?;
+
+        let matched = self.allocate_exception(matched)?;
+        if !is_split {
+            return Ok(matched);
+        }
+        let rest = match self.allocate_exception(rest) {
+            Ok(rest) => rest,
+            Err(err) => {
+                matched.drop_with_heap(self.heap);
+                return Err(err);
+            }
+        };
+        Ok(allocate_tuple(smallvec![matched, rest], self.heap)?)
+    }
+
+    /// Calls the function given to `split()` or `subgroup()` with an exception, returning whether it matched.
+    fn call_group_predicate(&mut self, predicate: &Value, exc: &SimpleException) -> RunResult<bool> {
+        let exc_id = self.heap.allocate(HeapData::Exception(exc.clone()))?;
+        let predicate = predicate.clone_with_heap(self.heap);
+        let result = self.call_sync(predicate, ArgValues::One(Value::Ref(exc_id)))?;
+        Ok(self.truthy(result))
+    }
+
+    /// Returns whether a value can be called as the condition of `split()` or `subgroup()`.
+    ///
+    /// Like CPython, classes other than exception types aren't accepted.
+    fn is_group_predicate(&self, value: &Value) -> bool {
+        match value {
+            Value::Builtin(Builtins::Function(_))
+            | Value::ModuleFunction(_)
+            | Value::DefFunction(_)
+            | Value::ExtFunction(_) => true,
+            Value::Ref(heap_id) => matches!(
+                self.heap.get(*heap_id),
+                HeapData::Closure(..)
+                    | HeapData::FunctionDefaults(..)
+                    | HeapData::BoundMethod(_)
+                    | HeapData::Partial(_)
+                    | HeapData::LruCacheWrapper(_)
+                    | HeapData::Instance(_)
+            ),
+            _ => false,
+        }
+    }
+
+    /// Collects the exception types from an exception type or a (nested) tuple of them.
+    ///
+    /// Returns `false` if the value contains anything other than exception types.
+    fn collect_exc_types(&self, value: &Value, types: &mut Vec<ExcType>) -> bool {
+        match value {
+            Value::Builtin(Builtins::ExcType(exc_type)) => {
+                types.push(*exc_type);
+                true
+            }
+            Value::Ref(heap_id) => match self.heap.get(*heap_id) {
+                HeapData::Tuple(tuple) => tuple.as_vec().iter().all(|v| self.collect_exc_types(v, types)),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Allocates an exception on the heap, returning `None` for no exception.
+    fn allocate_exception(&mut self, exc: Option<SimpleException>) -> RunResult<Value> {
+        match exc {
+            Some(exc) => Ok(Value::Ref(self.heap.allocate(HeapData::Exception(exc))?)),
+            None => Ok(Value::None),
+        }
+    }
+}
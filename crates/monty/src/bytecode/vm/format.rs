@@ -1,15 +1,68 @@
-//! F-string and value formatting helpers for the VM.
+//! F-string, `str.format()` and printf-style formatting helpers for the VM.
+//!
+//! All three share the f-string formatting functions in `fstring.rs`. They run in the
+//! VM since converting instances of user-defined classes to strings calls `__str__`
+//! or `__repr__`.
+
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, Signed, ToPrimitive};
 
 use super::VM;
 use crate::{
-    exception_private::{ExcType, RunError, SimpleException},
-    fstring::{ParsedFormatSpec, ascii_escape, decode_format_spec, format_string, format_with_spec},
+    args::ArgValues,
+    builtins::getattr_by_name,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    fstring::{
+        ConversionFlag, FormatFieldArg, FormatFieldLookup, FormatTemplatePart, ParsedFormatSpec, PercentCount,
+        PercentSpec, ascii_escape, decode_format_spec, format_char, format_float_e, format_float_f, format_float_g,
+        format_percent_int, format_str_with_spec, format_string, format_with_spec, parse_format_field_name,
+        parse_format_template, parse_percent_spec,
+    },
+    heap::{DropWithHeap, Heap, HeapData},
+    intern::{Interns, StaticStrings, StringId},
     io::PrintWriter,
     resource::ResourceTracker,
-    types::{PyTrait, str::allocate_string},
+    types::{Bytes, PyTrait, datetime, r#enum, str::allocate_string},
     value::Value,
 };
 
+/// The arguments of `str.format()` or `str.format_map()`.
+enum FormatArgs {
+    Args {
+        positional: Vec<Value>,
+        kwargs: Vec<(Value, Value)>,
+    },
+    Mapping(Value),
+}
+
+impl FormatArgs {
+    fn drop_with_heap(self, heap: &mut Heap<impl ResourceTracker>) {
+        match self {
+            Self::Args { positional, kwargs } => {
+                positional.drop_with_heap(heap);
+                for (key, value) in kwargs {
+                    key.drop_with_heap(heap);
+                    value.drop_with_heap(heap);
+                }
+            }
+            Self::Mapping(mapping) => mapping.drop_with_heap(heap),
+        }
+    }
+}
+
+/// How the replacement fields of a `str.format()` template pick positional arguments.
+///
+/// Automatic numbering (`{}`) and manual numbering (`{0}`) can't be mixed.
+#[derive(Clone, Copy)]
+enum FieldNumbering {
+    Unknown,
+    Auto(usize),
+    Manual,
+}
+
+/// Maximum nesting of replacement fields in format specs, like CPython.
+const MAX_FORMAT_RECURSION: u8 = 2;
+
 impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
     /// Builds an f-string by concatenating n string parts from the stack.
     pub(super) fn build_fstring(&mut self, count: usize) -> Result<(), RunError> {
@@ -45,85 +98,92 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
     /// - !r conversion: convert to repr first, then format as string
     /// - !a conversion: convert to ascii repr first, then format as string
     pub(super) fn format_value(&mut self, flags: u8) -> Result<(), RunError> {
-        let conversion = flags & 0x03;
+        let conversion = match flags & 0x03 {
+            1 => ConversionFlag::Str,
+            2 => ConversionFlag::Repr,
+            3 => ConversionFlag::Ascii,
+            _ => ConversionFlag::None,
+        };
         let has_format_spec = (flags & 0x04) != 0;
 
         // Pop format spec if present (pushed before value, so popped after)
-        let format_spec = if has_format_spec {
-            let spec_value = self.pop();
-            Some(spec_value)
-        } else {
-            None
-        };
-
+        let format_spec = if has_format_spec { Some(self.pop()) } else { None };
         let value = self.pop();
 
-        // Format with spec applied to original value type, or convert and format as string
-        let formatted = if let Some(spec_value) = format_spec {
-            // Get the parsed format spec
-            let spec = match self.get_format_spec(&spec_value, &value) {
-                Ok(s) => s,
-                Err(e) => {
-                    // Clean up both values before returning error
-                    spec_value.drop_with_heap(self.heap);
-                    value.drop_with_heap(self.heap);
-                    return Err(e);
-                }
-            };
-
-            // Format based on value type and conversion flag
-            // Use a helper closure to handle errors with proper cleanup
-            let format_result: Result<String, RunError> = match conversion {
-                // No conversion - format original value
-                0 => format_with_spec(&value, &spec, self.heap, self.interns),
-                // !s - convert to str, format as string
-                1 => {
-                    let s = value.py_str(self.heap, self.interns);
-                    format_string(&s, &spec).map_err(Into::into)
-                }
-                // !r - convert to repr, format as string
-                2 => {
-                    let s = value.py_repr(self.heap, self.interns);
-                    format_string(&s, &spec).map_err(Into::into)
-                }
-                // !a - convert to ascii, format as string
-                3 => {
-                    let s = ascii_escape(&value.py_repr(self.heap, self.interns));
-                    format_string(&s, &spec).map_err(Into::into)
-                }
-                _ => format_with_spec(&value, &spec, self.heap, self.interns),
-            };
-
-            // Handle format errors with proper cleanup
-            match format_result {
-                Ok(result) => {
-                    spec_value.drop_with_heap(self.heap);
-                    result
-                }
-                Err(e) => {
-                    spec_value.drop_with_heap(self.heap);
-                    value.drop_with_heap(self.heap);
-                    return Err(e);
-                }
-            }
-        } else {
-            // No format spec - just convert based on conversion flag
-            match conversion {
-                0 => value.py_str(self.heap, self.interns).into_owned(),
-                1 => value.py_str(self.heap, self.interns).into_owned(),
-                2 => value.py_repr(self.heap, self.interns).into_owned(),
-                3 => ascii_escape(&value.py_repr(self.heap, self.interns)),
-                _ => value.py_str(self.heap, self.interns).into_owned(),
+        // Dates and times take `strftime()` formats, which are never pre-parsed specs
+        let strftime = match &format_spec {
+            Some(spec_value) if conversion == ConversionFlag::None && !matches!(spec_value, Value::Int(_)) => {
+                self.strftime_spec(&value, &spec_value.py_str(self.heap, self.interns))
             }
+            _ => None,
         };
+        if let Some(formatted) = strftime {
+            format_spec.drop_with_heap(self.heap);
+            value.drop_with_heap(self.heap);
+            let value = allocate_string(formatted, self.heap)?;
+            self.push(value);
+            return Ok(());
+        }
 
+        let spec = match &format_spec {
+            Some(spec_value) => self.get_format_spec(spec_value, &value).map(Some),
+            None => Ok(None),
+        };
+        format_spec.drop_with_heap(self.heap);
+        let formatted = spec.and_then(|spec| self.format_converted(&value, conversion, spec.as_ref()));
         value.drop_with_heap(self.heap);
 
-        let value = allocate_string(formatted, self.heap)?;
+        let value = allocate_string(formatted?, self.heap)?;
         self.push(value);
         Ok(())
     }
 
+    /// Converts a value to a string with a conversion flag, then applies a format spec.
+    ///
+    /// Without a conversion the spec is applied to the value itself (type-specific
+    /// formatting), otherwise to the converted string. Instances of user-defined classes
+    /// may define `__str__` or `__repr__`, so they are always converted to a string first.
+    fn format_converted(
+        &mut self,
+        value: &Value,
+        conversion: ConversionFlag,
+        spec: Option<&ParsedFormatSpec>,
+    ) -> RunResult<String> {
+        // `IntEnum` and `StrEnum` members format like their values when given a spec
+        if conversion == ConversionFlag::None
+            && spec.is_some()
+            && let Some(inner) = r#enum::owned_mixin_value(value, self.heap, self.interns)
+        {
+            let result = self.format_converted(&inner, conversion, spec);
+            inner.drop_with_heap(self.heap);
+            return result;
+        }
+        let converted = match conversion {
+            ConversionFlag::None if !self.contains_instance(value) => {
+                return match spec {
+                    Some(spec) => format_with_spec(value, spec, self.heap, self.interns),
+                    None => Ok(value.py_str(self.heap, self.interns).into_owned()),
+                };
+            }
+            ConversionFlag::None | ConversionFlag::Str => self.str_value(value)?,
+            ConversionFlag::Repr => self.repr_value(value)?,
+            ConversionFlag::Ascii => ascii_escape(&self.repr_value(value)?),
+        };
+        match spec {
+            Some(spec) => format_str_with_spec(&converted, spec),
+            None => Ok(converted),
+        }
+    }
+
+    /// Formats a date, time or datetime with a format spec, which for them is a `strftime()` format.
+    ///
+    /// Returns `None` for other values.
+    fn strftime_spec(&self, value: &Value, spec: &str) -> Option<String> {
+        value
+            .ref_id()
+            .and_then(|id| datetime::format_spec(self.heap.get(id), spec))
+    }
+
     /// Gets a ParsedFormatSpec from a format spec value.
     ///
     /// The `value_for_error` parameter is used to include the value type in error messages.
@@ -135,21 +195,584 @@ impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
                 let encoded = u64::try_from((-*n) - 1).expect("format spec encoding validated non-negative");
                 Ok(decode_format_spec(encoded))
             }
-            _ => {
-                // Dynamic format spec - parse the string
-                let spec_str = spec_value.py_str(self.heap, self.interns);
-                spec_str.parse::<ParsedFormatSpec>().map_err(|invalid| {
-                    // Only fetch type in error path
-                    let value_type = value_for_error.py_type(self.heap);
-                    RunError::Exc(
-                        SimpleException::new_msg(
-                            ExcType::ValueError,
-                            format!("Invalid format specifier '{invalid}' for object of type '{value_type}'"),
-                        )
-                        .into(),
+            // Dynamic format spec - parse the string
+            _ => self.parse_format_spec(&spec_value.py_str(self.heap, self.interns), value_for_error),
+        }
+    }
+
+    /// Parses a format spec string, like the `>10` of `f"{x:>10}"`.
+    fn parse_format_spec(&self, spec: &str, value_for_error: &Value) -> Result<ParsedFormatSpec, RunError> {
+        spec.parse::<ParsedFormatSpec>().map_err(|invalid| {
+            // Only fetch type in error path
+            let value_type = value_for_error.py_type(self.heap);
+            RunError::Exc(
+                SimpleException::new_msg(
+                    ExcType::ValueError,
+                    format!("Invalid format specifier '{invalid}' for object of type '{value_type}'"),
+                )
+                .into(),
+            )
+        })
+    }
+
+    /// Implements `str.format(*args, **kwargs)` and `str.format_map(mapping)`.
+    ///
+    /// Takes ownership of `template`, the string the method is called on.
+    pub(super) fn call_str_format(&mut self, template: Value, name_id: StringId, args: ArgValues) -> RunResult<Value> {
+        let template_str = template.py_str(self.heap, self.interns).into_owned();
+        template.drop_with_heap(self.heap);
+
+        let args = if name_id == StaticStrings::FormatMap {
+            FormatArgs::Mapping(args.get_one_arg("str.format_map", self.heap)?)
+        } else {
+            let (positional, kwargs) = args.into_parts();
+            FormatArgs::Args {
+                positional: positional.collect(),
+                kwargs: kwargs.into_iter().collect(),
+            }
+        };
+
+        let mut out = String::new();
+        let mut numbering = FieldNumbering::Unknown;
+        let result = self.render_format_template(&template_str, &args, &mut numbering, MAX_FORMAT_RECURSION, &mut out);
+        args.drop_with_heap(self.heap);
+        result?;
+        allocate_string(out, self.heap)
+    }
+
+    /// Renders a `str.format()` template, or a format spec containing replacement fields.
+    fn render_format_template(
+        &mut self,
+        template: &str,
+        args: &FormatArgs,
+        numbering: &mut FieldNumbering,
+        recursion: u8,
+        out: &mut String,
+    ) -> RunResult<()> {
+        if recursion == 0 {
+            return Err(SimpleException::new_msg(ExcType::ValueError, "Max string recursion exceeded").into());
+        }
+        for part in parse_format_template(template)? {
+            let (name, conversion, spec) = match part {
+                FormatTemplatePart::Literal(text) => {
+                    out.push_str(text);
+                    continue;
+                }
+                FormatTemplatePart::Field { name, conversion, spec } => (name, conversion, spec),
+            };
+
+            let value = self.format_field_value(name, args, numbering)?;
+            let formatted = self.format_field(&value, conversion, spec, args, numbering, recursion);
+            value.drop_with_heap(self.heap);
+            out.push_str(&formatted?);
+        }
+        Ok(())
+    }
+
+    /// Formats the value of a replacement field, rendering any replacement fields in its spec first.
+    fn format_field(
+        &mut self,
+        value: &Value,
+        conversion: ConversionFlag,
+        spec: &str,
+        args: &FormatArgs,
+        numbering: &mut FieldNumbering,
+        recursion: u8,
+    ) -> RunResult<String> {
+        let rendered_spec;
+        let spec = if spec.contains('{') {
+            let mut rendered = String::new();
+            self.render_format_template(spec, args, numbering, recursion - 1, &mut rendered)?;
+            rendered_spec = rendered;
+            rendered_spec.as_str()
+        } else {
+            spec
+        };
+        if spec.is_empty() {
+            self.format_converted(value, conversion, None)
+        } else if conversion == ConversionFlag::None
+            && let Some(formatted) = self.strftime_spec(value, spec)
+        {
+            Ok(formatted)
+        } else {
+            let spec = self.parse_format_spec(spec, value)?;
+            self.format_converted(value, conversion, Some(&spec))
+        }
+    }
+
+    /// Looks up the value of a replacement field, like `0`, `name.attr` or `items[0]`.
+    fn format_field_value(
+        &mut self,
+        name: &str,
+        args: &FormatArgs,
+        numbering: &mut FieldNumbering,
+    ) -> RunResult<Value> {
+        let (arg, lookups) = parse_format_field_name(name)?;
+
+        let mut value = match (arg, args) {
+            (FormatFieldArg::Name(key), FormatArgs::Mapping(mapping)) => {
+                let key = allocate_string(key.to_owned(), self.heap)?;
+                let value = mapping.py_getitem(&key, self.heap, self.interns);
+                key.drop_with_heap(self.heap);
+                value?
+            }
+            (_, FormatArgs::Mapping(_)) => {
+                return Err(
+                    SimpleException::new_msg(ExcType::ValueError, "Format string contains positional fields").into(),
+                );
+            }
+            (FormatFieldArg::Name(key), FormatArgs::Args { kwargs, .. }) => {
+                let found = kwargs
+                    .iter()
+                    .find(|(name, _)| name.py_str(self.heap, self.interns) == key);
+                match found {
+                    Some((_, value)) => value.clone_with_heap(self.heap),
+                    None => {
+                        let key = allocate_string(key.to_owned(), self.heap)?;
+                        let error = ExcType::key_error(&key, self.heap, self.interns);
+                        key.drop_with_heap(self.heap);
+                        return Err(error);
+                    }
+                }
+            }
+            (FormatFieldArg::Auto | FormatFieldArg::Index(_), FormatArgs::Args { positional, .. }) => {
+                let index = next_field_index(arg, numbering)?;
+                let Some(value) = positional.get(index) else {
+                    return Err(SimpleException::new_msg(
+                        ExcType::IndexError,
+                        format!("Replacement index {index} out of range for positional args tuple"),
                     )
-                })
+                    .into());
+                };
+                value.clone_with_heap(self.heap)
             }
+        };
+
+        for lookup in lookups {
+            let next = match lookup {
+                FormatFieldLookup::Attr(attr) => getattr_by_name(&value, attr, self.heap, self.interns),
+                FormatFieldLookup::Index(index) => match i64::try_from(index) {
+                    Ok(index) => value.py_getitem(&Value::Int(index), self.heap, self.interns),
+                    Err(_) => Err(ExcType::index_error_int_too_large()),
+                },
+                FormatFieldLookup::Key(key) => match allocate_string(key.to_owned(), self.heap) {
+                    Ok(key) => {
+                        let item = value.py_getitem(&key, self.heap, self.interns);
+                        key.drop_with_heap(self.heap);
+                        item
+                    }
+                    Err(e) => Err(e),
+                },
+            };
+            value.drop_with_heap(self.heap);
+            value = next?;
+        }
+        Ok(value)
+    }
+
+    /// Implements printf-style formatting, `template % args`, for `str` and `bytes` templates.
+    ///
+    /// Returns `None` if `template` is neither, so `%` can report unsupported operands.
+    pub(super) fn percent_format(&mut self, template: &Value, args: &Value) -> RunResult<Option<Value>> {
+        let (template, is_bytes) = match template {
+            Value::InternString(id) => (self.interns.get_str(*id).as_bytes().to_vec(), false),
+            Value::InternBytes(id) => (self.interns.get_bytes(*id).to_vec(), true),
+            Value::Ref(id) => match self.heap.get(*id) {
+                HeapData::Str(s) => (s.as_str().as_bytes().to_vec(), false),
+                HeapData::Bytes(b) => (b.as_slice().to_vec(), true),
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        // A tuple provides the arguments, any other value is the single argument. Dicts
+        // (and lists, like CPython) may also provide the values of `%(key)s` specifiers.
+        let (items, mapping): (Vec<Value>, Option<&Value>) = match args {
+            Value::Ref(id) => match self.heap.get(*id) {
+                HeapData::Tuple(tuple) => (tuple.as_vec().iter().map(Value::copy_for_extend).collect(), None),
+                HeapData::Dict(_) | HeapData::List(_) => (vec![args.copy_for_extend()], Some(args)),
+                _ => (vec![args.copy_for_extend()], None),
+            },
+            _ => (vec![args.copy_for_extend()], None),
+        };
+        for item in &items {
+            if let Value::Ref(item_id) = item {
+                self.heap.inc_ref(*item_id);
+            }
+        }
+
+        let result = self.render_percent_template(&template, is_bytes, &items, mapping);
+        items.drop_with_heap(self.heap);
+        let out = result?;
+
+        let value = if is_bytes {
+            Value::Ref(self.heap.allocate(HeapData::Bytes(Bytes::new(out)))?)
+        } else {
+            let out = String::from_utf8(out).expect("formatting a str template produces UTF-8");
+            allocate_string(out, self.heap)?
+        };
+        Ok(Some(value))
+    }
+
+    /// Renders a printf-style template with the given arguments.
+    fn render_percent_template(
+        &mut self,
+        template: &[u8],
+        is_bytes: bool,
+        args: &[Value],
+        mapping: Option<&Value>,
+    ) -> RunResult<Vec<u8>> {
+        let mut out = Vec::with_capacity(template.len());
+        let mut next_arg = 0;
+        let mut pos = 0;
+        while let Some(offset) = template[pos..].iter().position(|&b| b == b'%') {
+            out.extend_from_slice(&template[pos..pos + offset]);
+            let spec = parse_percent_spec(template, pos + offset + 1)?;
+            pos = spec.end;
+            if spec.conversion == b'%' {
+                out.push(b'%');
+                continue;
+            }
+
+            let mut left_align = spec.left_align;
+            let width = match spec.width {
+                None => 0,
+                Some(PercentCount::Fixed(width)) => width,
+                Some(PercentCount::FromArgs) => {
+                    let width = percent_star_arg(args, &mut next_arg)?;
+                    left_align |= width < 0;
+                    usize::try_from(width.unsigned_abs()).unwrap_or(usize::MAX)
+                }
+            };
+            let precision = match spec.precision {
+                None => None,
+                Some(PercentCount::Fixed(precision)) => Some(precision),
+                Some(PercentCount::FromArgs) => {
+                    Some(usize::try_from(percent_star_arg(args, &mut next_arg)?).unwrap_or(0))
+                }
+            };
+            let format_spec = spec.format_spec(left_align, width, precision);
+
+            let value = match spec.key {
+                Some(key) => {
+                    let Some(mapping) = mapping else {
+                        return Err(ExcType::type_error("format requires a mapping"));
+                    };
+                    let key = if is_bytes {
+                        Value::Ref(self.heap.allocate(HeapData::Bytes(Bytes::new(key.to_vec())))?)
+                    } else {
+                        allocate_string(String::from_utf8_lossy(key).into_owned(), self.heap)?
+                    };
+                    let value = mapping.py_getitem(&key, self.heap, self.interns);
+                    key.drop_with_heap(self.heap);
+                    // The value for the key takes the place of the arguments
+                    next_arg = args.len();
+                    value?
+                }
+                None => {
+                    let Some(arg) = args.get(next_arg) else {
+                        return Err(ExcType::type_error("not enough arguments for format string"));
+                    };
+                    next_arg += 1;
+                    arg.clone_with_heap(self.heap)
+                }
+            };
+            let formatted = self.percent_format_value(&value, &spec, &format_spec, is_bytes, template);
+            value.drop_with_heap(self.heap);
+            out.extend_from_slice(&formatted?);
+        }
+        out.extend_from_slice(&template[pos..]);
+
+        if mapping.is_none() && next_arg < args.len() {
+            return Err(ExcType::type_error(
+                "not all arguments converted during string formatting",
+            ));
         }
+        Ok(out)
     }
+
+    /// Formats one argument of a printf-style template.
+    fn percent_format_value(
+        &mut self,
+        value: &Value,
+        spec: &PercentSpec<'_>,
+        format_spec: &ParsedFormatSpec,
+        is_bytes: bool,
+        template: &[u8],
+    ) -> RunResult<Vec<u8>> {
+        let conversion = spec.conversion;
+        // `IntEnum` members are numbers for the numeric conversions
+        if !matches!(conversion, b's' | b'b' | b'r' | b'a')
+            && let Some(inner) = r#enum::owned_mixin_value(value, self.heap, self.interns)
+        {
+            let result = self.percent_format_value(&inner, spec, format_spec, is_bytes, template);
+            inner.drop_with_heap(self.heap);
+            return result;
+        }
+        let text = match conversion {
+            b's' | b'b' if is_bytes => return self.percent_format_bytes(value, format_spec),
+            b's' => format_string(&self.str_value(value)?, format_spec)?,
+            b'r' | b'a' => {
+                let repr = self.repr_value(value)?;
+                // `bytes` templates only take ASCII, so `%r` is `%a` there
+                let repr = if conversion == b'a' || is_bytes {
+                    ascii_escape(&repr)
+                } else {
+                    repr
+                };
+                format_string(&repr, format_spec)?
+            }
+            b'd' | b'i' | b'u' => {
+                let Some((negative, digits)) = percent_int_digits(value, 10, true, self.heap, self.interns)? else {
+                    return Err(ExcType::type_error(format!(
+                        "%{} format: a real number is required, not {}",
+                        char::from(conversion),
+                        self.type_name(value)
+                    )));
+                };
+                format_percent_int(negative, &digits, "", format_spec)
+            }
+            b'x' | b'X' | b'o' => {
+                let radix = if conversion == b'o' { 8 } else { 16 };
+                let Some((negative, digits)) = percent_int_digits(value, radix, false, self.heap, self.interns)? else {
+                    return Err(ExcType::type_error(format!(
+                        "%{} format: an integer is required, not {}",
+                        char::from(conversion),
+                        self.type_name(value)
+                    )));
+                };
+                let prefix = match (spec.alternate, conversion) {
+                    (false, _) => "",
+                    (true, b'o') => "0o",
+                    (true, _) => "0x",
+                };
+                let formatted = format_percent_int(negative, &digits, prefix, format_spec);
+                if conversion == b'X' {
+                    formatted.to_uppercase()
+                } else {
+                    formatted
+                }
+            }
+            b'e' | b'E' | b'f' | b'F' | b'g' | b'G' => {
+                let Some(f) = percent_float(value, self.heap, self.interns) else {
+                    return Err(ExcType::type_error(format!(
+                        "must be real number, not {}",
+                        self.type_name(value)
+                    )));
+                };
+                match conversion {
+                    b'e' => format_float_e(f, format_spec, false),
+                    b'E' => format_float_e(f, format_spec, true),
+                    b'f' => format_float_f(f, format_spec),
+                    b'F' => format_float_f(f, format_spec).to_uppercase(),
+                    b'g' => format_float_g(f, format_spec),
+                    _ => format_float_g(f, format_spec).to_uppercase(),
+                }
+            }
+            b'c' if is_bytes => {
+                let byte = match value {
+                    Value::Int(n) => match u8::try_from(*n) {
+                        Ok(byte) => Some(byte),
+                        Err(_) => {
+                            return Err(
+                                SimpleException::new_msg(ExcType::OverflowError, "%c arg not in range(256)").into(),
+                            );
+                        }
+                    },
+                    _ => match self.bytes_of(value) {
+                        Some(&[byte]) => Some(byte),
+                        _ => None,
+                    },
+                };
+                let Some(byte) = byte else {
+                    return Err(ExcType::type_error(
+                        "%c requires an integer in range(256) or a single byte",
+                    ));
+                };
+                return Ok(pad_bytes(&[byte], format_spec));
+            }
+            b'c' => match value {
+                Value::Int(n) => format_char(*n, format_spec)?,
+                _ => {
+                    let s = value
+                        .as_either_str(self.heap)
+                        .map(|s| s.as_str(self.interns).to_owned())
+                        .filter(|s| s.chars().count() == 1);
+                    let Some(s) = s else {
+                        return Err(ExcType::type_error("%c requires int or char"));
+                    };
+                    format_string(&s, format_spec)?
+                }
+            },
+            _ => return Err(unsupported_percent_char(template, spec.end - 1, is_bytes)),
+        };
+        Ok(text.into_bytes())
+    }
+
+    /// Formats a `%s` or `%b` argument of a `bytes` template, which must be `bytes`.
+    fn percent_format_bytes(&self, value: &Value, format_spec: &ParsedFormatSpec) -> RunResult<Vec<u8>> {
+        let Some(bytes) = self.bytes_of(value) else {
+            return Err(ExcType::type_error(format!(
+                "%b requires a bytes-like object, or an object that implements __bytes__, not '{}'",
+                self.type_name(value)
+            )));
+        };
+        let bytes = match format_spec.precision {
+            Some(precision) if precision < bytes.len() => &bytes[..precision],
+            _ => bytes,
+        };
+        Ok(pad_bytes(bytes, format_spec))
+    }
+
+    /// Returns the contents of a `bytes` value.
+    fn bytes_of<'v>(&'v self, value: &Value) -> Option<&'v [u8]> {
+        match value {
+            Value::InternBytes(id) => Some(self.interns.get_bytes(*id)),
+            Value::Ref(id) => match self.heap.get(*id) {
+                HeapData::Bytes(b) => Some(b.as_slice()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Picks the positional argument index of a `{}` or `{0}` replacement field.
+fn next_field_index(arg: FormatFieldArg<'_>, numbering: &mut FieldNumbering) -> RunResult<usize> {
+    let (index, next) = match (arg, *numbering) {
+        (FormatFieldArg::Index(_), FieldNumbering::Auto(_)) => {
+            return Err(SimpleException::new_msg(
+                ExcType::ValueError,
+                "cannot switch from automatic field numbering to manual field specification",
+            )
+            .into());
+        }
+        (FormatFieldArg::Index(index), _) => (index, FieldNumbering::Manual),
+        (_, FieldNumbering::Manual) => {
+            return Err(SimpleException::new_msg(
+                ExcType::ValueError,
+                "cannot switch from manual field specification to automatic field numbering",
+            )
+            .into());
+        }
+        (_, FieldNumbering::Auto(index)) => (index, FieldNumbering::Auto(index + 1)),
+        (_, FieldNumbering::Unknown) => (0, FieldNumbering::Auto(1)),
+    };
+    *numbering = next;
+    Ok(index)
+}
+
+/// Takes a `*` width or precision from the arguments of printf-style formatting.
+fn percent_star_arg(args: &[Value], next_arg: &mut usize) -> RunResult<i64> {
+    let Some(arg) = args.get(*next_arg) else {
+        return Err(ExcType::type_error("not enough arguments for format string"));
+    };
+    *next_arg += 1;
+    match arg {
+        Value::Int(n) => Ok(*n),
+        Value::Bool(b) => Ok(i64::from(*b)),
+        _ => Err(ExcType::type_error("* wants int")),
+    }
+}
+
+/// Returns the sign and digits in `radix` of an integer argument of printf-style formatting.
+///
+/// Floats, decimals and fractions are truncated if `allow_float` (for `%d`). Returns `None`
+/// for other types.
+fn percent_int_digits(
+    value: &Value,
+    radix: u32,
+    allow_float: bool,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Option<(bool, String)>> {
+    let n = match value {
+        Value::Int(n) => {
+            let abs = n.unsigned_abs();
+            let digits = match radix {
+                8 => format!("{abs:o}"),
+                16 => format!("{abs:x}"),
+                _ => abs.to_string(),
+            };
+            return Ok(Some((*n < 0, digits)));
+        }
+        Value::Bool(b) => return Ok(Some((false, u8::from(*b).to_string()))),
+        Value::Float(f) if allow_float => {
+            if f.is_nan() {
+                return Err(
+                    SimpleException::new_msg(ExcType::ValueError, "cannot convert float NaN to integer").into(),
+                );
+            }
+            let Some(n) = BigInt::from_f64(f.trunc()) else {
+                return Err(SimpleException::new_msg(
+                    ExcType::OverflowError,
+                    "cannot convert float infinity to integer",
+                )
+                .into());
+            };
+            n
+        }
+        Value::InternLongInt(id) => interns.get_long_int(*id).clone(),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => li.inner().clone(),
+            HeapData::Decimal(decimal) if allow_float => decimal.trunc(heap)?,
+            HeapData::Fraction(fraction) if allow_float => fraction.trunc(),
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some((n.is_negative(), n.magnitude().to_str_radix(radix))))
+}
+
+/// Converts a numeric argument of printf-style formatting to a float, for `%f` and friends.
+fn percent_float(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> Option<f64> {
+    match value {
+        Value::Float(f) => Some(*f),
+        Value::Int(n) => Some(*n as f64),
+        Value::Bool(b) => Some(f64::from(u8::from(*b))),
+        Value::InternLongInt(id) => interns.get_long_int(*id).to_f64(),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => li.inner().to_f64(),
+            HeapData::Decimal(decimal) => Some(decimal.to_f64()),
+            HeapData::Fraction(fraction) => fraction.to_f64(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Pads bytes with spaces to the width of a printf-style conversion specifier.
+fn pad_bytes(bytes: &[u8], format_spec: &ParsedFormatSpec) -> Vec<u8> {
+    let padding = format_spec.width.saturating_sub(bytes.len());
+    let mut out = Vec::with_capacity(bytes.len() + padding);
+    if format_spec.align == Some('<') {
+        out.extend_from_slice(bytes);
+        out.resize(bytes.len() + padding, b' ');
+    } else {
+        out.resize(padding, b' ');
+        out.extend_from_slice(bytes);
+    }
+    out
+}
+
+/// Creates the error for an unknown conversion character at `pos` in a printf-style template.
+fn unsupported_percent_char(template: &[u8], pos: usize, is_bytes: bool) -> RunError {
+    let (c, index) = if is_bytes {
+        (char::from(template[pos]), pos)
+    } else {
+        let before = std::str::from_utf8(&template[..pos]).expect("str templates are UTF-8");
+        let rest = std::str::from_utf8(&template[pos..]).expect("str templates are UTF-8");
+        (
+            rest.chars().next().expect("conversion character exists"),
+            before.chars().count(),
+        )
+    };
+    SimpleException::new_msg(
+        ExcType::ValueError,
+        format!(
+            "unsupported format character '{c}' ({:#x}) at index {index}",
+            u32::from(c)
+        ),
+    )
+    .into()
 }
@@ -0,0 +1,193 @@
+//! `functools.reduce()` and calls of the objects created by the `functools` module, which
+//! call Python functions.
+
+use smallvec::SmallVec;
+
+use super::{VM, call::CallResult};
+use crate::{
+    args::{ArgValues, KwargsValues, bind_args},
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult},
+    heap::{DropWithHeap, HeapData, HeapId},
+    intern::StaticStrings,
+    io::PrintWriter,
+    resource::ResourceTracker,
+    types::{PyTrait, allocate_tuple},
+    value::{Marker, Value},
+};
+
+impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
+    /// Implements `functools.reduce(function, iterable[, initial])`.
+    pub(super) fn call_reduce(&mut self, args: ArgValues) -> RunResult<Value> {
+        let [function, iterable, initial] = bind_args(
+            "reduce",
+            args,
+            ["function", "iterable", "initial"],
+            2,
+            self.heap,
+            self.interns,
+        )?;
+        let function = function.expect("required argument is bound");
+        let iterator = match self.get_iter(iterable.expect("required argument is bound")) {
+            Ok(iterator) => iterator,
+            Err(e) => {
+                function.drop_with_heap(self.heap);
+                initial.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+        let Value::Ref(iter_id) = iterator else {
+            unreachable!("iterators are on the heap")
+        };
+        let this = self;
+        defer_drop!(function, this);
+        defer_drop!(iterator, this);
+
+        let mut total = initial;
+        loop {
+            let item = match this.iter_next(iter_id) {
+                Ok(Some(item)) => item,
+                Ok(None) => break,
+                Err(e) => {
+                    total.drop_with_heap(this.heap);
+                    return Err(e);
+                }
+            };
+            total = Some(match total {
+                None => item,
+                Some(total) => {
+                    let function = function.clone_with_heap(this.heap);
+                    this.call_sync(function, ArgValues::Two(total, item))?
+                }
+            });
+        }
+        total.ok_or_else(|| ExcType::type_error("reduce() of empty iterable with no initial value"))
+    }
+
+    /// Calls a function wrapped by `functools.lru_cache()`, unless its cache holds the result
+    /// of a call with equal arguments.
+    ///
+    /// The wrapped function is run to completion with `call_sync()`, so it can't call
+    /// external functions.
+    pub(super) fn call_cached(
+        &mut self,
+        wrapper_id: HeapId,
+        wrapper: Value,
+        args: ArgValues,
+    ) -> Result<CallResult, RunError> {
+        let this = self;
+        defer_drop!(wrapper, this);
+
+        let key = match this.cache_key(&args) {
+            Ok(key) => key,
+            Err(e) => {
+                args.drop_with_heap(this.heap);
+                return Err(e);
+            }
+        };
+        let interns = this.interns;
+        let found = this.heap.with_entry_mut(wrapper_id, |heap, data| {
+            let HeapData::LruCacheWrapper(wrapper) = data else {
+                unreachable!("entry was checked to be a cache wrapper")
+            };
+            wrapper.lookup(&key, heap, interns)
+        });
+        match found {
+            Ok(Some(result)) => {
+                key.drop_with_heap(this.heap);
+                args.drop_with_heap(this.heap);
+                return Ok(CallResult::Push(result));
+            }
+            Ok(None) => {}
+            Err(e) => {
+                key.drop_with_heap(this.heap);
+                args.drop_with_heap(this.heap);
+                return Err(e);
+            }
+        }
+
+        let HeapData::LruCacheWrapper(cached) = this.heap.get(wrapper_id) else {
+            unreachable!("entry was checked to be a cache wrapper")
+        };
+        let func = cached.func().copy_for_extend();
+        if let Value::Ref(id) = func {
+            this.heap.inc_ref(id);
+        }
+        let result = match this.call_sync(func, args) {
+            Ok(result) => result,
+            Err(e) => {
+                key.drop_with_heap(this.heap);
+                return Err(e);
+            }
+        };
+        let stored = result.clone_with_heap(this.heap);
+        let store_result = this.heap.with_entry_mut(wrapper_id, |heap, data| {
+            let HeapData::LruCacheWrapper(wrapper) = data else {
+                unreachable!("entry was checked to be a cache wrapper")
+            };
+            wrapper.store(key, stored, heap, interns)
+        });
+        match store_result {
+            Ok(()) => Ok(CallResult::Push(result)),
+            Err(e) => {
+                result.drop_with_heap(this.heap);
+                Err(e)
+            }
+        }
+    }
+
+    /// Builds the cache key of a call: a tuple of the positional arguments, followed by a
+    /// marker and each keyword argument's name and value if there are any.
+    fn cache_key(&mut self, args: &ArgValues) -> RunResult<Value> {
+        let mut items: SmallVec<_> = SmallVec::new();
+        let kwargs = match args {
+            ArgValues::Empty => None,
+            ArgValues::One(a) => {
+                items.push(a.clone_with_heap(self.heap));
+                None
+            }
+            ArgValues::Two(a, b) => {
+                items.push(a.clone_with_heap(self.heap));
+                items.push(b.clone_with_heap(self.heap));
+                None
+            }
+            ArgValues::Kwargs(kwargs) => Some(kwargs),
+            ArgValues::ArgsKargs { args, kwargs } => {
+                items.extend(args.iter().map(|arg| arg.clone_with_heap(self.heap)));
+                Some(kwargs)
+            }
+        };
+        if let Some(kwargs) = kwargs
+            && !kwargs.is_empty()
+        {
+            items.push(Value::Marker(Marker(StaticStrings::LruCache)));
+            match kwargs {
+                KwargsValues::Empty => {}
+                KwargsValues::Inline(pairs) => {
+                    for (name, value) in pairs {
+                        items.push(Value::InternString(*name));
+                        items.push(value.clone_with_heap(self.heap));
+                    }
+                }
+                KwargsValues::Dict(dict) => {
+                    for (name, value) in dict {
+                        items.push(name.clone_with_heap(self.heap));
+                        items.push(value.clone_with_heap(self.heap));
+                    }
+                }
+            }
+        }
+        // Report the unhashable argument, rather than the key tuple holding it
+        let unhashable = items
+            .iter()
+            .find(|item| item.py_hash(self.heap, self.interns).is_none())
+            .map(|item| item.py_type(self.heap));
+        if let Some(unhashable) = unhashable {
+            for item in items {
+                item.drop_with_heap(self.heap);
+            }
+            return Err(ExcType::type_error_unhashable(unhashable));
+        }
+        Ok(allocate_tuple(items, self.heap)?)
+    }
+}
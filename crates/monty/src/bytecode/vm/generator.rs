@@ -0,0 +1,443 @@
+//! Generator support for the VM.
+//!
+//! Calling a generator function binds its arguments into a [`Generator`] heap object
+//! without running the body. Resuming the generator moves its saved namespace, operand
+//! stack segment and exception stack segment back into a regular frame, tagged with a
+//! [`GeneratorFrame`] which records where control goes when the frame yields or returns:
+//! back into a `for` loop, into a delegating `yield from`, or out of a nested run loop
+//! for Rust callers such as `next()` and `list()`. `YieldValue` moves the state back
+//! into the heap object, so suspended generators are plain heap data.
+
+use super::{CallFrame, VM, call::CallResult};
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{FunctionId, StaticStrings, StringId},
+    io::PrintWriter,
+    resource::ResourceTracker,
+    types::{Generator, GeneratorState, PyTrait, Type},
+    value::Value,
+};
+
+/// How a generator frame was resumed, which decides where its yielded and returned values go.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub(crate) enum GeneratorResume {
+    /// Resumed from Rust (`next()`, generator methods, builtins consuming the generator)
+    /// in a nested run loop. Yielding or returning exits the nested loop with the value.
+    Sync,
+    /// Resumed by `ForIter`. Yielded values are pushed for the loop body; returning pops
+    /// the generator from the caller's stack and jumps to `end_ip`.
+    ForIter { end_ip: usize },
+    /// Resumed by `SendYieldFrom` in a delegating generator. Yielded values are pushed for
+    /// the delegating generator to yield; returning replaces the generator on the caller's
+    /// stack with the return value and jumps to `end_ip`.
+    YieldFrom { end_ip: usize },
+}
+
+/// Generator bookkeeping for a frame running a generator's body.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub(crate) struct GeneratorFrame {
+    /// The generator whose body the frame runs. The frame owns a reference to it.
+    pub generator_id: HeapId,
+    /// How the generator was resumed.
+    pub resume: GeneratorResume,
+    /// Length of the exception stack when the generator was resumed.
+    ///
+    /// Entries above it are exceptions being handled by the generator's body.
+    pub exception_base: usize,
+}
+
+/// Outcome of running a generator from Rust until it yields or returns.
+pub(super) enum GeneratorStep {
+    /// The generator yielded this value and is suspended.
+    Yielded(Value),
+    /// The generator returned this value and is completed.
+    Returned(Value),
+}
+
+impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
+    /// Creates a generator for a call to a generator function.
+    ///
+    /// Arguments are bound immediately, like for coroutines, but the body only starts
+    /// running when the generator is first advanced.
+    pub(super) fn create_generator(
+        &mut self,
+        func_id: FunctionId,
+        cells: &[HeapId],
+        defaults: Vec<Value>,
+        args: ArgValues,
+    ) -> Result<CallResult, RunError> {
+        let (namespace, frame_cells) = self.bind_detached_namespace(func_id, cells, defaults, args)?;
+
+        let generator = Generator::new(func_id, namespace, frame_cells);
+        let generator_id = self.heap.allocate(HeapData::Generator(generator))?;
+
+        Ok(CallResult::Push(Value::Ref(generator_id)))
+    }
+
+    /// Returns whether `value` is a generator object.
+    pub(super) fn is_generator(&self, value: &Value) -> bool {
+        matches!(value, Value::Ref(id) if matches!(self.heap.get(*id), HeapData::Generator(_)))
+    }
+
+    /// Pushes a frame which resumes the generator `generator_id`.
+    ///
+    /// `sent` becomes the result of the `yield` expression the generator is suspended at.
+    /// Returns `Ok(false)` without pushing a frame if the generator has already completed.
+    pub(super) fn resume_generator(
+        &mut self,
+        generator_id: HeapId,
+        sent: Value,
+        resume: GeneratorResume,
+    ) -> RunResult<bool> {
+        let HeapData::Generator(generator) = self.heap.get(generator_id) else {
+            unreachable!("resume_generator called with a non-generator")
+        };
+        let state = generator.state;
+        match state {
+            GeneratorState::Running => {
+                sent.drop_with_heap(self.heap);
+                return Err(generator_running_error());
+            }
+            GeneratorState::Completed => {
+                sent.drop_with_heap(self.heap);
+                return Ok(false);
+            }
+            GeneratorState::Created if !matches!(sent, Value::None) => {
+                sent.drop_with_heap(self.heap);
+                return Err(ExcType::type_error(
+                    "can't send non-None value to a just-started generator",
+                ));
+            }
+            GeneratorState::Created | GeneratorState::Suspended => {}
+        }
+
+        // Get call position BEFORE borrowing the generator mutably
+        let call_position = self.current_position();
+
+        let HeapData::Generator(generator) = self.heap.get_mut(generator_id) else {
+            unreachable!("resume_generator called with a non-generator")
+        };
+        let func_id = generator.func_id;
+        let ip = generator.ip;
+        let namespace = std::mem::take(&mut generator.namespace);
+        let saved_stack = std::mem::take(&mut generator.stack);
+        let saved_exc_stack = std::mem::take(&mut generator.exc_stack);
+        let frame_cells = generator.frame_cells.clone();
+        generator.state = GeneratorState::Running;
+
+        let namespace_idx = match self.namespaces.register_prebuilt(namespace, self.heap) {
+            Ok(idx) => idx,
+            Err(e) => {
+                if let HeapData::Generator(generator) = self.heap.get_mut(generator_id) {
+                    generator.state = GeneratorState::Completed;
+                }
+                saved_stack.drop_with_heap(self.heap);
+                saved_exc_stack.drop_with_heap(self.heap);
+                sent.drop_with_heap(self.heap);
+                return Err(e.into());
+            }
+        };
+
+        // The frame keeps the generator alive while it runs
+        self.heap.inc_ref(generator_id);
+
+        let stack_base = self.stack.len();
+        self.stack.extend(saved_stack);
+        if state == GeneratorState::Suspended {
+            // The result of the `yield` expression the generator is suspended at
+            self.push(sent);
+        } else {
+            sent.drop_with_heap(self.heap);
+        }
+        let exception_base = self.exception_stack.len();
+        self.exception_stack.extend(saved_exc_stack);
+
+        let func = self.interns.get_function(func_id);
+        let mut frame = CallFrame::new_function(
+            &func.code,
+            stack_base,
+            exception_base,
+            namespace_idx,
+            func_id,
+            frame_cells,
+            Some(call_position),
+        );
+        frame.ip = ip;
+        frame.generator = Some(GeneratorFrame {
+            generator_id,
+            resume,
+            exception_base,
+        });
+        self.frames.push(frame);
+
+        Ok(true)
+    }
+
+    /// Pops the current generator frame after a `yield`, moving its state back into the generator.
+    ///
+    /// The frame's IP must already point past the `YieldValue` instruction. Returns how
+    /// the generator was resumed, which decides where the yielded value goes.
+    pub(super) fn suspend_generator_frame(&mut self) -> GeneratorResume {
+        let frame = self.frames.pop().expect("no frame to suspend");
+        let generator = frame.generator.expect("YieldValue outside a generator frame");
+
+        let stack = self.stack.split_off(frame.stack_base);
+        let exc_base = generator.exception_base.min(self.exception_stack.len());
+        let exc_stack = self.exception_stack.split_off(exc_base);
+        let namespace = self.namespaces.take(frame.namespace_idx, self.heap);
+
+        let HeapData::Generator(gen_data) = self.heap.get_mut(generator.generator_id) else {
+            unreachable!("generator frame with a non-generator")
+        };
+        gen_data.namespace = namespace;
+        gen_data.stack = stack;
+        gen_data.exc_stack = exc_stack;
+        gen_data.ip = frame.ip;
+        gen_data.state = GeneratorState::Suspended;
+
+        self.heap.dec_ref(generator.generator_id);
+        generator.resume
+    }
+
+    /// Releases a generator frame that finished by returning or raising.
+    ///
+    /// Drops the exceptions the generator was still handling, marks the generator as
+    /// completed and releases the frame's reference to it.
+    pub(super) fn release_generator_frame(&mut self, generator: GeneratorFrame) {
+        while self.exception_stack.len() > generator.exception_base {
+            let exc = self.exception_stack.pop().unwrap();
+            exc.drop_with_heap(self.heap);
+        }
+        complete_generator(self.heap, generator.generator_id);
+    }
+
+    /// Routes the return value of a generator frame which has just been popped.
+    ///
+    /// Returns the value if the generator was resumed by a nested run loop, which must
+    /// exit with it. Otherwise the value is handled in the caller's frame.
+    pub(super) fn finish_generator_return(&mut self, resume: GeneratorResume, value: Value) -> Option<Value> {
+        match resume {
+            GeneratorResume::Sync => Some(value),
+            GeneratorResume::ForIter { end_ip } => {
+                // The loop is over - the return value is discarded
+                value.drop_with_heap(self.heap);
+                let generator = self.pop();
+                generator.drop_with_heap(self.heap);
+                self.current_frame_mut().ip = end_ip;
+                None
+            }
+            GeneratorResume::YieldFrom { end_ip } => {
+                // The return value is the result of the `yield from` expression
+                let generator = self.pop();
+                generator.drop_with_heap(self.heap);
+                self.push(value);
+                self.current_frame_mut().ip = end_ip;
+                None
+            }
+        }
+    }
+
+    /// Executes `SendYieldFrom`: sends the value on top of the stack into the iterator below it.
+    ///
+    /// A generator is resumed in a new frame. Other iterators are advanced directly and
+    /// only accept `None`. When the iterator is exhausted, it is replaced by the result of
+    /// the `yield from` expression and the current frame jumps to `end_ip`.
+    pub(super) fn send_yield_from(&mut self, end_ip: usize) -> RunResult<()> {
+        let sent = self.pop();
+        let Value::Ref(iter_id) = *self.peek() else {
+            sent.drop_with_heap(self.heap);
+            return Err(RunError::internal("SendYieldFrom: expected iterator ref on stack"));
+        };
+
+        let next = if matches!(self.heap.get(iter_id), HeapData::Generator(_)) {
+            if self.resume_generator(iter_id, sent, GeneratorResume::YieldFrom { end_ip })? {
+                return Ok(());
+            }
+            None
+        } else {
+            if !matches!(sent, Value::None) {
+                let iter_type = self.peek().py_type(self.heap);
+                sent.drop_with_heap(self.heap);
+                return Err(ExcType::attribute_error(iter_type, "send"));
+            }
+            self.iter_next(iter_id)?
+        };
+
+        if let Some(value) = next {
+            self.push(value);
+        } else {
+            // Exhausted - `yield from` over a non-generator evaluates to None
+            let iter = self.pop();
+            iter.drop_with_heap(self.heap);
+            self.push(Value::None);
+            self.current_frame_mut().ip = end_ip;
+        }
+        Ok(())
+    }
+
+    /// Resumes a generator from Rust, sending `sent`, and runs it until it yields or returns.
+    pub(super) fn generator_send(&mut self, generator_id: HeapId, sent: Value) -> RunResult<GeneratorStep> {
+        self.drive_generator(generator_id, sent, None)
+    }
+
+    /// Raises `exc` inside a generator at the `yield` it is suspended at.
+    ///
+    /// Generators which haven't started or have completed are closed and the exception
+    /// is raised directly.
+    pub(super) fn generator_throw(&mut self, generator_id: HeapId, exc: Value) -> RunResult<GeneratorStep> {
+        let is_exception = match &exc {
+            Value::Ref(id) => matches!(self.heap.get(*id), HeapData::Exception(_)),
+            Value::Builtin(Builtins::ExcType(_)) => true,
+            _ => false,
+        };
+        if !is_exception {
+            let type_name = self.type_name(&exc);
+            exc.drop_with_heap(self.heap);
+            return Err(ExcType::type_error(format!(
+                "exceptions must be classes or instances deriving from BaseException, not {type_name}"
+            )));
+        }
+
+        match self.generator_state(generator_id) {
+            GeneratorState::Suspended => self.drive_generator(generator_id, Value::None, Some(exc)),
+            GeneratorState::Running => {
+                exc.drop_with_heap(self.heap);
+                Err(generator_running_error())
+            }
+            GeneratorState::Created | GeneratorState::Completed => {
+                self.discard_generator_state(generator_id);
+                Err(self.make_exception(exc, true))
+            }
+        }
+    }
+
+    /// Closes a generator by raising `GeneratorExit` at the `yield` it is suspended at.
+    ///
+    /// Returns the generator's return value if it returns instead of yielding again.
+    pub(super) fn generator_close(&mut self, generator_id: HeapId) -> RunResult<Value> {
+        match self.generator_state(generator_id) {
+            GeneratorState::Suspended => {
+                let exc = Value::Builtin(Builtins::ExcType(ExcType::GeneratorExit));
+                match self.drive_generator(generator_id, Value::None, Some(exc)) {
+                    Ok(GeneratorStep::Yielded(value)) => {
+                        value.drop_with_heap(self.heap);
+                        Err(SimpleException::new_msg(ExcType::RuntimeError, "generator ignored GeneratorExit").into())
+                    }
+                    Ok(GeneratorStep::Returned(value)) => Ok(value),
+                    Err(RunError::Exc(exc)) if exc.exc.exc_type() == ExcType::GeneratorExit => Ok(Value::None),
+                    Err(e) => Err(e),
+                }
+            }
+            GeneratorState::Running => Err(generator_running_error()),
+            GeneratorState::Created | GeneratorState::Completed => {
+                self.discard_generator_state(generator_id);
+                Ok(Value::None)
+            }
+        }
+    }
+
+    /// Calls a method on a generator object (`send`, `throw` or `close`).
+    pub(super) fn call_generator_method(
+        &mut self,
+        generator_id: HeapId,
+        name_id: StringId,
+        args: ArgValues,
+    ) -> RunResult<Value> {
+        match StaticStrings::from_string_id(name_id) {
+            Some(StaticStrings::Send) => {
+                let sent = args.get_one_arg("generator.send", self.heap)?;
+                let step = self.generator_send(generator_id, sent)?;
+                self.step_value(step)
+            }
+            Some(StaticStrings::Throw) => {
+                let exc = args.get_one_arg("generator.throw", self.heap)?;
+                let step = self.generator_throw(generator_id, exc)?;
+                self.step_value(step)
+            }
+            Some(StaticStrings::Close) => {
+                args.check_zero_args("generator.close", self.heap)?;
+                self.generator_close(generator_id)
+            }
+            _ => {
+                args.drop_with_heap(self.heap);
+                Err(ExcType::attribute_error(Type::Generator, self.interns.get_str(name_id)))
+            }
+        }
+    }
+
+    /// Resumes a generator from Rust and runs it in a nested run loop until it yields or returns.
+    ///
+    /// If `throw` is given, it is raised in the generator's frame before running it.
+    fn drive_generator(&mut self, generator_id: HeapId, sent: Value, throw: Option<Value>) -> RunResult<GeneratorStep> {
+        let stack_len = self.stack.len();
+        let base = self.frames.len();
+        if !self.resume_generator(generator_id, sent, GeneratorResume::Sync)? {
+            throw.drop_with_heap(self.heap);
+            return Ok(GeneratorStep::Returned(Value::None));
+        }
+        let Some(value) = self.run_nested(base, stack_len, throw)? else {
+            return Err(SimpleException::new_msg(
+                ExcType::RuntimeError,
+                "external functions and OS calls are not supported inside generators consumed by builtins or generator methods",
+            )
+            .into());
+        };
+        if self.generator_state(generator_id) == GeneratorState::Completed {
+            Ok(GeneratorStep::Returned(value))
+        } else {
+            Ok(GeneratorStep::Yielded(value))
+        }
+    }
+
+    /// Converts the outcome of advancing a generator into the value of `next()` or `send()`.
+    ///
+    /// A returned generator raises `StopIteration`, carrying the return value.
+    pub(super) fn step_value(&mut self, step: GeneratorStep) -> RunResult<Value> {
+        match step {
+            GeneratorStep::Yielded(value) => Ok(value),
+            GeneratorStep::Returned(Value::None) => Err(ExcType::stop_iteration()),
+            GeneratorStep::Returned(value) => {
+                let msg = value.py_str(self.heap, self.interns).into_owned();
+                value.drop_with_heap(self.heap);
+                Err(SimpleException::new_msg(ExcType::StopIteration, msg).into())
+            }
+        }
+    }
+
+    fn generator_state(&self, generator_id: HeapId) -> GeneratorState {
+        let HeapData::Generator(generator) = self.heap.get(generator_id) else {
+            unreachable!("generator_state called with a non-generator")
+        };
+        generator.state
+    }
+
+    /// Closes a generator which isn't running, dropping its saved state.
+    fn discard_generator_state(&mut self, generator_id: HeapId) {
+        let HeapData::Generator(generator) = self.heap.get_mut(generator_id) else {
+            unreachable!("discard_generator_state called with a non-generator")
+        };
+        let namespace = std::mem::take(&mut generator.namespace);
+        let stack = std::mem::take(&mut generator.stack);
+        let exc_stack = std::mem::take(&mut generator.exc_stack);
+        generator.state = GeneratorState::Completed;
+        namespace.drop_with_heap(self.heap);
+        stack.drop_with_heap(self.heap);
+        exc_stack.drop_with_heap(self.heap);
+    }
+}
+
+/// Marks a generator whose frame is being discarded as completed and releases the frame's reference.
+pub(super) fn complete_generator(heap: &mut Heap<impl ResourceTracker>, generator_id: HeapId) {
+    if let HeapData::Generator(generator) = heap.get_mut(generator_id) {
+        generator.state = GeneratorState::Completed;
+    }
+    heap.dec_ref(generator_id);
+}
+
+/// Error raised when a generator is resumed while its frame is already running.
+fn generator_running_error() -> RunError {
+    SimpleException::new_msg(ExcType::ValueError, "generator already executing").into()
+}
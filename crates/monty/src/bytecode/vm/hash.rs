@@ -0,0 +1,290 @@
+//! Dict keys and set elements which are instances of user-defined classes.
+//!
+//! Dict and set storage hash and compare keys without the VM, so they can't call a
+//! user-defined `__hash__` or `__eq__`. Before a dict or set operation, the VM calls
+//! `__hash__` and caches the result on the heap, then compares the stored keys with the
+//! same hash using `__eq__`. The operation uses the stored key which is equal, if any,
+//! which storage then finds by identity.
+
+use ahash::AHashSet;
+
+use super::VM;
+use crate::{
+    args::ArgValues,
+    exception_private::RunResult,
+    heap::{DropWithHeap, HeapData, HeapId},
+    io::PrintWriter,
+    resource::ResourceTracker,
+    types::{Set, class::has_custom_hash},
+    value::Value,
+};
+
+impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
+    /// Returns the key stored in the dict, set or frozenset `container` which equals `key`,
+    /// or `key` itself if there is none or `container` is another value.
+    ///
+    /// Only keys holding instances with a user-defined `__hash__` are looked up here, since
+    /// storage hashes and compares other keys the same way Python does. Like CPython, stored
+    /// keys are compared with `__eq__` only if their hash matches. Takes ownership of `key`.
+    pub(super) fn resolve_key(&mut self, container: &Value, key: Value) -> RunResult<Value> {
+        match container {
+            Value::Ref(id) => self.resolve_key_in(*id, key),
+            _ => Ok(key),
+        }
+    }
+
+    /// Like [`Self::resolve_key`], for the container at `container_id`.
+    fn resolve_key_in(&mut self, container_id: HeapId, key: Value) -> RunResult<Value> {
+        if !matches!(
+            self.heap.get(container_id),
+            HeapData::Dict(_) | HeapData::Set(_) | HeapData::FrozenSet(_)
+        ) || !self.contains_instance(&key)
+        {
+            return Ok(key);
+        }
+        match self.prepare_hash(&key) {
+            Ok(true) => {}
+            Ok(false) => return Ok(key),
+            Err(e) => {
+                key.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        }
+        // Unhashable keys are left for storage to reject
+        let Some(hash) = key.py_hash(self.heap, self.interns) else {
+            return Ok(key);
+        };
+
+        let mut candidates: Vec<Value> = match self.heap.get(container_id) {
+            HeapData::Dict(dict) => dict.keys_with_hash(hash).map(Value::copy_for_extend).collect(),
+            HeapData::Set(set) => set
+                .storage()
+                .values_with_hash(hash)
+                .map(Value::copy_for_extend)
+                .collect(),
+            HeapData::FrozenSet(set) => set
+                .storage()
+                .values_with_hash(hash)
+                .map(Value::copy_for_extend)
+                .collect(),
+            _ => unreachable!("checked to be a dict or set"),
+        };
+        for candidate in &candidates {
+            if let Value::Ref(id) = candidate {
+                self.heap.inc_ref(*id);
+            }
+        }
+
+        let mut result = Ok(None);
+        for (index, candidate) in candidates.iter().enumerate() {
+            if candidate.is(&key) {
+                break;
+            }
+            match self.eq_values(candidate, &key) {
+                Ok(false) => {}
+                Ok(true) => {
+                    result = Ok(Some(index));
+                    break;
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        match result {
+            Ok(Some(index)) => {
+                let stored = candidates.swap_remove(index);
+                candidates.drop_with_heap(self.heap);
+                key.drop_with_heap(self.heap);
+                Ok(stored)
+            }
+            Ok(None) => {
+                candidates.drop_with_heap(self.heap);
+                Ok(key)
+            }
+            Err(e) => {
+                candidates.drop_with_heap(self.heap);
+                key.drop_with_heap(self.heap);
+                Err(e)
+            }
+        }
+    }
+
+    /// Calls `__hash__` on the instances in `value` whose class defines it, including those
+    /// in tuples, lists and dicts, and caches the results so storage can hash them.
+    ///
+    /// Returns whether `value` holds any such instance.
+    pub(super) fn prepare_hash(&mut self, value: &Value) -> RunResult<bool> {
+        let mut visited = AHashSet::new();
+        self.prepare_hash_inner(value, &mut visited)
+    }
+
+    fn prepare_hash_inner(&mut self, value: &Value, visited: &mut AHashSet<HeapId>) -> RunResult<bool> {
+        let Value::Ref(id) = value else {
+            return Ok(false);
+        };
+        let items: Vec<Value> = match self.heap.get(*id) {
+            // Like CPython, an instance found twice is hashed twice
+            HeapData::Instance(inst) => {
+                if !has_custom_hash(inst.class_id(), self.heap, self.interns) {
+                    return Ok(false);
+                }
+                let Value::Int(hash) = self.hash_instance(value)? else {
+                    unreachable!("hash_instance returns an int")
+                };
+                self.heap.set_hash(*id, u64::from_ne_bytes(hash.to_ne_bytes()));
+                return Ok(true);
+            }
+            _ if !visited.insert(*id) => return Ok(false),
+            HeapData::Tuple(tuple) => tuple.as_vec().iter().map(Value::copy_for_extend).collect(),
+            HeapData::List(list) => list.as_vec().iter().map(Value::copy_for_extend).collect(),
+            HeapData::Dict(dict) => dict
+                .iter()
+                .flat_map(|(k, v)| [k.copy_for_extend(), v.copy_for_extend()])
+                .collect(),
+            _ => return Ok(false),
+        };
+        for item in &items {
+            if let Value::Ref(item_id) = item {
+                self.heap.inc_ref(*item_id);
+            }
+        }
+
+        let mut found = false;
+        let mut result = Ok(());
+        for item in &items {
+            match self.prepare_hash_inner(item, visited) {
+                Ok(item_found) => found |= item_found,
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        items.drop_with_heap(self.heap);
+        result?;
+        // Tuples cache their hash, which depends on the new hashes of their instances
+        if found && matches!(self.heap.get(*id), HeapData::Tuple(_)) {
+            self.heap.reset_hash(*id);
+        }
+        Ok(found)
+    }
+
+    /// Resolves the first argument of a dict or set method like [`Self::resolve_key`].
+    ///
+    /// The other positional arguments of set methods are sets or iterables of elements, so
+    /// `__hash__` is called on their instances too.
+    pub(super) fn resolve_key_args(&mut self, container_id: HeapId, args: ArgValues) -> RunResult<ArgValues> {
+        if !self.has_instance_arg(&args) {
+            return Ok(args);
+        }
+        let is_set = !matches!(self.heap.get(container_id), HeapData::Dict(_));
+        let (positional, kwargs) = args.into_parts();
+        let mut values: Vec<Value> = positional.collect();
+        let mut result = Ok(());
+        if !values.is_empty() {
+            let key = values.remove(0);
+            match self.resolve_key_in(container_id, key) {
+                Ok(key) => values.insert(0, key),
+                Err(e) => result = Err(e),
+            }
+        }
+        if is_set && result.is_ok() {
+            for value in values.iter().skip(1) {
+                if let Err(e) = self.prepare_hash(value) {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        if let Err(e) = result {
+            values.drop_with_heap(self.heap);
+            kwargs.drop_with_heap(self.heap);
+            return Err(e);
+        }
+        if kwargs.is_empty() {
+            Ok(ArgValues::from_vec(values))
+        } else {
+            Ok(ArgValues::ArgsKargs { args: values, kwargs })
+        }
+    }
+
+    /// Sets `dict[key] = value` in the dict at `dict_id`, returning the value it replaced.
+    ///
+    /// Like CPython, a key equal to a stored key replaces its value and keeps the stored key.
+    pub(super) fn dict_set(&mut self, dict_id: HeapId, key: Value, value: Value) -> RunResult<Option<Value>> {
+        let key = match self.resolve_key_in(dict_id, key) {
+            Ok(key) => key,
+            Err(e) => {
+                value.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+        self.heap.with_entry_mut(dict_id, |heap, data| {
+            let HeapData::Dict(dict) = data else {
+                unreachable!("dict_set called with a non-dict")
+            };
+            dict.set(key, value, heap, self.interns)
+        })
+    }
+
+    /// Returns a new reference to `dict[key]` of the dict at `dict_id`, or `None` if the key
+    /// is missing.
+    pub(super) fn dict_get(&mut self, dict_id: HeapId, key: &Value) -> RunResult<Option<Value>> {
+        let key = self.resolve_key_in(dict_id, key.clone_with_heap(self.heap))?;
+        let result = self.heap.with_entry_mut(dict_id, |heap, data| {
+            let HeapData::Dict(dict) = data else {
+                unreachable!("dict_get called with a non-dict")
+            };
+            dict.get(&key, heap, self.interns)
+                .map(|value| value.map(|value| value.clone_with_heap(heap)))
+        });
+        key.drop_with_heap(self.heap);
+        result
+    }
+
+    /// Adds `value` to the set at `set_id`, returning whether it was added.
+    pub(super) fn set_insert(&mut self, set_id: HeapId, value: Value) -> RunResult<bool> {
+        let value = self.resolve_key_in(set_id, value)?;
+        self.heap.with_entry_mut(set_id, |heap, data| {
+            let HeapData::Set(set) = data else {
+                unreachable!("set_insert called with a non-set")
+            };
+            set.add(value, heap, self.interns)
+        })
+    }
+
+    /// Returns whether `container` contains `item`, comparing instances with `__eq__` when
+    /// `container` is a dict, set or frozenset.
+    pub(super) fn contains_key(&mut self, container: &Value, item: &Value) -> RunResult<bool> {
+        if !self.contains_instance(item) {
+            return container.py_contains(item, self.heap, self.interns);
+        }
+        let item = self.resolve_key(container, item.clone_with_heap(self.heap))?;
+        let result = container.py_contains(&item, self.heap, self.interns);
+        item.drop_with_heap(self.heap);
+        result
+    }
+
+    /// Builds a set from `items`, adding them one by one so their `__hash__` and `__eq__`
+    /// are called.
+    pub(super) fn build_set_from(&mut self, items: Vec<Value>) -> RunResult<Value> {
+        let set_id = match self.heap.allocate(HeapData::Set(Set::new())) {
+            Ok(set_id) => set_id,
+            Err(e) => {
+                items.drop_with_heap(self.heap);
+                return Err(e.into());
+            }
+        };
+        let mut items = items.into_iter();
+        while let Some(item) = items.next() {
+            if let Err(e) = self.set_insert(set_id, item) {
+                items.for_each(|item| item.drop_with_heap(self.heap));
+                Value::Ref(set_id).drop_with_heap(self.heap);
+                return Err(e);
+            }
+        }
+        Ok(Value::Ref(set_id))
+    }
+}
@@ -0,0 +1,630 @@
+//! Iterators which only the VM can advance.
+//!
+//! Generators run Python frames, and the iterators returned by `map()`, `filter()`,
+//! `iter(callable, sentinel)` and the `itertools` module call Python functions or advance
+//! other iterators for each value, so code which only has the heap can't advance them.
+//! `ForIter`, `next()`, `sum()`, `any()`, `all()` and `yield from` advance them lazily,
+//! as do the iterators `enumerate()` and `zip()` return over them; other builtins and
+//! opcodes which consume a whole iterable get a list of their values instead.
+
+use super::{VM, generator::GeneratorStep};
+use crate::{
+    args::ArgValues,
+    builtins::{Builtins, BuiltinsFunctions, enumerate_start, is_callable},
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{DropWithHeap, HeapData, HeapId},
+    intern::{StaticStrings, StringId},
+    io::PrintWriter,
+    modules::{
+        ModuleFunctions, collections::CollectionsFunctions, itertools::ItertoolsIter, math::MathFunctions,
+        random::RandomFunctions,
+    },
+    resource::ResourceTracker,
+    types::{
+        List, MontyIter, PyTrait, Type, allocate_tuple,
+        iter::{CallIter, advance_on_heap},
+    },
+    value::Value,
+};
+
+impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
+    /// Returns whether `value` is a generator or an iterator which calls Python functions.
+    pub(super) fn is_vm_iterator(&self, value: &Value) -> bool {
+        let Value::Ref(id) = value else {
+            return false;
+        };
+        match self.heap.get(*id) {
+            HeapData::Generator(_) => true,
+            HeapData::Iter(iter) => iter.call_state().is_some(),
+            _ => false,
+        }
+    }
+
+    /// Returns an iterator on the heap for an iterable, like `iter()`.
+    ///
+    /// Iterators and generators are their own iterators.
+    pub(super) fn get_iter(&mut self, value: Value) -> RunResult<Value> {
+        if let Value::Ref(id) = value
+            && matches!(self.heap.get(id), HeapData::Iter(_) | HeapData::Generator(_))
+        {
+            return Ok(value);
+        }
+        let iter = MontyIter::new(value, self.heap, self.interns)?;
+        let heap_id = self.heap.allocate(HeapData::Iter(iter))?;
+        Ok(Value::Ref(heap_id))
+    }
+
+    /// Like [`Self::get_iter`], returning the heap id of the iterator, which owns the reference.
+    pub(super) fn get_iter_id(&mut self, value: Value) -> RunResult<HeapId> {
+        let iter = self.get_iter(value)?;
+        let id = iter.ref_id().expect("iterators are heap values");
+        self.heap.inc_ref(id);
+        iter.drop_with_heap(self.heap);
+        Ok(id)
+    }
+
+    /// Advances any iterator on the heap, returning `None` once it is exhausted.
+    ///
+    /// The return value of a generator is discarded, as in a `for` loop.
+    ///
+    /// Checks the time limit first, since builtins consuming an infinite iterator like
+    /// `itertools.count()` may loop here without running any instructions.
+    pub(super) fn iter_next(&mut self, iter_id: HeapId) -> RunResult<Option<Value>> {
+        self.heap.tracker_mut().check_time()?;
+        if matches!(self.heap.get(iter_id), HeapData::Generator(_)) {
+            return match self.generator_send(iter_id, Value::None)? {
+                GeneratorStep::Yielded(value) => Ok(Some(value)),
+                GeneratorStep::Returned(value) => {
+                    value.drop_with_heap(self.heap);
+                    Ok(None)
+                }
+            };
+        }
+        if matches!(self.heap.get(iter_id), HeapData::Iter(iter) if iter.call_state().is_some()) {
+            self.call_iter_next(iter_id)
+        } else {
+            advance_on_heap(self.heap, iter_id, self.interns)
+        }
+    }
+
+    /// Advances an iterator created by `map()`, `filter()`, `iter(callable, sentinel)` or
+    /// the `itertools` module.
+    ///
+    /// The iterator is advanced with a copy of its state, since the functions it calls may
+    /// use it too, except for `itertools` iterators, which are advanced in place. Once
+    /// exhausted, the iterator releases its function and inputs.
+    pub(super) fn call_iter_next(&mut self, iter_id: HeapId) -> RunResult<Option<Value>> {
+        let HeapData::Iter(iter) = self.heap.get(iter_id) else {
+            unreachable!("call_iter_next called with a non-iterator")
+        };
+        let result = if matches!(iter.call_state(), Some(CallIter::Itertools(_))) {
+            self.itertools_next(iter_id)
+        } else {
+            let state = self.heap.with_entry_mut(iter_id, |heap, data| {
+                let HeapData::Iter(iter) = data else {
+                    unreachable!("call_iter_next called with a non-iterator")
+                };
+                iter.call_state().map(|state| state.clone_with_heap(heap))
+            });
+            let Some(state) = state else {
+                unreachable!("call_iter_next called with an iterator which doesn't call functions")
+            };
+            let result = match &state {
+                CallIter::Map { function, iterators } => self.map_next(function, iterators),
+                CallIter::Zip { iterators } => self.zip_next(iterators),
+                CallIter::Filter { function, iterator } => self.filter_next(function, *iterator),
+                CallIter::Sentinel { callable, sentinel } => self.sentinel_next(callable, sentinel),
+                CallIter::Itertools(_) | CallIter::Exhausted => Ok(None),
+            };
+            state.drop_with_heap(self.heap);
+            result
+        };
+
+        if matches!(result, Ok(None)) {
+            let HeapData::Iter(iter) = self.heap.get_mut(iter_id) else {
+                unreachable!("iterator changed type while advancing")
+            };
+            let state = iter
+                .call_state_mut()
+                .map(|state| std::mem::replace(state, CallIter::Exhausted));
+            state.drop_with_heap(self.heap);
+        }
+        result
+    }
+
+    /// Implements `map(function, *iterables)`, returning a lazy iterator.
+    pub(super) fn call_map(&mut self, args: ArgValues) -> RunResult<Value> {
+        let (mut positional, kwargs) = args.into_parts();
+        if !kwargs.is_empty() {
+            positional.drop_with_heap(self.heap);
+            kwargs.drop_with_heap(self.heap);
+            return Err(ExcType::type_error_no_kwargs("map"));
+        }
+        if positional.len() < 2 {
+            positional.drop_with_heap(self.heap);
+            return Err(ExcType::type_error("map() must have at least two arguments."));
+        }
+
+        let function = positional.next().expect("length checked above");
+        let mut iterators = Vec::with_capacity(positional.len());
+        while let Some(iterable) = positional.next() {
+            match self.get_iter_id(iterable) {
+                Ok(iter_id) => iterators.push(iter_id),
+                Err(e) => {
+                    positional.drop_with_heap(self.heap);
+                    CallIter::Map { function, iterators }.drop_with_heap(self.heap);
+                    return Err(e);
+                }
+            }
+        }
+        self.allocate_call_iter(CallIter::Map { function, iterators })
+    }
+
+    /// Implements `zip(*iterables)` when an iterable is a generator or function-calling
+    /// iterator, returning a lazy iterator.
+    pub(super) fn call_zip(&mut self, args: ArgValues) -> RunResult<Value> {
+        let (mut positional, kwargs) = args.into_parts();
+        if !kwargs.is_empty() {
+            positional.drop_with_heap(self.heap);
+            kwargs.drop_with_heap(self.heap);
+            return Err(
+                SimpleException::new_msg(ExcType::TypeError, "zip() does not support keyword arguments yet").into(),
+            );
+        }
+        let mut iterators = Vec::with_capacity(positional.len());
+        while let Some(iterable) = positional.next() {
+            match self.get_iter_id(iterable) {
+                Ok(iter_id) => iterators.push(iter_id),
+                Err(e) => {
+                    positional.drop_with_heap(self.heap);
+                    CallIter::Zip { iterators }.drop_with_heap(self.heap);
+                    return Err(e);
+                }
+            }
+        }
+        self.allocate_call_iter(CallIter::Zip { iterators })
+    }
+
+    /// Implements `enumerate(iterable, start=0)` when the iterable is a generator or
+    /// function-calling iterator, returning a lazy iterator which zips an
+    /// `itertools.count(start)` with it.
+    pub(super) fn call_enumerate(&mut self, args: ArgValues) -> RunResult<Value> {
+        let (iterable, start) = args.get_one_two_args("enumerate", self.heap)?;
+        let start = enumerate_start(start.as_ref(), self.heap);
+        let start = match start {
+            Ok(start) => start,
+            Err(e) => {
+                iterable.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+        let iterator = self.get_iter_id(iterable)?;
+        let counter = ItertoolsIter::Count {
+            current: Value::Int(start),
+            step: Value::Int(1),
+        };
+        match self
+            .heap
+            .allocate(HeapData::Iter(MontyIter::new_calling(CallIter::Itertools(Box::new(
+                counter,
+            ))))) {
+            Ok(counter) => self.allocate_call_iter(CallIter::Zip {
+                iterators: vec![counter, iterator],
+            }),
+            Err(e) => {
+                self.heap.dec_ref(iterator);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Implements `filter(function, iterable)`, returning a lazy iterator.
+    ///
+    /// A `None` function keeps the truthy items.
+    pub(super) fn call_filter(&mut self, args: ArgValues) -> RunResult<Value> {
+        let (function, iterable) = args.get_two_args("filter", self.heap)?;
+        match self.get_iter_id(iterable) {
+            Ok(iterator) => self.allocate_call_iter(CallIter::Filter { function, iterator }),
+            Err(e) => {
+                function.drop_with_heap(self.heap);
+                Err(e)
+            }
+        }
+    }
+
+    /// Implements `iter(callable, sentinel)`, returning an iterator which calls `callable`
+    /// until it returns a value equal to `sentinel`.
+    pub(super) fn call_iter_sentinel(&mut self, args: ArgValues) -> RunResult<Value> {
+        let (callable, sentinel) = args.get_two_args("iter", self.heap)?;
+        if !is_callable(&callable, self.heap) {
+            callable.drop_with_heap(self.heap);
+            sentinel.drop_with_heap(self.heap);
+            return Err(ExcType::type_error("iter(v, w): v must be callable"));
+        }
+        self.allocate_call_iter(CallIter::Sentinel { callable, sentinel })
+    }
+
+    /// Implements `next()` for generators and function-calling iterators, with an optional
+    /// default for when the iterator is exhausted.
+    pub(super) fn vm_iterator_next(&mut self, args: ArgValues) -> RunResult<Value> {
+        let (iterator, default) = args.get_one_two_args("next", self.heap)?;
+        let Value::Ref(iter_id) = iterator else {
+            unreachable!("vm_iterator_next called with a non-iterator")
+        };
+        let step = if self.is_generator(&iterator) {
+            self.generator_send(iter_id, Value::None)
+        } else {
+            self.iter_next(iter_id).map(|value| match value {
+                Some(value) => GeneratorStep::Yielded(value),
+                None => GeneratorStep::Returned(Value::None),
+            })
+        };
+        iterator.drop_with_heap(self.heap);
+        match (step, default) {
+            (Ok(GeneratorStep::Returned(value)), Some(default)) => {
+                value.drop_with_heap(self.heap);
+                Ok(default)
+            }
+            (Ok(step), default) => {
+                default.drop_with_heap(self.heap);
+                self.step_value(step)
+            }
+            (Err(e), default) => {
+                default.drop_with_heap(self.heap);
+                Err(e)
+            }
+        }
+    }
+
+    /// Implements `sum()` over a generator or function-calling iterator without collecting its values.
+    pub(super) fn vm_iterator_sum(&mut self, args: ArgValues) -> RunResult<Value> {
+        let (iterator, start) = args.get_one_two_args("sum", self.heap)?;
+        let mut accumulator = match start {
+            // Reject string start values - Python explicitly forbids this
+            Some(v) if matches!(v.py_type(self.heap), Type::Str) => {
+                iterator.drop_with_heap(self.heap);
+                v.drop_with_heap(self.heap);
+                return Err(SimpleException::new_msg(
+                    ExcType::TypeError,
+                    "sum() can't sum strings [use ''.join(seq) instead]",
+                )
+                .into());
+            }
+            Some(v) => v,
+            None => Value::Int(0),
+        };
+        let Value::Ref(iter_id) = iterator else {
+            unreachable!("vm_iterator_sum called with a non-iterator")
+        };
+
+        let result = loop {
+            let item = match self.iter_next(iter_id) {
+                Ok(Some(item)) => item,
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(e),
+            };
+            // Get item type before any operations (needed for error messages)
+            let item_type = item.py_type(self.heap);
+            let add_result = accumulator.py_add(&item, self.heap, self.interns);
+            item.drop_with_heap(self.heap);
+            match add_result {
+                Ok(Some(new_value)) => {
+                    accumulator.drop_with_heap(self.heap);
+                    accumulator = new_value;
+                }
+                Ok(None) => {
+                    let acc_type = accumulator.py_type(self.heap);
+                    break Err(ExcType::binary_type_error("+", acc_type, item_type));
+                }
+                Err(e) => break Err(e.into()),
+            }
+        };
+        iterator.drop_with_heap(self.heap);
+
+        match result {
+            Ok(()) => Ok(accumulator),
+            Err(e) => {
+                accumulator.drop_with_heap(self.heap);
+                Err(e)
+            }
+        }
+    }
+
+    /// Implements `any()` (or `all()` if `any` is false) over a generator or function-calling
+    /// iterator, stopping at the first value which decides the result.
+    pub(super) fn vm_iterator_any_all(&mut self, args: ArgValues, any: bool) -> RunResult<Value> {
+        let iterator = args.get_one_arg(if any { "any" } else { "all" }, self.heap)?;
+        let Value::Ref(iter_id) = iterator else {
+            unreachable!("vm_iterator_any_all called with a non-iterator")
+        };
+        let result = loop {
+            match self.iter_next(iter_id) {
+                Ok(Some(item)) => {
+                    if self.truthy(item) == any {
+                        break Ok(any);
+                    }
+                }
+                Ok(None) => break Ok(!any),
+                Err(e) => break Err(e),
+            }
+        };
+        iterator.drop_with_heap(self.heap);
+        result.map(Value::Bool)
+    }
+
+    /// Returns whether any positional argument is a generator or function-calling iterator.
+    pub(super) fn has_vm_iterator_arg(&self, args: &ArgValues) -> bool {
+        match args {
+            ArgValues::One(a) => self.is_vm_iterator(a),
+            ArgValues::Two(a, b) => self.is_vm_iterator(a) || self.is_vm_iterator(b),
+            ArgValues::ArgsKargs { args, .. } => args.iter().any(|a| self.is_vm_iterator(a)),
+            ArgValues::Empty | ArgValues::Kwargs(_) => false,
+        }
+    }
+
+    /// Returns whether the first positional argument is a generator or function-calling iterator.
+    pub(super) fn first_arg_is_vm_iterator(&self, args: &ArgValues) -> bool {
+        match args {
+            ArgValues::One(a) | ArgValues::Two(a, _) => self.is_vm_iterator(a),
+            ArgValues::ArgsKargs { args, .. } => args.first().is_some_and(|a| self.is_vm_iterator(a)),
+            ArgValues::Empty | ArgValues::Kwargs(_) => false,
+        }
+    }
+
+    /// Replaces generator and function-calling iterator arguments with lists of their values.
+    ///
+    /// Used for builtins and methods which consume whole iterables but can't advance
+    /// these iterators themselves; `which` selects their iterable arguments.
+    pub(super) fn materialize_vm_iterator_args(
+        &mut self,
+        args: ArgValues,
+        which: IterableArgs,
+    ) -> RunResult<ArgValues> {
+        match args {
+            ArgValues::One(a) => Ok(ArgValues::One(self.materialize_vm_iterator_arg(a, which, 0)?)),
+            ArgValues::Two(a, b) => {
+                let a = match self.materialize_vm_iterator_arg(a, which, 0) {
+                    Ok(a) => a,
+                    Err(e) => {
+                        b.drop_with_heap(self.heap);
+                        return Err(e);
+                    }
+                };
+                match self.materialize_vm_iterator_arg(b, which, 1) {
+                    Ok(b) => Ok(ArgValues::Two(a, b)),
+                    Err(e) => {
+                        a.drop_with_heap(self.heap);
+                        Err(e)
+                    }
+                }
+            }
+            ArgValues::ArgsKargs { args, kwargs } => {
+                let mut values = Vec::with_capacity(args.len());
+                let mut args = args.into_iter().enumerate();
+                while let Some((index, value)) = args.next() {
+                    match self.materialize_vm_iterator_arg(value, which, index) {
+                        Ok(value) => values.push(value),
+                        Err(e) => {
+                            values.drop_with_heap(self.heap);
+                            args.for_each(|(_, value)| value.drop_with_heap(self.heap));
+                            kwargs.drop_with_heap(self.heap);
+                            return Err(e);
+                        }
+                    }
+                }
+                Ok(ArgValues::ArgsKargs { args: values, kwargs })
+            }
+            other @ (ArgValues::Empty | ArgValues::Kwargs(_)) => Ok(other),
+        }
+    }
+
+    /// Materializes the positional argument at `index` if `which` selects it.
+    fn materialize_vm_iterator_arg(&mut self, value: Value, which: IterableArgs, index: usize) -> RunResult<Value> {
+        match which {
+            IterableArgs::Only(only) if only != index => Ok(value),
+            _ => self.materialize_vm_iterator(value),
+        }
+    }
+
+    /// Replaces a generator or function-calling iterator on top of the stack with a list of its values.
+    ///
+    /// Used by opcodes which consume a whole iterable, such as unpacking and `in`.
+    pub(super) fn materialize_top_vm_iterator(&mut self) -> RunResult<()> {
+        if !self.is_vm_iterator(self.peek()) {
+            return Ok(());
+        }
+        let value = self.pop();
+        let value = self.materialize_vm_iterator(value)?;
+        self.push(value);
+        Ok(())
+    }
+
+    /// Runs a generator or function-calling iterator to exhaustion and returns a list of its values.
+    ///
+    /// Other values are returned unchanged.
+    fn materialize_vm_iterator(&mut self, value: Value) -> RunResult<Value> {
+        if !self.is_vm_iterator(&value) {
+            return Ok(value);
+        }
+        let &Value::Ref(iter_id) = &value else {
+            unreachable!("iterators are heap values")
+        };
+        let mut values = Vec::new();
+        let result = loop {
+            match self.iter_next(iter_id) {
+                Ok(Some(item)) => values.push(item),
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(e),
+            }
+        };
+        value.drop_with_heap(self.heap);
+        if let Err(e) = result {
+            values.drop_with_heap(self.heap);
+            return Err(e);
+        }
+        let list_id = self.heap.allocate(HeapData::List(List::new(values)))?;
+        Ok(Value::Ref(list_id))
+    }
+
+    /// Calls the function of a `map()` iterator with the next item of each of its iterators.
+    fn map_next(&mut self, function: &Value, iterators: &[HeapId]) -> RunResult<Option<Value>> {
+        let Some(args) = self.next_items(iterators)? else {
+            return Ok(None);
+        };
+        let function = function.clone_with_heap(self.heap);
+        self.call_sync(function, ArgValues::from_vec(args)).map(Some)
+    }
+
+    /// Returns a tuple of the next item of each iterator of a `zip()` iterator.
+    fn zip_next(&mut self, iterators: &[HeapId]) -> RunResult<Option<Value>> {
+        let Some(items) = self.next_items(iterators)? else {
+            return Ok(None);
+        };
+        Ok(Some(allocate_tuple(items.into(), self.heap)?))
+    }
+
+    /// Returns the next item of each iterator, or `None` once any of them is exhausted.
+    fn next_items(&mut self, iterators: &[HeapId]) -> RunResult<Option<Vec<Value>>> {
+        let mut items = Vec::with_capacity(iterators.len());
+        for &iter_id in iterators {
+            match self.iter_next(iter_id) {
+                Ok(Some(item)) => items.push(item),
+                // The shortest iterable ends the iterator
+                Ok(None) => {
+                    items.drop_with_heap(self.heap);
+                    return Ok(None);
+                }
+                Err(e) => {
+                    items.drop_with_heap(self.heap);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(Some(items))
+    }
+
+    /// Returns the next item of a `filter()` iterator's input for which its function returns a truthy value.
+    fn filter_next(&mut self, function: &Value, iter_id: HeapId) -> RunResult<Option<Value>> {
+        loop {
+            let Some(item) = self.iter_next(iter_id)? else {
+                return Ok(None);
+            };
+            let keep = if matches!(function, Value::None) {
+                item.py_bool(self.heap, self.interns)
+            } else {
+                let function = function.clone_with_heap(self.heap);
+                let arg = item.clone_with_heap(self.heap);
+                match self.call_sync(function, ArgValues::One(arg)) {
+                    Ok(result) => self.truthy(result),
+                    Err(e) => {
+                        item.drop_with_heap(self.heap);
+                        return Err(e);
+                    }
+                }
+            };
+            if keep {
+                return Ok(Some(item));
+            }
+            item.drop_with_heap(self.heap);
+        }
+    }
+
+    /// Calls the callable of an `iter(callable, sentinel)` iterator, returning `None` once
+    /// it returns the sentinel.
+    fn sentinel_next(&mut self, callable: &Value, sentinel: &Value) -> RunResult<Option<Value>> {
+        let callable = callable.clone_with_heap(self.heap);
+        let value = self.call_sync(callable, ArgValues::Empty)?;
+        match self.eq_values(&value, sentinel) {
+            Ok(false) => Ok(Some(value)),
+            Ok(true) => {
+                value.drop_with_heap(self.heap);
+                Ok(None)
+            }
+            Err(e) => {
+                value.drop_with_heap(self.heap);
+                Err(e)
+            }
+        }
+    }
+
+    /// Allocates a function-calling iterator with the given state.
+    pub(super) fn allocate_call_iter(&mut self, state: CallIter) -> RunResult<Value> {
+        let iter = MontyIter::new_calling(state);
+        let heap_id = self.heap.allocate(HeapData::Iter(iter))?;
+        Ok(Value::Ref(heap_id))
+    }
+}
+
+/// The positional arguments of a builtin, function or method which are iterables it
+/// consumes whole.
+///
+/// `MontyIter` can't advance generators and function-calling iterators, so the VM collects
+/// these arguments into lists before the call. Keyword arguments are passed unchanged, so
+/// e.g. `random.choices(weights=gen)` isn't supported.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum IterableArgs {
+    /// Every positional argument, like the iterables of `set.union()`.
+    All,
+    /// Only the positional argument at this index, like the iterable of `dict.fromkeys()`.
+    Only(usize),
+}
+
+/// Returns the iterable arguments of a builtin which consumes whole iterables.
+///
+/// Builtins which advance these iterators lazily, like `sum()`, are handled before this is checked.
+pub(super) fn builtin_iterable_args(builtin: Builtins) -> Option<IterableArgs> {
+    match builtin {
+        Builtins::Function(BuiltinsFunctions::Max | BuiltinsFunctions::Min | BuiltinsFunctions::Sorted)
+        | Builtins::Type(
+            Type::List
+            | Type::Tuple
+            | Type::Set
+            | Type::FrozenSet
+            | Type::Dict
+            | Type::Bytes
+            | Type::ByteArray
+            | Type::Counter
+            | Type::OrderedDict
+            | Type::Deque,
+        ) => Some(IterableArgs::All),
+        Builtins::Type(Type::DefaultDict) => Some(IterableArgs::Only(1)),
+        _ => None,
+    }
+}
+
+/// Returns the iterable arguments of a module function which consumes whole iterables.
+pub(super) fn module_function_iterable_args(function: ModuleFunctions) -> Option<IterableArgs> {
+    match function {
+        ModuleFunctions::Math(MathFunctions::Fsum | MathFunctions::Prod | MathFunctions::Dist) => {
+            Some(IterableArgs::All)
+        }
+        ModuleFunctions::Collections(CollectionsFunctions::Namedtuple)
+        | ModuleFunctions::Random(RandomFunctions::Choices) => Some(IterableArgs::Only(1)),
+        _ => None,
+    }
+}
+
+/// Returns the iterable arguments of the method `name_id` of builtin types and modules.
+pub(super) fn method_iterable_args(name_id: StringId) -> Option<IterableArgs> {
+    match StaticStrings::from_string_id(name_id)? {
+        StaticStrings::Extend
+        | StaticStrings::Extendleft
+        | StaticStrings::Update
+        | StaticStrings::Subtract
+        | StaticStrings::Union
+        | StaticStrings::Intersection
+        | StaticStrings::Difference
+        | StaticStrings::SymmetricDifference
+        | StaticStrings::Issubset
+        | StaticStrings::Issuperset
+        | StaticStrings::Isdisjoint
+        | StaticStrings::Join
+        | StaticStrings::Fsum
+        | StaticStrings::Prod
+        | StaticStrings::Dist
+        | StaticStrings::UnderscoreMake => Some(IterableArgs::All),
+        StaticStrings::Fromkeys => Some(IterableArgs::Only(0)),
+        StaticStrings::Choices => Some(IterableArgs::Only(1)),
+        _ => None,
+    }
+}
@@ -0,0 +1,929 @@
+//! The iterators of the `itertools` module, created and advanced by the VM since they
+//! advance other iterators, which may be generators or call Python functions.
+//!
+//! Each is a function-calling iterator (see `bytecode/vm/iterator.rs`) with an
+//! [`ItertoolsIter`] state. The state is taken out of the iterator while it advances and
+//! put back afterwards, so advancing an iterator from a function it calls raises
+//! `ValueError`, like resuming a running generator.
+//!
+//! Groups returned by `groupby()` are iterators over lists of their values, so unlike
+//! CPython, a group can still be used after the `groupby()` iterator has moved past it.
+
+use smallvec::{SmallVec, smallvec};
+
+use super::VM;
+use crate::{
+    args::{ArgValues, KwargsValues, bind_args},
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    io::PrintWriter,
+    modules::itertools::{ItertoolsFunctions, ItertoolsIter},
+    resource::ResourceTracker,
+    types::{List, PyTrait, Type, allocate_tuple, iter::CallIter},
+    value::Value,
+};
+
+const ISLICE_STOP_ERROR: &str = "Stop argument for islice() must be None or an integer: 0 <= x <= sys.maxsize.";
+const ISLICE_START_ERROR: &str = "Indices for islice() must be None or an integer: 0 <= x <= sys.maxsize.";
+const ISLICE_STEP_ERROR: &str = "Step for islice() must be a positive integer or None.";
+
+impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
+    /// Calls an `itertools` function, returning the iterator it creates.
+    pub(super) fn call_itertools(&mut self, function: ItertoolsFunctions, args: ArgValues) -> RunResult<Value> {
+        match function {
+            ItertoolsFunctions::Accumulate => self.itertools_accumulate(args),
+            ItertoolsFunctions::Batched => self.itertools_batched(args),
+            ItertoolsFunctions::Chain => self.itertools_chain(args),
+            ItertoolsFunctions::Combinations => self.itertools_combinations(args),
+            ItertoolsFunctions::Count => self.itertools_count(args),
+            ItertoolsFunctions::Cycle => self.itertools_cycle(args),
+            ItertoolsFunctions::Groupby => self.itertools_groupby(args),
+            ItertoolsFunctions::Islice => self.itertools_islice(args),
+            ItertoolsFunctions::Pairwise => self.itertools_pairwise(args),
+            ItertoolsFunctions::Permutations => self.itertools_permutations(args),
+            ItertoolsFunctions::Product => self.itertools_product(args),
+            ItertoolsFunctions::Repeat => self.itertools_repeat(args),
+            ItertoolsFunctions::ZipLongest => self.itertools_zip_longest(args),
+        }
+    }
+
+    /// Implements `chain.from_iterable(iterable)`, which chains the iterables `iterable` returns.
+    pub(super) fn call_chain_from_iterable(&mut self, args: ArgValues) -> RunResult<Value> {
+        let iterable = args.get_one_arg("chain.from_iterable", self.heap)?;
+        let iterables = self.get_iter_id(iterable)?;
+        self.allocate_itertools(ItertoolsIter::Chain {
+            iterables,
+            current: None,
+        })
+    }
+
+    /// Advances an `itertools` iterator, taking its state out of the iterator meanwhile.
+    pub(super) fn itertools_next(&mut self, iter_id: HeapId) -> RunResult<Option<Value>> {
+        let mut state = self.replace_itertools_state(iter_id, ItertoolsIter::Running);
+        // Keeps the iterator alive until its state is put back
+        self.heap.inc_ref(iter_id);
+        let result = match &mut state {
+            ItertoolsIter::Accumulate {
+                iterator,
+                func,
+                total,
+                initial,
+            } => self.accumulate_next(*iterator, func, total, initial),
+            ItertoolsIter::Batched { iterator, size, strict } => self.batched_next(*iterator, *size, *strict),
+            ItertoolsIter::Chain { iterables, current } => self.chain_next(*iterables, current),
+            ItertoolsIter::Combinations { pool, r, indices } => self.combinations_next(pool, *r, indices),
+            ItertoolsIter::Count { current, step } => self.count_next(current, step),
+            ItertoolsIter::Cycle { iterator, saved, index } => self.cycle_next(iterator, saved, index),
+            ItertoolsIter::Groupby { iterator, key_fn, next } => self.groupby_next(*iterator, key_fn, next),
+            ItertoolsIter::Islice {
+                iterator,
+                next_index,
+                stop,
+                step,
+                position,
+            } => self.islice_next(*iterator, next_index, *stop, *step, position),
+            ItertoolsIter::Pairwise { iterator, previous } => self.pairwise_next(*iterator, previous),
+            ItertoolsIter::Permutations {
+                pool,
+                r,
+                next_index,
+                total,
+            } => self.permutations_next(pool, *r, next_index, *total),
+            ItertoolsIter::Product {
+                pools,
+                repeat,
+                next_index,
+                total,
+            } => self.product_next(pools, *repeat, next_index, *total),
+            ItertoolsIter::Repeat { object, times } => Ok(self.repeat_next(object, times)),
+            ItertoolsIter::ZipLongest { iterators, fillvalue } => self.zip_longest_next(iterators, fillvalue),
+            ItertoolsIter::Running => {
+                Err(SimpleException::new_msg(ExcType::ValueError, "itertools iterator already executing").into())
+            }
+        };
+        if state.has_refs() {
+            self.heap.mark_potential_cycle();
+        }
+        self.replace_itertools_state(iter_id, state);
+        self.heap.dec_ref(iter_id);
+        result
+    }
+
+    /// Replaces the state of an `itertools` iterator, returning the old state.
+    fn replace_itertools_state(&mut self, iter_id: HeapId, state: ItertoolsIter) -> ItertoolsIter {
+        let HeapData::Iter(iter) = self.heap.get_mut(iter_id) else {
+            unreachable!("itertools state replaced on a non-iterator")
+        };
+        // Advancing the iterator can't exhaust it meanwhile, as it's `Running`
+        let Some(CallIter::Itertools(current)) = iter.call_state_mut() else {
+            unreachable!("itertools state replaced on another iterator")
+        };
+        std::mem::replace(&mut **current, state)
+    }
+
+    /// Implements `accumulate(iterable, func=None, *, initial=None)`.
+    fn itertools_accumulate(&mut self, args: ArgValues) -> RunResult<Value> {
+        let [iterable, func, initial] = bind_args(
+            "accumulate",
+            args,
+            ["iterable", "func", "initial"],
+            1,
+            self.heap,
+            self.interns,
+        )?;
+        let func = func.unwrap_or(Value::None);
+        let iterator = match self.get_iter_id(iterable.expect("required argument is bound")) {
+            Ok(iterator) => iterator,
+            Err(e) => {
+                func.drop_with_heap(self.heap);
+                initial.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+        let total = initial.filter(|initial| !matches!(initial, Value::None));
+        self.allocate_itertools(ItertoolsIter::Accumulate {
+            iterator,
+            func,
+            initial: total.is_some(),
+            total,
+        })
+    }
+
+    fn accumulate_next(
+        &mut self,
+        iterator: HeapId,
+        func: &Value,
+        total: &mut Option<Value>,
+        initial: &mut bool,
+    ) -> RunResult<Option<Value>> {
+        if *initial {
+            *initial = false;
+            return Ok(total.as_ref().map(|total| total.clone_with_heap(self.heap)));
+        }
+        let Some(item) = self.iter_next(iterator)? else {
+            return Ok(None);
+        };
+        let next = match total {
+            None => item,
+            Some(total) if matches!(func, Value::None) => {
+                self.push(total.clone_with_heap(self.heap));
+                self.push(item);
+                self.binary_add()?;
+                self.pop()
+            }
+            Some(total) => {
+                let func = func.clone_with_heap(self.heap);
+                let total = total.clone_with_heap(self.heap);
+                self.call_sync(func, ArgValues::Two(total, item))?
+            }
+        };
+        total.replace(next.clone_with_heap(self.heap)).drop_with_heap(self.heap);
+        Ok(Some(next))
+    }
+
+    /// Implements `batched(iterable, n, *, strict=False)`.
+    fn itertools_batched(&mut self, args: ArgValues) -> RunResult<Value> {
+        let [iterable, n, strict] =
+            bind_args("batched", args, ["iterable", "n", "strict"], 2, self.heap, self.interns)?;
+        let iterable = iterable.expect("required argument is bound");
+        let n = n.expect("required argument is bound");
+        let strict = strict.is_some_and(|strict| self.truthy(strict));
+        let size = int_arg(&n, self.heap);
+        n.drop_with_heap(self.heap);
+        let size = match size.map(usize::try_from) {
+            Ok(Ok(size)) if size >= 1 => size,
+            Ok(_) => {
+                iterable.drop_with_heap(self.heap);
+                return Err(SimpleException::new_msg(ExcType::ValueError, "n must be at least one").into());
+            }
+            Err(e) => {
+                iterable.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+        let iterator = self.get_iter_id(iterable)?;
+        self.allocate_itertools(ItertoolsIter::Batched { iterator, size, strict })
+    }
+
+    fn batched_next(&mut self, iterator: HeapId, size: usize, strict: bool) -> RunResult<Option<Value>> {
+        let mut batch = Vec::new();
+        while batch.len() < size {
+            match self.iter_next(iterator) {
+                Ok(Some(item)) => batch.push(item),
+                Ok(None) => break,
+                Err(e) => {
+                    batch.drop_with_heap(self.heap);
+                    return Err(e);
+                }
+            }
+        }
+        if batch.is_empty() {
+            return Ok(None);
+        }
+        if batch.len() < size && strict {
+            batch.drop_with_heap(self.heap);
+            return Err(SimpleException::new_msg(ExcType::ValueError, "batched(): incomplete batch").into());
+        }
+        Ok(Some(allocate_tuple(batch.into(), self.heap)?))
+    }
+
+    /// Implements `chain(*iterables)`.
+    fn itertools_chain(&mut self, args: ArgValues) -> RunResult<Value> {
+        let (positional, kwargs) = args.into_parts();
+        if !kwargs.is_empty() {
+            positional.drop_with_heap(self.heap);
+            kwargs.drop_with_heap(self.heap);
+            return Err(ExcType::type_error_no_kwargs("chain"));
+        }
+        let iterables = allocate_tuple(positional.collect(), self.heap)?;
+        let iterables = self.get_iter_id(iterables)?;
+        self.allocate_itertools(ItertoolsIter::Chain {
+            iterables,
+            current: None,
+        })
+    }
+
+    fn chain_next(&mut self, iterables: HeapId, current: &mut Option<HeapId>) -> RunResult<Option<Value>> {
+        loop {
+            if let Some(current_id) = *current {
+                match self.iter_next(current_id) {
+                    Ok(None) => {}
+                    result => return result,
+                }
+                *current = None;
+                self.heap.dec_ref(current_id);
+            }
+            let Some(iterable) = self.iter_next(iterables)? else {
+                return Ok(None);
+            };
+            *current = Some(self.get_iter_id(iterable)?);
+        }
+    }
+
+    /// Implements `combinations(iterable, r)`.
+    fn itertools_combinations(&mut self, args: ArgValues) -> RunResult<Value> {
+        let [iterable, r] = bind_args("combinations", args, ["iterable", "r"], 2, self.heap, self.interns)?;
+        let iterable = iterable.expect("required argument is bound");
+        let r = match self.count_arg(r.expect("required argument is bound"), "r must be non-negative") {
+            Ok(r) => r,
+            Err(e) => {
+                iterable.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+        let pool = self.collect_iterable(iterable)?;
+        self.allocate_itertools(ItertoolsIter::Combinations { pool, r, indices: None })
+    }
+
+    /// Advances to the next combination in lexicographic order of the indices, as CPython does.
+    fn combinations_next(
+        &mut self,
+        pool: &[Value],
+        r: usize,
+        indices: &mut Option<Vec<usize>>,
+    ) -> RunResult<Option<Value>> {
+        let n = pool.len();
+        let next = match indices.take() {
+            None if r > n => return Ok(None),
+            None => (0..r).collect(),
+            Some(mut last) => {
+                let Some(i) = (0..r).rev().find(|&i| last[i] != i + n - r) else {
+                    return Ok(None);
+                };
+                last[i] += 1;
+                for j in i + 1..r {
+                    last[j] = last[j - 1] + 1;
+                }
+                last
+            }
+        };
+        let items: SmallVec<_> = next.iter().map(|&i| pool[i].clone_with_heap(self.heap)).collect();
+        *indices = Some(next);
+        Ok(Some(allocate_tuple(items, self.heap)?))
+    }
+
+    /// Implements `count(start=0, step=1)`.
+    fn itertools_count(&mut self, args: ArgValues) -> RunResult<Value> {
+        let [start, step] = bind_args("count", args, ["start", "step"], 0, self.heap, self.interns)?;
+        let start = start.unwrap_or(Value::Int(0));
+        let step = step.unwrap_or(Value::Int(1));
+        let is_number = |value: &Value| {
+            matches!(
+                value.py_type(self.heap),
+                Type::Int | Type::Float | Type::Bool | Type::Complex
+            )
+        };
+        if !is_number(&start) || !is_number(&step) {
+            start.drop_with_heap(self.heap);
+            step.drop_with_heap(self.heap);
+            return Err(ExcType::type_error("a number is required"));
+        }
+        self.allocate_itertools(ItertoolsIter::Count { current: start, step })
+    }
+
+    fn count_next(&mut self, current: &mut Value, step: &Value) -> RunResult<Option<Value>> {
+        self.push(current.clone_with_heap(self.heap));
+        self.push(step.clone_with_heap(self.heap));
+        self.binary_add()?;
+        let next = self.pop();
+        Ok(Some(std::mem::replace(current, next)))
+    }
+
+    /// Implements `cycle(iterable)`.
+    fn itertools_cycle(&mut self, args: ArgValues) -> RunResult<Value> {
+        let iterable = args.get_one_arg("cycle", self.heap)?;
+        let iterator = self.get_iter_id(iterable)?;
+        self.allocate_itertools(ItertoolsIter::Cycle {
+            iterator: Some(iterator),
+            saved: Vec::new(),
+            index: 0,
+        })
+    }
+
+    fn cycle_next(
+        &mut self,
+        iterator: &mut Option<HeapId>,
+        saved: &mut Vec<Value>,
+        index: &mut usize,
+    ) -> RunResult<Option<Value>> {
+        if let Some(input) = *iterator {
+            if let Some(item) = self.iter_next(input)? {
+                saved.push(item.clone_with_heap(self.heap));
+                return Ok(Some(item));
+            }
+            *iterator = None;
+            self.heap.dec_ref(input);
+        }
+        if saved.is_empty() {
+            return Ok(None);
+        }
+        let item = saved[*index].clone_with_heap(self.heap);
+        *index = (*index + 1) % saved.len();
+        Ok(Some(item))
+    }
+
+    /// Implements `groupby(iterable, key=None)`.
+    fn itertools_groupby(&mut self, args: ArgValues) -> RunResult<Value> {
+        let [iterable, key] = bind_args("groupby", args, ["iterable", "key"], 1, self.heap, self.interns)?;
+        let key = key.unwrap_or(Value::None);
+        let iterator = match self.get_iter_id(iterable.expect("required argument is bound")) {
+            Ok(iterator) => iterator,
+            Err(e) => {
+                key.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+        self.allocate_itertools(ItertoolsIter::Groupby {
+            iterator,
+            key_fn: key,
+            next: None,
+        })
+    }
+
+    /// Returns the next `(key, group)` pair, taking values from the input up to the first
+    /// value with a different key, which is kept for the following group.
+    fn groupby_next(
+        &mut self,
+        input: HeapId,
+        key_fn: &Value,
+        next: &mut Option<(Value, Value)>,
+    ) -> RunResult<Option<Value>> {
+        let (key, first) = if let Some(next) = next.take() {
+            next
+        } else {
+            let Some(item) = self.iter_next(input)? else {
+                return Ok(None);
+            };
+            match self.group_key(key_fn, &item) {
+                Ok(key) => (key, item),
+                Err(e) => {
+                    item.drop_with_heap(self.heap);
+                    return Err(e);
+                }
+            }
+        };
+
+        let mut group = vec![first];
+        let following = loop {
+            let item = match self.iter_next(input) {
+                Ok(Some(item)) => item,
+                Ok(None) => break Ok(None),
+                Err(e) => break Err(e),
+            };
+            let item_key = match self.group_key(key_fn, &item) {
+                Ok(item_key) => item_key,
+                Err(e) => {
+                    item.drop_with_heap(self.heap);
+                    break Err(e);
+                }
+            };
+            match self.eq_values(&key, &item_key) {
+                Ok(true) => {
+                    item_key.drop_with_heap(self.heap);
+                    group.push(item);
+                }
+                Ok(false) => break Ok(Some((item_key, item))),
+                Err(e) => {
+                    item_key.drop_with_heap(self.heap);
+                    item.drop_with_heap(self.heap);
+                    break Err(e);
+                }
+            }
+        };
+        let following = match following {
+            Ok(following) => following,
+            Err(e) => {
+                key.drop_with_heap(self.heap);
+                group.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+        *next = following;
+
+        let group_id = match self.heap.allocate(HeapData::List(List::new(group))) {
+            Ok(group_id) => group_id,
+            Err(e) => {
+                key.drop_with_heap(self.heap);
+                return Err(e.into());
+            }
+        };
+        let group = match self.get_iter(Value::Ref(group_id)) {
+            Ok(group) => group,
+            Err(e) => {
+                key.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+        Ok(Some(allocate_tuple(smallvec![key, group], self.heap)?))
+    }
+
+    /// Returns the `groupby()` key of `item`: the result of the key function, or the item itself.
+    fn group_key(&mut self, key_fn: &Value, item: &Value) -> RunResult<Value> {
+        if matches!(key_fn, Value::None) {
+            return Ok(item.clone_with_heap(self.heap));
+        }
+        let key_fn = key_fn.clone_with_heap(self.heap);
+        let item = item.clone_with_heap(self.heap);
+        self.call_sync(key_fn, ArgValues::One(item))
+    }
+
+    /// Implements `islice(iterable, stop)` and `islice(iterable, start, stop[, step])`.
+    fn itertools_islice(&mut self, args: ArgValues) -> RunResult<Value> {
+        let (mut positional, kwargs) = args.into_parts();
+        if !kwargs.is_empty() {
+            positional.drop_with_heap(self.heap);
+            kwargs.drop_with_heap(self.heap);
+            return Err(ExcType::type_error_no_kwargs("islice"));
+        }
+        let count = positional.len();
+        if !(2..=4).contains(&count) {
+            positional.drop_with_heap(self.heap);
+            return Err(if count < 2 {
+                ExcType::type_error_at_least("islice", 2, count)
+            } else {
+                ExcType::type_error_at_most("islice", 4, count)
+            });
+        }
+        let iterable = positional.next().expect("length checked above");
+        let bounds: Vec<Value> = positional.collect();
+        let parsed = match bounds.as_slice() {
+            [stop] => islice_bounds(None, stop, None),
+            [start, stop] => islice_bounds(Some(start), stop, None),
+            [start, stop, step] => islice_bounds(Some(start), stop, Some(step)),
+            _ => unreachable!("length checked above"),
+        };
+        bounds.drop_with_heap(self.heap);
+        let (start, stop, step) = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                iterable.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+        let iterator = self.get_iter_id(iterable)?;
+        self.allocate_itertools(ItertoolsIter::Islice {
+            iterator,
+            next_index: start,
+            stop,
+            step,
+            position: 0,
+        })
+    }
+
+    fn islice_next(
+        &mut self,
+        iterator: HeapId,
+        next_index: &mut usize,
+        stop: Option<usize>,
+        step: usize,
+        position: &mut usize,
+    ) -> RunResult<Option<Value>> {
+        let Some(item) = self.islice_take(iterator, position, *next_index, stop)? else {
+            return Ok(None);
+        };
+        let following = next_index.saturating_add(step);
+        *next_index = stop.map_or(following, |stop| following.min(stop));
+        Ok(Some(item))
+    }
+
+    /// Skips values of the input up to `next_index` and takes the value there, unless
+    /// it is at or past `stop`. `position` counts the values taken from the input.
+    fn islice_take(
+        &mut self,
+        input: HeapId,
+        position: &mut usize,
+        next_index: usize,
+        stop: Option<usize>,
+    ) -> RunResult<Option<Value>> {
+        while *position < next_index {
+            let Some(item) = self.iter_next(input)? else {
+                return Ok(None);
+            };
+            item.drop_with_heap(self.heap);
+            *position += 1;
+        }
+        if stop.is_some_and(|stop| *position >= stop) {
+            return Ok(None);
+        }
+        let item = self.iter_next(input)?;
+        if item.is_some() {
+            *position += 1;
+        }
+        Ok(item)
+    }
+
+    /// Implements `pairwise(iterable)`.
+    fn itertools_pairwise(&mut self, args: ArgValues) -> RunResult<Value> {
+        let iterable = args.get_one_arg("pairwise", self.heap)?;
+        let iterator = self.get_iter_id(iterable)?;
+        self.allocate_itertools(ItertoolsIter::Pairwise {
+            iterator,
+            previous: None,
+        })
+    }
+
+    fn pairwise_next(&mut self, input: HeapId, previous: &mut Option<Value>) -> RunResult<Option<Value>> {
+        let first = match previous {
+            Some(previous) => previous.clone_with_heap(self.heap),
+            None => {
+                let Some(first) = self.iter_next(input)? else {
+                    return Ok(None);
+                };
+                first
+            }
+        };
+        let item = match self.iter_next(input) {
+            Ok(Some(item)) => item,
+            result => {
+                first.drop_with_heap(self.heap);
+                return result;
+            }
+        };
+        previous
+            .replace(item.clone_with_heap(self.heap))
+            .drop_with_heap(self.heap);
+        Ok(Some(allocate_tuple(smallvec![first, item], self.heap)?))
+    }
+
+    /// Implements `permutations(iterable, r=None)`.
+    fn itertools_permutations(&mut self, args: ArgValues) -> RunResult<Value> {
+        let [iterable, r] = bind_args("permutations", args, ["iterable", "r"], 1, self.heap, self.interns)?;
+        let iterable = iterable.expect("required argument is bound");
+        let r = match r {
+            None | Some(Value::None) => None,
+            Some(r) => match self.count_arg(r, "r must be non-negative") {
+                Ok(r) => Some(r),
+                Err(e) => {
+                    iterable.drop_with_heap(self.heap);
+                    return Err(e);
+                }
+            },
+        };
+        let pool = self.collect_iterable(iterable)?;
+        let n = pool.len();
+        let r = r.unwrap_or(n);
+        let total = if r > n {
+            0
+        } else {
+            (n - r + 1..=n).fold(1, usize::saturating_mul)
+        };
+        self.allocate_itertools(ItertoolsIter::Permutations {
+            pool,
+            r,
+            next_index: 0,
+            total,
+        })
+    }
+
+    /// Returns the permutation at the next index, in CPython's order.
+    ///
+    /// The index is decoded with radices `n, n - 1, ...` into the position of each value
+    /// among the values not picked yet.
+    fn permutations_next(
+        &mut self,
+        pool: &[Value],
+        r: usize,
+        next_index: &mut usize,
+        total: usize,
+    ) -> RunResult<Option<Value>> {
+        let index = *next_index;
+        if index >= total {
+            return Ok(None);
+        }
+        *next_index += 1;
+
+        let n = pool.len();
+        let mut choices = vec![0; r];
+        let mut remainder = index;
+        for (j, choice) in choices.iter_mut().enumerate().rev() {
+            let radix = n - j;
+            *choice = remainder % radix;
+            remainder /= radix;
+        }
+        let mut available: Vec<usize> = (0..n).collect();
+        let items: SmallVec<_> = choices
+            .into_iter()
+            .map(|choice| {
+                let position = available.remove(choice);
+                pool[position].clone_with_heap(self.heap)
+            })
+            .collect();
+        Ok(Some(allocate_tuple(items, self.heap)?))
+    }
+
+    /// Implements `product(*iterables, repeat=1)`.
+    fn itertools_product(&mut self, args: ArgValues) -> RunResult<Value> {
+        let (positional, kwargs) = args.into_parts();
+        let iterables: Vec<Value> = positional.collect();
+        let repeat = match self.only_kwarg("product", "repeat", kwargs) {
+            Ok(None) => Ok(1),
+            Ok(Some(repeat)) => self.count_arg(repeat, "repeat argument cannot be negative"),
+            Err(e) => Err(e),
+        };
+        let repeat = match repeat {
+            Ok(repeat) => repeat,
+            Err(e) => {
+                iterables.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+
+        let mut pools: Vec<Vec<Value>> = Vec::with_capacity(iterables.len());
+        let mut iterables = iterables.into_iter();
+        while let Some(iterable) = iterables.next() {
+            match self.collect_iterable(iterable) {
+                Ok(pool) => pools.push(pool),
+                Err(e) => {
+                    iterables.drop_with_heap(self.heap);
+                    for pool in pools {
+                        pool.drop_with_heap(self.heap);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        let per_repeat = pools
+            .iter()
+            .fold(1, |total: usize, pool| total.saturating_mul(pool.len()));
+        let total = per_repeat
+            .checked_pow(u32::try_from(repeat).unwrap_or(u32::MAX))
+            .unwrap_or(usize::MAX);
+        self.allocate_itertools(ItertoolsIter::Product {
+            pools,
+            repeat,
+            next_index: 0,
+            total,
+        })
+    }
+
+    /// Returns the tuple at the next index, decoding it with the pool lengths as radices,
+    /// the rightmost pool changing fastest.
+    fn product_next(
+        &mut self,
+        pools: &[Vec<Value>],
+        repeat: usize,
+        next_index: &mut usize,
+        total: usize,
+    ) -> RunResult<Option<Value>> {
+        let index = *next_index;
+        if index >= total {
+            return Ok(None);
+        }
+        *next_index += 1;
+
+        let width = pools.len() * repeat;
+        let mut picks = vec![0; width];
+        let mut remainder = index;
+        for (position, pick) in picks.iter_mut().enumerate().rev() {
+            let len = pools[position % pools.len()].len();
+            *pick = remainder % len;
+            remainder /= len;
+        }
+        let items: SmallVec<_> = picks
+            .into_iter()
+            .enumerate()
+            .map(|(position, pick)| pools[position % pools.len()][pick].clone_with_heap(self.heap))
+            .collect();
+        Ok(Some(allocate_tuple(items, self.heap)?))
+    }
+
+    /// Implements `repeat(object[, times])`.
+    fn itertools_repeat(&mut self, args: ArgValues) -> RunResult<Value> {
+        let [object, times] = bind_args("repeat", args, ["object", "times"], 1, self.heap, self.interns)?;
+        let object = object.expect("required argument is bound");
+        let times = match times {
+            None => None,
+            Some(times) => {
+                let count = int_arg(&times, self.heap);
+                times.drop_with_heap(self.heap);
+                match count {
+                    // A negative count repeats nothing
+                    Ok(count) => Some(usize::try_from(count).unwrap_or(0)),
+                    Err(e) => {
+                        object.drop_with_heap(self.heap);
+                        return Err(e);
+                    }
+                }
+            }
+        };
+        self.allocate_itertools(ItertoolsIter::Repeat { object, times })
+    }
+
+    fn repeat_next(&mut self, object: &Value, times: &mut Option<usize>) -> Option<Value> {
+        if let Some(times) = times {
+            if *times == 0 {
+                return None;
+            }
+            *times -= 1;
+        }
+        Some(object.clone_with_heap(self.heap))
+    }
+
+    /// Implements `zip_longest(*iterables, fillvalue=None)`.
+    fn itertools_zip_longest(&mut self, args: ArgValues) -> RunResult<Value> {
+        let (positional, kwargs) = args.into_parts();
+        let iterables: Vec<Value> = positional.collect();
+        let fillvalue = match self.only_kwarg("zip_longest", "fillvalue", kwargs) {
+            Ok(fillvalue) => fillvalue.unwrap_or(Value::None),
+            Err(e) => {
+                iterables.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+        let mut iterators = Vec::with_capacity(iterables.len());
+        let mut iterables = iterables.into_iter();
+        while let Some(iterable) = iterables.next() {
+            match self.get_iter_id(iterable) {
+                Ok(iterator) => iterators.push(Some(iterator)),
+                Err(e) => {
+                    iterables.drop_with_heap(self.heap);
+                    fillvalue.drop_with_heap(self.heap);
+                    for iterator in iterators.into_iter().flatten() {
+                        self.heap.dec_ref(iterator);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        self.allocate_itertools(ItertoolsIter::ZipLongest { iterators, fillvalue })
+    }
+
+    fn zip_longest_next(&mut self, iterators: &mut [Option<HeapId>], fillvalue: &Value) -> RunResult<Option<Value>> {
+        if iterators.iter().all(Option::is_none) {
+            return Ok(None);
+        }
+        let mut values = Vec::with_capacity(iterators.len());
+        for slot in iterators.iter_mut() {
+            // Exhausted inputs are released and replaced with `None`
+            if let Some(input) = *slot {
+                match self.iter_next(input) {
+                    Ok(Some(item)) => {
+                        values.push(item);
+                        continue;
+                    }
+                    Ok(None) => {
+                        *slot = None;
+                        self.heap.dec_ref(input);
+                    }
+                    Err(e) => {
+                        values.drop_with_heap(self.heap);
+                        return Err(e);
+                    }
+                }
+            }
+            values.push(fillvalue.clone_with_heap(self.heap));
+        }
+        if iterators.iter().all(Option::is_none) {
+            values.drop_with_heap(self.heap);
+            return Ok(None);
+        }
+        Ok(Some(allocate_tuple(values.into(), self.heap)?))
+    }
+
+    /// Allocates an `itertools` iterator with the given state.
+    fn allocate_itertools(&mut self, state: ItertoolsIter) -> RunResult<Value> {
+        self.allocate_call_iter(CallIter::Itertools(Box::new(state)))
+    }
+
+    /// Converts an argument which must be a non-negative int, raising `ValueError` with
+    /// `negative_error` if it's negative. Takes ownership of `value`.
+    fn count_arg(&mut self, value: Value, negative_error: &'static str) -> RunResult<usize> {
+        let count = int_arg(&value, self.heap);
+        value.drop_with_heap(self.heap);
+        usize::try_from(count?).map_err(|_| SimpleException::new_msg(ExcType::ValueError, negative_error).into())
+    }
+
+    /// Returns the value of the only keyword argument `function` accepts, if given.
+    fn only_kwarg(&mut self, function: &str, param: &str, kwargs: KwargsValues) -> RunResult<Option<Value>> {
+        let mut found = None;
+        let mut error = None;
+        for (key, value) in kwargs {
+            let name = key
+                .as_either_str(self.heap)
+                .map(|name| name.as_str(self.interns).to_owned());
+            key.drop_with_heap(self.heap);
+            if error.is_some() {
+                value.drop_with_heap(self.heap);
+                continue;
+            }
+            match name {
+                Some(name) if name == param => {
+                    found.replace(value).drop_with_heap(self.heap);
+                }
+                Some(name) => {
+                    value.drop_with_heap(self.heap);
+                    error = Some(ExcType::type_error_unexpected_keyword(function, &name));
+                }
+                None => {
+                    value.drop_with_heap(self.heap);
+                    error = Some(ExcType::type_error("keywords must be strings"));
+                }
+            }
+        }
+        match error {
+            Some(error) => {
+                found.drop_with_heap(self.heap);
+                Err(error)
+            }
+            None => Ok(found),
+        }
+    }
+
+    /// Returns the values of an iterable, advancing it to the end.
+    fn collect_iterable(&mut self, iterable: Value) -> RunResult<Vec<Value>> {
+        let iterator = self.get_iter_id(iterable)?;
+        let mut values = Vec::new();
+        let result = loop {
+            match self.iter_next(iterator) {
+                Ok(Some(item)) => values.push(item),
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(e),
+            }
+        };
+        self.heap.dec_ref(iterator);
+        match result {
+            Ok(()) => Ok(values),
+            Err(e) => {
+                values.drop_with_heap(self.heap);
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Converts an argument which must be an int (or bool).
+fn int_arg(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<i64> {
+    match value {
+        Value::Bool(b) => Ok(i64::from(*b)),
+        _ => value.as_int(heap),
+    }
+}
+
+/// Parses the bounds of `islice()`, returning the start, stop and step.
+fn islice_bounds(
+    start: Option<&Value>,
+    stop: &Value,
+    step: Option<&Value>,
+) -> RunResult<(usize, Option<usize>, usize)> {
+    let stop = islice_arg(Some(stop), ISLICE_STOP_ERROR)?;
+    let start = islice_arg(start, ISLICE_START_ERROR)?.unwrap_or(0);
+    let step = match islice_arg(step, ISLICE_STEP_ERROR)? {
+        Some(0) => return Err(SimpleException::new_msg(ExcType::ValueError, ISLICE_STEP_ERROR).into()),
+        step => step.unwrap_or(1),
+    };
+    Ok((start, stop, step))
+}
+
+/// Parses an `islice()` bound, which must be `None` or a non-negative int.
+fn islice_arg(value: Option<&Value>, error: &'static str) -> RunResult<Option<usize>> {
+    let value = match value {
+        None | Some(Value::None) => return Ok(None),
+        Some(Value::Int(value)) => usize::try_from(*value).ok(),
+        Some(Value::Bool(value)) => Some(usize::from(*value)),
+        Some(_) => None,
+    };
+    value
+        .map(Some)
+        .ok_or_else(|| SimpleException::new_msg(ExcType::ValueError, error).into())
+}
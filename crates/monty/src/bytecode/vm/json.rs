@@ -0,0 +1,414 @@
+//! `json.dumps()`, run in the VM since it may call its `default` function for values
+//! JSON can't represent.
+
+use std::fmt::Write;
+
+use super::VM;
+use crate::{
+    args::{ArgValues, KwargsValues},
+    builtins::sort_values,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, HeapData, HeapId},
+    io::PrintWriter,
+    modules::json::{write_json_float, write_json_str},
+    resource::{LARGE_RESULT_THRESHOLD, ResourceTracker},
+    types::{PyTrait, str::allocate_string},
+    value::Value,
+};
+
+/// Options and state of a `json.dumps()` call.
+struct JsonEncoder {
+    /// Indent for each nesting level, `None` to write everything on one line.
+    indent: Option<String>,
+    item_separator: String,
+    key_separator: String,
+    sort_keys: bool,
+    ensure_ascii: bool,
+    default: Option<Value>,
+    /// Values being encoded: containers, and values passed to `default`. Their heap ids
+    /// detect circular references, and the length is checked against the recursion limit.
+    markers: Vec<Option<HeapId>>,
+    /// Number of arrays and objects being encoded, for indentation.
+    level: usize,
+    out: String,
+}
+
+impl JsonEncoder {
+    /// Writes a newline and the indent for the current level, if indenting.
+    fn write_newline_indent(&mut self) {
+        if let Some(indent) = &self.indent {
+            self.out.push('\n');
+            for _ in 0..self.level {
+                self.out.push_str(indent);
+            }
+        }
+    }
+}
+
+/// The items of a container being encoded, with their reference counts incremented.
+enum JsonContainer {
+    Array(Vec<Value>),
+    Object { keys: Vec<Value>, values: Vec<Value> },
+}
+
+impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
+    /// Implements `json.dumps(obj, *, indent=None, separators=None, sort_keys=False,
+    /// default=None, ensure_ascii=True)`.
+    pub(super) fn call_json_dumps(&mut self, args: ArgValues) -> RunResult<Value> {
+        let (mut positional, kwargs) = args.into_parts();
+        let positional_len = positional.len();
+        if positional_len != 1 {
+            positional.drop_with_heap(self.heap);
+            kwargs.drop_with_heap(self.heap);
+            return Err(if positional_len == 0 {
+                ExcType::type_error_missing_positional_with_names("dumps", &["obj"])
+            } else {
+                ExcType::type_error_too_many_positional("dumps", 1, positional_len, 0)
+            });
+        }
+        let obj = positional.next().expect("length checked above");
+
+        let mut encoder = match self.json_dumps_options(kwargs) {
+            Ok(encoder) => encoder,
+            Err(e) => {
+                obj.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+        let result = self.json_encode(&mut encoder, &obj);
+        obj.drop_with_heap(self.heap);
+        encoder.default.drop_with_heap(self.heap);
+        result?;
+        allocate_string(encoder.out, self.heap)
+    }
+
+    /// Parses the keyword arguments of `json.dumps()`.
+    fn json_dumps_options(&mut self, kwargs: KwargsValues) -> RunResult<JsonEncoder> {
+        let mut encoder = JsonEncoder {
+            indent: None,
+            item_separator: String::from(", "),
+            key_separator: String::from(": "),
+            sort_keys: false,
+            ensure_ascii: true,
+            default: None,
+            markers: Vec::new(),
+            level: 0,
+            out: String::new(),
+        };
+        let mut separators = None;
+        let mut error: Option<RunError> = None;
+
+        for (key, value) in kwargs {
+            // If we already hit an error, just drop remaining values
+            if error.is_some() {
+                key.drop_with_heap(self.heap);
+                value.drop_with_heap(self.heap);
+                continue;
+            }
+
+            let Some(keyword_name) = key.as_either_str(self.heap) else {
+                key.drop_with_heap(self.heap);
+                value.drop_with_heap(self.heap);
+                error = Some(ExcType::type_error("keywords must be strings"));
+                continue;
+            };
+
+            let key_str = keyword_name.as_str(self.interns);
+            match key_str {
+                "indent" => match self.json_indent(&value) {
+                    Ok(indent) => encoder.indent = indent,
+                    Err(e) => error = Some(e),
+                },
+                "separators" => match self.json_separators(&value) {
+                    Ok(seps) => separators = seps,
+                    Err(e) => error = Some(e),
+                },
+                "sort_keys" => encoder.sort_keys = value.py_bool(self.heap, self.interns),
+                "ensure_ascii" => encoder.ensure_ascii = value.py_bool(self.heap, self.interns),
+                "default" => {
+                    let default = (!matches!(value, Value::None)).then(|| value.clone_with_heap(self.heap));
+                    let old = std::mem::replace(&mut encoder.default, default);
+                    old.drop_with_heap(self.heap);
+                }
+                _ => error = Some(ExcType::type_error_unexpected_keyword("dumps", key_str)),
+            }
+            key.drop_with_heap(self.heap);
+            value.drop_with_heap(self.heap);
+        }
+
+        if let Some(error) = error {
+            encoder.default.drop_with_heap(self.heap);
+            return Err(error);
+        }
+        if let Some((item_separator, key_separator)) = separators {
+            encoder.item_separator = item_separator;
+            encoder.key_separator = key_separator;
+        } else if encoder.indent.is_some() {
+            // Items end at the line break, so trailing spaces are left out
+            encoder.item_separator = String::from(",");
+        }
+        Ok(encoder)
+    }
+
+    /// Converts the `indent` argument: a number of spaces or the indent string itself.
+    fn json_indent(&self, value: &Value) -> RunResult<Option<String>> {
+        match value {
+            Value::None => Ok(None),
+            Value::Bool(b) => Ok(Some(" ".repeat(usize::from(*b)))),
+            Value::Int(n) => {
+                let width = usize::try_from(*n).unwrap_or(0);
+                if width > LARGE_RESULT_THRESHOLD {
+                    self.heap.tracker().check_large_result(width)?;
+                }
+                Ok(Some(" ".repeat(width)))
+            }
+            _ => match value.as_either_str(self.heap) {
+                Some(s) => Ok(Some(s.as_str(self.interns).to_owned())),
+                None => Err(ExcType::type_error(format!(
+                    "indent must be an int, str or None, not {}",
+                    value.py_type(self.heap)
+                ))),
+            },
+        }
+    }
+
+    /// Converts the `separators` argument, an `(item_separator, key_separator)` pair.
+    fn json_separators(&self, value: &Value) -> RunResult<Option<(String, String)>> {
+        let items: &[Value] = match value {
+            Value::None => return Ok(None),
+            Value::Ref(id) => match self.heap.get(*id) {
+                HeapData::Tuple(tuple) => tuple.as_vec().as_slice(),
+                HeapData::List(list) => list.as_vec().as_slice(),
+                _ => &[],
+            },
+            _ => &[],
+        };
+        let mut strs = items.iter().map(|item| item.as_either_str(self.heap));
+        match (strs.next(), strs.next(), strs.next()) {
+            (Some(Some(item_separator)), Some(Some(key_separator)), None) => Ok(Some((
+                item_separator.as_str(self.interns).to_owned(),
+                key_separator.as_str(self.interns).to_owned(),
+            ))),
+            _ => Err(ExcType::type_error(
+                "separators must be a pair of strings (item_separator, key_separator)",
+            )),
+        }
+    }
+
+    /// Writes `value` as JSON to the encoder's output.
+    fn json_encode(&mut self, encoder: &mut JsonEncoder, value: &Value) -> RunResult<()> {
+        // The output is only allocated on the heap once it's complete, so check its size as it grows
+        if encoder.out.len() > LARGE_RESULT_THRESHOLD {
+            self.heap.tracker().check_large_result(encoder.out.len())?;
+        }
+        // Container items are copied without incrementing their reference counts while the heap
+        // is borrowed; the counts are incremented below, since `default` may modify the container
+        let (heap_id, container) = match value {
+            Value::None => {
+                encoder.out.push_str("null");
+                return Ok(());
+            }
+            Value::Bool(b) => {
+                encoder.out.push_str(if *b { "true" } else { "false" });
+                return Ok(());
+            }
+            Value::Int(i) => {
+                write!(encoder.out, "{i}").expect("writing to a String can't fail");
+                return Ok(());
+            }
+            Value::InternLongInt(long_int_id) => {
+                let long_int = self.interns.get_long_int(*long_int_id);
+                write!(encoder.out, "{long_int}").expect("writing to a String can't fail");
+                return Ok(());
+            }
+            Value::Float(f) => {
+                write_json_float(&mut encoder.out, *f, self.heap, self.interns);
+                return Ok(());
+            }
+            Value::InternString(string_id) => {
+                write_json_str(&mut encoder.out, self.interns.get_str(*string_id), encoder.ensure_ascii);
+                return Ok(());
+            }
+            Value::Ref(heap_id) => match self.heap.get(*heap_id) {
+                HeapData::Str(s) => {
+                    write_json_str(&mut encoder.out, s.as_str(), encoder.ensure_ascii);
+                    return Ok(());
+                }
+                HeapData::LongInt(long_int) => {
+                    write!(encoder.out, "{long_int}").expect("writing to a String can't fail");
+                    return Ok(());
+                }
+                HeapData::List(list) => (*heap_id, JsonContainer::Array(copy_values(list.as_vec()))),
+                HeapData::Tuple(tuple) => (*heap_id, JsonContainer::Array(copy_values(tuple.as_vec()))),
+                HeapData::NamedTuple(namedtuple) => (*heap_id, JsonContainer::Array(copy_values(namedtuple.as_vec()))),
+                HeapData::Dict(dict) => (
+                    *heap_id,
+                    JsonContainer::Object {
+                        keys: dict.iter().map(|(key, _)| key.copy_for_extend()).collect(),
+                        values: dict.iter().map(|(_, value)| value.copy_for_extend()).collect(),
+                    },
+                ),
+                _ => return self.json_encode_default(encoder, value),
+            },
+            _ => return self.json_encode_default(encoder, value),
+        };
+        match container {
+            JsonContainer::Array(items) => {
+                self.inc_ref_all(&items);
+                let result = self.json_encode_array(encoder, heap_id, &items);
+                items.drop_with_heap(self.heap);
+                result
+            }
+            JsonContainer::Object { keys, values } => {
+                self.inc_ref_all(&keys);
+                self.inc_ref_all(&values);
+                let result = self.json_encode_object(encoder, heap_id, &keys, &values);
+                keys.drop_with_heap(self.heap);
+                values.drop_with_heap(self.heap);
+                result
+            }
+        }
+    }
+
+    fn inc_ref_all(&mut self, values: &[Value]) {
+        for value in values {
+            if let Value::Ref(id) = value {
+                self.heap.inc_ref(*id);
+            }
+        }
+    }
+
+    /// Records that `heap_id` is being encoded, checking for circular references and the
+    /// recursion limit.
+    fn json_enter(&self, encoder: &mut JsonEncoder, heap_id: Option<HeapId>) -> RunResult<()> {
+        if heap_id.is_some() && encoder.markers.contains(&heap_id) {
+            return Err(SimpleException::new_msg(ExcType::ValueError, "Circular reference detected").into());
+        }
+        self.heap.tracker().check_recursion_depth(encoder.markers.len())?;
+        encoder.markers.push(heap_id);
+        Ok(())
+    }
+
+    fn json_encode_array(&mut self, encoder: &mut JsonEncoder, heap_id: HeapId, items: &[Value]) -> RunResult<()> {
+        if items.is_empty() {
+            encoder.out.push_str("[]");
+            return Ok(());
+        }
+        self.json_enter(encoder, Some(heap_id))?;
+        encoder.out.push('[');
+        encoder.level += 1;
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                encoder.out.push_str(&encoder.item_separator);
+            }
+            encoder.write_newline_indent();
+            self.json_encode(encoder, item)?;
+        }
+        encoder.level -= 1;
+        encoder.write_newline_indent();
+        encoder.out.push(']');
+        encoder.markers.pop();
+        Ok(())
+    }
+
+    fn json_encode_object(
+        &mut self,
+        encoder: &mut JsonEncoder,
+        heap_id: HeapId,
+        keys: &[Value],
+        values: &[Value],
+    ) -> RunResult<()> {
+        if keys.is_empty() {
+            encoder.out.push_str("{}");
+            return Ok(());
+        }
+        self.json_enter(encoder, Some(heap_id))?;
+
+        // Sort indices of the items rather than the items, so keys and values stay together
+        let mut order: Vec<Value> = (0..keys.len())
+            .map(|i| Value::Int(i64::try_from(i).expect("dict length fits in i64")))
+            .collect();
+        if encoder.sort_keys {
+            sort_values(&mut order, Some(keys), false, self.heap, self.interns)?;
+        }
+
+        encoder.out.push('{');
+        encoder.level += 1;
+        for (i, index) in order.iter().enumerate() {
+            let Value::Int(index) = index else {
+                unreachable!("order only holds indices")
+            };
+            let index = usize::try_from(*index).expect("index is in range");
+            if i > 0 {
+                encoder.out.push_str(&encoder.item_separator);
+            }
+            encoder.write_newline_indent();
+            self.json_encode_key(encoder, &keys[index])?;
+            encoder.out.push_str(&encoder.key_separator);
+            self.json_encode(encoder, &values[index])?;
+        }
+        encoder.level -= 1;
+        encoder.write_newline_indent();
+        encoder.out.push('}');
+        encoder.markers.pop();
+        Ok(())
+    }
+
+    /// Writes a dict key, converting keys which are numbers, booleans or `None` to strings.
+    fn json_encode_key(&self, encoder: &mut JsonEncoder, key: &Value) -> RunResult<()> {
+        let key_str = match key {
+            Value::None => String::from("null"),
+            Value::Bool(b) => String::from(if *b { "true" } else { "false" }),
+            Value::Int(i) => i.to_string(),
+            Value::InternLongInt(long_int_id) => self.interns.get_long_int(*long_int_id).to_string(),
+            Value::Float(f) => {
+                let mut s = String::new();
+                write_json_float(&mut s, *f, self.heap, self.interns);
+                s
+            }
+            Value::Ref(heap_id) if matches!(self.heap.get(*heap_id), HeapData::LongInt(_)) => {
+                key.py_str(self.heap, self.interns).into_owned()
+            }
+            _ => match key.as_either_str(self.heap) {
+                Some(s) => s.as_str(self.interns).to_owned(),
+                None => {
+                    return Err(ExcType::type_error(format!(
+                        "keys must be str, int, float, bool or None, not {}",
+                        self.type_name(key)
+                    )));
+                }
+            },
+        };
+        write_json_str(&mut encoder.out, &key_str, encoder.ensure_ascii);
+        Ok(())
+    }
+
+    /// Encodes a value JSON can't represent by calling `default` and encoding its result.
+    fn json_encode_default(&mut self, encoder: &mut JsonEncoder, value: &Value) -> RunResult<()> {
+        let Some(default) = &encoder.default else {
+            return Err(ExcType::type_error(format!(
+                "Object of type {} is not JSON serializable",
+                self.type_name(value)
+            )));
+        };
+        let heap_id = if let Value::Ref(heap_id) = value {
+            Some(*heap_id)
+        } else {
+            None
+        };
+        self.json_enter(encoder, heap_id)?;
+
+        let default = default.clone_with_heap(self.heap);
+        let arg = value.clone_with_heap(self.heap);
+        let replacement = self.call_sync(default, ArgValues::One(arg))?;
+        let result = self.json_encode(encoder, &replacement);
+        replacement.drop_with_heap(self.heap);
+        encoder.markers.pop();
+        result
+    }
+}
+
+/// Copies values without incrementing their reference counts.
+fn copy_values(values: &[Value]) -> Vec<Value> {
+    values.iter().map(Value::copy_for_extend).collect()
+}
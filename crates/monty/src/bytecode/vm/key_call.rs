@@ -0,0 +1,600 @@
+//! Builtins which order values by a key function: `sorted()`, `min()`, `max()` and `list.sort()`.
+//!
+//! All four call the key function on each item through `call_key`. Key functions
+//! defined in Python run in a nested run loop, like special methods, so they can't call
+//! the host themselves. Key functions run by the host (external functions, OS calls and
+//! methods of host objects) suspend the VM instead: the call in progress is kept in
+//! `VM::key_call`, and carries on with the next item when the VM is resumed with the key.
+//!
+//! `sorted()` and `list.sort()` compute the keys of all the items before ordering them.
+//! `min()` and `max()` advance their iterable one item at a time, keeping only the best
+//! item and its key, so they work on long and lazy iterables like generators; while the
+//! host computes a key, the item it belongs to is kept too.
+//!
+//! Keys from `functools.cmp_to_key()` are handled separately: the items are ordered by
+//! calling the comparison function on pairs of items, each call run in a nested run loop.
+//! Items (or keys) holding instances of user-defined classes are ordered the same way,
+//! calling `__lt__` or `__gt__` on them.
+
+use std::cmp::Ordering;
+
+use super::{FrameExit, VM, call::CallResult, class::sync_call_suspend_error, iterator::IterableArgs};
+use crate::{
+    args::ArgValues,
+    builtins::{BuiltinsFunctions, min_max_args, min_max_beats, min_max_empty, sort_values, sorted_args},
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    expressions::CmpOperator,
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    io::PrintWriter,
+    resource::ResourceTracker,
+    types::{List, PyTrait},
+    value::Value,
+};
+
+/// A key-ordered call waiting for the host to compute a key.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) enum KeyCall {
+    Sort(SortCall),
+    MinMax(MinMaxCall),
+}
+
+/// What a sort does with its items once the keys are computed.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+enum SortKind {
+    /// `sorted()`, returning a new list.
+    Sorted { reverse: bool },
+    /// `list.sort()`, putting the items back into the list, which is empty while sorting.
+    ListSort { list: Value, reverse: bool },
+}
+
+impl SortKind {
+    /// Returns whether the sort is in descending order.
+    fn reverse(&self) -> bool {
+        match self {
+            Self::Sorted { reverse } | Self::ListSort { reverse, .. } => *reverse,
+        }
+    }
+}
+
+/// How a sort compares values when it needs the VM to.
+enum VmOrdering<'a> {
+    /// With the comparison function given to `functools.cmp_to_key()`.
+    Cmp(&'a Value),
+    /// With `__lt__`, for values holding instances of user-defined classes.
+    Dunders,
+}
+
+/// A call of `sorted()` or `list.sort()` with a key function, part way through computing
+/// the keys.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct SortCall {
+    kind: SortKind,
+    key_fn: Value,
+    items: Vec<Value>,
+    /// Keys of the first `keys.len()` items.
+    keys: Vec<Value>,
+}
+
+/// How `min()` or `max()` compares its items.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+enum MinMaxKey {
+    /// By the items themselves.
+    Items,
+    /// By the keys the key function returns for them.
+    KeyFn(Value),
+    /// With the comparison function given to `functools.cmp_to_key()`.
+    Cmp(Value),
+}
+
+/// A call of `min()` or `max()`, part way through its items.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct MinMaxCall {
+    is_min: bool,
+    /// The iterator over the items, which the call holds a reference to.
+    iter_id: HeapId,
+    key: MinMaxKey,
+    /// The value to return if there are no items.
+    default: Option<Value>,
+    /// The best item so far.
+    best: Option<Value>,
+    /// The key of the best item, if there's a key function.
+    best_key: Option<Value>,
+    /// The item whose key the host is computing.
+    pending: Option<Value>,
+}
+
+impl<T: ResourceTracker> DropWithHeap<T> for MinMaxCall {
+    fn drop_with_heap(self, heap: &mut Heap<T>) {
+        heap.dec_ref(self.iter_id);
+        match self.key {
+            MinMaxKey::Items => {}
+            MinMaxKey::KeyFn(function) | MinMaxKey::Cmp(function) => function.drop_with_heap(heap),
+        }
+        self.default.drop_with_heap(heap);
+        self.best.drop_with_heap(heap);
+        self.best_key.drop_with_heap(heap);
+        self.pending.drop_with_heap(heap);
+    }
+}
+
+impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
+    /// Calls `sorted()`, `min()` or `max()`, which may be given a key function.
+    pub(super) fn call_key_builtin(&mut self, builtin: BuiltinsFunctions, args: ArgValues) -> RunResult<CallResult> {
+        if !matches!(builtin, BuiltinsFunctions::Sorted) {
+            return self.call_min_max(args, matches!(builtin, BuiltinsFunctions::Min));
+        }
+        let args = if self.has_vm_iterator_arg(&args) {
+            self.materialize_vm_iterator_args(args, IterableArgs::All)?
+        } else {
+            args
+        };
+        let (items, key_fn, reverse) = sorted_args(self.heap, args, self.interns)?;
+        self.start_sort(SortKind::Sorted { reverse }, key_fn, items)
+    }
+
+    /// Implements `list.sort(*, key=None, reverse=False)`.
+    ///
+    /// Takes ownership of `list`, the list being sorted.
+    pub(super) fn call_list_sort(&mut self, list: Value, args: ArgValues) -> RunResult<CallResult> {
+        let (key_fn, reverse) =
+            match args.extract_two_kwargs_only("list.sort", "key", "reverse", self.heap, self.interns) {
+                Ok(kwargs) => kwargs,
+                Err(e) => {
+                    list.drop_with_heap(self.heap);
+                    return Err(e);
+                }
+            };
+        let reverse = reverse.is_some_and(|reverse| {
+            let flag = reverse.py_bool(self.heap, self.interns);
+            reverse.drop_with_heap(self.heap);
+            flag
+        });
+        let key_fn = key_fn.filter(|key_fn| !matches!(key_fn, Value::None));
+
+        // The list is empty while it is sorted, like in CPython
+        let &Value::Ref(list_id) = &list else {
+            unreachable!("list.sort() called on a non-list")
+        };
+        let HeapData::List(l) = self.heap.get_mut(list_id) else {
+            unreachable!("list.sort() called on a non-list")
+        };
+        let items: Vec<Value> = l.as_vec_mut().drain(..).collect();
+        self.start_sort(SortKind::ListSort { list, reverse }, key_fn, items)
+    }
+
+    /// Continues a key-ordered call which suspended the VM, with the key returned by the host.
+    pub(super) fn resume_key_call(&mut self, call: KeyCall, key: Value) -> Result<FrameExit, RunError> {
+        let result = match call {
+            KeyCall::Sort(mut call) => {
+                call.keys.push(key);
+                self.run_sort(call)
+            }
+            KeyCall::MinMax(mut call) => {
+                let item = call.pending.take().expect("min() or max() suspended without an item");
+                match self.offer_min_max(&mut call, item, Some(key)) {
+                    Ok(()) => self.run_min_max(call),
+                    Err(e) => {
+                        call.drop_with_heap(self.heap);
+                        Err(e)
+                    }
+                }
+            }
+        };
+        match result {
+            Ok(CallResult::Push(value)) => {
+                self.push(value);
+                self.run()
+            }
+            Ok(CallResult::External(ext_function_id, args)) => Ok(FrameExit::ExternalCall {
+                ext_function_id,
+                args,
+                call_id: self.allocate_call_id(),
+            }),
+            Ok(CallResult::MethodCall(method_name, args)) => Ok(FrameExit::MethodCall {
+                method_name,
+                args,
+                call_id: self.allocate_call_id(),
+            }),
+            Ok(CallResult::OsCall(function, args)) => Ok(FrameExit::OsCall {
+                function,
+                args,
+                call_id: self.allocate_call_id(),
+            }),
+            Ok(CallResult::FramePushed) => unreachable!("key functions run to completion in a nested run loop"),
+            Err(e) => self.resume_with_exception(e),
+        }
+    }
+
+    /// Releases a key-ordered call which won't finish, restoring the list for `list.sort()`.
+    pub(super) fn abandon_key_call(&mut self, call: KeyCall) {
+        match call {
+            KeyCall::Sort(call) => self.abandon_sort(call),
+            KeyCall::MinMax(call) => call.drop_with_heap(self.heap),
+        }
+    }
+
+    /// Calls the key function `key_fn` on `item`.
+    ///
+    /// Returns `CallResult::Push` with the key, or the host call to suspend the VM with if
+    /// the key function has to be run by the host.
+    fn call_key(&mut self, key_fn: Value, item: Value) -> RunResult<CallResult> {
+        let stack_len = self.stack.len();
+        let base = self.frames.len();
+        match self.call_function(key_fn, ArgValues::One(item))? {
+            CallResult::FramePushed => self
+                .run_nested(base, stack_len, None)?
+                .ok_or_else(key_call_suspend_error)
+                .map(CallResult::Push),
+            // The VM can't suspend from inside a nested run loop
+            CallResult::External(_, args) | CallResult::MethodCall(_, args) | CallResult::OsCall(_, args)
+                if !self.sync_call_bases.is_empty() =>
+            {
+                args.drop_with_heap(self.heap);
+                self.abandon_os_request();
+                Err(sync_call_suspend_error())
+            }
+            result => Ok(result),
+        }
+    }
+
+    /// Returns the comparison function given to `functools.cmp_to_key()` if `key_fn` is the
+    /// key it returned. The returned value is owned.
+    fn cmp_to_key_function(&mut self, key_fn: &Value) -> Option<Value> {
+        let Value::Ref(key_id) = key_fn else {
+            return None;
+        };
+        let HeapData::CmpToKey(key) = self.heap.get(*key_id) else {
+            return None;
+        };
+        let cmp = key.cmp().copy_for_extend();
+        if let Value::Ref(id) = cmp {
+            self.heap.inc_ref(id);
+        }
+        Some(cmp)
+    }
+
+    /// Implements `min()` (if `is_min`) or `max()`, which may be given a key function.
+    fn call_min_max(&mut self, args: ArgValues, is_min: bool) -> RunResult<CallResult> {
+        let (iterable, key_fn, default) = min_max_args(self.heap, args, self.interns, is_min)?;
+        let iter_id = match self.get_iter_id(iterable) {
+            Ok(iter_id) => iter_id,
+            Err(e) => {
+                key_fn.drop_with_heap(self.heap);
+                default.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+        let key = match key_fn {
+            Some(key_fn) => match self.cmp_to_key_function(&key_fn) {
+                Some(cmp) => {
+                    key_fn.drop_with_heap(self.heap);
+                    MinMaxKey::Cmp(cmp)
+                }
+                None => MinMaxKey::KeyFn(key_fn),
+            },
+            None => MinMaxKey::Items,
+        };
+        self.run_min_max(MinMaxCall {
+            is_min,
+            iter_id,
+            key,
+            default,
+            best: None,
+            best_key: None,
+            pending: None,
+        })
+    }
+
+    /// Advances the iterator of a `min()` or `max()` call to the end, then returns the best item.
+    ///
+    /// If the key function has to be run by the host, the call is kept in `self.key_call`
+    /// with the item, and the host call is returned, for the VM to suspend with.
+    fn run_min_max(&mut self, mut call: MinMaxCall) -> RunResult<CallResult> {
+        loop {
+            let item = match self.iter_next(call.iter_id) {
+                Ok(Some(item)) => item,
+                Ok(None) => break,
+                Err(e) => {
+                    call.drop_with_heap(self.heap);
+                    return Err(e);
+                }
+            };
+            let key = if let MinMaxKey::KeyFn(key_fn) = &call.key {
+                let key_fn = key_fn.clone_with_heap(self.heap);
+                let arg = item.clone_with_heap(self.heap);
+                match self.call_key(key_fn, arg) {
+                    Ok(CallResult::Push(key)) => Some(key),
+                    Ok(host_call) => {
+                        call.pending = Some(item);
+                        self.key_call = Some(KeyCall::MinMax(call));
+                        return Ok(host_call);
+                    }
+                    Err(e) => {
+                        item.drop_with_heap(self.heap);
+                        call.drop_with_heap(self.heap);
+                        return Err(e);
+                    }
+                }
+            } else {
+                None
+            };
+            if let Err(e) = self.offer_min_max(&mut call, item, key) {
+                call.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        }
+
+        let is_min = call.is_min;
+        let best = call.best.take();
+        let default = call.default.take();
+        call.drop_with_heap(self.heap);
+        match best {
+            Some(best) => {
+                default.drop_with_heap(self.heap);
+                Ok(CallResult::Push(best))
+            }
+            None => min_max_empty(default, is_min).map(CallResult::Push),
+        }
+    }
+
+    /// Keeps `item` (with its `key`, if there's a key function) as the best item of a
+    /// `min()` or `max()` call if it beats the best so far, and releases the other.
+    fn offer_min_max(&mut self, call: &mut MinMaxCall, item: Value, key: Option<Value>) -> RunResult<()> {
+        let Some(best) = &call.best else {
+            call.best = Some(item);
+            call.best_key = key;
+            return Ok(());
+        };
+        let (compared, best_compared) = match (&key, &call.best_key) {
+            (Some(key), Some(best_key)) => (key, best_key),
+            _ => (&item, best),
+        };
+        match self.vm_min_max_beats(&call.key, compared, best_compared, call.is_min) {
+            Ok(true) => {
+                std::mem::replace(&mut call.best, Some(item)).drop_with_heap(self.heap);
+                std::mem::replace(&mut call.best_key, key).drop_with_heap(self.heap);
+                Ok(())
+            }
+            Ok(false) => {
+                item.drop_with_heap(self.heap);
+                key.drop_with_heap(self.heap);
+                Ok(())
+            }
+            Err(e) => {
+                item.drop_with_heap(self.heap);
+                key.drop_with_heap(self.heap);
+                Err(e)
+            }
+        }
+    }
+
+    /// Returns whether `item` replaces `best` as the result of `min()` or `max()`.
+    ///
+    /// Like CPython, `min()` asks whether `item < best` and `max()` whether `item > best`,
+    /// so the first of equal items wins. Values holding instances of user-defined classes
+    /// are compared with their special methods.
+    fn vm_min_max_beats(&mut self, key: &MinMaxKey, item: &Value, best: &Value, is_min: bool) -> RunResult<bool> {
+        if let MinMaxKey::Cmp(cmp) = key {
+            let wanted = if is_min { Ordering::Less } else { Ordering::Greater };
+            Ok(self.call_cmp(cmp, item, best)? == wanted)
+        } else if self.contains_instance(item) || self.contains_instance(best) {
+            let op = if is_min { CmpOperator::Lt } else { CmpOperator::Gt };
+            self.ord_values(item, best, &op)
+        } else {
+            min_max_beats(item, best, self.heap, self.interns, is_min)
+        }
+    }
+
+    /// Starts computing keys for a sort, or finishes it right away without a key function.
+    fn start_sort(&mut self, kind: SortKind, key_fn: Option<Value>, items: Vec<Value>) -> RunResult<CallResult> {
+        if let Some(cmp) = key_fn.as_ref().and_then(|key_fn| self.cmp_to_key_function(key_fn)) {
+            key_fn.drop_with_heap(self.heap);
+            let result = self.finish_vm_sort(kind, &VmOrdering::Cmp(&cmp), items, None);
+            cmp.drop_with_heap(self.heap);
+            return result.map(CallResult::Push);
+        }
+        match key_fn {
+            Some(key_fn) => {
+                let keys = Vec::with_capacity(items.len());
+                self.run_sort(SortCall {
+                    kind,
+                    key_fn,
+                    items,
+                    keys,
+                })
+            }
+            None => self.finish_sort(kind, items, None).map(CallResult::Push),
+        }
+    }
+
+    /// Calls the key function on each item still without a key, then finishes the sort.
+    ///
+    /// If the key function has to be run by the host, the call is kept in `self.key_call`
+    /// and the host call is returned, for the VM to suspend with.
+    fn run_sort(&mut self, mut call: SortCall) -> RunResult<CallResult> {
+        while call.keys.len() < call.items.len() {
+            let item = call.items[call.keys.len()].clone_with_heap(self.heap);
+            let key_fn = call.key_fn.clone_with_heap(self.heap);
+            match self.call_key(key_fn, item) {
+                Ok(CallResult::Push(key)) => call.keys.push(key),
+                Ok(host_call) => {
+                    self.key_call = Some(KeyCall::Sort(call));
+                    return Ok(host_call);
+                }
+                Err(e) => {
+                    self.abandon_sort(call);
+                    return Err(e);
+                }
+            }
+        }
+
+        let SortCall {
+            kind,
+            key_fn,
+            items,
+            keys,
+        } = call;
+        key_fn.drop_with_heap(self.heap);
+        self.finish_sort(kind, items, Some(keys)).map(CallResult::Push)
+    }
+
+    /// Releases a sort which won't finish, restoring the list for `list.sort()`.
+    fn abandon_sort(&mut self, call: SortCall) {
+        let SortCall {
+            kind,
+            key_fn,
+            items,
+            keys,
+        } = call;
+        key_fn.drop_with_heap(self.heap);
+        keys.drop_with_heap(self.heap);
+        match kind {
+            SortKind::Sorted { .. } => items.drop_with_heap(self.heap),
+            SortKind::ListSort { list, .. } => self.restore_list(list, items),
+        }
+    }
+
+    /// Orders the items of a sort, comparing `keys` instead of the items if given.
+    ///
+    /// Values holding instances of user-defined classes are compared with their special
+    /// methods, which run in the VM; other values are compared on the heap.
+    fn finish_sort(&mut self, kind: SortKind, mut items: Vec<Value>, keys: Option<Vec<Value>>) -> RunResult<Value> {
+        let compared = keys.as_deref().unwrap_or(items.as_slice());
+        if compared.iter().any(|value| self.contains_instance(value)) {
+            let result = self.finish_vm_sort(kind, &VmOrdering::Dunders, items, keys.as_deref());
+            keys.drop_with_heap(self.heap);
+            return result;
+        }
+        let result = sort_values(&mut items, keys.as_deref(), kind.reverse(), self.heap, self.interns);
+        keys.drop_with_heap(self.heap);
+        self.finish_sorted_items(kind, items, result)
+    }
+
+    /// Orders the items of a sort by comparisons run in the VM, comparing `keys` instead of
+    /// the items if given.
+    fn finish_vm_sort(
+        &mut self,
+        kind: SortKind,
+        ordering: &VmOrdering<'_>,
+        mut items: Vec<Value>,
+        keys: Option<&[Value]>,
+    ) -> RunResult<Value> {
+        let result = self.vm_sort(ordering, &mut items, keys, kind.reverse());
+        self.finish_sorted_items(kind, items, result)
+    }
+
+    /// Returns the result of a sort once its items are in order, or after ordering them
+    /// failed with `result`: a new list for `sorted()`, or `None` for `list.sort()` after
+    /// putting the items back into the list.
+    fn finish_sorted_items(&mut self, kind: SortKind, items: Vec<Value>, result: RunResult<()>) -> RunResult<Value> {
+        match kind {
+            SortKind::Sorted { .. } => {
+                if let Err(e) = result {
+                    items.drop_with_heap(self.heap);
+                    return Err(e);
+                }
+                let heap_id = self.heap.allocate(HeapData::List(List::new(items)))?;
+                Ok(Value::Ref(heap_id))
+            }
+            SortKind::ListSort { list, .. } => {
+                self.restore_list(list, items);
+                result.map(|()| Value::None)
+            }
+        }
+    }
+
+    /// Sorts `items` with comparisons run in the VM, comparing `keys` instead of the items if
+    /// given, by binary insertion so the sort is stable.
+    ///
+    /// On error, `items` is left unchanged.
+    fn vm_sort(
+        &mut self,
+        ordering: &VmOrdering<'_>,
+        items: &mut Vec<Value>,
+        keys: Option<&[Value]>,
+        reverse: bool,
+    ) -> RunResult<()> {
+        let compared = keys.unwrap_or(items.as_slice());
+        let mut order: Vec<usize> = Vec::with_capacity(compared.len());
+        for i in 0..compared.len() {
+            let (mut low, mut high) = (0, order.len());
+            while low < high {
+                let mid = low + (high - low) / 2;
+                if self.vm_sorts_before(ordering, &compared[i], &compared[order[mid]], reverse)? {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+            }
+            order.insert(low, i);
+        }
+
+        let mut unsorted: Vec<Option<Value>> = std::mem::take(items).into_iter().map(Some).collect();
+        items.extend(order.into_iter().map(|i| unsorted[i].take().expect("index used once")));
+        Ok(())
+    }
+
+    /// Returns whether `a` must be sorted before `b`, which was given earlier.
+    ///
+    /// Like CPython, sorting calls `__lt__` only, with the operands swapped when sorting in
+    /// descending order.
+    fn vm_sorts_before(&mut self, ordering: &VmOrdering<'_>, a: &Value, b: &Value, reverse: bool) -> RunResult<bool> {
+        match ordering {
+            VmOrdering::Cmp(cmp) => {
+                let before = if reverse { Ordering::Greater } else { Ordering::Less };
+                Ok(self.call_cmp(cmp, a, b)? == before)
+            }
+            VmOrdering::Dunders if reverse => self.ord_values(b, a, &CmpOperator::Lt),
+            VmOrdering::Dunders => self.ord_values(a, b, &CmpOperator::Lt),
+        }
+    }
+
+    /// Calls the comparison function `cmp` on `a` and `b`, returning how `a` orders
+    /// relative to `b` by comparing the result with zero.
+    fn call_cmp(&mut self, cmp: &Value, a: &Value, b: &Value) -> RunResult<Ordering> {
+        let cmp = cmp.clone_with_heap(self.heap);
+        let args = ArgValues::Two(a.clone_with_heap(self.heap), b.clone_with_heap(self.heap));
+        let result = self.call_sync_or(cmp, args, cmp_call_suspend_error)?;
+        let ordering = result.py_cmp(&Value::Int(0), self.heap, self.interns);
+        let result_type = result.py_type(self.heap);
+        result.drop_with_heap(self.heap);
+        ordering.ok_or_else(|| {
+            ExcType::type_error(format!(
+                "'<' not supported between instances of '{result_type}' and 'int'"
+            ))
+        })
+    }
+
+    /// Puts the items of a list being sorted back into it, releasing the reference to the list.
+    fn restore_list(&mut self, list: Value, items: Vec<Value>) {
+        let &Value::Ref(list_id) = &list else {
+            unreachable!("list.sort() called on a non-list")
+        };
+        let HeapData::List(l) = self.heap.get_mut(list_id) else {
+            unreachable!("list.sort() called on a non-list")
+        };
+        l.as_vec_mut().extend(items);
+        list.drop_with_heap(self.heap);
+    }
+}
+
+/// Error raised when a key function defined in Python tries to suspend execution for the host.
+///
+/// Passing the external function itself as `key=` works, as the builtin suspends for it.
+fn key_call_suspend_error() -> RunError {
+    SimpleException::new_msg(
+        ExcType::RuntimeError,
+        "external functions and OS calls are not supported inside key functions defined in Python",
+    )
+    .into()
+}
+
+/// Error raised when the comparison function of `functools.cmp_to_key()` is, or calls, a
+/// function run by the host.
+fn cmp_call_suspend_error() -> RunError {
+    SimpleException::new_msg(
+        ExcType::RuntimeError,
+        "external functions and OS calls are not supported in cmp_to_key() comparison functions",
+    )
+    .into()
+}
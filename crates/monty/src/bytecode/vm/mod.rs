@@ -7,15 +7,27 @@ mod async_exec;
 mod attr;
 mod binary;
 mod call;
+mod class;
 mod collections;
 mod compare;
 mod exceptions;
 mod format;
+mod functools;
+mod generator;
+mod hash;
+mod iterator;
+mod itertools;
+mod json;
+mod key_call;
+mod pattern;
+mod random;
+mod re;
 mod scheduler;
-
-use std::cmp::Ordering;
+mod textwrap;
 
 use call::CallResult;
+use generator::{GeneratorFrame, GeneratorResume};
+use key_call::KeyCall;
 use scheduler::Scheduler;
 
 use crate::{
@@ -24,6 +36,7 @@ use crate::{
     asyncio::{CallId, TaskId},
     bytecode::{code::Code, op::Opcode},
     exception_private::{ExcType, RunError, RunResult, SimpleException},
+    expressions::CmpOperator,
     heap::{ContainsHeap, Heap, HeapData, HeapId},
     intern::{ExtFunctionId, FunctionId, Interns, StringId},
     io::PrintWriter,
@@ -32,7 +45,7 @@ use crate::{
     os::OsFunction,
     parse::CodeRange,
     resource::ResourceTracker,
-    types::{LongInt, MontyIter, PyTrait, iter::advance_on_heap},
+    types::{LongInt, PyTrait, datetime::NowRequest, decimal, iter::advance_on_heap, random::SeedRequest, uuid},
     value::{BitwiseOp, Value},
 };
 
@@ -160,6 +173,7 @@ macro_rules! jump_relative {
 /// - `Push(value)`: Push the value onto the stack
 /// - `FramePushed`: Reload the cached frame (a new frame was pushed)
 /// - `External(ext_id, args)`: Return `FrameExit::ExternalCall` to yield to host
+/// - `MethodCall(name_id, args)`: Return `FrameExit::MethodCall` to yield to host
 /// - `OsCall(func, args)`: Return `FrameExit::OsCall` to yield to host
 /// - `Err(err)`: Handle the exception via `catch_sync!`
 macro_rules! handle_call_result {
@@ -177,6 +191,16 @@ macro_rules! handle_call_result {
                     call_id,
                 });
             }
+            Ok(CallResult::MethodCall(method_name, args)) => {
+                let call_id = $self.allocate_call_id();
+                // Sync cached IP back to frame before snapshot for resume
+                $self.current_frame_mut().ip = $cached_frame.ip;
+                return Ok(FrameExit::MethodCall {
+                    method_name,
+                    args,
+                    call_id,
+                });
+            }
             Ok(CallResult::OsCall(func, args)) => {
                 let call_id = $self.allocate_call_id();
                 // Sync cached IP back to frame before snapshot for resume
@@ -211,6 +235,19 @@ pub enum FrameExit {
         call_id: CallId,
     },
 
+    /// Execution paused for a call of a method implemented by the host.
+    ///
+    /// Like `ExternalCall`, but the function is a method of a host object (a dataclass
+    /// listing the method name), and the object is the first argument.
+    MethodCall {
+        /// Name of the method to call.
+        method_name: StringId,
+        /// Arguments for the method, starting with the object it is called on.
+        args: ArgValues,
+        /// Unique ID for this call, used for async correlation.
+        call_id: CallId,
+    },
+
     /// Execution paused for an os function call.
     ///
     /// The caller should execute a function corresponding to the `os_call` and call `resume()`
@@ -250,6 +287,11 @@ pub struct CallFrame<'code> {
     /// Used to identify where this frame's stack region begins.
     stack_base: usize,
 
+    /// Length of the exception stack when this frame was entered.
+    ///
+    /// Exceptions above it are being handled by this frame.
+    exception_base: usize,
+
     /// Namespace index for this frame's locals.
     namespace_idx: NamespaceId,
 
@@ -261,6 +303,18 @@ pub struct CallFrame<'code> {
 
     /// Call site position (for tracebacks).
     call_position: Option<CodeRange>,
+
+    /// Whether this frame runs `__init__` for a class instantiation.
+    ///
+    /// On return, the frame's `None` result is replaced by the new instance,
+    /// which was pushed just below the frame's stack region.
+    is_init: bool,
+
+    /// Set if this frame runs a generator's body.
+    ///
+    /// Yielding moves the frame's state into the generator; returning or raising
+    /// completes the generator.
+    generator: Option<GeneratorFrame>,
 }
 
 impl<'code> CallFrame<'code> {
@@ -270,10 +324,13 @@ impl<'code> CallFrame<'code> {
             code,
             ip: 0,
             stack_base: 0,
+            exception_base: 0,
             namespace_idx,
             function_id: None,
             cells: Vec::new(),
             call_position: None,
+            is_init: false,
+            generator: None,
         }
     }
 
@@ -281,6 +338,7 @@ impl<'code> CallFrame<'code> {
     pub fn new_function(
         code: &'code Code,
         stack_base: usize,
+        exception_base: usize,
         namespace_idx: NamespaceId,
         function_id: FunctionId,
         cells: Vec<HeapId>,
@@ -290,10 +348,13 @@ impl<'code> CallFrame<'code> {
             code,
             ip: 0,
             stack_base,
+            exception_base,
             namespace_idx,
             function_id: Some(function_id),
             cells,
             call_position,
+            is_init: false,
+            generator: None,
         }
     }
 }
@@ -336,6 +397,11 @@ pub struct SerializedFrame {
     /// Base index into operand stack for this frame's locals.
     stack_base: usize,
 
+    /// Length of the exception stack when this frame was entered.
+    ///
+    /// Exceptions above it are being handled by this frame.
+    exception_base: usize,
+
     /// Namespace index for this frame's locals.
     namespace_idx: NamespaceId,
 
@@ -344,6 +410,12 @@ pub struct SerializedFrame {
 
     /// Call site position (for tracebacks).
     call_position: Option<CodeRange>,
+
+    /// Whether this frame runs `__init__` for a class instantiation.
+    is_init: bool,
+
+    /// Set if this frame runs a generator's body.
+    generator: Option<GeneratorFrame>,
 }
 
 impl CallFrame<'_> {
@@ -353,9 +425,12 @@ impl CallFrame<'_> {
             function_id: self.function_id,
             ip: self.ip,
             stack_base: self.stack_base,
+            exception_base: self.exception_base,
             namespace_idx: self.namespace_idx,
             cells: self.cells.clone(),
             call_position: self.call_position,
+            is_init: self.is_init,
+            generator: self.generator,
         }
     }
 }
@@ -398,6 +473,16 @@ pub struct VMSnapshot {
     /// This enables async execution to be paused and resumed across host calls.
     /// None if no async operations have been performed yet.
     scheduler: Option<Scheduler>,
+
+    /// Call of `sorted()`, `min()`, `max()` or `list.sort()` waiting for the host to
+    /// run its key function.
+    key_call: Option<KeyCall>,
+    /// Call of `datetime.now()` or `date.today()` waiting for the host to return the current time.
+    pending_now: Option<NowRequest>,
+    /// Call of the `random` module waiting for the host to return a seed.
+    pending_seed: Option<SeedRequest>,
+    /// Whether a `uuid.uuid4()` call is waiting for the host to return random bytes.
+    pending_uuid4: bool,
 }
 
 // ============================================================================
@@ -459,6 +544,39 @@ pub struct VM<'a, T: ResourceTracker, P: PrintWriter> {
     /// Stored here because the main task's frames have `function_id: None` and
     /// need a reference to the module code when being restored after task switching.
     module_code: Option<&'a Code>,
+
+    /// Frame depths of nested `run()` calls made by `call_sync`.
+    ///
+    /// Special methods like `__repr__` and `__eq__` are invoked from Rust code in the
+    /// middle of an instruction, so they run in a nested run loop. Each entry is the
+    /// number of frames below the nested call's frame: returning from (or unwinding
+    /// past) that frame exits the nested loop instead of continuing in the caller.
+    sync_call_bases: Vec<usize>,
+
+    /// Call of `sorted()`, `min()`, `max()` or `list.sort()` waiting for the host to
+    /// run its key function.
+    ///
+    /// Set when the VM suspends for a host call made by a key function; `resume()`
+    /// passes the result to this call rather than pushing it onto the stack.
+    key_call: Option<KeyCall>,
+
+    /// Call of `datetime.now()` or `date.today()` waiting for the host to return the current time.
+    ///
+    /// Set when the VM suspends with `OsFunction::DateTimeNow`; `resume()` converts the host's
+    /// datetime to the value the call returns before pushing it.
+    pending_now: Option<NowRequest>,
+
+    /// Call of the `random` module waiting for the host to return a seed.
+    ///
+    /// Set when the VM suspends with `OsFunction::RandomSeed`; `resume()` seeds the generator
+    /// with the host's int and finishes the call before pushing its result.
+    pending_seed: Option<SeedRequest>,
+
+    /// Whether a `uuid.uuid4()` call is waiting for the host to return random bytes.
+    ///
+    /// Set when the VM suspends with `OsFunction::Uuid4`; `resume()` makes the UUID from
+    /// the host's bytes before pushing it.
+    pending_uuid4: bool,
 }
 
 impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
@@ -481,6 +599,11 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
             next_call_id: 0,
             scheduler: None, // Lazy - no allocation for sync code
             module_code: None,
+            sync_call_bases: Vec::new(),
+            key_call: None,
+            pending_now: None,
+            pending_seed: None,
+            pending_uuid4: false,
         }
     }
 
@@ -518,10 +641,13 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                     code,
                     ip: sf.ip,
                     stack_base: sf.stack_base,
+                    exception_base: sf.exception_base,
                     namespace_idx: sf.namespace_idx,
                     function_id: sf.function_id,
                     cells: sf.cells,
                     call_position: sf.call_position,
+                    is_init: sf.is_init,
+                    generator: sf.generator,
                 }
             })
             .collect();
@@ -538,13 +664,21 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
             next_call_id: snapshot.next_call_id,
             scheduler: snapshot.scheduler,
             module_code: Some(module_code),
+            sync_call_bases: Vec::new(),
+            key_call: snapshot.key_call,
+            pending_now: snapshot.pending_now,
+            pending_seed: snapshot.pending_seed,
+            pending_uuid4: snapshot.pending_uuid4,
         }
     }
     /// Consumes the VM and creates a snapshot for pause/resume if needed.
     pub fn check_snapshot(mut self, result: &RunResult<FrameExit>) -> Option<VMSnapshot> {
         if matches!(
             result,
-            Ok(FrameExit::ExternalCall { .. } | FrameExit::OsCall { .. } | FrameExit::ResolveFutures(_))
+            Ok(FrameExit::ExternalCall { .. }
+                | FrameExit::MethodCall { .. }
+                | FrameExit::OsCall { .. }
+                | FrameExit::ResolveFutures(_))
         ) {
             Some(self.snapshot())
         } else {
@@ -571,6 +705,10 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
             instruction_ip: self.instruction_ip,
             next_call_id: self.next_call_id,
             scheduler: self.scheduler,
+            key_call: self.key_call,
+            pending_now: self.pending_now,
+            pending_seed: self.pending_seed,
+            pending_uuid4: self.pending_uuid4,
         }
     }
 
@@ -595,6 +733,10 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
         for value in self.stack.drain(..) {
             value.drop_with_heap(self.heap);
         }
+        if let Some(call) = self.key_call.take() {
+            self.abandon_key_call(call);
+        }
+        self.abandon_os_request();
         // Clean up current frames (main module frame after return, or any remaining frames)
         self.cleanup_current_frames();
         // Clean up task frame namespaces (scheduler doesn't have access to namespaces)
@@ -619,9 +761,14 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
             let task_id = TaskId::new(u32::try_from(task_idx).expect("task_idx exceeds u32"));
             let task = scheduler.get_task_mut(task_id);
             for frame in std::mem::take(&mut task.frames) {
-                // Clean up cell references
-                for cell_id in frame.cells {
-                    self.heap.dec_ref(cell_id);
+                if let Some(generator) = frame.generator {
+                    // Generator frames don't own their cells - the namespace holds them
+                    generator::complete_generator(self.heap, generator.generator_id);
+                } else {
+                    // Clean up cell references
+                    for cell_id in frame.cells {
+                        self.heap.dec_ref(cell_id);
+                    }
                 }
                 // Clean up the namespace (but not the global namespace)
                 if frame.namespace_idx != GLOBAL_NS_IDX {
@@ -676,7 +823,8 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
             // For NoLimitTracker, these are inlined no-ops that compile away.
             self.heap.tracker_mut().check_time()?;
 
-            if self.heap.should_gc() {
+            // Values held by Rust code during a nested `call_sync` aren't GC roots, so skip GC there
+            if self.sync_call_bases.is_empty() && self.heap.should_gc() {
                 // Sync IP before GC for safety
                 self.current_frame_mut().ip = cached_frame.ip;
                 self.run_gc();
@@ -773,7 +921,11 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                 }
                 Opcode::DeleteLocal => {
                     let slot = u16::from(fetch_u8!(cached_frame));
-                    self.delete_local(&cached_frame, slot);
+                    try_catch_sync!(self, cached_frame, self.delete_local(&cached_frame, slot));
+                }
+                Opcode::DeleteLocalW => {
+                    let slot = fetch_u16!(cached_frame);
+                    try_catch_sync!(self, cached_frame, self.delete_local(&cached_frame, slot));
                 }
                 // Variables - Global Operations
                 Opcode::LoadGlobal => {
@@ -784,6 +936,10 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                     let slot = fetch_u16!(cached_frame);
                     self.store_global(slot);
                 }
+                Opcode::DeleteGlobal => {
+                    let slot = fetch_u16!(cached_frame);
+                    try_catch_sync!(self, cached_frame, self.delete_global(slot));
+                }
                 // Variables - Cell Operations (closures)
                 Opcode::LoadCell => {
                     let slot = fetch_u16!(cached_frame);
@@ -793,6 +949,10 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                     let slot = fetch_u16!(cached_frame);
                     self.store_cell(slot);
                 }
+                Opcode::DeleteCell => {
+                    let slot = fetch_u16!(cached_frame);
+                    try_catch_sync!(self, cached_frame, self.delete_cell(slot));
+                }
                 // Binary Operations - route through exception handling for tracebacks
                 Opcode::BinaryAdd => try_catch_sync!(self, cached_frame, self.binary_add()),
                 Opcode::BinarySub => try_catch_sync!(self, cached_frame, self.binary_sub()),
@@ -813,12 +973,12 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                 }
                 Opcode::BinaryMatMul => todo!("BinaryMatMul not implemented"),
                 // Comparison Operations
-                Opcode::CompareEq => self.compare_eq(),
-                Opcode::CompareNe => self.compare_ne(),
-                Opcode::CompareLt => self.compare_ord(Ordering::is_lt),
-                Opcode::CompareLe => self.compare_ord(Ordering::is_le),
-                Opcode::CompareGt => self.compare_ord(Ordering::is_gt),
-                Opcode::CompareGe => self.compare_ord(Ordering::is_ge),
+                Opcode::CompareEq => try_catch_sync!(self, cached_frame, self.compare_eq()),
+                Opcode::CompareNe => try_catch_sync!(self, cached_frame, self.compare_ne()),
+                Opcode::CompareLt => try_catch_sync!(self, cached_frame, self.compare_ord(&CmpOperator::Lt)),
+                Opcode::CompareLe => try_catch_sync!(self, cached_frame, self.compare_ord(&CmpOperator::LtE)),
+                Opcode::CompareGt => try_catch_sync!(self, cached_frame, self.compare_ord(&CmpOperator::Gt)),
+                Opcode::CompareGe => try_catch_sync!(self, cached_frame, self.compare_ord(&CmpOperator::GtE)),
                 Opcode::CompareIs => self.compare_is(false),
                 Opcode::CompareIsNot => self.compare_is(true),
                 Opcode::CompareIn => try_catch_sync!(self, cached_frame, self.compare_in(false)),
@@ -838,6 +998,7 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                 Opcode::UnaryNeg => {
                     // Unary minus - negate numeric value
                     let value = self.pop();
+                    let value = self.mixin_operand(value);
                     match value {
                         Value::Int(n) => {
                             // Use checked_neg to handle i64::MIN overflow
@@ -862,6 +1023,34 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                                     Ok(v) => self.push(v),
                                     Err(e) => catch_sync!(self, cached_frame, RunError::from(e)),
                                 }
+                            } else if let HeapData::Complex(c) = self.heap.get(id) {
+                                let negated = c.neg();
+                                value.drop_with_heap(self.heap);
+                                match negated.into_value(self.heap) {
+                                    Ok(v) => self.push(v),
+                                    Err(e) => catch_sync!(self, cached_frame, RunError::from(e)),
+                                }
+                            } else if let HeapData::TimeDelta(delta) = self.heap.get(id) {
+                                let negated = delta.neg();
+                                value.drop_with_heap(self.heap);
+                                match negated.and_then(|delta| Ok(delta.into_value(self.heap)?)) {
+                                    Ok(v) => self.push(v),
+                                    Err(e) => catch_sync!(self, cached_frame, e),
+                                }
+                            } else if let HeapData::Decimal(decimal) = self.heap.get(id) {
+                                let decimal = decimal.clone();
+                                value.drop_with_heap(self.heap);
+                                match decimal::unary_op(&decimal, decimal::UnaryOp::Neg, self.heap) {
+                                    Ok(v) => self.push(v),
+                                    Err(e) => catch_sync!(self, cached_frame, e),
+                                }
+                            } else if let HeapData::Fraction(fraction) = self.heap.get(id) {
+                                let negated = fraction.neg();
+                                value.drop_with_heap(self.heap);
+                                match negated.into_value(self.heap) {
+                                    Ok(v) => self.push(v),
+                                    Err(e) => catch_sync!(self, cached_frame, RunError::from(e)),
+                                }
                             } else {
                                 let value_type = value.py_type(self.heap);
                                 value.drop_with_heap(self.heap);
@@ -878,12 +1067,27 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                 Opcode::UnaryPos => {
                     // Unary plus - converts bools to int, no-op for other numbers
                     let value = self.pop();
+                    let value = self.mixin_operand(value);
                     match value {
                         Value::Int(_) | Value::Float(_) => self.push(value),
                         Value::Bool(b) => self.push(Value::Int(i64::from(b))),
                         Value::Ref(id) => {
-                            if matches!(self.heap.get(id), HeapData::LongInt(_)) {
-                                // LongInt - return as-is (value already has correct refcount)
+                            if let HeapData::Decimal(decimal) = self.heap.get(id) {
+                                // decimals round to the current context
+                                let decimal = decimal.clone();
+                                value.drop_with_heap(self.heap);
+                                match decimal::unary_op(&decimal, decimal::UnaryOp::Pos, self.heap) {
+                                    Ok(v) => self.push(v),
+                                    Err(e) => catch_sync!(self, cached_frame, e),
+                                }
+                            } else if matches!(
+                                self.heap.get(id),
+                                HeapData::LongInt(_)
+                                    | HeapData::Complex(_)
+                                    | HeapData::TimeDelta(_)
+                                    | HeapData::Fraction(_)
+                            ) {
+                                // LongInt, complex, timedelta and fraction - return as-is (value already has correct refcount)
                                 self.push(value);
                             } else {
                                 let value_type = value.py_type(self.heap);
@@ -901,6 +1105,7 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                 Opcode::UnaryInvert => {
                     // Bitwise NOT
                     let value = self.pop();
+                    let value = self.mixin_operand(value);
                     match value {
                         Value::Int(n) => self.push(Value::Int(!n)),
                         Value::Bool(b) => self.push(Value::Int(!i64::from(b))),
@@ -986,6 +1191,12 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                     let func_name_id = fetch_u16!(cached_frame);
                     try_catch_sync!(self, cached_frame, self.dict_merge(func_name_id));
                 }
+                Opcode::SetUpdate => {
+                    try_catch_sync!(self, cached_frame, self.set_update());
+                }
+                Opcode::DictUpdate => {
+                    try_catch_sync!(self, cached_frame, self.dict_update());
+                }
                 // Comprehension Building - append/add/set items during iteration
                 Opcode::ListAppend => {
                     let depth = fetch_u8!(cached_frame) as usize;
@@ -1003,7 +1214,7 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                 Opcode::BinarySubscr => {
                     let index = self.pop();
                     let obj = self.pop();
-                    let result = obj.py_getitem(&index, self.heap, self.interns);
+                    let result = self.binary_subscr(&obj, &index);
                     obj.drop_with_heap(self.heap);
                     index.drop_with_heap(self.heap);
                     match result {
@@ -1014,21 +1225,18 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                 Opcode::StoreSubscr => {
                     // Stack order: value, obj, index (TOS)
                     let index = self.pop();
-                    let mut obj = self.pop();
+                    let obj = self.pop();
                     let value = self.pop();
-                    let result = obj.py_setitem(index, value, self.heap, self.interns);
-                    obj.drop_with_heap(self.heap);
-                    if let Err(e) = result {
+                    if let Err(e) = self.store_subscr(obj, index, value) {
                         catch_sync!(self, cached_frame, e);
                     }
                 }
                 Opcode::DeleteSubscr => {
-                    // TODO: Implement py_delitem on Value
                     let index = self.pop();
                     let obj = self.pop();
-                    obj.drop_with_heap(self.heap);
-                    index.drop_with_heap(self.heap);
-                    todo!("DeleteSubscr: py_delitem not yet implemented")
+                    if let Err(e) = self.delete_subscr(obj, index) {
+                        catch_sync!(self, cached_frame, e);
+                    }
                 }
                 Opcode::LoadAttr => {
                     let name_idx = fetch_u16!(cached_frame);
@@ -1046,7 +1254,9 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                     try_catch_sync!(self, cached_frame, self.store_attr(name_id));
                 }
                 Opcode::DeleteAttr => {
-                    todo!("DeleteAttr not implemented")
+                    let name_idx = fetch_u16!(cached_frame);
+                    let name_id = StringId::from_index(name_idx);
+                    try_catch_sync!(self, cached_frame, self.delete_attr(name_id));
                 }
                 // Control Flow - use cached_frame.ip directly for jumps
                 Opcode::Jump => {
@@ -1090,12 +1300,8 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                 // Iteration - route through exception handling
                 Opcode::GetIter => {
                     let value = self.pop();
-                    // Create a MontyIter from the value and store on heap
-                    match MontyIter::new(value, self.heap, self.interns) {
-                        Ok(iter) => match self.heap.allocate(HeapData::Iter(iter)) {
-                            Ok(heap_id) => self.push(Value::Ref(heap_id)),
-                            Err(e) => catch_sync!(self, cached_frame, e.into()),
-                        },
+                    match self.get_iter(value) {
+                        Ok(iter) => self.push(iter),
                         Err(e) => catch_sync!(self, cached_frame, e),
                     }
                 }
@@ -1106,9 +1312,41 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                         return Err(RunError::internal("ForIter: expected iterator ref on stack"));
                     };
 
-                    // Use advance_iterator which avoids std::mem::replace overhead
-                    // by using a two-phase approach: read state, get value, update index
-                    match advance_on_heap(self.heap, heap_id, self.interns) {
+                    if matches!(self.heap.get(heap_id), HeapData::Generator(_)) {
+                        // Resume the generator in a new frame; it pushes the next value when
+                        // it yields, or pops itself and jumps to the end when it returns
+                        let mut end_ip = cached_frame.ip;
+                        jump_relative!(end_ip, offset);
+                        self.current_frame_mut().ip = cached_frame.ip;
+                        match self.resume_generator(heap_id, Value::None, GeneratorResume::ForIter { end_ip }) {
+                            Ok(true) => reload_cache!(self, cached_frame),
+                            Ok(false) => {
+                                let iter = self.pop();
+                                iter.drop_with_heap(self.heap);
+                                cached_frame.ip = end_ip;
+                            }
+                            Err(e) => {
+                                let iter = self.pop();
+                                iter.drop_with_heap(self.heap);
+                                catch_sync!(self, cached_frame, e);
+                            }
+                        }
+                        continue;
+                    }
+
+                    // Iterators created by map(), filter() and iter(callable, sentinel) call
+                    // Python functions in a nested run loop
+                    let calls_functions =
+                        matches!(self.heap.get(heap_id), HeapData::Iter(iter) if iter.call_state().is_some());
+                    let next = if calls_functions {
+                        self.current_frame_mut().ip = cached_frame.ip;
+                        self.call_iter_next(heap_id)
+                    } else {
+                        // Use advance_iterator which avoids std::mem::replace overhead
+                        // by using a two-phase approach: read state, get value, update index
+                        advance_on_heap(self.heap, heap_id, self.interns)
+                    };
+                    match next {
                         Ok(Some(value)) => self.push(value),
                         Ok(None) => {
                             // Iterator exhausted - pop it and jump to end
@@ -1283,6 +1521,9 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                     let heap_id = self.heap.allocate(HeapData::Closure(func_id, cells, defaults))?;
                     self.push(Value::Ref(heap_id));
                 }
+                // Class Definition
+                Opcode::LoadLocals => try_catch_sync!(self, cached_frame, self.load_locals()),
+                Opcode::BuildClass => try_catch_sync!(self, cached_frame, self.build_class()),
                 // Exception Handling
                 Opcode::Raise => {
                     let exc = self.pop();
@@ -1290,7 +1531,10 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                     catch_sync!(self, cached_frame, error);
                 }
                 Opcode::RaiseFrom => {
-                    todo!("RaiseFrom")
+                    let cause = self.pop();
+                    let exc = self.pop();
+                    let error = self.make_exception_from(exc, cause);
+                    catch_sync!(self, cached_frame, error);
                 }
                 Opcode::Reraise => {
                     // Pop the current exception from the stack to re-raise it
@@ -1319,9 +1563,20 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                     let result = result?;
                     self.push(Value::Bool(result));
                 }
+                Opcode::CheckEgMatch => try_catch_sync!(self, cached_frame, self.check_eg_match()),
+                Opcode::PrepReraiseStar => try_catch_sync!(self, cached_frame, self.prep_reraise_star()),
                 // Return - reload cache after popping frame
                 Opcode::ReturnValue => {
                     let value = self.pop();
+                    if let Some(generator) = self.current_frame().generator {
+                        // Generator finished - the value goes wherever it was resumed from
+                        self.pop_frame();
+                        if let Some(value) = self.finish_generator_return(generator.resume, value) {
+                            return Ok(FrameExit::Return(value));
+                        }
+                        reload_cache!(self, cached_frame);
+                        continue;
+                    }
                     if self.frames.len() == 1 {
                         // Last frame - check if this is main task or spawned task
                         let is_main_task = self.is_main_task();
@@ -1351,8 +1606,33 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                         }
                         continue;
                     }
+                    let is_init = self.current_frame().is_init;
+                    let is_sync_call = self.sync_call_bases.last() == Some(&(self.frames.len() - 1));
                     // Pop current frame and push return value
                     self.pop_frame();
+                    // `__init__` returns the instance, which the caller pushed below the frame
+                    let value = if is_init {
+                        let instance = self.pop();
+                        if !matches!(value, Value::None) {
+                            // Raise in the caller, at the call instruction which ends at the return address
+                            let value_type = value.py_type(self.heap);
+                            value.drop_with_heap(self.heap);
+                            instance.drop_with_heap(self.heap);
+                            let error = ExcType::type_error_init_return(value_type);
+                            if is_sync_call {
+                                return Err(error);
+                            }
+                            self.instruction_ip = self.current_frame().ip - 1;
+                            catch_sync!(self, cached_frame, error);
+                            continue;
+                        }
+                        instance
+                    } else {
+                        value
+                    };
+                    if is_sync_call {
+                        return Ok(FrameExit::Return(value));
+                    }
                     self.push(value);
                     // Reload cache from parent frame
                     reload_cache!(self, cached_frame);
@@ -1379,6 +1659,31 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                         }
                     }
                 }
+                // Generators
+                Opcode::YieldValue => {
+                    let value = self.pop();
+                    // Sync IP so the generator resumes after this instruction
+                    self.current_frame_mut().ip = cached_frame.ip;
+                    match self.suspend_generator_frame() {
+                        GeneratorResume::Sync => return Ok(FrameExit::Return(value)),
+                        GeneratorResume::ForIter { .. } | GeneratorResume::YieldFrom { .. } => {
+                            self.push(value);
+                            reload_cache!(self, cached_frame);
+                        }
+                    }
+                }
+                Opcode::SendYieldFrom => {
+                    let offset = fetch_i16!(cached_frame);
+                    let mut end_ip = cached_frame.ip;
+                    jump_relative!(end_ip, offset);
+                    // Sync IP before exec (may push a frame for a generator)
+                    self.current_frame_mut().ip = cached_frame.ip;
+                    match self.send_yield_from(end_ip) {
+                        // A frame may have been pushed, or the current frame's IP changed
+                        Ok(()) => reload_cache!(self, cached_frame),
+                        Err(e) => catch_sync!(self, cached_frame, e),
+                    }
+                }
                 // Unpacking - route through exception handling
                 Opcode::UnpackSequence => {
                     let count = fetch_u8!(cached_frame) as usize;
@@ -1389,6 +1694,16 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
                     let after = fetch_u8!(cached_frame) as usize;
                     try_catch_sync!(self, cached_frame, self.unpack_ex(before, after));
                 }
+                // Pattern Matching
+                Opcode::MatchSequence => self.match_sequence(),
+                Opcode::MatchMapping => self.match_mapping(),
+                Opcode::GetLen => try_catch_sync!(self, cached_frame, self.get_len()),
+                Opcode::MatchKeys => try_catch_sync!(self, cached_frame, self.match_keys()),
+                Opcode::CopyDictWithoutKeys => try_catch_sync!(self, cached_frame, self.copy_dict_without_keys()),
+                Opcode::MatchClass => {
+                    let nargs = fetch_u8!(cached_frame) as usize;
+                    try_catch_sync!(self, cached_frame, self.match_class(nargs));
+                }
                 // Special
                 Opcode::Nop => {
                     // No operation
@@ -1426,20 +1741,80 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
 
     /// Resumes execution after an external call completes.
     ///
-    /// Pushes the return value onto the stack and continues execution.
+    /// Pushes the return value onto the stack and continues execution. The current time
+    /// is first converted to the value `datetime.now()` or `date.today()` returns, a seed
+    /// is used to finish the `random` call waiting for it, and random bytes are made into
+    /// the UUID `uuid.uuid4()` returns. The result of a key function is passed to the
+    /// `sorted()`, `min()`, `max()` or `list.sort()` call waiting for it instead of being
+    /// pushed.
     pub fn resume(&mut self, obj: MontyObject) -> Result<FrameExit, RunError> {
         let value = obj
             .to_value(self.heap, self.interns)
             .map_err(|e| SimpleException::new(ExcType::RuntimeError, Some(format!("invalid return type: {e}"))))?;
+        let result = if let Some(request) = self.pending_now.take() {
+            request.finish(value, self.heap)
+        } else if let Some(request) = self.pending_seed.take() {
+            request.finish(value, self.heap, self.interns)
+        } else if std::mem::take(&mut self.pending_uuid4) {
+            uuid::finish_uuid4(value, self.heap)
+        } else {
+            Ok(value)
+        };
+        let value = match result {
+            Ok(value) => value,
+            Err(e) => return self.resume_with_exception(e),
+        };
+        if let Some(call) = self.key_call.take() {
+            return self.resume_key_call(call, value);
+        }
         self.push(value);
         self.run()
     }
 
+    /// Resumes execution after the host chose to resolve an external call asynchronously.
+    ///
+    /// Pushes an `ExternalFuture` for the call, which the code can await later. Key
+    /// functions must return their key right away, so they raise a `RuntimeError` instead.
+    pub fn resume_with_future(&mut self, call_id: CallId) -> Result<FrameExit, RunError> {
+        if self.key_call.is_some() {
+            let error =
+                SimpleException::new_msg(ExcType::RuntimeError, "key functions can't be resolved asynchronously");
+            return self.resume_with_exception(error.into());
+        }
+        if self.pending_now.take().is_some() {
+            let error = SimpleException::new_msg(
+                ExcType::RuntimeError,
+                "the current time can't be resolved asynchronously",
+            );
+            return self.resume_with_exception(error.into());
+        }
+        if self.pending_seed.is_some() {
+            let error =
+                SimpleException::new_msg(ExcType::RuntimeError, "random seeds can't be resolved asynchronously");
+            return self.resume_with_exception(error.into());
+        }
+        if std::mem::take(&mut self.pending_uuid4) {
+            let error =
+                SimpleException::new_msg(ExcType::RuntimeError, "random bytes can't be resolved asynchronously");
+            return self.resume_with_exception(error.into());
+        }
+        // Store pending call data in the scheduler so we can track the creator task
+        // and ignore results if the task is cancelled
+        self.add_pending_call(call_id);
+        self.push(Value::ExternalFuture(call_id));
+        self.run()
+    }
+
     /// Resumes execution after an external call raised an exception.
     ///
     /// Uses the exception handling mechanism to try to catch the exception.
     /// If caught, continues execution at the handler. If not, propagates the error.
     pub fn resume_with_exception(&mut self, error: RunError) -> Result<FrameExit, RunError> {
+        // The exception is raised by the call of `sorted()`, `min()`, `max()` or `list.sort()`
+        if let Some(call) = self.key_call.take() {
+            self.abandon_key_call(call);
+        }
+        self.abandon_os_request();
         // Use the normal exception handling mechanism
         // handle_exception returns None if caught, Some(error) if not caught
         if let Some(uncaught_error) = self.handle_exception(error) {
@@ -1449,6 +1824,16 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
         self.run()
     }
 
+    /// Drops the `datetime.now()`, `random` or `uuid.uuid4()` call waiting for the host,
+    /// when the host call it made won't be answered.
+    fn abandon_os_request(&mut self) {
+        self.pending_now = None;
+        self.pending_uuid4 = false;
+        if let Some(request) = self.pending_seed.take() {
+            request.abandon(self.heap);
+        }
+    }
+
     // ========================================================================
     // Stack Operations
     // ========================================================================
@@ -1502,6 +1887,7 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
     /// Pops the current frame from the call stack.
     ///
     /// Cleans up the frame's stack region and namespace (except for global namespace).
+    /// A generator frame finishing this way completes its generator.
     pub(super) fn pop_frame(&mut self) {
         let frame = self.frames.pop().expect("no frame to pop");
         // Clean up frame's stack region
@@ -1509,10 +1895,18 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
             let value = self.stack.pop().unwrap();
             value.drop_with_heap(self.heap);
         }
+        // Drop exceptions still being handled by the frame, e.g. on `return` in an except block
+        while self.exception_stack.len() > frame.exception_base {
+            let exc = self.exception_stack.pop().unwrap();
+            exc.drop_with_heap(self.heap);
+        }
         // Clean up the namespace (but not the global namespace)
         if frame.namespace_idx != GLOBAL_NS_IDX {
             self.namespaces.drop_with_heap(frame.namespace_idx, self.heap);
         }
+        if let Some(generator) = frame.generator {
+            self.release_generator_frame(generator);
+        }
     }
 
     /// Cleans up all frames for the current task before switching tasks.
@@ -1520,10 +1914,15 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
     /// Used when a task completes or fails and we need to switch to another task.
     /// Properly cleans up each frame's namespace and cell references.
     pub(super) fn cleanup_current_frames(&mut self) {
-        for frame in self.frames.drain(..) {
-            // Clean up cell references
-            for cell_id in frame.cells {
-                self.heap.dec_ref(cell_id);
+        for frame in std::mem::take(&mut self.frames) {
+            if let Some(generator) = frame.generator {
+                // Generator frames don't own their cells - the namespace holds them
+                self.release_generator_frame(generator);
+            } else {
+                // Clean up cell references
+                for cell_id in frame.cells {
+                    self.heap.dec_ref(cell_id);
+                }
             }
             // Clean up the namespace (but not the global namespace)
             if frame.namespace_idx != GLOBAL_NS_IDX {
@@ -1534,15 +1933,21 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
 
     /// Runs garbage collection with proper GC roots.
     ///
-    /// GC roots include values in namespaces, the operand stack, and exception stack.
+    /// GC roots include values in namespaces, the operand stack, and exception stack,
+    /// plus the generators run by active frames.
     fn run_gc(&mut self) {
         // Collect roots from all reachable values
         let stack_roots = self.stack.iter().filter_map(Value::ref_id);
         let exc_roots = self.exception_stack.iter().filter_map(Value::ref_id);
         let ns_roots = self.namespaces.iter_heap_ids();
+        let generator_roots = self.frames.iter().filter_map(|f| f.generator.map(|g| g.generator_id));
 
         // Collect all roots into a vec to avoid lifetime issues
-        let roots: Vec<HeapId> = stack_roots.chain(exc_roots).chain(ns_roots).collect();
+        let roots: Vec<HeapId> = stack_roots
+            .chain(exc_roots)
+            .chain(ns_roots)
+            .chain(generator_roots)
+            .collect();
 
         self.heap.collect_garbage(roots);
     }
@@ -1634,11 +2039,22 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
     }
 
     /// Deletes a local variable (sets it to Undefined).
-    fn delete_local(&mut self, cached_frame: &CachedFrame<'a>, slot: u16) {
+    ///
+    /// Raises the same error as loading the variable if it is not bound.
+    fn delete_local(&mut self, cached_frame: &CachedFrame<'a>, slot: u16) -> RunResult<()> {
         let namespace = self.namespaces.get_mut(cached_frame.namespace_idx);
         let ns_slot = NamespaceId::new(slot as usize);
         let old_value = std::mem::replace(namespace.get_mut(ns_slot), Value::Undefined);
+        if matches!(old_value, Value::Undefined) {
+            let name = cached_frame.code.local_name(slot);
+            return Err(if cached_frame.code.is_assigned_local(slot) {
+                self.unbound_local_error(slot, name)
+            } else {
+                self.name_error_for_local(slot, name)
+            });
+        }
         old_value.drop_with_heap(self.heap);
+        Ok(())
     }
 
     /// Loads a global variable and pushes it onto the stack.
@@ -1671,6 +2087,20 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
         old_value.drop_with_heap(self.heap);
     }
 
+    /// Deletes a global variable (sets it to Undefined).
+    ///
+    /// Returns a NameError if the variable is undefined.
+    fn delete_global(&mut self, slot: u16) -> RunResult<()> {
+        let namespace = self.namespaces.get_mut(GLOBAL_NS_IDX);
+        let old_value = std::mem::replace(namespace.get_mut(NamespaceId::new(slot as usize)), Value::Undefined);
+        if matches!(old_value, Value::Undefined) {
+            let name = self.current_frame().code.local_name(slot);
+            return Err(self.name_error(slot, name));
+        }
+        old_value.drop_with_heap(self.heap);
+        Ok(())
+    }
+
     /// Loads from a closure cell and pushes onto the stack.
     ///
     /// Returns a NameError if the cell value is undefined (free variable not bound).
@@ -1704,6 +2134,21 @@ impl<'a, T: ResourceTracker, P: PrintWriter> VM<'a, T, P> {
         let cell_id = self.current_frame().cells[slot as usize];
         self.heap.set_cell_value(cell_id, value);
     }
+
+    /// Deletes the contents of a closure cell (sets it to Undefined).
+    ///
+    /// Returns a NameError if the cell value is already undefined.
+    fn delete_cell(&mut self, slot: u16) -> RunResult<()> {
+        let cell_id = self.current_frame().cells[slot as usize];
+        let value = self.heap.get_cell_value(cell_id);
+        if matches!(value, Value::Undefined) {
+            let name = self.current_frame().code.local_name(slot);
+            return Err(self.free_var_error(name));
+        }
+        value.drop_with_heap(self.heap);
+        self.heap.set_cell_value(cell_id, Value::Undefined);
+        Ok(())
+    }
 }
 
 // `heap` is not a public field on VM, so this implementation needs to go here rather than in `heap.rs`
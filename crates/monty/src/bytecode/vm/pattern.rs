@@ -0,0 +1,381 @@
+//! Structural pattern matching helpers for the VM (`match` statements).
+//!
+//! The compiler lowers patterns to ordinary stack operations plus the opcodes
+//! implemented here, which test the shape of the subject and extract the values
+//! that sub-patterns are matched against.
+
+use super::VM;
+use crate::{
+    builtins::Builtins,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, HeapData},
+    intern::StaticStrings,
+    io::PrintWriter,
+    resource::ResourceTracker,
+    types::{
+        Dict, PyTrait, Type, allocate_tuple,
+        class::{class_lookup, class_name, is_subclass},
+    },
+    value::Value,
+};
+
+/// How positional sub-patterns of a class pattern are resolved.
+enum MatchArgs {
+    /// Builtins like `int(x)` and `str(x)` match their single positional sub-pattern
+    /// against the whole subject.
+    Subject,
+    /// Attribute names from `__match_args__` (or the fields of a dataclass or named tuple).
+    Names(Vec<String>),
+}
+
+impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
+    /// Pushes whether the subject on top of the stack can match a sequence pattern.
+    ///
+    /// Lists, tuples, named tuples and deques are sequences; strings, bytes and mappings are not.
+    pub(super) fn match_sequence(&mut self) {
+        let is_sequence = match self.peek() {
+            Value::Ref(id) => matches!(
+                self.heap.get(*id),
+                HeapData::List(_) | HeapData::Tuple(_) | HeapData::NamedTuple(_) | HeapData::Deque(_)
+            ),
+            _ => false,
+        };
+        self.push(Value::Bool(is_sequence));
+    }
+
+    /// Pushes whether the subject on top of the stack can match a mapping pattern.
+    pub(super) fn match_mapping(&mut self) {
+        let is_mapping = matches!(self.peek(), Value::Ref(id) if matches!(self.heap.get(*id), HeapData::Dict(_)));
+        self.push(Value::Bool(is_mapping));
+    }
+
+    /// Pushes the length of the subject on top of the stack, leaving the subject in place.
+    pub(super) fn get_len(&mut self) -> RunResult<()> {
+        let subject = self.peek();
+        let Some(len) = subject.py_len(self.heap, self.interns) else {
+            let type_name = subject.py_type(self.heap);
+            return Err(ExcType::type_error(format!(
+                "object of type '{type_name}' has no len()"
+            )));
+        };
+        let len = i64::try_from(len).expect("length exceeds i64");
+        self.push(Value::Int(len));
+        Ok(())
+    }
+
+    /// Looks up the keys of a mapping pattern in the subject.
+    ///
+    /// Stack: `[subject, keys] -> [subject, keys, values]` where `values` is a tuple
+    /// of the values for each key, or `None` if any key is missing.
+    pub(super) fn match_keys(&mut self) -> RunResult<()> {
+        let keys = self.pop();
+        let result = self.lookup_match_keys(&keys);
+        self.push(keys);
+        let values = match result? {
+            Some(values) => allocate_tuple(values.into(), self.heap)?,
+            None => Value::None,
+        };
+        self.push(values);
+        Ok(())
+    }
+
+    fn lookup_match_keys(&mut self, keys: &Value) -> RunResult<Option<Vec<Value>>> {
+        // Borrowed copies: `keys` keeps the items alive and they are never dropped here
+        let key_items: Vec<Value> = match keys {
+            Value::Ref(id) => match self.heap.get(*id) {
+                HeapData::Tuple(tuple) => tuple.as_vec().iter().map(Value::copy_for_extend).collect(),
+                _ => return Err(RunError::internal("MatchKeys expects a tuple of keys")),
+            },
+            _ => return Err(RunError::internal("MatchKeys expects a tuple of keys")),
+        };
+
+        for (i, key) in key_items.iter().enumerate() {
+            for previous in &key_items[..i] {
+                if key.py_eq(previous, self.heap, self.interns) {
+                    let repr = key.py_repr(self.heap, self.interns);
+                    return Err(SimpleException::new_msg(
+                        ExcType::ValueError,
+                        format!("mapping pattern checks duplicate key ({repr})"),
+                    )
+                    .into());
+                }
+            }
+        }
+
+        let Value::Ref(subject_id) = *self.peek() else {
+            return Ok(None);
+        };
+        let interns = self.interns;
+        self.heap.with_entry_mut(subject_id, |heap, data| {
+            let HeapData::Dict(dict) = data else {
+                return Ok(None);
+            };
+            let mut values = Vec::with_capacity(key_items.len());
+            for key in &key_items {
+                match dict.get(key, heap, interns) {
+                    Ok(Some(value)) => values.push(value.clone_with_heap(heap)),
+                    Ok(None) => {
+                        values.drop_with_heap(heap);
+                        return Ok(None);
+                    }
+                    Err(e) => {
+                        values.drop_with_heap(heap);
+                        return Err(e);
+                    }
+                }
+            }
+            Ok(Some(values))
+        })
+    }
+
+    /// Builds the `**rest` dict of a mapping pattern.
+    ///
+    /// Stack: `[subject, keys] -> [subject, rest]` where `rest` is a new dict holding
+    /// every item of the subject whose key is not in `keys`.
+    pub(super) fn copy_dict_without_keys(&mut self) -> RunResult<()> {
+        let keys = self.pop();
+        let result = self.dict_without_keys(&keys);
+        keys.drop_with_heap(self.heap);
+        let rest = result?;
+        let rest_id = self.heap.allocate(HeapData::Dict(rest))?;
+        self.push(Value::Ref(rest_id));
+        Ok(())
+    }
+
+    fn dict_without_keys(&mut self, keys: &Value) -> RunResult<Dict> {
+        let key_items: Vec<Value> = match keys {
+            Value::Ref(id) => match self.heap.get(*id) {
+                HeapData::Tuple(tuple) => tuple.as_vec().iter().map(Value::copy_for_extend).collect(),
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+        let Value::Ref(subject_id) = *self.peek() else {
+            return Err(RunError::internal("CopyDictWithoutKeys expects a dict subject"));
+        };
+        let interns = self.interns;
+        self.heap.with_entry_mut(subject_id, |heap, data| {
+            let HeapData::Dict(dict) = data else {
+                return Err(RunError::internal("CopyDictWithoutKeys expects a dict subject"));
+            };
+            let mut rest = Dict::with_capacity(dict.len().saturating_sub(key_items.len()));
+            for (key, value) in dict.iter() {
+                if key_items.iter().any(|k| k.py_eq(key, heap, interns)) {
+                    continue;
+                }
+                let key = key.clone_with_heap(heap);
+                let value = value.clone_with_heap(heap);
+                if let Some(old) = rest.set(key, value, heap, interns)? {
+                    old.drop_with_heap(heap);
+                }
+            }
+            Ok(rest)
+        })
+    }
+
+    /// Matches a class pattern.
+    ///
+    /// Stack: `[subject, cls, kw_names] -> [attrs]` where `attrs` is a tuple holding the
+    /// values for the `nargs` positional sub-patterns followed by those for the keyword
+    /// sub-patterns named in `kw_names`, or `None` if the subject is not an instance of
+    /// `cls` or lacks one of the attributes.
+    pub(super) fn match_class(&mut self, nargs: usize) -> RunResult<()> {
+        let kw_names = self.pop();
+        let cls = self.pop();
+        let subject = self.pop();
+        let result = self.match_class_attrs(&subject, &cls, &kw_names, nargs);
+        subject.drop_with_heap(self.heap);
+        cls.drop_with_heap(self.heap);
+        kw_names.drop_with_heap(self.heap);
+        let attrs = match result? {
+            Some(attrs) => allocate_tuple(attrs.into(), self.heap)?,
+            None => Value::None,
+        };
+        self.push(attrs);
+        Ok(())
+    }
+
+    fn match_class_attrs(
+        &mut self,
+        subject: &Value,
+        cls: &Value,
+        kw_names: &Value,
+        nargs: usize,
+    ) -> RunResult<Option<Vec<Value>>> {
+        let subject_type = subject.py_type(self.heap);
+        let (is_instance, cls_name) = match cls {
+            Value::Builtin(Builtins::Type(t)) => (subject_type.is_instance_of(*t), t.to_string()),
+            Value::Builtin(Builtins::ExcType(exc_type)) => (
+                matches!(subject_type, Type::Exception(et) if et.is_subclass_of(*exc_type)),
+                exc_type.to_string(),
+            ),
+            Value::Ref(cls_id) if matches!(self.heap.get(*cls_id), HeapData::ClassObject(_)) => {
+                let is_instance = match subject {
+                    Value::Ref(id) => match self.heap.get(*id) {
+                        HeapData::Instance(inst) => is_subclass(inst.class_id(), *cls_id, self.heap),
+                        _ => false,
+                    },
+                    _ => false,
+                };
+                let name = self.interns.get_str(class_name(*cls_id, self.heap)).to_owned();
+                (is_instance, name)
+            }
+            _ => return Err(ExcType::type_error("called match pattern must be a type")),
+        };
+        if !is_instance {
+            return Ok(None);
+        }
+
+        let mut names: Vec<String> = Vec::with_capacity(nargs);
+        let mut attrs: Vec<Value> = Vec::new();
+        if nargs > 0 {
+            match self.class_match_args(subject, cls, &cls_name)? {
+                MatchArgs::Subject => {
+                    if nargs > 1 {
+                        return Err(positional_count_error(&cls_name, 1, nargs));
+                    }
+                    attrs.push(subject.clone_with_heap(self.heap));
+                }
+                MatchArgs::Names(match_args) => {
+                    if nargs > match_args.len() {
+                        return Err(positional_count_error(&cls_name, match_args.len(), nargs));
+                    }
+                    names.extend(match_args.into_iter().take(nargs));
+                }
+            }
+        }
+        if let Value::Ref(id) = kw_names
+            && let HeapData::Tuple(tuple) = self.heap.get(*id)
+        {
+            for name in tuple.as_vec() {
+                if let Value::InternString(name_id) = name {
+                    names.push(self.interns.get_str(*name_id).to_owned());
+                }
+            }
+        }
+
+        for (i, name) in names.iter().enumerate() {
+            if names[..i].contains(name) {
+                attrs.drop_with_heap(self.heap);
+                return Err(ExcType::type_error(format!(
+                    "{cls_name}() got multiple sub-patterns for attribute '{name}'"
+                )));
+            }
+            match self.match_attr(subject, name) {
+                Some(value) => attrs.push(value),
+                None => {
+                    attrs.drop_with_heap(self.heap);
+                    return Ok(None);
+                }
+            }
+        }
+        Ok(Some(attrs))
+    }
+
+    /// Returns how the positional sub-patterns of a class pattern are resolved for `cls`.
+    ///
+    /// User-defined classes use their `__match_args__` attribute, dataclasses and named
+    /// tuples their field names.
+    fn class_match_args(&self, subject: &Value, cls: &Value, cls_name: &str) -> RunResult<MatchArgs> {
+        match cls {
+            Value::Builtin(Builtins::Type(
+                Type::Bool
+                | Type::Bytes
+                | Type::Dict
+                | Type::Float
+                | Type::FrozenSet
+                | Type::Int
+                | Type::List
+                | Type::Set
+                | Type::Str
+                | Type::Tuple,
+            )) => Ok(MatchArgs::Subject),
+            Value::Builtin(Builtins::Type(Type::Dataclass | Type::NamedTuple)) => {
+                let names = match subject {
+                    Value::Ref(id) => match self.heap.get(*id) {
+                        HeapData::Dataclass(dc) => dc.field_names().to_vec(),
+                        HeapData::NamedTuple(nt) => nt
+                            .field_names()
+                            .iter()
+                            .map(|name| name.as_str(self.interns).to_owned())
+                            .collect(),
+                        _ => Vec::new(),
+                    },
+                    _ => Vec::new(),
+                };
+                Ok(MatchArgs::Names(names))
+            }
+            Value::Ref(cls_id) => {
+                let match_args_name = self.interns.get_str(StaticStrings::DunderMatchArgs.into());
+                let Some(match_args) = class_lookup(Some(*cls_id), match_args_name, self.heap, self.interns) else {
+                    return Ok(MatchArgs::Names(Vec::new()));
+                };
+                let items = match match_args {
+                    Value::Ref(id) => match self.heap.get(*id) {
+                        HeapData::Tuple(tuple) => tuple.as_vec(),
+                        _ => return Err(match_args_type_error(cls_name, match_args.py_type(self.heap))),
+                    },
+                    _ => return Err(match_args_type_error(cls_name, match_args.py_type(self.heap))),
+                };
+                let mut names = Vec::with_capacity(items.len());
+                for item in items {
+                    let name = match item {
+                        Value::InternString(id) => self.interns.get_str(*id),
+                        Value::Ref(id) => match self.heap.get(*id) {
+                            HeapData::Str(s) => s.as_str(),
+                            _ => return Err(match_args_element_error(item.py_type(self.heap))),
+                        },
+                        _ => return Err(match_args_element_error(item.py_type(self.heap))),
+                    };
+                    names.push(name.to_owned());
+                }
+                Ok(MatchArgs::Names(names))
+            }
+            _ => Ok(MatchArgs::Names(Vec::new())),
+        }
+    }
+
+    /// Returns the attribute `name` of a class pattern subject, or `None` if it doesn't exist.
+    ///
+    /// Supports instances of user-defined classes (including class attributes),
+    /// dataclasses and named tuples. The returned value is owned.
+    fn match_attr(&mut self, subject: &Value, name: &str) -> Option<Value> {
+        let Value::Ref(id) = subject else {
+            return None;
+        };
+        let found = match self.heap.get(*id) {
+            HeapData::Instance(inst) => match inst.attrs().get_by_str(name, self.heap, self.interns) {
+                Some(value) => Some(value),
+                None => class_lookup(Some(inst.class_id()), name, self.heap, self.interns),
+            },
+            HeapData::Dataclass(dc) => dc.attrs().get_by_str(name, self.heap, self.interns),
+            HeapData::NamedTuple(nt) => nt
+                .field_names()
+                .iter()
+                .position(|field| field.as_str(self.interns) == name)
+                .map(|index| &nt.as_vec()[index]),
+            _ => None,
+        }?
+        .copy_for_extend();
+        if let Value::Ref(found_id) = found {
+            self.heap.inc_ref(found_id);
+        }
+        Some(found)
+    }
+}
+
+/// Creates the `TypeError` for a class pattern with too many positional sub-patterns.
+fn positional_count_error(cls_name: &str, accepted: usize, given: usize) -> RunError {
+    let plural = if accepted == 1 { "" } else { "s" };
+    ExcType::type_error(format!(
+        "{cls_name}() accepts {accepted} positional sub-pattern{plural} ({given} given)"
+    ))
+}
+
+fn match_args_type_error(cls_name: &str, type_name: Type) -> RunError {
+    ExcType::type_error(format!("{cls_name}.__match_args__ must be a tuple (got {type_name})"))
+}
+
+fn match_args_element_error(type_name: Type) -> RunError {
+    ExcType::type_error(format!("__match_args__ elements must be strings (got {type_name})"))
+}
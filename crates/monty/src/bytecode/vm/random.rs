@@ -0,0 +1,89 @@
+//! Calls of the `random` module which may need a seed from the host.
+//!
+//! Monty never reads entropy itself: `seed()` without a seed, `Random()` without a seed, and
+//! the first call of a module function before the program seeded the module's generator
+//! suspend the VM with `OsFunction::RandomSeed`. `resume()` then seeds the generator with
+//! the host's answer and finishes the call (see [`SeedRequest`]).
+
+use super::{VM, call::CallResult};
+use crate::{
+    args::{ArgValues, bind_args},
+    exception_private::RunResult,
+    heap::{DropWithHeap, HeapData, HeapId},
+    io::PrintWriter,
+    modules::random::RandomFunctions,
+    os::OsFunction,
+    resource::ResourceTracker,
+    types::{
+        Random,
+        random::{SeedRequest, constructor_seed, seed_value},
+    },
+    value::Value,
+};
+
+impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
+    /// Calls a `random` module function, first asking the host for a seed if the module's
+    /// generator hasn't been seeded yet.
+    pub(super) fn call_random(&mut self, function: RandomFunctions, args: ArgValues) -> RunResult<CallResult> {
+        if function == RandomFunctions::Seed {
+            return self.call_random_seed(None, args);
+        }
+        if !self.heap.has_random() {
+            return Ok(self.request_seed(SeedRequest::Call(function, args)));
+        }
+        let mut random = self.heap.take_random().expect("the generator was checked to be seeded");
+        let result = random.call(self.heap, function, args, self.interns);
+        self.heap.set_random(random);
+        result.map(CallResult::Push)
+    }
+
+    /// Implements `random.seed(a=None, version=2)`, or `Random.seed()` of the generator
+    /// `instance`.
+    pub(super) fn call_random_seed(&mut self, instance: Option<HeapId>, args: ArgValues) -> RunResult<CallResult> {
+        let [a, version] = bind_args("Random.seed", args, ["a", "version"], 0, self.heap, self.interns)?;
+        version.drop_with_heap(self.heap);
+        let seed = match &a {
+            None | Some(Value::None) => Ok(None),
+            Some(a) => seed_value(a, self.heap).map(Some),
+        };
+        a.drop_with_heap(self.heap);
+
+        let Some(seed) = seed? else {
+            let request = match instance {
+                Some(id) => {
+                    self.heap.inc_ref(id);
+                    SeedRequest::SeedInstance(id)
+                }
+                None => SeedRequest::SeedModule,
+            };
+            return Ok(self.request_seed(request));
+        };
+        let random = Random::new(&seed);
+        match instance {
+            Some(id) => {
+                if let HeapData::Random(instance) = self.heap.get_mut(id) {
+                    *instance = random;
+                }
+            }
+            None => self.heap.set_random(random),
+        }
+        Ok(CallResult::Push(Value::None))
+    }
+
+    /// Implements the `random.Random(x=None)` constructor.
+    pub(super) fn call_random_type(&mut self, args: ArgValues) -> RunResult<CallResult> {
+        match constructor_seed(args, self.heap, self.interns)? {
+            Some(seed) => {
+                let id = self.heap.allocate(HeapData::Random(Random::new(&seed)))?;
+                Ok(CallResult::Push(Value::Ref(id)))
+            }
+            None => Ok(self.request_seed(SeedRequest::New)),
+        }
+    }
+
+    /// Suspends the VM to ask the host for a seed, which `resume()` passes to `request`.
+    fn request_seed(&mut self, request: SeedRequest) -> CallResult {
+        self.pending_seed = Some(request);
+        CallResult::OsCall(OsFunction::RandomSeed, ArgValues::Empty)
+    }
+}
@@ -0,0 +1,172 @@
+//! `re.sub()` and `re.subn()`, and the `sub()` and `subn()` methods of patterns, run in
+//! the VM since the replacement may be a function called for each match.
+
+use smallvec::smallvec;
+
+use super::VM;
+use crate::{
+    args::{ArgValues, bind_args},
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, HeapId},
+    io::PrintWriter,
+    modules::re::{ReFunctions, compile},
+    regex::Captures,
+    resource::{LARGE_RESULT_THRESHOLD, ResourceTracker},
+    types::{
+        PyTrait, allocate_tuple,
+        re::{Matcher, expand_template, parse_template},
+        str::allocate_string,
+    },
+    value::Value,
+};
+
+impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
+    /// Implements `re.sub(pattern, repl, string, count=0, flags=0)` and `re.subn()`.
+    pub(super) fn call_re_sub(&mut self, function: ReFunctions, args: ArgValues) -> RunResult<Value> {
+        let name = function.to_string();
+        let [pattern, repl, string, count, flags] = bind_args(
+            &name,
+            args,
+            ["pattern", "repl", "string", "count", "flags"],
+            3,
+            self.heap,
+            self.interns,
+        )?;
+        let repl = repl.expect("required argument is bound");
+        let string = string.expect("required argument is bound");
+        let pattern_id = match compile(
+            self.heap,
+            pattern.expect("required argument is bound"),
+            flags,
+            self.interns,
+        ) {
+            Ok(pattern_id) => pattern_id,
+            Err(e) => {
+                repl.drop_with_heap(self.heap);
+                string.drop_with_heap(self.heap);
+                count.drop_with_heap(self.heap);
+                return Err(e);
+            }
+        };
+        let result = self.re_sub(
+            pattern_id,
+            &repl,
+            &string,
+            count.as_ref(),
+            function == ReFunctions::Subn,
+        );
+        repl.drop_with_heap(self.heap);
+        string.drop_with_heap(self.heap);
+        count.drop_with_heap(self.heap);
+        Value::Ref(pattern_id).drop_with_heap(self.heap);
+        result
+    }
+
+    /// Implements the `sub(repl, string, count=0)` and `subn()` methods of a pattern.
+    pub(super) fn call_pattern_sub(&mut self, pattern_id: HeapId, is_subn: bool, args: ArgValues) -> RunResult<Value> {
+        let name = if is_subn { "subn" } else { "sub" };
+        let [repl, string, count] = bind_args(name, args, ["repl", "string", "count"], 2, self.heap, self.interns)?;
+        let repl = repl.expect("required argument is bound");
+        let string = string.expect("required argument is bound");
+        let result = self.re_sub(pattern_id, &repl, &string, count.as_ref(), is_subn);
+        repl.drop_with_heap(self.heap);
+        string.drop_with_heap(self.heap);
+        count.drop_with_heap(self.heap);
+        result
+    }
+
+    /// Replaces the first `count` matches of a pattern in `string`, or all of them if
+    /// `count` is zero, returning the new string, and with `subn` the number of replacements.
+    ///
+    /// `repl` is either a template, which may refer to groups like `\1` and `\g<name>`, or a
+    /// function called with each match which returns the replacement, or `None` for nothing.
+    fn re_sub(
+        &mut self,
+        pattern_id: HeapId,
+        repl: &Value,
+        string: &Value,
+        count: Option<&Value>,
+        is_subn: bool,
+    ) -> RunResult<Value> {
+        let count = match count {
+            Some(count) => count.as_int(self.heap)?,
+            None => 0,
+        };
+        let mut matcher = Matcher::new(self.heap, pattern_id, string, None, None, self.interns)?;
+        let template = match repl.as_either_str(self.heap) {
+            Some(template) => Some(parse_template(matcher.regex(), template.as_str(self.interns))?),
+            None => None,
+        };
+
+        let mut out = String::new();
+        let mut last = 0;
+        let mut replaced: i64 = 0;
+        // The number of pieces joined so far, as counted by CPython's error messages
+        let mut pieces = 0;
+        while count == 0 || replaced < count {
+            let Some(captures) = matcher.next_match(self.heap)? else {
+                break;
+            };
+            let (start, end) = captures.spans[0].expect("the whole match always has a span");
+            if start > last {
+                out.push_str(&matcher.text()[last..start]);
+                pieces += 1;
+            }
+            match &template {
+                Some(template) => {
+                    out.push_str(&expand_template(template, matcher.text(), &captures.spans));
+                    pieces += 1;
+                }
+                None => {
+                    if self.re_sub_call(repl, string, &mut matcher, captures, pieces, &mut out)? {
+                        pieces += 1;
+                    }
+                }
+            }
+            // The output is only allocated on the heap once it's complete, so check its size as it grows
+            if out.len() > LARGE_RESULT_THRESHOLD {
+                self.heap.tracker().check_large_result(out.len())?;
+            }
+            last = end;
+            replaced += 1;
+        }
+        out.push_str(&matcher.text()[last..]);
+
+        let out = allocate_string(out, self.heap)?;
+        if is_subn {
+            Ok(allocate_tuple(smallvec![out, Value::Int(replaced)], self.heap)?)
+        } else {
+            Ok(out)
+        }
+    }
+
+    /// Calls the replacement function of `sub()` with a match, appending its result to `out`.
+    ///
+    /// Returns whether anything was appended, which isn't the case if the function returned `None`.
+    fn re_sub_call(
+        &mut self,
+        repl: &Value,
+        string: &Value,
+        matcher: &mut Matcher,
+        captures: Captures,
+        pieces: usize,
+        out: &mut String,
+    ) -> RunResult<bool> {
+        let re_match = matcher.allocate_match(self.heap, string, captures)?;
+        let repl = repl.clone_with_heap(self.heap);
+        let result = self.call_sync(repl, ArgValues::One(re_match))?;
+        let outcome = match result.as_either_str(self.heap) {
+            Some(text) => {
+                out.push_str(text.as_str(self.interns));
+                Ok(true)
+            }
+            None if matches!(result, Value::None) => Ok(false),
+            None => Err(ExcType::type_error(format!(
+                "sequence item {pieces}: expected str instance, {} found",
+                result.py_type(self.heap)
+            ))),
+        };
+        result.drop_with_heap(self.heap);
+        outcome
+    }
+}
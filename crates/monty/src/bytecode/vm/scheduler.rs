@@ -14,6 +14,8 @@ use std::collections::VecDeque;
 
 use ahash::{AHashMap, AHashSet};
 
+use super::generator::{GeneratorFrame, complete_generator};
+
 use crate::{
     args::ArgValues,
     asyncio::{CallId, TaskId},
@@ -94,12 +96,18 @@ pub(crate) struct SerializedTaskFrame {
     pub ip: usize,
     /// Base index into operand stack for this frame.
     pub stack_base: usize,
+    /// Length of the exception stack when this frame was entered.
+    pub exception_base: usize,
     /// Namespace index for this frame's locals.
     pub namespace_idx: NamespaceId,
     /// Captured cells for closures.
     pub cells: Vec<HeapId>,
     /// Call site position (for tracebacks).
     pub call_position: Option<CodeRange>,
+    /// Whether this frame runs `__init__` for a class instantiation.
+    pub is_init: bool,
+    /// Set if this frame runs a generator's body.
+    pub generator: Option<GeneratorFrame>,
 }
 
 impl Task {
@@ -545,8 +553,13 @@ impl Scheduler {
 
         // Clean up frame cell references and namespaces
         for frame in std::mem::take(&mut task.frames) {
-            for cell_id in frame.cells {
-                heap.dec_ref(cell_id);
+            if let Some(generator) = frame.generator {
+                // Generator frames don't own their cells - the namespace holds them
+                complete_generator(heap, generator.generator_id);
+            } else {
+                for cell_id in frame.cells {
+                    heap.dec_ref(cell_id);
+                }
             }
             // Clean up the namespace (but not the global namespace)
             if frame.namespace_idx != GLOBAL_NS_IDX {
@@ -0,0 +1,50 @@
+//! `textwrap.indent()`, whose predicate may be a Python function.
+
+use super::VM;
+use crate::{
+    args::ArgValues,
+    defer_drop,
+    exception_private::RunResult,
+    heap::DropWithHeap,
+    io::PrintWriter,
+    modules::textwrap::{indent_args, is_indented, lines_with_ends},
+    resource::ResourceTracker,
+    types::{PyTrait, str::allocate_string},
+    value::Value,
+};
+
+impl<T: ResourceTracker, P: PrintWriter> VM<'_, T, P> {
+    /// Implements `textwrap.indent(text, prefix, predicate=None)`.
+    ///
+    /// Adds `prefix` to the lines for which `predicate` returns true, or by default to the
+    /// lines which aren't only whitespace. Lines keep their line breaks, which are those of
+    /// `str.splitlines()`.
+    pub(super) fn call_indent(&mut self, args: ArgValues) -> RunResult<Value> {
+        let (text, prefix, predicate) = indent_args(args, self.heap, self.interns)?;
+        let this = self;
+        defer_drop!(predicate, this);
+
+        let mut result = String::with_capacity(text.len());
+        for line in lines_with_ends(&text) {
+            let indented = match predicate {
+                Some(predicate) => {
+                    let predicate = predicate.clone_with_heap(this.heap);
+                    let line = allocate_string(line.to_owned(), this.heap)?;
+                    let answer = this.call_sync(predicate, ArgValues::One(line))?;
+                    let indented = answer.py_bool(this.heap, this.interns);
+                    answer.drop_with_heap(this.heap);
+                    indented
+                }
+                None => is_indented(line),
+            };
+            if indented {
+                this.heap
+                    .tracker()
+                    .check_large_result(result.len() + prefix.len() + line.len())?;
+                result.push_str(&prefix);
+            }
+            result.push_str(line);
+        }
+        allocate_string(result, this.heap)
+    }
+}
@@ -12,12 +12,12 @@ use crate::{
     defer_drop,
     exception_public::{MontyException, StackFrame},
     fstring::FormatError,
-    heap::{Heap, HeapData},
+    heap::{DropWithHeap, Heap, HeapData},
     intern::{Interns, StaticStrings, StringId},
     parse::CodeRange,
     resource::ResourceTracker,
     types::{
-        AttrCallResult, PyTrait, Str, Type, allocate_tuple,
+        AttrCallResult, List, PyTrait, Str, Type, allocate_tuple,
         str::{StringRepr, string_repr_fmt},
     },
     value::Value,
@@ -39,6 +39,14 @@ pub enum ExcType {
     BaseException,
     SystemExit,
     KeyboardInterrupt,
+    /// Raised inside a generator when it is closed.
+    GeneratorExit,
+
+    // --- BaseExceptionGroup hierarchy ---
+    /// Groups several exceptions raised together, created by `except*` or directly.
+    BaseExceptionGroup,
+    /// Subclass of BaseExceptionGroup and Exception, for groups which only contain `Exception`s.
+    ExceptionGroup,
 
     // --- ArithmeticError hierarchy ---
     /// Intermediate class for arithmetic errors.
@@ -47,6 +55,14 @@ pub enum ExcType {
     OverflowError,
     /// Subclass of ArithmeticError.
     ZeroDivisionError,
+    /// Subclass of ArithmeticError (from decimal module) - the base of the decimal signals.
+    DecimalException,
+    /// Subclass of DecimalException (from decimal module) - for invalid decimal operations.
+    InvalidOperation,
+    /// Subclass of DecimalException and ZeroDivisionError (from decimal module).
+    DivisionByZero,
+    /// Subclass of DecimalException (from decimal module) - for results beyond the exponent limit.
+    Overflow,
 
     // --- LookupError hierarchy ---
     /// Intermediate class for lookup errors.
@@ -78,6 +94,8 @@ pub enum ExcType {
     ValueError,
     /// Subclass of ValueError - for encoding/decoding errors.
     UnicodeDecodeError,
+    /// Subclass of ValueError (from json module) - for malformed JSON documents.
+    JSONDecodeError,
 
     // --- ImportError hierarchy ---
     /// Import-related errors (module not found, name not in module).
@@ -100,6 +118,8 @@ pub enum ExcType {
     // --- Standalone exception types ---
     AssertionError,
     MemoryError,
+    /// From the re module (also available as `re.error`) - for invalid regular expressions.
+    PatternError,
     StopIteration,
     SyntaxError,
     TimeoutError,
@@ -112,8 +132,11 @@ impl ExcType {
     /// Implements Python's exception hierarchy for try/except matching:
     /// - `Exception` is the base class for all standard exceptions
     /// - `LookupError` is the base for `KeyError` and `IndexError`
-    /// - `ArithmeticError` is the base for `ZeroDivisionError` and `OverflowError`
+    /// - `ArithmeticError` is the base for `ZeroDivisionError`, `OverflowError` and the
+    ///   `decimal` exceptions, whose base is `DecimalException`
+    /// - `ZeroDivisionError` is also the base for `decimal.DivisionByZero`
     /// - `RuntimeError` is the base for `RecursionError` and `NotImplementedError`
+    /// - `ExceptionGroup` subclasses both `BaseExceptionGroup` and `Exception`
     ///
     /// Returns true if `self` would be caught by `except handler_type:`.
     #[must_use]
@@ -124,20 +147,42 @@ impl ExcType {
         match handler_type {
             // BaseException catches all exceptions
             Self::BaseException => true,
-            // Exception catches everything except BaseException, and direct subclasses: KeyboardInterrupt, SystemExit
-            Self::Exception => !matches!(self, Self::BaseException | Self::KeyboardInterrupt | Self::SystemExit),
+            // Exception catches everything except BaseException, and direct subclasses: KeyboardInterrupt,
+            // SystemExit, GeneratorExit, BaseExceptionGroup
+            Self::Exception => !matches!(
+                self,
+                Self::BaseException
+                    | Self::KeyboardInterrupt
+                    | Self::SystemExit
+                    | Self::GeneratorExit
+                    | Self::BaseExceptionGroup
+            ),
+            // BaseExceptionGroup catches ExceptionGroup
+            Self::BaseExceptionGroup => matches!(self, Self::ExceptionGroup),
             // LookupError catches KeyError and IndexError
             Self::LookupError => matches!(self, Self::KeyError | Self::IndexError),
-            // ArithmeticError catches ZeroDivisionError and OverflowError
-            Self::ArithmeticError => matches!(self, Self::ZeroDivisionError | Self::OverflowError),
+            // ArithmeticError catches ZeroDivisionError, OverflowError and the decimal exceptions
+            Self::ArithmeticError => matches!(
+                self,
+                Self::ZeroDivisionError
+                    | Self::OverflowError
+                    | Self::DecimalException
+                    | Self::InvalidOperation
+                    | Self::DivisionByZero
+                    | Self::Overflow
+            ),
+            // ZeroDivisionError catches decimal.DivisionByZero
+            Self::ZeroDivisionError => matches!(self, Self::DivisionByZero),
+            // DecimalException catches the decimal signals
+            Self::DecimalException => matches!(self, Self::InvalidOperation | Self::DivisionByZero | Self::Overflow),
             // RuntimeError catches RecursionError and NotImplementedError
             Self::RuntimeError => matches!(self, Self::RecursionError | Self::NotImplementedError),
             // AttributeError catches FrozenInstanceError
             Self::AttributeError => matches!(self, Self::FrozenInstanceError),
             // NameError catches UnboundLocalError
             Self::NameError => matches!(self, Self::UnboundLocalError),
-            // ValueError catches UnicodeDecodeError
-            Self::ValueError => matches!(self, Self::UnicodeDecodeError),
+            // ValueError catches UnicodeDecodeError and JSONDecodeError
+            Self::ValueError => matches!(self, Self::UnicodeDecodeError | Self::JSONDecodeError),
             // ImportError catches ModuleNotFoundError
             Self::ImportError => matches!(self, Self::ModuleNotFoundError),
             // OSError catches FileNotFoundError, FileExistsError, IsADirectoryError, NotADirectoryError
@@ -150,10 +195,17 @@ impl ExcType {
         }
     }
 
+    /// Whether this is `BaseExceptionGroup` or `ExceptionGroup`, whose instances hold sub-exceptions.
+    #[must_use]
+    pub fn is_group(self) -> bool {
+        matches!(self, Self::BaseExceptionGroup | Self::ExceptionGroup)
+    }
+
     /// Creates an exception instance from an exception type and arguments.
     ///
     /// Handles exception constructors like `ValueError('message')`.
-    /// Currently supports zero or one string argument.
+    /// Currently supports zero or one string argument, or a message and a sequence
+    /// of exceptions for exception groups.
     ///
     /// The `interns` parameter provides access to interned string content.
     /// Returns a heap-allocated exception value.
@@ -163,6 +215,11 @@ impl ExcType {
         args: ArgValues,
         interns: &Interns,
     ) -> RunResult<Value> {
+        if self.is_group() {
+            let exc = self.call_group(heap, args, interns)?;
+            let heap_id = heap.allocate(HeapData::Exception(exc))?;
+            return Ok(Value::Ref(heap_id));
+        }
         defer_drop!(args, heap);
         let exc = match args {
             ArgValues::Empty => Ok(SimpleException::new_none(self)),
@@ -191,6 +248,69 @@ impl ExcType {
         Ok(Value::Ref(heap_id))
     }
 
+    /// Creates an exception group from `BaseExceptionGroup(message, exceptions)`.
+    ///
+    /// The exceptions must be a non-empty list or tuple of exception instances.
+    /// `BaseExceptionGroup` creates an `ExceptionGroup` when they are all `Exception`s,
+    /// while `ExceptionGroup` refuses to hold any other exception, as in CPython.
+    fn call_group(
+        self,
+        heap: &mut Heap<impl ResourceTracker>,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<SimpleException> {
+        let (message, exceptions) = args.get_two_args("BaseExceptionGroup.__new__", heap)?;
+        defer_drop!(message, heap);
+        defer_drop!(exceptions, heap);
+
+        let Some(message) = message.as_either_str(heap) else {
+            return Err(Self::type_error(format!(
+                "BaseExceptionGroup.__new__() argument 1 must be str, not {}",
+                message.py_type(heap)
+            )));
+        };
+        let (items, is_tuple) = match exceptions {
+            Value::Ref(heap_id) => match heap.get(*heap_id) {
+                HeapData::List(list) => (list.as_vec().as_slice(), false),
+                HeapData::Tuple(tuple) => (tuple.as_vec().as_slice(), true),
+                _ => return Err(Self::type_error("second argument (exceptions) must be a sequence")),
+            },
+            _ => return Err(Self::type_error("second argument (exceptions) must be a sequence")),
+        };
+        if items.is_empty() {
+            return Err(SimpleException::new_msg(
+                Self::ValueError,
+                "second argument (exceptions) must be a non-empty sequence",
+            )
+            .into());
+        }
+
+        let mut grouped = Vec::with_capacity(items.len());
+        for (index, item) in items.iter().enumerate() {
+            let exc = match item {
+                Value::Ref(item_id) => match heap.get(*item_id) {
+                    HeapData::Exception(exc) => Some(exc.clone()),
+                    _ => None,
+                },
+                _ => None,
+            };
+            let Some(exc) = exc else {
+                return Err(SimpleException::new_msg(
+                    Self::ValueError,
+                    format!("Item {index} of second argument (exceptions) is not an exception"),
+                )
+                .into());
+            };
+            grouped.push(exc);
+        }
+
+        let group = SimpleException::new_group(message.as_str(interns).to_owned(), grouped, is_tuple);
+        if self == Self::ExceptionGroup && group.exc_type() == Self::BaseExceptionGroup {
+            return Err(Self::type_error("Cannot nest BaseExceptions in an ExceptionGroup"));
+        }
+        Ok(group)
+    }
+
     /// Creates an AttributeError for when an attribute is not found (GET operation).
     ///
     /// Sets `hide_caret: true` because CPython doesn't show carets for attribute GET errors.
@@ -249,6 +369,22 @@ impl ExcType {
         })
     }
 
+    /// Creates an AttributeError for a missing attribute on a user-defined class.
+    ///
+    /// Matches CPython's format: `AttributeError: type object 'Name' has no attribute 'attr'`
+    #[must_use]
+    pub(crate) fn attribute_error_type(class_name: &str, attr_name: &str) -> RunError {
+        let exc = SimpleException::new_msg(
+            Self::AttributeError,
+            format!("type object '{class_name}' has no attribute '{attr_name}'"),
+        );
+        RunError::Exc(ExceptionRaise {
+            exc,
+            frame: None,
+            hide_caret: true, // CPython doesn't show carets for attribute GET errors
+        })
+    }
+
     /// Creates a FrozenInstanceError for assigning to a frozen dataclass.
     ///
     /// Matches CPython's `dataclasses.FrozenInstanceError` which is a subclass of `AttributeError`.
@@ -262,6 +398,14 @@ impl ExcType {
         .into()
     }
 
+    /// Creates a FrozenInstanceError for deleting a field of a frozen dataclass.
+    ///
+    /// Matches CPython's format: `FrozenInstanceError: cannot delete field '{attr_name}'`
+    #[must_use]
+    pub(crate) fn frozen_instance_delete_error(attr_name: &str) -> RunError {
+        SimpleException::new_msg(Self::FrozenInstanceError, format!("cannot delete field '{attr_name}'")).into()
+    }
+
     #[must_use]
     pub(crate) fn type_error_not_sub(type_: Type) -> RunError {
         SimpleException::new_msg(Self::TypeError, format!("'{type_}' object is not subscriptable")).into()
@@ -287,6 +431,30 @@ impl ExcType {
         .into()
     }
 
+    /// Creates a TypeError for `del obj[key]` on a container that doesn't support it.
+    ///
+    /// Matches CPython's format: `TypeError: '{type}' object doesn't support item deletion`
+    #[must_use]
+    pub(crate) fn type_error_no_item_deletion(type_: Type) -> RunError {
+        SimpleException::new_msg(
+            Self::TypeError,
+            format!("'{type_}' object doesn't support item deletion"),
+        )
+        .into()
+    }
+
+    /// Creates a TypeError for `del obj[key]` on a value that isn't a container.
+    ///
+    /// Matches CPython's format: `TypeError: '{type}' object does not support item deletion`
+    #[must_use]
+    pub(crate) fn type_error_not_sub_deletion(type_: Type) -> RunError {
+        SimpleException::new_msg(
+            Self::TypeError,
+            format!("'{type_}' object does not support item deletion"),
+        )
+        .into()
+    }
+
     /// Creates a TypeError for unhashable types when calling `hash()`.
     ///
     /// This matches Python 3.14's error message: `TypeError: unhashable type: 'list'`
@@ -300,7 +468,7 @@ impl ExcType {
     /// This matches Python 3.14's error message:
     /// `TypeError: cannot use 'list' as a dict key (unhashable type: 'list')`
     #[must_use]
-    pub(crate) fn type_error_unhashable_dict_key(type_: Type) -> RunError {
+    pub(crate) fn type_error_unhashable_dict_key(type_: &str) -> RunError {
         SimpleException::new_msg(
             Self::TypeError,
             format!("cannot use '{type_}' as a dict key (unhashable type: '{type_}')"),
@@ -313,7 +481,7 @@ impl ExcType {
     /// This matches Python 3.14's error message:
     /// `TypeError: cannot use 'list' as a set element (unhashable type: 'list')`
     #[must_use]
-    pub(crate) fn type_error_unhashable_set_element(type_: Type) -> RunError {
+    pub(crate) fn type_error_unhashable_set_element(type_: &str) -> RunError {
         SimpleException::new_msg(
             Self::TypeError,
             format!("cannot use '{type_}' as a set element (unhashable type: '{type_}')"),
@@ -574,6 +742,12 @@ impl ExcType {
         SimpleException::new_msg(Self::TypeError, msg).into()
     }
 
+    /// Creates a simple ValueError with a custom message.
+    #[must_use]
+    pub(crate) fn value_error(msg: impl fmt::Display) -> RunError {
+        SimpleException::new_msg(Self::ValueError, msg).into()
+    }
+
     /// Creates a TypeError for bytes() constructor with invalid type.
     ///
     /// Matches CPython's format: `TypeError: cannot convert '{type}' object to bytes`
@@ -582,6 +756,14 @@ impl ExcType {
         SimpleException::new_msg(Self::TypeError, format!("cannot convert '{type_}' object to bytes")).into()
     }
 
+    /// Creates a TypeError for bytearray() constructor with invalid type.
+    ///
+    /// Matches CPython's format: `TypeError: cannot convert '{type}' object to bytearray`
+    #[must_use]
+    pub(crate) fn type_error_bytearray_init(type_: Type) -> RunError {
+        SimpleException::new_msg(Self::TypeError, format!("cannot convert '{type_}' object to bytearray")).into()
+    }
+
     /// Creates a TypeError for calling a non-callable type.
     ///
     /// Matches CPython's format: `TypeError: cannot create '{type}' instances`
@@ -590,6 +772,14 @@ impl ExcType {
         SimpleException::new_msg(Self::TypeError, format!("cannot create '{type_}' instances")).into()
     }
 
+    /// Creates a TypeError for an `__init__` method that returned something other than None.
+    ///
+    /// Matches CPython's format: `TypeError: __init__() should return None, not '{type}'`
+    #[must_use]
+    pub(crate) fn type_error_init_return(type_: Type) -> RunError {
+        SimpleException::new_msg(Self::TypeError, format!("__init__() should return None, not '{type_}'")).into()
+    }
+
     /// Creates a TypeError for non-iterable type in list/tuple/etc constructors.
     ///
     /// Matches CPython's format: `TypeError: '{type}' object is not iterable`
@@ -598,6 +788,14 @@ impl ExcType {
         SimpleException::new_msg(Self::TypeError, format!("'{type_}' object is not iterable")).into()
     }
 
+    /// Creates a TypeError for a `**` item in a dict display that is not a mapping.
+    ///
+    /// Matches CPython's format: `TypeError: '{type}' object is not a mapping`
+    #[must_use]
+    pub(crate) fn type_error_not_mapping(type_: Type) -> RunError {
+        SimpleException::new_msg(Self::TypeError, format!("'{type_}' object is not a mapping")).into()
+    }
+
     /// Creates a TypeError for int() constructor with invalid type.
     ///
     /// Matches CPython's format: `TypeError: int() argument must be a string, a bytes-like object or a real number, not '{type}'`
@@ -630,6 +828,14 @@ impl ExcType {
         SimpleException::new_msg(Self::ValueError, "negative count").into()
     }
 
+    /// Creates a ValueError for an int that does not fit in a single byte.
+    ///
+    /// Matches CPython's format: `ValueError: byte must be in range(0, 256)`
+    #[must_use]
+    pub(crate) fn value_error_byte_range() -> RunError {
+        SimpleException::new_msg(Self::ValueError, "byte must be in range(0, 256)").into()
+    }
+
     /// Creates a TypeError for isinstance() arg 2.
     ///
     /// Matches CPython's format: `TypeError: isinstance() arg 2 must be a type, a tuple of types, or a union`
@@ -654,6 +860,31 @@ impl ExcType {
         .into()
     }
 
+    /// Creates a TypeError for an exception group type in an `except*` clause.
+    ///
+    /// Matches CPython's format: `TypeError: catching ExceptionGroup with except* is not allowed. Use except instead.`
+    #[must_use]
+    pub(crate) fn except_star_group_type_error() -> RunError {
+        SimpleException::new_msg(
+            Self::TypeError,
+            "catching ExceptionGroup with except* is not allowed. Use except instead.",
+        )
+        .into()
+    }
+
+    /// Creates a TypeError for an invalid condition passed to `split()` or `subgroup()`.
+    ///
+    /// Matches CPython's format:
+    /// `TypeError: expected an exception type, a tuple of exception types, or a callable (other than a class)`
+    #[must_use]
+    pub(crate) fn type_error_group_condition() -> RunError {
+        SimpleException::new_msg(
+            Self::TypeError,
+            "expected an exception type, a tuple of exception types, or a callable (other than a class)",
+        )
+        .into()
+    }
+
     /// Creates a ValueError for range() step argument being zero.
     ///
     /// Matches CPython's format: `ValueError: range() arg 3 must not be zero`
@@ -698,6 +929,14 @@ impl ExcType {
         SimpleException::new_msg(Self::RuntimeError, "Set changed size during iteration").into()
     }
 
+    /// Creates a RuntimeError for deque mutation during iteration.
+    ///
+    /// Matches CPython's format: `RuntimeError: deque mutated during iteration`
+    #[must_use]
+    pub(crate) fn runtime_error_deque_mutated() -> RunError {
+        SimpleException::new_msg(Self::RuntimeError, "deque mutated during iteration").into()
+    }
+
     /// Creates a TypeError for functions that don't accept keyword arguments.
     ///
     /// Matches CPython's format: `TypeError: {name}() takes no keyword arguments`
@@ -730,6 +969,14 @@ impl ExcType {
         SimpleException::new_msg(Self::IndexError, "tuple index out of range").into()
     }
 
+    /// Creates an IndexError for deque index out of range.
+    ///
+    /// Matches CPython's format: `IndexError('deque index out of range')`
+    #[must_use]
+    pub(crate) fn deque_index_error() -> RunError {
+        SimpleException::new_msg(Self::IndexError, "deque index out of range").into()
+    }
+
     /// Creates an IndexError for string index out of range.
     ///
     /// Matches CPython's format: `IndexError('string index out of range')`
@@ -746,6 +993,14 @@ impl ExcType {
         SimpleException::new_msg(Self::IndexError, "index out of range").into()
     }
 
+    /// Creates an IndexError for bytearray index out of range.
+    ///
+    /// Matches CPython's format: `IndexError('bytearray index out of range')`
+    #[must_use]
+    pub(crate) fn bytearray_index_error() -> RunError {
+        SimpleException::new_msg(Self::IndexError, "bytearray index out of range").into()
+    }
+
     /// Creates an IndexError for range index out of range.
     ///
     /// Matches CPython's format: `IndexError('range object index out of range')`
@@ -778,6 +1033,18 @@ impl ExcType {
         .into()
     }
 
+    /// Creates a TypeError for non-integer bytearray indices.
+    ///
+    /// Matches CPython's format: `TypeError('bytearray indices must be integers or slices, not {index_type}')`
+    #[must_use]
+    pub(crate) fn type_error_bytearray_indices(index_type: Type) -> RunError {
+        SimpleException::new_msg(
+            Self::TypeError,
+            format!("bytearray indices must be integers or slices, not {index_type}"),
+        )
+        .into()
+    }
+
     /// Creates a NameError for accessing a free variable (nonlocal/closure) before it's assigned.
     ///
     /// Matches CPython's format: `NameError: cannot access free variable 'x' where it is not
@@ -947,6 +1214,22 @@ impl ExcType {
         SimpleException::new_msg(Self::ZeroDivisionError, "zero to a negative power").into()
     }
 
+    /// Creates a ZeroDivisionError for complex division by zero.
+    ///
+    /// Matches CPython's format: `ZeroDivisionError: complex division by zero`
+    #[must_use]
+    pub(crate) fn complex_zero_division() -> RunError {
+        SimpleException::new_msg(Self::ZeroDivisionError, "complex division by zero").into()
+    }
+
+    /// Creates a ZeroDivisionError for complex zero raised to a negative or complex power.
+    ///
+    /// Matches CPython's format: `ZeroDivisionError: 0.0 to a negative or complex power`
+    #[must_use]
+    pub(crate) fn complex_zero_power() -> RunError {
+        SimpleException::new_msg(Self::ZeroDivisionError, "0.0 to a negative or complex power").into()
+    }
+
     /// Creates an OverflowError for exponents that are too large.
     ///
     /// Matches CPython's format: `OverflowError: exponent too large`
@@ -1048,6 +1331,14 @@ impl ExcType {
         SimpleException::new_msg(Self::IndexError, "pop from empty list").into()
     }
 
+    /// Creates an IndexError for popping from an empty deque.
+    ///
+    /// Matches CPython's format: `IndexError: pop from an empty deque`
+    #[must_use]
+    pub(crate) fn index_error_pop_empty_deque() -> RunError {
+        SimpleException::new_msg(Self::IndexError, "pop from an empty deque").into()
+    }
+
     /// Creates an IndexError for list.pop(index) with invalid index.
     ///
     /// Matches CPython's format: `IndexError: pop index out of range`
@@ -1084,6 +1375,32 @@ impl ExcType {
         .into()
     }
 
+    /// Creates a JSONDecodeError for a malformed JSON document.
+    ///
+    /// `pos` is a byte offset into `doc`, reported as a character index with its line and column.
+    /// Matches CPython's format: `JSONDecodeError: Expecting value: line 1 column 1 (char 0)`
+    #[must_use]
+    pub(crate) fn json_decode_error(msg: &str, doc: &str, pos: usize) -> RunError {
+        let before = &doc[..pos];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line = before.matches('\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
+        let char_pos = before.chars().count();
+        SimpleException::new_msg(
+            Self::JSONDecodeError,
+            format!("{msg}: line {line} column {column} (char {char_pos})"),
+        )
+        .into()
+    }
+
+    /// Creates a PatternError (`re.error`) for an invalid regular expression or replacement template.
+    ///
+    /// Matches CPython's format: `PatternError: missing ), unterminated subpattern at position 0`
+    #[must_use]
+    pub(crate) fn re_error(msg: impl fmt::Display) -> RunError {
+        SimpleException::new_msg(Self::PatternError, msg.to_string()).into()
+    }
+
     /// Creates a ValueError for subsequence not found in bytes/str.
     ///
     /// Matches CPython's format: `ValueError: subsection not found`
@@ -1109,6 +1426,42 @@ impl ExcType {
 pub(crate) struct SimpleException {
     exc_type: ExcType,
     arg: Option<String>,
+    /// Where the exception was raised, recorded when it is caught.
+    ///
+    /// Used to print the exception's traceback when it's part of a later exception's chain.
+    #[serde(default)]
+    traceback: Option<Box<RawStackFrame>>,
+    /// `__cause__`, `__context__` and `__suppress_context__`, boxed since most exceptions have none.
+    #[serde(default)]
+    chain: Option<Box<ExceptionChain>>,
+    /// The sub-exceptions of an exception group, `None` for all other exceptions.
+    #[serde(default)]
+    group: Option<Box<ExceptionGroupItems>>,
+}
+
+/// The sub-exceptions held by a `BaseExceptionGroup` or `ExceptionGroup`.
+///
+/// Like chained exceptions, sub-exceptions are stored by value, so `.exceptions` returns
+/// new exception objects on each access.
+#[derive(Debug, Clone, PartialEq, Hash, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ExceptionGroupItems {
+    exceptions: Vec<SimpleException>,
+    /// Whether the exceptions were given as a tuple rather than a list, as shown by `repr()` and `.args`.
+    is_tuple: bool,
+}
+
+/// The exceptions chained to a [`SimpleException`].
+///
+/// Chained exceptions are stored by value, so `__cause__` and `__context__` return a new
+/// exception object on each access rather than the original one.
+#[derive(Debug, Clone, Default, PartialEq, Hash, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ExceptionChain {
+    /// The exception given to `raise ... from cause`.
+    cause: Option<SimpleException>,
+    /// The exception being handled when this one was raised.
+    context: Option<SimpleException>,
+    /// Set by `raise ... from ...`, hides the context when printing the traceback.
+    suppress_context: bool,
 }
 
 impl fmt::Display for SimpleException {
@@ -1117,11 +1470,24 @@ impl fmt::Display for SimpleException {
     }
 }
 impl From<MontyException> for SimpleException {
-    fn from(exc: MontyException) -> Self {
-        Self {
-            exc_type: exc.exc_type(),
-            arg: exc.into_message(),
+    fn from(mut exc: MontyException) -> Self {
+        let (cause, context, suppress_context) = exc.take_chain();
+        let exceptions = exc.take_exceptions();
+        let mut simple = Self::new(exc.exc_type(), exc.into_message());
+        if !exceptions.is_empty() {
+            simple.group = Some(Box::new(ExceptionGroupItems {
+                exceptions: exceptions.into_iter().map(Into::into).collect(),
+                is_tuple: false,
+            }));
         }
+        if cause.is_some() || context.is_some() || suppress_context {
+            simple.chain = Some(Box::new(ExceptionChain {
+                cause: cause.map(|cause| (*cause).into()),
+                context: context.map(|context| (*context).into()),
+                suppress_context,
+            }));
+        }
+        simple
     }
 }
 
@@ -1129,22 +1495,44 @@ impl SimpleException {
     /// Creates a new exception with the given type and optional argument message.
     #[must_use]
     pub fn new(exc_type: ExcType, arg: Option<String>) -> Self {
-        Self { exc_type, arg }
+        Self {
+            exc_type,
+            arg,
+            traceback: None,
+            chain: None,
+            group: None,
+        }
+    }
+
+    /// Creates an exception group holding `exceptions`, which must not be empty.
+    ///
+    /// The group is an `ExceptionGroup` if all the exceptions are `Exception`s, otherwise a
+    /// `BaseExceptionGroup`, as `BaseExceptionGroup(message, exceptions)` does.
+    #[must_use]
+    pub(crate) fn new_group(message: String, exceptions: Vec<Self>, is_tuple: bool) -> Self {
+        let exc_type = if exceptions
+            .iter()
+            .all(|exc| exc.exc_type.is_subclass_of(ExcType::Exception))
+        {
+            ExcType::ExceptionGroup
+        } else {
+            ExcType::BaseExceptionGroup
+        };
+        let mut group = Self::new(exc_type, Some(message));
+        group.group = Some(Box::new(ExceptionGroupItems { exceptions, is_tuple }));
+        group
     }
 
     /// Creates a new exception with the given type and argument message.
     #[must_use]
     pub fn new_msg(exc_type: ExcType, arg: impl fmt::Display) -> Self {
-        Self {
-            exc_type,
-            arg: Some(arg.to_string()),
-        }
+        Self::new(exc_type, Some(arg.to_string()))
     }
 
     /// Creates a new exception with the given type and no argument message.
     #[must_use]
     pub fn new_none(exc_type: ExcType) -> Self {
-        Self { exc_type, arg: None }
+        Self::new(exc_type, None)
     }
 
     #[must_use]
@@ -1157,9 +1545,176 @@ impl SimpleException {
         self.arg.as_ref()
     }
 
+    /// The exception's `__cause__`, set by `raise ... from cause`.
+    #[must_use]
+    pub fn cause(&self) -> Option<&Self> {
+        self.chain.as_ref().and_then(|chain| chain.cause.as_ref())
+    }
+
+    /// The exception's `__context__`: the exception being handled when it was raised.
+    #[must_use]
+    pub fn context(&self) -> Option<&Self> {
+        self.chain.as_ref().and_then(|chain| chain.context.as_ref())
+    }
+
+    /// The exception's `__suppress_context__`, set by `raise ... from ...`.
+    #[must_use]
+    pub fn suppress_context(&self) -> bool {
+        self.chain.as_ref().is_some_and(|chain| chain.suppress_context)
+    }
+
+    /// Sets `__cause__` as `raise ... from cause` does, which also suppresses the context.
+    ///
+    /// `None` is the result of `raise ... from None`.
+    pub(crate) fn set_cause(&mut self, cause: Option<Self>) {
+        let chain = self.chain.get_or_insert_default();
+        chain.cause = cause;
+        chain.suppress_context = true;
+    }
+
+    /// Sets `__context__` to the exception being handled when this one was raised.
+    ///
+    /// If this exception already appears in the context's own chain of contexts, it's
+    /// cut out of that chain to avoid a cycle, as CPython does.
+    pub(crate) fn set_context(&mut self, mut context: Self) {
+        let mut current = &mut context;
+        while let Some(chain) = current.chain.as_deref_mut() {
+            if chain.context.as_ref() == Some(&*self) {
+                chain.context = None;
+                break;
+            }
+            match &mut chain.context {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+        self.chain.get_or_insert_default().context = Some(context);
+    }
+
+    /// The sub-exceptions of an exception group, empty for other exceptions.
+    #[must_use]
+    pub fn exceptions(&self) -> &[Self] {
+        self.group.as_ref().map_or(&[], |group| group.exceptions.as_slice())
+    }
+
+    /// Splits an exception group into the parts which do and don't match a condition,
+    /// as `BaseExceptionGroup.split()` does.
+    ///
+    /// The condition is checked against the exception itself first, then recursively
+    /// against each sub-exception of a group which doesn't match as a whole. Either
+    /// part is `None` if it would be empty. Nested groups which only partly match are
+    /// split into two groups with the same message.
+    pub(crate) fn split(
+        &self,
+        matches: &mut impl FnMut(&Self) -> RunResult<bool>,
+    ) -> RunResult<(Option<Self>, Option<Self>)> {
+        if matches(self)? {
+            return Ok((Some(self.clone()), None));
+        }
+        let Some(group) = &self.group else {
+            return Ok((None, Some(self.clone())));
+        };
+        let mut matched = Vec::new();
+        let mut rest = Vec::new();
+        for exc in &group.exceptions {
+            let (exc_matched, exc_rest) = exc.split(matches)?;
+            matched.extend(exc_matched);
+            rest.extend(exc_rest);
+        }
+        Ok((self.derive(matched), self.derive(rest)))
+    }
+
+    /// Works out the exception an `except*` statement raises once all its clauses have run.
+    ///
+    /// `raised` holds the exceptions raised by the clauses, followed by the unhandled rest of
+    /// `self` if there is any. Groups split from `self` which were re-raised are merged with the
+    /// unhandled rest, keeping the structure of `self`, while other exceptions are new.
+    /// Returns `None` if nothing should be raised, a single exception unchanged, or a new group
+    /// with an empty message holding the new exceptions and the merged group.
+    ///
+    /// Re-raised groups are recognized by their sub-exceptions and chain. Unlike in CPython,
+    /// where only a bare `raise` re-raises, `raise e` of the group bound by the clause does too.
+    pub(crate) fn prep_reraise_star(&self, raised: Vec<Self>) -> Option<Self> {
+        if self.group.is_none() {
+            // A single exception was wrapped for the one clause which could match it
+            return raised.into_iter().next();
+        }
+        let mut leaves = Vec::new();
+        self.collect_leaves(&mut leaves);
+
+        let mut kept = Vec::new();
+        let mut new_excs = Vec::new();
+        for exc in &raised {
+            let mut exc_leaves = Vec::new();
+            exc.collect_leaves(&mut exc_leaves);
+            let is_reraise =
+                exc.group.is_some() && exc.chain == self.chain && exc_leaves.iter().all(|leaf| leaves.contains(leaf));
+            if is_reraise {
+                kept.extend(exc_leaves);
+            } else {
+                new_excs.push(exc);
+            }
+        }
+
+        let mut result: Vec<Self> = new_excs.into_iter().cloned().collect();
+        if !kept.is_empty() {
+            let mut is_kept = |exc: &Self| -> RunResult<bool> { Ok(exc.group.is_none() && kept.contains(&exc)) };
+            if let Ok((Some(keep), _)) = self.split(&mut is_kept) {
+                result.push(keep);
+            }
+        }
+        match result.len() {
+            0 | 1 => result.pop(),
+            _ => Some(Self::new_group(String::new(), result, false)),
+        }
+    }
+
+    /// Collects the exceptions in this exception group and its nested groups which aren't groups.
+    fn collect_leaves<'a>(&'a self, leaves: &mut Vec<&'a Self>) {
+        match &self.group {
+            Some(group) => {
+                for exc in &group.exceptions {
+                    exc.collect_leaves(leaves);
+                }
+            }
+            None => leaves.push(self),
+        }
+    }
+
+    /// Creates a group with this group's message holding `exceptions`, or `None` if there are none.
+    ///
+    /// Like CPython's `derive()` as used by `split()`, the new group keeps this group's
+    /// traceback, cause and context.
+    fn derive(&self, exceptions: Vec<Self>) -> Option<Self> {
+        if exceptions.is_empty() {
+            return None;
+        }
+        let mut derived = Self::new_group(self.arg.clone().unwrap_or_default(), exceptions, false);
+        derived.traceback.clone_from(&self.traceback);
+        derived.chain.clone_from(&self.chain);
+        Some(derived)
+    }
+
+    /// The traceback recorded when the exception was caught, innermost frame first.
+    #[must_use]
+    pub fn traceback(&self) -> Option<&RawStackFrame> {
+        self.traceback.as_deref()
+    }
+
+    /// Records where the exception was raised when it is caught.
+    pub(crate) fn set_traceback(&mut self, frame: Option<&RawStackFrame>) {
+        self.traceback = frame.map(|frame| Box::new(frame.clone()));
+    }
+
     /// str() for an exception
     #[must_use]
     pub fn py_str(&self) -> String {
+        if let Some(group) = &self.group {
+            let count = group.exceptions.len();
+            let plural = if count == 1 { "" } else { "s" };
+            let message = self.arg.as_deref().unwrap_or_default();
+            return format!("{message} ({count} sub-exception{plural})");
+        }
         match (self.exc_type, &self.arg) {
             // KeyError expecificaly uses repr of the key for str(exc)
             (ExcType::KeyError, Some(exc)) => StringRepr(exc).to_string(),
@@ -1181,6 +1736,21 @@ impl SimpleException {
             string_repr_fmt(arg, f)?;
         }
 
+        if let Some(group) = &self.group {
+            f.write_str(if group.is_tuple { ", (" } else { ", [" })?;
+            for (index, exc) in group.exceptions.iter().enumerate() {
+                if index > 0 {
+                    f.write_str(", ")?;
+                }
+                exc.py_repr_fmt(f)?;
+            }
+            match (group.is_tuple, group.exceptions.len()) {
+                (true, 1) => f.write_str(",)")?,
+                (true, _) => f.write_char(')')?,
+                (false, _) => f.write_char(']')?,
+            }
+        }
+
         f.write_char(')')
     }
 
@@ -1202,8 +1772,10 @@ impl SimpleException {
 
     /// Gets an attribute from this exception.
     ///
-    /// Handles the `.args` attribute by allocating a tuple containing the message.
-    /// Returns `Err(AttributeError)` for all other attributes.
+    /// Handles the `.args` attribute by allocating a tuple containing the message, and
+    /// the chaining attributes `__cause__`, `__context__` and `__suppress_context__`.
+    /// Exception groups also have `.message` and `.exceptions`, and their `.args` holds
+    /// the sub-exceptions too. Returns `Ok(None)` for all other attributes.
     pub fn py_getattr(
         &self,
         attr_id: StringId,
@@ -1212,17 +1784,61 @@ impl SimpleException {
     ) -> RunResult<Option<AttrCallResult>> {
         if attr_id == StaticStrings::Args {
             // Construct tuple with 0 or 1 elements based on whether arg exists
-            let elements = if let Some(arg_str) = &self.arg {
+            let mut elements = if let Some(arg_str) = &self.arg {
                 let str_id = heap.allocate(HeapData::Str(Str::from(arg_str.clone())))?;
                 smallvec![Value::Ref(str_id)]
             } else {
                 smallvec![]
             };
+            if let Some(group) = &self.group {
+                let exceptions = self.allocate_exceptions(heap)?;
+                let exceptions = if group.is_tuple {
+                    allocate_tuple(exceptions.into(), heap)?
+                } else {
+                    Value::Ref(heap.allocate(HeapData::List(List::new(exceptions)))?)
+                };
+                elements.push(exceptions);
+            }
             Ok(Some(AttrCallResult::Value(allocate_tuple(elements, heap)?)))
+        } else if self.group.is_some() && attr_id == StaticStrings::Message {
+            let message = self.arg.clone().unwrap_or_default();
+            let str_id = heap.allocate(HeapData::Str(Str::from(message)))?;
+            Ok(Some(AttrCallResult::Value(Value::Ref(str_id))))
+        } else if self.group.is_some() && attr_id == StaticStrings::Exceptions {
+            let exceptions = self.allocate_exceptions(heap)?;
+            Ok(Some(AttrCallResult::Value(allocate_tuple(exceptions.into(), heap)?)))
+        } else if attr_id == StaticStrings::DunderCause || attr_id == StaticStrings::DunderContext {
+            let chained = if attr_id == StaticStrings::DunderCause {
+                self.cause()
+            } else {
+                self.context()
+            };
+            let value = match chained {
+                Some(exc) => Value::Ref(heap.allocate(HeapData::Exception(exc.clone()))?),
+                None => Value::None,
+            };
+            Ok(Some(AttrCallResult::Value(value)))
+        } else if attr_id == StaticStrings::DunderSuppressContext {
+            Ok(Some(AttrCallResult::Value(Value::Bool(self.suppress_context()))))
         } else {
             Ok(None)
         }
     }
+
+    /// Allocates a new exception object for each sub-exception of an exception group.
+    fn allocate_exceptions(&self, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Vec<Value>> {
+        let mut values = Vec::with_capacity(self.exceptions().len());
+        for exc in self.exceptions() {
+            match heap.allocate(HeapData::Exception(exc.clone())) {
+                Ok(heap_id) => values.push(Value::Ref(heap_id)),
+                Err(err) => {
+                    values.drop_with_heap(heap);
+                    return Err(err.into());
+                }
+            }
+        }
+        Ok(values)
+    }
 }
 
 /// A raised exception with optional stack frame for traceback.
@@ -1306,30 +1922,54 @@ impl ExceptionRaise {
     /// Extracts preview lines from the source code for traceback display.
     #[must_use]
     pub fn into_python_exception(self, interns: &Interns, source: &str) -> MontyException {
-        let traceback = self
-            .frame
-            .map(|frame| {
-                let mut frames = Vec::new();
-                let mut current = Some(&frame);
-                while let Some(f) = current {
-                    frames.push(StackFrame::from_raw(f, interns, source));
-                    current = f.parent.as_deref();
-                }
-                // Reverse so outermost frame is first (Python's "most recent call last" ordering)
-                frames.reverse();
-                frames
-            })
-            .unwrap_or_default();
-
-        MontyException::new_full(self.exc.exc_type(), self.exc.arg().cloned(), traceback)
+        simple_into_python_exception(&self.exc, self.frame.as_ref(), interns, source)
     }
 }
 
+/// Converts an exception and its traceback to a `MontyException`, including its chain
+/// and the sub-exceptions of an exception group.
+///
+/// Chained exceptions and sub-exceptions use the traceback recorded when they were caught.
+fn simple_into_python_exception(
+    exc: &SimpleException,
+    frame: Option<&RawStackFrame>,
+    interns: &Interns,
+    source: &str,
+) -> MontyException {
+    let mut traceback = Vec::new();
+    let mut current = frame;
+    while let Some(f) = current {
+        traceback.push(StackFrame::from_raw(f, interns, source));
+        current = f.parent.as_deref();
+    }
+    // Reverse so outermost frame is first (Python's "most recent call last" ordering)
+    traceback.reverse();
+
+    let chained = |chained: Option<&SimpleException>| {
+        chained.map(|chained| {
+            Box::new(simple_into_python_exception(
+                chained,
+                chained.traceback(),
+                interns,
+                source,
+            ))
+        })
+    };
+    let exceptions = exc
+        .exceptions()
+        .iter()
+        .map(|sub| simple_into_python_exception(sub, sub.traceback(), interns, source))
+        .collect();
+    MontyException::new_full(exc.exc_type(), exc.arg().cloned(), traceback)
+        .with_chain(chained(exc.cause()), chained(exc.context()), exc.suppress_context())
+        .with_exceptions(exceptions)
+}
+
 /// A stack frame for traceback information.
 ///
 /// Stores position information and optional function name as StringId.
 /// The actual name string must be looked up externally when formatting the traceback.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct RawStackFrame {
     pub position: CodeRange,
     /// The name of the frame (function name StringId, or None for module-level code).
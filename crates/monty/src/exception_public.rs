@@ -16,6 +16,14 @@ pub struct MontyException {
     message: Option<String>,
     /// Stack trace of the exception, first is the outermost frame shown first in the traceback
     traceback: Vec<StackFrame>,
+    /// The exception's `__cause__`, set by `raise ... from cause`
+    cause: Option<Box<MontyException>>,
+    /// The exception's `__context__`, the exception being handled when this one was raised
+    context: Option<Box<MontyException>>,
+    /// The exception's `__suppress_context__`, set by `raise ... from ...`
+    suppress_context: bool,
+    /// The sub-exceptions of an `ExceptionGroup` or `BaseExceptionGroup`, empty for other exceptions
+    exceptions: Vec<MontyException>,
 }
 
 /// Number of identical consecutive frames to show before collapsing.
@@ -23,48 +31,46 @@ pub struct MontyException {
 /// CPython shows 3 identical frames, then "[Previous line repeated N more times]".
 const REPEAT_FRAMES_SHOWN: usize = 3;
 
+/// Maximum number of sub-exceptions shown for each exception group, as in CPython.
+const MAX_GROUP_WIDTH: usize = 15;
+
+/// Maximum nesting of exception groups shown in a traceback, as in CPython.
+const MAX_GROUP_DEPTH: usize = 10;
+
 /// Display implementation for MontyException should exactly match python traceback format.
 impl fmt::Display for MontyException {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // Print the traceback header if we have frames
-        if !self.traceback.is_empty() {
-            writeln!(f, "Traceback (most recent call last):")?;
-        }
+        let mut output = String::new();
+        self.write_traceback(&mut output, &mut GroupPrintContext::default())?;
+        // Like the summary line of a traceback without groups, the output has no trailing newline
+        f.write_str(output.strip_suffix('\n').unwrap_or(&output))
+    }
+}
 
-        // Print frames, collapsing consecutive identical frames like CPython does
-        let mut i = 0;
-        while i < self.traceback.len() {
-            let frame = &self.traceback[i];
+/// Tracks the exception groups being printed in a traceback, like CPython's `_ExceptionPrintContext`.
+#[derive(Debug, Default)]
+struct GroupPrintContext {
+    /// How many exception groups the exception being printed is nested in.
+    depth: usize,
+    /// Whether the closing line of the innermost group's sub-exceptions still needs printing.
+    need_close: bool,
+}
 
-            // Count consecutive identical frames
-            let mut repeat_count = 1;
-            while i + repeat_count < self.traceback.len()
-                && frames_are_identical(frame, &self.traceback[i + repeat_count])
-            {
-                repeat_count += 1;
-            }
+impl GroupPrintContext {
+    fn indent(&self) -> String {
+        " ".repeat(2 * self.depth)
+    }
 
-            if repeat_count > REPEAT_FRAMES_SHOWN {
-                // Show first REPEAT_FRAMES_SHOWN frames, then collapse the rest
-                for j in 0..REPEAT_FRAMES_SHOWN {
-                    write!(f, "{}", &self.traceback[i + j])?;
-                }
-                let collapsed = repeat_count - REPEAT_FRAMES_SHOWN;
-                writeln!(f, "  [Previous line repeated {collapsed} more times]")?;
-                i += repeat_count;
-            } else {
-                // Show all frames in this group
-                for j in 0..repeat_count {
-                    write!(f, "{}", &self.traceback[i + j])?;
-                }
-                i += repeat_count;
-            }
+    /// Writes `text`, with each line prefixed by the margin of the enclosing groups.
+    fn emit(&self, output: &mut String, text: &str, margin: char) {
+        if self.depth == 0 {
+            output.push_str(text);
+            return;
         }
-
-        if let Some(msg) = &self.message {
-            write!(f, "{}: {}", self.exc_type, msg)
-        } else {
-            write!(f, "{}", self.exc_type)
+        let prefix = format!("{}{margin} ", self.indent());
+        for line in text.split_inclusive('\n') {
+            output.push_str(&prefix);
+            output.push_str(line);
         }
     }
 }
@@ -77,11 +83,28 @@ impl MontyException {
     /// You can't provide a traceback here, it's send when raising the exception.
     #[must_use]
     pub fn new(exc_type: ExcType, message: Option<String>) -> Self {
-        Self {
-            exc_type,
-            message,
-            traceback: vec![],
-        }
+        Self::new_full(exc_type, message, vec![])
+    }
+
+    /// Returns this exception with the given sub-exceptions, making it an exception group.
+    ///
+    /// Used to pass an exception group into Monty, e.g. as the result of an external function.
+    /// The exception type should be `ExceptionGroup` or `BaseExceptionGroup`.
+    #[must_use]
+    pub fn with_exceptions(mut self, exceptions: Vec<Self>) -> Self {
+        self.exceptions = exceptions;
+        self
+    }
+
+    /// Returns this exception with the given `__cause__`, `__context__` and `__suppress_context__`.
+    ///
+    /// Used to pass a chained exception into Monty, e.g. as the result of an external function.
+    #[must_use]
+    pub fn with_chain(mut self, cause: Option<Box<Self>>, context: Option<Box<Self>>, suppress_context: bool) -> Self {
+        self.cause = cause;
+        self.context = context;
+        self.suppress_context = suppress_context;
+        self
     }
 
     /// The exception type raised.
@@ -114,16 +137,67 @@ impl MontyException {
         &self.traceback
     }
 
+    /// The exception's `__cause__`, set by `raise ... from cause`.
+    #[must_use]
+    pub fn cause(&self) -> Option<&Self> {
+        self.cause.as_deref()
+    }
+
+    /// The exception's `__context__`: the exception being handled when this one was raised.
+    #[must_use]
+    pub fn context(&self) -> Option<&Self> {
+        self.context.as_deref()
+    }
+
+    /// The exception's `__suppress_context__`, set by `raise ... from ...`.
+    ///
+    /// When set, the context is not shown in the traceback.
+    #[must_use]
+    pub fn suppress_context(&self) -> bool {
+        self.suppress_context
+    }
+
+    /// The sub-exceptions of an `ExceptionGroup` or `BaseExceptionGroup`.
+    ///
+    /// Empty for exceptions which aren't groups.
+    #[must_use]
+    pub fn exceptions(&self) -> &[Self] {
+        &self.exceptions
+    }
+
+    /// The chained exception shown before this one in the traceback, if any.
+    ///
+    /// This is the cause if there is one, otherwise the context unless it's suppressed.
+    #[must_use]
+    pub fn displayed_context(&self) -> Option<&Self> {
+        if self.cause.is_some() {
+            self.cause.as_deref()
+        } else if self.suppress_context {
+            None
+        } else {
+            self.context.as_deref()
+        }
+    }
+
     /// Returns a compact summary of the exception.
     ///
     /// Format: `ExceptionType: message` (e.g., `NotImplementedError: feature not supported`)
     /// If there's no message, just returns the exception type name.
+    ///
+    /// For exception groups the message includes the number of sub-exceptions, as `str()` does.
     #[must_use]
     pub fn summary(&self) -> String {
-        if let Some(msg) = &self.message {
-            format!("{}: {}", self.exc_type, msg)
+        if self.exceptions.is_empty() {
+            if let Some(msg) = &self.message {
+                format!("{}: {}", self.exc_type, msg)
+            } else {
+                self.exc_type.to_string()
+            }
         } else {
-            self.exc_type.to_string()
+            let count = self.exceptions.len();
+            let plural = if count == 1 { "" } else { "s" };
+            let msg = self.message.as_deref().unwrap_or_default();
+            format!("{}: {msg} ({count} sub-exception{plural})", self.exc_type)
         }
     }
 
@@ -131,10 +205,16 @@ impl MontyException {
     ///
     /// Format: `ExceptionType('message')` (e.g., `ValueError('invalid value')`)
     /// Uses appropriate quoting for messages containing quotes.
+    ///
+    /// Exception groups also show their sub-exceptions, e.g. `ExceptionGroup('msg', [ValueError('a')])`.
     #[must_use]
     pub fn py_repr(&self) -> String {
         let type_str: &'static str = self.exc_type.into();
-        if let Some(msg) = &self.message {
+        if !self.exceptions.is_empty() {
+            let msg = self.message.as_deref().unwrap_or_default();
+            let exceptions: Vec<String> = self.exceptions.iter().map(Self::py_repr).collect();
+            format!("{}({}, [{}])", type_str, StringRepr(msg), exceptions.join(", "))
+        } else if let Some(msg) = &self.message {
             format!("{}({})", type_str, StringRepr(msg))
         } else {
             format!("{type_str}()")
@@ -146,15 +226,143 @@ impl MontyException {
             exc_type,
             message,
             traceback,
+            cause: None,
+            context: None,
+            suppress_context: false,
+            exceptions: Vec::new(),
         }
     }
 
-    pub(crate) fn runtime_error(err: impl fmt::Display) -> Self {
-        Self {
-            exc_type: ExcType::RuntimeError,
-            message: Some(err.to_string()),
-            traceback: vec![],
+    /// Takes the exception's cause and context, returning them with `__suppress_context__`.
+    pub(crate) fn take_chain(&mut self) -> (Option<Box<Self>>, Option<Box<Self>>, bool) {
+        (self.cause.take(), self.context.take(), self.suppress_context)
+    }
+
+    /// Takes the sub-exceptions of an exception group.
+    pub(crate) fn take_exceptions(&mut self) -> Vec<Self> {
+        std::mem::take(&mut self.exceptions)
+    }
+
+    /// Writes the exception's traceback, including its chained exceptions and sub-exceptions.
+    ///
+    /// Follows CPython's `TracebackException.format()`: exception groups are drawn as a box
+    /// around their numbered sub-exceptions, each of which is printed in full.
+    fn write_traceback(&self, output: &mut String, ctx: &mut GroupPrintContext) -> fmt::Result {
+        // Print the chained exception first, like CPython does
+        if let Some(chained) = self.displayed_context() {
+            chained.write_traceback(output, ctx)?;
+            let message = if self.cause.is_some() {
+                "\nThe above exception was the direct cause of the following exception:\n\n"
+            } else {
+                "\nDuring handling of the above exception, another exception occurred:\n\n"
+            };
+            ctx.emit(output, message, '|');
+        }
+
+        if self.exceptions.is_empty() {
+            if !self.traceback.is_empty() {
+                ctx.emit(output, "Traceback (most recent call last):\n", '|');
+                ctx.emit(output, &self.format_frames()?, '|');
+            }
+            ctx.emit(output, &format!("{}\n", self.summary()), '|');
+            return Ok(());
+        }
+        if ctx.depth > MAX_GROUP_DEPTH {
+            ctx.emit(output, &format!("... (max_group_depth is {MAX_GROUP_DEPTH})\n"), '|');
+            return Ok(());
+        }
+
+        let is_toplevel = ctx.depth == 0;
+        if is_toplevel {
+            ctx.depth += 1;
+        }
+        if !self.traceback.is_empty() {
+            let margin = if is_toplevel { '+' } else { '|' };
+            ctx.emit(output, "Exception Group Traceback (most recent call last):\n", margin);
+            ctx.emit(output, &self.format_frames()?, '|');
+        }
+        ctx.emit(output, &format!("{}\n", self.summary()), '|');
+
+        let count = self.exceptions.len();
+        let shown = if count <= MAX_GROUP_WIDTH {
+            count
+        } else {
+            MAX_GROUP_WIDTH + 1
+        };
+        ctx.need_close = false;
+        for index in 0..shown {
+            let is_last = index == shown - 1;
+            if is_last {
+                // The closing line may be printed by a nested group instead
+                ctx.need_close = true;
+            }
+            let truncated = index >= MAX_GROUP_WIDTH;
+            let title = if truncated {
+                "...".to_owned()
+            } else {
+                (index + 1).to_string()
+            };
+            let corner = if index == 0 { "+-" } else { "  " };
+            writeln!(
+                output,
+                "{}{corner}+---------------- {title} ----------------",
+                ctx.indent()
+            )?;
+            ctx.depth += 1;
+            if truncated {
+                let remaining = count - MAX_GROUP_WIDTH;
+                let plural = if remaining == 1 { "" } else { "s" };
+                ctx.emit(output, &format!("and {remaining} more exception{plural}\n"), '|');
+            } else {
+                self.exceptions[index].write_traceback(output, ctx)?;
+            }
+            if is_last && ctx.need_close {
+                writeln!(output, "{}+------------------------------------", ctx.indent())?;
+                ctx.need_close = false;
+            }
+            ctx.depth -= 1;
         }
+        if is_toplevel {
+            ctx.depth = 0;
+        }
+        Ok(())
+    }
+
+    /// Formats the traceback frames, collapsing consecutive identical frames like CPython does.
+    fn format_frames(&self) -> Result<String, fmt::Error> {
+        let mut output = String::new();
+        let mut i = 0;
+        while i < self.traceback.len() {
+            let frame = &self.traceback[i];
+
+            // Count consecutive identical frames
+            let mut repeat_count = 1;
+            while i + repeat_count < self.traceback.len()
+                && frames_are_identical(frame, &self.traceback[i + repeat_count])
+            {
+                repeat_count += 1;
+            }
+
+            if repeat_count > REPEAT_FRAMES_SHOWN {
+                // Show first REPEAT_FRAMES_SHOWN frames, then collapse the rest
+                for j in 0..REPEAT_FRAMES_SHOWN {
+                    write!(output, "{}", &self.traceback[i + j])?;
+                }
+                let collapsed = repeat_count - REPEAT_FRAMES_SHOWN;
+                writeln!(output, "  [Previous line repeated {collapsed} more times]")?;
+            } else {
+                // Show all frames in this group
+                for j in 0..repeat_count {
+                    write!(output, "{}", &self.traceback[i + j])?;
+                }
+            }
+            i += repeat_count;
+        }
+        Ok(output)
+    }
+
+    pub(crate) fn runtime_error(err: impl fmt::Display) -> Self {
+        Self::new(ExcType::RuntimeError, Some(err.to_string()))
     }
 }
 
@@ -4,7 +4,7 @@ use crate::{
     fstring::FStringPart,
     intern::{BytesId, LongIntId, StringId},
     namespace::NamespaceId,
-    parse::{CodeRange, ParsedSignature, Try},
+    parse::{CodeRange, MatchCase, ParsedSignature, Try},
     signature::Signature,
     value::{EitherStr, Marker, Value},
 };
@@ -180,11 +180,17 @@ pub enum Expr {
         upper: Option<Box<ExprLoc>>,
         step: Option<Box<ExprLoc>>,
     },
-    Dict(Vec<(ExprLoc, ExprLoc)>),
+    /// Dict literal expression: `{'a': 1, **other}`.
+    Dict(Vec<DictItem>),
     /// Set literal expression: `{1, 2, 3}`.
     ///
     /// Note: `{}` is always a dict, not an empty set. Use `set()` for empty sets.
     Set(Vec<ExprLoc>),
+    /// Starred item in a list, tuple or set display: `[*a, *b]`.
+    ///
+    /// Only valid as a direct element of `List`, `Tuple` or `Set`, the parser
+    /// rejects starred expressions anywhere else.
+    Starred(Box<ExprLoc>),
     /// Unary `not` expression - evaluates to the boolean negation of the operand's truthiness.
     Not(Box<ExprLoc>),
     /// Unary minus expression - negates a numeric value.
@@ -266,6 +272,38 @@ pub enum Expr {
         /// The body is wrapped as `[Node::Return(body_expr)]` during preparation.
         func_def: Box<PreparedFunctionDef>,
     },
+    /// Yield expression: `yield` or `yield value`.
+    ///
+    /// Only valid inside a function body; a function containing a yield is a generator
+    /// function. Evaluates to the value sent into the generator when it is resumed.
+    Yield(Option<Box<ExprLoc>>),
+    /// Delegating yield expression: `yield from iterable`.
+    ///
+    /// Yields every value produced by `iterable`, forwarding sent values when the
+    /// iterable is a generator, and evaluates to the delegated generator's return value.
+    YieldFrom(Box<ExprLoc>),
+    /// Raw generator expression from the parser, before preparation.
+    ///
+    /// During the prepare phase, it gets converted to `Expr::Generator` with a prepared
+    /// `<genexpr>` function whose single parameter is the first iterable.
+    GeneratorRaw {
+        /// The interned `<genexpr>` name ID.
+        name_id: StringId,
+        /// The interned `.0` name ID of the function's only parameter.
+        param_id: StringId,
+        elt: Box<ExprLoc>,
+        generators: Vec<Comprehension>,
+    },
+    /// Generator expression: `(elt for target in iter if cond...)` (prepared form).
+    ///
+    /// Compiled as a call to a generator function whose body is the comprehension
+    /// loop with a `yield elt` in the innermost position. The first iterable is
+    /// evaluated eagerly in the enclosing scope and passed as the only argument.
+    Generator {
+        func_def: Box<PreparedFunctionDef>,
+        /// The first generator's iterable, evaluated in the enclosing scope.
+        iter: Box<ExprLoc>,
+    },
     /// Named expression (walrus operator): `(target := value)`
     ///
     /// Evaluates `value`, assigns it to `target`, and returns the value as the
@@ -280,6 +318,26 @@ pub enum Expr {
     },
 }
 
+/// An item in a dict display.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum DictItem {
+    /// Key-value pair: `key: value`
+    Pair(ExprLoc, ExprLoc),
+    /// Mapping unpacking: `**mapping` - copies all items of the mapping into the dict.
+    Unpack(ExprLoc),
+}
+
+impl DictItem {
+    /// Returns the expressions in this item in evaluation order.
+    pub fn exprs(&self) -> impl Iterator<Item = &ExprLoc> {
+        let (first, second) = match self {
+            Self::Pair(key, value) => (key, Some(value)),
+            Self::Unpack(mapping) => (mapping, None),
+        };
+        std::iter::once(first).chain(second)
+    }
+}
+
 /// Target for tuple unpacking - can be a single name, nested tuple, or starred target.
 ///
 /// Supports recursive structures like `(a, b), c` or `a, (b, c)`.
@@ -302,6 +360,154 @@ pub enum UnpackTarget {
     Starred(Identifier),
 }
 
+/// Target of a `del` statement.
+///
+/// Tuple and list targets like `del a, (b, c)` are flattened into their elements by the parser.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum DeleteTarget {
+    /// Variable: `del x`
+    Name(Identifier),
+    /// Subscript: `del d[key]` or `del lst[i:j]`
+    Subscript {
+        object: ExprLoc,
+        index: ExprLoc,
+        /// Position of the subscript expression (e.g., `d[key]`) for traceback carets.
+        position: CodeRange,
+    },
+    /// Attribute: `del obj.attr`
+    Attr {
+        object: ExprLoc,
+        attr: EitherStr,
+        /// Position of the attribute expression (e.g., `obj.attr`) for traceback carets.
+        position: CodeRange,
+    },
+}
+
+/// A pattern in a `case` clause of a `match` statement.
+///
+/// Capture patterns (`case x:`) and the wildcard (`case _:`) are both represented
+/// as `As` with no sub-pattern; the wildcard has no name.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum Pattern {
+    /// Value pattern compared with `==`: literals and dotted names like `Color.RED`.
+    Value(ExprLoc),
+    /// `None`, `True` or `False`, compared by identity.
+    Singleton(ExprLoc),
+    /// Sequence pattern: `[a, b, *rest]` or `(a, b)`.
+    ///
+    /// At most one sub-pattern may be a `Star`.
+    Sequence { patterns: Vec<Self>, position: CodeRange },
+    /// Mapping pattern: `{"key": value, **rest}`.
+    ///
+    /// `keys` and `patterns` have the same length; keys are literals or value patterns.
+    Mapping {
+        keys: Vec<ExprLoc>,
+        patterns: Vec<Self>,
+        rest: Option<Identifier>,
+        position: CodeRange,
+    },
+    /// Class pattern: `Point(x, y=0)`.
+    ///
+    /// Positional sub-patterns are matched against the attributes named by the class's
+    /// `__match_args__`, keyword sub-patterns against the named attributes.
+    Class {
+        cls: ExprLoc,
+        patterns: Vec<Self>,
+        kwd_attrs: Vec<StringId>,
+        kwd_patterns: Vec<Self>,
+        position: CodeRange,
+    },
+    /// Star pattern inside a sequence: `*rest` or `*_`.
+    Star {
+        name: Option<Identifier>,
+        position: CodeRange,
+    },
+    /// Capture, wildcard or AS pattern: `x`, `_` or `[a, b] as pair`.
+    As {
+        pattern: Option<Box<Self>>,
+        name: Option<Identifier>,
+        position: CodeRange,
+    },
+    /// OR pattern: `1 | 2 | 3`. Every alternative must bind the same names.
+    Or { patterns: Vec<Self>, position: CodeRange },
+}
+
+impl Pattern {
+    /// Returns the source range covered by this pattern.
+    #[must_use]
+    pub fn position(&self) -> CodeRange {
+        match self {
+            Self::Value(expr) | Self::Singleton(expr) => expr.position,
+            Self::Sequence { position, .. }
+            | Self::Mapping { position, .. }
+            | Self::Class { position, .. }
+            | Self::Star { position, .. }
+            | Self::As { position, .. }
+            | Self::Or { position, .. } => *position,
+        }
+    }
+
+    /// Returns whether this pattern matches every subject (a capture or the wildcard,
+    /// possibly inside an OR pattern).
+    #[must_use]
+    pub fn is_irrefutable(&self) -> bool {
+        match self {
+            Self::As { pattern: None, .. } => true,
+            Self::As {
+                pattern: Some(pattern), ..
+            } => pattern.is_irrefutable(),
+            Self::Or { patterns, .. } => patterns.iter().any(Self::is_irrefutable),
+            _ => false,
+        }
+    }
+
+    /// Calls `f` with every name bound by this pattern, in source order.
+    pub fn for_each_binding<'a>(&'a self, f: &mut impl FnMut(&'a Identifier)) {
+        match self {
+            Self::Value(_) | Self::Singleton(_) => {}
+            Self::Sequence { patterns, .. } => {
+                for p in patterns {
+                    p.for_each_binding(f);
+                }
+            }
+            Self::Mapping { patterns, rest, .. } => {
+                for p in patterns {
+                    p.for_each_binding(f);
+                }
+                if let Some(rest) = rest {
+                    f(rest);
+                }
+            }
+            Self::Class {
+                patterns, kwd_patterns, ..
+            } => {
+                for p in patterns.iter().chain(kwd_patterns) {
+                    p.for_each_binding(f);
+                }
+            }
+            Self::Star { name, .. } => {
+                if let Some(name) = name {
+                    f(name);
+                }
+            }
+            Self::As { pattern, name, .. } => {
+                if let Some(pattern) = pattern {
+                    pattern.for_each_binding(f);
+                }
+                if let Some(name) = name {
+                    f(name);
+                }
+            }
+            // All alternatives bind the same names, so the first one is representative
+            Self::Or { patterns, .. } => {
+                if let Some(first) = patterns.first() {
+                    first.for_each_binding(f);
+                }
+            }
+        }
+    }
+}
+
 /// A generator clause in a comprehension: `for target in iter [if cond1] [if cond2]...`
 ///
 /// Represents one `for` clause with zero or more `if` filters. Multiple generators
@@ -397,7 +603,17 @@ pub enum Node<F> {
     Expr(ExprLoc),
     Return(ExprLoc),
     ReturnNone,
-    Raise(Option<ExprLoc>),
+    /// Raise statement: `raise`, `raise exc` or `raise exc from cause`.
+    ///
+    /// `cause` is only set with `exc`, a bare `raise` can't have a cause.
+    Raise {
+        exc: Option<ExprLoc>,
+        cause: Option<ExprLoc>,
+    },
+    /// Delete statement: `del a, b[k], c.d`.
+    ///
+    /// Targets are deleted from left to right.
+    Delete(Vec<DeleteTarget>),
     Assert {
         test: ExprLoc,
         msg: Option<ExprLoc>,
@@ -476,6 +692,17 @@ pub enum Node<F> {
         or_else: Vec<Self>,
     },
     FunctionDef(F),
+    /// Class definition statement: `class Name(Base): body`.
+    ///
+    /// The class body is represented as a zero-argument function named after the class.
+    /// At runtime the body is executed once, its locals become the class namespace, and
+    /// the resulting class object is bound to the class name.
+    ClassDef {
+        /// The class body, modelled as a function definition.
+        body: F,
+        /// Optional single base class expression.
+        base: Option<ExprLoc>,
+    },
     /// Global variable declaration. Only present in parsed form, consumed during prepare.
     ///
     /// Declares that the listed names refer to module-level (global) variables,
@@ -497,6 +724,30 @@ pub enum Node<F> {
     /// Executes body, catches matching exceptions with handlers, runs else if no exception,
     /// and always runs finally.
     Try(Try<Self>),
+    /// Match statement: `match subject: case pattern [if guard]: body ...`.
+    ///
+    /// The subject is evaluated once and each case is tried in order; the body of the
+    /// first case whose pattern matches (and whose guard is truthy) is executed.
+    Match {
+        subject: ExprLoc,
+        cases: Vec<MatchCase<Self>>,
+    },
+    /// Context manager statement: `with context as target: body` (or `async with`).
+    ///
+    /// A statement with several items is parsed as nested `With` nodes, one per item.
+    /// The result of `__enter__` (or the awaited `__aenter__`) is bound to `target`, and
+    /// `__exit__` runs on every way out of the body. A truthy result from `__exit__`
+    /// suppresses an exception raised in the body.
+    With {
+        context: ExprLoc,
+        target: Option<UnpackTarget>,
+        /// Hidden local slot holding the context manager while the body runs.
+        ///
+        /// No Python name refers to it; the slot is allocated during prepare.
+        manager: Identifier,
+        body: Vec<Self>,
+        is_async: bool,
+    },
     /// Import statement (e.g., `import sys`, `import sys as s`).
     ///
     /// Loads a module and binds it to a name in the current namespace.
@@ -560,11 +811,21 @@ pub struct PreparedFunctionDef {
     /// Each group contains only the parameters that have defaults, in declaration order.
     /// The counts in `signature` indicate how many defaults exist for each group.
     pub default_exprs: Vec<ExprLoc>,
+    /// Prepared decorator expressions in source order, evaluated in the enclosing scope.
+    ///
+    /// They're evaluated before the defaults, then applied bottom-up to the new function
+    /// (or class, for a class body) before it's bound to its name.
+    pub decorators: Vec<ExprLoc>,
     /// Whether this is an async function (`async def`).
     ///
     /// When true, calling this function creates a `Coroutine` object instead of
     /// immediately pushing a frame.
     pub is_async: bool,
+    /// Whether the function body contains `yield` (a generator function).
+    ///
+    /// When true, calling this function creates a `Generator` object instead of
+    /// immediately pushing a frame.
+    pub is_generator: bool,
 }
 
 /// Type alias for prepared AST nodes (output of prepare phase).
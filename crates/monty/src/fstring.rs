@@ -11,7 +11,7 @@ use std::str::FromStr;
 use crate::{
     exception_private::{ExcType, RunError, SimpleException},
     expressions::ExprLoc,
-    heap::Heap,
+    heap::{Heap, HeapData},
     intern::{Interns, StringId},
     resource::ResourceTracker,
     types::{PyTrait, Type},
@@ -263,6 +263,14 @@ pub fn format_with_spec(
     heap: &Heap<impl ResourceTracker>,
     interns: &Interns,
 ) -> Result<String, RunError> {
+    // Decimals and fractions format exactly, with their own rules for the type characters
+    if let Value::Ref(id) = value {
+        match heap.get(*id) {
+            HeapData::Decimal(decimal) => return decimal.format_spec(spec, heap),
+            HeapData::Fraction(fraction) => return fraction.format_spec(spec),
+            _ => {}
+        }
+    }
     let value_type = value.py_type(heap);
 
     match (value, spec.type_char) {
@@ -275,24 +283,25 @@ pub fn format_with_spec(
         (Value::Int(n), Some('c')) => Ok(format_char(*n, spec)?),
 
         // Float formatting
-        (Value::Float(f), None | Some('g' | 'G')) => Ok(format_float_g(*f, spec)),
-        (Value::Float(f), Some('f' | 'F')) => Ok(format_float_f(*f, spec)),
+        (Value::Float(f), None | Some('g')) => Ok(format_float_g(*f, spec)),
+        (Value::Float(f), Some('G')) => Ok(format_float_g(*f, spec).to_uppercase()),
+        (Value::Float(f), Some('f')) => Ok(format_float_f(*f, spec)),
+        (Value::Float(f), Some('F')) => Ok(format_float_f(*f, spec).to_uppercase()),
         (Value::Float(f), Some('e')) => Ok(format_float_e(*f, spec, false)),
         (Value::Float(f), Some('E')) => Ok(format_float_e(*f, spec, true)),
         (Value::Float(f), Some('%')) => Ok(format_float_percent(*f, spec)),
 
         // Int to float formatting (Python allows this)
-        (Value::Int(n), Some('f' | 'F')) => Ok(format_float_f(*n as f64, spec)),
+        (Value::Int(n), Some('f')) => Ok(format_float_f(*n as f64, spec)),
+        (Value::Int(n), Some('F')) => Ok(format_float_f(*n as f64, spec).to_uppercase()),
         (Value::Int(n), Some('e')) => Ok(format_float_e(*n as f64, spec, false)),
         (Value::Int(n), Some('E')) => Ok(format_float_e(*n as f64, spec, true)),
-        (Value::Int(n), Some('g' | 'G')) => Ok(format_float_g(*n as f64, spec)),
+        (Value::Int(n), Some('g')) => Ok(format_float_g(*n as f64, spec)),
+        (Value::Int(n), Some('G')) => Ok(format_float_g(*n as f64, spec).to_uppercase()),
         (Value::Int(n), Some('%')) => Ok(format_float_percent(*n as f64, spec)),
 
         // String formatting (including InternString and heap strings)
-        (_, None | Some('s')) if value_type == Type::Str => {
-            let s = value.py_str(heap, interns);
-            Ok(format_string(&s, spec)?)
-        }
+        _ if value_type == Type::Str => format_str_with_spec(&value.py_str(heap, interns), spec),
 
         // Bool as int
         (Value::Bool(b), Some('d')) => Ok(format_int(i64::from(*b), spec)),
@@ -312,6 +321,20 @@ pub fn format_with_spec(
     }
 }
 
+/// Formats a string according to a format specification, like `format_with_spec` for strings.
+///
+/// Returns a `ValueError` for type characters other than `s`.
+pub fn format_str_with_spec(s: &str, spec: &ParsedFormatSpec) -> Result<String, RunError> {
+    match spec.type_char {
+        None | Some('s') => Ok(format_string(s, spec)?),
+        Some(c) => Err(SimpleException::new_msg(
+            ExcType::ValueError,
+            format!("Unknown format code '{c}' for object of type 'str'"),
+        )
+        .into()),
+    }
+}
+
 /// Encodes a ParsedFormatSpec into a u64 for storage in bytecode constants.
 ///
 /// Encoding layout (fits in 48 bits):
@@ -467,11 +490,15 @@ pub fn format_string(value: &str, spec: &ParsedFormatSpec) -> Result<String, For
 /// - Zero-padding: When `zero_pad` is true or `=` alignment, inserts zeros between sign and digits
 /// - Alignment: Right-aligned by default for numbers, pads to `width` with `fill` character
 pub fn format_int(n: i64, spec: &ParsedFormatSpec) -> String {
-    let is_negative = n < 0;
-    let abs_str = n.abs().to_string();
+    pad_number(n < 0, &n.unsigned_abs().to_string(), spec)
+}
 
+/// Pads the digits of a number, given its sign and its unsigned text, as `format_int` does.
+///
+/// Used for numbers formatted outside this module, like decimals and fractions.
+pub fn pad_number(negative: bool, body: &str, spec: &ParsedFormatSpec) -> String {
     // Build the sign prefix
-    let sign = if is_negative {
+    let sign = if negative {
         "-"
     } else {
         match spec.sign {
@@ -487,16 +514,16 @@ pub fn format_int(n: i64, spec: &ParsedFormatSpec) -> String {
     // Handle sign-aware zero-padding or regular padding
     if spec.zero_pad || align == '=' {
         let fill = if spec.zero_pad { '0' } else { spec.fill };
-        let total_len = sign.len() + abs_str.len();
+        let total_len = sign.len() + body.chars().count();
         if spec.width > total_len {
             let padding = spec.width - total_len;
             let pad_str: String = std::iter::repeat_n(fill, padding).collect();
-            format!("{sign}{pad_str}{abs_str}")
+            format!("{sign}{pad_str}{body}")
         } else {
-            format!("{sign}{abs_str}")
+            format!("{sign}{body}")
         }
     } else {
-        let value = format!("{sign}{abs_str}");
+        let value = format!("{sign}{body}");
         pad_string(&value, spec.width, align, spec.fill)
     }
 }
@@ -718,6 +745,370 @@ pub fn format_float_percent(f: f64, spec: &ParsedFormatSpec) -> String {
     pad_string(&value, spec.width, align, spec.fill)
 }
 
+// ============================================================================
+// str.format() templates
+// ============================================================================
+
+/// A piece of a `str.format()` template.
+#[derive(Debug)]
+pub enum FormatTemplatePart<'a> {
+    /// Literal text, with `{{` and `}}` already unescaped.
+    Literal(&'a str),
+    /// A replacement field like `{0.name!r:>10}`.
+    Field {
+        /// The argument index or name, followed by any attribute and item lookups.
+        name: &'a str,
+        conversion: ConversionFlag,
+        /// The format spec, which may contain nested replacement fields like `{:{width}}`.
+        spec: &'a str,
+    },
+}
+
+/// The argument a `str.format()` replacement field refers to.
+#[derive(Debug, Clone, Copy)]
+pub enum FormatFieldArg<'a> {
+    /// `{}` - the next positional argument.
+    Auto,
+    /// `{0}` - a positional argument by index.
+    Index(usize),
+    /// `{name}` - a keyword argument, or a key of the mapping for `str.format_map()`.
+    Name(&'a str),
+}
+
+/// An attribute or item lookup following the argument of a replacement field.
+#[derive(Debug, Clone, Copy)]
+pub enum FormatFieldLookup<'a> {
+    /// `.name`
+    Attr(&'a str),
+    /// `[0]` - item lookup with an integer.
+    Index(usize),
+    /// `[key]` - item lookup with a string.
+    Key(&'a str),
+}
+
+/// Splits a `str.format()` template into literal text and replacement fields.
+///
+/// Nested replacement fields in format specs are left in the spec, to be rendered
+/// before the spec is parsed.
+pub fn parse_format_template(template: &str) -> Result<Vec<FormatTemplatePart<'_>>, FormatError> {
+    let bytes = template.as_bytes();
+    let mut parts = Vec::new();
+    let mut literal_start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            // Doubled braces: keep the first as literal text and skip the second
+            b'{' | b'}' if bytes.get(i + 1) == Some(&bytes[i]) => {
+                parts.push(FormatTemplatePart::Literal(&template[literal_start..=i]));
+                i += 2;
+                literal_start = i;
+            }
+            b'}' => {
+                return Err(FormatError::ValueError(
+                    "Single '}' encountered in format string".to_owned(),
+                ));
+            }
+            b'{' => {
+                if literal_start < i {
+                    parts.push(FormatTemplatePart::Literal(&template[literal_start..i]));
+                }
+                // Find the matching '}', allowing nested fields in the format spec
+                let mut depth = 1;
+                let mut end = i + 1;
+                while end < bytes.len() {
+                    match bytes[end] {
+                        b'{' => depth += 1,
+                        b'}' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    end += 1;
+                }
+                if end == bytes.len() {
+                    let msg = if i + 1 == bytes.len() {
+                        "Single '{' encountered in format string"
+                    } else {
+                        "expected '}' before end of string"
+                    };
+                    return Err(FormatError::ValueError(msg.to_owned()));
+                }
+                parts.push(parse_format_field(&template[i + 1..end])?);
+                i = end + 1;
+                literal_start = i;
+            }
+            _ => i += 1,
+        }
+    }
+    if literal_start < bytes.len() {
+        parts.push(FormatTemplatePart::Literal(&template[literal_start..]));
+    }
+    Ok(parts)
+}
+
+/// Parses the contents of a replacement field, between the braces.
+fn parse_format_field(field: &str) -> Result<FormatTemplatePart<'_>, FormatError> {
+    let bytes = field.as_bytes();
+
+    // The field name ends at '!' or ':', except inside an item lookup
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'[' => {
+                while i < bytes.len() && bytes[i] != b']' {
+                    i += 1;
+                }
+            }
+            b'!' | b':' => break,
+            b'{' => return Err(FormatError::ValueError("unexpected '{' in field name".to_owned())),
+            _ => {}
+        }
+        i += 1;
+    }
+    let i = i.min(bytes.len());
+    let name = &field[..i];
+
+    let mut conversion = ConversionFlag::None;
+    let mut spec_start = i;
+    if bytes.get(i) == Some(&b'!') {
+        let Some(c) = field[i + 1..].chars().next() else {
+            return Err(FormatError::ValueError("unmatched '{' in format spec".to_owned()));
+        };
+        conversion = match c {
+            's' => ConversionFlag::Str,
+            'r' => ConversionFlag::Repr,
+            'a' => ConversionFlag::Ascii,
+            _ => return Err(FormatError::ValueError(format!("Unknown conversion specifier {c}"))),
+        };
+        spec_start = i + 1 + c.len_utf8();
+        if spec_start < bytes.len() && bytes[spec_start] != b':' {
+            return Err(FormatError::ValueError(
+                "expected ':' after conversion specifier".to_owned(),
+            ));
+        }
+    }
+    let spec = field.get(spec_start + 1..).unwrap_or("");
+    Ok(FormatTemplatePart::Field { name, conversion, spec })
+}
+
+/// Parses the name of a replacement field into the argument it refers to and the
+/// attribute and item lookups which follow, e.g. `0.name[key]`.
+pub fn parse_format_field_name(name: &str) -> Result<(FormatFieldArg<'_>, Vec<FormatFieldLookup<'_>>), FormatError> {
+    let arg_end = name.find(['.', '[']).unwrap_or(name.len());
+    let arg = &name[..arg_end];
+    let arg = if arg.is_empty() {
+        FormatFieldArg::Auto
+    } else if let Some(index) = parse_format_index(arg)? {
+        FormatFieldArg::Index(index)
+    } else {
+        FormatFieldArg::Name(arg)
+    };
+
+    let mut lookups = Vec::new();
+    let mut rest = &name[arg_end..];
+    while !rest.is_empty() {
+        if let Some(after_dot) = rest.strip_prefix('.') {
+            let attr_end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+            if attr_end == 0 {
+                return Err(FormatError::ValueError("Empty attribute in format string".to_owned()));
+            }
+            lookups.push(FormatFieldLookup::Attr(&after_dot[..attr_end]));
+            rest = &after_dot[attr_end..];
+        } else if let Some(after_bracket) = rest.strip_prefix('[') {
+            let Some(key_end) = after_bracket.find(']') else {
+                return Err(FormatError::ValueError("Missing ']' in format string".to_owned()));
+            };
+            let key = &after_bracket[..key_end];
+            if key.is_empty() {
+                return Err(FormatError::ValueError("Empty attribute in format string".to_owned()));
+            }
+            lookups.push(match parse_format_index(key)? {
+                Some(index) => FormatFieldLookup::Index(index),
+                None => FormatFieldLookup::Key(key),
+            });
+            rest = &after_bracket[key_end + 1..];
+        } else {
+            return Err(FormatError::ValueError(
+                "Only '.' or '[' may follow ']' in format field specifier".to_owned(),
+            ));
+        }
+    }
+    Ok((arg, lookups))
+}
+
+/// Parses an argument index or item index of a replacement field, returning `None` if
+/// it isn't all digits.
+fn parse_format_index(s: &str) -> Result<Option<usize>, FormatError> {
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Ok(None);
+    }
+    s.parse()
+        .map(Some)
+        .map_err(|_| FormatError::ValueError("Too many decimal digits in format string".to_owned()))
+}
+
+// ============================================================================
+// printf-style formatting
+// ============================================================================
+
+/// A width or precision of a printf-style conversion specifier.
+#[derive(Debug, Clone, Copy)]
+pub enum PercentCount {
+    Fixed(usize),
+    /// `*` - taken from the next argument.
+    FromArgs,
+}
+
+/// A printf-style conversion specifier, like `%-10.2f` or `%(name)s`.
+#[derive(Debug)]
+pub struct PercentSpec<'a> {
+    /// The mapping key of `%(key)s`.
+    pub key: Option<&'a [u8]>,
+    /// `-` flag: pad on the right.
+    pub left_align: bool,
+    /// `+` or ` ` flag: sign for positive numbers.
+    pub sign: Option<char>,
+    /// `0` flag: pad numbers with zeros.
+    pub zero_pad: bool,
+    /// `#` flag: add a base prefix for `%x` and `%o`.
+    pub alternate: bool,
+    pub width: Option<PercentCount>,
+    pub precision: Option<PercentCount>,
+    /// The conversion character, like `s` or `d`. Not validated.
+    pub conversion: u8,
+    /// Index of the first byte after the specifier.
+    pub end: usize,
+}
+
+impl PercentSpec<'_> {
+    /// Builds the format spec used by the f-string formatting functions, given the
+    /// resolved width and precision.
+    pub fn format_spec(&self, left_align: bool, width: usize, precision: Option<usize>) -> ParsedFormatSpec {
+        ParsedFormatSpec {
+            fill: ' ',
+            align: Some(if left_align { '<' } else { '>' }),
+            sign: self.sign,
+            zero_pad: self.zero_pad && !left_align,
+            width,
+            precision,
+            type_char: None,
+        }
+    }
+}
+
+/// Parses the printf-style conversion specifier starting at `start`, just after a `%`.
+///
+/// Works on bytes so it can be used for both `str` and `bytes` templates; all the
+/// characters of a specifier are ASCII.
+pub fn parse_percent_spec(template: &[u8], start: usize) -> Result<PercentSpec<'_>, FormatError> {
+    let incomplete = || FormatError::ValueError("incomplete format".to_owned());
+    let mut i = start;
+
+    let mut key = None;
+    if template.get(i) == Some(&b'(') {
+        // Keys may contain balanced parentheses
+        let mut depth = 1;
+        let key_start = i + 1;
+        i += 1;
+        while depth > 0 {
+            match template.get(i) {
+                Some(b'(') => depth += 1,
+                Some(b')') => depth -= 1,
+                Some(_) => {}
+                None => return Err(FormatError::ValueError("incomplete format key".to_owned())),
+            }
+            i += 1;
+        }
+        key = Some(&template[key_start..i - 1]);
+    }
+
+    let mut spec = PercentSpec {
+        key,
+        left_align: false,
+        sign: None,
+        zero_pad: false,
+        alternate: false,
+        width: None,
+        precision: None,
+        conversion: 0,
+        end: 0,
+    };
+    while let Some(&flag) = template.get(i) {
+        match flag {
+            b'-' => spec.left_align = true,
+            b'+' => spec.sign = Some('+'),
+            b' ' => spec.sign = spec.sign.or(Some(' ')),
+            b'0' => spec.zero_pad = true,
+            b'#' => spec.alternate = true,
+            _ => break,
+        }
+        i += 1;
+    }
+
+    spec.width = parse_percent_count(template, &mut i);
+    if template.get(i) == Some(&b'.') {
+        i += 1;
+        spec.precision = Some(parse_percent_count(template, &mut i).unwrap_or(PercentCount::Fixed(0)));
+    }
+
+    // Length modifiers are accepted and ignored, like CPython
+    while matches!(template.get(i), Some(b'h' | b'l' | b'L')) {
+        i += 1;
+    }
+
+    spec.conversion = *template.get(i).ok_or_else(incomplete)?;
+    spec.end = i + 1;
+    Ok(spec)
+}
+
+/// Parses a width or precision at `*i`, advancing past it.
+fn parse_percent_count(template: &[u8], i: &mut usize) -> Option<PercentCount> {
+    if template.get(*i) == Some(&b'*') {
+        *i += 1;
+        return Some(PercentCount::FromArgs);
+    }
+    let digits_start = *i;
+    while template.get(*i).is_some_and(u8::is_ascii_digit) {
+        *i += 1;
+    }
+    if *i == digits_start {
+        return None;
+    }
+    let digits = std::str::from_utf8(&template[digits_start..*i]).expect("digits are ASCII");
+    Some(PercentCount::Fixed(digits.parse().unwrap_or(usize::MAX)))
+}
+
+/// Formats an integer for printf-style formatting (`%d`, `%x`, `%o` and friends),
+/// given its sign and its digits in the target base.
+///
+/// The precision is the minimum number of digits, and `prefix` (like `0x` for `%#x`)
+/// goes between the sign and the digits. Zero-padding goes after the prefix.
+pub fn format_percent_int(negative: bool, digits: &str, prefix: &str, spec: &ParsedFormatSpec) -> String {
+    let zeros = spec.precision.unwrap_or(0).saturating_sub(digits.len());
+    let digits = format!("{}{digits}", "0".repeat(zeros));
+
+    let sign = if negative {
+        "-"
+    } else {
+        match spec.sign {
+            Some('+') => "+",
+            Some(' ') => " ",
+            _ => "",
+        }
+    };
+
+    if spec.zero_pad {
+        let padding = spec.width.saturating_sub(sign.len() + prefix.len() + digits.len());
+        format!("{sign}{prefix}{}{digits}", "0".repeat(padding))
+    } else {
+        let value = format!("{sign}{prefix}{digits}");
+        pad_string(&value, spec.width, spec.align.unwrap_or('>'), spec.fill)
+    }
+}
+
 // ============================================================================
 // Helper functions
 // ============================================================================
@@ -19,8 +19,15 @@ use crate::{
     intern::{FunctionId, Interns, StringId},
     resource::{ResourceError, ResourceTracker},
     types::{
-        AttrCallResult, Bytes, Dataclass, Dict, FrozenSet, List, LongInt, Module, MontyIter, NamedTuple, Path, PyTrait,
-        Range, Set, Slice, Str, Tuple, Type, allocate_tuple,
+        AttrCallResult, BoundMethod, ByteArray, Bytes, ClassObject, CmpToKey, Complex, Dataclass, Date, DateTime,
+        Decimal, DecimalContext, DecimalContextManager, Deque, Dict, DictView, Fraction, FrozenSet, Generator,
+        Instance, List, LongInt, LruCacheWrapper, Module, MontyIter, NamedTuple, NamedTupleType, Partial, Path,
+        PyTrait, Random, Range, ReMatch, RePattern, Set, Slice, Str, SuperProxy, Template, Time, TimeDelta, TimeZone,
+        Tuple, Type, Uuid, allocate_tuple,
+        bytearray::concat_bytearray,
+        class::{has_custom_hash, is_instance_hashable},
+        decimal::Context,
+        r#enum,
     },
     value::{EitherStr, Value},
 };
@@ -49,12 +56,22 @@ impl HeapId {
 pub(crate) enum HeapData {
     Str(Str),
     Bytes(Bytes),
+    /// A mutable sequence of bytes (`bytearray`).
+    ByteArray(ByteArray),
     List(List),
     Tuple(Tuple),
     NamedTuple(NamedTuple),
+    /// A type created by `collections.namedtuple()`, which creates `NamedTuple` instances when called.
+    NamedTupleType(NamedTupleType),
     Dict(Dict),
+    /// A live view over a dict, returned by `dict.keys()`, `dict.values()` and `dict.items()`.
+    ///
+    /// Holds a reference to the dict so iteration always sees its current contents.
+    DictView(DictView),
     Set(Set),
     FrozenSet(FrozenSet),
+    /// A double-ended queue from `collections.deque`.
+    Deque(Deque),
     /// A closure: a function that captures variables from enclosing scopes.
     ///
     /// Contains a reference to the function definition, a vector of captured cell HeapIds,
@@ -107,6 +124,20 @@ pub(crate) enum HeapData {
     /// when values fit, and promote to LongInt on overflow. When LongInt results fit back
     /// in i64, they are demoted back to `Value::Int` for performance.
     LongInt(LongInt),
+    /// A complex number (e.g., `1+2j`).
+    ///
+    /// Stored on the heap to keep `Value` enum at 16 bytes, like `LongInt`.
+    Complex(Complex),
+    /// A `datetime.date`.
+    Date(Date),
+    /// A `datetime.time`, naive or with a fixed-offset timezone.
+    Time(Time),
+    /// A `datetime.datetime`, naive or with a fixed-offset timezone.
+    DateTime(DateTime),
+    /// A `datetime.timedelta`.
+    TimeDelta(TimeDelta),
+    /// A `datetime.timezone` with a fixed UTC offset.
+    TimeZone(TimeZone),
     /// A Python module (e.g., `sys`, `typing`).
     ///
     /// Modules have a name and a dictionary of attributes. They are created by
@@ -117,6 +148,10 @@ pub(crate) enum HeapData {
     /// Contains pre-bound arguments and captured cells, ready to be awaited.
     /// When awaited, a new frame is pushed using the stored namespace.
     Coroutine(Coroutine),
+    /// A generator object from a generator function call or generator expression.
+    ///
+    /// Holds the suspended frame state between resumptions.
+    Generator(Generator),
     /// A gather() result tracking multiple coroutines/tasks.
     ///
     /// Created by asyncio.gather() and spawns tasks when awaited.
@@ -127,6 +162,46 @@ pub(crate) enum HeapData {
     /// Pure methods (name, parent, etc.) are handled directly by the VM.
     /// I/O methods (exists, read_text, etc.) yield external function calls.
     Path(Path),
+    /// A compiled regular expression from `re.compile()` and the other `re` functions.
+    RePattern(RePattern),
+    /// A successful regular expression match.
+    ///
+    /// Holds references to its pattern and to the string searched.
+    ReMatch(ReMatch),
+    /// A user-defined class created by a `class` statement.
+    ///
+    /// Holds the class name, optional base class and the class namespace
+    /// (methods and class attributes).
+    ClassObject(ClassObject),
+    /// An instance of a user-defined class, created by calling a `ClassObject`.
+    Instance(Instance),
+    /// A method bound to an instance, created by attribute access like `obj.method`.
+    BoundMethod(BoundMethod),
+    /// The proxy returned by `super()` inside a method.
+    SuperProxy(SuperProxy),
+    /// A `functools.partial` object, holding its function and arguments.
+    Partial(Partial),
+    /// A function wrapped by `functools.lru_cache()` or `functools.cache()`, holding the
+    /// function and its cache of results.
+    LruCacheWrapper(LruCacheWrapper),
+    /// A key function from `functools.cmp_to_key()`, holding its comparison function.
+    CmpToKey(CmpToKey),
+    /// A `random.Random` generator.
+    Random(Random),
+    /// A `string.Template`.
+    Template(Template),
+    /// A hash object of the `hashlib` module.
+    Hash(Hash),
+    /// A `uuid.UUID`.
+    Uuid(Uuid),
+    /// A `decimal.Decimal`.
+    Decimal(Decimal),
+    /// A `decimal.Context`, either the current context or a detached one.
+    DecimalContext(DecimalContext),
+    /// The context manager returned by `decimal.localcontext()`.
+    DecimalContextManager(DecimalContextManager),
+    /// A `fractions.Fraction`.
+    Fraction(Fraction),
 }
 
 impl HeapData {
@@ -145,9 +220,12 @@ impl HeapData {
             Self::List(_)
                 | Self::Tuple(_)
                 | Self::NamedTuple(_)
+                | Self::NamedTupleType(_)
                 | Self::Dict(_)
+                | Self::DictView(_)
                 | Self::Set(_)
                 | Self::FrozenSet(_)
+                | Self::Deque(_)
                 | Self::Closure(_, _, _)
                 | Self::FunctionDefaults(_, _)
                 | Self::Cell(_)
@@ -155,7 +233,15 @@ impl HeapData {
                 | Self::Iter(_)
                 | Self::Module(_)
                 | Self::Coroutine(_)
+                | Self::Generator(_)
                 | Self::GatherFuture(_)
+                | Self::ClassObject(_)
+                | Self::Instance(_)
+                | Self::BoundMethod(_)
+                | Self::SuperProxy(_)
+                | Self::Partial(_)
+                | Self::LruCacheWrapper(_)
+                | Self::CmpToKey(_)
         )
     }
 
@@ -175,6 +261,8 @@ impl HeapData {
             Self::Dict(dict) => dict.has_refs(),
             Self::Set(set) => set.has_refs(),
             Self::FrozenSet(fset) => fset.has_refs(),
+            Self::Deque(deque) => deque.contains_refs(),
+            Self::NamedTupleType(t) => t.defaults().iter().any(|v| matches!(v, Value::Ref(_))),
             // Closures always have refs when they have captured cells (HeapIds)
             Self::Closure(_, cells, defaults) => {
                 !cells.is_empty() || defaults.iter().any(|v| matches!(v, Value::Ref(_)))
@@ -188,6 +276,7 @@ impl HeapData {
             Self::Coroutine(coro) => {
                 !coro.frame_cells.is_empty() || coro.namespace.iter().any(|v| matches!(v, Value::Ref(_)))
             }
+            Self::Generator(generator) => generator.has_refs(),
             // GatherFutures have refs from coroutine items and results
             Self::GatherFuture(gather) => {
                 gather
@@ -199,14 +288,49 @@ impl HeapData {
                         .iter()
                         .any(|r| r.as_ref().is_some_and(|v| matches!(v, Value::Ref(_))))
             }
+            Self::ClassObject(cls) => cls.has_refs(),
+            Self::BoundMethod(method) => method.has_refs(),
+            Self::Partial(partial) => partial.has_refs(),
+            Self::LruCacheWrapper(wrapper) => wrapper.has_refs(),
+            Self::CmpToKey(key) => matches!(key.cmp(), Value::Ref(_)),
+            // Instances and super proxies always reference a class, and views their dict
+            Self::Instance(_) | Self::SuperProxy(_) | Self::DictView(_) => true,
+            // Matches always reference their pattern
+            Self::ReMatch(_) => true,
             // Leaf types cannot have refs
             Self::Str(_)
             | Self::Bytes(_)
+            | Self::ByteArray(_)
             | Self::Range(_)
             | Self::Slice(_)
             | Self::Exception(_)
             | Self::LongInt(_)
-            | Self::Path(_) => false,
+            | Self::Complex(_)
+            | Self::Date(_)
+            | Self::Time(_)
+            | Self::DateTime(_)
+            | Self::TimeDelta(_)
+            | Self::TimeZone(_)
+            | Self::Path(_)
+            | Self::RePattern(_)
+            | Self::Random(_)
+            | Self::Template(_)
+            | Self::Hash(_)
+            | Self::Uuid(_)
+            | Self::Decimal(_)
+            | Self::DecimalContext(_)
+            | Self::DecimalContextManager(_)
+            | Self::Fraction(_) => false,
+        }
+    }
+
+    /// Returns the contents of a `bytes` or `bytearray`, or `None` for any other type.
+    #[must_use]
+    pub fn as_bytes_like(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(b) => Some(b.as_slice()),
+            Self::ByteArray(b) => Some(b.as_slice()),
+            _ => None,
         }
     }
 
@@ -294,19 +418,98 @@ impl HeapData {
                 path.as_str().hash(&mut hasher);
                 Some(hasher.finish())
             }
-            // Mutable types, exceptions, iterators, modules, and async types cannot be hashed
-            // (Cell is handled specially in get_or_compute_hash)
+            // Patterns are immutable and hashable
+            Self::RePattern(pattern) => {
+                let mut hasher = DefaultHasher::new();
+                discriminant(self).hash(&mut hasher);
+                pattern.pattern().hash(&mut hasher);
+                pattern.regex().flags().hash(&mut hasher);
+                Some(hasher.finish())
+            }
+            // UUIDs are immutable and hashable
+            Self::Uuid(uuid) => {
+                let mut hasher = DefaultHasher::new();
+                discriminant(self).hash(&mut hasher);
+                uuid.hash(&mut hasher);
+                Some(hasher.finish())
+            }
+            // Decimals and fractions hash like the ints and floats they equal
+            Self::Decimal(decimal) => Some(decimal.hash()),
+            Self::Fraction(fraction) => Some(fraction.hash()),
+            // Mutable types, exceptions, iterators, modules, async types, random generators,
+            // templates, hash objects and decimal contexts cannot be hashed (Cell is handled
+            // specially in get_or_compute_hash)
             Self::List(_)
+            | Self::ByteArray(_)
             | Self::Dict(_)
+            | Self::DictView(_)
             | Self::Set(_)
+            | Self::Deque(_)
             | Self::Cell(_)
             | Self::Exception(_)
+            | Self::ReMatch(_)
             | Self::Iter(_)
             | Self::Module(_)
             | Self::Coroutine(_)
-            | Self::GatherFuture(_) => None,
+            | Self::Generator(_)
+            | Self::GatherFuture(_)
+            | Self::Random(_)
+            | Self::Template(_)
+            | Self::Hash(_)
+            | Self::DecimalContext(_)
+            | Self::DecimalContextManager(_) => None,
             // LongInt is immutable and hashable
             Self::LongInt(li) => Some(li.hash()),
+            // Complex is immutable and hashable
+            Self::Complex(c) => {
+                let mut hasher = DefaultHasher::new();
+                discriminant(self).hash(&mut hasher);
+                c.real().to_bits().hash(&mut hasher);
+                c.imag().to_bits().hash(&mut hasher);
+                Some(hasher.finish())
+            }
+            // Dates and times are immutable and hash by the same key they compare by,
+            // so aware times with different timezones but the same UTC time hash equal
+            Self::Date(date) => {
+                let mut hasher = DefaultHasher::new();
+                discriminant(self).hash(&mut hasher);
+                date.hash(&mut hasher);
+                Some(hasher.finish())
+            }
+            Self::Time(time) => {
+                let mut hasher = DefaultHasher::new();
+                discriminant(self).hash(&mut hasher);
+                time.hash_key().hash(&mut hasher);
+                Some(hasher.finish())
+            }
+            Self::DateTime(dt) => {
+                let mut hasher = DefaultHasher::new();
+                discriminant(self).hash(&mut hasher);
+                dt.hash_key().hash(&mut hasher);
+                Some(hasher.finish())
+            }
+            Self::TimeDelta(delta) => {
+                let mut hasher = DefaultHasher::new();
+                discriminant(self).hash(&mut hasher);
+                delta.hash(&mut hasher);
+                Some(hasher.finish())
+            }
+            Self::TimeZone(tz) => {
+                let mut hasher = DefaultHasher::new();
+                discriminant(self).hash(&mut hasher);
+                tz.offset().hash(&mut hasher);
+                Some(hasher.finish())
+            }
+            // Classes and instances are hashed by identity in get_or_compute_hash;
+            // bound methods and super proxies are not hashable here
+            Self::ClassObject(_)
+            | Self::NamedTupleType(_)
+            | Self::Instance(_)
+            | Self::BoundMethod(_)
+            | Self::SuperProxy(_)
+            | Self::Partial(_)
+            | Self::LruCacheWrapper(_)
+            | Self::CmpToKey(_) => None,
         }
     }
 }
@@ -320,12 +523,16 @@ impl PyTrait for HeapData {
         match self {
             Self::Str(s) => s.py_type(heap),
             Self::Bytes(b) => b.py_type(heap),
+            Self::ByteArray(b) => b.py_type(heap),
             Self::List(l) => l.py_type(heap),
             Self::Tuple(t) => t.py_type(heap),
             Self::NamedTuple(nt) => nt.py_type(heap),
             Self::Dict(d) => d.py_type(heap),
+            Self::DictView(view) => view.py_type(heap),
             Self::Set(s) => s.py_type(heap),
             Self::FrozenSet(fs) => fs.py_type(heap),
+            Self::Deque(deque) => deque.py_type(heap),
+            Self::NamedTupleType(t) => t.py_type(heap),
             Self::Closure(_, _, _) | Self::FunctionDefaults(_, _) => Type::Function,
             Self::Cell(_) => Type::Cell,
             Self::Range(_) => Type::Range,
@@ -335,9 +542,33 @@ impl PyTrait for HeapData {
             Self::Iter(_) => Type::Iterator,
             // LongInt is still `int` in Python - it's an implementation detail
             Self::LongInt(_) => Type::Int,
+            Self::Complex(c) => c.py_type(heap),
+            Self::Date(date) => date.py_type(heap),
+            Self::Time(time) => time.py_type(heap),
+            Self::DateTime(dt) => dt.py_type(heap),
+            Self::TimeDelta(delta) => delta.py_type(heap),
+            Self::TimeZone(tz) => tz.py_type(heap),
             Self::Module(_) => Type::Module,
             Self::Coroutine(_) | Self::GatherFuture(_) => Type::Coroutine,
+            Self::Generator(_) => Type::Generator,
             Self::Path(p) => p.py_type(heap),
+            Self::RePattern(pattern) => pattern.py_type(heap),
+            Self::ReMatch(re_match) => re_match.py_type(heap),
+            Self::ClassObject(cls) => cls.py_type(heap),
+            Self::Instance(inst) => inst.py_type(heap),
+            Self::BoundMethod(method) => method.py_type(heap),
+            Self::Partial(partial) => partial.py_type(heap),
+            Self::LruCacheWrapper(wrapper) => wrapper.py_type(heap),
+            Self::CmpToKey(key) => key.py_type(heap),
+            Self::Random(random) => random.py_type(heap),
+            Self::Template(template) => template.py_type(heap),
+            Self::Hash(hash) => hash.py_type(heap),
+            Self::Uuid(uuid) => uuid.py_type(heap),
+            Self::Decimal(decimal) => decimal.py_type(heap),
+            Self::DecimalContext(context) => context.py_type(heap),
+            Self::DecimalContextManager(manager) => manager.py_type(heap),
+            Self::Fraction(fraction) => fraction.py_type(heap),
+            Self::SuperProxy(proxy) => proxy.py_type(heap),
         }
     }
 
@@ -345,12 +576,16 @@ impl PyTrait for HeapData {
         match self {
             Self::Str(s) => s.py_estimate_size(),
             Self::Bytes(b) => b.py_estimate_size(),
+            Self::ByteArray(b) => b.py_estimate_size(),
             Self::List(l) => l.py_estimate_size(),
             Self::Tuple(t) => t.py_estimate_size(),
             Self::NamedTuple(nt) => nt.py_estimate_size(),
             Self::Dict(d) => d.py_estimate_size(),
+            Self::DictView(view) => view.py_estimate_size(),
             Self::Set(s) => s.py_estimate_size(),
             Self::FrozenSet(fs) => fs.py_estimate_size(),
+            Self::Deque(deque) => deque.py_estimate_size(),
+            Self::NamedTupleType(t) => t.py_estimate_size(),
             // TODO: should include size of captured cells and defaults
             Self::Closure(_, _, _) | Self::FunctionDefaults(_, _) => 0,
             Self::Cell(v) => std::mem::size_of::<Value>() + v.py_estimate_size(),
@@ -358,14 +593,21 @@ impl PyTrait for HeapData {
             Self::Slice(s) => s.py_estimate_size(),
             Self::Exception(e) => std::mem::size_of::<SimpleException>() + e.arg().map_or(0, String::len),
             Self::Dataclass(dc) => dc.py_estimate_size(),
-            Self::Iter(_) => std::mem::size_of::<MontyIter>(),
+            Self::Iter(iter) => iter.estimate_size(),
             Self::LongInt(li) => li.estimate_size(),
+            Self::Complex(c) => c.py_estimate_size(),
+            Self::Date(date) => date.py_estimate_size(),
+            Self::Time(time) => time.py_estimate_size(),
+            Self::DateTime(dt) => dt.py_estimate_size(),
+            Self::TimeDelta(delta) => delta.py_estimate_size(),
+            Self::TimeZone(tz) => tz.py_estimate_size(),
             Self::Module(m) => std::mem::size_of::<Module>() + m.attrs().py_estimate_size(),
             Self::Coroutine(coro) => {
                 std::mem::size_of::<Coroutine>()
                     + coro.namespace.len() * std::mem::size_of::<Value>()
                     + coro.frame_cells.len() * std::mem::size_of::<HeapId>()
             }
+            Self::Generator(generator) => generator.estimate_size(),
             Self::GatherFuture(gather) => {
                 std::mem::size_of::<GatherFuture>()
                     + gather.items.len() * std::mem::size_of::<crate::asyncio::GatherItem>()
@@ -373,6 +615,23 @@ impl PyTrait for HeapData {
                     + gather.pending_calls.len() * std::mem::size_of::<crate::asyncio::CallId>()
             }
             Self::Path(p) => p.py_estimate_size(),
+            Self::RePattern(pattern) => pattern.py_estimate_size(),
+            Self::ReMatch(re_match) => re_match.py_estimate_size(),
+            Self::ClassObject(cls) => cls.py_estimate_size(),
+            Self::Instance(inst) => inst.py_estimate_size(),
+            Self::BoundMethod(method) => method.py_estimate_size(),
+            Self::Partial(partial) => partial.py_estimate_size(),
+            Self::LruCacheWrapper(wrapper) => wrapper.py_estimate_size(),
+            Self::CmpToKey(key) => key.py_estimate_size(),
+            Self::Random(random) => random.py_estimate_size(),
+            Self::Template(template) => template.py_estimate_size(),
+            Self::Hash(hash) => hash.py_estimate_size(),
+            Self::Uuid(uuid) => uuid.py_estimate_size(),
+            Self::Decimal(decimal) => decimal.py_estimate_size(),
+            Self::DecimalContext(context) => context.py_estimate_size(),
+            Self::DecimalContextManager(manager) => manager.py_estimate_size(),
+            Self::Fraction(fraction) => fraction.py_estimate_size(),
+            Self::SuperProxy(proxy) => proxy.py_estimate_size(),
         }
     }
 
@@ -380,14 +639,17 @@ impl PyTrait for HeapData {
         match self {
             Self::Str(s) => PyTrait::py_len(s, heap, interns),
             Self::Bytes(b) => PyTrait::py_len(b, heap, interns),
+            Self::ByteArray(b) => PyTrait::py_len(b, heap, interns),
             Self::List(l) => PyTrait::py_len(l, heap, interns),
             Self::Tuple(t) => PyTrait::py_len(t, heap, interns),
             Self::NamedTuple(nt) => PyTrait::py_len(nt, heap, interns),
             Self::Dict(d) => PyTrait::py_len(d, heap, interns),
+            Self::DictView(view) => PyTrait::py_len(view, heap, interns),
             Self::Set(s) => PyTrait::py_len(s, heap, interns),
             Self::FrozenSet(fs) => PyTrait::py_len(fs, heap, interns),
+            Self::Deque(deque) => PyTrait::py_len(deque, heap, interns),
             Self::Range(r) => Some(r.len()),
-            // Cells, Slices, Exceptions, Dataclasses, Iterators, LongInts, Modules, Paths, and async types don't have length
+            // Cells, Slices, Exceptions, Dataclasses, Iterators, numbers, Modules, Paths, and async types don't have length
             Self::Cell(_)
             | Self::Closure(_, _, _)
             | Self::FunctionDefaults(_, _)
@@ -396,10 +658,35 @@ impl PyTrait for HeapData {
             | Self::Dataclass(_)
             | Self::Iter(_)
             | Self::LongInt(_)
+            | Self::Complex(_)
+            | Self::Date(_)
+            | Self::Time(_)
+            | Self::DateTime(_)
+            | Self::TimeDelta(_)
+            | Self::TimeZone(_)
             | Self::Module(_)
             | Self::Coroutine(_)
+            | Self::Generator(_)
             | Self::GatherFuture(_)
-            | Self::Path(_) => None,
+            | Self::Path(_)
+            | Self::RePattern(_)
+            | Self::ReMatch(_)
+            | Self::ClassObject(_)
+            | Self::NamedTupleType(_)
+            | Self::Instance(_)
+            | Self::BoundMethod(_)
+            | Self::SuperProxy(_)
+            | Self::Partial(_)
+            | Self::LruCacheWrapper(_)
+            | Self::CmpToKey(_)
+            | Self::Random(_)
+            | Self::Template(_)
+            | Self::Hash(_)
+            | Self::Uuid(_)
+            | Self::Decimal(_)
+            | Self::DecimalContext(_)
+            | Self::DecimalContextManager(_)
+            | Self::Fraction(_) => None,
         }
     }
 
@@ -407,6 +694,9 @@ impl PyTrait for HeapData {
         match (self, other) {
             (Self::Str(a), Self::Str(b)) => a.py_eq(b, heap, interns),
             (Self::Bytes(a), Self::Bytes(b)) => a.py_eq(b, heap, interns),
+            (Self::ByteArray(a), Self::ByteArray(b)) => a.py_eq(b, heap, interns),
+            // bytes and bytearray compare by content
+            (Self::Bytes(a), Self::ByteArray(b)) | (Self::ByteArray(b), Self::Bytes(a)) => a.as_slice() == b.as_slice(),
             (Self::List(a), Self::List(b)) => a.py_eq(b, heap, interns),
             (Self::Tuple(a), Self::Tuple(b)) => a.py_eq(b, heap, interns),
             (Self::NamedTuple(a), Self::NamedTuple(b)) => a.py_eq(b, heap, interns),
@@ -423,25 +713,55 @@ impl PyTrait for HeapData {
                     .all(|(a, b)| a.py_eq(b, heap, interns))
             }
             (Self::Dict(a), Self::Dict(b)) => a.py_eq(b, heap, interns),
+            // keys and items views compare like sets, with each other and with sets
+            (Self::DictView(view), other) | (other, Self::DictView(view)) if view.is_set_like() => {
+                view.set_like_eq(other, heap, interns)
+            }
             (Self::Set(a), Self::Set(b)) => a.py_eq(b, heap, interns),
             (Self::FrozenSet(a), Self::FrozenSet(b)) => a.py_eq(b, heap, interns),
+            (Self::Deque(a), Self::Deque(b)) => a.py_eq(b, heap, interns),
             (Self::Closure(a_id, a_cells, _), Self::Closure(b_id, b_cells, _)) => *a_id == *b_id && a_cells == b_cells,
             (Self::FunctionDefaults(a_id, _), Self::FunctionDefaults(b_id, _)) => *a_id == *b_id,
             (Self::Range(a), Self::Range(b)) => a.py_eq(b, heap, interns),
             (Self::Dataclass(a), Self::Dataclass(b)) => a.py_eq(b, heap, interns),
             // LongInt equality
             (Self::LongInt(a), Self::LongInt(b)) => a == b,
+            (Self::Complex(a), Self::Complex(b)) => a.py_eq(b, heap, interns),
+            (Self::Date(a), Self::Date(b)) => a.py_eq(b, heap, interns),
+            (Self::Time(a), Self::Time(b)) => a.py_eq(b, heap, interns),
+            (Self::DateTime(a), Self::DateTime(b)) => a.py_eq(b, heap, interns),
+            (Self::TimeDelta(a), Self::TimeDelta(b)) => a.py_eq(b, heap, interns),
+            (Self::TimeZone(a), Self::TimeZone(b)) => a.py_eq(b, heap, interns),
             // Slice equality
             (Self::Slice(a), Self::Slice(b)) => a.py_eq(b, heap, interns),
             // Path equality
             (Self::Path(a), Self::Path(b)) => a.py_eq(b, heap, interns),
+            (Self::RePattern(a), Self::RePattern(b)) => a.py_eq(b, heap, interns),
+            (Self::BoundMethod(a), Self::BoundMethod(b)) => a.py_eq(b, heap, interns),
+            (Self::Uuid(a), Self::Uuid(b)) => a.py_eq(b, heap, interns),
+            (Self::Decimal(a), Self::Decimal(b)) => a.py_eq(b, heap, interns),
+            (Self::DecimalContext(a), Self::DecimalContext(b)) => a.py_eq(b, heap, interns),
+            (Self::Fraction(a), Self::Fraction(b)) => a.py_eq(b, heap, interns),
             // Cells, Exceptions, Iterators, Modules, and async types compare by identity only (handled at Value level via HeapId comparison)
             (Self::Cell(_), Self::Cell(_))
             | (Self::Exception(_), Self::Exception(_))
             | (Self::Iter(_), Self::Iter(_))
+            | (Self::DictView(_), Self::DictView(_))
+            | (Self::ReMatch(_), Self::ReMatch(_))
             | (Self::Module(_), Self::Module(_))
             | (Self::Coroutine(_), Self::Coroutine(_))
-            | (Self::GatherFuture(_), Self::GatherFuture(_)) => false,
+            | (Self::Generator(_), Self::Generator(_))
+            | (Self::GatherFuture(_), Self::GatherFuture(_))
+            | (Self::ClassObject(_), Self::ClassObject(_))
+            | (Self::NamedTupleType(_), Self::NamedTupleType(_))
+            | (Self::Instance(_), Self::Instance(_))
+            | (Self::SuperProxy(_), Self::SuperProxy(_))
+            | (Self::Partial(_), Self::Partial(_))
+            | (Self::LruCacheWrapper(_), Self::LruCacheWrapper(_))
+            | (Self::CmpToKey(_), Self::CmpToKey(_))
+            | (Self::Random(_), Self::Random(_))
+            | (Self::Template(_), Self::Template(_))
+            | (Self::Hash(_), Self::Hash(_)) => false,
             _ => false, // Different types are never equal
         }
     }
@@ -450,12 +770,16 @@ impl PyTrait for HeapData {
         match self {
             Self::Str(s) => s.py_dec_ref_ids(stack),
             Self::Bytes(b) => b.py_dec_ref_ids(stack),
+            Self::ByteArray(b) => b.py_dec_ref_ids(stack),
             Self::List(l) => l.py_dec_ref_ids(stack),
             Self::Tuple(t) => t.py_dec_ref_ids(stack),
             Self::NamedTuple(nt) => nt.py_dec_ref_ids(stack),
             Self::Dict(d) => d.py_dec_ref_ids(stack),
+            Self::DictView(view) => view.py_dec_ref_ids(stack),
             Self::Set(s) => s.py_dec_ref_ids(stack),
             Self::FrozenSet(fs) => fs.py_dec_ref_ids(stack),
+            Self::Deque(deque) => deque.py_dec_ref_ids(stack),
+            Self::NamedTupleType(t) => t.py_dec_ref_ids(stack),
             Self::Closure(_, cells, defaults) => {
                 // Decrement ref count for captured cells
                 stack.extend(cells.iter().copied());
@@ -482,6 +806,7 @@ impl PyTrait for HeapData {
                     value.py_dec_ref_ids(stack);
                 }
             }
+            Self::Generator(generator) => generator.py_dec_ref_ids(stack),
             Self::GatherFuture(gather) => {
                 // Decrement ref count for coroutine HeapIds
                 for item in &gather.items {
@@ -494,8 +819,36 @@ impl PyTrait for HeapData {
                     result.py_dec_ref_ids(stack);
                 }
             }
-            // Range, Slice, Exception, LongInt, and Path have no nested heap references
-            Self::Range(_) | Self::Slice(_) | Self::Exception(_) | Self::LongInt(_) | Self::Path(_) => {}
+            Self::ClassObject(cls) => cls.py_dec_ref_ids(stack),
+            Self::Instance(inst) => inst.py_dec_ref_ids(stack),
+            Self::BoundMethod(method) => method.py_dec_ref_ids(stack),
+            Self::Partial(partial) => partial.py_dec_ref_ids(stack),
+            Self::LruCacheWrapper(wrapper) => wrapper.py_dec_ref_ids(stack),
+            Self::CmpToKey(key) => key.py_dec_ref_ids(stack),
+            Self::SuperProxy(proxy) => proxy.py_dec_ref_ids(stack),
+            Self::ReMatch(re_match) => re_match.py_dec_ref_ids(stack),
+            // Range, Slice, Exception, numbers, dates and times, Path, patterns and random
+            // generators have no nested heap references
+            Self::Range(_)
+            | Self::Slice(_)
+            | Self::Exception(_)
+            | Self::LongInt(_)
+            | Self::Complex(_)
+            | Self::Date(_)
+            | Self::Time(_)
+            | Self::DateTime(_)
+            | Self::TimeDelta(_)
+            | Self::TimeZone(_)
+            | Self::Path(_)
+            | Self::RePattern(_)
+            | Self::Random(_)
+            | Self::Template(_)
+            | Self::Hash(_)
+            | Self::Uuid(_)
+            | Self::Decimal(_)
+            | Self::DecimalContext(_)
+            | Self::DecimalContextManager(_)
+            | Self::Fraction(_) => {}
         }
     }
 
@@ -503,12 +856,16 @@ impl PyTrait for HeapData {
         match self {
             Self::Str(s) => s.py_bool(heap, interns),
             Self::Bytes(b) => b.py_bool(heap, interns),
+            Self::ByteArray(b) => b.py_bool(heap, interns),
             Self::List(l) => l.py_bool(heap, interns),
             Self::Tuple(t) => t.py_bool(heap, interns),
             Self::NamedTuple(nt) => nt.py_bool(heap, interns),
             Self::Dict(d) => d.py_bool(heap, interns),
+            Self::DictView(view) => view.py_bool(heap, interns),
             Self::Set(s) => s.py_bool(heap, interns),
             Self::FrozenSet(fs) => fs.py_bool(heap, interns),
+            Self::Deque(deque) => deque.py_bool(heap, interns),
+            Self::NamedTupleType(_) => true,
             Self::Closure(_, _, _) | Self::FunctionDefaults(_, _) => true,
             Self::Cell(_) => true, // Cells are always truthy
             Self::Range(r) => r.py_bool(heap, interns),
@@ -517,10 +874,30 @@ impl PyTrait for HeapData {
             Self::Dataclass(dc) => dc.py_bool(heap, interns),
             Self::Iter(_) => true, // Iterators are always truthy
             Self::LongInt(li) => !li.is_zero(),
+            Self::Complex(c) => c.py_bool(heap, interns),
+            Self::Date(_) | Self::Time(_) | Self::DateTime(_) | Self::TimeZone(_) => true,
+            Self::TimeDelta(delta) => delta.py_bool(heap, interns),
             Self::Module(_) => true,       // Modules are always truthy
             Self::Coroutine(_) => true,    // Coroutines are always truthy
+            Self::Generator(_) => true,    // Generators are always truthy
             Self::GatherFuture(_) => true, // GatherFutures are always truthy
             Self::Path(p) => p.py_bool(heap, interns),
+            Self::RePattern(_) | Self::ReMatch(_) => true,
+            Self::ClassObject(cls) => cls.py_bool(heap, interns),
+            Self::Instance(inst) => inst.py_bool(heap, interns),
+            Self::BoundMethod(method) => method.py_bool(heap, interns),
+            Self::Partial(partial) => partial.py_bool(heap, interns),
+            Self::LruCacheWrapper(wrapper) => wrapper.py_bool(heap, interns),
+            Self::CmpToKey(key) => key.py_bool(heap, interns),
+            Self::Random(random) => random.py_bool(heap, interns),
+            Self::Template(template) => template.py_bool(heap, interns),
+            Self::Hash(hash) => hash.py_bool(heap, interns),
+            Self::Uuid(uuid) => uuid.py_bool(heap, interns),
+            Self::Decimal(decimal) => decimal.py_bool(heap, interns),
+            Self::DecimalContext(context) => context.py_bool(heap, interns),
+            Self::DecimalContextManager(manager) => manager.py_bool(heap, interns),
+            Self::Fraction(fraction) => fraction.py_bool(heap, interns),
+            Self::SuperProxy(proxy) => proxy.py_bool(heap, interns),
         }
     }
 
@@ -534,12 +911,16 @@ impl PyTrait for HeapData {
         match self {
             Self::Str(s) => s.py_repr_fmt(f, heap, heap_ids, interns),
             Self::Bytes(b) => b.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::ByteArray(b) => b.py_repr_fmt(f, heap, heap_ids, interns),
             Self::List(l) => l.py_repr_fmt(f, heap, heap_ids, interns),
             Self::Tuple(t) => t.py_repr_fmt(f, heap, heap_ids, interns),
             Self::NamedTuple(nt) => nt.py_repr_fmt(f, heap, heap_ids, interns),
             Self::Dict(d) => d.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::DictView(view) => view.py_repr_fmt(f, heap, heap_ids, interns),
             Self::Set(s) => s.py_repr_fmt(f, heap, heap_ids, interns),
             Self::FrozenSet(fs) => fs.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::Deque(deque) => deque.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::NamedTupleType(t) => t.py_repr_fmt(f, heap, heap_ids, interns),
             Self::Closure(f_id, _, _) | Self::FunctionDefaults(f_id, _) => {
                 interns.get_function(*f_id).py_repr_fmt(f, interns, 0)
             }
@@ -551,14 +932,42 @@ impl PyTrait for HeapData {
             Self::Dataclass(dc) => dc.py_repr_fmt(f, heap, heap_ids, interns),
             Self::Iter(_) => write!(f, "<iterator>"),
             Self::LongInt(li) => write!(f, "{li}"),
+            Self::Complex(c) => c.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::Date(date) => date.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::Time(time) => time.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::DateTime(dt) => dt.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::TimeDelta(delta) => delta.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::TimeZone(tz) => tz.py_repr_fmt(f, heap, heap_ids, interns),
             Self::Module(m) => write!(f, "<module '{}'>", interns.get_str(m.name())),
             Self::Coroutine(coro) => {
                 let func = interns.get_function(coro.func_id);
                 let name = interns.get_str(func.name.name_id);
                 write!(f, "<coroutine object {name}>")
             }
+            Self::Generator(generator) => {
+                let func = interns.get_function(generator.func_id);
+                let name = interns.get_str(func.name.name_id);
+                write!(f, "<generator object {name}>")
+            }
             Self::GatherFuture(gather) => write!(f, "<gather({})>", gather.item_count()),
             Self::Path(p) => p.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::RePattern(pattern) => pattern.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::ReMatch(re_match) => re_match.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::ClassObject(cls) => cls.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::Instance(inst) => inst.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::BoundMethod(method) => method.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::Partial(partial) => partial.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::LruCacheWrapper(wrapper) => wrapper.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::CmpToKey(key) => key.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::Random(random) => random.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::Template(template) => template.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::Hash(hash) => hash.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::Uuid(uuid) => uuid.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::Decimal(decimal) => decimal.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::DecimalContext(context) => context.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::DecimalContextManager(manager) => manager.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::Fraction(fraction) => fraction.py_repr_fmt(f, heap, heap_ids, interns),
+            Self::SuperProxy(proxy) => proxy.py_repr_fmt(f, heap, heap_ids, interns),
         }
     }
 
@@ -572,6 +981,18 @@ impl PyTrait for HeapData {
             Self::Exception(e) => Cow::Owned(e.py_str()),
             // Paths return the path string without the PosixPath() wrapper
             Self::Path(p) => Cow::Owned(p.as_str().to_owned()),
+            // Dates and times use their ISO format, timezones their name
+            Self::Date(date) => date.py_str(heap, interns),
+            Self::Time(time) => time.py_str(heap, interns),
+            Self::DateTime(dt) => dt.py_str(heap, interns),
+            Self::TimeDelta(delta) => delta.py_str(heap, interns),
+            Self::TimeZone(tz) => tz.py_str(heap, interns),
+            // UUIDs use their canonical hyphenated form
+            Self::Uuid(uuid) => uuid.py_str(heap, interns),
+            Self::Decimal(decimal) => decimal.py_str(heap, interns),
+            Self::Fraction(fraction) => fraction.py_str(heap, interns),
+            // Enum members use their name or the string of their value
+            Self::Instance(inst) => inst.py_str(heap, interns),
             // All other types use repr
             _ => self.py_repr(heap, interns),
         }
@@ -586,9 +1007,17 @@ impl PyTrait for HeapData {
         match (self, other) {
             (Self::Str(a), Self::Str(b)) => a.py_add(b, heap, interns),
             (Self::Bytes(a), Self::Bytes(b)) => a.py_add(b, heap, interns),
+            (Self::ByteArray(a), Self::ByteArray(b)) => a.py_add(b, heap, interns),
+            // Mixed concatenation takes the type of the left operand
+            (Self::ByteArray(a), Self::Bytes(b)) => concat_bytearray(a.as_slice(), b.as_slice(), heap).map(Some),
+            (Self::Bytes(a), Self::ByteArray(b)) => {
+                let result = [a.as_slice(), b.as_slice()].concat();
+                Ok(Some(Value::Ref(heap.allocate(Self::Bytes(result.into()))?)))
+            }
             (Self::List(a), Self::List(b)) => a.py_add(b, heap, interns),
             (Self::Tuple(a), Self::Tuple(b)) => a.py_add(b, heap, interns),
             (Self::Dict(a), Self::Dict(b)) => a.py_add(b, heap, interns),
+            (Self::Deque(a), Self::Deque(b)) => a.py_add(b, heap, interns),
             // Cells and Dataclasses don't support arithmetic operations
             _ => Ok(None),
         }
@@ -658,6 +1087,7 @@ impl PyTrait for HeapData {
         match self {
             Self::Str(s) => s.py_iadd(other, heap, self_id, interns),
             Self::Bytes(b) => b.py_iadd(other, heap, self_id, interns),
+            Self::ByteArray(b) => b.py_iadd(other, heap, self_id, interns),
             Self::List(l) => l.py_iadd(other, heap, self_id, interns),
             Self::Tuple(t) => t.py_iadd(other, heap, self_id, interns),
             Self::Dict(d) => d.py_iadd(other, heap, self_id, interns),
@@ -679,13 +1109,30 @@ impl PyTrait for HeapData {
         match self {
             Self::Str(s) => s.py_call_attr(heap, attr, args, interns),
             Self::Bytes(b) => b.py_call_attr(heap, attr, args, interns),
+            Self::ByteArray(b) => b.py_call_attr(heap, attr, args, interns),
             Self::List(l) => l.py_call_attr(heap, attr, args, interns),
             Self::Tuple(t) => t.py_call_attr(heap, attr, args, interns),
+            Self::NamedTuple(nt) => nt.py_call_attr(heap, attr, args, interns),
+            Self::NamedTupleType(t) => t.py_call_attr(heap, attr, args, interns),
             Self::Dict(d) => d.py_call_attr(heap, attr, args, interns),
             Self::Set(s) => s.py_call_attr(heap, attr, args, interns),
             Self::FrozenSet(fs) => fs.py_call_attr(heap, attr, args, interns),
+            Self::Deque(deque) => deque.py_call_attr(heap, attr, args, interns),
             Self::Dataclass(dc) => dc.py_call_attr(heap, attr, args, interns),
             Self::Path(p) => p.py_call_attr(heap, attr, args, interns),
+            Self::Complex(c) => c.py_call_attr(heap, attr, args, interns),
+            Self::Date(date) => date.py_call_attr(heap, attr, args, interns),
+            Self::Time(time) => time.py_call_attr(heap, attr, args, interns),
+            Self::DateTime(dt) => dt.py_call_attr(heap, attr, args, interns),
+            Self::TimeDelta(delta) => delta.py_call_attr(heap, attr, args, interns),
+            Self::TimeZone(tz) => tz.py_call_attr(heap, attr, args, interns),
+            Self::ReMatch(re_match) => re_match.py_call_attr(heap, attr, args, interns),
+            Self::LruCacheWrapper(wrapper) => wrapper.py_call_attr(heap, attr, args, interns),
+            Self::Random(random) => random.py_call_attr(heap, attr, args, interns),
+            Self::Decimal(decimal) => decimal.py_call_attr(heap, attr, args, interns),
+            Self::DecimalContext(context) => context.py_call_attr(heap, attr, args, interns),
+            Self::DecimalContextManager(manager) => manager.py_call_attr(heap, attr, args, interns),
+            Self::Fraction(fraction) => fraction.py_call_attr(heap, attr, args, interns),
             _ => Err(ExcType::attribute_error(self.py_type(heap), attr.as_str(interns))),
         }
     }
@@ -713,11 +1160,16 @@ impl PyTrait for HeapData {
         match self {
             Self::Str(s) => s.py_getitem(key, heap, interns),
             Self::Bytes(b) => b.py_getitem(key, heap, interns),
+            Self::ByteArray(b) => b.py_getitem(key, heap, interns),
             Self::List(l) => l.py_getitem(key, heap, interns),
             Self::Tuple(t) => t.py_getitem(key, heap, interns),
             Self::NamedTuple(nt) => nt.py_getitem(key, heap, interns),
             Self::Dict(d) => d.py_getitem(key, heap, interns),
+            Self::Deque(deque) => deque.py_getitem(key, heap, interns),
             Self::Range(r) => r.py_getitem(key, heap, interns),
+            Self::ReMatch(re_match) => re_match.py_getitem(key, heap, interns),
+            Self::ClassObject(cls) => cls.py_getitem(key, heap, interns),
+            Self::Instance(inst) => inst.py_getitem(key, heap, interns),
             _ => Err(ExcType::type_error_not_sub(self.py_type(heap))),
         }
     }
@@ -732,13 +1184,37 @@ impl PyTrait for HeapData {
         match self {
             Self::Str(s) => s.py_setitem(key, value, heap, interns),
             Self::Bytes(b) => b.py_setitem(key, value, heap, interns),
+            Self::ByteArray(b) => b.py_setitem(key, value, heap, interns),
             Self::List(l) => l.py_setitem(key, value, heap, interns),
             Self::Tuple(t) => t.py_setitem(key, value, heap, interns),
             Self::Dict(d) => d.py_setitem(key, value, heap, interns),
+            Self::Deque(deque) => deque.py_setitem(key, value, heap, interns),
+            Self::Instance(inst) => inst.py_setitem(key, value, heap, interns),
             _ => Err(ExcType::type_error_not_sub_assignment(self.py_type(heap))),
         }
     }
 
+    fn py_delitem(&mut self, key: Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<()> {
+        match self {
+            Self::Str(s) => s.py_delitem(key, heap, interns),
+            Self::Bytes(b) => b.py_delitem(key, heap, interns),
+            Self::ByteArray(b) => b.py_delitem(key, heap, interns),
+            Self::List(l) => l.py_delitem(key, heap, interns),
+            Self::Tuple(t) => t.py_delitem(key, heap, interns),
+            Self::NamedTuple(nt) => nt.py_delitem(key, heap, interns),
+            Self::Dict(d) => d.py_delitem(key, heap, interns),
+            Self::Set(s) => s.py_delitem(key, heap, interns),
+            Self::FrozenSet(fs) => fs.py_delitem(key, heap, interns),
+            Self::Deque(deque) => deque.py_delitem(key, heap, interns),
+            Self::Range(r) => r.py_delitem(key, heap, interns),
+            Self::Instance(inst) => inst.py_delitem(key, heap, interns),
+            _ => {
+                key.drop_with_heap(heap);
+                Err(ExcType::type_error_not_sub_deletion(self.py_type(heap)))
+            }
+        }
+    }
+
     fn py_getattr(
         &self,
         attr_id: StringId,
@@ -749,9 +1225,28 @@ impl PyTrait for HeapData {
             Self::Dataclass(dc) => dc.py_getattr(attr_id, heap, interns),
             Self::Module(m) => Ok(m.py_getattr(attr_id, heap, interns)),
             Self::NamedTuple(nt) => nt.py_getattr(attr_id, heap, interns),
+            Self::NamedTupleType(t) => t.py_getattr(attr_id, heap, interns),
+            Self::Dict(d) => d.py_getattr(attr_id, heap, interns),
+            Self::Deque(deque) => deque.py_getattr(attr_id, heap, interns),
             Self::Slice(s) => s.py_getattr(attr_id, heap, interns),
             Self::Exception(exc) => exc.py_getattr(attr_id, heap, interns),
             Self::Path(p) => p.py_getattr(attr_id, heap, interns),
+            Self::Complex(c) => c.py_getattr(attr_id, heap, interns),
+            Self::Date(date) => date.py_getattr(attr_id, heap, interns),
+            Self::Time(time) => time.py_getattr(attr_id, heap, interns),
+            Self::DateTime(dt) => dt.py_getattr(attr_id, heap, interns),
+            Self::TimeDelta(delta) => delta.py_getattr(attr_id, heap, interns),
+            Self::TimeZone(tz) => tz.py_getattr(attr_id, heap, interns),
+            Self::RePattern(pattern) => pattern.py_getattr(attr_id, heap, interns),
+            Self::ReMatch(re_match) => re_match.py_getattr(attr_id, heap, interns),
+            Self::Partial(partial) => partial.py_getattr(attr_id, heap, interns),
+            Self::LruCacheWrapper(wrapper) => wrapper.py_getattr(attr_id, heap, interns),
+            Self::Template(template) => template.py_getattr(attr_id, heap, interns),
+            Self::Hash(hash) => hash.py_getattr(attr_id, heap, interns),
+            Self::Uuid(uuid) => uuid.py_getattr(attr_id, heap, interns),
+            Self::Decimal(decimal) => decimal.py_getattr(attr_id, heap, interns),
+            Self::DecimalContext(context) => context.py_getattr(attr_id, heap, interns),
+            Self::Fraction(fraction) => fraction.py_getattr(attr_id, heap, interns),
             // All other types don't support attribute access via py_getattr
             _ => Ok(None),
         }
@@ -788,7 +1283,13 @@ impl HashState {
             | HeapData::FunctionDefaults(_, _)
             | HeapData::Range(_)
             | HeapData::Slice(_)
-            | HeapData::LongInt(_) => Self::Unknown,
+            | HeapData::LongInt(_)
+            | HeapData::Complex(_)
+            | HeapData::Date(_)
+            | HeapData::Time(_)
+            | HeapData::DateTime(_)
+            | HeapData::TimeDelta(_)
+            | HeapData::TimeZone(_) => Self::Unknown,
             // Dataclass hashability depends on the mutable flag
             HeapData::Dataclass(dc) => {
                 if dc.is_frozen() {
@@ -799,14 +1300,35 @@ impl HashState {
             }
             // Path is immutable and hashable
             HeapData::Path(_) => Self::Unknown,
+            // Patterns, UUIDs, decimals and fractions are immutable and hashable
+            HeapData::RePattern(_) | HeapData::Uuid(_) | HeapData::Decimal(_) | HeapData::Fraction(_) => Self::Unknown,
+            // Classes and instances hash by identity (instances may opt out via `__hash__ = None`)
+            HeapData::ClassObject(_) | HeapData::NamedTupleType(_) | HeapData::Instance(_) => Self::Unknown,
+            // Bound methods, super proxies, functools objects, random generators, templates,
+            // hash objects and decimal contexts are not supported as dict keys
+            HeapData::BoundMethod(_)
+            | HeapData::SuperProxy(_)
+            | HeapData::Partial(_)
+            | HeapData::LruCacheWrapper(_)
+            | HeapData::CmpToKey(_)
+            | HeapData::Random(_)
+            | HeapData::Template(_)
+            | HeapData::Hash(_)
+            | HeapData::DecimalContext(_)
+            | HeapData::DecimalContextManager(_) => Self::Unhashable,
             // Mutable containers, exceptions, iterators, modules, and async types are unhashable
             HeapData::List(_)
+            | HeapData::ByteArray(_)
             | HeapData::Dict(_)
+            | HeapData::DictView(_)
             | HeapData::Set(_)
+            | HeapData::Deque(_)
             | HeapData::Exception(_)
+            | HeapData::ReMatch(_)
             | HeapData::Iter(_)
             | HeapData::Module(_)
             | HeapData::Coroutine(_)
+            | HeapData::Generator(_)
             | HeapData::GatherFuture(_) => Self::Unhashable,
         }
     }
@@ -862,18 +1384,30 @@ pub(crate) struct Heap<T: ResourceTracker> {
     /// In Python, `() is ()` is always `True` because empty tuples are interned.
     /// This field enables the same optimization.
     empty_tuple_id: Option<HeapId>,
+    /// The generator behind the `random` module's functions, `None` until it's seeded.
+    ///
+    /// Stored here rather than in the module so it's shared by every import of `random`
+    /// and saved with the heap, so a resumed run continues the same sequence.
+    random: Option<Random>,
+    /// The settings of the current `decimal` context, which round all decimal arithmetic.
+    ///
+    /// Like the `random` generator, it's shared by every import of `decimal` and saved
+    /// with the heap.
+    decimal_context: Context,
 }
 
 impl<T: ResourceTracker + serde::Serialize> serde::Serialize for Heap<T> {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         use serde::ser::SerializeStruct;
-        let mut state = serializer.serialize_struct("Heap", 6)?;
+        let mut state = serializer.serialize_struct("Heap", 8)?;
         state.serialize_field("entries", &self.entries)?;
         state.serialize_field("free_list", &self.free_list)?;
         state.serialize_field("tracker", &self.tracker)?;
         state.serialize_field("may_have_cycles", &self.may_have_cycles)?;
         state.serialize_field("allocations_since_gc", &self.allocations_since_gc)?;
         state.serialize_field("empty_tuple_id", &self.empty_tuple_id)?;
+        state.serialize_field("random", &self.random)?;
+        state.serialize_field("decimal_context", &self.decimal_context)?;
         state.end()
     }
 }
@@ -888,6 +1422,8 @@ impl<'de, T: ResourceTracker + serde::Deserialize<'de>> serde::Deserialize<'de>
             may_have_cycles: bool,
             allocations_since_gc: u32,
             empty_tuple_id: Option<HeapId>,
+            random: Option<Random>,
+            decimal_context: Context,
         }
         let fields = HeapFields::<T>::deserialize(deserializer)?;
         Ok(Self {
@@ -897,6 +1433,8 @@ impl<'de, T: ResourceTracker + serde::Deserialize<'de>> serde::Deserialize<'de>
             may_have_cycles: fields.may_have_cycles,
             allocations_since_gc: fields.allocations_since_gc,
             empty_tuple_id: fields.empty_tuple_id,
+            random: fields.random,
+            decimal_context: fields.decimal_context,
         })
     }
 }
@@ -945,6 +1483,8 @@ impl<T: ResourceTracker> Heap<T> {
             may_have_cycles: false,
             allocations_since_gc: 0,
             empty_tuple_id: None,
+            random: None,
+            decimal_context: Context::default(),
         }
     }
 
@@ -1052,6 +1592,34 @@ impl<T: ResourceTracker> Heap<T> {
         }
     }
 
+    /// Returns whether the `random` module's generator has been seeded.
+    #[must_use]
+    pub fn has_random(&self) -> bool {
+        self.random.is_some()
+    }
+
+    /// Takes the `random` module's generator out of the heap, to call it with the heap.
+    ///
+    /// Put it back with [`Self::set_random`].
+    pub fn take_random(&mut self) -> Option<Random> {
+        self.random.take()
+    }
+
+    /// Sets the `random` module's generator.
+    pub fn set_random(&mut self, random: Random) {
+        self.random = Some(random);
+    }
+
+    /// Returns the settings of the current `decimal` context.
+    pub fn decimal_context(&self) -> Context {
+        self.decimal_context
+    }
+
+    /// Replaces the settings of the current `decimal` context.
+    pub fn set_decimal_context(&mut self, context: Context) {
+        self.decimal_context = context;
+    }
+
     /// Increments the reference count for an existing heap entry.
     ///
     /// # Panics
@@ -1118,6 +1686,16 @@ impl<T: ResourceTracker> Heap<T> {
             .expect("Heap::get: data currently borrowed")
     }
 
+    /// Returns the ID and data of the live heap entry at the given index, if there is one.
+    ///
+    /// Used to resolve IDs received from outside the VM, which may refer to freed or
+    /// reused slots, without panicking.
+    #[must_use]
+    pub fn get_live(&self, index: usize) -> Option<(HeapId, &HeapData)> {
+        let data = self.entries.get(index)?.as_ref()?.data.as_ref()?;
+        Some((HeapId(index), data))
+    }
+
     /// Returns a mutable reference to the heap data stored at the given ID.
     ///
     /// # Panics
@@ -1156,8 +1734,48 @@ impl<T: ResourceTracker> Heap<T> {
             HashState::Unknown => {}
         }
 
-        // Handle Cell specially - uses identity-based hashing (like Python cell objects)
-        if let Some(HeapData::Cell(_)) = &entry.data {
+        // Instances whose class sets `__hash__ = None` are unhashable. A user-defined `__hash__`
+        // can only be called by the VM, which caches its result with `set_hash()`: until it
+        // has, these instances can't be hashed, but that isn't cached
+        if let Some(HeapData::Instance(inst)) = &entry.data {
+            let class_id = inst.class_id();
+            let hash = if !is_instance_hashable(class_id, self, interns) {
+                Some(None)
+            } else if has_custom_hash(class_id, self, interns) {
+                return None;
+            } else {
+                // Enum members hash like their value (`IntEnum` and `StrEnum`) or their name
+                r#enum::member_hash_key(id, self, interns).map(|key| {
+                    let hash = key.py_hash(self, interns);
+                    key.drop_with_heap(self);
+                    hash
+                })
+            };
+            if let Some(hash) = hash {
+                self.entries[id.index()]
+                    .as_mut()
+                    .expect("Heap::get_or_compute_hash: object already freed")
+                    .hash_state = match hash {
+                    Some(value) => HashState::Cached(value),
+                    None => HashState::Unhashable,
+                };
+                return hash;
+            }
+        }
+
+        let entry = self
+            .entries
+            .get_mut(id.index())
+            .expect("Heap::get_or_compute_hash: slot missing")
+            .as_mut()
+            .expect("Heap::get_or_compute_hash: object already freed");
+
+        // Cells, classes and instances use identity-based hashing (like Python objects
+        // without a `__hash__` override)
+        if let Some(
+            HeapData::Cell(_) | HeapData::ClassObject(_) | HeapData::NamedTupleType(_) | HeapData::Instance(_),
+        ) = &entry.data
+        {
             let mut hasher = DefaultHasher::new();
             id.hash(&mut hasher);
             let hash = hasher.finish();
@@ -1184,6 +1802,29 @@ impl<T: ResourceTracker> Heap<T> {
         hash
     }
 
+    /// Caches the result of calling a user-defined `__hash__` on the instance at `id`.
+    ///
+    /// The VM calls `__hash__` for each dict or set operation, like CPython, so this replaces
+    /// any earlier result.
+    pub(crate) fn set_hash(&mut self, id: HeapId, hash: u64) {
+        self.entries
+            .get_mut(id.index())
+            .expect("Heap::set_hash: slot missing")
+            .as_mut()
+            .expect("Heap::set_hash: object already freed")
+            .hash_state = HashState::Cached(hash);
+    }
+
+    /// Forgets the cached hash of the tuple at `id`, after the hash of an instance in it changed.
+    pub(crate) fn reset_hash(&mut self, id: HeapId) {
+        self.entries
+            .get_mut(id.index())
+            .expect("Heap::reset_hash: slot missing")
+            .as_mut()
+            .expect("Heap::reset_hash: object already freed")
+            .hash_state = HashState::Unknown;
+    }
+
     /// Calls an attribute on the heap entry, returning an `AttrCallResult` that may signal
     /// OS or external calls.
     ///
@@ -1377,7 +2018,7 @@ impl<T: ResourceTracker> Heap<T> {
     /// Multiplies (repeats) a sequence by an integer count.
     ///
     /// This method handles sequence repetition for Python's `*` operator when applied
-    /// to sequences (str, bytes, list, tuple). It creates a new heap-allocated sequence
+    /// to sequences (str, bytes, bytearray, list, tuple). It creates a new heap-allocated sequence
     /// with the elements repeated `count` times.
     ///
     /// # Arguments
@@ -1403,6 +2044,11 @@ impl<T: ResourceTracker> Heap<T> {
                 restore_data!(self, id, data, "mult_sequence");
                 Ok(Some(Value::Ref(self.allocate(HeapData::Bytes(repeated.into()))?)))
             }
+            HeapData::ByteArray(b) => {
+                let repeated = b.as_slice().repeat(count);
+                restore_data!(self, id, data, "mult_sequence");
+                Ok(Some(Value::Ref(self.allocate(HeapData::ByteArray(repeated.into()))?)))
+            }
             HeapData::List(list) => {
                 if count == 0 {
                     restore_data!(self, id, data, "mult_sequence");
@@ -1570,11 +2216,89 @@ fn collect_child_ids(data: &HeapData, work_list: &mut Vec<HeapId>) {
         // Leaf types with no heap references
         HeapData::Str(_)
         | HeapData::Bytes(_)
+        | HeapData::ByteArray(_)
         | HeapData::Range(_)
         | HeapData::Exception(_)
         | HeapData::LongInt(_)
+        | HeapData::Complex(_)
+        | HeapData::Date(_)
+        | HeapData::Time(_)
+        | HeapData::DateTime(_)
+        | HeapData::TimeDelta(_)
+        | HeapData::TimeZone(_)
         | HeapData::Slice(_)
-        | HeapData::Path(_) => {}
+        | HeapData::Path(_)
+        | HeapData::RePattern(_)
+        | HeapData::Random(_)
+        | HeapData::Template(_)
+        | HeapData::Hash(_)
+        | HeapData::Uuid(_)
+        | HeapData::Decimal(_)
+        | HeapData::DecimalContext(_)
+        | HeapData::DecimalContextManager(_)
+        | HeapData::Fraction(_) => {}
+        HeapData::ReMatch(re_match) => {
+            work_list.push(re_match.pattern_id());
+            if let Value::Ref(id) = re_match.string() {
+                work_list.push(*id);
+            }
+        }
+        HeapData::ClassObject(cls) => {
+            if let Some(base) = cls.base() {
+                work_list.push(base);
+            }
+            for (k, v) in cls.attrs() {
+                if let Value::Ref(id) = k {
+                    work_list.push(*id);
+                }
+                if let Value::Ref(id) = v {
+                    work_list.push(*id);
+                }
+            }
+        }
+        HeapData::Instance(inst) => {
+            work_list.push(inst.class_id());
+            for (k, v) in inst.attrs() {
+                if let Value::Ref(id) = k {
+                    work_list.push(*id);
+                }
+                if let Value::Ref(id) = v {
+                    work_list.push(*id);
+                }
+            }
+        }
+        HeapData::BoundMethod(method) => {
+            for value in [method.func(), method.self_obj()] {
+                if let Value::Ref(id) = value {
+                    work_list.push(*id);
+                }
+            }
+        }
+        HeapData::Partial(partial) => {
+            for value in partial.values() {
+                if let Value::Ref(id) = value {
+                    work_list.push(*id);
+                }
+            }
+        }
+        HeapData::LruCacheWrapper(wrapper) => {
+            for value in wrapper.values() {
+                if let Value::Ref(id) = value {
+                    work_list.push(*id);
+                }
+            }
+        }
+        HeapData::CmpToKey(key) => {
+            if let Value::Ref(id) = key.cmp() {
+                work_list.push(*id);
+            }
+        }
+        HeapData::SuperProxy(proxy) => {
+            work_list.push(proxy.class_id());
+            if let Value::Ref(id) = proxy.self_obj() {
+                work_list.push(*id);
+            }
+        }
         HeapData::List(list) => {
             // Skip iteration if no refs - major GC optimization for lists of primitives
             if !list.contains_refs() {
@@ -1621,7 +2345,11 @@ fn collect_child_ids(data: &HeapData, work_list: &mut Vec<HeapId>) {
                     work_list.push(*id);
                 }
             }
+            if let Some(Value::Ref(id)) = dict.default_factory() {
+                work_list.push(*id);
+            }
         }
+        HeapData::DictView(view) => work_list.push(view.dict_id()),
         HeapData::Set(set) => {
             for value in set.storage().iter() {
                 if let Value::Ref(id) = value {
@@ -1636,6 +2364,24 @@ fn collect_child_ids(data: &HeapData, work_list: &mut Vec<HeapId>) {
                 }
             }
         }
+        HeapData::Deque(deque) => {
+            // Skip iteration if no refs - GC optimization for deques of primitives
+            if !deque.contains_refs() {
+                return;
+            }
+            for value in deque.iter() {
+                if let Value::Ref(id) = value {
+                    work_list.push(*id);
+                }
+            }
+        }
+        HeapData::NamedTupleType(t) => {
+            for value in t.defaults() {
+                if let Value::Ref(id) = value {
+                    work_list.push(*id);
+                }
+            }
+        }
         HeapData::Closure(_, cells, defaults) => {
             // Add captured cells to work list
             for cell_id in cells {
@@ -1674,10 +2420,9 @@ fn collect_child_ids(data: &HeapData, work_list: &mut Vec<HeapId>) {
             }
         }
         HeapData::Iter(iter) => {
-            // Iterator holds a reference to the iterable being iterated
-            if let Value::Ref(id) = iter.value() {
-                work_list.push(*id);
-            }
+            // Iterator holds a reference to the iterable being iterated, or the function and
+            // inputs of a function-calling iterator
+            iter.push_ref_ids(work_list);
         }
         HeapData::Module(m) => {
             // Module attrs can contain references to heap values
@@ -1705,6 +2450,10 @@ fn collect_child_ids(data: &HeapData, work_list: &mut Vec<HeapId>) {
                 }
             }
         }
+        HeapData::Generator(generator) => {
+            // Add namespace, saved stack and exception values that are heap references
+            work_list.extend(generator.ref_ids());
+        }
         HeapData::GatherFuture(gather) => {
             // Add coroutine HeapIds to work list
             for item in &gather.items {
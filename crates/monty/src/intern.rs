@@ -69,7 +69,7 @@ static ASCII_STRS: LazyLock<[&'static str; 128]> = LazyLock::new(|| {
 });
 
 /// Static string values which are known at compile time and don't need to be interned.
-#[repr(u8)]
+#[repr(u16)]
 #[derive(
     Debug, Clone, Copy, FromRepr, EnumString, IntoStaticStr, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
 )]
@@ -175,6 +175,8 @@ pub enum StaticStrings {
     Encode,
     Isidentifier,
     Istitle,
+    Format,
+    FormatMap,
 
     // ==========================
     // Bytes methods
@@ -188,6 +190,12 @@ pub enum StaticStrings {
     Hex,
     Fromhex,
 
+    // ==========================
+    // Complex attributes and methods
+    Real,
+    Imag,
+    Conjugate,
+
     // ==========================
     // sys module strings
     #[strum(serialize = "sys")]
@@ -204,6 +212,8 @@ pub enum StaticStrings {
     Stdout,
     #[strum(serialize = "stderr")]
     Stderr,
+    Write,
+    Flush,
     #[strum(serialize = "major")]
     Major,
     #[strum(serialize = "minor")]
@@ -321,14 +331,544 @@ pub enum StaticStrings {
     #[strum(serialize = "default")]
     Default,
 
+    // ==========================
+    // json module strings
+    #[strum(serialize = "json")]
+    Json,
+    #[strum(serialize = "loads")]
+    Loads,
+    #[strum(serialize = "dumps")]
+    Dumps,
+    #[strum(serialize = "JSONDecodeError")]
+    JsonDecodeError,
+
+    // ==========================
+    // re module strings
+    // Also uses shared: SPLIT, START
+    // Single letter flags (`re.I`, `re.M`, ...) use the pre-interned ASCII strings
+    #[strum(serialize = "re")]
+    Re,
+    Compile,
+    Search,
+    Match,
+    Fullmatch,
+    Findall,
+    Finditer,
+    Sub,
+    Subn,
+    Escape,
+    Error,
+    #[strum(serialize = "PatternError")]
+    PatternError,
+    #[strum(serialize = "NOFLAG")]
+    Noflag,
+    #[strum(serialize = "IGNORECASE")]
+    Ignorecase,
+    #[strum(serialize = "LOCALE")]
+    Locale,
+    #[strum(serialize = "MULTILINE")]
+    Multiline,
+    #[strum(serialize = "DOTALL")]
+    Dotall,
+    #[strum(serialize = "UNICODE")]
+    Unicode,
+    #[strum(serialize = "VERBOSE")]
+    Verbose,
+    #[strum(serialize = "ASCII")]
+    Ascii,
+    // Pattern attributes
+    Pattern,
+    Flags,
+    Groups,
+    Groupindex,
+    // Match methods and attributes
+    Group,
+    Groupdict,
+    End,
+    Span,
+    Expand,
+    String,
+    Pos,
+    Endpos,
+    Lastindex,
+    Lastgroup,
+
+    // ==========================
+    // math module strings
+    Math,
+    Floor,
+    Ceil,
+    Trunc,
+    Sqrt,
+    Cbrt,
+    Exp,
+    #[strum(serialize = "exp2")]
+    Exp2,
+    Expm1,
+    Log,
+    #[strum(serialize = "log2")]
+    Log2,
+    #[strum(serialize = "log10")]
+    Log10,
+    #[strum(serialize = "log1p")]
+    Log1p,
+    Pow,
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    #[strum(serialize = "atan2")]
+    Atan2,
+    Sinh,
+    Cosh,
+    Tanh,
+    Asinh,
+    Acosh,
+    Atanh,
+    Hypot,
+    Dist,
+    Degrees,
+    Radians,
+    Fabs,
+    Copysign,
+    Fmod,
+    Remainder,
+    Modf,
+    Frexp,
+    Ldexp,
+    Isclose,
+    Isfinite,
+    Isinf,
+    Isnan,
+    Gcd,
+    Lcm,
+    Comb,
+    Perm,
+    Factorial,
+    Isqrt,
+    Fsum,
+    Prod,
+    // Constants, besides `e` which is a single ASCII letter
+    Pi,
+    Tau,
+    Inf,
+    Nan,
+
+    // ==========================
+    // datetime module strings
+    // Also uses shared: REPLACE
+    Datetime,
+    Date,
+    Time,
+    Timedelta,
+    Timezone,
+    #[strum(serialize = "MINYEAR")]
+    Minyear,
+    #[strum(serialize = "MAXYEAR")]
+    Maxyear,
+    #[strum(serialize = "UTC")]
+    UtcConst,
+    Utc,
+    // Class methods
+    Now,
+    Today,
+    Fromisoformat,
+    Fromordinal,
+    Fromisocalendar,
+    Fromtimestamp,
+    Strptime,
+    Combine,
+    // Class attributes
+    Min,
+    Max,
+    Resolution,
+    // Instance attributes
+    Year,
+    Month,
+    Day,
+    Week,
+    Hour,
+    Minute,
+    Second,
+    Microsecond,
+    Tzinfo,
+    Fold,
+    Days,
+    Seconds,
+    Microseconds,
+    // Instance methods
+    Isoformat,
+    Strftime,
+    Weekday,
+    Isoweekday,
+    Isocalendar,
+    Toordinal,
+    Timestamp,
+    Ctime,
+    Timetz,
+    Utcoffset,
+    Tzname,
+    Dst,
+    Astimezone,
+    TotalSeconds,
+
+    // ==========================
+    // collections module strings
+    // Also uses shared: APPEND, EXTEND, INSERT, REVERSE, POP, POPITEM, CLEAR, COPY, REMOVE, UPDATE,
+    // INDEX, COUNT
+    Collections,
+    Defaultdict,
+    #[strum(serialize = "Counter")]
+    CounterClass,
+    Deque,
+    #[strum(serialize = "OrderedDict")]
+    OrderedDictClass,
+    Namedtuple,
+    DefaultFactory,
+    // Counter methods
+    MostCommon,
+    Elements,
+    Subtract,
+    Total,
+    // OrderedDict methods
+    MoveToEnd,
+    // deque methods and attributes
+    Appendleft,
+    Popleft,
+    Extendleft,
+    Rotate,
+    Maxlen,
+    // Named tuple methods and attributes
+    #[strum(serialize = "_fields")]
+    UnderscoreFields,
+    #[strum(serialize = "_field_defaults")]
+    UnderscoreFieldDefaults,
+    #[strum(serialize = "_asdict")]
+    UnderscoreAsdict,
+    #[strum(serialize = "_replace")]
+    UnderscoreReplace,
+    #[strum(serialize = "_make")]
+    UnderscoreMake,
+
+    // ==========================
+    // itertools module strings
+    // Also uses shared: COUNT
+    Itertools,
+    Chain,
+    FromIterable,
+    Islice,
+    Groupby,
+    Product,
+    Permutations,
+    Combinations,
+    Accumulate,
+    Cycle,
+    Repeat,
+    ZipLongest,
+    Batched,
+    Pairwise,
+
+    // ==========================
+    // functools module strings
+    Functools,
+    Reduce,
+    Partial,
+    Cache,
+    LruCache,
+    CmpToKey,
+    Wraps,
+    UpdateWrapper,
+    // wraps() keyword argument
+    Wrapped,
+    // partial attributes, also uses shared: ARGS
+    Func,
+    Keywords,
+    // lru_cache wrapper methods and attributes
+    CacheInfo,
+    CacheClear,
+    #[strum(serialize = "CacheInfo")]
+    CacheInfoClass,
+    Hits,
+    Misses,
+    Maxsize,
+    Currsize,
+    #[strum(serialize = "__wrapped__")]
+    DunderWrapped,
+
+    // ==========================
+    // random module strings
+    // `random` is both the module and its function
+    Random,
+    #[strum(serialize = "Random")]
+    RandomClass,
+    Seed,
+    Getrandbits,
+    Randrange,
+    Randint,
+    Choice,
+    Choices,
+    Sample,
+    Shuffle,
+    Uniform,
+    Gauss,
+
+    // ==========================
+    // string module strings
+    // `string` itself is shared with `re.Match.string`
+    AsciiLetters,
+    AsciiLowercase,
+    AsciiUppercase,
+    Digits,
+    Hexdigits,
+    Octdigits,
+    Punctuation,
+    Printable,
+    Whitespace,
+    Capwords,
+    #[strum(serialize = "Template")]
+    TemplateClass,
+    Template,
+    Delimiter,
+    Substitute,
+    SafeSubstitute,
+    GetIdentifiers,
+    IsValid,
+
+    // ==========================
+    // textwrap module strings
+    Textwrap,
+    Wrap,
+    Fill,
+    Shorten,
+    Dedent,
+    Indent,
+
+    // ==========================
+    // base64 module strings
+    #[strum(serialize = "base64")]
+    Base64,
+    #[strum(serialize = "b64encode")]
+    B64encode,
+    #[strum(serialize = "b64decode")]
+    B64decode,
+    #[strum(serialize = "standard_b64encode")]
+    StandardB64encode,
+    #[strum(serialize = "standard_b64decode")]
+    StandardB64decode,
+    #[strum(serialize = "urlsafe_b64encode")]
+    UrlsafeB64encode,
+    #[strum(serialize = "urlsafe_b64decode")]
+    UrlsafeB64decode,
+    #[strum(serialize = "b32encode")]
+    B32encode,
+    #[strum(serialize = "b32decode")]
+    B32decode,
+    #[strum(serialize = "b16encode")]
+    B16encode,
+    #[strum(serialize = "b16decode")]
+    B16decode,
+
+    // ==========================
+    // hashlib module strings, also uses: NEW, UPDATE, COPY, NAME
+    Hashlib,
+    #[strum(serialize = "md5")]
+    Md5,
+    #[strum(serialize = "sha1")]
+    Sha1,
+    #[strum(serialize = "sha256")]
+    Sha256,
+    #[strum(serialize = "sha512")]
+    Sha512,
+    New,
+    Digest,
+    Hexdigest,
+    DigestSize,
+    BlockSize,
+
+    // ==========================
+    // uuid module strings, also uses: HEX, VERSION
+    Uuid,
+    #[strum(serialize = "UUID")]
+    UuidClass,
+    #[strum(serialize = "uuid3")]
+    Uuid3,
+    #[strum(serialize = "uuid4")]
+    Uuid4,
+    #[strum(serialize = "uuid5")]
+    Uuid5,
+    #[strum(serialize = "NAMESPACE_DNS")]
+    NamespaceDns,
+    #[strum(serialize = "NAMESPACE_URL")]
+    NamespaceUrl,
+    #[strum(serialize = "NAMESPACE_OID")]
+    NamespaceOid,
+    #[strum(serialize = "NAMESPACE_X500")]
+    NamespaceX500,
+    Int,
+    Bytes,
+    BytesLe,
+    Urn,
+    Variant,
+    Fields,
+
+    // ==========================
+    // enum module strings, also uses: NAME
+    Enum,
+    #[strum(serialize = "Enum")]
+    EnumClass,
+    #[strum(serialize = "IntEnum")]
+    IntEnum,
+    #[strum(serialize = "StrEnum")]
+    StrEnum,
+    Auto,
+    Value,
+    #[strum(serialize = "_name_")]
+    SunderName,
+    #[strum(serialize = "_value_")]
+    SunderValue,
+    #[strum(serialize = "__members__")]
+    DunderMembers,
+
+    // ==========================
+    // decimal module strings, also uses: COPY, REAL, IMAG, CONJUGATE
+    Decimal,
+    #[strum(serialize = "Decimal")]
+    DecimalClass,
+    #[strum(serialize = "Context")]
+    ContextClass,
+    Getcontext,
+    Setcontext,
+    Localcontext,
+    #[strum(serialize = "DecimalException")]
+    DecimalException,
+    #[strum(serialize = "InvalidOperation")]
+    InvalidOperation,
+    #[strum(serialize = "DivisionByZero")]
+    DivisionByZero,
+    #[strum(serialize = "Overflow")]
+    Overflow,
+    #[strum(serialize = "ROUND_CEILING")]
+    RoundCeiling,
+    #[strum(serialize = "ROUND_FLOOR")]
+    RoundFloor,
+    #[strum(serialize = "ROUND_UP")]
+    RoundUp,
+    #[strum(serialize = "ROUND_DOWN")]
+    RoundDown,
+    #[strum(serialize = "ROUND_HALF_UP")]
+    RoundHalfUp,
+    #[strum(serialize = "ROUND_HALF_DOWN")]
+    RoundHalfDown,
+    #[strum(serialize = "ROUND_HALF_EVEN")]
+    RoundHalfEven,
+    #[strum(serialize = "ROUND_05UP")]
+    Round05Up,
+    #[strum(serialize = "MAX_PREC")]
+    MaxPrec,
+    Prec,
+    Rounding,
+    #[strum(serialize = "Emax")]
+    Emax,
+    #[strum(serialize = "Emin")]
+    Emin,
+    Quantize,
+    ToIntegralValue,
+    ToIntegral,
+    Normalize,
+    IsNan,
+    IsInfinite,
+    IsFinite,
+    IsZero,
+    IsSigned,
+    Adjusted,
+    CopyAbs,
+    CopyNegate,
+    AsIntegerRatio,
+    FromFloat,
+
+    // ==========================
+    // fractions module strings, also uses: AS_INTEGER_RATIO, FROM_FLOAT, REAL, IMAG, CONJUGATE
+    Fractions,
+    #[strum(serialize = "Fraction")]
+    FractionClass,
+    Numerator,
+    Denominator,
+    LimitDenominator,
+    IsInteger,
+    FromDecimal,
+
     // ==========================
     // Exception attributes
     Args,
+    #[strum(serialize = "__cause__")]
+    DunderCause,
+    #[strum(serialize = "__context__")]
+    DunderContext,
+    #[strum(serialize = "__suppress_context__")]
+    DunderSuppressContext,
+    // Exception group attributes and methods, also uses: SPLIT
+    Message,
+    Exceptions,
+    Subgroup,
 
     // ==========================
     // Type attributes
     #[strum(serialize = "__name__")]
     DunderName,
+    #[strum(serialize = "__class__")]
+    DunderClass,
+    #[strum(serialize = "__match_args__")]
+    DunderMatchArgs,
+
+    // ==========================
+    // User-defined class special methods
+    #[strum(serialize = "__init__")]
+    DunderInit,
+    #[strum(serialize = "__repr__")]
+    DunderRepr,
+    #[strum(serialize = "__str__")]
+    DunderStr,
+    #[strum(serialize = "__eq__")]
+    DunderEq,
+    #[strum(serialize = "__ne__")]
+    DunderNe,
+    #[strum(serialize = "__lt__")]
+    DunderLt,
+    #[strum(serialize = "__le__")]
+    DunderLe,
+    #[strum(serialize = "__gt__")]
+    DunderGt,
+    #[strum(serialize = "__ge__")]
+    DunderGe,
+    #[strum(serialize = "__hash__")]
+    DunderHash,
+    #[strum(serialize = "__len__")]
+    DunderLen,
+    #[strum(serialize = "__getitem__")]
+    DunderGetitem,
+    #[strum(serialize = "__setitem__")]
+    DunderSetitem,
+    #[strum(serialize = "__delitem__")]
+    DunderDelitem,
+
+    // ==========================
+    // Context manager methods
+    #[strum(serialize = "__enter__")]
+    DunderEnter,
+    #[strum(serialize = "__exit__")]
+    DunderExit,
+    #[strum(serialize = "__aenter__")]
+    DunderAenter,
+    #[strum(serialize = "__aexit__")]
+    DunderAexit,
+
+    // ==========================
+    // Generator methods
+    Send,
+    Throw,
+    Close,
 
     // ==========================
     // pathlib module strings
@@ -416,7 +956,7 @@ impl StaticStrings {
     /// (e.g., it's an ASCII char or a dynamically interned string).
     pub fn from_string_id(id: StringId) -> Option<Self> {
         let enum_id = id.0.checked_sub(STATIC_STRING_ID_OFFSET)?;
-        u8::try_from(enum_id).ok().and_then(Self::from_repr)
+        u16::try_from(enum_id).ok().and_then(Self::from_repr)
     }
 }
 
@@ -658,6 +1198,23 @@ impl Interns {
         get_str(&self.strings, id)
     }
 
+    /// Finds the `StringId` of a string by its content, the reverse of `get_str`.
+    ///
+    /// Used for attribute names built at runtime, e.g. by `getattr()`. Returns `None` if the
+    /// string was never interned.
+    pub fn find_str(&self, s: &str) -> Option<StringId> {
+        if s.len() == 1 {
+            Some(StringId::from_ascii(s.as_bytes()[0]))
+        } else if let Ok(ss) = StaticStrings::from_str(s) {
+            Some(ss.into())
+        } else {
+            let index = self.strings.iter().position(|string| string == s)?;
+            Some(StringId(
+                (index + INTERN_STRING_ID_OFFSET).try_into().expect("StringId overflow"),
+            ))
+        }
+    }
+
     /// Looks up bytes by their `BytesId`.
     ///
     /// # Panics
@@ -688,6 +1245,14 @@ impl Interns {
         self.functions.get(id.index()).expect("Function not found")
     }
 
+    /// Returns the `FunctionId` with the given raw index, or `None` if there is no such function.
+    ///
+    /// Used to resolve function handles received from the host.
+    pub fn function_id_from_index(&self, index: usize) -> Option<FunctionId> {
+        let index = u16::try_from(index).ok()?;
+        (usize::from(index) < self.functions.len()).then(|| FunctionId::from_index(index))
+    }
+
     /// Lookup an external function name by its `ExtFunctionId`
     ///
     /// # Panics
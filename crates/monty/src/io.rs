@@ -5,7 +5,9 @@ use crate::exception_public::MontyException;
 /// Trait for handling output from the `print()` builtin function.
 ///
 /// Implement this trait to capture or redirect print output from sandboxed Python code.
-/// The default implementation `StdPrint` writes to stdout.
+/// Output written to `sys.stderr`, e.g. via `print(..., file=sys.stderr)`, arrives on the
+/// separate `stderr_*` methods so hosts can treat warnings differently from results.
+/// The default implementation `StdPrint` writes to the process's stdout and stderr.
 pub trait PrintWriter {
     /// Called once for each formatted argument passed to `print()`.
     ///
@@ -25,9 +27,58 @@ pub trait PrintWriter {
     /// # Arguments
     /// * `end` - The character to print after the formatted output.
     fn stdout_push(&mut self, end: char) -> Result<(), MontyException>;
+
+    /// Called for each piece of text written to stderr.
+    ///
+    /// Behaves like [`stdout_write`] but for `print(..., file=sys.stderr)` and
+    /// `sys.stderr.write()`. The default implementation forwards to [`stdout_write`], so
+    /// writers which don't distinguish the streams only need the `stdout_*` methods.
+    ///
+    /// # Arguments
+    /// * `output` - The text to write.
+    fn stderr_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        self.stdout_write(output)
+    }
+
+    /// Add a single character to stderr.
+    ///
+    /// Behaves like [`stdout_push`] but for stderr. The default implementation forwards
+    /// to [`stdout_push`].
+    ///
+    /// # Arguments
+    /// * `end` - The character to write.
+    fn stderr_push(&mut self, end: char) -> Result<(), MontyException> {
+        self.stdout_push(end)
+    }
 }
 
-/// Default `PrintWriter` that writes to stdout.
+/// The output stream a write is directed to, as selected by `print(file=...)` or by
+/// calling `sys.stdout.write()` / `sys.stderr.write()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PrintStream {
+    Stdout,
+    Stderr,
+}
+
+impl PrintStream {
+    /// Writes `output` to this stream of `print`.
+    pub fn write(self, print: &mut impl PrintWriter, output: Cow<'_, str>) -> Result<(), MontyException> {
+        match self {
+            Self::Stdout => print.stdout_write(output),
+            Self::Stderr => print.stderr_write(output),
+        }
+    }
+
+    /// Writes a single character to this stream of `print`.
+    pub fn push(self, print: &mut impl PrintWriter, end: char) -> Result<(), MontyException> {
+        match self {
+            Self::Stdout => print.stdout_push(end),
+            Self::Stderr => print.stderr_push(end),
+        }
+    }
+}
+
+/// Default `PrintWriter` that writes to the process's stdout and stderr.
 ///
 /// This is the default writer used when no custom writer is provided.
 #[derive(Debug)]
@@ -43,48 +94,80 @@ impl PrintWriter for StdPrint {
         print!("{end}");
         Ok(())
     }
+
+    fn stderr_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        eprint!("{output}");
+        Ok(())
+    }
+
+    fn stderr_push(&mut self, end: char) -> Result<(), MontyException> {
+        eprint!("{end}");
+        Ok(())
+    }
 }
 
-/// A `PrintWriter` that collects all output into a string.
+/// A `PrintWriter` that collects all output into strings.
 ///
-/// Uses interior mutability via `RefCell` to allow collecting output
-/// while being passed as a shared reference through the execution stack.
+/// Stdout and stderr are collected separately; use [`output`](Self::output) for stdout
+/// and [`stderr_output`](Self::stderr_output) for stderr.
 ///
 /// Useful for testing or capturing print output programmatically.
 #[derive(Debug, Default)]
-pub struct CollectStringPrint(String);
+pub struct CollectStringPrint {
+    stdout: String,
+    stderr: String,
+}
 
 impl CollectStringPrint {
     /// Creates a new empty `CollectStringPrint`.
     #[must_use]
     pub fn new() -> Self {
-        Self(String::new())
+        Self::default()
     }
 
-    /// Returns the collected output as a string slice.
-    ///
-    /// # Panics
-    /// Panics if the internal RefCell is currently borrowed mutably.
+    /// Returns the collected stdout output as a string slice.
     #[must_use]
     pub fn output(&self) -> &str {
-        self.0.as_str()
+        self.stdout.as_str()
+    }
+
+    /// Returns the collected stderr output as a string slice.
+    #[must_use]
+    pub fn stderr_output(&self) -> &str {
+        self.stderr.as_str()
     }
 
-    /// Consumes the writer and returns the collected output.
+    /// Consumes the writer and returns the collected stdout output.
     #[must_use]
     pub fn into_output(self) -> String {
-        self.0
+        self.stdout
+    }
+
+    /// Consumes the writer and returns the collected `(stdout, stderr)` output.
+    #[must_use]
+    pub fn into_outputs(self) -> (String, String) {
+        (self.stdout, self.stderr)
     }
 }
 
 impl PrintWriter for CollectStringPrint {
     fn stdout_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
-        self.0.push_str(&output);
+        self.stdout.push_str(&output);
         Ok(())
     }
 
     fn stdout_push(&mut self, end: char) -> Result<(), MontyException> {
-        self.0.push(end);
+        self.stdout.push(end);
+        Ok(())
+    }
+
+    fn stderr_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        self.stderr.push_str(&output);
+        Ok(())
+    }
+
+    fn stderr_push(&mut self, end: char) -> Result<(), MontyException> {
+        self.stderr.push(end);
         Ok(())
     }
 }
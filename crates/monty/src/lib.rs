@@ -19,6 +19,7 @@ mod object;
 mod os;
 mod parse;
 mod prepare;
+mod regex;
 mod resource;
 mod run;
 mod signature;
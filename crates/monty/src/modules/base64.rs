@@ -0,0 +1,491 @@
+//! Implementation of the `base64` module.
+//!
+//! Provides Python's `base64` module:
+//! - `b64encode()` and `b64decode()`, with their `standard_` and `urlsafe_` variants
+//! - `b32encode()` and `b32decode()`
+//! - `b16encode()` and `b16decode()`
+//!
+//! Encoders take bytes-like objects, and decoders also take ASCII strings. Malformed input
+//! raises `ValueError` with the messages of CPython's `binascii.Error`, which is a subclass
+//! of `ValueError`.
+
+use crate::{
+    args::{ArgValues, bind_args},
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{AttrCallResult, Bytes, Module, PyTrait},
+    value::Value,
+};
+
+/// base64 module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum Base64Functions {
+    #[strum(serialize = "b64encode")]
+    B64encode,
+    #[strum(serialize = "b64decode")]
+    B64decode,
+    #[strum(serialize = "standard_b64encode")]
+    StandardB64encode,
+    #[strum(serialize = "standard_b64decode")]
+    StandardB64decode,
+    #[strum(serialize = "urlsafe_b64encode")]
+    UrlsafeB64encode,
+    #[strum(serialize = "urlsafe_b64decode")]
+    UrlsafeB64decode,
+    #[strum(serialize = "b32encode")]
+    B32encode,
+    #[strum(serialize = "b32decode")]
+    B32decode,
+    #[strum(serialize = "b16encode")]
+    B16encode,
+    #[strum(serialize = "b16decode")]
+    B16decode,
+}
+
+const B64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const B32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const B16_ALPHABET: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Creates the `base64` module and allocates it on the heap.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Base64);
+
+    for (name, function) in [
+        (StaticStrings::B64encode, Base64Functions::B64encode),
+        (StaticStrings::B64decode, Base64Functions::B64decode),
+        (StaticStrings::StandardB64encode, Base64Functions::StandardB64encode),
+        (StaticStrings::StandardB64decode, Base64Functions::StandardB64decode),
+        (StaticStrings::UrlsafeB64encode, Base64Functions::UrlsafeB64encode),
+        (StaticStrings::UrlsafeB64decode, Base64Functions::UrlsafeB64decode),
+        (StaticStrings::B32encode, Base64Functions::B32encode),
+        (StaticStrings::B32decode, Base64Functions::B32decode),
+        (StaticStrings::B16encode, Base64Functions::B16encode),
+        (StaticStrings::B16decode, Base64Functions::B16decode),
+    ] {
+        module.set_attr(
+            name,
+            Value::ModuleFunction(ModuleFunctions::Base64(function)),
+            heap,
+            interns,
+        );
+    }
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a base64 module function.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    function: Base64Functions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let result = match function {
+        Base64Functions::B64encode => {
+            let [s, altchars] = bind_args("b64encode", args, ["s", "altchars"], 1, heap, interns)?;
+            let s = s.expect("required argument is bound");
+            let result = b64encode(&s, altchars.as_ref(), heap, interns);
+            s.drop_with_heap(heap);
+            altchars.drop_with_heap(heap);
+            result?
+        }
+        Base64Functions::B64decode => {
+            let [s, altchars, validate] =
+                bind_args("b64decode", args, ["s", "altchars", "validate"], 1, heap, interns)?;
+            let s = s.expect("required argument is bound");
+            let strict = validate
+                .as_ref()
+                .is_some_and(|validate| validate.py_bool(heap, interns));
+            let result = b64decode(&s, altchars.as_ref(), strict, heap, interns);
+            s.drop_with_heap(heap);
+            altchars.drop_with_heap(heap);
+            validate.drop_with_heap(heap);
+            result?
+        }
+        Base64Functions::StandardB64encode => {
+            let s = args.get_one_arg("standard_b64encode", heap)?;
+            let result = b64encode(&s, None, heap, interns);
+            s.drop_with_heap(heap);
+            result?
+        }
+        Base64Functions::StandardB64decode => {
+            let s = args.get_one_arg("standard_b64decode", heap)?;
+            let result = b64decode(&s, None, false, heap, interns);
+            s.drop_with_heap(heap);
+            result?
+        }
+        Base64Functions::UrlsafeB64encode => {
+            let s = args.get_one_arg("urlsafe_b64encode", heap)?;
+            let result = data_arg(&s, heap, interns).map(|data| translate(&encode_b64(&data), b"+/", b"-_"));
+            s.drop_with_heap(heap);
+            result?
+        }
+        Base64Functions::UrlsafeB64decode => {
+            let s = args.get_one_arg("urlsafe_b64decode", heap)?;
+            let result = decode_data_arg(&s, heap, interns)
+                .and_then(|data| decode_b64(&translate(&data, b"-_", b"+/"), false).map_err(binascii_error));
+            s.drop_with_heap(heap);
+            result?
+        }
+        Base64Functions::B32encode => {
+            let s = args.get_one_arg("b32encode", heap)?;
+            let result = data_arg(&s, heap, interns).map(|data| encode_b32(&data));
+            s.drop_with_heap(heap);
+            result?
+        }
+        Base64Functions::B32decode => {
+            let [s, casefold, map01] = bind_args("b32decode", args, ["s", "casefold", "map01"], 1, heap, interns)?;
+            let s = s.expect("required argument is bound");
+            let result = b32decode(&s, casefold.as_ref(), map01.as_ref(), heap, interns);
+            s.drop_with_heap(heap);
+            casefold.drop_with_heap(heap);
+            map01.drop_with_heap(heap);
+            result?
+        }
+        Base64Functions::B16encode => {
+            let s = args.get_one_arg("b16encode", heap)?;
+            let result = data_arg(&s, heap, interns).map(|data| encode_b16(&data));
+            s.drop_with_heap(heap);
+            result?
+        }
+        Base64Functions::B16decode => {
+            let [s, casefold] = bind_args("b16decode", args, ["s", "casefold"], 1, heap, interns)?;
+            let s = s.expect("required argument is bound");
+            let casefold = casefold.is_some_and(|casefold| {
+                let truthy = casefold.py_bool(heap, interns);
+                casefold.drop_with_heap(heap);
+                truthy
+            });
+            let result = decode_data_arg(&s, heap, interns).and_then(|mut data| {
+                if casefold {
+                    data.make_ascii_uppercase();
+                }
+                decode_b16(&data).map_err(binascii_error)
+            });
+            s.drop_with_heap(heap);
+            result?
+        }
+    };
+    heap.tracker().check_large_result(result.len())?;
+    let id = heap.allocate(HeapData::Bytes(Bytes::new(result)))?;
+    Ok(AttrCallResult::Value(Value::Ref(id)))
+}
+
+/// Implements `b64encode(s, altchars=None)`.
+fn b64encode(
+    s: &Value,
+    altchars: Option<&Value>,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Vec<u8>> {
+    let data = data_arg(s, heap, interns)?;
+    heap.tracker().check_large_result(data.len() / 3 * 4 + 4)?;
+    let encoded = encode_b64(&data);
+    match altchars_arg(altchars, heap, interns)? {
+        Some(altchars) => Ok(translate(&encoded, b"+/", &altchars)),
+        None => Ok(encoded),
+    }
+}
+
+/// Implements `b64decode(s, altchars=None, validate=False)`.
+fn b64decode(
+    s: &Value,
+    altchars: Option<&Value>,
+    strict: bool,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Vec<u8>> {
+    let mut data = decode_data_arg(s, heap, interns)?;
+    if let Some(altchars) = altchars_arg(altchars, heap, interns)? {
+        data = translate(&data, &altchars, b"+/");
+    }
+    decode_b64(&data, strict).map_err(binascii_error)
+}
+
+/// Implements `b32decode(s, casefold=False, map01=None)`.
+fn b32decode(
+    s: &Value,
+    casefold: Option<&Value>,
+    map01: Option<&Value>,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Vec<u8>> {
+    let mut data = decode_data_arg(s, heap, interns)?;
+    if !data.len().is_multiple_of(8) {
+        return Err(binascii_error("Incorrect padding"));
+    }
+    if let Some(map01) = map01.filter(|map01| !matches!(map01, Value::None)) {
+        let map01_bytes = decode_data_arg(map01, heap, interns)?;
+        let [one] = map01_bytes[..] else {
+            return Err(assertion_error(map01, heap, interns));
+        };
+        data = translate(&data, b"01", &[b'O', one]);
+    }
+    if casefold.is_some_and(|casefold| casefold.py_bool(heap, interns)) {
+        data.make_ascii_uppercase();
+    }
+    decode_b32(&data).map_err(binascii_error)
+}
+
+/// Returns the bytes of a bytes-like argument.
+///
+/// # Errors
+/// Returns `TypeError` for anything else.
+fn data_arg(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Vec<u8>> {
+    let bytes = match value {
+        Value::InternBytes(id) => Some(interns.get_bytes(*id)),
+        Value::Ref(id) => heap.get(*id).as_bytes_like(),
+        _ => None,
+    };
+    bytes.map(<[u8]>::to_vec).ok_or_else(|| {
+        ExcType::type_error(format!(
+            "a bytes-like object is required, not '{}'",
+            value.py_type(heap)
+        ))
+    })
+}
+
+/// Returns the bytes of an argument to decode, which may also be an ASCII string.
+///
+/// # Errors
+/// Returns `ValueError` for a string with other characters, and `TypeError` for anything
+/// but a string or a bytes-like object.
+fn decode_data_arg(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Vec<u8>> {
+    if let Some(text) = value.as_either_str(heap) {
+        let text = text.as_str(interns);
+        return if text.is_ascii() {
+            Ok(text.as_bytes().to_vec())
+        } else {
+            Err(SimpleException::new_msg(
+                ExcType::ValueError,
+                "string argument should contain only ASCII characters",
+            )
+            .into())
+        };
+    }
+    let type_name = value.py_type(heap);
+    data_arg(value, heap, interns).map_err(|_| {
+        ExcType::type_error(format!(
+            "argument should be a bytes-like object or ASCII string, not '{type_name}'"
+        ))
+    })
+}
+
+/// Returns the two characters of an `altchars` argument, unless it's `None`.
+fn altchars_arg(
+    altchars: Option<&Value>,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Option<[u8; 2]>> {
+    let Some(altchars) = altchars.filter(|altchars| !matches!(altchars, Value::None)) else {
+        return Ok(None);
+    };
+    match decode_data_arg(altchars, heap, interns)?[..] {
+        [plus, slash] => Ok(Some([plus, slash])),
+        _ => Err(assertion_error(altchars, heap, interns)),
+    }
+}
+
+/// Replaces each byte of `from` in `data` with the byte at the same position in `to`.
+fn translate(data: &[u8], from: &[u8], to: &[u8]) -> Vec<u8> {
+    data.iter()
+        .map(|byte| match from.iter().position(|b| b == byte) {
+            Some(index) => to[index],
+            None => *byte,
+        })
+        .collect()
+}
+
+/// Encodes bytes with the standard Base64 alphabet and padding.
+fn encode_b64(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |group, (i, byte)| group | (u32::from(*byte) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(B64_ALPHABET[(group >> (18 - 6 * i)) as usize & 0x3f]);
+            } else {
+                out.push(b'=');
+            }
+        }
+    }
+    out
+}
+
+/// Decodes Base64 as CPython's `binascii.a2b_base64()`.
+///
+/// Padding only matters at the end, where it must complete the last group of four
+/// characters. Other characters outside the alphabet are skipped, unless `strict`, which
+/// also rejects padding anywhere but at the end.
+fn decode_b64(data: &[u8], strict: bool) -> Result<Vec<u8>, String> {
+    if strict && data.first() == Some(&b'=') {
+        return Err("Leading padding not allowed".to_owned());
+    }
+    let mut out = Vec::with_capacity(data.len() / 4 * 3);
+    let mut quad_pos = 0;
+    let mut left: u32 = 0;
+    let mut pads = 0;
+    for &byte in data {
+        if byte == b'=' {
+            if strict {
+                if quad_pos == 0 || quad_pos + pads >= 4 {
+                    return Err("Excess padding not allowed".to_owned());
+                }
+                if quad_pos == 1 {
+                    return Err(one_extra_character(&out));
+                }
+            }
+            pads += 1;
+            continue;
+        }
+        let Some(value) = B64_ALPHABET.iter().position(|c| *c == byte) else {
+            if strict {
+                return Err("Only base64 data is allowed".to_owned());
+            }
+            continue;
+        };
+        if strict && pads > 0 {
+            return Err(if quad_pos + pads >= 4 {
+                "Excess data after padding".to_owned()
+            } else {
+                "Discontinuous padding not allowed".to_owned()
+            });
+        }
+        pads = 0;
+        left = (left << 6) | u32::try_from(value).expect("the alphabet has 64 characters");
+        quad_pos = (quad_pos + 1) % 4;
+        match quad_pos {
+            2 => out.push(low_byte(left >> 4)),
+            3 => out.push(low_byte(left >> 2)),
+            0 => out.push(low_byte(left)),
+            _ => {}
+        }
+    }
+    match quad_pos {
+        0 => Ok(out),
+        1 => Err(one_extra_character(&out)),
+        _ if quad_pos + pads >= 4 => Ok(out),
+        _ => Err("Incorrect padding".to_owned()),
+    }
+}
+
+/// The error for Base64 with one character more than a multiple of 4, which no bytes
+/// encode to, given the bytes decoded before it.
+fn one_extra_character(decoded: &[u8]) -> String {
+    format!(
+        "Invalid base64-encoded string: number of data characters ({}) cannot be 1 more than a multiple of 4",
+        decoded.len() / 3 * 4 + 1
+    )
+}
+
+/// Encodes bytes with the Base32 alphabet and padding.
+fn encode_b32(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len().div_ceil(5) * 8);
+    for chunk in data.chunks(5) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u64, |group, (i, byte)| group | (u64::from(*byte) << (32 - 8 * i)));
+        // The characters holding at least one bit of the chunk
+        let digits = (chunk.len() * 8).div_ceil(5);
+        for i in 0..8 {
+            if i < digits {
+                let index = usize::try_from((group >> (35 - 5 * i)) & 0x1f).expect("5 bits fit in usize");
+                out.push(B32_ALPHABET[index]);
+            } else {
+                out.push(b'=');
+            }
+        }
+    }
+    out
+}
+
+/// Decodes padded Base32 whose length is a multiple of 8, as CPython's `base64._b32decode()`.
+fn decode_b32(data: &[u8]) -> Result<Vec<u8>, &'static str> {
+    let digits = data.iter().rposition(|byte| *byte != b'=').map_or(0, |last| last + 1);
+    let padding = data.len() - digits;
+    let mut out = Vec::with_capacity(data.len() / 8 * 5);
+    let mut group: u64 = 0;
+    for chunk in data[..digits].chunks(8) {
+        group = 0;
+        for byte in chunk {
+            let value = B32_ALPHABET
+                .iter()
+                .position(|c| c == byte)
+                .ok_or("Non-base32 digit found")?;
+            group = (group << 5) | value as u64;
+        }
+        out.extend_from_slice(&group.to_be_bytes()[3..]);
+    }
+    if !matches!(padding, 0 | 1 | 3 | 4 | 6) {
+        return Err("Incorrect padding");
+    }
+    if padding > 0 && !out.is_empty() {
+        group <<= 5 * padding;
+        let kept = (43 - 5 * padding) / 8;
+        out.truncate(out.len() - 5);
+        out.extend_from_slice(&group.to_be_bytes()[3..3 + kept]);
+    }
+    Ok(out)
+}
+
+/// Encodes bytes as uppercase hexadecimal digits.
+fn encode_b16(data: &[u8]) -> Vec<u8> {
+    data.iter()
+        .flat_map(|byte| {
+            [
+                B16_ALPHABET[usize::from(byte >> 4)],
+                B16_ALPHABET[usize::from(byte & 0xf)],
+            ]
+        })
+        .collect()
+}
+
+/// Decodes uppercase hexadecimal digits.
+fn decode_b16(data: &[u8]) -> Result<Vec<u8>, &'static str> {
+    let digit = |byte: &u8| B16_ALPHABET.iter().position(|c| c == byte);
+    if !data.iter().all(|byte| digit(byte).is_some()) {
+        return Err("Non-base16 digit found");
+    }
+    if !data.len().is_multiple_of(2) {
+        return Err("Odd-length string");
+    }
+    Ok(data
+        .chunks(2)
+        .map(|pair| {
+            let high = digit(&pair[0]).expect("digits were checked");
+            let low = digit(&pair[1]).expect("digits were checked");
+            u8::try_from((high << 4) | low).expect("two hexadecimal digits make a byte")
+        })
+        .collect())
+}
+
+/// The low 8 bits of a group of decoded bits.
+fn low_byte(bits: u32) -> u8 {
+    bits.to_le_bytes()[0]
+}
+
+/// Creates the error CPython raises as `binascii.Error`, a subclass of `ValueError`.
+#[cold]
+fn binascii_error(msg: impl std::fmt::Display) -> RunError {
+    SimpleException::new_msg(ExcType::ValueError, msg).into()
+}
+
+/// Creates the error of CPython's assertion that `altchars` or `map01` has the right length.
+#[cold]
+fn assertion_error(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunError {
+    SimpleException::new_msg(ExcType::AssertionError, value.py_repr(heap, interns)).into()
+}
@@ -0,0 +1,228 @@
+//! Implementation of the `collections` module.
+//!
+//! Provides Python's `collections` module:
+//! - Classes: `defaultdict`, `Counter`, `OrderedDict` and `deque`
+//! - Functions: `namedtuple()`, which creates named tuple types at runtime
+//!
+//! `defaultdict`, `Counter` and `OrderedDict` are dicts with a [`crate::types::dict::DictKind`],
+//! `deque` is implemented in [`crate::types::deque`] and the types created by `namedtuple()`
+//! in [`crate::types::namedtuple`].
+
+use crate::{
+    args::{ArgValues, bind_args},
+    builtins::Builtins,
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{AttrCallResult, Module, MontyIter, NamedTupleType, PyTrait, Type},
+    value::{EitherStr, Value},
+};
+
+/// collections module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum CollectionsFunctions {
+    Namedtuple,
+}
+
+/// Python's keywords, which can't be used as type or field names.
+const KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+    "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "nonlocal",
+    "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+/// Creates the `collections` module and allocates it on the heap.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Collections);
+
+    for (name, t) in [
+        (StaticStrings::Defaultdict, Type::DefaultDict),
+        (StaticStrings::CounterClass, Type::Counter),
+        (StaticStrings::OrderedDictClass, Type::OrderedDict),
+        (StaticStrings::Deque, Type::Deque),
+    ] {
+        module.set_attr(name, Value::Builtin(Builtins::Type(t)), heap, interns);
+    }
+
+    module.set_attr(
+        StaticStrings::Namedtuple,
+        Value::ModuleFunction(ModuleFunctions::Collections(CollectionsFunctions::Namedtuple)),
+        heap,
+        interns,
+    );
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a collections module function.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    function: CollectionsFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let value = match function {
+        CollectionsFunctions::Namedtuple => namedtuple(heap, args, interns)?,
+    };
+    Ok(AttrCallResult::Value(value))
+}
+
+/// Implements `namedtuple(typename, field_names, *, rename=False, defaults=None, module=None)`.
+///
+/// `module` is accepted for compatibility but ignored, since all types are created in
+/// `__main__`.
+fn namedtuple(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let [typename, field_names, rename, defaults, module] = bind_args(
+        "namedtuple",
+        args,
+        ["typename", "field_names", "rename", "defaults", "module"],
+        2,
+        heap,
+        interns,
+    )?;
+    let typename = typename.expect("required argument is bound");
+    defer_drop!(typename, heap);
+    let field_names = field_names.expect("required argument is bound");
+    defer_drop!(rename, heap);
+    defer_drop!(module, heap);
+
+    let typename = typename.py_str(heap, interns).into_owned();
+    let rename = rename.as_ref().is_some_and(|rename| rename.py_bool(heap, interns));
+
+    let defaults: Vec<Value> = match defaults {
+        None | Some(Value::None) => Vec::new(),
+        Some(defaults) => {
+            let items = match MontyIter::new(defaults, heap, interns) {
+                Ok(mut iter) => {
+                    let items = iter.collect(heap, interns);
+                    iter.drop_with_heap(heap);
+                    items
+                }
+                Err(e) => Err(e),
+            };
+            match items {
+                Ok(items) => items,
+                Err(e) => {
+                    field_names.drop_with_heap(heap);
+                    return Err(e);
+                }
+            }
+        }
+    };
+
+    let names = match parse_field_names(&typename, field_names, rename, heap, interns) {
+        Ok(names) => names,
+        Err(e) => {
+            defaults.drop_with_heap(heap);
+            return Err(e);
+        }
+    };
+    if defaults.len() > names.len() {
+        defaults.drop_with_heap(heap);
+        return Err(ExcType::type_error("Got more default values than field names"));
+    }
+
+    let field_names = names.into_iter().map(EitherStr::from).collect();
+    let namedtuple_type = NamedTupleType::new(typename, field_names, defaults);
+    Ok(Value::Ref(heap.allocate(HeapData::NamedTupleType(namedtuple_type))?))
+}
+
+/// Converts the `field_names` argument of `namedtuple()` to a list of validated names.
+///
+/// `field_names` is either a string of names separated by commas and/or whitespace, or an
+/// iterable of names. With `rename`, invalid and duplicate names are replaced by `_index`
+/// rather than raising `ValueError`.
+fn parse_field_names(
+    typename: &str,
+    field_names: Value,
+    rename: bool,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Vec<String>> {
+    let mut names: Vec<String> = if let Some(names) = field_names.as_either_str(heap) {
+        field_names.drop_with_heap(heap);
+        names
+            .as_str(interns)
+            .replace(',', " ")
+            .split_whitespace()
+            .map(str::to_owned)
+            .collect()
+    } else {
+        let mut iter = MontyIter::new(field_names, heap, interns)?;
+        let items: RunResult<Vec<Value>> = iter.collect(heap, interns);
+        iter.drop_with_heap(heap);
+        let items = items?;
+        let names = items
+            .iter()
+            .map(|item| item.py_str(heap, interns).into_owned())
+            .collect();
+        items.drop_with_heap(heap);
+        names
+    };
+
+    if rename {
+        for index in 0..names.len() {
+            let name = &names[index];
+            if !is_identifier(name)
+                || KEYWORDS.contains(&name.as_str())
+                || name.starts_with('_')
+                || names[..index].contains(name)
+            {
+                names[index] = format!("_{index}");
+            }
+        }
+    }
+
+    for name in std::iter::once(typename).chain(names.iter().map(String::as_str)) {
+        if !is_identifier(name) {
+            return Err(invalid_name(name, "must be valid identifiers"));
+        }
+        if KEYWORDS.contains(&name) {
+            return Err(invalid_name(name, "cannot be a keyword"));
+        }
+    }
+
+    for (index, name) in names.iter().enumerate() {
+        if name.starts_with('_') && !rename {
+            return Err(SimpleException::new_msg(
+                ExcType::ValueError,
+                format!("Field names cannot start with an underscore: '{name}'"),
+            )
+            .into());
+        }
+        if names[..index].contains(name) {
+            return Err(SimpleException::new_msg(
+                ExcType::ValueError,
+                format!("Encountered duplicate field name: '{name}'"),
+            )
+            .into());
+        }
+    }
+    Ok(names)
+}
+
+/// Returns whether `name` is a valid Python identifier.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|first| first == '_' || first.is_alphabetic())
+        && chars.all(|c| c == '_' || c.is_alphanumeric())
+}
+
+/// Returns the `ValueError` for an invalid type or field name.
+fn invalid_name(name: &str, problem: &str) -> RunError {
+    SimpleException::new_msg(
+        ExcType::ValueError,
+        format!("Type names and field names {problem}: '{name}'"),
+    )
+    .into()
+}
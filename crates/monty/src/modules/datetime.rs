@@ -0,0 +1,48 @@
+//! Implementation of the `datetime` module.
+//!
+//! Provides Python's `datetime` module:
+//! - Classes: `date`, `time`, `datetime`, `timedelta` and `timezone`
+//! - Constants: `MINYEAR`, `MAXYEAR` and `UTC`
+//!
+//! The classes themselves are implemented in [`crate::types::datetime`]. There is no
+//! abstract `tzinfo` base class: the fixed-offset `timezone` is the only time zone.
+
+use crate::{
+    builtins::Builtins,
+    heap::{Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        Module, TimeZone, Type,
+        datetime::{MAX_YEAR, MIN_YEAR},
+    },
+    value::Value,
+};
+
+/// Creates the `datetime` module and allocates it on the heap.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Datetime);
+
+    for (name, t) in [
+        (StaticStrings::Date, Type::Date),
+        (StaticStrings::Time, Type::Time),
+        (StaticStrings::Datetime, Type::DateTime),
+        (StaticStrings::Timedelta, Type::TimeDelta),
+        (StaticStrings::Timezone, Type::TimeZone),
+    ] {
+        module.set_attr(name, Value::Builtin(Builtins::Type(t)), heap, interns);
+    }
+
+    module.set_attr(StaticStrings::Minyear, Value::Int(MIN_YEAR.into()), heap, interns);
+    module.set_attr(StaticStrings::Maxyear, Value::Int(MAX_YEAR.into()), heap, interns);
+    let utc = TimeZone::UTC.into_value(heap)?;
+    module.set_attr(StaticStrings::UtcConst, utc, heap, interns);
+
+    heap.allocate(HeapData::Module(module))
+}
@@ -0,0 +1,131 @@
+//! Implementation of the `decimal` module.
+//!
+//! Provides Python's `decimal` module:
+//! - `Decimal` and `Context`, the classes of decimals and contexts (see [`crate::types::decimal`])
+//! - `getcontext()`, `setcontext()` and `localcontext()`, which read and switch the current context
+//! - the rounding modes `ROUND_CEILING` ... `ROUND_05UP`, and `MAX_PREC`
+//! - `DecimalException` and the signals which raise: `InvalidOperation`, `DivisionByZero`
+//!   and `Overflow`
+//!
+//! The current context lives on the heap. `setcontext()` copies the settings of the context
+//! it's given rather than sharing it, so changing that context afterwards doesn't change the
+//! current one.
+
+use crate::{
+    args::{ArgValues, bind_args},
+    builtins::Builtins,
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        AttrCallResult, DecimalContext, DecimalContextManager, Module, Type,
+        decimal::{MAX_PREC, context_arg, context_value, context_with},
+    },
+    value::Value,
+};
+
+/// decimal module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum DecimalFunctions {
+    Getcontext,
+    Setcontext,
+    Localcontext,
+}
+
+/// Creates the `decimal` module and allocates it on the heap.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Decimal);
+
+    for (name, ty) in [
+        (StaticStrings::DecimalClass, Type::Decimal),
+        (StaticStrings::ContextClass, Type::DecimalContext),
+    ] {
+        module.set_attr(name, Value::Builtin(Builtins::Type(ty)), heap, interns);
+    }
+    for (name, function) in [
+        (StaticStrings::Getcontext, DecimalFunctions::Getcontext),
+        (StaticStrings::Setcontext, DecimalFunctions::Setcontext),
+        (StaticStrings::Localcontext, DecimalFunctions::Localcontext),
+    ] {
+        module.set_attr(
+            name,
+            Value::ModuleFunction(ModuleFunctions::Decimal(function)),
+            heap,
+            interns,
+        );
+    }
+    // the rounding modes are strings named like their constants, as in CPython
+    for name in [
+        StaticStrings::RoundCeiling,
+        StaticStrings::RoundFloor,
+        StaticStrings::RoundUp,
+        StaticStrings::RoundDown,
+        StaticStrings::RoundHalfUp,
+        StaticStrings::RoundHalfDown,
+        StaticStrings::RoundHalfEven,
+        StaticStrings::Round05Up,
+    ] {
+        module.set_attr(name, Value::InternString(name.into()), heap, interns);
+    }
+    module.set_attr(
+        StaticStrings::MaxPrec,
+        Value::Int(i64::try_from(MAX_PREC).expect("MAX_PREC fits in i64")),
+        heap,
+        interns,
+    );
+    for (name, exc_type) in [
+        (StaticStrings::DecimalException, ExcType::DecimalException),
+        (StaticStrings::InvalidOperation, ExcType::InvalidOperation),
+        (StaticStrings::DivisionByZero, ExcType::DivisionByZero),
+        (StaticStrings::Overflow, ExcType::Overflow),
+    ] {
+        module.set_attr(name, Value::Builtin(Builtins::ExcType(exc_type)), heap, interns);
+    }
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a decimal module function.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    function: DecimalFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let value = match function {
+        DecimalFunctions::Getcontext => {
+            args.check_zero_args("getcontext", heap)?;
+            DecimalContext::Current.into_value(heap)?
+        }
+        DecimalFunctions::Setcontext => {
+            let context = args.get_one_arg("setcontext", heap)?;
+            let settings = context_value(&context, heap);
+            context.drop_with_heap(heap);
+            let Some(settings) = settings else {
+                return Err(ExcType::type_error("argument must be a context"));
+            };
+            heap.set_decimal_context(settings);
+            Value::None
+        }
+        DecimalFunctions::Localcontext => {
+            let [ctx, prec, rounding] = bind_args("localcontext", args, ["ctx", "prec", "rounding"], 0, heap, interns)?;
+            let result = context_arg(ctx.as_ref(), heap)
+                .and_then(|context| context_with(context, prec.as_ref(), rounding.as_ref(), heap, interns));
+            ctx.drop_with_heap(heap);
+            prec.drop_with_heap(heap);
+            rounding.drop_with_heap(heap);
+            let manager = DecimalContextManager::new(result?);
+            Value::Ref(heap.allocate(HeapData::DecimalContextManager(manager))?)
+        }
+    };
+    Ok(AttrCallResult::Value(value))
+}
@@ -0,0 +1,37 @@
+//! Implementation of the `enum` module.
+//!
+//! Provides Python's `enum` module:
+//! - `Enum`, `IntEnum` and `StrEnum`, the bases enum classes are defined with
+//!   (see [`crate::types::enum`])
+//! - `auto()`, which stands in for a member value generated when the class is created
+
+use crate::{
+    builtins::Builtins,
+    heap::{Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    resource::{ResourceError, ResourceTracker},
+    types::{Module, Type},
+    value::Value,
+};
+
+/// Creates the `enum` module and allocates it on the heap.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Enum);
+
+    for (name, ty) in [
+        (StaticStrings::EnumClass, Type::Enum),
+        (StaticStrings::IntEnum, Type::IntEnum),
+        (StaticStrings::StrEnum, Type::StrEnum),
+        (StaticStrings::Auto, Type::EnumAuto),
+    ] {
+        module.set_attr(name, Value::Builtin(Builtins::Type(ty)), heap, interns);
+    }
+
+    heap.allocate(HeapData::Module(module))
+}
@@ -0,0 +1,33 @@
+//! Implementation of the `fractions` module.
+//!
+//! Provides Python's `fractions` module:
+//! - `Fraction`, the class of exact rational numbers (see [`crate::types::fraction`])
+
+use crate::{
+    builtins::Builtins,
+    heap::{Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    resource::{ResourceError, ResourceTracker},
+    types::{Module, Type},
+    value::Value,
+};
+
+/// Creates the `fractions` module and allocates it on the heap.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Fractions);
+
+    module.set_attr(
+        StaticStrings::FractionClass,
+        Value::Builtin(Builtins::Type(Type::Fraction)),
+        heap,
+        interns,
+    );
+
+    heap.allocate(HeapData::Module(module))
+}
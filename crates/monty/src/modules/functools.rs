@@ -0,0 +1,176 @@
+//! Implementation of the `functools` module.
+//!
+//! Provides Python's `functools` module:
+//! - `reduce()`, which folds an iterable with a function
+//! - `partial`, which gives a function some of its arguments ahead of the call
+//! - `cache()` and `lru_cache()`, which remember the results of a function
+//! - `cmp_to_key()`, which turns a comparison function into a key function
+//! - `wraps()` and `update_wrapper()`, which return the wrapper unchanged since
+//!   functions have no writable attributes in Monty
+//!
+//! The objects these create are in [`crate::types::functools`]. `reduce()` and calls of
+//! these objects may call Python functions, so they're handled by the VM (see
+//! `bytecode/vm/functools.rs`).
+
+use crate::{
+    args::{ArgValues, bind_args},
+    builtins::{Builtins, is_callable},
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{AttrCallResult, CmpToKey, LruCacheWrapper, Module, Partial, Type},
+    value::Value,
+};
+
+/// functools module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum FunctoolsFunctions {
+    Reduce,
+    Cache,
+    LruCache,
+    CmpToKey,
+    Wraps,
+    UpdateWrapper,
+    /// The decorator returned by `lru_cache(maxsize)`, as a partial object of this function
+    /// with `maxsize` as its first argument.
+    DecoratingFunction,
+}
+
+/// The `maxsize` of `lru_cache()` when it's used as a decorator without arguments.
+const DEFAULT_MAXSIZE: usize = 128;
+
+/// Creates the `functools` module and allocates it on the heap.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Functools);
+
+    module.set_attr(
+        StaticStrings::Partial,
+        Value::Builtin(Builtins::Type(Type::Partial)),
+        heap,
+        interns,
+    );
+    for (name, function) in [
+        (StaticStrings::Reduce, FunctoolsFunctions::Reduce),
+        (StaticStrings::Cache, FunctoolsFunctions::Cache),
+        (StaticStrings::LruCache, FunctoolsFunctions::LruCache),
+        (StaticStrings::CmpToKey, FunctoolsFunctions::CmpToKey),
+        (StaticStrings::Wraps, FunctoolsFunctions::Wraps),
+        (StaticStrings::UpdateWrapper, FunctoolsFunctions::UpdateWrapper),
+    ] {
+        module.set_attr(
+            name,
+            Value::ModuleFunction(ModuleFunctions::Functools(function)),
+            heap,
+            interns,
+        );
+    }
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a functools module function.
+///
+/// `reduce()` is called by the VM, since it calls a Python function.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    function: FunctoolsFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let value = match function {
+        FunctoolsFunctions::Reduce => unreachable!("functools.reduce() is called by the VM"),
+        FunctoolsFunctions::Cache => {
+            let func = args.get_one_arg("cache", heap)?;
+            allocate_wrapper(heap, func, None)?
+        }
+        FunctoolsFunctions::LruCache => lru_cache(heap, args, interns)?,
+        FunctoolsFunctions::DecoratingFunction => {
+            let (maxsize, func) = args.get_two_args("decorating_function", heap)?;
+            let maxsize = match maxsize {
+                Value::Int(maxsize) => Some(usize::try_from(maxsize).unwrap_or(0)),
+                _ => None,
+            };
+            allocate_wrapper(heap, func, maxsize)?
+        }
+        FunctoolsFunctions::CmpToKey => {
+            let [cmp] = bind_args("cmp_to_key", args, ["mycmp"], 1, heap, interns)?;
+            let cmp = cmp.expect("required argument is bound");
+            Value::Ref(heap.allocate(HeapData::CmpToKey(CmpToKey::new(cmp)))?)
+        }
+        FunctoolsFunctions::Wraps => {
+            let [wrapped, assigned, updated] =
+                bind_args("wraps", args, ["wrapped", "assigned", "updated"], 1, heap, interns)?;
+            assigned.drop_with_heap(heap);
+            updated.drop_with_heap(heap);
+            let wrapped = wrapped.expect("required argument is bound");
+            let update_wrapper = Value::ModuleFunction(ModuleFunctions::Functools(FunctoolsFunctions::UpdateWrapper));
+            let keyword = Value::InternString(StaticStrings::Wrapped.into());
+            let partial = Partial::new(update_wrapper, Vec::new(), vec![(keyword, wrapped)]);
+            Value::Ref(heap.allocate(HeapData::Partial(partial))?)
+        }
+        FunctoolsFunctions::UpdateWrapper => {
+            let [wrapper, wrapped, assigned, updated] = bind_args(
+                "update_wrapper",
+                args,
+                ["wrapper", "wrapped", "assigned", "updated"],
+                2,
+                heap,
+                interns,
+            )?;
+            wrapped.drop_with_heap(heap);
+            assigned.drop_with_heap(heap);
+            updated.drop_with_heap(heap);
+            wrapper.expect("required argument is bound")
+        }
+    };
+    Ok(AttrCallResult::Value(value))
+}
+
+/// Implements `lru_cache(maxsize=128, typed=False)`.
+///
+/// Used directly as a decorator, `maxsize` is the function to wrap. Otherwise returns the
+/// decorator, which wraps a function with a cache of at most `maxsize` results (negative
+/// sizes meaning 0), or an unbounded cache if `maxsize` is `None`.
+///
+/// `typed` is accepted for compatibility but ignored: arguments which compare equal
+/// share a cache entry whatever their types.
+fn lru_cache(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let [maxsize, typed] = bind_args("lru_cache", args, ["maxsize", "typed"], 0, heap, interns)?;
+    typed.drop_with_heap(heap);
+    let maxsize = match maxsize {
+        None | Some(Value::None) => Value::None,
+        Some(Value::Int(maxsize)) => Value::Int(maxsize.max(0)),
+        Some(Value::Bool(maxsize)) => Value::Int(i64::from(maxsize)),
+        Some(func) if is_callable(&func, heap) => {
+            return Ok(allocate_wrapper(heap, func, Some(DEFAULT_MAXSIZE))?);
+        }
+        Some(other) => {
+            other.drop_with_heap(heap);
+            return Err(ExcType::type_error(
+                "Expected first argument to be an integer, a callable, or None",
+            ));
+        }
+    };
+    let decorator = Value::ModuleFunction(ModuleFunctions::Functools(FunctoolsFunctions::DecoratingFunction));
+    let partial = Partial::new(decorator, vec![maxsize], Vec::new());
+    Ok(Value::Ref(heap.allocate(HeapData::Partial(partial))?))
+}
+
+/// Allocates a wrapper caching the results of `func`, taking ownership of it.
+fn allocate_wrapper(
+    heap: &mut Heap<impl ResourceTracker>,
+    func: Value,
+    maxsize: Option<usize>,
+) -> Result<Value, ResourceError> {
+    let wrapper = LruCacheWrapper::new(func, maxsize);
+    Ok(Value::Ref(heap.allocate(HeapData::LruCacheWrapper(wrapper))?))
+}
@@ -0,0 +1,133 @@
+//! Implementation of the `hashlib` module.
+//!
+//! Provides Python's `hashlib` module:
+//! - `md5()`, `sha1()`, `sha256()` and `sha512()`, which create hash objects
+//! - `new()`, which creates a hash object of an algorithm given by name
+//!
+//! The hash objects and algorithms are in [`crate::types::hashlib`].
+
+use crate::{
+    args::{ArgValues, bind_args},
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        AttrCallResult, Module, PyTrait,
+        hashlib::{Algorithm, Hash, hash_data},
+    },
+    value::Value,
+};
+
+/// hashlib module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum HashlibFunctions {
+    Md5,
+    Sha1,
+    Sha256,
+    Sha512,
+    New,
+}
+
+/// Creates the `hashlib` module and allocates it on the heap.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Hashlib);
+
+    for (name, function) in [
+        (StaticStrings::Md5, HashlibFunctions::Md5),
+        (StaticStrings::Sha1, HashlibFunctions::Sha1),
+        (StaticStrings::Sha256, HashlibFunctions::Sha256),
+        (StaticStrings::Sha512, HashlibFunctions::Sha512),
+        (StaticStrings::New, HashlibFunctions::New),
+    ] {
+        module.set_attr(
+            name,
+            Value::ModuleFunction(ModuleFunctions::Hashlib(function)),
+            heap,
+            interns,
+        );
+    }
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a hashlib module function.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    function: HashlibFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let (algorithm, data) = match function {
+        HashlibFunctions::Md5 => constructor_args(Algorithm::Md5, args, heap, interns)?,
+        HashlibFunctions::Sha1 => constructor_args(Algorithm::Sha1, args, heap, interns)?,
+        HashlibFunctions::Sha256 => constructor_args(Algorithm::Sha256, args, heap, interns)?,
+        HashlibFunctions::Sha512 => constructor_args(Algorithm::Sha512, args, heap, interns)?,
+        HashlibFunctions::New => new_args(args, heap, interns)?,
+    };
+
+    let mut hash = Hash::new(algorithm);
+    if let Some(data) = data {
+        let bytes = hash_data(&data, heap, interns);
+        data.drop_with_heap(heap);
+        hash.update_checked(&bytes?, heap.tracker_mut())?;
+    }
+    let id = heap.allocate(HeapData::Hash(hash))?;
+    Ok(AttrCallResult::Value(Value::Ref(id)))
+}
+
+/// Binds the arguments of `md5(string=b'', *, usedforsecurity=True)` and the other
+/// constructors, returning the data to hash first.
+fn constructor_args(
+    algorithm: Algorithm,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<(Algorithm, Option<Value>)> {
+    let [data, usedforsecurity] = bind_args(algorithm.name(), args, ["string", "usedforsecurity"], 0, heap, interns)?;
+    usedforsecurity.drop_with_heap(heap);
+    Ok((algorithm, data))
+}
+
+/// Binds the arguments of `new(name, data=b'', *, usedforsecurity=True)`, returning the
+/// named algorithm and the data to hash first.
+///
+/// # Errors
+/// Returns `ValueError` for an algorithm other than MD5, SHA-1, SHA-256 and SHA-512.
+fn new_args(
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<(Algorithm, Option<Value>)> {
+    let [name, data, usedforsecurity] = bind_args("new", args, ["name", "data", "usedforsecurity"], 1, heap, interns)?;
+    usedforsecurity.drop_with_heap(heap);
+    let name = name.expect("required argument is bound");
+    let algorithm = match name.as_either_str(heap) {
+        Some(text) => {
+            let text = text.as_str(interns);
+            Algorithm::from_name(text).ok_or_else(|| {
+                SimpleException::new_msg(ExcType::ValueError, format!("unsupported hash type {text}")).into()
+            })
+        }
+        None => Err(ExcType::type_error(format!(
+            "new() argument 'name' must be str, not {}",
+            name.py_type(heap)
+        ))),
+    };
+    name.drop_with_heap(heap);
+    match algorithm {
+        Ok(algorithm) => Ok((algorithm, data)),
+        Err(e) => {
+            data.drop_with_heap(heap);
+            Err(e)
+        }
+    }
+}
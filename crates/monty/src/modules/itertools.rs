@@ -0,0 +1,344 @@
+//! Implementation of the `itertools` module.
+//!
+//! Provides the iterator building blocks of Python's `itertools` module:
+//! - Infinite iterators: `count()`, `cycle()` and `repeat()`
+//! - Iterators over their inputs: `accumulate()`, `batched()`, `chain()` (and
+//!   `chain.from_iterable()`), `groupby()`, `islice()`, `pairwise()` and `zip_longest()`
+//! - Combinatoric iterators: `product()`, `permutations()` and `combinations()`
+//!
+//! All of them are lazy: they advance their inputs, which may be generators or call
+//! Python functions, one value at a time. So they're created and advanced by the VM
+//! (see `bytecode/vm/itertools.rs`), as iterators with a
+//! [`CallIter::Itertools`](crate::types::iter::CallIter) state, which is an [`ItertoolsIter`].
+
+use crate::{
+    args::ArgValues,
+    exception_private::RunResult,
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{AttrCallResult, Module, PyTrait},
+    value::Value,
+};
+
+/// itertools module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum ItertoolsFunctions {
+    Accumulate,
+    Batched,
+    Chain,
+    Combinations,
+    Count,
+    Cycle,
+    Groupby,
+    Islice,
+    Pairwise,
+    Permutations,
+    Product,
+    Repeat,
+    ZipLongest,
+}
+
+/// The state of an iterator created by the `itertools` module.
+///
+/// Inputs are iterators on the heap, owned by the state. The VM takes the state out of
+/// the iterator while advancing it, leaving `Running` in its place.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) enum ItertoolsIter {
+    /// `accumulate(iterable, func=None, *, initial=None)`; a `None` function adds.
+    Accumulate {
+        iterator: HeapId,
+        func: Value,
+        /// The running total, or the initial value; `None` before the first value.
+        total: Option<Value>,
+        /// Whether `total` is the initial value, which is returned first.
+        initial: bool,
+    },
+    /// `batched(iterable, n, *, strict=False)`.
+    Batched {
+        iterator: HeapId,
+        size: usize,
+        strict: bool,
+    },
+    /// `chain(*iterables)`, with an iterator over the iterables and one over the current
+    /// iterable, if any.
+    Chain { iterables: HeapId, current: Option<HeapId> },
+    /// `combinations(iterable, r)`, with the indices in `pool` of the last combination.
+    Combinations {
+        pool: Vec<Value>,
+        r: usize,
+        indices: Option<Vec<usize>>,
+    },
+    /// `count(start=0, step=1)`.
+    Count { current: Value, step: Value },
+    /// `cycle(iterable)`, with the values taken from the input, which is released once
+    /// exhausted, and the index of the next saved value to return.
+    Cycle {
+        iterator: Option<HeapId>,
+        saved: Vec<Value>,
+        index: usize,
+    },
+    /// `groupby(iterable, key=None)`, with the key and value of the first value of the
+    /// next group once the input has moved past the current one.
+    Groupby {
+        iterator: HeapId,
+        key_fn: Value,
+        next: Option<(Value, Value)>,
+    },
+    /// `islice(iterable, start, stop, step)`, with the position of the next value to
+    /// return and the number of values taken from the input.
+    Islice {
+        iterator: HeapId,
+        next_index: usize,
+        stop: Option<usize>,
+        step: usize,
+        position: usize,
+    },
+    /// `pairwise(iterable)`, with the last value taken from the input.
+    Pairwise { iterator: HeapId, previous: Option<Value> },
+    /// `permutations(iterable, r=None)`, with the index of the next permutation out of `total`.
+    Permutations {
+        pool: Vec<Value>,
+        r: usize,
+        next_index: usize,
+        total: usize,
+    },
+    /// `product(*iterables, repeat=1)`, with the index of the next tuple out of `total`.
+    Product {
+        pools: Vec<Vec<Value>>,
+        repeat: usize,
+        next_index: usize,
+        total: usize,
+    },
+    /// `repeat(object[, times])`, with the number of repeats left.
+    Repeat { object: Value, times: Option<usize> },
+    /// `zip_longest(*iterables, fillvalue=None)`, with each input replaced with `None`
+    /// once exhausted.
+    ZipLongest {
+        iterators: Vec<Option<HeapId>>,
+        fillvalue: Value,
+    },
+    /// The state while the VM advances the iterator.
+    Running,
+}
+
+impl ItertoolsIter {
+    /// Estimates the memory used by the values the state holds.
+    pub(crate) fn estimate_size(&self) -> usize {
+        let values = match self {
+            Self::Combinations { pool, .. } | Self::Permutations { pool, .. } => pool.len(),
+            Self::Cycle { saved, .. } => saved.len(),
+            Self::Product { pools, .. } => pools.iter().map(Vec::len).sum(),
+            _ => 0,
+        };
+        std::mem::size_of::<Self>() + values * std::mem::size_of::<Value>()
+    }
+
+    pub(crate) fn has_refs(&self) -> bool {
+        match self {
+            // All hold input iterators
+            Self::Accumulate { .. }
+            | Self::Batched { .. }
+            | Self::Chain { .. }
+            | Self::Groupby { .. }
+            | Self::Islice { .. }
+            | Self::Pairwise { .. } => true,
+            Self::Combinations { pool, .. } | Self::Permutations { pool, .. } => pool.iter().any(is_ref),
+            Self::Count { current, step } => is_ref(current) || is_ref(step),
+            Self::Cycle { iterator, saved, .. } => iterator.is_some() || saved.iter().any(is_ref),
+            Self::Product { pools, .. } => pools.iter().flatten().any(is_ref),
+            Self::Repeat { object, .. } => is_ref(object),
+            Self::ZipLongest { iterators, fillvalue } => iterators.iter().any(Option::is_some) || is_ref(fillvalue),
+            Self::Running => false,
+        }
+    }
+
+    pub(crate) fn push_ref_ids(&self, work_list: &mut Vec<HeapId>) {
+        let push = |work_list: &mut Vec<HeapId>, value: &Value| {
+            if let Value::Ref(id) = value {
+                work_list.push(*id);
+            }
+        };
+        match self {
+            Self::Accumulate {
+                iterator, func, total, ..
+            } => {
+                work_list.push(*iterator);
+                push(work_list, func);
+                if let Some(total) = total {
+                    push(work_list, total);
+                }
+            }
+            Self::Batched { iterator, .. } | Self::Islice { iterator, .. } => work_list.push(*iterator),
+            Self::Chain { iterables, current } => {
+                work_list.push(*iterables);
+                work_list.extend(current);
+            }
+            Self::Combinations { pool, .. } | Self::Permutations { pool, .. } => {
+                for value in pool {
+                    push(work_list, value);
+                }
+            }
+            Self::Count { current, step } => {
+                push(work_list, current);
+                push(work_list, step);
+            }
+            Self::Cycle { iterator, saved, .. } => {
+                work_list.extend(iterator);
+                for value in saved {
+                    push(work_list, value);
+                }
+            }
+            Self::Groupby { iterator, key_fn, next } => {
+                work_list.push(*iterator);
+                push(work_list, key_fn);
+                if let Some((key, item)) = next {
+                    push(work_list, key);
+                    push(work_list, item);
+                }
+            }
+            Self::Pairwise { iterator, previous } => {
+                work_list.push(*iterator);
+                if let Some(previous) = previous {
+                    push(work_list, previous);
+                }
+            }
+            Self::Product { pools, .. } => {
+                for value in pools.iter().flatten() {
+                    push(work_list, value);
+                }
+            }
+            Self::Repeat { object, .. } => push(work_list, object),
+            Self::ZipLongest { iterators, fillvalue } => {
+                work_list.extend(iterators.iter().flatten());
+                push(work_list, fillvalue);
+            }
+            Self::Running => {}
+        }
+    }
+
+    pub(crate) fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        match self {
+            Self::Accumulate {
+                iterator, func, total, ..
+            } => {
+                stack.push(*iterator);
+                func.py_dec_ref_ids(stack);
+                if let Some(total) = total {
+                    total.py_dec_ref_ids(stack);
+                }
+            }
+            Self::Batched { iterator, .. } | Self::Islice { iterator, .. } => stack.push(*iterator),
+            Self::Chain { iterables, current } => {
+                stack.push(*iterables);
+                stack.extend(current.iter());
+            }
+            Self::Combinations { pool, .. } | Self::Permutations { pool, .. } => {
+                for value in pool {
+                    value.py_dec_ref_ids(stack);
+                }
+            }
+            Self::Count { current, step } => {
+                current.py_dec_ref_ids(stack);
+                step.py_dec_ref_ids(stack);
+            }
+            Self::Cycle { iterator, saved, .. } => {
+                stack.extend(iterator.iter());
+                for value in saved {
+                    value.py_dec_ref_ids(stack);
+                }
+            }
+            Self::Groupby { iterator, key_fn, next } => {
+                stack.push(*iterator);
+                key_fn.py_dec_ref_ids(stack);
+                if let Some((key, item)) = next {
+                    key.py_dec_ref_ids(stack);
+                    item.py_dec_ref_ids(stack);
+                }
+            }
+            Self::Pairwise { iterator, previous } => {
+                stack.push(*iterator);
+                if let Some(previous) = previous {
+                    previous.py_dec_ref_ids(stack);
+                }
+            }
+            Self::Product { pools, .. } => {
+                for value in pools.iter_mut().flatten() {
+                    value.py_dec_ref_ids(stack);
+                }
+            }
+            Self::Repeat { object, .. } => object.py_dec_ref_ids(stack),
+            Self::ZipLongest { iterators, fillvalue } => {
+                stack.extend(iterators.iter().flatten());
+                fillvalue.py_dec_ref_ids(stack);
+            }
+            Self::Running => {}
+        }
+    }
+}
+
+impl<T: ResourceTracker> DropWithHeap<T> for ItertoolsIter {
+    fn drop_with_heap(mut self, heap: &mut Heap<T>) {
+        let mut ids = Vec::new();
+        self.py_dec_ref_ids(&mut ids);
+        for id in ids {
+            heap.dec_ref(id);
+        }
+    }
+}
+
+fn is_ref(value: &Value) -> bool {
+    matches!(value, Value::Ref(_))
+}
+
+/// The module's functions and the names they're bound to.
+const FUNCTIONS: [(StaticStrings, ItertoolsFunctions); 13] = [
+    (StaticStrings::Accumulate, ItertoolsFunctions::Accumulate),
+    (StaticStrings::Batched, ItertoolsFunctions::Batched),
+    (StaticStrings::Chain, ItertoolsFunctions::Chain),
+    (StaticStrings::Combinations, ItertoolsFunctions::Combinations),
+    (StaticStrings::Count, ItertoolsFunctions::Count),
+    (StaticStrings::Cycle, ItertoolsFunctions::Cycle),
+    (StaticStrings::Groupby, ItertoolsFunctions::Groupby),
+    (StaticStrings::Islice, ItertoolsFunctions::Islice),
+    (StaticStrings::Pairwise, ItertoolsFunctions::Pairwise),
+    (StaticStrings::Permutations, ItertoolsFunctions::Permutations),
+    (StaticStrings::Product, ItertoolsFunctions::Product),
+    (StaticStrings::Repeat, ItertoolsFunctions::Repeat),
+    (StaticStrings::ZipLongest, ItertoolsFunctions::ZipLongest),
+];
+
+/// Creates the `itertools` module and allocates it on the heap.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Itertools);
+
+    for (name, function) in FUNCTIONS {
+        module.set_attr(
+            name,
+            Value::ModuleFunction(ModuleFunctions::Itertools(function)),
+            heap,
+            interns,
+        );
+    }
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to an itertools module function.
+///
+/// All of them are called by the VM, since the iterators they create are advanced by it.
+pub(super) fn call(
+    _heap: &mut Heap<impl ResourceTracker>,
+    function: ItertoolsFunctions,
+    _args: ArgValues,
+) -> RunResult<AttrCallResult> {
+    unreachable!("itertools.{function}() is called by the VM")
+}
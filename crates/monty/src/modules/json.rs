@@ -0,0 +1,514 @@
+//! Implementation of the `json` module.
+//!
+//! Provides a minimal implementation of Python's `json` module with:
+//! - `loads(s)`: Parses a JSON document from a str, bytes or bytearray
+//! - `dumps(obj, *, indent=None, separators=None, sort_keys=False, default=None, ensure_ascii=True)`:
+//!   Serializes a value to a JSON string
+//! - `JSONDecodeError`: Raised by `loads()` for malformed documents, a subclass of `ValueError`
+//!
+//! Both work directly on heap values, so everything `loads()` builds is charged to the
+//! resource tracker. `dumps()` may call its `default` function, so it's run by the VM
+//! (see `bytecode/vm/json.rs`) using the string escaping helpers here.
+
+use std::fmt::Write;
+
+use num_bigint::BigInt;
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    exception_private::{ExcType, RunError, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{AttrCallResult, Dict, List, LongInt, Module, PyTrait, str::allocate_string},
+    value::Value,
+};
+
+/// JSON module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum JsonFunctions {
+    Loads,
+    Dumps,
+}
+
+/// Creates the `json` module and allocates it on the heap.
+///
+/// The module provides:
+/// - `loads(s)`: Parse a JSON document
+/// - `dumps(obj, ...)`: Serialize a value to JSON
+/// - `JSONDecodeError`: The exception raised for malformed documents
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Json);
+
+    module.set_attr(
+        StaticStrings::Loads,
+        Value::ModuleFunction(ModuleFunctions::Json(JsonFunctions::Loads)),
+        heap,
+        interns,
+    );
+    module.set_attr(
+        StaticStrings::Dumps,
+        Value::ModuleFunction(ModuleFunctions::Json(JsonFunctions::Dumps)),
+        heap,
+        interns,
+    );
+    module.set_attr(
+        StaticStrings::JsonDecodeError,
+        Value::Builtin(Builtins::ExcType(ExcType::JSONDecodeError)),
+        heap,
+        interns,
+    );
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a json module function.
+///
+/// `dumps()` is always called by the VM, since it may call its `default` function.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    functions: JsonFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    match functions {
+        JsonFunctions::Loads => loads(heap, args, interns).map(AttrCallResult::Value),
+        JsonFunctions::Dumps => unreachable!("json.dumps() is called by the VM"),
+    }
+}
+
+/// Implementation of `json.loads(s)`.
+///
+/// Bytes and bytearrays are decoded as UTF-8 first.
+///
+/// # Errors
+/// Returns `TypeError` if `s` isn't a str, bytes or bytearray, `UnicodeDecodeError` if
+/// bytes aren't valid UTF-8, and `JSONDecodeError` if the document is malformed.
+fn loads(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let value = args.get_one_arg("json.loads", heap)?;
+    let doc = match &value {
+        Value::InternString(string_id) => Ok(interns.get_str(*string_id).to_owned()),
+        Value::InternBytes(bytes_id) => decode_utf8(interns.get_bytes(*bytes_id)),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Str(s) => Ok(s.as_str().to_owned()),
+            HeapData::Bytes(b) => decode_utf8(b.as_slice()),
+            HeapData::ByteArray(b) => decode_utf8(b.as_slice()),
+            _ => Err(loads_type_error(&value, heap)),
+        },
+        _ => Err(loads_type_error(&value, heap)),
+    };
+    value.drop_with_heap(heap);
+
+    let doc = doc?;
+    let mut decoder = Decoder { doc: &doc, pos: 0 };
+    decoder.skip_whitespace();
+    let result = decoder.decode_value(heap, interns, 0)?;
+    decoder.skip_whitespace();
+    if decoder.pos == doc.len() {
+        Ok(result)
+    } else {
+        result.drop_with_heap(heap);
+        Err(decoder.error("Extra data", decoder.pos))
+    }
+}
+
+fn decode_utf8(bytes: &[u8]) -> RunResult<String> {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => Ok(s.to_owned()),
+        Err(_) => Err(ExcType::unicode_decode_error_invalid_utf8()),
+    }
+}
+
+#[cold]
+fn loads_type_error(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunError {
+    ExcType::type_error(format!(
+        "the JSON object must be str, bytes or bytearray, not {}",
+        value.py_type(heap)
+    ))
+}
+
+/// Recursive descent parser building heap values from a JSON document.
+///
+/// Error positions follow CPython's scanner, so messages match exactly.
+struct Decoder<'a> {
+    doc: &'a str,
+    /// Byte offset of the next character to read.
+    pos: usize,
+}
+
+impl Decoder<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.doc.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    #[cold]
+    fn error(&self, msg: &str, pos: usize) -> RunError {
+        ExcType::json_decode_error(msg, self.doc, pos)
+    }
+
+    /// Parses the value starting at the current position, with no leading whitespace.
+    ///
+    /// `depth` is the number of arrays and objects the value is nested in, checked against
+    /// the recursion limit so deeply nested documents can't overflow the stack.
+    fn decode_value(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+        depth: usize,
+    ) -> RunResult<Value> {
+        let rest = &self.doc[self.pos..];
+        let constant = if rest.starts_with("null") {
+            Some((Value::None, 4))
+        } else if rest.starts_with("true") {
+            Some((Value::Bool(true), 4))
+        } else if rest.starts_with("false") {
+            Some((Value::Bool(false), 5))
+        } else if rest.starts_with("NaN") {
+            Some((Value::Float(f64::NAN), 3))
+        } else if rest.starts_with("Infinity") {
+            Some((Value::Float(f64::INFINITY), 8))
+        } else if rest.starts_with("-Infinity") {
+            Some((Value::Float(f64::NEG_INFINITY), 9))
+        } else {
+            None
+        };
+        if let Some((value, len)) = constant {
+            self.pos += len;
+            return Ok(value);
+        }
+
+        match self.peek() {
+            Some(b'"') => {
+                let s = self.decode_string()?;
+                allocate_string(s, heap)
+            }
+            Some(b'[') => {
+                heap.tracker().check_recursion_depth(depth)?;
+                self.decode_array(heap, interns, depth + 1)
+            }
+            Some(b'{') => {
+                heap.tracker().check_recursion_depth(depth)?;
+                self.decode_object(heap, interns, depth + 1)
+            }
+            Some(b'-' | b'0'..=b'9') => match self.decode_number(heap)? {
+                Some(value) => Ok(value),
+                None => Err(self.error("Expecting value", self.pos)),
+            },
+            _ => Err(self.error("Expecting value", self.pos)),
+        }
+    }
+
+    /// Parses a number, returning `None` if there's no valid number at the current position.
+    ///
+    /// Like CPython, the longest valid prefix is used, so `1.` parses as `1` followed by extra data.
+    fn decode_number(&mut self, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Option<Value>> {
+        let bytes = self.doc.as_bytes();
+        let start = self.pos;
+        let mut end = start;
+        if bytes[end] == b'-' {
+            end += 1;
+        }
+        match bytes.get(end) {
+            Some(b'0') => end += 1,
+            Some(b'1'..=b'9') => {
+                while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+                    end += 1;
+                }
+            }
+            _ => return Ok(None),
+        }
+
+        let mut is_float = false;
+        if bytes.get(end) == Some(&b'.') && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) {
+            is_float = true;
+            end += 2;
+            while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+                end += 1;
+            }
+        }
+        if matches!(bytes.get(end), Some(b'e' | b'E')) {
+            let mut exp_end = end + 1;
+            if matches!(bytes.get(exp_end), Some(b'+' | b'-')) {
+                exp_end += 1;
+            }
+            if bytes.get(exp_end).is_some_and(u8::is_ascii_digit) {
+                is_float = true;
+                end = exp_end;
+                while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+                    end += 1;
+                }
+            }
+        }
+
+        self.pos = end;
+        let text = &self.doc[start..end];
+        let value = if is_float {
+            Value::Float(text.parse().expect("valid float literal"))
+        } else if let Ok(i) = text.parse::<i64>() {
+            Value::Int(i)
+        } else {
+            let bi: BigInt = text.parse().expect("valid integer literal");
+            LongInt::new(bi).into_value(heap)?
+        };
+        Ok(Some(value))
+    }
+
+    /// Parses a string, starting at its opening quote.
+    fn decode_string(&mut self) -> RunResult<String> {
+        let bytes = self.doc.as_bytes();
+        let start = self.pos;
+        let mut pos = start + 1;
+        let mut s = String::new();
+        loop {
+            let chunk_start = pos;
+            while bytes.get(pos).is_some_and(|&b| b != b'"' && b != b'\\' && b >= 0x20) {
+                pos += 1;
+            }
+            s.push_str(&self.doc[chunk_start..pos]);
+
+            match bytes.get(pos) {
+                None => return Err(self.error("Unterminated string starting at", start)),
+                Some(b'"') => {
+                    self.pos = pos + 1;
+                    return Ok(s);
+                }
+                Some(b'\\') => {
+                    let escape = match bytes.get(pos + 1) {
+                        None => return Err(self.error("Unterminated string starting at", start)),
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            let (c, len) = self.decode_unicode_escape(pos + 1)?;
+                            s.push(c);
+                            pos += 1 + len;
+                            continue;
+                        }
+                        Some(_) => return Err(self.error("Invalid \\escape", pos)),
+                    };
+                    s.push(escape);
+                    pos += 2;
+                }
+                Some(_) => return Err(self.error("Invalid control character at", pos)),
+            }
+        }
+    }
+
+    /// Decodes a `\uXXXX` escape, where `u_pos` is the position of the `u`.
+    ///
+    /// A high surrogate followed by an escaped low surrogate is combined into one character.
+    /// Lone surrogates can't be represented in a Rust string, so they're replaced by U+FFFD.
+    /// Returns the character and the number of bytes consumed after the backslash.
+    fn decode_unicode_escape(&self, u_pos: usize) -> RunResult<(char, usize)> {
+        let Some(code) = self.hex4(u_pos + 1) else {
+            return Err(self.error("Invalid \\uXXXX escape", u_pos));
+        };
+        if (0xD800..0xDC00).contains(&code) && self.doc[u_pos + 5..].starts_with("\\u") {
+            let Some(low) = self.hex4(u_pos + 7) else {
+                return Err(self.error("Invalid \\uXXXX escape", u_pos + 6));
+            };
+            if (0xDC00..0xE000).contains(&low) {
+                let combined = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                return Ok((char::from_u32(combined).expect("valid surrogate pair"), 11));
+            }
+        }
+        Ok((char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER), 5))
+    }
+
+    /// Parses four hex digits starting at `pos`.
+    fn hex4(&self, pos: usize) -> Option<u32> {
+        let digits = self.doc.get(pos..pos + 4)?;
+        if digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            u32::from_str_radix(digits, 16).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Parses an array, starting at its opening bracket.
+    fn decode_array(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+        depth: usize,
+    ) -> RunResult<Value> {
+        self.pos += 1;
+        let mut items: Vec<Value> = Vec::new();
+        if let Err(e) = self.decode_array_items(&mut items, heap, interns, depth) {
+            items.drop_with_heap(heap);
+            return Err(e);
+        }
+        let heap_id = heap.allocate(HeapData::List(List::new(items)))?;
+        Ok(Value::Ref(heap_id))
+    }
+
+    fn decode_array_items(
+        &mut self,
+        items: &mut Vec<Value>,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+        depth: usize,
+    ) -> RunResult<()> {
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            items.push(self.decode_value(heap, interns, depth)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some(b',') => {
+                    let comma = self.pos;
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    if self.peek() == Some(b']') {
+                        return Err(self.error("Illegal trailing comma before end of array", comma));
+                    }
+                }
+                _ => return Err(self.error("Expecting ',' delimiter", self.pos)),
+            }
+        }
+    }
+
+    /// Parses an object, starting at its opening brace.
+    ///
+    /// Later duplicate keys replace earlier ones, like CPython.
+    fn decode_object(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+        depth: usize,
+    ) -> RunResult<Value> {
+        self.pos += 1;
+        let mut pairs: Vec<(Value, Value)> = Vec::new();
+        if let Err(e) = self.decode_object_pairs(&mut pairs, heap, interns, depth) {
+            for (key, value) in pairs {
+                key.drop_with_heap(heap);
+                value.drop_with_heap(heap);
+            }
+            return Err(e);
+        }
+        let dict = Dict::from_pairs(pairs, heap, interns)?;
+        let heap_id = heap.allocate(HeapData::Dict(dict))?;
+        Ok(Value::Ref(heap_id))
+    }
+
+    fn decode_object_pairs(
+        &mut self,
+        pairs: &mut Vec<(Value, Value)>,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+        depth: usize,
+    ) -> RunResult<()> {
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            if self.peek() != Some(b'"') {
+                return Err(self.error("Expecting property name enclosed in double quotes", self.pos));
+            }
+            let key = self.decode_string()?;
+            let key = allocate_string(key, heap)?;
+            self.skip_whitespace();
+            if self.peek() != Some(b':') {
+                key.drop_with_heap(heap);
+                return Err(self.error("Expecting ':' delimiter", self.pos));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let value = match self.decode_value(heap, interns, depth) {
+                Ok(value) => value,
+                Err(e) => {
+                    key.drop_with_heap(heap);
+                    return Err(e);
+                }
+            };
+            pairs.push((key, value));
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some(b',') => {
+                    let comma = self.pos;
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    if self.peek() == Some(b'}') {
+                        return Err(self.error("Illegal trailing comma before end of object", comma));
+                    }
+                }
+                _ => return Err(self.error("Expecting ',' delimiter", self.pos)),
+            }
+        }
+    }
+}
+
+/// Writes `s` as a JSON string literal, including the quotes.
+///
+/// With `ensure_ascii`, all non-ASCII characters are escaped, using surrogate pairs
+/// outside the Basic Multilingual Plane.
+pub(crate) fn write_json_str(out: &mut String, s: &str, ensure_ascii: bool) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if c < ' ' || (ensure_ascii && c > '~') => {
+                let mut units = [0; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    write!(out, "\\u{unit:04x}").expect("writing to a String can't fail");
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Writes a float the way `json.dumps()` does: like `repr()`, but with JavaScript's
+/// names for infinities and NaN.
+pub(crate) fn write_json_float(out: &mut String, f: f64, heap: &Heap<impl ResourceTracker>, interns: &Interns) {
+    if f.is_nan() {
+        out.push_str("NaN");
+    } else if f == f64::INFINITY {
+        out.push_str("Infinity");
+    } else if f == f64::NEG_INFINITY {
+        out.push_str("-Infinity");
+    } else {
+        out.push_str(&Value::Float(f).py_repr(heap, interns));
+    }
+}
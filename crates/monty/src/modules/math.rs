@@ -0,0 +1,1042 @@
+//! Implementation of the `math` module.
+//!
+//! Provides Python's `math` module:
+//! - Constants: `pi`, `e`, `tau`, `inf` and `nan`
+//! - Rounding: `floor()`, `ceil()` and `trunc()`, which return ints
+//! - Powers and logarithms: `sqrt()`, `cbrt()`, `exp()`, `exp2()`, `expm1()`, `log()`,
+//!   `log2()`, `log10()`, `log1p()` and `pow()`
+//! - Trigonometry: `sin()`, `cos()`, `tan()`, their inverses, `atan2()`, the hyperbolic
+//!   functions, `hypot()`, `dist()`, `degrees()` and `radians()`
+//! - Floating point helpers: `fabs()`, `copysign()`, `fmod()`, `remainder()`, `modf()`,
+//!   `frexp()`, `ldexp()`, `isclose()`, `isfinite()`, `isinf()` and `isnan()`
+//! - Integer functions: `gcd()`, `lcm()`, `comb()`, `perm()`, `factorial()` and `isqrt()`,
+//!   which work with ints of any size
+//! - Sums and products: `fsum()` and `prod()`
+//!
+//! As in CPython, float functions raise `ValueError("math domain error")` for arguments
+//! outside their domain and `OverflowError("math range error")` when the result is too
+//! large for a float.
+
+use num_bigint::BigInt;
+use num_integer::{Integer, Roots};
+use num_traits::{FromPrimitive, One, Signed, ToPrimitive, Zero};
+use smallvec::smallvec;
+
+use crate::{
+    args::{ArgValues, bind_args},
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    modules::ModuleFunctions,
+    resource::{LARGE_RESULT_THRESHOLD, ResourceError, ResourceTracker},
+    types::{AttrCallResult, LongInt, Module, MontyIter, PyTrait, allocate_tuple, decimal::Rounding},
+    value::Value,
+};
+
+/// math module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum MathFunctions {
+    Floor,
+    Ceil,
+    Trunc,
+    Sqrt,
+    Cbrt,
+    Exp,
+    Exp2,
+    Expm1,
+    Log,
+    Log2,
+    Log10,
+    Log1p,
+    Pow,
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Atan2,
+    Sinh,
+    Cosh,
+    Tanh,
+    Asinh,
+    Acosh,
+    Atanh,
+    Hypot,
+    Dist,
+    Degrees,
+    Radians,
+    Fabs,
+    Copysign,
+    Fmod,
+    Remainder,
+    Modf,
+    Frexp,
+    Ldexp,
+    Isclose,
+    Isfinite,
+    Isinf,
+    Isnan,
+    Gcd,
+    Lcm,
+    Comb,
+    Perm,
+    Factorial,
+    Isqrt,
+    Fsum,
+    Prod,
+}
+
+/// The module's functions with their names.
+const FUNCTIONS: [(StaticStrings, MathFunctions); 49] = [
+    (StaticStrings::Floor, MathFunctions::Floor),
+    (StaticStrings::Ceil, MathFunctions::Ceil),
+    (StaticStrings::Trunc, MathFunctions::Trunc),
+    (StaticStrings::Sqrt, MathFunctions::Sqrt),
+    (StaticStrings::Cbrt, MathFunctions::Cbrt),
+    (StaticStrings::Exp, MathFunctions::Exp),
+    (StaticStrings::Exp2, MathFunctions::Exp2),
+    (StaticStrings::Expm1, MathFunctions::Expm1),
+    (StaticStrings::Log, MathFunctions::Log),
+    (StaticStrings::Log2, MathFunctions::Log2),
+    (StaticStrings::Log10, MathFunctions::Log10),
+    (StaticStrings::Log1p, MathFunctions::Log1p),
+    (StaticStrings::Pow, MathFunctions::Pow),
+    (StaticStrings::Sin, MathFunctions::Sin),
+    (StaticStrings::Cos, MathFunctions::Cos),
+    (StaticStrings::Tan, MathFunctions::Tan),
+    (StaticStrings::Asin, MathFunctions::Asin),
+    (StaticStrings::Acos, MathFunctions::Acos),
+    (StaticStrings::Atan, MathFunctions::Atan),
+    (StaticStrings::Atan2, MathFunctions::Atan2),
+    (StaticStrings::Sinh, MathFunctions::Sinh),
+    (StaticStrings::Cosh, MathFunctions::Cosh),
+    (StaticStrings::Tanh, MathFunctions::Tanh),
+    (StaticStrings::Asinh, MathFunctions::Asinh),
+    (StaticStrings::Acosh, MathFunctions::Acosh),
+    (StaticStrings::Atanh, MathFunctions::Atanh),
+    (StaticStrings::Hypot, MathFunctions::Hypot),
+    (StaticStrings::Dist, MathFunctions::Dist),
+    (StaticStrings::Degrees, MathFunctions::Degrees),
+    (StaticStrings::Radians, MathFunctions::Radians),
+    (StaticStrings::Fabs, MathFunctions::Fabs),
+    (StaticStrings::Copysign, MathFunctions::Copysign),
+    (StaticStrings::Fmod, MathFunctions::Fmod),
+    (StaticStrings::Remainder, MathFunctions::Remainder),
+    (StaticStrings::Modf, MathFunctions::Modf),
+    (StaticStrings::Frexp, MathFunctions::Frexp),
+    (StaticStrings::Ldexp, MathFunctions::Ldexp),
+    (StaticStrings::Isclose, MathFunctions::Isclose),
+    (StaticStrings::Isfinite, MathFunctions::Isfinite),
+    (StaticStrings::Isinf, MathFunctions::Isinf),
+    (StaticStrings::Isnan, MathFunctions::Isnan),
+    (StaticStrings::Gcd, MathFunctions::Gcd),
+    (StaticStrings::Lcm, MathFunctions::Lcm),
+    (StaticStrings::Comb, MathFunctions::Comb),
+    (StaticStrings::Perm, MathFunctions::Perm),
+    (StaticStrings::Factorial, MathFunctions::Factorial),
+    (StaticStrings::Isqrt, MathFunctions::Isqrt),
+    (StaticStrings::Fsum, MathFunctions::Fsum),
+    (StaticStrings::Prod, MathFunctions::Prod),
+];
+
+/// Creates the `math` module and allocates it on the heap.
+///
+/// The module provides the functions listed above and the constants `pi`, `e`, `tau`,
+/// `inf` and `nan`.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Math);
+
+    for (name, function) in FUNCTIONS {
+        module.set_attr(
+            name,
+            Value::ModuleFunction(ModuleFunctions::Math(function)),
+            heap,
+            interns,
+        );
+    }
+
+    module.set_attr(StaticStrings::Pi, Value::Float(std::f64::consts::PI), heap, interns);
+    module.set_attr(
+        StringId::from_ascii(b'e'),
+        Value::Float(std::f64::consts::E),
+        heap,
+        interns,
+    );
+    module.set_attr(StaticStrings::Tau, Value::Float(std::f64::consts::TAU), heap, interns);
+    module.set_attr(StaticStrings::Inf, Value::Float(f64::INFINITY), heap, interns);
+    module.set_attr(StaticStrings::Nan, Value::Float(f64::NAN), heap, interns);
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a math module function.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    function: MathFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let value = match function {
+        MathFunctions::Isclose => isclose(heap, args, interns)?,
+        MathFunctions::Prod => prod(heap, args, interns)?,
+        _ if has_kwargs(&args) => {
+            args.drop_with_heap(heap);
+            return Err(ExcType::type_error_no_kwargs(&format!("math.{function}")));
+        }
+        MathFunctions::Floor | MathFunctions::Ceil | MathFunctions::Trunc => {
+            let x = one_arg(function, args, heap)?;
+            defer_drop!(x, heap);
+            round_to_int(function, x, heap)?
+        }
+        MathFunctions::Log => {
+            let (x, base) = args.get_one_two_args("log", heap)?;
+            defer_drop!(x, heap);
+            defer_drop!(base, heap);
+            let log = log_helper(x, f64::ln, heap)?;
+            match base {
+                Some(base) => {
+                    let base_log = log_helper(base, f64::ln, heap)?;
+                    if base_log == 0.0 {
+                        return Err(
+                            SimpleException::new_msg(ExcType::ZeroDivisionError, "float division by zero").into(),
+                        );
+                    }
+                    Value::Float(log / base_log)
+                }
+                None => Value::Float(log),
+            }
+        }
+        MathFunctions::Log2 | MathFunctions::Log10 => {
+            let x = one_arg(function, args, heap)?;
+            defer_drop!(x, heap);
+            let f: fn(f64) -> f64 = if function == MathFunctions::Log2 {
+                f64::log2
+            } else {
+                f64::log10
+            };
+            Value::Float(log_helper(x, f, heap)?)
+        }
+        MathFunctions::Sqrt
+        | MathFunctions::Cbrt
+        | MathFunctions::Exp
+        | MathFunctions::Exp2
+        | MathFunctions::Expm1
+        | MathFunctions::Log1p
+        | MathFunctions::Sin
+        | MathFunctions::Cos
+        | MathFunctions::Tan
+        | MathFunctions::Asin
+        | MathFunctions::Acos
+        | MathFunctions::Atan
+        | MathFunctions::Sinh
+        | MathFunctions::Cosh
+        | MathFunctions::Tanh
+        | MathFunctions::Asinh
+        | MathFunctions::Acosh
+        | MathFunctions::Atanh
+        | MathFunctions::Degrees
+        | MathFunctions::Radians
+        | MathFunctions::Fabs => {
+            let x = one_arg(function, args, heap)?;
+            defer_drop!(x, heap);
+            Value::Float(unary(function, float_arg(x, heap)?)?)
+        }
+        MathFunctions::Isfinite | MathFunctions::Isinf | MathFunctions::Isnan => {
+            let x = one_arg(function, args, heap)?;
+            defer_drop!(x, heap);
+            let x = float_arg(x, heap)?;
+            Value::Bool(match function {
+                MathFunctions::Isfinite => x.is_finite(),
+                MathFunctions::Isinf => x.is_infinite(),
+                _ => x.is_nan(),
+            })
+        }
+        MathFunctions::Pow
+        | MathFunctions::Atan2
+        | MathFunctions::Copysign
+        | MathFunctions::Fmod
+        | MathFunctions::Remainder => {
+            let (x, y) = two_args(function, args, heap)?;
+            defer_drop!(x, heap);
+            defer_drop!(y, heap);
+            let (x, y) = (float_arg(x, heap)?, float_arg(y, heap)?);
+            Value::Float(binary(function, x, y)?)
+        }
+        MathFunctions::Ldexp => {
+            let (x, i) = two_args(function, args, heap)?;
+            defer_drop!(x, heap);
+            defer_drop!(i, heap);
+            Value::Float(ldexp(float_arg(x, heap)?, i, heap)?)
+        }
+        MathFunctions::Modf => {
+            let x = one_arg(function, args, heap)?;
+            defer_drop!(x, heap);
+            let x = float_arg(x, heap)?;
+            let (fraction, integer) = if x.is_infinite() {
+                (0.0_f64.copysign(x), x)
+            } else {
+                (x.fract(), x.trunc())
+            };
+            allocate_tuple(smallvec![Value::Float(fraction), Value::Float(integer)], heap)?
+        }
+        MathFunctions::Frexp => {
+            let x = one_arg(function, args, heap)?;
+            defer_drop!(x, heap);
+            let (mantissa, exponent) = frexp(float_arg(x, heap)?);
+            allocate_tuple(smallvec![Value::Float(mantissa), Value::Int(i64::from(exponent))], heap)?
+        }
+        MathFunctions::Hypot => {
+            let coordinates = float_args(args, heap)?;
+            Value::Float(coordinates.into_iter().fold(0.0, f64::hypot))
+        }
+        MathFunctions::Dist => {
+            let (p, q) = two_args(function, args, heap)?;
+            let p = match iter_floats(p, heap, interns) {
+                Ok(p) => p,
+                Err(e) => {
+                    q.drop_with_heap(heap);
+                    return Err(e);
+                }
+            };
+            let q = iter_floats(q, heap, interns)?;
+            if p.len() != q.len() {
+                return Err(SimpleException::new_msg(
+                    ExcType::ValueError,
+                    "both points must have the same number of dimensions",
+                )
+                .into());
+            }
+            Value::Float(p.iter().zip(&q).map(|(a, b)| a - b).fold(0.0, f64::hypot))
+        }
+        MathFunctions::Gcd | MathFunctions::Lcm => {
+            let numbers = int_args(args, heap)?;
+            let result = if function == MathFunctions::Gcd {
+                numbers.iter().fold(BigInt::zero(), |acc, n| acc.gcd(n))
+            } else {
+                numbers.iter().fold(BigInt::one(), |acc, n| acc.lcm(n))
+            };
+            LongInt::new(result).into_value(heap)?
+        }
+        MathFunctions::Isqrt => {
+            let n = one_arg(function, args, heap)?;
+            defer_drop!(n, heap);
+            let n = int_arg(n, heap)?;
+            if n.is_negative() {
+                return Err(
+                    SimpleException::new_msg(ExcType::ValueError, "isqrt() argument must be nonnegative").into(),
+                );
+            }
+            LongInt::new(n.sqrt()).into_value(heap)?
+        }
+        MathFunctions::Factorial => {
+            let n = one_arg(function, args, heap)?;
+            defer_drop!(n, heap);
+            let n = int_arg(n, heap)?;
+            factorial(&n, heap)?
+        }
+        MathFunctions::Comb | MathFunctions::Perm => comb_perm(function, args, heap)?,
+        MathFunctions::Fsum => {
+            let iterable = one_arg(function, args, heap)?;
+            let values = iter_floats(iterable, heap, interns)?;
+            Value::Float(fsum(&values)?)
+        }
+    };
+    Ok(AttrCallResult::Value(value))
+}
+
+/// Returns whether any keyword arguments were passed.
+fn has_kwargs(args: &ArgValues) -> bool {
+    match args {
+        ArgValues::Kwargs(kwargs) | ArgValues::ArgsKargs { kwargs, .. } => !kwargs.is_empty(),
+        _ => false,
+    }
+}
+
+/// Returns the only argument of a function, like CPython naming it `math.name()` in errors.
+fn one_arg(function: MathFunctions, args: ArgValues, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    match args {
+        ArgValues::One(x) => Ok(x),
+        other => other.get_one_arg(&format!("math.{function}"), heap),
+    }
+}
+
+/// Returns the two arguments of a function.
+fn two_args(
+    function: MathFunctions,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> RunResult<(Value, Value)> {
+    match args {
+        ArgValues::Two(x, y) => Ok((x, y)),
+        other => other.get_two_args(&function.to_string(), heap),
+    }
+}
+
+/// Converts a number to a float, as the math functions do with their arguments.
+///
+/// # Errors
+/// Returns `TypeError` for non-numbers and `OverflowError` for ints and fractions too large
+/// for a float.
+pub(crate) fn float_arg(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<f64> {
+    match value {
+        Value::Float(f) => Ok(*f),
+        Value::Int(i) => Ok(*i as f64),
+        Value::Bool(b) => Ok(f64::from(u8::from(*b))),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => match li.to_f64() {
+                Some(f) if f.is_finite() => Ok(f),
+                _ => Err(SimpleException::new_msg(ExcType::OverflowError, "int too large to convert to float").into()),
+            },
+            HeapData::Decimal(decimal) => Ok(decimal.to_f64()),
+            HeapData::Fraction(fraction) => fraction.to_float(),
+            _ => Err(not_real_number(value, heap)),
+        },
+        _ => Err(not_real_number(value, heap)),
+    }
+}
+
+#[cold]
+fn not_real_number(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunError {
+    ExcType::type_error(format!("must be real number, not {}", value.py_type(heap)))
+}
+
+/// Converts an int or bool argument to a `BigInt`.
+///
+/// # Errors
+/// Returns `TypeError` for anything else, including floats.
+pub(crate) fn int_arg(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<BigInt> {
+    match value {
+        Value::Int(i) => Ok(BigInt::from(*i)),
+        Value::Bool(b) => Ok(BigInt::from(u8::from(*b))),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => Ok(li.inner().clone()),
+            _ => Err(not_integer(value, heap)),
+        },
+        _ => Err(not_integer(value, heap)),
+    }
+}
+
+#[cold]
+fn not_integer(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunError {
+    ExcType::type_error(format!(
+        "'{}' object cannot be interpreted as an integer",
+        value.py_type(heap)
+    ))
+}
+
+/// Converts all the positional arguments of a variadic function to floats.
+fn float_args(args: ArgValues, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Vec<f64>> {
+    let (positional, kwargs) = args.into_parts();
+    kwargs.drop_with_heap(heap);
+    let mut result = Ok(Vec::with_capacity(positional.len()));
+    for value in positional {
+        if let Ok(floats) = &mut result {
+            match float_arg(&value, heap) {
+                Ok(f) => floats.push(f),
+                Err(e) => result = Err(e),
+            }
+        }
+        value.drop_with_heap(heap);
+    }
+    result
+}
+
+/// Converts all the positional arguments of a variadic function to `BigInt`s.
+fn int_args(args: ArgValues, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Vec<BigInt>> {
+    let (positional, kwargs) = args.into_parts();
+    kwargs.drop_with_heap(heap);
+    let mut result = Ok(Vec::with_capacity(positional.len()));
+    for value in positional {
+        if let Ok(ints) = &mut result {
+            match int_arg(&value, heap) {
+                Ok(n) => ints.push(n),
+                Err(e) => result = Err(e),
+            }
+        }
+        value.drop_with_heap(heap);
+    }
+    result
+}
+
+/// Converts the items of an iterable to floats, taking ownership of it.
+fn iter_floats(iterable: Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Vec<f64>> {
+    let mut iter = MontyIter::new(iterable, heap, interns)?;
+    let mut floats = Vec::new();
+    let result = loop {
+        match iter.for_next(heap, interns) {
+            Ok(Some(item)) => {
+                let f = float_arg(&item, heap);
+                item.drop_with_heap(heap);
+                match f {
+                    Ok(f) => floats.push(f),
+                    Err(e) => break Err(e),
+                }
+            }
+            Ok(None) => break Ok(floats),
+            Err(e) => break Err(e),
+        }
+    };
+    iter.drop_with_heap(heap);
+    result
+}
+
+#[cold]
+fn domain_error() -> RunError {
+    SimpleException::new_msg(ExcType::ValueError, "math domain error").into()
+}
+
+#[cold]
+fn range_error() -> RunError {
+    SimpleException::new_msg(ExcType::OverflowError, "math range error").into()
+}
+
+/// Checks the result of a float function of `x` like CPython does.
+///
+/// A NaN result from a non-NaN argument is a domain error. An infinite result from a finite
+/// argument is a range error for functions which can overflow, and a domain error for the
+/// others, like `log(0)`.
+fn check_result(x: f64, result: f64, can_overflow: bool) -> RunResult<f64> {
+    if result.is_nan() && !x.is_nan() {
+        Err(domain_error())
+    } else if result.is_infinite() && x.is_finite() {
+        Err(if can_overflow { range_error() } else { domain_error() })
+    } else {
+        Ok(result)
+    }
+}
+
+/// Applies a float function of one argument.
+fn unary(function: MathFunctions, x: f64) -> RunResult<f64> {
+    let (result, can_overflow) = match function {
+        // These can't fail, besides degrees() overflowing to infinity which CPython allows
+        MathFunctions::Degrees => return Ok(x.to_degrees()),
+        MathFunctions::Radians => return Ok(x.to_radians()),
+        MathFunctions::Fabs => return Ok(x.abs()),
+        MathFunctions::Sqrt => (x.sqrt(), false),
+        MathFunctions::Cbrt => (x.cbrt(), false),
+        MathFunctions::Exp => (x.exp(), true),
+        MathFunctions::Exp2 => (x.exp2(), true),
+        MathFunctions::Expm1 => (x.exp_m1(), true),
+        MathFunctions::Log1p => (x.ln_1p(), false),
+        MathFunctions::Sin => (x.sin(), false),
+        MathFunctions::Cos => (x.cos(), false),
+        MathFunctions::Tan => (x.tan(), false),
+        MathFunctions::Asin => (x.asin(), false),
+        MathFunctions::Acos => (x.acos(), false),
+        MathFunctions::Atan => (x.atan(), false),
+        MathFunctions::Sinh => (x.sinh(), true),
+        MathFunctions::Cosh => (x.cosh(), true),
+        MathFunctions::Tanh => (x.tanh(), false),
+        MathFunctions::Asinh => (x.asinh(), false),
+        MathFunctions::Acosh => (x.acosh(), false),
+        MathFunctions::Atanh => (x.atanh(), false),
+        _ => unreachable!("not a unary float function: {function}"),
+    };
+    check_result(x, result, can_overflow)
+}
+
+/// Applies a float function of two arguments.
+fn binary(function: MathFunctions, x: f64, y: f64) -> RunResult<f64> {
+    match function {
+        MathFunctions::Atan2 => Ok(x.atan2(y)),
+        MathFunctions::Copysign => Ok(x.copysign(y)),
+        MathFunctions::Pow => {
+            let result = x.powf(y);
+            if x.is_finite() && y.is_finite() {
+                if result.is_nan() {
+                    return Err(domain_error());
+                } else if result.is_infinite() {
+                    // `0.0 ** -1` is a domain error rather than a division by zero
+                    return Err(if x == 0.0 { domain_error() } else { range_error() });
+                }
+            }
+            Ok(result)
+        }
+        MathFunctions::Fmod => {
+            // fmod(x, inf) is x for finite x, which is also what C does, but be explicit
+            if y.is_infinite() && x.is_finite() {
+                return Ok(x);
+            }
+            let result = x % y;
+            if result.is_nan() && !x.is_nan() && !y.is_nan() {
+                Err(domain_error())
+            } else {
+                Ok(result)
+            }
+        }
+        MathFunctions::Remainder => remainder(x, y),
+        _ => unreachable!("not a binary float function: {function}"),
+    }
+}
+
+/// Implements `floor()`, `ceil()` and `trunc()`, which return ints unchanged.
+fn round_to_int(function: MathFunctions, x: &Value, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    // decimals and fractions round exactly
+    if let Value::Ref(id) = x {
+        match heap.get(*id) {
+            HeapData::Decimal(decimal) => {
+                let rounding = match function {
+                    MathFunctions::Floor => Rounding::Floor,
+                    MathFunctions::Ceil => Rounding::Ceiling,
+                    _ => Rounding::Down,
+                };
+                return decimal.to_integral(rounding).to_int(heap);
+            }
+            HeapData::Fraction(fraction) => {
+                let rounded = match function {
+                    MathFunctions::Floor => fraction.floor(),
+                    MathFunctions::Ceil => fraction.ceil(),
+                    _ => fraction.trunc(),
+                };
+                return Ok(LongInt::new(rounded).into_value(heap)?);
+            }
+            _ => {}
+        }
+    }
+    let f = match x {
+        Value::Float(f) => *f,
+        Value::Int(i) => return Ok(Value::Int(*i)),
+        Value::Bool(b) => return Ok(Value::Int(i64::from(*b))),
+        Value::Ref(id) if matches!(heap.get(*id), HeapData::LongInt(_)) => return Ok(x.clone_with_heap(heap)),
+        _ if function == MathFunctions::Trunc => {
+            return Err(ExcType::type_error(format!(
+                "type {} doesn't define __trunc__ method",
+                x.py_type(heap)
+            )));
+        }
+        _ => return Err(not_real_number(x, heap)),
+    };
+    let rounded = match function {
+        MathFunctions::Floor => f.floor(),
+        MathFunctions::Ceil => f.ceil(),
+        _ => f.trunc(),
+    };
+    if rounded.is_nan() {
+        return Err(SimpleException::new_msg(ExcType::ValueError, "cannot convert float NaN to integer").into());
+    }
+    match BigInt::from_f64(rounded) {
+        Some(n) => Ok(LongInt::new(n).into_value(heap)?),
+        None => {
+            Err(SimpleException::new_msg(ExcType::OverflowError, "cannot convert float infinity to integer").into())
+        }
+    }
+}
+
+/// Takes the logarithm of a number with `f`, which is `ln`, `log2` or `log10`.
+///
+/// Like CPython, ints too large for a float are split into a mantissa and a power of two,
+/// so `log(10 ** 400)` works.
+fn log_helper(value: &Value, f: fn(f64) -> f64, heap: &Heap<impl ResourceTracker>) -> RunResult<f64> {
+    if let Value::Ref(id) = value
+        && let HeapData::LongInt(li) = heap.get(*id)
+    {
+        if li.is_negative() || li.is_zero() {
+            return Err(domain_error());
+        }
+        if let Some(x) = li.to_f64()
+            && x.is_finite()
+        {
+            return Ok(f(x));
+        }
+        // x = mantissa * 2**bits, with the mantissa in [0.5, 1)
+        let bits = li.bits();
+        let top = (li.inner() >> (bits - 64))
+            .to_f64()
+            .expect("64 bit int converts to a float");
+        let mantissa = top * 2.0_f64.powi(-64);
+        return Ok(f(mantissa) + f(2.0) * bits as f64);
+    }
+    let x = float_arg(value, heap)?;
+    check_result(x, f(x), false)
+}
+
+/// IEEE 754 remainder of `x` by `y`, following CPython's implementation.
+fn remainder(x: f64, y: f64) -> RunResult<f64> {
+    if x.is_nan() {
+        return Ok(x);
+    }
+    if y.is_nan() {
+        return Ok(y);
+    }
+    if x.is_infinite() || y == 0.0 {
+        return Err(domain_error());
+    }
+    if y.is_infinite() {
+        return Ok(x);
+    }
+    let abs_x = x.abs();
+    let abs_y = y.abs();
+    let modulus = abs_x % abs_y;
+    let complement = abs_y - modulus;
+    let result = if modulus < complement {
+        modulus
+    } else if modulus > complement {
+        -complement
+    } else {
+        // Half way between two multiples of y, so round to the even one
+        modulus - 2.0 * ((0.5 * (abs_x - modulus)) % abs_y)
+    };
+    Ok(1.0_f64.copysign(x) * result)
+}
+
+/// Splits a float into a mantissa in [0.5, 1) and a power of two.
+pub(crate) fn frexp(x: f64) -> (f64, i32) {
+    if x == 0.0 || !x.is_finite() {
+        return (x, 0);
+    }
+    let bits = x.to_bits();
+    let exponent = i32::try_from((bits >> 52) & 0x7ff).expect("11 bit exponent fits in i32");
+    if exponent == 0 {
+        // Subnormal: scale into the normal range first
+        let (mantissa, exponent) = frexp(x * 2.0_f64.powi(54));
+        return (mantissa, exponent - 54);
+    }
+    let mantissa = f64::from_bits((bits & !(0x7ff << 52)) | (1022 << 52));
+    (mantissa, exponent - 1022)
+}
+
+/// Implements `ldexp(x, i)`, returning `x * 2**i`.
+fn ldexp(x: f64, i: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<f64> {
+    let Ok(exponent) = int_arg(i, heap) else {
+        return Err(ExcType::type_error("Expected an int as second argument to ldexp."));
+    };
+    // Exponents too large for an i64 always underflow to zero or overflow
+    let exponent = exponent
+        .to_i64()
+        .unwrap_or(if exponent.is_negative() { i64::MIN } else { i64::MAX });
+    if x == 0.0 || !x.is_finite() {
+        return Ok(x);
+    }
+    // Beyond this range the result is always zero or infinite
+    let mut exponent = i32::try_from(exponent.clamp(-2200, 2200)).expect("clamped exponent fits in i32");
+    let mut result = x;
+    // Scale in steps, since powers of two outside the normal range aren't representable
+    while exponent > 1023 {
+        result *= 2.0_f64.powi(1023);
+        exponent -= 1023;
+    }
+    while exponent < -1022 {
+        result *= 2.0_f64.powi(-1022);
+        exponent += 1022;
+    }
+    result *= 2.0_f64.powi(exponent);
+    if result.is_infinite() {
+        Err(range_error())
+    } else {
+        Ok(result)
+    }
+}
+
+/// Implements `factorial(n)`.
+fn factorial(n: &BigInt, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    if n.is_negative() {
+        return Err(
+            SimpleException::new_msg(ExcType::ValueError, "factorial() not defined for negative values").into(),
+        );
+    }
+    let Some(n) = n.to_u64().filter(|n| i64::try_from(*n).is_ok()) else {
+        return Err(SimpleException::new_msg(
+            ExcType::OverflowError,
+            "factorial() argument should not exceed 9223372036854775807",
+        )
+        .into());
+    };
+    check_product_size(n, u64::from(u64::BITS - n.leading_zeros()), heap)?;
+    Ok(LongInt::new(product(1, n)).into_value(heap)?)
+}
+
+/// Implements `comb(n, k)` and `perm(n, k=None)`.
+fn comb_perm(function: MathFunctions, args: ArgValues, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let (n, k) = if function == MathFunctions::Comb {
+        let (n, k) = two_args(function, args, heap)?;
+        (n, Some(k))
+    } else {
+        args.get_one_two_args("perm", heap)?
+    };
+    defer_drop!(n, heap);
+    defer_drop!(k, heap);
+    let n = int_arg(n, heap)?;
+    let k = match k {
+        Some(k) => int_arg(k, heap)?,
+        None => n.clone(),
+    };
+    if n.is_negative() {
+        return Err(SimpleException::new_msg(ExcType::ValueError, "n must be a non-negative integer").into());
+    }
+    if k.is_negative() {
+        return Err(SimpleException::new_msg(ExcType::ValueError, "k must be a non-negative integer").into());
+    }
+    if k > n {
+        return Ok(Value::Int(0));
+    }
+    // comb(n, k) == comb(n, n - k), so use the smaller to do less work
+    let (k, limit) = if function == MathFunctions::Comb {
+        (k.clone().min(&n - &k), "min(n - k, k)")
+    } else {
+        (k, "k")
+    };
+    let Some(k) = k.to_u64().filter(|k| i64::try_from(*k).is_ok()) else {
+        return Err(SimpleException::new_msg(
+            ExcType::OverflowError,
+            format!("{limit} must not exceed 9223372036854775807"),
+        )
+        .into());
+    };
+    check_product_size(k, n.bits(), heap)?;
+
+    let mut result = BigInt::one();
+    if function == MathFunctions::Comb {
+        // comb(n, i) * (n - i) is always divisible by i + 1, giving comb(n, i + 1)
+        for i in 0..k {
+            result = result * (&n - i) / (i + 1);
+        }
+    } else {
+        for i in 0..k {
+            result *= &n - i;
+        }
+    }
+    Ok(LongInt::new(result).into_value(heap)?)
+}
+
+/// Checks the size of a product of `count` factors of at most `bits_per_factor` bits
+/// before computing it.
+fn check_product_size(
+    count: u64,
+    bits_per_factor: u64,
+    heap: &Heap<impl ResourceTracker>,
+) -> Result<(), ResourceError> {
+    let estimated = count.saturating_mul(bits_per_factor) / 8;
+    let estimated = usize::try_from(estimated).unwrap_or(usize::MAX);
+    if estimated > LARGE_RESULT_THRESHOLD {
+        heap.tracker().check_large_result(estimated)?;
+    }
+    Ok(())
+}
+
+/// Multiplies the integers from `start` to `end` inclusive.
+///
+/// Factors are multiplied as machine integers until they overflow, so most of the work
+/// avoids big int arithmetic.
+fn product(start: u64, end: u64) -> BigInt {
+    let mut result = BigInt::one();
+    let mut chunk: u64 = 1;
+    for i in start..=end {
+        match chunk.checked_mul(i) {
+            Some(c) => chunk = c,
+            None => {
+                result *= chunk;
+                chunk = i;
+            }
+        }
+    }
+    result * chunk
+}
+
+/// Implements `isclose(a, b, *, rel_tol=1e-09, abs_tol=0.0)`.
+#[expect(clippy::float_cmp, reason = "equal values are always close")]
+fn isclose(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let (positional, kwargs) = args.into_parts();
+    let count = positional.len();
+    let mut positional: Vec<Value> = positional.collect();
+    let [rel_tol, abs_tol] = match bind_args(
+        "isclose",
+        ArgValues::Kwargs(kwargs),
+        ["rel_tol", "abs_tol"],
+        0,
+        heap,
+        interns,
+    ) {
+        Ok(values) => values,
+        Err(e) => {
+            positional.drop_with_heap(heap);
+            return Err(e);
+        }
+    };
+    defer_drop!(rel_tol, heap);
+    defer_drop!(abs_tol, heap);
+    if count != 2 {
+        positional.drop_with_heap(heap);
+        return Err(ExcType::type_error(if count > 2 {
+            format!("isclose() takes exactly 2 positional arguments ({count} given)")
+        } else {
+            let (name, pos) = if count == 0 { ("a", 1) } else { ("b", 2) };
+            format!("isclose() missing required argument '{name}' (pos {pos})")
+        }));
+    }
+    let b = positional.pop().expect("two arguments");
+    let a = positional.pop().expect("two arguments");
+    defer_drop!(a, heap);
+    defer_drop!(b, heap);
+
+    let a = float_arg(a, heap)?;
+    let b = float_arg(b, heap)?;
+    let rel_tol = rel_tol.as_ref().map_or(Ok(1e-9), |v| float_arg(v, heap))?;
+    let abs_tol = abs_tol.as_ref().map_or(Ok(0.0), |v| float_arg(v, heap))?;
+    if rel_tol < 0.0 || abs_tol < 0.0 {
+        return Err(SimpleException::new_msg(ExcType::ValueError, "tolerances must be non-negative").into());
+    }
+    if a == b {
+        // Also handles two infinities of the same sign
+        return Ok(Value::Bool(true));
+    }
+    if a.is_infinite() || b.is_infinite() {
+        return Ok(Value::Bool(false));
+    }
+    let diff = (b - a).abs();
+    Ok(Value::Bool(
+        diff <= (rel_tol * b).abs() || diff <= (rel_tol * a).abs() || diff <= abs_tol,
+    ))
+}
+
+/// Implements `prod(iterable, *, start=1)`, multiplying the items onto `start`.
+fn prod(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let (positional, kwargs) = args.into_parts();
+    let count = positional.len();
+    let mut positional: Vec<Value> = positional.collect();
+    let [start] = match bind_args("prod", ArgValues::Kwargs(kwargs), ["start"], 0, heap, interns) {
+        Ok(values) => values,
+        Err(e) => {
+            positional.drop_with_heap(heap);
+            return Err(e);
+        }
+    };
+    if count != 1 {
+        positional.drop_with_heap(heap);
+        start.drop_with_heap(heap);
+        return Err(ExcType::type_error(format!(
+            "prod() takes exactly 1 positional argument ({count} given)"
+        )));
+    }
+    let iterable = positional.pop().expect("one argument");
+    let mut accumulator = start.unwrap_or(Value::Int(1));
+
+    let mut iter = match MontyIter::new(iterable, heap, interns) {
+        Ok(iter) => iter,
+        Err(e) => {
+            accumulator.drop_with_heap(heap);
+            return Err(e);
+        }
+    };
+    let result = loop {
+        let item = match iter.for_next(heap, interns) {
+            Ok(Some(item)) => item,
+            Ok(None) => break Ok(()),
+            Err(e) => break Err(e),
+        };
+        let product = accumulator.py_mult(&item, heap, interns);
+        match product {
+            Ok(Some(product)) => {
+                item.drop_with_heap(heap);
+                accumulator.drop_with_heap(heap);
+                accumulator = product;
+            }
+            Ok(None) => {
+                let error = ExcType::binary_type_error("*", accumulator.py_type(heap), item.py_type(heap));
+                item.drop_with_heap(heap);
+                break Err(error);
+            }
+            Err(e) => {
+                item.drop_with_heap(heap);
+                break Err(e);
+            }
+        }
+    };
+    iter.drop_with_heap(heap);
+    match result {
+        Ok(()) => Ok(accumulator),
+        Err(e) => {
+            accumulator.drop_with_heap(heap);
+            Err(e)
+        }
+    }
+}
+
+/// Sums floats exactly, rounding only the final result, using Shewchuk's algorithm as
+/// CPython's `fsum()` does.
+///
+/// # Errors
+/// Returns `OverflowError` if an intermediate sum of finite values overflows, and
+/// `ValueError` for a sum of opposite infinities.
+#[expect(clippy::float_cmp, reason = "exact comparisons detect rounding")]
+fn fsum(values: &[f64]) -> RunResult<f64> {
+    // Non-overlapping partial sums, in increasing order of magnitude
+    let mut partials: Vec<f64> = Vec::new();
+    // Sums of the infinities and NaNs, which are kept out of the partials
+    let mut special_sum = 0.0;
+    let mut inf_sum = 0.0;
+
+    for &value in values {
+        let mut x = value;
+        partials.retain_mut(|partial| {
+            let (big, small) = if x.abs() < partial.abs() {
+                (*partial, x)
+            } else {
+                (x, *partial)
+            };
+            let hi = big + small;
+            let lo = small - (hi - big);
+            x = hi;
+            *partial = lo;
+            lo != 0.0
+        });
+        if x != 0.0 {
+            if x.is_finite() {
+                partials.push(x);
+            } else {
+                // A non-finite sum comes from a non-finite value, or from overflow
+                if value.is_finite() {
+                    return Err(
+                        SimpleException::new_msg(ExcType::OverflowError, "intermediate overflow in fsum").into(),
+                    );
+                }
+                if value.is_infinite() {
+                    inf_sum += value;
+                }
+                special_sum += value;
+                partials.clear();
+            }
+        }
+    }
+
+    if special_sum != 0.0 {
+        if inf_sum.is_nan() {
+            return Err(SimpleException::new_msg(ExcType::ValueError, "-inf + inf in fsum").into());
+        }
+        return Ok(special_sum);
+    }
+
+    // Add the partials from the top, stopping when the sum becomes inexact
+    let Some(mut hi) = partials.pop() else {
+        return Ok(0.0);
+    };
+    let mut lo = 0.0;
+    while let Some(y) = partials.pop() {
+        let x = hi;
+        hi = x + y;
+        lo = y - (hi - x);
+        if lo != 0.0 {
+            break;
+        }
+    }
+    // Correct the rounding of half-way cases, where the rest of the partials have the
+    // same sign as the error
+    if let Some(&next) = partials.last()
+        && ((lo < 0.0 && next < 0.0) || (lo > 0.0 && next > 0.0))
+    {
+        let y = lo * 2.0;
+        let x = hi + y;
+        if y == x - hi {
+            hi = x;
+        }
+    }
+    Ok(hi)
+}
@@ -1,7 +1,7 @@
 //! Built-in module implementations.
 //!
 //! This module provides implementations for Python built-in modules like `sys`, `typing`,
-//! and `asyncio`. These are created on-demand when import statements are executed.
+//! `asyncio`, `json`, `re`, `math`, `datetime`, `collections`, `itertools`, `functools`, `random`, `string`, `textwrap`, `base64`, `hashlib`, `uuid`, `enum`, `decimal` and `fractions`. These are created on-demand when import statements are executed.
 
 use std::fmt::{self, Write};
 
@@ -17,10 +17,26 @@ use crate::{
 };
 
 pub(crate) mod asyncio;
+pub(crate) mod base64;
+pub(crate) mod collections;
+pub(crate) mod datetime;
+pub(crate) mod decimal;
+pub(crate) mod r#enum;
+pub(crate) mod fractions;
+pub(crate) mod functools;
+pub(crate) mod hashlib;
+pub(crate) mod itertools;
+pub(crate) mod json;
+pub(crate) mod math;
 pub(crate) mod os;
 pub(crate) mod pathlib;
+pub(crate) mod random;
+pub(crate) mod re;
+pub(crate) mod string;
 pub(crate) mod sys;
+pub(crate) mod textwrap;
 pub(crate) mod typing;
+pub(crate) mod uuid;
 
 /// Built-in modules that can be imported.
 #[repr(u8)]
@@ -36,6 +52,38 @@ pub(crate) enum BuiltinModule {
     Pathlib,
     /// The `os` module providing operating system interface (only `getenv()` implemented).
     Os,
+    /// The `json` module providing JSON encoding and decoding (only `loads()` and `dumps()` implemented).
+    Json,
+    /// The `re` module providing regular expression matching.
+    Re,
+    /// The `math` module providing mathematical functions and constants.
+    Math,
+    /// The `datetime` module providing dates, times, durations and fixed-offset timezones.
+    Datetime,
+    /// The `collections` module providing `defaultdict`, `Counter`, `OrderedDict`, `deque` and `namedtuple()`.
+    Collections,
+    /// The `itertools` module providing lazy iterator building blocks.
+    Itertools,
+    /// The `functools` module providing `reduce()`, `partial`, `lru_cache()`, `cmp_to_key()` and `wraps()`.
+    Functools,
+    /// The `random` module providing deterministic, host-seeded random numbers.
+    Random,
+    /// The `string` module providing character constants, `capwords()` and `Template`.
+    String,
+    /// The `textwrap` module providing text wrapping, filling, dedenting and indenting.
+    Textwrap,
+    /// The `base64` module providing Base64, Base32 and Base16 encoding and decoding.
+    Base64,
+    /// The `hashlib` module providing MD5, SHA-1, SHA-256 and SHA-512 hashes.
+    Hashlib,
+    /// The `uuid` module providing `UUID`, `uuid3()`, `uuid4()` and `uuid5()`.
+    Uuid,
+    /// The `enum` module providing `Enum`, `IntEnum`, `StrEnum` and `auto()`.
+    Enum,
+    /// The `decimal` module providing `Decimal`, `Context` and the context functions.
+    Decimal,
+    /// The `fractions` module providing `Fraction`.
+    Fractions,
 }
 
 impl BuiltinModule {
@@ -47,6 +95,22 @@ impl BuiltinModule {
             StaticStrings::Asyncio => Some(Self::Asyncio),
             StaticStrings::Pathlib => Some(Self::Pathlib),
             StaticStrings::Os => Some(Self::Os),
+            StaticStrings::Json => Some(Self::Json),
+            StaticStrings::Re => Some(Self::Re),
+            StaticStrings::Math => Some(Self::Math),
+            StaticStrings::Datetime => Some(Self::Datetime),
+            StaticStrings::Collections => Some(Self::Collections),
+            StaticStrings::Itertools => Some(Self::Itertools),
+            StaticStrings::Functools => Some(Self::Functools),
+            StaticStrings::Random => Some(Self::Random),
+            StaticStrings::String => Some(Self::String),
+            StaticStrings::Textwrap => Some(Self::Textwrap),
+            StaticStrings::Base64 => Some(Self::Base64),
+            StaticStrings::Hashlib => Some(Self::Hashlib),
+            StaticStrings::Uuid => Some(Self::Uuid),
+            StaticStrings::Enum => Some(Self::Enum),
+            StaticStrings::Decimal => Some(Self::Decimal),
+            StaticStrings::Fractions => Some(Self::Fractions),
             _ => None,
         }
     }
@@ -65,6 +129,22 @@ impl BuiltinModule {
             Self::Asyncio => asyncio::create_module(heap, interns),
             Self::Pathlib => pathlib::create_module(heap, interns),
             Self::Os => os::create_module(heap, interns),
+            Self::Json => json::create_module(heap, interns),
+            Self::Re => re::create_module(heap, interns),
+            Self::Math => math::create_module(heap, interns),
+            Self::Datetime => datetime::create_module(heap, interns),
+            Self::Collections => collections::create_module(heap, interns),
+            Self::Itertools => itertools::create_module(heap, interns),
+            Self::Functools => functools::create_module(heap, interns),
+            Self::Random => random::create_module(heap, interns),
+            Self::String => string::create_module(heap, interns),
+            Self::Textwrap => textwrap::create_module(heap, interns),
+            Self::Base64 => base64::create_module(heap, interns),
+            Self::Hashlib => hashlib::create_module(heap, interns),
+            Self::Uuid => uuid::create_module(heap, interns),
+            Self::Enum => r#enum::create_module(heap, interns),
+            Self::Decimal => decimal::create_module(heap, interns),
+            Self::Fractions => fractions::create_module(heap, interns),
         }
     }
 }
@@ -74,6 +154,19 @@ impl BuiltinModule {
 pub(crate) enum ModuleFunctions {
     Asyncio(asyncio::AsyncioFunctions),
     Os(os::OsFunctions),
+    Json(json::JsonFunctions),
+    Re(re::ReFunctions),
+    Math(math::MathFunctions),
+    Collections(collections::CollectionsFunctions),
+    Itertools(itertools::ItertoolsFunctions),
+    Functools(functools::FunctoolsFunctions),
+    Random(random::RandomFunctions),
+    String(string::StringFunctions),
+    Textwrap(textwrap::TextwrapFunctions),
+    Base64(base64::Base64Functions),
+    Hashlib(hashlib::HashlibFunctions),
+    Uuid(uuid::UuidFunctions),
+    Decimal(decimal::DecimalFunctions),
 }
 
 impl fmt::Display for ModuleFunctions {
@@ -81,6 +174,19 @@ impl fmt::Display for ModuleFunctions {
         match self {
             Self::Asyncio(func) => write!(f, "{func}"),
             Self::Os(func) => write!(f, "{func}"),
+            Self::Json(func) => write!(f, "{func}"),
+            Self::Re(func) => write!(f, "{func}"),
+            Self::Math(func) => write!(f, "{func}"),
+            Self::Collections(func) => write!(f, "{func}"),
+            Self::Itertools(func) => write!(f, "{func}"),
+            Self::Functools(func) => write!(f, "{func}"),
+            Self::Random(func) => write!(f, "{func}"),
+            Self::String(func) => write!(f, "{func}"),
+            Self::Textwrap(func) => write!(f, "{func}"),
+            Self::Base64(func) => write!(f, "{func}"),
+            Self::Hashlib(func) => write!(f, "{func}"),
+            Self::Uuid(func) => write!(f, "{func}"),
+            Self::Decimal(func) => write!(f, "{func}"),
         }
     }
 }
@@ -90,10 +196,28 @@ impl ModuleFunctions {
     ///
     /// Returns `AttrCallResult` to support both immediate values and OS calls that
     /// require host involvement (e.g., `os.getenv()` needs the host to provide environment variables).
-    pub fn call(self, heap: &mut Heap<impl ResourceTracker>, args: ArgValues) -> RunResult<AttrCallResult> {
+    pub fn call(
+        self,
+        heap: &mut Heap<impl ResourceTracker>,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<AttrCallResult> {
         match self {
             Self::Asyncio(functions) => asyncio::call(heap, functions, args),
             Self::Os(functions) => os::call(heap, functions, args),
+            Self::Json(functions) => json::call(heap, functions, args, interns),
+            Self::Re(functions) => re::call(heap, functions, args, interns),
+            Self::Math(functions) => math::call(heap, functions, args, interns),
+            Self::Collections(functions) => collections::call(heap, functions, args, interns),
+            Self::Itertools(functions) => itertools::call(heap, functions, args),
+            Self::Functools(functions) => functools::call(heap, functions, args, interns),
+            Self::Random(functions) => random::call(heap, functions, args, interns),
+            Self::String(functions) => string::call(heap, functions, args, interns),
+            Self::Textwrap(functions) => textwrap::call(heap, functions, args, interns),
+            Self::Base64(functions) => base64::call(heap, functions, args, interns),
+            Self::Hashlib(functions) => hashlib::call(heap, functions, args, interns),
+            Self::Uuid(functions) => uuid::call(heap, functions, args, interns),
+            Self::Decimal(functions) => decimal::call(heap, functions, args, interns),
         }
     }
 
@@ -0,0 +1,99 @@
+//! Implementation of the `random` module.
+//!
+//! Provides Python's `random` module:
+//! - `random()`, `uniform()` and `gauss()`, which return random floats
+//! - `getrandbits()`, `randrange()` and `randint()`, which return random ints
+//! - `choice()`, `choices()`, `sample()` and `shuffle()`, which pick from sequences
+//! - `seed()`, which reseeds the module's generator
+//! - `Random`, the class of independent generators
+//!
+//! The generator is implemented in [`crate::types::random`]. The module's functions share
+//! one generator stored on the heap. Programs that don't seed it get a seed from the host,
+//! which the VM asks for before the first call (see `bytecode/vm/random.rs`).
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    exception_private::RunResult,
+    heap::{Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{AttrCallResult, Module, Type},
+    value::Value,
+};
+
+/// random module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum RandomFunctions {
+    Random,
+    Seed,
+    Getrandbits,
+    Randrange,
+    Randint,
+    Choice,
+    Choices,
+    Sample,
+    Shuffle,
+    Uniform,
+    Gauss,
+}
+
+/// Creates the `random` module and allocates it on the heap.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Random);
+
+    module.set_attr(
+        StaticStrings::RandomClass,
+        Value::Builtin(Builtins::Type(Type::Random)),
+        heap,
+        interns,
+    );
+    for (name, function) in [
+        (StaticStrings::Random, RandomFunctions::Random),
+        (StaticStrings::Seed, RandomFunctions::Seed),
+        (StaticStrings::Getrandbits, RandomFunctions::Getrandbits),
+        (StaticStrings::Randrange, RandomFunctions::Randrange),
+        (StaticStrings::Randint, RandomFunctions::Randint),
+        (StaticStrings::Choice, RandomFunctions::Choice),
+        (StaticStrings::Choices, RandomFunctions::Choices),
+        (StaticStrings::Sample, RandomFunctions::Sample),
+        (StaticStrings::Shuffle, RandomFunctions::Shuffle),
+        (StaticStrings::Uniform, RandomFunctions::Uniform),
+        (StaticStrings::Gauss, RandomFunctions::Gauss),
+    ] {
+        module.set_attr(
+            name,
+            Value::ModuleFunction(ModuleFunctions::Random(function)),
+            heap,
+            interns,
+        );
+    }
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a random module function, using the module's generator.
+///
+/// `seed()`, and any call before the generator is seeded, are handled by the VM, since
+/// they may need a seed from the host.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    function: RandomFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let mut random = heap
+        .take_random()
+        .expect("the VM seeds the generator before calling random functions");
+    let result = random.call(heap, function, args, interns);
+    heap.set_random(random);
+    Ok(AttrCallResult::Value(result?))
+}
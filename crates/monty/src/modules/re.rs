@@ -0,0 +1,264 @@
+//! Implementation of the `re` module.
+//!
+//! Provides Python's `re` module for str patterns:
+//! - `compile(pattern, flags=0)`: Compiles a pattern to an `re.Pattern`
+//! - `search()`, `match()` and `fullmatch()`: Find a single match, returning an `re.Match` or `None`
+//! - `findall()` and `finditer()`: Find every non-overlapping match
+//! - `sub()` and `subn()`: Replace matches with a template or the result of a function
+//! - `split()`: Split a string by the matches of a pattern
+//! - `escape()`: Escapes special characters in a string
+//! - `error` (also `PatternError`): Raised for invalid patterns
+//! - The flags `IGNORECASE`, `MULTILINE`, `DOTALL`, `VERBOSE`, `ASCII`, `UNICODE`, `LOCALE`
+//!   and `NOFLAG`, with their single letter aliases
+//!
+//! Matching uses the linear-time engine in `crate::regex`, so patterns which need
+//! backtracking (backreferences and lookaround) are rejected with `re.error`. Bytes
+//! patterns aren't supported. Patterns aren't cached, so code searching in a loop
+//! should `compile()` its pattern first.
+//!
+//! `sub()` and `subn()` may call a function for each match, so they're run by the VM
+//! (see `bytecode/vm/re.rs`).
+
+use crate::{
+    args::{ArgValues, bind_args},
+    builtins::Builtins,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    modules::ModuleFunctions,
+    regex::{ASCII, Anchor, DOTALL, IGNORECASE, LOCALE, MULTILINE, Regex, UNICODE, VERBOSE},
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        AttrCallResult, Module, PyTrait, RePattern,
+        re::{pattern_findall, pattern_finditer, pattern_search, pattern_split},
+        str::allocate_string,
+    },
+    value::Value,
+};
+
+/// re module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum ReFunctions {
+    Compile,
+    Search,
+    Match,
+    Fullmatch,
+    Findall,
+    Finditer,
+    Sub,
+    Subn,
+    Split,
+    Escape,
+}
+
+/// The module's flags, with their single letter aliases.
+const FLAGS: [(StaticStrings, u8, u32); 7] = [
+    (StaticStrings::Ignorecase, b'I', IGNORECASE),
+    (StaticStrings::Locale, b'L', LOCALE),
+    (StaticStrings::Multiline, b'M', MULTILINE),
+    (StaticStrings::Dotall, b'S', DOTALL),
+    (StaticStrings::Unicode, b'U', UNICODE),
+    (StaticStrings::Verbose, b'X', VERBOSE),
+    (StaticStrings::Ascii, b'A', ASCII),
+];
+
+/// Creates the `re` module and allocates it on the heap.
+///
+/// The module provides:
+/// - `compile()`, `search()`, `match()`, `fullmatch()`, `findall()`, `finditer()`, `sub()`,
+///   `subn()`, `split()` and `escape()`
+/// - `error` and its alias `PatternError`
+/// - The flag constants
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Re);
+
+    let functions = [
+        (StaticStrings::Compile, ReFunctions::Compile),
+        (StaticStrings::Search, ReFunctions::Search),
+        (StaticStrings::Match, ReFunctions::Match),
+        (StaticStrings::Fullmatch, ReFunctions::Fullmatch),
+        (StaticStrings::Findall, ReFunctions::Findall),
+        (StaticStrings::Finditer, ReFunctions::Finditer),
+        (StaticStrings::Sub, ReFunctions::Sub),
+        (StaticStrings::Subn, ReFunctions::Subn),
+        (StaticStrings::Split, ReFunctions::Split),
+        (StaticStrings::Escape, ReFunctions::Escape),
+    ];
+    for (name, function) in functions {
+        module.set_attr(
+            name,
+            Value::ModuleFunction(ModuleFunctions::Re(function)),
+            heap,
+            interns,
+        );
+    }
+
+    for name in [StaticStrings::Error, StaticStrings::PatternError] {
+        module.set_attr(
+            name,
+            Value::Builtin(Builtins::ExcType(ExcType::PatternError)),
+            heap,
+            interns,
+        );
+    }
+
+    module.set_attr(StaticStrings::Noflag, Value::Int(0), heap, interns);
+    for (name, letter, flag) in FLAGS {
+        module.set_attr(name, Value::Int(i64::from(flag)), heap, interns);
+        module.set_attr(StringId::from_ascii(letter), Value::Int(i64::from(flag)), heap, interns);
+    }
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a re module function.
+///
+/// `sub()` and `subn()` are always called by the VM, since they may call a function for
+/// each match.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    functions: ReFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let value = match functions {
+        ReFunctions::Compile => {
+            let [pattern, flags] = bind_args("compile", args, ["pattern", "flags"], 1, heap, interns)?;
+            let pattern_id = compile(heap, pattern.expect("required argument is bound"), flags, interns)?;
+            Value::Ref(pattern_id)
+        }
+        ReFunctions::Search | ReFunctions::Match | ReFunctions::Fullmatch => {
+            let name = functions.to_string();
+            let [pattern, string, flags] = bind_args(&name, args, ["pattern", "string", "flags"], 2, heap, interns)?;
+            let string = string.expect("required argument is bound");
+            defer_drop!(string, heap);
+            let pattern_id = compile(heap, pattern.expect("required argument is bound"), flags, interns)?;
+            let pattern = Value::Ref(pattern_id);
+            defer_drop!(pattern, heap);
+            let anchor = match functions {
+                ReFunctions::Search => Anchor::Unanchored,
+                ReFunctions::Match => Anchor::Start,
+                _ => Anchor::Both,
+            };
+            pattern_search(heap, pattern_id, string, None, None, anchor, interns)?
+        }
+        ReFunctions::Findall | ReFunctions::Finditer => {
+            let name = functions.to_string();
+            let [pattern, string, flags] = bind_args(&name, args, ["pattern", "string", "flags"], 2, heap, interns)?;
+            let string = string.expect("required argument is bound");
+            defer_drop!(string, heap);
+            let pattern_id = compile(heap, pattern.expect("required argument is bound"), flags, interns)?;
+            let pattern = Value::Ref(pattern_id);
+            defer_drop!(pattern, heap);
+            if functions == ReFunctions::Findall {
+                pattern_findall(heap, pattern_id, string, None, None, interns)?
+            } else {
+                pattern_finditer(heap, pattern_id, string, None, None, interns)?
+            }
+        }
+        ReFunctions::Split => {
+            let [pattern, string, maxsplit, flags] = bind_args(
+                "split",
+                args,
+                ["pattern", "string", "maxsplit", "flags"],
+                2,
+                heap,
+                interns,
+            )?;
+            let string = string.expect("required argument is bound");
+            defer_drop!(string, heap);
+            defer_drop!(maxsplit, heap);
+            let pattern_id = compile(heap, pattern.expect("required argument is bound"), flags, interns)?;
+            let pattern = Value::Ref(pattern_id);
+            defer_drop!(pattern, heap);
+            pattern_split(heap, pattern_id, string, maxsplit.as_ref(), interns)?
+        }
+        ReFunctions::Escape => {
+            let [pattern] = bind_args("escape", args, ["pattern"], 1, heap, interns)?;
+            let pattern = pattern.expect("required argument is bound");
+            defer_drop!(pattern, heap);
+            let Some(text) = pattern.as_either_str(heap) else {
+                return Err(ExcType::type_error(format!(
+                    "decoding to str: need a bytes-like object, {} found",
+                    pattern.py_type(heap)
+                )));
+            };
+            let escaped = escape(text.as_str(interns));
+            allocate_string(escaped, heap)?
+        }
+        ReFunctions::Sub | ReFunctions::Subn => unreachable!("re.sub() and re.subn() are called by the VM"),
+    };
+    Ok(AttrCallResult::Value(value))
+}
+
+/// Returns the compiled pattern for the `pattern` and `flags` arguments of a module function,
+/// taking ownership of both.
+///
+/// A compiled pattern is returned as is, with a new reference.
+///
+/// # Errors
+/// Returns `TypeError` if `pattern` isn't a str or compiled pattern, or `flags` isn't an
+/// int, `ValueError` for flags which can't be used, and `re.error` if the pattern is invalid.
+pub(crate) fn compile(
+    heap: &mut Heap<impl ResourceTracker>,
+    pattern: Value,
+    flags: Option<Value>,
+    interns: &Interns,
+) -> RunResult<HeapId> {
+    defer_drop!(pattern, heap);
+    defer_drop!(flags, heap);
+    let flags = match flags {
+        Some(flags) => flags.as_int(heap)?,
+        None => 0,
+    };
+
+    if let Value::Ref(id) = pattern
+        && matches!(heap.get(*id), HeapData::RePattern(_))
+    {
+        if flags != 0 {
+            return Err(SimpleException::new_msg(
+                ExcType::ValueError,
+                "cannot process flags argument with a compiled pattern",
+            )
+            .into());
+        }
+        heap.inc_ref(*id);
+        return Ok(*id);
+    }
+    let Some(text) = pattern.as_either_str(heap) else {
+        return Err(ExcType::type_error("first argument must be string or compiled pattern"));
+    };
+    let text = text.as_str(interns);
+
+    // Unknown flags are ignored
+    let known = IGNORECASE | LOCALE | MULTILINE | DOTALL | UNICODE | VERBOSE | ASCII;
+    let flags = u32::try_from(flags & i64::from(known)).expect("masked flags fit in u32");
+    if flags & LOCALE != 0 {
+        return Err(SimpleException::new_msg(ExcType::ValueError, "cannot use LOCALE flag with a str pattern").into());
+    }
+    if flags & ASCII != 0 && flags & UNICODE != 0 {
+        return Err(SimpleException::new_msg(ExcType::ValueError, "ASCII and UNICODE flags are incompatible").into());
+    }
+    let text = text.to_owned();
+    let regex = Regex::new(&text, flags).map_err(ExcType::re_error)?;
+    Ok(heap.allocate(HeapData::RePattern(RePattern::new(text, regex)))?)
+}
+
+/// Escapes the characters with a special meaning in patterns, as `re.escape()` does.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "()[]{}?*+-|^$\\.&~# \t\n\r\x0b\x0c".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,130 @@
+//! Implementation of the `string` module.
+//!
+//! Provides Python's `string` module:
+//! - the character constants, like `ascii_letters`, `digits` and `whitespace`
+//! - `capwords()`, which capitalizes the words of a string
+//! - `Template`, the class of `$`-substitution templates (see [`crate::types::template`])
+
+use crate::{
+    args::{ArgValues, bind_args},
+    builtins::Builtins,
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        AttrCallResult, Module, PyTrait, Str, Type,
+        str::{allocate_string, capitalize},
+    },
+    value::Value,
+};
+
+/// string module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum StringFunctions {
+    Capwords,
+}
+
+const ASCII_LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const ASCII_UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const PUNCTUATION: &str = r##"!"#$%&'()*+,-./:;<=>?@[\]^_`{|}~"##;
+const WHITESPACE: &str = " \t\n\r\x0b\x0c";
+
+/// Creates the `string` module and allocates it on the heap.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::String);
+
+    let ascii_letters = format!("{ASCII_LOWERCASE}{ASCII_UPPERCASE}");
+    let printable = format!("{DIGITS}{ascii_letters}{PUNCTUATION}{WHITESPACE}");
+    for (name, text) in [
+        (StaticStrings::AsciiLetters, ascii_letters.as_str()),
+        (StaticStrings::AsciiLowercase, ASCII_LOWERCASE),
+        (StaticStrings::AsciiUppercase, ASCII_UPPERCASE),
+        (StaticStrings::Digits, DIGITS),
+        (StaticStrings::Hexdigits, "0123456789abcdefABCDEF"),
+        (StaticStrings::Octdigits, "01234567"),
+        (StaticStrings::Punctuation, PUNCTUATION),
+        (StaticStrings::Printable, printable.as_str()),
+        (StaticStrings::Whitespace, WHITESPACE),
+    ] {
+        let id = heap.allocate(HeapData::Str(Str::new(text.to_owned())))?;
+        module.set_attr(name, Value::Ref(id), heap, interns);
+    }
+    module.set_attr(
+        StaticStrings::TemplateClass,
+        Value::Builtin(Builtins::Type(Type::Template)),
+        heap,
+        interns,
+    );
+    module.set_attr(
+        StaticStrings::Capwords,
+        Value::ModuleFunction(ModuleFunctions::String(StringFunctions::Capwords)),
+        heap,
+        interns,
+    );
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a string module function.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    function: StringFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    match function {
+        StringFunctions::Capwords => capwords(heap, args, interns).map(AttrCallResult::Value),
+    }
+}
+
+/// Implements `string.capwords(s, sep=None)`.
+///
+/// Splits `s` at `sep`, or at runs of whitespace, capitalizes each word and joins them
+/// with `sep`, or a space.
+///
+/// # Errors
+/// Returns `AttributeError` if `s` isn't a str or `sep` isn't a str or `None`, as CPython
+/// calls their methods, and `ValueError` for an empty `sep`.
+fn capwords(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let [s, sep] = bind_args("capwords", args, ["s", "sep"], 1, heap, interns)?;
+    let s = s.expect("required argument is bound");
+    let result = capwords_text(&s, sep.as_ref(), heap, interns);
+    s.drop_with_heap(heap);
+    sep.drop_with_heap(heap);
+    allocate_string(result?, heap)
+}
+
+fn capwords_text(
+    s: &Value,
+    sep: Option<&Value>,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<String> {
+    let Some(text) = s.as_either_str(heap) else {
+        return Err(ExcType::attribute_error(s.py_type(heap), "split"));
+    };
+    let text = text.as_str(interns);
+    match sep {
+        None | Some(Value::None) => Ok(text.split_whitespace().map(capitalize).collect::<Vec<_>>().join(" ")),
+        Some(sep) => {
+            let Some(sep) = sep.as_either_str(heap) else {
+                return Err(ExcType::attribute_error(sep.py_type(heap), "join"));
+            };
+            let sep = sep.as_str(interns);
+            if sep.is_empty() {
+                return Err(ExcType::value_error_empty_separator());
+            }
+            Ok(text.split(sep).map(capitalize).collect::<Vec<_>>().join(sep))
+        }
+    }
+}
@@ -4,14 +4,19 @@
 //! - `version`: Python version string (e.g., "3.14.0 (Monty)")
 //! - `version_info`: Named tuple (3, 14, 0, 'final', 0)
 //! - `platform`: Platform identifier ("monty")
-//! - `stdout`: Marker for standard output (no real functionality)
-//! - `stderr`: Marker for standard error (no real functionality)
+//! - `stdout`: Standard output, supporting `write()` and `flush()`
+//! - `stderr`: Standard error, supporting `write()` and `flush()`
+//!
+//! Writes to `stdout` and `stderr` go to the matching stream of the host's `PrintWriter`.
 
 use crate::{
-    heap::{Heap, HeapData, HeapId},
-    intern::{Interns, StaticStrings},
+    args::ArgValues,
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    io::{PrintStream, PrintWriter},
     resource::{ResourceError, ResourceTracker},
-    types::{Module, NamedTuple},
+    types::{Module, NamedTuple, PyTrait, Type},
     value::{Marker, Value},
 };
 
@@ -28,7 +33,7 @@ pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -
     // sys.platform
     module.set_attr(StaticStrings::Platform, StaticStrings::Monty.into(), heap, interns);
 
-    // sys.stdout / sys.stderr - markers for standard output/error
+    // sys.stdout / sys.stderr - markers for standard output/error, see `call_stream_method`
     module.set_attr(
         StaticStrings::Stdout,
         Value::Marker(Marker(StaticStrings::Stdout)),
@@ -72,3 +77,54 @@ pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -
 
     heap.allocate(HeapData::Module(module))
 }
+
+/// Returns the stream a `sys.stdout` or `sys.stderr` marker writes to.
+#[must_use]
+pub(crate) fn marker_stream(marker: Marker) -> Option<PrintStream> {
+    match marker.0 {
+        StaticStrings::Stdout => Some(PrintStream::Stdout),
+        StaticStrings::Stderr => Some(PrintStream::Stderr),
+        _ => None,
+    }
+}
+
+/// Calls a method of `sys.stdout` or `sys.stderr`.
+///
+/// - `write(s)` writes `s` to the stream and returns the number of characters written
+/// - `flush()` does nothing, since output isn't buffered
+pub(crate) fn call_stream_method(
+    stream: PrintStream,
+    method: StringId,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+    print: &mut impl PrintWriter,
+) -> RunResult<Value> {
+    match StaticStrings::from_string_id(method) {
+        Some(StaticStrings::Write) => {
+            let text = args.get_one_arg("write", heap)?;
+            let s = text.as_either_str(heap);
+            let type_name = text.py_type(heap);
+            text.drop_with_heap(heap);
+            let Some(s) = s else {
+                return Err(ExcType::type_error(format!(
+                    "write() argument must be str, not {type_name}"
+                )));
+            };
+            let s = s.as_str(interns);
+            stream.write(print, s.into())?;
+            let written = s.chars().count();
+            Ok(Value::Int(
+                i64::try_from(written).expect("string length exceeds i64::MAX"),
+            ))
+        }
+        Some(StaticStrings::Flush) => {
+            args.check_zero_args("flush", heap)?;
+            Ok(Value::None)
+        }
+        _ => {
+            args.drop_with_heap(heap);
+            Err(ExcType::attribute_error(Type::TextIOWrapper, interns.get_str(method)))
+        }
+    }
+}
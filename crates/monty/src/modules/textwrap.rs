@@ -0,0 +1,636 @@
+//! Implementation of the `textwrap` module.
+//!
+//! Provides Python's `textwrap` module:
+//! - `wrap()` and `fill()`, which break text into lines of at most `width` characters
+//! - `shorten()`, which collapses whitespace and truncates text to fit on one line
+//! - `dedent()`, which removes the common leading whitespace of lines
+//! - `indent()`, which prefixes lines
+//!
+//! `wrap()`, `fill()` and `shorten()` take the keyword arguments of CPython's `TextWrapper`,
+//! and split words the same way, including at hyphens and em-dashes. `indent()` may call a
+//! predicate, so it's run by the VM (see `bytecode/vm/textwrap.rs`).
+
+use std::iter;
+
+use crate::{
+    args::{ArgValues, bind_args, bind_args_vec},
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        AttrCallResult, List, Module, PyTrait,
+        str::{allocate_string, is_unicode_decimal},
+    },
+    value::Value,
+};
+
+/// textwrap module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum TextwrapFunctions {
+    Wrap,
+    Fill,
+    Shorten,
+    Dedent,
+    Indent,
+}
+
+/// The keyword arguments of `wrap()`, `fill()` and `shorten()`, in the order of the
+/// parameters of CPython's `TextWrapper`.
+const WRAPPER_PARAMS: [&str; 12] = [
+    "width",
+    "initial_indent",
+    "subsequent_indent",
+    "expand_tabs",
+    "replace_whitespace",
+    "fix_sentence_endings",
+    "break_long_words",
+    "drop_whitespace",
+    "break_on_hyphens",
+    "tabsize",
+    "max_lines",
+    "placeholder",
+];
+
+/// Creates the `textwrap` module and allocates it on the heap.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Textwrap);
+
+    for (name, function) in [
+        (StaticStrings::Wrap, TextwrapFunctions::Wrap),
+        (StaticStrings::Fill, TextwrapFunctions::Fill),
+        (StaticStrings::Shorten, TextwrapFunctions::Shorten),
+        (StaticStrings::Dedent, TextwrapFunctions::Dedent),
+        (StaticStrings::Indent, TextwrapFunctions::Indent),
+    ] {
+        module.set_attr(
+            name,
+            Value::ModuleFunction(ModuleFunctions::Textwrap(function)),
+            heap,
+            interns,
+        );
+    }
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a textwrap module function.
+///
+/// `indent()` is called by the VM, since it may call a Python function.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    function: TextwrapFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let value = match function {
+        TextwrapFunctions::Wrap => {
+            let (text, wrapper) = wrapper_args("wrap", args, false, heap, interns)?;
+            let lines = wrapper.wrap(&text, heap.tracker_mut())?;
+            let mut items = Vec::with_capacity(lines.len());
+            for line in lines {
+                items.push(allocate_string(line, heap)?);
+            }
+            Value::Ref(heap.allocate(HeapData::List(List::new(items)))?)
+        }
+        TextwrapFunctions::Fill => {
+            let (text, wrapper) = wrapper_args("fill", args, false, heap, interns)?;
+            let lines = wrapper.wrap(&text, heap.tracker_mut())?;
+            allocate_string(lines.join("\n"), heap)?
+        }
+        TextwrapFunctions::Shorten => {
+            let (text, wrapper) = wrapper_args("shorten", args, true, heap, interns)?;
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            let lines = wrapper.wrap(&text, heap.tracker_mut())?;
+            allocate_string(lines.join("\n"), heap)?
+        }
+        TextwrapFunctions::Dedent => {
+            let [text] = bind_args("dedent", args, ["text"], 1, heap, interns)?;
+            let text = text.expect("required argument is bound");
+            let result = str_arg("dedent", "text", &text, heap, interns).map(dedent);
+            text.drop_with_heap(heap);
+            allocate_string(result?, heap)?
+        }
+        TextwrapFunctions::Indent => unreachable!("textwrap.indent() is called by the VM"),
+    };
+    Ok(AttrCallResult::Value(value))
+}
+
+/// Binds the arguments of `wrap()`, `fill()` or `shorten()`, returning the text and the
+/// options of the wrapper.
+///
+/// `shorten()` requires `width` and always wraps to one line, so it doesn't take `max_lines`.
+fn wrapper_args(
+    name: &str,
+    args: ArgValues,
+    shorten: bool,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<(String, Wrapper)> {
+    let params: Vec<&str> = iter::once("text")
+        .chain(
+            WRAPPER_PARAMS
+                .into_iter()
+                .filter(|param| !shorten || *param != "max_lines"),
+        )
+        .collect();
+    let required = if shorten { 2 } else { 1 };
+    let bound = bind_args_vec(name, args, &params, required, heap, interns)?;
+
+    let mut text = String::new();
+    let mut wrapper = Wrapper::default();
+    if shorten {
+        wrapper.max_lines = Some(1);
+    }
+    let mut result = Ok(());
+    for (param, value) in params.into_iter().zip(bound) {
+        let Some(value) = value else { continue };
+        if result.is_ok() {
+            result = wrapper.set_option(name, param, &value, &mut text, heap, interns);
+        }
+        value.drop_with_heap(heap);
+    }
+    result.map(|()| (text, wrapper))
+}
+
+/// Returns the text of a str argument.
+///
+/// # Errors
+/// Returns `TypeError` for anything else.
+fn str_arg(
+    name: &str,
+    param: &str,
+    value: &Value,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<String> {
+    match value.as_either_str(heap) {
+        Some(text) => Ok(text.as_str(interns).to_owned()),
+        None => Err(ExcType::type_error(format!(
+            "{name}() argument '{param}' must be str, not {}",
+            value.py_type(heap)
+        ))),
+    }
+}
+
+/// The options of `wrap()`, `fill()` and `shorten()`, which CPython keeps on a `TextWrapper`.
+#[derive(Debug)]
+struct Wrapper {
+    width: i64,
+    initial_indent: String,
+    subsequent_indent: String,
+    expand_tabs: bool,
+    replace_whitespace: bool,
+    fix_sentence_endings: bool,
+    break_long_words: bool,
+    drop_whitespace: bool,
+    break_on_hyphens: bool,
+    tabsize: i64,
+    max_lines: Option<i64>,
+    placeholder: String,
+}
+
+impl Default for Wrapper {
+    fn default() -> Self {
+        Self {
+            width: 70,
+            initial_indent: String::new(),
+            subsequent_indent: String::new(),
+            expand_tabs: true,
+            replace_whitespace: true,
+            fix_sentence_endings: false,
+            break_long_words: true,
+            drop_whitespace: true,
+            break_on_hyphens: true,
+            tabsize: 8,
+            max_lines: None,
+            placeholder: " [...]".to_owned(),
+        }
+    }
+}
+
+impl Wrapper {
+    /// Sets the option `param` to `value`, or `text` for the `text` parameter.
+    fn set_option(
+        &mut self,
+        name: &str,
+        param: &str,
+        value: &Value,
+        text: &mut String,
+        heap: &Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<()> {
+        match param {
+            "text" => *text = str_arg(name, param, value, heap, interns)?,
+            "width" => self.width = value.as_int(heap)?,
+            "initial_indent" => self.initial_indent = str_arg(name, param, value, heap, interns)?,
+            "subsequent_indent" => self.subsequent_indent = str_arg(name, param, value, heap, interns)?,
+            "expand_tabs" => self.expand_tabs = value.py_bool(heap, interns),
+            "replace_whitespace" => self.replace_whitespace = value.py_bool(heap, interns),
+            "fix_sentence_endings" => self.fix_sentence_endings = value.py_bool(heap, interns),
+            "break_long_words" => self.break_long_words = value.py_bool(heap, interns),
+            "drop_whitespace" => self.drop_whitespace = value.py_bool(heap, interns),
+            "break_on_hyphens" => self.break_on_hyphens = value.py_bool(heap, interns),
+            "tabsize" => self.tabsize = value.as_int(heap)?,
+            "max_lines" => {
+                self.max_lines = match value {
+                    Value::None => None,
+                    _ => Some(value.as_int(heap)?),
+                };
+            }
+            "placeholder" => self.placeholder = str_arg(name, param, value, heap, interns)?,
+            _ => unreachable!("unknown TextWrapper parameter {param}"),
+        }
+        Ok(())
+    }
+
+    /// Wraps `text` into lines, as `TextWrapper.wrap()`.
+    ///
+    /// # Errors
+    /// Returns `ValueError` for a width which leaves no room for text or the placeholder.
+    fn wrap(&self, text: &str, tracker: &mut impl ResourceTracker) -> RunResult<Vec<String>> {
+        let text = self.munge_whitespace(text, tracker)?;
+        let chars: Vec<char> = text.chars().collect();
+        let mut chunks = if self.break_on_hyphens {
+            split_words(&chars)
+        } else {
+            split_spaces(&chars)
+        };
+        if self.fix_sentence_endings {
+            fix_sentence_endings(&mut chunks);
+        }
+        self.wrap_chunks(chunks, tracker)
+    }
+
+    /// Expands tabs and replaces each whitespace character with a space.
+    fn munge_whitespace(&self, text: &str, tracker: &impl ResourceTracker) -> Result<String, ResourceError> {
+        let text = if self.expand_tabs {
+            expand_tabs(text, self.tabsize, tracker)?
+        } else {
+            text.to_owned()
+        };
+        if self.replace_whitespace {
+            Ok(text.chars().map(|c| if is_wrap_space(c) { ' ' } else { c }).collect())
+        } else {
+            Ok(text)
+        }
+    }
+
+    /// Fills lines with chunks, as `TextWrapper._wrap_chunks()`.
+    fn wrap_chunks(&self, mut chunks: Vec<String>, tracker: &mut impl ResourceTracker) -> RunResult<Vec<String>> {
+        if self.width <= 0 {
+            return Err(ExcType::value_error(format!(
+                "invalid width {} (must be > 0)",
+                self.width
+            )));
+        }
+        let placeholder_len = char_len(&self.placeholder);
+        if let Some(max_lines) = self.max_lines {
+            let indent = if max_lines > 1 {
+                &self.subsequent_indent
+            } else {
+                &self.initial_indent
+            };
+            if char_len(indent) + char_len(self.placeholder.trim_start()) > self.width {
+                return Err(ExcType::value_error("placeholder too large for max width".to_owned()));
+            }
+        }
+        chunks.reverse();
+        let mut lines: Vec<String> = Vec::new();
+        let mut size = 0;
+        while !chunks.is_empty() {
+            tracker.check_time()?;
+            let mut line: Vec<String> = Vec::new();
+            let mut line_len = 0;
+            let indent = if lines.is_empty() {
+                &self.initial_indent
+            } else {
+                &self.subsequent_indent
+            };
+            let width = self.width.saturating_sub(char_len(indent));
+            if self.drop_whitespace && !lines.is_empty() && chunks.last().is_some_and(|chunk| is_blank(chunk)) {
+                chunks.pop();
+            }
+            while let Some(chunk) = chunks.last() {
+                let len = char_len(chunk);
+                if line_len + len > width {
+                    break;
+                }
+                line.extend(chunks.pop());
+                line_len += len;
+            }
+            if chunks.last().is_some_and(|chunk| char_len(chunk) > width) {
+                self.handle_long_word(&mut chunks, &mut line, line_len, width);
+                line_len = line.iter().map(|chunk| char_len(chunk)).sum();
+            }
+            if self.drop_whitespace && line.last().is_some_and(|chunk| is_blank(chunk)) {
+                line_len -= line.pop().map_or(0, |chunk| char_len(&chunk));
+            }
+            if line.is_empty() {
+                continue;
+            }
+            // Each line repeats its indent, so the result may be much larger than the text
+            size += indent.len() + line.iter().map(String::len).sum::<usize>();
+            tracker.check_large_result(size)?;
+
+            let fits = match self.max_lines {
+                None => true,
+                Some(max_lines) => {
+                    len_i64(lines.len() + 1) < max_lines
+                        || ((chunks.is_empty() || (self.drop_whitespace && chunks.len() == 1 && is_blank(&chunks[0])))
+                            && line_len <= width)
+                }
+            };
+            if fits {
+                lines.push(format!("{indent}{}", line.concat()));
+                continue;
+            }
+            // Too many lines: end with the placeholder, dropping words until it fits
+            while let Some(last) = line.last() {
+                if !is_blank(last) && line_len + placeholder_len <= width {
+                    lines.push(format!("{indent}{}{}", line.concat(), self.placeholder));
+                    return Ok(lines);
+                }
+                line_len -= char_len(last);
+                line.pop();
+            }
+            if let Some(previous) = lines.last_mut() {
+                let trimmed = previous.trim_end();
+                if char_len(trimmed) + placeholder_len <= self.width {
+                    *previous = format!("{trimmed}{}", self.placeholder);
+                    return Ok(lines);
+                }
+            }
+            lines.push(format!("{indent}{}", self.placeholder.trim_start()));
+            return Ok(lines);
+        }
+        Ok(lines)
+    }
+
+    /// Breaks a word longer than the line, as `TextWrapper._handle_long_word()`.
+    fn handle_long_word(&self, chunks: &mut Vec<String>, line: &mut Vec<String>, line_len: i64, width: i64) {
+        let space_left = if width < 1 { 1 } else { width - line_len };
+        if self.break_long_words && space_left > 0 {
+            let chunk: Vec<char> = chunks.last().expect("a long word is left").chars().collect();
+            let space_left = usize::try_from(space_left).expect("space left is positive");
+            let mut end = space_left;
+            if self.break_on_hyphens && chunk.len() > space_left {
+                // Break after the last hyphen which fits, unless only hyphens come before it
+                let hyphen = chunk[..space_left].iter().rposition(|c| *c == '-');
+                if let Some(hyphen) = hyphen
+                    && hyphen > 0
+                    && chunk[..hyphen].iter().any(|c| *c != '-')
+                {
+                    end = hyphen + 1;
+                }
+            }
+            let end = end.min(chunk.len());
+            line.push(chunk[..end].iter().collect());
+            *chunks.last_mut().expect("a long word is left") = chunk[end..].iter().collect();
+        } else if line.is_empty() {
+            line.extend(chunks.pop());
+        }
+    }
+}
+
+/// Splits text into words, whitespace and em-dashes, breaking hyphenated words after their
+/// hyphens, like CPython's `TextWrapper.wordsep_re`.
+fn split_words(chars: &[char]) -> Vec<String> {
+    let at = |i: usize| chars.get(i).copied();
+    let before = |i: usize, n: usize| i.checked_sub(n).and_then(at);
+    // `-{2,}\w` at `i`
+    let em_dash_at = |i: usize| {
+        let dashes = chars[i.min(chars.len())..].iter().take_while(|c| **c == '-').count();
+        dashes >= 2 && at(i + dashes).is_some_and(is_word)
+    };
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let end = if is_wrap_space(chars[start]) {
+            start + chars[start..].iter().take_while(|c| is_wrap_space(**c)).count()
+        } else if before(start, 1).is_some_and(is_word_punct) && em_dash_at(start) {
+            start + chars[start..].iter().take_while(|c| **c == '-').count()
+        } else {
+            let mut end = start + 1;
+            loop {
+                let next = at(end);
+                // A hyphen between letters, like "high-speed" or "a-b-c"
+                let hyphen = next == Some('-')
+                    && ((before(end, 1).is_some_and(is_letter) && before(end, 2).is_some_and(is_letter))
+                        || (before(end, 1).is_some_and(is_letter)
+                            && before(end, 2) == Some('-')
+                            && before(end, 3).is_some_and(is_letter)))
+                    && at(end + 1).is_some_and(is_letter)
+                    && (at(end + 2).is_some_and(is_letter)
+                        || (at(end + 2) == Some('-') && at(end + 3).is_some_and(is_letter)));
+                if hyphen {
+                    end += 1;
+                    break;
+                }
+                if next.is_none_or(is_wrap_space) || (before(end, 1).is_some_and(is_word_punct) && em_dash_at(end)) {
+                    break;
+                }
+                end += 1;
+            }
+            end
+        };
+        chunks.push(chars[start..end].iter().collect());
+        start = end;
+    }
+    chunks
+}
+
+/// Splits text into words and whitespace, like CPython's `TextWrapper.wordsep_simple_re`.
+fn split_spaces(chars: &[char]) -> Vec<String> {
+    let mut chunks: Vec<String> = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let space = is_wrap_space(chars[start]);
+        let len = chars[start..]
+            .iter()
+            .take_while(|c| is_wrap_space(**c) == space)
+            .count();
+        chunks.push(chars[start..start + len].iter().collect());
+        start += len;
+    }
+    chunks
+}
+
+/// Puts two spaces after each sentence, where a sentence ends with a lowercase letter,
+/// a `.`, `!` or `?` and maybe a quote.
+fn fix_sentence_endings(chunks: &mut [String]) {
+    let sentence_end = |chunk: &str| {
+        let mut rev = chunk.chars().rev().peekable();
+        rev.next_if(|c| matches!(c, '"' | '\''));
+        rev.next().is_some_and(|c| matches!(c, '.' | '!' | '?')) && rev.next().is_some_and(|c| c.is_ascii_lowercase())
+    };
+    let mut i = 0;
+    while i + 1 < chunks.len() {
+        if chunks[i + 1] == " " && sentence_end(&chunks[i]) {
+            chunks[i + 1] = "  ".to_owned();
+            i += 2;
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// `str.expandtabs()`: replaces tabs with spaces up to the next multiple of `tabsize` columns.
+fn expand_tabs(text: &str, tabsize: i64, tracker: &impl ResourceTracker) -> Result<String, ResourceError> {
+    let tabsize = usize::try_from(tabsize).unwrap_or(0);
+    let mut out = String::with_capacity(text.len());
+    let mut column = 0;
+    for c in text.chars() {
+        match c {
+            '\t' => {
+                if tabsize > 0 {
+                    let spaces = tabsize - column % tabsize;
+                    tracker.check_large_result(out.len() + spaces)?;
+                    out.extend(iter::repeat_n(' ', spaces));
+                    column += spaces;
+                }
+            }
+            '\n' | '\r' => {
+                out.push(c);
+                column = 0;
+            }
+            _ => {
+                out.push(c);
+                column += 1;
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Implements `textwrap.dedent(text)`.
+///
+/// Lines of only spaces and tabs are emptied, and the leading spaces and tabs common to
+/// all other lines are removed.
+fn dedent(text: String) -> String {
+    let lines: Vec<&str> = text
+        .split('\n')
+        .map(|line| {
+            if line.trim_start_matches([' ', '\t']).is_empty() {
+                ""
+            } else {
+                line
+            }
+        })
+        .collect();
+    let mut margin: Option<&str> = None;
+    for line in &lines {
+        let content = line.trim_start_matches([' ', '\t']);
+        if content.is_empty() {
+            continue;
+        }
+        let indent = &line[..line.len() - content.len()];
+        margin = Some(match margin {
+            None => indent,
+            Some(margin) => {
+                let common = margin.bytes().zip(indent.bytes()).take_while(|(a, b)| a == b).count();
+                &margin[..common]
+            }
+        });
+    }
+    let margin = margin.unwrap_or_default();
+    lines
+        .iter()
+        .map(|line| line.strip_prefix(margin).unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Binds the arguments of `textwrap.indent(text, prefix, predicate=None)`, returning the
+/// text, the prefix and the predicate unless it's `None`.
+pub(crate) fn indent_args(
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<(String, String, Option<Value>)> {
+    let [text, prefix, predicate] = bind_args("indent", args, ["text", "prefix", "predicate"], 2, heap, interns)?;
+    let text = text.expect("required argument is bound");
+    let prefix = prefix.expect("required argument is bound");
+    let strings = str_arg("indent", "text", &text, heap, interns)
+        .and_then(|text| str_arg("indent", "prefix", &prefix, heap, interns).map(|prefix| (text, prefix)));
+    text.drop_with_heap(heap);
+    prefix.drop_with_heap(heap);
+    let predicate = predicate.filter(|predicate| !matches!(predicate, Value::None));
+    match strings {
+        Ok((text, prefix)) => Ok((text, prefix, predicate)),
+        Err(e) => {
+            predicate.drop_with_heap(heap);
+            Err(e)
+        }
+    }
+}
+
+/// The default predicate of `textwrap.indent()`, which skips lines of only whitespace.
+pub(crate) fn is_indented(line: &str) -> bool {
+    !is_blank(line)
+}
+
+/// Splits text into lines keeping their line breaks, like `str.splitlines(True)`.
+pub(crate) fn lines_with_ends(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let end = rest
+            .char_indices()
+            .find_map(|(i, c)| match c {
+                '\r' if rest[i + 1..].starts_with('\n') => Some(i + 2),
+                '\n' | '\r' | '\x0b' | '\x0c' | '\x1c' | '\x1d' | '\x1e' | '\u{85}' | '\u{2028}' | '\u{2029}' => {
+                    Some(i + c.len_utf8())
+                }
+                _ => None,
+            })
+            .unwrap_or(rest.len());
+        let (line, remaining) = rest.split_at(end);
+        rest = remaining;
+        Some(line)
+    })
+}
+
+/// The whitespace textwrap splits words at; unlike `str.split()`, this is only ASCII whitespace.
+fn is_wrap_space(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\x0b' | '\x0c' | '\r' | ' ')
+}
+
+/// `\w` of a regex.
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// `[^\d\W]` of a regex: a word character which isn't a digit.
+fn is_letter(c: char) -> bool {
+    is_word(c) && !is_unicode_decimal(c)
+}
+
+/// Characters which may come before an em-dash, `[\w!"'&.,?]`.
+fn is_word_punct(c: char) -> bool {
+    is_word(c) || matches!(c, '!' | '"' | '\'' | '&' | '.' | ',' | '?')
+}
+
+/// Python's `s.strip() == ''`.
+fn is_blank(s: &str) -> bool {
+    s.chars().all(char::is_whitespace)
+}
+
+/// The length of a string in characters, which is what textwrap measures widths in.
+fn char_len(s: &str) -> i64 {
+    len_i64(s.chars().count())
+}
+
+fn len_i64(len: usize) -> i64 {
+    i64::try_from(len).unwrap_or(i64::MAX)
+}
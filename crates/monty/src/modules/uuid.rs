@@ -0,0 +1,140 @@
+//! Implementation of the `uuid` module.
+//!
+//! Provides Python's `uuid` module:
+//! - `UUID`, the class of UUIDs (see [`crate::types::uuid`])
+//! - `uuid3()` and `uuid5()`, which make name-based UUIDs with MD5 and SHA-1
+//! - `uuid4()`, which makes a random UUID from bytes the host provides
+//! - `NAMESPACE_DNS`, `NAMESPACE_URL`, `NAMESPACE_OID` and `NAMESPACE_X500`
+//!
+//! `uuid4()` suspends the VM with `OsFunction::Uuid4` to ask the host for 16 random bytes,
+//! so it's handled by the VM.
+
+use crate::{
+    args::{ArgValues, bind_args},
+    builtins::Builtins,
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        AttrCallResult, Module, PyTrait, Type,
+        hashlib::{Algorithm, Hash},
+        uuid::{NAMESPACE_DNS, NAMESPACE_OID, NAMESPACE_URL, NAMESPACE_X500, Uuid, uuid_arg},
+    },
+    value::Value,
+};
+
+/// uuid module functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, serde::Serialize, serde::Deserialize)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum UuidFunctions {
+    Uuid3,
+    Uuid4,
+    Uuid5,
+}
+
+/// Creates the `uuid` module and allocates it on the heap.
+///
+/// # Returns
+/// A HeapId pointing to the newly allocated module.
+///
+/// # Panics
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Uuid);
+
+    module.set_attr(
+        StaticStrings::UuidClass,
+        Value::Builtin(Builtins::Type(Type::Uuid)),
+        heap,
+        interns,
+    );
+    for (name, function) in [
+        (StaticStrings::Uuid3, UuidFunctions::Uuid3),
+        (StaticStrings::Uuid4, UuidFunctions::Uuid4),
+        (StaticStrings::Uuid5, UuidFunctions::Uuid5),
+    ] {
+        module.set_attr(
+            name,
+            Value::ModuleFunction(ModuleFunctions::Uuid(function)),
+            heap,
+            interns,
+        );
+    }
+    for (name, namespace) in [
+        (StaticStrings::NamespaceDns, NAMESPACE_DNS),
+        (StaticStrings::NamespaceUrl, NAMESPACE_URL),
+        (StaticStrings::NamespaceOid, NAMESPACE_OID),
+        (StaticStrings::NamespaceX500, NAMESPACE_X500),
+    ] {
+        let id = heap.allocate(HeapData::Uuid(namespace))?;
+        module.set_attr(name, Value::Ref(id), heap, interns);
+    }
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a uuid module function.
+///
+/// `uuid4()` is called by the VM, since it asks the host for random bytes.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    function: UuidFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let (name, algorithm, version) = match function {
+        UuidFunctions::Uuid3 => ("uuid3", Algorithm::Md5, 3),
+        UuidFunctions::Uuid5 => ("uuid5", Algorithm::Sha1, 5),
+        UuidFunctions::Uuid4 => unreachable!("uuid.uuid4() is called by the VM"),
+    };
+    let [namespace, name_arg] = bind_args(name, args, ["namespace", "name"], 2, heap, interns)?;
+    let namespace = namespace.expect("required argument is bound");
+    let name_arg = name_arg.expect("required argument is bound");
+    let result = name_based(&namespace, &name_arg, algorithm, version, heap, interns);
+    namespace.drop_with_heap(heap);
+    name_arg.drop_with_heap(heap);
+    let id = heap.allocate(HeapData::Uuid(result?))?;
+    Ok(AttrCallResult::Value(Value::Ref(id)))
+}
+
+/// Makes the UUID of `name` in `namespace`, from the first 16 bytes of the hash of the
+/// namespace's bytes followed by the name, encoded as UTF-8 if it's a str.
+///
+/// # Errors
+/// Returns `AttributeError` if `namespace` isn't a UUID, and `TypeError` if `name` isn't
+/// a str or bytes.
+fn name_based(
+    namespace: &Value,
+    name: &Value,
+    algorithm: Algorithm,
+    version: u8,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Uuid> {
+    let mut data = uuid_arg(namespace, heap)?.bytes().to_vec();
+    let name_bytes = match name {
+        Value::InternString(id) => Some(interns.get_str(*id).as_bytes()),
+        Value::InternBytes(id) => Some(interns.get_bytes(*id)),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Str(s) => Some(s.as_str().as_bytes()),
+            HeapData::Bytes(bytes) => Some(bytes.as_slice()),
+            _ => None,
+        },
+        _ => None,
+    };
+    let Some(name_bytes) = name_bytes else {
+        return Err(ExcType::type_error(format!(
+            "can't concat {} to bytes",
+            name.py_type(heap)
+        )));
+    };
+    data.extend_from_slice(name_bytes);
+
+    let mut hash = Hash::new(algorithm);
+    hash.update_checked(&data, heap.tracker_mut())?;
+    let digest = hash.digest();
+    let bytes = digest[..16].try_into().expect("digests have at least 16 bytes");
+    Ok(Uuid::from_bytes_with_version(bytes, version))
+}
@@ -236,6 +236,19 @@ impl Namespaces {
         self.reuse_ids.push(namespace_id);
     }
 
+    /// Removes a namespace, returning its values instead of dropping them.
+    ///
+    /// This is the counterpart of `register_prebuilt`, used when a suspended generator
+    /// takes ownership of its frame's namespace. The freed memory is tracked through
+    /// the heap's `ResourceTracker`.
+    pub fn take(&mut self, namespace_id: NamespaceId, heap: &mut Heap<impl ResourceTracker>) -> Vec<Value> {
+        let namespace = std::mem::take(&mut self.stack[namespace_id.index()].0);
+        let size = namespace.len() * std::mem::size_of::<Value>();
+        heap.tracker_mut().on_free(|| size);
+        self.reuse_ids.push(namespace_id);
+        namespace
+    }
+
     /// Cleans up the global namespace by dropping all values with proper ref counting.
     ///
     /// Call this before the namespaces is dropped to properly decrement reference counts
@@ -12,17 +12,20 @@ use crate::{
     builtins::{Builtins, BuiltinsFunctions},
     exception_private::{ExcType, SimpleException},
     heap::{Heap, HeapData, HeapId},
-    intern::Interns,
+    intern::{FunctionId, Interns},
     resource::{ResourceError, ResourceTracker},
     types::{
-        LongInt, NamedTuple, Path, PyTrait, Type, allocate_tuple,
+        ByteArray, Complex, Date, DateTime, Decimal, Fraction, Instance, LongInt, NamedTuple, Path, PyTrait, Time,
+        TimeDelta, TimeZone, Type, allocate_tuple,
         bytes::{Bytes, bytes_repr},
+        class::class_name,
         dict::Dict,
+        r#enum,
         list::List,
         set::{FrozenSet, Set},
         str::{Str, StringRepr, string_repr_fmt},
     },
-    value::{EitherStr, Value},
+    value::{EitherStr, Value, function_from_id, heap_tagged_id},
 };
 
 /// A Python value that can be passed to or returned from the interpreter.
@@ -36,12 +39,13 @@ use crate::{
 /// Most variants can be used both as inputs (passed to `Executor::run()`) and outputs
 /// (returned from execution). However:
 /// - `Repr` is output-only: represents values that have no direct `MontyObject` mapping
+/// - `Enum` is output-only: Monty enum classes can't be recreated from it
 /// - `Exception` can be used as input (to raise) or output (when code raises)
 ///
 /// # Hashability
 ///
-/// Only immutable variants (`None`, `Ellipsis`, `Bool`, `Int`, `Float`, `String`, `Bytes`)
-/// implement `Hash`. Attempting to hash mutable variants (`List`, `Dict`) will panic.
+/// Only immutable variants (`None`, `Ellipsis`, `Bool`, `Int`, `Float`, `Complex`, `String`, `Bytes`,
+/// `Decimal`, `Fraction` and the `datetime` types) implement `Hash`. Attempting to hash mutable variants (`List`, `Dict`) will panic.
 ///
 /// # JSON Serialization
 ///
@@ -81,10 +85,19 @@ pub enum MontyObject {
     BigInt(BigInt),
     /// Python float (64-bit IEEE 754).
     Float(f64),
+    /// Python complex number, with 64-bit real and imaginary parts.
+    Complex {
+        /// The real part.
+        real: f64,
+        /// The imaginary part.
+        imag: f64,
+    },
     /// Python string (UTF-8).
     String(String),
     /// Python bytes object.
     Bytes(Vec<u8>),
+    /// Python bytearray object (mutable bytes).
+    ByteArray(Vec<u8>),
     /// Python list (mutable sequence).
     List(Vec<Self>),
     /// Python tuple (immutable sequence).
@@ -120,10 +133,77 @@ pub enum MontyObject {
     /// Returned by the `type()` builtin and can be compared with other types.
     Type(Type),
     BuiltinFunction(BuiltinsFunctions),
+    /// A function defined in Monty code, passed to the host as a handle.
+    ///
+    /// For example, a decorator implemented as an external function receives the function
+    /// it decorates as a handle. Passing the handle back to Monty (e.g. by returning it from
+    /// the decorator) gives back the original function, as long as it still exists.
+    Function {
+        /// The function's name.
+        name: String,
+        /// The function's `id()`, which identifies it when the handle is passed back.
+        id: u64,
+    },
     /// Python `pathlib.Path` object (or technically a `PurePosixPath`).
     ///
     /// Represents a filesystem path. Can be used both as input (from host) and output.
     Path(String),
+    /// Python `datetime.date`.
+    Date {
+        /// The year, from 1 to 9999.
+        year: i32,
+        /// The month, from 1 to 12.
+        month: u8,
+        /// The day of the month, from 1.
+        day: u8,
+    },
+    /// Python `datetime.time`, naive or with a fixed UTC offset.
+    Time {
+        hour: u8,
+        minute: u8,
+        second: u8,
+        microsecond: u32,
+        /// Offset from UTC in seconds for an aware time, `None` for a naive one.
+        utc_offset: Option<i32>,
+    },
+    /// Python `datetime.datetime`, naive or with a fixed UTC offset.
+    ///
+    /// Only the offset of an aware datetime's `timezone` is kept, not its name.
+    DateTime {
+        year: i32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        microsecond: u32,
+        /// Offset from UTC in seconds for an aware datetime, `None` for a naive one.
+        utc_offset: Option<i32>,
+    },
+    /// Python `datetime.timedelta`, normalized like in Python so that only `days` may be negative.
+    TimeDelta {
+        days: i32,
+        /// Seconds, from 0 to 86399.
+        seconds: i32,
+        /// Microseconds, from 0 to 999999.
+        microseconds: i32,
+    },
+    /// Python `datetime.timezone`: a fixed offset from UTC, with an optional name.
+    TimeZone {
+        /// Offset from UTC in seconds, strictly between -24 and 24 hours.
+        utc_offset: i32,
+        /// The name returned by `tzname()`, if one was given.
+        name: Option<String>,
+    },
+    /// Python `decimal.Decimal`, as its string form, e.g. `"1.50"`, `"-Infinity"` or `"NaN"`.
+    Decimal(String),
+    /// Python `fractions.Fraction`, in lowest terms with a positive denominator.
+    Fraction {
+        /// The numerator, which carries the sign.
+        numerator: BigInt,
+        /// The denominator, which is always positive.
+        denominator: BigInt,
+    },
     /// A dataclass instance with class name, field names, attributes, method names, and mutability.
     Dataclass {
         /// The class name (e.g., "Point", "User").
@@ -139,6 +219,21 @@ pub enum MontyObject {
         /// Whether this dataclass instance is immutable.
         frozen: bool,
     },
+    /// A member of an enum class defined in Monty code, e.g. `Color.RED`.
+    ///
+    /// This is output-only and cannot be used as an input to `Executor::run()`.
+    Enum {
+        /// The enum class name (e.g., "Color").
+        class_name: String,
+        /// Identifier of the enum class, shared by all its members while the class is alive.
+        class_id: u64,
+        /// The `enum` module class the enum derives from: `Enum`, `IntEnum` or `StrEnum`.
+        base: Type,
+        /// The member name (e.g., "RED").
+        name: String,
+        /// The member value.
+        value: Box<Self>,
+    },
     /// Fallback for values that cannot be represented as other variants.
     ///
     /// Contains the `repr()` string of the original value.
@@ -187,7 +282,7 @@ impl MontyObject {
     /// Converts this `MontyObject` into an `Value`, allocating on the heap if needed.
     ///
     /// Immediate values (None, Bool, Int, Float, Ellipsis, Exception) are created directly.
-    /// Heap-allocated values (String, Bytes, List, Tuple, Dict) are allocated
+    /// Heap-allocated values (String, Bytes, ByteArray, Complex, List, Tuple, Dict) are allocated
     /// via the heap and wrapped in `Value::Ref`.
     ///
     /// # Errors
@@ -205,8 +300,10 @@ impl MontyObject {
             Self::Int(i) => Ok(Value::Int(i)),
             Self::BigInt(bi) => Ok(LongInt::new(bi).into_value(heap)?),
             Self::Float(f) => Ok(Value::Float(f)),
+            Self::Complex { real, imag } => Ok(Complex::new(real, imag).into_value(heap)?),
             Self::String(s) => Ok(Value::Ref(heap.allocate(HeapData::Str(Str::new(s)))?)),
             Self::Bytes(b) => Ok(Value::Ref(heap.allocate(HeapData::Bytes(Bytes::new(b)))?)),
+            Self::ByteArray(b) => Ok(Value::Ref(heap.allocate(HeapData::ByteArray(ByteArray::new(b)))?)),
             Self::List(items) => {
                 let values: Vec<Value> = items
                     .into_iter()
@@ -289,8 +386,32 @@ impl MontyObject {
                 Ok(Value::Ref(heap.allocate(HeapData::Dataclass(dc))?))
             }
             Self::Path(s) => Ok(Value::Ref(heap.allocate(HeapData::Path(Path::new(s)))?)),
+            Self::Decimal(s) => {
+                let decimal = Decimal::parse(&s).map_err(|_| InvalidInputError::invalid_type("invalid Decimal"))?;
+                Ok(decimal.into_value(heap)?)
+            }
+            Self::Fraction { numerator, denominator } => {
+                let fraction = Fraction::new(numerator, denominator)
+                    .ok_or(InvalidInputError::invalid_type("Fraction with zero denominator"))?;
+                Ok(fraction.into_value(heap)?)
+            }
+            object @ (Self::Date { .. }
+            | Self::Time { .. }
+            | Self::DateTime { .. }
+            | Self::TimeDelta { .. }
+            | Self::TimeZone { .. }) => {
+                let data = object
+                    .datetime_heap_data()
+                    .ok_or(InvalidInputError::invalid_type("out of range datetime"))?;
+                Ok(Value::Ref(heap.allocate(data)?))
+            }
             Self::Type(t) => Ok(Value::Builtin(Builtins::Type(t))),
             Self::BuiltinFunction(f) => Ok(Value::Builtin(Builtins::Function(f))),
+            Self::Function { id, .. } => usize::try_from(id)
+                .ok()
+                .and_then(|id| function_from_id(id, heap, interns))
+                .ok_or(InvalidInputError::invalid_type("stale function handle")),
+            Self::Enum { .. } => Err(InvalidInputError::invalid_type("Enum")),
             Self::Repr(_) => Err(InvalidInputError::invalid_type("Repr")),
             Self::Cycle(_, _) => Err(InvalidInputError::invalid_type("Cycle")),
         }
@@ -326,7 +447,7 @@ impl MontyObject {
                 if visited.contains(id) {
                     // Cycle detected - return appropriate placeholder
                     return match heap.get(*id) {
-                        HeapData::List(_) => Self::Cycle(*id, "[...]".to_owned()),
+                        HeapData::List(_) | HeapData::Deque(_) => Self::Cycle(*id, "[...]".to_owned()),
                         HeapData::Tuple(_) | HeapData::NamedTuple(_) => Self::Cycle(*id, "(...)".to_owned()),
                         HeapData::Dict(_) => Self::Cycle(*id, "{...}".to_owned()),
                         _ => Self::Cycle(*id, "...".to_owned()),
@@ -339,6 +460,7 @@ impl MontyObject {
                 let result = match heap.get(*id) {
                     HeapData::Str(s) => Self::String(s.as_str().to_owned()),
                     HeapData::Bytes(b) => Self::Bytes(b.as_slice().to_owned()),
+                    HeapData::ByteArray(b) => Self::ByteArray(b.as_slice().to_owned()),
                     HeapData::List(list) => Self::List(
                         list.as_vec()
                             .iter()
@@ -388,13 +510,26 @@ impl MontyObject {
                             .map(|obj| Self::from_value_inner(obj, heap, visited, interns))
                             .collect(),
                     ),
+                    // Deques are converted to lists, since MontyObject doesn't have a Deque variant
+                    HeapData::Deque(deque) => Self::List(
+                        deque
+                            .iter()
+                            .map(|obj| Self::from_value_inner(obj, heap, visited, interns))
+                            .collect(),
+                    ),
                     // Cells are internal closure implementation details
                     HeapData::Cell(inner) => {
                         // Show the cell's contents
                         Self::from_value_inner(inner, heap, visited, interns)
                     }
-                    HeapData::Closure(..) | HeapData::FunctionDefaults(..) => {
-                        Self::Repr(object.py_repr(heap, interns).into_owned())
+                    HeapData::Closure(func_id, ..) | HeapData::FunctionDefaults(func_id, ..) => {
+                        Self::function(*func_id, object, interns)
+                    }
+                    HeapData::DictView(view) => {
+                        // Represent dict views as a repr string, like CPython's `dict_keys([...])`
+                        let mut s = String::new();
+                        let _ = view.py_repr_fmt(&mut s, heap, visited, interns);
+                        Self::Repr(s)
                     }
                     HeapData::Range(range) => {
                         // Represent Range as a repr string since MontyObject doesn't have a Range variant
@@ -436,6 +571,10 @@ impl MontyObject {
                         Self::Repr("<iterator>".to_owned())
                     }
                     HeapData::LongInt(li) => Self::BigInt(li.inner().clone()),
+                    HeapData::Complex(c) => Self::Complex {
+                        real: c.real(),
+                        imag: c.imag(),
+                    },
                     HeapData::Module(m) => {
                         // Modules are represented as a repr string
                         Self::Repr(format!("<module '{}'>", interns.get_str(m.name())))
@@ -452,11 +591,86 @@ impl MontyObject {
                         let name = interns.get_str(func.name.name_id);
                         Self::Repr(format!("<coroutine object {name}>"))
                     }
+                    HeapData::Generator(generator) => {
+                        // Generators are represented as a repr string
+                        let func = interns.get_function(generator.func_id);
+                        let name = interns.get_str(func.name.name_id);
+                        Self::Repr(format!("<generator object {name}>"))
+                    }
                     HeapData::GatherFuture(gather) => {
                         // GatherFutures are represented as a repr string
                         Self::Repr(format!("<gather({})>", gather.item_count()))
                     }
                     HeapData::Path(path) => Self::Path(path.as_str().to_owned()),
+                    HeapData::Date(date) => Self::Date {
+                        year: date.year(),
+                        month: date.month(),
+                        day: date.day(),
+                    },
+                    HeapData::Time(time) => Self::Time {
+                        hour: time.hour(),
+                        minute: time.minute(),
+                        second: time.second(),
+                        microsecond: time.microsecond(),
+                        utc_offset: time.tzinfo().map(TimeZone::offset),
+                    },
+                    HeapData::DateTime(dt) => {
+                        let (date, time) = (dt.date(), dt.time());
+                        Self::DateTime {
+                            year: date.year(),
+                            month: date.month(),
+                            day: date.day(),
+                            hour: time.hour(),
+                            minute: time.minute(),
+                            second: time.second(),
+                            microsecond: time.microsecond(),
+                            utc_offset: time.tzinfo().map(TimeZone::offset),
+                        }
+                    }
+                    HeapData::TimeDelta(delta) => Self::TimeDelta {
+                        days: delta.days(),
+                        seconds: delta.seconds(),
+                        microseconds: delta.microseconds(),
+                    },
+                    HeapData::TimeZone(tz) => Self::TimeZone {
+                        utc_offset: tz.offset(),
+                        name: tz.name().map(ToOwned::to_owned),
+                    },
+                    HeapData::Decimal(decimal) => Self::Decimal(decimal.to_sci_string()),
+                    HeapData::Fraction(fraction) => Self::Fraction {
+                        numerator: fraction.numerator().clone(),
+                        denominator: fraction.denominator().clone(),
+                    },
+                    HeapData::Instance(inst) if r#enum::is_member(inst, heap) => {
+                        Self::enum_member(inst, heap, visited, interns)
+                    }
+                    HeapData::Instance(inst) => {
+                        // Instances are represented by their default repr, with their real id
+                        let mut s = String::new();
+                        let _ = inst.default_repr_fmt(&mut s, heap, interns, heap_tagged_id(*id));
+                        Self::Repr(s)
+                    }
+                    // Classes, bound methods, super proxies, regex, functools, random, template,
+                    // hash, UUID and decimal context objects are represented as a repr string
+                    data @ (HeapData::ClassObject(_)
+                    | HeapData::NamedTupleType(_)
+                    | HeapData::BoundMethod(_)
+                    | HeapData::SuperProxy(_)
+                    | HeapData::Partial(_)
+                    | HeapData::LruCacheWrapper(_)
+                    | HeapData::CmpToKey(_)
+                    | HeapData::Random(_)
+                    | HeapData::Template(_)
+                    | HeapData::Hash(_)
+                    | HeapData::Uuid(_)
+                    | HeapData::DecimalContext(_)
+                    | HeapData::DecimalContextManager(_)
+                    | HeapData::RePattern(_)
+                    | HeapData::ReMatch(_)) => {
+                        let mut s = String::new();
+                        let _ = data.py_repr_fmt(&mut s, heap, visited, interns);
+                        Self::Repr(s)
+                    }
                 };
 
                 // Remove from visited set after processing
@@ -466,12 +680,98 @@ impl MontyObject {
             Value::Builtin(Builtins::Type(t)) => Self::Type(*t),
             Value::Builtin(Builtins::ExcType(e)) => Self::Type(Type::Exception(*e)),
             Value::Builtin(Builtins::Function(f)) => Self::BuiltinFunction(*f),
+            Value::DefFunction(func_id) => Self::function(*func_id, object, interns),
             #[cfg(feature = "ref-count-panic")]
             Value::Dereferenced => panic!("Dereferenced found while converting to MontyObject"),
             _ => Self::Repr(object.py_repr(heap, interns).into_owned()),
         }
     }
 
+    /// Converts a `Date`, `Time`, `DateTime`, `TimeDelta` or `TimeZone` variant to the heap
+    /// data it stands for.
+    ///
+    /// Returns `None` for other variants, and for fields which are out of range.
+    fn datetime_heap_data(&self) -> Option<HeapData> {
+        let tzinfo = |utc_offset: Option<i32>| utc_offset.map(|offset| TimeZone::new(offset.into(), None)).transpose();
+        let data = match self {
+            Self::Date { year, month, day } => {
+                HeapData::Date(Date::new((*year).into(), (*month).into(), (*day).into()).ok()?)
+            }
+            Self::Time {
+                hour,
+                minute,
+                second,
+                microsecond,
+                utc_offset,
+            } => HeapData::Time(
+                Time::new(
+                    (*hour).into(),
+                    (*minute).into(),
+                    (*second).into(),
+                    (*microsecond).into(),
+                    tzinfo(*utc_offset).ok()?,
+                )
+                .ok()?,
+            ),
+            Self::DateTime {
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+                microsecond,
+                utc_offset,
+            } => HeapData::DateTime(DateTime::new(
+                Date::new((*year).into(), (*month).into(), (*day).into()).ok()?,
+                Time::new(
+                    (*hour).into(),
+                    (*minute).into(),
+                    (*second).into(),
+                    (*microsecond).into(),
+                    tzinfo(*utc_offset).ok()?,
+                )
+                .ok()?,
+            )),
+            Self::TimeDelta {
+                days,
+                seconds,
+                microseconds,
+            } => HeapData::TimeDelta(TimeDelta::new((*days).into(), (*seconds).into(), (*microseconds).into()).ok()?),
+            Self::TimeZone { utc_offset, name } => {
+                HeapData::TimeZone(TimeZone::new((*utc_offset).into(), name.clone()).ok()?)
+            }
+            _ => return None,
+        };
+        Some(data)
+    }
+
+    /// Converts an enum member.
+    fn enum_member(
+        inst: &Instance,
+        heap: &Heap<impl ResourceTracker>,
+        visited: &mut AHashSet<HeapId>,
+        interns: &Interns,
+    ) -> Self {
+        let (name, value) = r#enum::member_name_value(inst, heap, interns).expect("enum member");
+        Self::Enum {
+            class_name: interns.get_str(class_name(inst.class_id(), heap)).to_owned(),
+            class_id: inst.class_id().index() as u64,
+            base: inst.py_type(heap),
+            name: name.py_str(heap, interns).into_owned(),
+            value: Box::new(Self::from_value_inner(value, heap, visited, interns)),
+        }
+    }
+
+    /// Creates a function handle for a function defined in Monty code.
+    fn function(func_id: FunctionId, value: &Value, interns: &Interns) -> Self {
+        let func = interns.get_function(func_id);
+        Self::Function {
+            name: interns.get_str(func.name.name_id).to_owned(),
+            id: value.id() as u64,
+        }
+    }
+
     /// Returns the Python `repr()` string for this value.
     ///
     /// # Panics
@@ -500,7 +800,9 @@ impl MontyObject {
                 Ok(())
             }
             Self::String(s) => string_repr_fmt(s, f),
+            Self::Complex { real, imag } => Complex::new(*real, *imag).repr_fmt(f),
             Self::Bytes(b) => f.write_str(&bytes_repr(b)),
+            Self::ByteArray(b) => write!(f, "bytearray({})", bytes_repr(b)),
             Self::List(l) => {
                 f.write_char('[')?;
                 let mut iter = l.iter();
@@ -630,9 +932,34 @@ impl MontyObject {
                 }
                 f.write_char(')')
             }
+            Self::Enum {
+                class_name,
+                name,
+                value,
+                ..
+            } => {
+                write!(f, "<{class_name}.{name}: ")?;
+                value.repr_fmt(f)?;
+                f.write_char('>')
+            }
             Self::Path(p) => write!(f, "PosixPath('{p}')"),
+            Self::Decimal(s) => write!(f, "Decimal('{s}')"),
+            Self::Fraction { numerator, denominator } => write!(f, "Fraction({numerator}, {denominator})"),
+            Self::Date { .. }
+            | Self::Time { .. }
+            | Self::DateTime { .. }
+            | Self::TimeDelta { .. }
+            | Self::TimeZone { .. } => match self.datetime_heap_data() {
+                Some(HeapData::Date(date)) => date.repr_fmt(f),
+                Some(HeapData::Time(time)) => time.repr_fmt(f),
+                Some(HeapData::DateTime(dt)) => dt.repr_fmt(f),
+                Some(HeapData::TimeDelta(delta)) => delta.repr_fmt(f),
+                Some(HeapData::TimeZone(tz)) => tz.repr_fmt(f),
+                _ => write!(f, "<invalid {}>", self.type_name()),
+            },
             Self::Type(t) => write!(f, "<class '{t}'>"),
             Self::BuiltinFunction(func) => write!(f, "<built-in function {func}>"),
+            Self::Function { name, .. } => write!(f, "<function {name}>"),
             Self::Repr(s) => write!(f, "Repr({})", StringRepr(s)),
             Self::Cycle(_, placeholder) => f.write_str(placeholder),
         }
@@ -656,8 +983,9 @@ impl MontyObject {
             Self::Int(i) => *i != 0,
             Self::BigInt(bi) => !bi.is_zero(),
             Self::Float(f) => *f != 0.0,
+            Self::Complex { real, imag } => *real != 0.0 || *imag != 0.0,
             Self::String(s) => !s.is_empty(),
-            Self::Bytes(b) => !b.is_empty(),
+            Self::Bytes(b) | Self::ByteArray(b) => !b.is_empty(),
             Self::List(l) => !l.is_empty(),
             Self::Tuple(t) => !t.is_empty(),
             Self::NamedTuple { values, .. } => !values.is_empty(),
@@ -665,9 +993,20 @@ impl MontyObject {
             Self::Set(s) => !s.is_empty(),
             Self::FrozenSet(fs) => !fs.is_empty(),
             Self::Exception { .. } => true,
-            Self::Path(_) => true,          // Path instances are always truthy
+            Self::Path(_) => true, // Path instances are always truthy
+            Self::Decimal(s) => Decimal::parse(s).is_ok_and(|decimal| !decimal.is_zero()),
+            Self::Fraction { numerator, .. } => !numerator.is_zero(),
             Self::Dataclass { .. } => true, // Dataclass instances are always truthy
-            Self::Type(_) | Self::BuiltinFunction(_) | Self::Repr(_) | Self::Cycle(_, _) => true,
+            Self::Enum { base, value, .. } => *base == Type::Enum || value.is_truthy(),
+            Self::TimeDelta {
+                days,
+                seconds,
+                microseconds,
+            } => *days != 0 || *seconds != 0 || *microseconds != 0,
+            Self::Date { .. } | Self::Time { .. } | Self::DateTime { .. } | Self::TimeZone { .. } => true,
+            Self::Type(_) | Self::BuiltinFunction(_) | Self::Function { .. } | Self::Repr(_) | Self::Cycle(_, _) => {
+                true
+            }
         }
     }
 
@@ -682,8 +1021,10 @@ impl MontyObject {
             Self::Bool(_) => "bool",
             Self::Int(_) | Self::BigInt(_) => "int",
             Self::Float(_) => "float",
+            Self::Complex { .. } => "complex",
             Self::String(_) => "str",
             Self::Bytes(_) => "bytes",
+            Self::ByteArray(_) => "bytearray",
             Self::List(_) => "list",
             Self::Tuple(_) => "tuple",
             Self::NamedTuple { .. } => "namedtuple",
@@ -692,9 +1033,18 @@ impl MontyObject {
             Self::FrozenSet(_) => "frozenset",
             Self::Exception { .. } => "Exception",
             Self::Path(_) => "PosixPath",
+            Self::Decimal(_) => "Decimal",
+            Self::Fraction { .. } => "Fraction",
+            Self::Date { .. } => "date",
+            Self::Time { .. } => "time",
+            Self::DateTime { .. } => "datetime",
+            Self::TimeDelta { .. } => "timedelta",
+            Self::TimeZone { .. } => "timezone",
             Self::Dataclass { .. } => "dataclass",
+            Self::Enum { .. } => "enum",
             Self::Type(_) => "type",
             Self::BuiltinFunction(_) => "builtin_function_or_method",
+            Self::Function { .. } => "function",
             Self::Repr(_) => "repr",
             Self::Cycle(_, _) => "cycle",
         }
@@ -726,10 +1076,42 @@ impl Hash for MontyObject {
                 }
             }
             Self::Float(f) => f.to_bits().hash(state),
+            Self::Complex { real, imag } => {
+                real.to_bits().hash(state);
+                imag.to_bits().hash(state);
+            }
             Self::String(string) => string.hash(state),
             Self::Bytes(bytes) => bytes.hash(state),
             Self::Path(path) => path.hash(state),
+            Self::Decimal(s) => s.hash(state),
+            Self::Fraction { numerator, denominator } => (numerator, denominator).hash(state),
+            Self::Date { year, month, day } => (year, month, day).hash(state),
+            Self::Time {
+                hour,
+                minute,
+                second,
+                microsecond,
+                utc_offset,
+            } => (hour, minute, second, microsecond, utc_offset).hash(state),
+            Self::DateTime {
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+                microsecond,
+                utc_offset,
+            } => (year, month, day, hour, minute, second, microsecond, utc_offset).hash(state),
+            Self::TimeDelta {
+                days,
+                seconds,
+                microseconds,
+            } => (days, seconds, microseconds).hash(state),
+            Self::TimeZone { utc_offset, .. } => utc_offset.hash(state),
             Self::Type(t) => t.to_string().hash(state),
+            Self::Enum { class_name, name, .. } => (class_name, name).hash(state),
+            Self::Function { id, .. } => id.hash(state),
             Self::Cycle(_, _) => panic!("cycle values are not hashable"),
             _ => panic!("{} python values are not hashable", self.type_name()),
         }
@@ -748,8 +1130,19 @@ impl PartialEq for MontyObject {
             (Self::Int(a), Self::BigInt(b)) | (Self::BigInt(b), Self::Int(a)) => BigInt::from(*a) == *b,
             // Use to_bits() for float comparison to be consistent with Hash
             (Self::Float(a), Self::Float(b)) => a.to_bits() == b.to_bits(),
+            (
+                Self::Complex {
+                    real: a_real,
+                    imag: a_imag,
+                },
+                Self::Complex {
+                    real: b_real,
+                    imag: b_imag,
+                },
+            ) => a_real.to_bits() == b_real.to_bits() && a_imag.to_bits() == b_imag.to_bits(),
             (Self::String(a), Self::String(b)) => a == b,
             (Self::Bytes(a), Self::Bytes(b)) => a == b,
+            (Self::ByteArray(a), Self::ByteArray(b)) => a == b,
             (Self::List(a), Self::List(b)) => a == b,
             (Self::Tuple(a), Self::Tuple(b)) => a == b,
             (
@@ -806,7 +1199,122 @@ impl PartialEq for MontyObject {
                     && a_methods == b_methods
                     && a_frozen == b_frozen
             }
+            (
+                Self::Enum {
+                    class_name: a_class,
+                    class_id: a_class_id,
+                    name: a_name,
+                    value: a_value,
+                    ..
+                },
+                Self::Enum {
+                    class_name: b_class,
+                    class_id: b_class_id,
+                    name: b_name,
+                    value: b_value,
+                    ..
+                },
+            ) => a_class == b_class && a_class_id == b_class_id && a_name == b_name && a_value == b_value,
             (Self::Path(a), Self::Path(b)) => a == b,
+            (Self::Decimal(a), Self::Decimal(b)) => a == b,
+            (
+                Self::Fraction {
+                    numerator: a_numerator,
+                    denominator: a_denominator,
+                },
+                Self::Fraction {
+                    numerator: b_numerator,
+                    denominator: b_denominator,
+                },
+            ) => (a_numerator, a_denominator) == (b_numerator, b_denominator),
+            (
+                Self::Date {
+                    year: a_year,
+                    month: a_month,
+                    day: a_day,
+                },
+                Self::Date {
+                    year: b_year,
+                    month: b_month,
+                    day: b_day,
+                },
+            ) => (a_year, a_month, a_day) == (b_year, b_month, b_day),
+            (
+                Self::Time {
+                    hour: a_hour,
+                    minute: a_minute,
+                    second: a_second,
+                    microsecond: a_microsecond,
+                    utc_offset: a_offset,
+                },
+                Self::Time {
+                    hour: b_hour,
+                    minute: b_minute,
+                    second: b_second,
+                    microsecond: b_microsecond,
+                    utc_offset: b_offset,
+                },
+            ) => {
+                (a_hour, a_minute, a_second, a_microsecond, a_offset)
+                    == (b_hour, b_minute, b_second, b_microsecond, b_offset)
+            }
+            (
+                Self::DateTime {
+                    year: a_year,
+                    month: a_month,
+                    day: a_day,
+                    hour: a_hour,
+                    minute: a_minute,
+                    second: a_second,
+                    microsecond: a_microsecond,
+                    utc_offset: a_offset,
+                },
+                Self::DateTime {
+                    year: b_year,
+                    month: b_month,
+                    day: b_day,
+                    hour: b_hour,
+                    minute: b_minute,
+                    second: b_second,
+                    microsecond: b_microsecond,
+                    utc_offset: b_offset,
+                },
+            ) => {
+                (
+                    a_year,
+                    a_month,
+                    a_day,
+                    a_hour,
+                    a_minute,
+                    a_second,
+                    a_microsecond,
+                    a_offset,
+                ) == (
+                    b_year,
+                    b_month,
+                    b_day,
+                    b_hour,
+                    b_minute,
+                    b_second,
+                    b_microsecond,
+                    b_offset,
+                )
+            }
+            (
+                Self::TimeDelta {
+                    days: a_days,
+                    seconds: a_seconds,
+                    microseconds: a_micros,
+                },
+                Self::TimeDelta {
+                    days: b_days,
+                    seconds: b_seconds,
+                    microseconds: b_micros,
+                },
+            ) => (a_days, a_seconds, a_micros) == (b_days, b_seconds, b_micros),
+            // Timezones compare by offset, like in Python
+            (Self::TimeZone { utc_offset: a, .. }, Self::TimeZone { utc_offset: b, .. }) => a == b,
+            (Self::Function { id: a, .. }, Self::Function { id: b, .. }) => a == b,
             (Self::Repr(a), Self::Repr(b)) => a == b,
             (Self::Cycle(a, _), Self::Cycle(b, _)) => a == b,
             (Self::Type(a), Self::Type(b)) => a == b,
@@ -81,6 +81,23 @@ pub enum OsFunction {
     /// Get the entire environment as a dictionary
     #[strum(serialize = "os.environ")]
     GetEnviron,
+    /// Get the current time for `datetime.now()`, `datetime.today()` and `date.today()`
+    ///
+    /// The single argument is the `tz` passed to `datetime.now()`, or `None`. The host
+    /// returns a `datetime`: aware, or naive in local time, which Monty takes to be UTC.
+    #[strum(serialize = "datetime.now")]
+    DateTimeNow,
+    /// Get a seed for the `random` module, or for a `random.Random()` created without one
+    ///
+    /// Takes no arguments. The host returns an int: entropy for unpredictable numbers, or
+    /// a fixed value for reproducible runs.
+    #[strum(serialize = "random.seed")]
+    RandomSeed,
+    /// Get 16 random bytes for `uuid.uuid4()`
+    ///
+    /// Takes no arguments. The host returns 16 bytes, such as those of `os.urandom(16)`.
+    #[strum(serialize = "uuid.uuid4")]
+    Uuid4,
 }
 
 impl TryFrom<StaticStrings> for OsFunction {
@@ -16,10 +16,12 @@ use crate::{
     exception_private::ExcType,
     exception_public::{CodeLoc, MontyException},
     expressions::{
-        Callable, CmpOperator, Comprehension, Expr, ExprLoc, Identifier, Literal, Node, Operator, UnpackTarget,
+        Callable, CmpOperator, Comprehension, DeleteTarget, DictItem, Expr, ExprLoc, Identifier, Literal, Node,
+        Operator, Pattern, UnpackTarget,
     },
     fstring::{ConversionFlag, FStringPart, FormatSpec},
-    intern::{InternerBuilder, StringId},
+    intern::{InternerBuilder, StaticStrings, StringId},
+    types::Type,
     value::EitherStr,
 };
 
@@ -92,6 +94,8 @@ pub struct RawFunctionDef {
     pub body: Vec<ParseNode>,
     /// Whether this is an async function (`async def`).
     pub is_async: bool,
+    /// Decorator expressions in source order, applied bottom-up after the function is created.
+    pub decorators: Vec<ExprLoc>,
 }
 
 /// Type alias for parsed AST nodes (output of the parser).
@@ -107,6 +111,8 @@ pub struct Try<N> {
     pub handlers: Vec<ExceptHandler<N>>,
     pub or_else: Vec<N>,
     pub finally: Vec<N>,
+    /// Whether the handlers are `except*` clauses, which handle parts of exception groups.
+    pub is_star: bool,
 }
 
 /// A parsed exception handler (except clause).
@@ -123,6 +129,17 @@ pub struct ExceptHandler<N> {
     pub body: Vec<N>,
 }
 
+/// A parsed `case` clause of a `match` statement.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MatchCase<N> {
+    /// The pattern matched against the subject.
+    pub pattern: Pattern,
+    /// Optional guard (`case x if x > 0:`), evaluated after the pattern matched.
+    pub guard: Option<ExprLoc>,
+    /// Case body statements.
+    pub body: Vec<N>,
+}
+
 /// Result of parsing: the AST nodes and the string interner with all interned names.
 #[derive(Debug)]
 pub struct ParseResult {
@@ -204,6 +221,66 @@ impl<'a> Parser<'a> {
     /// Parses an exception handler (except clause).
     ///
     /// Handles `except:`, `except ExcType:`, and `except ExcType as name:` forms.
+    /// Checks that an `except*` clause body doesn't leave the clause with `break`, `continue` or `return`.
+    ///
+    /// `break` and `continue` are allowed in loops inside the body. Nested function and class
+    /// bodies aren't checked, since they don't run as part of the clause.
+    fn check_except_star_body(&self, body: &[Stmt], in_loop: bool) -> Result<(), ParseError> {
+        for stmt in body {
+            match stmt {
+                Stmt::Return(_) => {}
+                Stmt::Break(_) | Stmt::Continue(_) if !in_loop => {}
+                Stmt::For(ast::StmtFor { body, orelse, .. }) | Stmt::While(ast::StmtWhile { body, orelse, .. }) => {
+                    self.check_except_star_body(body, true)?;
+                    self.check_except_star_body(orelse, in_loop)?;
+                    continue;
+                }
+                Stmt::If(ast::StmtIf {
+                    body,
+                    elif_else_clauses,
+                    ..
+                }) => {
+                    self.check_except_star_body(body, in_loop)?;
+                    for clause in elif_else_clauses {
+                        self.check_except_star_body(&clause.body, in_loop)?;
+                    }
+                    continue;
+                }
+                Stmt::With(ast::StmtWith { body, .. }) => {
+                    self.check_except_star_body(body, in_loop)?;
+                    continue;
+                }
+                Stmt::Try(ast::StmtTry {
+                    body,
+                    handlers,
+                    orelse,
+                    finalbody,
+                    ..
+                }) => {
+                    for block in [body, orelse, finalbody] {
+                        self.check_except_star_body(block, in_loop)?;
+                    }
+                    for ast::ExceptHandler::ExceptHandler(handler) in handlers {
+                        self.check_except_star_body(&handler.body, in_loop)?;
+                    }
+                    continue;
+                }
+                Stmt::Match(ast::StmtMatch { cases, .. }) => {
+                    for case in cases {
+                        self.check_except_star_body(&case.body, in_loop)?;
+                    }
+                    continue;
+                }
+                _ => continue,
+            }
+            return Err(ParseError::syntax(
+                "'break', 'continue' and 'return' cannot appear in an except* block",
+                self.convert_range(stmt.range()),
+            ));
+        }
+        Ok(())
+    }
+
     fn parse_except_handler(
         &mut self,
         handler: ruff_python_ast::ExceptHandler,
@@ -218,6 +295,125 @@ impl<'a> Parser<'a> {
         Ok(ExceptHandler { exc_type, name, body })
     }
 
+    /// Parses a `case` clause of a `match` statement.
+    fn parse_match_case(&mut self, case: ast::MatchCase) -> Result<MatchCase<ParseNode>, ParseError> {
+        let pattern = self.parse_pattern(case.pattern)?;
+        let guard = match case.guard {
+            Some(guard) => Some(self.parse_expression(*guard)?),
+            None => None,
+        };
+        let body = self.parse_statements(case.body)?;
+        Ok(MatchCase { pattern, guard, body })
+    }
+
+    /// Parses a `match` pattern, recursing into sub-patterns.
+    fn parse_pattern(&mut self, pattern: ast::Pattern) -> Result<Pattern, ParseError> {
+        self.decr_depth_remaining(|| pattern.range())?;
+        let result = self.parse_pattern_impl(pattern);
+        self.depth_remaining += 1;
+        result
+    }
+
+    fn parse_pattern_impl(&mut self, pattern: ast::Pattern) -> Result<Pattern, ParseError> {
+        match pattern {
+            ast::Pattern::MatchValue(ast::PatternMatchValue { value, .. }) => {
+                Ok(Pattern::Value(self.parse_expression(*value)?))
+            }
+            ast::Pattern::MatchSingleton(ast::PatternMatchSingleton { value, range, .. }) => {
+                let literal = match value {
+                    ast::Singleton::None => Literal::None,
+                    ast::Singleton::True => Literal::Bool(true),
+                    ast::Singleton::False => Literal::Bool(false),
+                };
+                Ok(Pattern::Singleton(ExprLoc::new(
+                    self.convert_range(range),
+                    Expr::Literal(literal),
+                )))
+            }
+            ast::Pattern::MatchSequence(ast::PatternMatchSequence { patterns, range, .. }) => {
+                let position = self.convert_range(range);
+                let patterns = self.parse_patterns(patterns)?;
+                if patterns.iter().filter(|p| matches!(p, Pattern::Star { .. })).count() > 1 {
+                    return Err(ParseError::syntax(
+                        "multiple starred names in sequence pattern",
+                        position,
+                    ));
+                }
+                Ok(Pattern::Sequence { patterns, position })
+            }
+            ast::Pattern::MatchMapping(ast::PatternMatchMapping {
+                keys,
+                patterns,
+                rest,
+                range,
+                ..
+            }) => {
+                let keys = keys
+                    .into_iter()
+                    .map(|key| self.parse_expression(key))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let patterns = self.parse_patterns(patterns)?;
+                let rest = rest.map(|rest| self.identifier(&rest.id, rest.range));
+                Ok(Pattern::Mapping {
+                    keys,
+                    patterns,
+                    rest,
+                    position: self.convert_range(range),
+                })
+            }
+            ast::Pattern::MatchClass(ast::PatternMatchClass {
+                cls, arguments, range, ..
+            }) => {
+                let cls = self.parse_expression(*cls)?;
+                let patterns = self.parse_patterns(arguments.patterns)?;
+                let mut kwd_attrs = Vec::with_capacity(arguments.keywords.len());
+                let mut kwd_patterns = Vec::with_capacity(arguments.keywords.len());
+                for keyword in arguments.keywords {
+                    kwd_attrs.push(self.interner.intern(&keyword.attr.id));
+                    kwd_patterns.push(self.parse_pattern(keyword.pattern)?);
+                }
+                Ok(Pattern::Class {
+                    cls,
+                    patterns,
+                    kwd_attrs,
+                    kwd_patterns,
+                    position: self.convert_range(range),
+                })
+            }
+            ast::Pattern::MatchStar(ast::PatternMatchStar { name, range, .. }) => Ok(Pattern::Star {
+                name: self.capture_name(name),
+                position: self.convert_range(range),
+            }),
+            ast::Pattern::MatchAs(ast::PatternMatchAs {
+                pattern, name, range, ..
+            }) => {
+                let pattern = match pattern {
+                    Some(pattern) => Some(Box::new(self.parse_pattern(*pattern)?)),
+                    None => None,
+                };
+                Ok(Pattern::As {
+                    pattern,
+                    name: self.capture_name(name),
+                    position: self.convert_range(range),
+                })
+            }
+            ast::Pattern::MatchOr(ast::PatternMatchOr { patterns, range, .. }) => Ok(Pattern::Or {
+                patterns: self.parse_patterns(patterns)?,
+                position: self.convert_range(range),
+            }),
+        }
+    }
+
+    fn parse_patterns(&mut self, patterns: Vec<ast::Pattern>) -> Result<Vec<Pattern>, ParseError> {
+        patterns.into_iter().map(|p| self.parse_pattern(p)).collect()
+    }
+
+    /// Converts the name of a capture or star pattern; `_` is the wildcard and binds nothing.
+    fn capture_name(&mut self, name: Option<ast::Identifier>) -> Option<Identifier> {
+        name.filter(|name| name.id.as_str() != "_")
+            .map(|name| self.identifier(&name.id, name.range))
+    }
+
     fn parse_statement(&mut self, statement: Stmt) -> Result<ParseNode, ParseError> {
         self.decr_depth_remaining(|| statement.range())?;
         let result = self.parse_statement_impl(statement);
@@ -254,6 +450,7 @@ impl<'a> Parser<'a> {
                 };
 
                 let name = self.identifier(&function.name.id, function.name.range);
+                let decorators = self.parse_decorators(function.decorator_list)?;
                 // Parse function body recursively
                 let body = self.parse_statements(function.body)?;
                 let is_async = function.is_async;
@@ -263,20 +460,59 @@ impl<'a> Parser<'a> {
                     signature,
                     body,
                     is_async,
+                    decorators,
                 }))
             }
-            Stmt::ClassDef(c) => Err(ParseError::not_implemented(
-                "class definitions",
-                self.convert_range(c.range),
-            )),
+            Stmt::ClassDef(class) => {
+                let position = self.convert_range(class.range);
+                if class.type_params.is_some() {
+                    return Err(ParseError::not_implemented("generic class type parameters", position));
+                }
+
+                // Only a single positional base class is supported (no keywords such as `metaclass=`)
+                let base = match class.arguments {
+                    Some(arguments) => {
+                        let ast::Arguments { args, keywords, .. } = *arguments;
+                        if !keywords.is_empty() {
+                            return Err(ParseError::not_implemented("class keyword arguments", position));
+                        }
+                        let mut args = args.into_vec();
+                        if args.len() > 1 {
+                            return Err(ParseError::not_implemented("multiple inheritance", position));
+                        }
+                        args.pop().map(|base| self.parse_expression(base)).transpose()?
+                    }
+                    None => None,
+                };
+
+                let name = self.identifier(&class.name.id, class.name.range);
+                let decorators = self.parse_decorators(class.decorator_list)?;
+                let body = self.parse_statements(class.body)?;
+
+                // The class body is modelled as a zero-argument function whose locals become the
+                // class namespace when it is executed.
+                Ok(Node::ClassDef {
+                    body: RawFunctionDef {
+                        name,
+                        signature: ParsedSignature::default(),
+                        body,
+                        is_async: false,
+                        decorators,
+                    },
+                    base,
+                })
+            }
             Stmt::Return(ast::StmtReturn { value, .. }) => match value {
                 Some(value) => Ok(Node::Return(self.parse_expression(*value)?)),
                 None => Ok(Node::ReturnNone),
             },
-            Stmt::Delete(d) => Err(ParseError::not_implemented(
-                "the 'del' statement",
-                self.convert_range(d.range),
-            )),
+            Stmt::Delete(ast::StmtDelete { targets, .. }) => {
+                let mut delete_targets = Vec::with_capacity(targets.len());
+                for target in targets {
+                    self.parse_delete_target(target, &mut delete_targets)?;
+                }
+                Ok(Node::Delete(delete_targets))
+            }
             Stmt::TypeAlias(t) => Err(ParseError::not_implemented("type aliases", self.convert_range(t.range))),
             Stmt::Assign(ast::StmtAssign {
                 targets, value, range, ..
@@ -328,30 +564,48 @@ impl<'a> Parser<'a> {
                 let or_else = self.parse_elif_else_clauses(elif_else_clauses)?;
                 Ok(Node::If { test, body, or_else })
             }
-            Stmt::With(ast::StmtWith { is_async, range, .. }) => {
-                if is_async {
-                    Err(ParseError::not_implemented(
-                        "async context managers (async with)",
-                        self.convert_range(range),
-                    ))
-                } else {
-                    Err(ParseError::not_implemented(
-                        "context managers (with statements)",
-                        self.convert_range(range),
-                    ))
+            Stmt::With(ast::StmtWith {
+                is_async, items, body, ..
+            }) => {
+                // `with a, b: body` is equivalent to `with a: with b: body`, so build the
+                // nested nodes from the innermost item outwards
+                let mut body = self.parse_statements(body)?;
+                for item in items.into_iter().rev() {
+                    let context = self.parse_expression(item.context_expr)?;
+                    let target = match item.optional_vars {
+                        Some(target) => Some(self.parse_unpack_target(*target)?),
+                        None => None,
+                    };
+                    // The manager's slot is allocated during prepare; its name is never looked up
+                    let manager = Identifier::new(StaticStrings::DunderEnter.into(), context.position);
+                    body = vec![Node::With {
+                        context,
+                        target,
+                        manager,
+                        body,
+                        is_async,
+                    }];
                 }
+                Ok(body.pop().expect("with statement has at least one item"))
             }
-            Stmt::Match(m) => Err(ParseError::not_implemented(
-                "pattern matching (match statements)",
-                self.convert_range(m.range),
-            )),
-            Stmt::Raise(ast::StmtRaise { exc, .. }) => {
-                // TODO add cause to Node::Raise
-                let expr = match exc {
+            Stmt::Match(ast::StmtMatch { subject, cases, .. }) => {
+                let subject = self.parse_expression(*subject)?;
+                let cases = cases
+                    .into_iter()
+                    .map(|case| self.parse_match_case(case))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Node::Match { subject, cases })
+            }
+            Stmt::Raise(ast::StmtRaise { exc, cause, .. }) => {
+                let exc = match exc {
                     Some(expr) => Some(self.parse_expression(*expr)?),
                     None => None,
                 };
-                Ok(Node::Raise(expr))
+                let cause = match cause {
+                    Some(expr) => Some(self.parse_expression(*expr)?),
+                    None => None,
+                };
+                Ok(Node::Raise { exc, cause })
             }
             Stmt::Try(ast::StmtTry {
                 body,
@@ -359,29 +613,27 @@ impl<'a> Parser<'a> {
                 orelse,
                 finalbody,
                 is_star,
-                range,
                 ..
             }) => {
                 if is_star {
-                    Err(ParseError::not_implemented(
-                        "exception groups (try*/except*)",
-                        self.convert_range(range),
-                    ))
-                } else {
-                    let body = self.parse_statements(body)?;
-                    let handlers = handlers
-                        .into_iter()
-                        .map(|h| self.parse_except_handler(h))
-                        .collect::<Result<Vec<_>, _>>()?;
-                    let or_else = self.parse_statements(orelse)?;
-                    let finally = self.parse_statements(finalbody)?;
-                    Ok(Node::Try(Try {
-                        body,
-                        handlers,
-                        or_else,
-                        finally,
-                    }))
+                    for ast::ExceptHandler::ExceptHandler(handler) in &handlers {
+                        self.check_except_star_body(&handler.body, false)?;
+                    }
                 }
+                let body = self.parse_statements(body)?;
+                let handlers = handlers
+                    .into_iter()
+                    .map(|h| self.parse_except_handler(h))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let or_else = self.parse_statements(orelse)?;
+                let finally = self.parse_statements(finalbody)?;
+                Ok(Node::Try(Try {
+                    body,
+                    handlers,
+                    or_else,
+                    finally,
+                    is_star,
+                }))
             }
             Stmt::Assert(ast::StmtAssert { test, msg, .. }) => {
                 let test = self.parse_expression(*test)?;
@@ -522,6 +774,7 @@ impl<'a> Parser<'a> {
                     .into_iter()
                     .map(|e| self.parse_unpack_target(e)) // Use parse_unpack_target for recursion
                     .collect::<Result<Vec<_>, _>>()?;
+                check_starred_targets(&targets, targets_position)?;
                 Ok(Node::UnpackAssign {
                     targets,
                     targets_position,
@@ -535,6 +788,7 @@ impl<'a> Parser<'a> {
                     .into_iter()
                     .map(|e| self.parse_unpack_target(e))
                     .collect::<Result<Vec<_>, _>>()?;
+                check_starred_targets(&targets, targets_position)?;
                 Ok(Node::UnpackAssign {
                     targets,
                     targets_position,
@@ -700,25 +954,22 @@ impl<'a> Parser<'a> {
             )),
             AstExpr::Dict(ast::ExprDict { items, range, .. }) => {
                 let position = self.convert_range(range);
-                let mut pairs = Vec::new();
+                let mut dict_items = Vec::new();
                 for ast::DictItem { key, value } in items {
-                    // key is Option<Expr> - None represents ** unpacking which we don't support yet
+                    // key is Option<Expr> - None represents ** unpacking
                     if let Some(key_expr_ast) = key {
                         let key_expr = self.parse_expression(key_expr_ast)?;
                         let value_expr = self.parse_expression(value)?;
-                        pairs.push((key_expr, value_expr));
+                        dict_items.push(DictItem::Pair(key_expr, value_expr));
                     } else {
-                        return Err(ParseError::not_implemented(
-                            "dictionary unpacking in literals",
-                            position,
-                        ));
+                        dict_items.push(DictItem::Unpack(self.parse_expression(value)?));
                     }
                 }
-                Ok(ExprLoc::new(position, Expr::Dict(pairs)))
+                Ok(ExprLoc::new(position, Expr::Dict(dict_items)))
             }
             AstExpr::Set(ast::ExprSet { elts, range, .. }) => {
-                let elements: Result<Vec<_>, _> = elts.into_iter().map(|e| self.parse_expression(e)).collect();
-                Ok(ExprLoc::new(self.convert_range(range), Expr::Set(elements?)))
+                let elements = self.parse_display_elements(elts)?;
+                Ok(ExprLoc::new(self.convert_range(range), Expr::Set(elements)))
             }
             AstExpr::ListComp(ast::ExprListComp {
                 elt, generators, range, ..
@@ -758,28 +1009,40 @@ impl<'a> Parser<'a> {
             AstExpr::Generator(ast::ExprGenerator {
                 elt, generators, range, ..
             }) => {
-                // TODO: When proper generators are implemented, this should produce
-                // Expr::Generator instead of Expr::ListComp. Currently we treat generator
-                // expressions as list comprehensions since we don't have generator support.
+                // Names for the implicit generator function, interned here since the
+                // prepare phase can't add new strings
+                let name_id = self.interner.intern("<genexpr>");
+                let param_id = self.interner.intern(".0");
                 let elt = Box::new(self.parse_expression(*elt)?);
                 let generators = self.parse_comprehension_generators(generators)?;
                 Ok(ExprLoc::new(
                     self.convert_range(range),
-                    Expr::ListComp { elt, generators },
+                    Expr::GeneratorRaw {
+                        name_id,
+                        param_id,
+                        elt,
+                        generators,
+                    },
                 ))
             }
             AstExpr::Await(a) => {
                 let value = self.parse_expression(*a.value)?;
                 Ok(ExprLoc::new(self.convert_range(a.range), Expr::Await(Box::new(value))))
             }
-            AstExpr::Yield(y) => Err(ParseError::not_implemented(
-                "yield expressions",
-                self.convert_range(y.range),
-            )),
-            AstExpr::YieldFrom(y) => Err(ParseError::not_implemented(
-                "yield from expressions",
-                self.convert_range(y.range),
-            )),
+            AstExpr::Yield(y) => {
+                let value = match y.value {
+                    Some(value) => Some(Box::new(self.parse_expression(*value)?)),
+                    None => None,
+                };
+                Ok(ExprLoc::new(self.convert_range(y.range), Expr::Yield(value)))
+            }
+            AstExpr::YieldFrom(y) => {
+                let value = self.parse_expression(*y.value)?;
+                Ok(ExprLoc::new(
+                    self.convert_range(y.range),
+                    Expr::YieldFrom(Box::new(value)),
+                ))
+            }
             AstExpr::Compare(ast::ExprCompare {
                 left,
                 ops,
@@ -918,7 +1181,21 @@ impl<'a> Parser<'a> {
                         }
                     }
                     Number::Float(f) => Literal::Float(f),
-                    Number::Complex { .. } => return Err(ParseError::not_implemented("complex constants", position)),
+                    Number::Complex { real, imag } => {
+                        // Imaginary literals like `2j` become a call to `complex(real, imag)`,
+                        // since complex numbers live on the heap and can't be constants
+                        let args = ArgExprs::Two(
+                            ExprLoc::new(position, Expr::Literal(Literal::Float(real))),
+                            ExprLoc::new(position, Expr::Literal(Literal::Float(imag))),
+                        );
+                        return Ok(ExprLoc::new(
+                            position,
+                            Expr::Call {
+                                callable: Callable::Builtin(Builtins::Type(Type::Complex)),
+                                args: Box::new(args),
+                            },
+                        ));
+                    }
                 };
                 Ok(ExprLoc::new(position, Expr::Literal(const_value)))
             }
@@ -954,8 +1231,8 @@ impl<'a> Parser<'a> {
                     Expr::Subscript { object, index },
                 ))
             }
-            AstExpr::Starred(s) => Err(ParseError::not_implemented(
-                "starred expressions (*expr)",
+            AstExpr::Starred(s) => Err(ParseError::syntax(
+                "can't use starred expression here",
                 self.convert_range(s.range),
             )),
             AstExpr::Name(ast::ExprName { id, range, .. }) => {
@@ -970,19 +1247,11 @@ impl<'a> Parser<'a> {
                 Ok(ExprLoc::new(position, expr))
             }
             AstExpr::List(ast::ExprList { elts, range, .. }) => {
-                let items = elts
-                    .into_iter()
-                    .map(|f| self.parse_expression(f))
-                    .collect::<Result<_, ParseError>>()?;
-
+                let items = self.parse_display_elements(elts)?;
                 Ok(ExprLoc::new(self.convert_range(range), Expr::List(items)))
             }
             AstExpr::Tuple(ast::ExprTuple { elts, range, .. }) => {
-                let items = elts
-                    .into_iter()
-                    .map(|f| self.parse_expression(f))
-                    .collect::<Result<_, ParseError>>()?;
-
+                let items = self.parse_display_elements(elts)?;
                 Ok(ExprLoc::new(self.convert_range(range), Expr::Tuple(items)))
             }
             AstExpr::Slice(ast::ExprSlice {
@@ -1083,6 +1352,62 @@ impl<'a> Parser<'a> {
     ///
     /// Handles patterns like `a` (single variable), `a, b` (flat tuple), or `(a, b), c` (nested).
     /// Includes depth tracking to prevent stack overflow from deeply nested structures.
+    /// Parses a `del` target, flattening tuple and list targets into `targets`.
+    fn parse_delete_target(&mut self, ast: AstExpr, targets: &mut Vec<DeleteTarget>) -> Result<(), ParseError> {
+        self.decr_depth_remaining(|| ast.range())?;
+        let result = self.parse_delete_target_impl(ast, targets);
+        self.depth_remaining += 1;
+        result
+    }
+
+    fn parse_delete_target_impl(&mut self, ast: AstExpr, targets: &mut Vec<DeleteTarget>) -> Result<(), ParseError> {
+        match ast {
+            AstExpr::Name(ast::ExprName { id, range, .. }) => {
+                targets.push(DeleteTarget::Name(self.identifier(&id, range)));
+            }
+            AstExpr::Subscript(ast::ExprSubscript {
+                value, slice, range, ..
+            }) => targets.push(DeleteTarget::Subscript {
+                object: self.parse_expression(*value)?,
+                index: self.parse_expression(*slice)?,
+                position: self.convert_range(range),
+            }),
+            AstExpr::Attribute(ast::ExprAttribute { value, attr, range, .. }) => {
+                targets.push(DeleteTarget::Attr {
+                    object: self.parse_expression(*value)?,
+                    attr: EitherStr::Interned(self.interner.intern(attr.id())),
+                    position: self.convert_range(range),
+                });
+            }
+            AstExpr::Tuple(ast::ExprTuple { elts, .. }) | AstExpr::List(ast::ExprList { elts, .. }) => {
+                for elt in elts {
+                    self.parse_delete_target(elt, targets)?;
+                }
+            }
+            other => {
+                return Err(ParseError::syntax(
+                    "cannot delete expression",
+                    self.convert_range(other.range()),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the elements of a list, tuple or set display, where starred items like `*xs` are allowed.
+    fn parse_display_elements(&mut self, elts: Vec<AstExpr>) -> Result<Vec<ExprLoc>, ParseError> {
+        elts.into_iter()
+            .map(|elt| match elt {
+                AstExpr::Starred(ast::ExprStarred { value, range, .. }) => {
+                    let position = self.convert_range(range);
+                    let value = self.parse_expression(*value)?;
+                    Ok(ExprLoc::new(position, Expr::Starred(Box::new(value))))
+                }
+                other => self.parse_expression(other),
+            })
+            .collect()
+    }
+
     fn parse_unpack_target(&mut self, ast: AstExpr) -> Result<UnpackTarget, ParseError> {
         self.decr_depth_remaining(|| ast.range())?;
         let result = self.parse_unpack_target_impl(ast);
@@ -1102,14 +1427,7 @@ impl<'a> Parser<'a> {
                 if targets.is_empty() {
                     return Err(ParseError::syntax("empty tuple in unpack target", position));
                 }
-                // Validate at most one starred target
-                let starred_count = targets.iter().filter(|t| matches!(t, UnpackTarget::Starred(_))).count();
-                if starred_count > 1 {
-                    return Err(ParseError::syntax(
-                        "multiple starred expressions in assignment",
-                        position,
-                    ));
-                }
+                check_starred_targets(&targets, position)?;
                 Ok(UnpackTarget::Tuple { targets, position })
             }
             AstExpr::Starred(ast::ExprStarred { value, range, .. }) => {
@@ -1134,14 +1452,7 @@ impl<'a> Parser<'a> {
                 if targets.is_empty() {
                     return Err(ParseError::syntax("empty list in unpack target", position));
                 }
-                // Validate at most one starred target
-                let starred_count = targets.iter().filter(|t| matches!(t, UnpackTarget::Starred(_))).count();
-                if starred_count > 1 {
-                    return Err(ParseError::syntax(
-                        "multiple starred expressions in assignment",
-                        position,
-                    ));
-                }
+                check_starred_targets(&targets, position)?;
                 Ok(UnpackTarget::Tuple { targets, position })
             }
             other => Err(ParseError::syntax(
@@ -1175,6 +1486,14 @@ impl<'a> Parser<'a> {
             .collect()
     }
 
+    /// Parses the decorator expressions of a function or class definition, in source order.
+    fn parse_decorators(&mut self, decorators: Vec<ast::Decorator>) -> Result<Vec<ExprLoc>, ParseError> {
+        decorators
+            .into_iter()
+            .map(|decorator| self.parse_expression(decorator.expression))
+            .collect()
+    }
+
     /// Parses comprehension generators (the `for ... in ... if ...` clauses).
     ///
     /// Each generator represents one `for` clause with zero or more `if` filters.
@@ -1324,13 +1643,18 @@ impl<'a> Parser<'a> {
                     }
                 })
                 .collect();
-            let parsed = static_spec.parse().map_err(|spec_str| {
-                ParseError::syntax(
+            match static_spec.parse() {
+                Ok(parsed) => Ok(FormatSpec::Static(parsed)),
+                // Possibly a `strftime()` format for a date or time, checked at runtime
+                Err(_) if static_spec.contains('%') => {
+                    let string_id = self.interner.intern(&static_spec);
+                    Ok(FormatSpec::Dynamic(vec![FStringPart::Literal(string_id)]))
+                }
+                Err(spec_str) => Err(ParseError::syntax(
                     format!("Invalid format specifier '{spec_str}'"),
                     self.convert_range(spec.range),
-                )
-            })?;
-            Ok(FormatSpec::Static(parsed))
+                )),
+            }
         }
     }
 
@@ -1392,6 +1716,18 @@ fn first<T: fmt::Debug>(v: Vec<T>, position: CodeRange) -> Result<T, ParseError>
     }
 }
 
+/// Checks that an unpacking level has at most one starred target.
+fn check_starred_targets(targets: &[UnpackTarget], position: CodeRange) -> Result<(), ParseError> {
+    let starred_count = targets.iter().filter(|t| matches!(t, UnpackTarget::Starred(_))).count();
+    if starred_count > 1 {
+        return Err(ParseError::syntax(
+            "multiple starred expressions in assignment",
+            position,
+        ));
+    }
+    Ok(())
+}
+
 fn convert_op(op: AstOperator) -> Operator {
     match op {
         AstOperator::Add => Operator::Add,
@@ -1525,7 +1861,7 @@ pub enum ParseError {
 }
 
 impl ParseError {
-    fn not_implemented(msg: impl Into<Cow<'static, str>>, position: CodeRange) -> Self {
+    pub(crate) fn not_implemented(msg: impl Into<Cow<'static, str>>, position: CodeRange) -> Self {
         Self::NotImplemented {
             msg: msg.into(),
             position,
@@ -5,13 +5,16 @@ use ahash::{AHashMap, AHashSet};
 use crate::{
     args::ArgExprs,
     expressions::{
-        Callable, CmpOperator, Comprehension, Expr, ExprLoc, Identifier, Literal, NameScope, Node, Operator,
-        PreparedFunctionDef, PreparedNode, UnpackTarget,
+        Callable, CmpOperator, Comprehension, DeleteTarget, DictItem, Expr, ExprLoc, Identifier, Literal, NameScope,
+        Node, Operator, Pattern, PreparedFunctionDef, PreparedNode, UnpackTarget,
     },
     fstring::{FStringPart, FormatSpec},
     intern::{InternerBuilder, StringId},
     namespace::NamespaceId,
-    parse::{CodeRange, ExceptHandler, ParseError, ParseNode, ParseResult, ParsedSignature, RawFunctionDef, Try},
+    parse::{
+        CodeRange, ExceptHandler, MatchCase, ParseError, ParseNode, ParseResult, ParsedParam, ParsedSignature,
+        RawFunctionDef, Try,
+    },
     signature::Signature,
 };
 
@@ -92,6 +95,10 @@ struct Prepare<'i> {
     /// Whether this is the module-level scope.
     /// At module level, all variables are global and `global` keyword is a no-op.
     is_module_scope: bool,
+    /// Whether this scope is a class body.
+    /// Class bodies don't act as enclosing scopes: functions defined inside them
+    /// (methods) cannot capture class-level names as closure variables.
+    is_class_scope: bool,
     /// Names declared as `global` in this scope.
     /// These names will resolve to the global namespace instead of local.
     global_names: AHashSet<String>,
@@ -118,6 +125,15 @@ struct Prepare<'i> {
     /// that are both nonlocal and captured by nested functions), then extended as new
     /// captures are discovered during nested function preparation.
     cell_var_map: AHashMap<String, NamespaceId>,
+    /// Position of the first `yield` or `yield from` in this scope, if any.
+    ///
+    /// A function whose body contains a yield is a generator function.
+    yield_position: Option<CodeRange>,
+    /// Kind of inlined comprehension currently being prepared (e.g. "list comprehension").
+    ///
+    /// List, set and dict comprehensions run in the enclosing scope, so a yield inside
+    /// one would turn the enclosing function into a generator; CPython rejects it.
+    comprehension: Option<&'static str>,
 }
 
 impl<'i> Prepare<'i> {
@@ -144,6 +160,7 @@ impl<'i> Prepare<'i> {
             name_map,
             namespace_size,
             is_module_scope: true,
+            is_class_scope: false,
             global_names: AHashSet::new(),
             assigned_names: AHashSet::new(),
             names_assigned_in_order: AHashSet::new(),
@@ -151,6 +168,8 @@ impl<'i> Prepare<'i> {
             enclosing_locals: None,
             free_var_map: AHashMap::new(),
             cell_var_map: AHashMap::new(),
+            yield_position: None,
+            comprehension: None,
         }
     }
 
@@ -230,6 +249,7 @@ impl<'i> Prepare<'i> {
             name_map,
             namespace_size,
             is_module_scope: false,
+            is_class_scope: false,
             global_names,
             assigned_names,
             names_assigned_in_order: AHashSet::new(),
@@ -237,6 +257,8 @@ impl<'i> Prepare<'i> {
             enclosing_locals,
             free_var_map,
             cell_var_map,
+            yield_position: None,
+            comprehension: None,
         }
     }
 
@@ -259,8 +281,8 @@ impl<'i> Prepare<'i> {
                 Node::Expr(expr) => new_nodes.push(Node::Expr(self.prepare_expression(expr)?)),
                 Node::Return(expr) => new_nodes.push(Node::Return(self.prepare_expression(expr)?)),
                 Node::ReturnNone => new_nodes.push(Node::ReturnNone),
-                Node::Raise(exc) => {
-                    let expr = match exc {
+                Node::Raise { exc, cause } => {
+                    let exc = match exc {
                         Some(expr) => {
                             match expr.expr {
                                 // Handle raising an exception type constant without instantiation,
@@ -286,7 +308,11 @@ impl<'i> Prepare<'i> {
                         }
                         None => None,
                     };
-                    new_nodes.push(Node::Raise(expr));
+                    let cause = match cause {
+                        Some(cause) => Some(self.prepare_expression(cause)?),
+                        None => None,
+                    };
+                    new_nodes.push(Node::Raise { exc, cause });
                 }
                 Node::Assert { test, msg } => {
                     let test = self.prepare_expression(test)?;
@@ -362,6 +388,13 @@ impl<'i> Prepare<'i> {
                         value,
                     });
                 }
+                Node::Delete(targets) => {
+                    let targets = targets
+                        .into_iter()
+                        .map(|target| self.prepare_delete_target(target))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    new_nodes.push(Node::Delete(targets));
+                }
                 Node::For {
                     target,
                     iter,
@@ -401,9 +434,26 @@ impl<'i> Prepare<'i> {
                     signature,
                     body,
                     is_async,
+                    decorators,
                 }) => {
-                    let func_node = self.prepare_function_def(name, &signature, body, is_async)?;
-                    new_nodes.push(func_node);
+                    let func_def = self.prepare_function_def(name, &signature, body, is_async, decorators, false)?;
+                    new_nodes.push(Node::FunctionDef(func_def));
+                }
+                Node::ClassDef { body, base } => {
+                    let RawFunctionDef {
+                        name,
+                        signature,
+                        body,
+                        is_async,
+                        decorators,
+                    } = body;
+                    // Bases are evaluated in the enclosing scope before the class body runs
+                    let base = match base {
+                        Some(base) => Some(self.prepare_expression(base)?),
+                        None => None,
+                    };
+                    let body = self.prepare_function_def(name, &signature, body, is_async, decorators, true)?;
+                    new_nodes.push(Node::ClassDef { body, base });
                 }
                 Node::Global { names, position } => {
                     // At module level, `global` is a no-op since all variables are already global.
@@ -480,6 +530,7 @@ impl<'i> Prepare<'i> {
                     handlers,
                     or_else,
                     finally,
+                    is_star,
                 }) => {
                     let body = self.prepare_nodes(body)?;
                     let handlers = handlers
@@ -493,8 +544,36 @@ impl<'i> Prepare<'i> {
                         handlers,
                         or_else,
                         finally,
+                        is_star,
                     }));
                 }
+                Node::Match { subject, cases } => {
+                    let subject = self.prepare_expression(subject)?;
+                    let cases = cases
+                        .into_iter()
+                        .map(|case| self.prepare_match_case(case))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    new_nodes.push(Node::Match { subject, cases });
+                }
+                Node::With {
+                    context,
+                    target,
+                    manager,
+                    body,
+                    is_async,
+                } => {
+                    let context = self.prepare_expression(context)?;
+                    let manager = self.hidden_local(manager);
+                    let target = target.map(|target| self.prepare_unpack_target(target));
+                    let body = self.prepare_nodes(body)?;
+                    new_nodes.push(Node::With {
+                        context,
+                        target,
+                        manager,
+                        body,
+                        is_async,
+                    });
+                }
                 Node::Import { module_name, binding } => {
                     // Resolve the binding identifier to get the namespace slot
                     let (resolved_binding, _) = self.get_id(binding);
@@ -527,6 +606,37 @@ impl<'i> Prepare<'i> {
         Ok(new_nodes)
     }
 
+    /// Prepares a `del` target by resolving its name or sub-expressions.
+    fn prepare_delete_target(&mut self, target: DeleteTarget) -> Result<DeleteTarget, ParseError> {
+        Ok(match target {
+            DeleteTarget::Name(ident) => DeleteTarget::Name(self.get_id(ident).0),
+            DeleteTarget::Subscript {
+                object,
+                index,
+                position,
+            } => DeleteTarget::Subscript {
+                object: self.prepare_expression(object)?,
+                index: self.prepare_expression(index)?,
+                position,
+            },
+            DeleteTarget::Attr { object, attr, position } => DeleteTarget::Attr {
+                object: self.prepare_expression(object)?,
+                attr,
+                position,
+            },
+        })
+    }
+
+    /// Allocates a fresh local slot for a value the compiler keeps on behalf of a statement,
+    /// such as the context manager of a `with` block.
+    ///
+    /// The slot isn't registered in `name_map`, so no Python name can refer to it.
+    fn hidden_local(&mut self, ident: Identifier) -> Identifier {
+        let id = NamespaceId::new(self.namespace_size);
+        self.namespace_size += 1;
+        Identifier::new_with_scope(ident.name_id, ident.position, id, NameScope::Local)
+    }
+
     /// Prepares an exception handler by resolving names in the exception type and body.
     ///
     /// The exception variable (if present) is treated as an assigned name in the current scope.
@@ -552,6 +662,90 @@ impl<'i> Prepare<'i> {
         Ok(ExceptHandler { exc_type, name, body })
     }
 
+    /// Prepares a `case` clause by resolving names in its pattern, guard and body.
+    fn prepare_match_case(&mut self, case: MatchCase<ParseNode>) -> Result<MatchCase<PreparedNode>, ParseError> {
+        let pattern = self.prepare_pattern(case.pattern)?;
+        let guard = match case.guard {
+            Some(guard) => Some(self.prepare_expression(guard)?),
+            None => None,
+        };
+        let body = self.prepare_nodes(case.body)?;
+        Ok(MatchCase { pattern, guard, body })
+    }
+
+    /// Prepares a `match` pattern.
+    ///
+    /// Names captured by the pattern are assignments in the current scope, while value
+    /// patterns, mapping keys and class names are ordinary expressions.
+    fn prepare_pattern(&mut self, pattern: Pattern) -> Result<Pattern, ParseError> {
+        Ok(match pattern {
+            Pattern::Value(expr) => Pattern::Value(self.prepare_expression(expr)?),
+            Pattern::Singleton(expr) => Pattern::Singleton(expr),
+            Pattern::Sequence { patterns, position } => Pattern::Sequence {
+                patterns: self.prepare_patterns(patterns)?,
+                position,
+            },
+            Pattern::Mapping {
+                keys,
+                patterns,
+                rest,
+                position,
+            } => Pattern::Mapping {
+                keys: keys
+                    .into_iter()
+                    .map(|key| self.prepare_expression(key))
+                    .collect::<Result<Vec<_>, _>>()?,
+                patterns: self.prepare_patterns(patterns)?,
+                rest: rest.map(|rest| self.prepare_capture(rest)),
+                position,
+            },
+            Pattern::Class {
+                cls,
+                patterns,
+                kwd_attrs,
+                kwd_patterns,
+                position,
+            } => Pattern::Class {
+                cls: self.prepare_expression(cls)?,
+                patterns: self.prepare_patterns(patterns)?,
+                kwd_attrs,
+                kwd_patterns: self.prepare_patterns(kwd_patterns)?,
+                position,
+            },
+            Pattern::Star { name, position } => Pattern::Star {
+                name: name.map(|name| self.prepare_capture(name)),
+                position,
+            },
+            Pattern::As {
+                pattern,
+                name,
+                position,
+            } => Pattern::As {
+                pattern: match pattern {
+                    Some(pattern) => Some(Box::new(self.prepare_pattern(*pattern)?)),
+                    None => None,
+                },
+                name: name.map(|name| self.prepare_capture(name)),
+                position,
+            },
+            Pattern::Or { patterns, position } => Pattern::Or {
+                patterns: self.prepare_patterns(patterns)?,
+                position,
+            },
+        })
+    }
+
+    fn prepare_patterns(&mut self, patterns: Vec<Pattern>) -> Result<Vec<Pattern>, ParseError> {
+        patterns.into_iter().map(|p| self.prepare_pattern(p)).collect()
+    }
+
+    /// Resolves a name captured by a pattern, which is an assignment in the current scope.
+    fn prepare_capture(&mut self, ident: Identifier) -> Identifier {
+        self.names_assigned_in_order
+            .insert(self.interner.get_str(ident.name_id).to_string());
+        self.get_id(ident).0
+    }
+
     /// Prepares an expression by resolving names, transforming calls, and applying optimizations.
     ///
     /// Key transformations performed:
@@ -634,12 +828,17 @@ impl<'i> Prepare<'i> {
                 object: Box::new(self.prepare_expression(*object)?),
                 index: Box::new(self.prepare_expression(*index)?),
             },
-            Expr::Dict(pairs) => {
-                let prepared_pairs = pairs
+            Expr::Dict(items) => {
+                let prepared_items = items
                     .into_iter()
-                    .map(|(k, v)| Ok((self.prepare_expression(k)?, self.prepare_expression(v)?)))
+                    .map(|item| match item {
+                        DictItem::Pair(k, v) => {
+                            Ok(DictItem::Pair(self.prepare_expression(k)?, self.prepare_expression(v)?))
+                        }
+                        DictItem::Unpack(mapping) => Ok(DictItem::Unpack(self.prepare_expression(mapping)?)),
+                    })
                     .collect::<Result<_, ParseError>>()?;
-                Expr::Dict(prepared_pairs)
+                Expr::Dict(prepared_items)
             }
             Expr::Set(elements) => {
                 let expressions = elements
@@ -648,6 +847,7 @@ impl<'i> Prepare<'i> {
                     .collect::<Result<_, ParseError>>()?;
                 Expr::Set(expressions)
             }
+            Expr::Starred(value) => Expr::Starred(Box::new(self.prepare_expression(*value)?)),
             Expr::Not(operand) => Expr::Not(Box::new(self.prepare_expression(*operand)?)),
             Expr::UnaryMinus(operand) => Expr::UnaryMinus(Box::new(self.prepare_expression(*operand)?)),
             Expr::UnaryPlus(operand) => Expr::UnaryPlus(Box::new(self.prepare_expression(*operand)?)),
@@ -665,21 +865,27 @@ impl<'i> Prepare<'i> {
                 orelse: Box::new(self.prepare_expression(*orelse)?),
             },
             Expr::ListComp { elt, generators } => {
+                let outer = self.comprehension.replace("list comprehension");
                 let (generators, elt, _) = self.prepare_comprehension(generators, Some(*elt), None)?;
+                self.comprehension = outer;
                 Expr::ListComp {
                     elt: Box::new(elt.expect("list comp must have elt")),
                     generators,
                 }
             }
             Expr::SetComp { elt, generators } => {
+                let outer = self.comprehension.replace("set comprehension");
                 let (generators, elt, _) = self.prepare_comprehension(generators, Some(*elt), None)?;
+                self.comprehension = outer;
                 Expr::SetComp {
                     elt: Box::new(elt.expect("set comp must have elt")),
                     generators,
                 }
             }
             Expr::DictComp { key, value, generators } => {
+                let outer = self.comprehension.replace("dict comprehension");
                 let (generators, _, key_value) = self.prepare_comprehension(generators, None, Some((*key, *value)))?;
+                self.comprehension = outer;
                 let (key, value) = key_value.expect("dict comp must have key/value");
                 Expr::DictComp {
                     key: Box::new(key),
@@ -699,6 +905,25 @@ impl<'i> Prepare<'i> {
                 // Lambda should only be created during prepare, never during parsing
                 unreachable!("Expr::Lambda should not exist before prepare phase")
             }
+            Expr::GeneratorRaw {
+                name_id,
+                param_id,
+                elt,
+                generators,
+            } => {
+                return self.prepare_generator_expression(name_id, param_id, *elt, generators, position);
+            }
+            Expr::Generator { .. } => {
+                unreachable!("Expr::Generator should not exist before prepare phase")
+            }
+            Expr::Yield(value) => {
+                self.check_yield(position)?;
+                Expr::Yield(value.map(|v| self.prepare_expression(*v)).transpose()?.map(Box::new))
+            }
+            Expr::YieldFrom(value) => {
+                self.check_yield(position)?;
+                Expr::YieldFrom(Box::new(self.prepare_expression(*value)?))
+            }
             Expr::Slice { lower, upper, step } => Expr::Slice {
                 lower: lower.map(|e| self.prepare_expression(*e)).transpose()?.map(Box::new),
                 upper: upper.map(|e| self.prepare_expression(*e)).transpose()?.map(Box::new),
@@ -1046,13 +1271,26 @@ impl<'i> Prepare<'i> {
     /// When the nested function uses `nonlocal` declarations, those names must exist
     /// in an enclosing scope. The enclosing scope's variable becomes a cell_var
     /// (stored in a heap cell), and the nested function captures it as a free_var.
+    ///
+    /// # Class Bodies
+    ///
+    /// Class bodies are prepared as zero-argument functions (`is_class_body = true`).
+    /// Their locals are never cell variables, since methods cannot close over class-level names.
     fn prepare_function_def(
         &mut self,
         name: Identifier,
         parsed_sig: &ParsedSignature,
         body: Vec<ParseNode>,
         is_async: bool,
-    ) -> Result<PreparedNode, ParseError> {
+        decorators: Vec<ExprLoc>,
+        is_class_body: bool,
+    ) -> Result<PreparedFunctionDef, ParseError> {
+        // Decorators are evaluated in the enclosing scope, before the function is created
+        let decorators = decorators
+            .into_iter()
+            .map(|decorator| self.prepare_expression(decorator))
+            .collect::<Result<Vec<_>, _>>()?;
+
         // Register the function name in the current scope
         let (name, _) = self.get_id(name);
 
@@ -1060,7 +1298,10 @@ impl<'i> Prepare<'i> {
         let param_names: Vec<StringId> = parsed_sig.param_names().collect();
 
         // Pass 1: Collect scope information from the function body
-        let scope_info = collect_function_scope_info(&body, &param_names, self.interner);
+        let mut scope_info = collect_function_scope_info(&body, &param_names, self.interner);
+        if is_class_body {
+            scope_info.cell_var_names.clear();
+        }
 
         // Get the global name map to pass to the function preparer
         // At module level, use our own name_map; otherwise use the inherited global_name_map
@@ -1072,9 +1313,10 @@ impl<'i> Prepare<'i> {
 
         // Build enclosing_locals: names that are local to this scope (including params)
         // These are available for `nonlocal` declarations in nested functions
-        let enclosing_locals: AHashSet<String> = if self.is_module_scope {
+        let enclosing_locals: AHashSet<String> = if self.is_module_scope || self.is_class_scope {
             // At module level, there are no enclosing locals for nonlocal
-            // (module-level variables are accessed via `global`, not `nonlocal`)
+            // (module-level variables are accessed via `global`, not `nonlocal`).
+            // Class-level names are likewise invisible to methods.
             AHashSet::new()
         } else {
             // In a function: our params + assigned_names + existing name_map keys
@@ -1108,9 +1350,14 @@ impl<'i> Prepare<'i> {
             scope_info.cell_var_names,
             self.interner,
         );
+        inner_prepare.is_class_scope = is_class_body;
 
         // Prepare the function body
         let prepared_body = inner_prepare.prepare_nodes(body)?;
+        let is_generator = inner_prepare.yield_position.is_some();
+        if is_async && let Some(position) = inner_prepare.yield_position {
+            return Err(ParseError::not_implemented("async generators", position));
+        }
 
         // Mark variables that the inner function captures as our cell_vars
         // These are the names that appear in inner_prepare.free_var_map
@@ -1231,8 +1478,7 @@ impl<'i> Prepare<'i> {
             }
         }
 
-        // Return the prepared function definition inline in the AST
-        Ok(Node::FunctionDef(PreparedFunctionDef {
+        Ok(PreparedFunctionDef {
             name,
             signature,
             body: prepared_body,
@@ -1241,18 +1487,17 @@ impl<'i> Prepare<'i> {
             cell_var_count,
             cell_param_indices,
             default_exprs,
+            decorators,
             is_async,
-        }))
+            is_generator,
+        })
     }
 
     /// Prepares a lambda expression, converting it into a prepared function definition.
     ///
     /// Lambdas are essentially anonymous functions with an implicit return of their body
-    /// expression. This method follows the same preparation logic as `prepare_function_def`
-    /// but:
-    /// - Uses `<lambda>` as the function name (not registered in scope)
-    /// - Wraps the body expression as `Node::Return(body)`
-    /// - Returns `ExprLoc` with `Expr::Lambda` instead of `PreparedNode`
+    /// expression, so the body is wrapped as `Node::Return(body)` and prepared with
+    /// `prepare_anonymous_function`.
     fn prepare_lambda(
         &mut self,
         lambda_name_id: StringId,
@@ -1260,23 +1505,99 @@ impl<'i> Prepare<'i> {
         body: &ExprLoc,
         position: CodeRange,
     ) -> Result<ExprLoc, ParseError> {
-        // Create a synthetic <lambda> name identifier (not registered in scope)
-        let lambda_name = Identifier::new_with_scope(
-            lambda_name_id,
+        let body_nodes = vec![Node::Return(body.clone())];
+        let func_def = self.prepare_anonymous_function(lambda_name_id, parsed_sig, body_nodes, position)?;
+        Ok(ExprLoc::new(
+            position,
+            Expr::Lambda {
+                func_def: Box::new(func_def),
+            },
+        ))
+    }
+
+    /// Prepares a generator expression as a call to an anonymous generator function.
+    ///
+    /// The first iterable is evaluated in the enclosing scope and passed as the function's
+    /// only parameter (`.0`, as in CPython). The body is the comprehension loop, nested
+    /// `for`/`if` statements with a `yield elt` in the innermost position.
+    fn prepare_generator_expression(
+        &mut self,
+        name_id: StringId,
+        param_id: StringId,
+        elt: ExprLoc,
+        generators: Vec<Comprehension>,
+        position: CodeRange,
+    ) -> Result<ExprLoc, ParseError> {
+        let mut generators = generators.into_iter();
+        let first = generators
+            .next()
+            .expect("generator expression must have at least one generator");
+        let iter = self.prepare_expression(first.iter)?;
+
+        let param_iter = ExprLoc::new(iter.position, Expr::Name(Identifier::new(param_id, iter.position)));
+        let mut clauses = vec![(first.target, param_iter, first.ifs)];
+        clauses.extend(generators.map(|comp| (comp.target, comp.iter, comp.ifs)));
+
+        // Build the loop nest from the inside out
+        let mut body: Vec<ParseNode> = vec![Node::Expr(ExprLoc::new(elt.position, Expr::Yield(Some(Box::new(elt)))))];
+        for (target, iter, ifs) in clauses.into_iter().rev() {
+            for test in ifs.into_iter().rev() {
+                body = vec![Node::If {
+                    test,
+                    body,
+                    or_else: Vec::new(),
+                }];
+            }
+            body = vec![Node::For {
+                target,
+                iter,
+                body,
+                or_else: Vec::new(),
+            }];
+        }
+
+        let signature = ParsedSignature {
+            args: vec![ParsedParam {
+                name: param_id,
+                default: None,
+            }],
+            ..ParsedSignature::default()
+        };
+        let func_def = self.prepare_anonymous_function(name_id, &signature, body, position)?;
+        Ok(ExprLoc::new(
+            position,
+            Expr::Generator {
+                func_def: Box::new(func_def),
+                iter: Box::new(iter),
+            },
+        ))
+    }
+
+    /// Prepares the body of an anonymous function (a lambda or generator expression).
+    ///
+    /// Follows the same preparation logic as `prepare_function_def`, but the function
+    /// name (`<lambda>` or `<genexpr>`) is not registered in the enclosing scope.
+    fn prepare_anonymous_function(
+        &mut self,
+        name_id: StringId,
+        parsed_sig: &ParsedSignature,
+        body_nodes: Vec<ParseNode>,
+        position: CodeRange,
+    ) -> Result<PreparedFunctionDef, ParseError> {
+        // Create a synthetic name identifier (not registered in scope)
+        let name = Identifier::new_with_scope(
+            name_id,
             position,
             NamespaceId::new(0), // Placeholder, not actually used for storage
             NameScope::Local,
         );
 
-        // Wrap the body expression as a return statement for scope analysis
-        let body_as_node: ParseNode = Node::Return(body.clone());
-        let body_nodes = vec![body_as_node];
-
         // Extract param names from the parsed signature for scope analysis
         let param_names: Vec<StringId> = parsed_sig.param_names().collect();
 
-        // Pass 1: Collect scope information from the lambda body
-        // (Lambdas can't have global/nonlocal declarations, but can have nested functions)
+        // Pass 1: Collect scope information from the body
+        // (Lambdas and generator expressions can't have global/nonlocal declarations,
+        // but can have nested functions)
         let scope_info = collect_function_scope_info(&body_nodes, &param_names, self.interner);
 
         // Get the global name map to pass to the function preparer
@@ -1288,7 +1609,7 @@ impl<'i> Prepare<'i> {
 
         // Build enclosing_locals: names that are local to this scope or captured from enclosing scope.
         // This includes free_vars so that nested lambdas can capture pass-through variables.
-        let enclosing_locals: AHashSet<String> = if self.is_module_scope {
+        let enclosing_locals: AHashSet<String> = if self.is_module_scope || self.is_class_scope {
             AHashSet::new()
         } else {
             let mut locals = self.assigned_names.clone();
@@ -1309,7 +1630,7 @@ impl<'i> Prepare<'i> {
             .filter(|name| enclosing_locals.contains(name))
             .collect();
 
-        // Pass 2: Create child preparer for lambda body with scope info
+        // Pass 2: Create child preparer for the body with scope info
         let mut inner_prepare = Prepare::new_function(
             body_nodes.len(),
             &param_names,
@@ -1323,8 +1644,9 @@ impl<'i> Prepare<'i> {
             self.interner,
         );
 
-        // Prepare the lambda body
+        // Prepare the body
         let prepared_body = inner_prepare.prepare_nodes(body_nodes)?;
+        let is_generator = inner_prepare.yield_position.is_some();
 
         // Mark variables that the inner function captures as our cell_vars
         for captured_name in inner_prepare.free_var_map.keys() {
@@ -1428,9 +1750,9 @@ impl<'i> Prepare<'i> {
             }
         }
 
-        // Create the prepared function definition (lambdas are never async)
-        let func_def = PreparedFunctionDef {
-            name: lambda_name,
+        // Anonymous functions are never async
+        Ok(PreparedFunctionDef {
+            name,
             signature,
             body: prepared_body,
             namespace_size,
@@ -1438,15 +1760,23 @@ impl<'i> Prepare<'i> {
             cell_var_count,
             cell_param_indices,
             default_exprs,
+            decorators: Vec::new(),
             is_async: false,
-        };
+            is_generator,
+        })
+    }
 
-        Ok(ExprLoc::new(
-            position,
-            Expr::Lambda {
-                func_def: Box::new(func_def),
-            },
-        ))
+    /// Records a `yield` at `position`, rejecting it outside a function body or inside
+    /// an inlined comprehension.
+    fn check_yield(&mut self, position: CodeRange) -> Result<(), ParseError> {
+        if self.is_module_scope || self.is_class_scope {
+            return Err(ParseError::syntax("'yield' outside function", position));
+        }
+        if let Some(kind) = self.comprehension {
+            return Err(ParseError::syntax(format!("'yield' inside {kind}"), position));
+        }
+        self.yield_position.get_or_insert(position);
+        Ok(())
     }
 
     /// Resolves an identifier to its namespace index and scope, creating a new entry if needed.
@@ -1809,6 +2139,23 @@ fn collect_scope_info_from_node(
             // Scan value expression for walrus operators
             collect_assigned_names_from_expr(object, assigned_names, interner);
         }
+        Node::Delete(targets) => {
+            // `del x` makes `x` local, like an assignment
+            for target in targets {
+                match target {
+                    DeleteTarget::Name(ident) => {
+                        assigned_names.insert(interner.get_str(ident.name_id).to_string());
+                    }
+                    DeleteTarget::Subscript { object, index, .. } => {
+                        collect_assigned_names_from_expr(object, assigned_names, interner);
+                        collect_assigned_names_from_expr(index, assigned_names, interner);
+                    }
+                    DeleteTarget::Attr { object, .. } => {
+                        collect_assigned_names_from_expr(object, assigned_names, interner);
+                    }
+                }
+            }
+        }
         Node::SubscriptAssign { index, value, .. } => {
             // Subscript assignment doesn't create a new name, it modifies existing container
             // But scan expressions for walrus operators
@@ -1861,16 +2208,27 @@ fn collect_scope_info_from_node(
                 collect_scope_info_from_node(n, global_names, nonlocal_names, assigned_names, interner);
             }
         }
-        Node::FunctionDef(RawFunctionDef { name, .. }) => {
+        Node::FunctionDef(RawFunctionDef { name, decorators, .. }) => {
             // Function definition creates a local binding for the function name
             // But we don't recurse into the function body - that's a separate scope
             assigned_names.insert(interner.get_str(name.name_id).to_string());
+            for decorator in decorators {
+                collect_assigned_names_from_expr(decorator, assigned_names, interner);
+            }
+        }
+        Node::ClassDef { body, base } => {
+            // Class definition binds the class name; the class body is a separate scope
+            assigned_names.insert(interner.get_str(body.name.name_id).to_string());
+            for expr in body.decorators.iter().chain(base) {
+                collect_assigned_names_from_expr(expr, assigned_names, interner);
+            }
         }
         Node::Try(Try {
             body,
             handlers,
             or_else,
             finally,
+            ..
         }) => {
             // Recurse into all blocks
             for n in body {
@@ -1892,6 +2250,32 @@ fn collect_scope_info_from_node(
                 collect_scope_info_from_node(n, global_names, nonlocal_names, assigned_names, interner);
             }
         }
+        Node::Match { subject, cases } => {
+            collect_assigned_names_from_expr(subject, assigned_names, interner);
+            for case in cases {
+                // Names captured by the pattern are assigned
+                case.pattern.for_each_binding(&mut |ident| {
+                    assigned_names.insert(interner.get_str(ident.name_id).to_string());
+                });
+                if let Some(guard) = &case.guard {
+                    collect_assigned_names_from_expr(guard, assigned_names, interner);
+                }
+                for n in &case.body {
+                    collect_scope_info_from_node(n, global_names, nonlocal_names, assigned_names, interner);
+                }
+            }
+        }
+        Node::With {
+            context, target, body, ..
+        } => {
+            collect_assigned_names_from_expr(context, assigned_names, interner);
+            if let Some(target) = target {
+                collect_names_from_unpack_target(target, assigned_names, interner);
+            }
+            for n in body {
+                collect_scope_info_from_node(n, global_names, nonlocal_names, assigned_names, interner);
+            }
+        }
         // Import creates a binding for the module name (or alias)
         Node::Import { binding, .. } => {
             assigned_names.insert(interner.get_str(binding.name_id).to_string());
@@ -1906,8 +2290,10 @@ fn collect_scope_info_from_node(
         Node::Expr(expr) | Node::Return(expr) => {
             collect_assigned_names_from_expr(expr, assigned_names, interner);
         }
-        Node::Raise(Some(expr)) => {
-            collect_assigned_names_from_expr(expr, assigned_names, interner);
+        Node::Raise { exc, cause } => {
+            for expr in [exc, cause].into_iter().flatten() {
+                collect_assigned_names_from_expr(expr, assigned_names, interner);
+            }
         }
         Node::Assert { test, msg } => {
             collect_assigned_names_from_expr(test, assigned_names, interner);
@@ -1916,7 +2302,7 @@ fn collect_scope_info_from_node(
             }
         }
         // These don't create new names
-        Node::Pass | Node::ReturnNone | Node::Raise(None) | Node::Break { .. } | Node::Continue { .. } => {}
+        Node::Pass | Node::ReturnNone | Node::Break { .. } | Node::Continue { .. } => {}
     }
 }
 
@@ -1939,10 +2325,9 @@ fn collect_assigned_names_from_expr(expr: &ExprLoc, assigned_names: &mut AHashSe
                 collect_assigned_names_from_expr(item, assigned_names, interner);
             }
         }
-        Expr::Dict(pairs) => {
-            for (key, value) in pairs {
-                collect_assigned_names_from_expr(key, assigned_names, interner);
-                collect_assigned_names_from_expr(value, assigned_names, interner);
+        Expr::Dict(items) => {
+            for expr in items.iter().flat_map(DictItem::exprs) {
+                collect_assigned_names_from_expr(expr, assigned_names, interner);
             }
         }
         Expr::Op { left, right, .. } | Expr::CmpOp { left, right, .. } => {
@@ -1959,9 +2344,22 @@ fn collect_assigned_names_from_expr(expr: &ExprLoc, assigned_names: &mut AHashSe
         | Expr::UnaryMinus(operand)
         | Expr::UnaryPlus(operand)
         | Expr::UnaryInvert(operand)
-        | Expr::Await(operand) => {
+        | Expr::Starred(operand)
+        | Expr::Await(operand)
+        | Expr::YieldFrom(operand) => {
             collect_assigned_names_from_expr(operand, assigned_names, interner);
         }
+        Expr::Yield(value) => {
+            if let Some(value) = value {
+                collect_assigned_names_from_expr(value, assigned_names, interner);
+            }
+        }
+        // Only the first iterable of a generator expression is evaluated in this scope
+        Expr::GeneratorRaw { generators, .. } => {
+            if let Some(first) = generators.first() {
+                collect_assigned_names_from_expr(&first.iter, assigned_names, interner);
+            }
+        }
         Expr::Subscript { object, index } => {
             collect_assigned_names_from_expr(object, assigned_names, interner);
             collect_assigned_names_from_expr(index, assigned_names, interner);
@@ -2024,7 +2422,7 @@ fn collect_assigned_names_from_expr(expr: &ExprLoc, assigned_names: &mut AHashSe
             }
         }
         // Lambda bodies have their own scope - walrus inside them doesn't affect us
-        Expr::LambdaRaw { .. } | Expr::Lambda { .. } => {}
+        Expr::LambdaRaw { .. } | Expr::Lambda { .. } | Expr::Generator { .. } => {}
         // Leaf expressions don't contain walrus operators
         Expr::Literal(_) | Expr::Builtin(_) | Expr::Name(_) => {}
     }
@@ -2091,8 +2489,12 @@ fn collect_cell_vars_from_node(
     interner: &InternerBuilder,
 ) {
     match node {
-        Node::FunctionDef(RawFunctionDef { signature, body, .. }) => {
-            // Find what names are referenced inside this nested function
+        Node::FunctionDef(RawFunctionDef { signature, body, .. })
+        | Node::ClassDef {
+            body: RawFunctionDef { signature, body, .. },
+            ..
+        } => {
+            // Find what names are referenced inside this nested function (or class body)
             let mut referenced = AHashSet::new();
             for n in body {
                 collect_referenced_names_from_node(n, &mut referenced, interner);
@@ -2157,6 +2559,7 @@ fn collect_cell_vars_from_node(
             handlers,
             or_else,
             finally,
+            ..
         }) => {
             for n in body {
                 collect_cell_vars_from_node(n, our_locals, cell_vars, interner);
@@ -2173,6 +2576,23 @@ fn collect_cell_vars_from_node(
                 collect_cell_vars_from_node(n, our_locals, cell_vars, interner);
             }
         }
+        Node::Match { subject, cases } => {
+            collect_cell_vars_from_expr(subject, our_locals, cell_vars, interner);
+            for case in cases {
+                if let Some(guard) = &case.guard {
+                    collect_cell_vars_from_expr(guard, our_locals, cell_vars, interner);
+                }
+                for n in &case.body {
+                    collect_cell_vars_from_node(n, our_locals, cell_vars, interner);
+                }
+            }
+        }
+        Node::With { context, body, .. } => {
+            collect_cell_vars_from_expr(context, our_locals, cell_vars, interner);
+            for n in body {
+                collect_cell_vars_from_node(n, our_locals, cell_vars, interner);
+            }
+        }
         // Handle expressions that may contain lambdas
         Node::Expr(expr) | Node::Return(expr) => {
             collect_cell_vars_from_expr(expr, our_locals, cell_vars, interner);
@@ -2183,6 +2603,20 @@ fn collect_cell_vars_from_node(
         Node::OpAssign { object, .. } => {
             collect_cell_vars_from_expr(object, our_locals, cell_vars, interner);
         }
+        Node::Delete(targets) => {
+            for target in targets {
+                match target {
+                    DeleteTarget::Name(_) => {}
+                    DeleteTarget::Subscript { object, index, .. } => {
+                        collect_cell_vars_from_expr(object, our_locals, cell_vars, interner);
+                        collect_cell_vars_from_expr(index, our_locals, cell_vars, interner);
+                    }
+                    DeleteTarget::Attr { object, .. } => {
+                        collect_cell_vars_from_expr(object, our_locals, cell_vars, interner);
+                    }
+                }
+            }
+        }
         Node::SubscriptAssign { index, value, .. } => {
             collect_cell_vars_from_expr(index, our_locals, cell_vars, interner);
             collect_cell_vars_from_expr(value, our_locals, cell_vars, interner);
@@ -2259,10 +2693,9 @@ fn collect_cell_vars_from_expr(
                 collect_cell_vars_from_expr(item, our_locals, cell_vars, interner);
             }
         }
-        Expr::Dict(pairs) => {
-            for (key, value) in pairs {
-                collect_cell_vars_from_expr(key, our_locals, cell_vars, interner);
-                collect_cell_vars_from_expr(value, our_locals, cell_vars, interner);
+        Expr::Dict(items) => {
+            for expr in items.iter().flat_map(DictItem::exprs) {
+                collect_cell_vars_from_expr(expr, our_locals, cell_vars, interner);
             }
         }
         Expr::Op { left, right, .. } | Expr::CmpOp { left, right, .. } => {
@@ -2275,7 +2708,11 @@ fn collect_cell_vars_from_expr(
                 collect_cell_vars_from_expr(expr, our_locals, cell_vars, interner);
             }
         }
-        Expr::Not(operand) | Expr::UnaryMinus(operand) | Expr::UnaryPlus(operand) | Expr::UnaryInvert(operand) => {
+        Expr::Not(operand)
+        | Expr::UnaryMinus(operand)
+        | Expr::UnaryPlus(operand)
+        | Expr::UnaryInvert(operand)
+        | Expr::Starred(operand) => {
             collect_cell_vars_from_expr(operand, our_locals, cell_vars, interner);
         }
         Expr::Subscript { object, index } => {
@@ -2331,11 +2768,49 @@ fn collect_cell_vars_from_expr(
             // Only scan the value expression for cell vars
             collect_cell_vars_from_expr(value, our_locals, cell_vars, interner);
         }
-        Expr::Await(value) => {
+        Expr::Await(value) | Expr::YieldFrom(value) => {
             collect_cell_vars_from_expr(value, our_locals, cell_vars, interner);
         }
+        Expr::Yield(value) => {
+            if let Some(value) = value {
+                collect_cell_vars_from_expr(value, our_locals, cell_vars, interner);
+            }
+        }
+        Expr::GeneratorRaw { elt, generators, .. } => {
+            // The first iterable is evaluated in our scope
+            if let Some(first) = generators.first() {
+                collect_cell_vars_from_expr(&first.iter, our_locals, cell_vars, interner);
+            }
+
+            // The rest runs in the generator's own scope, capturing names from ours
+            for name in collect_generator_body_refs(elt, generators, interner) {
+                if our_locals.contains(&name) {
+                    cell_vars.insert(name);
+                }
+            }
+
+            // Check the body for nested lambdas, which can also capture the loop variables
+            let mut extended_locals = our_locals.clone();
+            for comp in generators {
+                collect_names_from_unpack_target(&comp.target, &mut extended_locals, interner);
+            }
+            collect_cell_vars_from_expr(elt, &extended_locals, cell_vars, interner);
+            for (i, comp) in generators.iter().enumerate() {
+                if i > 0 {
+                    collect_cell_vars_from_expr(&comp.iter, &extended_locals, cell_vars, interner);
+                }
+                for cond in &comp.ifs {
+                    collect_cell_vars_from_expr(cond, &extended_locals, cell_vars, interner);
+                }
+            }
+        }
         // Leaf expressions
-        Expr::Literal(_) | Expr::Builtin(_) | Expr::Name(_) | Expr::Lambda { .. } | Expr::Slice { .. } => {}
+        Expr::Literal(_)
+        | Expr::Builtin(_)
+        | Expr::Name(_)
+        | Expr::Lambda { .. }
+        | Expr::Generator { .. }
+        | Expr::Slice { .. } => {}
     }
 }
 
@@ -2397,8 +2872,11 @@ fn collect_referenced_names_from_node(node: &ParseNode, referenced: &mut AHashSe
     match node {
         Node::Expr(expr) => collect_referenced_names_from_expr(expr, referenced, interner),
         Node::Return(expr) => collect_referenced_names_from_expr(expr, referenced, interner),
-        Node::Raise(Some(expr)) => collect_referenced_names_from_expr(expr, referenced, interner),
-        Node::Raise(None) => {}
+        Node::Raise { exc, cause } => {
+            for expr in [exc, cause].into_iter().flatten() {
+                collect_referenced_names_from_expr(expr, referenced, interner);
+            }
+        }
         Node::Assert { test, msg } => {
             collect_referenced_names_from_expr(test, referenced, interner);
             if let Some(m) = msg {
@@ -2416,6 +2894,21 @@ fn collect_referenced_names_from_node(node: &ParseNode, referenced: &mut AHashSe
             referenced.insert(interner.get_str(target.name_id).to_string());
             collect_referenced_names_from_expr(object, referenced, interner);
         }
+        Node::Delete(targets) => {
+            for target in targets {
+                match target {
+                    // Deleting a name makes it local, so it doesn't refer to an enclosing scope
+                    DeleteTarget::Name(_) => {}
+                    DeleteTarget::Subscript { object, index, .. } => {
+                        collect_referenced_names_from_expr(object, referenced, interner);
+                        collect_referenced_names_from_expr(index, referenced, interner);
+                    }
+                    DeleteTarget::Attr { object, .. } => {
+                        collect_referenced_names_from_expr(object, referenced, interner);
+                    }
+                }
+            }
+        }
         Node::SubscriptAssign {
             target, index, value, ..
         } => {
@@ -2456,14 +2949,25 @@ fn collect_referenced_names_from_node(node: &ParseNode, referenced: &mut AHashSe
                 collect_referenced_names_from_node(n, referenced, interner);
             }
         }
-        Node::FunctionDef(_) => {
-            // Don't recurse into nested function bodies - they have their own scope
+        Node::FunctionDef(RawFunctionDef { decorators, .. }) => {
+            // Decorators are evaluated in this scope, but don't recurse into nested
+            // function bodies - they have their own scope
+            for decorator in decorators {
+                collect_referenced_names_from_expr(decorator, referenced, interner);
+            }
+        }
+        Node::ClassDef { body, base } => {
+            // Decorators and the base are evaluated in this scope, but the class body has its own scope
+            for expr in body.decorators.iter().chain(base) {
+                collect_referenced_names_from_expr(expr, referenced, interner);
+            }
         }
         Node::Try(Try {
             body,
             handlers,
             or_else,
             finally,
+            ..
         }) => {
             for n in body {
                 collect_referenced_names_from_node(n, referenced, interner);
@@ -2484,6 +2988,24 @@ fn collect_referenced_names_from_node(node: &ParseNode, referenced: &mut AHashSe
                 collect_referenced_names_from_node(n, referenced, interner);
             }
         }
+        Node::Match { subject, cases } => {
+            collect_referenced_names_from_expr(subject, referenced, interner);
+            for case in cases {
+                collect_referenced_names_from_pattern(&case.pattern, referenced, interner);
+                if let Some(guard) = &case.guard {
+                    collect_referenced_names_from_expr(guard, referenced, interner);
+                }
+                for n in &case.body {
+                    collect_referenced_names_from_node(n, referenced, interner);
+                }
+            }
+        }
+        Node::With { context, body, .. } => {
+            collect_referenced_names_from_expr(context, referenced, interner);
+            for n in body {
+                collect_referenced_names_from_node(n, referenced, interner);
+            }
+        }
         // Imports create bindings but don't reference names
         Node::Import { .. } | Node::ImportFrom { .. } => {}
         Node::Pass
@@ -2495,6 +3017,45 @@ fn collect_referenced_names_from_node(node: &ParseNode, referenced: &mut AHashSe
     }
 }
 
+/// Collects names read by a `match` pattern: value patterns, mapping keys and class names.
+fn collect_referenced_names_from_pattern(
+    pattern: &Pattern,
+    referenced: &mut AHashSet<String>,
+    interner: &InternerBuilder,
+) {
+    match pattern {
+        Pattern::Value(expr) => collect_referenced_names_from_expr(expr, referenced, interner),
+        Pattern::Singleton(_) | Pattern::Star { .. } | Pattern::As { pattern: None, .. } => {}
+        Pattern::Sequence { patterns, .. } | Pattern::Or { patterns, .. } => {
+            for p in patterns {
+                collect_referenced_names_from_pattern(p, referenced, interner);
+            }
+        }
+        Pattern::Mapping { keys, patterns, .. } => {
+            for key in keys {
+                collect_referenced_names_from_expr(key, referenced, interner);
+            }
+            for p in patterns {
+                collect_referenced_names_from_pattern(p, referenced, interner);
+            }
+        }
+        Pattern::Class {
+            cls,
+            patterns,
+            kwd_patterns,
+            ..
+        } => {
+            collect_referenced_names_from_expr(cls, referenced, interner);
+            for p in patterns.iter().chain(kwd_patterns) {
+                collect_referenced_names_from_pattern(p, referenced, interner);
+            }
+        }
+        Pattern::As {
+            pattern: Some(pattern), ..
+        } => collect_referenced_names_from_pattern(pattern, referenced, interner),
+    }
+}
+
 /// Collects all names referenced in an expression.
 fn collect_referenced_names_from_expr(
     expr: &crate::expressions::ExprLoc,
@@ -2513,10 +3074,9 @@ fn collect_referenced_names_from_expr(
                 collect_referenced_names_from_expr(item, referenced, interner);
             }
         }
-        Expr::Dict(pairs) => {
-            for (key, value) in pairs {
-                collect_referenced_names_from_expr(key, referenced, interner);
-                collect_referenced_names_from_expr(value, referenced, interner);
+        Expr::Dict(items) => {
+            for expr in items.iter().flat_map(DictItem::exprs) {
+                collect_referenced_names_from_expr(expr, referenced, interner);
             }
         }
         Expr::Op { left, right, .. } | Expr::CmpOp { left, right, .. } => {
@@ -2529,7 +3089,11 @@ fn collect_referenced_names_from_expr(
                 collect_referenced_names_from_expr(expr, referenced, interner);
             }
         }
-        Expr::Not(operand) | Expr::UnaryMinus(operand) | Expr::UnaryPlus(operand) | Expr::UnaryInvert(operand) => {
+        Expr::Not(operand)
+        | Expr::UnaryMinus(operand)
+        | Expr::UnaryPlus(operand)
+        | Expr::UnaryInvert(operand)
+        | Expr::Starred(operand) => {
             collect_referenced_names_from_expr(operand, referenced, interner);
         }
         Expr::FString(parts) => {
@@ -2606,9 +3170,15 @@ fn collect_referenced_names_from_expr(
                 }
             }
         }
-        Expr::Lambda { .. } => {
-            // Lambda should only exist after preparation; this function operates on raw expressions
-            unreachable!("Expr::Lambda should not exist during scope analysis")
+        Expr::GeneratorRaw { elt, generators, .. } => {
+            if let Some(first) = generators.first() {
+                collect_referenced_names_from_expr(&first.iter, referenced, interner);
+            }
+            referenced.extend(collect_generator_body_refs(elt, generators, interner));
+        }
+        Expr::Lambda { .. } | Expr::Generator { .. } => {
+            // Lambda and Generator should only exist after preparation; this function operates on raw expressions
+            unreachable!("prepared expressions should not exist during scope analysis")
         }
         Expr::Named { value, .. } => {
             // Only the value is referenced; target is being assigned, not read
@@ -2625,10 +3195,40 @@ fn collect_referenced_names_from_expr(
                 collect_referenced_names_from_expr(expr, referenced, interner);
             }
         }
-        Expr::Await(value) => {
+        Expr::Await(value) | Expr::YieldFrom(value) => {
             collect_referenced_names_from_expr(value, referenced, interner);
         }
+        Expr::Yield(value) => {
+            if let Some(value) = value {
+                collect_referenced_names_from_expr(value, referenced, interner);
+            }
+        }
+    }
+}
+
+/// Collects the names a generator expression's body references from enclosing scopes.
+///
+/// Everything except the first iterable runs in the generator's own function scope,
+/// so the first iterable is skipped and loop variables are excluded.
+fn collect_generator_body_refs(
+    elt: &ExprLoc,
+    generators: &[Comprehension],
+    interner: &InternerBuilder,
+) -> AHashSet<String> {
+    let mut comp_locals: AHashSet<String> = AHashSet::new();
+    let mut refs: AHashSet<String> = AHashSet::new();
+    for (i, comp) in generators.iter().enumerate() {
+        if i > 0 {
+            collect_referenced_names_from_expr(&comp.iter, &mut refs, interner);
+        }
+        collect_names_from_unpack_target(&comp.target, &mut comp_locals, interner);
+        for cond in &comp.ifs {
+            collect_referenced_names_from_expr(cond, &mut refs, interner);
+        }
     }
+    collect_referenced_names_from_expr(elt, &mut refs, interner);
+    refs.retain(|name| !comp_locals.contains(name));
+    refs
 }
 
 /// Collects referenced names from comprehension expressions.
@@ -0,0 +1,404 @@
+//! Regular expression engine used by the `re` module.
+//!
+//! Patterns use Python's `re` syntax and are compiled to a small instruction set which is
+//! run by a Pike VM (see `pikevm.rs`). The VM steps every live thread over the text in
+//! lockstep, so matching takes time linear in the length of the text for any pattern:
+//! there's no backtracking, and no pattern can cause catastrophic backtracking. Threads
+//! are kept in priority order, giving the same leftmost-first results as Python's
+//! backtracking engine.
+//!
+//! Features which can't be matched in linear time (backreferences, lookaround, atomic
+//! groups and possessive quantifiers) are rejected when the pattern is compiled.
+//!
+//! Positions are byte offsets into the text; callers convert to and from the character
+//! indices Python uses.
+
+use std::fmt;
+
+use crate::resource::{ResourceError, ResourceTracker};
+
+mod parse;
+mod pikevm;
+
+use parse::{Assertion, CharClass, Node};
+pub(crate) use pikevm::{Anchor, Cache};
+
+/// `re.IGNORECASE`: case-insensitive matching.
+pub(crate) const IGNORECASE: u32 = 2;
+/// `re.LOCALE`: locale dependent matching, only supported by bytes patterns in CPython.
+pub(crate) const LOCALE: u32 = 4;
+/// `re.MULTILINE`: `^` and `$` also match at the start and end of each line.
+pub(crate) const MULTILINE: u32 = 8;
+/// `re.DOTALL`: `.` also matches a newline.
+pub(crate) const DOTALL: u32 = 16;
+/// `re.UNICODE`: Unicode matching for `\w`, `\d`, `\s` and `\b`, the default for str patterns.
+pub(crate) const UNICODE: u32 = 32;
+/// `re.VERBOSE`: whitespace and `#` comments in the pattern are ignored.
+pub(crate) const VERBOSE: u32 = 64;
+/// `re.ASCII`: ASCII-only matching for `\w`, `\d`, `\s` and `\b`.
+pub(crate) const ASCII: u32 = 256;
+
+/// Maximum number of instructions in a compiled pattern.
+///
+/// Counted repetitions are compiled by repeating their body, so a short pattern like
+/// `(a{1000}){1000}` could otherwise use a lot of memory.
+const MAX_PROGRAM_SIZE: usize = 100_000;
+
+/// Maximum number of capture slots the VM may need to track across all instructions.
+const MAX_THREAD_SLOTS: usize = 1 << 20;
+
+/// A compiled regular expression.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Regex {
+    insts: Vec<Inst>,
+    /// The flags given when compiling, plus inline global flags and `UNICODE`, as in CPython.
+    flags: u32,
+    group_count: usize,
+    /// Named groups in the order they're defined, with their group numbers.
+    group_names: Vec<(String, usize)>,
+}
+
+/// A group's span as start and end byte offsets, or `None` if the group didn't match.
+pub(crate) type Spans = Vec<Option<(usize, usize)>>;
+
+/// The result of a successful search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Captures {
+    /// The span of the whole match followed by the span of each group.
+    pub spans: Spans,
+    /// The number of the group which closed last, for `Match.lastindex`.
+    pub last_group: Option<usize>,
+}
+
+impl Regex {
+    /// Compiles a pattern with the given `re` flags.
+    pub fn new(pattern: &str, flags: u32) -> Result<Self, RegexError> {
+        let parsed = parse::parse(pattern, flags)?;
+        let mut compiler = Compiler { insts: Vec::new() };
+        compiler.push(Inst::Save(0))?;
+        compiler.compile(&parsed.node)?;
+        compiler.push(Inst::Save(1))?;
+        compiler.push(Inst::Match)?;
+        let regex = Self {
+            insts: compiler.insts,
+            flags: parsed.flags,
+            group_count: parsed.group_count,
+            group_names: parsed.group_names,
+        };
+        if regex.slot_count().saturating_mul(regex.insts.len()) > MAX_THREAD_SLOTS {
+            return Err(RegexError::too_large());
+        }
+        Ok(regex)
+    }
+
+    /// Returns the effective flags of the pattern.
+    #[must_use]
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    /// Returns the number of capturing groups, not counting the whole match.
+    #[must_use]
+    pub fn group_count(&self) -> usize {
+        self.group_count
+    }
+
+    /// Returns the named groups in the order they're defined, with their group numbers.
+    #[must_use]
+    pub fn group_names(&self) -> &[(String, usize)] {
+        &self.group_names
+    }
+
+    /// Returns the number of the group with the given name.
+    #[must_use]
+    pub fn group_index(&self, name: &str) -> Option<usize> {
+        self.group_names
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, index)| *index)
+    }
+
+    /// Returns the name of the group with the given number, if it's named.
+    #[must_use]
+    pub fn group_name(&self, index: usize) -> Option<&str> {
+        self.group_names
+            .iter()
+            .find(|(_, i)| *i == index)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Returns an estimate of the memory used by the compiled pattern.
+    #[must_use]
+    pub fn estimate_size(&self) -> usize {
+        self.insts.len() * std::mem::size_of::<Inst>()
+            + self
+                .group_names
+                .iter()
+                .map(|(name, _)| name.len() + std::mem::size_of::<(String, usize)>())
+                .sum::<usize>()
+    }
+
+    /// Creates the scratch space needed to run this pattern.
+    ///
+    /// A cache can be reused for any number of searches with this pattern.
+    #[must_use]
+    pub fn cache(&self) -> Cache {
+        Cache::new(self.insts.len(), self.slot_count())
+    }
+
+    /// Searches `text` for a match starting at or after byte offset `start`.
+    ///
+    /// `anchor` restricts the match to start at `start`, and optionally to end at the end of
+    /// `text`. An empty match at `start` is skipped when `not_empty_at_start` is set, which
+    /// is how repeated searches move past an empty match.
+    ///
+    /// # Errors
+    /// Returns `ResourceError::Time` if the tracker's time limit is exceeded while matching.
+    pub fn search(
+        &self,
+        cache: &mut Cache,
+        text: &str,
+        start: usize,
+        anchor: Anchor,
+        not_empty_at_start: bool,
+        tracker: &mut impl ResourceTracker,
+    ) -> Result<Option<Captures>, ResourceError> {
+        let slots = pikevm::exec(&self.insts, cache, text, start, anchor, not_empty_at_start, tracker)?;
+        Ok(slots.map(|slots| {
+            let (last_group, slots) = slots.split_last().expect("slots include the last group");
+            let spans = slots
+                .chunks_exact(2)
+                .map(|pair| match (pair[0], pair[1]) {
+                    (Some(start), Some(end)) => Some((start, end)),
+                    _ => None,
+                })
+                .collect();
+            Captures {
+                spans,
+                last_group: *last_group,
+            }
+        }))
+    }
+
+    /// Parses a replacement template for `sub()` or `Match.expand()`.
+    ///
+    /// Handles the escapes `\n`, `\t` and friends, group references like `\1` and
+    /// `\g<name>`, and keeps unknown escapes of non-letters as they are.
+    ///
+    /// # Errors
+    /// Returns a `TemplateError` for bad escapes, references to groups which don't exist,
+    /// and malformed `\g<...>` references.
+    pub fn parse_template(&self, template: &str) -> Result<Vec<TemplatePart>, TemplateError> {
+        parse::parse_template(template, self)
+    }
+
+    /// Returns the number of capture slots: a start and end for the whole match and each
+    /// group, then the number of the last group closed.
+    fn slot_count(&self) -> usize {
+        (self.group_count + 1) * 2 + 1
+    }
+}
+
+/// A piece of a parsed replacement template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TemplatePart {
+    Literal(String),
+    /// The text matched by a group, or nothing if it didn't match.
+    Group(usize),
+}
+
+/// An error from compiling a pattern, raised as `re.error`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RegexError {
+    msg: String,
+    /// Character index into the pattern, and its line and column if the pattern has several lines.
+    position: Option<(usize, Option<(usize, usize)>)>,
+}
+
+impl RegexError {
+    /// Creates an error at the given character index of `pattern`.
+    fn new(msg: impl Into<String>, pattern: &[char], pos: usize) -> Self {
+        let line_col = pattern.contains(&'\n').then(|| {
+            let before = &pattern[..pos.min(pattern.len())];
+            let line = before.iter().filter(|c| **c == '\n').count() + 1;
+            let column = pos - before.iter().rposition(|c| *c == '\n').map_or(0, |i| i + 1) + 1;
+            (line, column)
+        });
+        Self {
+            msg: msg.into(),
+            position: Some((pos, line_col)),
+        }
+    }
+
+    fn too_large() -> Self {
+        Self {
+            msg: "pattern is too large".to_owned(),
+            position: None,
+        }
+    }
+}
+
+impl fmt::Display for RegexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.msg)?;
+        match self.position {
+            Some((pos, Some((line, column)))) => write!(f, " at position {pos} (line {line}, column {column})"),
+            Some((pos, None)) => write!(f, " at position {pos}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// An error from parsing a replacement template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TemplateError {
+    /// A malformed template, raised as `re.error`.
+    Invalid(RegexError),
+    /// A `\g<name>` reference to a group name the pattern doesn't have, raised as `IndexError`.
+    UnknownGroupName(String),
+}
+
+/// An instruction of a compiled pattern.
+///
+/// Instructions which consume a character (`Char`, `CharFold`, `Any`, `AnyAll` and `Class`)
+/// continue to the next instruction when they match, as do `Save` and passing `Assert`s.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+enum Inst {
+    Char(char),
+    /// A character matched case-insensitively, using only ASCII case rules if `ascii` is set.
+    CharFold {
+        c: char,
+        ascii: bool,
+    },
+    /// Any character except a newline.
+    Any,
+    /// Any character, for `.` with `DOTALL`.
+    AnyAll,
+    Class(CharClass),
+    Assert(Assertion),
+    /// Records the current position in a capture slot.
+    Save(usize),
+    /// Continues at both targets, preferring the first.
+    Split(usize, usize),
+    Jmp(usize),
+    Match,
+}
+
+/// Compiles a parsed pattern to instructions.
+struct Compiler {
+    insts: Vec<Inst>,
+}
+
+impl Compiler {
+    fn push(&mut self, inst: Inst) -> Result<usize, RegexError> {
+        if self.insts.len() >= MAX_PROGRAM_SIZE {
+            return Err(RegexError::too_large());
+        }
+        self.insts.push(inst);
+        Ok(self.insts.len() - 1)
+    }
+
+    /// Points the split at `index` to continue at `next` and `exit`, in priority order
+    /// according to `greedy`.
+    fn patch_split(&mut self, index: usize, next: usize, exit: usize, greedy: bool) {
+        self.insts[index] = if greedy {
+            Inst::Split(next, exit)
+        } else {
+            Inst::Split(exit, next)
+        };
+    }
+
+    fn compile(&mut self, node: &Node) -> Result<(), RegexError> {
+        match node {
+            Node::Empty => {}
+            Node::Char { c, fold: None } => {
+                self.push(Inst::Char(*c))?;
+            }
+            Node::Char { c, fold: Some(ascii) } => {
+                self.push(Inst::CharFold { c: *c, ascii: *ascii })?;
+            }
+            Node::Any { dotall } => {
+                self.push(if *dotall { Inst::AnyAll } else { Inst::Any })?;
+            }
+            Node::Class(class) => {
+                self.push(Inst::Class(class.clone()))?;
+            }
+            Node::Assert(assertion) => {
+                self.push(Inst::Assert(*assertion))?;
+            }
+            Node::Group { index, node } => {
+                self.push(Inst::Save(index * 2))?;
+                self.compile(node)?;
+                self.push(Inst::Save(index * 2 + 1))?;
+            }
+            Node::Concat(nodes) => {
+                for node in nodes {
+                    self.compile(node)?;
+                }
+            }
+            Node::Alternate(branches) => {
+                // Each branch but the last is tried before the rest: split to it or the next split
+                let mut jumps = Vec::with_capacity(branches.len());
+                for (i, branch) in branches.iter().enumerate() {
+                    if i + 1 == branches.len() {
+                        self.compile(branch)?;
+                    } else {
+                        let split = self.push(Inst::Split(0, 0))?;
+                        self.compile(branch)?;
+                        jumps.push(self.push(Inst::Jmp(0))?);
+                        let next = self.insts.len();
+                        self.patch_split(split, split + 1, next, true);
+                    }
+                }
+                let end = self.insts.len();
+                for jump in jumps {
+                    self.insts[jump] = Inst::Jmp(end);
+                }
+            }
+            Node::Repeat { node, min, max, greedy } => self.compile_repeat(node, *min, *max, *greedy)?,
+        }
+        Ok(())
+    }
+
+    fn compile_repeat(&mut self, node: &Node, min: u32, max: Option<u32>, greedy: bool) -> Result<(), RegexError> {
+        if node.is_empty() {
+            // Repeating nothing matches nothing, however many times, and the counts may be huge
+            return Ok(());
+        }
+        match max {
+            None if min > 0 => {
+                // `e{n,}` is `n - 1` copies then `e+`, which loops back over the last copy
+                for _ in 1..min {
+                    self.compile(node)?;
+                }
+                let start = self.insts.len();
+                self.compile(node)?;
+                let split = self.push(Inst::Split(0, 0))?;
+                self.patch_split(split, start, split + 1, greedy);
+            }
+            None => {
+                let split = self.push(Inst::Split(0, 0))?;
+                self.compile(node)?;
+                self.push(Inst::Jmp(split))?;
+                let exit = self.insts.len();
+                self.patch_split(split, split + 1, exit, greedy);
+            }
+            Some(max) => {
+                for _ in 0..min {
+                    self.compile(node)?;
+                }
+                // Each optional copy may skip straight past the rest
+                let mut splits = Vec::new();
+                for _ in min..max {
+                    splits.push(self.push(Inst::Split(0, 0))?);
+                    self.compile(node)?;
+                }
+                let exit = self.insts.len();
+                for split in splits {
+                    self.patch_split(split, split + 1, exit, greedy);
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,994 @@
+//! Parser for Python's regular expression syntax, and for `sub()` replacement templates.
+//!
+//! Error messages and positions match CPython's `re` module.
+
+use super::{
+    ASCII, DOTALL, IGNORECASE, LOCALE, MULTILINE, Regex, RegexError, TemplateError, TemplatePart, UNICODE, VERBOSE,
+};
+use crate::types::str::is_unicode_decimal;
+
+/// Maximum nesting of groups, which bounds recursion while parsing and compiling.
+const MAX_NESTING: usize = 200;
+
+/// Largest repeat count accepted by `{m,n}`, as in CPython.
+const MAX_REPEAT: u64 = u32::MAX as u64 - 1;
+
+/// A parsed pattern.
+#[derive(Debug)]
+pub(super) enum Node {
+    Empty,
+    /// A literal character; `fold` is set for case-insensitive matching, to whether only
+    /// ASCII case rules apply.
+    Char {
+        c: char,
+        fold: Option<bool>,
+    },
+    Any {
+        dotall: bool,
+    },
+    Class(CharClass),
+    Assert(Assertion),
+    /// A capturing group, with its group number.
+    Group {
+        index: usize,
+        node: Box<Self>,
+    },
+    Concat(Vec<Self>),
+    Alternate(Vec<Self>),
+    Repeat {
+        node: Box<Self>,
+        min: u32,
+        max: Option<u32>,
+        greedy: bool,
+    },
+}
+
+impl Node {
+    /// Checks whether the node compiles to no instructions at all.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Empty => true,
+            Self::Repeat { node, .. } => node.is_empty(),
+            _ => false,
+        }
+    }
+}
+
+/// A zero-width assertion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(super) enum Assertion {
+    /// `\A`, or `^` without `MULTILINE`.
+    StartText,
+    /// `\Z`.
+    EndText,
+    /// `$` without `MULTILINE`: the end of the text, or before a newline at the end.
+    EndTextOrFinalNewline,
+    /// `^` with `MULTILINE`.
+    StartLine,
+    /// `$` with `MULTILINE`.
+    EndLine,
+    /// `\b`, using only ASCII word characters if `ascii` is set.
+    WordBoundary { ascii: bool },
+    /// `\B`.
+    NotWordBoundary { ascii: bool },
+}
+
+impl Assertion {
+    /// Checks whether the assertion holds at byte offset `pos` of `text`.
+    pub fn holds(self, text: &str, pos: usize) -> bool {
+        let before = text[..pos].chars().next_back();
+        let after = text[pos..].chars().next();
+        match self {
+            Self::StartText => pos == 0,
+            Self::EndText => pos == text.len(),
+            Self::EndTextOrFinalNewline => pos == text.len() || (pos + 1 == text.len() && after == Some('\n')),
+            Self::StartLine => before.is_none_or(|c| c == '\n'),
+            Self::EndLine => after.is_none_or(|c| c == '\n'),
+            Self::WordBoundary { ascii } => is_word_boundary(before, after, ascii),
+            Self::NotWordBoundary { ascii } => !is_word_boundary(before, after, ascii),
+        }
+    }
+}
+
+fn is_word_boundary(before: Option<char>, after: Option<char>, ascii: bool) -> bool {
+    let is_word = |c: Option<char>| c.is_some_and(|c| PerlClass::Word.contains(c, ascii));
+    is_word(before) != is_word(after)
+}
+
+/// A character set like `[a-z_]`, or one of `\d`, `\w` and `\s`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(super) struct CharClass {
+    items: Vec<ClassItem>,
+    negated: bool,
+    /// Whether `\d`, `\w` and `\s` and case folding only use ASCII rules.
+    ascii: bool,
+    ignore_case: bool,
+}
+
+impl CharClass {
+    /// Checks whether the set matches `c`.
+    pub fn matches(&self, c: char) -> bool {
+        let found = self.contains(c)
+            || (self.ignore_case
+                && case_variants(c, self.ascii)
+                    .into_iter()
+                    .flatten()
+                    .any(|v| self.contains(v)));
+        found != self.negated
+    }
+
+    fn contains(&self, c: char) -> bool {
+        self.items.iter().any(|item| match *item {
+            ClassItem::Range(lo, hi) => lo <= c && c <= hi,
+            ClassItem::Perl { class, negated } => class.contains(c, self.ascii) != negated,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+enum ClassItem {
+    /// An inclusive range of characters, a single character being a range of one.
+    Range(char, char),
+    /// `\d`, `\w` or `\s`, or `\D`, `\W` or `\S` if `negated`.
+    Perl { class: PerlClass, negated: bool },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+enum PerlClass {
+    Digit,
+    Word,
+    Space,
+}
+
+impl PerlClass {
+    fn contains(self, c: char, ascii: bool) -> bool {
+        match (self, ascii) {
+            (Self::Digit, true) => c.is_ascii_digit(),
+            (Self::Digit, false) => is_unicode_decimal(c),
+            (Self::Word, true) => c.is_ascii_alphanumeric() || c == '_',
+            (Self::Word, false) => c.is_alphanumeric() || c == '_',
+            (Self::Space, true) => matches!(c, ' ' | '\t' | '\n' | '\r' | '\x0b' | '\x0c'),
+            // Python's `str.isspace()` also counts the information separators
+            (Self::Space, false) => c.is_whitespace() || ('\x1c'..='\x1f').contains(&c),
+        }
+    }
+}
+
+/// Returns the single-character lowercase and uppercase forms of `c`, where they exist.
+fn case_variants(c: char, ascii: bool) -> [Option<char>; 2] {
+    fn single(mut chars: impl Iterator<Item = char>) -> Option<char> {
+        let c = chars.next()?;
+        chars.next().is_none().then_some(c)
+    }
+    if ascii {
+        [Some(c.to_ascii_lowercase()), Some(c.to_ascii_uppercase())]
+    } else {
+        [single(c.to_lowercase()), single(c.to_uppercase())]
+    }
+}
+
+/// Checks whether two characters are equal ignoring case.
+pub(super) fn chars_eq_fold(a: char, b: char, ascii: bool) -> bool {
+    if a == b {
+        return true;
+    }
+    let [a_lower, a_upper] = case_variants(a, ascii);
+    let [b_lower, b_upper] = case_variants(b, ascii);
+    (a_lower.is_some() && a_lower == b_lower) || (a_upper.is_some() && a_upper == b_upper)
+}
+
+/// A parsed pattern, with the details of its groups.
+pub(super) struct Parsed {
+    pub node: Node,
+    pub flags: u32,
+    pub group_count: usize,
+    pub group_names: Vec<(String, usize)>,
+}
+
+/// Parses a pattern with the given flags.
+pub(super) fn parse(pattern: &str, flags: u32) -> Result<Parsed, RegexError> {
+    let mut parser = Parser {
+        chars: pattern.chars().collect(),
+        pos: 0,
+        group_count: 0,
+        group_names: Vec::new(),
+    };
+    let mut flags = parser.parse_global_flags(flags)?;
+    if flags & ASCII == 0 {
+        flags |= UNICODE;
+    }
+    let node = parser.parse_alternation(flags, 0)?;
+    if parser.pos < parser.chars.len() {
+        // Only an unmatched `)` stops the top level alternation early
+        return Err(parser.error("unbalanced parenthesis", parser.pos));
+    }
+    Ok(Parsed {
+        node,
+        flags,
+        group_count: parser.group_count,
+        group_names: parser.group_names,
+    })
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    group_count: usize,
+    group_names: Vec<(String, usize)>,
+}
+
+/// What an escape sequence outside a character set stands for.
+enum Escape {
+    Char(char),
+    Perl(PerlClass, bool),
+    Assert(Assertion),
+}
+
+/// One end of a range, or a whole item, in a character set.
+enum ClassAtom {
+    Char(char),
+    Perl(PerlClass, bool),
+}
+
+impl Parser {
+    fn error(&self, msg: impl Into<String>, pos: usize) -> RegexError {
+        RegexError::new(msg, &self.chars, pos)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn text(&self, start: usize, end: usize) -> String {
+        self.chars[start..end].iter().collect()
+    }
+
+    /// Parses inline flags like `(?i)` at the start of the pattern, which apply to all of it.
+    fn parse_global_flags(&mut self, mut flags: u32) -> Result<u32, RegexError> {
+        while self.peek() == Some('(') && self.peek_at(1) == Some('?') {
+            let start = self.pos;
+            self.pos += 2;
+            let Some(added) = self.parse_flag_letters()? else {
+                self.pos = start;
+                break;
+            };
+            if self.eat(')') {
+                flags |= added;
+                self.check_flags(flags)?;
+            } else {
+                // A scoped group like `(?i:...)`, parsed as part of the pattern
+                self.pos = start;
+                break;
+            }
+        }
+        Ok(flags)
+    }
+
+    /// Parses the letters of an inline flags group, after `(?`.
+    ///
+    /// Returns `None` if there are no flag letters, so this isn't a flags group.
+    fn parse_flag_letters(&mut self) -> Result<Option<u32>, RegexError> {
+        let mut added = 0;
+        while let Some(flag) = self.peek().and_then(flag_value) {
+            added |= flag;
+            self.pos += 1;
+        }
+        if added == 0 && self.peek() != Some('-') {
+            return Ok(None);
+        }
+        if added & LOCALE != 0 {
+            return Err(self.error("bad inline flags: cannot use 'L' flag with a str pattern", self.pos));
+        }
+        self.check_flags(added)?;
+        Ok(Some(added))
+    }
+
+    fn check_flags(&self, flags: u32) -> Result<(), RegexError> {
+        if flags & ASCII != 0 && flags & UNICODE != 0 {
+            return Err(self.error("bad inline flags: flags 'a', 'u' and 'L' are incompatible", self.pos));
+        }
+        Ok(())
+    }
+
+    /// Skips whitespace and `#` comments in verbose mode.
+    fn skip_verbose(&mut self, flags: u32) {
+        if flags & VERBOSE == 0 {
+            return;
+        }
+        while let Some(c) = self.peek() {
+            if c == '#' {
+                while self.peek().is_some_and(|c| c != '\n') {
+                    self.pos += 1;
+                }
+            } else if c.is_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Parses branches separated by `|`, up to a `)` or the end of the pattern.
+    fn parse_alternation(&mut self, flags: u32, depth: usize) -> Result<Node, RegexError> {
+        let mut branches = vec![self.parse_concat(flags, depth)?];
+        while self.eat('|') {
+            branches.push(self.parse_concat(flags, depth)?);
+        }
+        Ok(if branches.len() == 1 {
+            branches.pop().expect("one branch")
+        } else {
+            Node::Alternate(branches)
+        })
+    }
+
+    /// Parses a sequence of quantified atoms, up to a `|`, `)` or the end of the pattern.
+    fn parse_concat(&mut self, flags: u32, depth: usize) -> Result<Node, RegexError> {
+        let mut nodes = Vec::new();
+        loop {
+            self.skip_verbose(flags);
+            let Some(c) = self.peek() else { break };
+            if c == '|' || c == ')' {
+                break;
+            }
+            // Comments match nothing, and can't be repeated
+            if let Some(atom) = self.parse_atom(flags, depth)? {
+                nodes.push(self.parse_quantifiers(atom, flags)?);
+            }
+        }
+        Ok(match nodes.len() {
+            0 => Node::Empty,
+            1 => nodes.pop().expect("one node"),
+            _ => Node::Concat(nodes),
+        })
+    }
+
+    /// Parses any quantifier following an atom.
+    fn parse_quantifiers(&mut self, atom: Node, flags: u32) -> Result<Node, RegexError> {
+        self.skip_verbose(flags);
+        let quantifier_start = self.pos;
+        let Some((min, max)) = self.parse_quantifier()? else {
+            return Ok(atom);
+        };
+        if matches!(atom, Node::Assert(_)) {
+            return Err(self.error("nothing to repeat", quantifier_start));
+        }
+        let greedy = !self.eat('?');
+        if self.peek() == Some('+') {
+            if greedy {
+                return Err(self.error("possessive quantifiers are not supported", self.pos));
+            }
+            return Err(self.error("multiple repeat", self.pos));
+        }
+        self.skip_verbose(flags);
+        let repeat_pos = self.pos;
+        if self.parse_quantifier()?.is_some() {
+            return Err(self.error("multiple repeat", repeat_pos));
+        }
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+            greedy,
+        })
+    }
+
+    /// Parses a `*`, `+`, `?` or `{m,n}` quantifier, returning its minimum and maximum.
+    ///
+    /// A `{` which doesn't start a valid repeat is left to be parsed as a literal.
+    fn parse_quantifier(&mut self) -> Result<Option<(u32, Option<u32>)>, RegexError> {
+        let bounds = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => return self.parse_brace_repeat(),
+            _ => return Ok(None),
+        };
+        self.pos += 1;
+        Ok(Some(bounds))
+    }
+
+    fn parse_brace_repeat(&mut self) -> Result<Option<(u32, Option<u32>)>, RegexError> {
+        let start = self.pos;
+        self.pos += 1;
+        let min_start = self.pos;
+        let min = self.parse_repeat_number()?;
+        let max = if self.eat(',') {
+            self.parse_repeat_number()?
+        } else {
+            min
+        };
+        if !self.eat('}') || (self.pos == min_start + 1 && self.chars[min_start] == '}') {
+            // Not a repeat, so `{` is a literal
+            self.pos = start;
+            return Ok(None);
+        }
+        let min_value = min.unwrap_or(0);
+        if let Some(max) = max
+            && max < min_value
+        {
+            return Err(self.error("min repeat greater than max repeat", min_start));
+        }
+        Ok(Some((min_value, max)))
+    }
+
+    fn parse_repeat_number(&mut self) -> Result<Option<u32>, RegexError> {
+        let start = self.pos;
+        let mut value: u64 = 0;
+        while let Some(digit) = self.peek().and_then(|c| c.to_digit(10)) {
+            value = value.saturating_mul(10).saturating_add(u64::from(digit));
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Ok(None);
+        }
+        if value > MAX_REPEAT {
+            return Err(self.error("the repetition number is too large", start));
+        }
+        Ok(Some(u32::try_from(value).expect("checked against MAX_REPEAT")))
+    }
+
+    /// Parses a single atom: a character, set, group, escape or anchor.
+    fn parse_atom(&mut self, flags: u32, depth: usize) -> Result<Option<Node>, RegexError> {
+        let start = self.pos;
+        let c = self.peek().expect("caller checked for the end of the pattern");
+        self.pos += 1;
+        let node = match c {
+            '(' => return self.parse_group(start, flags, depth),
+            '[' => Node::Class(self.parse_class(start, flags)?),
+            '.' => Node::Any {
+                dotall: flags & DOTALL != 0,
+            },
+            '^' => Node::Assert(if flags & MULTILINE == 0 {
+                Assertion::StartText
+            } else {
+                Assertion::StartLine
+            }),
+            '$' => Node::Assert(if flags & MULTILINE == 0 {
+                Assertion::EndTextOrFinalNewline
+            } else {
+                Assertion::EndLine
+            }),
+            '*' | '+' | '?' => return Err(self.error("nothing to repeat", start)),
+            '{' => {
+                self.pos = start;
+                if self.parse_brace_repeat()?.is_some() {
+                    return Err(self.error("nothing to repeat", start));
+                }
+                self.pos = start + 1;
+                char_node('{', flags)
+            }
+            '\\' => match self.parse_escape(start, flags)? {
+                Escape::Char(c) => char_node(c, flags),
+                Escape::Perl(class, negated) => Node::Class(CharClass {
+                    items: vec![ClassItem::Perl { class, negated }],
+                    negated: false,
+                    ascii: flags & ASCII != 0,
+                    ignore_case: false,
+                }),
+                Escape::Assert(assertion) => Node::Assert(assertion),
+            },
+            c => char_node(c, flags),
+        };
+        Ok(Some(node))
+    }
+
+    /// Parses a group after its `(`, which is at `start`.
+    ///
+    /// Returns `None` for comments, which match nothing.
+    fn parse_group(&mut self, start: usize, flags: u32, depth: usize) -> Result<Option<Node>, RegexError> {
+        if depth >= MAX_NESTING {
+            return Err(self.error("too many nested groups", start));
+        }
+        let mut index = None;
+        let mut group_flags = flags;
+        if self.eat('?') {
+            let Some(c) = self.peek() else {
+                return Err(self.error("unexpected end of pattern", self.pos));
+            };
+            match c {
+                ':' => self.pos += 1,
+                'P' => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some('<') => {
+                            self.pos += 1;
+                            let name_start = self.pos;
+                            let name = self.parse_group_name('>')?;
+                            self.group_count += 1;
+                            if let Some((_, existing)) = self.group_names.iter().find(|(n, _)| *n == name) {
+                                return Err(self.error(
+                                    format!(
+                                        "redefinition of group name '{name}' as group {}; was group {existing}",
+                                        self.group_count
+                                    ),
+                                    name_start,
+                                ));
+                            }
+                            self.group_names.push((name, self.group_count));
+                            index = Some(self.group_count);
+                        }
+                        Some('=') => {
+                            self.pos += 1;
+                            let name_start = self.pos;
+                            let name = self.parse_group_name(')')?;
+                            if !self.group_names.iter().any(|(n, _)| *n == name) {
+                                return Err(self.error(format!("unknown group name '{name}'"), name_start));
+                            }
+                            return Err(self.error("backreferences are not supported", start));
+                        }
+                        Some(c) => return Err(self.error(format!("unknown extension ?P{c}"), start + 1)),
+                        None => return Err(self.error("unexpected end of pattern", self.pos)),
+                    }
+                }
+                '#' => {
+                    while let Some(c) = self.peek() {
+                        self.pos += 1;
+                        if c == ')' {
+                            return Ok(None);
+                        }
+                    }
+                    return Err(self.error("missing ), unterminated comment", start));
+                }
+                '=' | '!' => return Err(self.error("look-ahead assertions are not supported", start)),
+                '<' => match self.peek_at(1) {
+                    Some('=' | '!') => return Err(self.error("look-behind assertions are not supported", start)),
+                    Some(c) => return Err(self.error(format!("unknown extension ?<{c}"), start + 1)),
+                    None => return Err(self.error("unexpected end of pattern", self.pos + 1)),
+                },
+                '>' => return Err(self.error("atomic groups are not supported", start)),
+                '(' => return Err(self.error("conditional groups are not supported", start)),
+                _ => {
+                    let Some(added) = self.parse_flag_letters()? else {
+                        return Err(self.error(format!("unknown extension ?{c}"), start + 1));
+                    };
+                    let mut removed = 0;
+                    if self.eat('-') {
+                        let removed_start = self.pos;
+                        while let Some(flag) = self.peek().and_then(flag_value) {
+                            if flag & (ASCII | UNICODE | LOCALE) != 0 {
+                                return Err(self
+                                    .error("bad inline flags: cannot turn off flags 'a', 'u' and 'L'", self.pos + 1));
+                            }
+                            removed |= flag;
+                            self.pos += 1;
+                        }
+                        if self.pos == removed_start {
+                            return Err(self.error("missing flag", self.pos));
+                        }
+                    }
+                    match self.peek() {
+                        Some(':') => self.pos += 1,
+                        Some(')') if removed == 0 => {
+                            return Err(self.error("global flags not at the start of the expression", start));
+                        }
+                        Some(c) if c.is_ascii_alphabetic() => {
+                            return Err(self.error("unknown flag", self.pos));
+                        }
+                        Some(_) => return Err(self.error("missing :", self.pos)),
+                        None => return Err(self.error("missing -, : or )", self.pos)),
+                    }
+                    group_flags = (flags | added) & !removed;
+                    if added & ASCII != 0 {
+                        group_flags &= !UNICODE;
+                    } else if added & UNICODE != 0 {
+                        group_flags &= !ASCII;
+                    }
+                }
+            }
+        } else {
+            self.group_count += 1;
+            index = Some(self.group_count);
+        }
+
+        let node = self.parse_alternation(group_flags, depth + 1)?;
+        if !self.eat(')') {
+            return Err(self.error("missing ), unterminated subpattern", start));
+        }
+        Ok(Some(match index {
+            Some(index) => Node::Group {
+                index,
+                node: Box::new(node),
+            },
+            None => node,
+        }))
+    }
+
+    /// Parses a group name up to `terminator`, checking it's a valid identifier.
+    fn parse_group_name(&mut self, terminator: char) -> Result<String, RegexError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c != terminator) {
+            self.pos += 1;
+        }
+        if self.peek().is_none() {
+            let what = if terminator == '>' { "missing >" } else { "missing )" };
+            return Err(self.error(format!("{what}, unterminated name"), start));
+        }
+        let name = self.text(start, self.pos);
+        self.pos += 1;
+        if name.is_empty() {
+            return Err(self.error("missing group name", start));
+        }
+        if !is_identifier(&name) {
+            return Err(self.error(format!("bad character in group name '{name}'"), start));
+        }
+        Ok(name)
+    }
+
+    /// Parses a character set after its `[`, which is at `start`.
+    fn parse_class(&mut self, start: usize, flags: u32) -> Result<CharClass, RegexError> {
+        let negated = self.eat('^');
+        let mut items = Vec::new();
+        let mut first = true;
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.error("unterminated character set", start));
+            };
+            if c == ']' && !first {
+                self.pos += 1;
+                break;
+            }
+            first = false;
+            let item_start = self.pos;
+            let atom = self.parse_class_atom()?;
+            if self.peek() == Some('-') && self.peek_at(1).is_some_and(|c| c != ']') {
+                self.pos += 1;
+                let hi = self.parse_class_atom()?;
+                match (atom, hi) {
+                    (ClassAtom::Char(lo), ClassAtom::Char(hi)) if lo <= hi => items.push(ClassItem::Range(lo, hi)),
+                    _ => {
+                        let range = self.text(item_start, self.pos);
+                        return Err(self.error(format!("bad character range {range}"), item_start));
+                    }
+                }
+            } else {
+                items.push(match atom {
+                    ClassAtom::Char(c) => ClassItem::Range(c, c),
+                    ClassAtom::Perl(class, negated) => ClassItem::Perl { class, negated },
+                });
+            }
+        }
+        Ok(CharClass {
+            items,
+            negated,
+            ascii: flags & ASCII != 0,
+            ignore_case: flags & IGNORECASE != 0,
+        })
+    }
+
+    fn parse_class_atom(&mut self) -> Result<ClassAtom, RegexError> {
+        let start = self.pos;
+        let Some(c) = self.peek() else {
+            return Err(self.error("unterminated character set", start));
+        };
+        self.pos += 1;
+        if c != '\\' {
+            return Ok(ClassAtom::Char(c));
+        }
+        let Some(e) = self.peek() else {
+            return Err(self.error("bad escape (end of pattern)", start));
+        };
+        self.pos += 1;
+        Ok(match e {
+            'd' | 'D' | 'w' | 'W' | 's' | 'S' => {
+                let (class, negated) = perl_class(e);
+                ClassAtom::Perl(class, negated)
+            }
+            'b' => ClassAtom::Char('\x08'),
+            '0'..='7' => {
+                // Up to three octal digits
+                let mut value = e.to_digit(8).expect("octal digit");
+                for _ in 0..2 {
+                    match self.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            self.pos += 1;
+                        }
+                        None => break,
+                    }
+                }
+                ClassAtom::Char(self.octal_char(value, start)?)
+            }
+            _ => ClassAtom::Char(self.parse_char_escape(e, start)?),
+        })
+    }
+
+    /// Parses an escape outside a character set, after the `\` at `start`.
+    fn parse_escape(&mut self, start: usize, flags: u32) -> Result<Escape, RegexError> {
+        let Some(e) = self.peek() else {
+            return Err(self.error("bad escape (end of pattern)", start));
+        };
+        self.pos += 1;
+        Ok(match e {
+            'd' | 'D' | 'w' | 'W' | 's' | 'S' => {
+                let (class, negated) = perl_class(e);
+                Escape::Perl(class, negated)
+            }
+            'b' | 'B' => {
+                let ascii = flags & ASCII != 0;
+                Escape::Assert(if e == 'b' {
+                    Assertion::WordBoundary { ascii }
+                } else {
+                    Assertion::NotWordBoundary { ascii }
+                })
+            }
+            'A' => Escape::Assert(Assertion::StartText),
+            'Z' => Escape::Assert(Assertion::EndText),
+            '0' => {
+                // `\0` followed by up to two more octal digits
+                let mut value = 0;
+                for _ in 0..2 {
+                    match self.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            self.pos += 1;
+                        }
+                        None => break,
+                    }
+                }
+                Escape::Char(self.octal_char(value, start)?)
+            }
+            '1'..='9' => {
+                // Three octal digits are an octal escape, otherwise it's a group reference
+                let d1 = self.peek();
+                let d2 = self.peek_at(1);
+                if e <= '7'
+                    && let (Some(d1 @ '0'..='7'), Some(d2 @ '0'..='7')) = (d1, d2)
+                {
+                    self.pos += 2;
+                    let value = [e, d1, d2]
+                        .iter()
+                        .fold(0, |acc, c| acc * 8 + c.to_digit(8).expect("octal digit"));
+                    return Ok(Escape::Char(self.octal_char(value, start)?));
+                }
+                let mut group = e.to_digit(10).expect("digit") as usize;
+                if let Some(digit) = d1.and_then(|c| c.to_digit(10)) {
+                    group = group * 10 + digit as usize;
+                    self.pos += 1;
+                }
+                if group > self.group_count {
+                    return Err(self.error(format!("invalid group reference {group}"), start + 1));
+                }
+                return Err(self.error("backreferences are not supported", start));
+            }
+            'N' => return Err(self.error("named Unicode escapes are not supported", start)),
+            _ => Escape::Char(self.parse_char_escape(e, start)?),
+        })
+    }
+
+    /// Parses escapes shared by patterns and character sets: control characters, `\x`,
+    /// `\u` and `\U` escapes, and escaped punctuation.
+    fn parse_char_escape(&mut self, e: char, start: usize) -> Result<char, RegexError> {
+        Ok(match e {
+            'a' => '\x07',
+            'f' => '\x0c',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'v' => '\x0b',
+            'x' => self.parse_hex_escape(2, start)?,
+            'u' => self.parse_hex_escape(4, start)?,
+            'U' => self.parse_hex_escape(8, start)?,
+            c if c.is_ascii_alphanumeric() => {
+                return Err(self.error(format!("bad escape \\{c}"), start));
+            }
+            c => c,
+        })
+    }
+
+    fn parse_hex_escape(&mut self, digits: usize, start: usize) -> Result<char, RegexError> {
+        let mut value: u32 = 0;
+        for _ in 0..digits {
+            match self.peek().and_then(|c| c.to_digit(16)) {
+                Some(digit) => {
+                    value = value * 16 + digit;
+                    self.pos += 1;
+                }
+                None => {
+                    let escape = self.text(start, self.pos);
+                    return Err(self.error(format!("incomplete escape {escape}"), start));
+                }
+            }
+        }
+        char::from_u32(value).ok_or_else(|| {
+            let escape = self.text(start, self.pos);
+            self.error(format!("bad escape {escape}"), start)
+        })
+    }
+
+    fn octal_char(&self, value: u32, start: usize) -> Result<char, RegexError> {
+        if value > 0o377 {
+            let escape = self.text(start, self.pos);
+            return Err(self.error(format!("octal escape value {escape} outside of range 0-0o377"), start));
+        }
+        Ok(char::from_u32(value).expect("octal escapes are below 0o400"))
+    }
+}
+
+fn char_node(c: char, flags: u32) -> Node {
+    Node::Char {
+        c,
+        fold: (flags & IGNORECASE != 0).then_some(flags & ASCII != 0),
+    }
+}
+
+fn perl_class(e: char) -> (PerlClass, bool) {
+    let class = match e.to_ascii_lowercase() {
+        'd' => PerlClass::Digit,
+        'w' => PerlClass::Word,
+        _ => PerlClass::Space,
+    };
+    (class, e.is_ascii_uppercase())
+}
+
+/// Returns the value of an inline flag letter.
+fn flag_value(c: char) -> Option<u32> {
+    match c {
+        'a' => Some(ASCII),
+        'i' => Some(IGNORECASE),
+        'L' => Some(LOCALE),
+        'm' => Some(MULTILINE),
+        's' => Some(DOTALL),
+        'u' => Some(UNICODE),
+        'x' => Some(VERBOSE),
+        _ => None,
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c == '_' || c.is_alphabetic()) && chars.all(|c| c == '_' || c.is_alphanumeric())
+}
+
+/// Parses a replacement template, resolving group references against `regex`.
+pub(super) fn parse_template(template: &str, regex: &Regex) -> Result<Vec<TemplatePart>, TemplateError> {
+    let chars: Vec<char> = template.chars().collect();
+    let error = |msg: String, pos: usize| TemplateError::Invalid(RegexError::new(msg, &chars, pos));
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let c = chars[pos];
+        pos += 1;
+        if c != '\\' {
+            literal.push(c);
+            continue;
+        }
+        let start = pos - 1;
+        let Some(&e) = chars.get(pos) else {
+            return Err(error("bad escape (end of pattern)".to_owned(), start));
+        };
+        pos += 1;
+        let group = match e {
+            'g' => {
+                if chars.get(pos) != Some(&'<') {
+                    return Err(error("missing <".to_owned(), pos));
+                }
+                let name_start = pos + 1;
+                let Some(len) = chars[name_start..].iter().position(|c| *c == '>') else {
+                    return Err(error("missing >, unterminated name".to_owned(), name_start));
+                };
+                let name: String = chars[name_start..name_start + len].iter().collect();
+                pos = name_start + len + 1;
+                if name.is_empty() {
+                    return Err(error("missing group name".to_owned(), name_start));
+                }
+                if name.chars().all(|c| c.is_ascii_digit()) {
+                    let index = name.parse::<usize>().unwrap_or(usize::MAX);
+                    if index > regex.group_count() {
+                        return Err(error(format!("invalid group reference {index}"), name_start));
+                    }
+                    index
+                } else if is_identifier(&name) {
+                    regex.group_index(&name).ok_or(TemplateError::UnknownGroupName(name))?
+                } else {
+                    return Err(error(format!("bad character in group name '{name}'"), name_start));
+                }
+            }
+            '0' => {
+                // `\0` is an octal escape of up to three digits
+                let mut value = 0;
+                for _ in 0..2 {
+                    match chars.get(pos).and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            pos += 1;
+                        }
+                        None => break,
+                    }
+                }
+                literal.push(char::from_u32(value).expect("octal escapes are below 0o100"));
+                continue;
+            }
+            '1'..='9' => {
+                let d1 = chars.get(pos).copied();
+                let d2 = chars.get(pos + 1).copied();
+                if e <= '7'
+                    && let (Some(d1 @ '0'..='7'), Some(d2 @ '0'..='7')) = (d1, d2)
+                {
+                    pos += 2;
+                    let value = [e, d1, d2]
+                        .iter()
+                        .fold(0, |acc, c| acc * 8 + c.to_digit(8).expect("octal digit"));
+                    if value > 0o377 {
+                        let escape: String = chars[start..pos].iter().collect();
+                        return Err(error(
+                            format!("octal escape value {escape} outside of range 0-0o377"),
+                            start,
+                        ));
+                    }
+                    literal.push(char::from_u32(value).expect("checked against 0o377"));
+                    continue;
+                }
+                let mut index = e.to_digit(10).expect("digit") as usize;
+                if let Some(digit) = d1.and_then(|c| c.to_digit(10)) {
+                    index = index * 10 + digit as usize;
+                    pos += 1;
+                }
+                if index > regex.group_count() {
+                    return Err(error(format!("invalid group reference {index}"), start + 1));
+                }
+                index
+            }
+            'a' => {
+                literal.push('\x07');
+                continue;
+            }
+            'b' => {
+                literal.push('\x08');
+                continue;
+            }
+            'f' => {
+                literal.push('\x0c');
+                continue;
+            }
+            'n' => {
+                literal.push('\n');
+                continue;
+            }
+            'r' => {
+                literal.push('\r');
+                continue;
+            }
+            't' => {
+                literal.push('\t');
+                continue;
+            }
+            'v' => {
+                literal.push('\x0b');
+                continue;
+            }
+            '\\' => {
+                literal.push('\\');
+                continue;
+            }
+            e if e.is_ascii_alphabetic() => return Err(error(format!("bad escape \\{e}"), start)),
+            e => {
+                // Unknown escapes of other characters are kept as they are
+                literal.push('\\');
+                literal.push(e);
+                continue;
+            }
+        };
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+        }
+        parts.push(TemplatePart::Group(group));
+    }
+    if !literal.is_empty() {
+        parts.push(TemplatePart::Literal(literal));
+    }
+    Ok(parts)
+}
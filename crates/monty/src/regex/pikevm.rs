@@ -0,0 +1,232 @@
+//! Pike VM: runs a compiled pattern over the text, one character at a time.
+//!
+//! Every thread is at a distinct instruction, so there are at most as many live threads as
+//! instructions, and each character is processed in time proportional to the program size.
+//! Threads are kept in priority order, and a thread reaching `Match` cuts off all threads
+//! with lower priority, which gives the same leftmost-first semantics as backtracking.
+
+use std::mem;
+
+use super::{Inst, parse::chars_eq_fold};
+use crate::resource::{ResourceError, ResourceTracker};
+
+/// Where a match is allowed to start and end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Anchor {
+    /// The match may start anywhere at or after the start position, as in `search()`.
+    Unanchored,
+    /// The match must start at the start position, as in `match()`.
+    Start,
+    /// The match must start at the start position and end at the end of the text, as in
+    /// `fullmatch()`.
+    Both,
+}
+
+/// Scratch space for running a pattern, sized for its program.
+#[derive(Debug)]
+pub(crate) struct Cache {
+    clist: Threads,
+    nlist: Threads,
+    stack: Vec<Frame>,
+    /// Capture slots of the thread being added.
+    slots: Vec<Option<usize>>,
+}
+
+impl Cache {
+    pub(super) fn new(prog_len: usize, slot_count: usize) -> Self {
+        Self {
+            clist: Threads::new(prog_len, slot_count),
+            nlist: Threads::new(prog_len, slot_count),
+            stack: Vec::new(),
+            slots: vec![None; slot_count],
+        }
+    }
+}
+
+/// The threads at one position in the text, in priority order.
+///
+/// Tracks every instruction reached at this position, so each is only followed once, but only
+/// instructions which consume a character or match are run. Each has its own capture slots.
+#[derive(Debug)]
+struct Threads {
+    /// Instructions in the order they were reached; a sparse set together with `sparse`.
+    dense: Vec<usize>,
+    /// Index of each instruction in `dense`, only valid if `dense` agrees.
+    sparse: Vec<usize>,
+    /// Capture slots for each instruction, `slot_count` per instruction.
+    caps: Vec<Option<usize>>,
+    slot_count: usize,
+}
+
+impl Threads {
+    fn new(prog_len: usize, slot_count: usize) -> Self {
+        Self {
+            dense: Vec::with_capacity(prog_len),
+            sparse: vec![0; prog_len],
+            caps: vec![None; prog_len * slot_count],
+            slot_count,
+        }
+    }
+
+    /// Adds an instruction, returning `false` if it's already present.
+    fn insert(&mut self, pc: usize) -> bool {
+        let index = self.sparse[pc];
+        if index < self.dense.len() && self.dense[index] == pc {
+            return false;
+        }
+        self.sparse[pc] = self.dense.len();
+        self.dense.push(pc);
+        true
+    }
+
+    fn caps(&self, pc: usize) -> &[Option<usize>] {
+        &self.caps[pc * self.slot_count..(pc + 1) * self.slot_count]
+    }
+
+    fn caps_mut(&mut self, pc: usize) -> &mut [Option<usize>] {
+        &mut self.caps[pc * self.slot_count..(pc + 1) * self.slot_count]
+    }
+}
+
+/// Work left while following the instructions reachable from a new thread.
+#[derive(Debug, Clone, Copy)]
+enum Frame {
+    Explore(usize),
+    /// Undoes a `Save` once everything after it has been followed.
+    RestoreCapture {
+        slot: usize,
+        old: Option<usize>,
+    },
+}
+
+/// Runs the program over `text` from byte offset `start`, returning the capture slots of the
+/// first match found.
+///
+/// The last slot holds the number of the last group closed rather than a position.
+pub(super) fn exec(
+    insts: &[Inst],
+    cache: &mut Cache,
+    text: &str,
+    start: usize,
+    anchor: Anchor,
+    not_empty_at_start: bool,
+    tracker: &mut impl ResourceTracker,
+) -> Result<Option<Vec<Option<usize>>>, ResourceError> {
+    let Cache {
+        clist,
+        nlist,
+        stack,
+        slots,
+    } = cache;
+    clist.dense.clear();
+    nlist.dense.clear();
+    let mut matched = None;
+    let mut pos = start;
+    loop {
+        tracker.check_time()?;
+        // A new thread starting here has the lowest priority, and isn't needed once there's a match
+        if matched.is_none() && (anchor == Anchor::Unanchored || pos == start) {
+            slots.fill(None);
+            add_thread(clist, stack, slots, insts, text, pos, 0);
+        }
+        if clist.dense.is_empty() {
+            break;
+        }
+        let c = text[pos..].chars().next();
+        let next_pos = pos + c.map_or(0, char::len_utf8);
+        for i in 0..clist.dense.len() {
+            let pc = clist.dense[i];
+            let consumed = match &insts[pc] {
+                Inst::Match => {
+                    let caps = clist.caps(pc);
+                    let rejected = (anchor == Anchor::Both && pos != text.len())
+                        || (not_empty_at_start && pos == start && caps[0] == Some(start));
+                    if rejected {
+                        continue;
+                    }
+                    matched = Some(caps.to_vec());
+                    break;
+                }
+                Inst::Char(expected) => c == Some(*expected),
+                Inst::CharFold { c: expected, ascii } => c.is_some_and(|c| chars_eq_fold(c, *expected, *ascii)),
+                Inst::Any => c.is_some_and(|c| c != '\n'),
+                Inst::AnyAll => c.is_some(),
+                Inst::Class(class) => c.is_some_and(|c| class.matches(c)),
+                // Only reached while adding threads
+                Inst::Assert(_) | Inst::Save(_) | Inst::Split(..) | Inst::Jmp(_) => false,
+            };
+            if consumed {
+                slots.copy_from_slice(clist.caps(pc));
+                add_thread(nlist, stack, slots, insts, text, next_pos, pc + 1);
+            }
+        }
+        if c.is_none() {
+            break;
+        }
+        mem::swap(clist, nlist);
+        nlist.dense.clear();
+        pos = next_pos;
+    }
+    Ok(matched)
+}
+
+/// Adds a thread at `pc`, following jumps, splits, saves and assertions at byte offset `pos`
+/// to the instructions which consume a character or match.
+///
+/// `slots` holds the thread's capture slots, and is restored before returning.
+fn add_thread(
+    threads: &mut Threads,
+    stack: &mut Vec<Frame>,
+    slots: &mut [Option<usize>],
+    insts: &[Inst],
+    text: &str,
+    pos: usize,
+    pc: usize,
+) {
+    stack.push(Frame::Explore(pc));
+    while let Some(frame) = stack.pop() {
+        let mut pc = match frame {
+            Frame::Explore(pc) => pc,
+            Frame::RestoreCapture { slot, old } => {
+                slots[slot] = old;
+                continue;
+            }
+        };
+        while threads.insert(pc) {
+            match &insts[pc] {
+                Inst::Jmp(target) => pc = *target,
+                Inst::Split(first, second) => {
+                    stack.push(Frame::Explore(*second));
+                    pc = *first;
+                }
+                Inst::Save(slot) => {
+                    stack.push(Frame::RestoreCapture {
+                        slot: *slot,
+                        old: slots[*slot],
+                    });
+                    slots[*slot] = Some(pos);
+                    // Closing a group, other than the whole match, makes it the last group
+                    if slot % 2 == 1 && *slot > 1 {
+                        let last = slots.len() - 1;
+                        stack.push(Frame::RestoreCapture {
+                            slot: last,
+                            old: slots[last],
+                        });
+                        slots[last] = Some(slot / 2);
+                    }
+                    pc += 1;
+                }
+                Inst::Assert(assertion) => {
+                    if !assertion.holds(text, pos) {
+                        break;
+                    }
+                    pc += 1;
+                }
+                Inst::Char(_) | Inst::CharFold { .. } | Inst::Any | Inst::AnyAll | Inst::Class(_) | Inst::Match => {
+                    threads.caps_mut(pc).copy_from_slice(slots);
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -190,6 +190,10 @@ pub enum RunProgress<T: ResourceTracker> {
     ///
     /// When using async resolution, the code continues and may `await` the future later.
     /// If the future isn't resolved when awaited, execution yields with `ResolveFutures`.
+    ///
+    /// Calling one of the `methods` of a dataclass passed in by the host also pauses here:
+    /// `function_name` is then the method name (e.g. `__enter__` for a `with` statement)
+    /// and the first positional argument is the dataclass itself.
     FunctionCall {
         /// The name of the function being called.
         function_name: String,
@@ -397,16 +401,9 @@ impl<T: ResourceTracker> Snapshot<T> {
                 // Get the call_id and ext_function_id that were stored when this Snapshot was created
                 let call_id = CallId::new(self.pending_call_id);
 
-                // Store pending call data in the scheduler so we can track the creator task
-                // and ignore results if the task is cancelled
-                vm.add_pending_call(call_id);
-
-                // Push the ExternalFuture value onto the stack
+                // Push the ExternalFuture value onto the stack and continue execution
                 // This allows the code to continue and potentially await this future later
-                vm.push(Value::ExternalFuture(call_id));
-
-                // Continue execution
-                vm.run()
+                vm.resume_with_future(call_id)
             }
         };
 
@@ -653,6 +650,22 @@ fn handle_vm_result<T: ResourceTracker>(
                 state: new_snapshot!(call_id),
             })
         }
+        Ok(FrameExit::MethodCall {
+            method_name,
+            args,
+            call_id,
+        }) => {
+            let function_name = executor.interns.get_str(method_name).to_owned();
+            let (args_py, kwargs_py) = args.into_py_objects(&mut heap, &executor.interns);
+
+            Ok(RunProgress::FunctionCall {
+                function_name,
+                args: args_py,
+                kwargs: kwargs_py,
+                call_id: call_id.raw(),
+                state: new_snapshot!(call_id),
+            })
+        }
         Ok(FrameExit::OsCall {
             function,
             args,
@@ -911,6 +924,13 @@ fn frame_exit_to_object(
             ))
             .into())
         }
+        FrameExit::MethodCall { method_name, .. } => {
+            let method_name = interns.get_str(method_name);
+            Err(ExcType::not_implemented(format!(
+                "Method '{method_name}' not implemented with standard execution"
+            ))
+            .into())
+        }
         FrameExit::OsCall { function, .. } => Err(ExcType::not_implemented(format!(
             "OS function '{function}' not implemented with standard execution"
         ))
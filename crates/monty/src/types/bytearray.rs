@@ -0,0 +1,635 @@
+//! Python `bytearray` type: a mutable sequence of bytes.
+//!
+//! Mutating methods (`append`, `extend`, `insert`, `pop`, `remove`, `clear`, `reverse`)
+//! and item/slice assignment are implemented here. Every other method shares its
+//! implementation with `bytes` via `call_bytes_method_impl`, and any `bytes` values in the
+//! result are converted to `bytearray`, so e.g. `bytearray(b'a b').split()` returns a list
+//! of bytearrays as in CPython.
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use super::{
+    MontyIter, PyTrait, Slice, Type, allocate_tuple,
+    bytes::{byte_from_value, bytes_repr_fmt, call_bytes_method_impl, get_bytes_slice, zeroed_bytes},
+};
+use crate::{
+    args::ArgValues,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    resource::{ResourceError, ResourceTracker},
+    value::{EitherStr, Value},
+};
+
+/// Python bytearray value stored on the heap.
+///
+/// Unlike `Bytes`, a bytearray is mutable and therefore unhashable.
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ByteArray(Vec<u8>);
+
+impl ByteArray {
+    /// Creates a new ByteArray from a byte vector.
+    #[must_use]
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns a reference to the inner byte slice.
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the inner byte vector.
+    pub fn as_vec_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.0
+    }
+
+    /// Creates a bytearray from the `bytearray()` constructor call.
+    ///
+    /// - `bytearray()` with no args returns an empty bytearray
+    /// - `bytearray(int)` returns a bytearray of that length filled with zeros
+    /// - `bytearray(string, encoding)` encodes the string (UTF-8 only)
+    /// - `bytearray(bytes)` or `bytearray(bytearray)` copies the bytes
+    /// - `bytearray(iterable)` collects an iterable of ints in `range(0, 256)`
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let (source, encoding) = args.get_zero_one_two_args("bytearray", heap)?;
+        let bytes = match (source, encoding) {
+            (None, _) => Vec::new(),
+            (Some(source), None) => bytearray_from_source(source, heap, interns)?,
+            (Some(source), Some(encoding)) => {
+                let result = encode_str(&source, &encoding, heap, interns);
+                source.drop_with_heap(heap);
+                encoding.drop_with_heap(heap);
+                result?
+            }
+        };
+        let heap_id = heap.allocate(HeapData::ByteArray(Self::new(bytes)))?;
+        Ok(Value::Ref(heap_id))
+    }
+
+    /// Appends the bytes of `value` for `bytearray.extend()`.
+    ///
+    /// Accepts bytes-like objects and iterables of ints. Takes ownership of `value`.
+    fn extend(&mut self, value: Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<()> {
+        let bytes = bytes_from_iterable(value, heap, interns, |t| {
+            if t == Type::Str {
+                ExcType::type_error("expected iterable of integers; got: 'str'")
+            } else {
+                ExcType::type_error(format!("can't extend bytearray with {t}"))
+            }
+        })?;
+        self.0.extend(bytes);
+        Ok(())
+    }
+
+    /// Assigns `replacement` to the bytes selected by a slice, as in `ba[1:3] = b'xyz'`.
+    ///
+    /// Contiguous slices may change the length of the bytearray. Extended slices
+    /// require a replacement of the same length, except that an empty replacement
+    /// deletes the selected bytes.
+    fn setitem_slice(&mut self, slice: &Slice, replacement: Vec<u8>) -> RunResult<()> {
+        let len = self.0.len();
+        let (start, stop, step) = slice
+            .indices(len)
+            .map_err(|()| ExcType::value_error_slice_step_zero())?;
+
+        if step == 1 {
+            let start = start.min(len);
+            let stop = stop.max(start);
+            self.0.splice(start..stop, replacement);
+            return Ok(());
+        }
+
+        let positions = slice_positions(start, stop, step, len);
+        if replacement.is_empty() {
+            self.remove_positions(&positions);
+            return Ok(());
+        }
+        if replacement.len() != positions.len() {
+            return Err(SimpleException::new_msg(
+                ExcType::ValueError,
+                format!(
+                    "attempt to assign bytes of size {} to extended slice of size {}",
+                    replacement.len(),
+                    positions.len()
+                ),
+            )
+            .into());
+        }
+        for (position, byte) in positions.into_iter().zip(replacement) {
+            self.0[position] = byte;
+        }
+        Ok(())
+    }
+
+    /// Removes the bytes at the given positions.
+    fn remove_positions(&mut self, positions: &[usize]) {
+        let mut remove = vec![false; self.0.len()];
+        for &position in positions {
+            remove[position] = true;
+        }
+        let mut remove = remove.into_iter();
+        self.0.retain(|_| !remove.next().unwrap_or(false));
+    }
+}
+
+impl From<Vec<u8>> for ByteArray {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<ByteArray> for Vec<u8> {
+    fn from(bytes: ByteArray) -> Self {
+        bytes.0
+    }
+}
+
+impl std::ops::Deref for ByteArray {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl PyTrait for ByteArray {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::ByteArray
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.0.len()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        Some(self.0.len())
+    }
+
+    fn py_getitem(&self, key: &Value, heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> RunResult<Value> {
+        if let Value::Ref(id) = key
+            && let HeapData::Slice(slice) = heap.get(*id)
+        {
+            let (start, stop, step) = slice
+                .indices(self.0.len())
+                .map_err(|()| ExcType::value_error_slice_step_zero())?;
+            let sliced = get_bytes_slice(&self.0, start, stop, step);
+            let heap_id = heap.allocate(HeapData::ByteArray(Self::new(sliced)))?;
+            return Ok(Value::Ref(heap_id));
+        }
+
+        let index = normalize_index(key_to_index(key, heap)?, self.0.len())?;
+        Ok(Value::Int(i64::from(self.0[index])))
+    }
+
+    fn py_setitem(
+        &mut self,
+        key: Value,
+        value: Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<()> {
+        if let Value::Ref(id) = &key
+            && let HeapData::Slice(slice) = heap.get(*id)
+        {
+            let slice = slice.clone();
+            key.drop_with_heap(heap);
+            let replacement = bytes_from_iterable(value, heap, interns, |_| {
+                ExcType::type_error("can assign only bytes, buffers, or iterables of ints in range(0, 256)")
+            })?;
+            return self.setitem_slice(&slice, replacement);
+        }
+
+        let index = key_to_index(&key, heap);
+        key.drop_with_heap(heap);
+        let byte = byte_from_value(&value, heap);
+        value.drop_with_heap(heap);
+        let index = normalize_index(index?, self.0.len())?;
+        self.0[index] = byte?;
+        Ok(())
+    }
+
+    fn py_delitem(&mut self, key: Value, heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> RunResult<()> {
+        if let Value::Ref(id) = &key
+            && let HeapData::Slice(slice) = heap.get(*id)
+        {
+            let indices = slice.indices(self.0.len());
+            key.drop_with_heap(heap);
+            let (start, stop, step) = indices.map_err(|()| ExcType::value_error_slice_step_zero())?;
+            self.remove_positions(&slice_positions(start, stop, step, self.0.len()));
+            return Ok(());
+        }
+
+        let index = key_to_index(&key, heap);
+        key.drop_with_heap(heap);
+        let index = normalize_index(index?, self.0.len())?;
+        self.0.remove(index);
+        Ok(())
+    }
+
+    fn py_eq(&self, other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self.0 == other.0
+    }
+
+    /// Bytearrays don't contain nested heap references.
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No-op: bytearrays don't hold Value references
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        !self.0.is_empty()
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        f.write_str("bytearray(")?;
+        bytes_repr_fmt(&self.0, f)?;
+        f.write_char(')')
+    }
+
+    fn py_add(
+        &self,
+        other: &Self,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> Result<Option<Value>, ResourceError> {
+        Ok(Some(concat_bytearray(&self.0, &other.0, heap)?))
+    }
+
+    fn py_iadd(
+        &mut self,
+        other: Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        self_id: Option<HeapId>,
+        _interns: &Interns,
+    ) -> Result<bool, ResourceError> {
+        let Value::Ref(other_id) = &other else {
+            return Ok(false);
+        };
+
+        let extended = if Some(*other_id) == self_id {
+            self.0.extend_from_within(..);
+            true
+        } else if let Some(bytes) = heap.get(*other_id).as_bytes_like() {
+            self.0.extend_from_slice(bytes);
+            true
+        } else {
+            false
+        };
+        other.drop_with_heap(heap);
+        Ok(extended)
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        let Some(method) = attr.static_string() else {
+            args.drop_with_heap(heap);
+            return Err(ExcType::attribute_error(Type::ByteArray, attr.as_str(interns)));
+        };
+
+        call_bytearray_method(self, method, args, heap, interns)
+    }
+}
+
+/// Dispatches a method call on a bytearray.
+fn call_bytearray_method(
+    bytearray: &mut ByteArray,
+    method: StaticStrings,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    match method {
+        StaticStrings::Append => {
+            let item = args.get_one_arg("bytearray.append", heap)?;
+            let byte = byte_from_value(&item, heap);
+            item.drop_with_heap(heap);
+            bytearray.0.push(byte?);
+            Ok(Value::None)
+        }
+        StaticStrings::Extend => {
+            let iterable = args.get_one_arg("bytearray.extend", heap)?;
+            bytearray.extend(iterable, heap, interns)?;
+            Ok(Value::None)
+        }
+        StaticStrings::Insert => bytearray_insert(bytearray, args, heap),
+        StaticStrings::Pop => bytearray_pop(bytearray, args, heap),
+        StaticStrings::Remove => {
+            let item = args.get_one_arg("bytearray.remove", heap)?;
+            let byte = byte_from_value(&item, heap);
+            item.drop_with_heap(heap);
+            let byte = byte?;
+            let Some(index) = bytearray.0.iter().position(|&b| b == byte) else {
+                return Err(SimpleException::new_msg(ExcType::ValueError, "value not found in bytearray").into());
+            };
+            bytearray.0.remove(index);
+            Ok(Value::None)
+        }
+        StaticStrings::Clear => {
+            args.check_zero_args("bytearray.clear", heap)?;
+            bytearray.0.clear();
+            Ok(Value::None)
+        }
+        StaticStrings::Reverse => {
+            args.check_zero_args("bytearray.reverse", heap)?;
+            bytearray.0.reverse();
+            Ok(Value::None)
+        }
+        StaticStrings::Copy => {
+            args.check_zero_args("bytearray.copy", heap)?;
+            let heap_id = heap.allocate(HeapData::ByteArray(bytearray.clone()))?;
+            Ok(Value::Ref(heap_id))
+        }
+        _ => {
+            let result = call_bytes_method_impl(&bytearray.0, Type::ByteArray, method, args, heap, interns)?;
+            bytes_result_to_bytearray(result, heap)
+        }
+    }
+}
+
+/// Implements Python's `bytearray.insert(index, item)` method.
+///
+/// Negative indices count from the end and out-of-range indices are clamped, as for lists.
+fn bytearray_insert(
+    bytearray: &mut ByteArray,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> RunResult<Value> {
+    let (index_obj, item) = args.get_two_args("bytearray.insert", heap)?;
+    let index = index_obj.as_int(heap);
+    index_obj.drop_with_heap(heap);
+    let byte = byte_from_value(&item, heap);
+    item.drop_with_heap(heap);
+    let (index, byte) = (index?, byte?);
+
+    let len = bytearray.0.len();
+    let len_i64 = i64::try_from(len).expect("bytearray length exceeds i64::MAX");
+    let index = if index < 0 {
+        usize::try_from(index + len_i64).unwrap_or(0)
+    } else {
+        usize::try_from(index).unwrap_or(len).min(len)
+    };
+    bytearray.0.insert(index, byte);
+    Ok(Value::None)
+}
+
+/// Implements Python's `bytearray.pop([index])` method.
+///
+/// Removes the byte at the given index (default: -1) and returns it as an int.
+fn bytearray_pop(
+    bytearray: &mut ByteArray,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> RunResult<Value> {
+    let index = if let Some(v) = args.get_zero_one_arg("bytearray.pop", heap)? {
+        let result = v.as_int(heap);
+        v.drop_with_heap(heap);
+        result?
+    } else {
+        -1
+    };
+
+    if bytearray.0.is_empty() {
+        return Err(SimpleException::new_msg(ExcType::IndexError, "pop from empty bytearray").into());
+    }
+    let len = i64::try_from(bytearray.0.len()).expect("bytearray length exceeds i64::MAX");
+    let normalized = if index < 0 { index + len } else { index };
+    let Ok(index) = usize::try_from(normalized) else {
+        return Err(ExcType::index_error_pop_out_of_range());
+    };
+    if index >= bytearray.0.len() {
+        return Err(ExcType::index_error_pop_out_of_range());
+    }
+    Ok(Value::Int(i64::from(bytearray.0.remove(index))))
+}
+
+/// Converts the result of a shared `bytes` method into its `bytearray` equivalent.
+///
+/// `bytes` results become `bytearray`s, as do `bytes` items of list and tuple results
+/// (from `split()`, `partition()` and friends). Other values are returned unchanged.
+/// Takes ownership of `value`, which must be freshly allocated by the method.
+pub(crate) fn bytes_result_to_bytearray(value: Value, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let Value::Ref(id) = &value else {
+        return Ok(value);
+    };
+    let id = *id;
+
+    match heap.get_mut(id) {
+        HeapData::Bytes(bytes) => {
+            let bytes = std::mem::take(bytes.as_vec_mut());
+            value.drop_with_heap(heap);
+            let heap_id = heap.allocate(HeapData::ByteArray(ByteArray::new(bytes)))?;
+            Ok(Value::Ref(heap_id))
+        }
+        HeapData::List(list) => {
+            let items = std::mem::take(list.as_vec_mut());
+            let items = items_to_bytearray(items, heap);
+            let HeapData::List(list) = heap.get_mut(id) else {
+                unreachable!("list result changed type during conversion");
+            };
+            *list.as_vec_mut() = items?;
+            Ok(value)
+        }
+        HeapData::Tuple(tuple) => {
+            let items: Vec<Value> = tuple.as_vec().iter().map(Value::copy_for_extend).collect();
+            for item in &items {
+                if let Value::Ref(item_id) = item {
+                    heap.inc_ref(*item_id);
+                }
+            }
+            value.drop_with_heap(heap);
+            let items = items_to_bytearray(items, heap)?;
+            Ok(allocate_tuple(items.into_iter().collect(), heap)?)
+        }
+        _ => Ok(value),
+    }
+}
+
+/// Converts each `bytes` item to a `bytearray`, dropping the remaining items on error.
+fn items_to_bytearray(items: Vec<Value>, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Vec<Value>> {
+    let mut converted = Vec::with_capacity(items.len());
+    let mut items = items.into_iter();
+    while let Some(item) = items.next() {
+        match bytes_result_to_bytearray(item, heap) {
+            Ok(item) => converted.push(item),
+            Err(e) => {
+                converted.drop_with_heap(heap);
+                items.drop_with_heap(heap);
+                return Err(e);
+            }
+        }
+    }
+    Ok(converted)
+}
+
+/// Concatenates two byte sequences into a new bytearray.
+pub(crate) fn concat_bytearray(
+    left: &[u8],
+    right: &[u8],
+    heap: &mut Heap<impl ResourceTracker>,
+) -> Result<Value, ResourceError> {
+    let mut bytes = Vec::with_capacity(left.len() + right.len());
+    bytes.extend_from_slice(left);
+    bytes.extend_from_slice(right);
+    let heap_id = heap.allocate(HeapData::ByteArray(ByteArray::new(bytes)))?;
+    Ok(Value::Ref(heap_id))
+}
+
+/// Builds the contents of `bytearray(source)` for a single argument.
+///
+/// Takes ownership of `source`.
+fn bytearray_from_source(
+    source: Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Vec<u8>> {
+    match &source {
+        Value::Int(n) => zeroed_bytes(*n, heap),
+        Value::Bool(b) => Ok(vec![0u8; usize::from(*b)]),
+        Value::InternString(_) => Err(ExcType::type_error("string argument without an encoding")),
+        Value::Ref(id) if matches!(heap.get(*id), HeapData::Str(_)) => {
+            source.drop_with_heap(heap);
+            Err(ExcType::type_error("string argument without an encoding"))
+        }
+        _ => bytes_from_iterable(source, heap, interns, ExcType::type_error_bytearray_init),
+    }
+}
+
+/// Encodes a string for `bytearray(string, encoding)`. Only UTF-8 is supported.
+fn encode_str(
+    source: &Value,
+    encoding: &Value,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Vec<u8>> {
+    let Some(string) = source.as_either_str(heap) else {
+        return Err(ExcType::type_error("encoding without a string argument"));
+    };
+    let Some(encoding) = encoding.as_either_str(heap) else {
+        return Err(ExcType::type_error(format!(
+            "bytearray() argument 'encoding' must be str, not {}",
+            encoding.py_type(heap)
+        )));
+    };
+    let encoding = encoding.as_str(interns);
+    if !matches!(encoding.to_lowercase().as_str(), "utf-8" | "utf8" | "utf_8") {
+        return Err(ExcType::lookup_error_unknown_encoding(encoding));
+    }
+    Ok(string.as_str(interns).as_bytes().to_vec())
+}
+
+/// Collects the bytes of a bytes-like object or an iterable of ints.
+///
+/// `not_iterable` builds the error raised when `value` is neither, from its type.
+/// Takes ownership of `value`.
+fn bytes_from_iterable(
+    value: Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+    not_iterable: impl FnOnce(Type) -> RunError,
+) -> RunResult<Vec<u8>> {
+    let bytes_like = match &value {
+        Value::InternBytes(id) => Some(interns.get_bytes(*id).to_vec()),
+        Value::Ref(id) => heap.get(*id).as_bytes_like().map(<[u8]>::to_vec),
+        _ => None,
+    };
+    if let Some(bytes) = bytes_like {
+        value.drop_with_heap(heap);
+        return Ok(bytes);
+    }
+
+    // Strings iterate as characters, which can never be bytes
+    let value_type = value.py_type(heap);
+    if value_type == Type::Str {
+        value.drop_with_heap(heap);
+        return Err(not_iterable(value_type));
+    }
+    let Ok(mut iter) = MontyIter::new(value, heap, interns) else {
+        return Err(not_iterable(value_type));
+    };
+
+    let mut bytes = Vec::new();
+    loop {
+        let item = match iter.for_next(heap, interns) {
+            Ok(Some(item)) => item,
+            Ok(None) => break,
+            Err(e) => {
+                iter.drop_with_heap(heap);
+                return Err(e);
+            }
+        };
+        let byte = byte_from_value(&item, heap);
+        item.drop_with_heap(heap);
+        match byte {
+            Ok(byte) => bytes.push(byte),
+            Err(e) => {
+                iter.drop_with_heap(heap);
+                return Err(e);
+            }
+        }
+    }
+    iter.drop_with_heap(heap);
+    Ok(bytes)
+}
+
+/// Extracts an integer index from a subscript key.
+fn key_to_index(key: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<i64> {
+    match key {
+        Value::Int(i) => Ok(*i),
+        Value::Bool(b) => Ok(i64::from(*b)),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => li.to_i64().ok_or_else(ExcType::index_error_int_too_large),
+            _ => Err(ExcType::type_error_bytearray_indices(key.py_type(heap))),
+        },
+        _ => Err(ExcType::type_error_bytearray_indices(key.py_type(heap))),
+    }
+}
+
+/// Normalizes a possibly negative index, raising `IndexError` if it is out of range.
+fn normalize_index(index: i64, len: usize) -> RunResult<usize> {
+    let len_i64 = i64::try_from(len).expect("bytearray length exceeds i64::MAX");
+    let normalized = if index < 0 { index + len_i64 } else { index };
+    usize::try_from(normalized)
+        .ok()
+        .filter(|&i| i < len)
+        .ok_or_else(ExcType::bytearray_index_error)
+}
+
+/// Returns the positions selected by a slice, in slice order.
+///
+/// Walks the indices the same way as `get_bytes_slice`, including its `len + 1`
+/// sentinel for negative steps that run to the beginning.
+fn slice_positions(start: usize, stop: usize, step: i64, len: usize) -> Vec<usize> {
+    let mut positions = Vec::new();
+    if let Ok(step_usize) = usize::try_from(step) {
+        let mut i = start;
+        while i < stop && i < len {
+            positions.push(i);
+            i += step_usize;
+        }
+    } else {
+        let step_abs = usize::try_from(-step).expect("step is negative so -step is positive");
+        let stop = if stop > len { None } else { Some(stop) };
+        let mut i = start;
+        while i < len && stop.is_none_or(|stop| i > stop) {
+            positions.push(i);
+            match i.checked_sub(step_abs) {
+                Some(next) => i = next,
+                None => break,
+            }
+        }
+    }
+    positions
+}
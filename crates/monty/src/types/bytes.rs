@@ -76,7 +76,7 @@ use crate::{
     exception_private::{ExcType, RunResult, SimpleException},
     heap::{DropWithHeap, Heap, HeapData, HeapId},
     intern::{Interns, StaticStrings, StringId},
-    resource::ResourceTracker,
+    resource::{LARGE_RESULT_THRESHOLD, ResourceTracker},
     types::List,
     value::{EitherStr, Value},
 };
@@ -111,6 +111,35 @@ pub fn get_byte_at_index(bytes: &[u8], index: i64) -> Option<u8> {
     Some(bytes[idx])
 }
 
+/// Converts an int value to a single byte, as for `bytearray.append()` or `x in b'...'`.
+///
+/// Raises `ValueError` for ints outside `range(0, 256)` and `TypeError` for non-ints.
+pub(crate) fn byte_from_value(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<u8> {
+    let n = match value {
+        Value::Int(n) => *n,
+        Value::Bool(b) => i64::from(*b),
+        Value::Ref(id) if matches!(heap.get(*id), HeapData::LongInt(_)) => {
+            return Err(ExcType::value_error_byte_range());
+        }
+        _ => return Err(ExcType::type_error_not_integer(value.py_type(heap))),
+    };
+    u8::try_from(n).map_err(|_| ExcType::value_error_byte_range())
+}
+
+/// Creates the `n` zero bytes of `bytes(n)` or `bytearray(n)`.
+///
+/// Large sizes are checked with the resource tracker before anything is allocated.
+pub(crate) fn zeroed_bytes(n: i64, heap: &Heap<impl ResourceTracker>) -> RunResult<Vec<u8>> {
+    if n < 0 {
+        return Err(ExcType::value_error_negative_bytes_count());
+    }
+    let size = usize::try_from(n).unwrap_or(usize::MAX);
+    if size > LARGE_RESULT_THRESHOLD {
+        heap.tracker().check_large_result(size)?;
+    }
+    Ok(vec![0u8; size])
+}
+
 /// Extracts a slice of a byte array.
 ///
 /// Handles both positive and negative step values. For negative step,
@@ -185,7 +214,7 @@ impl Bytes {
     /// - `bytes()` with no args returns empty bytes
     /// - `bytes(int)` returns bytes of that length filled with zeros
     /// - `bytes(string)` encodes the string as UTF-8 (simplified, no encoding param)
-    /// - `bytes(bytes)` returns a copy of the bytes
+    /// - `bytes(bytes)` or `bytes(bytearray)` returns a copy of the bytes
     ///
     /// Note: Full Python semantics for bytes() are more complex (encoding, errors params).
     pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
@@ -198,11 +227,7 @@ impl Bytes {
             Some(v) => {
                 let result = match &v {
                     Value::Int(n) => {
-                        if *n < 0 {
-                            return Err(ExcType::value_error_negative_bytes_count());
-                        }
-                        let size = usize::try_from(*n).expect("bytes count validated non-negative");
-                        let bytes = vec![0u8; size];
+                        let bytes = zeroed_bytes(*n, heap)?;
                         heap.allocate(HeapData::Bytes(Self::new(bytes)))
                     }
                     Value::InternString(string_id) => {
@@ -216,6 +241,7 @@ impl Bytes {
                     Value::Ref(id) => match heap.get(*id) {
                         HeapData::Str(s) => heap.allocate(HeapData::Bytes(Self::new(s.as_str().as_bytes().to_vec()))),
                         HeapData::Bytes(b) => heap.allocate(HeapData::Bytes(Self::new(b.as_slice().to_vec()))),
+                        HeapData::ByteArray(b) => heap.allocate(HeapData::Bytes(Self::new(b.as_slice().to_vec()))),
                         _ => {
                             let err = ExcType::type_error_bytes_init(v.py_type(heap));
                             v.drop_with_heap(heap);
@@ -331,7 +357,7 @@ impl PyTrait for Bytes {
             return Err(ExcType::attribute_error(Type::Bytes, attr.as_str(interns)));
         };
 
-        call_bytes_method_impl(self.as_slice(), method, args, heap, interns)
+        call_bytes_method_impl(self.as_slice(), Type::Bytes, method, args, heap, interns)
     }
 }
 
@@ -350,16 +376,18 @@ pub fn call_bytes_method(
         args.drop_with_heap(heap);
         return Err(ExcType::attribute_error(Type::Bytes, interns.get_str(method_id)));
     };
-    call_bytes_method_impl(bytes, method, args, heap, interns)
+    call_bytes_method_impl(bytes, Type::Bytes, method, args, heap, interns)
 }
 
 /// Calls a bytes method on a byte slice.
 ///
 /// This is the unified implementation for bytes method calls, used by both
 /// heap-allocated `Bytes` (via `py_call_attr`) and interned bytes literals
-/// (`Value::InternBytes`).
-fn call_bytes_method_impl(
+/// (`Value::InternBytes`). `bytearray` also delegates its non-mutating methods here,
+/// passing its own type for error messages and converting the results afterwards.
+pub(crate) fn call_bytes_method_impl(
     bytes: &[u8],
+    py_type: Type,
     method: StaticStrings,
     args: ArgValues,
     heap: &mut Heap<impl ResourceTracker>,
@@ -459,7 +487,7 @@ fn call_bytes_method_impl(
         StaticStrings::Fromhex => bytes_fromhex(args, heap, interns),
         _ => {
             args.drop_with_heap(heap);
-            Err(ExcType::attribute_error(Type::Bytes, method.into()))
+            Err(ExcType::attribute_error(py_type, method.into()))
         }
     }
 }
@@ -640,6 +668,32 @@ fn find_subsequence(haystack: &[u8], needle: &[u8]) -> Option<usize> {
     haystack.windows(needle.len()).position(|window| window == needle)
 }
 
+/// Implements the `in` operator for `bytes` and `bytearray`.
+///
+/// An int item tests for a single byte value; a bytes-like item tests for a subsequence.
+pub(crate) fn bytes_contains(
+    bytes: &[u8],
+    item: &Value,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<bool> {
+    let needle = match item {
+        Value::InternBytes(id) => Some(interns.get_bytes(*id)),
+        Value::Ref(id) => heap.get(*id).as_bytes_like(),
+        _ => None,
+    };
+    if let Some(needle) = needle {
+        return Ok(needle.is_empty() || find_subsequence(bytes, needle).is_some());
+    }
+    if item.py_type(heap) == Type::Int || matches!(item, Value::Bool(_)) {
+        return Ok(bytes.contains(&byte_from_value(item, heap)?));
+    }
+    Err(ExcType::type_error(format!(
+        "a bytes-like object is required, not '{}'",
+        item.py_type(heap)
+    )))
+}
+
 /// Implements Python's `bytes.index(sub[, start[, end]])` method.
 ///
 /// Like find(), but raises ValueError if the subsequence is not found.
@@ -832,6 +886,7 @@ fn extract_bytes_for_prefix_suffix(
         ))),
         Value::Ref(id) => match heap.get(*id) {
             HeapData::Bytes(b) => Ok(PrefixSuffixArg::Single(b.as_slice().to_vec())),
+            HeapData::ByteArray(b) => Ok(PrefixSuffixArg::Single(b.as_slice().to_vec())),
             HeapData::Str(_) => Err(ExcType::type_error(format!(
                 "{method_name} first arg must be bytes or a tuple of bytes, not str"
             ))),
@@ -875,6 +930,7 @@ fn extract_single_bytes_for_prefix_suffix(
         Value::InternString(_) => Err(ExcType::type_error("expected bytes, not str")),
         Value::Ref(id) => match heap.get(*id) {
             HeapData::Bytes(b) => Ok(b.as_slice().to_vec()),
+            HeapData::ByteArray(b) => Ok(b.as_slice().to_vec()),
             _ => Err(ExcType::type_error("expected bytes")),
         },
         _ => Err(ExcType::type_error("expected bytes")),
@@ -891,6 +947,7 @@ fn extract_bytes_only(value: &Value, heap: &Heap<impl ResourceTracker>, interns:
         Value::InternString(_) => Err(ExcType::type_error("a bytes-like object is required, not 'str'")),
         Value::Ref(id) => match heap.get(*id) {
             HeapData::Bytes(b) => Ok(b.as_slice().to_vec()),
+            HeapData::ByteArray(b) => Ok(b.as_slice().to_vec()),
             HeapData::Str(_) => Err(ExcType::type_error("a bytes-like object is required, not 'str'")),
             _ => Err(ExcType::type_error("a bytes-like object is required")),
         },
@@ -2433,8 +2490,8 @@ fn bytes_join(
                 item.drop_with_heap(heap);
             }
             Value::Ref(heap_id) => {
-                if let HeapData::Bytes(b) = heap.get(*heap_id) {
-                    result.extend_from_slice(b.as_slice());
+                if let Some(b) = heap.get(*heap_id).as_bytes_like() {
+                    result.extend_from_slice(b);
                     item.drop_with_heap(heap);
                 } else {
                     let t = item.py_type(heap);
@@ -2598,7 +2655,7 @@ fn parse_bytes_hex_args(
                         .into());
                     }
                     st.to_owned()
-                } else if let HeapData::Bytes(b) = heap.get(*heap_id) {
+                } else if let Some(b) = heap.get(*heap_id).as_bytes_like() {
                     // Also accept single-byte bytes as separator
                     if b.len() != 1 {
                         v.drop_with_heap(heap);
@@ -2611,7 +2668,7 @@ fn parse_bytes_hex_args(
                         )
                         .into());
                     }
-                    (b.as_slice()[0] as char).to_string()
+                    (b[0] as char).to_string()
                 } else {
                     v.drop_with_heap(heap);
                     if let Some(bv) = bytes_per_sep_value {
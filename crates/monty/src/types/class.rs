@@ -0,0 +1,813 @@
+//! User-defined classes created by `class` statements.
+//!
+//! A class statement produces a [`ClassObject`]; calling it creates an [`Instance`].
+//! Functions found on a class are bound to the instance on attribute access, producing
+//! a [`BoundMethod`], while `super()` produces a [`SuperProxy`] that resolves attributes
+//! starting from the base of the class which defined the running method.
+//!
+//! Special methods like `__init__`, `__repr__` and `__eq__` are Python code, so they are
+//! invoked by the VM. The types here only provide storage and attribute resolution.
+
+use std::{borrow::Cow, fmt::Write};
+
+use ahash::AHashSet;
+
+use super::{
+    Dict, PyTrait,
+    r#enum::{self, EnumInfo, EnumKind},
+};
+use crate::{
+    exception_private::{ExcType, RunError, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{FunctionId, Interns, StaticStrings, StringId},
+    resource::ResourceTracker,
+    types::Type,
+    value::Value,
+};
+
+/// A user-defined class.
+///
+/// # Fields
+/// - `name`: The class name from the `class` statement
+/// - `base`: The single base class, if any (an explicit `object` base is stored as `None`)
+/// - `attrs`: Class attributes, including methods, in definition order
+/// - `enum_info`: The members of an enum class (see [`crate::types::r#enum`])
+///
+/// # Hashability
+/// Classes are hashable by identity, like all Python type objects.
+///
+/// # Reference Counting
+/// The class owns a reference to its base class and to every key and value in `attrs`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ClassObject {
+    /// The class name (e.g., "Point").
+    name: StringId,
+    /// The base class, if the class has one.
+    base: Option<HeapId>,
+    /// Class attributes and methods.
+    attrs: Dict,
+    /// Set for classes deriving from `enum.Enum`, `enum.IntEnum` or `enum.StrEnum`.
+    enum_info: Option<EnumInfo>,
+}
+
+impl ClassObject {
+    /// Creates a new class, which is an enum class if `enum_kind` is set.
+    ///
+    /// Ownership of the `base` reference and of `attrs` is transferred to the class.
+    #[must_use]
+    pub fn new(name: StringId, base: Option<HeapId>, attrs: Dict, enum_kind: Option<EnumKind>) -> Self {
+        Self {
+            name,
+            base,
+            attrs,
+            enum_info: enum_kind.map(EnumInfo::new),
+        }
+    }
+
+    /// Returns the class name.
+    #[must_use]
+    pub fn name(&self) -> StringId {
+        self.name
+    }
+
+    /// Returns the base class, if any.
+    #[must_use]
+    pub fn base(&self) -> Option<HeapId> {
+        self.base
+    }
+
+    /// Returns a reference to the class attributes.
+    #[must_use]
+    pub fn attrs(&self) -> &Dict {
+        &self.attrs
+    }
+
+    /// Returns the enum state, if this is an enum class.
+    #[must_use]
+    pub fn enum_info(&self) -> Option<&EnumInfo> {
+        self.enum_info.as_ref()
+    }
+
+    /// Records the member names of an enum class, once its members have been created.
+    pub fn set_enum_members(&mut self, members: Vec<StringId>) {
+        if let Some(info) = &mut self.enum_info {
+            info.set_members(members);
+        }
+    }
+
+    /// Returns whether this class holds any heap references.
+    #[inline]
+    #[must_use]
+    pub fn has_refs(&self) -> bool {
+        self.base.is_some() || self.attrs.has_refs()
+    }
+
+    /// Sets a class attribute, returning the previous value if there was one.
+    ///
+    /// The caller transfers ownership of both `name` and `value`.
+    pub fn set_attr(
+        &mut self,
+        name: Value,
+        value: Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Option<Value>> {
+        self.attrs.set(name, value, heap, interns)
+    }
+
+    /// Removes a class attribute, returning its name and value if it was set.
+    ///
+    /// The caller takes ownership of the returned values.
+    pub fn pop_attr(
+        &mut self,
+        name: &Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Option<(Value, Value)>> {
+        self.attrs.pop(name, heap, interns)
+    }
+}
+
+impl PyTrait for ClassObject {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Type
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.attrs.py_estimate_size()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        // Enum classes contain their members
+        self.enum_info.as_ref().map(|info| info.members().len())
+    }
+
+    fn py_eq(&self, _other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        // Classes compare by identity, which is handled at the Value level
+        false
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        if let Some(base) = self.base {
+            stack.push(base);
+        }
+        self.attrs.py_dec_ref_ids(stack);
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        interns: &Interns,
+    ) -> std::fmt::Result {
+        if self.enum_info.is_some() {
+            write!(f, "<enum '{}'>", interns.get_str(self.name))
+        } else {
+            write!(f, "<class '__main__.{}'>", interns.get_str(self.name))
+        }
+    }
+
+    fn py_getitem(&self, key: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+        if self.enum_info.is_some() {
+            r#enum::getitem(self, key, heap, interns)
+        } else {
+            Err(ExcType::type_error_not_sub(Type::Type))
+        }
+    }
+}
+
+/// An instance of a user-defined class.
+///
+/// # Fields
+/// - `class_id`: The class this is an instance of
+/// - `attrs`: Instance attributes, usually assigned in `__init__`
+///
+/// # Hashability
+/// Instances hash by identity unless their class sets `__hash__ = None`, which happens
+/// implicitly when a class defines `__eq__` without `__hash__`. A user-defined
+/// `__hash__` is called by the VM, which caches its result on the heap for dict and set
+/// storage, and compares stored keys with the same hash using `__eq__`. Dict and set
+/// displays, comprehensions, subscripts, `in`, methods taking a key like `dict.get()` and
+/// `set.add()`, and `set()` of a list or tuple look keys up this way. Other operations,
+/// like `|`, `update()` or `dict()` of pairs, hash the instances but compare them by
+/// identity, and keys which aren't instances are never compared with stored instances.
+///
+/// # Reference Counting
+/// The instance owns a reference to its class and to every key and value in `attrs`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Instance {
+    /// The class of this instance.
+    class_id: HeapId,
+    /// Instance attributes.
+    attrs: Dict,
+}
+
+impl Instance {
+    /// Creates a new instance with no attributes.
+    ///
+    /// Ownership of the `class_id` reference is transferred to the instance.
+    #[must_use]
+    pub fn new(class_id: HeapId) -> Self {
+        Self {
+            class_id,
+            attrs: Dict::new(),
+        }
+    }
+
+    /// Returns the class of this instance.
+    #[must_use]
+    pub fn class_id(&self) -> HeapId {
+        self.class_id
+    }
+
+    /// Returns a reference to the instance attributes.
+    #[must_use]
+    pub fn attrs(&self) -> &Dict {
+        &self.attrs
+    }
+
+    /// Sets an instance attribute, returning the previous value if there was one.
+    ///
+    /// The caller transfers ownership of both `name` and `value`.
+    pub fn set_attr(
+        &mut self,
+        name: Value,
+        value: Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Option<Value>> {
+        self.attrs.set(name, value, heap, interns)
+    }
+
+    /// Removes an instance attribute, returning its name and value if it was set.
+    ///
+    /// The caller takes ownership of the returned values.
+    pub fn pop_attr(
+        &mut self,
+        name: &Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Option<(Value, Value)>> {
+        self.attrs.pop(name, heap, interns)
+    }
+
+    /// Writes the default `object.__repr__` output, e.g. `<__main__.Point object at 0x...>`.
+    pub fn default_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        heap: &Heap<impl ResourceTracker>,
+        interns: &Interns,
+        py_id: usize,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "<__main__.{} object at 0x{py_id:x}>",
+            interns.get_str(class_name(self.class_id, heap))
+        )
+    }
+}
+
+impl PyTrait for Instance {
+    fn py_type(&self, heap: &Heap<impl ResourceTracker>) -> Type {
+        // Enum members are instances of the `enum` module class their class derives from
+        r#enum::enum_kind(self.class_id, heap).map_or(Type::Instance, EnumKind::base_type)
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.attrs.py_estimate_size()
+    }
+
+    fn py_len(&self, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> Option<usize> {
+        // `StrEnum` members have the length of their values
+        r#enum::instance_mixin_value(self, heap, interns).and_then(|value| value.py_len(heap, interns))
+    }
+
+    fn py_eq(&self, _other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        // Without a VM we can't run `__eq__`, so fall back to identity (handled at the Value level)
+        false
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        stack.push(self.class_id);
+        self.attrs.py_dec_ref_ids(stack);
+    }
+
+    fn py_bool(&self, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> bool {
+        // `IntEnum` and `StrEnum` members are as truthy as their values
+        r#enum::instance_mixin_value(self, heap, interns).is_none_or(|value| value.py_bool(heap, interns))
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        heap: &Heap<impl ResourceTracker>,
+        heap_ids: &mut AHashSet<HeapId>,
+        interns: &Interns,
+    ) -> std::fmt::Result {
+        if r#enum::is_member(self, heap) {
+            return r#enum::member_repr_fmt(self, f, heap, heap_ids, interns);
+        }
+        // The real id is filled in by `Value::py_repr_fmt`, which knows the HeapId
+        self.default_repr_fmt(f, heap, interns, 0)
+    }
+
+    fn py_str(&self, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> Cow<'static, str> {
+        match r#enum::member_str(self, heap, interns) {
+            Some(s) => Cow::Owned(s),
+            None => self.py_repr(heap, interns),
+        }
+    }
+
+    // The VM calls `__getitem__`, `__setitem__` and `__delitem__` on classes defining them,
+    // so subscripts only get here for classes which don't
+    fn py_getitem(&self, _key: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+        let name = interns.get_str(class_name(self.class_id, heap));
+        Err(ExcType::type_error(format!("'{name}' object is not subscriptable")))
+    }
+
+    fn py_setitem(
+        &mut self,
+        key: Value,
+        value: Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<()> {
+        key.drop_with_heap(heap);
+        value.drop_with_heap(heap);
+        let name = interns.get_str(class_name(self.class_id, heap));
+        Err(ExcType::type_error(format!(
+            "'{name}' object does not support item assignment"
+        )))
+    }
+
+    fn py_delitem(&mut self, key: Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<()> {
+        key.drop_with_heap(heap);
+        let name = interns.get_str(class_name(self.class_id, heap));
+        Err(ExcType::type_error(format!(
+            "'{name}' object doesn't support item deletion"
+        )))
+    }
+}
+
+/// A function bound to an instance, created by looking up a method on an instance
+/// (e.g. `m = point.dist`).
+///
+/// Calling it calls `func` with `self_obj` prepended to the arguments.
+///
+/// # Reference Counting
+/// Owns a reference to both the function and the instance.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct BoundMethod {
+    /// The function found on the class.
+    func: Value,
+    /// The instance the function is bound to.
+    self_obj: Value,
+}
+
+impl BoundMethod {
+    /// Creates a new bound method, taking ownership of both values.
+    #[must_use]
+    pub fn new(func: Value, self_obj: Value) -> Self {
+        Self { func, self_obj }
+    }
+
+    /// Returns the underlying function.
+    #[must_use]
+    pub fn func(&self) -> &Value {
+        &self.func
+    }
+
+    /// Returns the instance the method is bound to.
+    #[must_use]
+    pub fn self_obj(&self) -> &Value {
+        &self.self_obj
+    }
+
+    /// Returns whether this bound method holds any heap references.
+    #[inline]
+    #[must_use]
+    pub fn has_refs(&self) -> bool {
+        matches!(self.func, Value::Ref(_)) || matches!(self.self_obj, Value::Ref(_))
+    }
+}
+
+impl PyTrait for BoundMethod {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Method
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self.func.is(&other.func) && self.self_obj.is(&other.self_obj)
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        self.func.py_dec_ref_ids(stack);
+        self.self_obj.py_dec_ref_ids(stack);
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        heap: &Heap<impl ResourceTracker>,
+        heap_ids: &mut AHashSet<HeapId>,
+        interns: &Interns,
+    ) -> std::fmt::Result {
+        f.write_str("<bound method ")?;
+        if let Value::Ref(id) = &self.self_obj
+            && let HeapData::Instance(inst) = heap.get(*id)
+        {
+            write!(f, "{}.", interns.get_str(class_name(inst.class_id(), heap)))?;
+        }
+        if let Some(func_id) = function_id(&self.func, heap) {
+            f.write_str(interns.get_str(interns.get_function(func_id).name.name_id))?;
+        }
+        f.write_str(" of ")?;
+        self.self_obj.py_repr_fmt(f, heap, heap_ids, interns)?;
+        f.write_char('>')
+    }
+}
+
+/// The proxy object returned by `super()`.
+///
+/// Attribute lookups start at the base of `class_id` (the class that defined the method
+/// calling `super()`), and functions found there are bound to `self_obj`.
+///
+/// # Reference Counting
+/// Owns a reference to both the class and the instance.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct SuperProxy {
+    /// The class whose bases are searched.
+    class_id: HeapId,
+    /// The instance methods are bound to.
+    self_obj: Value,
+}
+
+impl SuperProxy {
+    /// Creates a new super proxy, taking ownership of both references.
+    #[must_use]
+    pub fn new(class_id: HeapId, self_obj: Value) -> Self {
+        Self { class_id, self_obj }
+    }
+
+    /// Returns the class whose bases are searched.
+    #[must_use]
+    pub fn class_id(&self) -> HeapId {
+        self.class_id
+    }
+
+    /// Returns the instance methods are bound to.
+    #[must_use]
+    pub fn self_obj(&self) -> &Value {
+        &self.self_obj
+    }
+}
+
+impl PyTrait for SuperProxy {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Super
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        false
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        stack.push(self.class_id);
+        self.self_obj.py_dec_ref_ids(stack);
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        heap: &Heap<impl ResourceTracker>,
+        heap_ids: &mut AHashSet<HeapId>,
+        interns: &Interns,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "<super: <class '{}'>, ",
+            interns.get_str(class_name(self.class_id, heap))
+        )?;
+        self.self_obj.py_repr_fmt(f, heap, heap_ids, interns)?;
+        f.write_char('>')
+    }
+}
+
+/// Result of resolving an attribute on a class, instance or `super()` proxy.
+pub(crate) enum ClassAttr {
+    /// A plain value: an instance attribute, a class attribute, or a function
+    /// accessed through the class itself.
+    Value(Value),
+    /// A function found on the class, to be called with `self_obj` as its first argument.
+    Method { func: Value, self_obj: Value },
+}
+
+/// Returns whether attribute access on this heap data goes through [`lookup_attr`].
+#[must_use]
+pub(crate) fn has_class_attrs(data: &HeapData) -> bool {
+    matches!(
+        data,
+        HeapData::ClassObject(_) | HeapData::Instance(_) | HeapData::SuperProxy(_)
+    )
+}
+
+/// Resolves an attribute on a class, instance or `super()` proxy.
+///
+/// Instances check their own attributes first, then their class and its bases;
+/// functions found on the class are returned as [`ClassAttr::Method`]. Returns `None`
+/// if the attribute doesn't exist or `heap_id` is not one of these types.
+///
+/// The returned values are owned (their refcounts have been incremented).
+pub(crate) fn lookup_attr(
+    heap_id: HeapId,
+    name_id: StringId,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Option<ClassAttr> {
+    let name = interns.get_str(name_id);
+    match heap.get(heap_id) {
+        HeapData::Instance(inst) => {
+            let class_id = inst.class_id();
+            if let Some(value) = inst.attrs().get_by_str(name, heap, interns) {
+                let value = value.copy_for_extend();
+                inc_ref_value(&value, heap);
+                return Some(ClassAttr::Value(value));
+            }
+            if name_id == StaticStrings::DunderClass {
+                heap.inc_ref(class_id);
+                return Some(ClassAttr::Value(Value::Ref(class_id)));
+            }
+            if let Some(value) = r#enum::member_property(inst, name_id, heap, interns) {
+                let value = value.copy_for_extend();
+                inc_ref_value(&value, heap);
+                return Some(ClassAttr::Value(value));
+            }
+            let found = class_lookup(Some(class_id), name, heap, interns)?.copy_for_extend();
+            inc_ref_value(&found, heap);
+            if is_method_function(&found, heap) {
+                heap.inc_ref(heap_id);
+                Some(ClassAttr::Method {
+                    func: found,
+                    self_obj: Value::Ref(heap_id),
+                })
+            } else {
+                Some(ClassAttr::Value(found))
+            }
+        }
+        HeapData::ClassObject(cls) => {
+            if name_id == StaticStrings::DunderName {
+                return Some(ClassAttr::Value(Value::InternString(cls.name())));
+            }
+            let found = class_lookup(Some(heap_id), name, heap, interns)?.copy_for_extend();
+            inc_ref_value(&found, heap);
+            Some(ClassAttr::Value(found))
+        }
+        HeapData::SuperProxy(proxy) => {
+            let self_obj = proxy.self_obj.copy_for_extend();
+            let start = match heap.get(proxy.class_id) {
+                HeapData::ClassObject(cls) => cls.base(),
+                _ => None,
+            };
+            let found = class_lookup(start, name, heap, interns)?.copy_for_extend();
+            inc_ref_value(&found, heap);
+            if is_method_function(&found, heap) {
+                inc_ref_value(&self_obj, heap);
+                Some(ClassAttr::Method { func: found, self_obj })
+            } else {
+                Some(ClassAttr::Value(found))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Looks up `name` on a class and its bases, returning a borrowed value.
+///
+/// `class_id` is the first class searched; `None` searches nothing. Callers that keep
+/// the value must copy it and increment its refcount.
+pub(crate) fn class_lookup<'h>(
+    class_id: Option<HeapId>,
+    name: &str,
+    heap: &'h Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Option<&'h Value> {
+    let mut current = class_id;
+    while let Some(id) = current {
+        let HeapData::ClassObject(cls) = heap.get(id) else {
+            return None;
+        };
+        if let Some(value) = cls.attrs().get_by_str(name, heap, interns) {
+            return Some(value);
+        }
+        current = cls.base();
+    }
+    None
+}
+
+/// Sets an attribute on a class or instance.
+///
+/// Returns `None` if `heap_id` is not a class or instance, in which case `value` is
+/// handed back to the caller. On success the previous value is dropped.
+pub(crate) fn set_attr(
+    heap_id: HeapId,
+    name_id: StringId,
+    value: Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Result<RunResult<()>, Value> {
+    if !matches!(heap.get(heap_id), HeapData::ClassObject(_) | HeapData::Instance(_)) {
+        return Err(value);
+    }
+    let name = Value::InternString(name_id);
+    Ok(heap.with_entry_mut(heap_id, |heap, data| {
+        let old = match data {
+            HeapData::ClassObject(cls) => cls.set_attr(name, value, heap, interns)?,
+            HeapData::Instance(inst) => inst.set_attr(name, value, heap, interns)?,
+            _ => unreachable!("type changed during borrow"),
+        };
+        if let Some(old) = old {
+            old.drop_with_heap(heap);
+        }
+        Ok(())
+    }))
+}
+
+/// Deletes an attribute from a class or instance.
+///
+/// Returns `None` if `heap_id` is not a class or instance. Only attributes stored on the
+/// object itself can be deleted; inherited class attributes raise `AttributeError`.
+pub(crate) fn del_attr(
+    heap_id: HeapId,
+    name_id: StringId,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Option<RunResult<()>> {
+    if !matches!(heap.get(heap_id), HeapData::ClassObject(_) | HeapData::Instance(_)) {
+        return None;
+    }
+    let name = Value::InternString(name_id);
+    let removed = heap.with_entry_mut(heap_id, |heap, data| match data {
+        HeapData::ClassObject(cls) => cls.pop_attr(&name, heap, interns),
+        HeapData::Instance(inst) => inst.pop_attr(&name, heap, interns),
+        _ => unreachable!("type changed during borrow"),
+    });
+    Some(match removed {
+        Ok(Some((old_name, old_value))) => {
+            old_name.drop_with_heap(heap);
+            old_value.drop_with_heap(heap);
+            Ok(())
+        }
+        Ok(None) => Err(attr_error(heap_id, name_id, heap, interns)),
+        Err(e) => Err(e),
+    })
+}
+
+/// Creates the `AttributeError` for a missing attribute on a class, instance or `super()` proxy.
+pub(crate) fn attr_error(
+    heap_id: HeapId,
+    name_id: StringId,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunError {
+    attr_error_str(heap_id, interns.get_str(name_id), heap, interns)
+}
+
+/// Creates the `AttributeError` for a missing attribute given by name rather than `StringId`.
+///
+/// Used for names built at runtime which were never interned.
+pub(crate) fn attr_error_str(
+    heap_id: HeapId,
+    attr: &str,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunError {
+    match heap.get(heap_id) {
+        HeapData::Instance(inst) => ExcType::attribute_error(interns.get_str(class_name(inst.class_id(), heap)), attr),
+        HeapData::ClassObject(cls) => ExcType::attribute_error_type(interns.get_str(cls.name()), attr),
+        other => ExcType::attribute_error(other.py_type(heap), attr),
+    }
+}
+
+/// Returns whether `class_id` is `target_id` or inherits from it.
+#[must_use]
+pub(crate) fn is_subclass(class_id: HeapId, target_id: HeapId, heap: &Heap<impl ResourceTracker>) -> bool {
+    let mut current = Some(class_id);
+    while let Some(id) = current {
+        if id == target_id {
+            return true;
+        }
+        current = match heap.get(id) {
+            HeapData::ClassObject(cls) => cls.base(),
+            _ => None,
+        };
+    }
+    false
+}
+
+/// Returns whether instances of `class_id` can be hashed.
+///
+/// Instances are unhashable when `__hash__` resolves to `None`.
+#[must_use]
+pub(crate) fn is_instance_hashable(class_id: HeapId, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> bool {
+    let name = interns.get_str(StaticStrings::DunderHash.into());
+    !matches!(class_lookup(Some(class_id), name, heap, interns), Some(Value::None))
+}
+
+/// Returns whether instances of `class_id` have a user-defined `__hash__`.
+///
+/// Dict and set storage can't call it, so these instances only hash once the VM has called it.
+#[must_use]
+pub(crate) fn has_custom_hash(class_id: HeapId, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> bool {
+    let name = interns.get_str(StaticStrings::DunderHash.into());
+    class_lookup(Some(class_id), name, heap, interns).is_some_and(|hash| !matches!(hash, Value::None))
+}
+
+/// Returns the error for a dict key or set element which can't be hashed.
+///
+/// `error` builds the error from the type name, which is the class name for instances.
+/// Instances with a user-defined `__hash__` the VM hasn't called get an error saying so instead.
+pub(crate) fn unhashable_error(
+    value: &Value,
+    error: impl FnOnce(&str) -> RunError,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunError {
+    if let Value::Ref(id) = value
+        && let HeapData::Instance(inst) = heap.get(*id)
+    {
+        let name = interns.get_str(class_name(inst.class_id(), heap));
+        if has_custom_hash(inst.class_id(), heap, interns) {
+            return ExcType::type_error(format!("'{name}' defines __hash__, which can't be called here"));
+        }
+        return error(name);
+    }
+    error(&value.py_type(heap).to_string())
+}
+
+/// Returns the class name for a class `HeapId`.
+pub(crate) fn class_name(class_id: HeapId, heap: &Heap<impl ResourceTracker>) -> StringId {
+    match heap.get(class_id) {
+        HeapData::ClassObject(cls) => cls.name(),
+        _ => StaticStrings::EmptyString.into(),
+    }
+}
+
+/// Returns whether a value is a Python function that becomes a bound method when
+/// looked up on an instance.
+///
+/// Builtin and external functions are not descriptors in Python, so they are not bound.
+/// Functions wrapped by `functools.lru_cache()` are, like the functions they wrap.
+#[must_use]
+pub(crate) fn is_method_function(value: &Value, heap: &Heap<impl ResourceTracker>) -> bool {
+    function_id(value, heap).is_some()
+        || matches!(value, Value::Ref(id) if matches!(heap.get(*id), HeapData::LruCacheWrapper(_)))
+}
+
+/// Returns the `FunctionId` of a user-defined function value, if it is one.
+#[must_use]
+pub(crate) fn function_id(value: &Value, heap: &Heap<impl ResourceTracker>) -> Option<FunctionId> {
+    match value {
+        Value::DefFunction(func_id) => Some(*func_id),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Closure(func_id, _, _) | HeapData::FunctionDefaults(func_id, _) => Some(*func_id),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Increments the refcount of a value copied with `copy_for_extend`.
+fn inc_ref_value(value: &Value, heap: &mut Heap<impl ResourceTracker>) {
+    if let Value::Ref(id) = value {
+        heap.inc_ref(*id);
+    }
+}
@@ -0,0 +1,417 @@
+//! Python `complex` type.
+//!
+//! Complex numbers are immutable pairs of floats stored on the heap. Arithmetic with
+//! `int`, `float` and `bool` operands promotes them to complex, following CPython's
+//! algorithms for division and integer powers so results match exactly.
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use super::{AttrCallResult, PyTrait, Type};
+use crate::{
+    args::ArgValues,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    resource::{ResourceError, ResourceTracker},
+    value::{EitherStr, Value},
+};
+
+/// Python complex value stored on the heap.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Complex {
+    real: f64,
+    imag: f64,
+}
+
+impl Complex {
+    /// Creates a new complex number from its real and imaginary parts.
+    #[must_use]
+    pub fn new(real: f64, imag: f64) -> Self {
+        Self { real, imag }
+    }
+
+    /// Returns the real part.
+    #[must_use]
+    pub fn real(self) -> f64 {
+        self.real
+    }
+
+    /// Returns the imaginary part.
+    #[must_use]
+    pub fn imag(self) -> f64 {
+        self.imag
+    }
+
+    /// Formats like CPython: `(1+2j)`, or just `2j` when the real part is `+0.0`.
+    pub fn repr_fmt(self, f: &mut impl Write) -> std::fmt::Result {
+        let imag = format_part(self.imag);
+        if self.real == 0.0 && self.real.is_sign_positive() {
+            return write!(f, "{imag}j");
+        }
+        let sign = if imag.starts_with('-') { "" } else { "+" };
+        write!(f, "({}{sign}{imag}j)", format_part(self.real))
+    }
+
+    /// Allocates this complex number on the heap.
+    pub fn into_value(self, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, ResourceError> {
+        Ok(Value::Ref(heap.allocate(HeapData::Complex(self))?))
+    }
+
+    /// Creates a complex number from the `complex()` constructor call.
+    ///
+    /// - `complex()` returns `0j`
+    /// - `complex(string)` parses strings like `'1+2j'`, `'-j'` or `'(3.5e2-1j)'`
+    /// - `complex(real[, imag])` accepts ints, floats and complex numbers for either part,
+    ///   computing `real + imag * 1j`
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let (real, imag) = args.get_zero_one_two_args("complex", heap)?;
+        let result = complex_from_args(real.as_ref(), imag.as_ref(), heap, interns);
+        real.drop_with_heap(heap);
+        imag.drop_with_heap(heap);
+        Ok(result?.into_value(heap)?)
+    }
+
+    /// Returns `abs(self)`, the magnitude of the complex number.
+    #[must_use]
+    pub fn abs(self) -> f64 {
+        self.real.hypot(self.imag)
+    }
+
+    /// Returns `-self`.
+    #[must_use]
+    pub fn neg(self) -> Self {
+        Self::new(-self.real, -self.imag)
+    }
+
+    /// Returns `self + other`.
+    #[must_use]
+    pub fn add(self, other: Self) -> Self {
+        Self::new(self.real + other.real, self.imag + other.imag)
+    }
+
+    /// Returns `self - other`.
+    #[must_use]
+    pub fn sub(self, other: Self) -> Self {
+        Self::new(self.real - other.real, self.imag - other.imag)
+    }
+
+    /// Returns `self * other`.
+    #[must_use]
+    pub fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.real * other.real - self.imag * other.imag,
+            self.real * other.imag + self.imag * other.real,
+        )
+    }
+
+    /// Divides using Smith's algorithm, as CPython does. Returns `None` for division by zero.
+    #[must_use]
+    pub fn div(self, other: Self) -> Option<Self> {
+        let abs_real = other.real.abs();
+        let abs_imag = other.imag.abs();
+        if abs_real >= abs_imag {
+            if abs_real == 0.0 {
+                return None;
+            }
+            let ratio = other.imag / other.real;
+            let denom = other.real + other.imag * ratio;
+            Some(Self::new(
+                (self.real + self.imag * ratio) / denom,
+                (self.imag - self.real * ratio) / denom,
+            ))
+        } else if abs_imag >= abs_real {
+            let ratio = other.real / other.imag;
+            let denom = other.real * ratio + other.imag;
+            Some(Self::new(
+                (self.real * ratio + self.imag) / denom,
+                (self.imag * ratio - self.real) / denom,
+            ))
+        } else {
+            // At least one of the divisor's parts is NaN
+            Some(Self::new(f64::NAN, f64::NAN))
+        }
+    }
+
+    /// Raises to a power, using repeated multiplication for small integer exponents
+    /// as CPython does. Returns `None` when raising zero to a negative or complex power.
+    #[must_use]
+    pub fn pow(self, exp: Self) -> Option<Self> {
+        if exp.imag == 0.0 && exp.real == exp.real.trunc() && exp.real.abs() <= 100.0 {
+            #[expect(
+                clippy::cast_possible_truncation,
+                reason = "exponent checked to be integral in [-100, 100]"
+            )]
+            let n = exp.real as i32;
+            let power = self.powu(n.unsigned_abs());
+            return if n >= 0 {
+                Some(power)
+            } else {
+                Self::new(1.0, 0.0).div(power)
+            };
+        }
+        if exp.real == 0.0 && exp.imag == 0.0 {
+            return Some(Self::new(1.0, 0.0));
+        }
+        if self.real == 0.0 && self.imag == 0.0 {
+            return if exp.imag != 0.0 || exp.real < 0.0 {
+                None
+            } else {
+                Some(Self::new(0.0, 0.0))
+            };
+        }
+        let magnitude = self.abs();
+        let angle = self.imag.atan2(self.real);
+        let mut len = magnitude.powf(exp.real);
+        let mut phase = angle * exp.real;
+        if exp.imag != 0.0 {
+            len /= (angle * exp.imag).exp();
+            phase += exp.imag * magnitude.ln();
+        }
+        Some(Self::new(len * phase.cos(), len * phase.sin()))
+    }
+
+    /// Raises to a non-negative integer power by binary exponentiation.
+    fn powu(self, n: u32) -> Self {
+        let mut result = Self::new(1.0, 0.0);
+        let mut power = self;
+        let mut mask = 1u32;
+        while mask > 0 && n >= mask {
+            if n & mask != 0 {
+                result = result.mul(power);
+            }
+            mask <<= 1;
+            power = power.mul(power);
+        }
+        result
+    }
+}
+
+impl PyTrait for Complex {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Complex
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self == other
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No-op: complex numbers don't hold Value references
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self.real != 0.0 || self.imag != 0.0
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        self.repr_fmt(f)
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        if attr.static_string() == Some(StaticStrings::Conjugate) {
+            args.check_zero_args("complex.conjugate", heap)?;
+            return Ok(Self::new(self.real, -self.imag).into_value(heap)?);
+        }
+        args.drop_with_heap(heap);
+        Err(ExcType::attribute_error(Type::Complex, attr.as_str(interns)))
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        _heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = match StaticStrings::from_string_id(attr_id) {
+            Some(StaticStrings::Real) => self.real,
+            Some(StaticStrings::Imag) => self.imag,
+            _ => return Ok(None),
+        };
+        Ok(Some(AttrCallResult::Value(Value::Float(value))))
+    }
+}
+
+/// Formats one part of a complex repr like a float repr, but without a trailing `.0`.
+fn format_part(value: f64) -> String {
+    if value.is_nan() {
+        "nan".to_owned()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Converts both operands of a binary operator to complex numbers.
+///
+/// Returns `None` unless at least one operand is complex and the other is an int,
+/// float, bool or complex, so callers can fall through to their usual handling.
+#[must_use]
+pub(crate) fn complex_operands(
+    lhs: &Value,
+    rhs: &Value,
+    heap: &Heap<impl ResourceTracker>,
+) -> Option<(Complex, Complex)> {
+    if !is_complex(lhs, heap) && !is_complex(rhs, heap) {
+        return None;
+    }
+    Some((as_complex(lhs, heap)?, as_complex(rhs, heap)?))
+}
+
+/// Returns whether `value` is a complex number.
+#[must_use]
+pub(crate) fn is_complex(value: &Value, heap: &Heap<impl ResourceTracker>) -> bool {
+    matches!(value, Value::Ref(id) if matches!(heap.get(*id), HeapData::Complex(_)))
+}
+
+/// Converts a numeric value (int, float, bool or complex) to a complex number.
+#[must_use]
+pub(crate) fn as_complex(value: &Value, heap: &Heap<impl ResourceTracker>) -> Option<Complex> {
+    match value {
+        Value::Int(i) => Some(Complex::new(*i as f64, 0.0)),
+        Value::Float(f) => Some(Complex::new(*f, 0.0)),
+        Value::Bool(b) => Some(Complex::new(f64::from(*b), 0.0)),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Complex(c) => Some(*c),
+            HeapData::LongInt(li) => Some(Complex::new(li.to_f64().unwrap_or(f64::INFINITY), 0.0)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Computes the value of `complex(real, imag)`.
+fn complex_from_args(
+    real: Option<&Value>,
+    imag: Option<&Value>,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Complex> {
+    let Some(real) = real else {
+        return Ok(Complex::new(0.0, 0.0));
+    };
+    if let Some(s) = real.as_either_str(heap) {
+        if imag.is_some() {
+            return Err(ExcType::type_error(
+                "complex() can't take second arg if first is a string",
+            ));
+        }
+        return parse_complex_str(s.as_str(interns)).ok_or_else(|| {
+            SimpleException::new_msg(ExcType::ValueError, "complex() arg is a malformed string").into()
+        });
+    }
+    let Some(real) = as_complex(real, heap) else {
+        return Err(ExcType::type_error(format!(
+            "complex() first argument must be a string or a number, not '{}'",
+            real.py_type(heap)
+        )));
+    };
+    let Some(imag) = imag else {
+        return Ok(real);
+    };
+    if imag.as_either_str(heap).is_some() {
+        return Err(ExcType::type_error("complex() second arg can't be a string"));
+    }
+    let Some(imag) = as_complex(imag, heap) else {
+        return Err(ExcType::type_error(format!(
+            "complex() second argument must be a number, not '{}'",
+            imag.py_type(heap)
+        )));
+    };
+    // real + imag * 1j
+    Ok(Complex::new(real.real - imag.imag, real.imag + imag.real))
+}
+
+/// Parses a `complex()` string argument, returning `None` if it is malformed.
+///
+/// Accepts surrounding whitespace and parentheses, a real part, an imaginary part
+/// ending in `j` or `J`, or both (e.g. `'1.5-2e3j'`). A bare `j` means `1j`.
+fn parse_complex_str(s: &str) -> Option<Complex> {
+    let mut s = s.trim();
+    if let Some(inner) = s.strip_prefix('(').and_then(|rest| rest.strip_suffix(')')) {
+        s = inner.trim();
+    }
+    let bytes = s.as_bytes();
+
+    let (first, first_len) = parse_float_prefix(s)?;
+    let rest = &s[first_len..];
+    match rest.as_bytes().first() {
+        None => first.map(|real| Complex::new(real, 0.0)),
+        Some(b'j' | b'J') if rest.len() == 1 => Some(Complex::new(0.0, first.unwrap_or_else(|| unit(bytes[0])))),
+        Some(b'+' | b'-') => {
+            let real = first?;
+            let (imag, imag_len) = parse_float_prefix(rest)?;
+            let suffix = &rest[imag_len..];
+            if !suffix.eq_ignore_ascii_case("j") {
+                return None;
+            }
+            Some(Complex::new(real, imag.unwrap_or_else(|| unit(rest.as_bytes()[0]))))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the implicit coefficient of a bare `j`, `+j` or `-j`.
+fn unit(first: u8) -> f64 {
+    if first == b'-' { -1.0 } else { 1.0 }
+}
+
+/// Parses the longest float at the start of `s`.
+///
+/// Returns the parsed value (`None` if only a sign was found) and the number of bytes
+/// consumed, or `None` if `s` doesn't start with a sign or a float.
+fn parse_float_prefix(s: &str) -> Option<(Option<f64>, usize)> {
+    let bytes = s.as_bytes();
+    let sign_len = usize::from(matches!(bytes.first(), Some(b'+' | b'-')));
+    let body = &s[sign_len..];
+
+    // Special values
+    let lower = body.to_ascii_lowercase();
+    for (word, value) in [("infinity", f64::INFINITY), ("inf", f64::INFINITY), ("nan", f64::NAN)] {
+        if lower.starts_with(word) {
+            let value = if bytes[0] == b'-' { -value } else { value };
+            return Some((Some(value), sign_len + word.len()));
+        }
+    }
+
+    let body = body.as_bytes();
+    let digits = |start: usize| body[start..].iter().take_while(|b| b.is_ascii_digit()).count();
+    let int_len = digits(0);
+    let mut len = int_len;
+    let mut frac_len = 0;
+    if body.get(len) == Some(&b'.') {
+        frac_len = digits(len + 1);
+        len += 1 + frac_len;
+    }
+    if int_len == 0 && frac_len == 0 {
+        return (sign_len > 0 || body.first().is_some_and(|b| matches!(b, b'j' | b'J'))).then_some((None, sign_len));
+    }
+    if matches!(body.get(len), Some(b'e' | b'E')) {
+        let exp_sign_len = usize::from(matches!(body.get(len + 1), Some(b'+' | b'-')));
+        let exp_len = digits(len + 1 + exp_sign_len);
+        if exp_len > 0 {
+            len += 1 + exp_sign_len + exp_len;
+        }
+    }
+    let end = sign_len + len;
+    s[..end].parse().ok().map(|value| (Some(value), end))
+}
@@ -0,0 +1,465 @@
+//! Python `collections.Counter` type.
+//!
+//! A counter is a [`Dict`] of kind [`DictKind::Counter`] mapping elements to their counts,
+//! so it shares all dict storage and methods. This module holds what differs: the
+//! constructor and `update()`, which add counts rather than replace values, the
+//! counter-only methods, order-insensitive equality where missing keys count as zero, and
+//! the `+`, `-`, `&` and `|` operators between counters.
+
+use std::cmp::Ordering;
+
+use smallvec::smallvec;
+
+use super::{Dict, List, MontyIter, PyTrait, allocate_tuple, dict::DictKind};
+use crate::{
+    args::{ArgValues, KwargsValues, bind_args},
+    exception_private::{ExcType, RunResult},
+    expressions::Operator,
+    heap::{DropWithHeap, Heap, HeapData},
+    intern::{Interns, StaticStrings},
+    resource::{LARGE_RESULT_THRESHOLD, ResourceTracker},
+    value::Value,
+};
+
+/// Creates a counter from the `collections.Counter()` constructor call.
+///
+/// Accepts an optional iterable of elements to count, or a mapping of elements to counts,
+/// followed by keyword arguments which are counted like a mapping.
+pub(crate) fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let heap_id = match heap.allocate(HeapData::Dict(Dict::with_kind(DictKind::Counter))) {
+        Ok(id) => id,
+        Err(e) => {
+            args.drop_with_heap(heap);
+            return Err(e.into());
+        }
+    };
+    let result = heap.with_entry_mut(heap_id, |heap, data| {
+        let HeapData::Dict(dict) = data else {
+            unreachable!("just allocated a dict")
+        };
+        counter_update(dict, "Counter", args, false, heap, interns)
+    });
+    match result {
+        Ok(()) => Ok(Value::Ref(heap_id)),
+        Err(e) => {
+            Value::Ref(heap_id).drop_with_heap(heap);
+            Err(e)
+        }
+    }
+}
+
+/// Calls one of the methods only counters have, or `update()` which counters override.
+pub(super) fn call_counter_method(
+    dict: &mut Dict,
+    method: StaticStrings,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    match method {
+        StaticStrings::MostCommon => {
+            let [n] = bind_args("Counter.most_common", args, ["n"], 0, heap, interns)?;
+            counter_most_common(dict, n, heap)
+        }
+        StaticStrings::Elements => {
+            args.check_zero_args("Counter.elements", heap)?;
+            counter_elements(dict, heap)
+        }
+        StaticStrings::Update => {
+            counter_update(dict, "Counter.update", args, false, heap, interns)?;
+            Ok(Value::None)
+        }
+        StaticStrings::Subtract => {
+            counter_update(dict, "Counter.subtract", args, true, heap, interns)?;
+            Ok(Value::None)
+        }
+        StaticStrings::Total => {
+            args.check_zero_args("Counter.total", heap)?;
+            let mut total = Value::Int(0);
+            for (_, count) in dict.iter() {
+                let sum = add_counts(&total, count, false, heap, interns);
+                total.drop_with_heap(heap);
+                total = sum?;
+            }
+            Ok(total)
+        }
+        _ => unreachable!("not a counter method: {method:?}"),
+    }
+}
+
+/// Compares two counters for equality, treating missing elements as having a count of zero.
+pub(super) fn counter_eq(a: &Dict, b: &Dict, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> bool {
+    let zero = Value::Int(0);
+    for (this, other) in [(a, b), (b, a)] {
+        for (key, count) in this {
+            let other_count = match other.get(key, heap, interns) {
+                Ok(other_count) => other_count.unwrap_or(&zero),
+                Err(_) => return false,
+            };
+            if !count.py_eq(other_count, heap, interns) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Orders two counts, for listing the most common elements first.
+///
+/// Counts are compared as floats with a total order, so that sorting never sees an
+/// inconsistent comparison; counts that aren't numbers sort below all numbers.
+pub(super) fn compare_counts(a: &Value, b: &Value) -> Ordering {
+    count_sort_key(a).total_cmp(&count_sort_key(b))
+}
+
+/// Applies `+`, `-`, `&` or `|` when both operands are counters.
+///
+/// `+` and `-` add or subtract counts, `&` takes the minimum and `|` the maximum of each
+/// count. As in CPython, only elements with a positive resulting count are kept.
+///
+/// Returns `Ok(None)` for other operands or operators, so the caller can report the usual
+/// TypeError.
+pub(crate) fn binary_op(
+    lhs: &Value,
+    rhs: &Value,
+    op: &Operator,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Option<Value>> {
+    let (Value::Ref(lhs_id), Value::Ref(rhs_id)) = (lhs, rhs) else {
+        return Ok(None);
+    };
+    if !is_counter(heap.get(*lhs_id)) || !is_counter(heap.get(*rhs_id)) {
+        return Ok(None);
+    }
+    let op = match op {
+        Operator::Add => CountOp::Add,
+        Operator::Sub => CountOp::Sub,
+        Operator::BitAnd => CountOp::Min,
+        Operator::BitOr => CountOp::Max,
+        _ => return Ok(None),
+    };
+    let result = heap.with_two(*lhs_id, *rhs_id, |heap, lhs, rhs| {
+        let (HeapData::Dict(lhs), HeapData::Dict(rhs)) = (lhs, rhs) else {
+            unreachable!("checked both operands are counters")
+        };
+        let mut result = Dict::with_kind(DictKind::Counter);
+        match combine_counters(&mut result, lhs, rhs, op, heap, interns) {
+            Ok(()) => Ok(result),
+            Err(e) => {
+                for (key, count) in result {
+                    key.drop_with_heap(heap);
+                    count.drop_with_heap(heap);
+                }
+                Err(e)
+            }
+        }
+    })?;
+    Ok(Some(Value::Ref(heap.allocate(HeapData::Dict(result))?)))
+}
+
+/// How [`binary_op`] combines the counts of an element in both counters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CountOp {
+    Add,
+    Sub,
+    Min,
+    Max,
+}
+
+/// Returns whether heap data is a `collections.Counter`.
+fn is_counter(data: &HeapData) -> bool {
+    matches!(data, HeapData::Dict(dict) if matches!(dict.kind(), DictKind::Counter))
+}
+
+/// Maps a count to the float it sorts by in [`compare_counts`].
+fn count_sort_key(count: &Value) -> f64 {
+    match count {
+        Value::Int(i) => *i as f64,
+        Value::Bool(b) => f64::from(u8::from(*b)),
+        Value::Float(f) => *f,
+        _ => f64::NEG_INFINITY,
+    }
+}
+
+/// Implements `Counter.most_common([n])`, returning a list of `(element, count)` tuples.
+///
+/// Elements with equal counts keep their insertion order, since the sort is stable.
+fn counter_most_common(dict: &Dict, n: Option<Value>, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let limit = match n {
+        None | Some(Value::None) => dict.len(),
+        Some(n) => {
+            let limit = n.as_int(heap);
+            n.drop_with_heap(heap);
+            usize::try_from(limit?).unwrap_or(0)
+        }
+    };
+    let mut entries: Vec<(&Value, &Value)> = dict.iter().collect();
+    entries.sort_by(|(_, a), (_, b)| compare_counts(b, a));
+
+    let mut items = Vec::with_capacity(limit.min(entries.len()));
+    for (key, count) in entries.into_iter().take(limit) {
+        let pair = smallvec![key.clone_with_heap(heap), count.clone_with_heap(heap)];
+        match allocate_tuple(pair, heap) {
+            Ok(tuple) => items.push(tuple),
+            Err(e) => {
+                items.drop_with_heap(heap);
+                return Err(e.into());
+            }
+        }
+    }
+    Ok(Value::Ref(heap.allocate(HeapData::List(List::new(items)))?))
+}
+
+/// Implements `Counter.elements()`, listing each element as many times as its count.
+///
+/// Elements with a count below one are left out.
+///
+/// Note: In Python this returns an iterator, but we return a list for simplicity.
+fn counter_elements(dict: &Dict, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let mut repeats = Vec::with_capacity(dict.len());
+    for (_, count) in dict {
+        let repeat = match count {
+            Value::Bool(b) => i64::from(*b),
+            _ => count.as_int(heap)?,
+        };
+        repeats.push(usize::try_from(repeat).unwrap_or(0));
+    }
+
+    let total = repeats
+        .iter()
+        .fold(0usize, |total, repeat| total.saturating_add(*repeat));
+    let estimated = total.saturating_mul(std::mem::size_of::<Value>());
+    if estimated > LARGE_RESULT_THRESHOLD {
+        heap.tracker().check_large_result(estimated)?;
+    }
+
+    let mut items = Vec::with_capacity(total);
+    for ((key, _), repeat) in dict.iter().zip(repeats) {
+        for _ in 0..repeat {
+            items.push(key.clone_with_heap(heap));
+        }
+    }
+    Ok(Value::Ref(heap.allocate(HeapData::List(List::new(items)))?))
+}
+
+/// Implements `Counter.update()` and `Counter.subtract()`, and fills a new counter.
+///
+/// The optional positional argument is either a mapping of elements to counts, or an
+/// iterable of elements which each count once. Keyword arguments are counted like a mapping.
+fn counter_update(
+    dict: &mut Dict,
+    name: &str,
+    args: ArgValues,
+    subtract: bool,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<()> {
+    let (mut pos, kwargs) = args.into_parts();
+    if pos.len() > 1 {
+        let count = pos.len();
+        pos.drop_with_heap(heap);
+        kwargs.drop_with_heap(heap);
+        return Err(ExcType::type_error_at_most(name, 1, count));
+    }
+
+    if let Some(other) = pos.next()
+        && let Err(e) = count_from(dict, other, subtract, heap, interns)
+    {
+        kwargs.drop_with_heap(heap);
+        return Err(e);
+    }
+    count_from_kwargs(dict, kwargs, subtract, heap, interns)
+}
+
+/// Adds (or subtracts) the counts of a mapping, or counts the elements of an iterable.
+fn count_from(
+    dict: &mut Dict,
+    other: Value,
+    subtract: bool,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<()> {
+    if let Value::Ref(id) = &other
+        && let HeapData::Dict(src) = heap.get(*id)
+    {
+        let pairs: Vec<(Value, Value)> = src
+            .iter()
+            .map(|(k, v)| (k.copy_for_extend(), v.copy_for_extend()))
+            .collect();
+        for (k, v) in &pairs {
+            if let Value::Ref(key_id) = k {
+                heap.inc_ref(*key_id);
+            }
+            if let Value::Ref(val_id) = v {
+                heap.inc_ref(*val_id);
+            }
+        }
+        other.drop_with_heap(heap);
+
+        let mut pairs = pairs.into_iter();
+        while let Some((key, count)) = pairs.next() {
+            let result = add_count(dict, key, &count, subtract, heap, interns);
+            count.drop_with_heap(heap);
+            if let Err(e) = result {
+                for (k, v) in pairs {
+                    k.drop_with_heap(heap);
+                    v.drop_with_heap(heap);
+                }
+                return Err(e);
+            }
+        }
+        return Ok(());
+    }
+
+    let one = Value::Int(1);
+    let mut iter = MontyIter::new(other, heap, interns)?;
+    loop {
+        let item = match iter.for_next(heap, interns) {
+            Ok(Some(item)) => item,
+            Ok(None) => break,
+            Err(e) => {
+                iter.drop_with_heap(heap);
+                return Err(e);
+            }
+        };
+        if let Err(e) = add_count(dict, item, &one, subtract, heap, interns) {
+            iter.drop_with_heap(heap);
+            return Err(e);
+        }
+    }
+    iter.drop_with_heap(heap);
+    Ok(())
+}
+
+/// Adds (or subtracts) counts given as keyword arguments.
+fn count_from_kwargs(
+    dict: &mut Dict,
+    kwargs: KwargsValues,
+    subtract: bool,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<()> {
+    let mut kwargs_iter = kwargs.into_iter();
+    while let Some((key, count)) = kwargs_iter.next() {
+        let result = add_count(dict, key, &count, subtract, heap, interns);
+        count.drop_with_heap(heap);
+        if let Err(e) = result {
+            for (k, v) in kwargs_iter {
+                k.drop_with_heap(heap);
+                v.drop_with_heap(heap);
+            }
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+/// Adds `amount` to the count of `key`, or subtracts it, where a missing key counts as zero.
+///
+/// Takes ownership of `key`.
+fn add_count(
+    dict: &mut Dict,
+    key: Value,
+    amount: &Value,
+    subtract: bool,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<()> {
+    let zero = Value::Int(0);
+    let new_count = match dict.get(&key, heap, interns) {
+        Ok(count) => add_counts(count.unwrap_or(&zero), amount, subtract, heap, interns),
+        Err(e) => Err(e),
+    };
+    match new_count {
+        Ok(new_count) => {
+            if let Some(old) = dict.set(key, new_count, heap, interns)? {
+                old.drop_with_heap(heap);
+            }
+            Ok(())
+        }
+        Err(e) => {
+            key.drop_with_heap(heap);
+            Err(e)
+        }
+    }
+}
+
+/// Returns `count + amount`, or `count - amount` when subtracting.
+fn add_counts(
+    count: &Value,
+    amount: &Value,
+    subtract: bool,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let (result, op) = if subtract {
+        (count.py_sub(amount, heap)?, "-")
+    } else {
+        (count.py_add(amount, heap, interns)?, "+")
+    };
+    result.ok_or_else(|| ExcType::binary_type_error(op, count.py_type(heap), amount.py_type(heap)))
+}
+
+/// Fills `result` with the positive counts of combining every element of `lhs` and `rhs`,
+/// where an element missing from one side counts as zero there.
+fn combine_counters(
+    result: &mut Dict,
+    lhs: &Dict,
+    rhs: &Dict,
+    op: CountOp,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<()> {
+    let zero = Value::Int(0);
+    for (key, lhs_count) in lhs {
+        let rhs_count = rhs.get(key, heap, interns)?.unwrap_or(&zero);
+        insert_if_positive(result, key, lhs_count, rhs_count, op, heap, interns)?;
+    }
+    for (key, rhs_count) in rhs {
+        if lhs.get(key, heap, interns)?.is_none() {
+            insert_if_positive(result, key, &zero, rhs_count, op, heap, interns)?;
+        }
+    }
+    Ok(())
+}
+
+/// Combines two counts of `key` and inserts the result into `result` if it is positive.
+fn insert_if_positive(
+    result: &mut Dict,
+    key: &Value,
+    lhs_count: &Value,
+    rhs_count: &Value,
+    op: CountOp,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<()> {
+    let count = match op {
+        CountOp::Add => add_counts(lhs_count, rhs_count, false, heap, interns)?,
+        CountOp::Sub => add_counts(lhs_count, rhs_count, true, heap, interns)?,
+        CountOp::Min | CountOp::Max => {
+            let Some(ordering) = lhs_count.py_cmp(rhs_count, heap, interns) else {
+                return Err(ExcType::type_error(format!(
+                    "'<' not supported between instances of '{}' and '{}'",
+                    lhs_count.py_type(heap),
+                    rhs_count.py_type(heap)
+                )));
+            };
+            let take_rhs = (ordering == Ordering::Less) == (op == CountOp::Max);
+            if take_rhs {
+                rhs_count.clone_with_heap(heap)
+            } else {
+                lhs_count.clone_with_heap(heap)
+            }
+        }
+    };
+    if count.py_cmp(&Value::Int(0), heap, interns) == Some(Ordering::Greater) {
+        let key = key.clone_with_heap(heap);
+        if let Some(old) = result.set(key, count, heap, interns)? {
+            old.drop_with_heap(heap);
+        }
+    } else {
+        count.drop_with_heap(heap);
+    }
+    Ok(())
+}
@@ -159,6 +159,30 @@ impl Dataclass {
         self.attrs.set(name, value, heap, interns)
     }
 
+    /// Deletes an attribute, as in `del obj.attr`.
+    ///
+    /// Returns `FrozenInstanceError` if the dataclass is frozen, or `AttributeError` if
+    /// the attribute isn't set.
+    pub fn del_attr(
+        &mut self,
+        name_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<()> {
+        let attr_name = interns.get_str(name_id);
+        if self.frozen {
+            return Err(ExcType::frozen_instance_delete_error(attr_name));
+        }
+        match self.attrs.pop(&Value::InternString(name_id), heap, interns)? {
+            Some((old_name, old_value)) => {
+                old_name.drop_with_heap(heap);
+                old_value.drop_with_heap(heap);
+                Ok(())
+            }
+            None => Err(ExcType::attribute_error(self.name(interns), attr_name)),
+        }
+    }
+
     /// Computes the hash for this dataclass if it's frozen.
     ///
     /// Returns Some(hash) for frozen (immutable) dataclasses, None for mutable ones.
@@ -269,8 +293,7 @@ impl PyTrait for Dataclass {
         defer_drop!(args, heap);
 
         if self.methods.contains(method_name) {
-            // TODO: Integrate with external call system
-            // For now return an error indicating this needs implementation
+            // The VM yields calls of these methods to the host before reaching here
             Err(ExcType::attribute_error_method_not_implemented(
                 self.name(interns),
                 method_name,
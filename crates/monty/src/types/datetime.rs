@@ -0,0 +1,2320 @@
+//! Python `datetime` module types: `date`, `time`, `datetime`, `timedelta` and `timezone`.
+//!
+//! All five are immutable values stored on the heap. The only `tzinfo` is the fixed-offset
+//! `timezone`, and where CPython would use the host's local time zone (naive `timestamp()`,
+//! `fromtimestamp()` and `astimezone()`) Monty uses UTC, so results never depend on the host.
+//! The current time comes from the host too: `datetime.now()` and `date.today()` yield
+//! `OsFunction::DateTimeNow` instead of reading the clock, see [`NowRequest`].
+//!
+//! Parsing and formatting (`fromisoformat`, `isoformat`, `strftime` and `strptime`) live in
+//! [`super::datetime_format`].
+use std::{borrow::Cow, cmp::Ordering, fmt::Write};
+
+use ahash::AHashSet;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
+
+use super::{
+    AttrCallResult, LongInt, NamedTuple, PyTrait, Type,
+    datetime_format::{self, Timespec},
+    str::{StringRepr, allocate_string},
+};
+use crate::{
+    args::{ArgValues, bind_args},
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    expressions::Operator,
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    resource::{ResourceError, ResourceTracker},
+    value::{EitherStr, Value},
+};
+
+/// Smallest year of a `date` or `datetime`, exposed as `datetime.MINYEAR`.
+pub(crate) const MIN_YEAR: i32 = 1;
+/// Largest year of a `date` or `datetime`, exposed as `datetime.MAXYEAR`.
+pub(crate) const MAX_YEAR: i32 = 9999;
+/// Largest magnitude of `timedelta.days`.
+const MAX_DELTA_DAYS: i64 = 999_999_999;
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const SECONDS_PER_DAY: i64 = 86_400;
+const MICROS_PER_DAY: i64 = SECONDS_PER_DAY * MICROS_PER_SECOND;
+/// Ordinal of `date.max`, 9999-12-31.
+const MAX_ORDINAL: i64 = 3_652_059;
+/// Ordinal of the Unix epoch, 1970-01-01.
+const EPOCH_ORDINAL: i64 = 719_163;
+
+const DAYS_IN_MONTH: [u8; 13] = [0, 31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+const DAYS_BEFORE_MONTH: [u16; 13] = [0, 0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+
+/// The `timedelta()` keyword arguments in positional order, with their length in microseconds.
+const TIMEDELTA_UNITS: [(&str, i64); 7] = [
+    ("days", MICROS_PER_DAY),
+    ("seconds", MICROS_PER_SECOND),
+    ("microseconds", 1),
+    ("milliseconds", 1000),
+    ("minutes", 60 * MICROS_PER_SECOND),
+    ("hours", 3600 * MICROS_PER_SECOND),
+    ("weeks", 7 * MICROS_PER_DAY),
+];
+
+// =============================================================================
+// timedelta
+// =============================================================================
+
+/// A duration, normalized like CPython so that only `days` can be negative,
+/// `0 <= seconds < 86400` and `0 <= microseconds < 1000000`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+pub(crate) struct TimeDelta {
+    days: i32,
+    seconds: i32,
+    microseconds: i32,
+}
+
+impl TimeDelta {
+    /// `timedelta(0)`.
+    pub const ZERO: Self = Self {
+        days: 0,
+        seconds: 0,
+        microseconds: 0,
+    };
+
+    /// Creates a timedelta from its (possibly unnormalized) components.
+    ///
+    /// # Errors
+    /// Returns `OverflowError` if the duration has more than 999999999 days.
+    pub fn new(days: i64, seconds: i64, microseconds: i64) -> RunResult<Self> {
+        Self::from_micros(
+            i128::from(days) * i128::from(MICROS_PER_DAY)
+                + i128::from(seconds) * i128::from(MICROS_PER_SECOND)
+                + i128::from(microseconds),
+        )
+    }
+
+    /// Creates a timedelta from a number of microseconds.
+    ///
+    /// # Errors
+    /// Returns `OverflowError` if the duration has more than 999999999 days.
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "days is checked to be in range and the rest is less than a day"
+    )]
+    pub fn from_micros(micros: i128) -> RunResult<Self> {
+        let days = micros.div_euclid(i128::from(MICROS_PER_DAY));
+        if days.abs() > i128::from(MAX_DELTA_DAYS) {
+            return Err(delta_overflow(days));
+        }
+        let rest = micros.rem_euclid(i128::from(MICROS_PER_DAY));
+        Ok(Self {
+            days: days as i32,
+            seconds: (rest / i128::from(MICROS_PER_SECOND)) as i32,
+            microseconds: (rest % i128::from(MICROS_PER_SECOND)) as i32,
+        })
+    }
+
+    /// Like [`Self::from_micros`], for results of arithmetic with arbitrarily large ints.
+    fn from_big_micros(micros: &BigInt) -> RunResult<Self> {
+        match micros.to_i128() {
+            Some(micros) => Self::from_micros(micros),
+            None => Err(delta_overflow(micros.div_floor(&BigInt::from(MICROS_PER_DAY)))),
+        }
+    }
+
+    /// Returns the number of days, which carries the sign of the duration.
+    #[must_use]
+    pub fn days(self) -> i32 {
+        self.days
+    }
+
+    /// Returns the seconds part, in `0..86400`.
+    #[must_use]
+    pub fn seconds(self) -> i32 {
+        self.seconds
+    }
+
+    /// Returns the microseconds part, in `0..1000000`.
+    #[must_use]
+    pub fn microseconds(self) -> i32 {
+        self.microseconds
+    }
+
+    /// Returns the whole duration in microseconds.
+    #[must_use]
+    pub fn total_micros(self) -> i128 {
+        i128::from(self.days) * i128::from(MICROS_PER_DAY)
+            + i128::from(self.seconds) * i128::from(MICROS_PER_SECOND)
+            + i128::from(self.microseconds)
+    }
+
+    /// Returns `-self`, which overflows only for `timedelta.min`.
+    pub fn neg(self) -> RunResult<Self> {
+        Self::from_micros(-self.total_micros())
+    }
+
+    /// Returns `abs(self)`.
+    pub fn abs(self) -> RunResult<Self> {
+        if self.days < 0 { self.neg() } else { Ok(self) }
+    }
+
+    /// Allocates this timedelta on the heap.
+    pub fn into_value(self, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, ResourceError> {
+        Ok(Value::Ref(heap.allocate(HeapData::TimeDelta(self))?))
+    }
+
+    /// Creates a timedelta from the `timedelta()` constructor call.
+    ///
+    /// Takes `days`, `seconds`, `microseconds`, `milliseconds`, `minutes`, `hours` and
+    /// `weeks`, each an int or a float. Ints are added exactly, while the fractional parts
+    /// of floats are summed and rounded half to even to whole microseconds, as CPython does.
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let params = TIMEDELTA_UNITS.map(|(name, _)| name);
+        let values = bind_args("timedelta", args, params, 0, heap, interns)?;
+        let result = timedelta_from_components(&values, heap);
+        for value in values {
+            value.drop_with_heap(heap);
+        }
+        Ok(result?.into_value(heap)?)
+    }
+
+    /// Formats like CPython: `datetime.timedelta(days=-1, seconds=86399)`, or
+    /// `datetime.timedelta(0)` for a zero duration.
+    pub fn repr_fmt(self, f: &mut impl Write) -> std::fmt::Result {
+        f.write_str("datetime.timedelta(")?;
+        let mut separator = "";
+        for (name, value) in [
+            ("days", self.days),
+            ("seconds", self.seconds),
+            ("microseconds", self.microseconds),
+        ] {
+            if value != 0 {
+                write!(f, "{separator}{name}={value}")?;
+                separator = ", ";
+            }
+        }
+        if separator.is_empty() {
+            f.write_char('0')?;
+        }
+        f.write_char(')')
+    }
+
+    /// Formats like CPython's `str()`: `-1 day, 23:59:59` or `2 days, 0:00:00.000005`.
+    pub fn str_fmt(self, f: &mut impl Write) -> std::fmt::Result {
+        if self.days != 0 {
+            let plural = if self.days.abs() == 1 { "" } else { "s" };
+            write!(f, "{} day{plural}, ", self.days)?;
+        }
+        write!(
+            f,
+            "{}:{:02}:{:02}",
+            self.seconds / 3600,
+            self.seconds / 60 % 60,
+            self.seconds % 60
+        )?;
+        if self.microseconds != 0 {
+            write!(f, ".{:06}", self.microseconds)?;
+        }
+        Ok(())
+    }
+}
+
+/// Sums the `timedelta()` constructor arguments, given in the order of `TIMEDELTA_UNITS`.
+fn timedelta_from_components(values: &[Option<Value>; 7], heap: &Heap<impl ResourceTracker>) -> RunResult<TimeDelta> {
+    let mut whole = BigInt::zero();
+    let mut fraction = 0.0;
+    for (value, (name, unit)) in values.iter().zip(TIMEDELTA_UNITS) {
+        let Some(value) = value else { continue };
+        match value {
+            Value::Int(i) => whole += BigInt::from(*i) * unit,
+            Value::Bool(b) => whole += i64::from(*b) * unit,
+            Value::Float(x) => {
+                whole += float_to_bigint(x.trunc())? * unit;
+                fraction += x.fract() * unit as f64;
+            }
+            _ => match value.ref_id().map(|id| heap.get(id)) {
+                Some(HeapData::LongInt(li)) => whole += li.inner() * unit,
+                _ => {
+                    return Err(ExcType::type_error(format!(
+                        "unsupported type for timedelta {name} component: {}",
+                        value.py_type(heap)
+                    )));
+                }
+            },
+        }
+    }
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "the fractions sum to less than the total of all the units"
+    )]
+    let fraction = fraction.round_ties_even() as i64;
+    TimeDelta::from_big_micros(&(whole + fraction))
+}
+
+impl PyTrait for TimeDelta {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::TimeDelta
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self == other
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No-op: timedeltas don't hold Value references
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        *self != Self::ZERO
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        self.repr_fmt(f)
+    }
+
+    fn py_str(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Cow<'static, str> {
+        let mut s = String::new();
+        self.str_fmt(&mut s).expect("writing to a String can't fail");
+        Cow::Owned(s)
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        if attr.static_string() == Some(StaticStrings::TotalSeconds) {
+            args.check_zero_args("timedelta.total_seconds", heap)?;
+            return Ok(Value::Float(self.total_micros() as f64 / 1e6));
+        }
+        args.drop_with_heap(heap);
+        Err(ExcType::attribute_error(Type::TimeDelta, attr.as_str(interns)))
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        _heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = match StaticStrings::from_string_id(attr_id) {
+            Some(StaticStrings::Days) => self.days,
+            Some(StaticStrings::Seconds) => self.seconds,
+            Some(StaticStrings::Microseconds) => self.microseconds,
+            _ => return Ok(None),
+        };
+        Ok(Some(AttrCallResult::Value(Value::Int(i64::from(value)))))
+    }
+}
+
+#[cold]
+fn delta_overflow(days: impl std::fmt::Display) -> RunError {
+    SimpleException::new_msg(
+        ExcType::OverflowError,
+        format!("days={days}; must have magnitude <= {MAX_DELTA_DAYS}"),
+    )
+    .into()
+}
+
+// =============================================================================
+// timezone
+// =============================================================================
+
+/// A fixed offset from UTC in whole seconds, with an optional name.
+///
+/// Timezones compare and hash by offset alone, as in CPython.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct TimeZone {
+    offset: i32,
+    name: Option<String>,
+}
+
+impl TimeZone {
+    /// `timezone.utc`.
+    pub const UTC: Self = Self { offset: 0, name: None };
+
+    /// Creates a timezone from an offset in seconds.
+    ///
+    /// # Errors
+    /// Returns `ValueError` unless the offset is strictly between -24 and 24 hours.
+    pub fn new(offset: i64, name: Option<String>) -> RunResult<Self> {
+        if offset.abs() >= SECONDS_PER_DAY {
+            let mut delta = String::new();
+            TimeDelta::new(0, offset, 0)?
+                .repr_fmt(&mut delta)
+                .expect("writing to a String can't fail");
+            return Err(ExcType::value_error(format!(
+                "offset must be a timedelta strictly between -timedelta(hours=24) and timedelta(hours=24), not {delta}."
+            )));
+        }
+        let offset = i32::try_from(offset).expect("offset is less than a day");
+        Ok(Self { offset, name })
+    }
+
+    /// Returns the offset from UTC in seconds.
+    #[must_use]
+    pub fn offset(&self) -> i32 {
+        self.offset
+    }
+
+    /// Returns the name given to the constructor, if any.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns `tzname()`: the given name, or one like `UTC+05:30` derived from the offset.
+    #[must_use]
+    pub fn tzname(&self) -> Cow<'_, str> {
+        match &self.name {
+            Some(name) => Cow::Borrowed(name),
+            None if self.offset == 0 => Cow::Borrowed("UTC"),
+            None => {
+                let mut name = "UTC".to_owned();
+                datetime_format::write_offset(&mut name, self.offset, ":");
+                Cow::Owned(name)
+            }
+        }
+    }
+
+    /// Returns the offset in microseconds.
+    fn offset_micros(&self) -> i64 {
+        i64::from(self.offset) * MICROS_PER_SECOND
+    }
+
+    /// Allocates this timezone on the heap.
+    pub fn into_value(self, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, ResourceError> {
+        Ok(Value::Ref(heap.allocate(HeapData::TimeZone(self))?))
+    }
+
+    /// Creates a timezone from the `timezone(offset, name=None)` constructor call.
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let [offset, name] = bind_args("timezone", args, ["offset", "name"], 1, heap, interns)?;
+        defer_drop!(offset, heap);
+        defer_drop!(name, heap);
+        let Some(delta) = offset.as_ref().and_then(|offset| as_timedelta(offset, heap)) else {
+            let offset_type = offset.as_ref().map_or(Type::NoneType, |offset| offset.py_type(heap));
+            return Err(ExcType::type_error(format!(
+                "timezone() argument 1 must be datetime.timedelta, not {offset_type}"
+            )));
+        };
+        if delta.microseconds != 0 {
+            return Err(ExcType::value_error(
+                "offset must be a timedelta representing a whole number of seconds",
+            ));
+        }
+        let name = match name {
+            None => None,
+            Some(name) => match name.as_either_str(heap) {
+                Some(name) => Some(name.as_str(interns).to_owned()),
+                None => {
+                    return Err(ExcType::type_error(format!(
+                        "timezone() argument 2 must be str, not {}",
+                        name.py_type(heap)
+                    )));
+                }
+            },
+        };
+        let offset = i64::from(delta.days) * SECONDS_PER_DAY + i64::from(delta.seconds);
+        Ok(Self::new(offset, name)?.into_value(heap)?)
+    }
+
+    /// Formats like CPython: `datetime.timezone.utc`, or
+    /// `datetime.timezone(datetime.timedelta(seconds=3600), 'CET')`.
+    pub fn repr_fmt(&self, f: &mut impl Write) -> std::fmt::Result {
+        if self.offset == 0 && self.name.is_none() {
+            return f.write_str("datetime.timezone.utc");
+        }
+        f.write_str("datetime.timezone(")?;
+        TimeDelta::new(0, i64::from(self.offset), 0)
+            .expect("offset is less than a day")
+            .repr_fmt(f)?;
+        if let Some(name) = &self.name {
+            write!(f, ", {}", StringRepr(name))?;
+        }
+        f.write_char(')')
+    }
+}
+
+impl PyTrait for TimeZone {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::TimeZone
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.name.as_ref().map_or(0, String::len)
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self.offset == other.offset
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No-op: timezones don't hold Value references
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        self.repr_fmt(f)
+    }
+
+    fn py_str(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Cow<'static, str> {
+        Cow::Owned(self.tzname().into_owned())
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        let method = attr.static_string();
+        let name = match method {
+            Some(StaticStrings::Utcoffset) => "utcoffset",
+            Some(StaticStrings::Tzname) => "tzname",
+            Some(StaticStrings::Dst) => "dst",
+            _ => {
+                args.drop_with_heap(heap);
+                return Err(ExcType::attribute_error(Type::TimeZone, attr.as_str(interns)));
+            }
+        };
+        // The argument is ignored, but must be a datetime or None as in CPython
+        let dt = args.get_one_arg(&format!("timezone.{name}"), heap)?;
+        defer_drop!(dt, heap);
+        if !matches!(dt, Value::None) && !is_datetime(dt, heap) {
+            return Err(ExcType::type_error(format!(
+                "{name}(dt) argument must be a datetime instance or None, not {}",
+                dt.py_type(heap)
+            )));
+        }
+        match method {
+            Some(StaticStrings::Utcoffset) => Ok(TimeDelta::new(0, i64::from(self.offset), 0)?.into_value(heap)?),
+            Some(StaticStrings::Tzname) => allocate_string(self.tzname().into_owned(), heap),
+            _ => Ok(Value::None),
+        }
+    }
+}
+
+// =============================================================================
+// date
+// =============================================================================
+
+/// A date in the proleptic Gregorian calendar, between years 1 and 9999.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// `date.min`, 0001-01-01.
+    pub const MIN: Self = Self {
+        year: MIN_YEAR,
+        month: 1,
+        day: 1,
+    };
+
+    /// `date.max`, 9999-12-31.
+    pub const MAX: Self = Self {
+        year: MAX_YEAR,
+        month: 12,
+        day: 31,
+    };
+
+    /// 1900-01-01, whose fields the date directives of `time.strftime()` use, as in CPython.
+    const TIME_STRFTIME: Self = Self {
+        year: 1900,
+        month: 1,
+        day: 1,
+    };
+
+    /// Creates a date, checking each field is in range.
+    ///
+    /// # Errors
+    /// Returns `ValueError` naming the first field which is out of range.
+    pub fn new(year: i64, month: i64, day: i64) -> RunResult<Self> {
+        if !(i64::from(MIN_YEAR)..=i64::from(MAX_YEAR)).contains(&year) {
+            return Err(ExcType::value_error(format!(
+                "year must be in {MIN_YEAR}..{MAX_YEAR}, not {year}"
+            )));
+        }
+        if !(1..=12).contains(&month) {
+            return Err(ExcType::value_error(format!("month must be in 1..12, not {month}")));
+        }
+        #[expect(clippy::cast_possible_truncation, reason = "fields are checked to be in range")]
+        let (year, month) = (year as i32, month as u8);
+        let days = days_in_month(year, month);
+        if !(1..=i64::from(days)).contains(&day) {
+            return Err(ExcType::value_error(format!(
+                "day {day} must be in range 1..{days} for month {month} in year {year}"
+            )));
+        }
+        #[expect(clippy::cast_possible_truncation, reason = "day is checked to be in range")]
+        let day = day as u8;
+        Ok(Self { year, month, day })
+    }
+
+    /// Creates a date from its proleptic Gregorian ordinal, where 0001-01-01 is day 1.
+    ///
+    /// # Errors
+    /// Returns `OverflowError` for ordinals outside `date.min..=date.max`.
+    pub fn from_ordinal(ordinal: i64) -> RunResult<Self> {
+        if !(1..=MAX_ORDINAL).contains(&ordinal) {
+            return Err(SimpleException::new_msg(ExcType::OverflowError, "date value out of range").into());
+        }
+        let (year, month, day) = ordinal_to_ymd(ordinal);
+        let year = i32::try_from(year).expect("the ordinal is in range");
+        Ok(Self { year, month, day })
+    }
+
+    /// Creates a date from an ISO calendar year, week and weekday, like `date.fromisocalendar()`.
+    pub fn from_isocalendar(year: i64, week: i64, weekday: i64) -> RunResult<Self> {
+        if !(i64::from(MIN_YEAR)..=i64::from(MAX_YEAR)).contains(&year) {
+            return Err(ExcType::value_error(format!("Year is out of range: {year}")));
+        }
+        if !(1..53).contains(&week) {
+            // Years starting on a Thursday, or leap years starting on a Wednesday, have 53 weeks
+            let first_weekday = ymd_to_ordinal(year, 1, 1) % 7;
+            if week != 53 || !(first_weekday == 4 || (first_weekday == 3 && is_leap(year))) {
+                return Err(ExcType::value_error(format!("Invalid week: {week}")));
+            }
+        }
+        if !(1..=7).contains(&weekday) {
+            return Err(ExcType::value_error(format!(
+                "Invalid weekday: {weekday} (range is [1, 7])"
+            )));
+        }
+        Self::from_ordinal(iso_week1_monday(year) + (week - 1) * 7 + (weekday - 1))
+    }
+
+    /// Returns the year.
+    #[must_use]
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    /// Returns the month, from 1 to 12.
+    #[must_use]
+    pub fn month(self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day of the month, from 1.
+    #[must_use]
+    pub fn day(self) -> u8 {
+        self.day
+    }
+
+    /// Returns the proleptic Gregorian ordinal, where 0001-01-01 is day 1.
+    #[must_use]
+    pub fn toordinal(self) -> i64 {
+        ymd_to_ordinal(i64::from(self.year), self.month, self.day)
+    }
+
+    /// Returns the day of the week, where Monday is 0 and Sunday is 6.
+    #[must_use]
+    pub fn weekday(self) -> u8 {
+        #[expect(clippy::cast_possible_truncation, reason = "a weekday is less than 7")]
+        let weekday = ((self.toordinal() + 6) % 7) as u8;
+        weekday
+    }
+
+    /// Returns the day of the year, where January 1st is day 1.
+    #[must_use]
+    pub fn day_of_year(self) -> u16 {
+        #[expect(clippy::cast_possible_truncation, reason = "a year has fewer than 367 days")]
+        let day = (days_before_month(i64::from(self.year), self.month) + i64::from(self.day)) as u16;
+        day
+    }
+
+    /// Returns the ISO calendar `(year, week, weekday)`, where weekdays run from 1 (Monday) to 7.
+    #[must_use]
+    #[expect(clippy::cast_possible_truncation, reason = "ISO years, weeks and weekdays are small")]
+    pub fn isocalendar(self) -> (i32, u8, u8) {
+        let mut year = i64::from(self.year);
+        let today = self.toordinal();
+        let mut week1_monday = iso_week1_monday(year);
+        let (mut week, mut day) = (today - week1_monday).div_mod_floor(&7);
+        if week < 0 {
+            year -= 1;
+            week1_monday = iso_week1_monday(year);
+            (week, day) = (today - week1_monday).div_mod_floor(&7);
+        } else if week >= 52 && today >= iso_week1_monday(year + 1) {
+            year += 1;
+            week = 0;
+        }
+        (year as i32, (week + 1) as u8, (day + 1) as u8)
+    }
+
+    /// Returns this date moved by a number of days.
+    ///
+    /// # Errors
+    /// Returns `OverflowError` if the result is outside `date.min..=date.max`.
+    pub fn add_days(self, days: i64) -> RunResult<Self> {
+        Self::from_ordinal(self.toordinal() + days)
+    }
+
+    /// Allocates this date on the heap.
+    pub fn into_value(self, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, ResourceError> {
+        Ok(Value::Ref(heap.allocate(HeapData::Date(self))?))
+    }
+
+    /// Creates a date from the `date(year, month, day)` constructor call.
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let values = bind_args("date", args, ["year", "month", "day"], 3, heap, interns)?;
+        let result = date_from_args(&values, heap);
+        for value in values {
+            value.drop_with_heap(heap);
+        }
+        Ok(result?.into_value(heap)?)
+    }
+
+    /// Returns the date in ISO 8601 format, `YYYY-MM-DD`.
+    #[must_use]
+    pub fn isoformat(self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    /// Formats like CPython: `datetime.date(2024, 1, 5)`.
+    pub fn repr_fmt(self, f: &mut impl Write) -> std::fmt::Result {
+        write!(f, "datetime.date({}, {}, {})", self.year, self.month, self.day)
+    }
+
+    /// Implements `date.replace(year, month, day)`.
+    fn replace(self, args: ArgValues, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+        let values = bind_args("replace", args, ["year", "month", "day"], 0, heap, interns)?;
+        let result = replace_date(self, &values, heap);
+        for value in values {
+            value.drop_with_heap(heap);
+        }
+        Ok(result?.into_value(heap)?)
+    }
+}
+
+/// Builds a date from the bound `year`, `month` and `day` arguments.
+fn date_from_args(values: &[Option<Value>], heap: &Heap<impl ResourceTracker>) -> RunResult<Date> {
+    Date::new(
+        int_or(values[0].as_ref(), 1, heap)?,
+        int_or(values[1].as_ref(), 1, heap)?,
+        int_or(values[2].as_ref(), 1, heap)?,
+    )
+}
+
+/// Builds a copy of `date` with the bound `year`, `month` and `day` arguments replaced.
+fn replace_date(date: Date, values: &[Option<Value>], heap: &Heap<impl ResourceTracker>) -> RunResult<Date> {
+    Date::new(
+        int_or(values[0].as_ref(), i64::from(date.year), heap)?,
+        int_or(values[1].as_ref(), i64::from(date.month), heap)?,
+        int_or(values[2].as_ref(), i64::from(date.day), heap)?,
+    )
+}
+
+impl PyTrait for Date {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Date
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self == other
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No-op: dates don't hold Value references
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        self.repr_fmt(f)
+    }
+
+    fn py_str(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Cow<'static, str> {
+        Cow::Owned(self.isoformat())
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        match attr.static_string() {
+            Some(StaticStrings::Isoformat) => {
+                args.check_zero_args("date.isoformat", heap)?;
+                allocate_string(self.isoformat(), heap)
+            }
+            Some(StaticStrings::Replace) => self.replace(args, heap, interns),
+            Some(method) if is_calendar_method(method) => {
+                call_calendar_method("date", *self, &Time::MIDNIGHT, method, args, heap, interns)
+            }
+            _ => {
+                args.drop_with_heap(heap);
+                Err(ExcType::attribute_error(Type::Date, attr.as_str(interns)))
+            }
+        }
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        _heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        Ok(date_attr(*self, attr_id).map(AttrCallResult::Value))
+    }
+}
+
+/// Returns the `year`, `month` or `day` attribute of a date or datetime.
+fn date_attr(date: Date, attr_id: StringId) -> Option<Value> {
+    let value = match StaticStrings::from_string_id(attr_id)? {
+        StaticStrings::Year => i64::from(date.year),
+        StaticStrings::Month => i64::from(date.month),
+        StaticStrings::Day => i64::from(date.day),
+        _ => return None,
+    };
+    Some(Value::Int(value))
+}
+
+/// Returns whether `method` is one of the methods shared by dates and datetimes
+/// which [`call_calendar_method`] implements.
+fn is_calendar_method(method: StaticStrings) -> bool {
+    matches!(
+        method,
+        StaticStrings::Strftime
+            | StaticStrings::Weekday
+            | StaticStrings::Isoweekday
+            | StaticStrings::Isocalendar
+            | StaticStrings::Toordinal
+            | StaticStrings::Ctime
+    )
+}
+
+/// Calls a method shared by dates and datetimes, where a date has a `time` of midnight.
+fn call_calendar_method(
+    type_name: &str,
+    date: Date,
+    time: &Time,
+    method: StaticStrings,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let method_name: &'static str = method.into();
+    let name = format!("{type_name}.{method_name}");
+    if method == StaticStrings::Strftime {
+        let format = args.get_one_arg(&name, heap)?;
+        defer_drop!(format, heap);
+        let format = str_arg(format, "strftime", 1, heap)?;
+        return allocate_string(datetime_format::strftime(format.as_str(interns), date, time), heap);
+    }
+    args.check_zero_args(&name, heap)?;
+    match method {
+        StaticStrings::Weekday => Ok(Value::Int(i64::from(date.weekday()))),
+        StaticStrings::Isoweekday => Ok(Value::Int(i64::from(date.weekday()) + 1)),
+        StaticStrings::Toordinal => Ok(Value::Int(date.toordinal())),
+        StaticStrings::Ctime => allocate_string(datetime_format::strftime("%c", date, time), heap),
+        _ => {
+            let (year, week, weekday) = date.isocalendar();
+            let calendar_date = NamedTuple::new(
+                "datetime.IsoCalendarDate".to_owned(),
+                vec![
+                    StaticStrings::Year.into(),
+                    StaticStrings::Week.into(),
+                    StaticStrings::Weekday.into(),
+                ],
+                vec![
+                    Value::Int(i64::from(year)),
+                    Value::Int(i64::from(week)),
+                    Value::Int(i64::from(weekday)),
+                ],
+            );
+            Ok(Value::Ref(heap.allocate(HeapData::NamedTuple(calendar_date))?))
+        }
+    }
+}
+
+// =============================================================================
+// time
+// =============================================================================
+
+/// A time of day with microsecond precision and an optional fixed-offset timezone.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Time {
+    hour: u8,
+    minute: u8,
+    second: u8,
+    microsecond: u32,
+    tzinfo: Option<TimeZone>,
+}
+
+impl Time {
+    /// `time()`, naive midnight.
+    pub const MIDNIGHT: Self = Self {
+        hour: 0,
+        minute: 0,
+        second: 0,
+        microsecond: 0,
+        tzinfo: None,
+    };
+
+    /// `time.max`, 23:59:59.999999.
+    pub const MAX: Self = Self {
+        hour: 23,
+        minute: 59,
+        second: 59,
+        microsecond: 999_999,
+        tzinfo: None,
+    };
+
+    /// Creates a time, checking each field is in range.
+    ///
+    /// # Errors
+    /// Returns `ValueError` naming the first field which is out of range.
+    #[expect(clippy::cast_possible_truncation, reason = "fields are checked to be in range")]
+    pub fn new(hour: i64, minute: i64, second: i64, microsecond: i64, tzinfo: Option<TimeZone>) -> RunResult<Self> {
+        for (name, value, max) in [
+            ("hour", hour, 23),
+            ("minute", minute, 59),
+            ("second", second, 59),
+            ("microsecond", microsecond, 999_999),
+        ] {
+            if !(0..=max).contains(&value) {
+                return Err(ExcType::value_error(format!("{name} must be in 0..{max}, not {value}")));
+            }
+        }
+        Ok(Self {
+            hour: hour as u8,
+            minute: minute as u8,
+            second: second as u8,
+            microsecond: microsecond as u32,
+            tzinfo,
+        })
+    }
+
+    /// Creates a time from a number of microseconds since midnight, in `0..MICROS_PER_DAY`.
+    #[expect(clippy::cast_possible_truncation, reason = "a time of day has small fields")]
+    fn from_micros_of_day(micros: i64, tzinfo: Option<TimeZone>) -> Self {
+        debug_assert!((0..MICROS_PER_DAY).contains(&micros), "not a time of day");
+        let seconds = micros / MICROS_PER_SECOND;
+        Self {
+            hour: (seconds / 3600) as u8,
+            minute: (seconds / 60 % 60) as u8,
+            second: (seconds % 60) as u8,
+            microsecond: (micros % MICROS_PER_SECOND) as u32,
+            tzinfo,
+        }
+    }
+
+    /// Returns the hour, from 0 to 23.
+    #[must_use]
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Returns the minute, from 0 to 59.
+    #[must_use]
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Returns the second, from 0 to 59.
+    #[must_use]
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Returns the microsecond, from 0 to 999999.
+    #[must_use]
+    pub fn microsecond(&self) -> u32 {
+        self.microsecond
+    }
+
+    /// Returns the timezone, or `None` for a naive time.
+    #[must_use]
+    pub fn tzinfo(&self) -> Option<&TimeZone> {
+        self.tzinfo.as_ref()
+    }
+
+    /// Returns a copy of this time with a different timezone.
+    #[must_use]
+    pub fn with_tzinfo(&self, tzinfo: Option<TimeZone>) -> Self {
+        Self { tzinfo, ..*self }
+    }
+
+    /// Returns the number of microseconds since midnight.
+    fn micros_of_day(&self) -> i64 {
+        (i64::from(self.hour) * 3600 + i64::from(self.minute) * 60 + i64::from(self.second)) * MICROS_PER_SECOND
+            + i64::from(self.microsecond)
+    }
+
+    /// Compares two times, adjusting aware times to UTC.
+    ///
+    /// Returns `None` when comparing a naive time with an aware one.
+    fn compare(&self, other: &Self) -> Option<Ordering> {
+        match (&self.tzinfo, &other.tzinfo) {
+            (None, None) => Some(self.micros_of_day().cmp(&other.micros_of_day())),
+            (Some(a), Some(b)) => {
+                Some((self.micros_of_day() - a.offset_micros()).cmp(&(other.micros_of_day() - b.offset_micros())))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns a value which is equal for equal times, for hashing.
+    #[must_use]
+    pub fn hash_key(&self) -> i64 {
+        self.micros_of_day() - self.tzinfo.as_ref().map_or(0, TimeZone::offset_micros)
+    }
+
+    /// Allocates this time on the heap.
+    pub fn into_value(self, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, ResourceError> {
+        Ok(Value::Ref(heap.allocate(HeapData::Time(self))?))
+    }
+
+    /// Creates a time from the `time(hour=0, minute=0, second=0, microsecond=0, tzinfo=None)`
+    /// constructor call.
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let values = bind_args(
+            "time",
+            args,
+            ["hour", "minute", "second", "microsecond", "tzinfo"],
+            0,
+            heap,
+            interns,
+        )?;
+        let result = replace_time(&Self::MIDNIGHT, &values, heap);
+        for value in values {
+            value.drop_with_heap(heap);
+        }
+        Ok(result?.into_value(heap)?)
+    }
+
+    /// Formats like CPython: `datetime.time(12, 30)`, omitting zero seconds and microseconds.
+    pub fn repr_fmt(&self, f: &mut impl Write) -> std::fmt::Result {
+        write!(f, "datetime.time({}, {}", self.hour, self.minute)?;
+        self.repr_tail_fmt(f)
+    }
+
+    /// Writes the seconds, microseconds and timezone of a time or datetime repr, and the `)`.
+    fn repr_tail_fmt(&self, f: &mut impl Write) -> std::fmt::Result {
+        if self.second != 0 || self.microsecond != 0 {
+            write!(f, ", {}", self.second)?;
+        }
+        if self.microsecond != 0 {
+            write!(f, ", {}", self.microsecond)?;
+        }
+        if let Some(tzinfo) = &self.tzinfo {
+            f.write_str(", tzinfo=")?;
+            tzinfo.repr_fmt(f)?;
+        }
+        f.write_char(')')
+    }
+
+    /// Returns `utcoffset()`, or `None` for a naive time.
+    fn utcoffset(&self, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        match &self.tzinfo {
+            Some(tzinfo) => Ok(TimeDelta::new(0, i64::from(tzinfo.offset), 0)?.into_value(heap)?),
+            None => Ok(Value::None),
+        }
+    }
+
+    /// Returns `tzname()`, or `None` for a naive time.
+    fn tzname(&self, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        match &self.tzinfo {
+            Some(tzinfo) => allocate_string(tzinfo.tzname().into_owned(), heap),
+            None => Ok(Value::None),
+        }
+    }
+
+    /// Returns the `tzinfo` attribute.
+    fn tzinfo_value(&self, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, ResourceError> {
+        match &self.tzinfo {
+            Some(tzinfo) => tzinfo.clone().into_value(heap),
+            None => Ok(Value::None),
+        }
+    }
+}
+
+/// Builds a copy of `time` with the bound `hour`, `minute`, `second`, `microsecond` and
+/// `tzinfo` arguments replaced.
+fn replace_time(time: &Time, values: &[Option<Value>], heap: &Heap<impl ResourceTracker>) -> RunResult<Time> {
+    let tzinfo = match &values[4] {
+        Some(tzinfo) => tzinfo_arg(tzinfo, heap)?,
+        None => time.tzinfo.clone(),
+    };
+    Time::new(
+        int_or(values[0].as_ref(), i64::from(time.hour), heap)?,
+        int_or(values[1].as_ref(), i64::from(time.minute), heap)?,
+        int_or(values[2].as_ref(), i64::from(time.second), heap)?,
+        int_or(values[3].as_ref(), i64::from(time.microsecond), heap)?,
+        tzinfo,
+    )
+}
+
+impl PyTrait for Time {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Time
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self.compare(other) == Some(Ordering::Equal)
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No-op: times don't hold Value references
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        self.repr_fmt(f)
+    }
+
+    fn py_str(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Cow<'static, str> {
+        let mut s = String::new();
+        datetime_format::write_iso_time(&mut s, self, Timespec::Auto);
+        Cow::Owned(s)
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        match attr.static_string() {
+            Some(StaticStrings::Isoformat) => {
+                let [timespec] = bind_args("isoformat", args, ["timespec"], 0, heap, interns)?;
+                defer_drop!(timespec, heap);
+                let timespec = timespec_arg(timespec.as_ref(), 1, heap, interns)?;
+                let mut s = String::new();
+                datetime_format::write_iso_time(&mut s, self, timespec);
+                allocate_string(s, heap)
+            }
+            Some(StaticStrings::Strftime) => {
+                let format = args.get_one_arg("time.strftime", heap)?;
+                defer_drop!(format, heap);
+                let format = str_arg(format, "strftime", 1, heap)?;
+                allocate_string(
+                    datetime_format::strftime(format.as_str(interns), Date::TIME_STRFTIME, self),
+                    heap,
+                )
+            }
+            Some(StaticStrings::Replace) => {
+                let values = bind_args(
+                    "replace",
+                    args,
+                    ["hour", "minute", "second", "microsecond", "tzinfo"],
+                    0,
+                    heap,
+                    interns,
+                )?;
+                let result = replace_time(self, &values, heap);
+                for value in values {
+                    value.drop_with_heap(heap);
+                }
+                Ok(result?.into_value(heap)?)
+            }
+            Some(StaticStrings::Utcoffset) => {
+                args.check_zero_args("time.utcoffset", heap)?;
+                self.utcoffset(heap)
+            }
+            Some(StaticStrings::Tzname) => {
+                args.check_zero_args("time.tzname", heap)?;
+                self.tzname(heap)
+            }
+            Some(StaticStrings::Dst) => {
+                args.check_zero_args("time.dst", heap)?;
+                Ok(Value::None)
+            }
+            _ => {
+                args.drop_with_heap(heap);
+                Err(ExcType::attribute_error(Type::Time, attr.as_str(interns)))
+            }
+        }
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        Ok(time_attr(self, attr_id, heap)?.map(AttrCallResult::Value))
+    }
+}
+
+/// Returns an attribute of a time or datetime's time of day, like `hour` or `tzinfo`.
+fn time_attr(time: &Time, attr_id: StringId, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Option<Value>> {
+    let value = match StaticStrings::from_string_id(attr_id) {
+        Some(StaticStrings::Hour) => u32::from(time.hour),
+        Some(StaticStrings::Minute) => u32::from(time.minute),
+        Some(StaticStrings::Second) => u32::from(time.second),
+        Some(StaticStrings::Microsecond) => time.microsecond,
+        // Folds only matter for time zones with transitions, which Monty doesn't have
+        Some(StaticStrings::Fold) => 0,
+        Some(StaticStrings::Tzinfo) => return Ok(Some(time.tzinfo_value(heap)?)),
+        _ => return Ok(None),
+    };
+    Ok(Some(Value::Int(i64::from(value))))
+}
+
+// =============================================================================
+// datetime
+// =============================================================================
+
+/// A date and time of day, with an optional fixed-offset timezone.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct DateTime {
+    date: Date,
+    time: Time,
+}
+
+impl DateTime {
+    /// Combines a date and a time of day.
+    #[must_use]
+    pub fn new(date: Date, time: Time) -> Self {
+        Self { date, time }
+    }
+
+    /// Creates a naive or aware datetime from microseconds since 0001-01-01 00:00 local time.
+    ///
+    /// # Errors
+    /// Returns `OverflowError` if the result is outside `datetime.min..=datetime.max`.
+    fn from_local_micros(micros: i128, tzinfo: Option<TimeZone>) -> RunResult<Self> {
+        let day = i128::from(MICROS_PER_DAY);
+        let date = match i64::try_from(micros.div_euclid(day)) {
+            Ok(days) => Date::from_ordinal(days + 1)?,
+            Err(_) => return Err(SimpleException::new_msg(ExcType::OverflowError, "date value out of range").into()),
+        };
+        #[expect(clippy::cast_possible_truncation, reason = "the remainder is less than a day")]
+        let time = Time::from_micros_of_day(micros.rem_euclid(day) as i64, tzinfo);
+        Ok(Self { date, time })
+    }
+
+    /// Returns the date part.
+    #[must_use]
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    /// Returns the time part, including the timezone.
+    #[must_use]
+    pub fn time(&self) -> &Time {
+        &self.time
+    }
+
+    /// Returns the number of microseconds since 0001-01-01 00:00 in the datetime's own time zone.
+    fn local_micros(&self) -> i64 {
+        (self.date.toordinal() - 1) * MICROS_PER_DAY + self.time.micros_of_day()
+    }
+
+    /// Returns the number of microseconds since 0001-01-01 00:00 UTC, treating naive datetimes as UTC.
+    fn utc_micros(&self) -> i64 {
+        self.local_micros() - self.time.tzinfo.as_ref().map_or(0, TimeZone::offset_micros)
+    }
+
+    /// Returns the POSIX timestamp, treating naive datetimes as UTC.
+    #[must_use]
+    pub fn timestamp(&self) -> f64 {
+        (self.utc_micros() - (EPOCH_ORDINAL - 1) * MICROS_PER_DAY) as f64 / 1e6
+    }
+
+    /// Creates an aware datetime in `tzinfo`, or a naive UTC one, from a POSIX timestamp in microseconds.
+    fn from_timestamp_micros(micros: i128, tzinfo: Option<TimeZone>) -> RunResult<Self> {
+        let offset = tzinfo.as_ref().map_or(0, TimeZone::offset_micros);
+        let local = micros + i128::from((EPOCH_ORDINAL - 1) * MICROS_PER_DAY + offset);
+        Self::from_local_micros(local, tzinfo).map_err(|_| {
+            let year =
+                ordinal_to_ymd(i64::try_from(local.div_euclid(i128::from(MICROS_PER_DAY)) + 1).unwrap_or(i64::MAX / 2))
+                    .0;
+            ExcType::value_error(format!("year {year} is out of range"))
+        })
+    }
+
+    /// Returns the same instant in another timezone, treating naive datetimes as UTC.
+    ///
+    /// # Errors
+    /// Returns `OverflowError` if the result is outside `datetime.min..=datetime.max`.
+    pub fn astimezone(&self, tzinfo: TimeZone) -> RunResult<Self> {
+        let local = i128::from(self.utc_micros()) + i128::from(tzinfo.offset_micros());
+        Self::from_local_micros(local, Some(tzinfo))
+    }
+
+    /// Compares two datetimes, adjusting aware datetimes to UTC.
+    ///
+    /// Returns `None` when comparing a naive datetime with an aware one.
+    fn compare(&self, other: &Self) -> Option<Ordering> {
+        match (&self.time.tzinfo, &other.time.tzinfo) {
+            (None, None) | (Some(_), Some(_)) => Some(self.utc_micros().cmp(&other.utc_micros())),
+            _ => None,
+        }
+    }
+
+    /// Returns a value which is equal for equal datetimes, for hashing.
+    #[must_use]
+    pub fn hash_key(&self) -> i64 {
+        self.utc_micros()
+    }
+
+    /// Allocates this datetime on the heap.
+    pub fn into_value(self, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, ResourceError> {
+        Ok(Value::Ref(heap.allocate(HeapData::DateTime(self))?))
+    }
+
+    /// Creates a datetime from the
+    /// `datetime(year, month, day, hour=0, minute=0, second=0, microsecond=0, tzinfo=None)`
+    /// constructor call.
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let values = bind_args("datetime", args, DATETIME_PARAMS, 3, heap, interns)?;
+        let result = date_from_args(&values[..3], heap)
+            .and_then(|date| Ok(Self::new(date, replace_time(&Time::MIDNIGHT, &values[3..], heap)?)));
+        for value in values {
+            value.drop_with_heap(heap);
+        }
+        Ok(result?.into_value(heap)?)
+    }
+
+    /// Returns the datetime in ISO 8601 format, e.g. `2024-01-05T12:30:00+01:00`.
+    #[must_use]
+    pub fn isoformat(&self, separator: char, timespec: Timespec) -> String {
+        let mut s = self.date.isoformat();
+        s.push(separator);
+        datetime_format::write_iso_time(&mut s, &self.time, timespec);
+        s
+    }
+
+    /// Formats like CPython: `datetime.datetime(2024, 1, 5, 12, 30)`.
+    pub fn repr_fmt(&self, f: &mut impl Write) -> std::fmt::Result {
+        write!(
+            f,
+            "datetime.datetime({}, {}, {}, {}, {}",
+            self.date.year, self.date.month, self.date.day, self.time.hour, self.time.minute
+        )?;
+        self.time.repr_tail_fmt(f)
+    }
+
+    /// Implements `datetime.isoformat(sep='T', timespec='auto')`.
+    fn call_isoformat(
+        &self,
+        args: ArgValues,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        let [separator, timespec] = bind_args("isoformat", args, ["sep", "timespec"], 0, heap, interns)?;
+        defer_drop!(separator, heap);
+        defer_drop!(timespec, heap);
+        let separator = match separator {
+            Some(separator) => {
+                let text = separator.as_either_str(heap);
+                let mut chars = text.as_ref().map_or("", |text| text.as_str(interns)).chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => c,
+                    _ => {
+                        return Err(ExcType::type_error(format!(
+                            "isoformat() argument 1 must be a unicode character, not {}",
+                            separator.py_type(heap)
+                        )));
+                    }
+                }
+            }
+            None => 'T',
+        };
+        let timespec = timespec_arg(timespec.as_ref(), 2, heap, interns)?;
+        allocate_string(self.isoformat(separator, timespec), heap)
+    }
+
+    /// Implements `datetime.astimezone(tz=None)`, where `None` means UTC.
+    fn call_astimezone(
+        &self,
+        args: ArgValues,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        let [tz] = bind_args("astimezone", args, ["tz"], 0, heap, interns)?;
+        defer_drop!(tz, heap);
+        let tzinfo = match tz {
+            Some(tz) => tzinfo_arg(tz, heap)?,
+            None => None,
+        };
+        Ok(self.astimezone(tzinfo.unwrap_or(TimeZone::UTC))?.into_value(heap)?)
+    }
+}
+
+/// Parameter names of the `datetime()` constructor and `datetime.replace()`.
+const DATETIME_PARAMS: [&str; 8] = [
+    "year",
+    "month",
+    "day",
+    "hour",
+    "minute",
+    "second",
+    "microsecond",
+    "tzinfo",
+];
+
+impl PyTrait for DateTime {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::DateTime
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self.compare(other) == Some(Ordering::Equal)
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // No-op: datetimes don't hold Value references
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        self.repr_fmt(f)
+    }
+
+    fn py_str(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Cow<'static, str> {
+        Cow::Owned(self.isoformat(' ', Timespec::Auto))
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        match attr.static_string() {
+            Some(StaticStrings::Date) => {
+                args.check_zero_args("datetime.date", heap)?;
+                Ok(self.date.into_value(heap)?)
+            }
+            Some(StaticStrings::Time) => {
+                args.check_zero_args("datetime.time", heap)?;
+                Ok(self.time.with_tzinfo(None).into_value(heap)?)
+            }
+            Some(StaticStrings::Timetz) => {
+                args.check_zero_args("datetime.timetz", heap)?;
+                Ok(self.time.clone().into_value(heap)?)
+            }
+            Some(StaticStrings::Isoformat) => self.call_isoformat(args, heap, interns),
+            Some(StaticStrings::Replace) => {
+                let values = bind_args("replace", args, DATETIME_PARAMS, 0, heap, interns)?;
+                let result = replace_date(self.date, &values[..3], heap)
+                    .and_then(|date| Ok(Self::new(date, replace_time(&self.time, &values[3..], heap)?)));
+                for value in values {
+                    value.drop_with_heap(heap);
+                }
+                Ok(result?.into_value(heap)?)
+            }
+            Some(StaticStrings::Timestamp) => {
+                args.check_zero_args("datetime.timestamp", heap)?;
+                Ok(Value::Float(self.timestamp()))
+            }
+            Some(StaticStrings::Astimezone) => self.call_astimezone(args, heap, interns),
+            Some(StaticStrings::Utcoffset) => {
+                args.check_zero_args("datetime.utcoffset", heap)?;
+                self.time.utcoffset(heap)
+            }
+            Some(StaticStrings::Tzname) => {
+                args.check_zero_args("datetime.tzname", heap)?;
+                self.time.tzname(heap)
+            }
+            Some(StaticStrings::Dst) => {
+                args.check_zero_args("datetime.dst", heap)?;
+                Ok(Value::None)
+            }
+            Some(method) if is_calendar_method(method) => {
+                call_calendar_method("datetime", self.date, &self.time, method, args, heap, interns)
+            }
+            _ => {
+                args.drop_with_heap(heap);
+                Err(ExcType::attribute_error(Type::DateTime, attr.as_str(interns)))
+            }
+        }
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = match date_attr(self.date, attr_id) {
+            Some(value) => Some(value),
+            None => time_attr(&self.time, attr_id, heap)?,
+        };
+        Ok(value.map(AttrCallResult::Value))
+    }
+}
+
+// =============================================================================
+// Class methods and attributes
+// =============================================================================
+
+/// Returns whether `method` is `datetime.now()`, `datetime.today()` or `date.today()`,
+/// which ask the host for the current time via [`start_now`].
+#[must_use]
+pub(crate) fn is_now_method(t: Type, method_id: StringId) -> bool {
+    match t {
+        Type::Date => method_id == StaticStrings::Today,
+        Type::DateTime => method_id == StaticStrings::Now || method_id == StaticStrings::Today,
+        _ => false,
+    }
+}
+
+/// The call of `datetime.now()`, `datetime.today()` or `date.today()` waiting for the host
+/// to answer `OsFunction::DateTimeNow` with the current time.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) enum NowRequest {
+    /// `date.today()`, which returns the date part of the host's naive datetime.
+    Date,
+    /// `datetime.now(tz)` or `datetime.today()`, which returns a naive datetime
+    /// or an aware one in the given timezone.
+    DateTime(Option<TimeZone>),
+}
+
+/// Checks the arguments of a method for which [`is_now_method`] is true.
+///
+/// Returns the request to finish once the host answers, and the argument of the
+/// `OsFunction::DateTimeNow` call: the requested timezone, or `None`.
+pub(crate) fn start_now(
+    t: Type,
+    method_id: StringId,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<(NowRequest, ArgValues)> {
+    if method_id == StaticStrings::Today {
+        let name = if t == Type::Date {
+            "date.today"
+        } else {
+            "datetime.today"
+        };
+        args.check_zero_args(name, heap)?;
+        let request = if t == Type::Date {
+            NowRequest::Date
+        } else {
+            NowRequest::DateTime(None)
+        };
+        return Ok((request, ArgValues::One(Value::None)));
+    }
+    let [tz] = bind_args("now", args, ["tz"], 0, heap, interns)?;
+    let tz = tz.unwrap_or(Value::None);
+    match tzinfo_arg(&tz, heap) {
+        Ok(tzinfo) => Ok((NowRequest::DateTime(tzinfo), ArgValues::One(tz))),
+        Err(e) => {
+            tz.drop_with_heap(heap);
+            Err(e)
+        }
+    }
+}
+
+impl NowRequest {
+    /// Converts the host's answer to the value the call returns.
+    ///
+    /// The host answers with a datetime. A naive answer is the local time, which is
+    /// taken to be UTC when a timezone was requested, and an aware answer is converted
+    /// to the requested timezone, or to naive UTC when none was.
+    pub fn finish(self, value: Value, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        defer_drop!(value, heap);
+        let Some(now) = as_datetime(value, heap) else {
+            return Err(ExcType::type_error(format!(
+                "datetime.now() OS call must return a datetime, not '{}'",
+                value.py_type(heap)
+            )));
+        };
+        let (date_only, tzinfo) = match self {
+            Self::Date => (true, None),
+            Self::DateTime(tzinfo) => (false, tzinfo),
+        };
+        let now = match tzinfo {
+            Some(tzinfo) => now.astimezone(tzinfo)?,
+            None if now.time.tzinfo.is_some() => DateTime::from_local_micros(i128::from(now.utc_micros()), None)?,
+            None => now,
+        };
+        if date_only {
+            Ok(now.date.into_value(heap)?)
+        } else {
+            Ok(now.into_value(heap)?)
+        }
+    }
+}
+
+/// Returns whether `method` is a class method of `t` which [`call_class_method`] implements.
+#[must_use]
+pub(crate) fn is_class_method(t: Type, method_id: StringId) -> bool {
+    let Some(method) = StaticStrings::from_string_id(method_id) else {
+        return false;
+    };
+    match t {
+        Type::Date => matches!(
+            method,
+            StaticStrings::Fromisoformat
+                | StaticStrings::Fromordinal
+                | StaticStrings::Fromisocalendar
+                | StaticStrings::Fromtimestamp
+                | StaticStrings::Strptime
+        ),
+        Type::Time => matches!(method, StaticStrings::Fromisoformat | StaticStrings::Strptime),
+        Type::DateTime => matches!(
+            method,
+            StaticStrings::Fromisoformat
+                | StaticStrings::Fromordinal
+                | StaticStrings::Fromisocalendar
+                | StaticStrings::Fromtimestamp
+                | StaticStrings::Strptime
+                | StaticStrings::Combine
+        ),
+        _ => false,
+    }
+}
+
+/// Calls a class method of `date`, `time` or `datetime`, like `datetime.fromisoformat()`.
+///
+/// The caller must have checked the method with [`is_class_method`].
+pub(crate) fn call_class_method(
+    t: Type,
+    method_id: StringId,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let type_name = if t == Type::Date {
+        "date"
+    } else if t == Type::Time {
+        "time"
+    } else {
+        "datetime"
+    };
+    match StaticStrings::from_string_id(method_id) {
+        Some(StaticStrings::Fromisoformat) => {
+            let string = args.get_one_arg(&format!("{type_name}.fromisoformat"), heap)?;
+            defer_drop!(string, heap);
+            let Some(text) = string.as_either_str(heap) else {
+                return Err(ExcType::type_error("fromisoformat: argument must be str"));
+            };
+            let text = text.as_str(interns);
+            let value = match t {
+                Type::Date => datetime_format::parse_iso_date(text).map(|date| date.into_value(heap)),
+                Type::Time => datetime_format::parse_iso_time(text).map(|time| time.into_value(heap)),
+                _ => datetime_format::parse_iso_datetime(text).map(|dt| dt.into_value(heap)),
+            };
+            match value {
+                Some(value) => Ok(value?),
+                None => Err(ExcType::value_error(format!(
+                    "Invalid isoformat string: {}",
+                    StringRepr(text)
+                ))),
+            }
+        }
+        Some(StaticStrings::Fromordinal) => {
+            let ordinal = args.get_one_arg(&format!("{type_name}.fromordinal"), heap)?;
+            defer_drop!(ordinal, heap);
+            let ordinal = int_arg(ordinal, heap)?;
+            if ordinal < 1 {
+                return Err(ExcType::value_error("ordinal must be >= 1"));
+            }
+            let date = Date::from_ordinal(ordinal)
+                .map_err(|_| ExcType::value_error(format!("year {} is out of range", ordinal_to_ymd(ordinal).0)))?;
+            date_or_datetime(t, date, heap)
+        }
+        Some(StaticStrings::Fromisocalendar) => {
+            let [year, week, day] = bind_args("fromisocalendar", args, ["year", "week", "day"], 3, heap, interns)?;
+            defer_drop!(year, heap);
+            defer_drop!(week, heap);
+            defer_drop!(day, heap);
+            let date = Date::from_isocalendar(
+                int_or(year.as_ref(), 1, heap)?,
+                int_or(week.as_ref(), 1, heap)?,
+                int_or(day.as_ref(), 1, heap)?,
+            )?;
+            date_or_datetime(t, date, heap)
+        }
+        Some(StaticStrings::Fromtimestamp) => {
+            let [timestamp, tz] = if t == Type::Date {
+                let timestamp = args.get_one_arg("date.fromtimestamp", heap)?;
+                [Some(timestamp), None]
+            } else {
+                bind_args("fromtimestamp", args, ["timestamp", "tz"], 1, heap, interns)?
+            };
+            defer_drop!(timestamp, heap);
+            defer_drop!(tz, heap);
+            let tzinfo = match tz {
+                Some(tz) => tzinfo_arg(tz, heap)?,
+                None => None,
+            };
+            let micros = timestamp_micros(timestamp.as_ref().unwrap_or(&Value::None), heap)?;
+            let dt = DateTime::from_timestamp_micros(micros, tzinfo)?;
+            if t == Type::Date {
+                Ok(dt.date.into_value(heap)?)
+            } else {
+                Ok(dt.into_value(heap)?)
+            }
+        }
+        Some(StaticStrings::Strptime) => {
+            let (string, format) = args.get_two_args(&format!("{type_name}.strptime"), heap)?;
+            defer_drop!(string, heap);
+            defer_drop!(format, heap);
+            let string = str_arg(string, "strptime", 1, heap)?;
+            let format = str_arg(format, "strptime", 2, heap)?;
+            let dt = datetime_format::strptime(string.as_str(interns), format.as_str(interns))?;
+            match t {
+                Type::Date => Ok(dt.date.into_value(heap)?),
+                Type::Time => Ok(dt.time.into_value(heap)?),
+                _ => Ok(dt.into_value(heap)?),
+            }
+        }
+        _ => {
+            let [date, time, tzinfo] = bind_args("combine", args, ["date", "time", "tzinfo"], 2, heap, interns)?;
+            defer_drop!(date, heap);
+            defer_drop!(time, heap);
+            defer_drop!(tzinfo, heap);
+            let date_value = date.as_ref().unwrap_or(&Value::None);
+            let Some(date) = as_date(date_value, heap) else {
+                return Err(ExcType::type_error(format!(
+                    "combine() argument 1 must be datetime.date, not {}",
+                    date_value.py_type(heap)
+                )));
+            };
+            let time_value = time.as_ref().unwrap_or(&Value::None);
+            let Some(time) = as_time(time_value, heap) else {
+                return Err(ExcType::type_error(format!(
+                    "combine() argument 2 must be datetime.time, not {}",
+                    time_value.py_type(heap)
+                )));
+            };
+            let tzinfo = match tzinfo {
+                Some(tzinfo) => tzinfo_arg(tzinfo, heap)?,
+                None => time.tzinfo.clone(),
+            };
+            Ok(DateTime::new(date, time.with_tzinfo(tzinfo)).into_value(heap)?)
+        }
+    }
+}
+
+/// Returns `date` as a date, or as a datetime at midnight for `datetime` class methods.
+fn date_or_datetime(t: Type, date: Date, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    if t == Type::Date {
+        Ok(date.into_value(heap)?)
+    } else {
+        Ok(DateTime::new(date, Time::MIDNIGHT).into_value(heap)?)
+    }
+}
+
+/// Converts a POSIX timestamp argument to microseconds, rounding floats half to even.
+fn timestamp_micros(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<i128> {
+    let seconds = match value {
+        Value::Float(x) => *x,
+        Value::Ref(id) if matches!(heap.get(*id), HeapData::LongInt(_)) => f64::INFINITY,
+        _ => return Ok(i128::from(int_arg(value, heap)?) * i128::from(MICROS_PER_SECOND)),
+    };
+    if seconds.is_nan() {
+        return Err(ExcType::value_error("Invalid value NaN (not a number)"));
+    }
+    if seconds.abs() >= 1e18 {
+        return Err(
+            SimpleException::new_msg(ExcType::OverflowError, "timestamp out of range for platform time_t").into(),
+        );
+    }
+    let whole = seconds.trunc();
+    #[expect(clippy::cast_possible_truncation, reason = "the timestamp is checked to be in range")]
+    let micros = whole as i128 * i128::from(MICROS_PER_SECOND) + ((seconds - whole) * 1e6).round_ties_even() as i128;
+    Ok(micros)
+}
+
+/// Returns a class attribute of one of the datetime types, like `date.min` or `timezone.utc`.
+///
+/// Returns `Ok(None)` for other types and attributes.
+pub(crate) fn type_attr(
+    t: Type,
+    attr_id: StringId,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> Result<Option<Value>, ResourceError> {
+    let Some(attr) = StaticStrings::from_string_id(attr_id) else {
+        return Ok(None);
+    };
+    let one_micro = TimeDelta {
+        microseconds: 1,
+        ..TimeDelta::ZERO
+    };
+    let value = match (t, attr) {
+        (Type::Date, StaticStrings::Min) => Date::MIN.into_value(heap)?,
+        (Type::Date, StaticStrings::Max) => Date::MAX.into_value(heap)?,
+        (Type::Date, StaticStrings::Resolution) => TimeDelta {
+            days: 1,
+            ..TimeDelta::ZERO
+        }
+        .into_value(heap)?,
+        (Type::Time, StaticStrings::Min) => Time::MIDNIGHT.into_value(heap)?,
+        (Type::Time, StaticStrings::Max) => Time::MAX.into_value(heap)?,
+        (Type::DateTime, StaticStrings::Min) => DateTime::new(Date::MIN, Time::MIDNIGHT).into_value(heap)?,
+        (Type::DateTime, StaticStrings::Max) => DateTime::new(Date::MAX, Time::MAX).into_value(heap)?,
+        (Type::TimeDelta, StaticStrings::Min) => TimeDelta {
+            days: -999_999_999,
+            ..TimeDelta::ZERO
+        }
+        .into_value(heap)?,
+        (Type::TimeDelta, StaticStrings::Max) => TimeDelta {
+            days: 999_999_999,
+            seconds: 86_399,
+            microseconds: 999_999,
+        }
+        .into_value(heap)?,
+        (Type::Time | Type::DateTime | Type::TimeDelta, StaticStrings::Resolution) => one_micro.into_value(heap)?,
+        (Type::TimeZone, StaticStrings::Utc) => TimeZone::UTC.into_value(heap)?,
+        (Type::TimeZone, StaticStrings::Min) => TimeZone {
+            offset: -86_340,
+            name: None,
+        }
+        .into_value(heap)?,
+        (Type::TimeZone, StaticStrings::Max) => TimeZone {
+            offset: 86_340,
+            name: None,
+        }
+        .into_value(heap)?,
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+// =============================================================================
+// Arithmetic and comparison
+// =============================================================================
+
+/// An operand of arithmetic involving dates, datetimes or timedeltas.
+enum Operand {
+    Date(Date),
+    DateTime(DateTime),
+    Delta(TimeDelta),
+    Int(BigInt),
+    Float(f64),
+    Other,
+}
+
+impl Operand {
+    fn of(value: &Value, heap: &Heap<impl ResourceTracker>) -> Self {
+        match value {
+            Value::Int(i) => Self::Int(BigInt::from(*i)),
+            Value::Bool(b) => Self::Int(BigInt::from(u8::from(*b))),
+            Value::Float(f) => Self::Float(*f),
+            Value::Ref(id) => match heap.get(*id) {
+                HeapData::Date(date) => Self::Date(*date),
+                HeapData::DateTime(dt) => Self::DateTime(dt.clone()),
+                HeapData::TimeDelta(delta) => Self::Delta(*delta),
+                HeapData::LongInt(li) => Self::Int(li.inner().clone()),
+                _ => Self::Other,
+            },
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Applies an arithmetic operator to dates, datetimes and timedeltas.
+///
+/// Supports `date ± timedelta`, `date - date` (and the same for datetimes),
+/// `timedelta ± timedelta`, `timedelta * number`, `timedelta / number`,
+/// `timedelta // int`, and `/`, `//` and `%` between timedeltas.
+///
+/// Returns `Ok(None)` for other operands, so the caller can report the usual TypeError.
+pub(crate) fn binary_op(
+    lhs: &Value,
+    rhs: &Value,
+    op: &Operator,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> RunResult<Option<Value>> {
+    if !is_arithmetic_operand(lhs, heap) && !is_arithmetic_operand(rhs, heap) {
+        return Ok(None);
+    }
+    let result = match (op, Operand::of(lhs, heap), Operand::of(rhs, heap)) {
+        (Operator::Add, Operand::Delta(a), Operand::Delta(b)) => {
+            Operand::Delta(TimeDelta::from_micros(a.total_micros() + b.total_micros())?)
+        }
+        (Operator::Sub, Operand::Delta(a), Operand::Delta(b)) => {
+            Operand::Delta(TimeDelta::from_micros(a.total_micros() - b.total_micros())?)
+        }
+        (Operator::Add, Operand::Date(date), Operand::Delta(delta))
+        | (Operator::Add, Operand::Delta(delta), Operand::Date(date)) => {
+            Operand::Date(date.add_days(i64::from(delta.days))?)
+        }
+        (Operator::Sub, Operand::Date(date), Operand::Delta(delta)) => {
+            Operand::Date(date.add_days(-i64::from(delta.days))?)
+        }
+        (Operator::Sub, Operand::Date(a), Operand::Date(b)) => {
+            Operand::Delta(TimeDelta::new(a.toordinal() - b.toordinal(), 0, 0)?)
+        }
+        (Operator::Add, Operand::DateTime(dt), Operand::Delta(delta))
+        | (Operator::Add, Operand::Delta(delta), Operand::DateTime(dt)) => {
+            let micros = i128::from(dt.local_micros()) + delta.total_micros();
+            Operand::DateTime(DateTime::from_local_micros(micros, dt.time.tzinfo)?)
+        }
+        (Operator::Sub, Operand::DateTime(dt), Operand::Delta(delta)) => {
+            let micros = i128::from(dt.local_micros()) - delta.total_micros();
+            Operand::DateTime(DateTime::from_local_micros(micros, dt.time.tzinfo)?)
+        }
+        (Operator::Sub, Operand::DateTime(a), Operand::DateTime(b)) => {
+            if a.time.tzinfo.is_some() != b.time.tzinfo.is_some() {
+                return Err(ExcType::type_error(
+                    "can't subtract offset-naive and offset-aware datetimes",
+                ));
+            }
+            Operand::Delta(TimeDelta::from_micros(
+                i128::from(a.utc_micros()) - i128::from(b.utc_micros()),
+            )?)
+        }
+        (Operator::Mult, Operand::Delta(delta), Operand::Int(n))
+        | (Operator::Mult, Operand::Int(n), Operand::Delta(delta)) => {
+            Operand::Delta(TimeDelta::from_big_micros(&(BigInt::from(delta.total_micros()) * n))?)
+        }
+        (Operator::Mult, Operand::Delta(delta), Operand::Float(x))
+        | (Operator::Mult, Operand::Float(x), Operand::Delta(delta)) => {
+            let (numerator, denominator) = float_ratio(x)?;
+            let micros = BigInt::from(delta.total_micros()) * numerator;
+            Operand::Delta(TimeDelta::from_big_micros(&div_round_half_even(&micros, &denominator))?)
+        }
+        (Operator::Div, Operand::Delta(a), Operand::Delta(b)) => {
+            if b == TimeDelta::ZERO {
+                return Err(ExcType::zero_division().into());
+            }
+            Operand::Float(a.total_micros() as f64 / b.total_micros() as f64)
+        }
+        (Operator::Div, Operand::Delta(delta), Operand::Int(n)) => {
+            if n.is_zero() {
+                return Err(ExcType::zero_division().into());
+            }
+            let micros = div_round_half_even(&BigInt::from(delta.total_micros()), &n);
+            Operand::Delta(TimeDelta::from_big_micros(&micros)?)
+        }
+        (Operator::Div, Operand::Delta(delta), Operand::Float(x)) => {
+            if x == 0.0 {
+                return Err(ExcType::zero_division().into());
+            }
+            let (numerator, denominator) = float_ratio(x)?;
+            let micros = BigInt::from(delta.total_micros()) * denominator;
+            Operand::Delta(TimeDelta::from_big_micros(&div_round_half_even(&micros, &numerator))?)
+        }
+        (Operator::FloorDiv, Operand::Delta(a), Operand::Delta(b)) => {
+            if b == TimeDelta::ZERO {
+                return Err(ExcType::zero_division().into());
+            }
+            Operand::Int(BigInt::from(a.total_micros().div_floor(&b.total_micros())))
+        }
+        (Operator::FloorDiv, Operand::Delta(delta), Operand::Int(n)) => {
+            if n.is_zero() {
+                return Err(ExcType::zero_division().into());
+            }
+            Operand::Delta(TimeDelta::from_big_micros(
+                &BigInt::from(delta.total_micros()).div_floor(&n),
+            )?)
+        }
+        (Operator::Mod, Operand::Delta(a), Operand::Delta(b)) => {
+            if b == TimeDelta::ZERO {
+                return Err(ExcType::zero_division().into());
+            }
+            Operand::Delta(TimeDelta::from_micros(a.total_micros().mod_floor(&b.total_micros()))?)
+        }
+        _ => return Ok(None),
+    };
+    let value = match result {
+        Operand::Date(date) => date.into_value(heap)?,
+        Operand::DateTime(dt) => dt.into_value(heap)?,
+        Operand::Delta(delta) => delta.into_value(heap)?,
+        Operand::Int(n) => LongInt::new(n).into_value(heap)?,
+        Operand::Float(x) => Value::Float(x),
+        Operand::Other => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+/// Returns whether `value` is a date, datetime or timedelta, the types [`binary_op`] handles.
+fn is_arithmetic_operand(value: &Value, heap: &Heap<impl ResourceTracker>) -> bool {
+    matches!(value, Value::Ref(id) if matches!(
+        heap.get(*id),
+        HeapData::Date(_) | HeapData::DateTime(_) | HeapData::TimeDelta(_)
+    ))
+}
+
+/// Returns `num / den` rounded half to even.
+fn div_round_half_even(num: &BigInt, den: &BigInt) -> BigInt {
+    let (num, den) = if den.is_negative() {
+        (-num, -den)
+    } else {
+        (num.clone(), den.clone())
+    };
+    let (quotient, remainder) = num.div_mod_floor(&den);
+    let twice: BigInt = remainder * 2;
+    match twice.cmp(&den) {
+        Ordering::Greater => quotient + 1,
+        Ordering::Equal if quotient.is_odd() => quotient + 1,
+        _ => quotient,
+    }
+}
+
+/// Returns an exact `(numerator, denominator)` pair for a float, like `float.as_integer_ratio()`
+/// but not reduced to lowest terms.
+fn float_ratio(x: f64) -> RunResult<(BigInt, BigInt)> {
+    if x.is_nan() {
+        return Err(ExcType::value_error("cannot convert NaN to integer ratio"));
+    }
+    if x.is_infinite() {
+        return Err(
+            SimpleException::new_msg(ExcType::OverflowError, "cannot convert Infinity to integer ratio").into(),
+        );
+    }
+    let bits = x.to_bits();
+    let exponent = i32::try_from((bits >> 52) & 0x7ff).expect("11-bit exponent");
+    let fraction = bits & 0x000f_ffff_ffff_ffff;
+    let (mantissa, exponent) = if exponent == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | 0x0010_0000_0000_0000, exponent - 1075)
+    };
+    let mantissa = if x.is_sign_negative() {
+        -BigInt::from(mantissa)
+    } else {
+        BigInt::from(mantissa)
+    };
+    if exponent >= 0 {
+        Ok((mantissa << exponent.unsigned_abs(), BigInt::from(1)))
+    } else {
+        Ok((mantissa, BigInt::from(1) << exponent.unsigned_abs()))
+    }
+}
+
+/// Compares two datetime values of the same type, for `<`, `sorted()`, `min()` and the like.
+///
+/// Returns `None` for other values, dates against datetimes, and naive against aware values.
+#[must_use]
+pub(crate) fn compare(lhs: &HeapData, rhs: &HeapData) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (HeapData::Date(a), HeapData::Date(b)) => Some(a.cmp(b)),
+        (HeapData::Time(a), HeapData::Time(b)) => a.compare(b),
+        (HeapData::DateTime(a), HeapData::DateTime(b)) => a.compare(b),
+        (HeapData::TimeDelta(a), HeapData::TimeDelta(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+/// Raises the TypeError of an ordering comparison involving a datetime value which
+/// [`compare`] can't order, as CPython does rather than returning `False`.
+///
+/// Does nothing when neither operand is a date, time, datetime or timedelta, or when
+/// the operands can be ordered.
+pub(crate) fn check_orderable(
+    lhs: &Value,
+    rhs: &Value,
+    symbol: &str,
+    heap: &Heap<impl ResourceTracker>,
+) -> RunResult<()> {
+    let data = |value: &Value| {
+        value.ref_id().map(|id| heap.get(id)).filter(|data| {
+            matches!(
+                data,
+                HeapData::Date(_) | HeapData::Time(_) | HeapData::DateTime(_) | HeapData::TimeDelta(_)
+            )
+        })
+    };
+    let (lhs_data, rhs_data) = (data(lhs), data(rhs));
+    if lhs_data.is_none() && rhs_data.is_none() {
+        return Ok(());
+    }
+    match (lhs_data, rhs_data) {
+        (Some(HeapData::Time(a)), Some(HeapData::Time(b))) if a.compare(b).is_none() => {
+            Err(ExcType::type_error("can't compare offset-naive and offset-aware times"))
+        }
+        (Some(HeapData::DateTime(a)), Some(HeapData::DateTime(b))) if a.compare(b).is_none() => Err(
+            ExcType::type_error("can't compare offset-naive and offset-aware datetimes"),
+        ),
+        (Some(a), Some(b)) if compare(a, b).is_some() => Ok(()),
+        _ => Err(ExcType::type_error(format!(
+            "'{symbol}' not supported between instances of '{}' and '{}'",
+            lhs.py_type(heap),
+            rhs.py_type(heap)
+        ))),
+    }
+}
+
+/// Formats a date, time or datetime with a format spec, as its `__format__` does for
+/// `format()`, `str.format()` and f-strings: the spec is a `strftime()` format.
+///
+/// Returns `None` for other values.
+#[must_use]
+pub(crate) fn format_spec(data: &HeapData, spec: &str) -> Option<String> {
+    match data {
+        HeapData::Date(date) => Some(datetime_format::strftime(spec, *date, &Time::MIDNIGHT)),
+        HeapData::Time(time) => Some(datetime_format::strftime(spec, Date::TIME_STRFTIME, time)),
+        HeapData::DateTime(dt) => Some(datetime_format::strftime(spec, dt.date, &dt.time)),
+        _ => None,
+    }
+}
+
+// =============================================================================
+// Argument helpers
+// =============================================================================
+
+/// Returns the datetime `value` refers to, if it is one.
+fn as_datetime(value: &Value, heap: &Heap<impl ResourceTracker>) -> Option<DateTime> {
+    match value {
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::DateTime(dt) => Some(dt.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns whether `value` is a datetime.
+fn is_datetime(value: &Value, heap: &Heap<impl ResourceTracker>) -> bool {
+    matches!(value, Value::Ref(id) if matches!(heap.get(*id), HeapData::DateTime(_)))
+}
+
+/// Returns the date `value` refers to, or the date part of a datetime.
+fn as_date(value: &Value, heap: &Heap<impl ResourceTracker>) -> Option<Date> {
+    match value {
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Date(date) => Some(*date),
+            HeapData::DateTime(dt) => Some(dt.date),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the time `value` refers to, if it is one.
+fn as_time(value: &Value, heap: &Heap<impl ResourceTracker>) -> Option<Time> {
+    match value {
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Time(time) => Some(time.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the timedelta `value` refers to, if it is one.
+fn as_timedelta(value: &Value, heap: &Heap<impl ResourceTracker>) -> Option<TimeDelta> {
+    match value {
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::TimeDelta(delta) => Some(*delta),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Converts a `tzinfo` argument, which must be `None` or a `timezone`.
+fn tzinfo_arg(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<Option<TimeZone>> {
+    if let Value::Ref(id) = value
+        && let HeapData::TimeZone(tz) = heap.get(*id)
+    {
+        return Ok(Some(tz.clone()));
+    }
+    match value {
+        Value::None => Ok(None),
+        _ => Err(ExcType::type_error(format!(
+            "tzinfo argument must be None or of a tzinfo subclass, not type '{}'",
+            value.py_type(heap)
+        ))),
+    }
+}
+
+/// Converts an integer field argument to `i64`.
+///
+/// # Errors
+/// Returns `TypeError` for non-integers, including floats, and `OverflowError` for ints
+/// which don't fit, like CPython's C-level integer fields.
+fn int_arg(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<i64> {
+    match value {
+        Value::Int(i) => Ok(*i),
+        Value::Bool(b) => Ok(i64::from(*b)),
+        Value::Ref(id) if matches!(heap.get(*id), HeapData::LongInt(_)) => {
+            Err(SimpleException::new_msg(ExcType::OverflowError, "Python int too large to convert to C long").into())
+        }
+        _ => Err(ExcType::type_error_not_integer(value.py_type(heap))),
+    }
+}
+
+/// Converts an optional integer field argument, using `default` if it wasn't given.
+fn int_or(value: Option<&Value>, default: i64, heap: &Heap<impl ResourceTracker>) -> RunResult<i64> {
+    value.map_or(Ok(default), |value| int_arg(value, heap))
+}
+
+/// Returns the text of a str argument.
+///
+/// # Errors
+/// Returns `TypeError` like `strftime() argument 1 must be str, not int` for other types.
+fn str_arg(value: &Value, function: &str, position: usize, heap: &Heap<impl ResourceTracker>) -> RunResult<EitherStr> {
+    value.as_either_str(heap).ok_or_else(|| {
+        ExcType::type_error(format!(
+            "{function}() argument {position} must be str, not {}",
+            value.py_type(heap)
+        ))
+    })
+}
+
+/// Converts an optional `timespec` argument of `isoformat()`.
+fn timespec_arg(
+    value: Option<&Value>,
+    position: usize,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Timespec> {
+    let Some(value) = value else {
+        return Ok(Timespec::Auto);
+    };
+    let text = str_arg(value, "isoformat", position, heap)?;
+    text.as_str(interns)
+        .parse()
+        .map_err(|_| ExcType::value_error("Unknown timespec value"))
+}
+
+/// Converts a float to an int like `int()`, for the whole part of `timedelta()` arguments.
+fn float_to_bigint(x: f64) -> RunResult<BigInt> {
+    if x.is_nan() {
+        return Err(ExcType::value_error("cannot convert float NaN to integer"));
+    }
+    BigInt::from_f64(x).ok_or_else(|| {
+        SimpleException::new_msg(ExcType::OverflowError, "cannot convert float infinity to integer").into()
+    })
+}
+
+// =============================================================================
+// Calendar arithmetic, following CPython's `_pydatetime`
+// =============================================================================
+
+fn is_leap(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    if month == 2 && is_leap(i64::from(year)) {
+        29
+    } else {
+        DAYS_IN_MONTH[usize::from(month)]
+    }
+}
+
+/// Returns the number of days in the years before `year`, since year 1.
+fn days_before_year(year: i64) -> i64 {
+    let y = year - 1;
+    y * 365 + y.div_euclid(4) - y.div_euclid(100) + y.div_euclid(400)
+}
+
+/// Returns the number of days in the months of `year` before `month`.
+fn days_before_month(year: i64, month: u8) -> i64 {
+    i64::from(DAYS_BEFORE_MONTH[usize::from(month)]) + i64::from(month > 2 && is_leap(year))
+}
+
+fn ymd_to_ordinal(year: i64, month: u8, day: u8) -> i64 {
+    days_before_year(year) + days_before_month(year, month) + i64::from(day)
+}
+
+/// Converts an ordinal to `(year, month, day)`, also for ordinals outside the supported range.
+fn ordinal_to_ymd(ordinal: i64) -> (i64, u8, u8) {
+    let (n400, n) = (ordinal - 1).div_mod_floor(&146_097);
+    let (n100, n) = n.div_mod_floor(&36_524);
+    let (n4, n) = n.div_mod_floor(&1461);
+    let (n1, n) = n.div_mod_floor(&365);
+    let year = n400 * 400 + 1 + n100 * 100 + n4 * 4 + n1;
+    if n1 == 4 || n100 == 4 {
+        return (year - 1, 12, 31);
+    }
+    let leap = n1 == 3 && (n4 != 24 || n100 == 3);
+    #[expect(clippy::cast_possible_truncation, reason = "n is a day of the year")]
+    let mut month = ((n + 50) >> 5) as u8;
+    let mut preceding = i64::from(DAYS_BEFORE_MONTH[usize::from(month)]) + i64::from(month > 2 && leap);
+    if preceding > n {
+        month -= 1;
+        preceding -= i64::from(DAYS_IN_MONTH[usize::from(month)]) + i64::from(month == 2 && leap);
+    }
+    let day = u8::try_from(n - preceding + 1).expect("a day of the month");
+    (year, month, day)
+}
+
+/// Returns the ordinal of the Monday starting ISO week 1 of `year`.
+fn iso_week1_monday(year: i64) -> i64 {
+    let first_day = ymd_to_ordinal(year, 1, 1);
+    let first_weekday = (first_day + 6) % 7;
+    let monday = first_day - first_weekday;
+    // Week 1 is the week with the year's first Thursday
+    if first_weekday > 3 { monday + 7 } else { monday }
+}
@@ -0,0 +1,730 @@
+//! Parsing and formatting of the `datetime` types: `isoformat()`, `fromisoformat()`,
+//! `strftime()` and `strptime()`.
+//!
+//! Names of days and months are always English and `%c`, `%x` and `%X` use the C locale's
+//! formats, so results don't depend on the host's locale.
+use std::fmt::Write;
+
+use strum::EnumString;
+
+use super::{
+    datetime::{Date, DateTime, Time, TimeZone},
+    str::StringRepr,
+};
+use crate::exception_private::{ExcType, RunResult};
+
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// The `timespec` argument of `isoformat()`, choosing which time fields to include.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum Timespec {
+    /// Seconds, or microseconds if they aren't zero.
+    Auto,
+    Hours,
+    Minutes,
+    Seconds,
+    Milliseconds,
+    Microseconds,
+}
+
+// =============================================================================
+// isoformat and fromisoformat
+// =============================================================================
+
+/// Writes a UTC offset in seconds as `+HH<separator>MM`, adding `<separator>SS` if the
+/// seconds aren't zero.
+pub(crate) fn write_offset(out: &mut String, offset: i32, separator: &str) {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.unsigned_abs();
+    write!(out, "{sign}{:02}{separator}{:02}", offset / 3600, offset / 60 % 60)
+        .expect("writing to a String can't fail");
+    if offset % 60 != 0 {
+        write!(out, "{separator}{:02}", offset % 60).expect("writing to a String can't fail");
+    }
+}
+
+/// Writes a time in ISO 8601 format, like `12:30:00.500000`, followed by its UTC offset
+/// if it is aware.
+pub(crate) fn write_iso_time(out: &mut String, time: &Time, timespec: Timespec) {
+    let timespec = match timespec {
+        Timespec::Auto if time.microsecond() != 0 => Timespec::Microseconds,
+        Timespec::Auto => Timespec::Seconds,
+        timespec => timespec,
+    };
+    write!(out, "{:02}", time.hour()).expect("writing to a String can't fail");
+    if timespec != Timespec::Hours {
+        write!(out, ":{:02}", time.minute()).expect("writing to a String can't fail");
+    }
+    if !matches!(timespec, Timespec::Hours | Timespec::Minutes) {
+        write!(out, ":{:02}", time.second()).expect("writing to a String can't fail");
+    }
+    match timespec {
+        Timespec::Milliseconds => write!(out, ".{:03}", time.microsecond() / 1000),
+        Timespec::Microseconds => write!(out, ".{:06}", time.microsecond()),
+        _ => Ok(()),
+    }
+    .expect("writing to a String can't fail");
+    if let Some(tzinfo) = time.tzinfo() {
+        write_offset(out, tzinfo.offset(), ":");
+    }
+}
+
+/// Parses a date in any of the ISO 8601 formats `date.fromisoformat()` accepts:
+/// `YYYY-MM-DD`, `YYYYMMDD`, `YYYY-Www`, `YYYYWww`, `YYYY-Www-D` or `YYYYWwwD`.
+///
+/// Returns `None` if the string isn't a valid date.
+#[must_use]
+pub(crate) fn parse_iso_date(s: &str) -> Option<Date> {
+    let (date, len) = parse_date_prefix(s.as_bytes())?;
+    (len == s.len()).then_some(date)
+}
+
+/// Parses a time like `time.fromisoformat()`: `HH[:MM[:SS[.fff]]]` or `HH[MM[SS[.fff]]]`,
+/// optionally starting with `T` and ending with a UTC offset like `Z`, `+05:30` or `-0800`.
+///
+/// Fractions of a second may have any number of digits, and are truncated to microseconds.
+/// Returns `None` if the string isn't a valid time.
+#[must_use]
+pub(crate) fn parse_iso_time(s: &str) -> Option<Time> {
+    parse_time_with_offset(s.strip_prefix('T').unwrap_or(s).as_bytes())
+}
+
+/// Parses a datetime like `datetime.fromisoformat()`: a date as for [`parse_iso_date`],
+/// optionally followed by any separator character and a time as for [`parse_iso_time`].
+///
+/// Returns `None` if the string isn't a valid datetime.
+#[must_use]
+pub(crate) fn parse_iso_datetime(s: &str) -> Option<DateTime> {
+    let (date, len) = parse_date_prefix(s.as_bytes())?;
+    let mut rest = s[len..].chars();
+    let time = match rest.next() {
+        Some(_) => parse_time_with_offset(rest.as_str().as_bytes())?,
+        None => Time::MIDNIGHT,
+    };
+    Some(DateTime::new(date, time))
+}
+
+/// Parses an ISO 8601 date at the start of `s`, returning it with the number of bytes used.
+fn parse_date_prefix(s: &[u8]) -> Option<(Date, usize)> {
+    let year = parse_digits(s, 0, 4)?;
+    let extended = s.get(4) == Some(&b'-');
+    let mut pos = 4 + usize::from(extended);
+    if s.get(pos) == Some(&b'W') {
+        let week = parse_digits(s, pos + 1, 2)?;
+        pos += 3;
+        let mut weekday = 1;
+        if extended {
+            if s.get(pos) == Some(&b'-') {
+                weekday = parse_digits(s, pos + 1, 1)?;
+                pos += 2;
+            }
+        } else if let Some(day) = parse_digits(s, pos, 1) {
+            weekday = day;
+            pos += 1;
+        }
+        return Some((Date::from_isocalendar(year, week, weekday).ok()?, pos));
+    }
+    let month = parse_digits(s, pos, 2)?;
+    pos += 2;
+    if extended {
+        if s.get(pos) != Some(&b'-') {
+            return None;
+        }
+        pos += 1;
+    }
+    let day = parse_digits(s, pos, 2)?;
+    Some((Date::new(year, month, day).ok()?, pos + 2))
+}
+
+/// Parses a time of day, optionally followed by a UTC offset.
+fn parse_time_with_offset(s: &[u8]) -> Option<Time> {
+    let (time, offset) = match s.iter().position(|c| matches!(c, b'+' | b'-' | b'Z')) {
+        Some(index) => (&s[..index], Some(&s[index..])),
+        None => (s, None),
+    };
+    let [hour, minute, second, microsecond] = parse_hms(time)?;
+    let tzinfo = match offset {
+        None => None,
+        Some([b'Z']) => Some(TimeZone::UTC),
+        Some([sign @ (b'+' | b'-'), offset @ ..]) => {
+            let [hours, minutes, seconds, micros] = parse_hms(offset)?;
+            // Only whole-second offsets are supported
+            if micros != 0 {
+                return None;
+            }
+            let offset = hours * 3600 + minutes * 60 + seconds;
+            Some(TimeZone::new(if *sign == b'-' { -offset } else { offset }, None).ok()?)
+        }
+        Some(_) => return None,
+    };
+    Time::new(hour, minute, second, microsecond, tzinfo).ok()
+}
+
+/// Parses `HH[:MM[:SS[.fff]]]` or `HH[MM[SS[.fff]]]` into hours, minutes, seconds and
+/// microseconds, accepting `,` as the decimal separator too.
+fn parse_hms(s: &[u8]) -> Option<[i64; 4]> {
+    if s.is_empty() {
+        return None;
+    }
+    let extended = s.get(2) == Some(&b':');
+    let mut fields = [0; 4];
+    let mut count = 0;
+    let mut pos = 0;
+    while count < 3 && pos < s.len() {
+        if count > 0 && extended {
+            if s[pos] != b':' {
+                return None;
+            }
+            pos += 1;
+        }
+        fields[count] = parse_digits(s, pos, 2)?;
+        pos += 2;
+        count += 1;
+    }
+    if pos == s.len() {
+        return Some(fields);
+    }
+    if count < 3 || !matches!(s[pos], b'.' | b',') {
+        return None;
+    }
+    let digits = &s[pos + 1..];
+    if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    fields[3] = (0..6).fold(0, |micros, i| {
+        micros * 10 + digits.get(i).map_or(0, |digit| i64::from(digit - b'0'))
+    });
+    Some(fields)
+}
+
+/// Parses exactly `count` ASCII digits starting at `start`.
+fn parse_digits(s: &[u8], start: usize, count: usize) -> Option<i64> {
+    s.get(start..start + count)?.iter().try_fold(0, |n, digit| {
+        digit.is_ascii_digit().then(|| n * 10 + i64::from(digit - b'0'))
+    })
+}
+
+// =============================================================================
+// strftime
+// =============================================================================
+
+/// Formats a date and time like `strftime()`.
+///
+/// Supports the C standard directives and the common glibc extensions, including the `-`
+/// flag to drop padding (e.g. `%-d`). Unknown directives are kept as they are, as glibc does.
+#[must_use]
+pub(crate) fn strftime(format: &str, date: Date, time: &Time) -> String {
+    let mut out = String::with_capacity(format.len() + 16);
+    let mut rest = format;
+    while let Some(index) = rest.find('%') {
+        out.push_str(&rest[..index]);
+        let spec = &rest[index + 1..];
+        let (pad, spec) = match spec.strip_prefix('-') {
+            Some(spec) => (false, spec),
+            None => (true, spec),
+        };
+        if let Some(spec) = spec.strip_prefix(":z") {
+            if let Some(tzinfo) = time.tzinfo() {
+                write_offset(&mut out, tzinfo.offset(), ":");
+            }
+            rest = spec;
+            continue;
+        }
+        let mut chars = spec.chars();
+        let Some(directive) = chars.next() else {
+            // A trailing `%` is kept
+            out.push_str(&rest[index..]);
+            return out;
+        };
+        if !write_directive(&mut out, directive, pad, date, time) {
+            out.push('%');
+            if !pad {
+                out.push('-');
+            }
+            out.push(directive);
+        }
+        rest = chars.as_str();
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Writes one `strftime()` directive, returning `false` if it is unknown.
+fn write_directive(out: &mut String, directive: char, pad: bool, date: Date, time: &Time) -> bool {
+    let weekday = usize::from(date.weekday());
+    let month = usize::from(date.month()) - 1;
+    let year = i64::from(date.year());
+    // Sunday-based weekday, as in C's `tm_wday`
+    let sunday_weekday = (date.weekday() + 1) % 7;
+    let day_of_year = i64::from(date.day_of_year());
+    match directive {
+        'd' => write_number(out, pad, i64::from(date.day()), 2),
+        'm' => write_number(out, pad, i64::from(date.month()), 2),
+        'Y' => write_number(out, pad, year, 4),
+        'y' => write_number(out, pad, year % 100, 2),
+        'C' => write_number(out, pad, year / 100, 2),
+        'H' => write_number(out, pad, i64::from(time.hour()), 2),
+        'I' => write_number(out, pad, i64::from((time.hour() + 11) % 12 + 1), 2),
+        'M' => write_number(out, pad, i64::from(time.minute()), 2),
+        'S' => write_number(out, pad, i64::from(time.second()), 2),
+        'f' => write_number(out, pad, i64::from(time.microsecond()), 6),
+        'j' => write_number(out, pad, day_of_year, 3),
+        'w' => write_number(out, pad, i64::from(sunday_weekday), 1),
+        'u' => write_number(out, pad, i64::from(date.weekday()) + 1, 1),
+        'U' => write_number(out, pad, (day_of_year + 6 - i64::from(sunday_weekday)) / 7, 2),
+        'W' => write_number(out, pad, (day_of_year + 6 - i64::from(date.weekday())) / 7, 2),
+        'G' => write_number(out, pad, i64::from(date.isocalendar().0), 4),
+        'V' => write_number(out, pad, i64::from(date.isocalendar().1), 2),
+        'e' if pad => write!(out, "{:>2}", date.day()).expect("writing to a String can't fail"),
+        'e' => write_number(out, false, i64::from(date.day()), 1),
+        'a' => out.push_str(&WEEKDAY_NAMES[weekday][..3]),
+        'A' => out.push_str(WEEKDAY_NAMES[weekday]),
+        'b' | 'h' => out.push_str(&MONTH_NAMES[month][..3]),
+        'B' => out.push_str(MONTH_NAMES[month]),
+        'p' => out.push_str(if time.hour() < 12 { "AM" } else { "PM" }),
+        'z' => {
+            if let Some(tzinfo) = time.tzinfo() {
+                write_offset(out, tzinfo.offset(), "");
+            }
+        }
+        'Z' => {
+            if let Some(tzinfo) = time.tzinfo() {
+                out.push_str(&tzinfo.tzname());
+            }
+        }
+        'c' => out.push_str(&strftime("%a %b %e %H:%M:%S %Y", date, time)),
+        'x' | 'D' => out.push_str(&strftime("%m/%d/%y", date, time)),
+        'X' | 'T' => out.push_str(&strftime("%H:%M:%S", date, time)),
+        'F' => out.push_str(&strftime("%Y-%m-%d", date, time)),
+        'R' => out.push_str(&strftime("%H:%M", date, time)),
+        'r' => out.push_str(&strftime("%I:%M:%S %p", date, time)),
+        'n' => out.push('\n'),
+        't' => out.push('\t'),
+        '%' => out.push('%'),
+        _ => return false,
+    }
+    true
+}
+
+/// Writes a number zero-padded to `width` digits, unless `pad` is false.
+fn write_number(out: &mut String, pad: bool, value: i64, width: usize) {
+    if pad {
+        write!(out, "{value:0width$}")
+    } else {
+        write!(out, "{value}")
+    }
+    .expect("writing to a String can't fail");
+}
+
+// =============================================================================
+// strptime
+// =============================================================================
+
+/// One element of a compiled `strptime()` format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    /// A character matched case-insensitively.
+    Literal(char),
+    /// A run of whitespace, matching one or more whitespace characters.
+    Space,
+    /// A directive like `%Y`.
+    Field(char),
+}
+
+/// The directives `strptime()` can parse, besides `%c`, `%x`, `%X` and `%%`.
+const STRPTIME_DIRECTIVES: &str = "aAbBdfGHIjmMpSuUVwWyYzZ";
+
+/// Parses a string like `datetime.strptime()`, following CPython's `_strptime` module.
+///
+/// Fields which aren't given default to 1900-01-01 00:00. The result is aware only if
+/// the format has `%z`.
+///
+/// # Errors
+/// Returns `ValueError` for bad formats, strings which don't match the format and
+/// values which don't make a valid datetime.
+pub(crate) fn strptime(string: &str, format: &str) -> RunResult<DateTime> {
+    let tokens = compile_format(format)?;
+    let input: Vec<char> = string.chars().collect();
+    let mut fields = Vec::new();
+    let Some(end) = match_tokens(&tokens, &input, 0, &mut fields) else {
+        return Err(ExcType::value_error(format!(
+            "time data {} does not match format {}",
+            StringRepr(string),
+            StringRepr(format)
+        )));
+    };
+    if end != input.len() {
+        let remains: String = input[end..].iter().collect();
+        return Err(ExcType::value_error(format!("unconverted data remains: {remains}")));
+    }
+    build_datetime(&fields)
+}
+
+/// Compiles a `strptime()` format, expanding `%c`, `%x` and `%X` to their C locale formats.
+fn compile_format(format: &str) -> RunResult<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => match chars.next() {
+                None => return Err(ExcType::value_error(format!("stray % in format '{format}'"))),
+                Some('%') => tokens.push(Token::Literal('%')),
+                Some('c') => tokens.extend(compile_format("%a %b %d %H:%M:%S %Y")?),
+                Some('x') => tokens.extend(compile_format("%m/%d/%y")?),
+                Some('X') => tokens.extend(compile_format("%H:%M:%S")?),
+                Some(directive) if STRPTIME_DIRECTIVES.contains(directive) => tokens.push(Token::Field(directive)),
+                Some(directive) => {
+                    return Err(ExcType::value_error(format!(
+                        "'{directive}' is a bad directive in format '{format}'"
+                    )));
+                }
+            },
+            c if c.is_whitespace() => {
+                if tokens.last() != Some(&Token::Space) {
+                    tokens.push(Token::Space);
+                }
+            }
+            c => tokens.push(Token::Literal(c)),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Matches `tokens` against `input` from `pos`, backtracking like a regex would.
+///
+/// On success, returns the end of the match and leaves the text matched by each directive
+/// in `fields`. The match doesn't have to reach the end of the input.
+fn match_tokens(tokens: &[Token], input: &[char], pos: usize, fields: &mut Vec<(char, String)>) -> Option<usize> {
+    let Some((token, rest)) = tokens.split_first() else {
+        return Some(pos);
+    };
+    match *token {
+        Token::Literal(c) => {
+            let next = input.get(pos)?;
+            if next.to_lowercase().eq(c.to_lowercase()) {
+                match_tokens(rest, input, pos + 1, fields)
+            } else {
+                None
+            }
+        }
+        Token::Space => {
+            let spaces = input[pos.min(input.len())..]
+                .iter()
+                .take_while(|c| c.is_whitespace())
+                .count();
+            (1..=spaces)
+                .rev()
+                .find_map(|len| match_tokens(rest, input, pos + len, fields))
+        }
+        Token::Field(directive) => {
+            for len in field_candidates(directive, &input[pos.min(input.len())..]) {
+                fields.push((directive, input[pos..pos + len].iter().collect()));
+                if let Some(end) = match_tokens(rest, input, pos + len, fields) {
+                    return Some(end);
+                }
+                fields.pop();
+            }
+            None
+        }
+    }
+}
+
+/// Returns the lengths of the text at the start of `input` which `directive` could match,
+/// in the order CPython's regex would try them.
+fn field_candidates(directive: char, input: &[char]) -> Vec<usize> {
+    let names: &[&str] = match directive {
+        'a' => &["mon", "tue", "wed", "thu", "fri", "sat", "sun"],
+        'A' => &[
+            "monday",
+            "tuesday",
+            "wednesday",
+            "thursday",
+            "friday",
+            "saturday",
+            "sunday",
+        ],
+        'b' => &[
+            "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+        ],
+        'B' => &[
+            "january",
+            "february",
+            "march",
+            "april",
+            "may",
+            "june",
+            "july",
+            "august",
+            "september",
+            "october",
+            "november",
+            "december",
+        ],
+        'p' => &["am", "pm"],
+        'Z' => &["utc", "gmt"],
+        'z' => return offset_candidates(input),
+        _ => return number_candidates(directive, input),
+    };
+    names
+        .iter()
+        .filter(|name| {
+            name.len() <= input.len()
+                && input[..name.len()]
+                    .iter()
+                    .zip(name.chars())
+                    .all(|(c, expected)| c.to_ascii_lowercase() == expected)
+        })
+        .map(|name| name.len())
+        .collect()
+}
+
+/// Returns the candidate lengths of a numeric directive, longest first.
+fn number_candidates(directive: char, input: &[char]) -> Vec<usize> {
+    let (min_len, max_len, min, max) = match directive {
+        'd' => (1, 2, 1, 31),
+        'f' => (1, 6, 0, 999_999),
+        'H' => (1, 2, 0, 23),
+        'I' | 'm' => (1, 2, 1, 12),
+        'G' | 'Y' => (4, 4, 0, 9999),
+        'j' => (1, 3, 1, 366),
+        'M' => (1, 2, 0, 59),
+        'S' => (1, 2, 0, 61),
+        'U' | 'V' | 'W' => (1, 2, 0, 53),
+        'w' => (1, 1, 0, 6),
+        'u' => (1, 1, 1, 7),
+        _ => (2, 2, 0, 99),
+    };
+    let digits = input.iter().take(max_len).take_while(|c| c.is_ascii_digit()).count();
+    let mut candidates: Vec<usize> = (min_len..=digits)
+        .rev()
+        .filter(|&len| (min..=max).contains(&chars_to_number(&input[..len])))
+        .collect();
+    // Days may also be a space-padded digit, as `%e` formats them
+    if directive == 'd' && input.first() == Some(&' ') && input.get(1).is_some_and(|c| matches!(c, '1'..='9')) {
+        candidates.push(2);
+    }
+    candidates
+}
+
+/// Returns the candidate lengths of a `%z` offset: `Z`, or `+HHMM`, `+HH:MM`, `+HH:MM:SS`
+/// or `+HH:MM:SS.ffffff` (with or without colons), longest first.
+fn offset_candidates(input: &[char]) -> Vec<usize> {
+    if input.first() == Some(&'Z') {
+        return vec![1];
+    }
+    let digit = |i: usize| input.get(i).is_some_and(char::is_ascii_digit);
+    let sixty = |i: usize| input.get(i).is_some_and(|c| matches!(c, '0'..='5')) && digit(i + 1);
+    if !matches!(input.first(), Some('+' | '-')) || !digit(1) || !digit(2) {
+        return Vec::new();
+    }
+    let mut pos = 3 + usize::from(input.get(3) == Some(&':'));
+    if !sixty(pos) {
+        return Vec::new();
+    }
+    pos += 2;
+    let mut candidates = vec![pos];
+    let seconds = pos + usize::from(input.get(pos) == Some(&':'));
+    if sixty(seconds) {
+        let end = seconds + 2;
+        candidates.push(end);
+        if input.get(end) == Some(&'.') {
+            let fraction = input[end + 1..]
+                .iter()
+                .take(6)
+                .take_while(|c| c.is_ascii_digit())
+                .count();
+            candidates.extend((1..=fraction).map(|len| end + 1 + len));
+        }
+    }
+    candidates.reverse();
+    candidates
+}
+
+/// Converts ASCII digits to a number.
+fn chars_to_number(digits: &[char]) -> i64 {
+    digits
+        .iter()
+        .fold(0, |n, digit| n * 10 + i64::from(digit.to_digit(10).unwrap_or(0)))
+}
+
+/// Builds the datetime from the fields matched by `strptime()`, as `_strptime` does.
+fn build_datetime(fields: &[(char, String)]) -> RunResult<DateTime> {
+    let mut year = None;
+    let (mut month, mut day) = (1, 1);
+    let (mut hour, mut minute, mut second, mut microsecond) = (0, 0, 0, 0);
+    let (mut iso_year, mut iso_week, mut weekday, mut julian) = (None, None, None, None);
+    let mut week_of_year = None;
+    let mut offset = None;
+    let mut tzname = None;
+    for (directive, text) in fields {
+        let chars: Vec<char> = text.trim_start().chars().collect();
+        let value = chars_to_number(&chars);
+        match *directive {
+            'y' => year = Some(if value <= 68 { value + 2000 } else { value + 1900 }),
+            'Y' => year = Some(value),
+            'G' => iso_year = Some(value),
+            'm' => month = value,
+            'B' | 'b' => month = name_index(&MONTH_NAMES, text) + 1,
+            'd' => day = value,
+            'H' => hour = value,
+            'I' => {
+                let pm = fields
+                    .iter()
+                    .any(|(directive, text)| *directive == 'p' && text.eq_ignore_ascii_case("pm"));
+                hour = match (value, pm) {
+                    (12, false) => 0,
+                    (12, true) => 12,
+                    (hour, true) => hour + 12,
+                    (hour, false) => hour,
+                };
+            }
+            'M' => minute = value,
+            'S' => second = value,
+            'f' => microsecond = value * 10_i64.pow(6 - u32::try_from(chars.len()).expect("at most 6 digits")),
+            'A' | 'a' => weekday = Some(name_index(&WEEKDAY_NAMES, text)),
+            'w' => weekday = Some((value + 6) % 7),
+            'u' => weekday = Some(value - 1),
+            'j' => julian = Some(value),
+            'U' => week_of_year = Some((value, false)),
+            'W' => week_of_year = Some((value, true)),
+            'V' => iso_week = Some(value),
+            'z' => offset = Some(parse_strptime_offset(text)?),
+            'Z' => tzname = Some(text.clone()),
+            _ => {}
+        }
+    }
+
+    if year.is_none() && iso_year.is_some() {
+        if iso_week.is_none() || weekday.is_none() {
+            return Err(ExcType::value_error(
+                "ISO year directive '%G' must be used with the ISO week directive '%V' and a weekday \
+                 directive ('%A', '%a', '%w', or '%u').",
+            ));
+        }
+        if julian.is_some() {
+            return Err(ExcType::value_error(
+                "Day of the year directive '%j' is not compatible with ISO year directive '%G'. Use '%Y' instead.",
+            ));
+        }
+    } else if iso_week.is_some() {
+        if year.is_none() || weekday.is_none() {
+            return Err(ExcType::value_error(
+                "ISO week directive '%V' must be used with the ISO year directive '%G' and a weekday \
+                 directive ('%A', '%a', '%w', or '%u').",
+            ));
+        }
+        return Err(ExcType::value_error(
+            "ISO week directive '%V' is incompatible with the year directive '%Y'. Use the ISO year '%G' instead.",
+        ));
+    }
+
+    let mut year = year.unwrap_or(1900);
+    if julian.is_none()
+        && let Some(weekday) = weekday
+    {
+        if let Some((week, starts_monday)) = week_of_year {
+            julian = Some(julian_from_week(year, week, weekday, starts_monday)?);
+        } else if let (Some(iso_year), Some(iso_week)) = (iso_year, iso_week) {
+            let date = Date::from_isocalendar(iso_year, iso_week, weekday + 1)?;
+            (year, month, day) = (i64::from(date.year()), i64::from(date.month()), i64::from(date.day()));
+        }
+        if let Some(days) = julian
+            && days <= 0
+        {
+            year -= 1;
+            julian = Some(
+                days + if Date::new(year, 12, 31)?.day_of_year() == 366 {
+                    366
+                } else {
+                    365
+                },
+            );
+        }
+    }
+    if let Some(julian) = julian {
+        let date = Date::from_ordinal(Date::new(year, 1, 1)?.toordinal() + julian - 1)?;
+        (year, month, day) = (i64::from(date.year()), i64::from(date.month()), i64::from(date.day()));
+    }
+
+    let tzinfo = offset.map(|offset| TimeZone::new(offset, tzname)).transpose()?;
+    Ok(DateTime::new(
+        Date::new(year, month, day)?,
+        Time::new(hour, minute, second, microsecond, tzinfo)?,
+    ))
+}
+
+/// Returns the index of the name starting with `text`, ignoring case.
+fn name_index(names: &[&str], text: &str) -> i64 {
+    let text = text.to_ascii_lowercase();
+    let index = names
+        .iter()
+        .position(|name| name.to_ascii_lowercase().starts_with(&text))
+        .unwrap_or_default();
+    i64::try_from(index).expect("there are only a few names")
+}
+
+/// Returns the day of the year from a `%U` or `%W` week number and a Monday-based weekday.
+fn julian_from_week(year: i64, week: i64, weekday: i64, starts_monday: bool) -> RunResult<i64> {
+    let mut first_weekday = i64::from(Date::new(year, 1, 1)?.weekday());
+    let mut weekday = weekday;
+    if !starts_monday {
+        first_weekday = (first_weekday + 1) % 7;
+        weekday = (weekday + 1) % 7;
+    }
+    if week == 0 {
+        return Ok(1 + weekday - first_weekday);
+    }
+    let week_0_length = (7 - first_weekday) % 7;
+    Ok(1 + week_0_length + 7 * (week - 1) + weekday)
+}
+
+/// Converts the text matched by `%z` to an offset in seconds.
+fn parse_strptime_offset(text: &str) -> RunResult<i64> {
+    if text == "Z" {
+        return Ok(0);
+    }
+    let mut z = text.to_owned();
+    if z.as_bytes()[3] == b':' {
+        z.remove(3);
+        if z.len() > 5 {
+            if z.as_bytes()[5] != b':' {
+                return Err(ExcType::value_error(format!("Inconsistent use of : in {text}")));
+            }
+            z.remove(5);
+        }
+    }
+    let number = |range: std::ops::Range<usize>| z.get(range).and_then(|s| s.parse::<i64>().ok()).unwrap_or(0);
+    let offset = number(1..3) * 3600 + number(3..5) * 60 + number(5..7);
+    // Fractions of a second are only allowed if they are zero, as offsets are whole seconds
+    if z.get(8..)
+        .is_some_and(|fraction| fraction.bytes().any(|digit| digit != b'0'))
+    {
+        return Err(ExcType::value_error(
+            "offset must be a timedelta representing a whole number of seconds",
+        ));
+    }
+    Ok(if z.starts_with('-') { -offset } else { offset })
+}
@@ -0,0 +1,2113 @@
+//! The `decimal.Decimal` and `decimal.Context` types of the `decimal` module (see
+//! `modules/decimal.rs`).
+//!
+//! A [`Decimal`] is an immutable decimal floating point number with an arbitrary-precision
+//! coefficient, so `Decimal('0.1') + Decimal('0.2')` is exactly `Decimal('0.3')`. Arithmetic
+//! rounds to the precision and rounding mode of the current [`Context`], which lives on the
+//! heap so it's saved with snapshots. Conversions from ints, floats and strings are exact,
+//! decimals mix with ints in arithmetic, and they compare and hash like the ints, floats and
+//! fractions they equal.
+//!
+//! Only the context settings `prec` and `rounding` can be changed: the exponent limits are
+//! CPython's defaults, and the signals CPython traps by default raise while the others are
+//! ignored.
+
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::hash_map::DefaultHasher,
+    fmt::Write,
+    hash::{Hash, Hasher},
+    mem::discriminant,
+};
+
+use ahash::AHashSet;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use smallvec::smallvec;
+
+use super::{
+    AttrCallResult, LongInt, PyTrait, Type, allocate_tuple,
+    fraction::{self, check_power_of_ten, hash_rational, int_value},
+    str::allocate_string,
+};
+use crate::{
+    args::{ArgValues, bind_args},
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    expressions::Operator,
+    fstring::{ParsedFormatSpec, pad_number},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    resource::{ResourceError, ResourceTracker},
+    value::{EitherStr, Value},
+};
+
+/// The largest precision a context accepts, `decimal.MAX_PREC`.
+pub(crate) const MAX_PREC: u64 = 999_999_999_999_999_999;
+/// The largest adjusted exponent of a context-rounded result, `Context.Emax`.
+pub(crate) const EMAX: i64 = 999_999;
+/// The smallest adjusted exponent of a normal context-rounded result, `Context.Emin`.
+pub(crate) const EMIN: i64 = -999_999;
+/// The largest adjusted exponent a `Decimal` can be constructed with, `decimal.MAX_EMAX`.
+const MAX_EMAX: i64 = 999_999_999_999_999_999;
+/// The smallest exponent a `Decimal` can be constructed with, `decimal.MIN_ETINY`.
+const MIN_ETINY: i64 = -1_999_999_999_999_999_997;
+/// The rounding modes of `decimal`, named like the module constants (`ROUND_HALF_EVEN` etc.).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) enum Rounding {
+    Ceiling,
+    Floor,
+    Up,
+    Down,
+    HalfUp,
+    HalfDown,
+    HalfEven,
+    ZeroFiveUp,
+}
+
+impl Rounding {
+    /// All rounding modes, in the order CPython lists them in error messages.
+    pub const ALL: [Self; 8] = [
+        Self::Ceiling,
+        Self::Floor,
+        Self::Up,
+        Self::Down,
+        Self::HalfUp,
+        Self::HalfDown,
+        Self::HalfEven,
+        Self::ZeroFiveUp,
+    ];
+
+    /// The name of the module constant for this mode, which is also its value.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ceiling => "ROUND_CEILING",
+            Self::Floor => "ROUND_FLOOR",
+            Self::Up => "ROUND_UP",
+            Self::Down => "ROUND_DOWN",
+            Self::HalfUp => "ROUND_HALF_UP",
+            Self::HalfDown => "ROUND_HALF_DOWN",
+            Self::HalfEven => "ROUND_HALF_EVEN",
+            Self::ZeroFiveUp => "ROUND_05UP",
+        }
+    }
+
+    /// The mode named by a module constant like `'ROUND_HALF_UP'`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rounding| rounding.name() == name)
+    }
+}
+
+/// The settings of a `decimal.Context` that Monty supports: the precision and rounding mode.
+///
+/// The exponent limits are fixed at CPython's defaults, and every signal that CPython traps
+/// by default (`InvalidOperation`, `DivisionByZero` and `Overflow`) raises, while the others
+/// are ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Context {
+    pub prec: u64,
+    pub rounding: Rounding,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self {
+            prec: 28,
+            rounding: Rounding::HalfEven,
+        }
+    }
+}
+
+impl Context {
+    /// The smallest exponent of a subnormal result, `Context.Etiny()`.
+    #[must_use]
+    pub fn etiny(self) -> i64 {
+        EMIN - self.prec_i64() + 1
+    }
+
+    fn prec_i64(self) -> i64 {
+        i64::try_from(self.prec).unwrap_or(i64::MAX)
+    }
+}
+
+/// The conditions of `decimal` that raise an exception.
+///
+/// Several conditions raise the same exception class and only differ in its message,
+/// e.g. `DivisionUndefined` raises `InvalidOperation` for `0 / 0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Signal {
+    InvalidOperation,
+    ConversionSyntax,
+    DivisionByZero,
+    DivisionUndefined,
+    DivisionImpossible,
+    Overflow,
+}
+
+impl Signal {
+    /// The name of the condition, shown in the exception message like CPython's
+    /// `[<class 'decimal.DivisionUndefined'>]`.
+    #[must_use]
+    pub fn condition_name(self) -> &'static str {
+        match self {
+            Self::InvalidOperation => "InvalidOperation",
+            Self::ConversionSyntax => "ConversionSyntax",
+            Self::DivisionByZero => "DivisionByZero",
+            Self::DivisionUndefined => "DivisionUndefined",
+            Self::DivisionImpossible => "DivisionImpossible",
+            Self::Overflow => "Overflow",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+enum Kind {
+    Finite,
+    Infinite,
+    NaN,
+}
+
+/// A `decimal.Decimal`: a sign, an arbitrary-precision coefficient and a base-10 exponent,
+/// or one of the special values infinity and (quiet) NaN.
+///
+/// Values keep their exponent, so `Decimal('1.50')` and `Decimal('1.5')` are equal but print
+/// differently. Operations round their results to the precision of the current context.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Decimal {
+    negative: bool,
+    kind: Kind,
+    /// The coefficient of a finite value, zero for the special values.
+    coeff: BigUint,
+    exp: i64,
+}
+
+/// Where the digits discarded by rounding fall, relative to half a unit in the last kept place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Discarded {
+    Zero,
+    BelowHalf,
+    Half,
+    AboveHalf,
+}
+
+fn pow10(n: u64) -> BigUint {
+    BigUint::from(10u32).pow(u32::try_from(n).unwrap_or(u32::MAX))
+}
+
+/// The number of decimal digits of `n`, 1 for zero.
+fn num_digits(n: &BigUint) -> u64 {
+    if let Some(small) = n.to_u64() {
+        return u64::from(small.checked_ilog10().unwrap_or(0)) + 1;
+    }
+    // 2^(bits-1) <= n < 2^bits, so the estimate is at most one digit off
+    #[expect(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    let mut digits = ((n.bits() - 1) as f64 * std::f64::consts::LOG10_2) as u64 + 1;
+    while *n >= pow10(digits) {
+        digits += 1;
+    }
+    while digits > 1 && *n < pow10(digits - 1) {
+        digits -= 1;
+    }
+    digits
+}
+
+/// Splits off the last `drop` digits of `coeff`, returning the kept digits and where the
+/// dropped ones fall.
+fn split_digits(coeff: &BigUint, drop: u64) -> (BigUint, Discarded) {
+    if drop == 0 {
+        return (coeff.clone(), Discarded::Zero);
+    }
+    if drop > num_digits(coeff) {
+        let discarded = if coeff.is_zero() {
+            Discarded::Zero
+        } else {
+            Discarded::BelowHalf
+        };
+        return (BigUint::zero(), discarded);
+    }
+    let divisor = pow10(drop);
+    let (kept, rest) = coeff.div_rem(&divisor);
+    let discarded = if rest.is_zero() {
+        Discarded::Zero
+    } else {
+        match (rest << 1u8).cmp(&divisor) {
+            Ordering::Less => Discarded::BelowHalf,
+            Ordering::Equal => Discarded::Half,
+            Ordering::Greater => Discarded::AboveHalf,
+        }
+    };
+    (kept, discarded)
+}
+
+/// Drops the last `drop` digits of `coeff`, rounding the rest with `rounding`.
+fn round_digits(coeff: &BigUint, drop: u64, rounding: Rounding, negative: bool) -> BigUint {
+    let (kept, discarded) = split_digits(coeff, drop);
+    let round_up = match (discarded, rounding) {
+        (Discarded::Zero, _) | (_, Rounding::Down) => false,
+        (_, Rounding::Up) => true,
+        (_, Rounding::Ceiling) => !negative,
+        (_, Rounding::Floor) => negative,
+        (_, Rounding::HalfUp) => discarded != Discarded::BelowHalf,
+        (_, Rounding::HalfDown) => discarded == Discarded::AboveHalf,
+        (_, Rounding::HalfEven) => discarded == Discarded::AboveHalf || (discarded == Discarded::Half && kept.bit(0)),
+        (_, Rounding::ZeroFiveUp) => {
+            let last = (&kept % 10u32).to_u32().unwrap_or_default();
+            last == 0 || last == 5
+        }
+    };
+    if round_up { kept + 1u32 } else { kept }
+}
+
+fn i64_digits(n: u64) -> i64 {
+    i64::try_from(n).unwrap_or(i64::MAX)
+}
+
+impl Decimal {
+    fn finite(negative: bool, coeff: BigUint, exp: i64) -> Self {
+        Self {
+            negative,
+            kind: Kind::Finite,
+            coeff,
+            exp,
+        }
+    }
+
+    #[must_use]
+    pub fn nan() -> Self {
+        Self {
+            negative: false,
+            kind: Kind::NaN,
+            coeff: BigUint::zero(),
+            exp: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn infinity(negative: bool) -> Self {
+        Self {
+            negative,
+            kind: Kind::Infinite,
+            coeff: BigUint::zero(),
+            exp: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn from_bigint(n: &BigInt) -> Self {
+        Self::finite(n.is_negative(), n.magnitude().clone(), 0)
+    }
+
+    /// Creates the decimal with exactly the value of a float, like `Decimal(0.1)`.
+    #[must_use]
+    pub fn from_f64(f: f64) -> Self {
+        if f.is_nan() {
+            return Self::nan();
+        }
+        let negative = f.is_sign_negative();
+        if f.is_infinite() {
+            return Self::infinity(negative);
+        }
+        let bits = f.to_bits();
+        let biased = i64::try_from((bits >> 52) & 0x7ff).unwrap_or_default();
+        let fraction = bits & ((1 << 52) - 1);
+        let (mut mantissa, mut exp2) = if biased == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | 1 << 52, biased - 1075)
+        };
+        if mantissa == 0 {
+            return Self::finite(negative, BigUint::zero(), 0);
+        }
+        let zeros = mantissa.trailing_zeros();
+        mantissa >>= zeros;
+        exp2 += i64::from(zeros);
+        if exp2 >= 0 {
+            Self::finite(negative, BigUint::from(mantissa) << exp2.unsigned_abs(), 0)
+        } else {
+            // m * 2^-k == m * 5^k * 10^-k
+            let scale = BigUint::from(5u32).pow(u32::try_from(exp2.unsigned_abs()).unwrap_or(u32::MAX));
+            Self::finite(negative, BigUint::from(mantissa) * scale, exp2)
+        }
+    }
+
+    /// Creates a decimal from its sign, digits and exponent, as `Decimal((sign, digits, exp))` does.
+    #[must_use]
+    pub fn from_parts(negative: bool, coeff: BigUint, exp: i64) -> Self {
+        Self::finite(negative, coeff, exp)
+    }
+
+    /// Parses a decimal string like the `Decimal` constructor: leading and trailing whitespace
+    /// and underscores are ignored, and `Infinity`, `Inf` and `NaN` are accepted in any case.
+    pub fn parse(text: &str) -> Result<Self, Signal> {
+        let cleaned: String = text.trim().chars().filter(|c| *c != '_').collect();
+        let (negative, body) = match cleaned.as_bytes().first() {
+            Some(b'-') => (true, &cleaned[1..]),
+            Some(b'+') => (false, &cleaned[1..]),
+            _ => (false, cleaned.as_str()),
+        };
+        let body = body.to_ascii_lowercase();
+        if body == "inf" || body == "infinity" {
+            return Ok(Self::infinity(negative));
+        }
+        if body == "nan" {
+            return Ok(Self {
+                negative,
+                ..Self::nan()
+            });
+        }
+
+        let (mantissa, exponent) = match body.split_once('e') {
+            Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+            None => (body.as_str(), None),
+        };
+        let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if (int_part.is_empty() && frac_part.is_empty()) || !all_digits(int_part) || !all_digits(frac_part) {
+            return Err(Signal::ConversionSyntax);
+        }
+        let exp = match exponent {
+            None => 0,
+            Some(exponent) => {
+                let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+                if digits.is_empty() || !all_digits(digits) {
+                    return Err(Signal::ConversionSyntax);
+                }
+                exponent.parse::<i64>().map_err(|_| Signal::InvalidOperation)?
+            }
+        };
+        let digits = format!("{int_part}{frac_part}");
+        let coeff = BigUint::parse_bytes(digits.as_bytes(), 10).unwrap_or_default();
+        let exp = i64::try_from(frac_part.len())
+            .ok()
+            .and_then(|frac_len| exp.checked_sub(frac_len))
+            .ok_or(Signal::InvalidOperation)?;
+        let value = Self::finite(negative, coeff, exp);
+        if exp < MIN_ETINY || value.adjusted() > MAX_EMAX {
+            return Err(Signal::InvalidOperation);
+        }
+        Ok(value)
+    }
+
+    #[must_use]
+    pub fn is_nan(&self) -> bool {
+        self.kind == Kind::NaN
+    }
+
+    #[must_use]
+    pub fn is_infinite(&self) -> bool {
+        self.kind == Kind::Infinite
+    }
+
+    #[must_use]
+    pub fn is_finite(&self) -> bool {
+        self.kind == Kind::Finite
+    }
+
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.is_finite() && self.coeff.is_zero()
+    }
+
+    /// Whether the sign bit is set, which it also is for `-0`, `-Infinity` and `-NaN`.
+    #[must_use]
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    #[must_use]
+    pub fn coefficient(&self) -> &BigUint {
+        &self.coeff
+    }
+
+    #[must_use]
+    pub fn exponent(&self) -> i64 {
+        self.exp
+    }
+
+    /// The exponent of the most significant digit, `Decimal.adjusted()`; zero for special values.
+    #[must_use]
+    pub fn adjusted(&self) -> i64 {
+        if self.is_finite() {
+            self.exp.saturating_add(i64_digits(num_digits(&self.coeff)) - 1)
+        } else {
+            0
+        }
+    }
+
+    /// The number of digits of the coefficient.
+    #[must_use]
+    pub fn digits(&self) -> u64 {
+        num_digits(&self.coeff)
+    }
+
+    /// Whether the value is finite and has no fractional part.
+    #[must_use]
+    pub fn is_integral(&self) -> bool {
+        if !self.is_finite() {
+            return false;
+        }
+        if self.exp >= 0 || self.coeff.is_zero() {
+            return true;
+        }
+        let drop = self.exp.unsigned_abs();
+        drop <= num_digits(&self.coeff) && (&self.coeff % pow10(drop)).is_zero()
+    }
+
+    #[must_use]
+    pub fn negated(&self) -> Self {
+        Self {
+            negative: !self.negative,
+            ..self.clone()
+        }
+    }
+
+    #[must_use]
+    pub fn abs_value(&self) -> Self {
+        Self {
+            negative: false,
+            ..self.clone()
+        }
+    }
+
+    /// Rounds the value to an integer with `rounding`, or returns `None` for special values.
+    #[must_use]
+    pub fn to_bigint(&self, rounding: Rounding) -> Option<BigInt> {
+        if !self.is_finite() {
+            return None;
+        }
+        let magnitude = if self.exp >= 0 {
+            &self.coeff * pow10(self.exp.unsigned_abs())
+        } else {
+            round_digits(&self.coeff, self.exp.unsigned_abs(), rounding, self.negative)
+        };
+        let sign = if self.negative { Sign::Minus } else { Sign::Plus };
+        Some(BigInt::from_biguint(sign, magnitude))
+    }
+
+    /// The float nearest to the value.
+    #[must_use]
+    pub fn to_f64(&self) -> f64 {
+        self.to_sci_string().parse().unwrap_or(f64::NAN)
+    }
+
+    /// The value as a fraction in lowest terms with a positive denominator, or `None` for
+    /// special values.
+    #[must_use]
+    pub fn to_ratio(&self) -> Option<(BigInt, BigInt)> {
+        if !self.is_finite() {
+            return None;
+        }
+        let sign = if self.negative { Sign::Minus } else { Sign::Plus };
+        if self.exp >= 0 {
+            let numerator = &self.coeff * pow10(self.exp.unsigned_abs());
+            return Some((BigInt::from_biguint(sign, numerator), BigInt::one()));
+        }
+        let denominator = pow10(self.exp.unsigned_abs());
+        let divisor = self.coeff.gcd(&denominator);
+        Some((
+            BigInt::from_biguint(sign, &self.coeff / &divisor),
+            BigInt::from(denominator / divisor),
+        ))
+    }
+
+    /// The sign of the value as -1, 0 or 1, with zeros unsigned.
+    fn signum(&self) -> i8 {
+        if self.is_zero() {
+            0
+        } else if self.negative {
+            -1
+        } else {
+            1
+        }
+    }
+
+    /// Compares two values numerically, or returns `None` if either is a NaN.
+    #[must_use]
+    pub fn compare(&self, other: &Self) -> Option<Ordering> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+        let (sign, other_sign) = (self.signum(), other.signum());
+        if sign != other_sign || sign == 0 {
+            return Some(sign.cmp(&other_sign));
+        }
+        let magnitude = match (self.kind, other.kind) {
+            (Kind::Infinite, Kind::Infinite) => Ordering::Equal,
+            (Kind::Infinite, _) => Ordering::Greater,
+            (_, Kind::Infinite) => Ordering::Less,
+            _ => self.cmp_magnitude(other),
+        };
+        Some(if sign < 0 { magnitude.reverse() } else { magnitude })
+    }
+
+    fn cmp_magnitude(&self, other: &Self) -> Ordering {
+        let (adjusted, other_adjusted) = (self.adjusted(), other.adjusted());
+        if adjusted != other_adjusted {
+            return adjusted.cmp(&other_adjusted);
+        }
+        // with equal adjusted exponents, aligning scales by fewer digits than the coefficients have
+        if self.exp >= other.exp {
+            (&self.coeff * pow10(self.exp.abs_diff(other.exp))).cmp(&other.coeff)
+        } else {
+            self.coeff.cmp(&(&other.coeff * pow10(self.exp.abs_diff(other.exp))))
+        }
+    }
+
+    /// Compares the value with the fraction `numerator / denominator` (where `denominator` is
+    /// positive), or returns `None` if the value is a NaN.
+    #[must_use]
+    pub fn cmp_ratio(&self, numerator: &BigInt, denominator: &BigInt) -> Option<Ordering> {
+        if self.is_nan() {
+            return None;
+        }
+        let other_sign: i8 = match numerator.sign() {
+            Sign::Minus => -1,
+            Sign::NoSign => 0,
+            Sign::Plus => 1,
+        };
+        let sign = self.signum();
+        if sign != other_sign || sign == 0 {
+            return Some(sign.cmp(&other_sign));
+        }
+        let magnitude = if self.is_infinite() {
+            Ordering::Greater
+        } else {
+            self.cmp_magnitude_ratio(numerator.magnitude(), denominator.magnitude())
+        };
+        Some(if sign < 0 { magnitude.reverse() } else { magnitude })
+    }
+
+    fn cmp_magnitude_ratio(&self, numerator: &BigUint, denominator: &BigUint) -> Ordering {
+        // n/d lies strictly between 10^(estimate-1) and 10^(estimate+1), and the value
+        // between 10^adjusted and 10^(adjusted+1), so most comparisons need no big powers
+        let estimate = i64_digits(num_digits(numerator)) - i64_digits(num_digits(denominator));
+        let adjusted = self.adjusted();
+        if adjusted > estimate {
+            return Ordering::Greater;
+        }
+        if adjusted + 2 <= estimate {
+            return Ordering::Less;
+        }
+        let scale = pow10(self.exp.unsigned_abs());
+        if self.exp >= 0 {
+            (&self.coeff * scale * denominator).cmp(numerator)
+        } else {
+            (&self.coeff * denominator).cmp(&(numerator * scale))
+        }
+    }
+
+    /// The string form of `str()`, in scientific notation when the exponent is positive or the
+    /// value is smaller than `1E-6`.
+    #[must_use]
+    pub fn to_sci_string(&self) -> String {
+        let sign = if self.negative { "-" } else { "" };
+        match self.kind {
+            Kind::Infinite => return format!("{sign}Infinity"),
+            Kind::NaN => return format!("{sign}NaN"),
+            Kind::Finite => {}
+        }
+        let digits = self.coeff.to_string();
+        let len = i64_digits(digits.len() as u64);
+        let adjusted = self.exp.saturating_add(len - 1);
+        if self.exp > 0 || adjusted < -6 {
+            let (first, rest) = digits.split_at(1);
+            let point = if rest.is_empty() { "" } else { "." };
+            return format!("{sign}{first}{point}{rest}E{adjusted:+}");
+        }
+        if self.exp == 0 {
+            return format!("{sign}{digits}");
+        }
+        let before_point = len + self.exp;
+        if before_point > 0 {
+            let (int_part, frac_part) = digits.split_at(before_point.unsigned_abs() as usize);
+            format!("{sign}{int_part}.{frac_part}")
+        } else {
+            let zeros = "0".repeat(before_point.unsigned_abs() as usize);
+            format!("{sign}0.{zeros}{digits}")
+        }
+    }
+
+    /// Rounds a finite result to the context: to `prec` digits, and no further right than
+    /// `Etiny`, raising `Overflow` if its adjusted exponent passes `Emax`.
+    fn finalize(negative: bool, coeff: BigUint, exp: i64, ctx: Context) -> Result<Self, Signal> {
+        let prec = ctx.prec_i64();
+        let digits = i64_digits(num_digits(&coeff));
+        let drop = (digits.saturating_sub(prec))
+            .max(ctx.etiny().saturating_sub(exp))
+            .max(0);
+        let (mut coeff, mut exp) = (coeff, exp);
+        if drop > 0 {
+            coeff = round_digits(&coeff, drop.unsigned_abs(), ctx.rounding, negative);
+            exp += drop;
+            if i64_digits(num_digits(&coeff)) > prec {
+                coeff /= 10u32;
+                exp += 1;
+            }
+        }
+        if coeff.is_zero() {
+            return Ok(Self::finite(negative, coeff, exp.min(EMAX)));
+        }
+        if exp.saturating_add(i64_digits(num_digits(&coeff)) - 1) > EMAX {
+            return Err(Signal::Overflow);
+        }
+        Ok(Self::finite(negative, coeff, exp))
+    }
+
+    /// Changes the exponent of a finite value to `exp`, padding the coefficient with zeros or
+    /// rounding off digits with `rounding`.
+    fn rescale(&self, exp: i64, rounding: Rounding) -> Self {
+        let coeff = if exp <= self.exp {
+            &self.coeff * pow10(self.exp.abs_diff(exp))
+        } else {
+            round_digits(&self.coeff, self.exp.abs_diff(exp), rounding, self.negative)
+        };
+        Self::finite(self.negative, coeff, exp)
+    }
+
+    /// The first NaN operand, which is the result of any arithmetic on it.
+    fn nan_operand(&self, other: &Self) -> Option<Self> {
+        [self, other].into_iter().find(|value| value.is_nan()).cloned()
+    }
+
+    /// Unary plus, which rounds the value to the context.
+    pub fn plus(&self, ctx: Context) -> Result<Self, Signal> {
+        if !self.is_finite() {
+            return Ok(self.clone());
+        }
+        let negative = self.negative && !(self.is_zero() && ctx.rounding != Rounding::Floor);
+        Self::finalize(negative, self.coeff.clone(), self.exp, ctx)
+    }
+
+    /// Unary minus, which also rounds the value to the context.
+    pub fn minus(&self, ctx: Context) -> Result<Self, Signal> {
+        if self.is_nan() {
+            return Ok(self.clone());
+        }
+        if self.is_zero() && ctx.rounding != Rounding::Floor {
+            return self.abs_value().plus(ctx);
+        }
+        self.negated().plus(ctx)
+    }
+
+    /// `abs()`, which also rounds the value to the context.
+    pub fn abs(&self, ctx: Context) -> Result<Self, Signal> {
+        if self.negative && !self.is_nan() {
+            self.minus(ctx)
+        } else {
+            self.plus(ctx)
+        }
+    }
+
+    pub fn add(&self, other: &Self, ctx: Context) -> Result<Self, Signal> {
+        if let Some(nan) = self.nan_operand(other) {
+            return Ok(nan);
+        }
+        if self.is_infinite() || other.is_infinite() {
+            if self.is_infinite() && other.is_infinite() && self.negative != other.negative {
+                return Err(Signal::InvalidOperation);
+            }
+            return Ok(if self.is_infinite() {
+                self.clone()
+            } else {
+                other.clone()
+            });
+        }
+
+        let exp = self.exp.min(other.exp);
+        let negative_zero = ctx.rounding == Rounding::Floor && self.negative != other.negative;
+        if self.is_zero() && other.is_zero() {
+            let negative = (self.negative && other.negative) || negative_zero;
+            return Self::finalize(negative, BigUint::zero(), exp, ctx);
+        }
+        if self.is_zero() || other.is_zero() {
+            let value = if self.is_zero() { other } else { self };
+            let exp = exp.max(value.exp.saturating_sub(ctx.prec_i64()).saturating_sub(1));
+            let value = value.rescale(exp, ctx.rounding);
+            return Self::finalize(value.negative, value.coeff, value.exp, ctx);
+        }
+
+        // a much smaller operand only matters as a sticky digit below the rounding position
+        let (mut big, mut small) = if self.exp < other.exp {
+            (other.clone(), self.clone())
+        } else {
+            (self.clone(), other.clone())
+        };
+        let big_digits = i64_digits(num_digits(&big.coeff));
+        let threshold = big.exp + (-1i64).min(big_digits.saturating_sub(ctx.prec_i64()).saturating_sub(2));
+        if small.adjusted() < threshold {
+            small.coeff = BigUint::one();
+            small.exp = threshold;
+        }
+        big.coeff *= pow10(big.exp.abs_diff(small.exp));
+        big.exp = small.exp;
+
+        let signed = |value: &Self| {
+            let sign = if value.negative { Sign::Minus } else { Sign::Plus };
+            BigInt::from_biguint(sign, value.coeff.clone())
+        };
+        let sum = signed(&big) + signed(&small);
+        if sum.is_zero() {
+            return Self::finalize(negative_zero, BigUint::zero(), small.exp, ctx);
+        }
+        Self::finalize(sum.is_negative(), sum.magnitude().clone(), small.exp, ctx)
+    }
+
+    pub fn sub(&self, other: &Self, ctx: Context) -> Result<Self, Signal> {
+        if let Some(nan) = self.nan_operand(other) {
+            return Ok(nan);
+        }
+        self.add(&other.negated(), ctx)
+    }
+
+    pub fn mul(&self, other: &Self, ctx: Context) -> Result<Self, Signal> {
+        if let Some(nan) = self.nan_operand(other) {
+            return Ok(nan);
+        }
+        let negative = self.negative != other.negative;
+        if self.is_infinite() || other.is_infinite() {
+            if self.is_zero() || other.is_zero() {
+                return Err(Signal::InvalidOperation);
+            }
+            return Ok(Self::infinity(negative));
+        }
+        Self::finalize(negative, &self.coeff * &other.coeff, self.exp + other.exp, ctx)
+    }
+
+    pub fn div(&self, other: &Self, ctx: Context) -> Result<Self, Signal> {
+        if let Some(nan) = self.nan_operand(other) {
+            return Ok(nan);
+        }
+        let negative = self.negative != other.negative;
+        match (self.is_infinite(), other.is_infinite()) {
+            (true, true) => return Err(Signal::InvalidOperation),
+            (true, false) => return Ok(Self::infinity(negative)),
+            (false, true) => return Self::finalize(negative, BigUint::zero(), ctx.etiny(), ctx),
+            (false, false) => {}
+        }
+        if other.is_zero() {
+            return Err(if self.is_zero() {
+                Signal::DivisionUndefined
+            } else {
+                Signal::DivisionByZero
+            });
+        }
+        let ideal_exp = self.exp - other.exp;
+        if self.is_zero() {
+            return Self::finalize(negative, BigUint::zero(), ideal_exp, ctx);
+        }
+
+        // scale the dividend so the quotient has more than `prec` digits
+        let (digits, other_digits) = (i64_digits(self.digits()), i64_digits(other.digits()));
+        let shift = ctx.prec_i64().saturating_add(other_digits - digits + 1).max(0);
+        let (mut quotient, remainder) = (&self.coeff * pow10(shift.unsigned_abs())).div_rem(&other.coeff);
+        let mut exp = ideal_exp - shift;
+        if remainder.is_zero() {
+            // an exact quotient keeps as many of its trailing zeros as the ideal exponent allows
+            while exp < ideal_exp && (&quotient % 10u32).is_zero() {
+                quotient /= 10u32;
+                exp += 1;
+            }
+        } else {
+            // a sticky digit stops an inexact quotient from looking like a halfway case
+            quotient = quotient * 10u32 + 1u32;
+            exp -= 1;
+        }
+        Self::finalize(negative, quotient, exp, ctx)
+    }
+
+    /// The quotient truncated towards zero and the remainder of dividing finite `self` by a
+    /// non-zero `other`, which raise `DivisionImpossible` if the quotient has more than
+    /// `prec` digits.
+    fn divide(&self, other: &Self, ctx: Context) -> Result<(Self, Self), Signal> {
+        let negative = self.negative != other.negative;
+        let ideal_exp = if other.is_infinite() {
+            self.exp
+        } else {
+            self.exp.min(other.exp)
+        };
+        let expdiff = self.adjusted().saturating_sub(other.adjusted());
+        if self.is_zero() || other.is_infinite() || expdiff <= -2 {
+            return Ok((
+                Self::finite(negative, BigUint::zero(), 0),
+                self.rescale(ideal_exp, ctx.rounding),
+            ));
+        }
+        if expdiff <= ctx.prec_i64() {
+            let (mut coeff, mut other_coeff) = (self.coeff.clone(), other.coeff.clone());
+            if self.exp >= other.exp {
+                coeff *= pow10(self.exp.abs_diff(other.exp));
+            } else {
+                other_coeff *= pow10(self.exp.abs_diff(other.exp));
+            }
+            let (quotient, remainder) = coeff.div_rem(&other_coeff);
+            if num_digits(&quotient) <= ctx.prec {
+                return Ok((
+                    Self::finite(negative, quotient, 0),
+                    Self::finite(self.negative, remainder, ideal_exp),
+                ));
+            }
+        }
+        Err(Signal::DivisionImpossible)
+    }
+
+    /// Integer division `//`, which truncates towards zero unlike `int`'s.
+    pub fn div_int(&self, other: &Self, ctx: Context) -> Result<Self, Signal> {
+        if let Some(nan) = self.nan_operand(other) {
+            return Ok(nan);
+        }
+        if self.is_infinite() {
+            if other.is_infinite() {
+                return Err(Signal::InvalidOperation);
+            }
+            return Ok(Self::infinity(self.negative != other.negative));
+        }
+        if other.is_zero() {
+            return Err(if self.is_zero() {
+                Signal::DivisionUndefined
+            } else {
+                Signal::DivisionByZero
+            });
+        }
+        Ok(self.divide(other, ctx)?.0)
+    }
+
+    /// The remainder `%`, which has the sign of the dividend unlike `int`'s.
+    pub fn rem(&self, other: &Self, ctx: Context) -> Result<Self, Signal> {
+        if let Some(nan) = self.nan_operand(other) {
+            return Ok(nan);
+        }
+        if self.is_infinite() {
+            return Err(Signal::InvalidOperation);
+        }
+        if other.is_zero() {
+            return Err(if self.is_zero() {
+                Signal::DivisionUndefined
+            } else {
+                Signal::InvalidOperation
+            });
+        }
+        let remainder = self.divide(other, ctx)?.1;
+        Self::finalize(remainder.negative, remainder.coeff, remainder.exp, ctx)
+    }
+
+    /// Whether the value is exactly one or minus one, with any number of trailing zeros.
+    fn is_unit(&self) -> bool {
+        self.is_finite()
+            && self.exp <= 0
+            && num_digits(&self.coeff) == self.exp.unsigned_abs() + 1
+            && self.coeff == pow10(self.exp.unsigned_abs())
+    }
+
+    /// Raises the value to an integer power.
+    ///
+    /// Small powers are computed exactly and then rounded; large ones by repeated squaring
+    /// with guard digits.
+    pub fn pow_int(&self, n: &BigInt, ctx: Context) -> Result<Self, Signal> {
+        if self.is_nan() {
+            return Ok(self.clone());
+        }
+        let negative = self.negative && n.is_odd();
+        if n.is_zero() {
+            if self.is_zero() {
+                return Err(Signal::InvalidOperation);
+            }
+            return Ok(Self::finite(false, BigUint::one(), 0));
+        }
+        if self.is_infinite() || self.is_zero() {
+            let large = self.is_infinite() == n.is_positive();
+            return Ok(if large {
+                Self::infinity(negative)
+            } else {
+                Self::finite(negative, BigUint::zero(), 0)
+            });
+        }
+        if self.is_unit() {
+            // an exact power of one keeps up to `prec` digits of its trailing zeros
+            let multiplier = if n.is_negative() {
+                0
+            } else {
+                n.to_i64().unwrap_or(i64::MAX).min(ctx.prec_i64())
+            };
+            let exp = self.exp.saturating_mul(multiplier).max(1 - ctx.prec_i64());
+            return Ok(Self::finite(negative, pow10(exp.unsigned_abs()), exp));
+        }
+
+        let Some(power) = n.magnitude().to_u64() else {
+            // far outside the exponent limits whatever the digits are
+            return match ((self.adjusted() >= 0), n.is_positive()) {
+                (true, true) | (false, false) => Err(Signal::Overflow),
+                (false, true) => Self::underflow(negative, ctx),
+                (true, false) => Self::finalize(negative, BigUint::zero(), ctx.etiny(), ctx),
+            };
+        };
+        let magnitude = Self::finite(false, self.coeff.clone(), self.exp);
+        let powered = magnitude.pow_magnitude(power, ctx)?;
+        let powered = match powered {
+            Powered::Value(value) => value,
+            Powered::Huge => {
+                return if n.is_positive() {
+                    Err(Signal::Overflow)
+                } else {
+                    Self::finalize(negative, BigUint::zero(), ctx.etiny(), ctx)
+                };
+            }
+            Powered::Tiny => {
+                return if n.is_positive() {
+                    Self::underflow(negative, ctx)
+                } else {
+                    Err(Signal::Overflow)
+                };
+            }
+        };
+        if n.is_positive() {
+            Self::finalize(negative, powered.coeff, powered.exp, ctx)
+        } else if powered.adjusted().saturating_neg() < ctx.etiny() - 1 {
+            // like libmpdec, which takes the reciprocal first, a reciprocal certainly below the
+            // subnormal range is zero whatever the rounding
+            Self::finalize(negative, BigUint::zero(), ctx.etiny(), ctx)
+        } else {
+            Self::finite(negative, BigUint::one(), 0).div(&powered, ctx)
+        }
+    }
+
+    /// Rounds a non-zero value far below the subnormal range, which is zero unless the rounding
+    /// mode rounds it away from zero.
+    fn underflow(negative: bool, ctx: Context) -> Result<Self, Signal> {
+        Self::finalize(negative, BigUint::one(), ctx.etiny() - 2, ctx)
+    }
+
+    /// `self ** n` for positive finite `self`, exactly if the result is small enough and
+    /// otherwise with more digits than the context keeps and a sticky last digit.
+    fn pow_magnitude(&self, n: u64, ctx: Context) -> Result<Powered, Signal> {
+        let limit = ctx.prec.saturating_mul(3).saturating_add(100);
+        if self.digits().saturating_mul(n) <= limit
+            && let Some(exp) = self.exp.checked_mul(i64::try_from(n).unwrap_or(i64::MAX))
+        {
+            let coeff = self.coeff.pow(u32::try_from(n).unwrap_or(u32::MAX));
+            return Ok(Powered::Value(Self::finite(false, coeff, exp)));
+        }
+
+        // with the trailing zeros stripped, the coefficient of the exact power has no trailing
+        // zeros either, so if it had to be truncated it has more digits than are kept and
+        // rounding it is inexact
+        let mut coeff = self.coeff.clone();
+        let mut exp = i128::from(self.exp);
+        let mut stripped = 0u64;
+        while (&coeff % 10u32).is_zero() {
+            coeff /= 10u32;
+            exp += 1;
+            stripped += 1;
+        }
+        let precision = ctx.prec.saturating_add(30);
+        let mut truncated = false;
+        let mut truncate = |coeff: BigUint, exp: i128| {
+            let digits = num_digits(&coeff);
+            if digits <= precision {
+                (coeff, exp)
+            } else {
+                truncated = true;
+                let drop = digits - precision;
+                (coeff / pow10(drop), exp + i128::from(drop))
+            }
+        };
+        let mut result = (BigUint::one(), 0i128);
+        let mut base = (coeff, exp);
+        let mut remaining = n;
+        loop {
+            if remaining & 1 == 1 {
+                result = truncate(&result.0 * &base.0, result.1 + base.1);
+            }
+            remaining >>= 1;
+            if remaining == 0 {
+                break;
+            }
+            base = truncate(&base.0 * &base.0, base.1 * 2);
+        }
+
+        let (coeff, exp) = result;
+        let adjusted = exp + i128::from(i64_digits(num_digits(&coeff))) - 1;
+        let limit = i128::from(EMAX) + i128::from(ctx.prec_i64()) + 2;
+        if adjusted > limit {
+            return Ok(Powered::Huge);
+        }
+        if adjusted < -limit {
+            return Ok(Powered::Tiny);
+        }
+        let exp = i64::try_from(exp).map_err(|_| Signal::Overflow)?;
+        if truncated {
+            Ok(Powered::Value(Self::finite(false, coeff * 10u32 + 1u32, exp - 1)))
+        } else {
+            // an exact power keeps the trailing zeros of the exact result, up to `prec` digits
+            let pad = stripped.saturating_mul(n).min(ctx.prec);
+            Ok(Powered::Value(Self::finite(
+                false,
+                coeff * pow10(pad),
+                exp - i64_digits(pad),
+            )))
+        }
+    }
+
+    /// `quantize()`: the value rounded to the exponent of `other`, raising `InvalidOperation`
+    /// if that needs more than `prec` digits.
+    pub fn quantize(&self, other: &Self, rounding: Rounding, ctx: Context) -> Result<Self, Signal> {
+        if let Some(nan) = self.nan_operand(other) {
+            return Ok(nan);
+        }
+        if self.is_infinite() || other.is_infinite() {
+            if self.is_infinite() && other.is_infinite() {
+                return Ok(self.clone());
+            }
+            return Err(Signal::InvalidOperation);
+        }
+        let exp = other.exp;
+        if exp < ctx.etiny() || exp > EMAX {
+            return Err(Signal::InvalidOperation);
+        }
+        if self.is_zero() {
+            return Ok(Self::finite(self.negative, BigUint::zero(), exp));
+        }
+        let adjusted = self.adjusted();
+        if adjusted > EMAX || adjusted.saturating_sub(exp).saturating_add(1) > ctx.prec_i64() {
+            return Err(Signal::InvalidOperation);
+        }
+        let result = self.rescale(exp, rounding);
+        if result.adjusted() > EMAX || num_digits(&result.coeff) > ctx.prec {
+            return Err(Signal::InvalidOperation);
+        }
+        Ok(result)
+    }
+
+    /// `to_integral_value()`: the value rounded to an integer with `rounding`, keeping its sign.
+    #[must_use]
+    pub fn to_integral(&self, rounding: Rounding) -> Self {
+        if !self.is_finite() || self.exp >= 0 {
+            return self.clone();
+        }
+        self.rescale(0, rounding)
+    }
+
+    /// `normalize()`: the value rounded to the context with its trailing zeros stripped.
+    pub fn normalize(&self, ctx: Context) -> Result<Self, Signal> {
+        if !self.is_finite() {
+            return Ok(self.clone());
+        }
+        let mut result = Self::finalize(self.negative, self.coeff.clone(), self.exp, ctx)?;
+        if result.coeff.is_zero() {
+            result.exp = 0;
+            return Ok(result);
+        }
+        while result.exp < EMAX && (&result.coeff % 10u32).is_zero() {
+            result.coeff /= 10u32;
+            result.exp += 1;
+        }
+        Ok(result)
+    }
+
+    /// Rounds a finite value to `places` significant digits, like `_pydecimal`'s `_round`.
+    fn round_places(&self, places: u64, rounding: Rounding) -> Self {
+        if self.is_zero() {
+            return self.clone();
+        }
+        let places = i64_digits(places);
+        let adjusted = self.adjusted();
+        let result = self.rescale(adjusted + 1 - places, rounding);
+        // rounding up can add a digit, as for 99.97 to 3 places
+        let result_adjusted = result.adjusted();
+        if result_adjusted == adjusted {
+            result
+        } else {
+            result.rescale(result_adjusted + 1 - places, rounding)
+        }
+    }
+
+    /// Formats the value for a format spec with a float presentation type (`e`, `E`, `f`, `F`,
+    /// `g`, `G`, `n` or `%`, where `None` means `G`), rounding with `rounding`.
+    ///
+    /// Returns whether the result is negative, the integer part and the rest, which are
+    /// separate so the caller can pad the integer part with zeros.
+    #[must_use]
+    pub fn format_parts(
+        &self,
+        type_char: Option<char>,
+        precision: Option<u64>,
+        rounding: Rounding,
+    ) -> (bool, String, String) {
+        let type_char = type_char.unwrap_or('G');
+        if !self.is_finite() {
+            let mut body = self.abs_value().to_sci_string();
+            if type_char == '%' {
+                body.push('%');
+            }
+            return (self.negative, body, String::new());
+        }
+        let precision = match (type_char, precision) {
+            ('g' | 'G' | 'n', Some(0)) => Some(1),
+            _ => precision,
+        };
+
+        let mut value = self.clone();
+        if type_char == '%' {
+            value.exp = value.exp.saturating_add(2);
+        }
+        if let Some(precision) = precision {
+            match type_char {
+                'e' | 'E' => value = value.round_places(precision + 1, rounding),
+                'f' | 'F' | '%' => value = value.rescale(-i64_digits(precision), rounding),
+                _ if num_digits(&value.coeff) > precision => value = value.round_places(precision, rounding),
+                _ => {}
+            }
+        }
+        // zeros with a positive exponent can't be shown in fixed point
+        if value.is_zero() && value.exp > 0 && matches!(type_char, 'f' | 'F' | '%') {
+            value = value.rescale(0, rounding);
+        }
+
+        let digits = value.coeff.to_string();
+        let len = i64_digits(digits.len() as u64);
+        let left_digits = value.exp + len;
+        let dot_place = match type_char {
+            'e' | 'E' => match precision {
+                Some(precision) if value.is_zero() => 1 - i64_digits(precision),
+                _ => 1,
+            },
+            'f' | 'F' | '%' => left_digits,
+            _ if value.exp <= 0 && left_digits > -6 => left_digits,
+            _ => 1,
+        };
+        let (int_part, mut frac_part) = if dot_place < 0 {
+            let zeros = "0".repeat(dot_place.unsigned_abs() as usize);
+            ("0".to_owned(), format!("{zeros}{digits}"))
+        } else if dot_place > len {
+            let zeros = "0".repeat((dot_place - len).unsigned_abs() as usize);
+            (format!("{digits}{zeros}"), String::new())
+        } else {
+            let (int_part, frac_part) = digits.split_at(dot_place.unsigned_abs() as usize);
+            let int_part = if int_part.is_empty() { "0" } else { int_part };
+            (int_part.to_owned(), frac_part.to_owned())
+        };
+        if !frac_part.is_empty() {
+            frac_part.insert(0, '.');
+        }
+        let exp = left_digits - dot_place;
+        if exp != 0 || matches!(type_char, 'e' | 'E') {
+            let e = if matches!(type_char, 'E' | 'G') { 'E' } else { 'e' };
+            frac_part.push_str(&format!("{e}{exp:+}"));
+        }
+        if type_char == '%' {
+            frac_part.push('%');
+        }
+        (value.negative, int_part, frac_part)
+    }
+}
+
+/// The result of raising a positive value to a power, which may lie far outside the exponent
+/// limits.
+enum Powered {
+    Value(Decimal),
+    Huge,
+    Tiny,
+}
+
+impl Decimal {
+    /// Creates a `decimal.Decimal` from the `Decimal(value='0', context=None)` constructor
+    /// call.
+    ///
+    /// The conversion is exact, whatever the precision of the context.
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let [value, context] = bind_args("Decimal", args, ["value", "context"], 0, heap, interns)?;
+        let result = match &value {
+            Some(value) => Self::from_value(value, heap, interns),
+            None => Ok(Self::from_bigint(&BigInt::zero())),
+        };
+        let checked = context_arg(context.as_ref(), heap);
+        value.drop_with_heap(heap);
+        context.drop_with_heap(heap);
+        checked?;
+        Ok(result?.into_value(heap)?)
+    }
+
+    /// Converts the argument of `Decimal()`: an int, a float, a decimal, a string or a
+    /// `(sign, digits, exponent)` tuple.
+    fn from_value(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Self> {
+        if let Some(decimal) = operand(value, heap) {
+            return Ok(decimal);
+        }
+        match value {
+            Value::Float(f) => return Ok(Self::from_f64(*f)),
+            Value::Ref(id) => match heap.get(*id) {
+                HeapData::Tuple(tuple) => return Self::from_tuple(tuple.as_vec(), heap, interns),
+                HeapData::List(list) => return Self::from_tuple(list.as_vec(), heap, interns),
+                _ => {}
+            },
+            _ => {}
+        }
+        match value.as_either_str(heap) {
+            Some(text) => Ok(Self::parse(text.as_str(interns))?),
+            None => Err(ExcType::type_error(format!(
+                "conversion from {} to Decimal is not supported",
+                value.py_type(heap)
+            ))),
+        }
+    }
+
+    /// Converts a `(sign, digits, exponent)` sequence, where the exponent may also be `'F'`
+    /// for infinity or `'n'` or `'N'` for NaN.
+    fn from_tuple(items: &[Value], heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Self> {
+        let [sign, digits, exponent] = items else {
+            return Err(ExcType::value_error("argument must be a sequence of length 3"));
+        };
+        let negative = match int_value(sign, heap) {
+            Some(sign) if sign.is_zero() => false,
+            Some(sign) if sign.is_one() => true,
+            _ => return Err(ExcType::value_error("sign must be an integer with the value 0 or 1")),
+        };
+        if let Some(text) = exponent.as_either_str(heap) {
+            return match text.as_str(interns) {
+                "F" => Ok(Self::infinity(negative)),
+                "n" | "N" => Ok(Self::nan()),
+                _ => Err(ExcType::value_error(
+                    "string argument in the third position must be 'F', 'n' or 'N'",
+                )),
+            };
+        }
+        let digit_values = match digits {
+            Value::Ref(id) => match heap.get(*id) {
+                HeapData::Tuple(tuple) => Some(tuple.as_vec().as_slice()),
+                HeapData::List(list) => Some(list.as_vec().as_slice()),
+                _ => None,
+            },
+            _ => None,
+        };
+        let coeff = digit_values.and_then(|values| {
+            values.iter().try_fold(BigUint::zero(), |coeff, digit| {
+                let digit = int_value(digit, heap)?.to_u8().filter(|digit| *digit < 10)?;
+                Some(coeff * 10u32 + digit)
+            })
+        });
+        let Some(coeff) = coeff else {
+            return Err(ExcType::value_error("coefficient must be a tuple of digits"));
+        };
+        let Some(exponent) = int_value(exponent, heap).and_then(|exponent| exponent.to_i64()) else {
+            return Err(ExcType::value_error("exponent must be an integer"));
+        };
+        Ok(Self::from_parts(negative, coeff, exponent))
+    }
+
+    /// Allocates the decimal on the heap.
+    pub fn into_value(self, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, ResourceError> {
+        Ok(Value::Ref(heap.allocate(HeapData::Decimal(self))?))
+    }
+
+    /// Converts the decimal to an int for `int()`, truncating towards zero.
+    ///
+    /// # Errors
+    /// Returns `ValueError` for NaNs and `OverflowError` for infinities.
+    pub fn to_int(&self, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        let int = self.trunc(heap)?;
+        Ok(LongInt::new(int).into_value(heap)?)
+    }
+
+    /// The value truncated towards zero, as a `BigInt`.
+    ///
+    /// # Errors
+    /// Returns `ValueError` for NaNs and `OverflowError` for infinities.
+    pub fn trunc(&self, heap: &Heap<impl ResourceTracker>) -> RunResult<BigInt> {
+        if self.is_nan() {
+            return Err(ExcType::value_error("cannot convert NaN to integer"));
+        }
+        if self.is_infinite() {
+            return Err(SimpleException::new_msg(ExcType::OverflowError, "cannot convert Infinity to integer").into());
+        }
+        check_power_of_ten(self.exp.max(0).unsigned_abs(), heap)?;
+        Ok(self.to_bigint(Rounding::Down).expect("value is finite"))
+    }
+
+    /// The exact value as a fraction in lowest terms, as `as_integer_ratio()` returns it.
+    ///
+    /// # Errors
+    /// Returns `OverflowError` for infinities and `ValueError` for NaNs.
+    pub fn integer_ratio(&self, heap: &Heap<impl ResourceTracker>) -> RunResult<(BigInt, BigInt)> {
+        if !self.is_finite() {
+            return Err(fraction::special_ratio_error(self.is_nan()));
+        }
+        check_power_of_ten(self.exp.unsigned_abs(), heap)?;
+        Ok(self.to_ratio().expect("value is finite"))
+    }
+
+    /// The hash of the decimal, which is the hash of the int, float or fraction it equals.
+    ///
+    /// Values with huge exponents, which no int or float of a reasonable size equals, hash
+    /// their digits and exponent instead of computing the power of ten.
+    #[must_use]
+    pub fn hash(&self) -> u64 {
+        if !self.is_finite() {
+            let mut hasher = DefaultHasher::new();
+            let f = if self.is_nan() {
+                f64::NAN
+            } else if self.negative {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            };
+            discriminant(&Value::Float(0.0)).hash(&mut hasher);
+            f.to_bits().hash(&mut hasher);
+            return hasher.finish();
+        }
+        let (mut coeff, mut exp) = (self.coeff.clone(), self.exp);
+        while !coeff.is_zero() && (&coeff % 10u32).is_zero() {
+            coeff /= 10u32;
+            exp += 1;
+        }
+        if exp.unsigned_abs() > EXACT_HASH_EXPONENT {
+            let mut hasher = DefaultHasher::new();
+            self.negative.hash(&mut hasher);
+            coeff.hash(&mut hasher);
+            exp.hash(&mut hasher);
+            return hasher.finish();
+        }
+        let (numerator, denominator) = Self::finite(self.negative, coeff, exp)
+            .to_ratio()
+            .expect("value is finite");
+        hash_rational(&numerator, &denominator)
+    }
+
+    /// Formats the decimal for a format spec, like `Decimal.__format__`, rounding with the
+    /// current context.
+    ///
+    /// Supports the presentation types `e`, `E`, `f`, `F`, `g`, `G`, `n` and `%`, and
+    /// formats like `str()` (or `g` with a precision) without one.
+    pub fn format_spec(&self, spec: &ParsedFormatSpec, heap: &Heap<impl ResourceTracker>) -> RunResult<String> {
+        let supported = matches!(
+            spec.type_char,
+            None | Some('e' | 'E' | 'f' | 'F' | 'g' | 'G' | 'n' | '%')
+        );
+        if !supported || (spec.zero_pad && spec.align.is_some()) {
+            return Err(ExcType::value_error("invalid format string"));
+        }
+        let precision = spec
+            .precision
+            .map(|precision| u64::try_from(precision).unwrap_or(u64::MAX));
+        let mut digits = precision.unwrap_or_default();
+        if self.is_finite() && matches!(spec.type_char, Some('f' | 'F' | '%')) {
+            digits = digits.saturating_add(self.adjusted().unsigned_abs());
+        }
+        check_power_of_ten(digits, heap)?;
+        let rounding = heap.decimal_context().rounding;
+        let (negative, int_part, rest) = self.format_parts(spec.type_char, precision, rounding);
+        let body = format!("{int_part}{rest}");
+        if self.is_finite() {
+            Ok(pad_number(negative, &body, spec))
+        } else {
+            // infinities and NaNs are padded with spaces even with a zero-padded width
+            let spec = ParsedFormatSpec {
+                zero_pad: false,
+                ..spec.clone()
+            };
+            Ok(pad_number(negative, &body, &spec))
+        }
+    }
+
+    /// Rounds the decimal to an int for `round()`, or to `ndigits` decimal places with the
+    /// rounding mode of the current context.
+    pub fn py_round(&self, ndigits: Option<i64>, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        let ctx = heap.decimal_context();
+        match ndigits {
+            None => self.to_integral(Rounding::HalfEven).to_int(heap),
+            Some(ndigits) => {
+                let exponent = Self::finite(false, BigUint::one(), ndigits.saturating_neg());
+                if self.is_finite() {
+                    check_power_of_ten(self.exp.abs_diff(exponent.exp).min(ctx.prec), heap)?;
+                }
+                let rounded = self.quantize(&exponent, ctx.rounding, ctx)?;
+                Ok(rounded.into_value(heap)?)
+            }
+        }
+    }
+}
+
+/// The largest exponent of a decimal (with its trailing zeros removed) which is hashed
+/// exactly, with a power of ten of about 40KB.
+const EXACT_HASH_EXPONENT: u64 = 100_000;
+
+impl PyTrait for Decimal {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Decimal
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + usize::try_from(self.coeff.bits().div_ceil(8)).unwrap_or(usize::MAX)
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self.compare(other) == Some(Ordering::Equal)
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {}
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        !self.is_zero()
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        write!(f, "Decimal('{}')", self.to_sci_string())
+    }
+
+    fn py_str(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Cow<'static, str> {
+        Cow::Owned(self.to_sci_string())
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        let result = match attr.static_string() {
+            Some(StaticStrings::Quantize) => {
+                let [exp, rounding, context] = bind_args(
+                    "Decimal.quantize",
+                    args,
+                    ["exp", "rounding", "context"],
+                    1,
+                    heap,
+                    interns,
+                )?;
+                let result = self.quantize_args(exp.as_ref(), rounding.as_ref(), context.as_ref(), heap, interns);
+                exp.drop_with_heap(heap);
+                rounding.drop_with_heap(heap);
+                context.drop_with_heap(heap);
+                result?
+            }
+            Some(StaticStrings::ToIntegralValue | StaticStrings::ToIntegral) => {
+                let [rounding, context] = bind_args(
+                    "Decimal.to_integral_value",
+                    args,
+                    ["rounding", "context"],
+                    0,
+                    heap,
+                    interns,
+                )?;
+                let ctx = context_arg(context.as_ref(), heap);
+                let result = ctx.and_then(|ctx| rounding_or_default(rounding.as_ref(), ctx, heap, interns));
+                rounding.drop_with_heap(heap);
+                context.drop_with_heap(heap);
+                self.to_integral(result?)
+            }
+            Some(StaticStrings::Normalize) => {
+                let [context] = bind_args("Decimal.normalize", args, ["context"], 0, heap, interns)?;
+                let ctx = context_arg(context.as_ref(), heap);
+                context.drop_with_heap(heap);
+                self.normalize(ctx?)?
+            }
+            Some(StaticStrings::CopyAbs) => {
+                args.check_zero_args("Decimal.copy_abs", heap)?;
+                self.abs_value()
+            }
+            Some(StaticStrings::CopyNegate) => {
+                args.check_zero_args("Decimal.copy_negate", heap)?;
+                self.negated()
+            }
+            Some(StaticStrings::Conjugate) => {
+                args.check_zero_args("Decimal.conjugate", heap)?;
+                self.clone()
+            }
+            Some(StaticStrings::Adjusted) => {
+                args.check_zero_args("Decimal.adjusted", heap)?;
+                let adjusted = if self.is_finite() { self.adjusted() } else { 0 };
+                return Ok(Value::Int(adjusted));
+            }
+            Some(StaticStrings::AsIntegerRatio) => {
+                args.check_zero_args("Decimal.as_integer_ratio", heap)?;
+                let (numerator, denominator) = self.integer_ratio(heap)?;
+                let numerator = LongInt::new(numerator).into_value(heap)?;
+                let denominator = LongInt::new(denominator).into_value(heap)?;
+                return Ok(allocate_tuple(smallvec![numerator, denominator], heap)?);
+            }
+            Some(
+                predicate @ (StaticStrings::IsNan
+                | StaticStrings::IsInfinite
+                | StaticStrings::IsFinite
+                | StaticStrings::IsZero
+                | StaticStrings::IsSigned),
+            ) => {
+                args.check_zero_args(&format!("Decimal.{predicate}"), heap)?;
+                return Ok(Value::Bool(match predicate {
+                    StaticStrings::IsNan => self.is_nan(),
+                    StaticStrings::IsInfinite => self.is_infinite(),
+                    StaticStrings::IsFinite => self.is_finite(),
+                    StaticStrings::IsZero => self.is_zero(),
+                    _ => self.is_negative(),
+                }));
+            }
+            _ => {
+                args.drop_with_heap(heap);
+                return Err(ExcType::attribute_error(Type::Decimal, attr.as_str(interns)));
+            }
+        };
+        Ok(result.into_value(heap)?)
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = match StaticStrings::from_string_id(attr_id) {
+            Some(StaticStrings::Real) => self.clone().into_value(heap)?,
+            Some(StaticStrings::Imag) => Self::from_bigint(&BigInt::zero()).into_value(heap)?,
+            _ => return Ok(None),
+        };
+        Ok(Some(AttrCallResult::Value(value)))
+    }
+}
+
+impl Decimal {
+    /// Implements `quantize(exp, rounding=None, context=None)`, where `exp` may be a decimal
+    /// or an int.
+    fn quantize_args(
+        &self,
+        exp: Option<&Value>,
+        rounding: Option<&Value>,
+        context: Option<&Value>,
+        heap: &Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Self> {
+        let exp = exp.expect("exp is required");
+        let Some(exp) = operand(exp, heap) else {
+            return Err(ExcType::type_error(format!(
+                "conversion from {} to Decimal is not supported",
+                exp.py_type(heap)
+            )));
+        };
+        let ctx = context_arg(context, heap)?;
+        let rounding = rounding_or_default(rounding, ctx, heap, interns)?;
+        if self.is_finite() && exp.is_finite() {
+            check_power_of_ten(self.exp.abs_diff(exp.exp).min(ctx.prec), heap)?;
+        }
+        Ok(self.quantize(&exp, rounding, ctx)?)
+    }
+}
+
+/// The state of a `decimal.Context` object.
+///
+/// `getcontext()` and `localcontext()` give the current context, whose settings live on
+/// the heap so changing them changes how all decimal arithmetic rounds, while `Context()`
+/// makes a detached one, which only has an effect once passed to `setcontext()`,
+/// `localcontext()` or a method's `context` argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) enum DecimalContext {
+    /// The current context of the program, [`Heap::decimal_context`].
+    Current,
+    /// A context made with `Context()` and its settings.
+    Detached(Context),
+}
+
+impl DecimalContext {
+    /// Creates a `decimal.Context` from the `Context(prec=None, rounding=None)` constructor
+    /// call, starting from the default settings.
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let [prec, rounding] = bind_args("Context", args, ["prec", "rounding"], 0, heap, interns)?;
+        let result = context_with(Context::default(), prec.as_ref(), rounding.as_ref(), heap, interns);
+        prec.drop_with_heap(heap);
+        rounding.drop_with_heap(heap);
+        Ok(Self::Detached(result?).into_value(heap)?)
+    }
+
+    /// Allocates the context on the heap.
+    pub fn into_value(self, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, ResourceError> {
+        Ok(Value::Ref(heap.allocate(HeapData::DecimalContext(self))?))
+    }
+
+    /// The settings of the context.
+    #[must_use]
+    pub fn settings(self, heap: &Heap<impl ResourceTracker>) -> Context {
+        match self {
+            Self::Current => heap.decimal_context(),
+            Self::Detached(context) => context,
+        }
+    }
+}
+
+impl PyTrait for DecimalContext {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::DecimalContext
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self == other
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {}
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        let context = self.settings(heap);
+        write!(
+            f,
+            "Context(prec={}, rounding={}, Emin={EMIN}, Emax={EMAX}, capitals=1, clamp=0, flags=[], \
+             traps=[InvalidOperation, DivisionByZero, Overflow])",
+            context.prec,
+            context.rounding.name()
+        )
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        if attr.static_string() == Some(StaticStrings::Copy) {
+            args.check_zero_args("Context.copy", heap)?;
+            return Ok(Self::Detached(self.settings(heap)).into_value(heap)?);
+        }
+        args.drop_with_heap(heap);
+        Err(ExcType::attribute_error(Type::DecimalContext, attr.as_str(interns)))
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let context = self.settings(heap);
+        let value = match StaticStrings::from_string_id(attr_id) {
+            Some(StaticStrings::Prec) => Value::Int(i64::try_from(context.prec).expect("prec is at most MAX_PREC")),
+            Some(StaticStrings::Rounding) => allocate_string(context.rounding.name().to_owned(), heap)?,
+            Some(StaticStrings::Emax) => Value::Int(EMAX),
+            Some(StaticStrings::Emin) => Value::Int(EMIN),
+            _ => return Ok(None),
+        };
+        Ok(Some(AttrCallResult::Value(value)))
+    }
+}
+
+/// Sets the `prec` or `rounding` attribute of a `decimal.Context`.
+///
+/// Returns `Err(value)` if `heap_id` is not a context, handing `value` back to the caller.
+pub(crate) fn set_context_attr(
+    heap_id: HeapId,
+    name_id: StringId,
+    value: Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Result<RunResult<()>, Value> {
+    let HeapData::DecimalContext(context) = heap.get(heap_id) else {
+        return Err(value);
+    };
+    let context = *context;
+    let settings = context.settings(heap);
+    let result = match StaticStrings::from_string_id(name_id) {
+        Some(StaticStrings::Prec) => context_with(settings, Some(&value), None, heap, interns),
+        Some(StaticStrings::Rounding) => context_with(settings, None, Some(&value), heap, interns),
+        _ => Err(ExcType::attribute_error(Type::DecimalContext, interns.get_str(name_id))),
+    };
+    value.drop_with_heap(heap);
+    Ok(result.map(|settings| match context {
+        DecimalContext::Current => heap.set_decimal_context(settings),
+        DecimalContext::Detached(_) => {
+            *heap.get_mut(heap_id) = HeapData::DecimalContext(DecimalContext::Detached(settings));
+        }
+    }))
+}
+
+/// The context manager returned by `decimal.localcontext()`.
+///
+/// `__enter__` saves the current context and replaces it with `context`, and `__exit__`
+/// restores the saved one, so changes made inside the `with` block don't leak out of it.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct DecimalContextManager {
+    context: Context,
+    saved: Option<Context>,
+}
+
+impl DecimalContextManager {
+    /// Creates a manager which switches to `context` for the `with` block.
+    #[must_use]
+    pub fn new(context: Context) -> Self {
+        Self { context, saved: None }
+    }
+}
+
+impl PyTrait for DecimalContextManager {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::DecimalContextManager
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        false
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {}
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        f.write_str("<decimal.ContextManager object>")
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        match attr.static_string() {
+            Some(StaticStrings::DunderEnter) => {
+                args.check_zero_args("__enter__", heap)?;
+                self.saved = Some(heap.decimal_context());
+                heap.set_decimal_context(self.context);
+                Ok(DecimalContext::Current.into_value(heap)?)
+            }
+            Some(StaticStrings::DunderExit) => {
+                args.drop_with_heap(heap);
+                if let Some(saved) = self.saved.take() {
+                    heap.set_decimal_context(saved);
+                }
+                Ok(Value::Bool(false))
+            }
+            _ => {
+                args.drop_with_heap(heap);
+                Err(ExcType::attribute_error(
+                    Type::DecimalContextManager,
+                    attr.as_str(interns),
+                ))
+            }
+        }
+    }
+}
+
+impl From<Signal> for RunError {
+    fn from(signal: Signal) -> Self {
+        let exc_type = match signal {
+            Signal::DivisionByZero => ExcType::DivisionByZero,
+            Signal::Overflow => ExcType::Overflow,
+            _ => ExcType::InvalidOperation,
+        };
+        let message = format!("[<class 'decimal.{}'>]", signal.condition_name());
+        SimpleException::new_msg(exc_type, message).into()
+    }
+}
+
+/// Returns the settings of `context` with `prec` and `rounding` replaced by the given values,
+/// where `None` (or Python's `None`) keeps the current setting.
+///
+/// # Errors
+/// Returns `TypeError` for a non-int precision or an unknown rounding mode, and `ValueError`
+/// for a precision out of range.
+pub(crate) fn context_with(
+    context: Context,
+    prec: Option<&Value>,
+    rounding: Option<&Value>,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Context> {
+    let mut context = context;
+    if let Some(prec) = prec.filter(|prec| !matches!(prec, Value::None)) {
+        let Some(prec) = int_value(prec, heap) else {
+            return Err(ExcType::type_error("an integer is required"));
+        };
+        context.prec = prec
+            .to_u64()
+            .filter(|prec| (1..=MAX_PREC).contains(prec))
+            .ok_or_else(|| ExcType::value_error("valid range for prec is [1, MAX_PREC]"))?;
+    }
+    if let Some(rounding) = rounding.filter(|rounding| !matches!(rounding, Value::None)) {
+        context.rounding = rounding_arg(rounding, heap, interns)?;
+    }
+    Ok(context)
+}
+
+/// Converts a rounding mode argument, like `decimal.ROUND_HALF_UP`.
+fn rounding_arg(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Rounding> {
+    value
+        .as_either_str(heap)
+        .and_then(|name| Rounding::from_name(name.as_str(interns)))
+        .ok_or_else(|| {
+            ExcType::type_error(
+                "valid values for rounding are:\n  [ROUND_CEILING, ROUND_FLOOR, ROUND_UP, ROUND_DOWN,\n   \
+                 ROUND_HALF_UP, ROUND_HALF_DOWN, ROUND_HALF_EVEN,\n   ROUND_05UP]",
+            )
+        })
+}
+
+/// Converts an optional `rounding` argument, defaulting to the rounding mode of `context`.
+fn rounding_or_default(
+    rounding: Option<&Value>,
+    context: Context,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Rounding> {
+    match rounding {
+        None | Some(Value::None) => Ok(context.rounding),
+        Some(rounding) => rounding_arg(rounding, heap, interns),
+    }
+}
+
+/// Converts an optional `context` argument, defaulting to the current context.
+///
+/// # Errors
+/// Returns `TypeError` if the argument isn't a `decimal.Context`.
+pub(crate) fn context_arg(context: Option<&Value>, heap: &Heap<impl ResourceTracker>) -> RunResult<Context> {
+    match context {
+        None | Some(Value::None) => Ok(heap.decimal_context()),
+        Some(value) => {
+            context_value(value, heap).ok_or_else(|| ExcType::type_error("optional argument must be a context"))
+        }
+    }
+}
+
+/// The settings of a `decimal.Context` value, or `None` for other values.
+#[must_use]
+pub(crate) fn context_value(value: &Value, heap: &Heap<impl ResourceTracker>) -> Option<Context> {
+    match value {
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::DecimalContext(context) => Some(context.settings(heap)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Implements the class method `Decimal.from_float()`, which also accepts ints.
+pub(crate) fn from_float(
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    _interns: &Interns,
+) -> RunResult<Value> {
+    let value = args.get_one_arg("Decimal.from_float", heap)?;
+    defer_drop!(value, heap);
+    let decimal = match value {
+        Value::Float(f) => Decimal::from_f64(*f),
+        _ => match int_value(value, heap) {
+            Some(int) => Decimal::from_bigint(&int),
+            None => return Err(ExcType::type_error("argument must be int or float")),
+        },
+    };
+    Ok(decimal.into_value(heap)?)
+}
+
+/// Converts an int, bool or decimal, the values decimals mix with in arithmetic, to a decimal.
+#[must_use]
+fn operand(value: &Value, heap: &Heap<impl ResourceTracker>) -> Option<Decimal> {
+    match as_decimal(value, heap) {
+        Some(decimal) => Some(decimal.clone()),
+        None => int_value(value, heap).map(|int| Decimal::from_bigint(&int)),
+    }
+}
+
+/// Returns whether `value` is a decimal.
+#[must_use]
+pub(crate) fn is_decimal(value: &Value, heap: &Heap<impl ResourceTracker>) -> bool {
+    as_decimal(value, heap).is_some()
+}
+
+/// The decimal `value` refers to, if it's a decimal.
+fn as_decimal<'h>(value: &Value, heap: &'h Heap<impl ResourceTracker>) -> Option<&'h Decimal> {
+    match value {
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Decimal(decimal) => Some(decimal),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Applies an arithmetic operator when either operand is a decimal and the other is a
+/// decimal or an int, rounding the result with the current context.
+///
+/// Returns `Ok(None)` for other operands, including floats and fractions, so the caller can
+/// report the usual TypeError.
+pub(crate) fn binary_op(
+    lhs: &Value,
+    rhs: &Value,
+    op: &Operator,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> RunResult<Option<Value>> {
+    if !is_decimal(lhs, heap) && !is_decimal(rhs, heap) {
+        return Ok(None);
+    }
+    let (Some(a), Some(b)) = (operand(lhs, heap), operand(rhs, heap)) else {
+        return Ok(None);
+    };
+    let ctx = heap.decimal_context();
+    // the work of most operations grows with the precision, and of additions with the
+    // distance between the exponents, which a huge precision stops rounding from capping
+    let digits = a.digits().saturating_add(b.digits());
+    let work = match op {
+        Operator::Add | Operator::Sub => a.exp.abs_diff(b.exp).min(ctx.prec.saturating_add(digits)),
+        Operator::Mult => 0,
+        Operator::Pow => ctx.prec.saturating_mul(3).saturating_add(digits),
+        _ => ctx.prec.saturating_add(digits),
+    };
+    if a.is_finite() && b.is_finite() {
+        check_power_of_ten(work, heap)?;
+    }
+    let result = match op {
+        Operator::Add => a.add(&b, ctx),
+        Operator::Sub => a.sub(&b, ctx),
+        Operator::Mult => a.mul(&b, ctx),
+        Operator::Div => a.div(&b, ctx),
+        Operator::FloorDiv => a.div_int(&b, ctx),
+        Operator::Mod => a.rem(&b, ctx),
+        Operator::Pow => power(&a, &b, ctx),
+        _ => return Ok(None),
+    };
+    Ok(Some(result?.into_value(heap)?))
+}
+
+/// Raises `base` to a decimal power, which must be an integer (or a NaN).
+fn power(base: &Decimal, exponent: &Decimal, ctx: Context) -> RunResult<Decimal> {
+    if base.is_nan() || exponent.is_nan() {
+        return Ok(Decimal::nan());
+    }
+    if !exponent.is_integral() {
+        return Err(ExcType::not_implemented("non-integral Decimal exponents are not supported").into());
+    }
+    // a power of at least 1e20 overflows or underflows unless the base is a unit, zero or an
+    // infinity, where only its sign and parity matter, so don't build all the digits of one
+    // with a huge exponent like `1e999999999`; any multiple of ten stands in for it
+    let exponent = if exponent.exp > 0 && exponent.adjusted() >= 20 {
+        let sign = if exponent.negative { Sign::Minus } else { Sign::Plus };
+        BigInt::from_biguint(sign, pow10(20))
+    } else {
+        exponent.to_bigint(Rounding::Down).expect("exponent is finite")
+    };
+    Ok(base.pow_int(&exponent, ctx)?)
+}
+
+/// Applies unary minus, plus or `abs()` to a decimal, rounding it with the current context.
+pub(crate) fn unary_op(decimal: &Decimal, op: UnaryOp, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let ctx = heap.decimal_context();
+    let result = match op {
+        UnaryOp::Neg => decimal.minus(ctx),
+        UnaryOp::Pos => decimal.plus(ctx),
+        UnaryOp::Abs => decimal.abs(ctx),
+    };
+    Ok(result?.into_value(heap)?)
+}
+
+/// The unary operations on decimals, which round to the context unlike those of other numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnaryOp {
+    Neg,
+    Pos,
+    Abs,
+}
+
+/// Compares two numbers exactly when either is a decimal.
+///
+/// Returns `None` when neither operand is a decimal, so callers fall through to their usual
+/// comparison, and `Some(None)` when the operands can't be ordered, like a decimal NaN or a
+/// decimal and a string.
+#[must_use]
+pub(crate) fn compare(lhs: &Value, rhs: &Value, heap: &Heap<impl ResourceTracker>) -> Option<Option<Ordering>> {
+    if let Some(decimal) = as_decimal(lhs, heap) {
+        Some(compare_with(decimal, rhs, heap))
+    } else {
+        as_decimal(rhs, heap).map(|decimal| compare_with(decimal, lhs, heap).map(Ordering::reverse))
+    }
+}
+
+/// Compares a decimal with another number: a decimal, an int, a float or a fraction.
+fn compare_with(decimal: &Decimal, other: &Value, heap: &Heap<impl ResourceTracker>) -> Option<Ordering> {
+    if let Some(other) = operand(other, heap) {
+        return decimal.compare(&other);
+    }
+    match other {
+        Value::Float(f) if f.is_nan() => None,
+        Value::Float(f) if f.is_infinite() => decimal.compare(&Decimal::infinity(*f < 0.0)),
+        Value::Float(f) => {
+            let (numerator, denominator) = Decimal::from_f64(*f).to_ratio().expect("float is finite");
+            decimal.cmp_ratio(&numerator, &denominator)
+        }
+        _ => {
+            let fraction = fraction::as_rational(other, heap)?;
+            decimal.cmp_ratio(fraction.numerator(), fraction.denominator())
+        }
+    }
+}
+
+/// Raises the error of an ordering comparison involving a decimal which [`compare`] can't
+/// order: `InvalidOperation` if a NaN is involved, and the usual TypeError for other types.
+///
+/// Does nothing when neither operand is a decimal, or when the operands can be ordered.
+pub(crate) fn check_orderable(
+    lhs: &Value,
+    rhs: &Value,
+    symbol: &str,
+    heap: &Heap<impl ResourceTracker>,
+) -> RunResult<()> {
+    if !matches!(compare(lhs, rhs, heap), Some(None)) {
+        return Ok(());
+    }
+    let is_number = |value: &Value| {
+        matches!(value, Value::Int(_) | Value::Bool(_) | Value::Float(_))
+            || matches!(value, Value::Ref(id) if matches!(heap.get(*id), HeapData::LongInt(_) | HeapData::Decimal(_) | HeapData::Fraction(_)))
+    };
+    if is_number(lhs) && is_number(rhs) {
+        Err(Signal::InvalidOperation.into())
+    } else {
+        Err(ExcType::type_error(format!(
+            "'{symbol}' not supported between instances of '{}' and '{}'",
+            lhs.py_type(heap),
+            rhs.py_type(heap)
+        )))
+    }
+}
@@ -0,0 +1,540 @@
+//! Python `collections.deque` type.
+//!
+//! A double-ended queue backed by a `VecDeque`, so appending and popping at either end is
+//! O(1). A deque may have a maximum length, in which case adding items to a full deque
+//! discards items from the opposite end.
+
+use std::{collections::VecDeque, fmt::Write};
+
+use ahash::AHashSet;
+
+use super::{AttrCallResult, MontyIter, PyTrait, Type};
+use crate::{
+    args::{ArgValues, bind_args},
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    resource::{ResourceError, ResourceTracker},
+    value::{EitherStr, Value},
+};
+
+/// Python `collections.deque` value stored on the heap.
+///
+/// # Implemented Methods
+/// - `append(x)` / `appendleft(x)` - Add an item to the right or left end
+/// - `pop()` / `popleft()` - Remove and return the rightmost or leftmost item
+/// - `extend(iterable)` / `extendleft(iterable)` - Add items to the right or left end
+/// - `rotate(n=1)` - Rotate `n` steps to the right, or to the left if negative
+/// - `insert(i, x)` - Insert an item at position `i`
+/// - `remove(value)` - Remove the first occurrence of value
+/// - `index(value[, start[, stop]])` - Find the first index of value
+/// - `count(value)` - Count occurrences
+/// - `clear()`, `copy()` and `reverse()`
+///
+/// The `maxlen` attribute is `None` for unbounded deques.
+///
+/// # Reference Counting
+/// Items added to the deque are owned by it; items discarded because the deque is full
+/// have their refcounts decremented.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Deque {
+    items: VecDeque<Value>,
+    /// The maximum length, or `None` for an unbounded deque.
+    maxlen: Option<usize>,
+    /// True if any item is a `Value::Ref`. Only transitions from false to true, like `List`.
+    contains_refs: bool,
+}
+
+impl Deque {
+    /// Creates a deque from the given items, keeping only the last `maxlen` of them.
+    ///
+    /// Note: This does NOT increment reference counts - the caller must
+    /// ensure refcounts are properly managed.
+    #[must_use]
+    pub fn new(
+        items: impl IntoIterator<Item = Value>,
+        maxlen: Option<usize>,
+        heap: &mut Heap<impl ResourceTracker>,
+    ) -> Self {
+        let mut deque = Self {
+            items: VecDeque::new(),
+            maxlen,
+            contains_refs: false,
+        };
+        for item in items {
+            deque.push_back(item, heap);
+        }
+        deque
+    }
+
+    /// Returns the number of items in the deque.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns the item at the given index from the left, or `None` if out of bounds.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&Value> {
+        self.items.get(index)
+    }
+
+    /// Returns whether the deque contains any heap references.
+    ///
+    /// When false, `collect_child_ids` and `py_dec_ref_ids` can skip iteration.
+    #[inline]
+    #[must_use]
+    pub fn contains_refs(&self) -> bool {
+        self.contains_refs
+    }
+
+    /// Returns an iterator over the items, from left to right.
+    pub fn iter(&self) -> impl Iterator<Item = &Value> {
+        self.items.iter()
+    }
+
+    /// Creates a deque from the `collections.deque()` constructor call.
+    ///
+    /// - `deque()` returns an empty, unbounded deque
+    /// - `deque(iterable, maxlen=None)` holds the items of the iterable, of which only the
+    ///   last `maxlen` are kept
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let [iterable, maxlen] = bind_args("deque", args, ["iterable", "maxlen"], 0, heap, interns)?;
+        let maxlen = match maxlen {
+            None | Some(Value::None) => None,
+            Some(Value::Int(n)) if n < 0 => {
+                iterable.drop_with_heap(heap);
+                return Err(SimpleException::new_msg(ExcType::ValueError, "maxlen must be non-negative").into());
+            }
+            Some(Value::Int(n)) => Some(usize::try_from(n).unwrap_or(usize::MAX)),
+            Some(Value::Bool(b)) => Some(usize::from(b)),
+            Some(other) => {
+                other.drop_with_heap(heap);
+                iterable.drop_with_heap(heap);
+                return Err(ExcType::type_error("an integer is required"));
+            }
+        };
+        let items: Vec<Value> = match iterable {
+            Some(iterable) => {
+                let mut iter = MontyIter::new(iterable, heap, interns)?;
+                let items = iter.collect(heap, interns);
+                iter.drop_with_heap(heap);
+                items?
+            }
+            None => Vec::new(),
+        };
+        let deque = Self::new(items, maxlen, heap);
+        Ok(Value::Ref(heap.allocate(HeapData::Deque(deque))?))
+    }
+
+    /// Adds an item to the right end, discarding the leftmost item if the deque is full.
+    fn push_back(&mut self, item: Value, heap: &mut Heap<impl ResourceTracker>) {
+        if self.maxlen == Some(0) {
+            item.drop_with_heap(heap);
+            return;
+        }
+        if self.maxlen == Some(self.items.len())
+            && let Some(discarded) = self.items.pop_front()
+        {
+            discarded.drop_with_heap(heap);
+        }
+        self.track_refs(&item, heap);
+        self.items.push_back(item);
+    }
+
+    /// Adds an item to the left end, discarding the rightmost item if the deque is full.
+    fn push_front(&mut self, item: Value, heap: &mut Heap<impl ResourceTracker>) {
+        if self.maxlen == Some(0) {
+            item.drop_with_heap(heap);
+            return;
+        }
+        if self.maxlen == Some(self.items.len())
+            && let Some(discarded) = self.items.pop_back()
+        {
+            discarded.drop_with_heap(heap);
+        }
+        self.track_refs(&item, heap);
+        self.items.push_front(item);
+    }
+
+    /// Updates `contains_refs` for an item about to be added.
+    fn track_refs(&mut self, item: &Value, heap: &mut Heap<impl ResourceTracker>) {
+        if matches!(item, Value::Ref(_)) {
+            self.contains_refs = true;
+            heap.mark_potential_cycle();
+        }
+    }
+
+    /// Converts a subscript to an index from the left.
+    ///
+    /// Deques only support integer subscripts, not slices.
+    fn index_of(&self, key: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<usize> {
+        let index = match key {
+            Value::Int(i) => *i,
+            Value::Bool(b) => i64::from(*b),
+            _ => {
+                return Err(ExcType::type_error(format!(
+                    "sequence index must be integer, not '{}'",
+                    key.py_type(heap)
+                )));
+            }
+        };
+        let len = i64::try_from(self.items.len()).expect("deque length exceeds i64::MAX");
+        let normalized = if index < 0 { index + len } else { index };
+        if normalized < 0 || normalized >= len {
+            return Err(ExcType::deque_index_error());
+        }
+        Ok(usize::try_from(normalized).expect("deque index validated non-negative"))
+    }
+}
+
+impl PyTrait for Deque {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Deque
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.items.len() * std::mem::size_of::<Value>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        Some(self.items.len())
+    }
+
+    fn py_getitem(&self, key: &Value, heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> RunResult<Value> {
+        let index = self.index_of(key, heap)?;
+        Ok(self.items[index].clone_with_heap(heap))
+    }
+
+    fn py_setitem(
+        &mut self,
+        key: Value,
+        value: Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<()> {
+        let index = self.index_of(&key, heap);
+        key.drop_with_heap(heap);
+        let index = match index {
+            Ok(index) => index,
+            Err(e) => {
+                value.drop_with_heap(heap);
+                return Err(e);
+            }
+        };
+        self.track_refs(&value, heap);
+        let old_value = std::mem::replace(&mut self.items[index], value);
+        old_value.drop_with_heap(heap);
+        Ok(())
+    }
+
+    fn py_delitem(&mut self, key: Value, heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> RunResult<()> {
+        let index = self.index_of(&key, heap);
+        key.drop_with_heap(heap);
+        let removed = self.items.remove(index?).expect("deque index validated in bounds");
+        removed.drop_with_heap(heap);
+        Ok(())
+    }
+
+    fn py_eq(&self, other: &Self, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> bool {
+        self.items.len() == other.items.len()
+            && self
+                .items
+                .iter()
+                .zip(&other.items)
+                .all(|(a, b)| a.py_eq(b, heap, interns))
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        if !self.contains_refs {
+            return;
+        }
+        for obj in &mut self.items {
+            if let Value::Ref(id) = obj {
+                stack.push(*id);
+                #[cfg(feature = "ref-count-panic")]
+                obj.dec_ref_forget();
+            }
+        }
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        !self.items.is_empty()
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        heap: &Heap<impl ResourceTracker>,
+        heap_ids: &mut AHashSet<HeapId>,
+        interns: &Interns,
+    ) -> std::fmt::Result {
+        f.write_str("deque([")?;
+        let mut first = true;
+        for item in &self.items {
+            if !first {
+                f.write_str(", ")?;
+            }
+            first = false;
+            item.py_repr_fmt(f, heap, heap_ids, interns)?;
+        }
+        f.write_str("]")?;
+        if let Some(maxlen) = self.maxlen {
+            write!(f, ", maxlen={maxlen}")?;
+        }
+        f.write_char(')')
+    }
+
+    fn py_add(
+        &self,
+        other: &Self,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> Result<Option<Value>, ResourceError> {
+        // The result keeps the maxlen of the left operand
+        let items: Vec<Value> = self
+            .items
+            .iter()
+            .chain(&other.items)
+            .map(|item| item.clone_with_heap(heap))
+            .collect();
+        let deque = Self::new(items, self.maxlen, heap);
+        Ok(Some(Value::Ref(heap.allocate(HeapData::Deque(deque))?)))
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        let Some(method) = attr.static_string() else {
+            args.drop_with_heap(heap);
+            return Err(ExcType::attribute_error(Type::Deque, attr.as_str(interns)));
+        };
+
+        match method {
+            StaticStrings::Append => {
+                let item = args.get_one_arg("deque.append", heap)?;
+                self.push_back(item, heap);
+                Ok(Value::None)
+            }
+            StaticStrings::Appendleft => {
+                let item = args.get_one_arg("deque.appendleft", heap)?;
+                self.push_front(item, heap);
+                Ok(Value::None)
+            }
+            StaticStrings::Pop => {
+                args.check_zero_args("deque.pop", heap)?;
+                self.items.pop_back().ok_or_else(ExcType::index_error_pop_empty_deque)
+            }
+            StaticStrings::Popleft => {
+                args.check_zero_args("deque.popleft", heap)?;
+                self.items.pop_front().ok_or_else(ExcType::index_error_pop_empty_deque)
+            }
+            StaticStrings::Extend | StaticStrings::Extendleft => {
+                let name = if method == StaticStrings::Extend {
+                    "deque.extend"
+                } else {
+                    "deque.extendleft"
+                };
+                let iterable = args.get_one_arg(name, heap)?;
+                let mut iter = MontyIter::new(iterable, heap, interns)?;
+                let items: RunResult<Vec<Value>> = iter.collect(heap, interns);
+                iter.drop_with_heap(heap);
+                for item in items? {
+                    if method == StaticStrings::Extend {
+                        self.push_back(item, heap);
+                    } else {
+                        self.push_front(item, heap);
+                    }
+                }
+                Ok(Value::None)
+            }
+            StaticStrings::Rotate => {
+                let n = args.get_zero_one_arg("deque.rotate", heap)?;
+                let n = match n {
+                    Some(n) => {
+                        let result = n.as_int(heap);
+                        n.drop_with_heap(heap);
+                        result?
+                    }
+                    None => 1,
+                };
+                if !self.items.is_empty() {
+                    let len = i64::try_from(self.items.len()).expect("deque length exceeds i64::MAX");
+                    let steps = usize::try_from(n.rem_euclid(len)).expect("rem_euclid is non-negative");
+                    self.items.rotate_right(steps);
+                }
+                Ok(Value::None)
+            }
+            StaticStrings::Insert => deque_insert(self, args, heap),
+            StaticStrings::Remove => {
+                let value = args.get_one_arg("deque.remove", heap)?;
+                let position = self.items.iter().position(|item| value.py_eq(item, heap, interns));
+                match position {
+                    Some(index) => {
+                        value.drop_with_heap(heap);
+                        let removed = self.items.remove(index).expect("position is in bounds");
+                        removed.drop_with_heap(heap);
+                        Ok(Value::None)
+                    }
+                    None => Err(not_in_deque(value, heap, interns)),
+                }
+            }
+            StaticStrings::Index => deque_index(self, args, heap, interns),
+            StaticStrings::Count => {
+                let value = args.get_one_arg("deque.count", heap)?;
+                let count = self
+                    .items
+                    .iter()
+                    .filter(|item| value.py_eq(item, heap, interns))
+                    .count();
+                value.drop_with_heap(heap);
+                Ok(Value::Int(i64::try_from(count).expect("count exceeds i64::MAX")))
+            }
+            StaticStrings::Clear => {
+                args.check_zero_args("deque.clear", heap)?;
+                for item in self.items.drain(..) {
+                    item.drop_with_heap(heap);
+                }
+                Ok(Value::None)
+            }
+            StaticStrings::Copy => {
+                args.check_zero_args("deque.copy", heap)?;
+                let items: Vec<Value> = self.items.iter().map(|item| item.clone_with_heap(heap)).collect();
+                let deque = Self::new(items, self.maxlen, heap);
+                Ok(Value::Ref(heap.allocate(HeapData::Deque(deque))?))
+            }
+            StaticStrings::Reverse => {
+                args.check_zero_args("deque.reverse", heap)?;
+                self.items.make_contiguous().reverse();
+                Ok(Value::None)
+            }
+            _ => {
+                args.drop_with_heap(heap);
+                Err(ExcType::attribute_error(Type::Deque, attr.as_str(interns)))
+            }
+        }
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        _heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        if attr_id == StaticStrings::Maxlen {
+            let maxlen = self.maxlen.map_or(Value::None, |maxlen| {
+                Value::Int(i64::try_from(maxlen).expect("maxlen exceeds i64::MAX"))
+            });
+            Ok(Some(AttrCallResult::Value(maxlen)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Implements Python's `deque.insert(i, x)` method.
+///
+/// Like `list.insert()`, the index is clamped to the bounds of the deque. Inserting into a
+/// full bounded deque raises IndexError rather than discarding an item.
+fn deque_insert(deque: &mut Deque, args: ArgValues, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let (index, item) = args.get_two_args("deque.insert", heap)?;
+    let index_result = index.as_int(heap);
+    index.drop_with_heap(heap);
+    let index = match index_result {
+        Ok(index) => index,
+        Err(e) => {
+            item.drop_with_heap(heap);
+            return Err(e);
+        }
+    };
+    if deque.maxlen == Some(deque.items.len()) {
+        item.drop_with_heap(heap);
+        return Err(SimpleException::new_msg(ExcType::IndexError, "deque already at its maximum size").into());
+    }
+    let len = deque.items.len();
+    let len_i64 = i64::try_from(len).expect("deque length exceeds i64::MAX");
+    let index = if index < 0 {
+        usize::try_from(index + len_i64).unwrap_or(0)
+    } else {
+        usize::try_from(index).unwrap_or(len).min(len)
+    };
+    deque.track_refs(&item, heap);
+    deque.items.insert(index, item);
+    Ok(Value::None)
+}
+
+/// Implements Python's `deque.index(value[, start[, stop]])` method.
+fn deque_index(
+    deque: &Deque,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let (mut pos, kwargs) = args.into_parts();
+    if !kwargs.is_empty() {
+        pos.drop_with_heap(heap);
+        kwargs.drop_with_heap(heap);
+        return Err(ExcType::type_error_no_kwargs("deque.index"));
+    }
+    let count = pos.len();
+    if !(1..=3).contains(&count) {
+        pos.drop_with_heap(heap);
+        return Err(if count == 0 {
+            ExcType::type_error_at_least("deque.index", 1, count)
+        } else {
+            ExcType::type_error_at_most("deque.index", 3, count)
+        });
+    }
+    let value = pos.next().expect("checked at least one argument");
+
+    // Bounds are clamped like slice indices
+    let len = deque.items.len();
+    let mut bounds = [0, len];
+    let mut error = None;
+    for (bound, arg) in bounds.iter_mut().zip(pos) {
+        if error.is_none() {
+            match arg.as_int(heap) {
+                Ok(index) if index < 0 => {
+                    *bound = len.saturating_sub(usize::try_from(index.unsigned_abs()).unwrap_or(usize::MAX));
+                }
+                Ok(index) => *bound = usize::try_from(index).unwrap_or(len).min(len),
+                Err(e) => error = Some(e),
+            }
+        }
+        arg.drop_with_heap(heap);
+    }
+    if let Some(e) = error {
+        value.drop_with_heap(heap);
+        return Err(e);
+    }
+    let [start, stop] = bounds;
+
+    let position = deque
+        .items
+        .iter()
+        .enumerate()
+        .take(stop)
+        .skip(start)
+        .find(|(_, item)| value.py_eq(item, heap, interns))
+        .map(|(index, _)| index);
+    match position {
+        Some(index) => {
+            value.drop_with_heap(heap);
+            Ok(Value::Int(i64::try_from(index).expect("index exceeds i64::MAX")))
+        }
+        None => Err(not_in_deque(value, heap, interns)),
+    }
+}
+
+/// Creates the ValueError for `deque.index()` and `deque.remove()`, consuming the value.
+///
+/// Matches CPython's format: `ValueError: 2 is not in deque`
+fn not_in_deque(value: Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunError {
+    let message = format!("{} is not in deque", value.py_repr(heap, interns));
+    value.drop_with_heap(heap);
+    SimpleException::new_msg(ExcType::ValueError, message).into()
+}
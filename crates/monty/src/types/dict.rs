@@ -8,12 +8,14 @@ use ahash::AHashSet;
 use hashbrown::{HashTable, hash_table::Entry};
 use smallvec::smallvec;
 
-use super::{List, MontyIter, PyTrait, allocate_tuple};
+use super::{AttrCallResult, MontyIter, PyTrait, allocate_tuple, class::unhashable_error, counter};
 use crate::{
-    args::{ArgValues, KwargsValues},
-    exception_private::{ExcType, RunResult},
-    heap::{Heap, HeapData, HeapId},
-    intern::{Interns, StaticStrings},
+    args::{ArgValues, KwargsValues, bind_args},
+    builtins::is_callable,
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
     resource::ResourceTracker,
     types::Type,
     value::{EitherStr, Value},
@@ -39,12 +41,22 @@ use crate::{
 ///
 /// All dict methods from Python's builtins are implemented.
 ///
+/// # Dict Subclasses
+/// `collections.defaultdict`, `collections.Counter` and `collections.OrderedDict` are
+/// dicts too, distinguished by their [`DictKind`]. They share the storage and methods
+/// above; see [`DictKind`] for how each one differs.
+///
 /// # Storage Strategy
-/// Uses a `HashTable<usize>` for hash lookups combined with a dense `Vec<DictEntry>`
+/// Uses a `HashTable<usize>` for hash lookups combined with a `Vec` of entries
 /// to preserve insertion order (matching Python 3.7+ behavior). The hash table maps
 /// key hashes to indices in the entries vector. This design provides O(1) lookups
 /// while maintaining insertion order for iteration.
 ///
+/// Like in CPython, removing an entry leaves an empty slot behind instead of shifting the
+/// entries after it, so removals and moving an entry to either end (for `OrderedDict`
+/// and `functools.lru_cache()`) are O(1). The empty slots are dropped once they
+/// outnumber the entries, or before an entry is looked up by its position.
+///
 /// # Reference Counting
 /// When values are added via `set()`, their reference counts are incremented.
 /// When using `from_pairs()`, ownership is transferred without incrementing refcounts
@@ -59,12 +71,37 @@ use crate::{
 pub(crate) struct Dict {
     /// indices mapping from the entry hash to its index.
     indices: HashTable<usize>,
-    /// entries is a dense vec maintaining entry order.
-    entries: Vec<DictEntry>,
+    /// entries in order, with `None` in the slots of removed entries.
+    entries: Vec<Option<DictEntry>>,
+    /// The number of removed entries in `entries`.
+    removed: usize,
+    /// The number of slots at the start of `entries` which are all removed entries.
+    first: usize,
     /// True if any key or value in the dict is a `Value::Ref`. Used to skip iteration
     /// in `collect_child_ids` and `py_dec_ref_ids` when no refs are present.
     /// Only transitions from false to true (never back) since tracking removals would be O(n).
     contains_refs: bool,
+    /// Whether this is a plain dict or one of the dict subclasses of the `collections` module.
+    kind: DictKind,
+}
+
+/// The dict subclasses of the `collections` module, which are stored as a [`Dict`].
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub(crate) enum DictKind {
+    /// A plain `dict`.
+    #[default]
+    Dict,
+    /// A `collections.defaultdict` and its `default_factory`, which may be `None`.
+    ///
+    /// Subscripting a missing key calls the factory through the VM, since it may be a
+    /// Python function, and stores its result under the key.
+    DefaultDict(Value),
+    /// A `collections.Counter`: missing keys count as zero, `update()` adds counts and
+    /// counters support `+`, `-`, `&` and `|`. See [`super::counter`].
+    Counter,
+    /// A `collections.OrderedDict`: equality between ordered dicts is order-sensitive, and
+    /// `move_to_end()` and `popitem(last=False)` reorder or pop from the front.
+    OrderedDict,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -86,7 +123,43 @@ impl Dict {
         Self {
             indices: HashTable::with_capacity(capacity),
             entries: Vec::with_capacity(capacity),
+            removed: 0,
+            first: 0,
             contains_refs: false,
+            kind: DictKind::Dict,
+        }
+    }
+
+    /// Creates a new empty dict of the given kind.
+    #[must_use]
+    pub fn with_kind(kind: DictKind) -> Self {
+        let mut dict = Self::new();
+        dict.set_kind(kind);
+        dict
+    }
+
+    /// Makes this plain dict a dict of the given kind.
+    fn set_kind(&mut self, kind: DictKind) {
+        // A default factory on the heap is a reference held by the dict
+        if matches!(kind, DictKind::DefaultDict(Value::Ref(_))) {
+            self.contains_refs = true;
+        }
+        self.kind = kind;
+    }
+
+    /// Returns whether this is a plain dict or a dict subclass of the `collections` module.
+    #[must_use]
+    pub fn kind(&self) -> &DictKind {
+        &self.kind
+    }
+
+    /// Returns the `default_factory` of a `defaultdict`, or `None` for other dicts and
+    /// defaultdicts without a factory.
+    #[must_use]
+    pub fn default_factory(&self) -> Option<&Value> {
+        match &self.kind {
+            DictKind::DefaultDict(factory) if !matches!(factory, Value::None) => Some(factory),
+            _ => None,
         }
     }
 
@@ -161,26 +234,25 @@ impl Dict {
             // Key exists, replace in place to preserve insertion order.
             // The new duplicate key must be dropped since we keep the existing key.
             // The old value must also be dropped since we're replacing it.
-            let existing_bucket = &mut self.entries[index];
+            let existing_bucket = self.entries[index].as_mut().expect("indexed entries are present");
             let old_value = std::mem::replace(&mut existing_bucket.value, value);
             old_value.drop_with_heap(heap);
             key.drop_with_heap(heap);
         } else {
             // Key doesn't exist, add new pair to indices and entries
-            let index = self.entries.len();
-            self.entries.push(DictEntry { key, value, hash });
-            self.indices
-                .insert_unique(hash, index, |index| self.entries[*index].hash);
+            self.push_entry(DictEntry { key, value, hash });
         }
         Ok(())
     }
 
     fn drop_all_entries(&mut self, heap: &mut Heap<impl ResourceTracker>) {
-        for entry in self.entries.drain(..) {
+        for entry in self.entries.drain(..).flatten() {
             entry.key.drop_with_heap(heap);
             entry.value.drop_with_heap(heap);
         }
         self.indices.clear();
+        self.removed = 0;
+        self.first = 0;
     }
 
     /// Gets a value from the dict by key.
@@ -194,12 +266,17 @@ impl Dict {
         interns: &Interns,
     ) -> RunResult<Option<&Value>> {
         if let Some(index) = self.find_index_hash(key, heap, interns)?.0 {
-            Ok(Some(&self.entries[index].value))
+            Ok(Some(&self.entry(index).value))
         } else {
             Ok(None)
         }
     }
 
+    /// Returns the keys stored with the given hash, for the VM to compare with `__eq__`.
+    pub fn keys_with_hash(&self, hash: u64) -> impl Iterator<Item = &Value> {
+        self.indices.iter_hash(hash).map(|&index| &self.entry(index).key)
+    }
+
     /// Gets a value from the dict by string key name (immutable lookup).
     ///
     /// This is an O(1) lookup that doesn't require mutable heap access.
@@ -213,7 +290,7 @@ impl Dict {
         // Find entry with matching hash and key
         self.indices
             .find(hash, |&idx| {
-                let entry_key = &self.entries[idx].key;
+                let entry_key = &self.entry(idx).key;
                 match entry_key {
                     Value::InternString(id) => interns.get_str(*id) == key_str,
                     Value::Ref(id) => {
@@ -226,7 +303,7 @@ impl Dict {
                     _ => false,
                 }
             })
-            .map(|&idx| &self.entries[idx].value)
+            .map(|&idx| &self.entry(idx).value)
     }
 
     /// Sets a key-value pair in the dict.
@@ -264,7 +341,7 @@ impl Dict {
         let entry = DictEntry { key, value, hash };
         if let Some(index) = opt_index {
             // Key exists, replace in place to preserve insertion order
-            let old_entry = std::mem::replace(&mut self.entries[index], entry);
+            let old_entry = self.entries[index].replace(entry).expect("indexed entries are present");
 
             // Decrement refcount for old key (we're discarding it)
             old_entry.key.drop_with_heap(heap);
@@ -272,10 +349,7 @@ impl Dict {
             Ok(Some(old_entry.value))
         } else {
             // Key doesn't exist, add new pair to indices and entries
-            let index = self.entries.len();
-            self.entries.push(entry);
-            self.indices
-                .insert_unique(hash, index, |index| self.entries[*index].hash);
+            self.push_entry(entry);
             Ok(None)
         }
     }
@@ -295,17 +369,19 @@ impl Dict {
     ) -> RunResult<Option<(Value, Value)>> {
         let hash = key
             .py_hash(heap, interns)
-            .ok_or_else(|| ExcType::type_error_unhashable_dict_key(key.py_type(heap)))?;
+            .ok_or_else(|| unhashable_error(key, ExcType::type_error_unhashable_dict_key, heap, interns))?;
 
         let entry = self.indices.entry(
             hash,
-            |v| key.py_eq(&self.entries[*v].key, heap, interns),
-            |index| self.entries[*index].hash,
+            |v| key.py_eq(&slot(&self.entries, *v).key, heap, interns),
+            |index| slot(&self.entries, *index).hash,
         );
 
         if let Entry::Occupied(occ_entry) = entry {
-            let entry = self.entries.remove(*occ_entry.get());
+            let index = *occ_entry.get();
             occ_entry.remove();
+            let entry = self.take_entry(index);
+            self.compact_if_sparse();
             // Don't decrement refcounts - caller now owns the values
             Ok(Some((entry.key, entry.value)))
         } else {
@@ -313,46 +389,80 @@ impl Dict {
         }
     }
 
-    /// Returns a vector of all keys in the dict with proper reference counting.
+    /// Removes and returns the first key-value pair, or `None` if the dict is empty.
     ///
-    /// Each key's reference count is incremented since the returned vector
-    /// now holds additional references to these values.
-    #[must_use]
-    pub fn keys(&self, heap: &mut Heap<impl ResourceTracker>) -> Vec<Value> {
-        self.entries
-            .iter()
-            .map(|entry| entry.key.clone_with_heap(heap))
-            .collect()
+    /// Like `pop()`, the caller assumes ownership of the removed key and value.
+    pub fn pop_first(&mut self) -> Option<(Value, Value)> {
+        let index = self.first + self.entries[self.first..].iter().position(Option::is_some)?;
+        self.unindex(index);
+        let entry = self.take_entry(index);
+        self.first = index + 1;
+        self.compact_if_sparse();
+        Some((entry.key, entry.value))
     }
 
-    /// Returns a vector of all values in the dict with proper reference counting.
+    /// Removes and returns the last key-value pair, or `None` if the dict is empty.
     ///
-    /// Each value's reference count is incremented since the returned vector
-    /// now holds additional references to these values.
-    #[must_use]
-    pub fn values(&self, heap: &mut Heap<impl ResourceTracker>) -> Vec<Value> {
-        self.entries
-            .iter()
-            .map(|entry| entry.value.clone_with_heap(heap))
-            .collect()
+    /// Like `pop()`, the caller assumes ownership of the removed key and value.
+    pub fn pop_last(&mut self) -> Option<(Value, Value)> {
+        loop {
+            let index = self.entries.len().checked_sub(1)?;
+            if self.entries[index].is_some() {
+                self.unindex(index);
+            } else {
+                self.removed -= 1;
+            }
+            let entry = self.entries.pop().expect("index is in bounds");
+            self.first = self.first.min(self.entries.len());
+            if let Some(entry) = entry {
+                self.compact_if_sparse();
+                return Some((entry.key, entry.value));
+            }
+        }
     }
 
-    /// Returns a vector of all (key, value) pairs in the dict with proper reference counting.
+    /// Moves the entry for `key` to the end of the dict, or to the front if `last` is false.
     ///
-    /// Each key and value's reference count is incremented since the returned vector
-    /// now holds additional references to these values.
-    #[must_use]
-    pub fn items(&self, heap: &mut Heap<impl ResourceTracker>) -> Vec<(Value, Value)> {
-        self.entries
-            .iter()
-            .map(|entry| (entry.key.clone_with_heap(heap), entry.value.clone_with_heap(heap)))
-            .collect()
+    /// Returns whether the key was found, or Err if the key is unhashable.
+    pub fn move_to_end(
+        &mut self,
+        key: &Value,
+        last: bool,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<bool> {
+        let Some(index) = self.find_index_hash(key, heap, interns)?.0 else {
+            return Ok(false);
+        };
+        let end = if last { self.entries.len() - 1 } else { self.first };
+        if index == end {
+            return Ok(true);
+        }
+        self.unindex(index);
+        let entry = self.take_entry(index);
+        if last {
+            self.push_entry(entry);
+        } else {
+            // Leave as many free slots in front as there are entries, so moving entries to
+            // the front stays O(1) amortized
+            if self.first == 0 {
+                self.compact(self.len() + 1);
+            }
+            self.first -= 1;
+            self.removed -= 1;
+            let hash = entry.hash;
+            self.entries[self.first] = Some(entry);
+            self.indices
+                .insert_unique(hash, self.first, |index| slot(&self.entries, *index).hash);
+        }
+        self.compact_if_sparse();
+        Ok(true)
     }
 
     /// Returns the number of key-value pairs in the dict.
     #[must_use]
     pub fn len(&self) -> usize {
-        self.entries.len()
+        self.entries.len() - self.removed
     }
 
     /// Returns true if the dict is empty.
@@ -370,8 +480,55 @@ impl Dict {
     ///
     /// Used for index-based iteration in for loops. Returns a reference to
     /// the key at the given position in insertion order.
-    pub fn key_at(&self, index: usize) -> Option<&Value> {
-        self.entries.get(index).map(|e| &e.key)
+    pub fn key_at(&mut self, index: usize) -> Option<&Value> {
+        self.item_at(index).map(|(key, _)| key)
+    }
+
+    /// Returns the (key, value) pair at the given iteration index, or None if out of bounds.
+    ///
+    /// Used for index-based iteration over dict views. Drops the slots of removed entries
+    /// first if there are any between the entries.
+    pub fn item_at(&mut self, index: usize) -> Option<(&Value, &Value)> {
+        if self.removed > self.first {
+            self.compact(0);
+        }
+        self.entries
+            .get(self.first + index)
+            .map(|e| slot_entry(e.as_ref()))
+            .map(|e| (&e.key, &e.value))
+    }
+
+    /// Creates a dict from the `collections.defaultdict()` constructor call.
+    ///
+    /// The first argument is the `default_factory`, which must be callable or `None`.
+    /// The remaining arguments are handled like those of `dict.update()`.
+    pub fn init_default_dict(
+        heap: &mut Heap<impl ResourceTracker>,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        let (mut pos, kwargs) = args.into_parts();
+        let factory = pos.next().unwrap_or(Value::None);
+        let rest: Vec<Value> = pos.collect();
+        if !matches!(factory, Value::None) && !is_callable(&factory, heap) {
+            factory.drop_with_heap(heap);
+            rest.drop_with_heap(heap);
+            kwargs.drop_with_heap(heap);
+            return Err(ExcType::type_error("first argument must be callable or None"));
+        }
+        let args = ArgValues::ArgsKargs { args: rest, kwargs };
+        allocate_updated(DictKind::DefaultDict(factory), args, heap, interns)
+    }
+
+    /// Creates a dict from the `collections.OrderedDict()` constructor call.
+    ///
+    /// The arguments are handled like those of `dict.update()`.
+    pub fn init_ordered_dict(
+        heap: &mut Heap<impl ResourceTracker>,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        allocate_updated(DictKind::OrderedDict, args, heap, interns)
     }
 
     /// Creates a dict from the `dict()` constructor call.
@@ -435,18 +592,97 @@ impl Dict {
     ) -> RunResult<(Option<usize>, u64)> {
         let hash = key
             .py_hash(heap, interns)
-            .ok_or_else(|| ExcType::type_error_unhashable_dict_key(key.py_type(heap)))?;
+            .ok_or_else(|| unhashable_error(key, ExcType::type_error_unhashable_dict_key, heap, interns))?;
 
         let opt_index = self
             .indices
-            .find(hash, |v| key.py_eq(&self.entries[*v].key, heap, interns))
+            .find(hash, |v| key.py_eq(&self.entry(*v).key, heap, interns))
             .copied();
         Ok((opt_index, hash))
     }
+
+    /// Returns the entry at `index`, which must be in the indices hash table.
+    fn entry(&self, index: usize) -> &DictEntry {
+        slot(&self.entries, index)
+    }
+
+    /// Adds an entry for a key not in the dict at the end.
+    fn push_entry(&mut self, entry: DictEntry) {
+        let index = self.entries.len();
+        let hash = entry.hash;
+        self.entries.push(Some(entry));
+        self.indices
+            .insert_unique(hash, index, |index| slot(&self.entries, *index).hash);
+    }
+
+    /// Removes the entry at `index` from the indices hash table.
+    fn unindex(&mut self, index: usize) {
+        let hash = self.entry(index).hash;
+        if let Ok(entry) = self.indices.find_entry(hash, |&i| i == index) {
+            entry.remove();
+        }
+    }
+
+    /// Takes the entry at `index` out of its slot, which must already be unindexed.
+    fn take_entry(&mut self, index: usize) -> DictEntry {
+        let entry = self.entries[index].take().expect("indexed entries are present");
+        self.removed += 1;
+        entry
+    }
+
+    /// Drops the slots of removed entries once they outnumber the entries, so removing
+    /// entries stays O(1) amortized.
+    fn compact_if_sparse(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            self.entries.clear();
+            self.indices.clear();
+            self.removed = 0;
+            self.first = 0;
+        } else if self.removed - self.first > len || self.first > 2 * len {
+            self.compact(0);
+        }
+    }
+
+    /// Drops the slots of removed entries, leaving `free` empty slots at the start, and
+    /// rebuilds the indices hash table.
+    fn compact(&mut self, free: usize) {
+        let mut entries = Vec::with_capacity(free + self.len());
+        entries.resize_with(free, || None);
+        entries.extend(self.entries.drain(..).filter(Option::is_some));
+        self.entries = entries;
+        self.removed = free;
+        self.first = free;
+        self.rebuild_indices();
+    }
+
+    /// Rebuilds the indices hash table after entries were removed or reordered.
+    fn rebuild_indices(&mut self) {
+        self.indices.clear();
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if let Some(entry) = entry {
+                self.indices
+                    .insert_unique(entry.hash, idx, |&i| slot(&self.entries, i).hash);
+            }
+        }
+    }
+}
+
+/// Returns the entry at `index` of a dict's entries, which must be in its indices hash table.
+///
+/// A free function rather than a method so hash table callbacks can borrow the entries
+/// while the table is borrowed mutably.
+fn slot(entries: &[Option<DictEntry>], index: usize) -> &DictEntry {
+    slot_entry(entries[index].as_ref())
+}
+
+/// Unwraps an entry the indices hash table refers to.
+fn slot_entry(entry: Option<&DictEntry>) -> &DictEntry {
+    entry.expect("indexed entries are present")
 }
 
 /// Iterator over borrowed (key, value) pairs in a dict.
-pub(crate) struct DictIter<'a>(std::slice::Iter<'a, DictEntry>);
+pub(crate) struct DictIter<'a>(std::iter::Flatten<std::slice::Iter<'a, Option<DictEntry>>>);
 
 impl<'a> Iterator for DictIter<'a> {
     type Item = (&'a Value, &'a Value);
@@ -459,22 +695,24 @@ impl<'a> IntoIterator for &'a Dict {
     type Item = (&'a Value, &'a Value);
     type IntoIter = DictIter<'a>;
     fn into_iter(self) -> Self::IntoIter {
-        DictIter(self.entries.iter())
+        DictIter(self.entries.iter().flatten())
     }
 }
 
-/// Iterator over owned (key, value) pairs from a consumed dict.
-pub(crate) struct DictIntoIter(std::vec::IntoIter<DictEntry>);
+/// Iterator over owned (key, value) pairs from a consumed dict, with the number left.
+pub(crate) struct DictIntoIter(std::iter::Flatten<std::vec::IntoIter<Option<DictEntry>>>, usize);
 
 impl Iterator for DictIntoIter {
     type Item = (Value, Value);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next().map(|e| (e.key, e.value))
+        let entry = self.0.next()?;
+        self.1 -= 1;
+        Some((entry.key, entry.value))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.0.size_hint()
+        (self.1, Some(self.1))
     }
 }
 
@@ -484,13 +722,19 @@ impl IntoIterator for Dict {
     type Item = (Value, Value);
     type IntoIter = DictIntoIter;
     fn into_iter(self) -> Self::IntoIter {
-        DictIntoIter(self.entries.into_iter())
+        let len = self.len();
+        DictIntoIter(self.entries.into_iter().flatten(), len)
     }
 }
 
 impl PyTrait for Dict {
     fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
-        Type::Dict
+        match self.kind {
+            DictKind::Dict => Type::Dict,
+            DictKind::DefaultDict(_) => Type::DefaultDict,
+            DictKind::Counter => Type::Counter,
+            DictKind::OrderedDict => Type::OrderedDict,
+        }
     }
 
     fn py_estimate_size(&self) -> usize {
@@ -503,12 +747,24 @@ impl PyTrait for Dict {
     }
 
     fn py_eq(&self, other: &Self, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> bool {
+        match (&self.kind, &other.kind) {
+            (DictKind::Counter, DictKind::Counter) => return counter::counter_eq(self, other, heap, interns),
+            // Ordered dicts only compare in order with each other, not with other dicts
+            (DictKind::OrderedDict, DictKind::OrderedDict) => {
+                return self.len() == other.len()
+                    && self
+                        .iter()
+                        .zip(other)
+                        .all(|(a, b)| a.0.py_eq(b.0, heap, interns) && a.1.py_eq(b.1, heap, interns));
+            }
+            _ => {}
+        }
         if self.len() != other.len() {
             return false;
         }
 
         // Check that all keys in self exist in other with equal values
-        for entry in &self.entries {
+        for entry in self.entries.iter().flatten() {
             match other.get(&entry.key, heap, interns) {
                 Ok(Some(other_v)) => {
                     if !entry.value.py_eq(other_v, heap, interns) {
@@ -526,7 +782,7 @@ impl PyTrait for Dict {
         if !self.contains_refs {
             return;
         }
-        for entry in &mut self.entries {
+        for entry in self.entries.iter_mut().flatten() {
             if let Value::Ref(id) = &entry.key {
                 stack.push(*id);
                 #[cfg(feature = "ref-count-panic")]
@@ -538,6 +794,13 @@ impl PyTrait for Dict {
                 entry.value.dec_ref_forget();
             }
         }
+        if let DictKind::DefaultDict(factory) = &mut self.kind
+            && let Value::Ref(id) = factory
+        {
+            stack.push(*id);
+            #[cfg(feature = "ref-count-panic")]
+            factory.dec_ref_forget();
+        }
     }
 
     fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
@@ -551,22 +814,31 @@ impl PyTrait for Dict {
         heap_ids: &mut AHashSet<HeapId>,
         interns: &Interns,
     ) -> std::fmt::Result {
-        if self.is_empty() {
-            return f.write_str("{}");
-        }
-
-        f.write_char('{')?;
-        let mut first = true;
-        for entry in &self.entries {
-            if !first {
+        match &self.kind {
+            DictKind::Dict => repr_entries_fmt(self.entries.iter().flatten(), f, heap, heap_ids, interns),
+            DictKind::DefaultDict(factory) => {
+                f.write_str("defaultdict(")?;
+                factory.py_repr_fmt(f, heap, heap_ids, interns)?;
                 f.write_str(", ")?;
+                repr_entries_fmt(self.entries.iter().flatten(), f, heap, heap_ids, interns)?;
+                f.write_char(')')
+            }
+            DictKind::Counter if self.is_empty() => f.write_str("Counter()"),
+            DictKind::OrderedDict if self.is_empty() => f.write_str("OrderedDict()"),
+            // Counters show their most common elements first
+            DictKind::Counter => {
+                let mut entries: Vec<&DictEntry> = self.entries.iter().flatten().collect();
+                entries.sort_by(|a, b| counter::compare_counts(&b.value, &a.value));
+                f.write_str("Counter(")?;
+                repr_entries_fmt(entries.into_iter(), f, heap, heap_ids, interns)?;
+                f.write_char(')')
+            }
+            DictKind::OrderedDict => {
+                f.write_str("OrderedDict(")?;
+                repr_entries_fmt(self.entries.iter().flatten(), f, heap, heap_ids, interns)?;
+                f.write_char(')')
             }
-            first = false;
-            entry.key.py_repr_fmt(f, heap, heap_ids, interns)?;
-            f.write_str(": ")?;
-            entry.value.py_repr_fmt(f, heap, heap_ids, interns)?;
         }
-        f.write_char('}')
     }
 
     fn py_getitem(&self, key: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
@@ -579,6 +851,8 @@ impl PyTrait for Dict {
                 }
                 Ok(value)
             }
+            // Missing keys of counters count as zero, without being added
+            None if matches!(self.kind, DictKind::Counter) => Ok(Value::Int(0)),
             None => Err(ExcType::key_error(key, heap, interns)),
         }
     }
@@ -597,6 +871,22 @@ impl PyTrait for Dict {
         Ok(())
     }
 
+    fn py_delitem(&mut self, key: Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<()> {
+        let result = match self.pop(&key, heap, interns) {
+            Ok(Some((old_key, old_value))) => {
+                old_key.drop_with_heap(heap);
+                old_value.drop_with_heap(heap);
+                Ok(())
+            }
+            // Deleting a missing key of a counter does nothing
+            Ok(None) if matches!(self.kind, DictKind::Counter) => Ok(()),
+            Ok(None) => Err(ExcType::key_error(&key, heap, interns)),
+            Err(e) => Err(e),
+        };
+        key.drop_with_heap(heap);
+        result
+    }
+
     fn py_call_attr(
         &mut self,
         heap: &mut Heap<impl ResourceTracker>,
@@ -605,9 +895,38 @@ impl PyTrait for Dict {
         interns: &Interns,
     ) -> RunResult<Value> {
         let Some(method) = attr.static_string() else {
-            return Err(ExcType::attribute_error(Type::Dict, attr.as_str(interns)));
+            args.drop_with_heap(heap);
+            return Err(ExcType::attribute_error(self.py_type(heap), attr.as_str(interns)));
         };
 
+        match (&self.kind, method) {
+            (
+                DictKind::Counter,
+                StaticStrings::MostCommon
+                | StaticStrings::Elements
+                | StaticStrings::Update
+                | StaticStrings::Subtract
+                | StaticStrings::Total,
+            ) => return counter::call_counter_method(self, method, args, heap, interns),
+            (DictKind::OrderedDict, StaticStrings::MoveToEnd) => {
+                return ordered_dict_move_to_end(self, args, heap, interns);
+            }
+            (DictKind::OrderedDict, StaticStrings::Popitem) => {
+                let [last] = bind_args("OrderedDict.popitem", args, ["last"], 0, heap, interns)?;
+                let last = last.is_none_or(|last| {
+                    let last_bool = last.py_bool(heap, interns);
+                    last.drop_with_heap(heap);
+                    last_bool
+                });
+                return if last {
+                    dict_popitem(self, heap)
+                } else {
+                    ordered_dict_popitem_first(self, heap)
+                };
+            }
+            _ => {}
+        }
+
         match method {
             StaticStrings::Get => {
                 // dict.get() accepts 1 or 2 arguments
@@ -632,29 +951,12 @@ impl PyTrait for Dict {
                 default.drop_with_heap(heap);
                 Ok(value)
             }
-            StaticStrings::Keys => {
-                args.check_zero_args("dict.keys", heap)?;
-                let keys = self.keys(heap);
-                let list_id = heap.allocate(HeapData::List(List::new(keys)))?;
-                Ok(Value::Ref(list_id))
-            }
-            StaticStrings::Values => {
-                args.check_zero_args("dict.values", heap)?;
-                let values = self.values(heap);
-                let list_id = heap.allocate(HeapData::List(List::new(values)))?;
-                Ok(Value::Ref(list_id))
-            }
-            StaticStrings::Items => {
-                args.check_zero_args("dict.items", heap)?;
-                // Return list of tuples
-                let items = self.items(heap);
-                let mut tuples: Vec<Value> = Vec::with_capacity(items.len());
-                for (k, v) in items {
-                    let tuple_val = allocate_tuple(smallvec![k, v], heap)?;
-                    tuples.push(tuple_val);
-                }
-                let list_id = heap.allocate(HeapData::List(List::new(tuples)))?;
-                Ok(Value::Ref(list_id))
+            // Views hold a reference to the dict, which only the VM knows
+            StaticStrings::Keys | StaticStrings::Values | StaticStrings::Items => {
+                args.drop_with_heap(heap);
+                Err(RunError::internal(
+                    "dict.keys(), dict.values() and dict.items() must be called through the VM",
+                ))
             }
             StaticStrings::Pop => {
                 // dict.pop() accepts 1 or 2 arguments (key, optional default)
@@ -708,7 +1010,87 @@ impl PyTrait for Dict {
             }
             // fromkeys is a classmethod but also accessible on instances
             StaticStrings::Fromkeys => dict_fromkeys(args, heap, interns),
-            _ => Err(ExcType::attribute_error(Type::Dict, attr.as_str(interns))),
+            _ => {
+                args.drop_with_heap(heap);
+                Err(ExcType::attribute_error(self.py_type(heap), attr.as_str(interns)))
+            }
+        }
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        match &self.kind {
+            DictKind::DefaultDict(factory) if attr_id == StaticStrings::DefaultFactory => {
+                Ok(Some(AttrCallResult::Value(factory.clone_with_heap(heap))))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Writes dict entries as `{key: value, ...}`, the repr of a plain dict.
+fn repr_entries_fmt<'a>(
+    entries: impl Iterator<Item = &'a DictEntry>,
+    f: &mut impl Write,
+    heap: &Heap<impl ResourceTracker>,
+    heap_ids: &mut AHashSet<HeapId>,
+    interns: &Interns,
+) -> std::fmt::Result {
+    f.write_char('{')?;
+    let mut first = true;
+    for entry in entries {
+        if !first {
+            f.write_str(", ")?;
+        }
+        first = false;
+        entry.key.py_repr_fmt(f, heap, heap_ids, interns)?;
+        f.write_str(": ")?;
+        entry.value.py_repr_fmt(f, heap, heap_ids, interns)?;
+    }
+    f.write_char('}')
+}
+
+impl DictKind {
+    /// Clones the kind, incrementing the refcount of a `defaultdict`'s factory.
+    fn clone_with_heap(&self, heap: &mut Heap<impl ResourceTracker>) -> Self {
+        match self {
+            Self::Dict => Self::Dict,
+            Self::DefaultDict(factory) => Self::DefaultDict(factory.clone_with_heap(heap)),
+            Self::Counter => Self::Counter,
+            Self::OrderedDict => Self::OrderedDict,
+        }
+    }
+}
+
+/// Allocates an empty dict of the given kind and fills it like `dict.update(*args, **kwargs)`.
+fn allocate_updated(
+    kind: DictKind,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let heap_id = match heap.allocate(HeapData::Dict(Dict::with_kind(kind))) {
+        Ok(id) => id,
+        Err(e) => {
+            args.drop_with_heap(heap);
+            return Err(e.into());
+        }
+    };
+    let result = heap.with_entry_mut(heap_id, |heap, data| {
+        let HeapData::Dict(dict) = data else {
+            unreachable!("just allocated a dict")
+        };
+        dict_update(dict, args, heap, interns)
+    });
+    match result {
+        Ok(_) => Ok(Value::Ref(heap_id)),
+        Err(e) => {
+            Value::Ref(heap_id).drop_with_heap(heap);
+            Err(e)
         }
     }
 }
@@ -717,11 +1099,7 @@ impl PyTrait for Dict {
 ///
 /// Removes all items from the dict.
 fn dict_clear(dict: &mut Dict, heap: &mut Heap<impl ResourceTracker>) {
-    for entry in dict.entries.drain(..) {
-        entry.key.drop_with_heap(heap);
-        entry.value.drop_with_heap(heap);
-    }
-    dict.indices.clear();
+    dict.drop_all_entries(heap);
     // Note: contains_refs stays true even if all refs removed, per conservative GC strategy
 }
 
@@ -735,7 +1113,9 @@ fn dict_copy(dict: &Dict, heap: &mut Heap<impl ResourceTracker>, interns: &Inter
         .map(|(k, v)| (k.clone_with_heap(heap), v.clone_with_heap(heap)))
         .collect();
 
-    let new_dict = Dict::from_pairs(pairs, heap, interns)?;
+    let mut new_dict = Dict::from_pairs(pairs, heap, interns)?;
+    // Copies of dict subclasses keep their type, and a defaultdict its factory
+    new_dict.set_kind(dict.kind.clone_with_heap(heap));
     let heap_id = heap.allocate(HeapData::Dict(new_dict))?;
     Ok(Value::Ref(heap_id))
 }
@@ -1009,45 +1389,84 @@ fn dict_setdefault(
 /// Removes and returns the last inserted key-value pair as a tuple.
 /// Raises KeyError if the dict is empty.
 fn dict_popitem(dict: &mut Dict, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
-    if dict.is_empty() {
+    // Remove the last entry (LIFO order)
+    let Some((key, value)) = dict.pop_last() else {
         return Err(ExcType::key_error_popitem_empty_dict());
-    }
+    };
 
-    // Remove the last entry (LIFO order)
-    let entry = dict.entries.pop().expect("dict is not empty");
+    // Create tuple (key, value)
+    Ok(allocate_tuple(smallvec![key, value], heap)?)
+}
 
-    // Remove from indices - need to find the entry with this index
-    // Since we removed the last entry, we need to clear and rebuild indices
-    // (This is simpler than trying to find and remove the specific hash entry)
-    // TODO: This O(n) rebuild could be optimized by finding and removing the
-    // specific hash entry directly from the hashbrown table.
-    dict.indices.clear();
-    for (idx, e) in dict.entries.iter().enumerate() {
-        dict.indices.insert_unique(e.hash, idx, |&i| dict.entries[i].hash);
-    }
+/// Implements `OrderedDict.popitem(last=False)`.
+///
+/// Removes and returns the first inserted key-value pair as a tuple.
+/// Raises KeyError if the dict is empty.
+fn ordered_dict_popitem_first(dict: &mut Dict, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let Some((key, value)) = dict.pop_first() else {
+        return Err(ExcType::key_error_popitem_empty_dict());
+    };
+    Ok(allocate_tuple(smallvec![key, value], heap)?)
+}
 
-    // Create tuple (key, value)
-    Ok(allocate_tuple(smallvec![entry.key, entry.value], heap)?)
+/// Implements `OrderedDict.move_to_end(key, last=True)`.
+///
+/// Moves an existing key to the end of the dict, or to the front if `last` is false.
+/// Raises KeyError if the key is missing.
+fn ordered_dict_move_to_end(
+    dict: &mut Dict,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let [key, last] = bind_args("move_to_end", args, ["key", "last"], 1, heap, interns)?;
+    let key = key.expect("key is required");
+    defer_drop!(key, heap);
+    let last = match last {
+        Some(last) => {
+            let last_bool = last.py_bool(heap, interns);
+            last.drop_with_heap(heap);
+            last_bool
+        }
+        None => true,
+    };
+
+    if !dict.move_to_end(key, last, heap, interns)? {
+        return Err(ExcType::key_error(key, heap, interns));
+    }
+    Ok(Value::None)
 }
 
 // Custom serde implementation for Dict.
-// Serializes entries and contains_refs; rebuilds the indices hash table on deserialize.
+// Serializes entries (without the slots of removed entries), contains_refs and kind;
+// rebuilds the indices hash table on deserialize.
 impl serde::Serialize for Dict {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         use serde::ser::SerializeStruct;
-        let mut state = serializer.serialize_struct("Dict", 2)?;
-        state.serialize_field("entries", &self.entries)?;
+        let mut state = serializer.serialize_struct("Dict", 3)?;
+        state.serialize_field("entries", &SerializeEntries(&self.entries))?;
         state.serialize_field("contains_refs", &self.contains_refs)?;
+        state.serialize_field("kind", &self.kind)?;
         state.end()
     }
 }
 
+/// Serializes the entries of a dict as a sequence, skipping the slots of removed entries.
+struct SerializeEntries<'a>(&'a [Option<DictEntry>]);
+
+impl serde::Serialize for SerializeEntries<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().flatten())
+    }
+}
+
 impl<'de> serde::Deserialize<'de> for Dict {
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         #[derive(serde::Deserialize)]
         struct DictFields {
             entries: Vec<DictEntry>,
             contains_refs: bool,
+            kind: DictKind,
         }
         let fields = DictFields::deserialize(deserializer)?;
         // Rebuild the indices hash table from the entries
@@ -1057,8 +1476,11 @@ impl<'de> serde::Deserialize<'de> for Dict {
         }
         Ok(Self {
             indices,
-            entries: fields.entries,
+            entries: fields.entries.into_iter().map(Some).collect(),
+            removed: 0,
+            first: 0,
             contains_refs: fields.contains_refs,
+            kind: fields.kind,
         })
     }
 }
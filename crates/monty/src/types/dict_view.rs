@@ -0,0 +1,317 @@
+//! Python dict view types: `dict_keys`, `dict_values` and `dict_items`.
+//!
+//! Views are returned by `dict.keys()`, `dict.values()` and `dict.items()`. They hold a
+//! reference to the dict rather than a copy of its contents, so they always reflect the
+//! dict's current state and are iterated lazily by `MontyIter`.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+use smallvec::smallvec;
+
+use crate::{
+    args::ArgValues,
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    resource::ResourceTracker,
+    types::{PyTrait, Type, allocate_tuple},
+    value::Value,
+};
+
+/// Which part of each dict entry a view exposes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) enum DictViewKind {
+    Keys,
+    Values,
+    Items,
+}
+
+impl DictViewKind {
+    /// Returns the view created by the dict method `name_id`, if it is `keys`, `values` or `items`.
+    #[must_use]
+    pub fn from_method(name_id: StringId) -> Option<Self> {
+        match StaticStrings::from_string_id(name_id)? {
+            StaticStrings::Keys => Some(Self::Keys),
+            StaticStrings::Values => Some(Self::Values),
+            StaticStrings::Items => Some(Self::Items),
+            _ => None,
+        }
+    }
+
+    /// Returns the qualified name of the dict method creating this view, for error messages.
+    fn method_name(self) -> &'static str {
+        match self {
+            Self::Keys => "dict.keys",
+            Self::Values => "dict.values",
+            Self::Items => "dict.items",
+        }
+    }
+}
+
+/// A live view over a dict's keys, values or `(key, value)` pairs.
+///
+/// Keys and items views are set-like: they support `&`, `|`, `^` and `-` with any
+/// iterable, producing a new `set`.
+///
+/// # Reference Counting
+/// Owns a reference to the dict, keeping it alive for as long as the view exists.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct DictView {
+    /// The dict this view reads from.
+    dict_id: HeapId,
+    kind: DictViewKind,
+}
+
+impl DictView {
+    /// Creates a new view, taking ownership of a reference to the dict.
+    #[must_use]
+    pub fn new(dict_id: HeapId, kind: DictViewKind) -> Self {
+        Self { dict_id, kind }
+    }
+
+    /// Implements `dict.keys()`, `dict.values()` and `dict.items()`.
+    ///
+    /// Takes ownership of `dict`, whose reference is transferred to the new view.
+    pub fn call(
+        dict: Value,
+        kind: DictViewKind,
+        args: ArgValues,
+        heap: &mut Heap<impl ResourceTracker>,
+    ) -> RunResult<Value> {
+        if let Err(err) = args.check_zero_args(kind.method_name(), heap) {
+            dict.drop_with_heap(heap);
+            return Err(err);
+        }
+        let Value::Ref(dict_id) = dict else {
+            unreachable!("dict view created from a non-dict")
+        };
+        #[cfg(feature = "ref-count-panic")]
+        {
+            let mut dict = dict;
+            dict.dec_ref_forget();
+        }
+        let view_id = heap.allocate(HeapData::DictView(Self::new(dict_id, kind)))?;
+        Ok(Value::Ref(view_id))
+    }
+
+    /// Returns the dict this view reads from.
+    #[must_use]
+    pub fn dict_id(&self) -> HeapId {
+        self.dict_id
+    }
+
+    /// Returns which part of each entry this view exposes.
+    #[must_use]
+    pub fn kind(&self) -> DictViewKind {
+        self.kind
+    }
+
+    /// Returns the current number of entries in the underlying dict.
+    #[must_use]
+    pub fn len(&self, heap: &Heap<impl ResourceTracker>) -> usize {
+        let HeapData::Dict(dict) = heap.get(self.dict_id) else {
+            panic!("DictView: expected Dict on heap");
+        };
+        dict.len()
+    }
+
+    /// Implements the `in` operator.
+    ///
+    /// Keys are looked up by hash, items by looking up the key and comparing the value,
+    /// and values by a linear search.
+    pub fn contains(&self, item: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<bool> {
+        match self.kind {
+            DictViewKind::Keys => heap.with_entry_mut(self.dict_id, |heap, data| {
+                let HeapData::Dict(dict) = data else {
+                    panic!("DictView: expected Dict on heap");
+                };
+                dict.get(item, heap, interns).map(|v| v.is_some())
+            }),
+            DictViewKind::Values => heap.with_entry_mut(self.dict_id, |heap, data| {
+                let HeapData::Dict(dict) = data else {
+                    panic!("DictView: expected Dict on heap");
+                };
+                Ok(dict.iter().any(|(_, v)| item.py_eq(v, heap, interns)))
+            }),
+            DictViewKind::Items => {
+                // Only 2-tuples can be items; clone them so the tuple may be borrowed freely
+                let pair = match item {
+                    Value::Ref(id) => match heap.get(*id) {
+                        HeapData::Tuple(tuple) if tuple.as_vec().len() == 2 => {
+                            Some((tuple.as_vec()[0].copy_for_extend(), tuple.as_vec()[1].copy_for_extend()))
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                let Some((key, value)) = pair else {
+                    return Ok(false);
+                };
+                for v in [&key, &value] {
+                    if let Value::Ref(id) = v {
+                        heap.inc_ref(*id);
+                    }
+                }
+                let result = heap.with_entry_mut(self.dict_id, |heap, data| {
+                    let HeapData::Dict(dict) = data else {
+                        panic!("DictView: expected Dict on heap");
+                    };
+                    Ok(dict
+                        .get(&key, heap, interns)?
+                        .is_some_and(|v| value.py_eq(v, heap, interns)))
+                });
+                key.drop_with_heap(heap);
+                value.drop_with_heap(heap);
+                result
+            }
+        }
+    }
+}
+
+impl DictView {
+    /// Whether this is a keys or items view, which are set-like.
+    #[must_use]
+    pub fn is_set_like(&self) -> bool {
+        self.kind != DictViewKind::Values
+    }
+
+    /// Implements `==` between a keys or items view and `other`, which is equal if it's a
+    /// set, a frozenset or another set-like view with the same elements.
+    ///
+    /// Elements which can't be looked up in `other`, like items with unhashable values,
+    /// make the comparison false where CPython raises `TypeError`, as `==` can't fail here.
+    pub fn set_like_eq(&self, other: &HeapData, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> bool {
+        let other_len = match other {
+            HeapData::Set(set) => set.len(),
+            HeapData::FrozenSet(set) => set.len(),
+            HeapData::DictView(view) if view.is_set_like() => view.len(heap),
+            _ => return false,
+        };
+        let len = self.len(heap);
+        if len != other_len {
+            return false;
+        }
+        // elements of a view are distinct, so with equal lengths containment is equality
+        for index in 0..len {
+            let Ok(item) = view_item_at(heap, self.dict_id, self.kind, index, len) else {
+                return false;
+            };
+            let found = match other {
+                HeapData::Set(set) => set.contains(&item, heap, interns),
+                HeapData::FrozenSet(set) => set.contains(&item, heap, interns),
+                HeapData::DictView(view) => view.contains(&item, heap, interns),
+                _ => unreachable!("other is set-like"),
+            };
+            item.drop_with_heap(heap);
+            if !matches!(found, Ok(true)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Returns whether `value` is a keys or items view, which support set operators.
+#[must_use]
+pub(crate) fn is_set_like_view(value: &Value, heap: &Heap<impl ResourceTracker>) -> bool {
+    matches!(value, Value::Ref(id) if matches!(heap.get(*id), HeapData::DictView(view) if view.is_set_like()))
+}
+
+/// Returns the item a view yields for the dict entry at `index`.
+///
+/// `expected_len` is the dict's length when iteration started; a different length
+/// raises `RuntimeError`, as in CPython. Items views allocate a new `(key, value)` tuple.
+pub(crate) fn view_item_at(
+    heap: &mut Heap<impl ResourceTracker>,
+    dict_id: HeapId,
+    kind: DictViewKind,
+    index: usize,
+    expected_len: usize,
+) -> RunResult<Value> {
+    let HeapData::Dict(dict) = heap.get_mut(dict_id) else {
+        panic!("view_item_at: expected Dict on heap");
+    };
+    if dict.len() != expected_len {
+        return Err(ExcType::runtime_error_dict_changed_size());
+    }
+    let (key, value) = dict.item_at(index).expect("index should be valid");
+    if kind != DictViewKind::Items {
+        let item = if kind == DictViewKind::Keys { key } else { value };
+        return Ok(clone_copied(item.copy_for_extend(), heap));
+    }
+    let (key, value) = (key.copy_for_extend(), value.copy_for_extend());
+    let key = clone_copied(key, heap);
+    let value = clone_copied(value, heap);
+    Ok(allocate_tuple(smallvec![key, value], heap)?)
+}
+
+/// Increments the reference count of a value copied via `copy_for_extend()`.
+fn clone_copied(value: Value, heap: &mut Heap<impl ResourceTracker>) -> Value {
+    if let Value::Ref(id) = &value {
+        heap.inc_ref(*id);
+    }
+    value
+}
+
+impl PyTrait for DictView {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        match self.kind {
+            DictViewKind::Keys => Type::DictKeys,
+            DictViewKind::Values => Type::DictValues,
+            DictViewKind::Items => Type::DictItems,
+        }
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn py_len(&self, heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        Some(self.len(heap))
+    }
+
+    fn py_eq(&self, _other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        false
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        stack.push(self.dict_id);
+    }
+
+    fn py_bool(&self, heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self.len(heap) > 0
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        heap: &Heap<impl ResourceTracker>,
+        heap_ids: &mut AHashSet<HeapId>,
+        interns: &Interns,
+    ) -> std::fmt::Result {
+        let HeapData::Dict(dict) = heap.get(self.dict_id) else {
+            panic!("DictView: expected Dict on heap");
+        };
+        write!(f, "{}([", self.py_type(heap))?;
+        let mut first = true;
+        for (key, value) in dict {
+            if !first {
+                f.write_str(", ")?;
+            }
+            first = false;
+            match self.kind {
+                DictViewKind::Keys => key.py_repr_fmt(f, heap, heap_ids, interns)?,
+                DictViewKind::Values => value.py_repr_fmt(f, heap, heap_ids, interns)?,
+                DictViewKind::Items => {
+                    f.write_char('(')?;
+                    key.py_repr_fmt(f, heap, heap_ids, interns)?;
+                    f.write_str(", ")?;
+                    value.py_repr_fmt(f, heap, heap_ids, interns)?;
+                    f.write_char(')')?;
+                }
+            }
+        }
+        f.write_str("])")
+    }
+}
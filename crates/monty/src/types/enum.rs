@@ -0,0 +1,737 @@
+//! Enumerations, classes deriving from `enum.Enum`, `enum.IntEnum` or `enum.StrEnum`.
+//!
+//! An enum class is a [`ClassObject`] carrying an [`EnumInfo`], and its members are
+//! [`Instance`]s of the class with `_name_` and `_value_` attributes. `BuildClass` turns
+//! the values assigned in the class body into members with [`create_members`], replacing
+//! each class attribute with its member. A name whose value equals an earlier member's
+//! value becomes an alias, referring to the earlier member.
+//!
+//! Members of `IntEnum` and `StrEnum` classes stand in for their values: they compare,
+//! hash, format and take part in arithmetic like them. [`mixin_value`] returns the value
+//! for those cases. In the VM, members compare through the special methods their classes
+//! inherit from `int` and `str` ([`mixin_dunder`]); the heap only compares them by value
+//! once the builtin comparisons have failed, for dict and set lookups.
+
+use std::{cmp::Ordering, fmt::Write};
+
+use ahash::AHashSet;
+use smallvec::SmallVec;
+
+use super::{
+    ClassObject, Dict, Instance, PyTrait, Type, allocate_tuple,
+    class::{self, is_method_function},
+    str::allocate_string,
+};
+use crate::{
+    args::ArgValues,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    resource::ResourceTracker,
+    value::{Marker, Value},
+};
+
+/// The kind of an enum class, decided by the `enum` module class it derives from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) enum EnumKind {
+    /// `enum.Enum`: members only equal themselves.
+    Enum,
+    /// `enum.IntEnum`: members are ints.
+    IntEnum,
+    /// `enum.StrEnum`: members are strings.
+    StrEnum,
+}
+
+impl EnumKind {
+    /// Returns the kind of enum created by subclassing the builtin type `t`, if any.
+    #[must_use]
+    pub fn from_type(t: Type) -> Option<Self> {
+        match t {
+            Type::Enum => Some(Self::Enum),
+            Type::IntEnum => Some(Self::IntEnum),
+            Type::StrEnum => Some(Self::StrEnum),
+            _ => None,
+        }
+    }
+
+    /// Returns the `enum` module class this kind of enum derives from.
+    #[must_use]
+    pub fn base_type(self) -> Type {
+        match self {
+            Self::Enum => Type::Enum,
+            Self::IntEnum => Type::IntEnum,
+            Self::StrEnum => Type::StrEnum,
+        }
+    }
+}
+
+/// The enum-specific state of an enum class.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct EnumInfo {
+    /// Which `enum` module class the enum derives from.
+    kind: EnumKind,
+    /// Names of the members in definition order, excluding aliases.
+    members: Vec<StringId>,
+}
+
+impl EnumInfo {
+    /// Creates the state of an enum class which doesn't have members yet.
+    #[must_use]
+    pub fn new(kind: EnumKind) -> Self {
+        Self {
+            kind,
+            members: Vec::new(),
+        }
+    }
+
+    /// Returns the kind of the enum.
+    #[must_use]
+    pub fn kind(&self) -> EnumKind {
+        self.kind
+    }
+
+    /// Returns the names of the members in definition order, excluding aliases.
+    #[must_use]
+    pub fn members(&self) -> &[StringId] {
+        &self.members
+    }
+
+    /// Records the member names, once the members have been created.
+    pub fn set_members(&mut self, members: Vec<StringId>) {
+        self.members = members;
+    }
+}
+
+/// Returns the kind of enum `class_id` is, or `None` if it isn't an enum class.
+#[must_use]
+pub(crate) fn enum_kind(class_id: HeapId, heap: &Heap<impl ResourceTracker>) -> Option<EnumKind> {
+    match heap.get(class_id) {
+        HeapData::ClassObject(cls) => cls.enum_info().map(EnumInfo::kind),
+        _ => None,
+    }
+}
+
+/// Returns the `_name_` or `_value_` attribute of an enum member.
+fn member_attr<'h>(
+    inst: &'h Instance,
+    name: StaticStrings,
+    heap: &'h Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Option<&'h Value> {
+    inst.attrs().get_by_str(interns.get_str(name.into()), heap, interns)
+}
+
+/// Returns the value an `IntEnum` or `StrEnum` member stands in for, or `None` if
+/// `value` isn't such a member.
+#[must_use]
+pub(crate) fn mixin_value<'h>(
+    value: &Value,
+    heap: &'h Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Option<&'h Value> {
+    let Value::Ref(id) = value else {
+        return None;
+    };
+    let HeapData::Instance(inst) = heap.get(*id) else {
+        return None;
+    };
+    instance_mixin_value(inst, heap, interns)
+}
+
+/// Like [`mixin_value`], for an instance which has already been looked up.
+#[must_use]
+pub(crate) fn instance_mixin_value<'h>(
+    inst: &'h Instance,
+    heap: &'h Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Option<&'h Value> {
+    match enum_kind(inst.class_id(), heap)? {
+        EnumKind::Enum => None,
+        EnumKind::IntEnum | EnumKind::StrEnum => member_attr(inst, StaticStrings::SunderValue, heap, interns),
+    }
+}
+
+/// Like [`mixin_value`], but returns an owned value.
+pub(crate) fn owned_mixin_value(
+    value: &Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Option<Value> {
+    let inner = mixin_value(value, heap, interns)?.copy_for_extend();
+    if let Value::Ref(id) = &inner {
+        heap.inc_ref(*id);
+    }
+    Some(inner)
+}
+
+/// Returns owned copies of both operands with `IntEnum` and `StrEnum` members replaced
+/// by their values, or `None` if neither operand is such a member.
+///
+/// Operators retry with these operands when they don't support the original ones.
+pub(crate) fn mixin_operands(
+    lhs: &Value,
+    rhs: &Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Option<(Value, Value)> {
+    let lhs_inner = owned_mixin_value(lhs, heap, interns);
+    let rhs_inner = owned_mixin_value(rhs, heap, interns);
+    if lhs_inner.is_none() && rhs_inner.is_none() {
+        return None;
+    }
+    let lhs = lhs_inner.unwrap_or_else(|| lhs.clone_with_heap(heap));
+    let rhs = rhs_inner.unwrap_or_else(|| rhs.clone_with_heap(heap));
+    Some((lhs, rhs))
+}
+
+/// Runs the comparison special method `name` (`__eq__`, `__lt__`, ...) which `IntEnum` and
+/// `StrEnum` classes inherit from `int` and `str`, comparing the value of the member `value`
+/// with `other`, or with its value if `other` is a member too.
+///
+/// Returns `None` if `value` isn't such a member, if `name` isn't a comparison method, or
+/// if the values can't be ordered (where the method would return `NotImplemented`).
+pub(crate) fn mixin_dunder(
+    value: &Value,
+    name: StaticStrings,
+    other: &Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Option<Value> {
+    // `None` for `__eq__` and `__ne__`, which don't order the values
+    let check: Option<fn(Ordering) -> bool> = match name {
+        StaticStrings::DunderEq | StaticStrings::DunderNe => None,
+        StaticStrings::DunderLt => Some(Ordering::is_lt),
+        StaticStrings::DunderLe => Some(Ordering::is_le),
+        StaticStrings::DunderGt => Some(Ordering::is_gt),
+        StaticStrings::DunderGe => Some(Ordering::is_ge),
+        _ => return None,
+    };
+    mixin_value(value, heap, interns)?;
+    let (lhs, rhs) = mixin_operands(value, other, heap, interns).expect("value is a member");
+    let result = match check {
+        Some(check) => lhs.py_cmp(&rhs, heap, interns).map(check),
+        None => Some(lhs.py_eq(&rhs, heap, interns) == matches!(name, StaticStrings::DunderEq)),
+    };
+    lhs.drop_with_heap(heap);
+    rhs.drop_with_heap(heap);
+    result.map(Value::Bool)
+}
+
+/// Returns the value the hash of an enum member is computed from: the value of
+/// `IntEnum` and `StrEnum` members, so they hash like it, and the name of other members.
+///
+/// Returns `None` if `id` isn't an enum member. The returned value is owned.
+pub(crate) fn member_hash_key(id: HeapId, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Option<Value> {
+    let HeapData::Instance(inst) = heap.get(id) else {
+        return None;
+    };
+    let attr = match enum_kind(inst.class_id(), heap)? {
+        EnumKind::Enum => StaticStrings::SunderName,
+        EnumKind::IntEnum | EnumKind::StrEnum => StaticStrings::SunderValue,
+    };
+    let key = member_attr(inst, attr, heap, interns)?.copy_for_extend();
+    if let Value::Ref(id) = &key {
+        heap.inc_ref(*id);
+    }
+    Some(key)
+}
+
+/// Returns whether an instance is an enum member.
+///
+/// Calling an enum class looks up a member rather than creating an instance, so every
+/// instance of an enum class is a member.
+#[must_use]
+pub(crate) fn is_member(inst: &Instance, heap: &Heap<impl ResourceTracker>) -> bool {
+    enum_kind(inst.class_id(), heap).is_some()
+}
+
+/// Writes the repr of an enum member, e.g. `<Color.RED: 1>`.
+pub(crate) fn member_repr_fmt(
+    inst: &Instance,
+    f: &mut impl Write,
+    heap: &Heap<impl ResourceTracker>,
+    heap_ids: &mut AHashSet<HeapId>,
+    interns: &Interns,
+) -> std::fmt::Result {
+    write!(f, "<{}.", interns.get_str(class::class_name(inst.class_id(), heap)))?;
+    if let Some(name) = member_attr(inst, StaticStrings::SunderName, heap, interns) {
+        f.write_str(&name.py_str(heap, interns))?;
+    }
+    f.write_str(": ")?;
+    if let Some(value) = member_attr(inst, StaticStrings::SunderValue, heap, interns) {
+        value.py_repr_fmt(f, heap, heap_ids, interns)?;
+    }
+    f.write_char('>')
+}
+
+/// Returns the `str()` of an enum member: `Color.RED` for `Enum` members, and the
+/// string of the value for `IntEnum` and `StrEnum` members.
+///
+/// Returns `None` if `inst` isn't an enum member.
+#[must_use]
+pub(crate) fn member_str(inst: &Instance, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> Option<String> {
+    let kind = enum_kind(inst.class_id(), heap)?;
+    if kind != EnumKind::Enum {
+        return Some(
+            instance_mixin_value(inst, heap, interns)?
+                .py_str(heap, interns)
+                .into_owned(),
+        );
+    }
+    let name = member_attr(inst, StaticStrings::SunderName, heap, interns)?;
+    let class_name = interns.get_str(class::class_name(inst.class_id(), heap));
+    Some(format!("{class_name}.{}", name.py_str(heap, interns)))
+}
+
+/// Returns the `name` and `value` attributes of enum members, which are read from
+/// `_name_` and `_value_`.
+///
+/// Returns `None` for other attributes, or if `inst` isn't an enum member.
+#[must_use]
+pub(crate) fn member_property<'h>(
+    inst: &'h Instance,
+    name_id: StringId,
+    heap: &'h Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Option<&'h Value> {
+    let attr = if name_id == StaticStrings::Name {
+        StaticStrings::SunderName
+    } else if name_id == StaticStrings::Value {
+        StaticStrings::SunderValue
+    } else {
+        return None;
+    };
+    enum_kind(inst.class_id(), heap)?;
+    member_attr(inst, attr, heap, interns)
+}
+
+/// Returns the `_name_` and `_value_` attributes of an enum member.
+///
+/// Returns `None` if `inst` isn't an enum member.
+#[must_use]
+pub(crate) fn member_name_value<'h>(
+    inst: &'h Instance,
+    heap: &'h Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Option<(&'h Value, &'h Value)> {
+    enum_kind(inst.class_id(), heap)?;
+    let name = member_attr(inst, StaticStrings::SunderName, heap, interns)?;
+    let value = member_attr(inst, StaticStrings::SunderValue, heap, interns)?;
+    Some((name, value))
+}
+
+/// Returns the members of an enum class in definition order, excluding aliases.
+///
+/// The returned `HeapId`s are borrowed.
+fn member_ids(cls: &ClassObject, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> Vec<HeapId> {
+    let Some(info) = cls.enum_info() else {
+        return Vec::new();
+    };
+    info.members()
+        .iter()
+        .filter_map(
+            |name_id| match cls.attrs().get_by_str(interns.get_str(*name_id), heap, interns) {
+                Some(Value::Ref(id)) => Some(*id),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+/// Returns the members of an enum class with their values, in definition order.
+///
+/// The values are owned (their refcounts have been incremented), the `HeapId`s are borrowed.
+fn member_values(cls: &ClassObject, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Vec<(HeapId, Value)> {
+    let members: Vec<(HeapId, Value)> = member_ids(cls, heap, interns)
+        .into_iter()
+        .filter_map(|id| match heap.get(id) {
+            HeapData::Instance(inst) => {
+                member_attr(inst, StaticStrings::SunderValue, heap, interns).map(|v| (id, v.copy_for_extend()))
+            }
+            _ => None,
+        })
+        .collect();
+    for (_, value) in &members {
+        if let Value::Ref(id) = value {
+            heap.inc_ref(*id);
+        }
+    }
+    members
+}
+
+/// Returns whether `value` is a member of the enum class `class_id`, or an alias of one.
+fn is_member_of(value: &Value, class_id: HeapId, heap: &Heap<impl ResourceTracker>) -> bool {
+    matches!(value, Value::Ref(id) if matches!(heap.get(*id), HeapData::Instance(inst) if inst.class_id() == class_id))
+}
+
+/// Creates the members of a newly built enum class from its class attributes.
+///
+/// Dunder and private (`__name`) attributes and functions are not members,
+/// and other `_sunder_` names are reserved. `auto()` values are replaced with one more
+/// than the largest previous value (`name.lower()` for `StrEnum`), `IntEnum` values are
+/// converted with `int()`, and `StrEnum` values must be strings.
+pub(crate) fn create_members(
+    class_id: HeapId,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<()> {
+    let HeapData::ClassObject(cls) = heap.get(class_id) else {
+        unreachable!("create_members called with a non-class")
+    };
+    let kind = cls
+        .enum_info()
+        .expect("create_members called with a non-enum class")
+        .kind();
+    let mut names = Vec::new();
+    for (key, value) in cls.attrs().iter() {
+        let Value::InternString(name_id) = key else {
+            continue;
+        };
+        let name = interns.get_str(*name_id);
+        if name.starts_with("__") || is_method_function(value, heap) {
+            continue;
+        }
+        if name.len() > 2 && name.starts_with('_') && name.ends_with('_') {
+            return Err(SimpleException::new_msg(
+                ExcType::ValueError,
+                format!("_sunder_ names, such as '{name}', are reserved for future Enum use"),
+            )
+            .into());
+        }
+        names.push(*name_id);
+    }
+
+    let mut builder = MembersBuilder {
+        class_id,
+        kind,
+        members: Vec::new(),
+        member_ids: Vec::new(),
+        values: Vec::new(),
+    };
+    let mut result = Ok(());
+    for name_id in names {
+        result = builder.add(name_id, heap, interns);
+        if result.is_err() {
+            break;
+        }
+    }
+    let members = builder.finish(heap);
+    result?;
+
+    let HeapData::ClassObject(cls) = heap.get_mut(class_id) else {
+        unreachable!("enum class changed type")
+    };
+    cls.set_enum_members(members);
+    Ok(())
+}
+
+/// State of [`create_members`] while it goes through the class attributes.
+struct MembersBuilder {
+    /// The enum class.
+    class_id: HeapId,
+    /// The kind of the enum class.
+    kind: EnumKind,
+    /// Names of the members created so far, excluding aliases.
+    members: Vec<StringId>,
+    /// The members created so far (owned references).
+    member_ids: Vec<HeapId>,
+    /// The values of the members created so far (owned).
+    values: Vec<Value>,
+}
+
+impl MembersBuilder {
+    /// Replaces the class attribute `name_id` with a new member, or with an alias if its
+    /// value equals the value of an earlier member.
+    fn add(&mut self, name_id: StringId, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<()> {
+        let HeapData::ClassObject(cls) = heap.get(self.class_id) else {
+            unreachable!("enum class changed type")
+        };
+        let raw = cls
+            .attrs()
+            .get_by_str(interns.get_str(name_id), heap, interns)
+            .expect("member name is a class attribute")
+            .copy_for_extend();
+        if let Value::Ref(id) = &raw {
+            heap.inc_ref(*id);
+        }
+        let value = member_value(self.kind, name_id, raw, &self.values, heap, interns)?;
+
+        let member_id = if let Some(index) = self.values.iter().position(|v| v.py_eq(&value, heap, interns)) {
+            value.drop_with_heap(heap);
+            self.member_ids[index]
+        } else {
+            // The member's reference to the class is released when the member is freed
+            heap.inc_ref(self.class_id);
+            let member_id = match heap.allocate(HeapData::Instance(Instance::new(self.class_id))) {
+                Ok(id) => id,
+                Err(e) => {
+                    heap.dec_ref(self.class_id);
+                    value.drop_with_heap(heap);
+                    return Err(e.into());
+                }
+            };
+            self.members.push(name_id);
+            self.member_ids.push(member_id);
+            self.values.push(value.clone_with_heap(heap));
+            set_instance_attr(
+                member_id,
+                StaticStrings::SunderName,
+                Value::InternString(name_id),
+                heap,
+                interns,
+            )?;
+            set_instance_attr(member_id, StaticStrings::SunderValue, value, heap, interns)?;
+            member_id
+        };
+        heap.inc_ref(member_id);
+        let Ok(result) = class::set_attr(self.class_id, name_id, Value::Ref(member_id), heap, interns) else {
+            unreachable!("enum class changed type")
+        };
+        result
+    }
+
+    /// Releases the builder's references, returning the member names.
+    ///
+    /// Members created before an error stay attached to the class, which the caller frees.
+    fn finish(self, heap: &mut Heap<impl ResourceTracker>) -> Vec<StringId> {
+        self.values.drop_with_heap(heap);
+        for member_id in self.member_ids {
+            heap.dec_ref(member_id);
+        }
+        self.members
+    }
+}
+
+/// Sets an attribute on a newly created member, taking ownership of `value`.
+fn set_instance_attr(
+    member_id: HeapId,
+    name: StaticStrings,
+    value: Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<()> {
+    let Ok(result) = class::set_attr(member_id, name.into(), value, heap, interns) else {
+        unreachable!("enum members are instances")
+    };
+    result
+}
+
+/// Resolves the value of a new member from the value assigned in the class body,
+/// taking ownership of `raw`.
+///
+/// `previous` holds the values of the members defined so far, for `auto()`.
+fn member_value(
+    kind: EnumKind,
+    name_id: StringId,
+    raw: Value,
+    previous: &[Value],
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let value = if matches!(raw, Value::Marker(Marker(StaticStrings::Auto))) {
+        if kind == EnumKind::StrEnum {
+            return allocate_string(interns.get_str(name_id).to_lowercase(), heap);
+        }
+        next_value(previous, heap, interns)?
+    } else {
+        raw
+    };
+    match kind {
+        EnumKind::Enum => Ok(value),
+        EnumKind::IntEnum => Type::Int.call(heap, ArgValues::One(value), interns),
+        EnumKind::StrEnum => {
+            if value.as_either_str(heap).is_some() {
+                Ok(value)
+            } else {
+                let repr = value.py_repr(heap, interns).into_owned();
+                value.drop_with_heap(heap);
+                Err(ExcType::type_error(format!("{repr} is not a string")))
+            }
+        }
+    }
+}
+
+/// Returns the value for `auto()`: one more than the largest previous value, or 1 for
+/// the first member.
+fn next_value(previous: &[Value], heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+    let Some((first, rest)) = previous.split_first() else {
+        return Ok(Value::Int(1));
+    };
+    let mut last = first;
+    for value in rest {
+        match value.py_cmp(last, heap, interns) {
+            Some(ordering) if ordering.is_ge() => last = value,
+            Some(_) => {}
+            None => return Err(ExcType::type_error("unable to sort non-numeric values")),
+        }
+    }
+    match last.py_add(&Value::Int(1), heap, interns)? {
+        Some(next) => Ok(next),
+        None => Err(ExcType::type_error(format!(
+            "unable to increment {}",
+            last.py_repr(heap, interns)
+        ))),
+    }
+}
+
+/// Implements calling an enum class, `Color(1)`, which returns the member with the given value.
+pub(crate) fn call_enum_class(
+    class_id: HeapId,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let value = args.get_one_arg(interns.get_str(class::class_name(class_id, heap)), heap)?;
+    if is_member_of(&value, class_id, heap) {
+        return Ok(value);
+    }
+    let found = heap.with_entry_mut(class_id, |heap, data| {
+        let HeapData::ClassObject(cls) = data else {
+            unreachable!("call_enum_class called with a non-class")
+        };
+        let members = member_values(cls, heap, interns);
+        let found = members
+            .iter()
+            .find(|(_, member_value)| member_value.py_eq(&value, heap, interns))
+            .map(|(id, _)| *id);
+        for (_, member_value) in members {
+            member_value.drop_with_heap(heap);
+        }
+        found
+    });
+    if let Some(member_id) = found {
+        value.drop_with_heap(heap);
+        heap.inc_ref(member_id);
+        return Ok(Value::Ref(member_id));
+    }
+    let message = format!(
+        "{} is not a valid {}",
+        value.py_repr(heap, interns),
+        interns.get_str(class::class_name(class_id, heap))
+    );
+    value.drop_with_heap(heap);
+    Err(SimpleException::new_msg(ExcType::ValueError, message).into())
+}
+
+/// Implements `Color['RED']`, which returns the member (or alias) with the given name.
+pub(crate) fn getitem(
+    cls: &ClassObject,
+    key: &Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let member = key
+        .as_either_str(heap)
+        .and_then(|name| cls.attrs().get_by_str(name.as_str(interns), heap, interns))
+        .and_then(|value| match value {
+            Value::Ref(id) => Some(*id),
+            _ => None,
+        })
+        .filter(|id| member_ids(cls, heap, interns).contains(id));
+    match member {
+        Some(id) => {
+            heap.inc_ref(id);
+            Ok(Value::Ref(id))
+        }
+        None => Err(ExcType::key_error(key, heap, interns)),
+    }
+}
+
+/// Implements `item in Color`, which is true for members and for member values.
+///
+/// `cls` has been taken out of the heap, so its id is passed separately.
+pub(crate) fn contains(
+    class_id: HeapId,
+    cls: &ClassObject,
+    item: &Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> bool {
+    if is_member_of(item, class_id, heap) {
+        return true;
+    }
+    let members = member_values(cls, heap, interns);
+    let found = members.iter().any(|(_, value)| value.py_eq(item, heap, interns));
+    for (_, value) in members {
+        value.drop_with_heap(heap);
+    }
+    found
+}
+
+/// Returns a tuple of the members of an enum class, excluding aliases, for iteration.
+pub(crate) fn members_tuple(
+    class_id: HeapId,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let HeapData::ClassObject(cls) = heap.get(class_id) else {
+        unreachable!("members_tuple called with a non-class")
+    };
+    let ids = member_ids(cls, heap, interns);
+    let items: SmallVec<_> = ids
+        .into_iter()
+        .map(|id| {
+            heap.inc_ref(id);
+            Value::Ref(id)
+        })
+        .collect();
+    Ok(allocate_tuple(items, heap)?)
+}
+
+/// Returns `__members__`, a dict of the member names, including aliases, to the members.
+///
+/// Returns `Ok(None)` if `class_id` isn't an enum class.
+pub(crate) fn members_dict(
+    class_id: HeapId,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Option<Value>> {
+    let HeapData::ClassObject(cls) = heap.get(class_id) else {
+        return Ok(None);
+    };
+    if cls.enum_info().is_none() {
+        return Ok(None);
+    }
+    let pairs: Vec<(Value, Value)> = cls
+        .attrs()
+        .iter()
+        .filter(|(key, value)| matches!(key, Value::InternString(_)) && is_member_of(value, class_id, heap))
+        .map(|(key, value)| (key.copy_for_extend(), value.copy_for_extend()))
+        .collect();
+    for (_, value) in &pairs {
+        if let Value::Ref(id) = value {
+            heap.inc_ref(*id);
+        }
+    }
+    let dict = Dict::from_pairs(pairs, heap, interns)?;
+    Ok(Some(Value::Ref(heap.allocate(HeapData::Dict(dict))?)))
+}
+
+/// Checks that assigning or deleting the class attribute `name_id` of an enum class
+/// doesn't replace a member.
+pub(crate) fn check_member_change(
+    class_id: HeapId,
+    name_id: StringId,
+    deleting: bool,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<()> {
+    let HeapData::ClassObject(cls) = heap.get(class_id) else {
+        return Ok(());
+    };
+    let name = interns.get_str(name_id);
+    if cls.enum_info().is_none()
+        || !cls
+            .attrs()
+            .get_by_str(name, heap, interns)
+            .is_some_and(|value| is_member_of(value, class_id, heap))
+    {
+        return Ok(());
+    }
+    let message = if deleting {
+        format!("'{}' cannot delete member '{name}'.", interns.get_str(cls.name()))
+    } else {
+        format!("cannot reassign member '{name}'")
+    };
+    Err(SimpleException::new_msg(ExcType::AttributeError, message).into())
+}
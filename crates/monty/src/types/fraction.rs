@@ -0,0 +1,1112 @@
+//! The `fractions.Fraction` type of the `fractions` module (see `modules/fractions.rs`).
+//!
+//! A [`Fraction`] is an immutable rational number with arbitrary-precision numerator and
+//! denominator, always in lowest terms with a positive denominator. Arithmetic with ints
+//! stays exact, while arithmetic with floats gives floats, as in CPython. Fractions hash
+//! like the ints and floats they equal, so they find each other as dict keys.
+
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::hash_map::DefaultHasher,
+    fmt::Write,
+    hash::{Hash, Hasher},
+    mem::discriminant,
+};
+
+use ahash::AHashSet;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, Pow, Signed, ToPrimitive, Zero};
+use smallvec::smallvec;
+
+use super::{AttrCallResult, LongInt, PyTrait, Type, allocate_tuple, str::StringRepr};
+use crate::{
+    args::{ArgValues, bind_args},
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    expressions::Operator,
+    fstring::{ParsedFormatSpec, pad_number},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    modules::math::int_arg,
+    resource::{LARGE_RESULT_THRESHOLD, ResourceError, ResourceTracker},
+    value::{EitherStr, Value},
+};
+
+/// A `fractions.Fraction`: a rational number stored in lowest terms with a positive denominator.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Fraction {
+    numerator: BigInt,
+    denominator: BigInt,
+}
+
+impl Fraction {
+    /// Creates a `fractions.Fraction` from the `Fraction(numerator=0, denominator=None)`
+    /// constructor call.
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let [numerator, denominator] = bind_args("Fraction", args, ["numerator", "denominator"], 0, heap, interns)?;
+        let result = Self::from_args(numerator.as_ref(), denominator.as_ref(), heap, interns);
+        numerator.drop_with_heap(heap);
+        denominator.drop_with_heap(heap);
+        Ok(result?.into_value(heap)?)
+    }
+
+    fn from_args(
+        numerator: Option<&Value>,
+        denominator: Option<&Value>,
+        heap: &Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Self> {
+        let Some(numerator) = numerator else {
+            return Ok(Self::from_bigint(BigInt::zero()));
+        };
+        match denominator {
+            None | Some(Value::None) => Self::from_value(numerator, heap, interns),
+            Some(denominator) => {
+                let (Some(numerator), Some(denominator)) =
+                    (as_rational(numerator, heap), as_rational(denominator, heap))
+                else {
+                    return Err(ExcType::type_error("both arguments should be Rational instances"));
+                };
+                numerator
+                    .div(&denominator)
+                    .ok_or_else(|| zero_denominator(numerator.numerator()))
+            }
+        }
+    }
+
+    /// Converts the single argument of `Fraction()`: a rational, a float, a decimal or a string.
+    fn from_value(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Self> {
+        if let Some(fraction) = as_rational(value, heap) {
+            return Ok(fraction);
+        }
+        if let Value::Float(f) = value {
+            return float_fraction(*f);
+        }
+        if let Value::Ref(id) = value
+            && let HeapData::Decimal(decimal) = heap.get(*id)
+        {
+            let (numerator, denominator) = decimal.integer_ratio(heap)?;
+            return Ok(Self { numerator, denominator });
+        }
+        let Some(text) = value.as_either_str(heap) else {
+            return Err(ExcType::type_error(
+                "argument should be a string or a Rational instance",
+            ));
+        };
+        let text = text.as_str(interns);
+        let Some((numerator, denominator, exponent)) = Self::parse(text) else {
+            return Err(ExcType::value_error(format!(
+                "Invalid literal for Fraction: {}",
+                StringRepr(text)
+            )));
+        };
+        if denominator.is_zero() {
+            return Err(zero_denominator(&numerator));
+        }
+        check_power_of_ten(exponent.unsigned_abs(), heap)?;
+        Ok(Self::scaled(numerator, denominator, exponent).expect("denominator is non-zero"))
+    }
+
+    /// Creates the fraction `numerator / denominator` in lowest terms, or returns `None` if the
+    /// denominator is zero.
+    #[must_use]
+    pub fn new(numerator: BigInt, denominator: BigInt) -> Option<Self> {
+        if denominator.is_zero() {
+            return None;
+        }
+        let mut divisor = numerator.gcd(&denominator);
+        if denominator.is_negative() {
+            divisor = -divisor;
+        }
+        Some(Self {
+            numerator: numerator / &divisor,
+            denominator: denominator / divisor,
+        })
+    }
+
+    #[must_use]
+    pub fn from_bigint(numerator: BigInt) -> Self {
+        Self {
+            numerator,
+            denominator: BigInt::one(),
+        }
+    }
+
+    /// Creates the fraction with exactly the value of a float, or returns `None` for infinities
+    /// and NaNs.
+    #[must_use]
+    pub fn from_f64(f: f64) -> Option<Self> {
+        if !f.is_finite() {
+            return None;
+        }
+        let bits = f.to_bits();
+        let biased = (bits >> 52) & 0x7ff;
+        let fraction = bits & ((1 << 52) - 1);
+        let (mut mantissa, mut exp2) = if biased == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | 1 << 52, i64::try_from(biased).unwrap_or_default() - 1075)
+        };
+        if mantissa == 0 {
+            return Some(Self::from_bigint(BigInt::zero()));
+        }
+        let zeros = mantissa.trailing_zeros();
+        mantissa >>= zeros;
+        exp2 += i64::from(zeros);
+        let sign = if f < 0.0 { Sign::Minus } else { Sign::Plus };
+        let mantissa = BigInt::from_biguint(sign, BigUint::from(mantissa));
+        Some(if exp2 >= 0 {
+            Self::from_bigint(mantissa << exp2.unsigned_abs())
+        } else {
+            // the mantissa is odd, so the fraction is already in lowest terms
+            Self {
+                numerator: mantissa,
+                denominator: BigInt::one() << exp2.unsigned_abs(),
+            }
+        })
+    }
+
+    /// Parses a string like the `Fraction` constructor: `'3/4'`, `' -12 / 5 '`, `'1.25'`,
+    /// `'.5e-3'` or `'1_000'`.
+    ///
+    /// Returns the numerator, the (non-zero-checked) denominator and a power of ten to scale
+    /// them by, which is kept separate so callers can check the size of the result first.
+    #[must_use]
+    pub fn parse(text: &str) -> Option<(BigInt, BigInt, i64)> {
+        let text = text.trim();
+        let (negative, body) = match text.as_bytes().first() {
+            Some(b'-') => (true, &text[1..]),
+            Some(b'+') => (false, &text[1..]),
+            _ => (false, text),
+        };
+        let bytes = body.as_bytes();
+        let starts_with_digit = |at: usize| bytes.get(at).is_some_and(u8::is_ascii_digit);
+        if !starts_with_digit(0) && !(bytes.first() == Some(&b'.') && starts_with_digit(1)) {
+            return None;
+        }
+        let (int_digits, mut pos) = digit_group(bytes, 0);
+        let mut numerator = parse_digits(&int_digits);
+        let mut denominator = BigInt::one();
+        let mut exponent = 0i64;
+
+        let after_space = pos + body[pos..].len() - body[pos..].trim_start().len();
+        if bytes.get(after_space) == Some(&b'/') {
+            let start = after_space + 1 + body[after_space + 1..].len() - body[after_space + 1..].trim_start().len();
+            let (digits, end) = digit_group(bytes, start);
+            if digits.is_empty() {
+                return None;
+            }
+            denominator = parse_digits(&digits);
+            pos = end;
+        } else {
+            if bytes.get(pos) == Some(&b'.') {
+                let (digits, end) = digit_group(bytes, pos + 1);
+                numerator =
+                    numerator * BigInt::from(10u32).pow(u32::try_from(digits.len()).ok()?) + parse_digits(&digits);
+                exponent = -i64::try_from(digits.len()).ok()?;
+                pos = end;
+            }
+            if matches!(bytes.get(pos), Some(b'e' | b'E')) {
+                let sign_len = usize::from(matches!(bytes.get(pos + 1), Some(b'+' | b'-')));
+                let (digits, end) = digit_group(bytes, pos + 1 + sign_len);
+                if digits.is_empty() {
+                    return None;
+                }
+                // exponents too large for an i64 can't be computed anyway, so they saturate
+                let value = digits.parse::<i64>().unwrap_or(i64::MAX);
+                let value = if bytes[pos + 1] == b'-' { -value } else { value };
+                exponent = exponent.saturating_add(value);
+                pos = end;
+            }
+        }
+        if pos != bytes.len() {
+            return None;
+        }
+        if negative {
+            numerator = -numerator;
+        }
+        Some((numerator, denominator, exponent))
+    }
+
+    /// Creates `numerator / denominator * 10**exponent` in lowest terms, as returned by
+    /// [`Self::parse`].
+    #[must_use]
+    pub fn scaled(numerator: BigInt, denominator: BigInt, exponent: i64) -> Option<Self> {
+        let scale = BigInt::from(10u32).pow(u32::try_from(exponent.unsigned_abs()).unwrap_or(u32::MAX));
+        if exponent >= 0 {
+            Self::new(numerator * scale, denominator)
+        } else {
+            Self::new(numerator, denominator * scale)
+        }
+    }
+
+    #[must_use]
+    pub fn numerator(&self) -> &BigInt {
+        &self.numerator
+    }
+
+    #[must_use]
+    pub fn denominator(&self) -> &BigInt {
+        &self.denominator
+    }
+
+    #[must_use]
+    pub fn is_integer(&self) -> bool {
+        self.denominator.is_one()
+    }
+
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    #[must_use]
+    pub fn neg(&self) -> Self {
+        Self {
+            numerator: -&self.numerator,
+            denominator: self.denominator.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn abs(&self) -> Self {
+        Self {
+            numerator: self.numerator.abs(),
+            denominator: self.denominator.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn add(&self, other: &Self) -> Self {
+        let numerator = &self.numerator * &other.denominator + &other.numerator * &self.denominator;
+        Self::new(numerator, &self.denominator * &other.denominator).expect("denominators are positive")
+    }
+
+    #[must_use]
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+
+    #[must_use]
+    pub fn mul(&self, other: &Self) -> Self {
+        Self::new(
+            &self.numerator * &other.numerator,
+            &self.denominator * &other.denominator,
+        )
+        .expect("denominators are positive")
+    }
+
+    /// Returns `self / other`, or `None` if `other` is zero.
+    #[must_use]
+    pub fn div(&self, other: &Self) -> Option<Self> {
+        Self::new(
+            &self.numerator * &other.denominator,
+            &self.denominator * &other.numerator,
+        )
+    }
+
+    /// Returns `self // other`, or `None` if `other` is zero.
+    #[must_use]
+    pub fn floor_div(&self, other: &Self) -> Option<BigInt> {
+        if other.is_zero() {
+            return None;
+        }
+        Some((&self.numerator * &other.denominator).div_floor(&(&self.denominator * &other.numerator)))
+    }
+
+    /// Returns `self % other`, which takes the sign of `other`, or `None` if `other` is zero.
+    #[must_use]
+    pub fn rem(&self, other: &Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+        let remainder = (&self.numerator * &other.denominator).mod_floor(&(&other.numerator * &self.denominator));
+        Self::new(remainder, &self.denominator * &other.denominator)
+    }
+
+    /// Raises the fraction to the integer power `exponent`, or returns `None` for a negative
+    /// power of zero.
+    ///
+    /// Exponents beyond `u32::MAX` saturate; callers check that the result fits first.
+    #[must_use]
+    pub fn pow(&self, exponent: &BigInt) -> Option<Self> {
+        let power = exponent.magnitude().to_u32().unwrap_or(u32::MAX);
+        let numerator = Pow::pow(&self.numerator, power);
+        let denominator = Pow::pow(&self.denominator, power);
+        if exponent.is_negative() {
+            Self::new(denominator, numerator)
+        } else {
+            Some(Self { numerator, denominator })
+        }
+    }
+
+    #[must_use]
+    pub fn floor(&self) -> BigInt {
+        self.numerator.div_floor(&self.denominator)
+    }
+
+    #[must_use]
+    pub fn ceil(&self) -> BigInt {
+        -(-&self.numerator).div_floor(&self.denominator)
+    }
+
+    /// Rounds towards zero, as `int()` does.
+    #[must_use]
+    pub fn trunc(&self) -> BigInt {
+        &self.numerator / &self.denominator
+    }
+
+    /// Rounds to the nearest integer, with ties going to the even one.
+    #[must_use]
+    pub fn round(&self) -> BigInt {
+        let (floor, remainder) = self.numerator.div_mod_floor(&self.denominator);
+        let twice = remainder * 2u32;
+        match twice.cmp(&self.denominator) {
+            Ordering::Less => floor,
+            Ordering::Greater => floor + 1u32,
+            Ordering::Equal if floor.is_even() => floor,
+            Ordering::Equal => floor + 1u32,
+        }
+    }
+
+    /// Rounds to a multiple of `10**-ndigits`, with ties going to the even multiple, as
+    /// `round(fraction, ndigits)` does.
+    #[must_use]
+    pub fn round_to(&self, ndigits: i64) -> Self {
+        let shift =
+            Self::from_bigint(BigInt::from(10u32).pow(u32::try_from(ndigits.unsigned_abs()).unwrap_or(u32::MAX)));
+        if ndigits > 0 {
+            let rounded = self.mul(&shift).round();
+            Self::new(rounded, shift.numerator).expect("powers of ten are positive")
+        } else {
+            let rounded = self.div(&shift).expect("powers of ten are positive").round();
+            Self::from_bigint(rounded * shift.numerator)
+        }
+    }
+
+    /// The float nearest to the fraction, or `None` if it's too large for a float.
+    #[must_use]
+    pub fn to_f64(&self) -> Option<f64> {
+        ratio_to_f64(&self.numerator, &self.denominator)
+    }
+
+    /// Compares the fraction with a float, or returns `None` if the float is a NaN.
+    #[must_use]
+    pub fn cmp_f64(&self, f: f64) -> Option<Ordering> {
+        if f.is_nan() {
+            return None;
+        }
+        if f.is_infinite() {
+            return Some(if f > 0.0 { Ordering::Less } else { Ordering::Greater });
+        }
+        Some(self.cmp(&Self::from_f64(f).expect("float is finite")))
+    }
+
+    /// The closest fraction with a denominator of at most `max_denominator`, which must be
+    /// positive, as `limit_denominator()` finds it.
+    #[must_use]
+    pub fn limit_denominator(&self, max_denominator: &BigInt) -> Self {
+        if &self.denominator <= max_denominator {
+            return self.clone();
+        }
+        let (mut p0, mut q0, mut p1, mut q1) = (BigInt::zero(), BigInt::one(), BigInt::one(), BigInt::zero());
+        let (mut n, mut d) = (self.numerator.clone(), self.denominator.clone());
+        loop {
+            let a = n.div_floor(&d);
+            let q2 = &q0 + &a * &q1;
+            if &q2 > max_denominator {
+                break;
+            }
+            let p2 = &p0 + &a * &p1;
+            (p0, q0, p1, q1) = (p1, q1, p2, q2);
+            let next_d = &n - &a * &d;
+            (n, d) = (d, next_d);
+        }
+        let k = (max_denominator - &q0).div_floor(&q1);
+        if BigInt::from(2u32) * &d * (&q0 + &k * &q1) <= self.denominator {
+            Self {
+                numerator: p1,
+                denominator: q1,
+            }
+        } else {
+            Self {
+                numerator: p0 + &k * &p1,
+                denominator: q0 + k * q1,
+            }
+        }
+    }
+
+    /// Formats the fraction for a format spec with a float presentation type (`e`, `E`, `f`,
+    /// `F`, `g`, `G` or `%`), with `precision` defaulting to 6.
+    ///
+    /// Returns whether the result is negative and its digits, which the caller pads.
+    #[must_use]
+    pub fn format_float_style(&self, type_char: char, precision: Option<usize>) -> (bool, String) {
+        let precision = i64::try_from(precision.unwrap_or(6)).unwrap_or(i64::MAX);
+        let (n, d) = (&self.numerator, &self.denominator);
+        let (negative, significand, exponent, scientific, point_pos) = if matches!(type_char, 'f' | 'F' | '%') {
+            let exponent = if type_char == '%' { -precision - 2 } else { -precision };
+            let (negative, significand) = round_to_exponent(n, d, exponent);
+            (negative, significand, exponent, false, precision)
+        } else {
+            let figures = if matches!(type_char, 'g' | 'G') {
+                precision.max(1)
+            } else {
+                precision + 1
+            };
+            let (negative, significand, exponent) = round_to_figures(n, d, figures);
+            let scientific = matches!(type_char, 'e' | 'E') || exponent > 0 || exponent + figures <= -4;
+            let point_pos = if scientific { figures - 1 } else { -exponent };
+            (negative, significand, exponent, scientific, point_pos)
+        };
+
+        let suffix = if type_char == '%' {
+            "%".to_owned()
+        } else if scientific {
+            let indicator = if matches!(type_char, 'E' | 'F' | 'G') { 'E' } else { 'e' };
+            let shown = exponent + point_pos;
+            format!(
+                "{indicator}{}{:02}",
+                if shown < 0 { '-' } else { '+' },
+                shown.unsigned_abs()
+            )
+        } else {
+            String::new()
+        };
+        let point_pos = usize::try_from(point_pos).unwrap_or_default();
+        let mut digits = significand.to_string();
+        if digits.len() < point_pos + 1 {
+            digits.insert_str(0, &"0".repeat(point_pos + 1 - digits.len()));
+        }
+        let (leading, frac_part) = digits.split_at(digits.len() - point_pos);
+        let frac_part = if matches!(type_char, 'g' | 'G') {
+            frac_part.trim_end_matches('0')
+        } else {
+            frac_part
+        };
+        let separator = if frac_part.is_empty() { "" } else { "." };
+        (negative, format!("{leading}{separator}{frac_part}{suffix}"))
+    }
+
+    /// Allocates the fraction on the heap.
+    pub fn into_value(self, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, ResourceError> {
+        Ok(Value::Ref(heap.allocate(HeapData::Fraction(self))?))
+    }
+
+    /// The float value of the fraction, as `float()` converts it.
+    ///
+    /// # Errors
+    /// Returns `OverflowError` if the fraction is too large for a float.
+    pub fn to_float(&self) -> RunResult<f64> {
+        self.to_f64().ok_or_else(|| {
+            SimpleException::new_msg(ExcType::OverflowError, "integer division result too large for a float").into()
+        })
+    }
+
+    /// The hash of the fraction, which is the hash of the int or float it equals, if any.
+    #[must_use]
+    pub fn hash(&self) -> u64 {
+        hash_rational(&self.numerator, &self.denominator)
+    }
+
+    /// Formats the fraction for a format spec, like `Fraction.__format__`.
+    ///
+    /// Without a presentation type the fraction is formatted as `n/d` (or `n` for integers),
+    /// and the float types `e`, `E`, `f`, `F`, `g`, `G` and `%` round it exactly.
+    pub fn format_spec(&self, spec: &ParsedFormatSpec) -> RunResult<String> {
+        match spec.type_char {
+            None if !spec.zero_pad && spec.precision.is_none() => {
+                let magnitude = self.numerator.magnitude();
+                let body = if self.is_integer() {
+                    magnitude.to_string()
+                } else {
+                    format!("{magnitude}/{}", self.denominator)
+                };
+                Ok(pad_number(self.numerator.is_negative(), &body, spec))
+            }
+            Some(type_char @ ('e' | 'E' | 'f' | 'F' | 'g' | 'G' | '%')) if !(spec.zero_pad && spec.align.is_some()) => {
+                let (negative, body) = self.format_float_style(type_char, spec.precision);
+                Ok(pad_number(negative, &body, spec))
+            }
+            _ => Err(ExcType::value_error(format!(
+                "Invalid format specifier '{}' for object of type 'Fraction'",
+                spec_text(spec)
+            ))),
+        }
+    }
+
+    /// Rounds the fraction to an integer for `round()`, or to `ndigits` decimal places.
+    pub fn py_round(&self, ndigits: Option<i64>, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        match ndigits {
+            None => Ok(LongInt::new(self.round()).into_value(heap)?),
+            Some(ndigits) => {
+                check_power_of_ten(ndigits.unsigned_abs(), heap)?;
+                Ok(self.round_to(ndigits).into_value(heap)?)
+            }
+        }
+    }
+}
+
+impl PartialOrd for Fraction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Fraction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.numerator * &other.denominator).cmp(&(&other.numerator * &self.denominator))
+    }
+}
+
+/// Reads a run of digits with single underscores between them, like the `\d+(_\d+)*` of
+/// CPython's pattern, returning the digits and the position after them.
+
+impl PyTrait for Fraction {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Fraction
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + bigint_size(&self.numerator) + bigint_size(&self.denominator)
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self == other
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {}
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        !self.is_zero()
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        write!(f, "Fraction({}, {})", self.numerator, self.denominator)
+    }
+
+    fn py_str(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Cow<'static, str> {
+        if self.is_integer() {
+            Cow::Owned(self.numerator.to_string())
+        } else {
+            Cow::Owned(format!("{}/{}", self.numerator, self.denominator))
+        }
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        let value = match attr.static_string() {
+            Some(StaticStrings::LimitDenominator) => {
+                let [max_denominator] = bind_args(
+                    "Fraction.limit_denominator",
+                    args,
+                    ["max_denominator"],
+                    0,
+                    heap,
+                    interns,
+                )?;
+                let limit = max_denominator.as_ref().map(|value| int_arg(value, heap));
+                max_denominator.drop_with_heap(heap);
+                let limit = limit.transpose()?.unwrap_or_else(|| BigInt::from(1_000_000));
+                if limit < BigInt::one() {
+                    return Err(ExcType::value_error("max_denominator should be at least 1"));
+                }
+                self.limit_denominator(&limit).into_value(heap)?
+            }
+            Some(StaticStrings::AsIntegerRatio) => {
+                args.check_zero_args("Fraction.as_integer_ratio", heap)?;
+                let numerator = LongInt::new(self.numerator.clone()).into_value(heap)?;
+                let denominator = LongInt::new(self.denominator.clone()).into_value(heap)?;
+                allocate_tuple(smallvec![numerator, denominator], heap)?
+            }
+            Some(StaticStrings::IsInteger) => {
+                args.check_zero_args("Fraction.is_integer", heap)?;
+                Value::Bool(self.is_integer())
+            }
+            Some(StaticStrings::Conjugate) => {
+                args.check_zero_args("Fraction.conjugate", heap)?;
+                self.clone().into_value(heap)?
+            }
+            _ => {
+                args.drop_with_heap(heap);
+                return Err(ExcType::attribute_error(Type::Fraction, attr.as_str(interns)));
+            }
+        };
+        Ok(value)
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = match StaticStrings::from_string_id(attr_id) {
+            Some(StaticStrings::Numerator) => LongInt::new(self.numerator.clone()).into_value(heap)?,
+            Some(StaticStrings::Denominator) => LongInt::new(self.denominator.clone()).into_value(heap)?,
+            Some(StaticStrings::Real) => self.clone().into_value(heap)?,
+            Some(StaticStrings::Imag) => Value::Int(0),
+            _ => return Ok(None),
+        };
+        Ok(Some(AttrCallResult::Value(value)))
+    }
+}
+
+/// Implements the class method `Fraction.from_float()`, which also accepts ints.
+pub(crate) fn from_float(
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let value = args.get_one_arg("Fraction.from_float", heap)?;
+    defer_drop!(value, heap);
+    let fraction = match value {
+        Value::Float(f) => float_fraction(*f)?,
+        _ => match int_value(value, heap) {
+            Some(int) => Fraction::from_bigint(int),
+            None => {
+                return Err(ExcType::type_error(format!(
+                    "Fraction.from_float() only takes floats, not {} ({})",
+                    value.py_repr(heap, interns),
+                    value.py_type(heap)
+                )));
+            }
+        },
+    };
+    Ok(fraction.into_value(heap)?)
+}
+
+/// Implements the class method `Fraction.from_decimal()`, which also accepts ints.
+pub(crate) fn from_decimal(
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let value = args.get_one_arg("Fraction.from_decimal", heap)?;
+    defer_drop!(value, heap);
+    let fraction = if let Some(int) = int_value(value, heap) {
+        Fraction::from_bigint(int)
+    } else if let Value::Ref(id) = value
+        && let HeapData::Decimal(decimal) = heap.get(*id)
+    {
+        let (numerator, denominator) = decimal.integer_ratio(heap)?;
+        Fraction::new(numerator, denominator).expect("denominator is positive")
+    } else {
+        return Err(ExcType::type_error(format!(
+            "Fraction.from_decimal() only takes Decimals, not {} ({})",
+            value.py_repr(heap, interns),
+            value.py_type(heap)
+        )));
+    };
+    Ok(fraction.into_value(heap)?)
+}
+
+/// Applies an arithmetic operator when either operand is a fraction.
+///
+/// Fractions combine with ints (and bools) into fractions, except that `//` gives an int,
+/// and with floats into floats. Returns `Ok(None)` for other operands, including decimals,
+/// so the caller can report the usual TypeError.
+pub(crate) fn binary_op(
+    lhs: &Value,
+    rhs: &Value,
+    op: &Operator,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> RunResult<Option<Value>> {
+    if !is_fraction(lhs, heap) && !is_fraction(rhs, heap) {
+        return Ok(None);
+    }
+    if let (Some(a), Some(b)) = (as_rational(lhs, heap), as_rational(rhs, heap)) {
+        let int_base = !is_fraction(lhs, heap);
+        return rational_op(&a, &b, op, int_base, heap);
+    }
+    let float = |value: &Value| match value {
+        Value::Float(f) => Some(Ok(*f)),
+        _ => as_rational(value, heap).map(|fraction| fraction.to_float()),
+    };
+    match (float(lhs), float(rhs)) {
+        (Some(a), Some(b)) => float_op(a?, b?, op, heap),
+        _ => Ok(None),
+    }
+}
+
+/// Applies an arithmetic operator to two rationals, where `int_base` says the left operand
+/// is an int, which keeps `int ** non-negative int` an int.
+fn rational_op(
+    a: &Fraction,
+    b: &Fraction,
+    op: &Operator,
+    int_base: bool,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> RunResult<Option<Value>> {
+    let result = match op {
+        Operator::Add => a.add(b),
+        Operator::Sub => a.sub(b),
+        Operator::Mult => a.mul(b),
+        Operator::Div => a.div(b).ok_or_else(|| zero_denominator(a.numerator()))?,
+        Operator::FloorDiv => {
+            let quotient = a.floor_div(b).ok_or_else(ExcType::zero_division)?;
+            return Ok(Some(LongInt::new(quotient).into_value(heap)?));
+        }
+        Operator::Mod => a.rem(b).ok_or_else(ExcType::zero_division)?,
+        Operator::Pow => return rational_pow(a, b, int_base, heap),
+        _ => return Ok(None),
+    };
+    Ok(Some(result.into_value(heap)?))
+}
+
+/// Raises a rational to a rational power: exactly for integer exponents, and as floats
+/// otherwise.
+fn rational_pow(
+    base: &Fraction,
+    exponent: &Fraction,
+    int_base: bool,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> RunResult<Option<Value>> {
+    if !exponent.is_integer() {
+        return float_op(base.to_float()?, exponent.to_float()?, &Operator::Pow, heap);
+    }
+    let mut exponent = exponent.numerator().clone();
+    if exponent.magnitude().to_u32().is_none() {
+        let trivial_base = base.is_integer() && (base.is_zero() || base.numerator().magnitude().is_one());
+        if !trivial_base {
+            return Err(ExcType::overflow_exponent_too_large());
+        }
+        // powers of zero and one only depend on the sign and parity of the exponent
+        let parity = if exponent.is_odd() { 1 } else { 2 };
+        exponent = if exponent.is_negative() {
+            BigInt::from(-parity)
+        } else {
+            BigInt::from(parity)
+        };
+    }
+    let bits = base.numerator().bits().max(base.denominator().bits());
+    if bits > 1 {
+        let estimated = LongInt::estimate_pow_bytes(bits, exponent.magnitude().to_u64().unwrap_or(u64::MAX));
+        match estimated {
+            Some(estimated) if estimated <= LARGE_RESULT_THRESHOLD => {}
+            _ => heap.tracker().check_large_result(estimated.unwrap_or(usize::MAX))?,
+        }
+    }
+    let power = base.pow(&exponent).ok_or_else(|| zero_denominator(&BigInt::one()))?;
+    if int_base && !exponent.is_negative() {
+        return Ok(Some(LongInt::new(power.numerator).into_value(heap)?));
+    }
+    Ok(Some(power.into_value(heap)?))
+}
+
+/// Applies an arithmetic operator to the float values of a fraction and another number,
+/// as CPython does when a fraction meets a float.
+fn float_op(a: f64, b: f64, op: &Operator, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Option<Value>> {
+    let (lhs, rhs) = (Value::Float(a), Value::Float(b));
+    match op {
+        Operator::Add => Ok(Some(Value::Float(a + b))),
+        Operator::Sub => Ok(Some(Value::Float(a - b))),
+        Operator::Mult => Ok(Some(Value::Float(a * b))),
+        Operator::Div if b == 0.0 => Err(ExcType::zero_division().into()),
+        Operator::Div => Ok(Some(Value::Float(a / b))),
+        Operator::FloorDiv => lhs.py_floordiv(&rhs, heap),
+        Operator::Mod => lhs.py_mod(&rhs, heap),
+        Operator::Pow => lhs.py_pow(&rhs, heap),
+        _ => Ok(None),
+    }
+}
+
+/// Compares two numbers exactly when either is a fraction.
+///
+/// Returns `None` when neither operand is a fraction, so callers fall through to their usual
+/// comparison, and `Some(None)` when the operands can't be ordered, like a fraction and a
+/// NaN or a string.
+#[must_use]
+pub(crate) fn compare(lhs: &Value, rhs: &Value, heap: &Heap<impl ResourceTracker>) -> Option<Option<Ordering>> {
+    if !is_fraction(lhs, heap) && !is_fraction(rhs, heap) {
+        return None;
+    }
+    Some(match (lhs, rhs) {
+        (Value::Float(f), _) => as_rational(rhs, heap)
+            .and_then(|fraction| fraction.cmp_f64(*f))
+            .map(Ordering::reverse),
+        (_, Value::Float(f)) => as_rational(lhs, heap).and_then(|fraction| fraction.cmp_f64(*f)),
+        _ => match (as_rational(lhs, heap), as_rational(rhs, heap)) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            _ => None,
+        },
+    })
+}
+
+/// Raises the TypeError of an ordering comparison between a fraction and a value which isn't
+/// a number.
+///
+/// Does nothing when neither operand is a fraction, or when the operands can be ordered or
+/// are unordered only because of a float NaN.
+pub(crate) fn check_orderable(
+    lhs: &Value,
+    rhs: &Value,
+    symbol: &str,
+    heap: &Heap<impl ResourceTracker>,
+) -> RunResult<()> {
+    if !matches!(compare(lhs, rhs, heap), Some(None))
+        || matches!(lhs, Value::Float(_))
+        || matches!(rhs, Value::Float(_))
+    {
+        return Ok(());
+    }
+    Err(ExcType::type_error(format!(
+        "'{symbol}' not supported between instances of '{}' and '{}'",
+        lhs.py_type(heap),
+        rhs.py_type(heap)
+    )))
+}
+
+/// Returns whether `value` is a fraction.
+#[must_use]
+pub(crate) fn is_fraction(value: &Value, heap: &Heap<impl ResourceTracker>) -> bool {
+    matches!(value, Value::Ref(id) if matches!(heap.get(*id), HeapData::Fraction(_)))
+}
+
+/// Converts an int, bool or fraction, the values `fractions` treats as exact rationals, to
+/// a fraction.
+#[must_use]
+pub(crate) fn as_rational(value: &Value, heap: &Heap<impl ResourceTracker>) -> Option<Fraction> {
+    if let Value::Ref(id) = value
+        && let HeapData::Fraction(fraction) = heap.get(*id)
+    {
+        return Some(fraction.clone());
+    }
+    int_value(value, heap).map(Fraction::from_bigint)
+}
+
+/// The value of an int or bool.
+#[must_use]
+pub(crate) fn int_value(value: &Value, heap: &Heap<impl ResourceTracker>) -> Option<BigInt> {
+    match value {
+        Value::Int(i) => Some(BigInt::from(*i)),
+        Value::Bool(b) => Some(BigInt::from(u8::from(*b))),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => Some(li.inner().clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Converts a float to the fraction with exactly its value, as `Fraction(float)` does.
+///
+/// # Errors
+/// Returns `OverflowError` for infinities and `ValueError` for NaNs.
+fn float_fraction(f: f64) -> RunResult<Fraction> {
+    Fraction::from_f64(f).ok_or_else(|| special_ratio_error(f.is_nan()))
+}
+
+/// Creates the error of converting an infinity or NaN to an integer ratio, for floats and
+/// decimals.
+#[cold]
+pub(crate) fn special_ratio_error(nan: bool) -> RunError {
+    if nan {
+        ExcType::value_error("cannot convert NaN to integer ratio")
+    } else {
+        SimpleException::new_msg(ExcType::OverflowError, "cannot convert Infinity to integer ratio").into()
+    }
+}
+
+/// Hashes the rational `numerator / denominator` (in lowest terms, with a positive
+/// denominator) like the int or float it equals, so equal numbers find each other as dict
+/// keys whatever their types.
+#[must_use]
+pub(crate) fn hash_rational(numerator: &BigInt, denominator: &BigInt) -> u64 {
+    if denominator.is_one() {
+        return LongInt::new(numerator.clone()).hash();
+    }
+    let mut hasher = DefaultHasher::new();
+    let exact_float = ratio_to_f64(numerator, denominator).filter(|f| {
+        Fraction::from_f64(*f).is_some_and(|exact| &exact.numerator == numerator && &exact.denominator == denominator)
+    });
+    if let Some(f) = exact_float {
+        discriminant(&Value::Float(0.0)).hash(&mut hasher);
+        f.to_bits().hash(&mut hasher);
+    } else {
+        numerator.hash(&mut hasher);
+        denominator.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Checks with the resource tracker that `10 ** digits` can be computed, before building a
+/// number with that many digits.
+///
+/// # Errors
+/// Returns `OverflowError` for powers too large to compute at all.
+pub(crate) fn check_power_of_ten(digits: u64, heap: &Heap<impl ResourceTracker>) -> RunResult<()> {
+    if digits > u64::from(u32::MAX) {
+        return Err(ExcType::overflow_exponent_too_large());
+    }
+    // a decimal digit takes log2(10) / 8, a little under half a byte
+    let estimated = usize::try_from(digits / 2).unwrap_or(usize::MAX);
+    if estimated > LARGE_RESULT_THRESHOLD {
+        heap.tracker().check_large_result(estimated)?;
+    }
+    Ok(())
+}
+
+/// The estimated size of the digits of a `BigInt`.
+fn bigint_size(n: &BigInt) -> usize {
+    usize::try_from(n.bits().div_ceil(8)).unwrap_or(usize::MAX)
+}
+
+/// Recreates the text of a format spec for error messages.
+fn spec_text(spec: &ParsedFormatSpec) -> String {
+    let mut text = String::new();
+    if let Some(align) = spec.align {
+        if spec.fill != ' ' {
+            text.push(spec.fill);
+        }
+        text.push(align);
+    }
+    text.extend(spec.sign);
+    if spec.zero_pad {
+        text.push('0');
+    }
+    if spec.width > 0 {
+        text.push_str(&spec.width.to_string());
+    }
+    if let Some(precision) = spec.precision {
+        text.push_str(&format!(".{precision}"));
+    }
+    text.extend(spec.type_char);
+    text
+}
+
+/// Creates the `ZeroDivisionError` of a fraction with a zero denominator, like
+/// `Fraction(1, 0)`.
+#[cold]
+fn zero_denominator(numerator: &BigInt) -> RunError {
+    SimpleException::new_msg(ExcType::ZeroDivisionError, format!("Fraction({numerator}, 0)")).into()
+}
+
+/// Reads the digits of `bytes` from `start`, skipping single underscores between digits,
+/// and returns them with the position after them.
+///
+/// Returns no digits, and `start`, if there's no digit at `start`.
+fn digit_group(bytes: &[u8], start: usize) -> (String, usize) {
+    let mut digits = String::new();
+    let mut pos = start;
+    while let Some(&byte) = bytes.get(pos) {
+        if byte.is_ascii_digit() {
+            digits.push(char::from(byte));
+            pos += 1;
+        } else if byte == b'_' && !digits.is_empty() && bytes.get(pos + 1).is_some_and(u8::is_ascii_digit) {
+            pos += 1;
+        } else {
+            break;
+        }
+    }
+    (digits, pos)
+}
+
+fn parse_digits(digits: &str) -> BigInt {
+    BigInt::parse_bytes(digits.as_bytes(), 10).unwrap_or_default()
+}
+
+/// Divides two integers (with `denominator` positive) into the nearest float, rounding ties
+/// to even, or returns `None` if the result is too large for a float.
+#[must_use]
+pub(crate) fn ratio_to_f64(numerator: &BigInt, denominator: &BigInt) -> Option<f64> {
+    if numerator.is_zero() {
+        return Some(0.0);
+    }
+    let sign = if numerator.is_negative() { -1.0 } else { 1.0 };
+    let (a, b) = (numerator.magnitude(), denominator.magnitude());
+    let diff = i64::try_from(a.bits()).unwrap_or(i64::MAX) - i64::try_from(b.bits()).unwrap_or(i64::MAX);
+    if diff > 1025 {
+        return None;
+    }
+    if diff < -1076 {
+        return Some(sign * 0.0);
+    }
+    // a quotient of 55 or 56 bits, or fewer for subnormal results, then rounded to 53 bits
+    let shift = (diff - 55).max(-1076);
+    let (quotient, remainder) = if shift >= 0 {
+        a.div_rem(&(b << shift.unsigned_abs()))
+    } else {
+        (a << shift.unsigned_abs()).div_rem(b)
+    };
+    let bits = i64::try_from(quotient.bits()).unwrap_or_default();
+    let lsb = (shift + bits - 53).max(-1074);
+    let drop = (lsb - shift).unsigned_abs();
+    let mut mantissa = (&quotient >> drop).to_u64().unwrap_or_default();
+    let half = BigUint::one() << (drop - 1);
+    let dropped = quotient & ((BigUint::one() << drop) - 1u32);
+    let round_up = match dropped.cmp(&half) {
+        Ordering::Greater => true,
+        Ordering::Equal => !remainder.is_zero() || mantissa & 1 == 1,
+        Ordering::Less => false,
+    };
+    if round_up {
+        mantissa += 1;
+    }
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "the mantissa has at most 54 bits and is a power of two if it has 54"
+    )]
+    let mantissa = mantissa as f64;
+    let lsb = i32::try_from(lsb).unwrap_or(i32::MAX);
+    let result = if lsb < -1000 {
+        // two steps, so the first product stays normal and both are exact
+        mantissa * 2f64.powi(-1000) * 2f64.powi(lsb + 1000)
+    } else {
+        mantissa * 2f64.powi(lsb)
+    };
+    result.is_finite().then_some(sign * result)
+}
+
+/// Rounds `n / d` to a multiple of `10**exponent`, with ties going to the even multiple.
+///
+/// Returns whether the value is negative and the magnitude of the multiple.
+fn round_to_exponent(n: &BigInt, d: &BigInt, exponent: i64) -> (bool, BigInt) {
+    let scale = BigInt::from(10u32).pow(u32::try_from(exponent.unsigned_abs()).unwrap_or(u32::MAX));
+    let (n, d) = if exponent >= 0 {
+        (n.clone(), d * scale)
+    } else {
+        (n * scale, d.clone())
+    };
+    let (mut quotient, remainder) = (&n + (&d >> 1u32)).div_mod_floor(&d);
+    if remainder.is_zero() && d.is_even() && quotient.is_odd() {
+        quotient -= 1u32;
+    }
+    (n.is_negative(), quotient.abs())
+}
+
+/// Rounds `n / d` to `figures` significant digits, with ties going to the even one.
+///
+/// Returns whether the value is negative, the digits and the exponent of the last one.
+fn round_to_figures(n: &BigInt, d: &BigInt, figures: i64) -> (bool, BigInt, i64) {
+    if n.is_zero() {
+        return (false, BigInt::zero(), 1 - figures);
+    }
+    let (str_n, str_d) = (n.abs().to_string(), d.to_string());
+    let len = |s: &str| i64::try_from(s.len()).unwrap_or(i64::MAX);
+    let m = len(&str_n) - len(&str_d) + i64::from(str_d <= str_n);
+    let mut exponent = m - figures;
+    let (negative, mut significand) = round_to_exponent(n, d, exponent);
+    if len(&significand.to_string()) == figures + 1 {
+        significand /= 10u32;
+        exponent += 1;
+    }
+    (negative, significand, exponent)
+}
@@ -0,0 +1,541 @@
+//! Objects created by the `functools` module (see `modules/functools.rs`).
+//!
+//! - [`Partial`]: `functools.partial` objects, which call a function with some arguments
+//!   already given
+//! - [`LruCacheWrapper`]: the functions returned by `lru_cache()` and `cache()`, which
+//!   remember the results of calling the function they wrap
+//! - [`CmpToKey`]: the key functions returned by `cmp_to_key()`
+//!
+//! Calling any of them may call a Python function, so the VM calls them (see
+//! `bytecode/vm/functools.rs`); this module holds their state.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use super::{AttrCallResult, Dict, NamedTuple, PyTrait, Type, allocate_tuple};
+use crate::{
+    args::{ArgValues, KwargsValues},
+    builtins::is_callable,
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    resource::ResourceTracker,
+    value::{EitherStr, Value},
+};
+
+/// A `functools.partial` object: a function with some positional and keyword arguments
+/// already given.
+///
+/// Calling it calls `func` with `args` followed by the call's positional arguments, and
+/// `keywords` updated with the call's keyword arguments.
+///
+/// # Reference Counting
+/// Owns a reference to the function and to each argument.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Partial {
+    func: Value,
+    args: Vec<Value>,
+    /// Keyword arguments as `(name, value)` pairs, where each name is a str.
+    keywords: Vec<(Value, Value)>,
+}
+
+impl Partial {
+    /// Creates a partial object, taking ownership of all values.
+    #[must_use]
+    pub fn new(func: Value, args: Vec<Value>, keywords: Vec<(Value, Value)>) -> Self {
+        Self { func, args, keywords }
+    }
+
+    /// Creates a partial object from the `functools.partial(func, /, *args, **keywords)`
+    /// constructor call.
+    ///
+    /// As in CPython, a partial object of a partial object is flattened into one which
+    /// calls the innermost function.
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let (mut positional, kwargs) = args.into_parts();
+        let Some(func) = positional.next() else {
+            kwargs.drop_with_heap(heap);
+            return Err(ExcType::type_error("type 'partial' takes at least one argument"));
+        };
+        if !is_callable(&func, heap) {
+            func.drop_with_heap(heap);
+            positional.drop_with_heap(heap);
+            kwargs.drop_with_heap(heap);
+            return Err(ExcType::type_error("the first argument must be callable"));
+        }
+
+        let mut partial = if let Value::Ref(id) = func
+            && let HeapData::Partial(inner) = heap.get(id)
+        {
+            let flattened = Self::new(
+                inner.func.copy_for_extend(),
+                inner.args.iter().map(Value::copy_for_extend).collect(),
+                inner
+                    .keywords
+                    .iter()
+                    .map(|(key, value)| (key.copy_for_extend(), value.copy_for_extend()))
+                    .collect(),
+            );
+            for value in flattened.values() {
+                if let Value::Ref(id) = value {
+                    heap.inc_ref(*id);
+                }
+            }
+            func.drop_with_heap(heap);
+            flattened
+        } else {
+            Self::new(func, Vec::new(), Vec::new())
+        };
+        partial.args.extend(positional);
+        for (key, value) in kwargs {
+            partial.set_keyword(key, value, heap, interns);
+        }
+        Ok(Value::Ref(heap.allocate(HeapData::Partial(partial))?))
+    }
+
+    /// Returns the function called by this partial object.
+    #[must_use]
+    pub fn func(&self) -> &Value {
+        &self.func
+    }
+
+    /// Returns the function, then the positional arguments, then each keyword argument's
+    /// name and value, which are all the values this partial object holds.
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        std::iter::once(&self.func)
+            .chain(&self.args)
+            .chain(self.keywords.iter().flat_map(|(key, value)| [key, value]))
+    }
+
+    /// Returns whether this partial object holds any heap references.
+    #[must_use]
+    pub fn has_refs(&self) -> bool {
+        self.values().any(|value| matches!(value, Value::Ref(_)))
+    }
+
+    /// Returns the function to call and the arguments to call it with, for a call of this
+    /// partial object with `args`.
+    pub fn bind(
+        &self,
+        args: ArgValues,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<(Value, ArgValues)> {
+        let (positional, kwargs) = args.into_parts();
+        let mut all_args: Vec<Value> = self.args.iter().map(|arg| arg.clone_with_heap(heap)).collect();
+        all_args.extend(positional);
+
+        let kwargs = if self.keywords.is_empty() {
+            kwargs
+        } else {
+            let mut pairs: Vec<(Value, Value)> = self
+                .keywords
+                .iter()
+                .map(|(key, value)| (key.clone_with_heap(heap), value.clone_with_heap(heap)))
+                .collect();
+            pairs.extend(kwargs);
+            match Dict::from_pairs(pairs, heap, interns) {
+                Ok(dict) => KwargsValues::Dict(dict),
+                Err(e) => {
+                    all_args.drop_with_heap(heap);
+                    return Err(e);
+                }
+            }
+        };
+
+        let args = if kwargs.is_empty() {
+            ArgValues::from_vec(all_args)
+        } else if all_args.is_empty() {
+            ArgValues::Kwargs(kwargs)
+        } else {
+            ArgValues::ArgsKargs { args: all_args, kwargs }
+        };
+        Ok((self.func.clone_with_heap(heap), args))
+    }
+
+    /// Sets a keyword argument, replacing any with the same name.
+    fn set_keyword(&mut self, key: Value, value: Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) {
+        let name = key.py_str(heap, interns);
+        let existing = self
+            .keywords
+            .iter()
+            .position(|(existing, _)| existing.py_str(heap, interns) == name);
+        match existing {
+            Some(index) => {
+                key.drop_with_heap(heap);
+                std::mem::replace(&mut self.keywords[index].1, value).drop_with_heap(heap);
+            }
+            None => self.keywords.push((key, value)),
+        }
+    }
+}
+
+impl PyTrait for Partial {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Partial
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.args.len() * std::mem::size_of::<Value>()
+            + self.keywords.len() * std::mem::size_of::<(Value, Value)>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        // Partial objects compare by identity, which is handled at the Value level
+        false
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        self.func.py_dec_ref_ids(stack);
+        for arg in &mut self.args {
+            arg.py_dec_ref_ids(stack);
+        }
+        for (key, value) in &mut self.keywords {
+            key.py_dec_ref_ids(stack);
+            value.py_dec_ref_ids(stack);
+        }
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        heap: &Heap<impl ResourceTracker>,
+        heap_ids: &mut AHashSet<HeapId>,
+        interns: &Interns,
+    ) -> std::fmt::Result {
+        f.write_str("functools.partial(")?;
+        self.func.py_repr_fmt(f, heap, heap_ids, interns)?;
+        for arg in &self.args {
+            f.write_str(", ")?;
+            arg.py_repr_fmt(f, heap, heap_ids, interns)?;
+        }
+        for (key, value) in &self.keywords {
+            write!(f, ", {}=", key.py_str(heap, interns))?;
+            value.py_repr_fmt(f, heap, heap_ids, interns)?;
+        }
+        f.write_char(')')
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = match StaticStrings::from_string_id(attr_id) {
+            Some(StaticStrings::Func) => self.func.clone_with_heap(heap),
+            Some(StaticStrings::Args) => {
+                let args = self.args.iter().map(|arg| arg.clone_with_heap(heap)).collect();
+                allocate_tuple(args, heap)?
+            }
+            Some(StaticStrings::Keywords) => {
+                let pairs = self
+                    .keywords
+                    .iter()
+                    .map(|(key, value)| (key.clone_with_heap(heap), value.clone_with_heap(heap)))
+                    .collect();
+                let dict = Dict::from_pairs(pairs, heap, interns)?;
+                Value::Ref(heap.allocate(HeapData::Dict(dict))?)
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(AttrCallResult::Value(value)))
+    }
+}
+
+/// The function returned by `functools.lru_cache()` and `functools.cache()`, which
+/// remembers the results of calling the function it wraps.
+///
+/// Results are kept in a dict keyed by the call's arguments. With a `maxsize`, the dict is
+/// kept in order of use, so the least recently used result is the first one, and that
+/// result is discarded when the cache grows beyond it. Both take O(1) time, as the dict
+/// moves and pops its entries without shifting the others.
+///
+/// # Reference Counting
+/// Owns a reference to the function and to each key and result in the cache.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct LruCacheWrapper {
+    func: Value,
+    /// The most results to keep, or `None` for no limit.
+    maxsize: Option<usize>,
+    cache: Dict,
+    hits: usize,
+    misses: usize,
+}
+
+impl LruCacheWrapper {
+    /// Creates a wrapper of `func` with an empty cache, taking ownership of `func`.
+    #[must_use]
+    pub fn new(func: Value, maxsize: Option<usize>) -> Self {
+        Self {
+            func,
+            maxsize,
+            cache: Dict::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the wrapped function.
+    #[must_use]
+    pub fn func(&self) -> &Value {
+        &self.func
+    }
+
+    /// Returns the wrapped function, then each key and result in the cache, which are all
+    /// the values this wrapper holds.
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        std::iter::once(&self.func).chain(self.cache.iter().flat_map(|(key, value)| [key, value]))
+    }
+
+    /// Returns whether this wrapper holds any heap references.
+    #[must_use]
+    pub fn has_refs(&self) -> bool {
+        matches!(self.func, Value::Ref(_)) || self.cache.has_refs()
+    }
+
+    /// Looks up the result of a call with the arguments making up `key`, counting a hit or
+    /// a miss.
+    ///
+    /// With a `maxsize`, a result found is moved to the end of the cache, as the most
+    /// recently used. Like in CPython, unbounded caches aren't kept in order of use.
+    ///
+    /// # Errors
+    /// Returns `TypeError` if `key` is unhashable.
+    pub fn lookup(
+        &mut self,
+        key: &Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Option<Value>> {
+        let Some(found) = self.cache.get(key, heap, interns)?.map(Value::copy_for_extend) else {
+            self.misses += 1;
+            return Ok(None);
+        };
+        self.hits += 1;
+        if self.maxsize.is_some() {
+            self.cache.move_to_end(key, true, heap, interns)?;
+        }
+        if let Value::Ref(id) = &found {
+            heap.inc_ref(*id);
+        }
+        Ok(Some(found))
+    }
+
+    /// Stores the result of a call, discarding the least recently used result if the
+    /// cache has grown beyond `maxsize`.
+    ///
+    /// Takes ownership of `key` and `result`.
+    pub fn store(
+        &mut self,
+        key: Value,
+        result: Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<()> {
+        if self.maxsize == Some(0) {
+            key.drop_with_heap(heap);
+            result.drop_with_heap(heap);
+            return Ok(());
+        }
+        if let Some(old) = self.cache.set(key, result, heap, interns)? {
+            old.drop_with_heap(heap);
+        }
+        if self.maxsize.is_some_and(|maxsize| self.cache.len() > maxsize)
+            && let Some((key, result)) = self.cache.pop_first()
+        {
+            key.drop_with_heap(heap);
+            result.drop_with_heap(heap);
+        }
+        Ok(())
+    }
+
+    /// Returns the `cache_info()` named tuple of hits, misses, maxsize and current size.
+    fn cache_info(&self, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        let field_names = [
+            StaticStrings::Hits,
+            StaticStrings::Misses,
+            StaticStrings::Maxsize,
+            StaticStrings::Currsize,
+        ]
+        .into_iter()
+        .map(EitherStr::from)
+        .collect();
+        let maxsize = self
+            .maxsize
+            .map_or(Value::None, |maxsize| Value::Int(count_value(maxsize)));
+        let items = vec![
+            Value::Int(count_value(self.hits)),
+            Value::Int(count_value(self.misses)),
+            maxsize,
+            Value::Int(count_value(self.cache.len())),
+        ];
+        let info = NamedTuple::new(StaticStrings::CacheInfoClass, field_names, items);
+        Ok(Value::Ref(heap.allocate(HeapData::NamedTuple(info))?))
+    }
+
+    /// Empties the cache and resets its statistics.
+    fn cache_clear(&mut self, heap: &mut Heap<impl ResourceTracker>) {
+        let cache = std::mem::take(&mut self.cache);
+        for (key, result) in cache {
+            key.drop_with_heap(heap);
+            result.drop_with_heap(heap);
+        }
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
+
+impl PyTrait for LruCacheWrapper {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::LruCacheWrapper
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.cache.py_estimate_size()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        // Wrappers compare by identity, which is handled at the Value level
+        false
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        self.func.py_dec_ref_ids(stack);
+        self.cache.py_dec_ref_ids(stack);
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        f.write_str("<functools._lru_cache_wrapper object>")
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        match attr.static_string() {
+            Some(StaticStrings::CacheInfo) => {
+                args.check_zero_args("cache_info", heap)?;
+                self.cache_info(heap)
+            }
+            Some(StaticStrings::CacheClear) => {
+                args.check_zero_args("cache_clear", heap)?;
+                self.cache_clear(heap);
+                Ok(Value::None)
+            }
+            _ => {
+                args.drop_with_heap(heap);
+                Err(ExcType::attribute_error(Type::LruCacheWrapper, attr.as_str(interns)))
+            }
+        }
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        if attr_id == StaticStrings::DunderWrapped {
+            Ok(Some(AttrCallResult::Value(self.func.clone_with_heap(heap))))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// The key function returned by `functools.cmp_to_key()`.
+///
+/// `sorted()`, `min()`, `max()` and `list.sort()` recognise it and order their items by
+/// calling the comparison function on pairs of items, rather than calling the key
+/// function on each item. It can't be called directly.
+///
+/// # Reference Counting
+/// Owns a reference to the comparison function.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct CmpToKey {
+    cmp: Value,
+}
+
+impl CmpToKey {
+    /// Creates a key function for the comparison function `cmp`, taking ownership of it.
+    #[must_use]
+    pub fn new(cmp: Value) -> Self {
+        Self { cmp }
+    }
+
+    /// Returns the comparison function.
+    #[must_use]
+    pub fn cmp(&self) -> &Value {
+        &self.cmp
+    }
+}
+
+impl PyTrait for CmpToKey {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::CmpToKey
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        // Key functions compare by identity, which is handled at the Value level
+        false
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        self.cmp.py_dec_ref_ids(stack);
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        f.write_str("<functools.KeyWrapper object>")
+    }
+}
+
+/// Converts a count to a Python int.
+fn count_value(count: usize) -> i64 {
+    i64::try_from(count).unwrap_or(i64::MAX)
+}
@@ -0,0 +1,123 @@
+//! Generator objects created by calling generator functions.
+//!
+//! A generator holds the suspended state of a generator function's frame: its namespace,
+//! the instruction pointer to resume at, and the operand and exception stack segments
+//! that were live at the last `yield`. The VM moves this state back into a real frame
+//! when the generator is resumed and out again when it yields, so suspended generators
+//! are plain heap data and survive snapshot serialization.
+
+use crate::{heap::HeapId, intern::FunctionId, value::Value};
+
+/// Generator execution state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) enum GeneratorState {
+    /// Generator has been created but its body has not started running.
+    Created,
+    /// Generator is paused at a `yield`.
+    Suspended,
+    /// Generator's frame is currently on the VM's frame stack.
+    Running,
+    /// Generator has returned or raised, it cannot be resumed again.
+    Completed,
+}
+
+/// A generator object representing a generator function call result.
+///
+/// Created when a function containing `yield` is called. Argument binding happens at
+/// call time, exactly like for coroutines; the body only starts running when the
+/// generator is first advanced.
+///
+/// # Namespace Layout
+///
+/// The `namespace` vector has the layout of the function's namespace:
+/// ```text
+/// [params...][cell_vars...][free_vars...][locals...]
+/// ```
+/// While the generator is running, its namespace is registered with the VM's `Namespaces`
+/// and `namespace`, `stack` and `exc_stack` are empty.
+///
+/// # Reference Counting
+/// The generator owns every value in `namespace`, `stack` and `exc_stack`. Like frames,
+/// `frame_cells` does not own its cells: the namespace slots hold those references.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Generator {
+    /// The generator function to execute.
+    pub func_id: FunctionId,
+    /// Namespace values of the suspended frame.
+    pub namespace: Vec<Value>,
+    /// HeapIds of the cells visible to the frame.
+    pub frame_cells: Vec<HeapId>,
+    /// Operand stack segment of the suspended frame (e.g. the iterators of enclosing `for` loops).
+    pub stack: Vec<Value>,
+    /// Exceptions being handled by the suspended frame when it yielded.
+    pub exc_stack: Vec<Value>,
+    /// Instruction pointer to resume execution at.
+    pub ip: usize,
+    /// Current execution state.
+    pub state: GeneratorState,
+}
+
+impl Generator {
+    /// Creates a new generator for a generator function call.
+    ///
+    /// # Arguments
+    /// * `func_id` - The generator function to execute
+    /// * `namespace` - Pre-bound namespace with parameters and captured variables
+    /// * `frame_cells` - HeapIds of the cells visible to the frame
+    pub fn new(func_id: FunctionId, namespace: Vec<Value>, frame_cells: Vec<HeapId>) -> Self {
+        Self {
+            func_id,
+            namespace,
+            frame_cells,
+            stack: Vec::new(),
+            exc_stack: Vec::new(),
+            ip: 0,
+            state: GeneratorState::Created,
+        }
+    }
+
+    /// Returns true if the generator has finished and can no longer produce values.
+    #[must_use]
+    pub fn is_completed(&self) -> bool {
+        self.state == GeneratorState::Completed
+    }
+
+    /// Returns true if any of the owned values is a heap reference.
+    #[must_use]
+    pub fn has_refs(&self) -> bool {
+        self.namespace
+            .iter()
+            .chain(&self.stack)
+            .chain(&self.exc_stack)
+            .any(|v| matches!(v, Value::Ref(_)))
+    }
+
+    /// Pushes the HeapIds of all owned references onto `stack` for ref-count decrementing.
+    pub fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        for value in self
+            .namespace
+            .iter_mut()
+            .chain(&mut self.stack)
+            .chain(&mut self.exc_stack)
+        {
+            value.py_dec_ref_ids(stack);
+        }
+    }
+
+    /// Returns the HeapIds of all references held by the generator, for GC tracing.
+    pub fn ref_ids(&self) -> impl Iterator<Item = HeapId> + '_ {
+        self.namespace
+            .iter()
+            .chain(&self.stack)
+            .chain(&self.exc_stack)
+            .filter_map(|v| if let Value::Ref(id) = v { Some(*id) } else { None })
+    }
+
+    /// Estimates the memory used by the generator.
+    #[must_use]
+    pub fn estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + (self.namespace.len() + self.stack.len() + self.exc_stack.len()) * std::mem::size_of::<Value>()
+            + self.frame_cells.len() * std::mem::size_of::<HeapId>()
+    }
+}
@@ -0,0 +1,718 @@
+//! The hash objects of the `hashlib` module (see `modules/hashlib.rs`).
+//!
+//! [`Hash`] implements MD5, SHA-1, SHA-256 and SHA-512 in plain Rust, so digests match
+//! CPython's without depending on a system library. Hashes are plain data, so they are
+//! saved with the heap like any other value.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use super::{Bytes, PyTrait, Type};
+use crate::{
+    args::ArgValues,
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    resource::{ResourceError, ResourceTracker},
+    types::{AttrCallResult, str::allocate_string},
+    value::{EitherStr, Value},
+};
+
+/// Number of bytes hashed between checks of the time limit.
+const UPDATE_CHUNK: usize = 64 * 1024;
+
+/// A hash algorithm of the `hashlib` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) enum Algorithm {
+    Md5,
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl Algorithm {
+    /// The algorithm's name, as `hashlib.new()` takes it and `HASH.name` returns it.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Md5 => "md5",
+            Self::Sha1 => "sha1",
+            Self::Sha256 => "sha256",
+            Self::Sha512 => "sha512",
+        }
+    }
+
+    /// Looks up an algorithm by name, ignoring case like `hashlib.new()`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Md5, Self::Sha1, Self::Sha256, Self::Sha512]
+            .into_iter()
+            .find(|algorithm| algorithm.name().eq_ignore_ascii_case(name))
+    }
+
+    /// The size of the digest in bytes.
+    #[must_use]
+    pub fn digest_size(self) -> i64 {
+        match self {
+            Self::Md5 => 16,
+            Self::Sha1 => 20,
+            Self::Sha256 => 32,
+            Self::Sha512 => 64,
+        }
+    }
+
+    /// The size of the blocks the algorithm compresses, in bytes.
+    #[must_use]
+    pub fn block_size(self) -> usize {
+        match self {
+            Self::Md5 | Self::Sha1 | Self::Sha256 => 64,
+            Self::Sha512 => 128,
+        }
+    }
+}
+
+/// A `hashlib` hash object: the chaining values of the algorithm, the bytes of the block
+/// not yet compressed and the number of bytes hashed so far.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Hash {
+    algorithm: Algorithm,
+    /// Chaining values: 4 words for MD5, 5 for SHA-1 and 8 for SHA-256, all 32-bit,
+    /// and 8 64-bit words for SHA-512.
+    words: Vec<u64>,
+    buffer: Vec<u8>,
+    length: u128,
+}
+
+impl Hash {
+    /// Creates a hash of no data.
+    #[must_use]
+    pub fn new(algorithm: Algorithm) -> Self {
+        let words = match algorithm {
+            Algorithm::Md5 => vec![0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476],
+            Algorithm::Sha1 => vec![0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476, 0xc3d2_e1f0],
+            Algorithm::Sha256 => SHA256_INIT.iter().map(|w| u64::from(*w)).collect(),
+            Algorithm::Sha512 => SHA512_INIT.to_vec(),
+        };
+        Self {
+            algorithm,
+            words,
+            buffer: Vec::with_capacity(algorithm.block_size()),
+            length: 0,
+        }
+    }
+
+    /// Hashes `data` in chunks, checking the time limit between them.
+    pub fn update_checked(&mut self, data: &[u8], tracker: &mut impl ResourceTracker) -> Result<(), ResourceError> {
+        for chunk in data.chunks(UPDATE_CHUNK) {
+            tracker.check_time()?;
+            self.update(chunk);
+        }
+        Ok(())
+    }
+
+    /// Hashes `data`.
+    fn update(&mut self, mut data: &[u8]) {
+        let block_size = self.algorithm.block_size();
+        self.length += data.len() as u128;
+        if !self.buffer.is_empty() {
+            let take = (block_size - self.buffer.len()).min(data.len());
+            self.buffer.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.buffer.len() < block_size {
+                return;
+            }
+            let block = std::mem::take(&mut self.buffer);
+            self.compress(&block);
+            self.buffer = block;
+            self.buffer.clear();
+        }
+        let mut blocks = data.chunks_exact(block_size);
+        for block in &mut blocks {
+            self.compress(block);
+        }
+        self.buffer.extend_from_slice(blocks.remainder());
+    }
+
+    /// Returns the digest of the bytes hashed so far, leaving the hash unchanged.
+    #[must_use]
+    #[expect(clippy::cast_possible_truncation, reason = "32-bit words are stored in u64s")]
+    pub fn digest(&self) -> Vec<u8> {
+        let mut digest = self.clone();
+        let block_size = self.algorithm.block_size();
+        // The length field is 8 bytes, or 16 for SHA-512
+        let length_size = block_size / 8;
+        let used = (self.buffer.len() + 1) % block_size;
+        let zeros = (2 * block_size - length_size - used) % block_size;
+        let mut padding = vec![0x80];
+        padding.resize(1 + zeros, 0);
+        let bits = self.length.wrapping_mul(8);
+        match self.algorithm {
+            Algorithm::Md5 => padding.extend_from_slice(&(bits as u64).to_le_bytes()),
+            Algorithm::Sha1 | Algorithm::Sha256 => padding.extend_from_slice(&(bits as u64).to_be_bytes()),
+            Algorithm::Sha512 => padding.extend_from_slice(&bits.to_be_bytes()),
+        }
+        digest.update(&padding);
+        debug_assert!(digest.buffer.is_empty());
+        match self.algorithm {
+            Algorithm::Md5 => digest.words.iter().flat_map(|w| (*w as u32).to_le_bytes()).collect(),
+            Algorithm::Sha1 | Algorithm::Sha256 => {
+                digest.words.iter().flat_map(|w| (*w as u32).to_be_bytes()).collect()
+            }
+            Algorithm::Sha512 => digest.words.iter().flat_map(|w| w.to_be_bytes()).collect(),
+        }
+    }
+
+    /// Returns the digest as a string of lowercase hex digits.
+    #[must_use]
+    pub fn hexdigest(&self) -> String {
+        let digest = self.digest();
+        let mut hex = String::with_capacity(2 * digest.len());
+        for byte in digest {
+            let _ = write!(hex, "{byte:02x}");
+        }
+        hex
+    }
+
+    fn compress(&mut self, block: &[u8]) {
+        match self.algorithm {
+            Algorithm::Md5 => md5_compress(&mut self.words, block),
+            Algorithm::Sha1 => sha1_compress(&mut self.words, block),
+            Algorithm::Sha256 => sha256_compress(&mut self.words, block),
+            Algorithm::Sha512 => sha512_compress(&mut self.words, block),
+        }
+    }
+}
+
+impl PyTrait for Hash {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Hash
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.words.len() * std::mem::size_of::<u64>() + self.buffer.capacity()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        // Hashes compare by identity, which is handled at the Value level
+        false
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {}
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        write!(f, "<{} _hashlib.HASH object>", self.algorithm.name())
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = match StaticStrings::from_string_id(attr_id) {
+            Some(StaticStrings::Name) => allocate_string(self.algorithm.name().to_owned(), heap)?,
+            Some(StaticStrings::DigestSize) => Value::Int(self.algorithm.digest_size()),
+            Some(StaticStrings::BlockSize) => {
+                Value::Int(i64::try_from(self.algorithm.block_size()).expect("block sizes are small"))
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(AttrCallResult::Value(value)))
+    }
+}
+
+/// Calls a method of the hash object `hash_id`.
+///
+/// The hash stays on the heap while the arguments are read, so `h.update(h)` raises the
+/// usual `TypeError`.
+pub(crate) fn call_hash_method(
+    heap: &mut Heap<impl ResourceTracker>,
+    hash_id: HeapId,
+    attr: &EitherStr,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<Value> {
+    match attr.static_string() {
+        Some(StaticStrings::Update) => {
+            let data = args.get_one_arg("HASH.update", heap)?;
+            let bytes = hash_data(&data, heap, interns);
+            data.drop_with_heap(heap);
+            for chunk in bytes?.chunks(UPDATE_CHUNK) {
+                heap.tracker_mut().check_time()?;
+                hash_mut(heap, hash_id).update(chunk);
+            }
+            Ok(Value::None)
+        }
+        Some(StaticStrings::Digest) => {
+            args.check_zero_args("HASH.digest", heap)?;
+            let digest = hash_ref(heap, hash_id).digest();
+            Ok(Value::Ref(heap.allocate(HeapData::Bytes(Bytes::new(digest)))?))
+        }
+        Some(StaticStrings::Hexdigest) => {
+            args.check_zero_args("HASH.hexdigest", heap)?;
+            let hex = hash_ref(heap, hash_id).hexdigest();
+            allocate_string(hex, heap)
+        }
+        Some(StaticStrings::Copy) => {
+            args.check_zero_args("HASH.copy", heap)?;
+            let copy = hash_ref(heap, hash_id).clone();
+            Ok(Value::Ref(heap.allocate(HeapData::Hash(copy))?))
+        }
+        _ => {
+            args.drop_with_heap(heap);
+            Err(ExcType::attribute_error(Type::Hash, attr.as_str(interns)))
+        }
+    }
+}
+
+/// Returns the bytes of a value passed to a hash: a `bytes` or `bytearray`.
+///
+/// # Errors
+/// Returns `TypeError` for a `str`, which must be encoded first, and for any other type.
+pub(crate) fn hash_data(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Vec<u8>> {
+    let bytes = match value {
+        Value::InternBytes(id) => Some(interns.get_bytes(*id)),
+        Value::Ref(id) => heap.get(*id).as_bytes_like(),
+        _ => None,
+    };
+    match bytes {
+        Some(bytes) => Ok(bytes.to_vec()),
+        None if value.py_type(heap) == Type::Str => Err(ExcType::type_error("Strings must be encoded before hashing")),
+        None => Err(ExcType::type_error("object supporting the buffer API required")),
+    }
+}
+
+fn hash_ref(heap: &Heap<impl ResourceTracker>, hash_id: HeapId) -> &Hash {
+    match heap.get(hash_id) {
+        HeapData::Hash(hash) => hash,
+        _ => unreachable!("hash methods are only called for hashes"),
+    }
+}
+
+fn hash_mut(heap: &mut Heap<impl ResourceTracker>, hash_id: HeapId) -> &mut Hash {
+    match heap.get_mut(hash_id) {
+        HeapData::Hash(hash) => hash,
+        _ => unreachable!("hash methods are only called for hashes"),
+    }
+}
+
+/// The rotation of each MD5 step, by round and step within the round.
+const MD5_SHIFTS: [u32; 16] = [7, 12, 17, 22, 5, 9, 14, 20, 4, 11, 16, 23, 6, 10, 15, 21];
+
+/// `floor(abs(sin(i + 1)) * 2**32)` for each of the 64 MD5 steps.
+const MD5_K: [u32; 64] = [
+    0xd76a_a478,
+    0xe8c7_b756,
+    0x2420_70db,
+    0xc1bd_ceee,
+    0xf57c_0faf,
+    0x4787_c62a,
+    0xa830_4613,
+    0xfd46_9501,
+    0x6980_98d8,
+    0x8b44_f7af,
+    0xffff_5bb1,
+    0x895c_d7be,
+    0x6b90_1122,
+    0xfd98_7193,
+    0xa679_438e,
+    0x49b4_0821,
+    0xf61e_2562,
+    0xc040_b340,
+    0x265e_5a51,
+    0xe9b6_c7aa,
+    0xd62f_105d,
+    0x0244_1453,
+    0xd8a1_e681,
+    0xe7d3_fbc8,
+    0x21e1_cde6,
+    0xc337_07d6,
+    0xf4d5_0d87,
+    0x455a_14ed,
+    0xa9e3_e905,
+    0xfcef_a3f8,
+    0x676f_02d9,
+    0x8d2a_4c8a,
+    0xfffa_3942,
+    0x8771_f681,
+    0x6d9d_6122,
+    0xfde5_380c,
+    0xa4be_ea44,
+    0x4bde_cfa9,
+    0xf6bb_4b60,
+    0xbebf_bc70,
+    0x289b_7ec6,
+    0xeaa1_27fa,
+    0xd4ef_3085,
+    0x0488_1d05,
+    0xd9d4_d039,
+    0xe6db_99e5,
+    0x1fa2_7cf8,
+    0xc4ac_5665,
+    0xf429_2244,
+    0x432a_ff97,
+    0xab94_23a7,
+    0xfc93_a039,
+    0x655b_59c3,
+    0x8f0c_cc92,
+    0xffef_f47d,
+    0x8584_5dd1,
+    0x6fa8_7e4f,
+    0xfe2c_e6e0,
+    0xa301_4314,
+    0x4e08_11a1,
+    0xf753_7e82,
+    0xbd3a_f235,
+    0x2ad7_d2bb,
+    0xeb86_d391,
+];
+
+#[expect(
+    clippy::many_single_char_names,
+    reason = "the variables are named as in the specification"
+)]
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "MD5 words are stored in u64s but are 32-bit"
+)]
+fn md5_compress(words: &mut [u64], block: &[u8]) {
+    let m: Vec<u32> = block
+        .chunks_exact(4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    let [mut a, mut b, mut c, mut d] = [words[0] as u32, words[1] as u32, words[2] as u32, words[3] as u32];
+    for i in 0..64 {
+        let (f, g) = match i / 16 {
+            0 => ((b & c) | (!b & d), i),
+            1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+            2 => (b ^ c ^ d, (3 * i + 5) % 16),
+            _ => (c ^ (b | !d), (7 * i) % 16),
+        };
+        let rotated = a
+            .wrapping_add(f)
+            .wrapping_add(MD5_K[i])
+            .wrapping_add(m[g])
+            .rotate_left(MD5_SHIFTS[(i / 16) * 4 + i % 4]);
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(rotated);
+    }
+    for (word, value) in words.iter_mut().zip([a, b, c, d]) {
+        *word = u64::from((*word as u32).wrapping_add(value));
+    }
+}
+
+#[expect(
+    clippy::many_single_char_names,
+    reason = "the variables are named as in the specification"
+)]
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "SHA-1 words are stored in u64s but are 32-bit"
+)]
+fn sha1_compress(words: &mut [u64], block: &[u8]) {
+    let mut w = [0u32; 80];
+    for (i, b) in block.chunks_exact(4).enumerate() {
+        w[i] = u32::from_be_bytes([b[0], b[1], b[2], b[3]]);
+    }
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+    let mut h: Vec<u32> = words.iter().map(|word| *word as u32).collect();
+    let [mut a, mut b, mut c, mut d, mut e] = [h[0], h[1], h[2], h[3], h[4]];
+    for (i, wi) in w.iter().enumerate() {
+        let (f, k) = match i / 20 {
+            0 => ((b & c) | (!b & d), 0x5a82_7999),
+            1 => (b ^ c ^ d, 0x6ed9_eba1),
+            2 => ((b & c) | (b & d) | (c & d), 0x8f1b_bcdc),
+            _ => (b ^ c ^ d, 0xca62_c1d6),
+        };
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(*wi);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+    for (word, value) in h.iter_mut().zip([a, b, c, d, e]) {
+        *word = word.wrapping_add(value);
+    }
+    for (word, value) in words.iter_mut().zip(h) {
+        *word = u64::from(value);
+    }
+}
+
+const SHA256_INIT: [u32; 8] = [
+    0x6a09_e667,
+    0xbb67_ae85,
+    0x3c6e_f372,
+    0xa54f_f53a,
+    0x510e_527f,
+    0x9b05_688c,
+    0x1f83_d9ab,
+    0x5be0_cd19,
+];
+
+const SHA256_K: [u32; 64] = [
+    0x428a_2f98,
+    0x7137_4491,
+    0xb5c0_fbcf,
+    0xe9b5_dba5,
+    0x3956_c25b,
+    0x59f1_11f1,
+    0x923f_82a4,
+    0xab1c_5ed5,
+    0xd807_aa98,
+    0x1283_5b01,
+    0x2431_85be,
+    0x550c_7dc3,
+    0x72be_5d74,
+    0x80de_b1fe,
+    0x9bdc_06a7,
+    0xc19b_f174,
+    0xe49b_69c1,
+    0xefbe_4786,
+    0x0fc1_9dc6,
+    0x240c_a1cc,
+    0x2de9_2c6f,
+    0x4a74_84aa,
+    0x5cb0_a9dc,
+    0x76f9_88da,
+    0x983e_5152,
+    0xa831_c66d,
+    0xb003_27c8,
+    0xbf59_7fc7,
+    0xc6e0_0bf3,
+    0xd5a7_9147,
+    0x06ca_6351,
+    0x1429_2967,
+    0x27b7_0a85,
+    0x2e1b_2138,
+    0x4d2c_6dfc,
+    0x5338_0d13,
+    0x650a_7354,
+    0x766a_0abb,
+    0x81c2_c92e,
+    0x9272_2c85,
+    0xa2bf_e8a1,
+    0xa81a_664b,
+    0xc24b_8b70,
+    0xc76c_51a3,
+    0xd192_e819,
+    0xd699_0624,
+    0xf40e_3585,
+    0x106a_a070,
+    0x19a4_c116,
+    0x1e37_6c08,
+    0x2748_774c,
+    0x34b0_bcb5,
+    0x391c_0cb3,
+    0x4ed8_aa4a,
+    0x5b9c_ca4f,
+    0x682e_6ff3,
+    0x748f_82ee,
+    0x78a5_636f,
+    0x84c8_7814,
+    0x8cc7_0208,
+    0x90be_fffa,
+    0xa450_6ceb,
+    0xbef9_a3f7,
+    0xc671_78f2,
+];
+
+#[expect(
+    clippy::many_single_char_names,
+    reason = "the variables are named as in the specification"
+)]
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "SHA-256 words are stored in u64s but are 32-bit"
+)]
+fn sha256_compress(words: &mut [u64], block: &[u8]) {
+    let mut w = [0u32; 64];
+    for (i, b) in block.chunks_exact(4).enumerate() {
+        w[i] = u32::from_be_bytes([b[0], b[1], b[2], b[3]]);
+    }
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+    }
+    let mut h = [0u32; 8];
+    for (h, word) in h.iter_mut().zip(words.iter()) {
+        *h = *word as u32;
+    }
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+    for (k, wi) in SHA256_K.iter().zip(w) {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = hh.wrapping_add(s1).wrapping_add(ch).wrapping_add(*k).wrapping_add(wi);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+        hh = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+    for (word, value) in words.iter_mut().zip([a, b, c, d, e, f, g, hh]) {
+        *word = u64::from((*word as u32).wrapping_add(value));
+    }
+}
+
+const SHA512_INIT: [u64; 8] = [
+    0x6a09_e667_f3bc_c908,
+    0xbb67_ae85_84ca_a73b,
+    0x3c6e_f372_fe94_f82b,
+    0xa54f_f53a_5f1d_36f1,
+    0x510e_527f_ade6_82d1,
+    0x9b05_688c_2b3e_6c1f,
+    0x1f83_d9ab_fb41_bd6b,
+    0x5be0_cd19_137e_2179,
+];
+
+const SHA512_K: [u64; 80] = [
+    0x428a_2f98_d728_ae22,
+    0x7137_4491_23ef_65cd,
+    0xb5c0_fbcf_ec4d_3b2f,
+    0xe9b5_dba5_8189_dbbc,
+    0x3956_c25b_f348_b538,
+    0x59f1_11f1_b605_d019,
+    0x923f_82a4_af19_4f9b,
+    0xab1c_5ed5_da6d_8118,
+    0xd807_aa98_a303_0242,
+    0x1283_5b01_4570_6fbe,
+    0x2431_85be_4ee4_b28c,
+    0x550c_7dc3_d5ff_b4e2,
+    0x72be_5d74_f27b_896f,
+    0x80de_b1fe_3b16_96b1,
+    0x9bdc_06a7_25c7_1235,
+    0xc19b_f174_cf69_2694,
+    0xe49b_69c1_9ef1_4ad2,
+    0xefbe_4786_384f_25e3,
+    0x0fc1_9dc6_8b8c_d5b5,
+    0x240c_a1cc_77ac_9c65,
+    0x2de9_2c6f_592b_0275,
+    0x4a74_84aa_6ea6_e483,
+    0x5cb0_a9dc_bd41_fbd4,
+    0x76f9_88da_8311_53b5,
+    0x983e_5152_ee66_dfab,
+    0xa831_c66d_2db4_3210,
+    0xb003_27c8_98fb_213f,
+    0xbf59_7fc7_beef_0ee4,
+    0xc6e0_0bf3_3da8_8fc2,
+    0xd5a7_9147_930a_a725,
+    0x06ca_6351_e003_826f,
+    0x1429_2967_0a0e_6e70,
+    0x27b7_0a85_46d2_2ffc,
+    0x2e1b_2138_5c26_c926,
+    0x4d2c_6dfc_5ac4_2aed,
+    0x5338_0d13_9d95_b3df,
+    0x650a_7354_8baf_63de,
+    0x766a_0abb_3c77_b2a8,
+    0x81c2_c92e_47ed_aee6,
+    0x9272_2c85_1482_353b,
+    0xa2bf_e8a1_4cf1_0364,
+    0xa81a_664b_bc42_3001,
+    0xc24b_8b70_d0f8_9791,
+    0xc76c_51a3_0654_be30,
+    0xd192_e819_d6ef_5218,
+    0xd699_0624_5565_a910,
+    0xf40e_3585_5771_202a,
+    0x106a_a070_32bb_d1b8,
+    0x19a4_c116_b8d2_d0c8,
+    0x1e37_6c08_5141_ab53,
+    0x2748_774c_df8e_eb99,
+    0x34b0_bcb5_e19b_48a8,
+    0x391c_0cb3_c5c9_5a63,
+    0x4ed8_aa4a_e341_8acb,
+    0x5b9c_ca4f_7763_e373,
+    0x682e_6ff3_d6b2_b8a3,
+    0x748f_82ee_5def_b2fc,
+    0x78a5_636f_4317_2f60,
+    0x84c8_7814_a1f0_ab72,
+    0x8cc7_0208_1a64_39ec,
+    0x90be_fffa_2363_1e28,
+    0xa450_6ceb_de82_bde9,
+    0xbef9_a3f7_b2c6_7915,
+    0xc671_78f2_e372_532b,
+    0xca27_3ece_ea26_619c,
+    0xd186_b8c7_21c0_c207,
+    0xeada_7dd6_cde0_eb1e,
+    0xf57d_4f7f_ee6e_d178,
+    0x06f0_67aa_7217_6fba,
+    0x0a63_7dc5_a2c8_98a6,
+    0x113f_9804_bef9_0dae,
+    0x1b71_0b35_131c_471b,
+    0x28db_77f5_2304_7d84,
+    0x32ca_ab7b_40c7_2493,
+    0x3c9e_be0a_15c9_bebc,
+    0x431d_67c4_9c10_0d4c,
+    0x4cc5_d4be_cb3e_42b6,
+    0x597f_299c_fc65_7e2a,
+    0x5fcb_6fab_3ad6_faec,
+    0x6c44_198c_4a47_5817,
+];
+
+#[expect(
+    clippy::many_single_char_names,
+    reason = "the variables are named as in the specification"
+)]
+fn sha512_compress(words: &mut [u64], block: &[u8]) {
+    let mut w = [0u64; 80];
+    for (i, b) in block.chunks_exact(8).enumerate() {
+        w[i] = u64::from_be_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]);
+    }
+    for i in 16..80 {
+        let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+        let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+        w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+    }
+    let mut h = [0u64; 8];
+    h.copy_from_slice(words);
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+    for (k, wi) in SHA512_K.iter().zip(w) {
+        let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = hh.wrapping_add(s1).wrapping_add(ch).wrapping_add(*k).wrapping_add(wi);
+        let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+        hh = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+    for (word, value) in words.iter_mut().zip([a, b, c, d, e, f, g, hh]) {
+        *word = word.wrapping_add(value);
+    }
+}
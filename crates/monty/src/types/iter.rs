@@ -18,7 +18,7 @@
 //! - Single `get_mut()` call to compute value and advance index
 //! - No additional heap access needed during iteration
 //!
-//! **Multi-phase approach** for complex iterators (IterStr, HeapRef):
+//! **Multi-phase approach** for complex iterators (IterStr, HeapRef, DictView):
 //! 1. `iter_state()` - reads current state without mutation, returns `Option<IterState>`
 //! 2. Get the value (may access other heap objects like strings or containers)
 //! 3. `advance()` - updates the index after the caller has done its work
@@ -26,17 +26,28 @@
 //! This allows `advance_on_heap()` to coordinate access without extracting
 //! the iterator from the heap (avoiding `std::mem::replace` overhead).
 //!
+//! Iterating over an iterator on the heap, like `list(iter(x))`, advances that
+//! iterator in place through `advance_on_heap()`.
+//!
 //! ## Builtin Support
 //!
 //! The `iterator_next()` helper implements the `next()` builtin.
+//!
+//! Iterators created by `map()`, `filter()`, `iter(callable, sentinel)` and the `itertools`
+//! module call Python functions or advance other iterators to produce their values, so
+//! they can only be advanced by the VM. They are `MontyIter`s holding a [`CallIter`]: the
+//! callable, their inputs and any state which changes as they advance. Generators and
+//! these iterators reaching `MontyIter::new()` raise an internal error: builtins and
+//! methods consuming them get a list of their values from the VM instead.
 
 use crate::{
     args::ArgValues,
-    exception_private::{ExcType, RunResult},
+    exception_private::{ExcType, RunError, RunResult},
     heap::{DropWithHeap, Heap, HeapData, HeapId},
     intern::{BytesId, Interns, StringId},
+    modules::itertools::ItertoolsIter,
     resource::ResourceTracker,
-    types::{PyTrait, Range, str::allocate_char},
+    types::{DictViewKind, PyTrait, Range, dict_view::view_item_at, r#enum, str::allocate_char},
     value::Value,
 };
 
@@ -61,16 +72,16 @@ impl MontyIter {
     ///
     /// - `iter(iterable)` - Returns an iterator for the iterable. If the argument is
     ///   already an iterator, returns the same object.
-    /// - `iter(callable, sentinel)` - Not yet supported.
+    /// - `iter(callable, sentinel)` - Calls a function, so the VM intercepts it before we get here.
     pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
         let (iterable, sentinel) = args.get_one_two_args("iter", heap)?;
 
         if let Some(s) = sentinel {
-            // Two-argument form: iter(callable, sentinel)
-            // This is the sentinel iteration protocol, not yet supported
             iterable.drop_with_heap(heap);
             s.drop_with_heap(heap);
-            return Err(ExcType::type_error("iter(callable, sentinel) is not yet supported"));
+            return Err(RunError::internal(
+                "iter(callable, sentinel) must be called through the VM",
+            ));
         }
 
         // Check if already an iterator - return self
@@ -89,10 +100,20 @@ impl MontyIter {
 
     /// Creates a new MontyIter from a Value.
     ///
-    /// Returns an error if the value is not iterable.
+    /// Returns an error if the value is not iterable, or an internal error for generators
+    /// and function-calling iterators, which only the VM can advance.
     /// For strings, copies the string content for byte-offset based iteration.
     /// For ranges, the data is copied so the heap reference is dropped immediately.
+    /// Enum classes are iterated through a tuple of their members.
     pub fn new(mut value: Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Self> {
+        // Enum classes iterate over their members
+        if let Value::Ref(id) = &value
+            && matches!(heap.get(*id), HeapData::ClassObject(cls) if cls.enum_info().is_some())
+        {
+            let members = r#enum::members_tuple(*id, heap, interns);
+            value.drop_with_heap(heap);
+            value = members?;
+        }
         if let Some(iter_value) = IterValue::new(&value, heap, interns) {
             // For Range, we copy next/step/len into ForIterValue::Range, so we don't need
             // to keep the heap object alive during iteration. Drop it immediately to avoid
@@ -108,36 +129,92 @@ impl MontyIter {
                 value,
             })
         } else {
-            let err = ExcType::type_error_not_iterable(value.py_type(heap));
+            let err = match &value {
+                Value::Ref(id) if matches!(heap.get(*id), HeapData::Iter(_) | HeapData::Generator(_)) => {
+                    call_iter_error()
+                }
+                _ => ExcType::type_error_not_iterable(value.py_type(heap)),
+            };
             value.drop_with_heap(heap);
             Err(err)
         }
     }
 
+    /// Creates an iterator which calls a Python function or advances other iterators to
+    /// produce each value.
+    pub(crate) fn new_calling(state: CallIter) -> Self {
+        Self {
+            index: 0,
+            iter_value: IterValue::Calling(state),
+            value: Value::None,
+        }
+    }
+
+    /// Returns the state of a function-calling iterator, or `None` for other iterators.
+    #[must_use]
+    pub(crate) fn call_state(&self) -> Option<&CallIter> {
+        match &self.iter_value {
+            IterValue::Calling(state) => Some(state),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable state of a function-calling iterator, or `None` for other iterators.
+    pub(crate) fn call_state_mut(&mut self) -> Option<&mut CallIter> {
+        match &mut self.iter_value {
+            IterValue::Calling(state) => Some(state),
+            _ => None,
+        }
+    }
+
     /// Drops the iterator and its held value properly.
     pub fn drop_with_heap(self, heap: &mut Heap<impl ResourceTracker>) {
         self.value.drop_with_heap(heap);
+        if let IterValue::Calling(state) = self.iter_value {
+            state.drop_with_heap(heap);
+        }
     }
 
     /// Collects HeapIds from this iterator for reference counting cleanup.
     pub fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
         self.value.py_dec_ref_ids(stack);
+        if let IterValue::Calling(state) = &mut self.iter_value {
+            state.py_dec_ref_ids(stack);
+        }
+    }
+
+    /// Estimates the memory used by the iterator, including the values held by an
+    /// `itertools` iterator.
+    pub fn estimate_size(&self) -> usize {
+        let held = match &self.iter_value {
+            IterValue::Calling(CallIter::Itertools(state)) => state.estimate_size(),
+            _ => 0,
+        };
+        std::mem::size_of::<Self>() + held
     }
 
-    /// Returns whether this iterator holds a heap reference (`Value::Ref`).
+    /// Returns whether this iterator holds a heap reference.
     ///
     /// Used during allocation to determine if this container could create cycles.
     #[inline]
     #[must_use]
     pub fn has_refs(&self) -> bool {
-        matches!(self.value, Value::Ref(_))
+        match &self.iter_value {
+            IterValue::Calling(state) => state.has_refs(),
+            _ => matches!(self.value, Value::Ref(_)),
+        }
     }
 
-    /// Returns a reference to the underlying value being iterated.
+    /// Pushes the HeapIds of the heap references held by the iterator onto `work_list`.
     ///
-    /// Used by GC to traverse heap references held by the iterator.
-    pub fn value(&self) -> &Value {
-        &self.value
+    /// Used by GC to traverse the iterator's references.
+    pub fn push_ref_ids(&self, work_list: &mut Vec<HeapId>) {
+        if let Value::Ref(id) = &self.value {
+            work_list.push(*id);
+        }
+        if let IterValue::Calling(state) = &self.iter_value {
+            state.push_ref_ids(work_list);
+        }
     }
 
     /// Returns the current iterator state without mutation.
@@ -150,9 +227,10 @@ impl MontyIter {
     fn iter_state(&self) -> Option<IterState> {
         match &self.iter_value {
             // Range, InternBytes, and ASCII IterStr are handled by try_advance_simple() fast path
-            IterValue::Range { .. } | IterValue::InternBytes { .. } => {
-                unreachable!("Range and InternBytes use fast path, not iter_state")
+            IterValue::Range { .. } | IterValue::InternBytes { .. } | IterValue::Calling(_) => {
+                unreachable!("Range, InternBytes and Calling use fast path, not iter_state")
             }
+            IterValue::Iterator { iter_id } => Some(IterState::Iterator { iter_id: *iter_id }),
             IterValue::IterStr {
                 string,
                 byte_offset,
@@ -191,6 +269,18 @@ impl MontyIter {
                     expected_len: if *checks_mutation { *len } else { None },
                 })
             }
+            IterValue::DictView { dict_id, kind, len } => {
+                if self.index >= *len {
+                    None
+                } else {
+                    Some(IterState::DictView {
+                        dict_id: *dict_id,
+                        kind: *kind,
+                        index: self.index,
+                        len: *len,
+                    })
+                }
+            }
         }
     }
 
@@ -259,7 +349,8 @@ impl MontyIter {
                     Some(Ok(Some(Value::Int(i64::from(bytes[i])))))
                 }
             }
-            IterValue::HeapRef { .. } => None,
+            IterValue::HeapRef { .. } | IterValue::DictView { .. } | IterValue::Iterator { .. } => None,
+            IterValue::Calling(_) => Some(Err(call_iter_error())),
         }
     }
 
@@ -333,6 +424,22 @@ impl MontyIter {
                 self.index += 1;
                 Ok(Some(clone_and_inc_ref(item, heap)))
             }
+            IterValue::DictView { dict_id, kind, len } => {
+                if self.index >= *len {
+                    return Ok(None);
+                }
+                let item = view_item_at(heap, *dict_id, *kind, self.index, *len)?;
+                self.index += 1;
+                Ok(Some(item))
+            }
+            IterValue::Iterator { iter_id } => {
+                let item = advance_on_heap(heap, *iter_id, interns)?;
+                if item.is_some() {
+                    self.index += 1;
+                }
+                Ok(item)
+            }
+            IterValue::Calling(_) => Err(call_iter_error()),
         }
     }
 
@@ -340,19 +447,30 @@ impl MontyIter {
     ///
     /// For immutable types (Range, Tuple, Str, Bytes, FrozenSet), returns the exact remaining count.
     /// For List, returns current length minus index (may change if list is mutated).
-    /// For Dict and Set, returns the captured length minus index (used for size-change detection).
+    /// For Dict, Set and dict views, returns the captured length minus index (used for size-change detection).
+    /// For iterators on the heap, returns their own remaining size.
     pub fn size_hint(&self, heap: &Heap<impl ResourceTracker>) -> usize {
         let len = match &self.iter_value {
-            IterValue::Range { len, .. } | IterValue::IterStr { len, .. } | IterValue::InternBytes { len, .. } => *len,
+            IterValue::Range { len, .. }
+            | IterValue::IterStr { len, .. }
+            | IterValue::InternBytes { len, .. }
+            | IterValue::DictView { len, .. } => *len,
             IterValue::HeapRef { heap_id, len, .. } => {
-                // For List (len=None), check current length dynamically
-                len.unwrap_or_else(|| {
-                    let HeapData::List(list) = heap.get(*heap_id) else {
-                        panic!("HeapRef with len=None should only be List")
-                    };
-                    list.len()
+                // For List and ByteArray (len=None), check current length dynamically
+                len.unwrap_or_else(|| match heap.get(*heap_id) {
+                    HeapData::List(list) => list.len(),
+                    HeapData::ByteArray(b) => b.len(),
+                    _ => panic!("HeapRef with len=None should only be List or ByteArray"),
                 })
             }
+            IterValue::Iterator { iter_id } => {
+                let HeapData::Iter(iter) = heap.get(*iter_id) else {
+                    panic!("Iterator should reference an iterator on the heap");
+                };
+                return iter.size_hint(heap);
+            }
+            // The number of values depends on the functions called
+            IterValue::Calling(_) => 0,
         };
         len.saturating_sub(self.index)
     }
@@ -392,7 +510,7 @@ impl<T: ResourceTracker> Iterator for HeapedMontyIter<'_, T> {
 /// Uses a fast path for simple iterators (Range, InternBytes, ASCII IterStr) that don't need
 /// additional heap access - these are handled with a single mutable borrow.
 ///
-/// For complex iterators (IterStr, HeapRef), uses a multi-phase approach:
+/// For complex iterators (IterStr, HeapRef, DictView), uses a multi-phase approach:
 /// 1. Read iterator state (immutable borrow ends)
 /// 2. Based on state, get the value (may access other heap objects)
 /// 3. Update iterator index (mutable borrow)
@@ -450,6 +568,16 @@ pub(crate) fn advance_on_heap(
             }
             (item, None)
         }
+        IterState::DictView {
+            dict_id,
+            kind,
+            index,
+            len,
+        } => (view_item_at(heap, dict_id, kind, index, len)?, None),
+        IterState::Iterator { iter_id } => match advance_on_heap(heap, iter_id, interns)? {
+            Some(item) => (item, None),
+            None => return Ok(None),
+        },
     };
 
     // Phase 3: Advance the iterator
@@ -466,12 +594,12 @@ pub(crate) fn advance_on_heap(
 /// Returns `Ok(None)` if the index is out of bounds (for lists that shrunk during iteration).
 /// Returns `Err` if a dict/set changed size during iteration (RuntimeError).
 fn get_heap_item(
-    heap: &Heap<impl ResourceTracker>,
+    heap: &mut Heap<impl ResourceTracker>,
     heap_id: HeapId,
     index: usize,
     expected_len: Option<usize>,
 ) -> RunResult<Option<Value>> {
-    match heap.get(heap_id) {
+    match heap.get_mut(heap_id) {
         HeapData::List(list) => {
             // Check if list shrunk during iteration
             if index >= list.len() {
@@ -493,6 +621,8 @@ fn get_heap_item(
             ))
         }
         HeapData::Bytes(bytes) => Ok(Some(Value::Int(i64::from(bytes.as_slice()[index])))),
+        // ByteArray can shrink during iteration, like List
+        HeapData::ByteArray(bytes) => Ok(bytes.get(index).map(|&b| Value::Int(i64::from(b)))),
         HeapData::Set(set) => {
             // Check for set mutation
             if let Some(expected) = expected_len
@@ -507,6 +637,15 @@ fn get_heap_item(
                     .copy_for_extend(),
             ))
         }
+        HeapData::Deque(deque) => {
+            // Check for deque mutation
+            if let Some(expected) = expected_len
+                && deque.len() != expected
+            {
+                return Err(ExcType::runtime_error_deque_mutated());
+            }
+            Ok(Some(deque.get(index).expect("index should be valid").copy_for_extend()))
+        }
         HeapData::FrozenSet(frozenset) => Ok(Some(
             frozenset
                 .storage()
@@ -597,6 +736,24 @@ enum IterState {
         index: usize,
         expected_len: Option<usize>,
     },
+    /// Dict view iterator; `len` is the dict's length when iteration started.
+    DictView {
+        dict_id: HeapId,
+        kind: DictViewKind,
+        index: usize,
+        len: usize,
+    },
+    /// Iterator over another iterator on the heap, which is advanced in place.
+    Iterator { iter_id: HeapId },
+}
+
+/// Error for function-calling iterators reaching code which can't call functions.
+///
+/// The VM advances these iterators itself, so this indicates a missing interception.
+fn call_iter_error() -> RunError {
+    RunError::internal(
+        "map, filter, zip, enumerate, iter(callable, sentinel) and itertools iterators must be advanced by the VM",
+    )
 }
 
 /// Increments the reference count for a value copied via `copy_for_extend()`.
@@ -615,7 +772,7 @@ fn clone_and_inc_ref(value: Value, heap: &mut Heap<impl ResourceTracker>) -> Val
 ///
 /// Each variant stores the data needed to iterate over a specific type,
 /// excluding the index which is stored in the parent `MontyIter` struct.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 enum IterValue {
     /// Iterating over a Range, yields `Value::Int`.
     Range {
@@ -648,13 +805,170 @@ enum IterValue {
     ///
     /// - `len`: `None` for List (checked dynamically since lists can mutate during iteration),
     ///   `Some(n)` for other types (captured at construction for exhaustion checking).
-    /// - `checks_mutation`: `true` for Dict/Set (raises RuntimeError if size changes),
+    /// - `checks_mutation`: `true` for Dict/Set/Deque (raises RuntimeError if size changes),
     ///   `false` for other types.
     HeapRef {
         heap_id: HeapId,
         len: Option<usize>,
         checks_mutation: bool,
     },
+    /// Iterating over a dict view, yielding keys, values or `(key, value)` tuples.
+    ///
+    /// `MontyIter::value` holds the view, which keeps the dict alive. `len` is captured at
+    /// construction; a different dict length raises RuntimeError like dict iteration.
+    DictView {
+        dict_id: HeapId,
+        kind: DictViewKind,
+        len: usize,
+    },
+    /// Iterating over an iterator on the heap, advancing it in place.
+    ///
+    /// `MontyIter::value` holds the iterator, which is never a function-calling iterator.
+    Iterator { iter_id: HeapId },
+    /// Calling a Python function or advancing other iterators for each value. Advanced
+    /// by the VM, since it needs to run the function.
+    Calling(CallIter),
+}
+
+/// The state of a function-calling iterator: the builtin which created it, its function
+/// and inputs, and anything which changes as it advances.
+///
+/// Inputs are iterators on the heap, owned by the state.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) enum CallIter {
+    /// `map(function, *iterables)`, with an iterator per iterable.
+    Map { function: Value, iterators: Vec<HeapId> },
+    /// `zip(*iterables)` over generators or function-calling iterators, with an iterator
+    /// per iterable. `enumerate()` over them zips an `itertools.count()` with the iterable.
+    Zip { iterators: Vec<HeapId> },
+    /// `filter(function, iterable)`; a `None` function keeps the truthy values.
+    Filter { function: Value, iterator: HeapId },
+    /// `iter(callable, sentinel)`.
+    Sentinel { callable: Value, sentinel: Value },
+    /// An iterator from the `itertools` module, whose state the VM advances in place.
+    Itertools(Box<ItertoolsIter>),
+    /// An exhausted iterator, which has released its function and inputs.
+    Exhausted,
+}
+
+impl CallIter {
+    /// Returns a copy of the state with new references to the values it holds, so the VM
+    /// can advance the iterator while functions it calls use the iterator.
+    ///
+    /// Not used for `itertools` iterators, which the VM advances in place.
+    pub(crate) fn clone_with_heap(&self, heap: &mut Heap<impl ResourceTracker>) -> Self {
+        let mut clone_id = |id: HeapId| {
+            heap.inc_ref(id);
+            id
+        };
+        match self {
+            Self::Map { function, iterators } => Self::Map {
+                function: function.clone_with_heap(heap),
+                iterators: iterators.iter().map(|&id| clone_id(id)).collect(),
+            },
+            Self::Zip { iterators } => Self::Zip {
+                iterators: iterators.iter().map(|&id| clone_id(id)).collect(),
+            },
+            Self::Filter { function, iterator } => Self::Filter {
+                function: function.clone_with_heap(heap),
+                iterator: clone_id(*iterator),
+            },
+            Self::Sentinel { callable, sentinel } => Self::Sentinel {
+                callable: callable.clone_with_heap(heap),
+                sentinel: sentinel.clone_with_heap(heap),
+            },
+            Self::Itertools(_) => unreachable!("itertools iterators are advanced in place"),
+            Self::Exhausted => Self::Exhausted,
+        }
+    }
+
+    fn has_refs(&self) -> bool {
+        match self {
+            // All hold input iterators
+            Self::Map { .. } | Self::Zip { .. } | Self::Filter { .. } => true,
+            Self::Sentinel { callable, sentinel } => {
+                matches!(callable, Value::Ref(_)) || matches!(sentinel, Value::Ref(_))
+            }
+            Self::Itertools(state) => state.has_refs(),
+            Self::Exhausted => false,
+        }
+    }
+
+    fn push_ref_ids(&self, work_list: &mut Vec<HeapId>) {
+        let values: &[&Value] = match self {
+            Self::Map { function, iterators } => {
+                work_list.extend_from_slice(iterators);
+                &[function]
+            }
+            Self::Zip { iterators } => {
+                work_list.extend_from_slice(iterators);
+                &[]
+            }
+            Self::Filter { function, iterator } => {
+                work_list.push(*iterator);
+                &[function]
+            }
+            Self::Sentinel { callable, sentinel } => &[callable, sentinel],
+            Self::Itertools(state) => {
+                state.push_ref_ids(work_list);
+                &[]
+            }
+            Self::Exhausted => &[],
+        };
+        for value in values {
+            if let Value::Ref(id) = value {
+                work_list.push(*id);
+            }
+        }
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        match self {
+            Self::Map { function, iterators } => {
+                function.py_dec_ref_ids(stack);
+                stack.extend_from_slice(iterators);
+            }
+            Self::Zip { iterators } => stack.extend_from_slice(iterators),
+            Self::Filter { function, iterator } => {
+                function.py_dec_ref_ids(stack);
+                stack.push(*iterator);
+            }
+            Self::Sentinel { callable, sentinel } => {
+                callable.py_dec_ref_ids(stack);
+                sentinel.py_dec_ref_ids(stack);
+            }
+            Self::Itertools(state) => state.py_dec_ref_ids(stack),
+            Self::Exhausted => {}
+        }
+    }
+}
+
+impl<T: ResourceTracker> DropWithHeap<T> for CallIter {
+    fn drop_with_heap(self, heap: &mut Heap<T>) {
+        match self {
+            Self::Map { function, iterators } => {
+                function.drop_with_heap(heap);
+                for id in iterators {
+                    heap.dec_ref(id);
+                }
+            }
+            Self::Zip { iterators } => {
+                for id in iterators {
+                    heap.dec_ref(id);
+                }
+            }
+            Self::Filter { function, iterator } => {
+                function.drop_with_heap(heap);
+                heap.dec_ref(iterator);
+            }
+            Self::Sentinel { callable, sentinel } => {
+                callable.drop_with_heap(heap);
+                sentinel.drop_with_heap(heap);
+            }
+            Self::Itertools(state) => (*state).drop_with_heap(heap),
+            Self::Exhausted => {}
+        }
+    }
 }
 
 impl IterValue {
@@ -702,8 +1016,8 @@ impl IterValue {
     /// Creates an iterator value from heap data.
     fn from_heap_data(heap_id: HeapId, heap: &Heap<impl ResourceTracker>) -> Option<Self> {
         match heap.get(heap_id) {
-            // List: no captured len (checked dynamically), no mutation check
-            HeapData::List(_) => Some(Self::HeapRef {
+            // List/ByteArray: no captured len (checked dynamically), no mutation check
+            HeapData::List(_) | HeapData::ByteArray(_) => Some(Self::HeapRef {
                 heap_id,
                 len: None,
                 checks_mutation: false,
@@ -729,7 +1043,7 @@ impl IterValue {
                 len: Some(frozenset.len()),
                 checks_mutation: false,
             }),
-            // Dict/Set: captured len, WITH mutation check
+            // Dict/Set/Deque: captured len, WITH mutation check
             HeapData::Dict(dict) => Some(Self::HeapRef {
                 heap_id,
                 len: Some(dict.len()),
@@ -740,12 +1054,26 @@ impl IterValue {
                 len: Some(set.len()),
                 checks_mutation: true,
             }),
+            HeapData::Deque(deque) => Some(Self::HeapRef {
+                heap_id,
+                len: Some(deque.len()),
+                checks_mutation: true,
+            }),
+            // Dict views: iterate the dict they reference, WITH mutation check
+            HeapData::DictView(view) => Some(Self::DictView {
+                dict_id: view.dict_id(),
+                kind: view.kind(),
+                len: view.len(heap),
+            }),
+            // Iterators: advanced in place, unless only the VM can advance them
+            HeapData::Iter(iter) if iter.call_state().is_none() => Some(Self::Iterator { iter_id: heap_id }),
             // String: copy content for iteration
             HeapData::Str(s) => Some(Self::from_str(s.as_str())),
             // Range: copy values for iteration
             HeapData::Range(range) => Some(Self::from_range(range)),
-            // Closures, FunctionDefaults, Cells, Exceptions, Dataclasses, Iterators, LongInts, Slices, Modules,
-            // Paths, and async types are not iterable
+            // Closures, FunctionDefaults, Cells, Exceptions, Dataclasses, numbers, dates and times, Slices,
+            // Modules, Paths, regex patterns and matches, async types, and user-defined classes and instances
+            // are not iterable; generators and function-calling iterators need the VM
             HeapData::Closure(_, _, _)
             | HeapData::FunctionDefaults(_, _)
             | HeapData::Cell(_)
@@ -753,11 +1081,36 @@ impl IterValue {
             | HeapData::Dataclass(_)
             | HeapData::Iter(_)
             | HeapData::LongInt(_)
+            | HeapData::Complex(_)
+            | HeapData::Date(_)
+            | HeapData::Time(_)
+            | HeapData::DateTime(_)
+            | HeapData::TimeDelta(_)
+            | HeapData::TimeZone(_)
             | HeapData::Slice(_)
             | HeapData::Module(_)
             | HeapData::Path(_)
+            | HeapData::RePattern(_)
+            | HeapData::ReMatch(_)
             | HeapData::Coroutine(_)
-            | HeapData::GatherFuture(_) => None,
+            | HeapData::Generator(_)
+            | HeapData::GatherFuture(_)
+            | HeapData::ClassObject(_)
+            | HeapData::NamedTupleType(_)
+            | HeapData::Instance(_)
+            | HeapData::BoundMethod(_)
+            | HeapData::SuperProxy(_)
+            | HeapData::Partial(_)
+            | HeapData::LruCacheWrapper(_)
+            | HeapData::CmpToKey(_)
+            | HeapData::Random(_)
+            | HeapData::Template(_)
+            | HeapData::Hash(_)
+            | HeapData::Uuid(_)
+            | HeapData::Decimal(_)
+            | HeapData::DecimalContext(_)
+            | HeapData::DecimalContextManager(_)
+            | HeapData::Fraction(_) => None,
         }
     }
 }
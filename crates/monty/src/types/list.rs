@@ -1,4 +1,4 @@
-use std::{cmp::Ordering, fmt::Write};
+use std::fmt::Write;
 
 use ahash::AHashSet;
 use smallvec::SmallVec;
@@ -6,11 +6,9 @@ use smallvec::SmallVec;
 use super::{MontyIter, PyTrait};
 use crate::{
     args::ArgValues,
-    builtins::Builtins,
-    exception_private::{ExcType, RunError, RunResult},
+    exception_private::{ExcType, RunResult},
     heap::{DropWithHeap, Heap, HeapData, HeapId},
     intern::{Interns, StaticStrings},
-    io::PrintWriter,
     resource::{ResourceError, ResourceTracker},
     types::Type,
     value::{EitherStr, Value},
@@ -34,9 +32,8 @@ use crate::{
 /// - `reverse()` - Reverse in place
 /// - `sort([key][, reverse])` - Sort in place
 ///
-/// Note: `sort(key=...)` supports builtin key functions (len, abs, etc.)
-/// but not user-defined functions. This is handled at VM level for access
-/// to function calling machinery.
+/// Note: `sort()` is handled at VM level for access to function calling
+/// machinery, since key functions may be user-defined or external functions.
 ///
 /// All list methods from Python's builtins are implemented.
 ///
@@ -192,6 +189,46 @@ impl List {
         let heap_id = heap.allocate(HeapData::List(Self::new(items)))?;
         Ok(Value::Ref(heap_id))
     }
+
+    /// Removes the items selected by a slice, as in `del lst[1:5:2]`.
+    fn delitem_slice(&mut self, slice: &crate::types::Slice, heap: &mut Heap<impl ResourceTracker>) -> RunResult<()> {
+        let (start, stop, step) = slice
+            .indices(self.items.len())
+            .map_err(|()| ExcType::value_error_slice_step_zero())?;
+
+        // Mark the selected indices, walking them the same way as `get_slice_items`
+        let len = self.items.len();
+        let mut remove = vec![false; len];
+        if let Ok(step_usize) = usize::try_from(step) {
+            let mut i = start;
+            while i < stop && i < len {
+                remove[i] = true;
+                i += step_usize;
+            }
+        } else {
+            let step_abs = usize::try_from(-step).expect("step is negative so -step is positive");
+            // stop > len means "go to the beginning"
+            let stop = if stop > len { None } else { Some(stop) };
+            let mut i = start;
+            while i < len && stop.is_none_or(|stop| i > stop) {
+                remove[i] = true;
+                match i.checked_sub(step_abs) {
+                    Some(next) => i = next,
+                    None => break,
+                }
+            }
+        }
+
+        let items = std::mem::take(&mut self.items);
+        for (item, removed) in items.into_iter().zip(remove) {
+            if removed {
+                item.drop_with_heap(heap);
+            } else {
+                self.items.push(item);
+            }
+        }
+        Ok(())
+    }
 }
 
 impl From<List> for Vec<Value> {
@@ -306,6 +343,31 @@ impl PyTrait for List {
         Ok(())
     }
 
+    fn py_delitem(&mut self, key: Value, heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> RunResult<()> {
+        if let Value::Ref(id) = &key
+            && let HeapData::Slice(slice) = heap.get(*id)
+        {
+            let slice = slice.clone();
+            key.drop_with_heap(heap);
+            return self.delitem_slice(&slice, heap);
+        }
+
+        let index = key.as_index(heap, Type::List);
+        key.drop_with_heap(heap);
+        let index = index?;
+
+        // Normalize negative indices (Python-style: -1 = last element)
+        let len = i64::try_from(self.items.len()).expect("list length exceeds i64::MAX");
+        let normalized_index = if index < 0 { index + len } else { index };
+        if normalized_index < 0 || normalized_index >= len {
+            return Err(ExcType::list_assignment_index_error());
+        }
+
+        let idx = usize::try_from(normalized_index).expect("index validated non-negative");
+        self.items.remove(idx).drop_with_heap(heap);
+        Ok(())
+    }
+
     fn py_eq(&self, other: &Self, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> bool {
         if self.items.len() != other.items.len() {
             return false;
@@ -756,211 +818,6 @@ fn normalize_list_index(index: i64, len: usize) -> usize {
     }
 }
 
-/// Performs an in-place sort on a list with optional key function and reverse flag.
-///
-/// This is called from the VM's `call_attr` when `list.sort()` is invoked.
-/// The function lives here (rather than in VM) to keep list-related logic together,
-/// with the VM only passing through its resources.
-///
-/// Uses a staged approach to avoid borrow checker issues:
-/// 1. Parse and validate arguments
-/// 2. Extract items from the list (temporarily empties it)
-/// 3. Compute key values if a key function is provided
-/// 4. Sort indices based on items or key values
-/// 5. Rearrange items in sorted order and put back into the list
-///
-/// # Arguments
-/// * `list_id` - The heap ID of the list to sort
-/// * `args` - The method arguments (keyword-only: `key` and `reverse`)
-/// * `heap` - The heap for memory management
-/// * `interns` - Interned strings for comparisons
-/// * `print_writer` - Output writer (needed for builtin function calls)
-pub(crate) fn do_list_sort(
-    list_id: HeapId,
-    args: ArgValues,
-    heap: &mut Heap<impl ResourceTracker>,
-    interns: &Interns,
-    print_writer: &mut impl PrintWriter,
-) -> Result<(), RunError> {
-    // Parse keyword-only arguments: key and reverse
-    let (key_arg, reverse_arg) = args.extract_two_kwargs_only("list.sort", "key", "reverse", heap, interns)?;
-
-    // Convert reverse to bool (default false)
-    let reverse = if let Some(v) = reverse_arg {
-        let result = v.py_bool(heap, interns);
-        v.drop_with_heap(heap);
-        result
-    } else {
-        false
-    };
-
-    // Handle key function (None means no key function)
-    let key_fn = match key_arg {
-        Some(v) if matches!(v, Value::None) => {
-            v.drop_with_heap(heap);
-            None
-        }
-        other => other,
-    };
-
-    // Step 1: Extract items from the list (temporarily empties it)
-    let mut items: Vec<Value> = {
-        let HeapData::List(list) = heap.get_mut(list_id) else {
-            if let Some(k) = key_fn {
-                k.drop_with_heap(heap);
-            }
-            return Err(RunError::internal("expected list in do_list_sort"));
-        };
-        list.as_vec_mut().drain(..).collect()
-    };
-
-    // Step 2: Compute key values if key function provided
-    let key_values: Option<Vec<Value>> = if let Some(ref key) = key_fn {
-        let mut keys: Vec<Value> = Vec::with_capacity(items.len());
-        for item in &items {
-            let elem = item.clone_with_heap(heap);
-            match call_key_function(key, elem, heap, interns, print_writer) {
-                Ok(key_value) => keys.push(key_value),
-                Err(e) => {
-                    // Clean up and restore items to list on error
-                    for k in keys {
-                        k.drop_with_heap(heap);
-                    }
-                    if let Some(k) = key_fn {
-                        k.drop_with_heap(heap);
-                    }
-                    // Restore items to the list
-                    if let HeapData::List(list) = heap.get_mut(list_id) {
-                        for item in items {
-                            list.as_vec_mut().push(item);
-                        }
-                    }
-                    return Err(e);
-                }
-            }
-        }
-        Some(keys)
-    } else {
-        None
-    };
-
-    // Drop the key function - we're done with it
-    if let Some(k) = key_fn {
-        k.drop_with_heap(heap);
-    }
-
-    // Step 3: Sort indices based on items or key values
-    let len = items.len();
-    let mut indices: Vec<usize> = (0..len).collect();
-    let mut sort_error: Option<RunError> = None;
-
-    if let Some(ref keys) = key_values {
-        indices.sort_by(|&a, &b| {
-            if sort_error.is_some() {
-                return Ordering::Equal;
-            }
-            if let Some(ord) = keys[a].py_cmp(&keys[b], heap, interns) {
-                if reverse { ord.reverse() } else { ord }
-            } else {
-                sort_error = Some(ExcType::type_error(format!(
-                    "'<' not supported between instances of '{}' and '{}'",
-                    keys[a].py_type(heap),
-                    keys[b].py_type(heap)
-                )));
-                Ordering::Equal
-            }
-        });
-    } else {
-        indices.sort_by(|&a, &b| {
-            if sort_error.is_some() {
-                return Ordering::Equal;
-            }
-            if let Some(ord) = items[a].py_cmp(&items[b], heap, interns) {
-                if reverse { ord.reverse() } else { ord }
-            } else {
-                sort_error = Some(ExcType::type_error(format!(
-                    "'<' not supported between instances of '{}' and '{}'",
-                    items[a].py_type(heap),
-                    items[b].py_type(heap)
-                )));
-                Ordering::Equal
-            }
-        });
-    }
-
-    // Clean up key values
-    if let Some(keys) = key_values {
-        for k in keys {
-            k.drop_with_heap(heap);
-        }
-    }
-
-    // Check for sort error
-    if let Some(err) = sort_error {
-        // Restore items to list before returning error
-        if let HeapData::List(list) = heap.get_mut(list_id) {
-            for item in items {
-                list.as_vec_mut().push(item);
-            }
-        }
-        return Err(err);
-    }
-
-    // Step 4: Rearrange items in sorted order using index permutation
-    let mut sorted_items: Vec<Value> = Vec::with_capacity(len);
-    for &i in &indices {
-        // Move the value out, replacing with Undefined as placeholder
-        sorted_items.push(std::mem::replace(&mut items[i], Value::Undefined));
-    }
-
-    // Put sorted items back into the list
-    let HeapData::List(list) = heap.get_mut(list_id) else {
-        return Err(RunError::internal("expected list in do_list_sort"));
-    };
-
-    for item in sorted_items {
-        list.as_vec_mut().push(item);
-    }
-
-    // items now contains Undefined values - no cleanup needed
-    Ok(())
-}
-
-/// Calls a key function on a single element for sorting.
-///
-/// Currently supports builtin functions directly. User-defined functions return
-/// an error since they would require VM frame management for proper execution.
-fn call_key_function(
-    key_fn: &Value,
-    elem: Value,
-    heap: &mut Heap<impl ResourceTracker>,
-    interns: &Interns,
-    print_writer: &mut impl PrintWriter,
-) -> Result<Value, RunError> {
-    match key_fn {
-        Value::Builtin(Builtins::Function(builtin)) => {
-            let args = ArgValues::One(elem);
-            builtin.call(heap, args, interns, print_writer)
-        }
-        Value::Builtin(Builtins::Type(t)) => {
-            // Type constructors (int, str, float, etc.) are callable key functions
-            let args = ArgValues::One(elem);
-            t.call(heap, args, interns)
-        }
-        Value::DefFunction(_) | Value::ExtFunction(_) | Value::Ref(_) => {
-            // User-defined or external functions require VM frame management
-            elem.drop_with_heap(heap);
-            Err(ExcType::type_error(
-                "list.sort() key argument must be a builtin function (user-defined functions not yet supported)",
-            ))
-        }
-        _ => {
-            elem.drop_with_heap(heap);
-            Err(ExcType::type_error("list.sort() key must be callable or None"))
-        }
-    }
-}
-
 /// Writes a formatted sequence of values to a formatter.
 ///
 /// This helper function is used to implement `__repr__` for sequence types like
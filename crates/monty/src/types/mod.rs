@@ -5,9 +5,23 @@
 ///
 /// The `AbstractValue` trait provides a common interface for all heap-allocated
 /// types, enabling efficient dispatch via `enum_dispatch`.
+pub mod bytearray;
 pub mod bytes;
+pub mod class;
+pub mod complex;
+pub mod counter;
 pub mod dataclass;
+pub mod datetime;
+pub mod datetime_format;
+pub mod decimal;
+pub mod deque;
 pub mod dict;
+pub mod dict_view;
+pub mod r#enum;
+pub mod fraction;
+pub mod functools;
+pub mod generator;
+pub mod hashlib;
 pub mod iter;
 pub mod list;
 pub mod long_int;
@@ -16,27 +30,46 @@ pub mod namedtuple;
 pub mod path;
 pub mod property;
 pub mod py_trait;
+pub mod random;
 pub mod range;
+pub mod re;
 pub mod set;
 pub mod slice;
 pub mod str;
+pub mod template;
 pub mod tuple;
 pub mod r#type;
+pub mod uuid;
 
+pub(crate) use bytearray::ByteArray;
 pub(crate) use bytes::Bytes;
+pub(crate) use class::{BoundMethod, ClassObject, Instance, SuperProxy};
+pub(crate) use complex::Complex;
 pub(crate) use dataclass::Dataclass;
+pub(crate) use datetime::{Date, DateTime, Time, TimeDelta, TimeZone};
+pub(crate) use decimal::{Decimal, DecimalContext, DecimalContextManager};
+pub(crate) use deque::Deque;
 pub(crate) use dict::Dict;
+pub(crate) use dict_view::{DictView, DictViewKind};
+pub(crate) use fraction::Fraction;
+pub(crate) use functools::{CmpToKey, LruCacheWrapper, Partial};
+pub(crate) use generator::{Generator, GeneratorState};
+pub(crate) use hashlib::Hash;
 pub(crate) use iter::MontyIter;
 pub(crate) use list::List;
 pub(crate) use long_int::LongInt;
 pub(crate) use module::Module;
-pub(crate) use namedtuple::NamedTuple;
+pub(crate) use namedtuple::{NamedTuple, NamedTupleType};
 pub(crate) use path::Path;
 pub(crate) use property::Property;
 pub(crate) use py_trait::{AttrCallResult, PyTrait};
+pub(crate) use random::Random;
 pub(crate) use range::Range;
+pub(crate) use re::{ReMatch, RePattern};
 pub(crate) use set::{FrozenSet, Set};
 pub(crate) use slice::Slice;
 pub(crate) use str::Str;
+pub(crate) use template::Template;
 pub(crate) use tuple::{Tuple, allocate_tuple};
 pub(crate) use r#type::Type;
+pub(crate) use uuid::Uuid;
@@ -14,16 +14,19 @@
 /// # Use Case
 ///
 /// This type is used for `sys.version_info` and similar structured tuples where
-/// named access improves usability and readability.
+/// named access improves usability and readability, and for instances of the types
+/// created by `collections.namedtuple()`, which are [`NamedTupleType`]s.
 use std::fmt::Write;
 
 use ahash::AHashSet;
+use smallvec::SmallVec;
 
-use super::PyTrait;
+use super::{Dict, MontyIter, PyTrait, allocate_tuple, str::allocate_string};
 use crate::{
+    args::{ArgValues, bind_args_vec},
     exception_private::{ExcType, RunResult},
-    heap::{Heap, HeapId},
-    intern::{Interns, StringId},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
     resource::ResourceTracker,
     types::{AttrCallResult, Type},
     value::{EitherStr, Value},
@@ -147,6 +150,45 @@ impl NamedTuple {
         }
         self.items.get(usize::try_from(normalized).ok()?)
     }
+
+    /// Implements `_replace(**kwargs)`, returning a copy with the given fields replaced.
+    fn replace(&self, args: ArgValues, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+        let (pos, kwargs) = args.into_parts();
+        let count = pos.len();
+        if count != 0 {
+            pos.drop_with_heap(heap);
+            kwargs.drop_with_heap(heap);
+            return Err(ExcType::type_error_too_many_positional("_replace", 0, count, 0));
+        }
+
+        let mut items: Vec<Value> = self.items.iter().map(|item| item.clone_with_heap(heap)).collect();
+        let mut unexpected = Vec::new();
+        for (key, value) in kwargs {
+            let index = key.as_either_str(heap).and_then(|key_name| {
+                let key_str = key_name.as_str(interns);
+                let index = self
+                    .field_names
+                    .iter()
+                    .position(|field| field.as_str(interns) == key_str);
+                if index.is_none() {
+                    unexpected.push(format!("'{key_str}'"));
+                }
+                index
+            });
+            key.drop_with_heap(heap);
+            match index {
+                Some(index) => std::mem::replace(&mut items[index], value).drop_with_heap(heap),
+                None => value.drop_with_heap(heap),
+            }
+        }
+        if !unexpected.is_empty() {
+            items.drop_with_heap(heap);
+            let message = format!("Got unexpected field names: [{}]", unexpected.join(", "));
+            return Err(ExcType::type_error(message));
+        }
+        let replaced = Self::new(self.name.clone(), self.field_names.clone(), items);
+        Ok(Value::Ref(heap.allocate(HeapData::NamedTuple(replaced))?))
+    }
 }
 
 impl PyTrait for NamedTuple {
@@ -239,6 +281,38 @@ impl PyTrait for NamedTuple {
         f.write_char(')')
     }
 
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        match attr.static_string() {
+            Some(StaticStrings::UnderscoreAsdict) => {
+                args.check_zero_args("_asdict", heap)?;
+                let mut pairs = Vec::with_capacity(self.items.len());
+                for (field_name, value) in self.field_names.iter().zip(&self.items) {
+                    let key = match field_name_value(field_name, heap) {
+                        Ok(key) => key,
+                        Err(e) => {
+                            drop_pairs(pairs, heap);
+                            return Err(e);
+                        }
+                    };
+                    pairs.push((key, value.clone_with_heap(heap)));
+                }
+                let dict = Dict::from_pairs(pairs, heap, interns)?;
+                Ok(Value::Ref(heap.allocate(HeapData::Dict(dict))?))
+            }
+            Some(StaticStrings::UnderscoreReplace) => self.replace(args, heap, interns),
+            _ => {
+                args.drop_with_heap(heap);
+                Err(ExcType::attribute_error(self.name(interns), attr.as_str(interns)))
+            }
+        }
+    }
+
     fn py_getattr(
         &self,
         attr_id: StringId,
@@ -247,9 +321,223 @@ impl PyTrait for NamedTuple {
     ) -> RunResult<Option<AttrCallResult>> {
         if let Some(value) = self.get_by_name(attr_id, interns) {
             Ok(Some(AttrCallResult::Value(value.clone_with_heap(heap))))
+        } else if attr_id == StaticStrings::UnderscoreFields {
+            Ok(Some(AttrCallResult::Value(fields_tuple(&self.field_names, heap)?)))
         } else {
             // we use name here, not `self.py_type(heap)` hence returning a Ok(None)
             Err(ExcType::attribute_error(self.name(interns), interns.get_str(attr_id)))
         }
     }
 }
+
+/// A named tuple type created at runtime by `collections.namedtuple()`.
+///
+/// Calling the type creates a [`NamedTuple`] with its name and field names, so instances
+/// don't reference the type; `isinstance()` instead checks that an instance has the same
+/// name and field names.
+///
+/// # Supported Attributes
+/// - `_fields` - Tuple of the field names
+/// - `_field_defaults` - Dict of the fields with defaults to their default values
+/// - `_make(iterable)` - Creates an instance from an iterable of values
+/// - `__name__` - The type name
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct NamedTupleType {
+    /// Type name, e.g. `Point`.
+    name: EitherStr,
+    /// Field names in order.
+    field_names: Vec<EitherStr>,
+    /// Default values for the rightmost fields, at most one per field.
+    defaults: Vec<Value>,
+}
+
+impl NamedTupleType {
+    /// Creates a new named tuple type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more defaults than fields.
+    #[must_use]
+    pub fn new(name: impl Into<EitherStr>, field_names: Vec<EitherStr>, defaults: Vec<Value>) -> Self {
+        assert!(
+            defaults.len() <= field_names.len(),
+            "NamedTupleType can't have more defaults than fields"
+        );
+        Self {
+            name: name.into(),
+            field_names,
+            defaults,
+        }
+    }
+
+    /// Returns the type name.
+    #[must_use]
+    pub fn name<'a>(&'a self, interns: &'a Interns) -> &'a str {
+        self.name.as_str(interns)
+    }
+
+    /// Returns the default values, which are the only heap references the type holds.
+    #[must_use]
+    pub fn defaults(&self) -> &[Value] {
+        &self.defaults
+    }
+
+    /// Returns whether `namedtuple` is an instance of this type.
+    ///
+    /// Instances don't reference their type, so this compares the name and field names,
+    /// which instances copy from the type that created them.
+    #[must_use]
+    pub fn is_type_of(&self, namedtuple: &NamedTuple) -> bool {
+        self.name == namedtuple.name && self.field_names == namedtuple.field_names
+    }
+
+    /// Creates an instance from a call of the type, like `Point(1, y=2)`.
+    ///
+    /// Arguments bind to the fields like the parameters of a function, and fields with
+    /// defaults may be left out.
+    pub fn call(&self, args: ArgValues, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+        let name = format!("{}.__new__", self.name.as_str(interns));
+        let params: Vec<&str> = self.field_names.iter().map(|field| field.as_str(interns)).collect();
+        let required = params.len() - self.defaults.len();
+        let bound = bind_args_vec(&name, args, &params, required, heap, interns)?;
+        let items: Vec<Value> = bound
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| value.unwrap_or_else(|| self.defaults[index - required].clone_with_heap(heap)))
+            .collect();
+        self.allocate_instance(items, heap)
+    }
+
+    /// Allocates an instance holding `items`, one per field.
+    fn allocate_instance(&self, items: Vec<Value>, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        let namedtuple = NamedTuple::new(self.name.clone(), self.field_names.clone(), items);
+        Ok(Value::Ref(heap.allocate(HeapData::NamedTuple(namedtuple))?))
+    }
+}
+
+impl PyTrait for NamedTupleType {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Type
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.name.py_estimate_size()
+            + self.field_names.iter().map(EitherStr::py_estimate_size).sum::<usize>()
+            + self.defaults.len() * std::mem::size_of::<Value>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        // Types compare by identity, which is handled at the Value level
+        false
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        for default in &mut self.defaults {
+            if let Value::Ref(id) = default {
+                stack.push(*id);
+                #[cfg(feature = "ref-count-panic")]
+                default.dec_ref_forget();
+            }
+        }
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        interns: &Interns,
+    ) -> std::fmt::Result {
+        write!(f, "<class '__main__.{}'>", self.name.as_str(interns))
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        if attr.static_string() != Some(StaticStrings::UnderscoreMake) {
+            args.drop_with_heap(heap);
+            return Err(ExcType::attribute_error(Type::Type, attr.as_str(interns)));
+        }
+        let iterable = args.get_one_arg("_make", heap)?;
+        let mut iter = MontyIter::new(iterable, heap, interns)?;
+        let items: RunResult<Vec<Value>> = iter.collect(heap, interns);
+        iter.drop_with_heap(heap);
+        let items = items?;
+        if items.len() != self.field_names.len() {
+            let message = format!("Expected {} arguments, got {}", self.field_names.len(), items.len());
+            items.drop_with_heap(heap);
+            return Err(ExcType::type_error(message));
+        }
+        self.allocate_instance(items, heap)
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = match StaticStrings::from_string_id(attr_id) {
+            Some(StaticStrings::UnderscoreFields) => fields_tuple(&self.field_names, heap)?,
+            Some(StaticStrings::UnderscoreFieldDefaults) => {
+                let first_default = self.field_names.len() - self.defaults.len();
+                let mut pairs = Vec::with_capacity(self.defaults.len());
+                for (field_name, default) in self.field_names[first_default..].iter().zip(&self.defaults) {
+                    let key = match field_name_value(field_name, heap) {
+                        Ok(key) => key,
+                        Err(e) => {
+                            drop_pairs(pairs, heap);
+                            return Err(e);
+                        }
+                    };
+                    pairs.push((key, default.clone_with_heap(heap)));
+                }
+                let dict = Dict::from_pairs(pairs, heap, interns)?;
+                Value::Ref(heap.allocate(HeapData::Dict(dict))?)
+            }
+            Some(StaticStrings::DunderName) => field_name_value(&self.name, heap)?,
+            _ => return Ok(None),
+        };
+        Ok(Some(AttrCallResult::Value(value)))
+    }
+}
+
+/// Returns a field or type name as a Python str.
+fn field_name_value(name: &EitherStr, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    match name {
+        EitherStr::Interned(id) => Ok(Value::InternString(*id)),
+        EitherStr::Heap(s) => allocate_string(s.clone(), heap),
+    }
+}
+
+/// Returns the `_fields` tuple of field names.
+fn fields_tuple(field_names: &[EitherStr], heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let mut names = SmallVec::with_capacity(field_names.len());
+    for field_name in field_names {
+        match field_name_value(field_name, heap) {
+            Ok(name) => names.push(name),
+            Err(e) => {
+                names.into_iter().collect::<Vec<Value>>().drop_with_heap(heap);
+                return Err(e);
+            }
+        }
+    }
+    Ok(allocate_tuple(names, heap)?)
+}
+
+/// Drops key-value pairs collected for a dict which won't be built.
+fn drop_pairs(pairs: Vec<(Value, Value)>, heap: &mut Heap<impl ResourceTracker>) {
+    for (key, value) in pairs {
+        key.drop_with_heap(heap);
+        value.drop_with_heap(heap);
+    }
+}
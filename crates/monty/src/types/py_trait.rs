@@ -347,6 +347,17 @@ pub trait PyTrait {
         .into())
     }
 
+    /// Python subscript delete operation (`__delitem__`), e.g., `del d[key]`.
+    ///
+    /// Removes the item associated with the key, or returns an error if the key is invalid
+    /// or the type doesn't support subscript deletion. Takes ownership of `key`.
+    ///
+    /// Default implementation returns TypeError.
+    fn py_delitem(&mut self, key: Value, heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> RunResult<()> {
+        key.drop_with_heap(heap);
+        Err(ExcType::type_error_no_item_deletion(self.py_type(heap)))
+    }
+
     /// Python attribute get operation (`__getattr__`), e.g., `obj.attr`.
     ///
     /// Returns the value associated with the attribute (owned), or `Ok(None)` if the type
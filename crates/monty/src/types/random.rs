@@ -0,0 +1,891 @@
+//! The random number generator of the `random` module (see `modules/random.rs`).
+//!
+//! [`Random`] is a port of CPython's Mersenne Twister (MT19937) and of the methods of
+//! `random.Random` built on it, so a given seed produces the same numbers as in CPython.
+//!
+//! Monty never reads entropy from the OS: when a program needs a generator it hasn't
+//! seeded itself, the VM asks the host for a seed with `OsFunction::RandomSeed` and
+//! finishes the call with a [`SeedRequest`] once the host answers. Generators are plain
+//! data, so they are saved with the heap and a resumed run continues the same sequence.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{ToPrimitive, Zero};
+
+use super::{List, LongInt, PyTrait, Type};
+use crate::{
+    args::{ArgValues, bind_args},
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::{
+        math::{float_arg, frexp, int_arg},
+        random::RandomFunctions,
+    },
+    resource::{LARGE_RESULT_THRESHOLD, ResourceTracker},
+    value::{EitherStr, Value},
+};
+
+/// Size of the Mersenne Twister state, in 32-bit words.
+const N: usize = 624;
+/// Offset of the word mixed into each word of the state by a twist.
+const M: usize = 397;
+const MATRIX_A: u32 = 0x9908_b0df;
+const UPPER_MASK: u32 = 0x8000_0000;
+const LOWER_MASK: u32 = 0x7fff_ffff;
+
+/// A Mersenne Twister random number generator, the state of a `random.Random` object and
+/// of the `random` module's functions.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Random {
+    /// The `N` words of the generator state.
+    state: Vec<u32>,
+    /// Index of the next word of `state` to output; `N` when the state must be twisted first.
+    index: usize,
+    /// The second normal deviate computed by the last `gauss()` call, returned by the next one.
+    gauss_next: Option<f64>,
+}
+
+impl Random {
+    /// Creates a generator seeded with `seed`, as CPython seeds one with an int.
+    ///
+    /// Only the absolute value of the seed is used.
+    #[must_use]
+    pub fn new(seed: &BigInt) -> Self {
+        let mut key = seed.magnitude().to_u32_digits();
+        if key.is_empty() {
+            key.push(0);
+        }
+
+        let mut random = Self::from_u32(19_650_218);
+        let mt = &mut random.state;
+        let mut i = 1;
+        let mut j = 0;
+        for _ in 0..N.max(key.len()) {
+            let prev = mt[i - 1] ^ (mt[i - 1] >> 30);
+            mt[i] = (mt[i] ^ prev.wrapping_mul(1_664_525))
+                .wrapping_add(key[j])
+                .wrapping_add(word(j));
+            i += 1;
+            j += 1;
+            if i >= N {
+                mt[0] = mt[N - 1];
+                i = 1;
+            }
+            if j >= key.len() {
+                j = 0;
+            }
+        }
+        for _ in 0..N - 1 {
+            let prev = mt[i - 1] ^ (mt[i - 1] >> 30);
+            mt[i] = (mt[i] ^ prev.wrapping_mul(1_566_083_941)).wrapping_sub(word(i));
+            i += 1;
+            if i >= N {
+                mt[0] = mt[N - 1];
+                i = 1;
+            }
+        }
+        // The most significant bit is set so the state is never all zeros
+        mt[0] = 0x8000_0000;
+        random
+    }
+
+    /// Creates a `random.Random` object from the `Random(x)` constructor call.
+    ///
+    /// Only calls with a seed get here: the VM asks the host for a seed when a program
+    /// calls `Random()` without one.
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let Some(seed) = constructor_seed(args, heap, interns)? else {
+            return Err(ExcType::not_implemented("random.Random() without a seed can only be called directly").into());
+        };
+        Ok(Value::Ref(heap.allocate(HeapData::Random(Self::new(&seed)))?))
+    }
+
+    /// Creates a generator from a single 32-bit seed, the first step of seeding with a key.
+    fn from_u32(seed: u32) -> Self {
+        let mut state = Vec::with_capacity(N);
+        state.push(seed);
+        for i in 1..N {
+            let prev = state[i - 1];
+            state.push(
+                1_812_433_253_u32
+                    .wrapping_mul(prev ^ (prev >> 30))
+                    .wrapping_add(word(i)),
+            );
+        }
+        Self {
+            state,
+            index: N,
+            gauss_next: None,
+        }
+    }
+
+    /// Returns the next 32 random bits.
+    fn next_u32(&mut self) -> u32 {
+        if self.index >= N {
+            self.twist();
+        }
+        let mut y = self.state[self.index];
+        self.index += 1;
+        y ^= y >> 11;
+        y ^= (y << 7) & 0x9d2c_5680;
+        y ^= (y << 15) & 0xefc6_0000;
+        y ^ (y >> 18)
+    }
+
+    /// Generates the next `N` words of the state.
+    fn twist(&mut self) {
+        let mt = &mut self.state;
+        for k in 0..N {
+            let y = (mt[k] & UPPER_MASK) | (mt[(k + 1) % N] & LOWER_MASK);
+            let mag = if y & 1 == 0 { 0 } else { MATRIX_A };
+            mt[k] = mt[(k + M) % N] ^ (y >> 1) ^ mag;
+        }
+        self.index = 0;
+    }
+
+    /// Returns a float in `[0.0, 1.0)` with 53 random bits, like `random()`.
+    pub fn random(&mut self) -> f64 {
+        let a = self.next_u32() >> 5;
+        let b = self.next_u32() >> 6;
+        (f64::from(a) * 67_108_864.0 + f64::from(b)) * (1.0 / 9_007_199_254_740_992.0)
+    }
+
+    /// Returns an int with `k` random bits, where `k` is at most 64.
+    ///
+    /// Consumes the same output as `getrandbits(k)`.
+    fn getrandbits_u64(&mut self, k: u32) -> u64 {
+        match k {
+            0 => 0,
+            1..=32 => u64::from(self.next_u32() >> (32 - k)),
+            _ => {
+                let low = u64::from(self.next_u32());
+                let high = u64::from(self.next_u32() >> (64 - k));
+                low | (high << 32)
+            }
+        }
+    }
+
+    /// Returns an int with `k` random bits, like `getrandbits(k)`.
+    fn getrandbits(&mut self, k: u64) -> BigInt {
+        if k <= 64 {
+            let k = u32::try_from(k).expect("k is at most 64");
+            return BigInt::from(self.getrandbits_u64(k));
+        }
+        let words = (k - 1) / 32 + 1;
+        let mut digits = Vec::with_capacity(usize::try_from(words).unwrap_or_default());
+        let mut remaining = k;
+        while remaining > 0 {
+            let bits = self.next_u32();
+            if remaining < 32 {
+                digits.push(bits >> (32 - remaining));
+                remaining = 0;
+            } else {
+                digits.push(bits);
+                remaining -= 32;
+            }
+        }
+        BigInt::from(BigUint::new(digits))
+    }
+
+    /// Returns a random int in `[0, n)`, like CPython's `_randbelow()`.
+    ///
+    /// `n` must be positive.
+    fn randbelow(&mut self, n: usize) -> usize {
+        let k = usize::BITS - n.leading_zeros();
+        loop {
+            let r = usize::try_from(self.getrandbits_u64(k)).expect("has no more bits than n");
+            if r < n {
+                return r;
+            }
+        }
+    }
+
+    /// Like [`Self::randbelow`], for any positive int.
+    fn randbelow_big(&mut self, n: &BigInt) -> BigInt {
+        if let Some(n) = n.to_usize() {
+            return BigInt::from(self.randbelow(n));
+        }
+        let k = n.bits();
+        loop {
+            let r = self.getrandbits(k);
+            if &r < n {
+                return r;
+            }
+        }
+    }
+
+    /// Returns a normal deviate with mean 0 and standard deviation 1, as `gauss()` computes
+    /// them: in pairs, returning the second one on the next call.
+    fn gauss(&mut self) -> f64 {
+        if let Some(z) = self.gauss_next.take() {
+            return z;
+        }
+        let x2pi = self.random() * std::f64::consts::TAU;
+        let g2rad = (-2.0 * (1.0 - self.random()).ln()).sqrt();
+        self.gauss_next = Some(x2pi.sin() * g2rad);
+        x2pi.cos() * g2rad
+    }
+
+    /// Returns `k` distinct random indices into a population of `n`, like `sample()`.
+    ///
+    /// Picks from a pool of the remaining indices for small populations, and retries
+    /// picks of already selected indices for large ones, as CPython does.
+    fn sample_indices(&mut self, n: usize, k: usize) -> Vec<usize> {
+        let mut setsize: usize = 21;
+        if k > 5 {
+            #[expect(
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss,
+                reason = "the logarithm of a usize is small and positive"
+            )]
+            let exponent = (k.saturating_mul(3) as f64).log(4.0).ceil() as u32;
+            setsize = setsize.saturating_add(4_usize.saturating_pow(exponent));
+        }
+
+        let mut result = Vec::with_capacity(k);
+        if n <= setsize {
+            let mut pool: Vec<usize> = (0..n).collect();
+            for i in 0..k {
+                let j = self.randbelow(n - i);
+                result.push(pool[j]);
+                pool[j] = pool[n - i - 1];
+            }
+        } else {
+            let mut selected = AHashSet::new();
+            for _ in 0..k {
+                let mut j = self.randbelow(n);
+                while !selected.insert(j) {
+                    j = self.randbelow(n);
+                }
+                result.push(j);
+            }
+        }
+        result
+    }
+
+    /// Calls one of the methods of `random.Random`, which are also the `random` module's
+    /// functions.
+    pub fn call(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        function: RandomFunctions,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        match function {
+            RandomFunctions::Random => {
+                args.check_zero_args("Random.random", heap)?;
+                Ok(Value::Float(self.random()))
+            }
+            RandomFunctions::Getrandbits => {
+                let k = args.get_one_arg("Random.getrandbits", heap)?;
+                defer_drop!(k, heap);
+                let k = int_arg(k, heap)?;
+                if k.sign() == Sign::Minus {
+                    return Err(
+                        SimpleException::new_msg(ExcType::ValueError, "number of bits must be non-negative").into(),
+                    );
+                }
+                let Some(k) = k.to_u64() else {
+                    return Err(SimpleException::new_msg(
+                        ExcType::OverflowError,
+                        "getrandbits() number of bits too large",
+                    )
+                    .into());
+                };
+                let bytes = usize::try_from(k / 8).unwrap_or(usize::MAX);
+                if bytes > LARGE_RESULT_THRESHOLD {
+                    heap.tracker().check_large_result(bytes)?;
+                }
+                Ok(LongInt::new(self.getrandbits(k)).into_value(heap)?)
+            }
+            RandomFunctions::Randrange => {
+                let [start, stop, step] =
+                    bind_args("Random.randrange", args, ["start", "stop", "step"], 1, heap, interns)?;
+                defer_drop!(start, heap);
+                defer_drop!(stop, heap);
+                defer_drop!(step, heap);
+                let start = int_arg(start.as_ref().expect("required argument is bound"), heap)?;
+                let stop = match stop {
+                    None | Some(Value::None) => None,
+                    Some(stop) => Some(int_arg(stop, heap)?),
+                };
+                let step = step.as_ref().map(|step| int_arg(step, heap)).transpose()?;
+                let n = self.randrange(start, stop, step)?;
+                Ok(LongInt::new(n).into_value(heap)?)
+            }
+            RandomFunctions::Randint => {
+                let [a, b] = bind_args("Random.randint", args, ["a", "b"], 2, heap, interns)?;
+                defer_drop!(a, heap);
+                defer_drop!(b, heap);
+                let a = int_arg(a.as_ref().expect("required argument is bound"), heap)?;
+                let b = int_arg(b.as_ref().expect("required argument is bound"), heap)?;
+                let n = self.randrange(a, Some(b + 1), None)?;
+                Ok(LongInt::new(n).into_value(heap)?)
+            }
+            RandomFunctions::Choice => {
+                let seq = args.get_one_arg("Random.choice", heap)?;
+                defer_drop!(seq, heap);
+                let n = sequence_len(seq, heap, interns)?;
+                if n == 0 {
+                    return Err(
+                        SimpleException::new_msg(ExcType::IndexError, "Cannot choose from an empty sequence").into(),
+                    );
+                }
+                let index = self.randbelow(n);
+                seq.py_getitem(&index_value(index), heap, interns)
+            }
+            RandomFunctions::Choices => self.choices(heap, args, interns),
+            RandomFunctions::Sample => self.sample(heap, args, interns),
+            RandomFunctions::Shuffle => {
+                let x = args.get_one_arg("Random.shuffle", heap)?;
+                defer_drop!(x, heap);
+                self.shuffle(x, heap, interns)?;
+                Ok(Value::None)
+            }
+            RandomFunctions::Uniform => {
+                let [a, b] = bind_args("Random.uniform", args, ["a", "b"], 2, heap, interns)?;
+                defer_drop!(a, heap);
+                defer_drop!(b, heap);
+                let a = float_arg(a.as_ref().expect("required argument is bound"), heap)?;
+                let b = float_arg(b.as_ref().expect("required argument is bound"), heap)?;
+                Ok(Value::Float(a + (b - a) * self.random()))
+            }
+            RandomFunctions::Gauss => {
+                let [mu, sigma] = bind_args("Random.gauss", args, ["mu", "sigma"], 0, heap, interns)?;
+                defer_drop!(mu, heap);
+                defer_drop!(sigma, heap);
+                let mu = mu.as_ref().map_or(Ok(0.0), |mu| float_arg(mu, heap))?;
+                let sigma = sigma.as_ref().map_or(Ok(1.0), |sigma| float_arg(sigma, heap))?;
+                Ok(Value::Float(mu + self.gauss() * sigma))
+            }
+            RandomFunctions::Seed => unreachable!("seed() is called by the VM, which may ask the host for a seed"),
+        }
+    }
+
+    /// Implements `randrange(start, stop=None, step=1)` once its arguments are ints.
+    fn randrange(&mut self, start: BigInt, stop: Option<BigInt>, step: Option<BigInt>) -> RunResult<BigInt> {
+        let Some(stop) = stop else {
+            if step.is_some_and(|step| step != BigInt::from(1)) {
+                return Err(ExcType::type_error("Missing a non-None stop argument"));
+            }
+            if start.sign() == Sign::Plus {
+                return Ok(self.randbelow_big(&start));
+            }
+            return Err(SimpleException::new_msg(ExcType::ValueError, "empty range for randrange()").into());
+        };
+
+        let width = &stop - &start;
+        let Some(step) = step.filter(|step| *step != BigInt::from(1)) else {
+            if width.sign() == Sign::Plus {
+                return Ok(start + self.randbelow_big(&width));
+            }
+            return Err(empty_range(format!("{start}, {stop}")));
+        };
+
+        let n = match step.sign() {
+            Sign::Plus => (&width + &step - 1) / &step,
+            Sign::Minus => (&width + &step + 1) / &step,
+            Sign::NoSign => {
+                return Err(SimpleException::new_msg(ExcType::ValueError, "zero step for randrange()").into());
+            }
+        };
+        // Truncating division only differs from floor division for negative quotients,
+        // which are empty ranges either way
+        if n.sign() != Sign::Plus {
+            return Err(empty_range(format!("{start}, {stop}, {step}")));
+        }
+        Ok(start + step * self.randbelow_big(&n))
+    }
+
+    /// Implements `choices(population, weights=None, *, cum_weights=None, k=1)`.
+    fn choices(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        let [population, weights, cum_weights, k] = bind_args(
+            "Random.choices",
+            args,
+            ["population", "weights", "cum_weights", "k"],
+            1,
+            heap,
+            interns,
+        )?;
+        defer_drop!(population, heap);
+        defer_drop!(weights, heap);
+        defer_drop!(cum_weights, heap);
+        defer_drop!(k, heap);
+        let population = population.as_ref().expect("required argument is bound");
+        let n = sequence_len(population, heap, interns)?;
+        let k = match k {
+            Some(k) => {
+                let k = int_arg(k, heap)?;
+                if k.sign() == Sign::Minus {
+                    0
+                } else {
+                    k.to_usize().unwrap_or(usize::MAX)
+                }
+            }
+            None => 1,
+        };
+        let estimated = k.saturating_mul(std::mem::size_of::<Value>());
+        if estimated > LARGE_RESULT_THRESHOLD {
+            heap.tracker().check_large_result(estimated)?;
+        }
+
+        let weights = weights.as_ref().filter(|weights| !matches!(weights, Value::None));
+        let cum_weights = cum_weights.as_ref().filter(|weights| !matches!(weights, Value::None));
+        let cum_weights = match (weights, cum_weights) {
+            (None, None) => {
+                #[expect(
+                    clippy::cast_possible_truncation,
+                    clippy::cast_sign_loss,
+                    reason = "random() * n is in [0, n)"
+                )]
+                let indices: Vec<usize> = (0..k).map(|_| (self.random() * n as f64) as usize).collect();
+                return items_at(population, &indices, heap, interns);
+            }
+            (Some(weights), None) => {
+                if let Value::Int(k) = weights {
+                    return Err(ExcType::type_error(format!(
+                        "The number of choices must be a keyword argument: k={k}"
+                    )));
+                }
+                let mut total = 0.0;
+                let mut cum_weights = Vec::new();
+                for weight in float_items(weights, heap, interns)? {
+                    total += weight;
+                    cum_weights.push(total);
+                }
+                cum_weights
+            }
+            (None, Some(cum_weights)) => float_items(cum_weights, heap, interns)?,
+            (Some(_), Some(_)) => {
+                return Err(ExcType::type_error(
+                    "Cannot specify both weights and cumulative weights",
+                ));
+            }
+        };
+
+        if cum_weights.len() != n {
+            return Err(SimpleException::new_msg(
+                ExcType::ValueError,
+                "The number of weights does not match the population",
+            )
+            .into());
+        }
+        let total = cum_weights.last().copied().unwrap_or_default();
+        if total <= 0.0 {
+            return Err(
+                SimpleException::new_msg(ExcType::ValueError, "Total of weights must be greater than zero").into(),
+            );
+        }
+        if !total.is_finite() {
+            return Err(SimpleException::new_msg(ExcType::ValueError, "Total of weights must be finite").into());
+        }
+        let indices: Vec<usize> = (0..k)
+            .map(|_| {
+                let x = self.random() * total;
+                // bisect_right(cum_weights, x, 0, n - 1)
+                cum_weights[..n - 1].partition_point(|weight| *weight <= x)
+            })
+            .collect();
+        items_at(population, &indices, heap, interns)
+    }
+
+    /// Implements `sample(population, k, *, counts=None)`.
+    fn sample(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        let [population, k, counts] =
+            bind_args("Random.sample", args, ["population", "k", "counts"], 2, heap, interns)?;
+        defer_drop!(population, heap);
+        defer_drop!(k, heap);
+        defer_drop!(counts, heap);
+        let population = population.as_ref().expect("required argument is bound");
+        if !matches!(
+            population.py_type(heap),
+            Type::List
+                | Type::Tuple
+                | Type::NamedTuple
+                | Type::Str
+                | Type::Bytes
+                | Type::ByteArray
+                | Type::Range
+                | Type::Deque
+        ) {
+            return Err(ExcType::type_error(
+                "Population must be a sequence.  For dicts or sets, use sorted(d).",
+            ));
+        }
+        let n = sequence_len(population, heap, interns)?;
+        let k = int_arg(k.as_ref().expect("required argument is bound"), heap)?;
+
+        let Some(counts) = counts.as_ref().filter(|counts| !matches!(counts, Value::None)) else {
+            let k = sample_size(&k, n)?;
+            let indices = self.sample_indices(n, k);
+            return items_at(population, &indices, heap, interns);
+        };
+
+        let counts = collect_items(counts, heap, interns)?;
+        let mut cum_counts = Vec::with_capacity(counts.len());
+        let mut total = BigInt::zero();
+        let all_ints = counts.iter().all(|count| {
+            let Some(count) = int_value(count, heap) else {
+                return false;
+            };
+            total += count;
+            cum_counts.push(total.clone());
+            true
+        });
+        counts.drop_with_heap(heap);
+        if !all_ints {
+            return Err(ExcType::type_error("Counts must be integers"));
+        }
+        if cum_counts.len() != n {
+            return Err(SimpleException::new_msg(
+                ExcType::ValueError,
+                "The number of counts does not match the population",
+            )
+            .into());
+        }
+        cum_counts.pop();
+        if total.sign() == Sign::Minus {
+            return Err(SimpleException::new_msg(ExcType::ValueError, "Counts must be non-negative").into());
+        }
+        let Some(total) = total.to_usize() else {
+            return Err(SimpleException::new_msg(ExcType::OverflowError, "Total of counts is too large").into());
+        };
+        let k = sample_size(&k, total)?;
+        let indices: Vec<usize> = self
+            .sample_indices(total, k)
+            .into_iter()
+            .map(|selection| {
+                let selection = BigInt::from(selection);
+                cum_counts.partition_point(|count| *count <= selection)
+            })
+            .collect();
+        items_at(population, &indices, heap, interns)
+    }
+
+    /// Implements `shuffle(x)`, which shuffles a list in place.
+    ///
+    /// Like CPython, other sequences only fail when the first swap is attempted.
+    fn shuffle(&mut self, x: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<()> {
+        if let Value::Ref(id) = x
+            && let HeapData::List(list) = heap.get_mut(*id)
+        {
+            let items = list.as_vec_mut();
+            for i in (1..items.len()).rev() {
+                let j = self.randbelow(i + 1);
+                items.swap(i, j);
+            }
+            return Ok(());
+        }
+        let n = sequence_len(x, heap, interns)?;
+        if n < 2 {
+            return Ok(());
+        }
+        self.randbelow(n);
+        Err(ExcType::type_error_not_sub_assignment(x.py_type(heap)))
+    }
+}
+
+impl PyTrait for Random {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Random
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.state.len() * std::mem::size_of::<u32>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        // Generators compare by identity, which is handled at the Value level
+        false
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {}
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        f.write_str("<random.Random object>")
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        let function = match attr.static_string() {
+            Some(StaticStrings::Random) => RandomFunctions::Random,
+            Some(StaticStrings::Getrandbits) => RandomFunctions::Getrandbits,
+            Some(StaticStrings::Randrange) => RandomFunctions::Randrange,
+            Some(StaticStrings::Randint) => RandomFunctions::Randint,
+            Some(StaticStrings::Choice) => RandomFunctions::Choice,
+            Some(StaticStrings::Choices) => RandomFunctions::Choices,
+            Some(StaticStrings::Sample) => RandomFunctions::Sample,
+            Some(StaticStrings::Shuffle) => RandomFunctions::Shuffle,
+            Some(StaticStrings::Uniform) => RandomFunctions::Uniform,
+            Some(StaticStrings::Gauss) => RandomFunctions::Gauss,
+            _ => {
+                args.drop_with_heap(heap);
+                return Err(ExcType::attribute_error(Type::Random, attr.as_str(interns)));
+            }
+        };
+        self.call(heap, function, args, interns)
+    }
+}
+
+/// A call waiting for the host to answer `OsFunction::RandomSeed` with a seed, because it
+/// needs a generator seeded from entropy.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) enum SeedRequest {
+    /// A call of one of the `random` module's functions before the module's generator
+    /// was seeded.
+    Call(RandomFunctions, ArgValues),
+    /// `random.seed()` without a seed.
+    SeedModule,
+    /// `Random.seed()` without a seed, which reseeds this generator.
+    ///
+    /// Holds a reference to the generator.
+    SeedInstance(HeapId),
+    /// `random.Random()` without a seed.
+    New,
+}
+
+impl SeedRequest {
+    /// Seeds the requested generator with the host's answer, and finishes the call.
+    pub fn finish(self, value: Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+        let seed = int_value(&value, heap);
+        let Some(seed) = seed else {
+            let error = ExcType::type_error(format!(
+                "random.seed() OS call must return an int, not '{}'",
+                value.py_type(heap)
+            ));
+            value.drop_with_heap(heap);
+            self.abandon(heap);
+            return Err(error);
+        };
+        value.drop_with_heap(heap);
+
+        let mut random = Random::new(&seed);
+        match self {
+            Self::Call(function, args) => {
+                let result = random.call(heap, function, args, interns);
+                heap.set_random(random);
+                result
+            }
+            Self::SeedModule => {
+                heap.set_random(random);
+                Ok(Value::None)
+            }
+            Self::SeedInstance(id) => {
+                if let HeapData::Random(instance) = heap.get_mut(id) {
+                    *instance = random;
+                }
+                heap.dec_ref(id);
+                Ok(Value::None)
+            }
+            Self::New => Ok(Value::Ref(heap.allocate(HeapData::Random(random))?)),
+        }
+    }
+
+    /// Releases the values held by a request which won't be finished.
+    pub fn abandon(self, heap: &mut Heap<impl ResourceTracker>) {
+        match self {
+            Self::Call(_, args) => args.drop_with_heap(heap),
+            Self::SeedInstance(id) => heap.dec_ref(id),
+            Self::SeedModule | Self::New => {}
+        }
+    }
+}
+
+/// Converts the argument of `seed()` or `Random()` to the int to seed with.
+///
+/// As in CPython, a float seed is replaced by its hash.
+///
+/// # Errors
+/// Returns `NotImplementedError` for str, bytes and bytearray seeds, and `TypeError` for
+/// other types which can't seed a generator.
+pub(crate) fn seed_value(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<BigInt> {
+    if let Some(seed) = int_value(value, heap) {
+        return Ok(seed);
+    }
+    if let Value::Float(f) = value {
+        #[expect(clippy::cast_sign_loss, reason = "CPython seeds with the hash as an unsigned int")]
+        return Ok(BigInt::from(float_hash(*f) as u64));
+    }
+    match value.py_type(heap) {
+        Type::Str | Type::Bytes | Type::ByteArray => {
+            Err(ExcType::not_implemented("str, bytes and bytearray seeds are not supported").into())
+        }
+        _ => Err(ExcType::type_error(
+            "The only supported seed types are:\nNone, int, float, str, bytes, and bytearray.",
+        )),
+    }
+}
+
+/// Binds the arguments of the `Random(x=None)` constructor, returning the seed, or `None`
+/// when the generator must be seeded by the host.
+pub(crate) fn constructor_seed(
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Option<BigInt>> {
+    let [x] = bind_args("Random", args, ["x"], 0, heap, interns)?;
+    defer_drop!(x, heap);
+    match x {
+        None | Some(Value::None) => Ok(None),
+        Some(x) => seed_value(x, heap).map(Some),
+    }
+}
+
+/// Returns the value of an int or bool, or `None` for any other type.
+fn int_value(value: &Value, heap: &Heap<impl ResourceTracker>) -> Option<BigInt> {
+    match value {
+        Value::Int(_) | Value::Bool(_) => int_arg(value, heap).ok(),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => Some(li.inner().clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Computes CPython's hash of a float, reducing it modulo the Mersenne prime `2**61 - 1`.
+fn float_hash(value: f64) -> i64 {
+    const BITS: i32 = 61;
+    const MODULUS: u64 = (1 << BITS) - 1;
+
+    if value.is_nan() {
+        return 0;
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { 314_159 } else { -314_159 };
+    }
+
+    let (mut mantissa, mut exponent) = frexp(value);
+    let negative = mantissa < 0.0;
+    mantissa = mantissa.abs();
+    let mut x: u64 = 0;
+    // Process the mantissa 28 bits at a time, multiplying x by 2**28 modulo the prime
+    while mantissa > 0.0 {
+        x = ((x << 28) & MODULUS) | (x >> (BITS - 28));
+        mantissa *= 268_435_456.0;
+        exponent -= 28;
+        #[expect(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            reason = "the mantissa is below 2**28 and non-negative"
+        )]
+        let y = mantissa as u64;
+        mantissa -= y as f64;
+        x += y;
+        if x >= MODULUS {
+            x -= MODULUS;
+        }
+    }
+
+    let exponent = if exponent >= 0 {
+        exponent % BITS
+    } else {
+        BITS - 1 - ((-1 - exponent) % BITS)
+    };
+    x = ((x << exponent) & MODULUS) | (x >> (BITS - exponent));
+    let hash = i64::try_from(x).expect("x is below the modulus");
+    match if negative { -hash } else { hash } {
+        -1 => -2,
+        hash => hash,
+    }
+}
+
+/// Returns the length of a sequence argument.
+fn sequence_len(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<usize> {
+    value
+        .py_len(heap, interns)
+        .ok_or_else(|| ExcType::type_error(format!("object of type '{}' has no len()", value.py_type(heap))))
+}
+
+/// Converts an index into a sequence to an int.
+fn index_value(index: usize) -> Value {
+    Value::Int(i64::try_from(index).expect("sequence lengths fit in an i64"))
+}
+
+/// Returns a list of the items of `population` at `indices`.
+fn items_at(
+    population: &Value,
+    indices: &[usize],
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let mut items = Vec::with_capacity(indices.len());
+    for &index in indices {
+        match population.py_getitem(&index_value(index), heap, interns) {
+            Ok(item) => items.push(item),
+            Err(e) => {
+                items.drop_with_heap(heap);
+                return Err(e);
+            }
+        }
+    }
+    Ok(Value::Ref(heap.allocate(HeapData::List(List::new(items)))?))
+}
+
+/// Collects the items of an iterable argument.
+fn collect_items(iterable: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Vec<Value>> {
+    let mut iter = super::MontyIter::new(iterable.clone_with_heap(heap), heap, interns)?;
+    let items = iter.collect(heap, interns);
+    iter.drop_with_heap(heap);
+    items
+}
+
+/// Collects the items of an iterable of numbers as floats.
+fn float_items(iterable: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Vec<f64>> {
+    let items = collect_items(iterable, heap, interns)?;
+    let floats = items.iter().map(|item| float_arg(item, heap)).collect();
+    items.drop_with_heap(heap);
+    floats
+}
+
+/// Checks the size `k` of a sample from a population of `n`.
+fn sample_size(k: &BigInt, n: usize) -> RunResult<usize> {
+    match k.to_usize() {
+        Some(k) if k <= n => Ok(k),
+        _ => Err(SimpleException::new_msg(ExcType::ValueError, "Sample larger than population or is negative").into()),
+    }
+}
+
+#[cold]
+fn empty_range(args: String) -> RunError {
+    SimpleException::new_msg(ExcType::ValueError, format!("empty range in randrange({args})")).into()
+}
+
+/// Converts an index into the generator state to the word mixed in at that index.
+fn word(i: usize) -> u32 {
+    u32::try_from(i).expect("indices into the state fit in a u32")
+}
@@ -0,0 +1,941 @@
+//! Python `re.Pattern` and `re.Match` types.
+//!
+//! Patterns are created by `re.compile()` and the other `re` functions (see `modules/re.rs`).
+//! A match keeps references to its pattern and to the string searched, and stores the span
+//! of each group both as byte offsets, to slice the string, and as the character indices
+//! Python uses.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+use smallvec::smallvec;
+
+use super::{
+    AttrCallResult, Dict, List, MontyIter, PyTrait, Type, allocate_tuple,
+    str::{allocate_string, string_repr_fmt},
+};
+use crate::{
+    args::{ArgValues, bind_args},
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    regex::{
+        ASCII, Anchor, Cache, Captures, DOTALL, IGNORECASE, LOCALE, MULTILINE, Regex, Spans, TemplateError,
+        TemplatePart, UNICODE, VERBOSE,
+    },
+    resource::{ResourceError, ResourceTracker},
+    value::{EitherStr, Value},
+};
+
+/// Flag names shown by `repr()` of a pattern, in CPython's order.
+const FLAG_NAMES: [(u32, &str); 7] = [
+    (IGNORECASE, "re.IGNORECASE"),
+    (LOCALE, "re.LOCALE"),
+    (MULTILINE, "re.MULTILINE"),
+    (DOTALL, "re.DOTALL"),
+    (UNICODE, "re.UNICODE"),
+    (VERBOSE, "re.VERBOSE"),
+    (ASCII, "re.ASCII"),
+];
+
+/// A compiled regular expression, `re.Pattern`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct RePattern {
+    /// The pattern string it was compiled from.
+    pattern: String,
+    regex: Regex,
+}
+
+impl RePattern {
+    #[must_use]
+    pub fn new(pattern: String, regex: Regex) -> Self {
+        Self { pattern, regex }
+    }
+
+    /// Returns the pattern string.
+    #[must_use]
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Returns the compiled regular expression.
+    #[must_use]
+    pub fn regex(&self) -> &Regex {
+        &self.regex
+    }
+}
+
+impl PyTrait for RePattern {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::RePattern
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.pattern.len() + self.regex.estimate_size()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self.pattern == other.pattern && self.regex.flags() == other.regex.flags()
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {}
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        // Like CPython, the pattern's repr is cut to 200 characters
+        let mut pattern = String::new();
+        string_repr_fmt(&self.pattern, &mut pattern)?;
+        f.write_str("re.compile(")?;
+        f.write_str(truncate_chars(&pattern, 200))?;
+        // `UNICODE` is the default for str patterns, so isn't shown
+        let mut flags = self.regex.flags() & !UNICODE;
+        if flags != 0 {
+            let mut first = true;
+            for (flag, name) in FLAG_NAMES {
+                if flags & flag != 0 {
+                    f.write_str(if first { ", " } else { "|" })?;
+                    f.write_str(name)?;
+                    flags &= !flag;
+                    first = false;
+                }
+            }
+            if flags != 0 {
+                write!(f, "{}0x{flags:x}", if first { ", " } else { "|" })?;
+            }
+        }
+        f.write_char(')')
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = match StaticStrings::from_string_id(attr_id) {
+            Some(StaticStrings::Pattern) => allocate_string(self.pattern.clone(), heap)?,
+            Some(StaticStrings::Flags) => Value::Int(i64::from(self.regex.flags())),
+            Some(StaticStrings::Groups) => Value::Int(group_number(self.regex.group_count())),
+            Some(StaticStrings::Groupindex) => {
+                let mut pairs = Vec::with_capacity(self.regex.group_names().len());
+                for (name, index) in self.regex.group_names() {
+                    match allocate_string(name.clone(), heap) {
+                        Ok(key) => pairs.push((key, Value::Int(group_number(*index)))),
+                        Err(e) => {
+                            drop_pairs(pairs, heap);
+                            return Err(e);
+                        }
+                    }
+                }
+                let dict = Dict::from_pairs(pairs, heap, interns)?;
+                Value::Ref(heap.allocate(HeapData::Dict(dict))?)
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(AttrCallResult::Value(value)))
+    }
+}
+
+/// A successful match, `re.Match`.
+///
+/// # Reference Counting
+/// Owns a reference to its pattern and, if it's on the heap, to the string searched.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ReMatch {
+    pattern_id: HeapId,
+    /// The string searched, always a str.
+    string: Value,
+    /// Byte offsets of the whole match and each group.
+    byte_spans: Spans,
+    /// Character indices of the whole match and each group.
+    spans: Spans,
+    /// The `pos` and `endpos` the search was limited to, as character indices.
+    pos: usize,
+    endpos: usize,
+    /// The number of the group which closed last.
+    last_group: Option<usize>,
+}
+
+impl ReMatch {
+    /// Returns the pattern this match was made by.
+    #[must_use]
+    pub fn pattern_id(&self) -> HeapId {
+        self.pattern_id
+    }
+
+    /// Returns the string searched.
+    #[must_use]
+    pub fn string(&self) -> &Value {
+        &self.string
+    }
+
+    /// Returns the text of the string searched.
+    fn text<'a>(&self, heap: &'a Heap<impl ResourceTracker>, interns: &'a Interns) -> &'a str {
+        // borrowed rather than copied with `as_either_str`, as groups are sliced from it
+        match &self.string {
+            Value::InternString(string_id) => interns.get_str(*string_id),
+            Value::Ref(id) => match heap.get(*id) {
+                HeapData::Str(s) => s.as_str(),
+                _ => panic!("ReMatch: string should be a str"),
+            },
+            _ => panic!("ReMatch: string should be a str"),
+        }
+    }
+
+    /// Returns the number of the group `key` refers to, by number or name.
+    ///
+    /// # Errors
+    /// Returns `IndexError` if there's no such group.
+    fn group_index(&self, key: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<usize> {
+        let index = match key {
+            Value::Int(i) => usize::try_from(*i).ok(),
+            Value::Bool(b) => Some(usize::from(*b)),
+            _ => key.as_either_str(heap).and_then(|name| {
+                let HeapData::RePattern(pattern) = heap.get(self.pattern_id) else {
+                    panic!("ReMatch: expected RePattern on heap");
+                };
+                pattern.regex().group_index(name.as_str(interns))
+            }),
+        };
+        index
+            .filter(|index| *index < self.spans.len())
+            .ok_or_else(|| SimpleException::new_msg(ExcType::IndexError, "no such group").into())
+    }
+
+    /// Returns the text matched by a group, or `default` if it didn't match.
+    fn group_value(
+        &self,
+        index: usize,
+        default: &Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        match self.byte_spans[index] {
+            Some((start, end)) => {
+                let text = self.text(heap, interns)[start..end].to_owned();
+                allocate_string(text, heap)
+            }
+            None => Ok(default.clone_with_heap(heap)),
+        }
+    }
+
+    /// Returns the text matched by each of a list of groups, or `default` for those which
+    /// didn't match.
+    fn group_values(
+        &self,
+        indexes: impl IntoIterator<Item = usize>,
+        default: &Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Vec<Value>> {
+        let mut values = Vec::new();
+        for index in indexes {
+            match self.group_value(index, default, heap, interns) {
+                Ok(value) => values.push(value),
+                Err(e) => {
+                    values.drop_with_heap(heap);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(values)
+    }
+
+    /// Implements `Match.group(*groups)`.
+    fn group(&self, args: ArgValues, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+        let (positional, kwargs) = args.into_parts();
+        if !kwargs.is_empty() {
+            positional.drop_with_heap(heap);
+            kwargs.drop_with_heap(heap);
+            return Err(ExcType::type_error_no_kwargs("Match.group"));
+        }
+        let keys: Vec<Value> = positional.collect();
+        defer_drop!(keys, heap);
+        let indexes = keys
+            .iter()
+            .map(|key| self.group_index(key, heap, interns))
+            .collect::<RunResult<Vec<_>>>()?;
+        match indexes.as_slice() {
+            [] => self.group_value(0, &Value::None, heap, interns),
+            [index] => self.group_value(*index, &Value::None, heap, interns),
+            _ => {
+                let values = self.group_values(indexes.iter().copied(), &Value::None, heap, interns)?;
+                Ok(allocate_tuple(values.into(), heap)?)
+            }
+        }
+    }
+
+    /// Implements `Match.groups(default=None)`.
+    fn groups(&self, default: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+        let values = self.group_values(1..self.spans.len(), default, heap, interns)?;
+        Ok(allocate_tuple(values.into(), heap)?)
+    }
+
+    /// Implements `Match.groupdict(default=None)`.
+    fn groupdict(&self, default: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+        let HeapData::RePattern(pattern) = heap.get(self.pattern_id) else {
+            panic!("ReMatch: expected RePattern on heap");
+        };
+        let names = pattern.regex().group_names().to_vec();
+        let values = self.group_values(names.iter().map(|(_, index)| *index), default, heap, interns)?;
+        let mut pairs = Vec::with_capacity(names.len());
+        let mut values = values.into_iter();
+        for ((name, _), value) in names.into_iter().zip(values.by_ref()) {
+            match allocate_string(name, heap) {
+                Ok(key) => pairs.push((key, value)),
+                Err(e) => {
+                    value.drop_with_heap(heap);
+                    values.drop_with_heap(heap);
+                    drop_pairs(pairs, heap);
+                    return Err(e);
+                }
+            }
+        }
+        let dict = Dict::from_pairs(pairs, heap, interns)?;
+        Ok(Value::Ref(heap.allocate(HeapData::Dict(dict))?))
+    }
+
+    /// Implements `Match.expand(template)`.
+    fn expand(&self, template: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+        let Some(template) = template.as_either_str(heap) else {
+            return Err(ExcType::type_error(format!(
+                "expected str instance, {} found",
+                template.py_type(heap)
+            )));
+        };
+        let HeapData::RePattern(pattern) = heap.get(self.pattern_id) else {
+            panic!("ReMatch: expected RePattern on heap");
+        };
+        let parts = parse_template(pattern.regex(), template.as_str(interns))?;
+        let expanded = expand_template(&parts, self.text(heap, interns), &self.byte_spans);
+        allocate_string(expanded, heap)
+    }
+
+    /// Returns the character span of the group given by the optional argument of
+    /// `start()`, `end()` and `span()`, or `(-1, -1)` if it didn't match.
+    fn span_arg(
+        &self,
+        method: &str,
+        args: ArgValues,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<(i64, i64)> {
+        let index = match args.get_zero_one_arg(method, heap)? {
+            Some(key) => {
+                defer_drop!(key, heap);
+                self.group_index(key, heap, interns)?
+            }
+            None => 0,
+        };
+        Ok(self.spans[index].map_or((-1, -1), |(start, end)| (group_number(start), group_number(end))))
+    }
+}
+
+impl PyTrait for ReMatch {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::ReMatch
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.spans.len() * 2 * std::mem::size_of::<Option<(usize, usize)>>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        false
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        stack.push(self.pattern_id);
+        self.string.py_dec_ref_ids(stack);
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        interns: &Interns,
+    ) -> std::fmt::Result {
+        let (start, end) = self.spans[0].expect("the whole match always has a span");
+        let (byte_start, byte_end) = self.byte_spans[0].expect("the whole match always has a span");
+        // Like CPython, the match's repr is cut to 50 characters
+        let mut matched = String::new();
+        string_repr_fmt(&self.text(heap, interns)[byte_start..byte_end], &mut matched)?;
+        write!(
+            f,
+            "<re.Match object; span=({start}, {end}), match={}>",
+            truncate_chars(&matched, 50)
+        )
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        match attr.static_string() {
+            Some(StaticStrings::Group) => self.group(args, heap, interns),
+            Some(StaticStrings::Groups) => {
+                let [default] = bind_args("groups", args, ["default"], 0, heap, interns)?;
+                let default = default.unwrap_or(Value::None);
+                defer_drop!(default, heap);
+                self.groups(default, heap, interns)
+            }
+            Some(StaticStrings::Groupdict) => {
+                let [default] = bind_args("groupdict", args, ["default"], 0, heap, interns)?;
+                let default = default.unwrap_or(Value::None);
+                defer_drop!(default, heap);
+                self.groupdict(default, heap, interns)
+            }
+            Some(StaticStrings::Start) => Ok(Value::Int(self.span_arg("Match.start", args, heap, interns)?.0)),
+            Some(StaticStrings::End) => Ok(Value::Int(self.span_arg("Match.end", args, heap, interns)?.1)),
+            Some(StaticStrings::Span) => {
+                let (start, end) = self.span_arg("Match.span", args, heap, interns)?;
+                Ok(allocate_tuple(smallvec![Value::Int(start), Value::Int(end)], heap)?)
+            }
+            Some(StaticStrings::Expand) => {
+                let template = args.get_one_arg("Match.expand", heap)?;
+                defer_drop!(template, heap);
+                self.expand(template, heap, interns)
+            }
+            _ => {
+                args.drop_with_heap(heap);
+                Err(ExcType::attribute_error(Type::ReMatch, attr.as_str(interns)))
+            }
+        }
+    }
+
+    fn py_getitem(&self, key: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+        let index = self.group_index(key, heap, interns)?;
+        self.group_value(index, &Value::None, heap, interns)
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = match StaticStrings::from_string_id(attr_id) {
+            Some(StaticStrings::String) => self.string.clone_with_heap(heap),
+            Some(StaticStrings::Re) => {
+                heap.inc_ref(self.pattern_id);
+                Value::Ref(self.pattern_id)
+            }
+            Some(StaticStrings::Pos) => Value::Int(group_number(self.pos)),
+            Some(StaticStrings::Endpos) => Value::Int(group_number(self.endpos)),
+            Some(StaticStrings::Lastindex) => self
+                .last_group
+                .map_or(Value::None, |index| Value::Int(group_number(index))),
+            Some(StaticStrings::Lastgroup) => {
+                let HeapData::RePattern(pattern) = heap.get(self.pattern_id) else {
+                    panic!("ReMatch: expected RePattern on heap");
+                };
+                match self.last_group.and_then(|index| pattern.regex().group_name(index)) {
+                    Some(name) => allocate_string(name.to_owned(), heap)?,
+                    None => Value::None,
+                }
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(AttrCallResult::Value(value)))
+    }
+}
+
+/// Drops key-value pairs collected for a dict which won't be built.
+fn drop_pairs(pairs: Vec<(Value, Value)>, heap: &mut Heap<impl ResourceTracker>) {
+    for (key, value) in pairs {
+        key.drop_with_heap(heap);
+        value.drop_with_heap(heap);
+    }
+}
+
+/// Parses a replacement template, converting errors to Python exceptions.
+///
+/// # Errors
+/// Returns `re.error` for a malformed template and `IndexError` for an unknown group name.
+pub(crate) fn parse_template(regex: &Regex, template: &str) -> RunResult<Vec<TemplatePart>> {
+    regex.parse_template(template).map_err(|e| match e {
+        TemplateError::Invalid(e) => ExcType::re_error(e),
+        TemplateError::UnknownGroupName(name) => {
+            SimpleException::new_msg(ExcType::IndexError, format!("unknown group name '{name}'")).into()
+        }
+    })
+}
+
+/// Builds the replacement for a match from a parsed template.
+///
+/// Groups which didn't match are replaced by nothing.
+#[must_use]
+pub(crate) fn expand_template(parts: &[TemplatePart], text: &str, byte_spans: &Spans) -> String {
+    let mut out = String::new();
+    for part in parts {
+        match part {
+            TemplatePart::Literal(literal) => out.push_str(literal),
+            TemplatePart::Group(index) => {
+                if let Some((start, end)) = byte_spans[*index] {
+                    out.push_str(&text[start..end]);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Runs a pattern over a string: a single search, or successive non-overlapping matches.
+pub(crate) struct Matcher {
+    pattern_id: HeapId,
+    regex: Regex,
+    cache: Cache,
+    /// The text of the string searched, cut off at `endpos`.
+    text: String,
+    /// The `pos` and `endpos` arguments, as character indices clamped to the string.
+    pos: usize,
+    endpos: usize,
+    /// Byte offset the next search starts at, or `None` once there are no more matches.
+    next: Option<usize>,
+    /// Whether the last match was empty, in which case the next can't be empty at the same position.
+    after_empty: bool,
+    indexer: CharIndexer,
+}
+
+impl Matcher {
+    /// Prepares to run a pattern over `string` between the character indices `pos` and
+    /// `endpos`, which default to the whole string.
+    ///
+    /// # Errors
+    /// Returns `TypeError` if `string` isn't a str, or `pos` or `endpos` isn't an int.
+    pub fn new(
+        heap: &Heap<impl ResourceTracker>,
+        pattern_id: HeapId,
+        string: &Value,
+        pos: Option<&Value>,
+        endpos: Option<&Value>,
+        interns: &Interns,
+    ) -> RunResult<Self> {
+        let Some(text) = string.as_either_str(heap) else {
+            return Err(match string.py_type(heap) {
+                Type::Bytes | Type::ByteArray => {
+                    ExcType::type_error("cannot use a string pattern on a bytes-like object")
+                }
+                t => ExcType::type_error(format!("expected string or bytes-like object, got '{t}'")),
+            });
+        };
+        let text = text.as_str(interns);
+        let len = if text.is_ascii() {
+            text.len()
+        } else {
+            text.chars().count()
+        };
+        let pos = char_index_arg(pos, 0, len, heap)?;
+        let endpos = char_index_arg(endpos, len, len, heap)?;
+        let next = (pos <= endpos).then(|| byte_offset(text, pos));
+        let text = text[..byte_offset(text, endpos)].to_owned();
+
+        let HeapData::RePattern(pattern) = heap.get(pattern_id) else {
+            panic!("Matcher: expected RePattern on heap");
+        };
+        let regex = pattern.regex().clone();
+        Ok(Self {
+            pattern_id,
+            cache: regex.cache(),
+            regex,
+            indexer: CharIndexer::new(&text),
+            text,
+            pos,
+            endpos,
+            next,
+            after_empty: false,
+        })
+    }
+
+    /// Returns the regular expression being run.
+    #[must_use]
+    pub fn regex(&self) -> &Regex {
+        &self.regex
+    }
+
+    /// Returns the text searched, up to `endpos`.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Searches once from `pos`, as `search()`, `match()` and `fullmatch()` do.
+    pub fn search(
+        &mut self,
+        anchor: Anchor,
+        heap: &mut Heap<impl ResourceTracker>,
+    ) -> Result<Option<Captures>, ResourceError> {
+        let Some(start) = self.next else {
+            return Ok(None);
+        };
+        self.regex
+            .search(&mut self.cache, &self.text, start, anchor, false, heap.tracker_mut())
+    }
+
+    /// Finds the next match after the last one found.
+    ///
+    /// As in CPython, an empty match may directly follow a non-empty one, but not
+    /// another empty one.
+    pub fn next_match(&mut self, heap: &mut Heap<impl ResourceTracker>) -> Result<Option<Captures>, ResourceError> {
+        let Some(start) = self.next else {
+            return Ok(None);
+        };
+        let captures = self.regex.search(
+            &mut self.cache,
+            &self.text,
+            start,
+            Anchor::Unanchored,
+            self.after_empty,
+            heap.tracker_mut(),
+        )?;
+        match &captures {
+            Some(captures) => {
+                let (start, end) = captures.spans[0].expect("the whole match always has a span");
+                self.after_empty = start == end;
+                self.next = Some(end);
+            }
+            None => self.next = None,
+        }
+        Ok(captures)
+    }
+
+    /// Creates a match object for a match found in `string`, which must be the string this
+    /// matcher was created for, and allocates it on the heap.
+    pub fn allocate_match(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        string: &Value,
+        captures: Captures,
+    ) -> Result<Value, ResourceError> {
+        let spans = captures
+            .spans
+            .iter()
+            .map(|span| {
+                span.map(|(start, end)| {
+                    (
+                        self.indexer.char_index(&self.text, start),
+                        self.indexer.char_index(&self.text, end),
+                    )
+                })
+            })
+            .collect();
+        heap.inc_ref(self.pattern_id);
+        let re_match = ReMatch {
+            pattern_id: self.pattern_id,
+            string: string.clone_with_heap(heap),
+            byte_spans: captures.spans,
+            spans,
+            pos: self.pos,
+            endpos: self.endpos,
+            last_group: captures.last_group,
+        };
+        Ok(Value::Ref(heap.allocate(HeapData::ReMatch(re_match))?))
+    }
+}
+
+/// Converts a `pos` or `endpos` argument to a character index within a string of `len`
+/// characters, clamping it to the string like CPython.
+fn char_index_arg(
+    value: Option<&Value>,
+    default: usize,
+    len: usize,
+    heap: &Heap<impl ResourceTracker>,
+) -> RunResult<usize> {
+    match value {
+        Some(value) => {
+            let index = value.as_int(heap)?;
+            Ok(usize::try_from(index).map_or(0, |index| index.min(len)))
+        }
+        None => Ok(default),
+    }
+}
+
+/// Converts byte offsets into a string to character indices.
+///
+/// Scans from the last offset converted, so converting increasing offsets, as found by
+/// successive searches, takes time linear in the length of the string overall.
+struct CharIndexer {
+    ascii: bool,
+    byte: usize,
+    index: usize,
+}
+
+impl CharIndexer {
+    fn new(text: &str) -> Self {
+        Self {
+            ascii: text.is_ascii(),
+            byte: 0,
+            index: 0,
+        }
+    }
+
+    /// Returns the character index of a byte offset into `text`, which must be on a
+    /// character boundary.
+    fn char_index(&mut self, text: &str, byte: usize) -> usize {
+        if self.ascii {
+            return byte;
+        }
+        if byte >= self.byte {
+            self.index += text[self.byte..byte].chars().count();
+        } else {
+            self.index -= text[byte..self.byte].chars().count();
+        }
+        self.byte = byte;
+        self.index
+    }
+}
+
+/// Returns the byte offset of a character index, or the length of `text` if it's past the end.
+fn byte_offset(text: &str, index: usize) -> usize {
+    if text.is_ascii() {
+        index.min(text.len())
+    } else {
+        text.char_indices().nth(index).map_or(text.len(), |(offset, _)| offset)
+    }
+}
+
+/// Calls a method of a pattern other than `sub()` and `subn()`, which are run by the VM
+/// since they may call a function for each match.
+pub(crate) fn call_pattern_method(
+    heap: &mut Heap<impl ResourceTracker>,
+    pattern_id: HeapId,
+    attr: &EitherStr,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<Value> {
+    match attr.static_string() {
+        Some(
+            method @ (StaticStrings::Search
+            | StaticStrings::Match
+            | StaticStrings::Fullmatch
+            | StaticStrings::Findall
+            | StaticStrings::Finditer),
+        ) => {
+            let [string, pos, endpos] = bind_args(
+                attr.as_str(interns),
+                args,
+                ["string", "pos", "endpos"],
+                1,
+                heap,
+                interns,
+            )?;
+            let string = string.expect("required argument is bound");
+            defer_drop!(string, heap);
+            defer_drop!(pos, heap);
+            defer_drop!(endpos, heap);
+            let (pos, endpos) = (pos.as_ref(), endpos.as_ref());
+            match method {
+                StaticStrings::Search => {
+                    pattern_search(heap, pattern_id, string, pos, endpos, Anchor::Unanchored, interns)
+                }
+                StaticStrings::Match => pattern_search(heap, pattern_id, string, pos, endpos, Anchor::Start, interns),
+                StaticStrings::Fullmatch => {
+                    pattern_search(heap, pattern_id, string, pos, endpos, Anchor::Both, interns)
+                }
+                StaticStrings::Findall => pattern_findall(heap, pattern_id, string, pos, endpos, interns),
+                _ => pattern_finditer(heap, pattern_id, string, pos, endpos, interns),
+            }
+        }
+        Some(StaticStrings::Split) => {
+            let [string, maxsplit] = bind_args("split", args, ["string", "maxsplit"], 1, heap, interns)?;
+            let string = string.expect("required argument is bound");
+            defer_drop!(string, heap);
+            defer_drop!(maxsplit, heap);
+            pattern_split(heap, pattern_id, string, maxsplit.as_ref(), interns)
+        }
+        _ => {
+            args.drop_with_heap(heap);
+            Err(ExcType::attribute_error(Type::RePattern, attr.as_str(interns)))
+        }
+    }
+}
+
+/// Implements `search()`, `match()` and `fullmatch()`, returning the match or `None`.
+pub(crate) fn pattern_search(
+    heap: &mut Heap<impl ResourceTracker>,
+    pattern_id: HeapId,
+    string: &Value,
+    pos: Option<&Value>,
+    endpos: Option<&Value>,
+    anchor: Anchor,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let mut matcher = Matcher::new(heap, pattern_id, string, pos, endpos, interns)?;
+    match matcher.search(anchor, heap)? {
+        Some(captures) => Ok(matcher.allocate_match(heap, string, captures)?),
+        None => Ok(Value::None),
+    }
+}
+
+/// Implements `findall()`.
+///
+/// Each item is the whole match if the pattern has no groups, the text of the group if it
+/// has one, or a tuple of the groups' text otherwise, with unmatched groups as `''`.
+pub(crate) fn pattern_findall(
+    heap: &mut Heap<impl ResourceTracker>,
+    pattern_id: HeapId,
+    string: &Value,
+    pos: Option<&Value>,
+    endpos: Option<&Value>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let mut matcher = Matcher::new(heap, pattern_id, string, pos, endpos, interns)?;
+    let items = collect_matches(&mut matcher, heap, |matcher, heap, captures| {
+        let group_text = |span: Option<(usize, usize)>| span.map_or("", |(start, end)| &matcher.text[start..end]);
+        match captures.spans.as_slice() {
+            [span] | [_, span] => allocate_string(group_text(*span).to_owned(), heap),
+            [_, groups @ ..] => {
+                let values = allocate_strings(groups.iter().map(|span| Some(group_text(*span))), heap)?;
+                Ok(allocate_tuple(values.into(), heap)?)
+            }
+            [] => unreachable!("the whole match always has a span"),
+        }
+    })?;
+    Ok(Value::Ref(heap.allocate(HeapData::List(List::new(items)))?))
+}
+
+/// Implements `finditer()`.
+///
+/// All the matches are found up front, and an iterator over them returned.
+pub(crate) fn pattern_finditer(
+    heap: &mut Heap<impl ResourceTracker>,
+    pattern_id: HeapId,
+    string: &Value,
+    pos: Option<&Value>,
+    endpos: Option<&Value>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let mut matcher = Matcher::new(heap, pattern_id, string, pos, endpos, interns)?;
+    let items = collect_matches(&mut matcher, heap, |matcher, heap, captures| {
+        Ok(matcher.allocate_match(heap, string, captures)?)
+    })?;
+    let list = Value::Ref(heap.allocate(HeapData::List(List::new(items)))?);
+    let iter = MontyIter::new(list, heap, interns)?;
+    Ok(Value::Ref(heap.allocate(HeapData::Iter(iter))?))
+}
+
+/// Implements `split()`.
+///
+/// The text of any groups is included between the pieces, with `None` for unmatched groups.
+/// At most `maxsplit` splits are made if it's positive, and none if it's negative.
+pub(crate) fn pattern_split(
+    heap: &mut Heap<impl ResourceTracker>,
+    pattern_id: HeapId,
+    string: &Value,
+    maxsplit: Option<&Value>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let maxsplit = match maxsplit {
+        Some(maxsplit) => maxsplit.as_int(heap)?,
+        None => 0,
+    };
+    let mut matcher = Matcher::new(heap, pattern_id, string, None, None, interns)?;
+    let mut items = Vec::new();
+    let mut last = 0;
+    let mut splits = 0;
+    let result = loop {
+        if maxsplit < 0 || (maxsplit > 0 && splits >= maxsplit) {
+            break Ok(());
+        }
+        let captures = match matcher.next_match(heap) {
+            Ok(Some(captures)) => captures,
+            Ok(None) => break Ok(()),
+            Err(e) => break Err(e.into()),
+        };
+        let (start, end) = captures.spans[0].expect("the whole match always has a span");
+        let text = matcher.text();
+        let pieces = std::iter::once(Some(&text[last..start]))
+            .chain(captures.spans[1..].iter().map(|span| span.map(|(s, e)| &text[s..e])));
+        match allocate_strings(pieces, heap) {
+            Ok(values) => items.extend(values),
+            Err(e) => break Err(e),
+        }
+        last = end;
+        splits += 1;
+    };
+    let result = result.and_then(|()| allocate_string(matcher.text()[last..].to_owned(), heap));
+    match result {
+        Ok(rest) => items.push(rest),
+        Err(e) => {
+            items.drop_with_heap(heap);
+            return Err(e);
+        }
+    }
+    Ok(Value::Ref(heap.allocate(HeapData::List(List::new(items)))?))
+}
+
+/// Finds every match, converting each to a value with `f`.
+fn collect_matches<T: ResourceTracker>(
+    matcher: &mut Matcher,
+    heap: &mut Heap<T>,
+    mut f: impl FnMut(&mut Matcher, &mut Heap<T>, Captures) -> RunResult<Value>,
+) -> RunResult<Vec<Value>> {
+    let mut items = Vec::new();
+    let result = loop {
+        let captures = match matcher.next_match(heap) {
+            Ok(Some(captures)) => captures,
+            Ok(None) => break Ok(()),
+            Err(e) => break Err(e.into()),
+        };
+        match f(matcher, heap, captures) {
+            Ok(item) => items.push(item),
+            Err(e) => break Err(e),
+        }
+    };
+    match result {
+        Ok(()) => Ok(items),
+        Err(e) => {
+            items.drop_with_heap(heap);
+            Err(e)
+        }
+    }
+}
+
+/// Allocates a string for each piece of text, or `None` for missing pieces.
+fn allocate_strings<'a>(
+    pieces: impl IntoIterator<Item = Option<&'a str>>,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> RunResult<Vec<Value>> {
+    let mut values = Vec::new();
+    for piece in pieces {
+        let value = match piece {
+            Some(piece) => match allocate_string(piece.to_owned(), heap) {
+                Ok(value) => value,
+                Err(e) => {
+                    values.drop_with_heap(heap);
+                    return Err(e);
+                }
+            },
+            None => Value::None,
+        };
+        values.push(value);
+    }
+    Ok(values)
+}
+
+/// Converts a group number or character index to a Python int.
+fn group_number(n: usize) -> i64 {
+    i64::try_from(n).expect("index fits in i64")
+}
+
+/// Returns the start of `s` up to `max` characters.
+fn truncate_chars(s: &str, max: usize) -> &str {
+    s.char_indices().nth(max).map_or(s, |(offset, _)| &s[..offset])
+}
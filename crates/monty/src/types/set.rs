@@ -3,7 +3,7 @@ use std::fmt::Write;
 use ahash::AHashSet;
 use hashbrown::HashTable;
 
-use super::{MontyIter, PyTrait};
+use super::{MontyIter, PyTrait, class::unhashable_error};
 use crate::{
     args::ArgValues,
     exception_private::{ExcType, RunResult},
@@ -120,7 +120,7 @@ impl SetStorage {
     /// the set, it will be dropped.
     fn add(&mut self, value: Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<bool> {
         let Some(hash) = value.py_hash(heap, interns) else {
-            let err = ExcType::type_error_unhashable_set_element(value.py_type(heap));
+            let err = unhashable_error(&value, ExcType::type_error_unhashable_set_element, heap, interns);
             value.drop_with_heap(heap);
             return Err(err);
         };
@@ -150,7 +150,7 @@ impl SetStorage {
     fn remove(&mut self, value: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<bool> {
         let hash = value
             .py_hash(heap, interns)
-            .ok_or_else(|| ExcType::type_error_unhashable_set_element(value.py_type(heap)))?;
+            .ok_or_else(|| unhashable_error(value, ExcType::type_error_unhashable_set_element, heap, interns))?;
 
         let entry = self.indices.entry(
             hash,
@@ -233,7 +233,7 @@ impl SetStorage {
     pub fn contains(&self, value: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<bool> {
         let hash = value
             .py_hash(heap, interns)
-            .ok_or_else(|| ExcType::type_error_unhashable_set_element(value.py_type(heap)))?;
+            .ok_or_else(|| unhashable_error(value, ExcType::type_error_unhashable_set_element, heap, interns))?;
 
         Ok(self
             .indices
@@ -246,6 +246,11 @@ impl SetStorage {
         self.entries.iter().map(|e| &e.value)
     }
 
+    /// Returns the values stored with the given hash, for the VM to compare with `__eq__`.
+    pub(crate) fn values_with_hash(&self, hash: u64) -> impl Iterator<Item = &Value> {
+        self.indices.iter_hash(hash).map(|&idx| &self.entries[idx].value)
+    }
+
     /// Returns the value at the given index, if valid.
     ///
     /// Used by MontyIter for index-based iteration.
@@ -950,6 +955,49 @@ impl Set {
     }
 }
 
+/// A set operator applied by [`set_operation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SetOp {
+    /// `|`
+    Union,
+    /// `&`
+    Intersection,
+    /// `-`
+    Difference,
+    /// `^`
+    SymmetricDifference,
+}
+
+/// Applies a set operator to two iterables, returning a new `set`.
+///
+/// Used for the operators of set-like dict views, which accept any iterable on either
+/// side: the left operand is converted to a set and combined with the right one.
+/// Takes ownership of both operands.
+pub(crate) fn set_operation(
+    lhs: Value,
+    rhs: Value,
+    op: SetOp,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let set = match Set::from_iterable(lhs, heap, interns) {
+        Ok(set) => set,
+        Err(err) => {
+            rhs.drop_with_heap(heap);
+            return Err(err);
+        }
+    };
+    let result = match op {
+        SetOp::Union => set.union_from_value(rhs, heap, interns),
+        SetOp::Intersection => set.intersection_from_value(rhs, heap, interns),
+        SetOp::Difference => set.difference_from_value(rhs, heap, interns),
+        SetOp::SymmetricDifference => set.symmetric_difference_from_value(rhs, heap, interns),
+    };
+    set.0.drop_all_values(heap);
+    let heap_id = heap.allocate(HeapData::Set(result?))?;
+    Ok(Value::Ref(heap_id))
+}
+
 /// Python frozenset type - immutable, unordered collection of unique hashable elements.
 ///
 /// FrozenSets support the same set algebra operations as sets (union, intersection,
@@ -11,7 +11,7 @@ use smallvec::smallvec;
 use super::{Bytes, MontyIter, PyTrait};
 use crate::{
     args::ArgValues,
-    exception_private::{ExcType, RunResult},
+    exception_private::{ExcType, RunError, RunResult},
     heap::{DropWithHeap, Heap, HeapData, HeapId},
     intern::{Interns, StaticStrings, StringId},
     resource::{ResourceError, ResourceTracker},
@@ -348,10 +348,6 @@ pub fn call_str_method(
 ///
 /// The following Python string methods are not yet implemented:
 ///
-/// - `format()` - Requires implementing the format spec mini-language (PEP 3101),
-///   which is complex and involves parsing format specifications like `{:>10.2f}`.
-/// - `format_map(mapping)` - Similar to `format()` but takes a mapping; depends on
-///   `format()` implementation.
 /// - `maketrans()` / `translate()` - Character translation tables; moderate complexity,
 ///   requires building and applying Unicode translation maps.
 /// - `expandtabs(tabsize=8)` - Tab expansion; simple but rarely used in practice.
@@ -468,6 +464,14 @@ fn call_str_method_impl(
             let iterable = args.get_one_arg("str.join", heap)?;
             str_join(s, iterable, heap, interns)
         }
+        // Replacement fields may call `__str__` or `__repr__` on instances of user-defined
+        // classes, so the VM formats strings itself
+        StaticStrings::Format | StaticStrings::FormatMap => {
+            args.drop_with_heap(heap);
+            Err(RunError::internal(
+                "str.format() and str.format_map() must be called through the VM",
+            ))
+        }
         _ => {
             args.drop_with_heap(heap);
             Err(ExcType::attribute_error(Type::Str, method.into()))
@@ -617,11 +621,14 @@ fn str_upper(s: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value>
 }
 
 /// Implements Python's `str.capitalize()` method.
-///
-/// Returns a copy of the string with its first character capitalized and the rest lowercased.
 fn str_capitalize(s: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    allocate_string(capitalize(s), heap)
+}
+
+/// Returns a copy of the string with its first character capitalized and the rest lowercased.
+pub(crate) fn capitalize(s: &str) -> String {
     let mut chars = s.chars();
-    let result = match chars.next() {
+    match chars.next() {
         None => String::new(),
         Some(first) => {
             let mut result = first.to_uppercase().to_string();
@@ -630,8 +637,7 @@ fn str_capitalize(s: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<V
             }
             result
         }
-    };
-    allocate_string(result, heap)
+    }
 }
 
 /// Implements Python's `str.title()` method.
@@ -770,7 +776,7 @@ fn str_isdecimal(s: &str) -> bool {
 ///
 /// This covers decimal digit ranges from various scripts including ASCII, Arabic-Indic,
 /// Devanagari, Bengali, Thai, Fullwidth, and many others.
-fn is_unicode_decimal(c: char) -> bool {
+pub(crate) fn is_unicode_decimal(c: char) -> bool {
     let cp = c as u32;
     matches!(
         cp,
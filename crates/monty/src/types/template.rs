@@ -0,0 +1,310 @@
+//! The `string.Template` type of the `string` module (see `modules/string.rs`).
+//!
+//! Templates use CPython's default syntax: `$$` is an escaped `$`, and `$name` and
+//! `${name}` are placeholders whose names are ASCII identifiers. Values are looked up in
+//! the keyword arguments first and then in the mapping, and converted with `str()`.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use super::{AttrCallResult, List, PyTrait, Type, str::allocate_string};
+use crate::{
+    args::ArgValues,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    resource::ResourceTracker,
+    value::{EitherStr, Value},
+};
+
+/// A `string.Template`, holding its template string.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Template {
+    template: String,
+}
+
+impl Template {
+    /// Creates a `string.Template` from the `Template(template)` constructor call.
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let template = args.get_one_arg("Template", heap)?;
+        let text = template.as_either_str(heap).map(|text| text.as_str(interns).to_owned());
+        let Some(text) = text else {
+            let error = ExcType::type_error(format!(
+                "expected string or bytes-like object, got '{}'",
+                template.py_type(heap)
+            ));
+            template.drop_with_heap(heap);
+            return Err(error);
+        };
+        template.drop_with_heap(heap);
+        Ok(Value::Ref(heap.allocate(HeapData::Template(Self { template: text }))?))
+    }
+}
+
+impl PyTrait for Template {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Template
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.template.len()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, _other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        // Templates compare by identity, which is handled at the Value level
+        false
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {}
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        f.write_str("<string.Template object>")
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = match StaticStrings::from_string_id(attr_id) {
+            Some(StaticStrings::Template) => allocate_string(self.template.clone(), heap)?,
+            Some(StaticStrings::Delimiter) => allocate_string("$".to_owned(), heap)?,
+            _ => return Ok(None),
+        };
+        Ok(Some(AttrCallResult::Value(value)))
+    }
+}
+
+/// Calls a method of the template `template_id`.
+///
+/// The template stays on the heap while its arguments are read, since a value substituted
+/// into it may be the template itself.
+pub(crate) fn call_template_method(
+    heap: &mut Heap<impl ResourceTracker>,
+    template_id: HeapId,
+    attr: &EitherStr,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let HeapData::Template(template) = heap.get(template_id) else {
+        unreachable!("template methods are only called for templates")
+    };
+    let template = template.template.clone();
+    match attr.static_string() {
+        Some(method @ (StaticStrings::Substitute | StaticStrings::SafeSubstitute)) => {
+            let safe = method == StaticStrings::SafeSubstitute;
+            let name = if safe {
+                "Template.safe_substitute"
+            } else {
+                "Template.substitute"
+            };
+            let (positional, kwargs) = args.into_parts();
+            let mut positional: Vec<Value> = positional.collect();
+            let mut keywords = Vec::with_capacity(kwargs.len());
+            for (key, value) in kwargs {
+                let key_text = key
+                    .as_either_str(heap)
+                    .map(|text| text.as_str(interns).to_owned())
+                    .unwrap_or_default();
+                key.drop_with_heap(heap);
+                keywords.push((key_text, value));
+            }
+            let result = if positional.len() > 1 {
+                Err(ExcType::type_error(format!(
+                    "{name}() takes from 1 to 2 positional arguments but {} were given",
+                    positional.len() + 1
+                )))
+            } else {
+                substitute(&template, safe, positional.first(), &keywords, heap, interns)
+            };
+            positional.drop_with_heap(heap);
+            for (_, value) in keywords {
+                value.drop_with_heap(heap);
+            }
+            allocate_string(result?, heap)
+        }
+        Some(StaticStrings::GetIdentifiers) => {
+            args.check_zero_args("Template.get_identifiers", heap)?;
+            let mut identifiers: Vec<&str> = Vec::new();
+            for placeholder in placeholders(&template) {
+                if let Placeholder::Named(name) | Placeholder::Braced(name) = placeholder.kind
+                    && !identifiers.contains(&name)
+                {
+                    identifiers.push(name);
+                }
+            }
+            let mut items = Vec::with_capacity(identifiers.len());
+            for name in identifiers {
+                items.push(allocate_string(name.to_owned(), heap)?);
+            }
+            Ok(Value::Ref(heap.allocate(HeapData::List(List::new(items)))?))
+        }
+        Some(StaticStrings::IsValid) => {
+            args.check_zero_args("Template.is_valid", heap)?;
+            let valid = placeholders(&template).all(|placeholder| placeholder.kind != Placeholder::Invalid);
+            Ok(Value::Bool(valid))
+        }
+        _ => {
+            args.drop_with_heap(heap);
+            Err(ExcType::attribute_error(Type::Template, attr.as_str(interns)))
+        }
+    }
+}
+
+/// Substitutes the placeholders of `template` with the values of `keywords`, or else of
+/// `mapping`.
+///
+/// `safe_substitute()` leaves placeholders without a value and invalid ones as they are,
+/// where `substitute()` raises `KeyError` and `ValueError`.
+fn substitute(
+    template: &str,
+    safe: bool,
+    mapping: Option<&Value>,
+    keywords: &[(String, Value)],
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<String> {
+    let mut result = String::with_capacity(template.len());
+    let mut last = 0;
+    for placeholder in placeholders(template) {
+        result.push_str(&template[last..placeholder.start]);
+        last = placeholder.end;
+        let original = &template[placeholder.start..placeholder.end];
+        let name = match placeholder.kind {
+            Placeholder::Escaped => {
+                result.push('$');
+                continue;
+            }
+            Placeholder::Invalid if safe => {
+                result.push_str(original);
+                continue;
+            }
+            Placeholder::Invalid => return Err(invalid_placeholder(template, placeholder.end)),
+            Placeholder::Named(name) | Placeholder::Braced(name) => name,
+        };
+
+        if let Some((_, value)) = keywords.iter().find(|(key, _)| key == name) {
+            result.push_str(&value.py_str(heap, interns));
+            continue;
+        }
+        let key = allocate_string(name.to_owned(), heap)?;
+        let value = match mapping {
+            Some(mapping) => mapping.py_getitem(&key, heap, interns),
+            None => Err(ExcType::key_error(&key, heap, interns)),
+        };
+        key.drop_with_heap(heap);
+        match value {
+            Ok(value) => {
+                result.push_str(&value.py_str(heap, interns));
+                value.drop_with_heap(heap);
+            }
+            Err(RunError::Exc(exc)) if safe && exc.exc.exc_type() == ExcType::KeyError => result.push_str(original),
+            Err(e) => return Err(e),
+        }
+    }
+    result.push_str(&template[last..]);
+    heap.tracker().check_large_result(result.len())?;
+    Ok(result)
+}
+
+/// Creates the `ValueError` of an invalid placeholder, whose `$` ends just before `end`,
+/// giving its line and column as CPython does.
+fn invalid_placeholder(template: &str, end: usize) -> RunError {
+    let mut line = 1;
+    let mut column = 0;
+    let mut chars = template[..end].chars().peekable();
+    while let Some(c) = chars.next() {
+        column += 1;
+        let line_break = match c {
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                true
+            }
+            '\n' | '\x0b' | '\x0c' | '\x1c' | '\x1d' | '\x1e' | '\u{85}' | '\u{2028}' | '\u{2029}' => true,
+            _ => false,
+        };
+        if line_break {
+            line += 1;
+            column = 0;
+        }
+    }
+    SimpleException::new_msg(
+        ExcType::ValueError,
+        format!("Invalid placeholder in string: line {line}, col {column}"),
+    )
+    .into()
+}
+
+/// A `$` placeholder of a template, from its `$` to its end.
+struct PlaceholderMatch<'a> {
+    start: usize,
+    end: usize,
+    kind: Placeholder<'a>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Placeholder<'a> {
+    /// `$$`, which stands for a `$`.
+    Escaped,
+    /// `$name`.
+    Named(&'a str),
+    /// `${name}`.
+    Braced(&'a str),
+    /// A `$` followed by anything else.
+    Invalid,
+}
+
+/// Iterates over the placeholders of a template, left to right.
+fn placeholders(template: &str) -> impl Iterator<Item = PlaceholderMatch<'_>> {
+    let mut position = 0;
+    std::iter::from_fn(move || {
+        let start = position + template[position..].find('$')?;
+        let rest = &template[start + 1..];
+        let (kind, length) = if rest.starts_with('$') {
+            (Placeholder::Escaped, 1)
+        } else if let length @ 1.. = identifier_len(rest) {
+            (Placeholder::Named(&rest[..length]), length)
+        } else if let Some(braced) = rest.strip_prefix('{')
+            && let length @ 1.. = identifier_len(braced)
+            && braced[length..].starts_with('}')
+        {
+            (Placeholder::Braced(&braced[..length]), length + 2)
+        } else {
+            (Placeholder::Invalid, 0)
+        };
+        position = start + 1 + length;
+        Some(PlaceholderMatch {
+            start,
+            end: position,
+            kind,
+        })
+    })
+}
+
+/// Returns the length of the ASCII identifier at the start of `text`, or 0 if there is none.
+fn identifier_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    if !bytes.first().is_some_and(|b| b.is_ascii_alphabetic() || *b == b'_') {
+        return 0;
+    }
+    bytes
+        .iter()
+        .position(|b| !(b.is_ascii_alphanumeric() || *b == b'_'))
+        .unwrap_or(bytes.len())
+}
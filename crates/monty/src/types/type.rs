@@ -8,12 +8,14 @@ use crate::{
     defer_drop,
     exception_private::{ExcType, RunError, RunResult, SimpleException},
     heap::{Heap, HeapData},
-    intern::Interns,
+    intern::{Interns, StaticStrings},
     resource::ResourceTracker,
     types::{
-        Bytes, Dict, FrozenSet, List, LongInt, MontyIter, Path, PyTrait, Range, Set, Slice, Str, Tuple, str::StringRepr,
+        ByteArray, Bytes, Complex, Date, DateTime, Decimal, DecimalContext, Deque, Dict, Fraction, FrozenSet, List,
+        LongInt, MontyIter, Partial, Path, PyTrait, Random, Range, Set, Slice, Str, Template, Time, TimeDelta,
+        TimeZone, Tuple, Uuid, counter, r#enum, str::StringRepr,
     },
-    value::Value,
+    value::{Marker, Value},
 };
 
 /// Represents the Python type of a value.
@@ -33,14 +35,25 @@ pub enum Type {
     Bool,
     Int,
     Float,
+    Complex,
     Range,
     Slice,
     Str,
     Bytes,
+    ByteArray,
     List,
     Tuple,
     NamedTuple,
     Dict,
+    /// The view returned by `dict.keys()` - displays as "dict_keys"
+    #[strum(disabled)]
+    DictKeys,
+    /// The view returned by `dict.values()` - displays as "dict_values"
+    #[strum(disabled)]
+    DictValues,
+    /// The view returned by `dict.items()` - displays as "dict_items"
+    #[strum(disabled)]
+    DictItems,
     Set,
     FrozenSet,
     Dataclass,
@@ -53,6 +66,8 @@ pub enum Type {
     Iterator,
     /// Coroutine type for async functions and external futures.
     Coroutine,
+    /// Generator type for generator functions and generator expressions.
+    Generator,
     Module,
     /// Marker types like stdout/stderr - displays as "TextIOWrapper"
     #[strum(serialize = "TextIOWrapper")]
@@ -66,6 +81,95 @@ pub enum Type {
     /// A property descriptor - displays as "property"
     #[strum(serialize = "property")]
     Property,
+    /// The `object` base class, usable as an explicit base in class definitions.
+    Object,
+    /// An instance of a user-defined class - displays as "object"
+    #[strum(disabled)]
+    Instance,
+    /// A function bound to an instance - displays as "method"
+    #[strum(disabled)]
+    Method,
+    /// The proxy returned by `super()` - displays as "super"
+    #[strum(disabled)]
+    Super,
+    /// A compiled regular expression from `re.compile()` - displays as "re.Pattern"
+    #[strum(disabled)]
+    RePattern,
+    /// A regular expression match - displays as "re.Match"
+    #[strum(disabled)]
+    ReMatch,
+    /// A calendar date from the `datetime` module - displays as "datetime.date"
+    #[strum(disabled)]
+    Date,
+    /// A time of day from the `datetime` module - displays as "datetime.time"
+    #[strum(disabled)]
+    Time,
+    /// A date and time from the `datetime` module - displays as "datetime.datetime"
+    #[strum(disabled)]
+    DateTime,
+    /// A duration from the `datetime` module - displays as "datetime.timedelta"
+    #[strum(disabled)]
+    TimeDelta,
+    /// A fixed-offset timezone from the `datetime` module - displays as "datetime.timezone"
+    #[strum(disabled)]
+    TimeZone,
+    /// A dict with a default factory from the `collections` module - displays as "collections.defaultdict"
+    #[strum(disabled)]
+    DefaultDict,
+    /// A dict of counts from the `collections` module - displays as "collections.Counter"
+    #[strum(disabled)]
+    Counter,
+    /// An order-sensitive dict from the `collections` module - displays as "collections.OrderedDict"
+    #[strum(disabled)]
+    OrderedDict,
+    /// A double-ended queue from the `collections` module - displays as "collections.deque"
+    #[strum(disabled)]
+    Deque,
+    /// A partial application from the `functools` module - displays as "functools.partial"
+    #[strum(disabled)]
+    Partial,
+    /// A function wrapped by `functools.lru_cache()` - displays as "functools._lru_cache_wrapper"
+    #[strum(disabled)]
+    LruCacheWrapper,
+    /// A key created by `functools.cmp_to_key()` - displays as "functools.KeyWrapper"
+    #[strum(disabled)]
+    CmpToKey,
+    /// A random number generator from the `random` module - displays as "random.Random"
+    #[strum(disabled)]
+    Random,
+    /// A `$`-substitution template from the `string` module - displays as "string.Template"
+    #[strum(disabled)]
+    Template,
+    /// A hash object from the `hashlib` module - displays as "_hashlib.HASH"
+    #[strum(disabled)]
+    Hash,
+    /// A UUID from the `uuid` module - displays as "uuid.UUID"
+    #[strum(disabled)]
+    Uuid,
+    /// The base class of enumerations from the `enum` module - displays as "enum.Enum"
+    #[strum(disabled)]
+    Enum,
+    /// The base class of int enumerations from the `enum` module - displays as "enum.IntEnum"
+    #[strum(disabled)]
+    IntEnum,
+    /// The base class of str enumerations from the `enum` module - displays as "enum.StrEnum"
+    #[strum(disabled)]
+    StrEnum,
+    /// The automatic member value placeholder from the `enum` module - displays as "enum.auto"
+    #[strum(disabled)]
+    EnumAuto,
+    /// A decimal number from the `decimal` module - displays as "decimal.Decimal"
+    #[strum(disabled)]
+    Decimal,
+    /// An arithmetic context from the `decimal` module - displays as "decimal.Context"
+    #[strum(disabled)]
+    DecimalContext,
+    /// The context manager of `decimal.localcontext()` - displays as "decimal.ContextManager"
+    #[strum(disabled)]
+    DecimalContextManager,
+    /// A rational number from the `fractions` module - displays as "fractions.Fraction"
+    #[strum(disabled)]
+    Fraction,
 }
 
 impl fmt::Display for Type {
@@ -77,14 +181,19 @@ impl fmt::Display for Type {
             Self::Bool => f.write_str("bool"),
             Self::Int => f.write_str("int"),
             Self::Float => f.write_str("float"),
+            Self::Complex => f.write_str("complex"),
             Self::Range => f.write_str("range"),
             Self::Slice => f.write_str("slice"),
             Self::Str => f.write_str("str"),
             Self::Bytes => f.write_str("bytes"),
+            Self::ByteArray => f.write_str("bytearray"),
             Self::List => f.write_str("list"),
             Self::Tuple => f.write_str("tuple"),
             Self::NamedTuple => f.write_str("namedtuple"),
             Self::Dict => f.write_str("dict"),
+            Self::DictKeys => f.write_str("dict_keys"),
+            Self::DictValues => f.write_str("dict_values"),
+            Self::DictItems => f.write_str("dict_items"),
             Self::Set => f.write_str("set"),
             Self::FrozenSet => f.write_str("frozenset"),
             Self::Dataclass => f.write_str("dataclass"),
@@ -94,11 +203,41 @@ impl fmt::Display for Type {
             Self::Cell => f.write_str("cell"),
             Self::Iterator => f.write_str("iterator"),
             Self::Coroutine => f.write_str("coroutine"),
+            Self::Generator => f.write_str("generator"),
             Self::Module => f.write_str("module"),
             Self::TextIOWrapper => f.write_str("_io.TextIOWrapper"),
             Self::SpecialForm => f.write_str("typing._SpecialForm"),
             Self::Path => f.write_str("PosixPath"),
             Self::Property => f.write_str("property"),
+            Self::Object | Self::Instance => f.write_str("object"),
+            Self::Method => f.write_str("method"),
+            Self::Super => f.write_str("super"),
+            Self::RePattern => f.write_str("re.Pattern"),
+            Self::ReMatch => f.write_str("re.Match"),
+            Self::Date => f.write_str("datetime.date"),
+            Self::Time => f.write_str("datetime.time"),
+            Self::DateTime => f.write_str("datetime.datetime"),
+            Self::TimeDelta => f.write_str("datetime.timedelta"),
+            Self::TimeZone => f.write_str("datetime.timezone"),
+            Self::DefaultDict => f.write_str("collections.defaultdict"),
+            Self::Counter => f.write_str("collections.Counter"),
+            Self::OrderedDict => f.write_str("collections.OrderedDict"),
+            Self::Deque => f.write_str("collections.deque"),
+            Self::Partial => f.write_str("functools.partial"),
+            Self::LruCacheWrapper => f.write_str("functools._lru_cache_wrapper"),
+            Self::CmpToKey => f.write_str("functools.KeyWrapper"),
+            Self::Random => f.write_str("random.Random"),
+            Self::Template => f.write_str("string.Template"),
+            Self::Hash => f.write_str("_hashlib.HASH"),
+            Self::Uuid => f.write_str("uuid.UUID"),
+            Self::Enum => f.write_str("enum.Enum"),
+            Self::IntEnum => f.write_str("enum.IntEnum"),
+            Self::StrEnum => f.write_str("enum.StrEnum"),
+            Self::EnumAuto => f.write_str("enum.auto"),
+            Self::Decimal => f.write_str("decimal.Decimal"),
+            Self::DecimalContext => f.write_str("decimal.Context"),
+            Self::DecimalContextManager => f.write_str("decimal.ContextManager"),
+            Self::Fraction => f.write_str("fractions.Fraction"),
         }
     }
 }
@@ -108,13 +247,36 @@ impl Type {
     ///
     /// This handles Python's subtype relationships:
     /// - `bool` is a subtype of `int` (so `isinstance(True, int)` returns True)
+    /// - `datetime` is a subtype of `date`
+    /// - `defaultdict`, `Counter` and `OrderedDict` are subtypes of `dict`
+    /// - named tuples are subtypes of `tuple`
+    /// - `IntEnum` and `StrEnum` are subtypes of `Enum`, and of `int` and `str` respectively
+    /// - every type is a subtype of `object`
     #[must_use]
     pub fn is_instance_of(self, other: Self) -> bool {
-        if self == other {
+        if self == other || other == Self::Object {
             true
         } else if self == Self::Bool && other == Self::Int {
             // bool is a subtype of int in Python
             true
+        } else if self == Self::DateTime && other == Self::Date {
+            // datetime is a subclass of date
+            true
+        } else if other == Self::Dict {
+            // the dict types of the collections module subclass dict
+            matches!(self, Self::DefaultDict | Self::Counter | Self::OrderedDict)
+        } else if self == Self::NamedTuple && other == Self::Tuple {
+            // named tuple classes subclass tuple
+            true
+        } else if matches!(self, Self::IntEnum | Self::StrEnum) && other == Self::Enum {
+            // members of int and str enums are enum members
+            true
+        } else if self == Self::IntEnum && other == Self::Int {
+            // `IntEnum` subclasses int
+            true
+        } else if self == Self::StrEnum && other == Self::Str {
+            // `StrEnum` subclasses str
+            true
         } else {
             false
         }
@@ -141,6 +303,8 @@ impl Type {
             Self::Slice => Some(11),
             Self::Iterator => Some(12),
             Self::Path => Some(13),
+            Self::ByteArray => Some(14),
+            Self::Complex => Some(15),
             _ => None,
         }
     }
@@ -165,6 +329,8 @@ impl Type {
             11 => Some(Self::Slice),
             12 => Some(Self::Iterator),
             13 => Some(Self::Path),
+            14 => Some(Self::ByteArray),
+            15 => Some(Self::Complex),
             _ => None,
         }
     }
@@ -188,16 +354,43 @@ impl Type {
             Self::FrozenSet => FrozenSet::init(heap, args, interns),
             Self::Str => Str::init(heap, args, interns),
             Self::Bytes => Bytes::init(heap, args, interns),
+            Self::ByteArray => ByteArray::init(heap, args, interns),
+            Self::Complex => Complex::init(heap, args, interns),
             Self::Range => Range::init(heap, args),
             Self::Slice => Slice::init(heap, args),
             Self::Iterator => MontyIter::init(heap, args, interns),
             Self::Path => Path::init(heap, args, interns),
+            Self::Date => Date::init(heap, args, interns),
+            Self::Time => Time::init(heap, args, interns),
+            Self::DateTime => DateTime::init(heap, args, interns),
+            Self::TimeDelta => TimeDelta::init(heap, args, interns),
+            Self::TimeZone => TimeZone::init(heap, args, interns),
+            Self::DefaultDict => Dict::init_default_dict(heap, args, interns),
+            Self::Counter => counter::init(heap, args, interns),
+            Self::OrderedDict => Dict::init_ordered_dict(heap, args, interns),
+            Self::Deque => Deque::init(heap, args, interns),
+            Self::Partial => Partial::init(heap, args, interns),
+            Self::Random => Random::init(heap, args, interns),
+            Self::Template => Template::init(heap, args, interns),
+            Self::Uuid => Uuid::init(heap, args, interns),
+            Self::Decimal => Decimal::init(heap, args, interns),
+            Self::DecimalContext => DecimalContext::init(heap, args, interns),
+            Self::Fraction => Fraction::init(heap, args, interns),
+            Self::EnumAuto => {
+                args.check_zero_args("auto", heap)?;
+                Ok(Value::Marker(Marker(StaticStrings::Auto)))
+            }
 
             // Primitive types - inline implementation
             Self::Int => {
                 let Some(v) = args.get_zero_one_arg("int", heap)? else {
                     return Ok(Value::Int(0));
                 };
+                // `IntEnum` members convert like their values
+                if let Some(inner) = r#enum::owned_mixin_value(&v, heap, interns) {
+                    v.drop_with_heap(heap);
+                    return self.call(heap, ArgValues::One(inner), interns);
+                }
                 defer_drop!(v, heap);
                 match v {
                     Value::Int(i) => Ok(Value::Int(*i)),
@@ -212,6 +405,11 @@ impl Type {
                                 parse_int_from_str(&s, heap)
                             }
                             HeapData::LongInt(li) => li.clone().into_value(heap).map_err(Into::into),
+                            HeapData::Decimal(decimal) => decimal.clone().to_int(heap),
+                            HeapData::Fraction(fraction) => {
+                                let int = fraction.trunc();
+                                LongInt::new(int).into_value(heap).map_err(Into::into)
+                            }
                             _ => Err(ExcType::type_error_int_conversion(v.py_type(heap))),
                         }
                     }
@@ -222,6 +420,11 @@ impl Type {
                 let Some(v) = args.get_zero_one_arg("float", heap)? else {
                     return Ok(Value::Float(0.0));
                 };
+                // `IntEnum` members convert like their values
+                if let Some(inner) = r#enum::owned_mixin_value(&v, heap, interns) {
+                    v.drop_with_heap(heap);
+                    return self.call(heap, ArgValues::One(inner), interns);
+                }
                 defer_drop!(v, heap);
                 match v {
                     Value::Float(f) => Ok(Value::Float(*f)),
@@ -232,6 +435,8 @@ impl Type {
                     }
                     Value::Ref(heap_id) => match heap.get(*heap_id) {
                         HeapData::Str(s) => Ok(Value::Float(parse_f64_from_str(s.as_str())?)),
+                        HeapData::Decimal(decimal) => Ok(Value::Float(decimal.to_f64())),
+                        HeapData::Fraction(fraction) => Ok(Value::Float(fraction.to_float()?)),
                         _ => Err(ExcType::type_error_float_conversion(v.py_type(heap))),
                     },
                     _ => Err(ExcType::type_error_float_conversion(v.py_type(heap))),
@@ -0,0 +1,365 @@
+//! The `uuid.UUID` type of the `uuid` module (see `modules/uuid.rs`).
+//!
+//! A [`Uuid`] is an immutable 128-bit value, which compares, orders and hashes by its int.
+//! `uuid4()` needs random bytes, which Monty never reads itself: the VM asks the host for
+//! them with `OsFunction::Uuid4`, and makes the UUID with [`finish_uuid4`] when it resumes.
+
+use std::{borrow::Cow, fmt::Write};
+
+use ahash::AHashSet;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+use super::{AttrCallResult, Bytes, LongInt, PyTrait, Type, str::allocate_string};
+use crate::{
+    args::{ArgValues, bind_args},
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    modules::math::int_arg,
+    resource::ResourceTracker,
+    value::Value,
+};
+
+/// The variant bits of RFC 4122 UUIDs, in the position of `clock_seq_hi_variant`.
+const VARIANT_MASK: u128 = 0xc000 << 48;
+const VARIANT_RFC_4122: u128 = 0x8000 << 48;
+/// The version bits, in the position of `time_hi_version`.
+const VERSION_MASK: u128 = 0xf000 << 64;
+
+/// The namespace UUIDs `uuid.NAMESPACE_DNS`, `NAMESPACE_URL`, `NAMESPACE_OID` and
+/// `NAMESPACE_X500`, for `uuid3()` and `uuid5()`.
+pub(crate) const NAMESPACE_DNS: Uuid = Uuid(0x6ba7_b810_9dad_11d1_80b4_00c0_4fd4_30c8);
+pub(crate) const NAMESPACE_URL: Uuid = Uuid(0x6ba7_b811_9dad_11d1_80b4_00c0_4fd4_30c8);
+pub(crate) const NAMESPACE_OID: Uuid = Uuid(0x6ba7_b812_9dad_11d1_80b4_00c0_4fd4_30c8);
+pub(crate) const NAMESPACE_X500: Uuid = Uuid(0x6ba7_b814_9dad_11d1_80b4_00c0_4fd4_30c8);
+
+/// A `uuid.UUID`, stored as its int.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Uuid(u128);
+
+impl Uuid {
+    /// Creates a UUID from its 16 bytes in big-endian order, setting the variant to
+    /// RFC 4122 and the version to `version`, as `UUID(bytes=..., version=...)` does.
+    #[must_use]
+    pub fn from_bytes_with_version(bytes: [u8; 16], version: u8) -> Self {
+        Self(u128::from_be_bytes(bytes)).with_version(version)
+    }
+
+    /// Creates a `uuid.UUID` from the `UUID(hex=None, bytes=None, bytes_le=None, fields=None,
+    /// int=None, version=None)` constructor call.
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let bound = bind_args(
+            "UUID",
+            args,
+            ["hex", "bytes", "bytes_le", "fields", "int", "version"],
+            0,
+            heap,
+            interns,
+        )?;
+        let result = Self::from_args(&bound, heap, interns);
+        for value in bound {
+            value.drop_with_heap(heap);
+        }
+        Ok(Value::Ref(heap.allocate(HeapData::Uuid(result?))?))
+    }
+
+    fn from_args(bound: &[Option<Value>; 6], heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Self> {
+        let [hex, bytes, bytes_le, fields, int, version] = bound;
+        let given = [hex, bytes, bytes_le, fields, int]
+            .into_iter()
+            .filter(|arg| !matches!(arg, None | Some(Value::None)))
+            .count();
+        if given != 1 {
+            return Err(ExcType::type_error(
+                "one of the hex, bytes, bytes_le, fields, or int arguments must be given",
+            ));
+        }
+
+        let uuid = if let Some(hex) = hex.as_ref().filter(|v| !matches!(v, Value::None)) {
+            Self::parse_hex(hex, heap, interns)?
+        } else if let Some(bytes) = bytes.as_ref().filter(|v| !matches!(v, Value::None)) {
+            Self(u128::from_be_bytes(uuid_bytes(bytes, "bytes", heap, interns)?))
+        } else if let Some(bytes_le) = bytes_le.as_ref().filter(|v| !matches!(v, Value::None)) {
+            Self::from_bytes_le(uuid_bytes(bytes_le, "bytes_le", heap, interns)?)
+        } else if let Some(fields) = fields.as_ref().filter(|v| !matches!(v, Value::None)) {
+            Self::from_fields(fields, heap)?
+        } else {
+            let int = int_arg(int.as_ref().expect("one argument is given"), heap)?;
+            match int.to_u128() {
+                Some(int) => Self(int),
+                None => return Err(ExcType::value_error("int is out of range (need a 128-bit value)")),
+            }
+        };
+
+        match version {
+            None | Some(Value::None) => Ok(uuid),
+            Some(version) => match int_arg(version, heap)?.to_u8() {
+                Some(version @ 1..=5) => Ok(uuid.with_version(version)),
+                _ => Err(ExcType::value_error("illegal version number")),
+            },
+        }
+    }
+
+    /// Parses a string of 32 hex digits, optionally with hyphens, braces and a URN prefix.
+    fn parse_hex(hex: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Self> {
+        let text = match hex {
+            Value::InternString(id) => interns.get_str(*id),
+            Value::Ref(id) => match heap.get(*id) {
+                HeapData::Str(s) => s.as_str(),
+                _ => return Err(ExcType::attribute_error(hex.py_type(heap), "replace")),
+            },
+            _ => return Err(ExcType::attribute_error(hex.py_type(heap), "replace")),
+        };
+        let digits = text.replace("urn:", "").replace("uuid:", "");
+        let digits = digits.trim_matches(['{', '}']).replace('-', "");
+        if digits.chars().count() != 32 {
+            return Err(ExcType::value_error("badly formed hexadecimal UUID string"));
+        }
+        match u128::from_str_radix(&digits, 16) {
+            Ok(int) => Ok(Self(int)),
+            Err(_) => Err(ExcType::value_error(format!(
+                "invalid literal for int() with base 16: '{digits}'"
+            ))),
+        }
+    }
+
+    /// Creates a UUID from its bytes with the first three fields in little-endian order.
+    fn from_bytes_le(mut bytes: [u8; 16]) -> Self {
+        bytes[..4].reverse();
+        bytes[4..6].reverse();
+        bytes[6..8].reverse();
+        Self(u128::from_be_bytes(bytes))
+    }
+
+    /// Creates a UUID from a 6-tuple of its fields.
+    fn from_fields(fields: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<Self> {
+        let items = match fields {
+            Value::Ref(id) => match heap.get(*id) {
+                HeapData::Tuple(tuple) => Some(tuple.as_vec()),
+                _ => None,
+            },
+            _ => None,
+        };
+        let Some(items) = items else {
+            return Err(ExcType::type_error(format!(
+                "object of type '{}' has no len()",
+                fields.py_type(heap)
+            )));
+        };
+        if items.len() != 6 {
+            return Err(ExcType::value_error("fields is not a 6-tuple"));
+        }
+        let mut int = 0u128;
+        for (index, (item, (bits, size))) in items
+            .iter()
+            .zip([
+                (32, "a 32"),
+                (16, "a 16"),
+                (16, "a 16"),
+                (8, "an 8"),
+                (8, "an 8"),
+                (48, "a 48"),
+            ])
+            .enumerate()
+        {
+            let field = int_arg(item, heap)?;
+            let Some(field) = field.to_u64().filter(|field| field >> bits == 0) else {
+                return Err(ExcType::value_error(format!(
+                    "field {} out of range (need {size}-bit value)",
+                    index + 1
+                )));
+            };
+            int = (int << bits) | u128::from(field);
+        }
+        Ok(Self(int))
+    }
+
+    /// Sets the variant to RFC 4122 and the version to `version`.
+    fn with_version(self, version: u8) -> Self {
+        let int = (self.0 & !VARIANT_MASK) | VARIANT_RFC_4122;
+        Self((int & !VERSION_MASK) | (u128::from(version) << 76))
+    }
+
+    /// The 16 bytes of the UUID in big-endian order.
+    #[must_use]
+    pub fn bytes(self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+
+    /// The name of the variant of the UUID, one of `uuid`'s `RESERVED_NCS`, `RFC_4122`,
+    /// `RESERVED_MICROSOFT` and `RESERVED_FUTURE`.
+    fn variant(self) -> &'static str {
+        if self.0 & (0x8000 << 48) == 0 {
+            "reserved for NCS compatibility"
+        } else if self.0 & (0x4000 << 48) == 0 {
+            "specified in RFC 4122"
+        } else if self.0 & (0x2000 << 48) == 0 {
+            "reserved for Microsoft compatibility"
+        } else {
+            "reserved for future definition"
+        }
+    }
+
+    /// The version of an RFC 4122 UUID, or `None` for other variants.
+    fn version(self) -> Option<i64> {
+        (self.0 & VARIANT_MASK == VARIANT_RFC_4122).then(|| i64::from(self.bytes()[6] >> 4))
+    }
+
+    /// The 32 hex digits of the UUID.
+    fn hex(self) -> String {
+        format!("{:032x}", self.0)
+    }
+
+    /// The canonical form of the UUID, its hex digits in groups of 8, 4, 4, 4 and 12.
+    fn canonical(self) -> String {
+        let hex = self.hex();
+        format!(
+            "{}-{}-{}-{}-{}",
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        )
+    }
+}
+
+impl PyTrait for Uuid {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Uuid
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(&self, other: &Self, _heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self == other
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {}
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        write!(f, "UUID('{}')", self.canonical())
+    }
+
+    fn py_str(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Cow<'static, str> {
+        Cow::Owned(self.canonical())
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = match StaticStrings::from_string_id(attr_id) {
+            Some(StaticStrings::Hex) => allocate_string(self.hex(), heap)?,
+            Some(StaticStrings::Int) => LongInt::new(BigInt::from(self.0)).into_value(heap)?,
+            Some(StaticStrings::Bytes) => {
+                Value::Ref(heap.allocate(HeapData::Bytes(Bytes::new(self.bytes().to_vec())))?)
+            }
+            Some(StaticStrings::BytesLe) => {
+                // Reversing the first three fields is its own inverse
+                let bytes = Self::from_bytes_le(self.bytes()).bytes();
+                Value::Ref(heap.allocate(HeapData::Bytes(Bytes::new(bytes.to_vec())))?)
+            }
+            Some(StaticStrings::Urn) => allocate_string(format!("urn:uuid:{}", self.canonical()), heap)?,
+            Some(StaticStrings::Variant) => allocate_string(self.variant().to_owned(), heap)?,
+            Some(StaticStrings::Version) => self.version().map_or(Value::None, Value::Int),
+            Some(StaticStrings::Fields) => {
+                let bytes = self.bytes();
+                let field = |range: std::ops::Range<usize>| {
+                    Value::Int(bytes[range].iter().fold(0, |acc, byte| (acc << 8) | i64::from(*byte)))
+                };
+                let items = [
+                    field(0..4),
+                    field(4..6),
+                    field(6..8),
+                    field(8..9),
+                    field(9..10),
+                    field(10..16),
+                ];
+                super::tuple::allocate_tuple(items.into_iter().collect(), heap)?
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(AttrCallResult::Value(value)))
+    }
+}
+
+/// Returns the 16 bytes of the `bytes` or `bytes_le` argument of `UUID()`.
+fn uuid_bytes(value: &Value, name: &str, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<[u8; 16]> {
+    let bytes = match value {
+        Value::InternBytes(id) => Some(interns.get_bytes(*id)),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Bytes(bytes) => Some(bytes.as_slice()),
+            _ => None,
+        },
+        _ => None,
+    };
+    let Some(bytes) = bytes else {
+        return Err(ExcType::type_error(format!(
+            "object of type '{}' has no len()",
+            value.py_type(heap)
+        )));
+    };
+    bytes
+        .try_into()
+        .map_err(|_| ExcType::value_error(format!("{name} is not a 16-char string")))
+}
+
+/// Returns the UUID of a value, for the namespace argument of `uuid3()` and `uuid5()`.
+///
+/// # Errors
+/// Returns `AttributeError` for other types, which have no `bytes` attribute.
+pub(crate) fn uuid_arg(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<Uuid> {
+    if let Value::Ref(id) = value
+        && let HeapData::Uuid(uuid) = heap.get(*id)
+    {
+        return Ok(*uuid);
+    }
+    Err(ExcType::attribute_error(value.py_type(heap), "bytes"))
+}
+
+/// Creates the UUID of `uuid.uuid4()` from the host's answer to `OsFunction::Uuid4`,
+/// 16 random bytes.
+pub(crate) fn finish_uuid4(value: Value, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let bytes = match &value {
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Bytes(bytes) => Some(<[u8; 16]>::try_from(bytes.as_slice()).map_err(|_| bytes.len())),
+            _ => None,
+        },
+        _ => None,
+    };
+    let error = match bytes {
+        Some(Ok(bytes)) => {
+            value.drop_with_heap(heap);
+            return Ok(Value::Ref(
+                heap.allocate(HeapData::Uuid(Uuid::from_bytes_with_version(bytes, 4)))?,
+            ));
+        }
+        Some(Err(len)) => ExcType::value_error(format!("uuid.uuid4() OS call must return 16 bytes, not {len}")),
+        None => ExcType::type_error(format!(
+            "uuid.uuid4() OS call must return bytes, not '{}'",
+            value.py_type(heap)
+        )),
+    };
+    value.drop_with_heap(heap);
+    Err(error)
+}
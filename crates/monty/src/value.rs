@@ -22,9 +22,11 @@ use crate::{
     modules::ModuleFunctions,
     resource::{LARGE_RESULT_THRESHOLD, ResourceTracker},
     types::{
-        AttrCallResult, LongInt, Property, PyTrait, Str, Type,
-        bytes::{bytes_repr_fmt, get_byte_at_index, get_bytes_slice},
-        path,
+        AttrCallResult, BoundMethod, ByteArray, LongInt, Property, PyTrait, Str, Type,
+        bytes::{bytes_contains, bytes_repr_fmt, get_byte_at_index, get_bytes_slice},
+        class::{self, ClassAttr},
+        complex::{as_complex, complex_operands},
+        datetime, decimal, r#enum, fraction, path,
         str::{allocate_char, get_char_at_index, get_str_slice, string_repr_fmt},
     },
 };
@@ -155,6 +157,10 @@ impl PyTrait for Value {
     }
 
     fn py_eq(&self, other: &Self, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> bool {
+        // decimals and fractions compare exactly with each other and with ints and floats
+        if let Some(ordering) = decimal::compare(self, other, heap).or_else(|| fraction::compare(self, other, heap)) {
+            return ordering == Some(Ordering::Equal);
+        }
         match (self, other) {
             (Self::Undefined, _) => false,
             (_, Self::Undefined) => false,
@@ -169,12 +175,23 @@ impl PyTrait for Value {
             (Self::Float(v1), Self::Bool(v2)) => *v1 == (i64::from(*v2) as f64),
             (Self::None, Self::None) => true,
 
+            // A complex number equals an int or float when its imaginary part is zero
+            (Self::Ref(id), Self::Int(_) | Self::Float(_) | Self::Bool(_))
+            | (Self::Int(_) | Self::Float(_) | Self::Bool(_), Self::Ref(id))
+                if matches!(heap.get(*id), HeapData::Complex(_)) =>
+            {
+                match (as_complex(self, heap), as_complex(other, heap)) {
+                    (Some(a), Some(b)) => a == b,
+                    _ => false,
+                }
+            }
+
             // Int == LongInt comparison
             (Self::Int(a), Self::Ref(id)) => {
                 if let HeapData::LongInt(li) = heap.get(*id) {
                     BigInt::from(*a) == *li.inner()
                 } else {
-                    false
+                    mixin_eq(self, other, heap, interns)
                 }
             }
             // LongInt == Int comparison
@@ -182,7 +199,7 @@ impl PyTrait for Value {
                 if let HeapData::LongInt(li) = heap.get(*id) {
                     *li.inner() == BigInt::from(*b)
                 } else {
-                    false
+                    mixin_eq(self, other, heap, interns)
                 }
             }
 
@@ -193,14 +210,14 @@ impl PyTrait for Value {
                 if let HeapData::Str(s2) = heap.get(*id2) {
                     interns.get_str(*string_id) == s2.as_str()
                 } else {
-                    false
+                    mixin_eq(self, other, heap, interns)
                 }
             }
             (Self::Ref(id1), Self::InternString(string_id)) => {
                 if let HeapData::Str(s1) = heap.get(*id1) {
                     s1.as_str() == interns.get_str(*string_id)
                 } else {
-                    false
+                    mixin_eq(self, other, heap, interns)
                 }
             }
 
@@ -210,20 +227,14 @@ impl PyTrait for Value {
                 b1 == b2 || interns.get_bytes(*b1) == interns.get_bytes(*b2)
             }
             // same for bytes
-            (Self::InternBytes(bytes_id), Self::Ref(id2)) => {
-                if let HeapData::Bytes(b2) = heap.get(*id2) {
-                    interns.get_bytes(*bytes_id) == b2.as_slice()
-                } else {
-                    false
-                }
-            }
-            (Self::Ref(id1), Self::InternBytes(bytes_id)) => {
-                if let HeapData::Bytes(b1) = heap.get(*id1) {
-                    b1.as_slice() == interns.get_bytes(*bytes_id)
-                } else {
-                    false
-                }
-            }
+            (Self::InternBytes(bytes_id), Self::Ref(id2)) => heap
+                .get(*id2)
+                .as_bytes_like()
+                .is_some_and(|b2| interns.get_bytes(*bytes_id) == b2),
+            (Self::Ref(id1), Self::InternBytes(bytes_id)) => heap
+                .get(*id1)
+                .as_bytes_like()
+                .is_some_and(|b1| b1 == interns.get_bytes(*bytes_id)),
 
             (Self::Ref(id1), Self::Ref(id2)) => {
                 if *id1 == *id2 {
@@ -231,6 +242,7 @@ impl PyTrait for Value {
                 }
                 // Need to use with_two for proper borrow management
                 heap.with_two(*id1, *id2, |heap, left, right| left.py_eq(right, heap, interns))
+                    || mixin_eq(self, other, heap, interns)
             }
 
             // Builtins equality - just check the enums are equal
@@ -243,11 +255,14 @@ impl PyTrait for Value {
             // Properties compare equal if they're the same variant
             (Self::Property(p1), Self::Property(p2)) => p1 == p2,
 
-            _ => false,
+            _ => mixin_eq(self, other, heap, interns),
         }
     }
 
     fn py_cmp(&self, other: &Self, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Option<Ordering> {
+        if let Some(ordering) = decimal::compare(self, other, heap).or_else(|| fraction::compare(self, other, heap)) {
+            return ordering;
+        }
         match (self, other) {
             (Self::Int(s), Self::Int(o)) => s.partial_cmp(o),
             (Self::Float(s), Self::Float(o)) => s.partial_cmp(o),
@@ -260,7 +275,7 @@ impl PyTrait for Value {
                 if let HeapData::LongInt(li) = heap.get(*id) {
                     BigInt::from(*a).partial_cmp(li.inner())
                 } else {
-                    None
+                    mixin_cmp(self, other, heap, interns)
                 }
             }
             // LongInt vs Int comparison
@@ -268,7 +283,7 @@ impl PyTrait for Value {
                 if let HeapData::LongInt(li) = heap.get(*id) {
                     li.inner().partial_cmp(&BigInt::from(*b))
                 } else {
-                    None
+                    mixin_cmp(self, other, heap, interns)
                 }
             }
             // LongInt vs LongInt comparison
@@ -283,15 +298,17 @@ impl PyTrait for Value {
                             None
                         }
                     })
+                } else if let (HeapData::Uuid(a), HeapData::Uuid(b)) = (heap.get(*id1), heap.get(*id2)) {
+                    a.partial_cmp(b)
                 } else {
-                    None
+                    datetime::compare(heap.get(*id1), heap.get(*id2)).or_else(|| mixin_cmp(self, other, heap, interns))
                 }
             }
             (Self::InternString(s1), Self::InternString(s2)) => interns.get_str(*s1).partial_cmp(interns.get_str(*s2)),
             (Self::InternBytes(b1), Self::InternBytes(b2)) => {
                 interns.get_bytes(*b1).partial_cmp(interns.get_bytes(*b2))
             }
-            _ => None,
+            _ => mixin_cmp(self, other, heap, interns),
         }
     }
 
@@ -365,7 +382,7 @@ impl PyTrait for Value {
                 if heap_ids.contains(id) {
                     // Cycle detected - write type-specific placeholder following Python semantics
                     match heap.get(*id) {
-                        HeapData::List(_) => f.write_str("[...]"),
+                        HeapData::List(_) | HeapData::Deque(_) => f.write_str("[...]"),
                         HeapData::Tuple(_) => f.write_str("(...)"),
                         HeapData::Dict(_) => f.write_str("{...}"),
                         // Other types don't typically have cycles, but handle gracefully
@@ -373,7 +390,14 @@ impl PyTrait for Value {
                     }
                 } else {
                     heap_ids.insert(*id);
-                    let result = heap.get(*id).py_repr_fmt(f, heap, heap_ids, interns);
+                    let result = match heap.get(*id) {
+                        HeapData::Instance(inst) if r#enum::is_member(inst, heap) => {
+                            r#enum::member_repr_fmt(inst, f, heap, heap_ids, interns)
+                        }
+                        // The default instance repr includes the object's id, which only we know
+                        HeapData::Instance(inst) => inst.default_repr_fmt(f, heap, interns, heap_tagged_id(*id)),
+                        data => data.py_repr_fmt(f, heap, heap_ids, interns),
+                    };
                     heap_ids.remove(id);
                     result
                 }
@@ -397,6 +421,9 @@ impl PyTrait for Value {
         heap: &mut Heap<impl ResourceTracker>,
         interns: &Interns,
     ) -> Result<Option<Value>, crate::resource::ResourceError> {
+        if let Some((a, b)) = complex_operands(self, other, heap) {
+            return a.add(b).into_value(heap).map(Some);
+        }
         match (self, other) {
             // Int + Int with overflow detection
             (Self::Int(a), Self::Int(b)) => {
@@ -477,8 +504,9 @@ impl PyTrait for Value {
                 b.extend_from_slice(bytes2);
                 Ok(Some(Self::Ref(heap.allocate(HeapData::Bytes(b.into()))?)))
             }
+            // bytes + bytearray gives bytes
             (Self::InternBytes(bytes_id), Self::Ref(id2)) => {
-                if let HeapData::Bytes(b2) = heap.get(*id2) {
+                if let Some(b2) = heap.get(*id2).as_bytes_like() {
                     let bytes1 = interns.get_bytes(*bytes_id);
                     let mut b = Vec::with_capacity(bytes1.len() + b2.len());
                     b.extend_from_slice(bytes1);
@@ -488,16 +516,23 @@ impl PyTrait for Value {
                     Ok(None)
                 }
             }
+            // bytearray + bytes gives bytearray
             (Self::Ref(id1), Self::InternBytes(bytes_id)) => {
-                if let HeapData::Bytes(b1) = heap.get(*id1) {
-                    let bytes2 = interns.get_bytes(*bytes_id);
-                    let mut b = Vec::with_capacity(b1.len() + bytes2.len());
-                    b.extend_from_slice(b1);
-                    b.extend_from_slice(bytes2);
-                    Ok(Some(Self::Ref(heap.allocate(HeapData::Bytes(b.into()))?)))
+                let (b1, is_bytearray) = match heap.get(*id1) {
+                    HeapData::Bytes(b1) => (b1.as_slice(), false),
+                    HeapData::ByteArray(b1) => (b1.as_slice(), true),
+                    _ => return Ok(None),
+                };
+                let bytes2 = interns.get_bytes(*bytes_id);
+                let mut b = Vec::with_capacity(b1.len() + bytes2.len());
+                b.extend_from_slice(b1);
+                b.extend_from_slice(bytes2);
+                let data = if is_bytearray {
+                    HeapData::ByteArray(ByteArray::new(b))
                 } else {
-                    Ok(None)
-                }
+                    HeapData::Bytes(b.into())
+                };
+                Ok(Some(Self::Ref(heap.allocate(data)?)))
             }
             _ => Ok(None),
         }
@@ -508,6 +543,9 @@ impl PyTrait for Value {
         other: &Self,
         heap: &mut Heap<impl ResourceTracker>,
     ) -> Result<Option<Self>, crate::resource::ResourceError> {
+        if let Some((a, b)) = complex_operands(self, other, heap) {
+            return a.sub(b).into_value(heap).map(Some);
+        }
         match (self, other) {
             // Int - Int with overflow detection
             (Self::Int(a), Self::Int(b)) => {
@@ -724,7 +762,7 @@ impl PyTrait for Value {
                 Ok(true)
             }
             (Self::InternBytes(bytes_id), Self::Ref(id2)) => {
-                let result = if let HeapData::Bytes(b2) = heap.get(*id2) {
+                let result = if let Some(b2) = heap.get(*id2).as_bytes_like() {
                     let bytes1 = interns.get_bytes(*bytes_id);
                     let mut b = Vec::with_capacity(bytes1.len() + b2.len());
                     b.extend_from_slice(bytes1);
@@ -738,14 +776,17 @@ impl PyTrait for Value {
                 other.drop_with_heap(heap);
                 Ok(result)
             }
-            (Self::Ref(id1), Self::InternBytes(bytes_id)) => {
-                if let HeapData::Bytes(b1) = heap.get_mut(*id1) {
+            (Self::Ref(id1), Self::InternBytes(bytes_id)) => match heap.get_mut(*id1) {
+                HeapData::Bytes(b1) => {
                     b1.as_vec_mut().extend_from_slice(interns.get_bytes(*bytes_id));
                     Ok(true)
-                } else {
-                    Ok(false)
                 }
-            }
+                HeapData::ByteArray(b1) => {
+                    b1.as_vec_mut().extend_from_slice(interns.get_bytes(*bytes_id));
+                    Ok(true)
+                }
+                _ => Ok(false),
+            },
             (Self::Ref(id), Self::Ref(_)) => {
                 heap.with_entry_mut(*id, |heap, data| data.py_iadd(other, heap, Some(*id), interns))
             }
@@ -763,6 +804,9 @@ impl PyTrait for Value {
         heap: &mut Heap<impl ResourceTracker>,
         interns: &Interns,
     ) -> RunResult<Option<Value>> {
+        if let Some((a, b)) = complex_operands(self, other, heap) {
+            return Ok(Some(a.mul(b).into_value(heap)?));
+        }
         match (self, other) {
             // Numeric multiplication with overflow promotion to LongInt
             (Self::Int(a), Self::Int(b)) => {
@@ -934,6 +978,10 @@ impl PyTrait for Value {
         heap: &mut Heap<impl ResourceTracker>,
         interns: &Interns,
     ) -> RunResult<Option<Value>> {
+        if let Some((a, b)) = complex_operands(self, other, heap) {
+            let quotient = a.div(b).ok_or_else(ExcType::complex_zero_division)?;
+            return Ok(Some(quotient.into_value(heap)?));
+        }
         match (self, other) {
             // True division always returns float
             (Self::Int(a), Self::Int(b)) => {
@@ -1223,6 +1271,10 @@ impl PyTrait for Value {
     }
 
     fn py_pow(&self, other: &Self, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Option<Value>> {
+        if let Some((a, b)) = complex_operands(self, other, heap) {
+            let power = a.pow(b).ok_or_else(ExcType::complex_zero_power)?;
+            return Ok(Some(power.into_value(heap)?));
+        }
         match (self, other) {
             (Self::Int(base), Self::Int(exp)) => {
                 if *base == 0 && *exp < 0 {
@@ -1502,6 +1554,22 @@ impl PyTrait for Value {
         match self {
             Self::Ref(id) => {
                 let id = *id;
+                // `ba[i:j] = ba` reads the bytearray while it is taken out of the heap, so assign from a copy
+                let value = if value.ref_id() == Some(id)
+                    && let HeapData::ByteArray(b) = heap.get(id)
+                {
+                    let copy = HeapData::Bytes(b.as_slice().to_vec().into());
+                    value.drop_with_heap(heap);
+                    match heap.allocate(copy) {
+                        Ok(copy_id) => Self::Ref(copy_id),
+                        Err(err) => {
+                            key.drop_with_heap(heap);
+                            return Err(err.into());
+                        }
+                    }
+                } else {
+                    value
+                };
                 heap.with_entry_mut(id, |heap, data| data.py_setitem(key, value, heap, interns))
             }
             _ => Err(ExcType::type_error(format!(
@@ -1510,6 +1578,23 @@ impl PyTrait for Value {
             ))),
         }
     }
+
+    fn py_delitem(&mut self, key: Self, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<()> {
+        match self {
+            Self::Ref(id) => {
+                let id = *id;
+                heap.with_entry_mut(id, |heap, data| data.py_delitem(key, heap, interns))
+            }
+            Self::InternString(_) | Self::InternBytes(_) => {
+                key.drop_with_heap(heap);
+                Err(ExcType::type_error_no_item_deletion(self.py_type(heap)))
+            }
+            _ => {
+                key.drop_with_heap(heap);
+                Err(ExcType::type_error_not_sub_deletion(self.py_type(heap)))
+            }
+        }
+    }
 }
 
 impl Value {
@@ -1664,10 +1749,12 @@ impl Value {
     /// Checks if `item` is contained in `self` (the container).
     ///
     /// Implements Python's `in` operator for various container types:
-    /// - List/Tuple: linear search with equality
+    /// - List/Tuple/Deque: linear search with equality
     /// - Dict: key lookup
+    /// - Dict views: key, value or (key, value) lookup
     /// - Set/FrozenSet: element lookup
     /// - Str: substring search
+    /// - Bytes/ByteArray: byte value or subsequence search
     pub fn py_contains(
         &self,
         item: &Self,
@@ -1682,10 +1769,29 @@ impl Value {
                 heap.with_entry_mut(*heap_id, |heap, data| match data {
                     HeapData::List(el) => Ok(el.as_vec().iter().any(|i| item.py_eq(i, heap, interns))),
                     HeapData::Tuple(el) => Ok(el.as_vec().iter().any(|i| item.py_eq(i, heap, interns))),
+                    HeapData::Deque(deque) => Ok(deque.iter().any(|i| item.py_eq(i, heap, interns))),
                     HeapData::Dict(dict) => dict.get(item, heap, interns).map(|m| m.is_some()),
+                    HeapData::DictView(view) => view.contains(item, heap, interns),
+                    HeapData::ClassObject(cls) if cls.enum_info().is_some() => {
+                        Ok(r#enum::contains(*heap_id, cls, item, heap, interns))
+                    }
                     HeapData::Set(set) => set.contains(item, heap, interns),
                     HeapData::FrozenSet(fset) => fset.contains(item, heap, interns),
                     HeapData::Str(s) => str_contains(s.as_str(), item, heap, interns),
+                    HeapData::Bytes(b) => bytes_contains(b.as_slice(), item, heap, interns),
+                    HeapData::ByteArray(b) => bytes_contains(b.as_slice(), item, heap, interns),
+                    // `StrEnum` members contain what their values contain
+                    HeapData::Instance(inst) if r#enum::instance_mixin_value(inst, heap, interns).is_some() => {
+                        let inner = r#enum::instance_mixin_value(inst, heap, interns)
+                            .expect("checked above")
+                            .copy_for_extend();
+                        if let Self::Ref(id) = &inner {
+                            heap.inc_ref(*id);
+                        }
+                        let result = inner.py_contains(item, heap, interns);
+                        inner.drop_with_heap(heap);
+                        result
+                    }
                     HeapData::Range(range) => {
                         // Range containment is O(1) - check bounds and step alignment
                         let n = match item {
@@ -1724,6 +1830,7 @@ impl Value {
                 let container_str = interns.get_str(*string_id);
                 str_contains(container_str, item, heap, interns)
             }
+            Self::InternBytes(bytes_id) => bytes_contains(interns.get_bytes(*bytes_id), item, heap, interns),
             _ => {
                 let type_name = self.py_type(heap);
                 Err(ExcType::type_error(format!(
@@ -1744,7 +1851,31 @@ impl Value {
         heap: &mut Heap<impl ResourceTracker>,
         interns: &Interns,
     ) -> RunResult<AttrCallResult> {
+        // Functions defined in Monty code expose their name, e.g. for registration decorators
+        if name_id == StaticStrings::DunderName
+            && let Some(func_id) = class::function_id(self, heap)
+        {
+            let name_id = interns.get_function(func_id).name.name_id;
+            return Ok(AttrCallResult::Value(Self::InternString(name_id)));
+        }
+
         match self {
+            Self::Ref(heap_id) if class::has_class_attrs(heap.get(*heap_id)) => {
+                if name_id == StaticStrings::DunderMembers
+                    && let Some(members) = r#enum::members_dict(*heap_id, heap, interns)?
+                {
+                    return Ok(AttrCallResult::Value(members));
+                }
+                // Classes, instances and `super()` resolve attributes through the class chain
+                return match class::lookup_attr(*heap_id, name_id, heap, interns) {
+                    Some(ClassAttr::Value(value)) => Ok(AttrCallResult::Value(value)),
+                    Some(ClassAttr::Method { func, self_obj }) => {
+                        let method_id = heap.allocate(HeapData::BoundMethod(BoundMethod::new(func, self_obj)))?;
+                        Ok(AttrCallResult::Value(Self::Ref(method_id)))
+                    }
+                    None => Err(class::attr_error(*heap_id, name_id, heap, interns)),
+                };
+            }
             Self::Ref(heap_id) => {
                 // Use with_entry_mut to get access to both data and heap without borrow conflicts.
                 // This allows py_getattr to allocate (for computed attributes) while we hold the data.
@@ -1756,10 +1887,15 @@ impl Value {
             Self::Builtin(Builtins::Type(t)) => {
                 // Handle type object attributes like __name__
                 if name_id == StaticStrings::DunderName {
+                    // `__name__` leaves out the module of types like `datetime.date`
                     let name_str = t.to_string();
+                    let name_str = name_str.rsplit('.').next().unwrap_or_default();
                     let str_id = heap.allocate(HeapData::Str(Str::from(name_str)))?;
                     return Ok(AttrCallResult::Value(Self::Ref(str_id)));
                 }
+                if let Some(value) = datetime::type_attr(*t, name_id, heap)? {
+                    return Ok(AttrCallResult::Value(value));
+                }
             }
             _ => {}
         }
@@ -1769,7 +1905,7 @@ impl Value {
 
     /// Sets an attribute on this value.
     ///
-    /// Currently only Dataclass objects support attribute setting.
+    /// Currently only Dataclass objects and user-defined classes and instances support attribute setting.
     /// Returns AttributeError for other types.
     ///
     /// Takes ownership of `value` and drops it on error.
@@ -1785,6 +1921,18 @@ impl Value {
 
         if let Self::Ref(heap_id) = self {
             let heap_id = *heap_id;
+            if let Err(e) = r#enum::check_member_change(heap_id, name_id, false, heap, interns) {
+                value.drop_with_heap(heap);
+                return Err(e);
+            }
+            let value = match class::set_attr(heap_id, name_id, value, heap, interns) {
+                Ok(result) => return result,
+                Err(value) => value,
+            };
+            let value = match decimal::set_context_attr(heap_id, name_id, value, heap, interns) {
+                Ok(result) => return result,
+                Err(value) => value,
+            };
             let is_dataclass = matches!(heap.get(heap_id), HeapData::Dataclass(_));
 
             if is_dataclass {
@@ -1816,6 +1964,38 @@ impl Value {
         }
     }
 
+    /// Deletes an attribute from this value, as in `del obj.attr`.
+    ///
+    /// Only Dataclass objects and user-defined classes and instances support attribute
+    /// deletion. Returns AttributeError for other types.
+    pub fn py_del_attr(
+        &self,
+        name_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<()> {
+        if let Self::Ref(heap_id) = self {
+            let heap_id = *heap_id;
+            r#enum::check_member_change(heap_id, name_id, true, heap, interns)?;
+            if let Some(result) = class::del_attr(heap_id, name_id, heap, interns) {
+                return result;
+            }
+            if matches!(heap.get(heap_id), HeapData::Dataclass(_)) {
+                return heap.with_entry_mut(heap_id, |heap, data| {
+                    if let HeapData::Dataclass(dc) = data {
+                        dc.del_attr(name_id, heap, interns)
+                    } else {
+                        unreachable!("type changed during borrow")
+                    }
+                });
+            }
+        }
+        Err(ExcType::attribute_error_no_setattr(
+            self.py_type(heap),
+            interns.get_str(name_id),
+        ))
+    }
+
     /// Extracts an integer value from the Value.
     ///
     /// Accepts `Int` and `LongInt` (if it fits in i64). Returns a `TypeError` for other types
@@ -2195,6 +2375,8 @@ impl BitwiseOp {
 ///   provide functionality in the sandboxed environment
 /// - Typing constructs from the `typing` module that are imported for type hints but
 ///   don't need runtime functionality
+/// - `enum.auto()` placeholders, which are replaced by generated values when the
+///   enum class is created
 ///
 /// Wraps a `StaticStrings` variant to leverage its string conversion capabilities.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
@@ -2210,6 +2392,7 @@ impl Marker {
         match self.0 {
             StaticStrings::Stdout | StaticStrings::Stderr => Type::TextIOWrapper,
             StaticStrings::UnionType => Type::Type,
+            StaticStrings::Auto => Type::EnumAuto,
             _ => Type::SpecialForm,
         }
     }
@@ -2218,6 +2401,7 @@ impl Marker {
     ///
     /// System markers have special repr formats ("<stdout>", "<stderr>").
     /// `typing.Union` uses `<class 'typing.Union'>` format (matching CPython).
+    /// `enum.auto()` displays as "auto(_auto_null)" like an unresolved CPython `auto`.
     /// Other typing markers are prefixed with "typing." (e.g., "typing.Any").
     fn py_repr_fmt(self, f: &mut impl Write) -> fmt::Result {
         let s: &'static str = self.0.into();
@@ -2225,6 +2409,7 @@ impl Marker {
             StaticStrings::Stdout => f.write_str("<stdout>")?,
             StaticStrings::Stderr => f.write_str("<stderr>")?,
             StaticStrings::UnionType => f.write_str("<class 'typing.Union'>")?,
+            StaticStrings::Auto => f.write_str("auto(_auto_null)")?,
             _ => write!(f, "typing.{s}")?,
         }
         Ok(())
@@ -2305,6 +2490,29 @@ pub fn heap_tagged_id(heap_id: HeapId) -> usize {
     HEAP_ID_TAG | (heap_id.index() & HEAP_ID_MASK)
 }
 
+/// Resolves a function's `id()` back to the function, taking a new reference to it.
+///
+/// This is the inverse of `Value::id` for functions defined in Monty code, used for function
+/// handles passed back by the host. Returns `None` if the ID isn't a function's, or the
+/// function has since been freed.
+pub(crate) fn function_from_id(id: usize, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Option<Value> {
+    // Each ID space is identified by its tag being the highest set bit
+    if id & !HEAP_ID_MASK == HEAP_ID_TAG {
+        let (heap_id, data) = heap.get_live(id & HEAP_ID_MASK)?;
+        if !matches!(data, HeapData::Closure(..) | HeapData::FunctionDefaults(..)) {
+            return None;
+        }
+        heap.inc_ref(heap_id);
+        Some(Value::Ref(heap_id))
+    } else if id & !FUNCTION_ID_MASK == FUNCTION_ID_TAG {
+        interns
+            .function_id_from_index(id & FUNCTION_ID_MASK)
+            .map(Value::DefFunction)
+    } else {
+        None
+    }
+}
+
 /// Computes a deterministic ID for an i64 integer value.
 /// Uses the value's hash combined with a type tag to ensure uniqueness across types.
 #[inline]
@@ -2435,6 +2643,32 @@ fn extract_bigint(value: &Value, heap: &Heap<impl ResourceTracker>) -> Option<Bi
     }
 }
 
+/// Equality of operands which the builtin comparisons found different, comparing
+/// `IntEnum` and `StrEnum` members by their values, as their classes derive from `int`
+/// and `str`.
+///
+/// Only called once the builtin comparisons have failed, so they don't pay for it. The VM
+/// compares members through their special methods instead (see [`r#enum::mixin_dunder`]),
+/// this covers comparisons made by the heap, like dict and set lookups.
+fn mixin_eq(lhs: &Value, rhs: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> bool {
+    let Some((lhs, rhs)) = r#enum::mixin_operands(lhs, rhs, heap, interns) else {
+        return false;
+    };
+    let result = lhs.py_eq(&rhs, heap, interns);
+    lhs.drop_with_heap(heap);
+    rhs.drop_with_heap(heap);
+    result
+}
+
+/// Like [`mixin_eq`], for ordering.
+fn mixin_cmp(lhs: &Value, rhs: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Option<Ordering> {
+    let (lhs, rhs) = r#enum::mixin_operands(lhs, rhs, heap, interns)?;
+    let result = lhs.py_cmp(&rhs, heap, interns);
+    lhs.drop_with_heap(heap);
+    rhs.drop_with_heap(heap);
+    result
+}
+
 /// Helper for substring containment check in strings.
 ///
 /// Called by `py_contains` when the container is a string.
@@ -0,0 +1,73 @@
+import base64
+
+# === b64encode() / b64decode() ===
+assert base64.b64encode(b'') == b'', 'b64encode empty'
+assert base64.b64encode(b'f') == b'Zg==', 'b64encode 1 byte'
+assert base64.b64encode(b'fo') == b'Zm8=', 'b64encode 2 bytes'
+assert base64.b64encode(b'foo') == b'Zm9v', 'b64encode 3 bytes'
+assert base64.b64encode(b'hello world') == b'aGVsbG8gd29ybGQ=', 'b64encode text'
+assert base64.b64encode(bytearray(b'\xfb\xff')) == b'+/8=', 'b64encode bytearray'
+assert base64.b64encode(b'\xfb\xff', altchars=b'-_') == b'-_8=', 'b64encode altchars'
+assert base64.b64decode(b'aGVsbG8gd29ybGQ=') == b'hello world', 'b64decode'
+assert base64.b64decode('aGVsbG8gd29ybGQ=') == b'hello world', 'b64decode str'
+assert base64.b64decode(b'aGVs\nbG8g d29y!bGQ=') == b'hello world', 'b64decode skips other characters'
+assert base64.b64decode(b'-_8=', altchars=b'-_') == b'\xfb\xff', 'b64decode altchars'
+assert base64.b64decode(b'Zg==', validate=True) == b'f', 'b64decode validate'
+assert base64.b64decode(b'Zm8=\n') == b'fo', 'b64decode ignores characters after padding'
+
+# === standard and urlsafe ===
+assert base64.standard_b64encode(b'\xfb\xff') == b'+/8=', 'standard_b64encode'
+assert base64.standard_b64decode(b'+/8=') == b'\xfb\xff', 'standard_b64decode'
+assert base64.urlsafe_b64encode(b'\xfb\xff') == b'-_8=', 'urlsafe_b64encode'
+assert base64.urlsafe_b64decode(b'-_8=') == b'\xfb\xff', 'urlsafe_b64decode'
+assert base64.urlsafe_b64decode('-_8=') == b'\xfb\xff', 'urlsafe_b64decode str'
+
+# === b32encode() / b32decode() ===
+assert base64.b32encode(b'') == b'', 'b32encode empty'
+assert base64.b32encode(b'a') == b'ME======', 'b32encode 1 byte'
+assert base64.b32encode(b'hello') == b'NBSWY3DP', 'b32encode 5 bytes'
+assert base64.b32encode(b'hello world') == b'NBSWY3DPEB3W64TMMQ======', 'b32encode text'
+assert base64.b32decode(b'NBSWY3DPEB3W64TMMQ======') == b'hello world', 'b32decode'
+assert base64.b32decode('nbswy3dp', casefold=True) == b'hello', 'b32decode casefold'
+assert base64.b32decode(b'ME======') == b'a', 'b32decode padding'
+assert base64.b32decode(b'O0======', map01=b'L') == base64.b32decode(b'OO======'), 'b32decode map01 O'
+assert base64.b32decode(b'1E======', map01=b'L') == base64.b32decode(b'LE======'), 'b32decode map01 L'
+
+# === b16encode() / b16decode() ===
+assert base64.b16encode(b'\x00\xffhi') == b'00FF6869', 'b16encode'
+assert base64.b16decode(b'00FF6869') == b'\x00\xffhi', 'b16decode'
+assert base64.b16decode('00ff6869', casefold=True) == b'\x00\xffhi', 'b16decode casefold'
+
+# === round trips ===
+data = bytes(range(256))
+assert base64.b64decode(base64.b64encode(data)) == data, 'b64 round trip'
+assert base64.urlsafe_b64decode(base64.urlsafe_b64encode(data)) == data, 'urlsafe round trip'
+assert base64.b32decode(base64.b32encode(data)) == data, 'b32 round trip'
+assert base64.b16decode(base64.b16encode(data)) == data, 'b16 round trip'
+
+
+# === errors ===
+def error(exc_type, f, *args, **kwargs):
+    try:
+        f(*args, **kwargs)
+    except exc_type as e:
+        return str(e)
+    assert False, f'{f} should have raised'
+
+
+# binascii.Error is a subclass of ValueError
+assert error(TypeError, base64.b64encode, 'abc') == "a bytes-like object is required, not 'str'", 'b64encode str'
+assert error(ValueError, base64.b64decode, b'Zg') == 'Incorrect padding', 'b64decode bad padding'
+assert error(ValueError, base64.b64decode, b'Z') == (
+    'Invalid base64-encoded string: number of data characters (1) cannot be 1 more than a multiple of 4'
+), 'b64decode one extra character'
+assert error(ValueError, base64.b64decode, b'Zg=!', validate=True) == 'Only base64 data is allowed', 'validate'
+assert error(ValueError, base64.b64decode, b'Zg==Zm8=', validate=True) == 'Excess data after padding', 'excess'
+assert error(ValueError, base64.b64decode, 'é') == 'string argument should contain only ASCII characters', 'non-ascii'
+assert error(TypeError, base64.b64decode, 1) == (
+    "argument should be a bytes-like object or ASCII string, not 'int'"
+), 'b64decode int'
+assert error(ValueError, base64.b32decode, b'ME=====') == 'Incorrect padding', 'b32decode bad padding'
+assert error(ValueError, base64.b32decode, b'me======') == 'Non-base32 digit found', 'b32decode lowercase'
+assert error(ValueError, base64.b16decode, b'0') == 'Odd-length string', 'b16decode odd length'
+assert error(ValueError, base64.b16decode, b'ff') == 'Non-base16 digit found', 'b16decode lowercase'
@@ -0,0 +1,85 @@
+import sys
+
+
+class Point:
+    kind = 'point'
+
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+    def total(self):
+        return self.x + self.y
+
+
+p = Point(1, 2)
+
+# === getattr() ===
+assert getattr(p, 'x') == 1, 'getattr instance attribute'
+assert getattr(p, 'kind') == 'point', 'getattr class attribute via instance'
+assert getattr(Point, 'kind') == 'point', 'getattr class attribute'
+assert getattr(p, 'total')() == 3, 'getattr bound method'
+assert getattr(p, 'missing', None) is None, 'getattr default'
+assert getattr(p, 'x', 10) == 1, 'getattr default unused'
+assert getattr(p, 'never_used_anywhere', 'd') == 'd', 'getattr default for unknown name'
+name = 'y'
+assert getattr(p, name) == 2, 'getattr with variable name'
+assert getattr(p, 'x' + '') == 1, 'getattr with runtime-built name'
+assert getattr(sys.version_info, 'major') == 3, 'getattr on named tuple'
+
+# === hasattr() ===
+assert hasattr(p, 'x'), 'hasattr instance attribute'
+assert hasattr(p, 'total'), 'hasattr method'
+assert hasattr(Point, 'kind'), 'hasattr class attribute'
+assert not hasattr(p, 'missing'), 'hasattr missing'
+assert not hasattr(p, 'never' + '_seen'), 'hasattr unknown name'
+assert not hasattr(None, 'x'), 'hasattr on None'
+assert hasattr(sys.version_info, 'minor'), 'hasattr named tuple'
+
+# === setattr() ===
+assert setattr(p, 'x', 10) is None, 'setattr returns None'
+assert p.x == 10, 'setattr existing attribute'
+setattr(p, 'z', 5)
+assert p.z == 5, 'setattr new attribute'
+attr = 'y'
+setattr(p, attr, [1])
+assert p.y == [1], 'setattr with variable name'
+setattr(Point, 'kind', 'vector')
+assert p.kind == 'vector', 'setattr class attribute'
+
+try:
+    getattr(p, 1)
+    assert False, 'should raise'
+except TypeError as e:
+    assert str(e) == 'attribute name must be string, not \'int\'', str(e)
+
+try:
+    getattr(p, 'missing')
+    assert False, 'should raise'
+except AttributeError as e:
+    assert str(e) == "'Point' object has no attribute 'missing'", str(e)
+
+try:
+    getattr(p, 'unknown' + '_name')
+    assert False, 'should raise'
+except AttributeError as e:
+    assert str(e) == "'Point' object has no attribute 'unknown_name'", str(e)
+
+# === callable() ===
+
+
+def func():
+    pass
+
+
+assert callable(func), 'function is callable'
+assert callable(lambda: 1), 'lambda is callable'
+assert callable(len), 'builtin is callable'
+assert callable(int), 'type is callable'
+assert callable(Point), 'class is callable'
+assert callable(p.total), 'bound method is callable'
+assert not callable(p), 'instance is not callable'
+assert not callable(1), 'int is not callable'
+assert not callable('func'), 'str is not callable'
+assert not callable(None), 'None is not callable'
+assert not callable([func]), 'list is not callable'
@@ -64,3 +64,23 @@ assert next(it1) == 2, 'independent iterators: it1 second should be 2'
 assert next(it2) == 1, 'independent iterators: it2 first should be 1 (independent)'
 assert next(it1) == 3, 'independent iterators: it1 third should be 3'
 assert next(it2) == 2, 'independent iterators: it2 second should be 2'
+
+# === Builtins consuming iterators ===
+# Iterators are advanced in place, so values already read are skipped
+it = iter([1, 2, 3])
+next(it)
+assert list(it) == [2, 3], 'list() of a partly consumed iterator'
+assert list(it) == [], 'list() of an exhausted iterator'
+assert tuple(iter('ab')) == ('a', 'b'), 'tuple() of an iterator'
+assert sorted(iter([3, 1, 2])) == [1, 2, 3], 'sorted() of an iterator'
+assert dict(iter([('a', 1)])) == {'a': 1}, 'dict() of an iterator'
+assert dict([iter('ab')]) == {'a': 'b'}, 'dict() of iterator pairs'
+assert bytes(iter([104, 105])) == b'hi', 'bytes() of an iterator'
+assert list(iter(iter([1, 2]))) == [1, 2], 'list() of iter() of an iterator'
+assert ','.join(iter(['a', 'b'])) == 'a,b', 'str.join() of an iterator'
+
+try:
+    reversed(iter([1, 2]))
+    assert False, 'reversed() of an iterator should raise TypeError'
+except TypeError as e:
+    assert str(e).endswith('object is not reversible'), str(e)
@@ -0,0 +1,42 @@
+# === iter(callable, sentinel) ===
+items = [1, 2, 3, None, 4]
+
+
+def pop_front():
+    return items.pop(0)
+
+
+assert list(iter(pop_front, None)) == [1, 2, 3], 'iter stops at sentinel'
+assert items == [4], 'sentinel is consumed but later values are not'
+
+count = 0
+
+
+def counter():
+    global count
+    count += 1
+    return count
+
+
+it = iter(counter, 3)
+assert iter(it) is it, 'callable iterator is its own iterator'
+assert next(it) == 1, 'first call'
+assert next(it) == 2, 'second call'
+assert next(it, 'end') == 'end', 'sentinel reached'
+assert next(it, 'end') == 'end', 'stays exhausted'
+assert count == 3, 'callable not called after exhaustion'
+
+lines = ['a', 'b', '']
+assert [line.upper() for line in iter(lambda: lines.pop(0), '')] == ['A', 'B'], 'comprehension over iter'
+
+total = 0
+values = [5, 6, 0]
+for v in iter(lambda: values.pop(0), 0):
+    total += v
+assert total == 11, 'for loop over iter'
+
+nums = [3, 1, 2, -1]
+assert sorted(iter(lambda: nums.pop(0), -1)) == [1, 2, 3], 'sorted of iter'
+
+iter(1, 2)
+# Raise=TypeError('iter(v, w): v must be callable')
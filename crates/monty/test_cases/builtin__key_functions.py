@@ -0,0 +1,147 @@
+# === sorted() ===
+rows = [{'name': 'b', 'score': 2}, {'name': 'a', 'score': 3}, {'name': 'c', 'score': 1}]
+assert [r['name'] for r in sorted(rows, key=lambda r: r['score'])] == ['c', 'b', 'a'], 'sorted with lambda key'
+assert [r['name'] for r in sorted(rows, key=lambda r: r['score'], reverse=True)] == ['a', 'b', 'c'], (
+    'sorted with key and reverse'
+)
+assert sorted([3, 1, 2], reverse=True) == [3, 2, 1], 'sorted reverse'
+assert sorted([3, 1, 2], reverse=False) == [1, 2, 3], 'sorted reverse=False'
+assert sorted([3, 1, 2], key=None) == [1, 2, 3], 'sorted key=None'
+assert sorted(['bb', 'a', 'ccc'], key=len) == ['a', 'bb', 'ccc'], 'sorted with builtin key'
+assert sorted(['10', '9', '100'], key=int) == ['9', '10', '100'], 'sorted with type key'
+assert sorted([-3, 1, -2], key=abs, reverse=True) == [-3, -2, 1], 'sorted abs reverse'
+assert sorted([], key=lambda x: x) == [], 'sorted empty with key'
+assert sorted((x for x in [2, 3, 1]), key=lambda x: -x) == [3, 2, 1], 'sorted generator with key'
+
+
+def last_char(s):
+    return s[-1]
+
+
+assert sorted(['ab', 'ca', 'bc'], key=last_char) == ['ca', 'ab', 'bc'], 'sorted with function key'
+
+# stability, also in reverse
+pairs = [(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')]
+assert sorted(pairs, key=lambda p: p[0]) == [(0, 'b'), (0, 'd'), (1, 'a'), (1, 'c')], 'sorted is stable'
+assert sorted(pairs, key=lambda p: p[0], reverse=True) == [(1, 'a'), (1, 'c'), (0, 'b'), (0, 'd')], (
+    'sorted reverse is stable'
+)
+
+# key is called once per item
+calls = []
+
+
+def tracked(x):
+    calls.append(x)
+    return -x
+
+
+assert sorted([1, 2, 3], key=tracked) == [3, 2, 1], 'sorted with tracked key'
+assert calls == [1, 2, 3], 'key called once per item in order'
+
+# methods as keys
+
+
+class Item:
+    def __init__(self, weight):
+        self.weight = weight
+
+    def get_weight(self):
+        return self.weight
+
+
+items = [Item(3), Item(1), Item(2)]
+assert [i.weight for i in sorted(items, key=lambda i: i.weight)] == [1, 2, 3], 'sorted instances'
+assert [i.get_weight() for i in sorted(items, key=Item.get_weight)] == [1, 2, 3], 'sorted by method'
+
+# === list.sort() ===
+lst = [3, 1, 2]
+lst.sort(key=lambda x: -x)
+assert lst == [3, 2, 1], 'list.sort with lambda key'
+lst.sort(key=tracked, reverse=True)
+assert lst == [1, 2, 3], 'list.sort with function key reverse'
+words = ['ccc', 'a', 'bb']
+words.sort(key=lambda w: len(w))
+assert words == ['a', 'bb', 'ccc'], 'list.sort lambda len'
+
+# === min() / max() ===
+assert max(['a', 'abc', 'ab'], key=len) == 'abc', 'max with builtin key'
+assert min(['a', 'abc', 'ab'], key=len) == 'a', 'min with builtin key'
+assert max(rows, key=lambda r: r['score'])['name'] == 'a', 'max with lambda key'
+assert min(rows, key=lambda r: r['score'])['name'] == 'c', 'min with lambda key'
+assert max(3, -5, 4, key=abs) == -5, 'max of args with key'
+assert min(3, -5, 4, key=lambda x: -x) == 4, 'min of args with key'
+assert max([], default=None) is None, 'max default'
+assert min([], default=0) == 0, 'min default'
+assert max([], key=len, default='none') == 'none', 'max key and default'
+assert max([1, 2], default=0) == 2, 'max default unused'
+assert max([1, 3, 2], key=None) == 3, 'max key=None'
+assert max(['a', 'b', 'c'], key=lambda x: 0) == 'a', 'max returns first of equal items'
+assert min(['a', 'b', 'c'], key=lambda x: 0) == 'a', 'min returns first of equal items'
+assert sorted([2, 1, 3], reverse=[1]) == [3, 2, 1], 'reverse uses truthiness'
+assert max((x for x in [1, 5, 2]), key=lambda x: x % 5) == 2, 'max generator with key'
+assert min(map(lambda x: x * 2, [3, 1, 2]), key=lambda x: -x) == 6, 'min map with key'
+assert max(range(10**6)) == 999999, 'max of a long range'
+
+# min() and max() advance generators one item at a time
+log = []
+
+
+def produce():
+    for i in [2, 3, 1]:
+        log.append(('yield', i))
+        yield i
+
+
+def record_key(x):
+    log.append(('key', x))
+    return x
+
+
+assert max(produce(), key=record_key) == 3, 'max generator with recorded key'
+assert log == [('yield', 2), ('key', 2), ('yield', 3), ('key', 3), ('yield', 1), ('key', 1)], log
+log = []
+assert min(produce()) == 1, 'min generator without key'
+assert log == [('yield', 2), ('yield', 3), ('yield', 1)], log
+
+# === errors ===
+try:
+    min([])
+    assert False, 'should raise'
+except ValueError as e:
+    assert str(e) == 'min() iterable argument is empty', str(e)
+
+try:
+    max(1, 2, default=0)
+    assert False, 'should raise'
+except TypeError as e:
+    assert str(e) == 'Cannot specify a default for max() with multiple positional arguments', str(e)
+
+try:
+    max([1, 'a'], key=lambda x: x)
+    assert False, 'should raise'
+except TypeError as e:
+    assert str(e) == "'>' not supported between instances of 'str' and 'int'", str(e)
+
+try:
+    sorted([1, 'a'], key=lambda x: x)
+    assert False, 'should raise'
+except TypeError as e:
+    assert str(e) == "'<' not supported between instances of 'str' and 'int'", str(e)
+
+try:
+    min(['a', 1])
+    assert False, 'should raise'
+except TypeError as e:
+    assert str(e) == "'<' not supported between instances of 'int' and 'str'", str(e)
+
+lst = [2, 0, 1]
+try:
+    lst.sort(key=lambda x: 1 // x)
+    assert False, 'should raise'
+except ZeroDivisionError:
+    pass
+assert sorted(lst) == [0, 1, 2], 'list restored after failed key'
+
+sorted([2, 1], key=lambda x: x.missing)
+# Raise=AttributeError("'int' object has no attribute 'missing'")
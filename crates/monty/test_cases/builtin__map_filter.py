@@ -0,0 +1,98 @@
+# === map() ===
+assert list(map(abs, [-1, 2, -3])) == [1, 2, 3], 'map with builtin function'
+assert list(map(str, [1, 2])) == ['1', '2'], 'map with type'
+assert list(map(lambda x: x * 2, [1, 2, 3])) == [2, 4, 6], 'map with lambda'
+
+
+def add(a, b):
+    return a + b
+
+
+assert list(map(add, [1, 2, 3], [10, 20, 30])) == [11, 22, 33], 'map with two iterables'
+assert list(map(add, [1, 2, 3], [10])) == [11], 'map stops at the shortest iterable'
+assert list(map(divmod, [7, 9], [2, 4])) == [(3, 1), (2, 1)], 'map passes two args to builtins'
+assert list(map(pow, [2, 3], [2, 2], [5, 5])) == [4, 4], 'map with three iterables'
+assert list(map(abs, [])) == [], 'map over empty list'
+assert list(map(lambda c: c.upper(), 'abc')) == ['A', 'B', 'C'], 'map over string'
+assert list(map(lambda k: k, {'a': 1, 'b': 2})) == ['a', 'b'], 'map over dict keys'
+
+# map is lazy
+calls = []
+
+
+def record(x):
+    calls.append(x)
+    return x
+
+
+m = map(record, [1, 2, 3])
+assert calls == [], 'map does not call until iterated'
+assert next(m) == 1, 'next on map'
+assert calls == [1], 'map called once'
+assert list(m) == [2, 3], 'rest of map'
+assert list(m) == [], 'exhausted map'
+assert next(m, 'done') == 'done', 'next default on exhausted map'
+assert iter(m) is m, 'map is its own iterator'
+
+# consumers of map
+assert sum(map(lambda x: x * x, range(4))) == 14, 'sum of map'
+assert any(map(lambda x: x > 2, [1, 2, 3])), 'any of map'
+assert not all(map(lambda x: x > 2, [1, 2, 3])), 'all of map'
+assert tuple(map(lambda x: x + 1, (1, 2))) == (2, 3), 'tuple of map'
+assert set(map(lambda x: x % 2, range(5))) == {0, 1}, 'set of map'
+assert dict(map(lambda x: (x, x * 10), [1, 2])) == {1: 10, 2: 20}, 'dict of map'
+assert sorted(map(lambda x: -x, [1, 3, 2])) == [-3, -2, -1], 'sorted of map'
+assert max(map(len, ['a', 'abc', 'ab'])) == 3, 'max of map'
+assert ', '.join(map(str, [1, 2, 3])) == '1, 2, 3', 'join of map'
+assert list(enumerate(map(abs, [-1, -2]))) == [(0, 1), (1, 2)], 'enumerate of map'
+assert list(zip(map(abs, [-1, -2]), 'ab')) == [(1, 'a'), (2, 'b')], 'zip of map'
+assert 4 in map(lambda x: x * 2, [1, 2]), 'in on map'
+a, b = map(int, ['1', '2'])
+assert (a, b) == (1, 2), 'unpack map'
+assert [*map(abs, [-1])] == [1], 'starred map'
+
+total = 0
+for n in map(lambda x: x * 3, [1, 2]):
+    total += n
+assert total == 9, 'for loop over map'
+assert [x + 1 for x in map(abs, [-1, -2])] == [2, 3], 'comprehension over map'
+
+# closures and generators
+factor = 5
+assert list(map(lambda x: x * factor, [1, 2])) == [5, 10], 'map with closure'
+assert list(map(abs, (x - 2 for x in range(4)))) == [2, 1, 0, 1], 'map over generator'
+assert list(map(abs, map(lambda x: x - 1, [0, 1, 2]))) == [1, 0, 1], 'nested map'
+
+
+def gen_map():
+    yield from map(lambda x: x * 2, [1, 2])
+
+
+assert list(gen_map()) == [2, 4], 'yield from map'
+
+# errors raised by the function propagate
+try:
+    list(map(lambda x: 1 // x, [1, 0]))
+    assert False, 'should raise'
+except ZeroDivisionError:
+    pass
+
+# === filter() ===
+assert list(filter(lambda x: x > 1, [1, 2, 3])) == [2, 3], 'filter with lambda'
+assert list(filter(None, [0, 1, '', 'a', None, [], [1]])) == [1, 'a', [1]], 'filter with None'
+assert list(filter(len, ['', 'a', ''])) == ['a'], 'filter with builtin function'
+
+
+def is_even(n):
+    return n % 2 == 0
+
+
+assert list(filter(is_even, range(7))) == [0, 2, 4, 6], 'filter with function'
+assert list(filter(is_even, [])) == [], 'filter over empty list'
+assert list(filter(lambda x: x, map(lambda x: x - 1, [0, 1, 2]))) == [-1, 1], 'filter of map'
+assert sum(filter(is_even, range(5))) == 6, 'sum of filter'
+
+f = filter(is_even, [1, 2, 3, 4])
+assert next(f) == 2, 'next on filter'
+assert list(f) == [4], 'rest of filter'
+assert next(f, None) is None, 'exhausted filter'
@@ -0,0 +1,165 @@
+# === Construction ===
+assert bytearray() == bytearray(b''), 'empty bytearray'
+assert bytearray(3) == bytearray(b'\x00\x00\x00'), 'bytearray from int'
+assert bytearray(b'abc') == b'abc', 'bytearray from bytes'
+assert bytearray(range(3)) == bytearray(b'\x00\x01\x02'), 'bytearray from range'
+assert bytearray([104, 105]) == b'hi', 'bytearray from list'
+assert bytearray('hé', 'utf-8') == bytearray(b'h\xc3\xa9'), 'bytearray from str with encoding'
+assert bytearray(bytearray(b'x')) == b'x', 'bytearray from bytearray'
+assert repr(type(bytearray())) == "<class 'bytearray'>", 'bytearray type'
+
+# === repr and truthiness ===
+assert repr(bytearray(b'ab')) == "bytearray(b'ab')", 'bytearray repr'
+assert repr(bytearray()) == "bytearray(b'')", 'empty bytearray repr'
+assert not bytearray(), 'empty bytearray is falsy'
+assert bytearray(b'a'), 'non-empty bytearray is truthy'
+
+# === Equality with bytes ===
+assert bytearray(b'ab') == b'ab', 'bytearray equals bytes'
+assert b'ab' == bytearray(b'ab'), 'bytes equals bytearray'
+assert bytearray(b'ab') != b'ac', 'bytearray not equal bytes'
+
+# === Indexing and slicing ===
+b = bytearray(b'hello')
+assert b[0] == 104, 'getitem'
+assert b[-1] == 111, 'negative getitem'
+assert b[1:3] == bytearray(b'el'), 'slice'
+assert repr(b[::2]) == "bytearray(b'hlo')", 'slice returns bytearray'
+
+# === Item and slice assignment ===
+b = bytearray(b'abc')
+b[0] = 120
+assert b == b'xbc', 'setitem'
+b[-1] = 122
+assert b == b'xbz', 'negative setitem'
+b[1:2] = b'1234'
+assert b == b'x1234z', 'slice assignment grows'
+b[1:5] = b''
+assert b == b'xz', 'slice assignment shrinks'
+b = bytearray(b'abcdef')
+b[::2] = b''
+assert b == b'bdf', 'empty extended slice assignment deletes'
+b = bytearray(b'abcdef')
+b[::-1] = b'123456'
+assert b == b'654321', 'reversed extended slice assignment'
+b = bytearray(b'abcdef')
+b[4:1] = b'xy'
+assert b == b'abcdxyef', 'empty slice assignment inserts'
+b = bytearray(b'ab')
+b[:] = [1, 2, 3]
+assert b == b'\x01\x02\x03', 'slice assignment from list'
+
+# === Deletion ===
+b = bytearray(b'abcdef')
+del b[0]
+assert b == b'bcdef', 'delitem'
+del b[1:3]
+assert b == b'bef', 'delete slice'
+del b[::2]
+assert b == b'e', 'delete extended slice'
+
+# === Mutating methods ===
+b = bytearray(b'ab')
+b.append(99)
+assert b == b'abc', 'append'
+b.extend(b'de')
+assert b == b'abcde', 'extend bytes'
+b.extend([102, 103])
+assert b == b'abcdefg', 'extend list'
+b.insert(0, 65)
+assert b == b'Aabcdefg', 'insert'
+b.insert(-1, 70)
+assert b == b'AabcdefFg', 'insert negative'
+assert b.pop() == 103, 'pop'
+assert b.pop(0) == 65, 'pop index'
+b.remove(70)
+assert b == b'abcdef', 'remove'
+b.reverse()
+assert b == b'fedcba', 'reverse'
+c = b.copy()
+b.clear()
+assert b == bytearray(), 'clear'
+assert c == b'fedcba', 'copy is independent'
+
+# === Shared bytes methods ===
+assert bytearray(b'Hello').upper() == bytearray(b'HELLO'), 'upper'
+assert repr(bytearray(b'a b').split()) == "[bytearray(b'a'), bytearray(b'b')]", 'split returns bytearrays'
+assert bytearray(b'abc').find(b'c') == 2, 'find'
+assert bytearray(b'abc').startswith(b'ab'), 'startswith'
+assert bytearray(b'hi').decode() == 'hi', 'decode'
+assert bytearray(b'ab').hex() == '6162', 'hex'
+assert bytearray.fromhex('6162') == bytearray(b'ab'), 'fromhex'
+assert repr(bytearray.fromhex('00')) == "bytearray(b'\\x00')", 'fromhex returns bytearray'
+
+# === Concatenation and repetition ===
+assert b'ab' + bytearray(b'c') == b'abc', 'bytes + bytearray'
+assert repr(b'ab' + bytearray(b'c')) == "b'abc'", 'bytes + bytearray is bytes'
+assert repr(bytearray(b'c') + b'ab') == "bytearray(b'cab')", 'bytearray + bytes is bytearray'
+assert bytearray(b'a') + bytearray(b'b') == b'ab', 'bytearray + bytearray'
+assert repr(3 * bytearray(b'x')) == "bytearray(b'xxx')", 'repeat'
+b = bytearray(b'a')
+b += b'b'
+assert b == b'ab', 'iadd bytes'
+
+# === Membership and iteration ===
+assert 97 in bytearray(b'abc'), 'int in bytearray'
+assert b'bc' in bytearray(b'abc'), 'bytes in bytearray'
+assert bytearray(b'b') in b'abc', 'bytearray in bytes'
+assert 120 not in bytearray(b'abc'), 'int not in bytearray'
+assert list(bytearray(b'ab')) == [97, 98], 'iterate bytearray'
+assert len(bytearray(b'abc')) == 3, 'len'
+
+# === Errors ===
+try:
+    bytearray(b'a')[0] = b'a'
+    assert False, 'setitem with bytes should error'
+except TypeError as e:
+    assert str(e) == "'bytes' object cannot be interpreted as an integer", f'setitem message, error: {e}'
+
+try:
+    bytearray(b'a')[0] = 256
+    assert False, 'setitem out of range should error'
+except ValueError as e:
+    assert str(e) == 'byte must be in range(0, 256)', f'byte range message, error: {e}'
+
+try:
+    bytearray(b'a')[1]
+    assert False, 'getitem out of range should error'
+except IndexError as e:
+    assert str(e) == 'bytearray index out of range', f'index message, error: {e}'
+
+try:
+    bytearray(1.5)
+    assert False, 'bytearray from float should error'
+except TypeError as e:
+    assert str(e) == "cannot convert 'float' object to bytearray", f'init message, error: {e}'
+
+try:
+    bytearray('abc')
+    assert False, 'bytearray from str should error'
+except TypeError as e:
+    assert str(e) == 'string argument without an encoding', f'str init message, error: {e}'
+
+try:
+    bytearray(b'a').extend(5)
+    assert False, 'extend with int should error'
+except TypeError as e:
+    assert str(e) == "can't extend bytearray with int", f'extend message, error: {e}'
+
+try:
+    bytearray(b'a').remove(98)
+    assert False, 'remove missing should error'
+except ValueError as e:
+    assert str(e) == 'value not found in bytearray', f'remove message, error: {e}'
+
+try:
+    'a' in b'abc'
+    assert False, 'str in bytes should error'
+except TypeError as e:
+    assert str(e) == "a bytes-like object is required, not 'str'", f'contains message, error: {e}'
+
+try:
+    hash(bytearray())
+    assert False, 'hash should error'
+except TypeError as e:
+    assert str(e) == "unhashable type: 'bytearray'", f'hash message, error: {e}'
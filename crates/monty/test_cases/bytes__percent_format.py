@@ -0,0 +1,28 @@
+# === bytes templates ===
+assert b'%s %d' % (b'ab', 3) == b'ab 3', 'bytes and int'
+assert b'%b' % b'xy' == b'xy', '%b is %s'
+assert b'%5s|%-5b|%.1s' % (b'a', b'b', b'xyz') == b'    a|b    |x', 'width and precision'
+assert b'%r %a' % (b'x', 'é') == b"b'x' '\\xe9'", 'repr is ascii'
+assert b'%c%c' % (65, b'B') == b'AB', 'characters'
+assert b'%x %05.1f' % (255, 2.25) == b'ff 002.2', 'numbers'
+assert b'%(k)s' % {b'k': b'v'} == b'v', 'mapping keys are bytes'
+assert b'100%%' % () == b'100%', 'literal percent'
+assert b'\xff%s' % b'\xfe' == b'\xff\xfe', 'non-ascii bytes'
+
+try:
+    b'%s' % 'a'
+    assert False, 'str argument should fail'
+except TypeError as e:
+    assert str(e) == "%b requires a bytes-like object, or an object that implements __bytes__, not 'str'", str(e)
+
+try:
+    b'%c' % 256
+    assert False, 'large %c should fail'
+except OverflowError as e:
+    assert str(e) == '%c arg not in range(256)', str(e)
+
+try:
+    b'%c' % b'ab'
+    assert False, '%c of several bytes should fail'
+except TypeError as e:
+    assert str(e) == '%c requires an integer in range(256) or a single byte', str(e)
@@ -0,0 +1,81 @@
+# === Instance attributes and __init__ ===
+class Point:
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+    def dist2(self):
+        return self.x * self.x + self.y * self.y
+
+    def move(self, dx, dy=0):
+        self.x += dx
+        self.y += dy
+        return self
+
+
+p = Point(3, 4)
+assert p.x == 3, 'instance attribute x'
+assert p.y == 4, 'instance attribute y'
+assert p.dist2() == 25, 'method call'
+
+# === Methods with keyword and default args ===
+p.move(1)
+assert p.x == 4, 'move updates x'
+assert p.y == 4, 'default arg'
+p.move(dy=2, dx=0)
+assert p.y == 6, 'keyword args'
+assert p.move(0) is p, 'method can return self'
+
+# === Bound methods ===
+m = p.dist2
+assert m() == 52, 'bound method call'
+assert repr(type(m)) == "<class 'method'>", 'bound method type'
+
+# === Class attributes ===
+class Counter:
+    count = 0
+    label = 'counter'
+
+    def __init__(self):
+        Counter.count += 1
+
+
+a = Counter()
+b = Counter()
+assert Counter.count == 2, 'class attribute shared'
+assert a.count == 2, 'class attribute via instance'
+assert a.label == 'counter', 'class attribute lookup'
+a.label = 'mine'
+assert a.label == 'mine', 'instance attribute shadows class attribute'
+assert b.label == 'counter', 'other instances unaffected'
+
+# === Class without __init__ ===
+class Empty:
+    pass
+
+
+e = Empty()
+e.value = 42
+assert e.value == 42, 'attributes can be added to instances'
+
+# === Classes are callable values ===
+def build(cls):
+    return cls()
+
+
+assert isinstance(build(Empty), Empty), 'class passed as value'
+assert type(e) is Empty, 'type() returns the class'
+assert Empty.__name__ == 'Empty', 'class __name__'
+assert repr(Point) == "<class '__main__.Point'>", 'class repr'
+assert e.__class__ is Empty, 'instance __class__'
+
+# === Truthiness and identity ===
+assert bool(e), 'instances are truthy'
+assert e == e, 'instance equals itself'
+assert e != Empty(), 'distinct instances are not equal'
+assert hash(e) == hash(e), 'instances hash by identity'
+d = {e: 1}
+assert d[e] == 1, 'instances can be dict keys'
+
+# === Functions stored on the class can be called through the class ===
+assert Point.dist2(p) == 52, 'unbound method call'
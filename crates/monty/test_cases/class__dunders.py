@@ -0,0 +1,182 @@
+# === __repr__ and __str__ ===
+class Vec:
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+    def __repr__(self):
+        return f'Vec({self.x}, {self.y})'
+
+    def __eq__(self, other):
+        return isinstance(other, Vec) and self.x == other.x and self.y == other.y
+
+
+v = Vec(1, 2)
+assert repr(v) == 'Vec(1, 2)', 'custom __repr__'
+assert str(v) == 'Vec(1, 2)', 'str falls back to __repr__'
+assert f'{v}' == 'Vec(1, 2)', 'f-string uses __repr__ via __str__'
+assert f'{v!r}' == 'Vec(1, 2)', 'f-string !r'
+assert repr([v, v]) == '[Vec(1, 2), Vec(1, 2)]', '__repr__ inside list'
+assert repr((v,)) == '(Vec(1, 2),)', '__repr__ inside tuple'
+assert repr({'a': v}) == "{'a': Vec(1, 2)}", '__repr__ inside dict'
+
+
+class Named:
+    def __init__(self, name):
+        self.name = name
+
+    def __str__(self):
+        return 'Named ' + self.name
+
+    def __repr__(self):
+        return 'Named(' + repr(self.name) + ')'
+
+
+n = Named('x')
+assert str(n) == 'Named x', 'custom __str__'
+assert repr(n) == "Named('x')", 'custom __repr__ alongside __str__'
+assert f'{n}' == 'Named x', 'f-string uses __str__'
+assert f'{n!r}' == "Named('x')", 'f-string !r uses __repr__'
+assert str([n]) == "[Named('x')]", 'str of list uses __repr__ of items'
+
+# === __eq__ and __ne__ ===
+assert Vec(1, 2) == Vec(1, 2), 'custom __eq__'
+assert Vec(1, 2) != Vec(2, 1), '__ne__ falls back to inverted __eq__'
+assert not (Vec(1, 2) == 5), '__eq__ with other type'
+assert Vec(1, 2) in [Vec(0, 0), Vec(1, 2)], 'in uses __eq__'
+assert Vec(3, 3) not in (Vec(0, 0),), 'not in uses __eq__'
+assert [Vec(1, 2)] == [Vec(1, 2)], '__eq__ on list items'
+assert (Vec(1, 2), 3) == (Vec(1, 2), 3), '__eq__ on tuple items'
+assert [Vec(1, 2)] != [Vec(2, 1)], '__eq__ on unequal list items'
+assert [[Vec(1, 2)]] == [[Vec(1, 2)]], '__eq__ on nested list items'
+assert {'a': Vec(1, 2)} == {'a': Vec(1, 2)}, '__eq__ on dict values'
+assert {'a': Vec(1, 2)} != {'b': Vec(1, 2)}, 'dict keys still compared'
+assert [Vec(1, 2)] in [[Vec(0, 0)], [Vec(1, 2)]], 'in compares nested items with __eq__'
+assert (Vec(1, 2),) not in [(Vec(2, 1),)], 'not in compares nested items with __eq__'
+
+# === __eq__ without __hash__ makes instances unhashable ===
+try:
+    hash(v)
+    assert False, 'should be unhashable'
+except TypeError as e:
+    assert str(e) == "unhashable type: 'Vec'", 'unhashable message'
+
+# === __hash__ ===
+class Key:
+    def __init__(self, k):
+        self.k = k
+
+    def __eq__(self, other):
+        return self.k == other.k
+
+    def __hash__(self):
+        return hash(self.k)
+
+
+assert hash(Key(5)) == hash(5), 'custom __hash__'
+
+# === __len__ ===
+class Bag:
+    def __init__(self, items):
+        self.items = items
+
+    def __len__(self):
+        return len(self.items)
+
+
+assert len(Bag([1, 2, 3])) == 3, 'custom __len__'
+
+# === __getitem__, __setitem__ and __delitem__ ===
+class Grid:
+    def __init__(self):
+        self.cells = {}
+
+    def __getitem__(self, pos):
+        return self.cells.get(pos, '.')
+
+    def __setitem__(self, pos, value):
+        self.cells[pos] = value
+
+    def __delitem__(self, pos):
+        del self.cells[pos]
+
+
+g = Grid()
+g[0, 1] = 'x'
+assert g[0, 1] == 'x', 'custom __setitem__ and __getitem__'
+assert g[1, 1] == '.', 'custom __getitem__ default'
+g[0, 1] += 'y'
+assert g.cells == {(0, 1): 'xy'}, 'augmented assignment uses both'
+del g[0, 1]
+assert g.cells == {}, 'custom __delitem__'
+
+try:
+    Bag([1])[0]
+    assert False, 'should raise'
+except TypeError as e:
+    assert str(e) == "'Bag' object is not subscriptable", str(e)
+
+try:
+    Bag([1])[0] = 2
+    assert False, 'should raise'
+except TypeError as e:
+    assert str(e) == "'Bag' object does not support item assignment", str(e)
+
+# === Ordering ===
+class Version:
+    def __init__(self, n):
+        self.n = n
+
+    def __lt__(self, other):
+        return self.n < other.n
+
+
+assert Version(1) < Version(2), 'custom __lt__'
+assert Version(3) > Version(2), 'reflected __lt__ for >'
+
+try:
+    Version(1) <= Version(2)
+    assert False, 'should raise'
+except TypeError as e:
+    assert str(e) == "'<=' not supported between instances of 'Version' and 'Version'", 'ordering error'
+
+assert (Version(1), 5) < (Version(2), 0), '__lt__ on tuple items'
+one = Version(1)
+assert [one] < [one, Version(0)], 'shorter list orders first'
+assert [v.n for v in sorted([Version(3), Version(1), Version(2)])] == [1, 2, 3], 'sorted uses __lt__'
+assert [v.n for v in sorted([Version(3), Version(1), Version(2)], reverse=True)] == [3, 2, 1], 'sorted reverse'
+assert min(Version(3), Version(1), Version(2)).n == 1, 'min uses __lt__'
+assert max([Version(3), Version(1), Version(2)]).n == 3, 'max uses reflected __lt__'
+assert sorted([(Version(2), 'b'), (Version(1), 'a')])[0][1] == 'a', 'sorted tuples holding instances'
+pairs = [('x', Version(2)), ('y', Version(1))]
+assert [p[0] for p in sorted(pairs, key=lambda p: p[1])] == ['y', 'x'], 'sorted with instance keys'
+versions = [Version(2), Version(1)]
+versions.sort()
+assert [v.n for v in versions] == [1, 2], 'list.sort uses __lt__'
+
+
+class Tagged:
+    def __init__(self, n, tag):
+        self.n = n
+        self.tag = tag
+
+    def __lt__(self, other):
+        return self.n < other.n
+
+
+tagged = [Tagged(1, 'a'), Tagged(0, 'b'), Tagged(1, 'c'), Tagged(0, 'd')]
+assert [t.tag for t in sorted(tagged)] == ['b', 'd', 'a', 'c'], 'sort with __lt__ is stable'
+assert [t.tag for t in sorted(tagged, reverse=True)] == ['a', 'c', 'b', 'd'], 'reverse sort is stable'
+assert min(tagged).tag == 'b', 'min keeps the first of equal items'
+
+# === Errors raised in special methods propagate ===
+class Broken:
+    def __repr__(self):
+        raise ValueError('bad repr')
+
+
+try:
+    repr(Broken())
+    assert False, 'should raise'
+except ValueError as e:
+    assert str(e) == 'bad repr', 'error from __repr__'
@@ -0,0 +1,103 @@
+# Instances with a user-defined __hash__ as dict keys and set elements
+calls = []
+
+
+class Key:
+    def __init__(self, k):
+        self.k = k
+
+    def __eq__(self, other):
+        calls.append(('eq', self.k))
+        return isinstance(other, Key) and self.k == other.k
+
+    def __hash__(self):
+        calls.append(('hash', self.k))
+        return hash(self.k)
+
+    def __repr__(self):
+        return f'Key({self.k})'
+
+
+# === dict displays and subscripts ===
+first = Key(1)
+d = {first: 'a', Key(2): 'b'}
+assert len(d) == 2, 'dict display with instance keys'
+assert d[Key(1)] == 'a', 'subscript with an equal key'
+assert d[first] == 'a', 'subscript with the same key'
+d[Key(1)] = 'c'
+assert len(d) == 2, 'equal key replaces the value'
+assert d[first] == 'c', 'replaced value'
+assert [k for k in d][0] is first, 'the stored key is kept'
+assert {Key(1): 'x', Key(1): 'y'} == {Key(1): 'y'}, 'duplicate keys in a display'
+del d[Key(2)]
+assert list(d) == [first], 'del with an equal key'
+assert repr(d) == "{Key(1): 'c'}", 'repr of dict with instance keys'
+
+try:
+    d[Key(3)]
+    assert False, 'should raise'
+except KeyError:
+    pass
+
+# === in ===
+assert Key(1) in d, 'in dict with an equal key'
+assert Key(3) not in d, 'not in dict'
+assert Key(1) in {Key(1)}, 'in set with an equal element'
+assert Key(2) not in frozenset([Key(1)]), 'not in frozenset'
+assert (Key(1), 2) in {(Key(1), 2): None}, 'tuple key holding an instance'
+assert (Key(1), 3) not in {(Key(1), 2): None}, 'tuple key compares all items'
+
+# === comprehensions ===
+assert len({Key(i % 2): i for i in range(4)}) == 2, 'dict comprehension'
+assert len({Key(i % 3) for i in range(6)}) == 3, 'set comprehension'
+
+# === sets ===
+s = {Key(1), Key(1), Key(2)}
+assert len(s) == 2, 'set display'
+s.add(Key(2))
+assert len(s) == 2, 'add an equal element'
+s.add(Key(3))
+assert len(s) == 3, 'add a new element'
+s.discard(Key(3))
+s.remove(Key(2))
+assert len(s) == 1 and Key(1) in s, 'remove with an equal element'
+assert len(set([Key(1), Key(1)])) == 1, 'set() of a list'
+assert len(frozenset((Key(1), Key(2), Key(1)))) == 2, 'frozenset() of a tuple'
+
+# === dict methods ===
+d = {Key(1): 'a'}
+assert d.get(Key(1)) == 'a', 'get with an equal key'
+assert d.get(Key(2), 'z') == 'z', 'get default'
+assert d.setdefault(Key(1), 'b') == 'a', 'setdefault with an equal key'
+assert d.pop(Key(1)) == 'a', 'pop with an equal key'
+assert d == {}, 'popped'
+
+# === equal hashes compare with __eq__ ===
+calls.clear()
+d = {Key(1): 'a'}
+assert d[Key(1)] == 'a', 'lookup'
+assert calls == [('hash', 1), ('hash', 1), ('eq', 1)], calls
+
+# === __hash__ errors ===
+class BadHash:
+    def __hash__(self):
+        return 'nope'
+
+
+try:
+    {BadHash(): 1}
+    assert False, 'should raise'
+except TypeError as e:
+    assert str(e) == '__hash__ method should return an integer', str(e)
+
+
+class Unhashable:
+    def __eq__(self, other):
+        return True
+
+
+try:
+    {Unhashable()}
+    assert False, 'should raise'
+except TypeError:
+    pass
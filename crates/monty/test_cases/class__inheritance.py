@@ -0,0 +1,78 @@
+# === Single inheritance and super() ===
+class Animal:
+    kind = 'animal'
+
+    def __init__(self, name):
+        self.name = name
+
+    def speak(self):
+        return 'generic noise'
+
+    def describe(self):
+        return self.name + ' says ' + self.speak()
+
+
+class Dog(Animal):
+    kind = 'dog'
+
+    def __init__(self, name, breed):
+        super().__init__(name)
+        self.breed = breed
+
+    def speak(self):
+        return 'woof'
+
+
+class Puppy(Dog):
+    def speak(self):
+        return super().speak() + '!'
+
+
+d = Dog('rex', 'collie')
+assert d.name == 'rex', 'base __init__ via super()'
+assert d.breed == 'collie', 'subclass __init__'
+assert d.speak() == 'woof', 'overridden method'
+assert d.describe() == 'rex says woof', 'inherited method calls override'
+assert d.kind == 'dog', 'class attribute override'
+
+p = Puppy('bit', 'pug')
+assert p.speak() == 'woof!', 'super() in multi-level hierarchy'
+assert p.describe() == 'bit says woof!', 'inherited __init__ from grandparent chain'
+assert p.kind == 'dog', 'class attribute inherited'
+
+# === isinstance with class hierarchies ===
+assert isinstance(p, Puppy), 'isinstance own class'
+assert isinstance(p, Dog), 'isinstance parent'
+assert isinstance(p, Animal), 'isinstance grandparent'
+assert not isinstance(d, Puppy), 'isinstance subclass is false'
+assert isinstance(d, (int, Animal)), 'isinstance tuple with class'
+assert isinstance(d, object), 'everything is an object'
+
+# === Explicit two-argument super ===
+class Base:
+    def value(self):
+        return 1
+
+
+class Child(Base):
+    def value(self):
+        return super(Child, self).value() + 1
+
+
+assert Child().value() == 2, 'two-argument super'
+
+# === super().__init__() falls back to object.__init__ ===
+class Plain:
+    def __init__(self):
+        super().__init__()
+        self.ready = True
+
+
+assert Plain().ready, 'object.__init__ via super'
+
+# === Explicit object base ===
+class Explicit(object):
+    pass
+
+
+assert isinstance(Explicit(), Explicit), 'explicit object base'
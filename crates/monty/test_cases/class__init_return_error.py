@@ -0,0 +1,14 @@
+class Bad:
+    def __init__(self):
+        return 1
+
+
+Bad()
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "class__init_return_error.py", line 6, in <module>
+    Bad()
+    ~~~~~
+TypeError: __init__() should return None, not 'int'
+"""
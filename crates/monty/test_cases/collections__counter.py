@@ -0,0 +1,52 @@
+from collections import Counter
+
+# === counting ===
+c = Counter('abracadabra')
+assert c['a'] == 5, 'count of a'
+assert c['b'] == 2, 'count of b'
+assert c['z'] == 0, 'missing element counts as zero'
+assert 'z' not in c, 'missing element is not added'
+assert len(c) == 5, 'number of distinct elements'
+assert Counter({'a': 2, 'b': 1}) == Counter(a=2, b=1), 'mapping and kwargs'
+assert Counter() == Counter(), 'empty counters'
+assert isinstance(c, dict), 'Counter is a dict'
+
+# === most_common ===
+assert c.most_common(1) == [('a', 5)], 'most common element'
+assert c.most_common(3) == [('a', 5), ('b', 2), ('r', 2)], 'ties keep insertion order'
+assert len(c.most_common()) == 5, 'all elements'
+assert Counter().most_common() == [], 'empty counter'
+
+# === update and subtract ===
+c = Counter(a=1)
+c.update(['a', 'b'])
+assert c == Counter(a=2, b=1), 'update with iterable'
+c.update({'b': 3})
+assert c == Counter(a=2, b=4), 'update with mapping adds counts'
+c.update(c=1)
+assert c['c'] == 1, 'update with kwargs'
+c.subtract(['a', 'a', 'a'])
+assert c['a'] == -1, 'subtract can go negative'
+c.subtract({'b': 1})
+assert c['b'] == 3, 'subtract with mapping'
+
+# === total and elements ===
+assert Counter(a=2, b=3).total() == 5, 'total'
+assert sorted(Counter(a=2, b=1, c=0, d=-1).elements()) == ['a', 'a', 'b'], 'elements skips counts below one'
+
+# === arithmetic ===
+a = Counter(a=3, b=1)
+b = Counter(a=1, b=2, c=1)
+assert a + b == Counter(a=4, b=3, c=1), 'add'
+assert a - b == Counter(a=2), 'subtract keeps positive counts'
+assert a & b == Counter(a=1, b=1), 'intersection takes the minimum'
+assert a | b == Counter(a=3, b=2, c=1), 'union takes the maximum'
+a += b
+assert a == Counter(a=4, b=3, c=1), 'in-place add'
+
+# === equality and repr ===
+assert Counter(a=1, b=0) == Counter(a=1), 'zero counts equal missing elements'
+assert Counter(a=1) != Counter(a=2), 'different counts'
+assert repr(Counter()) == 'Counter()', 'empty repr'
+assert repr(Counter('aab')) == "Counter({'a': 2, 'b': 1})", 'repr'
+assert repr(Counter('abb')) == "Counter({'b': 2, 'a': 1})", 'repr lists most common first'
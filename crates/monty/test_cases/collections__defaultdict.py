@@ -0,0 +1,50 @@
+from collections import defaultdict
+
+# === factories ===
+counts = defaultdict(int)
+for word in ['a', 'b', 'a', 'c', 'a']:
+    counts[word] = counts[word] + 1
+assert counts == {'a': 3, 'b': 1, 'c': 1}, 'int factory'
+assert counts['missing'] == 0, 'missing key is created with the default'
+assert 'missing' in counts, 'missing key is stored'
+
+groups = defaultdict(list)
+groups['x'].append(1)
+groups['x'].append(2)
+groups['y'].append(3)
+assert groups == {'x': [1, 2], 'y': [3]}, 'list factory'
+
+labels = defaultdict(lambda: 'unknown')
+assert labels['k'] == 'unknown', 'lambda factory'
+labels['j'] = 'set'
+assert labels['j'] == 'set', 'explicit value is kept'
+
+# === default_factory ===
+assert defaultdict(int).default_factory is int, 'default_factory attribute'
+assert defaultdict().default_factory is None, 'no factory'
+assert defaultdict(None, {'a': 1}) == {'a': 1}, 'None factory with initial items'
+
+try:
+    defaultdict()['missing']
+    assert False, 'should raise KeyError'
+except KeyError as e:
+    assert e.args == ('missing',), 'KeyError without a factory'
+
+try:
+    defaultdict(1)
+    assert False, 'should raise TypeError'
+except TypeError as e:
+    assert str(e) == 'first argument must be callable or None', 'non-callable factory'
+
+# === get() doesn't use the factory ===
+d = defaultdict(int)
+assert d.get('a') is None, 'get of missing key'
+assert len(d) == 0, 'get does not insert'
+
+# === initial values and dict behaviour ===
+d = defaultdict(int, {'a': 1}, b=2)
+assert d == {'a': 1, 'b': 2}, 'initial mapping and kwargs'
+assert isinstance(d, dict), 'defaultdict is a dict'
+assert type(d) is defaultdict, 'type of defaultdict'
+assert repr(defaultdict(int, {'a': 1})) == "defaultdict(<class 'int'>, {'a': 1})", 'repr'
+assert repr(defaultdict()) == 'defaultdict(None, {})', 'repr without factory'
@@ -0,0 +1,85 @@
+from collections import deque
+
+
+def error(f, *args):
+    try:
+        f(*args)
+    except Exception as e:
+        return f'{type(e).__name__}: {e}'
+    assert False, f'{f} should have raised'
+
+
+# === both ends ===
+d = deque([2, 3])
+d.append(4)
+d.appendleft(1)
+assert list(d) == [1, 2, 3, 4], 'append and appendleft'
+assert d.pop() == 4, 'pop'
+assert d.popleft() == 1, 'popleft'
+d.extend([4, 5])
+d.extendleft([1, 0])
+assert list(d) == [0, 1, 2, 3, 4, 5], 'extend and extendleft'
+assert len(d) == 6, 'len'
+assert d[0] == 0, 'first item'
+assert d[-1] == 5, 'last item'
+d[1] = 10
+assert d[1] == 10, 'set item'
+del d[1]
+assert list(d) == [0, 2, 3, 4, 5], 'del item'
+assert 3 in d, 'contains'
+assert 9 not in d, 'not contains'
+
+# === maxlen ===
+d = deque([1, 2, 3], maxlen=3)
+d.append(4)
+assert list(d) == [2, 3, 4], 'append drops from the left'
+d.appendleft(1)
+assert list(d) == [1, 2, 3], 'appendleft drops from the right'
+d.extend([4, 5])
+assert list(d) == [3, 4, 5], 'extend keeps the last items'
+assert d.maxlen == 3, 'maxlen attribute'
+assert deque().maxlen is None, 'no maxlen'
+assert list(deque(range(5), maxlen=2)) == [3, 4], 'maxlen on creation'
+assert list(deque([1], maxlen=0)) == [], 'zero maxlen'
+
+# === rotate, reverse, count, index, remove ===
+d = deque([1, 2, 3, 4, 5])
+d.rotate(2)
+assert list(d) == [4, 5, 1, 2, 3], 'rotate right'
+d.rotate(-3)
+assert list(d) == [2, 3, 4, 5, 1], 'rotate left'
+d.rotate()
+assert list(d) == [1, 2, 3, 4, 5], 'rotate by one'
+d.reverse()
+assert list(d) == [5, 4, 3, 2, 1], 'reverse'
+assert deque([1, 2, 1]).count(1) == 2, 'count'
+assert deque([1, 2, 3]).index(2) == 1, 'index'
+d = deque([1, 2, 3, 2])
+d.remove(2)
+assert list(d) == [1, 3, 2], 'remove first match'
+d.clear()
+assert len(d) == 0, 'clear'
+d = deque([1, 2])
+c = d.copy()
+c.append(3)
+assert list(d) == [1, 2], 'copy is independent'
+d.insert(1, 5)
+assert list(d) == [1, 5, 2], 'insert'
+
+# === comparison, bool and repr ===
+assert deque([1, 2]) == deque([1, 2]), 'equal deques'
+assert deque([1, 2]) != deque([2, 1]), 'order matters'
+assert deque([1, 2]) != [1, 2], 'deque is not equal to list'
+assert deque([1]) + deque([2]) == deque([1, 2]), 'concatenation'
+assert not deque(), 'empty deque is falsy'
+assert deque([0]), 'non-empty deque is truthy'
+assert repr(deque([1, 2])) == 'deque([1, 2])', 'repr'
+assert repr(deque()) == 'deque([])', 'empty repr'
+assert repr(deque([1], maxlen=3)) == 'deque([1], maxlen=3)', 'repr with maxlen'
+
+# === errors ===
+assert error(deque().pop) == 'IndexError: pop from an empty deque', 'pop empty'
+assert error(deque().popleft) == 'IndexError: pop from an empty deque', 'popleft empty'
+assert error(lambda: deque()[0]) == 'IndexError: deque index out of range', 'index out of range'
+assert error(deque([1]).index, 2) == 'ValueError: 2 is not in deque', 'index missing'
+assert error(deque, [], -1) == 'ValueError: maxlen must be non-negative', 'negative maxlen'
@@ -0,0 +1,62 @@
+from collections import namedtuple
+
+
+def error(f, *args, **kwargs):
+    try:
+        f(*args, **kwargs)
+    except Exception as e:
+        return f'{type(e).__name__}: {e}'
+    assert False, f'{f} should have raised'
+
+
+# === creating types and instances ===
+Point = namedtuple('Point', 'x y')
+p = Point(1, 2)
+assert p.x == 1, 'first field'
+assert p.y == 2, 'second field'
+assert p[0] == 1, 'index access'
+assert p == (1, 2), 'equal to a tuple'
+assert len(p) == 2, 'len'
+assert Point(x=3, y=4) == (3, 4), 'keyword arguments'
+assert repr(p) == 'Point(x=1, y=2)', 'repr'
+assert Point.__name__ == 'Point', 'type name'
+assert namedtuple('P', 'a, b')._fields == ('a', 'b'), 'comma separated fields'
+assert namedtuple('P', ['a', 'b'])._fields == ('a', 'b'), 'list of fields'
+x, y = p
+assert (x, y) == (1, 2), 'unpacking'
+
+# === defaults ===
+Config = namedtuple('Config', ['host', 'port', 'debug'], defaults=[8080, False])
+assert Config('localhost') == ('localhost', 8080, False), 'defaults fill the rightmost fields'
+assert Config('h', debug=True) == ('h', 8080, True), 'keyword overrides a default'
+assert Config._field_defaults == {'port': 8080, 'debug': False}, '_field_defaults'
+
+# === helper methods ===
+assert p._asdict() == {'x': 1, 'y': 2}, '_asdict'
+assert p._replace(y=5) == Point(1, 5), '_replace'
+assert p == Point(1, 2), '_replace leaves the original unchanged'
+assert Point._make([7, 8]) == Point(7, 8), '_make'
+assert p._fields == ('x', 'y'), '_fields of an instance'
+
+# === isinstance ===
+assert isinstance(p, Point), 'instance of its type'
+assert isinstance(p, tuple), 'instance of tuple'
+assert not isinstance((1, 2), Point), 'plain tuple is not an instance'
+Other = namedtuple('Other', 'x y')
+assert not isinstance(p, Other), 'different named tuple type'
+
+# === rename ===
+assert namedtuple('R', ['a', 'def', 'a', '_b'], rename=True)._fields == ('a', '_1', '_2', '_3'), 'rename'
+
+# === errors ===
+assert error(namedtuple, 'P', 'x def') == "ValueError: Type names and field names cannot be a keyword: 'def'", 'keyword'
+assert error(namedtuple, 'P', 'x 1y') == (
+    "ValueError: Type names and field names must be valid identifiers: '1y'"
+), 'invalid identifier'
+assert error(namedtuple, 'P', 'x _y') == "ValueError: Field names cannot start with an underscore: '_y'", 'underscore'
+assert error(namedtuple, 'P', 'x x') == "ValueError: Encountered duplicate field name: 'x'", 'duplicate'
+assert error(namedtuple, 'P', 'x', defaults=[1, 2]) == 'TypeError: Got more default values than field names', (
+    'too many defaults'
+)
+assert error(Point._make, [1]) == 'TypeError: Expected 2 arguments, got 1', '_make with wrong length'
+assert error(p._replace, z=1) == "TypeError: Got unexpected field names: ['z']", '_replace with unknown field'
@@ -0,0 +1,56 @@
+from collections import OrderedDict
+
+# === ordering ===
+od = OrderedDict()
+od['a'] = 1
+od['b'] = 2
+od['c'] = 3
+assert list(od) == ['a', 'b', 'c'], 'insertion order'
+od.move_to_end('a')
+assert list(od) == ['b', 'c', 'a'], 'move_to_end'
+od.move_to_end('a', last=False)
+assert list(od) == ['a', 'b', 'c'], 'move_to_end to the front'
+
+try:
+    od.move_to_end('z')
+    assert False, 'should raise KeyError'
+except KeyError as e:
+    assert e.args == ('z',), 'move_to_end of missing key'
+
+# === popitem ===
+od = OrderedDict([('a', 1), ('b', 2), ('c', 3)])
+assert od.popitem() == ('c', 3), 'popitem takes the last item'
+assert od.popitem(last=False) == ('a', 1), 'popitem takes the first item'
+assert list(od.items()) == [('b', 2)], 'remaining items'
+
+# === reordering many items ===
+od = OrderedDict((i, i * i) for i in range(100))
+for i in range(0, 100, 2):
+    od.move_to_end(i)
+assert list(od) == list(range(1, 100, 2)) + list(range(0, 100, 2)), 'odd keys first'
+for i in range(10):
+    od.move_to_end(i, last=False)
+assert list(od)[:10] == list(range(9, -1, -1)), 'moved to the front in reverse'
+assert len(od) == 100, 'moving keeps every item'
+assert od[42] == 1764, 'moved items keep their values'
+assert [od.popitem(last=False)[0] for _ in range(5)] == [9, 8, 7, 6, 5], 'popitem from the front'
+assert od.popitem() == (98, 9604), 'popitem from the end after moves'
+del od[11]
+assert 11 not in od and 13 in od, 'deleted item'
+od[11] = 0
+assert list(od)[-1] == 11, 'reinserted item goes to the end'
+
+queue = OrderedDict()
+for i in range(50):
+    queue[i] = None
+    if i % 3 == 2:
+        queue.popitem(last=False)
+assert list(queue) == list(range(16, 50)), 'queue after pops from the front'
+assert OrderedDict.fromkeys(queue) == queue, 'equal after pops from the front'
+
+# === equality ===
+assert OrderedDict(a=1, b=2) == OrderedDict(a=1, b=2), 'same order'
+assert OrderedDict(a=1, b=2) != OrderedDict(b=2, a=1), 'order matters between OrderedDicts'
+assert OrderedDict(a=1, b=2) == {'b': 2, 'a': 1}, 'order does not matter against a dict'
+assert isinstance(OrderedDict(), dict), 'OrderedDict is a dict'
+assert repr(OrderedDict()) == 'OrderedDict()', 'empty repr'
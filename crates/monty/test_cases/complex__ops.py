@@ -0,0 +1,86 @@
+# === Literals and repr ===
+assert repr(1j) == '1j', 'imaginary literal repr'
+assert repr(1 + 2j) == '(1+2j)', 'complex repr'
+assert repr(1 - 2j) == '(1-2j)', 'negative imag repr'
+assert repr(1.5 + 0j) == '(1.5+0j)', 'zero imag repr'
+assert repr(2.5j) == '2.5j', 'float imaginary literal repr'
+assert str(3 + 4j) == '(3+4j)', 'complex str'
+assert repr(type(1j)) == "<class 'complex'>", 'complex type'
+
+# === Constructor ===
+assert complex() == 0j, 'no args'
+assert complex(1) == 1 + 0j, 'int arg'
+assert complex(1, 2) == 1 + 2j, 'two args'
+assert complex(1.5, -2) == 1.5 - 2j, 'float and int args'
+assert complex('1+2j') == 1 + 2j, 'string arg'
+assert complex(' -j ') == -1j, 'string unit imag'
+assert complex('(3.5e2-1j)') == 350 - 1j, 'parenthesized string'
+assert complex(1 + 1j, 1 + 1j) == 2j, 'complex args'
+
+# === Attributes ===
+z = 3 + 4j
+assert z.real == 3.0, 'real'
+assert z.imag == 4.0, 'imag'
+assert z.conjugate() == 3 - 4j, 'conjugate'
+assert abs(z) == 5.0, 'abs'
+
+# === Arithmetic ===
+assert (1 + 2j) + (3 + 4j) == 4 + 6j, 'add'
+assert (1 + 2j) - 1 == 2j, 'sub int'
+assert 2 * (1 + 2j) == 2 + 4j, 'int mul'
+assert (1 + 2j) * (3 + 4j) == -5 + 10j, 'mul'
+assert (2 + 3j) / (1 - 1j) == -0.5 + 2.5j, 'div'
+assert 1j**2 == -1 + 0j, 'pow int'
+assert (1 + 2j) ** 3 == -11 - 2j, 'pow cube'
+assert repr(2**1j) == '(0.7692389013639721+0.6389612763136348j)', 'real to complex pow'
+assert -(1 + 2j) == -1 - 2j, 'negation'
+assert +(1 + 2j) == 1 + 2j, 'unary plus'
+z = 1j
+z += 1
+assert z == 1 + 1j, 'augmented add'
+
+# === Equality and truthiness ===
+assert 1 + 0j == 1, 'complex equals int'
+assert 1.5 == 1.5 + 0j, 'float equals complex'
+assert 1j != 1, 'complex not equal int'
+assert not 0j, 'zero is falsy'
+assert 1j, 'non-zero is truthy'
+assert hash(1 + 0j) == hash(1 + 0j), 'complex is hashable'
+assert {1j: 'a'}[1j] == 'a', 'complex dict key'
+
+# === Errors ===
+try:
+    1j / 0
+    assert False, 'division by zero should error'
+except ZeroDivisionError as e:
+    assert str(e) == 'complex division by zero', f'division message, error: {e}'
+
+try:
+    0j**-1
+    assert False, 'zero to negative power should error'
+except ZeroDivisionError as e:
+    assert str(e) == '0.0 to a negative or complex power', f'power message, error: {e}'
+
+try:
+    1j // 2
+    assert False, 'floor division should error'
+except TypeError as e:
+    assert str(e) == "unsupported operand type(s) for //: 'complex' and 'int'", f'floordiv message, error: {e}'
+
+try:
+    complex('abc')
+    assert False, 'malformed string should error'
+except ValueError as e:
+    assert str(e) == 'complex() arg is a malformed string', f'malformed message, error: {e}'
+
+try:
+    complex('1+2j', 1)
+    assert False, 'string with second arg should error'
+except TypeError as e:
+    assert str(e) == "complex() can't take second arg if first is a string", f'second arg message, error: {e}'
+
+try:
+    complex([])
+    assert False, 'list arg should error'
+except TypeError as e:
+    assert str(e) == "complex() first argument must be a string or a number, not 'list'", f'list message, error: {e}'
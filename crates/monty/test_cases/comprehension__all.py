@@ -164,13 +164,10 @@ def outer_shadow_unpack():
 
 assert outer_shadow_unpack() == [3, 7], 'shadow unpack in closure'
 
-# === Generator expressions (temporary: treated as list comprehensions) ===
-# TODO: When proper generators are implemented, these should return generator objects
-# instead of lists. For now, generator expressions are parsed as list comprehensions.
-# See iter__generator_expr.py for tests, and iter__generator_expr_type.py for
-# a type check test (xfail=cpython since CPython has real generators).
+# === Generator expressions ===
+# See iter__generator_expr.py and generator__*.py for more tests
 
-# Generator in list() call - works identically in both Monty and CPython
+# Generator in list() call
 assert list(x for x in [1, 2, 3]) == [1, 2, 3], 'generator in list()'
 assert tuple(x for x in [1, 2, 3]) == (1, 2, 3), 'generator in tuple()'
 
@@ -0,0 +1,25 @@
+# call-external
+# === del on a mutable dataclass ===
+point = make_mutable_point()
+del point.x
+try:
+    point.x
+    assert False, 'field should be deleted'
+except AttributeError:
+    pass
+assert point.y == 2, 'other field kept'
+
+try:
+    del point.z
+    assert False, 'missing attribute should fail'
+except AttributeError as e:
+    assert str(e) == "'MutablePoint' object has no attribute 'z'", 'missing attribute message'
+
+# === del on a frozen dataclass ===
+frozen = make_point()
+try:
+    del frozen.x
+    assert False, 'frozen field cannot be deleted'
+except AttributeError as e:
+    assert str(e) == "cannot delete field 'x'", 'frozen delete message'
+assert frozen.x == 1, 'frozen field kept'
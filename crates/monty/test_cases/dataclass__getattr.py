@@ -0,0 +1,14 @@
+# call-external
+# === getattr/hasattr/setattr on dataclasses ===
+point = make_point()
+assert getattr(point, 'x') == 1, 'getattr dataclass field'
+assert getattr(point, 'y', 0) == 2, 'getattr dataclass field with default'
+assert getattr(point, 'z', 0) == 0, 'getattr missing dataclass field'
+assert hasattr(point, 'x'), 'hasattr dataclass field'
+assert not hasattr(point, 'z'), 'hasattr missing dataclass field'
+
+mut = make_mutable_point()
+setattr(mut, 'x', 10)
+assert mut.x == 10, 'setattr dataclass field'
+assert getattr(mut, 'x') == 10, 'getattr after setattr'
+assert not callable(mut), 'dataclass instance is not callable'
@@ -0,0 +1,87 @@
+import datetime
+from datetime import MAXYEAR, MINYEAR, date, time, timedelta, timezone
+
+# === module ===
+assert MINYEAR == 1, 'MINYEAR'
+assert MAXYEAR == 9999, 'MAXYEAR'
+assert datetime.date is date, 'module attribute'
+assert repr(date) == "<class 'datetime.date'>", 'class repr'
+assert date.__name__ == 'date', 'class name'
+assert type(date(2024, 1, 1)).__name__ == 'date', 'type name'
+
+# === date construction and attributes ===
+d = date(2024, 2, 29)
+assert d.year == 2024, 'year'
+assert d.month == 2, 'month'
+assert d.day == 29, 'day'
+assert repr(d) == 'datetime.date(2024, 2, 29)', 'date repr'
+assert str(d) == '2024-02-29', 'date str'
+assert d.isoformat() == '2024-02-29', 'date isoformat'
+assert date(year=1, month=1, day=1) == date.min, 'keyword arguments'
+assert repr(date.max) == 'datetime.date(9999, 12, 31)', 'date.max'
+assert date.resolution == timedelta(days=1), 'date.resolution'
+assert isinstance(d, date), 'isinstance date'
+assert not isinstance(d, datetime.datetime), 'a date is not a datetime'
+
+# === calendar ===
+assert date(2024, 1, 1).weekday() == 0, 'monday weekday'
+assert date(2024, 1, 7).isoweekday() == 7, 'sunday isoweekday'
+assert date(2024, 1, 1).toordinal() == 738886, 'toordinal'
+assert date.fromordinal(738886) == date(2024, 1, 1), 'fromordinal'
+assert date(1, 1, 1).toordinal() == 1, 'first ordinal'
+assert tuple(date(2021, 1, 3).isocalendar()) == (2020, 53, 7), 'isocalendar in the previous iso year'
+ic = date(2024, 12, 30).isocalendar()
+assert (ic.year, ic.week, ic.weekday) == (2025, 1, 1), 'isocalendar named fields'
+assert date.fromisocalendar(2020, 53, 7) == date(2021, 1, 3), 'fromisocalendar'
+assert date(2024, 3, 1).ctime() == 'Fri Mar  1 00:00:00 2024', 'date ctime'
+assert d.replace(year=2025, day=28) == date(2025, 2, 28), 'date replace'
+assert d.replace() == d, 'replace without arguments'
+
+# === date arithmetic ===
+assert date(2024, 2, 28) + timedelta(days=1) == date(2024, 2, 29), 'leap day'
+assert timedelta(days=2) + date(2023, 12, 31) == date(2024, 1, 2), 'timedelta + date'
+assert date(2024, 3, 1) - timedelta(days=1) == date(2024, 2, 29), 'date - timedelta'
+assert date(2024, 1, 1) - date(2023, 1, 1) == timedelta(days=365), 'date - date'
+assert date(2024, 1, 1) + timedelta(hours=47) == date(2024, 1, 2), 'hours are dropped'
+assert date(2024, 1, 1) - timedelta(seconds=1) == date(2024, 1, 1), 'only whole days are subtracted'
+assert date(2024, 1, 1) + timedelta(seconds=-1) == date(2023, 12, 31), 'negative timedeltas have negative days'
+x = date(2024, 1, 31)
+x += timedelta(days=1)
+assert x == date(2024, 2, 1), 'in-place addition'
+
+# === date comparison and hashing ===
+assert date(2024, 1, 1) < date(2024, 1, 2), 'less than'
+assert date(2024, 12, 31) >= date(2024, 1, 1), 'greater or equal'
+assert date(2024, 1, 1) != date(2024, 1, 2), 'not equal'
+assert date(2024, 1, 1) != '2024-01-01', 'not equal to a str'
+assert sorted([date(2024, 3, 1), date(2023, 1, 1), date(2024, 1, 1)])[0] == date(2023, 1, 1), 'sorted'
+assert max(date(2024, 3, 1), date(2025, 1, 1)) == date(2025, 1, 1), 'max'
+assert {date(2024, 1, 1): 'a'}[date(2024, 1, 1)] == 'a', 'date as a dict key'
+assert len({date(2024, 1, 1), date(2024, 1, 1), date(2024, 1, 2)}) == 2, 'set of dates'
+assert bool(date.min), 'dates are truthy'
+
+# === time ===
+t = time(13, 5, 9, 250)
+assert (t.hour, t.minute, t.second, t.microsecond) == (13, 5, 9, 250), 'time attributes'
+assert t.tzinfo is None, 'naive time'
+assert repr(t) == 'datetime.time(13, 5, 9, 250)', 'time repr'
+assert repr(time(8, 0)) == 'datetime.time(8, 0)', 'time repr without seconds'
+assert str(t) == '13:05:09.000250', 'time str'
+assert time() == time.min, 'midnight'
+assert repr(time.max) == 'datetime.time(23, 59, 59, 999999)', 'time.max'
+assert time(7, 30).isoformat() == '07:30:00', 'time isoformat'
+assert time(7, 30, 1, 5).isoformat('milliseconds') == '07:30:01.000', 'time isoformat milliseconds'
+assert time(7, 30).isoformat(timespec='hours') == '07', 'time isoformat hours'
+assert t.replace(hour=1, microsecond=0) == time(1, 5, 9), 'time replace'
+assert time(9, 0) < time(10, 0), 'time ordering'
+assert t.strftime('%H:%M %p') == '13:05 PM', 'time strftime'
+assert time(12, 0).utcoffset() is None, 'naive utcoffset'
+
+tz = timezone(timedelta(hours=2))
+aware = time(12, 0, tzinfo=tz)
+assert aware.utcoffset() == timedelta(hours=2), 'aware utcoffset'
+assert aware.tzname() == 'UTC+02:00', 'aware tzname'
+assert str(aware) == '12:00:00+02:00', 'aware time str'
+assert aware == time(10, 0, tzinfo=timezone.utc), 'aware times compare in UTC'
+assert aware != time(12, 0), 'naive and aware times are not equal'
+assert hash(aware) == hash(time(11, 0, tzinfo=timezone(timedelta(hours=1)))), 'equal times hash equal'
@@ -0,0 +1,89 @@
+from datetime import UTC, date, datetime, time, timedelta, timezone
+
+# === construction and attributes ===
+dt = datetime(2024, 3, 10, 14, 30, 5, 123)
+assert (dt.year, dt.month, dt.day) == (2024, 3, 10), 'date attributes'
+assert (dt.hour, dt.minute, dt.second, dt.microsecond) == (14, 30, 5, 123), 'time attributes'
+assert dt.tzinfo is None, 'naive datetime'
+assert dt.fold == 0, 'fold'
+assert repr(dt) == 'datetime.datetime(2024, 3, 10, 14, 30, 5, 123)', 'datetime repr'
+assert repr(datetime(2024, 3, 10)) == 'datetime.datetime(2024, 3, 10, 0, 0)', 'midnight repr'
+assert str(dt) == '2024-03-10 14:30:05.000123', 'datetime str'
+assert datetime(2024, 1, 1, minute=5) == datetime(2024, 1, 1, 0, 5), 'keyword arguments'
+assert isinstance(dt, datetime), 'isinstance datetime'
+assert isinstance(dt, date), 'a datetime is a date'
+assert dt.date() == date(2024, 3, 10), 'date()'
+assert dt.time() == time(14, 30, 5, 123), 'time()'
+assert datetime.combine(date(2024, 1, 2), time(3, 4)) == datetime(2024, 1, 2, 3, 4), 'combine'
+assert dt.replace(day=1, hour=0) == datetime(2024, 3, 1, 0, 30, 5, 123), 'replace'
+assert dt.weekday() == 6, 'weekday'
+assert dt.toordinal() == date(2024, 3, 10).toordinal(), 'toordinal'
+assert datetime.min == datetime(1, 1, 1), 'datetime.min'
+assert datetime.max == datetime(9999, 12, 31, 23, 59, 59, 999999), 'datetime.max'
+assert dt.ctime() == 'Sun Mar 10 14:30:05 2024', 'ctime'
+
+# === isoformat ===
+assert dt.isoformat() == '2024-03-10T14:30:05.000123', 'isoformat'
+assert dt.isoformat(' ') == '2024-03-10 14:30:05.000123', 'isoformat separator'
+assert dt.isoformat(timespec='seconds') == '2024-03-10T14:30:05', 'isoformat seconds'
+assert dt.isoformat(sep='_', timespec='minutes') == '2024-03-10_14:30', 'isoformat minutes'
+assert datetime(2024, 1, 1, 9).isoformat() == '2024-01-01T09:00:00', 'isoformat without microseconds'
+
+# === arithmetic ===
+assert dt + timedelta(hours=10) == datetime(2024, 3, 11, 0, 30, 5, 123), 'add hours'
+assert dt - timedelta(days=10, microseconds=123) == datetime(2024, 2, 29, 14, 30, 5), 'subtract timedelta'
+assert datetime(2024, 1, 1) - datetime(2023, 12, 31, 12) == timedelta(hours=12), 'datetime - datetime'
+assert datetime(2023, 12, 31, 12) - datetime(2024, 1, 1) == timedelta(hours=-12), 'negative difference'
+assert timedelta(seconds=30) + datetime(2024, 1, 1) == datetime(2024, 1, 1, 0, 0, 30), 'timedelta + datetime'
+
+# === comparison ===
+assert datetime(2024, 1, 1) < datetime(2024, 1, 1, 0, 0, 0, 1), 'microsecond ordering'
+assert datetime(2024, 1, 1) != date(2024, 1, 1), 'datetime is not equal to date'
+assert sorted([datetime(2024, 5, 1), datetime(2024, 1, 1, 12), datetime(2024, 1, 1)]) == [
+    datetime(2024, 1, 1),
+    datetime(2024, 1, 1, 12),
+    datetime(2024, 5, 1),
+], 'sorted'
+
+# === aware datetimes ===
+cet = timezone(timedelta(hours=1), 'CET')
+aware = datetime(2024, 6, 1, 12, 0, tzinfo=cet)
+assert repr(aware) == (
+    "datetime.datetime(2024, 6, 1, 12, 0, tzinfo=datetime.timezone(datetime.timedelta(seconds=3600), 'CET'))"
+), 'aware repr'
+assert str(aware) == '2024-06-01 12:00:00+01:00', 'aware str'
+assert aware.tzname() == 'CET', 'tzname'
+assert aware.utcoffset() == timedelta(hours=1), 'utcoffset'
+assert aware.dst() is None, 'dst'
+assert aware.timetz() == time(12, 0, tzinfo=cet), 'timetz'
+assert aware.time().tzinfo is None, 'time() is naive'
+utc = aware.astimezone(timezone.utc)
+assert utc == datetime(2024, 6, 1, 11, 0, tzinfo=UTC), 'astimezone'
+assert utc.hour == 11, 'astimezone hour'
+assert aware == utc, 'the same instant in different timezones is equal'
+assert hash(aware) == hash(utc), 'equal instants hash equal'
+assert aware - utc == timedelta(0), 'difference of aware datetimes'
+assert aware + timedelta(hours=1) == datetime(2024, 6, 1, 13, 0, tzinfo=cet), 'aware arithmetic keeps tzinfo'
+assert (aware + timedelta(hours=1)).tzinfo == cet, 'tzinfo after arithmetic'
+assert aware.timestamp() == 1717239600.0, 'timestamp'
+assert datetime.fromtimestamp(1717239600, timezone.utc) == utc, 'fromtimestamp'
+assert datetime.fromtimestamp(0.5, tz=UTC).microsecond == 500000, 'fromtimestamp with a fraction'
+assert aware != datetime(2024, 6, 1, 12, 0), 'aware and naive datetimes are not equal'
+assert aware.isoformat() == '2024-06-01T12:00:00+01:00', 'aware isoformat'
+assert aware.replace(tzinfo=None) == datetime(2024, 6, 1, 12, 0), 'replace tzinfo'
+
+# === fromisoformat ===
+assert date.fromisoformat('2024-01-15') == date(2024, 1, 15), 'date.fromisoformat'
+assert date.fromisoformat('20240115') == date(2024, 1, 15), 'basic format'
+assert date.fromisoformat('2024-W03-1') == date(2024, 1, 15), 'iso week date'
+assert time.fromisoformat('10:20:30.5') == time(10, 20, 30, 500000), 'time.fromisoformat'
+assert time.fromisoformat('10:20+05:30').utcoffset() == timedelta(hours=5, minutes=30), 'time with offset'
+assert datetime.fromisoformat('2024-01-15T10:20:30') == datetime(2024, 1, 15, 10, 20, 30), 'datetime.fromisoformat'
+assert datetime.fromisoformat('2024-01-15 10:20') == datetime(2024, 1, 15, 10, 20), 'space separator'
+assert datetime.fromisoformat('2024-01-15') == datetime(2024, 1, 15), 'date only'
+assert datetime.fromisoformat('2024-01-15T10:20:30Z').tzinfo == timezone.utc, 'Z suffix'
+parsed = datetime.fromisoformat('2024-01-15T10:20:30.123456-03:00')
+assert parsed == datetime(2024, 1, 15, 13, 20, 30, 123456, tzinfo=UTC), 'negative offset'
+assert parsed.utcoffset() == timedelta(hours=-3), 'parsed offset'
+assert datetime.fromisoformat(dt.isoformat()) == dt, 'isoformat round trip'
+assert datetime.fromisoformat(aware.isoformat()) == aware, 'aware round trip'
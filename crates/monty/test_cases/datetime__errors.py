@@ -0,0 +1,62 @@
+from datetime import date, datetime, time, timedelta, timezone
+
+
+def error(f, *args, **kwargs):
+    try:
+        f(*args, **kwargs)
+    except Exception as e:
+        return f'{type(e).__name__}: {e}'
+    assert False, f'{f} should have raised'
+
+
+# === out of range values ===
+# (only the start of these messages is checked, as CPython versions add more detail)
+assert error(date, 0, 1, 1).startswith('ValueError: year '), 'year'
+assert error(date, 2024, 13, 1).startswith('ValueError: month must be in 1..12'), 'month'
+assert error(date, 2023, 2, 29).startswith('ValueError: day '), 'day'
+assert error(time, 24).startswith('ValueError: hour must be in 0..23'), 'hour'
+assert error(datetime, 2024, 1, 1, 0, 60).startswith('ValueError: minute must be in 0..59'), 'minute'
+assert error(time, 0, 0, 0, 1000000).startswith('ValueError: microsecond must be in 0..999999'), 'microsecond'
+assert error(lambda: date.max + timedelta(days=1)) == 'OverflowError: date value out of range', 'date overflow'
+assert error(lambda: datetime.min - timedelta(microseconds=1)) == 'OverflowError: date value out of range', (
+    'datetime overflow'
+)
+assert error(date.fromordinal, 0) == 'ValueError: ordinal must be >= 1', 'fromordinal'
+assert error(timezone, timedelta(hours=24)).startswith('ValueError: offset must be a timedelta strictly between'), (
+    'timezone offset'
+)
+
+# === type errors ===
+assert error(date, '2024', 1, 1) == "TypeError: 'str' object cannot be interpreted as an integer", 'str year'
+assert error(lambda: date(2024, 1, 1) + 1) == (
+    "TypeError: unsupported operand type(s) for +: 'datetime.date' and 'int'"
+), 'date + int'
+assert error(lambda: timedelta(1) + 1) == (
+    "TypeError: unsupported operand type(s) for +: 'datetime.timedelta' and 'int'"
+), 'timedelta + int'
+assert error(lambda: date(2024, 1, 1) < 5) == (
+    "TypeError: '<' not supported between instances of 'datetime.date' and 'int'"
+), 'date < int'
+assert error(lambda: datetime(2024, 1, 1) < datetime(2024, 1, 1, tzinfo=timezone.utc)) == (
+    "TypeError: can't compare offset-naive and offset-aware datetimes"
+), 'naive < aware'
+assert error(lambda: datetime(2024, 1, 1) - datetime(2024, 1, 1, tzinfo=timezone.utc)) == (
+    "TypeError: can't subtract offset-naive and offset-aware datetimes"
+), 'naive - aware'
+assert error(datetime, 2024, 1, 1, tzinfo='UTC') == (
+    "TypeError: tzinfo argument must be None or of a tzinfo subclass, not type 'str'"
+), 'bad tzinfo'
+assert error(lambda: timedelta(1) / 0).startswith('ZeroDivisionError: '), 'divide by zero'
+
+# === parsing ===
+assert error(date.fromisoformat, '2024-1-1') == "ValueError: Invalid isoformat string: '2024-1-1'", 'short month'
+assert error(datetime.fromisoformat, 'not a date') == "ValueError: Invalid isoformat string: 'not a date'", 'garbage'
+assert error(time.fromisoformat, '10:2') == "ValueError: Invalid isoformat string: '10:2'", 'one digit minute'
+assert error(datetime.strptime, '2024', '%Y-%m') == "ValueError: time data '2024' does not match format '%Y-%m'", (
+    'strptime mismatch'
+)
+assert error(datetime.strptime, '2024-01-01x', '%Y-%m-%d') == 'ValueError: unconverted data remains: x', (
+    'strptime remainder'
+)
+assert error(datetime.strptime, '2023-02-29', '%Y-%m-%d').startswith('ValueError: day '), 'strptime invalid day'
+assert error(datetime.fromisoformat, 5) == 'TypeError: fromisoformat: argument must be str', 'fromisoformat int'
@@ -0,0 +1,53 @@
+from datetime import date, datetime, time, timedelta, timezone
+
+dt = datetime(2024, 3, 5, 7, 8, 9, 12345)
+
+# === strftime ===
+assert dt.strftime('%Y-%m-%d %H:%M:%S') == '2024-03-05 07:08:09', 'basic directives'
+assert dt.strftime('%y %j %f') == '24 065 012345', 'short year, day of year and microseconds'
+assert dt.strftime('%a %A %b %B') == 'Tue Tuesday Mar March', 'names'
+assert dt.strftime('%I:%M %p') == '07:08 AM', '12 hour clock'
+assert datetime(2024, 3, 5, 0, 30).strftime('%I %p') == '12 AM', 'midnight on a 12 hour clock'
+assert dt.strftime('%w %u %U %W %V %G') == '2 2 09 10 10 2024', 'week directives'
+assert dt.strftime('%%Y %e') == '%Y  5', 'escaped percent and space padded day'
+assert dt.strftime('%c') == 'Tue Mar  5 07:08:09 2024', 'locale datetime'
+assert dt.strftime('%x %X') == '03/05/24 07:08:09', 'locale date and time'
+assert dt.strftime('%z%Z') == '', 'naive offset and name are empty'
+aware = dt.replace(tzinfo=timezone(timedelta(hours=-5, minutes=-30)))
+assert aware.strftime('%z %Z') == '-0530 UTC-05:30', 'aware offset and name'
+assert date(2024, 12, 25).strftime('%A %d %B') == 'Wednesday 25 December', 'date strftime'
+assert date(2024, 12, 25).strftime('%H:%M') == '00:00', 'date has a midnight time'
+assert time(18, 45).strftime('%H:%M:%S') == '18:45:00', 'time strftime'
+assert dt.strftime('') == '', 'empty format'
+assert dt.strftime('no directives') == 'no directives', 'format without directives'
+
+# === format specs ===
+assert f'{dt:%Y/%m/%d}' == '2024/03/05', 'f-string format spec'
+assert f'{date(2024, 1, 2):%d.%m.%Y}' == '02.01.2024', 'date f-string'
+assert f'{time(9, 5):%H h %M}' == '09 h 05', 'time f-string'
+fmt = '%H:%M'
+assert f'{dt:{fmt}}' == '07:08', 'dynamic format spec'
+assert '{:%Y-%m}'.format(dt) == '2024-03', 'str.format'
+assert '{when:%B %-d}'.replace('-', '').format(when=dt) == 'March 05', 'str.format keyword'
+assert f'{dt}' == '2024-03-05 07:08:09.012345', 'f-string without a spec uses str'
+assert f'{dt!r}' == 'datetime.datetime(2024, 3, 5, 7, 8, 9, 12345)', 'f-string repr'
+
+# === strptime ===
+parsed = datetime.strptime('2024-03-05 07:08:09', '%Y-%m-%d %H:%M:%S')
+assert parsed == datetime(2024, 3, 5, 7, 8, 9), 'strptime'
+assert datetime.strptime('5 March 2024', '%d %B %Y') == datetime(2024, 3, 5), 'month name'
+assert datetime.strptime('tue, 05 mar 24', '%a, %d %b %y') == datetime(2024, 3, 5), 'case insensitive names'
+assert datetime.strptime('07:08 PM', '%I:%M %p') == datetime(1900, 1, 1, 19, 8), 'default date and pm'
+assert datetime.strptime('12 AM', '%I %p').hour == 0, '12 am is midnight'
+assert datetime.strptime('2024065', '%Y%j') == datetime(2024, 3, 5), 'day of year'
+assert datetime.strptime('20240305T070809', '%Y%m%dT%H%M%S') == datetime(2024, 3, 5, 7, 8, 9), 'no separators'
+assert datetime.strptime('1.5', '%S.%f').microsecond == 500000, 'fraction'
+assert datetime.strptime('2024 10 2', '%Y %W %w') == datetime(2024, 3, 5), 'week number and weekday'
+assert datetime.strptime('10%', '%H%%') == datetime(1900, 1, 1, 10), 'escaped percent'
+assert datetime.strptime('  2024-3-5', '  %Y-%m-%d') == datetime(2024, 3, 5), 'unpadded numbers'
+with_offset = datetime.strptime('2024-03-05 07:08 +0130', '%Y-%m-%d %H:%M %z')
+assert with_offset.utcoffset() == timedelta(hours=1, minutes=30), 'offset'
+assert with_offset == datetime(2024, 3, 5, 5, 38, tzinfo=timezone.utc), 'aware result'
+assert datetime.strptime('07:08 Z', '%H:%M %z').tzinfo == timezone.utc, 'Z offset'
+assert datetime.strptime('-05:00', '%z').utcoffset() == timedelta(hours=-5), 'offset with a colon'
+assert datetime.strptime(aware.strftime('%Y-%m-%d %H:%M:%S.%f%z'), '%Y-%m-%d %H:%M:%S.%f%z') == aware, 'round trip'
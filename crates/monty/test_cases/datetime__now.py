@@ -0,0 +1,30 @@
+# call-external
+# datetime.now() and date.today() ask the host for the current time via an OS call,
+# so only properties which hold for any clock are checked here
+from datetime import date, datetime, timedelta, timezone
+
+# === datetime.now() ===
+now = datetime.now()
+assert type(now) is datetime, 'now() returns a datetime'
+assert now.tzinfo is None, 'now() without a timezone is naive'
+assert now.year >= 2024, 'now() is recent'
+
+utc_now = datetime.now(timezone.utc)
+assert utc_now.tzinfo == timezone.utc, 'now(tz) uses the timezone'
+assert utc_now.utcoffset() == timedelta(0), 'utc offset'
+
+tz = timezone(timedelta(hours=-5))
+local = datetime.now(tz=tz)
+assert local.tzinfo == tz, 'now(tz=...) keyword'
+assert local.utcoffset() == timedelta(hours=-5), 'now(tz) offset'
+assert abs(local - utc_now) < timedelta(minutes=1), 'the same instant in different timezones'
+
+later = datetime.now(timezone.utc)
+assert later >= utc_now, 'the clock does not go backwards'
+
+# === date.today() ===
+today = date.today()
+assert type(today) is date, 'today() returns a date'
+assert today.year >= 2024, 'today() is recent'
+assert isinstance(datetime.today(), datetime), 'datetime.today() returns a datetime'
+assert datetime.today().tzinfo is None, 'datetime.today() is naive'
@@ -0,0 +1,76 @@
+from datetime import datetime, timedelta, timezone
+
+# === construction and normalization ===
+td = timedelta(days=1, hours=2, minutes=3, seconds=4, milliseconds=5, microseconds=6)
+assert (td.days, td.seconds, td.microseconds) == (1, 7384, 5006), 'normalized fields'
+assert timedelta(1, 2, 3) == timedelta(days=1, seconds=2, microseconds=3), 'positional arguments'
+assert timedelta(weeks=2) == timedelta(days=14), 'weeks'
+neg = timedelta(seconds=-1)
+assert (neg.days, neg.seconds, neg.microseconds) == (-1, 86399, 0), 'negative normalization'
+assert timedelta(hours=1.5) == timedelta(minutes=90), 'float hours'
+assert timedelta(microseconds=0.5) == timedelta(0), 'half a microsecond rounds to even'
+assert timedelta(microseconds=1.5) == timedelta(microseconds=2), 'one and a half microseconds rounds to even'
+assert timedelta(days=0.1).microseconds == 0, 'exact float days'
+assert timedelta() == timedelta(0), 'empty timedelta'
+assert timedelta.max == timedelta(days=999999999, seconds=86399, microseconds=999999), 'timedelta.max'
+assert timedelta.min == timedelta(days=-999999999), 'timedelta.min'
+assert timedelta.resolution == timedelta(microseconds=1), 'timedelta.resolution'
+
+# === repr and str ===
+assert repr(td) == 'datetime.timedelta(days=1, seconds=7384, microseconds=5006)', 'repr'
+assert repr(timedelta(0)) == 'datetime.timedelta(0)', 'zero repr'
+assert repr(timedelta(minutes=-1)) == 'datetime.timedelta(days=-1, seconds=86340)', 'negative repr'
+assert str(td) == '1 day, 2:03:04.005006', 'str'
+assert str(timedelta(hours=5)) == '5:00:00', 'str without days'
+assert str(timedelta(days=-2, hours=1)) == '-2 days, 1:00:00', 'negative str'
+assert str(timedelta(days=3)) == '3 days, 0:00:00', 'plural days'
+
+# === arithmetic ===
+hour = timedelta(hours=1)
+assert hour + hour == timedelta(hours=2), 'add'
+assert hour - timedelta(hours=3) == timedelta(hours=-2), 'subtract'
+assert -hour == timedelta(hours=-1), 'negate'
+assert +hour == hour, 'unary plus'
+assert abs(timedelta(hours=-3)) == timedelta(hours=3), 'abs'
+assert hour * 3 == timedelta(hours=3), 'multiply by int'
+assert 2 * hour == timedelta(hours=2), 'int * timedelta'
+assert hour * 0.5 == timedelta(minutes=30), 'multiply by float'
+assert hour / 4 == timedelta(minutes=15), 'divide by int'
+assert hour / 0.5 == timedelta(hours=2), 'divide by float'
+assert hour / timedelta(minutes=20) == 3.0, 'divide by timedelta'
+assert hour // timedelta(minutes=25) == 2, 'floor divide by timedelta'
+assert hour // 7 == timedelta(microseconds=514285714), 'floor divide by int'
+assert hour % timedelta(minutes=25) == timedelta(minutes=10), 'modulo'
+assert timedelta(minutes=-5) % hour == timedelta(minutes=55), 'modulo of a negative'
+assert timedelta(seconds=1) / 3 == timedelta(microseconds=333333), 'division rounds'
+assert hour.total_seconds() == 3600.0, 'total_seconds'
+assert timedelta(microseconds=-1).total_seconds() == -1e-06, 'negative total_seconds'
+assert sum([hour, hour, timedelta(minutes=30)], timedelta()) == timedelta(hours=2.5), 'sum with a timedelta start'
+total = timedelta()
+for minutes in [10, 20, 30]:
+    total += timedelta(minutes=minutes)
+assert total == hour, 'in-place addition'
+
+# === comparison and truthiness ===
+assert timedelta(hours=-1) < timedelta(0) < hour, 'ordering'
+assert max([hour, timedelta(days=1), timedelta(0)]) == timedelta(days=1), 'max'
+assert not timedelta(0), 'zero is falsy'
+assert timedelta(microseconds=1), 'non-zero is truthy'
+assert hash(timedelta(hours=24)) == hash(timedelta(days=1)), 'equal timedeltas hash equal'
+assert {timedelta(days=1): 'day'}[timedelta(hours=24)] == 'day', 'timedelta as a dict key'
+
+# === timezone ===
+utc = timezone.utc
+assert repr(utc) == 'datetime.timezone.utc', 'utc repr'
+assert str(utc) == 'UTC', 'utc str'
+assert timezone(timedelta(0)) == utc, 'zero offset is utc'
+ist = timezone(timedelta(hours=5, minutes=30))
+assert repr(ist) == 'datetime.timezone(datetime.timedelta(seconds=19800))', 'timezone repr'
+assert str(ist) == 'UTC+05:30', 'timezone str'
+assert str(timezone(-timedelta(hours=8))) == 'UTC-08:00', 'negative timezone str'
+assert timezone(timedelta(hours=1), 'CET').tzname(None) == 'CET', 'named timezone'
+assert ist.utcoffset(None) == timedelta(hours=5, minutes=30), 'timezone utcoffset'
+assert ist.dst(None) is None, 'timezone dst'
+assert timezone(timedelta(hours=1), 'A') == timezone(timedelta(hours=1), 'B'), 'timezones compare by offset'
+assert len({timezone(timedelta(hours=1)), timezone(timedelta(hours=1), 'X')}) == 1, 'timezones hash by offset'
+assert ist.utcoffset(datetime(2024, 1, 1)) == timedelta(seconds=19800), 'utcoffset with a datetime'
@@ -0,0 +1,93 @@
+import decimal
+from decimal import Decimal
+
+# === construction ===
+assert Decimal() == 0, 'no argument'
+assert str(Decimal('1.50')) == '1.50', 'str keeps trailing zeros'
+assert repr(Decimal('1.50')) == "Decimal('1.50')", 'repr'
+assert str(Decimal(' -7 ')) == '-7', 'whitespace and sign'
+assert str(Decimal('1e3')) == '1E+3', 'exponent'
+assert str(Decimal('0.000001')) == '0.000001', 'small'
+assert str(Decimal('0.0000001')) == '1E-7', 'scientific notation'
+assert str(Decimal(42)) == '42', 'from int'
+assert str(Decimal(10**30)) == '1000000000000000000000000000000', 'from big int'
+assert str(Decimal(0.5)) == '0.5', 'from float'
+assert str(Decimal(0.1)) == '0.1000000000000000055511151231257827021181583404541015625', 'floats are exact'
+assert str(Decimal((1, (3, 1, 4), -2))) == '-3.14', 'from tuple'
+assert str(Decimal('inf')) == 'Infinity', 'infinity'
+assert str(Decimal('-Infinity')) == '-Infinity', 'negative infinity'
+assert str(Decimal('nan')) == 'NaN', 'nan'
+assert str(Decimal(Decimal('2.5'))) == '2.5', 'from decimal'
+assert Decimal.from_float(0.25) == Decimal('0.25'), 'from_float'
+
+# === arithmetic ===
+assert str(Decimal('0.1') + Decimal('0.2')) == '0.3', 'exact addition'
+assert str(Decimal('1.10') + 1) == '2.10', 'mixed with int'
+assert str(2 - Decimal('0.5')) == '1.5', 'reflected subtraction'
+assert str(Decimal('1.5') * Decimal('2.0')) == '3.00', 'multiplication keeps exponents'
+assert str(Decimal(1) / Decimal(3)) == '0.3333333333333333333333333333', 'division rounds to 28 digits'
+assert str(Decimal(2) / Decimal(3)) == '0.6666666666666666666666666667', 'division rounds half even'
+assert str(Decimal(6) / Decimal(3)) == '2', 'exact division'
+assert str(Decimal('7.5') // 2) == '3', 'floor division truncates'
+assert str(Decimal(-7) // 2) == '-3', 'floor division of negatives truncates'
+assert str(Decimal(-7) % 2) == '-1', 'modulo takes the sign of the dividend'
+assert divmod(Decimal(7), Decimal(2)) == (Decimal(3), Decimal(1)), 'divmod'
+assert str(Decimal(2) ** 10) == '1024', 'power'
+assert str(Decimal('1.5') ** 2) == '2.25', 'decimal power'
+assert str(Decimal(2) ** -1) == '0.5', 'negative power'
+assert str(pow(Decimal(3), 2)) == '9', 'pow()'
+assert str(Decimal(1) ** Decimal('1e999999999')) == '1', 'huge power of one'
+assert str(Decimal('1.0') ** Decimal('1e999999999')) == '1.000000000000000000000000000', 'huge power keeps precision'
+assert str(Decimal('0.5') ** Decimal('1e999999999')) == '0E-1000026', 'huge power underflows'
+assert str(Decimal(-1) ** Decimal('-1e999999999')) == '1', 'huge negative power'
+assert str(-Decimal('1.5')) == '-1.5', 'negation'
+assert str(+Decimal('-0')) == '0', 'plus of negative zero'
+assert str(abs(Decimal('-2.5'))) == '2.5', 'abs'
+assert str(Decimal('Infinity') + 1) == 'Infinity', 'infinity arithmetic'
+assert str(sum([Decimal('0.1')] * 10)) == '1.0', 'sum'
+total = Decimal(0)
+total += Decimal('0.01')
+assert str(total) == '0.01', 'augmented assignment'
+
+# === comparison and hashing ===
+assert Decimal('1.0') == Decimal('1.00'), 'trailing zeros compare equal'
+assert Decimal('1.0') == 1, 'equal to int'
+assert Decimal('0.5') == 0.5, 'equal to float'
+assert Decimal('0.1') != 0.1, 'not equal to an inexact float'
+assert Decimal('1.5') < 2 and Decimal('2.5') > 2.4, 'ordering with numbers'
+assert sorted([Decimal('2.5'), Decimal(1), Decimal('-3')]) == [Decimal(-3), Decimal(1), Decimal('2.5')], 'sorting'
+assert max(Decimal('0.1'), Decimal('0.2')) == Decimal('0.2'), 'max'
+assert Decimal('NaN') != Decimal('NaN'), 'NaN is not equal to itself'
+assert hash(Decimal('1.0')) == hash(1), 'hash matches int'
+assert hash(Decimal('0.5')) == hash(0.5), 'hash matches float'
+assert hash(Decimal(10**20)) == hash(10**20), 'hash matches big int'
+assert len({Decimal('1.0'), Decimal(1), 1}) == 1, 'set of equal values'
+assert {Decimal('2'): 'two'}[2] == 'two', 'dict key'
+assert bool(Decimal('0.00')) is False and bool(Decimal('0.01')) is True, 'truthiness'
+
+# === conversions ===
+assert int(Decimal('-3.7')) == -3, 'int truncates'
+assert float(Decimal('0.25')) == 0.25, 'float'
+assert round(Decimal('2.5')) == 2, 'round half even'
+assert round(Decimal('3.5')) == 4, 'round up to even'
+assert str(round(Decimal('1.2345'), 2)) == '1.23', 'round to digits'
+assert str(round(Decimal('123'), -1)) == '1.2E+2', 'round to tens'
+assert Decimal('1.5').as_integer_ratio() == (3, 2), 'as_integer_ratio'
+assert Decimal('-3.25').is_signed(), 'is_signed'
+assert Decimal('NaN').is_nan() and Decimal('-inf').is_infinite(), 'is_nan and is_infinite'
+assert Decimal('0.0').is_zero() and Decimal(1).is_finite(), 'is_zero and is_finite'
+assert Decimal('123.45').adjusted() == 2, 'adjusted'
+assert str(Decimal('-1.5').copy_abs()) == '1.5', 'copy_abs'
+assert str(Decimal('1.5').copy_negate()) == '-1.5', 'copy_negate'
+assert str(Decimal('1.500').normalize()) == '1.5', 'normalize'
+assert str(Decimal('100').normalize()) == '1E+2', 'normalize integers'
+assert str(Decimal('2.5').to_integral_value()) == '2', 'to_integral_value'
+assert str(Decimal('2.5').to_integral_value(rounding=decimal.ROUND_HALF_UP)) == '3', 'to_integral_value rounding'
+
+# === formatting ===
+assert f'{Decimal("1.005"):.2f}' == '1.00', 'fixed point rounds half even'
+assert f'{Decimal("1234.5"):,.2f}' == '1,234.50', 'thousands separator'
+assert f'{Decimal("-2.5"):>8}' == '    -2.5', 'alignment'
+assert f'{Decimal("12345"):.2e}' == '1.23e+4', 'exponent'
+assert f'{Decimal("0.25"):%}' == '25%', 'percent'
+assert '%.2f' % Decimal('1.005') == '1.00', 'percent formatting'
@@ -0,0 +1,65 @@
+import decimal
+from decimal import Context, Decimal, getcontext, localcontext, setcontext
+
+# === the default context ===
+ctx = getcontext()
+assert ctx.prec == 28, 'default precision'
+assert ctx.rounding == decimal.ROUND_HALF_EVEN, 'default rounding'
+assert decimal.ROUND_HALF_UP == 'ROUND_HALF_UP', 'rounding modes are strings'
+assert decimal.MAX_PREC > 10**8, 'MAX_PREC'
+
+# === changing the current context ===
+with localcontext() as ctx:
+    ctx.prec = 5
+    assert str(Decimal(1) / Decimal(7)) == '0.14286', 'lower precision'
+    assert str(Decimal('1.234567') + 0) == '1.2346', 'precision applies to results'
+    assert str(Decimal('1.234567')) == '1.234567', 'but not to construction'
+    ctx.rounding = decimal.ROUND_DOWN
+    assert str(Decimal(2) / Decimal(3)) == '0.66666', 'rounding mode'
+assert str(Decimal(1) / Decimal(7)) == '0.1428571428571428571428571429', 'restored after the with block'
+
+with localcontext(prec=3, rounding=decimal.ROUND_UP):
+    assert str(Decimal(1) / Decimal(3)) == '0.334', 'localcontext keywords'
+    assert getcontext().prec == 3, 'getcontext inside the block'
+assert getcontext().prec == 28, 'getcontext after the block'
+
+with localcontext(Context(prec=2)):
+    assert str(Decimal(100) * 3) == '3.0E+2', 'localcontext with a context'
+
+# setcontext copies the settings of the context
+mine = Context(prec=4)
+setcontext(mine)
+assert str(Decimal(2) / Decimal(3)) == '0.6667', 'setcontext'
+setcontext(Context())
+assert getcontext().prec == 28, 'reset with a default context'
+
+# the current context object follows the settings
+getcontext().prec = 6
+assert str(Decimal(1) / Decimal(3)) == '0.333333', 'setting prec on getcontext()'
+getcontext().prec = 28
+
+# === rounding modes ===
+cases = [
+    (decimal.ROUND_CEILING, '1.3', '-1.2'),
+    (decimal.ROUND_FLOOR, '1.2', '-1.3'),
+    (decimal.ROUND_UP, '1.3', '-1.3'),
+    (decimal.ROUND_DOWN, '1.2', '-1.2'),
+    (decimal.ROUND_HALF_UP, '1.3', '-1.3'),
+    (decimal.ROUND_HALF_DOWN, '1.2', '-1.2'),
+    (decimal.ROUND_HALF_EVEN, '1.2', '-1.2'),
+    (decimal.ROUND_05UP, '1.2', '-1.2'),
+]
+for rounding, positive, negative in cases:
+    assert str(Decimal('1.25').quantize(Decimal('0.1'), rounding=rounding)) == positive, rounding
+    assert str(Decimal('-1.25').quantize(Decimal('0.1'), rounding=rounding)) == negative, rounding
+assert str(Decimal('1.05').quantize(Decimal('0.1'), rounding=decimal.ROUND_05UP)) == '1.1', 'ROUND_05UP away from 0'
+
+# === quantize ===
+assert str(Decimal('7.325').quantize(Decimal('.01'))) == '7.32', 'quantize to cents'
+assert str(Decimal('7.325').quantize(Decimal('.01'), decimal.ROUND_HALF_UP)) == '7.33', 'positional rounding'
+assert str(Decimal('7').quantize(Decimal('1.000'))) == '7.000', 'quantize pads with zeros'
+assert str(Decimal('123.456').quantize(Decimal('1E+1'))) == '1.2E+2', 'quantize to tens'
+assert str(Decimal('2.5').quantize(1)) == '2', 'quantize to an int'
+assert str(Decimal('1.5').quantize(Decimal('1'), context=Context(rounding=decimal.ROUND_UP))) == '2', (
+    'quantize with a context'
+)
@@ -0,0 +1,53 @@
+import decimal
+from decimal import Context, Decimal, localcontext
+
+
+def error(f, *args, **kwargs):
+    try:
+        f(*args, **kwargs)
+    except Exception as e:
+        return type(e).__name__
+
+
+# === signals ===
+assert error(lambda: Decimal(1) / 0) == 'DivisionByZero', 'division by zero'
+assert error(lambda: Decimal(1) // 0) == 'DivisionByZero', 'floor division by zero'
+assert error(lambda: Decimal(0) / 0) == 'InvalidOperation', 'zero by zero'
+assert error(lambda: Decimal(1) % 0) == 'InvalidOperation', 'modulo by zero'
+assert error(Decimal, 'abc') == 'InvalidOperation', 'invalid string'
+assert error(lambda: Decimal('inf') - Decimal('inf')) == 'InvalidOperation', 'inf - inf'
+assert error(lambda: Decimal('NaN') < 1) == 'InvalidOperation', 'ordering NaN'
+assert error(lambda: Decimal('1e999999') * Decimal('1e999999')) == 'Overflow', 'overflow'
+assert error(lambda: Decimal(2) ** Decimal('1e999999999')) == 'Overflow', 'huge power overflows'
+assert error(lambda: Decimal('1.5').quantize(Decimal('1e-30'))) == 'InvalidOperation', 'quantize beyond precision'
+
+# the signals are arithmetic errors
+try:
+    Decimal(1) / 0
+except ZeroDivisionError:
+    pass
+try:
+    Decimal('x')
+except decimal.DecimalException:
+    pass
+try:
+    Decimal(1) / 0
+except ArithmeticError as e:
+    assert isinstance(e, decimal.DivisionByZero), 'isinstance of the signal'
+assert issubclass(decimal.InvalidOperation, ArithmeticError), 'InvalidOperation is an ArithmeticError'
+
+# === conversion errors ===
+assert error(int, Decimal('NaN')) == 'ValueError', 'int of NaN'
+assert error(int, Decimal('inf')) == 'OverflowError', 'int of infinity'
+assert error(round, Decimal('NaN')) == 'ValueError', 'round of NaN'
+
+# === type errors ===
+assert error(Decimal, [1, 2]) == 'ValueError', 'list of the wrong length'
+assert error(Decimal, None) == 'TypeError', 'None'
+assert error(lambda: Decimal(1) + 0.5) == 'TypeError', 'no mixing with floats'
+assert error(lambda: Decimal(1) < 'a') == 'TypeError', 'ordering with str'
+assert error(lambda: Decimal(1) + 'a') == 'TypeError', 'adding str'
+assert error(lambda: Context(prec=0)) == 'ValueError', 'zero precision'
+assert error(lambda: Context(rounding='sideways')) == 'TypeError', 'unknown rounding mode'
+assert error(lambda: localcontext(prec='3')) == 'TypeError', 'str precision'
+assert error(decimal.setcontext, 1) == 'TypeError', 'setcontext of a non-context'
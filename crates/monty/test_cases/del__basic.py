@@ -0,0 +1,250 @@
+# === del name ===
+x = 1
+del x
+try:
+    x
+    assert False, 'x should be deleted'
+except NameError as e:
+    assert str(e) == "name 'x' is not defined", 'deleted global raises NameError'
+
+x = 2
+assert x == 2, 'name can be rebound after del'
+
+a, b = 1, 2
+del a, b
+try:
+    a
+    assert False, 'a should be deleted'
+except NameError:
+    pass
+
+# === del unbound name ===
+try:
+    del undefined_name
+    assert False, 'del of unknown name should fail'
+except NameError as e:
+    assert str(e) == "name 'undefined_name' is not defined", 'del unknown name message'
+
+
+def delete_twice():
+    value = 1
+    del value
+    del value
+
+
+try:
+    delete_twice()
+    assert False, 'second del should fail'
+except UnboundLocalError as e:
+    assert str(e) == "cannot access local variable 'value' where it is not associated with a value", (
+        'del unbound local message'
+    )
+
+# === del local, global and nonlocal ===
+
+
+def local_del():
+    y = [1, 2]
+    del y
+    try:
+        return y
+    except UnboundLocalError:
+        return 'deleted'
+
+
+assert local_del() == 'deleted', 'local variable is deleted'
+
+g = 'global'
+
+
+def global_del():
+    global g
+    del g
+
+
+global_del()
+try:
+    g
+    assert False, 'g should be deleted'
+except NameError:
+    pass
+
+
+def outer():
+    captured = 'value'
+
+    def inner():
+        nonlocal captured
+        del captured
+
+    inner()
+    try:
+        return captured
+    except NameError:
+        return 'deleted'
+
+
+assert outer() == 'deleted', 'nonlocal variable is deleted'
+
+# === del dict item ===
+d = {'a': 1, 'b': 2, 'c': 3}
+del d['b']
+assert d == {'a': 1, 'c': 3}, 'dict key deleted'
+assert list(d) == ['a', 'c'], 'order kept after del'
+del d['a'], d['c']
+assert d == {}, 'several dict keys deleted'
+
+try:
+    del d['missing']
+    assert False, 'missing key should fail'
+except KeyError as e:
+    assert e.args == ('missing',), 'KeyError carries the key'
+
+try:
+    del d[[]]
+    assert False, 'unhashable key should fail'
+except TypeError:
+    pass
+
+payload = {'id': 1, 'secret': 'x', 'nested': {'token': 't', 'keep': True}}
+del payload['secret']
+del payload['nested']['token']
+assert payload == {'id': 1, 'nested': {'keep': True}}, 'nested dict cleanup'
+
+# === del list item ===
+lst = [0, 1, 2, 3, 4]
+del lst[0]
+assert lst == [1, 2, 3, 4], 'del first item'
+del lst[-1]
+assert lst == [1, 2, 3], 'del negative index'
+del lst[True]
+assert lst == [1, 3], 'del bool index'
+
+try:
+    del lst[10]
+    assert False, 'index out of range should fail'
+except IndexError as e:
+    assert str(e) == 'list assignment index out of range', 'del list index error message'
+
+try:
+    del lst['a']
+    assert False, 'str index should fail'
+except TypeError as e:
+    assert str(e) == 'list indices must be integers or slices, not str', 'del list index type message'
+
+# === del list slice ===
+lst = list(range(10))
+del lst[2:5]
+assert lst == [0, 1, 5, 6, 7, 8, 9], 'del simple slice'
+
+lst = list(range(10))
+del lst[::2]
+assert lst == [1, 3, 5, 7, 9], 'del stepped slice'
+
+lst = list(range(10))
+del lst[::-3]
+assert lst == [1, 2, 4, 5, 7, 8], 'del negative stepped slice'
+
+lst = list(range(10))
+del lst[8:2:-2]
+assert lst == [0, 1, 2, 3, 5, 7, 9], 'del negative stepped slice with bounds'
+
+lst = list(range(5))
+del lst[:]
+assert lst == [], 'del whole slice'
+
+lst = list(range(5))
+del lst[10:]
+assert lst == [0, 1, 2, 3, 4], 'del empty slice'
+
+try:
+    del lst[::0]
+    assert False, 'zero step should fail'
+except ValueError as e:
+    assert str(e) == 'slice step cannot be zero', 'zero step message'
+
+# === del unsupported item ===
+try:
+    del (1, 2)[0]
+    assert False, 'tuple del should fail'
+except TypeError as e:
+    assert str(e) == "'tuple' object doesn't support item deletion", 'tuple del message'
+
+try:
+    del 'abc'[0]
+    assert False, 'str del should fail'
+except TypeError as e:
+    assert str(e) == "'str' object doesn't support item deletion", 'str del message'
+
+number = 5
+try:
+    del number[0]
+    assert False, 'int del should fail'
+except TypeError as e:
+    assert str(e) == "'int' object does not support item deletion", 'int del message'
+
+# === del attribute ===
+
+
+class Point:
+    kind = 'point'
+
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+
+p = Point(1, 2)
+del p.x
+try:
+    p.x
+    assert False, 'instance attribute should be deleted'
+except AttributeError:
+    pass
+assert p.y == 2, 'other attribute kept'
+
+try:
+    del p.x
+    assert False, 'deleting twice should fail'
+except AttributeError as e:
+    assert str(e) == "'Point' object has no attribute 'x'", 'instance del message'
+
+try:
+    del p.kind
+    assert False, 'class attribute cannot be deleted from instance'
+except AttributeError:
+    pass
+
+del Point.kind
+try:
+    Point.kind
+    assert False, 'class attribute should be deleted'
+except AttributeError:
+    pass
+
+try:
+    del Point.kind
+    assert False, 'deleting class attribute twice should fail'
+except AttributeError as e:
+    assert str(e) == "type object 'Point' has no attribute 'kind'", 'class del message'
+
+try:
+    del number.foo
+    assert False, 'int attribute del should fail'
+except AttributeError as e:
+    assert str(e) == "'int' object has no attribute 'foo' and no __dict__ for setting new attributes", (
+        'int attribute del message'
+    )
+
+# === del mixed targets ===
+items = [1, 2, 3]
+data = {'k': items}
+q = Point(3, 4)
+del data['k'][0], q.y, (items,)
+assert data == {'k': [2, 3]}, 'subscript of subscript deleted'
+assert q.x == 3, 'other attribute kept'
+try:
+    q.y
+    assert False, 'attribute in tuple of targets should be deleted'
+except AttributeError:
+    pass
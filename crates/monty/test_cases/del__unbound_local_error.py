@@ -0,0 +1,16 @@
+def clear():
+    del cache
+
+
+clear()
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "del__unbound_local_error.py", line 5, in <module>
+    clear()
+    ~~~~~~~
+  File "del__unbound_local_error.py", line 2, in clear
+    del cache
+        ~~~~~
+UnboundLocalError: cannot access local variable 'cache' where it is not associated with a value
+"""
@@ -1,4 +1,3 @@
-# xfail=cpython
 d = {'a': 1, 'b': 2}
 d.items()
-# Return=[('a', 1), ('b', 2)]
+# Return=dict_items([('a', 1), ('b', 2)])
@@ -1,4 +1,3 @@
-# xfail=cpython
 d = {'a': 1, 'b': 2}
 d.keys()
-# Return=['a', 'b']
+# Return=dict_keys(['a', 'b'])
@@ -1,4 +1,3 @@
-# xfail=cpython
 d = {'a': 1, 'b': 2}
 d.values()
-# Return=[1, 2]
+# Return=dict_values([1, 2])
@@ -43,6 +43,19 @@ assert d == {'b': 2}, 'pop removes key'
 d = {'a': 1}
 assert d.pop('missing', 'default') == 'default', 'pop missing with default'
 
+d = {i: str(i) for i in range(20)}
+for i in range(0, 20, 3):
+    del d[i]
+assert list(d) == [i for i in range(20) if i % 3], 'order kept after deleting items'
+assert list(d.items())[:2] == [(1, '1'), (2, '2')], 'items after deleting items'
+d[0] = 'zero'
+assert list(d)[-1] == 0, 'reinserted key goes to the end'
+for i in list(d):
+    del d[i]
+assert d == {} and list(d) == [], 'every item deleted'
+d['a'] = 1
+assert d.popitem() == ('a', 1), 'popitem after deleting items'
+
 # === Dict with tuple key ===
 d = {(1, 2): 'value'}
 assert d[(1, 2)] == 'value', 'tuple key'
@@ -0,0 +1,2 @@
+d = {'a': 1, **[('b', 2)]}
+# Raise=TypeError("'list' object is not a mapping")
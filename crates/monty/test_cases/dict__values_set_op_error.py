@@ -0,0 +1,3 @@
+d = {'a': 1}
+d.values() & {1}
+# Raise=TypeError("unsupported operand type(s) for &: 'dict_values' and 'set'")
@@ -0,0 +1,4 @@
+d = {'a': 1, 'b': 2}
+for k, v in d.items():
+    d['c'] = 3
+# Raise=RuntimeError('dictionary changed size during iteration')
@@ -0,0 +1,101 @@
+# === View types and repr ===
+d = {'a': 1, 'b': 2}
+assert repr(d.keys()) == "dict_keys(['a', 'b'])", 'keys repr'
+assert repr(d.values()) == 'dict_values([1, 2])', 'values repr'
+assert repr(d.items()) == "dict_items([('a', 1), ('b', 2)])", 'items repr'
+assert repr({}.keys()) == 'dict_keys([])', 'empty keys repr'
+assert repr(type(d.keys())) == "<class 'dict_keys'>", 'keys type'
+assert repr(type(d.values())) == "<class 'dict_values'>", 'values type'
+assert repr(type(d.items())) == "<class 'dict_items'>", 'items type'
+
+# === len and truthiness ===
+assert len(d.keys()) == 2, 'len keys'
+assert len(d.values()) == 2, 'len values'
+assert len(d.items()) == 2, 'len items'
+assert not {}.keys(), 'empty view is falsy'
+assert d.items(), 'non-empty view is truthy'
+
+# === Iteration ===
+assert list(d.keys()) == ['a', 'b'], 'iterate keys'
+assert list(d.values()) == [1, 2], 'iterate values'
+assert list(d.items()) == [('a', 1), ('b', 2)], 'iterate items'
+pairs = []
+for k, v in d.items():
+    pairs.append(k + str(v))
+assert pairs == ['a1', 'b2'], 'unpack items in for loop'
+assert sum(d.values()) == 3, 'sum values'
+assert sorted({'b': 1, 'a': 2}.keys()) == ['a', 'b'], 'sorted keys'
+it = iter(d.values())
+assert next(it) == 1, 'next on view iterator'
+assert next(it) == 2, 'next on view iterator again'
+
+# === Views reflect later changes ===
+d = {'a': 1}
+keys = d.keys()
+values = d.values()
+items = d.items()
+d['b'] = 2
+assert list(keys) == ['a', 'b'], 'keys sees new key'
+assert list(values) == [1, 2], 'values sees new value'
+assert len(items) == 2, 'items len sees new key'
+d['a'] = 10
+assert list(values) == [10, 2], 'values sees updated value'
+del d['a']
+assert list(items) == [('b', 2)], 'items sees deleted key'
+d.clear()
+assert len(keys) == 0, 'keys sees clear'
+
+# === Membership ===
+d = {'a': 1, 'b': [2]}
+assert 'a' in d.keys(), 'key in keys'
+assert 'z' not in d.keys(), 'key not in keys'
+assert 1 in d.values(), 'value in values'
+assert [2] in d.values(), 'list value in values'
+assert 3 not in d.values(), 'value not in values'
+assert ('a', 1) in d.items(), 'pair in items'
+assert ('b', [2]) in d.items(), 'pair with list value in items'
+assert ('a', 2) not in d.items(), 'pair with wrong value not in items'
+assert ('z', 1) not in d.items(), 'pair with missing key not in items'
+assert 'a' not in d.items(), 'non-tuple not in items'
+assert ('a', 1, 2) not in d.items(), 'triple not in items'
+
+# === Set operations on keys ===
+d = {'a': 1, 'b': 2, 'c': 3}
+assert d.keys() & {'a', 'z'} == {'a'}, 'keys & set'
+assert d.keys() | {'z'} == {'a', 'b', 'c', 'z'}, 'keys | set'
+assert d.keys() - {'a'} == {'b', 'c'}, 'keys - set'
+assert d.keys() ^ {'a', 'z'} == {'b', 'c', 'z'}, 'keys ^ set'
+assert d.keys() & ['b', 'c', 'x'] == {'b', 'c'}, 'keys & list'
+assert d.keys() - {'a': 0}.keys() == {'b', 'c'}, 'keys - keys'
+assert {'a', 'z'} & d.keys() == {'a'}, 'set & keys'
+assert ['a', 'z'] - d.keys() == {'z'}, 'list - keys'
+assert {'z'} ^ d.keys() == {'a', 'b', 'c', 'z'}, 'set ^ keys'
+assert type(d.keys() & set()) == set, 'set operation returns a set'
+s = d.keys()
+s -= {'a', 'b'}
+assert s == {'c'}, 'in-place difference on keys view'
+
+# === Set operations on items ===
+d = {'a': 1, 'b': 2}
+assert d.items() & {('a', 1), ('b', 3)} == {('a', 1)}, 'items & set'
+assert d.items() - {('a', 1)} == {('b', 2)}, 'items - set'
+assert d.items() | {('c', 3)} == {('a', 1), ('b', 2), ('c', 3)}, 'items | set'
+
+# === Equality ===
+d1 = {'a': 1, 'b': 2}
+d2 = {'b': 2, 'a': 1}
+assert d1.keys() == {'a', 'b'}, 'keys == set'
+assert {'b', 'a'} == d1.keys(), 'set == keys'
+assert d1.keys() == frozenset({'a', 'b'}), 'keys == frozenset'
+assert d1.keys() != {'a'}, 'keys != smaller set'
+assert d1.keys() != {'a', 'c'}, 'keys != different set'
+assert d1.keys() == d2.keys(), 'keys == keys of another dict'
+assert d1.items() == d2.items(), 'items == items of another dict'
+assert d1.items() == {('a', 1), ('b', 2)}, 'items == set'
+assert d1.items() != {'a': 1, 'b': 3}.items(), 'items with a different value'
+assert d1.keys() != ['a', 'b'], 'keys != list'
+assert d1.keys() != d1.items(), 'keys != items'
+assert {}.keys() == {}.items() == set(), 'empty views equal the empty set'
+assert d1.values() != d2.values(), 'values views are not set-like'
+v = d1.values()
+assert v == v, 'a values view equals itself'
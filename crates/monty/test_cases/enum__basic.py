@@ -0,0 +1,123 @@
+from enum import Enum, auto
+
+
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+    BLUE = 3
+    CRIMSON = 1
+
+    def describe(self):
+        return f'{self.name} is {self.value}'
+
+
+# === members ===
+assert repr(Color.RED) == '<Color.RED: 1>', 'repr'
+assert str(Color.RED) == 'Color.RED', 'str'
+assert f'{Color.GREEN}' == 'Color.GREEN', 'f-string'
+assert Color.RED.name == 'RED', 'name'
+assert Color.RED.value == 1, 'value'
+assert Color.RED._name_ == 'RED', '_name_'
+assert Color.BLUE._value_ == 3, '_value_'
+assert type(Color.RED) is Color, 'type of a member'
+assert isinstance(Color.RED, Color), 'isinstance of the class'
+assert isinstance(Color.RED, Enum), 'isinstance of Enum'
+assert Color.RED.describe() == 'RED is 1', 'method'
+assert bool(Color.RED), 'members are truthy'
+
+# === aliases ===
+assert Color.CRIMSON is Color.RED, 'alias refers to the first member'
+assert Color.CRIMSON.name == 'RED', 'alias name'
+assert list(Color.__members__) == ['RED', 'GREEN', 'BLUE', 'CRIMSON'], '__members__ includes aliases'
+assert Color.__members__['CRIMSON'] is Color.RED, '__members__ alias value'
+
+# === iteration ===
+assert list(Color) == [Color.RED, Color.GREEN, Color.BLUE], 'iteration skips aliases'
+assert len(Color) == 3, 'len'
+assert [c.value for c in Color] == [1, 2, 3], 'values'
+assert [c.name for c in Color] == ['RED', 'GREEN', 'BLUE'], 'names'
+
+# === lookup ===
+assert Color(2) is Color.GREEN, 'lookup by value'
+assert Color(Color.BLUE) is Color.BLUE, 'lookup by member'
+assert Color['GREEN'] is Color.GREEN, 'lookup by name'
+assert Color['CRIMSON'] is Color.RED, 'lookup of an alias by name'
+assert getattr(Color, 'BLUE') is Color.BLUE, 'getattr'
+
+# === membership ===
+assert Color.RED in Color, 'member in class'
+assert 1 in Color, 'value in class'
+assert 5 not in Color, 'missing value'
+assert 'RED' not in Color, 'names are not values'
+
+# === comparison and hashing ===
+assert Color.RED == Color.RED, 'equal to itself'
+assert Color.RED != Color.GREEN, 'different members'
+assert Color.RED != 1, 'not equal to its value'
+assert {Color.RED: 'r', Color.GREEN: 'g'}[Color.CRIMSON] == 'r', 'dict keys'
+assert len({Color.RED, Color.CRIMSON, Color.GREEN}) == 2, 'set of members'
+assert hash(Color.RED) == hash('RED'), 'hash of the name'
+
+
+# === auto() ===
+class Shape(Enum):
+    CIRCLE = auto()
+    SQUARE = auto()
+    TRIANGLE = 10
+    LINE = auto()
+
+
+assert [s.value for s in Shape] == [1, 2, 10, 11], 'auto values'
+assert repr(auto()) == 'auto(_auto_null)', 'auto repr'
+
+
+# === other member values ===
+class Misc(Enum):
+    PAIR = (1, 2)
+    TEXT = 'text'
+    _private = 5
+    FUNC = len
+
+    def method(self):
+        return 1
+
+
+assert Misc.PAIR.value == (1, 2), 'tuple value'
+assert Misc((1, 2)) is Misc.PAIR, 'lookup by tuple value'
+assert repr(Misc.TEXT) == "<Misc.TEXT: 'text'>", 'str value repr'
+assert Misc._private.value == 5, 'single underscore names are members'
+assert Misc.FUNC.value is len, 'builtin functions are members'
+assert len(Misc) == 4, 'methods are not members'
+
+
+# === custom __str__ ===
+class Greeting(Enum):
+    HELLO = 'hi'
+
+    def __str__(self):
+        return self.value.upper()
+
+
+assert str(Greeting.HELLO) == 'HI', 'custom __str__'
+assert f'{Greeting.HELLO}' == 'HI', 'custom __str__ in f-string'
+assert repr(Greeting.HELLO) == "<Greeting.HELLO: 'hi'>", 'repr ignores __str__'
+
+# === the class ===
+assert repr(Color) == "<enum 'Color'>", 'class repr'
+assert repr(Enum) == "<enum 'Enum'>", 'Enum repr'
+assert Color.__name__ == 'Color', 'class name'
+
+
+# === subclassing an enum without members ===
+class Base(Enum):
+    def double(self):
+        return self.value * 2
+
+
+class Number(Base):
+    ONE = 1
+    TWO = 2
+
+
+assert Number.TWO.double() == 4, 'inherited method'
+assert isinstance(Number.ONE, Base), 'isinstance of the base enum'
@@ -0,0 +1,82 @@
+from enum import Enum, IntEnum, StrEnum, auto
+
+
+def error(f, *args):
+    try:
+        f(*args)
+    except Exception as e:
+        return f'{type(e).__name__}: {e}'
+    assert False, f'{f} should have raised'
+
+
+class Color(Enum):
+    RED = 1
+    GREEN = 2
+
+
+# === lookups ===
+assert error(Color, 5) == 'ValueError: 5 is not a valid Color', 'missing value'
+assert error(Color, 'RED') == "ValueError: 'RED' is not a valid Color", 'name is not a value'
+assert error(lambda: Color['BLUE']) == "KeyError: 'BLUE'", 'missing name'
+assert error(lambda: Color.BLUE) == "AttributeError: type object 'Color' has no attribute 'BLUE'", 'missing attribute'
+
+
+# === changing members ===
+def reassign():
+    Color.RED = 3
+
+
+def delete():
+    del Color.RED
+
+
+assert error(reassign) == "AttributeError: cannot reassign member 'RED'", 'reassign'
+assert error(delete) == "AttributeError: 'Color' cannot delete member 'RED'.", 'delete'
+assert Color.RED.value == 1, 'member unchanged'
+
+# === operators ===
+assert error(lambda: Color.RED < Color.GREEN) == (
+    "TypeError: '<' not supported between instances of 'Color' and 'Color'"
+), 'plain members are unordered'
+
+
+# === class definitions ===
+def extend():
+    class More(Color):
+        BLUE = 3
+
+
+def sunder():
+    class Bad(Enum):
+        _bad_ = 1
+
+
+def str_auto():
+    class Mixed(Enum):
+        A = 'a'
+        B = auto()
+
+
+def mixed_auto():
+    class Mixed(Enum):
+        A = 1
+        B = 'b'
+        C = auto()
+
+
+def str_value():
+    class Bad(StrEnum):
+        A = 1
+
+
+def int_value():
+    class Bad(IntEnum):
+        A = 'x'
+
+
+assert error(extend) == "TypeError: <enum 'More'> cannot extend <enum 'Color'>", 'extending an enum with members'
+assert error(sunder) == "ValueError: _sunder_ names, such as '_bad_', are reserved for future Enum use", 'sunder'
+assert error(str_auto) == "TypeError: unable to increment 'a'", 'auto after a str'
+assert error(mixed_auto) == 'TypeError: unable to sort non-numeric values', 'auto after mixed values'
+assert error(str_value) == 'TypeError: 1 is not a string', 'StrEnum value'
+assert error(int_value) == "ValueError: invalid literal for int() with base 10: 'x'", 'IntEnum value'
@@ -0,0 +1,120 @@
+from enum import Enum, IntEnum, StrEnum, auto
+
+
+class Priority(IntEnum):
+    LOW = auto()
+    MEDIUM = auto()
+    HIGH = 10
+    URGENT = auto()
+
+
+class Empty(IntEnum):
+    ZERO = 0
+
+
+# === IntEnum members ===
+assert [p.value for p in Priority] == [1, 2, 10, 11], 'auto values'
+assert repr(Priority.LOW) == '<Priority.LOW: 1>', 'repr'
+assert str(Priority.LOW) == '1', 'str is the value'
+assert f'{Priority.HIGH}' == '10', 'f-string'
+assert f'{Priority.LOW:>5}' == '    1', 'format spec'
+assert '%s %d' % (Priority.LOW, Priority.MEDIUM) == '1 2', 'percent formatting'
+assert isinstance(Priority.LOW, int), 'isinstance of int'
+assert isinstance(Priority.LOW, IntEnum), 'isinstance of IntEnum'
+assert isinstance(Priority.LOW, Enum), 'isinstance of Enum'
+assert type(Priority.LOW) is Priority, 'type of a member'
+assert not Empty.ZERO, 'zero member is falsy'
+assert bool(Priority.LOW), 'non-zero member is truthy'
+
+# === IntEnum comparisons ===
+assert Priority.LOW == 1, 'equal to its value'
+assert 2 == Priority.MEDIUM, 'reversed equality'
+assert Priority.LOW != 2, 'not equal to another int'
+assert Priority.LOW < Priority.MEDIUM < Priority.HIGH, 'ordering'
+assert Priority.HIGH > 5, 'compared with int'
+assert 3 <= Priority.HIGH, 'int compared with member'
+assert sorted([Priority.HIGH, 0, Priority.LOW]) == [0, 1, 10], 'sorting with ints'
+assert max(Priority) is Priority.URGENT, 'max'
+assert hash(Priority.LOW) == hash(1), 'hash of the value'
+assert {1: 'one'}[Priority.LOW] == 'one', 'lookup of an int key'
+assert {Priority.LOW: 'low'}[1] == 'low', 'lookup with an int'
+assert Priority.MEDIUM in [0, 2], 'in a list of ints'
+assert 2 in (Priority.LOW, Priority.MEDIUM), 'int in a tuple of members'
+assert Priority.LOW in {1, 5}, 'in a set of ints'
+assert [3, 2, 1].index(Priority.MEDIUM) == 1, 'list.index'
+assert (Priority.LOW, 'b') < (Priority.MEDIUM, 'a'), 'ordering in tuples'
+assert min(5, Priority.HIGH, Priority.MEDIUM) is Priority.MEDIUM, 'min with ints'
+
+
+class Countdown(IntEnum):
+    ONE = 1
+    TWO = 2
+
+    def __lt__(self, other):
+        return int(self) > int(other)
+
+
+assert Countdown.TWO < Countdown.ONE, 'own __lt__ used over the one of int'
+assert sorted([Countdown.ONE, Countdown.TWO]) == [2, 1], 'sorted with own __lt__'
+assert Countdown.ONE == 1, 'equality still from int'
+
+# === IntEnum arithmetic ===
+assert Priority.LOW + 1 == 2, 'add'
+assert 1 + Priority.MEDIUM == 3, 'reversed add'
+assert Priority.HIGH * Priority.MEDIUM == 20, 'multiply members'
+assert Priority.HIGH // 3 == 3, 'floor divide'
+assert Priority.HIGH - Priority.LOW == 9, 'subtract'
+assert -Priority.LOW == -1, 'negate'
+assert Priority.HIGH | 1 == 11, 'bitwise or'
+assert type(Priority.LOW + 1) is int, 'arithmetic gives an int'
+x = Priority.LOW
+x += 5
+assert x == 6, 'in-place add'
+assert int(Priority.HIGH) == 10, 'int()'
+assert float(Priority.LOW) == 1.0, 'float()'
+assert [10, 20, 30][Priority.LOW] == 20, 'list index'
+assert 'abc'[Priority.MEDIUM] == 'c', 'str index'
+assert range(20)[Priority.HIGH] == 10, 'range index'
+assert Priority(2) is Priority.MEDIUM, 'lookup by value'
+assert Priority['HIGH'] == 10, 'lookup by name'
+
+
+# === IntEnum value conversion ===
+class Converted(IntEnum):
+    A = '5'
+    B = 2.0
+
+
+assert Converted.A == 5, 'str value converted with int()'
+assert Converted.B.value == 2, 'float value converted with int()'
+
+
+# === StrEnum members ===
+class Mode(StrEnum):
+    READ = auto()
+    WRITE = 'w'
+    APPEND = auto()
+
+
+assert [m.value for m in Mode] == ['read', 'w', 'append'], 'auto values are lowercase names'
+assert repr(Mode.READ) == "<Mode.READ: 'read'>", 'repr'
+assert str(Mode.WRITE) == 'w', 'str is the value'
+assert f'[{Mode.READ}]' == '[read]', 'f-string'
+assert f'{Mode.WRITE:>3}' == '  w', 'format spec'
+assert isinstance(Mode.READ, str), 'isinstance of str'
+assert isinstance(Mode.READ, StrEnum), 'isinstance of StrEnum'
+
+# === StrEnum comparisons and operations ===
+assert Mode.READ == 'read', 'equal to its value'
+assert 'w' == Mode.WRITE, 'reversed equality'
+assert Mode.APPEND < Mode.READ, 'ordering'
+assert hash(Mode.READ) == hash('read'), 'hash of the value'
+assert {'w': 1}[Mode.WRITE] == 1, 'lookup of a str key'
+assert Mode.READ + '!' == 'read!', 'concatenation'
+assert '>' + Mode.WRITE == '>w', 'reversed concatenation'
+assert Mode.READ.upper() == 'READ', 'str method'
+assert Mode.APPEND.startswith('app'), 'str method with args'
+assert len(Mode.APPEND) == 6, 'len of the value'
+assert 'ea' in Mode.READ, 'substring'
+assert Mode('w') is Mode.WRITE, 'lookup by value'
+assert 'r' * 0 + Mode.WRITE * 2 == 'ww', 'repeat'
@@ -0,0 +1,170 @@
+# === raise from ===
+try:
+    try:
+        1 / 0
+    except ZeroDivisionError as e:
+        raise ValueError('bad value') from e
+except ValueError as e:
+    assert type(e.__cause__) is ZeroDivisionError, 'cause is the original exception'
+    assert str(e.__cause__) == 'division by zero', 'cause keeps its message'
+    assert type(e.__context__) is ZeroDivisionError, 'context is also set'
+    assert e.__suppress_context__ is True, 'raise from suppresses the context'
+
+try:
+    raise ValueError('a') from KeyError
+except ValueError as e:
+    assert type(e.__cause__) is KeyError, 'cause can be an exception type'
+    assert e.__cause__.args == (), 'exception type cause is instantiated'
+    assert e.__context__ is None, 'no context outside an except block'
+
+try:
+    raise ValueError('a') from TypeError('b')
+except ValueError as e:
+    assert e.__cause__.args == ('b',), 'cause can be a new exception instance'
+
+# === raise from None ===
+try:
+    try:
+        {}['missing']
+    except KeyError:
+        raise AttributeError('no attribute') from None
+except AttributeError as e:
+    assert e.__cause__ is None, 'from None leaves no cause'
+    assert type(e.__context__) is KeyError, 'from None keeps the context'
+    assert e.__suppress_context__ is True, 'from None suppresses the context'
+
+# === implicit context ===
+try:
+    try:
+        raise KeyError('k')
+    except KeyError:
+        raise ValueError('during handling')
+except ValueError as e:
+    assert e.__cause__ is None, 'no explicit cause'
+    assert type(e.__context__) is KeyError, 'context is the handled exception'
+    assert e.__suppress_context__ is False, 'context is not suppressed'
+
+try:
+    try:
+        raise KeyError('k')
+    except KeyError:
+        [][0]
+except IndexError as e:
+    assert type(e.__context__) is KeyError, 'errors from operations get a context'
+
+
+def fail():
+    raise RuntimeError('in function')
+
+
+try:
+    try:
+        1 / 0
+    except ZeroDivisionError:
+        fail()
+except RuntimeError as e:
+    assert type(e.__context__) is ZeroDivisionError, 'context crosses function calls'
+
+try:
+    try:
+        raise KeyError('inner')
+    finally:
+        raise ValueError('in finally')
+except ValueError as e:
+    assert type(e.__context__) is KeyError, 'finally block raising gets a context'
+
+# === no context ===
+try:
+    raise ValueError('plain')
+except ValueError as e:
+    assert e.__cause__ is None, 'plain raise has no cause'
+    assert e.__context__ is None, 'plain raise has no context'
+    assert e.__suppress_context__ is False, 'plain raise does not suppress context'
+
+try:
+    try:
+        raise KeyError('k')
+    except KeyError:
+        pass
+    raise ValueError('after handler')
+except ValueError as e:
+    assert e.__context__ is None, 'handled exception is not the context after its handler'
+
+try:
+    try:
+        try:
+            raise KeyError('k')
+        except KeyError:
+            raise TypeError('escapes handler')
+    except TypeError:
+        pass
+    raise ValueError('later')
+except ValueError as e:
+    assert e.__context__ is None, 'exception that escaped a handler is not the context'
+
+
+def return_in_handler():
+    try:
+        raise KeyError('k')
+    except KeyError:
+        return 'returned'
+
+
+assert return_in_handler() == 'returned', 'return in handler'
+try:
+    raise ValueError('after return')
+except ValueError as e:
+    assert e.__context__ is None, 'returning from a handler ends the handling'
+
+# === re-raising ===
+try:
+    try:
+        raise KeyError('k')
+    except KeyError as e:
+        raise e
+except KeyError as e:
+    assert e.__context__ is None, 'raising the handled exception does not make it its own context'
+
+try:
+    try:
+        raise KeyError('k')
+    except KeyError:
+        try:
+            raise ValueError('v')
+        except ValueError:
+            raise
+except ValueError as e:
+    assert type(e.__context__) is KeyError, 'bare raise keeps the context'
+
+try:
+    try:
+        raise ValueError('v') from KeyError('k')
+    except ValueError:
+        raise
+except ValueError as e:
+    assert type(e.__cause__) is KeyError, 'bare raise keeps the cause'
+
+# === chains ===
+try:
+    try:
+        try:
+            raise KeyError('first')
+        except KeyError:
+            raise TypeError('second')
+    except TypeError:
+        raise ValueError('third')
+except ValueError as e:
+    assert type(e.__context__) is TypeError, 'context of third'
+    assert type(e.__context__.__context__) is KeyError, 'context of second'
+    assert e.__context__.__context__.__context__ is None, 'first has no context'
+
+# === invalid cause ===
+try:
+    raise ValueError('a') from 1
+except TypeError as e:
+    assert str(e) == 'exception causes must derive from BaseException', 'invalid cause message'
+
+try:
+    raise 1 from ValueError
+except TypeError as e:
+    assert str(e) == 'exceptions must derive from BaseException', 'invalid exception checked first'
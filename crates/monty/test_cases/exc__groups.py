@@ -0,0 +1,101 @@
+# === Construction ===
+eg = ExceptionGroup('msg', [ValueError('a'), TypeError('b')])
+assert type(eg) is ExceptionGroup, 'ExceptionGroup type'
+assert repr(eg) == "ExceptionGroup('msg', [ValueError('a'), TypeError('b')])", 'repr keeps the list'
+assert str(eg) == 'msg (2 sub-exceptions)', 'str includes the count'
+assert eg.message == 'msg', 'message attribute'
+assert len(eg.args) == 2, 'args has message and exceptions'
+assert eg.args[0] == 'msg', 'args[0] is the message'
+assert repr(eg.args[1]) == "[ValueError('a'), TypeError('b')]", 'args[1] is the exceptions as passed'
+assert type(eg.exceptions) is tuple, 'exceptions is a tuple'
+assert len(eg.exceptions) == 2, 'exceptions length'
+assert repr(eg.exceptions[0]) == "ValueError('a')", 'first sub-exception'
+assert repr(eg.exceptions[1]) == "TypeError('b')", 'second sub-exception'
+
+single = ExceptionGroup('one', (KeyError('k'),))
+assert repr(single) == "ExceptionGroup('one', (KeyError('k'),))", 'repr keeps the tuple'
+assert str(single) == 'one (1 sub-exception)', 'str singular'
+
+base = BaseExceptionGroup('b', [ValueError(1)])
+assert type(base) is ExceptionGroup, 'BaseExceptionGroup of Exceptions is an ExceptionGroup'
+base = BaseExceptionGroup('b', [KeyboardInterrupt(), ValueError(1)])
+assert type(base) is BaseExceptionGroup, 'BaseExceptionGroup with a BaseException'
+assert repr(base) == "BaseExceptionGroup('b', [KeyboardInterrupt(), ValueError(1)])", 'base group repr'
+
+# === Hierarchy ===
+assert isinstance(eg, Exception), 'ExceptionGroup is an Exception'
+assert isinstance(eg, BaseExceptionGroup), 'ExceptionGroup is a BaseExceptionGroup'
+assert not isinstance(base, Exception), 'BaseExceptionGroup is not an Exception'
+assert isinstance(base, BaseException), 'BaseExceptionGroup is a BaseException'
+
+try:
+    raise eg
+except ExceptionGroup as e:
+    assert e.message == 'msg', 'plain except catches groups'
+
+# === Constructor errors ===
+try:
+    ExceptionGroup('m', [])
+    assert False, 'empty list should raise'
+except ValueError as e:
+    assert str(e) == 'second argument (exceptions) must be a non-empty sequence', 'empty error'
+
+try:
+    ExceptionGroup('m', ValueError())
+    assert False, 'non-sequence should raise'
+except TypeError as e:
+    assert str(e) == 'second argument (exceptions) must be a sequence', 'non-sequence error'
+
+try:
+    ExceptionGroup('m', [1])
+    assert False, 'non-exception item should raise'
+except ValueError as e:
+    assert str(e) == 'Item 0 of second argument (exceptions) is not an exception', 'item error'
+
+try:
+    ExceptionGroup('m', [KeyboardInterrupt()])
+    assert False, 'BaseException item should raise'
+except TypeError as e:
+    assert str(e) == 'Cannot nest BaseExceptions in an ExceptionGroup', 'nest error'
+
+try:
+    ExceptionGroup(1, [ValueError()])
+    assert False, 'non-str message should raise'
+except TypeError as e:
+    assert str(e) == 'BaseExceptionGroup.__new__() argument 1 must be str, not int', 'message type error'
+
+# === split and subgroup ===
+nested = ExceptionGroup('outer', [ValueError('a'), ExceptionGroup('inner', [TypeError('b'), ValueError('c')])])
+
+match, rest = nested.split(ValueError)
+assert repr(match) == "ExceptionGroup('outer', [ValueError('a'), ExceptionGroup('inner', [ValueError('c')])])", 'match'
+assert repr(rest) == "ExceptionGroup('outer', [ExceptionGroup('inner', [TypeError('b')])])", 'rest'
+
+match, rest = nested.split((TypeError, KeyError))
+assert repr(match) == "ExceptionGroup('outer', [ExceptionGroup('inner', [TypeError('b')])])", 'tuple of types'
+
+match, rest = nested.split(ExceptionGroup)
+assert repr(match) == repr(nested), 'whole group matches'
+assert rest is None, 'nothing left'
+
+assert nested.subgroup(KeyError) is None, 'no match gives None'
+assert repr(nested.subgroup(TypeError)) == "ExceptionGroup('outer', [ExceptionGroup('inner', [TypeError('b')])])", (
+    'subgroup'
+)
+
+
+def is_c(exc):
+    return str(exc) == 'c'
+
+
+assert repr(nested.subgroup(is_c)) == "ExceptionGroup('outer', [ExceptionGroup('inner', [ValueError('c')])])", (
+    'predicate'
+)
+
+try:
+    nested.split(1)
+    assert False, 'invalid condition should raise'
+except TypeError as e:
+    assert str(e) == (
+        'expected an exception type, a tuple of exception types, or a callable (other than a class)'
+    ), 'condition error'
@@ -0,0 +1,53 @@
+# call-external
+# === external function as a decorator ===
+@return_value
+def double(x):
+    return x * 2
+
+
+assert double(3) == 6, 'external decorator returns the function'
+
+
+@return_value
+def with_default(x, y=10):
+    return x + y
+
+
+assert with_default(1) == 11, 'function with defaults survives the round trip'
+
+
+def make_adder(n):
+    @return_value
+    def add(x):
+        return x + n
+
+    return add
+
+
+assert make_adder(5)(1) == 6, 'closure survives the round trip'
+
+
+# === stacked with a Monty decorator ===
+def negate(func):
+    def wrapper(x):
+        return -func(x)
+
+    return wrapper
+
+
+@negate
+@return_value
+def triple(x):
+    return x * 3
+
+
+assert triple(2) == -6, 'external decorator applied first'
+
+
+@return_value
+@negate
+def quadruple(x):
+    return x * 4
+
+
+assert quadruple(2) == -8, 'wrapper function passed to the external decorator'
@@ -0,0 +1,30 @@
+# call-external
+# xfail=cpython
+# Key functions defined in Python run to completion inside the builtin, so they can't call
+# external functions; passing the external function itself as the key works
+from functools import cmp_to_key
+
+try:
+    sorted([3, 1, 2], key=lambda x: return_value(x))
+    assert False, 'should raise'
+except RuntimeError as e:
+    assert str(e) == 'external functions and OS calls are not supported inside key functions defined in Python', str(e)
+
+lst = [2, 3, 1]
+try:
+    lst.sort(key=lambda x: return_value(x))
+    assert False, 'should raise'
+except RuntimeError:
+    pass
+assert sorted(lst) == [1, 2, 3], 'the list keeps its items'
+
+
+def compare(a, b):
+    return add_ints(a, -b)
+
+
+try:
+    max([1, 2], key=cmp_to_key(compare))
+    assert False, 'should raise'
+except RuntimeError as e:
+    assert str(e) == 'external functions and OS calls are not supported in cmp_to_key() comparison functions', str(e)
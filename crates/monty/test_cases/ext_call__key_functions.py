@@ -0,0 +1,39 @@
+# call-external
+# === External functions as key functions ===
+assert sorted([3, 1, 2], key=return_value) == [1, 2, 3], 'sorted with external key'
+assert sorted(['b', 'c', 'a'], key=return_value, reverse=True) == ['c', 'b', 'a'], 'sorted external key reverse'
+assert sorted([], key=return_value) == [], 'sorted empty with external key'
+assert max([3, 5, 4], key=return_value) == 5, 'max with external key'
+assert min(3, 1, 2, key=return_value) == 1, 'min of args with external key'
+assert max([], key=return_value, default='empty') == 'empty', 'max default with external key'
+
+lst = [2, 3, 1]
+lst.sort(key=return_value)
+assert lst == [1, 2, 3], 'list.sort with external key'
+
+
+def sort_in_function(items):
+    return sorted(items, key=return_value)
+
+
+assert sort_in_function([5, 4]) == [4, 5], 'external key inside a function'
+
+# values computed before the call survive the suspension
+results = [add_ints(1, 1), sorted([2, 1], key=return_value), add_ints(2, 2)]
+assert results == [2, [1, 2], 4], 'external key between other external calls'
+
+total = 0
+for x in sorted([3, 1, 2], key=return_value):
+    total = add_ints(total, x)
+assert total == 6, 'external key in for loop'
+
+# errors comparing keys are raised where sorted() is called
+try:
+    sorted([1, 'a'], key=return_value)
+    assert False, 'should raise'
+except TypeError as e:
+    assert str(e) == "'<' not supported between instances of 'str' and 'int'", str(e)
+
+# min() and max() advance generators between the external keys
+assert max((x * 2 for x in [1, 3, 2]), key=return_value) == 6, 'max generator with external key'
+assert min((x for x in []), key=return_value, default=0) == 0, 'min empty generator with external key'
@@ -0,0 +1,73 @@
+from decimal import Decimal
+from fractions import Fraction
+
+# === construction ===
+assert Fraction() == 0, 'no argument'
+assert str(Fraction(6, 4)) == '3/2', 'lowest terms'
+assert repr(Fraction(6, 4)) == 'Fraction(3, 2)', 'repr'
+assert str(Fraction(3, -6)) == '-1/2', 'sign moves to the numerator'
+assert str(Fraction(5)) == '5', 'whole number'
+assert str(Fraction('3/7')) == '3/7', 'from string'
+assert str(Fraction(' -1.25 ')) == '-5/4', 'from decimal string'
+assert str(Fraction('1e-3')) == '1/1000', 'from exponent string'
+assert str(Fraction(0.75)) == '3/4', 'from float'
+assert str(Fraction(0.1)) == '3602879701896397/36028797018963968', 'floats are exact'
+assert str(Fraction(Decimal('1.1'))) == '11/10', 'from decimal'
+assert str(Fraction(Fraction(1, 2), Fraction(3, 4))) == '2/3', 'ratio of fractions'
+assert Fraction.from_float(0.5) == Fraction(1, 2), 'from_float'
+assert Fraction.from_decimal(Decimal('0.2')) == Fraction(1, 5), 'from_decimal'
+f = Fraction(10**20, 3)
+assert f.numerator == 10**20 and f.denominator == 3, 'big numerator'
+
+# === arithmetic ===
+assert Fraction(1, 3) + Fraction(1, 6) == Fraction(1, 2), 'addition'
+assert Fraction(1, 2) - 1 == Fraction(-1, 2), 'mixed with int'
+assert 3 * Fraction(1, 6) == Fraction(1, 2), 'reflected multiplication'
+assert Fraction(1, 2) / Fraction(1, 4) == 2, 'division'
+assert 1 / Fraction(3) == Fraction(1, 3), 'reflected division'
+assert Fraction(7, 2) // 1 == 3, 'floor division'
+assert Fraction(-7, 2) // 1 == -4, 'floor division rounds down'
+assert Fraction(7, 2) % 1 == Fraction(1, 2), 'modulo'
+assert divmod(Fraction(7, 2), 2) == (1, Fraction(3, 2)), 'divmod'
+assert Fraction(2, 3) ** 2 == Fraction(4, 9), 'power'
+assert Fraction(2, 3) ** -2 == Fraction(9, 4), 'negative power'
+assert Fraction(1, 4) ** Fraction(1, 2) == 0.5, 'fractional power gives a float'
+assert Fraction(1, 2) + 0.25 == 0.75, 'mixing with floats gives floats'
+assert isinstance(Fraction(1, 2) * 1.0, float), 'float result'
+assert -Fraction(1, 2) == Fraction(-1, 2), 'negation'
+assert abs(Fraction(-1, 2)) == Fraction(1, 2), 'abs'
+assert sum([Fraction(1, 3)] * 3) == 1, 'sum'
+x = Fraction(1, 2)
+x += 1
+assert x == Fraction(3, 2), 'augmented assignment'
+
+# === comparison and hashing ===
+assert Fraction(1, 2) == 0.5, 'equal to float'
+assert Fraction(2, 1) == 2, 'equal to int'
+assert Fraction(1, 3) != 0.3333333333333333, 'not equal to an inexact float'
+assert Fraction(1, 2) == Decimal('0.5'), 'equal to decimal'
+assert Fraction(1, 3) < Fraction(1, 2) < 1 < Fraction(3, 2), 'ordering'
+assert Fraction(1, 3) < 0.34, 'ordering with floats'
+assert sorted([Fraction(1, 2), Fraction(-1), Fraction(1, 3)]) == [-1, Fraction(1, 3), Fraction(1, 2)], 'sorting'
+assert hash(Fraction(2)) == hash(2), 'hash matches int'
+assert hash(Fraction(1, 2)) == hash(0.5), 'hash matches float'
+assert hash(Fraction(1, 2)) == hash(Decimal('0.5')), 'hash matches decimal'
+assert len({Fraction(1, 2), 0.5, Fraction(2, 4)}) == 1, 'set of equal values'
+assert bool(Fraction(0)) is False and bool(Fraction(1, 9)) is True, 'truthiness'
+
+# === conversions ===
+assert int(Fraction(-7, 2)) == -3, 'int truncates'
+assert float(Fraction(1, 4)) == 0.25, 'float'
+assert round(Fraction(5, 2)) == 2, 'round half even'
+assert round(Fraction(7, 2)) == 4, 'round up to even'
+assert round(Fraction(1234, 1000), 2) == Fraction(123, 100), 'round to digits'
+assert Fraction(3, 4).as_integer_ratio() == (3, 4), 'as_integer_ratio'
+assert Fraction(4, 2).is_integer(), 'is_integer'
+assert Fraction(3, 1000).limit_denominator(10) == 0, 'limit_denominator to zero'
+assert Fraction('3.1415926535897932').limit_denominator(1000) == Fraction(355, 113), 'limit_denominator'
+
+# === formatting ===
+assert f'{Fraction(1, 3):.4f}' == '0.3333', 'fixed point'
+assert f'{Fraction(-5, 2):>6}' == '  -5/2', 'alignment'
+assert f'{Fraction(1, 8):.1%}' == '12.5%', 'percent'
+assert '%.3f' % Fraction(2, 3) == '0.667', 'percent formatting'
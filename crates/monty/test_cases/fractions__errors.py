@@ -0,0 +1,30 @@
+from fractions import Fraction
+
+
+def error(f, *args):
+    try:
+        f(*args)
+    except Exception as e:
+        return f'{type(e).__name__}: {e}'
+    assert False, f'{f} should have raised'
+
+
+assert error(Fraction, 1, 0) == 'ZeroDivisionError: Fraction(1, 0)', 'zero denominator'
+assert error(Fraction, '1/0') == 'ZeroDivisionError: Fraction(1, 0)', 'zero denominator in a string'
+assert error(lambda: Fraction(1, 2) / 0) == 'ZeroDivisionError: Fraction(1, 0)', 'division by zero'
+assert error(lambda: Fraction(0) ** -1) == 'ZeroDivisionError: Fraction(1, 0)', 'zero to a negative power'
+assert error(Fraction, 'one') == "ValueError: Invalid literal for Fraction: 'one'", 'invalid string'
+assert error(Fraction, '1/2/3') == "ValueError: Invalid literal for Fraction: '1/2/3'", 'two slashes'
+assert error(Fraction, 1.5, 2) == 'TypeError: both arguments should be Rational instances', 'float numerator'
+assert error(Fraction, None) == 'TypeError: argument should be a string or a Rational instance', 'None'
+assert error(Fraction, float('nan')) == 'ValueError: cannot convert NaN to integer ratio', 'NaN'
+assert error(Fraction, float('inf')) == 'OverflowError: cannot convert Infinity to integer ratio', 'infinity'
+assert error(lambda: Fraction(1, 2) < 'a') == (
+    "TypeError: '<' not supported between instances of 'Fraction' and 'str'"
+), 'ordering with str'
+assert error(lambda: Fraction(1, 2) + 'a') == (
+    "TypeError: unsupported operand type(s) for +: 'Fraction' and 'str'"
+), 'adding str'
+assert error(Fraction(1, 2).limit_denominator, 0) == 'ValueError: max_denominator should be at least 1', (
+    'limit_denominator'
+)
@@ -0,0 +1,14 @@
+not_a_decorator = 1
+
+
+@not_a_decorator
+def f():
+    pass
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "function__decorator_not_callable.py", line 4, in <module>
+    @not_a_decorator
+     ~~~~~~~~~~~~~~~
+TypeError: 'int' object is not callable
+"""
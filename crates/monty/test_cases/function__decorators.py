@@ -0,0 +1,205 @@
+# === simple decorator ===
+def shout(func):
+    def wrapper(*args, **kwargs):
+        return func(*args, **kwargs).upper()
+
+    return wrapper
+
+
+@shout
+def greet(name):
+    return 'hello ' + name
+
+
+assert greet('world') == 'HELLO WORLD', 'decorator wraps the function'
+assert greet(name='bob') == 'HELLO BOB', 'wrapper forwards kwargs'
+
+# === decorator with arguments ===
+def repeat(times):
+    def decorator(func):
+        def wrapper(x):
+            result = x
+            for _ in range(times):
+                result = func(result)
+            return result
+
+        return wrapper
+
+    return decorator
+
+
+@repeat(3)
+def add_one(x):
+    return x + 1
+
+
+assert add_one(0) == 3, 'decorator factory'
+
+# === stacked decorators apply bottom-up ===
+order = []
+
+
+def tag(label):
+    order.append('eval ' + label)
+
+    def decorator(func):
+        order.append('apply ' + label)
+
+        def wrapper():
+            return label + '(' + func() + ')'
+
+        return wrapper
+
+    return decorator
+
+
+@tag('a')
+@tag('b')
+def base():
+    return 'x'
+
+
+assert base() == 'a(b(x))', 'innermost decorator is applied first'
+assert order == ['eval a', 'eval b', 'apply b', 'apply a'], 'decorators are evaluated top-down then applied bottom-up'
+
+# === decorator can return anything ===
+def constant(func):
+    return 42
+
+
+@constant
+def replaced():
+    return 'never'
+
+
+assert replaced == 42, 'name is bound to the decorator result'
+
+
+# === registration decorator ===
+registry = {}
+
+
+def register(func):
+    registry[func.__name__] = func
+    return func
+
+
+@register
+def first():
+    return 1
+
+
+@register
+def second(x=2):
+    return x
+
+
+assert registry['first']() == 1, 'registered function'
+assert registry['second']() == 2, 'registered function with defaults'
+assert registry['second'] is second, 'decorator returned the same function'
+
+# === decorator sees the previous binding ===
+def value():
+    return 'old'
+
+
+def keep_previous(func):
+    return value
+
+
+@keep_previous
+def value():
+    return 'new'
+
+
+assert value() == 'old', 'name is only rebound after decoration'
+
+# === decorators in nested scopes ===
+def outer():
+    def local_decorator(func):
+        def wrapper():
+            return func() * 2
+
+        return wrapper
+
+    def inner():
+        @local_decorator
+        def nested():
+            return 21
+
+        return nested()
+
+    return inner()
+
+
+assert outer() == 42, 'decorator from an enclosing function scope'
+
+
+def make_counter():
+    calls = []
+
+    def counted(func):
+        def wrapper(*args):
+            calls.append(args)
+            return func(*args)
+
+        return wrapper
+
+    @counted
+    def double(x):
+        return x * 2
+
+    double(1)
+    double(2)
+    return calls
+
+
+assert make_counter() == [(1,), (2,)], 'decorated closure'
+
+
+# === decorated methods ===
+def twice(func):
+    def wrapper(self):
+        return func(self) * 2
+
+    return wrapper
+
+
+class Box:
+    def __init__(self, value):
+        self.value = value
+
+    @twice
+    def doubled(self):
+        return self.value
+
+
+assert Box(4).doubled() == 8, 'decorated method'
+
+
+# === class decorators ===
+def add_greeting(cls):
+    cls.greeting = 'hi'
+    return cls
+
+
+@add_greeting
+class Greeter:
+    pass
+
+
+assert Greeter.greeting == 'hi', 'class decorator modifies the class'
+assert Greeter().greeting == 'hi', 'instances see the class decorator changes'
+
+
+# === async function decorators ===
+def identity(func):
+    return func
+
+
+@identity
+async def fetch():
+    return 'fetched'
+
+
+assert await fetch() == 'fetched', 'decorated async function'
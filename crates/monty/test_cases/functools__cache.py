@@ -0,0 +1,127 @@
+from functools import cache, cmp_to_key, lru_cache
+
+
+# === cache ===
+calls = []
+
+
+@cache
+def fib(n):
+    calls.append(n)
+    if n < 2:
+        return n
+    return fib(n - 1) + fib(n - 2)
+
+
+assert fib(30) == 832040, 'cached recursion'
+assert len(calls) == 31, 'each value computed once'
+assert fib(30) == 832040, 'cached result'
+assert len(calls) == 31, 'no new calls'
+info = fib.cache_info()
+assert info.hits == 29, 'hits'
+assert info.misses == 31, 'misses'
+assert info.maxsize is None, 'cache has no maxsize'
+assert info.currsize == 31, 'currsize'
+fib.cache_clear()
+assert fib.cache_info().currsize == 0, 'cache cleared'
+assert fib.cache_info().hits == 0, 'stats cleared'
+
+# === lru_cache ===
+seen = []
+
+
+@lru_cache(maxsize=2)
+def double(x):
+    seen.append(x)
+    return x * 2
+
+
+assert double(1) == 2, 'first call'
+assert double(2) == 4, 'second call'
+assert double(1) == 2, 'cached call'
+assert double(3) == 6, 'evicts least recently used'
+assert double(1) == 2, 'recently used is kept'
+assert double(2) == 4, 'evicted value is recomputed'
+assert seen == [1, 2, 3, 2], 'lru order'
+assert double.cache_info().maxsize == 2, 'maxsize'
+assert double.cache_info().currsize == 2, 'currsize bounded'
+
+
+@lru_cache(maxsize=10)
+def square(x):
+    seen.append(x)
+    return x * x
+
+
+seen.clear()
+for round in range(5):
+    for x in range(10):
+        # keeps 0 in the cache by using it between every other call
+        assert square(0) == 0, 'hot key'
+        assert square(x + round) == (x + round) ** 2, 'cached or computed square'
+assert seen == [0] + list(range(1, 14)), 'only keys beyond maxsize are computed again'
+assert square.cache_info().hits == 86, square.cache_info()
+assert square.cache_info().currsize == 10, 'currsize bounded by maxsize'
+
+
+@lru_cache
+def plain(x, y=0):
+    seen.append((x, y))
+    return x + y
+
+
+seen.clear()
+assert plain(1, y=2) == 3, 'keyword call'
+assert plain(1, y=2) == 3, 'cached keyword call'
+assert plain(1, 2) == 3, 'positional call is a different key'
+assert seen == [(1, 2), (1, 2)], 'keyword and positional keys'
+assert plain.cache_info().maxsize == 128, 'default maxsize'
+
+
+@lru_cache(maxsize=None)
+def unbounded(x):
+    return [x]
+
+
+assert unbounded(1) is unbounded(1), 'unbounded returns the same object'
+assert plain.__wrapped__(5) == 5, '__wrapped__ calls the function'
+assert callable(plain), 'wrapper is callable'
+
+try:
+    plain([1])
+    assert False, 'unhashable argument should raise'
+except TypeError as e:
+    assert str(e) == "unhashable type: 'list'", 'unhashable argument'
+
+
+class Circle:
+    def __init__(self, r):
+        self.r = r
+
+    @cache
+    def area(self):
+        seen.append(self.r)
+        return 3 * self.r * self.r
+
+
+seen.clear()
+c = Circle(2)
+assert c.area() == 12, 'cached method'
+assert c.area() == 12, 'cached method again'
+assert seen == [2], 'method computed once'
+
+# === cmp_to_key ===
+def by_length(a, b):
+    return len(a) - len(b)
+
+
+words = ['ccc', 'a', 'bb', 'dd', '']
+assert sorted(words, key=cmp_to_key(by_length)) == ['', 'a', 'bb', 'dd', 'ccc'], 'sorted with cmp_to_key'
+assert sorted(words, key=cmp_to_key(by_length), reverse=True) == ['ccc', 'bb', 'dd', 'a', ''], 'reverse is stable'
+assert min(words, key=cmp_to_key(by_length)) == '', 'min with cmp_to_key'
+assert max(words, key=cmp_to_key(by_length)) == 'ccc', 'max with cmp_to_key'
+assert max(['bb', 'aa'], key=cmp_to_key(by_length)) == 'bb', 'max keeps the first of equal values'
+nums = [3, 1, 2]
+nums.sort(key=cmp_to_key(lambda a, b: b - a))
+assert nums == [3, 2, 1], 'list.sort with cmp_to_key'
+assert min([], key=cmp_to_key(by_length), default=None) is None, 'min of empty with default'
@@ -0,0 +1,30 @@
+import functools
+from functools import cmp_to_key, lru_cache, partial, reduce
+
+
+def error(f, *args):
+    try:
+        f(*args)
+    except Exception as e:
+        return f'{type(e).__name__}: {e}'
+    assert False, f'{f} should have raised'
+
+
+assert error(reduce, lambda a, b: a, []) == (
+    'TypeError: reduce() of empty iterable with no initial value'
+), 'reduce of empty'
+assert error(reduce, lambda a, b: 1 / 0, [1, 2]) == 'ZeroDivisionError: division by zero', 'reduce function raises'
+assert error(partial) == "TypeError: type 'partial' takes at least one argument", 'partial without function'
+assert error(partial, 'x') == 'TypeError: the first argument must be callable', 'partial of str'
+assert error(lru_cache, 'x') == (
+    'TypeError: Expected first argument to be an integer, a callable, or None'
+), 'lru_cache of str'
+assert error(lambda: sorted([1, 2], key=cmp_to_key(lambda a, b: 'x'))) == (
+    "TypeError: '<' not supported between instances of 'str' and 'int'"
+), 'comparison returning str'
+assert error(lambda: sorted([1, 2], key=cmp_to_key(lambda a, b: 1 / 0))) == (
+    'ZeroDivisionError: division by zero'
+), 'comparison raises'
+nums = [3, 1, 2]
+error(lambda: nums.sort(key=cmp_to_key(lambda a, b: 1 / 0)))
+assert sorted(nums) == [1, 2, 3], 'list keeps its items when sorting fails'
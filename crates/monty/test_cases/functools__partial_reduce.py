@@ -0,0 +1,81 @@
+import functools
+from functools import partial, reduce, update_wrapper, wraps
+
+
+def error(f, *args):
+    try:
+        f(*args)
+    except Exception as e:
+        return f'{type(e).__name__}: {e}'
+    assert False, f'{f} should have raised'
+
+
+def power(base, exp=2, *, mod=None):
+    result = base**exp
+    if mod is not None:
+        result = result % mod
+    return result
+
+
+# === reduce ===
+assert reduce(lambda a, b: a + b, [1, 2, 3, 4]) == 10, 'reduce sum'
+assert reduce(lambda a, b: a * b, range(1, 6), 1) == 120, 'reduce with initial'
+assert reduce(max, [3, 9, 2]) == 9, 'reduce with builtin'
+assert reduce(lambda a, b: a + b, [], 7) == 7, 'reduce empty with initial'
+assert reduce(lambda a, b: a + b, [5]) == 5, 'reduce single value'
+assert reduce(lambda a, b: a + [b], 'ab', []) == ['a', 'b'], 'reduce into list'
+assert reduce(lambda a, b: a + b, (x * x for x in range(4))) == 14, 'reduce generator'
+assert error(reduce, lambda a, b: a, []) == (
+    'TypeError: reduce() of empty iterable with no initial value'
+), 'reduce empty'
+assert functools.reduce(lambda a, b: a - b, [10, 1, 2]) == 7, 'reduce through module'
+
+# === partial ===
+square = partial(power, exp=2)
+assert square(5) == 25, 'partial keyword'
+cube = partial(power, exp=3)
+assert cube(2) == 8, 'another partial'
+two_to = partial(power, 2)
+assert two_to(10) == 1024, 'partial positional'
+assert two_to(exp=3) == 8, 'partial positional and call keyword'
+assert partial(power, 3, mod=5)(2) == 4, 'partial keyword only'
+assert cube(2, exp=4) == 16, 'call keyword overrides partial keyword'
+assert partial(max, 5)(1, 9) == 9, 'partial of builtin'
+assert square.func is power, 'partial func'
+assert two_to.args == (2,), 'partial args'
+assert square.keywords == {'exp': 2}, 'partial keywords'
+nested = partial(partial(power, 2), exp=5)
+assert nested.func is power, 'nested partial flattened'
+assert nested.args == (2,), 'nested partial args'
+assert nested() == 32, 'nested partial call'
+assert list(map(partial(power, exp=3), [1, 2, 3])) == [1, 8, 27], 'partial in map'
+assert type(square).__name__ == 'partial', 'partial type'
+assert callable(square), 'partial is callable'
+assert error(partial, 1) == 'TypeError: the first argument must be callable', 'partial of non-callable'
+
+# === wraps ===
+def logged(func):
+    @wraps(func)
+    def wrapper(*args, **kwargs):
+        return ('logged', func(*args, **kwargs))
+
+    return wrapper
+
+
+@logged
+def add(a, b):
+    return a + b
+
+
+assert add(1, 2) == ('logged', 3), 'wraps keeps the wrapper'
+
+
+def inner():
+    return 1
+
+
+def outer():
+    return inner() + 1
+
+
+assert update_wrapper(outer, inner) is outer, 'update_wrapper returns the wrapper'
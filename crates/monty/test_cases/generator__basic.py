@@ -0,0 +1,329 @@
+# === Basic generator function ===
+def count_up(n):
+    i = 0
+    while i < n:
+        yield i
+        i += 1
+
+
+assert list(count_up(4)) == [0, 1, 2, 3], 'generator in list()'
+assert list(count_up(0)) == [], 'empty generator'
+
+# === Body doesn't run until first next() ===
+log = []
+
+
+def logged():
+    log.append('start')
+    yield 1
+    log.append('end')
+
+
+g = logged()
+assert log == [], 'body not started on call'
+assert next(g) == 1, 'first value'
+assert log == ['start'], 'body ran up to first yield'
+assert next(g, 'done') == 'done', 'next default when exhausted'
+assert log == ['start', 'end'], 'body ran to completion'
+assert next(g, 'again') == 'again', 'exhausted generator stays exhausted'
+
+# === StopIteration ===
+g = count_up(1)
+assert next(g) == 0, 'single value'
+try:
+    next(g)
+    assert False, 'should raise StopIteration'
+except StopIteration:
+    pass
+
+
+def returns_value():
+    yield 1
+    return 'result'
+
+
+g = returns_value()
+next(g)
+try:
+    next(g)
+    assert False, 'should raise StopIteration'
+except StopIteration as e:
+    assert str(e) == 'result', 'StopIteration carries return value'
+
+# === for loops ===
+total = 0
+for x in count_up(5):
+    total += x
+assert total == 10, 'for loop over generator'
+
+result = []
+for x in count_up(3):
+    for y in count_up(2):
+        result.append((x, y))
+assert result == [(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)], 'nested for loops'
+
+result = []
+for x in count_up(10):
+    if x == 3:
+        break
+    result.append(x)
+assert result == [0, 1, 2], 'break out of generator loop'
+
+for x in count_up(2):
+    pass
+else:
+    result.append('else')
+assert result[-1] == 'else', 'for-else over generator'
+
+# === Generator keeps its state across loops ===
+g = count_up(5)
+first = []
+for x in g:
+    first.append(x)
+    if x == 1:
+        break
+rest = list(g)
+assert first == [0, 1], 'first part'
+assert rest == [2, 3, 4], 'rest continues where the loop stopped'
+
+# === Generators are their own iterators ===
+g = count_up(3)
+assert iter(g) is g, 'iter(generator) is the generator'
+
+# === Arguments and closures ===
+def repeat(value, times=2):
+    for _ in range(times):
+        yield value
+
+
+assert list(repeat('a')) == ['a', 'a'], 'default argument'
+assert list(repeat('b', times=3)) == ['b', 'b', 'b'], 'keyword argument'
+
+
+def make_counter(step):
+    def gen(n):
+        for i in range(n):
+            yield i * step
+
+    return gen
+
+
+assert list(make_counter(10)(3)) == [0, 10, 20], 'generator closure'
+
+
+def outer():
+    values = []
+
+    def collect():
+        for i in range(3):
+            values.append(i)
+            yield i
+
+    return collect, values
+
+
+collect, values = outer()
+g = collect()
+next(g)
+next(g)
+assert values == [0, 1], 'generator writes to captured cell'
+
+# === Independent generators ===
+a = count_up(3)
+b = count_up(3)
+assert next(a) == 0, 'a first'
+assert next(a) == 1, 'a second'
+assert next(b) == 0, 'b is independent'
+
+# === yield in expressions ===
+def doubler():
+    x = 1
+    while True:
+        x = (yield x * 2) or x + 1
+
+
+g = doubler()
+assert next(g) == 2, 'first yield'
+assert next(g) == 4, 'yield result None falls back'
+
+
+def yield_none():
+    yield
+
+
+assert list(yield_none()) == [None], 'bare yield yields None'
+
+# === try/except/finally inside generators ===
+def guarded():
+    try:
+        yield 1
+        raise ValueError('inside')
+    except ValueError:
+        yield 'caught'
+    finally:
+        yield 'finally'
+
+
+assert list(guarded()) == [1, 'caught', 'finally'], 'yield in try/except/finally'
+
+
+def yield_in_except():
+    try:
+        raise KeyError('k')
+    except KeyError:
+        yield 1
+        yield 2
+        raise
+
+
+g = yield_in_except()
+assert next(g) == 1, 'yield in except block'
+assert next(g) == 2, 'second yield in except block'
+try:
+    next(g)
+    assert False, 'bare raise should re-raise KeyError'
+except KeyError:
+    pass
+
+# === Exceptions propagate to the consumer ===
+def failing():
+    yield 1
+    raise ValueError('boom')
+
+
+result = []
+try:
+    for x in failing():
+        result.append(x)
+except ValueError as e:
+    result.append(str(e))
+assert result == [1, 'boom'], 'exception from generator in for loop'
+
+try:
+    list(failing())
+    assert False, 'list() should propagate the exception'
+except ValueError as e:
+    assert str(e) == 'boom', 'exception from generator in list()'
+
+g = failing()
+next(g)
+try:
+    next(g)
+except ValueError:
+    pass
+assert list(g) == [], 'generator is finished after raising'
+
+
+def raises_stop_iteration():
+    yield 1
+    raise StopIteration
+
+
+try:
+    list(raises_stop_iteration())
+    assert False, 'StopIteration inside a generator should become RuntimeError'
+except RuntimeError as e:
+    assert str(e) == 'generator raised StopIteration', 'RuntimeError message'
+
+# === Generator already executing ===
+def self_iterating():
+    yield next(me)
+
+
+me = self_iterating()
+try:
+    next(me)
+    assert False, 'should raise ValueError'
+except ValueError as e:
+    assert str(e) == 'generator already executing', 'already executing message'
+
+# === Builtins consuming generators ===
+assert tuple(count_up(3)) == (0, 1, 2), 'tuple()'
+assert set(repeat(1, 3)) == {1}, 'set()'
+assert sorted(count_up(3)) == [0, 1, 2], 'sorted()'
+assert max(count_up(4)) == 3, 'max()'
+assert min(count_up(4)) == 0, 'min()'
+assert list(enumerate(repeat('x'))) == [(0, 'x'), (1, 'x')], 'enumerate()'
+assert list(zip(count_up(2), repeat('y'))) == [(0, 'y'), (1, 'y')], 'zip()'
+assert list(enumerate(count_up(2), 5)) == [(5, 0), (6, 1)], 'enumerate() with start'
+assert list(zip(count_up(3), 'ab', [7, 8, 9])) == [(0, 'a', 7), (1, 'b', 8)], 'zip() with other iterables'
+
+
+def naturals():
+    n = 0
+    while True:
+        yield n
+        n += 1
+
+
+assert next(enumerate(naturals())) == (0, 0), 'enumerate() of an infinite generator is lazy'
+assert list(zip(naturals(), 'xy')) == [(0, 'x'), (1, 'y')], 'zip() with an infinite generator is lazy'
+
+events = []
+
+
+def logged(n):
+    for i in range(n):
+        events.append(f'yield {i}')
+        yield i
+
+
+for i, value in enumerate(logged(2)):
+    events.append(f'body {i}')
+assert events == ['yield 0', 'body 0', 'yield 1', 'body 1'], 'enumerate() interleaves with the loop body'
+events.clear()
+for a, b in zip(logged(2), 'ab'):
+    events.append(f'body {b}')
+assert events == ['yield 0', 'body a', 'yield 1', 'body b'], 'zip() interleaves with the loop body'
+
+assert sum(count_up(5)) == 10, 'sum()'
+assert sum(count_up(3), 10) == 13, 'sum() with start'
+assert any(count_up(3)), 'any()'
+assert not all(count_up(3)), 'all()'
+assert dict(zip(count_up(2), count_up(2))) == {0: 0, 1: 1}, 'dict() from zip of generators'
+assert ', '.join(repeat('a', 3)) == 'a, a, a', 'str.join()'
+assert 2 in count_up(3), 'in operator'
+assert 5 not in count_up(3), 'not in operator'
+
+a, b, c = count_up(3)
+assert (a, b, c) == (0, 1, 2), 'unpacking a generator'
+first, *rest = count_up(4)
+assert first == 0 and rest == [1, 2, 3], 'starred unpacking'
+assert [*count_up(2), 9] == [0, 1, 9], 'star in list display'
+
+lst = [0]
+lst.extend(count_up(2))
+assert lst == [0, 0, 1], 'list.extend()'
+s = {9}
+s.update(count_up(2))
+assert s == {9, 0, 1}, 'set.update()'
+
+# === Builtins and methods consuming part of their arguments ===
+from collections import defaultdict, namedtuple
+
+assert bytearray(count_up(3)) == bytearray(b'\x00\x01\x02'), 'bytearray()'
+assert dict.fromkeys(count_up(2)) == {0: None, 1: None}, 'dict.fromkeys()'
+g = count_up(1)
+assert dict.fromkeys('a', g)['a'] is g, 'dict.fromkeys() keeps a generator value'
+dd = defaultdict(list, zip(count_up(2), 'ab'))
+assert dd == {0: 'a', 1: 'b'}, 'defaultdict() with a generator of pairs'
+Point = namedtuple('Point', (name for name in 'xy'))
+assert Point(1, 2).y == 2, 'namedtuple() field names from a generator'
+
+try:
+    reversed(count_up(2))
+    assert False, 'reversed() of a generator should raise TypeError'
+except TypeError as e:
+    assert str(e) == "'generator' object is not reversible", str(e)
+
+
+class Collector:
+    def extend(self, items):
+        return items
+
+
+g = count_up(2)
+assert Collector().extend(g) is g, 'methods of user-defined classes get generators unchanged'
+
+# === repr ===
+assert repr(count_up(1)).startswith('<generator object count_up'), 'generator repr'
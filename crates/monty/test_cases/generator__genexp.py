@@ -0,0 +1,80 @@
+# === Generator expressions are lazy ===
+log = []
+
+
+def record(x):
+    log.append(x)
+    return x
+
+
+g = (record(x) for x in range(3))
+assert log == [], 'nothing evaluated on creation'
+assert next(g) == 0, 'first value'
+assert log == [0], 'one element evaluated'
+assert list(g) == [1, 2], 'remaining values'
+assert log == [0, 1, 2], 'all elements evaluated'
+assert list(g) == [], 'exhausted'
+
+# === The outermost iterable is evaluated immediately ===
+def source():
+    log.append('source')
+    return [1, 2]
+
+
+log = []
+g = (x for x in source())
+assert log == ['source'], 'outer iterable evaluated eagerly'
+
+try:
+    (x for x in 5)
+    assert False, 'non-iterable should fail at creation'
+except TypeError:
+    pass
+
+# === Lazy consumption by builtins ===
+assert sum(x for x in range(1000000)) == 499999500000, 'sum over a large range'
+assert any(x > 10 for x in range(1000000000)), 'any() stops early'
+assert not all(x < 10 for x in range(1000000000)), 'all() stops early'
+assert next(x for x in range(1000000000) if x * x > 50) == 8, 'next() stops early'
+
+# === Conditions and nesting ===
+assert list(x for x in range(10) if x % 3 == 0) == [0, 3, 6, 9], 'condition'
+assert list(x * y for x in range(1, 3) for y in range(1, 3)) == [1, 2, 2, 4], 'nested loops'
+assert list(x for x in range(20) if x % 2 if x % 3) == [1, 5, 7, 11, 13, 17, 19], 'multiple conditions'
+assert list((a, b) for a, b in [(1, 2), (3, 4)]) == [(1, 2), (3, 4)], 'unpacking target'
+
+# === Captures enclosing variables ===
+def scaled(values, factor):
+    return list(v * factor for v in values)
+
+
+assert scaled([1, 2], 3) == [3, 6], 'captures function local'
+
+offset = 100
+g = (x + offset for x in range(2))
+offset = 200
+assert list(g) == [200, 201], 'captured variables are read when the generator runs'
+
+# === Iterating a generator expression ===
+total = 0
+for x in (y * 2 for y in range(4)):
+    total += x
+assert total == 12, 'for loop over generator expression'
+
+g = (x for x in range(3))
+assert iter(g) is g, 'generator expression is its own iterator'
+
+# === Chained generator expressions ===
+evens = (x for x in range(10) if x % 2 == 0)
+squares = (x * x for x in evens)
+assert list(squares) == [0, 4, 16, 36, 64], 'generator over generator'
+
+# === Builtins ===
+assert max(len(w) for w in ['a', 'abc', 'ab']) == 3, 'max()'
+assert min(len(w) for w in ['a', 'abc', 'ab']) == 1, 'min()'
+assert sorted(-x for x in range(3)) == [-2, -1, 0], 'sorted()'
+assert tuple(str(x) for x in range(3)) == ('0', '1', '2'), 'tuple()'
+assert dict((x, x * x) for x in range(3)) == {0: 0, 1: 1, 2: 4}, 'dict()'
+assert '-'.join(str(x) for x in range(3)) == '0-1-2', 'str.join()'
+assert set(x % 2 for x in range(5)) == {0, 1}, 'set()'
+assert sum((x for x in [1.5, 2.5]), 1) == 5.0, 'sum() with start'
@@ -0,0 +1,184 @@
+# === send() ===
+def accumulator():
+    total = 0
+    while True:
+        value = yield total
+        if value is None:
+            break
+        total += value
+    return total
+
+
+g = accumulator()
+assert g.send(None) == 0, 'priming send(None) is like next()'
+assert g.send(5) == 5, 'send value'
+assert g.send(10) == 15, 'send another value'
+try:
+    g.send(None)
+    assert False, 'should raise StopIteration'
+except StopIteration as e:
+    assert str(e) == '15', 'StopIteration carries return value'
+
+g = accumulator()
+try:
+    g.send(1)
+    assert False, 'should raise TypeError'
+except TypeError as e:
+    assert str(e) == "can't send non-None value to a just-started generator", 'just-started message'
+
+
+def echo():
+    received = []
+    while len(received) < 3:
+        received.append((yield len(received)))
+    return received
+
+
+g = echo()
+next(g)
+g.send('a')
+g.send('b')
+try:
+    g.send('c')
+except StopIteration:
+    pass
+
+# === throw() ===
+def catcher():
+    while True:
+        try:
+            yield 'waiting'
+        except ValueError:
+            yield 'caught'
+
+
+g = catcher()
+assert next(g) == 'waiting', 'first yield'
+assert g.throw(ValueError) == 'caught', 'throw exception type, caught inside'
+assert next(g) == 'waiting', 'loop continues after catching'
+assert g.throw(ValueError('instance')) == 'caught', 'throw exception instance'
+
+try:
+    g.throw(KeyError('not caught'))
+    assert False, 'uncaught thrown exception should propagate'
+except KeyError:
+    pass
+assert next(g, 'finished') == 'finished', 'generator finished after uncaught throw'
+
+
+def cleanup_on_throw():
+    try:
+        yield 1
+    finally:
+        log.append('cleanup')
+
+
+log = []
+g = cleanup_on_throw()
+next(g)
+try:
+    g.throw(RuntimeError)
+except RuntimeError:
+    pass
+assert log == ['cleanup'], 'finally runs when exception is thrown in'
+
+def count_up(n):
+    for i in range(n):
+        yield i
+
+
+g = count_up(3)
+try:
+    g.throw(IndexError)
+    assert False, 'throw into unstarted generator raises directly'
+except IndexError:
+    pass
+assert list(g) == [], 'unstarted generator is closed by throw'
+
+g = count_up(3)
+try:
+    g.throw(1)
+    assert False, 'throwing a non-exception should fail'
+except TypeError as e:
+    assert str(e) == 'exceptions must be classes or instances deriving from BaseException, not int', 'throw message'
+
+# === close() ===
+log = []
+
+
+def closable():
+    try:
+        yield 1
+        yield 2
+    finally:
+        log.append('closed')
+
+
+g = closable()
+assert next(g) == 1, 'first value before close'
+assert g.close() is None, 'close returns None'
+assert log == ['closed'], 'finally runs on close'
+assert list(g) == [], 'closed generator is exhausted'
+assert g.close() is None, 'closing twice is fine'
+
+g = closable()
+g.close()
+assert log == ['closed'], 'closing an unstarted generator does not run it'
+
+
+def catches_generator_exit():
+    try:
+        yield 1
+    except GeneratorExit:
+        log.append('exit')
+        raise
+
+
+log = []
+g = catches_generator_exit()
+next(g)
+g.close()
+assert log == ['exit'], 'GeneratorExit raised inside generator'
+
+
+def ignores_generator_exit():
+    try:
+        yield 1
+    except GeneratorExit:
+        pass
+    yield 2
+
+
+g = ignores_generator_exit()
+next(g)
+try:
+    g.close()
+    assert False, 'ignoring GeneratorExit should raise RuntimeError'
+except RuntimeError as e:
+    assert str(e) == 'generator ignored GeneratorExit', 'ignored GeneratorExit message'
+
+
+def returns_on_close():
+    try:
+        yield 1
+    except GeneratorExit:
+        return 'closing value'
+
+
+g = returns_on_close()
+next(g)
+assert g.close() == 'closing value', 'close returns the return value'
+
+# === GeneratorExit isn't an Exception ===
+def broad_except():
+    try:
+        yield 1
+    except Exception:
+        log.append('wrong')
+
+
+log = []
+g = broad_except()
+next(g)
+g.close()
+assert log == [], 'except Exception does not catch GeneratorExit'
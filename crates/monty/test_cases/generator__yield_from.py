@@ -0,0 +1,112 @@
+# === Delegating to a generator ===
+def inner():
+    yield 1
+    yield 2
+    return 'inner done'
+
+
+def outer():
+    result = yield from inner()
+    yield result
+
+
+assert list(outer()) == [1, 2, 'inner done'], 'yield from forwards values and returns result'
+
+# === Delegating to other iterables ===
+def from_iterables():
+    yield from [1, 2]
+    yield from (3, 4)
+    yield from range(5, 7)
+    yield from 'ab'
+    result = yield from []
+    yield result
+
+
+assert list(from_iterables()) == [1, 2, 3, 4, 5, 6, 'a', 'b', None], 'yield from iterables'
+
+# === Recursive delegation ===
+def flatten(items):
+    for item in items:
+        if isinstance(item, list):
+            yield from flatten(item)
+        else:
+            yield item
+
+
+assert list(flatten([1, [2, [3, 4]], [], 5])) == [1, 2, 3, 4, 5], 'recursive yield from'
+
+
+def tree(depth):
+    if depth == 0:
+        yield 'leaf'
+        return
+    yield from tree(depth - 1)
+    yield depth
+
+
+assert list(tree(3)) == ['leaf', 1, 2, 3], 'nested delegation'
+
+# === send() is forwarded to the delegate ===
+def adder():
+    total = 0
+    while True:
+        value = yield total
+        if value is None:
+            return total
+        total += value
+
+
+def delegator():
+    result = yield from adder()
+    yield 'total', result
+
+
+g = delegator()
+assert next(g) == 0, 'prime through delegator'
+assert g.send(3) == 3, 'send forwarded'
+assert g.send(4) == 7, 'send forwarded again'
+assert g.send(None) == ('total', 7), 'delegate return value'
+
+# === Exceptions propagate out of the delegate ===
+def failing():
+    yield 1
+    raise ValueError('inner failure')
+
+
+def handles_failure():
+    try:
+        yield from failing()
+    except ValueError as e:
+        yield str(e)
+
+
+assert list(handles_failure()) == [1, 'inner failure'], 'exception from delegate'
+
+# === Driving with a for loop ===
+result = []
+for x in outer():
+    result.append(x)
+assert result == [1, 2, 'inner done'], 'for loop over delegating generator'
+
+# === Delegating to an exhausted generator ===
+def exhausted_delegate():
+    g = inner()
+    list(g)
+    result = yield from g
+    yield result
+
+
+assert list(exhausted_delegate()) == [None], 'yield from exhausted generator'
+
+# === Non-None send to a non-generator delegate ===
+def list_delegate():
+    yield from [1, 2]
+
+
+g = list_delegate()
+next(g)
+try:
+    g.send('x')
+    assert False, 'should raise AttributeError'
+except AttributeError:
+    pass
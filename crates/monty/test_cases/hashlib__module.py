@@ -0,0 +1,63 @@
+import hashlib
+
+# === hexdigest() of the constructors ===
+assert hashlib.md5(b'abc').hexdigest() == '900150983cd24fb0d6963f7d28e17f72', 'md5'
+assert hashlib.sha1(b'abc').hexdigest() == 'a9993e364706816aba3e25717850c26c9cd0d89d', 'sha1'
+assert hashlib.sha256(b'abc').hexdigest() == (
+    'ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad'
+), 'sha256'
+assert hashlib.sha512(b'abc').hexdigest() == (
+    'ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a'
+    '2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f'
+), 'sha512'
+assert hashlib.md5().hexdigest() == 'd41d8cd98f00b204e9800998ecf8427e', 'md5 of nothing'
+assert hashlib.sha256(b'a' * 1000).hexdigest() == (
+    '41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3'
+), 'sha256 of several blocks'
+assert hashlib.sha1(bytearray(b'abc')).hexdigest() == 'a9993e364706816aba3e25717850c26c9cd0d89d', 'bytearray'
+assert hashlib.md5(b'abc', usedforsecurity=False).hexdigest() == '900150983cd24fb0d6963f7d28e17f72', 'usedforsecurity'
+
+# === digest() ===
+assert hashlib.md5(b'').digest() == b'\xd4\x1d\x8c\xd9\x8f\x00\xb2\x04\xe9\x80\t\x98\xec\xf8B~', 'md5 digest'
+assert len(hashlib.sha512().digest()) == 64, 'sha512 digest length'
+
+# === update() and copy() ===
+h = hashlib.sha256()
+h.update(b'a')
+h.update(b'bc')
+assert h.hexdigest() == hashlib.sha256(b'abc').hexdigest(), 'update in pieces'
+assert h.hexdigest() == hashlib.sha256(b'abc').hexdigest(), 'digest leaves the hash unchanged'
+c = h.copy()
+c.update(b'd')
+assert c.hexdigest() == hashlib.sha256(b'abcd').hexdigest(), 'copy is updated'
+assert h.hexdigest() == hashlib.sha256(b'abc').hexdigest(), 'original is unchanged by copy updates'
+
+# === new() ===
+assert hashlib.new('md5', b'abc').hexdigest() == hashlib.md5(b'abc').hexdigest(), 'new md5'
+assert hashlib.new('SHA256', data=b'abc').hexdigest() == hashlib.sha256(b'abc').hexdigest(), 'new is case-insensitive'
+assert hashlib.new('sha1').hexdigest() == hashlib.sha1().hexdigest(), 'new without data'
+
+# === attributes ===
+h = hashlib.sha256()
+assert h.name == 'sha256', 'name'
+assert h.digest_size == 32, 'digest_size'
+assert h.block_size == 64, 'block_size'
+assert hashlib.md5().digest_size == 16, 'md5 digest_size'
+assert hashlib.sha1().digest_size == 20, 'sha1 digest_size'
+assert hashlib.sha512().block_size == 128, 'sha512 block_size'
+assert hashlib.new('SHA512').name == 'sha512', 'name is lowercase'
+
+
+# === errors ===
+def error(exc_type, f, *args):
+    try:
+        f(*args)
+    except exc_type as e:
+        return str(e)
+    assert False, f'{f} should have raised'
+
+
+assert error(TypeError, hashlib.md5, 'abc') == 'Strings must be encoded before hashing', 'md5 of str'
+assert error(TypeError, hashlib.sha256, 1) == 'object supporting the buffer API required', 'sha256 of int'
+assert error(TypeError, lambda: hashlib.sha1().update('abc')) == 'Strings must be encoded before hashing', 'update with str'
+assert error(ValueError, hashlib.new, 'sha3') == 'unsupported hash type sha3', 'new of unknown algorithm'
@@ -0,0 +1,21 @@
+from itertools import batched
+
+
+def error(f, *args):
+    try:
+        f(*args)
+    except Exception as e:
+        return f'{type(e).__name__}: {e}'
+    assert False, f'{f} should have raised'
+
+
+# === batched ===
+assert list(batched('abcdefg', 3)) == [('a', 'b', 'c'), ('d', 'e', 'f'), ('g',)], 'batched'
+assert list(batched(range(4), 2)) == [(0, 1), (2, 3)], 'batched exactly'
+assert list(batched([], 2)) == [], 'batched empty'
+assert list(batched((x for x in range(3)), 5)) == [(0, 1, 2)], 'batched generator'
+assert list(batched(range(4), 2, strict=True)) == [(0, 1), (2, 3)], 'strict complete batches'
+assert error(lambda: list(batched(range(3), 2, strict=True))) == (
+    'ValueError: batched(): incomplete batch'
+), 'strict incomplete batch'
+assert error(batched, 'ab', 0) == 'ValueError: n must be at least one', 'batch size zero'
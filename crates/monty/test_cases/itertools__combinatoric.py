@@ -0,0 +1,70 @@
+from itertools import combinations, islice, permutations, product
+
+
+def error(f, *args):
+    try:
+        f(*args)
+    except Exception as e:
+        return f'{type(e).__name__}: {e}'
+    assert False, f'{f} should have raised'
+
+
+# === product ===
+assert list(product('ab', [1, 2])) == [('a', 1), ('a', 2), ('b', 1), ('b', 2)], 'product of two'
+assert list(product([0, 1], repeat=2)) == [(0, 0), (0, 1), (1, 0), (1, 1)], 'product repeat'
+assert list(product()) == [()], 'product of nothing'
+assert list(product('ab', [])) == [], 'product with an empty pool'
+assert list(product([1], repeat=0)) == [()], 'product repeat zero'
+assert len(list(product(range(3), range(4), range(5)))) == 60, 'product length'
+assert list(product('ab', 'c', repeat=2))[:3] == [
+    ('a', 'c', 'a', 'c'),
+    ('a', 'c', 'b', 'c'),
+    ('b', 'c', 'a', 'c'),
+], 'product of pools repeated'
+assert error(lambda: product([1], repeat=-1)) == 'ValueError: repeat argument cannot be negative', 'negative repeat'
+
+# === permutations ===
+assert list(permutations([1, 2, 3])) == [
+    (1, 2, 3),
+    (1, 3, 2),
+    (2, 1, 3),
+    (2, 3, 1),
+    (3, 1, 2),
+    (3, 2, 1),
+], 'permutations of all'
+assert list(permutations('abc', 2)) == [
+    ('a', 'b'),
+    ('a', 'c'),
+    ('b', 'a'),
+    ('b', 'c'),
+    ('c', 'a'),
+    ('c', 'b'),
+], 'permutations of two'
+assert list(permutations('ab', 3)) == [], 'permutations longer than the pool'
+assert list(permutations('ab', 0)) == [()], 'permutations of zero'
+assert list(permutations([])) == [()], 'permutations of empty'
+assert len(list(permutations(range(6)))) == 720, 'permutations length'
+assert error(permutations, 'ab', -1) == 'ValueError: r must be non-negative', 'negative r'
+
+# === combinations ===
+assert list(combinations('abcd', 2)) == [
+    ('a', 'b'),
+    ('a', 'c'),
+    ('a', 'd'),
+    ('b', 'c'),
+    ('b', 'd'),
+    ('c', 'd'),
+], 'combinations of two'
+assert list(combinations(range(4), 3)) == [(0, 1, 2), (0, 1, 3), (0, 2, 3), (1, 2, 3)], 'combinations of three'
+assert list(combinations('ab', 0)) == [()], 'combinations of zero'
+assert list(combinations('ab', 3)) == [], 'combinations longer than the pool'
+assert len(list(combinations(range(10), 4))) == 210, 'combinations length'
+assert error(combinations, 'ab', -1) == 'ValueError: r must be non-negative', 'negative r'
+
+# === lazy ===
+big = product(range(1000), repeat=10)
+assert next(big) == (0,) * 10, 'huge product is lazy'
+assert list(islice(permutations(range(20)), 2)) == [
+    tuple(range(20)),
+    tuple(range(18)) + (19, 18),
+], 'huge permutations are lazy'
@@ -0,0 +1,43 @@
+import itertools
+from itertools import count, cycle, islice, repeat
+
+
+def error(f, *args):
+    try:
+        f(*args)
+    except Exception as e:
+        return f'{type(e).__name__}: {e}'
+    assert False, f'{f} should have raised'
+
+
+# === count ===
+assert list(islice(count(), 4)) == [0, 1, 2, 3], 'count from zero'
+assert list(islice(count(10, 5), 3)) == [10, 15, 20], 'count with step'
+assert list(islice(count(1.5, 0.5), 3)) == [1.5, 2.0, 2.5], 'count of floats'
+assert list(islice(count(step=-1), 3)) == [0, -1, -2], 'count step keyword'
+c = count(3)
+assert next(c) == 3, 'next of count'
+assert next(c) == 4, 'count advances'
+assert error(count, 'a') == 'TypeError: a number is required', 'count of str'
+
+# === cycle ===
+assert list(islice(cycle('ab'), 5)) == ['a', 'b', 'a', 'b', 'a'], 'cycle of str'
+assert list(cycle([])) == [], 'cycle of empty list'
+assert list(islice(cycle(x * 2 for x in range(3)), 7)) == [0, 2, 4, 0, 2, 4, 0], 'cycle of generator'
+
+# === repeat ===
+assert list(repeat('x', 3)) == ['x', 'x', 'x'], 'repeat with times'
+assert list(repeat(1, -2)) == [], 'repeat with negative times'
+assert list(islice(repeat(None), 2)) == [None, None], 'repeat forever'
+assert list(repeat(object=5, times=2)) == [5, 5], 'repeat keywords'
+assert error(repeat, 1, 'a') == "TypeError: 'str' object cannot be interpreted as an integer", 'repeat times str'
+
+# === loops over infinite iterators ===
+total = 0
+for i in count(1):
+    if i > 10:
+        break
+    total += i
+assert total == 55, 'for loop over count breaks'
+assert next(x for x in count() if x * x > 50) == 8, 'generator over count'
+assert sum(islice(itertools.count(), 100)) == 4950, 'module attribute access'
@@ -0,0 +1,74 @@
+from itertools import accumulate, chain, groupby, islice, pairwise, zip_longest
+
+
+def error(f, *args):
+    try:
+        f(*args)
+    except Exception as e:
+        return f'{type(e).__name__}: {e}'
+    assert False, f'{f} should have raised'
+
+
+def gen(n):
+    for i in range(n):
+        yield i
+
+
+# === chain ===
+assert list(chain([1, 2], (3,), 'ab')) == [1, 2, 3, 'a', 'b'], 'chain of iterables'
+assert list(chain()) == [], 'empty chain'
+assert list(chain([], [1], [])) == [1], 'chain skips empty iterables'
+assert list(chain(gen(2), gen(3))) == [0, 1, 0, 1, 2], 'chain of generators'
+assert list(chain.from_iterable(['ab', [1]])) == ['a', 'b', 1], 'chain.from_iterable'
+assert list(chain.from_iterable([x] * x for x in range(4))) == [1, 2, 2, 3, 3, 3], 'from_iterable of generator'
+c = chain([1], [2])
+assert iter(c) is c, 'chain is its own iterator'
+assert next(c) == 1, 'next of chain'
+
+# === islice ===
+assert list(islice('abcdefg', 2)) == ['a', 'b'], 'islice stop'
+assert list(islice('abcdefg', 2, 4)) == ['c', 'd'], 'islice start stop'
+assert list(islice('abcdefg', 2, None)) == ['c', 'd', 'e', 'f', 'g'], 'islice no stop'
+assert list(islice('abcdefg', 0, None, 2)) == ['a', 'c', 'e', 'g'], 'islice step'
+assert list(islice('abc', 5, 10)) == [], 'islice past the end'
+assert list(islice(gen(10), 1, 8, 3)) == [1, 4, 7], 'islice of generator'
+it = iter(range(10))
+assert list(islice(it, 3)) == [0, 1, 2], 'islice takes values'
+assert next(it) == 3, 'islice leaves the rest'
+assert error(islice, [1], -1) == (
+    'ValueError: Stop argument for islice() must be None or an integer: 0 <= x <= sys.maxsize.'
+), 'islice negative stop'
+assert error(islice, [1], -1, 2) == (
+    'ValueError: Indices for islice() must be None or an integer: 0 <= x <= sys.maxsize.'
+), 'islice negative start'
+assert error(islice, [1], 0, 2, 0) == (
+    'ValueError: Step for islice() must be a positive integer or None.'
+), 'islice zero step'
+
+# === accumulate ===
+assert list(accumulate([1, 2, 3, 4])) == [1, 3, 6, 10], 'running sum'
+assert list(accumulate([3, 1, 4, 1, 5], max)) == [3, 3, 4, 4, 5], 'running max'
+assert list(accumulate([1, 2, 3], lambda a, b: a * b)) == [1, 2, 6], 'running product'
+assert list(accumulate([1, 2], initial=10)) == [10, 11, 13], 'accumulate initial'
+assert list(accumulate([], initial=0)) == [0], 'accumulate only initial'
+assert list(accumulate([])) == [], 'accumulate empty'
+assert list(accumulate(['a', 'b', 'c'])) == ['a', 'ab', 'abc'], 'accumulate strings'
+
+# === groupby ===
+groups = [(k, list(g)) for k, g in groupby('aaabbc')]
+assert groups == [('a', ['a', 'a', 'a']), ('b', ['b', 'b']), ('c', ['c'])], 'groupby runs'
+assert [k for k, _ in groupby([1, 1, 2, 1])] == [1, 2, 1], 'groupby keys'
+by_parity = [(k, list(g)) for k, g in groupby([2, 4, 1, 3, 6], key=lambda x: x % 2)]
+assert by_parity == [(0, [2, 4]), (1, [1, 3]), (0, [6])], 'groupby key function'
+assert list(groupby([])) == [], 'groupby empty'
+
+# === pairwise ===
+assert list(pairwise('abcd')) == [('a', 'b'), ('b', 'c'), ('c', 'd')], 'pairwise'
+assert list(pairwise([1])) == [], 'pairwise of one value'
+assert list(pairwise(gen(3))) == [(0, 1), (1, 2)], 'pairwise of generator'
+
+# === zip_longest ===
+assert list(zip_longest('ab', 'xyz')) == [('a', 'x'), ('b', 'y'), (None, 'z')], 'zip_longest'
+assert list(zip_longest([1], [2, 3], fillvalue=0)) == [(1, 2), (0, 3)], 'zip_longest fillvalue'
+assert list(zip_longest()) == [], 'zip_longest of nothing'
+assert list(zip_longest([1, 2])) == [(1,), (2,)], 'zip_longest of one iterable'
@@ -0,0 +1,138 @@
+import json
+from json import dumps
+
+# === Scalars ===
+assert json.dumps(None) == 'null', 'None'
+assert json.dumps(True) == 'true', 'True'
+assert json.dumps(False) == 'false', 'False'
+assert json.dumps(42) == '42', 'int'
+assert json.dumps(-(10**20)) == '-100000000000000000000', 'big int'
+assert json.dumps(1.5) == '1.5', 'float'
+assert json.dumps(1.0) == '1.0', 'whole float'
+assert json.dumps(float('nan')) == 'NaN', 'nan'
+assert json.dumps(float('inf')) == 'Infinity', 'infinity'
+assert json.dumps(float('-inf')) == '-Infinity', 'negative infinity'
+assert json.dumps('hi') == '"hi"', 'string'
+
+# === String escaping ===
+assert json.dumps('a"b\\c') == '"a\\"b\\\\c"', 'quote and backslash'
+assert json.dumps('\n\r\t\b\f\x01') == '"\\n\\r\\t\\b\\f\\u0001"', 'control characters'
+assert json.dumps('é中😀\x7f') == '"\\u00e9\\u4e2d\\ud83d\\ude00\\u007f"', 'ensure_ascii escapes'
+assert json.dumps('é中😀\x01', ensure_ascii=False) == '"é中😀\\u0001"', 'ensure_ascii=False'
+assert json.dumps('/') == '"/"', 'slash is not escaped'
+
+# === Containers ===
+assert json.dumps([]) == '[]', 'empty list'
+assert json.dumps({}) == '{}', 'empty dict'
+assert json.dumps([1, 'a', None, [True]]) == '[1, "a", null, [true]]', 'nested list'
+assert json.dumps((1, 2)) == '[1, 2]', 'tuple as array'
+assert json.dumps({'a': 1, 'b': [2, {'c': None}]}) == '{"a": 1, "b": [2, {"c": null}]}', 'nested dict'
+assert json.dumps({'b': 1, 'a': 2}) == '{"b": 1, "a": 2}', 'dict keeps key order'
+assert dumps([3]) == '[3]', 'imported dumps'
+
+# === Non-string keys ===
+assert json.dumps({1: 'a', 2.5: 'b', False: 'c', None: 'd'}) == '{"1": "a", "2.5": "b", "false": "c", "null": "d"}', (
+    'keys are converted to strings'
+)
+
+# === sort_keys ===
+assert json.dumps({'b': 1, 'a': 2, 'c': 3}, sort_keys=True) == '{"a": 2, "b": 1, "c": 3}', 'sort_keys'
+assert json.dumps({10: 1, 2: 2}, sort_keys=True) == '{"2": 2, "10": 1}', 'sort_keys compares original keys'
+assert json.dumps({'x': {'b': 1, 'a': 2}}, sort_keys=True) == '{"x": {"a": 2, "b": 1}}', 'sort_keys nested'
+
+# === separators ===
+assert json.dumps([1, {'a': 2}], separators=(',', ':')) == '[1,{"a":2}]', 'compact separators'
+assert json.dumps((1, 'x'), separators=[';', '=']) == '[1;"x"]', 'separators as list'
+
+# === indent ===
+assert json.dumps([1, [2, 3], {}], indent=2) == '[\n  1,\n  [\n    2,\n    3\n  ],\n  {}\n]', 'int indent'
+assert json.dumps({'a': [1]}, indent='\t') == '{\n\t"a": [\n\t\t1\n\t]\n}', 'str indent'
+assert json.dumps([1, 2], indent=0) == '[\n1,\n2\n]', 'zero indent'
+assert json.dumps({'a': 1, 'b': 2}, indent=1, separators=(', ', ':')) == '{\n "a":1, \n "b":2\n}', (
+    'indent with separators'
+)
+assert json.dumps([], indent=2) == '[]', 'empty list with indent'
+
+# === default ===
+
+
+class Point:
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+
+def encode_point(obj):
+    if isinstance(obj, Point):
+        return {'x': obj.x, 'y': obj.y}
+    raise TypeError('cannot encode')
+
+
+assert json.dumps([Point(1, 2)], default=encode_point) == '[{"x": 1, "y": 2}]', 'default for instances'
+assert json.dumps({1, 2}, default=sorted) == '[1, 2]', 'default with a builtin'
+assert json.dumps({'p': Point(0, Point(3, 4))}, default=encode_point) == '{"p": {"x": 0, "y": {"x": 3, "y": 4}}}', (
+    'default results are encoded recursively'
+)
+assert json.dumps(b'ab', default=lambda b: b.decode()) == '"ab"', 'default with a lambda'
+
+# === Errors ===
+try:
+    json.dumps(Point(1, 2))
+    assert False, 'instance without default should error'
+except TypeError as e:
+    assert str(e) == 'Object of type Point is not JSON serializable', f'instance message, error: {e}'
+
+try:
+    json.dumps({1, 2})
+    assert False, 'set should error'
+except TypeError as e:
+    assert str(e) == 'Object of type set is not JSON serializable', f'set message, error: {e}'
+
+try:
+    json.dumps({(1, 2): 3})
+    assert False, 'tuple key should error'
+except TypeError as e:
+    assert str(e) == 'keys must be str, int, float, bool or None, not tuple', f'key message, error: {e}'
+
+try:
+    json.dumps(Point(1, 2), default='encode_point')
+    assert False, 'uncallable default should error'
+except TypeError:
+    pass
+
+try:
+    json.dumps({1}, default=encode_point)
+    assert False, 'default raising should error'
+except TypeError as e:
+    assert str(e) == 'cannot encode', f'default error message, error: {e}'
+
+items = [1]
+items.append(items)
+try:
+    json.dumps(items)
+    assert False, 'circular list should error'
+except ValueError as e:
+    assert str(e) == 'Circular reference detected', f'circular message, error: {e}'
+
+d = {}
+d['self'] = [d]
+try:
+    json.dumps(d)
+    assert False, 'circular dict should error'
+except ValueError as e:
+    assert str(e) == 'Circular reference detected', f'circular dict message, error: {e}'
+
+try:
+    json.dumps({1}, default=lambda o: o)
+    assert False, 'default returning its argument should error'
+except ValueError as e:
+    assert str(e) == 'Circular reference detected', f'default circular message, error: {e}'
+
+try:
+    json.dumps([1], 2)
+    assert False, 'extra positional should error'
+except TypeError as e:
+    assert str(e) == 'dumps() takes 1 positional argument but 2 were given', f'positional message, error: {e}'
+
+shared = [1]
+assert json.dumps([shared, shared]) == '[[1], [1]]', 'shared references are not circular'
@@ -0,0 +1,136 @@
+import json
+from json import JSONDecodeError, loads
+
+# === Scalars ===
+assert json.loads('null') is None, 'null'
+assert json.loads('true') is True, 'true'
+assert json.loads('false') is False, 'false'
+assert json.loads('42') == 42, 'int'
+assert json.loads('-0') == 0, 'negative zero int'
+assert json.loads('12345678901234567890') == 12345678901234567890, 'big int'
+assert json.loads('1.5') == 1.5, 'float'
+assert json.loads('-2.5e3') == -2500.0, 'float with exponent'
+assert json.loads('1E2') == 100.0, 'uppercase exponent is a float'
+assert json.loads('1E400') == float('inf'), 'overflowing float'
+assert json.loads('-Infinity') == float('-inf'), 'negative infinity'
+nan = json.loads('NaN')
+assert nan != nan, 'NaN'
+assert json.loads(' \t\n\r 7 \n') == 7, 'surrounding whitespace'
+
+# === Strings ===
+assert json.loads('"hello"') == 'hello', 'string'
+assert json.loads('""') == '', 'empty string'
+assert json.loads('"a\\"b\\\\c\\/d"') == 'a"b\\c/d', 'simple escapes'
+assert json.loads('"\\b\\f\\n\\r\\t"') == '\b\f\n\r\t', 'control escapes'
+assert json.loads('"\\u00e9\\u4e2d"') == 'é中', 'unicode escapes'
+assert json.loads('"\\ud83d\\ude00"') == '😀', 'surrogate pair escape'
+assert json.loads('"é😀"') == 'é😀', 'raw non-ascii'
+
+# === Arrays and objects ===
+assert json.loads('[]') == [], 'empty array'
+assert json.loads('[1, "a", null, [true]]') == [1, 'a', None, [True]], 'nested array'
+assert json.loads('{}') == {}, 'empty object'
+assert json.loads('{"a": 1, "b": [2, {"c": null}]}') == {'a': 1, 'b': [2, {'c': None}]}, 'nested object'
+assert json.loads('{"a": 1, "a": 2}') == {'a': 2}, 'duplicate keys keep the last value'
+assert list(json.loads('{"z": 1, "a": 2}')) == ['z', 'a'], 'object keeps key order'
+assert json.loads(' [ 1 , { "a" : 2 } ] ') == [1, {'a': 2}], 'whitespace between tokens'
+
+# === bytes input and direct import ===
+assert json.loads(b'[1, 2]') == [1, 2], 'bytes input'
+assert json.loads(bytearray(b'{"x": "\xc3\xa9"}')) == {'x': 'é'}, 'bytearray input'
+assert loads('[3]') == [3], 'imported loads'
+
+# === Errors ===
+try:
+    json.loads('')
+    assert False, 'empty document should error'
+except json.JSONDecodeError as e:
+    assert str(e) == 'Expecting value: line 1 column 1 (char 0)', f'empty message, error: {e}'
+
+try:
+    json.loads('[1 2]')
+    assert False, 'missing comma should error'
+except JSONDecodeError as e:
+    assert str(e) == "Expecting ',' delimiter: line 1 column 4 (char 3)", f'delimiter message, error: {e}'
+
+try:
+    json.loads('{"a":\n  1,\n  }')
+    assert False, 'trailing comma should error'
+except ValueError as e:
+    assert str(e) == 'Illegal trailing comma before end of object: line 2 column 4 (char 9)', (
+        f'trailing comma message, error: {e}'
+    )
+
+try:
+    json.loads('[1,]')
+    assert False, 'trailing comma in array should error'
+except json.JSONDecodeError as e:
+    assert str(e) == 'Illegal trailing comma before end of array: line 1 column 3 (char 2)', f'array comma, error: {e}'
+
+try:
+    json.loads("{'a': 1}")
+    assert False, 'single quoted key should error'
+except json.JSONDecodeError as e:
+    assert str(e) == 'Expecting property name enclosed in double quotes: line 1 column 2 (char 1)', (
+        f'property name message, error: {e}'
+    )
+
+try:
+    json.loads('{"a" 1}')
+    assert False, 'missing colon should error'
+except json.JSONDecodeError as e:
+    assert str(e) == "Expecting ':' delimiter: line 1 column 6 (char 5)", f'colon message, error: {e}'
+
+try:
+    json.loads('"é')
+    assert False, 'unterminated string should error'
+except json.JSONDecodeError as e:
+    assert str(e) == 'Unterminated string starting at: line 1 column 1 (char 0)', f'unterminated message, error: {e}'
+
+try:
+    json.loads('"a\nb"')
+    assert False, 'control character should error'
+except json.JSONDecodeError as e:
+    assert str(e) == 'Invalid control character at: line 1 column 3 (char 2)', f'control message, error: {e}'
+
+try:
+    json.loads('"\\x"')
+    assert False, 'invalid escape should error'
+except json.JSONDecodeError as e:
+    assert str(e) == 'Invalid \\escape: line 1 column 2 (char 1)', f'escape message, error: {e}'
+
+try:
+    json.loads('"ab\\u12"')
+    assert False, 'short unicode escape should error'
+except json.JSONDecodeError as e:
+    assert str(e) == 'Invalid \\uXXXX escape: line 1 column 5 (char 4)', f'unicode escape message, error: {e}'
+
+try:
+    json.loads('éé 1 2')
+    assert False, 'non-ascii garbage should error'
+except json.JSONDecodeError as e:
+    assert str(e) == 'Expecting value: line 1 column 1 (char 0)', f'garbage message, error: {e}'
+
+try:
+    json.loads('["é"] x')
+    assert False, 'extra data should error'
+except json.JSONDecodeError as e:
+    assert str(e) == 'Extra data: line 1 column 7 (char 6)', f'extra data message, error: {e}'
+
+try:
+    json.loads('1.')
+    assert False, 'incomplete float should error'
+except json.JSONDecodeError as e:
+    assert str(e) == 'Extra data: line 1 column 2 (char 1)', f'incomplete float message, error: {e}'
+
+try:
+    json.loads('[-]')
+    assert False, 'lone minus should error'
+except json.JSONDecodeError as e:
+    assert str(e) == 'Expecting value: line 1 column 2 (char 1)', f'lone minus message, error: {e}'
+
+try:
+    json.loads(1)
+    assert False, 'int input should error'
+except TypeError as e:
+    assert str(e) == 'the JSON object must be str, bytes or bytearray, not int', f'type message, error: {e}'
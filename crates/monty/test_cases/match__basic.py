@@ -0,0 +1,137 @@
+# === Literal and value patterns ===
+def describe(x):
+    match x:
+        case None:
+            return 'nothing'
+        case True:
+            return 'yes'
+        case 0:
+            return 'zero'
+        case 1 | 2 | 3:
+            return 'small'
+        case 'hello':
+            return 'greeting'
+        case -1:
+            return 'minus one'
+        case _:
+            return 'other'
+
+
+assert describe(0) == 'zero', 'int literal'
+assert describe(2) == 'small', 'or pattern'
+assert describe('hello') == 'greeting', 'str literal'
+assert describe(None) == 'nothing', 'None singleton'
+assert describe(True) == 'yes', 'True singleton'
+assert describe(-1) == 'minus one', 'negative literal'
+assert describe(99) == 'other', 'wildcard'
+assert describe(1.0) == 'small', 'value patterns compare with =='
+
+
+# === Value patterns with dotted names ===
+class Color:
+    RED = 1
+    GREEN = 2
+
+
+def color_name(c):
+    match c:
+        case Color.RED:
+            return 'red'
+        case Color.GREEN:
+            return 'green'
+    return 'unknown'
+
+
+assert color_name(1) == 'red', 'dotted value pattern'
+assert color_name(2) == 'green', 'second dotted value pattern'
+assert color_name(3) == 'unknown', 'no case matched'
+
+# === Capture patterns and guards ===
+match 42:
+    case n if n < 0:
+        sign = 'negative'
+    case n if n > 0:
+        sign = 'positive'
+    case n:
+        sign = 'zero'
+assert sign == 'positive', 'guard selects case'
+assert n == 42, 'capture binds name'
+
+# === AS patterns ===
+match 3:
+    case (1 | 2 | 3) as small:
+        pass
+assert small == 3, 'as pattern binds matched value'
+
+match [1, 2]:
+    case [1, _] as pair:
+        pass
+assert pair == [1, 2], 'as pattern on sequence'
+
+# === No case matches ===
+result = 'untouched'
+match 'x':
+    case 'y':
+        result = 'y'
+assert result == 'untouched', 'no case runs when nothing matches'
+
+# === Sequence patterns ===
+def shape(seq):
+    match seq:
+        case []:
+            return 'empty'
+        case [x]:
+            return f'one {x}'
+        case [x, y]:
+            return f'two {x} {y}'
+        case [first, *rest]:
+            return f'many {first} {rest}'
+
+
+assert shape([]) == 'empty', 'empty sequence'
+assert shape([1]) == 'one 1', 'single item'
+assert shape((1, 2)) == 'two 1 2', 'tuple matches list pattern'
+assert shape([1, 2, 3, 4]) == 'many 1 [2, 3, 4]', 'star pattern collects list'
+
+match [1, 2, 3, 4, 5]:
+    case [a, *middle, b]:
+        pass
+assert (a, middle, b) == (1, [2, 3, 4], 5), 'star in the middle'
+
+match (1, 2):
+    case [*_, last]:
+        pass
+assert last == 2, 'star wildcard'
+
+match 'abc':
+    case [a, b, c]:
+        matched = 'sequence'
+    case _:
+        matched = 'not a sequence'
+assert matched == 'not a sequence', 'str is not a sequence pattern subject'
+
+match [1, [2, 3]]:
+    case [1, [x, y]]:
+        pass
+assert (x, y) == (2, 3), 'nested sequence patterns'
+
+match [1, 2]:
+    case [1, 3] | [1, 2]:
+        matched = 'second alternative'
+assert matched == 'second alternative', 'or of sequence patterns'
+
+match [0, 'a']:
+    case [0, str() as s] | [1, s]:
+        pass
+assert s == 'a', 'or alternatives bind the same names'
+
+# === Match in a loop with break ===
+found = None
+for item in [1, 'two', 3.0, [4]]:
+    match item:
+        case str():
+            found = item
+            break
+        case _:
+            pass
+assert found == 'two', 'break inside a case body'
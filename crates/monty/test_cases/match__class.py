@@ -0,0 +1,91 @@
+# === Builtin class patterns ===
+def kind(x):
+    match x:
+        case bool():
+            return 'bool'
+        case int(n):
+            return f'int {n}'
+        case str(s) if s:
+            return 'non-empty str'
+        case str():
+            return 'empty str'
+        case list([first, *_]):
+            return f'list starting {first}'
+        case dict():
+            return 'dict'
+        case _:
+            return 'other'
+
+
+assert kind(True) == 'bool', 'bool class pattern'
+assert kind(5) == 'int 5', 'int binds the subject'
+assert kind('a') == 'non-empty str', 'str with guard'
+assert kind('') == 'empty str', 'str without guard'
+assert kind([9, 8]) == 'list starting 9', 'list sub-pattern matches subject'
+assert kind({}) == 'dict', 'dict class pattern'
+assert kind(1.5) == 'other', 'float is not int'
+
+
+# === User classes with keyword patterns ===
+class Point:
+    __match_args__ = ('x', 'y')
+
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+
+def where(p):
+    match p:
+        case Point(x=0, y=0):
+            return 'origin'
+        case Point(0, y):
+            return f'on y axis at {y}'
+        case Point(x, 0):
+            return f'on x axis at {x}'
+        case Point(x=x, y=y) if x == y:
+            return 'diagonal'
+        case Point():
+            return 'somewhere'
+        case _:
+            return 'not a point'
+
+
+assert where(Point(0, 0)) == 'origin', 'keyword patterns'
+assert where(Point(0, 5)) == 'on y axis at 5', 'positional pattern via __match_args__'
+assert where(Point(3, 0)) == 'on x axis at 3', 'second positional pattern'
+assert where(Point(2, 2)) == 'diagonal', 'keyword captures with guard'
+assert where(Point(1, 2)) == 'somewhere', 'empty class pattern'
+assert where((0, 0)) == 'not a point', 'isinstance check'
+
+
+# === Subclasses and inherited __match_args__ ===
+class Point3(Point):
+    def __init__(self, x, y, z):
+        super().__init__(x, y)
+        self.z = z
+
+
+match Point3(1, 2, 3):
+    case Point(a, b, z=c):
+        pass
+assert (a, b, c) == (1, 2, 3), 'subclass matches base class pattern'
+
+
+# === Missing attributes fail the match ===
+class Empty:
+    pass
+
+
+match Empty():
+    case Empty(missing=1):
+        matched = True
+    case _:
+        matched = False
+assert matched is False, 'missing attribute fails the pattern'
+
+# === Nested class patterns ===
+match [Point(1, 2), Point(3, 4)]:
+    case [Point(x=x1), Point(y=y2)]:
+        pass
+assert (x1, y2) == (1, 4), 'class patterns inside a sequence'
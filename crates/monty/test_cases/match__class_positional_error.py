@@ -0,0 +1,19 @@
+class Point:
+    __match_args__ = ('x',)
+
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+
+match Point(1, 2):
+    case Point(a, b):
+        pass
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "match__class_positional_error.py", line 10, in <module>
+    case Point(a, b):
+         ~~~~~~~~~~~
+TypeError: Point() accepts 1 positional sub-pattern (2 given)
+"""
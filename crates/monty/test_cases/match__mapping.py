@@ -0,0 +1,52 @@
+# === Mapping patterns ===
+def handle(event):
+    match event:
+        case {'type': 'click', 'pos': [x, y]}:
+            return f'click {x},{y}'
+        case {'type': 'key', 'key': str() as key}:
+            return f'key {key}'
+        case {'type': t}:
+            return f'unknown {t}'
+        case {}:
+            return 'empty'
+        case _:
+            return 'not a mapping'
+
+
+assert handle({'type': 'click', 'pos': [1, 2]}) == 'click 1,2', 'nested sequence in mapping'
+assert handle({'type': 'key', 'key': 'a'}) == 'key a', 'class pattern in mapping'
+assert handle({'type': 'key', 'key': 1}) == 'unknown key', 'failed value falls through'
+assert handle({'type': 'scroll', 'extra': 1}) == 'unknown scroll', 'extra keys are ignored'
+assert handle({'other': 1}) == 'empty', 'empty mapping pattern matches any mapping'
+assert handle([1, 2]) == 'not a mapping', 'list is not a mapping'
+
+# === Rest patterns ===
+match {'a': 1, 'b': 2, 'c': 3}:
+    case {'a': 1, **rest}:
+        pass
+assert rest == {'b': 2, 'c': 3}, 'rest collects remaining items'
+
+match {'a': 1}:
+    case {**everything}:
+        pass
+assert everything == {'a': 1}, 'rest alone copies the mapping'
+
+original = {'x': 1, 'y': 2}
+match original:
+    case {'x': _, **others}:
+        pass
+assert original == {'x': 1, 'y': 2}, 'subject is not modified'
+
+# === Missing keys ===
+match {'a': 1}:
+    case {'a': 1, 'b': 2}:
+        matched = True
+    case _:
+        matched = False
+assert matched is False, 'missing key fails the pattern'
+
+# === Non-string keys ===
+match {1: 'one', None: 'none'}:
+    case {1: one, None: none}:
+        pass
+assert (one, none) == ('one', 'none'), 'int and None keys'
@@ -0,0 +1,13 @@
+match 1:
+    case x:
+        pass
+    case 2:
+        pass
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "match__unreachable_error.py", line 2
+    case x:
+         ~
+SyntaxError: name capture 'x' makes remaining patterns unreachable
+"""
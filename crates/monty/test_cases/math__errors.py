@@ -0,0 +1,300 @@
+import math
+
+# === domain errors ===
+for f, args in [
+    (math.sqrt, (-1,)),
+    (math.log, (0,)),
+    (math.log, (0.0,)),
+    (math.log, (-(10**400),)),
+    (math.log, (2, 0)),
+    (math.log10, (-1,)),
+    (math.log2, (0,)),
+    (math.log1p, (-1,)),
+    (math.acos, (2,)),
+    (math.asin, (-2,)),
+    (math.atanh, (1,)),
+    (math.acosh, (0.5,)),
+    (math.sin, (math.inf,)),
+    (math.pow, (0, -1)),
+    (math.pow, (-1, 0.5)),
+    (math.fmod, (1, 0)),
+    (math.fmod, (math.inf, 1)),
+    (math.remainder, (5, 0)),
+    (math.remainder, (math.inf, 1)),
+]:
+    try:
+        f(*args)
+        assert False, f'{f} of {args} should error'
+    except ValueError as e:
+        assert str(e) == 'math domain error', f'{f} of {args}, error: {e}'
+
+# === range errors ===
+for f, args in [
+    (math.exp, (1000,)),
+    (math.exp2, (1100,)),
+    (math.expm1, (710,)),
+    (math.cosh, (1000,)),
+    (math.sinh, (-1000,)),
+    (math.pow, (10, 1000)),
+    (math.ldexp, (1.0, 10000)),
+    (math.ldexp, (1.0, 10**30)),
+]:
+    try:
+        f(*args)
+        assert False, f'{f} of {args} should error'
+    except OverflowError as e:
+        assert str(e) == 'math range error', f'{f} of {args}, error: {e}'
+
+try:
+    math.log(2, 1)
+    assert False, 'log base 1 should error'
+except ZeroDivisionError as e:
+    assert str(e) == 'float division by zero', f'log base 1, error: {e}'
+
+for f in [math.sqrt, math.fabs, math.exp, math.isfinite]:
+    try:
+        f(10**400)
+        assert False, f'{f} of a huge int should error'
+    except OverflowError as e:
+        assert str(e) == 'int too large to convert to float', f'{f} of a huge int, error: {e}'
+
+# === rounding errors ===
+try:
+    math.floor(math.inf)
+    assert False, 'floor of inf should error'
+except OverflowError as e:
+    assert str(e) == 'cannot convert float infinity to integer', f'floor of inf, error: {e}'
+
+try:
+    math.ceil(math.nan)
+    assert False, 'ceil of nan should error'
+except ValueError as e:
+    assert str(e) == 'cannot convert float NaN to integer', f'ceil of nan, error: {e}'
+
+try:
+    math.floor('a')
+    assert False, 'floor of a str should error'
+except TypeError as e:
+    assert str(e) == 'must be real number, not str', f'floor of a str, error: {e}'
+
+try:
+    math.trunc('x')
+    assert False, 'trunc of a str should error'
+except TypeError as e:
+    assert str(e) == "type str doesn't define __trunc__ method", f'trunc of a str, error: {e}'
+
+# === argument types ===
+try:
+    math.sqrt('a')
+    assert False, 'sqrt of a str should error'
+except TypeError as e:
+    assert str(e) == 'must be real number, not str', f'sqrt of a str, error: {e}'
+
+try:
+    math.pow(2, None)
+    assert False, 'pow with None should error'
+except TypeError as e:
+    assert str(e) == 'must be real number, not NoneType', f'pow with None, error: {e}'
+
+for f, args in [
+    (math.gcd, (1.5, 2)),
+    (math.lcm, (2, 1.0)),
+    (math.factorial, (5.0,)),
+    (math.comb, (5, 2.0)),
+    (math.perm, (5.5,)),
+    (math.isqrt, (4.0,)),
+]:
+    try:
+        f(*args)
+        assert False, f'{f} of {args} should error'
+    except TypeError as e:
+        assert str(e) == "'float' object cannot be interpreted as an integer", f'{f} of {args}, error: {e}'
+
+try:
+    math.ldexp(1.0, 2.0)
+    assert False, 'ldexp with a float exponent should error'
+except TypeError as e:
+    assert str(e) == 'Expected an int as second argument to ldexp.', f'ldexp, error: {e}'
+
+try:
+    math.fsum(3)
+    assert False, 'fsum of an int should error'
+except TypeError as e:
+    assert str(e) == "'int' object is not iterable", f'fsum of an int, error: {e}'
+
+try:
+    math.fsum([1, 'a'])
+    assert False, 'fsum of a str should error'
+except TypeError as e:
+    assert str(e) == 'must be real number, not str', f'fsum of a str, error: {e}'
+
+try:
+    math.dist(1, 2)
+    assert False, 'dist of ints should error'
+except TypeError as e:
+    assert str(e) == "'int' object is not iterable", f'dist of ints, error: {e}'
+
+try:
+    math.prod([2, None])
+    assert False, 'prod with None should error'
+except TypeError as e:
+    assert str(e) == "unsupported operand type(s) for *: 'int' and 'NoneType'", f'prod, error: {e}'
+
+# === integer function values ===
+try:
+    math.factorial(-1)
+    assert False, 'factorial of a negative should error'
+except ValueError as e:
+    assert str(e) == 'factorial() not defined for negative values', f'factorial, error: {e}'
+
+try:
+    math.factorial(10**20)
+    assert False, 'factorial of a huge int should error'
+except OverflowError as e:
+    assert str(e) == 'factorial() argument should not exceed 9223372036854775807', f'factorial, error: {e}'
+
+try:
+    math.comb(-1, 2)
+    assert False, 'comb with a negative n should error'
+except ValueError as e:
+    assert str(e) == 'n must be a non-negative integer', f'comb, error: {e}'
+
+try:
+    math.perm(5, -1)
+    assert False, 'perm with a negative k should error'
+except ValueError as e:
+    assert str(e) == 'k must be a non-negative integer', f'perm, error: {e}'
+
+try:
+    math.comb(10**30, 10**20)
+    assert False, 'comb with a huge k should error'
+except OverflowError as e:
+    assert str(e) == 'min(n - k, k) must not exceed 9223372036854775807', f'comb, error: {e}'
+
+try:
+    math.perm(10**30, 10**20)
+    assert False, 'perm with a huge k should error'
+except OverflowError as e:
+    assert str(e) == 'k must not exceed 9223372036854775807', f'perm, error: {e}'
+
+try:
+    math.isqrt(-1)
+    assert False, 'isqrt of a negative should error'
+except ValueError as e:
+    assert str(e) == 'isqrt() argument must be nonnegative', f'isqrt, error: {e}'
+
+# === fsum errors ===
+try:
+    math.fsum([math.inf, -math.inf])
+    assert False, 'fsum of opposite infinities should error'
+except ValueError as e:
+    assert str(e) == '-inf + inf in fsum', f'fsum of infinities, error: {e}'
+
+try:
+    math.fsum([1e308, 1e308])
+    assert False, 'fsum overflow should error'
+except OverflowError as e:
+    assert str(e) == 'intermediate overflow in fsum', f'fsum overflow, error: {e}'
+
+try:
+    math.dist((1, 2), (1, 2, 3))
+    assert False, 'dist of different dimensions should error'
+except ValueError as e:
+    assert str(e) == 'both points must have the same number of dimensions', f'dist, error: {e}'
+
+# === argument counts ===
+try:
+    math.sqrt()
+    assert False, 'sqrt without arguments should error'
+except TypeError as e:
+    assert str(e) == 'math.sqrt() takes exactly one argument (0 given)', f'sqrt, error: {e}'
+
+try:
+    math.floor(1, 2)
+    assert False, 'floor with two arguments should error'
+except TypeError as e:
+    assert str(e) == 'math.floor() takes exactly one argument (2 given)', f'floor, error: {e}'
+
+try:
+    math.sqrt(x=4)
+    assert False, 'sqrt with a keyword should error'
+except TypeError as e:
+    assert str(e) == 'math.sqrt() takes no keyword arguments', f'sqrt keyword, error: {e}'
+
+try:
+    math.gcd(4, b=6)
+    assert False, 'gcd with a keyword should error'
+except TypeError as e:
+    assert str(e) == 'math.gcd() takes no keyword arguments', f'gcd keyword, error: {e}'
+
+try:
+    math.atan2(1)
+    assert False, 'atan2 with one argument should error'
+except TypeError as e:
+    assert str(e) == 'atan2 expected 2 arguments, got 1', f'atan2, error: {e}'
+
+try:
+    math.pow(1, 2, 3)
+    assert False, 'pow with three arguments should error'
+except TypeError as e:
+    assert str(e) == 'pow expected 2 arguments, got 3', f'pow, error: {e}'
+
+try:
+    math.perm()
+    assert False, 'perm without arguments should error'
+except TypeError as e:
+    assert str(e) == 'perm expected at least 1 argument, got 0', f'perm, error: {e}'
+
+try:
+    math.perm(1, 2, 3)
+    assert False, 'perm with three arguments should error'
+except TypeError as e:
+    assert str(e) == 'perm expected at most 2 arguments, got 3', f'perm, error: {e}'
+
+try:
+    math.log()
+    assert False, 'log without arguments should error'
+except TypeError as e:
+    assert str(e) == 'log expected at least 1 argument, got 0', f'log, error: {e}'
+
+try:
+    math.log(1, 2, 3)
+    assert False, 'log with three arguments should error'
+except TypeError as e:
+    assert str(e) == 'log expected at most 2 arguments, got 3', f'log, error: {e}'
+
+try:
+    math.isclose(1, 2, 3)
+    assert False, 'isclose with three positional arguments should error'
+except TypeError as e:
+    assert str(e) == 'isclose() takes exactly 2 positional arguments (3 given)', f'isclose, error: {e}'
+
+try:
+    math.isclose(1)
+    assert False, 'isclose with one argument should error'
+except TypeError as e:
+    assert str(e) == "isclose() missing required argument 'b' (pos 2)", f'isclose, error: {e}'
+
+try:
+    math.isclose(1, 1, tol=1)
+    assert False, 'isclose with an unknown keyword should error'
+except TypeError as e:
+    assert str(e) == "isclose() got an unexpected keyword argument 'tol'", f'isclose keyword, error: {e}'
+
+try:
+    math.isclose(1, 1, rel_tol=-1)
+    assert False, 'isclose with a negative tolerance should error'
+except ValueError as e:
+    assert str(e) == 'tolerances must be non-negative', f'isclose tolerance, error: {e}'
+
+try:
+    math.prod()
+    assert False, 'prod without arguments should error'
+except TypeError as e:
+    assert str(e) == 'prod() takes exactly 1 positional argument (0 given)', f'prod, error: {e}'
+
+try:
+    math.prod([1], foo=1)
+    assert False, 'prod with an unknown keyword should error'
+except TypeError as e:
+    assert str(e) == "prod() got an unexpected keyword argument 'foo'", f'prod keyword, error: {e}'
@@ -0,0 +1,169 @@
+import math
+from math import floor, pi, sqrt
+
+# === constants ===
+assert math.pi == 3.141592653589793, 'pi'
+assert math.e == 2.718281828459045, 'e'
+assert math.tau == 2 * math.pi, 'tau'
+assert math.inf == float('inf'), 'inf'
+assert math.inf > 1e308, 'inf is larger than any float'
+assert math.nan != math.nan, 'nan is not equal to itself'
+assert pi == math.pi, 'imported constant'
+
+# === rounding ===
+assert math.floor(2.7) == 2, 'floor'
+assert math.floor(-2.5) == -3, 'floor of a negative'
+assert math.ceil(2.1) == 3, 'ceil'
+assert math.ceil(-2.5) == -2, 'ceil of a negative'
+assert math.trunc(-2.7) == -2, 'trunc'
+assert type(math.floor(2.5)) is int, 'floor returns an int'
+assert math.floor(7) == 7, 'floor of an int'
+assert math.ceil(True) == 1, 'ceil of a bool'
+assert math.floor(2.0**100) == 2**100, 'floor of a large float is exact'
+assert math.trunc(-1e20) == -(10**20), 'trunc of a large float'
+assert math.ceil(10**30) == 10**30, 'ceil of a large int'
+assert floor(-0.5) == -1, 'imported floor'
+
+# === powers and logarithms ===
+assert math.sqrt(16) == 4.0, 'sqrt'
+assert math.sqrt(2.25) == 1.5, 'sqrt of a float'
+assert sqrt(10**30) == 1e15, 'sqrt of a large int'
+assert math.isclose(math.cbrt(27), 3.0), 'cbrt'
+assert math.cbrt(-8) == -2.0, 'cbrt of a negative'
+assert math.exp(0) == 1.0, 'exp'
+assert math.isclose(math.exp(1), math.e), 'exp(1)'
+assert math.exp2(10) == 1024.0, 'exp2'
+assert math.isclose(math.expm1(1e-10), 1e-10), 'expm1 is accurate for small values'
+assert math.exp(-1000) == 0.0, 'exp underflows to zero'
+assert math.log(1) == 0.0, 'log'
+assert math.isclose(math.log(math.e), 1.0), 'log(e)'
+assert math.isclose(math.log(8, 2), 3.0), 'log with a base'
+assert math.isclose(math.log(100, 10), 2.0), 'log base 10'
+assert math.log2(1024) == 10.0, 'log2'
+assert math.log10(1000) == 3.0, 'log10'
+assert math.isclose(math.log1p(1e-10), 1e-10), 'log1p is accurate for small values'
+assert math.isclose(math.log(10**400), 921.0340371976182), 'log of a huge int'
+assert math.log2(2**1100) == 1100.0, 'log2 of a huge int'
+assert math.log10(10**400) == 400.0, 'log10 of a huge int'
+assert math.isclose(math.log(10**400, 10), 400.0), 'log of a huge int with a base'
+assert math.log(math.inf) == math.inf, 'log of inf'
+assert math.pow(2, 10) == 1024.0, 'pow'
+assert math.pow(2, -1) == 0.5, 'pow with a negative exponent'
+assert math.pow(4, 0.5) == 2.0, 'pow with a float exponent'
+assert math.pow(math.nan, 0) == 1.0, 'pow(nan, 0)'
+assert math.pow(1, math.nan) == 1.0, 'pow(1, nan)'
+assert math.pow(0.0, -math.inf) == math.inf, 'pow(0, -inf)'
+
+# === trigonometry ===
+assert math.sin(0) == 0.0, 'sin'
+assert math.isclose(math.sin(math.pi / 2), 1.0), 'sin(pi / 2)'
+assert math.cos(0) == 1.0, 'cos'
+assert math.isclose(math.tan(math.pi / 4), 1.0), 'tan'
+assert math.isclose(math.asin(1), math.pi / 2), 'asin'
+assert math.acos(1) == 0.0, 'acos'
+assert math.isclose(math.atan(1), math.pi / 4), 'atan'
+assert math.isclose(math.atan2(1, -1), 3 * math.pi / 4), 'atan2'
+assert math.atan2(0.0, -0.0) == math.pi, 'atan2 keeps the sign of zero'
+assert math.sinh(0) == 0.0, 'sinh'
+assert math.cosh(0) == 1.0, 'cosh'
+assert math.tanh(1000) == 1.0, 'tanh saturates'
+assert math.isclose(math.asinh(math.sinh(2)), 2.0), 'asinh'
+assert math.isclose(math.acosh(math.cosh(2)), 2.0), 'acosh'
+assert math.isclose(math.atanh(math.tanh(0.5)), 0.5), 'atanh'
+assert math.degrees(math.pi) == 180.0, 'degrees'
+assert math.radians(180) == math.pi, 'radians'
+assert math.hypot(3, 4) == 5.0, 'hypot'
+assert math.hypot() == 0.0, 'hypot without arguments'
+assert math.hypot(-5) == 5.0, 'hypot of one value'
+assert math.hypot(math.nan, math.inf) == math.inf, 'hypot with inf and nan'
+assert math.isclose(math.hypot(1, 2, 2), 3.0), 'hypot of three values'
+assert math.dist((0, 0), (3, 4)) == 5.0, 'dist'
+assert math.dist([1, 1, 1], [1, 1, 1]) == 0.0, 'dist of equal points'
+assert math.dist((), ()) == 0.0, 'dist of empty points'
+
+# === floating point helpers ===
+assert math.fabs(-3) == 3.0, 'fabs'
+assert type(math.fabs(-3)) is float, 'fabs returns a float'
+assert math.copysign(3, -0.0) == -3.0, 'copysign'
+assert math.fmod(7, 3) == 1.0, 'fmod'
+assert math.fmod(-7, 3) == -1.0, 'fmod keeps the sign of x'
+assert math.fmod(5, math.inf) == 5.0, 'fmod by inf'
+assert math.remainder(7, 2) == -1.0, 'remainder rounds half to even'
+assert math.remainder(5, 2) == 1.0, 'remainder'
+assert math.remainder(10, 3) == 1.0, 'remainder of ints'
+assert math.remainder(3, math.inf) == 3.0, 'remainder by inf'
+assert math.modf(2.5) == (0.5, 2.0), 'modf'
+assert math.modf(-2.5) == (-0.5, -2.0), 'modf of a negative'
+assert math.modf(math.inf) == (0.0, math.inf), 'modf of inf'
+assert math.frexp(8) == (0.5, 4), 'frexp'
+assert math.frexp(0.0) == (0.0, 0), 'frexp of zero'
+assert math.frexp(-3.0) == (-0.75, 2), 'frexp of a negative'
+assert math.frexp(1e-320) == (0.98828125, -1063), 'frexp of a subnormal'
+assert math.ldexp(0.5, 4) == 8.0, 'ldexp'
+assert math.ldexp(1.0, -1074) == 5e-324, 'ldexp to the smallest subnormal'
+assert math.ldexp(2.0**-1000, 2000) == 2.0**1000, 'ldexp with a large exponent'
+assert math.ldexp(1.0, -(10**30)) == 0.0, 'ldexp underflows to zero'
+assert math.ldexp(math.inf, 5) == math.inf, 'ldexp of inf'
+assert math.isfinite(1.0), 'isfinite'
+assert not math.isfinite(math.nan), 'nan is not finite'
+assert math.isinf(-math.inf), 'isinf'
+assert not math.isinf(1e308), 'isinf of a large float'
+assert math.isnan(math.nan), 'isnan'
+assert not math.isnan(1), 'isnan of an int'
+
+# === isclose ===
+assert math.isclose(1.0, 1.0 + 1e-10), 'isclose within the default tolerance'
+assert not math.isclose(1.0, 1.001), 'isclose outside the default tolerance'
+assert math.isclose(1.0, 1.001, rel_tol=0.01), 'isclose with rel_tol'
+assert not math.isclose(0.0, 1e-10), 'zero is only close to zero by default'
+assert math.isclose(0.0, 1e-10, abs_tol=1e-9), 'isclose with abs_tol'
+assert math.isclose(math.inf, math.inf), 'infinities are close'
+assert not math.isclose(math.inf, -math.inf), 'opposite infinities are not close'
+assert not math.isclose(math.nan, math.nan), 'nan is not close to anything'
+assert math.isclose(a=1, b=1), 'isclose with keyword arguments'
+
+# === integer functions ===
+assert math.gcd(12, 18) == 6, 'gcd'
+assert math.gcd(-4, 6) == 2, 'gcd of a negative'
+assert math.gcd(12, 18, 8) == 2, 'gcd of three numbers'
+assert math.gcd() == 0, 'gcd without arguments'
+assert math.gcd(0, 0) == 0, 'gcd of zeros'
+assert math.gcd(2**100, 2**80 * 3) == 2**80, 'gcd of large ints'
+assert math.lcm(4, 6) == 12, 'lcm'
+assert math.lcm(4, -6) == 12, 'lcm of a negative'
+assert math.lcm(4, -6, 0) == 0, 'lcm with zero'
+assert math.lcm() == 1, 'lcm without arguments'
+assert math.lcm(2**70, 3) == 3 * 2**70, 'lcm of large ints'
+assert math.factorial(0) == 1, 'factorial(0)'
+assert math.factorial(5) == 120, 'factorial'
+assert math.factorial(25) == 15511210043330985984000000, 'factorial of a large result'
+assert len(str(math.factorial(1000))) == 2568, 'factorial(1000)'
+assert math.comb(5, 2) == 10, 'comb'
+assert math.comb(5, 0) == 1, 'comb with k = 0'
+assert math.comb(5, 7) == 0, 'comb with k > n'
+assert math.comb(100, 50) == 100891344545564193334812497256, 'comb of a large result'
+assert math.comb(10**20, 1) == 10**20, 'comb of a large int'
+assert math.comb(10**20, 10**20 - 1) == 10**20, 'comb with a large k'
+assert math.perm(5, 2) == 20, 'perm'
+assert math.perm(5) == 120, 'perm without k'
+assert math.perm(5, 7) == 0, 'perm with k > n'
+assert math.perm(10**20, 2) == 10**40 - 10**20, 'perm of a large int'
+assert math.isqrt(17) == 4, 'isqrt'
+assert math.isqrt(0) == 0, 'isqrt(0)'
+assert math.isqrt(10**40) == 10**20, 'isqrt of a large int'
+assert math.isqrt(True) == 1, 'isqrt of a bool'
+
+# === fsum and prod ===
+assert math.fsum([0.1] * 10) == 1.0, 'fsum is exact'
+assert math.fsum([1e100, 1.0, -1e100, 1e-100]) == 1.0, 'fsum with cancellation'
+assert math.fsum([]) == 0.0, 'fsum of nothing'
+assert math.fsum(range(5)) == 10.0, 'fsum of a range'
+assert math.fsum([1, math.inf]) == math.inf, 'fsum with inf'
+assert math.isnan(math.fsum([1, math.nan])), 'fsum with nan'
+assert math.fsum((x / 10 for x in range(1, 4))) == 0.6, 'fsum of a generator'
+assert math.prod([1, 2, 3, 4]) == 24, 'prod'
+assert math.prod([]) == 1, 'prod of nothing'
+assert math.prod(range(1, 6), start=2) == 240, 'prod with start'
+assert math.prod([2.5, 2]) == 5.0, 'prod of floats'
+assert math.prod([2**40, 2**40]) == 2**80, 'prod overflowing to a large int'
+assert math.prod([[1], 3]) == [1, 1, 1], 'prod uses multiplication'
@@ -1,3 +0,0 @@
-# xfail=cpython
-1 + 2j
-# Raise=NotImplementedError('The monty syntax parser does not yet support complex constants')
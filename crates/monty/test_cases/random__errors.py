@@ -0,0 +1,76 @@
+import random
+
+random.seed(0)
+
+try:
+    random.choice([])
+    assert False, 'choice() of an empty list should error'
+except IndexError as e:
+    assert str(e) == 'Cannot choose from an empty sequence', f'choice() error: {e}'
+
+try:
+    random.sample(range(3), 5)
+    assert False, 'sample() larger than the population should error'
+except ValueError as e:
+    assert str(e) == 'Sample larger than population or is negative', f'sample() error: {e}'
+
+try:
+    random.getrandbits(-1)
+    assert False, 'getrandbits() of a negative number should error'
+except ValueError as e:
+    assert str(e) == 'number of bits must be non-negative', f'getrandbits() error: {e}'
+
+try:
+    random.choices('ab', weights=[1], k=2)
+    assert False, 'choices() with the wrong number of weights should error'
+except ValueError as e:
+    assert str(e) == 'The number of weights does not match the population', f'choices() error: {e}'
+
+try:
+    random.choices('ab', weights=[0, 0])
+    assert False, 'choices() with zero weights should error'
+except ValueError as e:
+    assert str(e) == 'Total of weights must be greater than zero', f'choices() error: {e}'
+
+try:
+    random.choices('ab', weights=[1, 1], cum_weights=[1, 2])
+    assert False, 'choices() with weights and cum_weights should error'
+except TypeError as e:
+    assert str(e) == 'Cannot specify both weights and cumulative weights', f'choices() error: {e}'
+
+try:
+    random.shuffle((1, 2, 3))
+    assert False, 'shuffle() of a tuple should error'
+except TypeError as e:
+    assert str(e) == "'tuple' object does not support item assignment", f'shuffle() error: {e}'
+
+try:
+    random.sample({1, 2, 3}, 2)
+    assert False, 'sample() of a set should error'
+except TypeError:
+    pass
+
+try:
+    random.sample('abc', 2, counts=[1, 2])
+    assert False, 'sample() with the wrong number of counts should error'
+except ValueError as e:
+    assert str(e) == 'The number of counts does not match the population', f'sample() error: {e}'
+
+for args in [(0,), (5, 5), (5, 1), (0, 10, 0), (0, 10, -1)]:
+    try:
+        random.randrange(*args)
+        assert False, f'randrange{args} should error'
+    except ValueError:
+        pass
+
+try:
+    random.randint(5, 1)
+    assert False, 'randint() of an empty range should error'
+except ValueError:
+    pass
+
+try:
+    random.seed([1, 2])
+    assert False, 'seed() of a list should error'
+except TypeError:
+    pass
@@ -0,0 +1,79 @@
+# seeded generators produce the same numbers as CPython's Mersenne Twister
+import random
+
+# === module functions ===
+random.seed(1234)
+assert random.random() == 0.9664535356921388, 'random()'
+assert random.random() == 0.4407325991753527, 'random() again'
+assert random.randint(1, 100) == 1, 'randint()'
+assert random.randrange(10) == 1, 'randrange(stop)'
+assert random.randrange(5, 50, 5) == 5, 'randrange(start, stop, step)'
+assert random.randrange(-10, -1) == -9, 'randrange() with negative bounds'
+assert random.getrandbits(8) == 25, 'getrandbits(8)'
+assert random.getrandbits(70) == 283243033364652365878, 'getrandbits(70) is a big int'
+assert random.uniform(1, 2) == 1.0174280481846183, 'uniform()'
+assert random.gauss(0, 1) == 0.17807739879139933, 'gauss()'
+assert random.gauss(10, 2) == 9.970141461949364, 'gauss() uses the cached second value'
+assert random.choice('abcdef') == 'f', 'choice() of a str'
+assert random.choice([1, 2, 3]) == 3, 'choice() of a list'
+assert random.choice(range(100)) == 61, 'choice() of a range'
+assert random.choices('abc', k=5) == ['b', 'a', 'a', 'a', 'a'], 'choices()'
+assert random.choices([1, 2, 3], weights=[10, 1, 1], k=4) == [1, 3, 1, 1], 'choices() with weights'
+assert random.choices('xy', cum_weights=[1, 5], k=3) == ['y', 'y', 'x'], 'choices() with cum_weights'
+assert random.sample(range(100), 5) == [74, 5, 34, 71, 8], 'sample() of a range'
+assert random.sample('hello', 3) == ['l', 'l', 'e'], 'sample() of a str'
+assert random.sample(['a', 'b'], counts=[2, 3], k=4) == ['b', 'a', 'b', 'a'], 'sample() with counts'
+items = list(range(10))
+assert random.shuffle(items) is None, 'shuffle() returns None'
+assert items == [3, 1, 6, 2, 5, 9, 4, 0, 8, 7], 'shuffle() shuffles in place'
+
+# === seed types ===
+random.seed(10**30)
+assert random.random() == 0.9341508484568806, 'big int seed'
+random.seed(-5)
+assert random.random() == 0.6229016948897019, 'negative seeds use their absolute value'
+random.seed(True)
+assert random.random() == 0.13436424411240122, 'bool seed'
+random.seed(2.5)
+assert random.random() == 0.41877545666909954, 'float seed'
+random.seed(0)
+assert random.random() == 0.8444218515250481, 'zero seed'
+
+# === reseeding repeats the sequence ===
+random.seed(7)
+first = [random.random() for _ in range(3)]
+random.seed(7)
+assert [random.random() for _ in range(3)] == first, 'same seed, same numbers'
+
+# === Random instances ===
+r = random.Random(99)
+assert r.random() == 0.40397807494366633, 'Random.random()'
+assert r.randint(0, 9) == 3, 'Random.randint()'
+assert r.choice('xyz') == 'z', 'Random.choice()'
+assert r.seed(99) is None, 'Random.seed() returns None'
+assert r.random() == 0.40397807494366633, 'Random.seed() restarts the sequence'
+assert random.Random(5).sample(range(10**6), 3) == [653159, 267853, 777820], 'sample() of a large range'
+assert type(r) is random.Random, 'type of an instance'
+assert isinstance(r, random.Random), 'isinstance of an instance'
+
+a = random.Random(1)
+b = random.Random(1)
+a.random()
+assert a.random() != b.random(), 'instances have independent state'
+random.seed(1)
+c = random.Random(1)
+assert random.random() == c.random(), 'the module and an instance with the same seed agree'
+
+# === ranges of results ===
+random.seed(42)
+for _ in range(200):
+    assert 0.0 <= random.random() < 1.0, 'random() is in [0, 1)'
+    assert 3 <= random.randint(3, 5) <= 5, 'randint() is inclusive'
+    assert random.randrange(0, 10, 3) in (0, 3, 6, 9), 'randrange() respects step'
+    assert -1.0 <= random.uniform(-1, 1) <= 1.0, 'uniform() is in range'
+    assert 0 <= random.getrandbits(5) < 32, 'getrandbits() is in range'
+assert random.getrandbits(0) == 0, 'getrandbits(0)'
+assert sorted(random.sample(range(10), 10)) == list(range(10)), 'sample() of everything is a permutation'
+assert random.sample([], 0) == [], 'empty sample'
+assert random.choices([], k=0) == [], 'empty choices'
+assert random.randint(4, 4) == 4, 'randint() of one value'
@@ -0,0 +1,19 @@
+# call-external
+# the random module asks the host for a seed when the code doesn't seed it,
+# so only properties which hold for any seed are checked here
+import random
+
+x = random.random()
+assert 0.0 <= x < 1.0, 'random() is in [0, 1)'
+assert 1 <= random.randint(1, 6) <= 6, 'randint() is in range'
+assert random.choice('abc') in 'abc', 'choice() picks from the sequence'
+
+items = list(range(20))
+random.shuffle(items)
+assert sorted(items) == list(range(20)), 'shuffle() keeps the items'
+
+r = random.Random()
+assert 0.0 <= r.random() < 1.0, 'an unseeded instance works'
+
+random.seed()
+assert 0.0 <= random.random() < 1.0, 'seed() without a seed reseeds from the host'
@@ -0,0 +1,179 @@
+import re
+
+# === invalid patterns ===
+try:
+    re.compile('(a')
+    assert False, 'unterminated group should error'
+except re.error as e:
+    assert str(e) == 'missing ), unterminated subpattern at position 0', f'unterminated group, error: {e}'
+
+try:
+    re.compile('a)')
+    assert False, 'unbalanced parenthesis should error'
+except re.error as e:
+    assert str(e) == 'unbalanced parenthesis at position 1', f'unbalanced parenthesis, error: {e}'
+
+try:
+    re.compile('*a')
+    assert False, 'nothing to repeat should error'
+except re.error as e:
+    assert str(e) == 'nothing to repeat at position 0', f'nothing to repeat, error: {e}'
+
+try:
+    re.compile('[a')
+    assert False, 'unterminated set should error'
+except re.error as e:
+    assert str(e) == 'unterminated character set at position 0', f'unterminated set, error: {e}'
+
+try:
+    re.compile('[z-a]')
+    assert False, 'bad range should error'
+except re.error as e:
+    assert str(e) == 'bad character range z-a at position 1', f'bad range, error: {e}'
+
+try:
+    re.compile(r'\q')
+    assert False, 'bad escape should error'
+except re.error as e:
+    assert str(e) == 'bad escape \\q at position 0', f'bad escape, error: {e}'
+
+try:
+    re.compile('a{3,1}')
+    assert False, 'bad repeat should error'
+except re.error as e:
+    assert str(e) == 'min repeat greater than max repeat at position 2', f'bad repeat, error: {e}'
+
+try:
+    re.compile('(?P<n>a)(?P<n>b)')
+    assert False, 'duplicate group name should error'
+except re.error as e:
+    assert str(e) == "redefinition of group name 'n' as group 2; was group 1 at position 12", f'duplicate, error: {e}'
+
+try:
+    re.compile('a\n(')
+    assert False, 'multiline pattern error'
+except re.error as e:
+    assert str(e) == 'missing ), unterminated subpattern at position 2 (line 2, column 1)', f'line, error: {e}'
+
+try:
+    re.compile('(')
+    assert False, 'PatternError alias'
+except re.PatternError as e:
+    assert isinstance(e, Exception), 'error is an Exception'
+
+# === argument errors ===
+try:
+    re.compile(1)
+    assert False, 'int pattern should error'
+except TypeError as e:
+    assert str(e) == 'first argument must be string or compiled pattern', f'int pattern, error: {e}'
+
+try:
+    re.search('a', 1)
+    assert False, 'int string should error'
+except TypeError as e:
+    assert str(e) == "expected string or bytes-like object, got 'int'", f'int string, error: {e}'
+
+try:
+    re.search('a', b'a')
+    assert False, 'bytes string should error'
+except TypeError as e:
+    assert str(e) == 'cannot use a string pattern on a bytes-like object', f'bytes string, error: {e}'
+
+try:
+    re.compile(re.compile('a'), re.I)
+    assert False, 'flags with compiled pattern should error'
+except ValueError as e:
+    assert str(e) == 'cannot process flags argument with a compiled pattern', f'flags, error: {e}'
+
+try:
+    re.compile('a', re.LOCALE)
+    assert False, 'LOCALE should error'
+except ValueError as e:
+    assert str(e) == 'cannot use LOCALE flag with a str pattern', f'LOCALE, error: {e}'
+
+try:
+    re.compile('a', re.ASCII | re.UNICODE)
+    assert False, 'ASCII and UNICODE should error'
+except ValueError as e:
+    assert str(e) == 'ASCII and UNICODE flags are incompatible', f'ASCII and UNICODE, error: {e}'
+
+try:
+    re.escape(1)
+    assert False, 'escape of an int should error'
+except TypeError as e:
+    assert str(e) == 'decoding to str: need a bytes-like object, int found', f'escape, error: {e}'
+
+try:
+    re.search('a')
+    assert False, 'missing argument should error'
+except TypeError as e:
+    assert str(e) == "search() missing 1 required positional argument: 'string'", f'missing, error: {e}'
+
+try:
+    re.compile('a').search('a', bad=1)
+    assert False, 'unexpected keyword should error'
+except TypeError as e:
+    assert str(e) == "search() got an unexpected keyword argument 'bad'", f'keyword, error: {e}'
+
+# === match errors ===
+m = re.match('(a)(?P<n>b)', 'ab')
+try:
+    m.group(3)
+    assert False, 'missing group should error'
+except IndexError as e:
+    assert str(e) == 'no such group', f'missing group, error: {e}'
+
+try:
+    m.group('x')
+    assert False, 'missing named group should error'
+except IndexError as e:
+    assert str(e) == 'no such group', f'missing named group, error: {e}'
+
+try:
+    m[-1]
+    assert False, 'negative group should error'
+except IndexError as e:
+    assert str(e) == 'no such group', f'negative group, error: {e}'
+
+try:
+    m.expand(r'\g<x>')
+    assert False, 'unknown name in template should error'
+except IndexError as e:
+    assert str(e) == "unknown group name 'x'", f'template name, error: {e}'
+
+try:
+    m.expand('\\')
+    assert False, 'bad template should error'
+except re.error as e:
+    assert str(e) == 'bad escape (end of pattern) at position 0', f'bad template, error: {e}'
+
+# === sub errors ===
+try:
+    re.sub('a', r'\3', 'a')
+    assert False, 'invalid group reference should error'
+except re.error as e:
+    assert str(e) == 'invalid group reference 3 at position 1', f'group reference, error: {e}'
+
+try:
+    re.sub('a', lambda m: 1, 'a')
+    assert False, 'function returning an int should error'
+except TypeError as e:
+    assert str(e) == 'sequence item 0: expected str instance, int found', f'function result, error: {e}'
+
+try:
+    re.sub('a', lambda m: None if m.start() == 1 else 1.5, 'baca')
+    assert False, 'function returning a float should error'
+except TypeError as e:
+    assert str(e) == 'sequence item 2: expected str instance, float found', f'later result, error: {e}'
+
+
+def fail(m):
+    raise KeyError('boom')
+
+
+try:
+    re.sub('a', fail, 'a')
+    assert False, 'exception in function should propagate'
+except KeyError as e:
+    assert e.args == ('boom',), 'exception propagates'
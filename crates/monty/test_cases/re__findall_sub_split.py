@@ -0,0 +1,86 @@
+import re
+
+# === findall ===
+assert re.findall(r'\d+', 'a1b22c333') == ['1', '22', '333'], 'findall without groups'
+assert re.findall(r'(\w)=(\d)', 'a=1 b=2') == [('a', '1'), ('b', '2')], 'findall with groups'
+assert re.findall(r'(\w)=\d', 'a=1 b=2') == ['a', 'b'], 'findall with one group'
+assert re.findall(r'(a)|b', 'ab') == ['a', ''], 'unmatched group is empty'
+assert re.findall('x', 'abc') == [], 'findall without matches'
+assert re.findall('', 'ab') == ['', '', ''], 'empty matches'
+assert re.findall('|a', 'a') == ['', 'a', ''], 'empty match followed by a non-empty one'
+assert re.findall('a*', 'baaa') == ['', 'aaa', ''], 'empty and non-empty matches'
+assert re.compile('o').findall('foo boo', 3) == ['o', 'o'], 'pattern findall with pos'
+assert re.findall('é', 'éaé') == ['é', 'é'], 'non-ascii findall'
+
+# === finditer ===
+spans = [m.span() for m in re.finditer(r'\w+', 'one two  three')]
+assert spans == [(0, 3), (4, 7), (9, 14)], 'finditer spans'
+it = re.compile(r'(\d)').finditer('a1b2')
+assert next(it).group(1) == '1', 'first match'
+assert next(it).start() == 3, 'second match'
+assert list(it) == [], 'exhausted iterator'
+assert [m.group() for m in re.finditer('', 'ab')] == ['', '', ''], 'finditer empty matches'
+assert [m.span() for m in re.finditer('😀', 'a😀b😀')] == [(1, 2), (3, 4)], 'non-ascii finditer spans'
+
+# === sub ===
+assert re.sub(r'\d', '#', 'a1b2c3') == 'a#b#c#', 'sub all'
+assert re.sub(r'\d', '#', 'a1b2c3', count=2) == 'a#b#c3', 'sub with count'
+assert re.sub(r'\d', '#', 'a1b2c3', count=1) == 'a#b2c3', 'sub count keyword'
+assert re.sub(r'\d', '#', 'a1', count=-1) == 'a1', 'negative count replaces nothing'
+assert re.sub('x', '-', 'abc') == 'abc', 'sub without matches'
+assert re.sub(r'(\w+)@(\w+)', r'\2 at \1', 'me@home') == 'home at me', 'sub with group references'
+assert re.sub(r'(?P<w>\w+)', r'<\g<w>>', 'a b') == '<a> <b>', 'sub with named group reference'
+assert re.sub('a', r'\g<0>\g<0>', 'cat') == 'caat', 'sub with group 0'
+assert re.sub('(a)|b', r'[\1]', 'ab') == '[a][]', 'unmatched group is replaced by nothing'
+assert re.sub('a', r'\n\t\\', 'a') == '\n\t\\', 'template escapes'
+assert re.sub('x*', '-', 'abxd') == '-a-b--d-', 'sub with empty matches'
+assert re.sub('b', 'B', 'abc', flags=re.I) == 'aBc', 'sub with flags'
+assert re.sub('B', 'x', 'abc', count=0, flags=re.I) == 'axc', 'sub with count and flags'
+assert re.sub('é', 'e', 'café') == 'cafe', 'non-ascii sub'
+
+
+def double(m):
+    return m.group() * 2
+
+
+assert re.sub(r'\d', double, 'a1b2') == 'a11b22', 'sub with function'
+assert re.sub(r'\d', lambda m: str(int(m.group()) + 1), '1 9') == '2 10', 'sub with lambda'
+assert re.sub(r'(\w)', lambda m: m.group(1).upper(), 'ab', count=1) == 'Ab', 'sub with function and count'
+assert re.sub('a', lambda m: None, 'banana') == 'bnn', 'function returning None'
+seen = []
+re.sub(r'\w', lambda m: seen.append(m.start()) or '', 'xyz')
+assert seen == [0, 1, 2], 'function called for each match in order'
+
+# === subn ===
+assert re.subn(r'\d', '#', 'a1b2') == ('a#b#', 2), 'subn'
+assert re.subn('x', '#', 'abc') == ('abc', 0), 'subn without matches'
+assert re.subn(r'\d', lambda m: '', '1a2', count=1) == ('a2', 1), 'subn with function and count'
+
+# === pattern sub and subn ===
+p = re.compile(r'\s+')
+assert p.sub(' ', 'a  b \t c') == 'a b c', 'pattern sub'
+assert p.sub(' ', 'a  b  c', 1) == 'a b  c', 'pattern sub with count'
+assert p.subn('', ' a b ') == ('ab', 3), 'pattern subn'
+assert re.compile('(a)').sub(lambda m: m.group(1).upper(), 'aba') == 'AbA', 'pattern sub with function'
+
+# === split ===
+assert re.split(r',\s*', 'a, b,c') == ['a', 'b', 'c'], 'split'
+assert re.split(r'(,)', 'a,b') == ['a', ',', 'b'], 'split keeps groups'
+assert re.split(r'(,)|(;)', 'a,b;c') == ['a', ',', None, 'b', None, ';', 'c'], 'split with unmatched groups'
+assert re.split(',', 'a,b,c,d', maxsplit=2) == ['a', 'b', 'c,d'], 'split with maxsplit'
+assert re.split(',', 'a,b,c', maxsplit=1) == ['a', 'b,c'], 'split maxsplit keyword'
+assert re.split(',', 'a,b', maxsplit=-1) == ['a,b'], 'negative maxsplit splits nothing'
+assert re.split('x', 'abc') == ['abc'], 'split without matches'
+assert re.split(',', ',a,') == ['', 'a', ''], 'split at the ends'
+assert re.split('x*', 'axbc') == ['', 'a', '', 'b', 'c', ''], 'split on empty matches'
+assert re.split('A', 'bab', flags=re.I) == ['b', 'b'], 'split with flags'
+assert re.compile(r'\W+').split('one, two. three') == ['one', 'two', 'three'], 'pattern split'
+assert re.compile(r'\W+').split('a b c', 1) == ['a', 'b c'], 'pattern split with maxsplit'
+
+# === escape ===
+assert re.escape('a.b*c') == 'a\\.b\\*c', 'escape'
+assert re.escape('1+1=2') == '1\\+1=2', 'escape leaves other characters'
+assert re.escape('é_x y') == 'é_x\\ y', 'escape space'
+assert re.escape('') == '', 'escape empty string'
+special = '()[]{}?*+-|^$\\.&~# \t\n'
+assert re.fullmatch(re.escape(special), special), 'escaped pattern matches literally'
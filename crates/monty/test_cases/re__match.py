@@ -0,0 +1,119 @@
+import re
+from re import compile, search
+
+# === search, match and fullmatch ===
+m = re.search(r'\d+', 'abc 123 def')
+assert m is not None, 'search finds digits'
+assert m.group() == '123', 'search group'
+assert m.start() == 4, 'search start'
+assert m.end() == 7, 'search end'
+assert m.span() == (4, 7), 'search span'
+assert re.search(r'\d', 'abc') is None, 'search without a match'
+assert re.match('ab', 'abc').group() == 'ab', 'match at the start'
+assert re.match('bc', 'abc') is None, 'match is anchored'
+assert re.fullmatch('a.c', 'abc').group() == 'abc', 'fullmatch'
+assert re.fullmatch('a.', 'abc') is None, 'fullmatch must match everything'
+assert re.fullmatch('a|ab', 'ab').group() == 'ab', 'fullmatch tries later alternatives'
+assert search('x', 'axb').span() == (1, 2), 'imported search'
+
+# === groups ===
+m = re.match(r'(\w+) (\w+)(!)?', 'hello world')
+assert m.group(0) == 'hello world', 'group 0'
+assert m.group(1) == 'hello', 'group 1'
+assert m.group(2) == 'world', 'group 2'
+assert m.group(3) is None, 'unmatched group'
+assert m.group(1, 2) == ('hello', 'world'), 'several groups'
+assert m.groups() == ('hello', 'world', None), 'groups'
+assert m.groups('-') == ('hello', 'world', '-'), 'groups with default'
+assert m[0] == 'hello world', 'getitem'
+assert m[2] == 'world', 'getitem group'
+assert m.start(2) == 6, 'start of group'
+assert m.end(1) == 5, 'end of group'
+assert m.span(3) == (-1, -1), 'span of unmatched group'
+assert m.lastindex == 2, 'lastindex'
+assert m.lastgroup is None, 'lastgroup without names'
+assert m.string == 'hello world', 'string'
+assert m.pos == 0, 'pos'
+assert m.endpos == 11, 'endpos'
+
+# === named groups ===
+m = re.match(r'(?P<first>\w+) (?P<last>\w+)', 'Jane Doe')
+assert m.group('first') == 'Jane', 'named group'
+assert m['last'] == 'Doe', 'named getitem'
+assert m.groupdict() == {'first': 'Jane', 'last': 'Doe'}, 'groupdict'
+assert m.start('last') == 5, 'start of named group'
+assert m.lastgroup == 'last', 'lastgroup'
+assert re.match('(?P<a>x)?y', 'y').groupdict('z') == {'a': 'z'}, 'groupdict with default'
+assert m.expand(r'\2, \g<first> \g<1>') == 'Doe, Jane Jane', 'expand'
+
+# === compiled patterns ===
+p = re.compile(r'(?P<num>\d+)(x)?')
+assert p.pattern == r'(?P<num>\d+)(x)?', 'pattern attribute'
+assert p.groups == 2, 'groups attribute'
+assert p.groupindex == {'num': 1}, 'groupindex attribute'
+assert p.flags == re.UNICODE, 'default flags'
+assert p.search('ab12cd').group() == '12', 'pattern search'
+assert p.match('12').group('num') == '12', 'pattern match'
+assert p.match('a12') is None, 'pattern match is anchored'
+assert p.fullmatch('12x').groups() == ('12', 'x'), 'pattern fullmatch'
+assert re.compile(p) is p, 'compiling a pattern returns it'
+assert re.search(p, 'x5').group() == '5', 'module function with compiled pattern'
+assert compile('a') == compile('a'), 'patterns compare equal'
+assert compile('a') != compile('a', re.I), 'patterns with different flags differ'
+assert hash(compile('ab')) == hash(compile('ab')), 'equal patterns hash equally'
+
+# === pos and endpos ===
+p = re.compile(r'\d')
+assert p.search('1a2b3', 1).group() == '2', 'search from pos'
+assert p.search('1a2b3', 1, 2) is None, 'search up to endpos'
+assert p.search('1a2b3', pos=3).span() == (4, 5), 'pos keyword'
+assert p.match('a1', 1).group() == '1', 'match at pos'
+assert p.search('123', -5).group() == '1', 'negative pos is clamped'
+assert p.search('123', 2, 1) is None, 'endpos before pos'
+m = p.search('a1b2', 2, 100)
+assert m.pos == 2, 'match pos'
+assert m.endpos == 4, 'match endpos is clamped'
+assert re.compile('$').search('abc', 0, 2).span() == (2, 2), '$ matches at endpos'
+assert re.compile('^a').search('ba', 1) is None, '^ does not match at pos'
+
+# === unicode ===
+m = re.search('(é+)(.)', 'caféé😀!')
+assert m.span() == (3, 6), 'spans are character indices'
+assert m.group(2) == '😀', 'non-ascii group'
+assert re.match(r'\w+', 'naïve café').group() == 'naïve', 'unicode word characters'
+assert re.match(r'\d+', '١٢٣').group() == '١٢٣', 'unicode digits'
+assert re.match(r'\w+', 'naïve', re.ASCII).group() == 'na', 'ascii word characters'
+
+# === flags ===
+assert re.match('abc', 'ABC', re.IGNORECASE).group() == 'ABC', 'IGNORECASE'
+assert re.match('[a-z]+', 'xYz', re.I).group() == 'xYz', 'IGNORECASE with ranges'
+assert re.match('é', 'É', re.I).group() == 'É', 'IGNORECASE with non-ascii'
+assert re.findall('^x', 'x\nx', re.MULTILINE) == ['x', 'x'], 'MULTILINE'
+assert re.findall('^x', 'x\nx') == ['x'], 'no MULTILINE'
+assert re.match('a.b', 'a\nb') is None, 'dot does not match newline'
+assert re.match('a.b', 'a\nb', re.DOTALL).group() == 'a\nb', 'DOTALL'
+assert re.match('a b # comment', 'ab', re.VERBOSE).group() == 'ab', 'VERBOSE'
+assert re.match('(?i)abc', 'AbC').group() == 'AbC', 'inline flag'
+assert re.match('a(?i:b)c', 'aBc').group() == 'aBc', 'scoped inline flag'
+assert re.match('a(?i:b)c', 'aBC') is None, 'scoped inline flag ends'
+assert re.I == re.IGNORECASE == 2, 'I'
+assert re.M == re.MULTILINE == 8, 'M'
+assert re.S == re.DOTALL == 16, 'S'
+assert re.X == re.VERBOSE == 64, 'X'
+assert re.A == re.ASCII == 256, 'A'
+assert re.U == re.UNICODE == 32, 'U'
+assert re.NOFLAG == 0, 'NOFLAG'
+assert re.compile('a', re.I | re.M).flags == re.I | re.M | re.U, 'compiled flags'
+assert re.compile('(?s)a').flags == re.S | re.U, 'inline flags are included'
+
+# === repr ===
+assert repr(re.compile('a+b')) == "re.compile('a+b')", 'pattern repr'
+assert repr(re.compile(r'\d', re.I | re.M)) == "re.compile('\\\\d', re.IGNORECASE|re.MULTILINE)", (
+    'pattern repr with flags'
+)
+assert repr(re.compile('a', re.A)) == "re.compile('a', re.ASCII)", 'pattern repr with ASCII'
+assert repr(re.search('b+', 'abbc')) == "<re.Match object; span=(1, 3), match='bb'>", 'match repr'
+
+# === truthiness ===
+assert re.match('', 'x'), 'empty match is truthy'
+assert bool(re.compile('')), 'pattern is truthy'
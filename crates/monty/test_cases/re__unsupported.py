@@ -0,0 +1,43 @@
+# xfail=cpython
+# Monty's regex engine runs in linear time, so features which need backtracking are rejected
+import re
+
+try:
+    re.compile(r'(a)\1')
+    assert False, 'backreferences should error'
+except re.error as e:
+    assert str(e) == 'backreferences are not supported at position 3', f'backreference, error: {e}'
+
+try:
+    re.compile('(?P<x>a)(?P=x)')
+    assert False, 'named backreferences should error'
+except re.error as e:
+    assert str(e) == 'backreferences are not supported at position 8', f'named backreference, error: {e}'
+
+try:
+    re.compile('a(?=b)')
+    assert False, 'look-ahead should error'
+except re.error as e:
+    assert str(e) == 'look-ahead assertions are not supported at position 1', f'look-ahead, error: {e}'
+
+try:
+    re.compile('(?<!a)b')
+    assert False, 'look-behind should error'
+except re.error as e:
+    assert str(e) == 'look-behind assertions are not supported at position 0', f'look-behind, error: {e}'
+
+try:
+    re.compile('(?>a)')
+    assert False, 'atomic groups should error'
+except re.error as e:
+    assert str(e) == 'atomic groups are not supported at position 0', f'atomic group, error: {e}'
+
+try:
+    re.compile('a++')
+    assert False, 'possessive quantifiers should error'
+except re.error as e:
+    assert str(e) == 'possessive quantifiers are not supported at position 2', f'possessive, error: {e}'
+
+# Patterns which take exponential time with a backtracking engine are fast
+assert re.match('(a*)*b', 'a' * 5000) is None, 'nested quantifiers'
+assert re.fullmatch('(a|aa)+', 'a' * 5000) is not None, 'overlapping alternatives'
@@ -0,0 +1,11 @@
+v1 = [1]
+v2 = [2]
+v3 = [3]
+d = {'a': v1, 'b': v2}
+lst = [v3, v3]
+del d['a']
+del lst[0]
+tmp = v2
+del tmp
+d
+# ref-counts={'v1': 1, 'v2': 2, 'v3': 2, 'd': 2, 'lst': 1}
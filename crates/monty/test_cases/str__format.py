@@ -0,0 +1,146 @@
+# === Positional and indexed fields ===
+assert '{} {}'.format('a', 'b') == 'a b', 'automatic numbering'
+assert '{0}{1}{0}'.format('a', 'b') == 'aba', 'manual numbering'
+assert '{}'.format(1.5) == '1.5', 'float uses str'
+assert 'no fields'.format(1, x=2) == 'no fields', 'unused arguments'
+assert '{{}} {{{}}}'.format(1) == '{} {1}', 'escaped braces'
+
+# === Keyword fields ===
+assert '{name} is {age}'.format(name='Ann', age=30) == 'Ann is 30', 'keyword fields'
+assert '{0} {x}'.format(1, x=2) == '1 2', 'positional and keyword'
+kwargs = {'a': 1, 'b': 2}
+assert '{a}-{b}'.format(**kwargs) == '1-2', 'unpacked kwargs'
+
+# === Format specs ===
+assert '{:>10}'.format('hi') == '        hi', 'right align'
+assert '{name:>10}'.format(name='hi') == '        hi', 'named right align'
+assert '{:*^10}|{:<5}|'.format('x', 'ab') == '****x*****|ab   |', 'fill and align'
+assert '{:.2f}'.format(3.14159) == '3.14', 'float precision'
+assert '{:05d}'.format(42) == '00042', 'zero padding'
+assert '{:+d}'.format(5) == '+5', 'sign'
+assert '{:x} {:o} {:b}'.format(255, 8, 5) == 'ff 10 101', 'bases'
+assert '{:%}'.format(0.5) == '50.000000%', 'percent'
+assert '{:e}'.format(12345.678) == '1.234568e+04', 'exponent'
+assert '{:G}'.format(1e20) == '1E+20', 'uppercase general'
+assert '{:{w}.{p}f}'.format(3.14159, w=8, p=2) == '    3.14', 'nested fields in spec'
+assert '{:{}}'.format('a', 3) == 'a  ', 'nested automatic field'
+
+# === Conversions ===
+assert '{!r}'.format('a') == "'a'", 'repr conversion'
+assert '{!s}'.format('a') == 'a', 'str conversion'
+assert '{!a}'.format('é') == "'\\xe9'", 'ascii conversion'
+assert '{!r:>6}'.format('a') == "   'a'", 'conversion with spec'
+
+# === Attribute and item lookup ===
+assert '{x[0]} {x[1]}'.format(x=[4, 5]) == '4 5', 'list index'
+assert '{d[k]}'.format(d={'k': 'v'}) == 'v', 'dict key'
+assert '{0[1][0]}'.format([[1], [2]]) == '2', 'chained index'
+assert '{0[ 0]}'.format({' 0': 1}) == '1', 'non-digit key is a string'
+
+
+class Point:
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+    def __repr__(self):
+        return f'Point({self.x}, {self.y})'
+
+
+p = Point(1, 2)
+assert '{}'.format(p) == 'Point(1, 2)', 'instance uses __repr__'
+assert '{!r:>13}'.format(p) == '  Point(1, 2)', 'instance repr with spec'
+assert '{p.x},{p.y}'.format(p=p) == '1,2', 'instance attributes'
+assert '{}'.format([p]) == '[Point(1, 2)]', 'instance in a list'
+
+
+class Name:
+    def __str__(self):
+        return 'name'
+
+
+assert '{}|{!s:>6}'.format(Name(), Name()) == 'name|  name', 'instance uses __str__'
+
+# === format_map ===
+assert '{a} {b}'.format_map({'a': 1, 'b': 'x'}) == '1 x', 'format_map'
+assert '{a[0]:>3}'.format_map({'a': [7]}) == '  7', 'format_map with lookup and spec'
+assert 'plain'.format_map({}) == 'plain', 'format_map without fields'
+
+# === Errors ===
+try:
+    '{} {0}'.format(1)
+    assert False, 'mixed numbering should fail'
+except ValueError as e:
+    assert str(e) == 'cannot switch from automatic field numbering to manual field specification', str(e)
+
+try:
+    '{0} {}'.format(1)
+    assert False, 'mixed numbering should fail'
+except ValueError as e:
+    assert str(e) == 'cannot switch from manual field specification to automatic field numbering', str(e)
+
+try:
+    '{} {}'.format(1)
+    assert False, 'missing argument should fail'
+except IndexError as e:
+    assert str(e) == 'Replacement index 1 out of range for positional args tuple', str(e)
+
+try:
+    '{a}'.format(b=1)
+    assert False, 'missing keyword should fail'
+except KeyError as e:
+    assert e.args == ('a',), e.args
+
+try:
+    '{'.format()
+    assert False, 'single brace should fail'
+except ValueError as e:
+    assert str(e) == "Single '{' encountered in format string", str(e)
+
+try:
+    'a } b'.format()
+    assert False, 'single closing brace should fail'
+except ValueError as e:
+    assert str(e) == "Single '}' encountered in format string", str(e)
+
+try:
+    '{0'.format(1)
+    assert False, 'unclosed field should fail'
+except ValueError as e:
+    assert str(e) == "expected '}' before end of string", str(e)
+
+try:
+    '{!x}'.format(1)
+    assert False, 'bad conversion should fail'
+except ValueError as e:
+    assert str(e) == 'Unknown conversion specifier x', str(e)
+
+try:
+    '{0.}'.format(1)
+    assert False, 'empty attribute should fail'
+except ValueError as e:
+    assert str(e) == 'Empty attribute in format string', str(e)
+
+try:
+    '{a:{b:{c}}}'.format(a=1, b=2, c=3)
+    assert False, 'deep nesting should fail'
+except ValueError as e:
+    assert str(e) == 'Max string recursion exceeded', str(e)
+
+try:
+    '{:d}'.format('a')
+    assert False, 'bad format code should fail'
+except ValueError as e:
+    assert str(e) == "Unknown format code 'd' for object of type 'str'", str(e)
+
+try:
+    '{0.missing}'.format(p)
+    assert False, 'missing attribute should fail'
+except AttributeError as e:
+    assert str(e) == "'Point' object has no attribute 'missing'", str(e)
+
+try:
+    '{0}'.format_map({})
+    assert False, 'positional field in format_map should fail'
+except ValueError as e:
+    assert str(e) == 'Format string contains positional fields', str(e)
@@ -0,0 +1,2 @@
+'{a}'.format_map()
+# Raise=TypeError('str.format_map() takes exactly one argument (0 given)')
@@ -0,0 +1,143 @@
+# === Basic conversions ===
+assert 'hello %s' % 'world' == 'hello world', 'single argument'
+assert '%s and %s' % ('a', 'b') == 'a and b', 'tuple arguments'
+assert '%s' % (1,) == '1', 'single item tuple'
+assert '%s' % [1, 2] == '[1, 2]', 'list is a single argument'
+assert '%s' % {'a': 1} == "{'a': 1}", 'dict is a single argument'
+assert '%s' % None == 'None', 'None'
+assert '%r' % 'a' == "'a'", 'repr'
+assert '%a' % 'é' == "'\\xe9'", 'ascii'
+assert '%d %i %u' % (3, -4, 5) == '3 -4 5', 'integers'
+assert '%d' % 3.9 == '3', 'float truncated by %d'
+assert '%d' % True == '1', 'bool as int'
+assert '%d' % 10**20 == '100000000000000000000', 'big int'
+assert '100%%' % () == '100%', 'literal percent'
+assert '%.1f%%' % 12.34 == '12.3%', 'literal percent after float'
+assert 'no specifiers' % () == 'no specifiers', 'empty tuple'
+assert 'abc' % {'a': 1} == 'abc', 'unused mapping'
+
+# === Width, precision and flags ===
+assert '%5s|%-5s|%.2s' % ('a', 'b', 'xyz') == '    a|b    |xy', 'string width and precision'
+assert '%.3d|%5.3d|%-5d|%05d|%+d|% d' % (5, 5, 5, -5, 5, 5) == '005|  005|5    |-0005|+5| 5', 'integer flags'
+assert '%*d|%-*.*f' % (5, 3, 8, 2, 3.14159) == '    3|3.14    ', 'star width and precision'
+assert '%*d' % (-4, 1) == '1   ', 'negative star width left aligns'
+assert '%.2f' % 3.14159 == '3.14', 'float precision'
+assert '%f' % 1 == '1.000000', 'int as float'
+assert '%08.3f' % -3.14159 == '-003.142', 'zero padded float'
+assert '%e %E' % (12345.678, 1.5) == '1.234568e+04 1.500000E+00', 'exponent'
+assert '%g %G' % (0.00001234, 1e20) == '1.234e-05 1E+20', 'general'
+assert '%F' % float('inf') == 'INF', 'uppercase infinity'
+
+# === Bases ===
+assert '%x %X %o' % (255, 255, 8) == 'ff FF 10', 'bases'
+assert '%#x %#o %#X' % (31, 15, -31) == '0x1f 0o17 -0X1F', 'alternate form'
+assert '%#06x' % 31 == '0x001f', 'zero padding after prefix'
+assert '%x' % 10**20 == '56bc75e2d63100000', 'big int hex'
+
+# === Characters ===
+assert '%c%c' % (97, 'b') == 'ab', 'int and str characters'
+assert '%3c|' % 'x' == '  x|', 'padded character'
+
+# === Mapping keys ===
+assert '%(a)s %(b)r' % {'a': 1, 'b': 'x'} == "1 'x'", 'mapping keys'
+assert '%(name)-6s|' % {'name': 'ab'} == 'ab    |', 'mapping key with flags'
+assert '%(a)s %(a)s' % {'a': 1} == '1 1', 'repeated key'
+
+
+# === Instances ===
+class Point:
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+    def __repr__(self):
+        return f'Point({self.x}, {self.y})'
+
+
+class Name:
+    def __str__(self):
+        return 'name'
+
+
+assert '%s' % Point(1, 2) == 'Point(1, 2)', 'instance uses __repr__ for %s'
+assert '%r' % (Point(1, 2),) == 'Point(1, 2)', 'instance uses __repr__'
+assert '%-6s|' % Name() == 'name  |', 'instance uses __str__'
+assert '%s' % [Point(3, 4)] == '[Point(3, 4)]', 'instance in a list'
+
+# === Augmented assignment ===
+s = '%s-%s'
+s %= ('a', 'b')
+assert s == 'a-b', 'in-place formatting'
+
+# === Errors ===
+try:
+    '%s %s' % (1,)
+    assert False, 'too few arguments should fail'
+except TypeError as e:
+    assert str(e) == 'not enough arguments for format string', str(e)
+
+try:
+    '%s' % (1, 2)
+    assert False, 'too many arguments should fail'
+except TypeError as e:
+    assert str(e) == 'not all arguments converted during string formatting', str(e)
+
+try:
+    'abc' % 5
+    assert False, 'unused argument should fail'
+except TypeError as e:
+    assert str(e) == 'not all arguments converted during string formatting', str(e)
+
+try:
+    '%d' % 'a'
+    assert False, '%d of a string should fail'
+except TypeError as e:
+    assert str(e) == '%d format: a real number is required, not str', str(e)
+
+try:
+    '%x' % 1.5
+    assert False, '%x of a float should fail'
+except TypeError as e:
+    assert str(e) == '%x format: an integer is required, not float', str(e)
+
+try:
+    '%f' % 'a'
+    assert False, '%f of a string should fail'
+except TypeError as e:
+    assert str(e) == 'must be real number, not str', str(e)
+
+try:
+    '%c' % 'ab'
+    assert False, '%c of a long string should fail'
+except TypeError as e:
+    assert str(e) == '%c requires int or char', str(e)
+
+try:
+    '%y' % 1
+    assert False, 'unknown conversion should fail'
+except ValueError as e:
+    assert str(e) == "unsupported format character 'y' (0x79) at index 1", str(e)
+
+try:
+    '%(a)s' % 1
+    assert False, 'key without a mapping should fail'
+except TypeError as e:
+    assert str(e) == 'format requires a mapping', str(e)
+
+try:
+    '%(a)d' % {}
+    assert False, 'missing key should fail'
+except KeyError as e:
+    assert e.args == ('a',), e.args
+
+try:
+    'abc %' % ()
+    assert False, 'incomplete specifier should fail'
+except ValueError as e:
+    assert str(e) == 'incomplete format', str(e)
+
+try:
+    '%(a' % {'a': 1}
+    assert False, 'incomplete key should fail'
+except ValueError as e:
+    assert str(e) == 'incomplete format key', str(e)
@@ -0,0 +1,76 @@
+import string
+from string import Template
+
+# === constants ===
+assert string.ascii_lowercase == 'abcdefghijklmnopqrstuvwxyz', 'ascii_lowercase'
+assert string.ascii_uppercase == 'ABCDEFGHIJKLMNOPQRSTUVWXYZ', 'ascii_uppercase'
+assert string.ascii_letters == string.ascii_lowercase + string.ascii_uppercase, 'ascii_letters'
+assert string.digits == '0123456789', 'digits'
+assert string.hexdigits == '0123456789abcdefABCDEF', 'hexdigits'
+assert string.octdigits == '01234567', 'octdigits'
+assert string.punctuation == '!"#$%&\'()*+,-./:;<=>?@[\\]^_`{|}~', 'punctuation'
+assert string.whitespace == ' \t\n\r\x0b\x0c', 'whitespace'
+assert string.printable == string.digits + string.ascii_letters + string.punctuation + string.whitespace, 'printable'
+assert len(string.printable) == 100, 'printable length'
+
+# === capwords() ===
+assert string.capwords('hello world') == 'Hello World', 'capwords basic'
+assert string.capwords('  hello   WORLD  ') == 'Hello World', 'capwords collapses whitespace'
+assert string.capwords('hello-world foo-bar', '-') == 'Hello-World foo-Bar', 'capwords with sep'
+assert string.capwords('a,b,,c', sep=',') == 'A,B,,C', 'capwords keeps empty words'
+assert string.capwords('') == '', 'capwords empty'
+assert string.capwords("they're bill's friends") == "They're Bill's Friends", 'capwords apostrophes'
+
+# === Template.substitute() ===
+t = Template('$who likes $what')
+assert t.substitute(who='tim', what='kung pao') == 'tim likes kung pao', 'substitute keywords'
+assert t.substitute({'who': 'tim', 'what': 'pie'}) == 'tim likes pie', 'substitute mapping'
+assert t.substitute({'who': 'tim', 'what': 'pie'}, what='cake') == 'tim likes cake', 'keywords win over mapping'
+assert Template('${noun}ification').substitute(noun='Python') == 'Pythonification', 'braced placeholder'
+assert Template('$$ is a dollar').substitute() == '$ is a dollar', 'escaped dollar'
+assert Template('$x and $x').substitute(x=1) == '1 and 1', 'values converted with str()'
+assert Template('$a$b').substitute(a=[1], b=None) == '[1]None', 'adjacent placeholders'
+assert t.template == '$who likes $what', 'template attribute'
+assert t.delimiter == '$', 'delimiter attribute'
+
+# === Template.safe_substitute() ===
+assert t.safe_substitute(who='tim') == 'tim likes $what', 'safe_substitute keeps missing'
+assert Template('$ and ${x').safe_substitute() == '$ and ${x', 'safe_substitute keeps invalid'
+assert Template('${who} pays $5').safe_substitute(who='al') == 'al pays $5', 'safe_substitute invalid name'
+
+# === get_identifiers() and is_valid() ===
+assert Template('$a ${b} $a $$c').get_identifiers() == ['a', 'b'], 'get_identifiers'
+assert Template('$a $b').is_valid(), 'is_valid'
+assert not Template('$a $').is_valid(), 'is_valid with a bare dollar'
+assert not Template('${a').is_valid(), 'is_valid with an unclosed brace'
+
+# === errors ===
+try:
+    t.substitute(who='tim')
+    assert False, 'missing key should raise'
+except KeyError as e:
+    assert e.args == ('what',), f'missing key: {e.args}'
+
+try:
+    Template('abc $ def').substitute()
+    assert False, 'invalid placeholder should raise'
+except ValueError as e:
+    assert str(e) == 'Invalid placeholder in string: line 1, col 5', f'invalid placeholder: {e}'
+
+try:
+    Template('one\ntwo $1').substitute()
+    assert False, 'invalid placeholder on line 2 should raise'
+except ValueError as e:
+    assert str(e) == 'Invalid placeholder in string: line 2, col 5', f'invalid placeholder line 2: {e}'
+
+try:
+    string.capwords('a b', '')
+    assert False, 'empty separator should raise'
+except ValueError as e:
+    assert str(e) == 'empty separator', f'empty separator: {e}'
+
+try:
+    string.capwords(1)
+    assert False, 'capwords of int should raise'
+except AttributeError as e:
+    assert str(e) == "'int' object has no attribute 'split'", f'capwords of int: {e}'
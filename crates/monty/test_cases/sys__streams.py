@@ -0,0 +1,22 @@
+import sys
+
+# === write() and flush() ===
+assert sys.stdout.write('') == 0, 'write empty returns 0'
+assert sys.stderr.write('hé\n') == 3, 'write returns number of characters'
+assert sys.stdout.flush() is None, 'flush returns None'
+print('to stderr', file=sys.stderr)
+print('to stdout', file=sys.stdout)
+print('to default', file=None)
+
+# === Errors ===
+try:
+    sys.stdout.write(1)
+    assert False, 'write of non-str should error'
+except TypeError as e:
+    assert str(e) == 'write() argument must be str, not int', f'write message, error: {e}'
+
+try:
+    print('x', file=5)
+    assert False, 'print to non-file should error'
+except AttributeError as e:
+    assert str(e) == "'int' object has no attribute 'write'", f'print file message, error: {e}'
@@ -0,0 +1,97 @@
+import textwrap
+
+text = 'The quick brown fox jumps over the lazy dog. It was a sunny day, and the dog did not mind.'
+
+# === wrap() ===
+assert textwrap.wrap(text, 20) == [
+    'The quick brown fox',
+    'jumps over the lazy',
+    'dog. It was a sunny',
+    'day, and the dog did',
+    'not mind.',
+], 'wrap width 20'
+assert textwrap.wrap('') == [], 'wrap empty'
+assert textwrap.wrap('   ') == [], 'wrap whitespace'
+assert textwrap.wrap('a\tb', width=10) == ['a       b'], 'wrap expands tabs'
+assert textwrap.wrap('a\tb', width=10, tabsize=2) == ['a b'], 'wrap tabsize'
+assert textwrap.wrap('supercalifragilistic', 6) == ['superc', 'alifra', 'gilist', 'ic'], 'wrap breaks long words'
+assert textwrap.wrap('supercalifragilistic is long', 6, break_long_words=False) == [
+    'supercalifragilistic',
+    'is',
+    'long',
+], 'wrap without breaking long words'
+assert textwrap.wrap('well-known hyphenated-words here', 12) == [
+    'well-known',
+    'hyphenated-',
+    'words here',
+], 'wrap at hyphens'
+assert textwrap.wrap('well-known hyphenated-words here', 12, break_on_hyphens=False) == [
+    'well-known h',
+    'yphenated-wo',
+    'rds here',
+], 'wrap without breaking on hyphens'
+assert textwrap.wrap(text, 30, initial_indent='* ', subsequent_indent='  ') == [
+    '* The quick brown fox jumps',
+    '  over the lazy dog. It was a',
+    '  sunny day, and the dog did',
+    '  not mind.',
+], 'wrap with indents'
+assert textwrap.wrap(text, 30, max_lines=2) == [
+    'The quick brown fox jumps over',
+    'the lazy dog. It was a [...]',
+], 'wrap max_lines'
+assert textwrap.wrap('Hello there.  How are you? Fine.', 40, fix_sentence_endings=True) == [
+    'Hello there.  How are you?  Fine.'
+], 'wrap fix_sentence_endings'
+assert textwrap.wrap('  indented text', 40) == ['  indented text'], 'wrap keeps leading whitespace of the first line'
+assert textwrap.wrap('abc   def', 4) == ['abc', 'def'], 'wrap drops whitespace at line breaks'
+assert textwrap.wrap('abc   def', 4, drop_whitespace=False) == ['abc', '   ', 'def'], 'wrap keeps whitespace'
+assert textwrap.wrap('a b\nc', 40, replace_whitespace=False) == ['a b\nc'], 'wrap without replacing whitespace'
+
+# === fill() ===
+assert textwrap.fill(text, 40) == (
+    'The quick brown fox jumps over the lazy\ndog. It was a sunny day, and the dog did\nnot mind.'
+), 'fill'
+assert textwrap.fill('') == '', 'fill empty'
+
+# === shorten() ===
+assert textwrap.shorten('Hello  world!', width=12) == 'Hello world!', 'shorten fits'
+assert textwrap.shorten('Hello  world!', width=11) == 'Hello [...]', 'shorten truncates'
+assert textwrap.shorten('Hello world', width=10, placeholder='...') == 'Hello...', 'shorten placeholder'
+assert textwrap.shorten(text, 25) == 'The quick brown fox [...]', 'shorten long text'
+
+# === dedent() ===
+assert textwrap.dedent('    a\n    b\n') == 'a\nb\n', 'dedent common'
+assert textwrap.dedent('    a\n      b\n') == 'a\n  b\n', 'dedent keeps relative indent'
+assert textwrap.dedent('  a\n\n  b') == 'a\n\nb', 'dedent ignores blank lines'
+assert textwrap.dedent('  a\n    \n  b') == 'a\n\nb', 'dedent normalizes whitespace-only lines'
+assert textwrap.dedent('\ta\n  b') == '\ta\n  b', 'dedent tabs and spaces differ'
+assert textwrap.dedent('no indent\n  x') == 'no indent\n  x', 'dedent nothing common'
+
+# === indent() ===
+assert textwrap.indent('a\nb\n', '> ') == '> a\n> b\n', 'indent'
+assert textwrap.indent('a\n\n  \nb', '> ') == '> a\n\n  \n> b', 'indent skips blank lines'
+assert textwrap.indent('a\n\nb', '+', lambda line: True) == '+a\n+\n+b', 'indent with predicate'
+assert textwrap.indent('one\ntwo\nthree', '- ', predicate=lambda line: 't' in line) == (
+    'one\n- two\n- three'
+), 'indent predicate keyword'
+assert textwrap.indent('a\r\nb', '.') == '.a\r\n.b', 'indent keeps line breaks'
+
+# === errors ===
+try:
+    textwrap.wrap('abc', 0)
+    assert False, 'width 0 should raise'
+except ValueError as e:
+    assert str(e) == 'invalid width 0 (must be > 0)', f'width error: {e}'
+
+try:
+    textwrap.shorten('hello world', 3)
+    assert False, 'placeholder too large should raise'
+except ValueError as e:
+    assert str(e) == 'placeholder too large for max width', f'placeholder error: {e}'
+
+try:
+    textwrap.indent('a', '>', lambda line: 1 / 0)
+    assert False, 'predicate error should propagate'
+except ZeroDivisionError:
+    pass
@@ -0,0 +1,20 @@
+items = []
+try:
+    items[0]
+except IndexError:
+    raise ValueError('no items')
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "traceback__exception_context.py", line 3, in <module>
+    items[0]
+    ~~~~~~~~
+IndexError: list index out of range
+
+During handling of the above exception, another exception occurred:
+
+Traceback (most recent call last):
+  File "traceback__exception_context.py", line 5, in <module>
+    raise ValueError('no items')
+ValueError: no items
+"""
@@ -0,0 +1,16 @@
+def numbers():
+    yield 1
+    raise ValueError('bad value')
+
+
+result = list(numbers())
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "traceback__generator.py", line 6, in <module>
+    result = list(numbers())
+             ~~~~~~~~~~~~~~~
+  File "traceback__generator.py", line 3, in numbers
+    raise ValueError('bad value')
+ValueError: bad value
+"""
@@ -0,0 +1,33 @@
+def check(value):
+    if value < 0:
+        raise ValueError('negative')
+
+
+def process(value):
+    try:
+        check(value)
+    except ValueError as e:
+        raise RuntimeError('could not process') from e
+
+
+process(-1)
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "traceback__raise_from.py", line 8, in process
+    check(value)
+    ~~~~~~~~~~~~
+  File "traceback__raise_from.py", line 3, in check
+    raise ValueError('negative')
+ValueError: negative
+
+The above exception was the direct cause of the following exception:
+
+Traceback (most recent call last):
+  File "traceback__raise_from.py", line 13, in <module>
+    process(-1)
+    ~~~~~~~~~~~
+  File "traceback__raise_from.py", line 10, in process
+    raise RuntimeError('could not process') from e
+RuntimeError: could not process
+"""
@@ -0,0 +1,11 @@
+try:
+    1 / 0
+except ZeroDivisionError:
+    raise ValueError('hidden context') from None
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "traceback__raise_from_none.py", line 4, in <module>
+    raise ValueError('hidden context') from None
+ValueError: hidden context
+"""
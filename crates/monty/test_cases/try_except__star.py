@@ -0,0 +1,129 @@
+# === Matching parts of a group ===
+log = []
+try:
+    raise ExceptionGroup('eg', [ValueError(1), TypeError(2), ValueError(3)])
+except* ValueError as e:
+    log.append(repr(e))
+except* TypeError as e:
+    log.append(repr(e))
+assert log == ["ExceptionGroup('eg', [ValueError(1), ValueError(3)])", "ExceptionGroup('eg', [TypeError(2)])"], (
+    'each clause gets its part'
+)
+
+# === Naked exceptions are wrapped ===
+try:
+    raise ValueError(1)
+except* ValueError as e:
+    assert type(e) is ExceptionGroup, 'naked exception is wrapped'
+    assert e.message == '', 'wrapper has an empty message'
+    assert repr(e.exceptions) == '(ValueError(1),)', 'wrapper holds the exception'
+
+try:
+    try:
+        raise ValueError(1)
+    except* TypeError:
+        assert False, 'should not match'
+except ValueError as e:
+    assert repr(e) == 'ValueError(1)', 'unmatched naked exception propagates unchanged'
+
+# === Unhandled parts propagate ===
+try:
+    try:
+        raise ExceptionGroup('outer', [ValueError('a'), ExceptionGroup('inner', [TypeError('b'), ValueError('c')])])
+    except* ValueError:
+        pass
+except ExceptionGroup as e:
+    assert repr(e) == "ExceptionGroup('outer', [ExceptionGroup('inner', [TypeError('b')])])", 'rest propagates'
+
+# === Exceptions raised in clauses ===
+try:
+    try:
+        raise ExceptionGroup('eg', [ValueError(1)])
+    except* ValueError:
+        raise KeyError('k')
+except KeyError as e:
+    assert repr(e) == "KeyError('k')", 'single raised exception propagates alone'
+    assert repr(e.__context__) == "ExceptionGroup('eg', [ValueError(1)])", 'context is the matched part'
+
+try:
+    try:
+        raise ExceptionGroup('outer', [ValueError('a'), ExceptionGroup('inner', [TypeError('b')])])
+    except* ValueError:
+        raise KeyError('k')
+except ExceptionGroup as e:
+    assert repr(e) == "ExceptionGroup('', [KeyError('k'), ExceptionGroup('outer', [ExceptionGroup('inner', [TypeError('b')])])])", (
+        'raised exceptions combined with the rest'
+    )
+
+try:
+    try:
+        raise ExceptionGroup('eg', [ValueError(1), TypeError(2)])
+    except* ValueError:
+        raise KeyError('k')
+    except* TypeError:
+        raise IndexError('i')
+except ExceptionGroup as e:
+    assert repr(e) == "ExceptionGroup('', [KeyError('k'), IndexError('i')])", 'several raised exceptions'
+
+# === Bare raise ===
+try:
+    try:
+        raise ExceptionGroup('eg', [ValueError(1), TypeError(2)])
+    except* ValueError:
+        raise
+except ExceptionGroup as e:
+    assert repr(e) == "ExceptionGroup('eg', [ValueError(1), TypeError(2)])", 'bare raise keeps the original group'
+
+# === else and finally ===
+log = []
+try:
+    log.append('body')
+except* ValueError:
+    log.append('handler')
+else:
+    log.append('else')
+finally:
+    log.append('finally')
+assert log == ['body', 'else', 'finally'], 'else runs without exception'
+
+log = []
+try:
+    raise ExceptionGroup('eg', [ValueError(1)])
+except* ValueError:
+    log.append('handler')
+else:
+    log.append('else')
+finally:
+    log.append('finally')
+assert log == ['handler', 'finally'], 'else skipped after a handled exception'
+
+# === Name is unbound after the clause ===
+try:
+    raise ExceptionGroup('eg', [ValueError(1)])
+except* ValueError as err:
+    pass
+try:
+    err
+    assert False, 'name should be unbound'
+except NameError:
+    pass
+
+# === BaseExceptionGroup ===
+try:
+    try:
+        raise BaseExceptionGroup('b', [KeyboardInterrupt(), ValueError(1)])
+    except* ValueError as e:
+        assert repr(e) == "ExceptionGroup('b', [ValueError(1)])", 'matched part is an ExceptionGroup'
+except BaseExceptionGroup as e:
+    assert repr(e) == "BaseExceptionGroup('b', [KeyboardInterrupt()])", 'unmatched part propagates'
+
+# === Loops inside clauses ===
+total = 0
+try:
+    raise ExceptionGroup('eg', [ValueError(1)])
+except* ValueError:
+    for i in range(10):
+        if i == 3:
+            break
+        total += i
+assert total == 3, 'break in a loop inside except*'
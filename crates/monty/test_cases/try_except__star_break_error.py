@@ -0,0 +1,13 @@
+for i in range(3):
+    try:
+        raise ValueError(i)
+    except* ValueError:
+        break
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "try_except__star_break_error.py", line 5
+    break
+    ~~~~~
+SyntaxError: 'break', 'continue' and 'return' cannot appear in an except* block
+"""
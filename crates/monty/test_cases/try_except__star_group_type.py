@@ -0,0 +1,5 @@
+try:
+    raise ValueError(1)
+except* ExceptionGroup:
+    pass
+# Raise=TypeError('catching ExceptionGroup with except* is not allowed. Use except instead.')
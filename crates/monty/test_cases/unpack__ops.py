@@ -151,3 +151,38 @@ assert tail == [], 'single item: tail is empty'
 assert a == 1, 'bracket syntax: a'
 assert b == [2, 3], 'bracket syntax: b'
 assert c == 4, 'bracket syntax: c'
+
+# === Unpacking other iterables ===
+a, b, c = range(3)
+assert (a, b, c) == (0, 1, 2), 'unpack range'
+
+k1, k2 = {'x': 1, 'y': 2}
+assert (k1, k2) == ('x', 'y'), 'unpack dict keys'
+
+a, b = iter([10, 20])
+assert (a, b) == (10, 20), 'unpack iterator'
+
+a, b = (i * 2 for i in range(2))
+assert (a, b) == (0, 2), 'unpack generator'
+
+a, b = b'hi'
+assert (a, b) == (104, 105), 'unpack bytes'
+
+first, *rest = range(4)
+assert first == 0, 'star from range: first'
+assert rest == [1, 2, 3], 'star from range: rest'
+
+*keys, last = {'a': 1, 'b': 2, 'c': 3}
+assert keys == ['a', 'b'], 'star from dict: keys'
+assert last == 'c', 'star from dict: last'
+
+it = iter([1, 2, 3])
+head, *tail = it
+assert head == 1, 'star from iterator: head'
+assert tail == [2, 3], 'star from iterator: tail'
+assert next(it, 'done') == 'done', 'star consumes the iterator'
+
+for x, *ys in [(1, 2, 3), (4,)]:
+    assert isinstance(ys, list), 'star in for loop target'
+assert x == 4, 'star in for loop: last x'
+assert ys == [], 'star in for loop: last ys'
@@ -0,0 +1,67 @@
+# === Starred items in list displays ===
+a = [1, 2]
+b = (3, 4)
+assert [*a, *b] == [1, 2, 3, 4], 'list from two starred'
+assert [0, *a, 5] == [0, 1, 2, 5], 'list with starred in the middle'
+assert [*a] == [1, 2], 'list with only starred'
+assert [*a] is not a, 'starred copy is a new list'
+assert [*range(3), *'ab'] == [0, 1, 2, 'a', 'b'], 'list from range and str'
+assert [*[], *()] == [], 'list from empty iterables'
+assert [*{'x': 1, 'y': 2}] == ['x', 'y'], 'list from dict keys'
+assert [*(i * i for i in range(3))] == [0, 1, 4], 'list from generator'
+assert [*iter([7, 8])] == [7, 8], 'list from iterator'
+
+# === Starred items in tuple displays ===
+xs = [1, 2, 3]
+t = (*xs, 4)
+assert t == (1, 2, 3, 4), 'tuple with starred first'
+assert isinstance(t, tuple), 'starred tuple display is a tuple'
+t = *xs, 0
+assert t == (1, 2, 3, 0), 'tuple without parens'
+assert (*xs,) == (1, 2, 3), 'tuple with only starred'
+assert (0, *range(2), *b) == (0, 0, 1, 3, 4), 'tuple with several starred'
+
+
+def returns_starred(items):
+    return *items, 'end'
+
+
+assert returns_starred([1]) == (1, 'end'), 'return starred tuple'
+
+# === Starred items in set displays ===
+s = {*a, *b}
+assert s == {1, 2, 3, 4}, 'set from two starred'
+assert {*a, 2, *[2, 5]} == {1, 2, 5}, 'set with duplicates'
+assert {0, *range(3)} == {0, 1, 2}, 'set with starred range'
+
+# === Mapping unpacking in dict displays ===
+d1 = {'a': 1, 'b': 2}
+d2 = {'b': 3, 'c': 4}
+assert {**d1, **d2} == {'a': 1, 'b': 3, 'c': 4}, 'merge dicts, later wins'
+assert {**d1, 'a': 10} == {'a': 10, 'b': 2}, 'pair after unpack overrides'
+assert {'a': 10, **d1} == {'a': 1, 'b': 2}, 'unpack after pair overrides'
+assert list({'z': 0, **d1, 'y': 5}) == ['z', 'a', 'b', 'y'], 'insertion order is kept'
+assert {**{}} == {}, 'unpack empty dict'
+assert {**{1: 'one', (2, 3): 'pair'}} == {1: 'one', (2, 3): 'pair'}, 'non-string keys'
+merged = {**d1}
+assert merged == d1 and merged is not d1, 'unpacked copy is a new dict'
+
+# === Evaluation order ===
+order = []
+
+
+def log(value):
+    order.append(value)
+    return value
+
+
+[log(1), *log([2]), log(3)]
+assert order == [1, [2], 3], 'list display evaluation order'
+order = []
+{log('k'): log('v'), **log({}), log('k2'): log('v2')}
+assert order == ['k', 'v', {}, 'k2', 'v2'], 'dict display evaluation order'
+
+# === Starred in subscripts ===
+grid = {(1, 2): 'x'}
+pos = [1, 2]
+assert grid[*pos,] == 'x', 'starred tuple as subscript'
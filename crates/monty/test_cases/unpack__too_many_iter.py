@@ -0,0 +1,9 @@
+a, b = iter([1, 2, 3])
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "unpack__too_many_iter.py", line 1, in <module>
+    a, b = iter([1, 2, 3])
+    ~~~~
+ValueError: too many values to unpack (expected 2)
+"""
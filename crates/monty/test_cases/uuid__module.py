@@ -0,0 +1,80 @@
+import uuid
+from uuid import UUID
+
+# === UUID() ===
+u = UUID('12345678-1234-5678-1234-567812345678')
+assert str(u) == '12345678-1234-5678-1234-567812345678', 'str'
+assert repr(u) == "UUID('12345678-1234-5678-1234-567812345678')", 'repr'
+assert u.hex == '12345678123456781234567812345678', 'hex'
+assert u.int == 24197857161011715162171839636988778104, 'int'
+assert u.bytes == b'\x124Vx\x124Vx\x124Vx\x124Vx', 'bytes'
+assert u.bytes_le == b'xV4\x124\x12xV\x124Vx\x124Vx', 'bytes_le'
+assert u.urn == 'urn:uuid:12345678-1234-5678-1234-567812345678', 'urn'
+assert u.variant == 'reserved for NCS compatibility', 'variant'
+assert u.version is None, 'version of a non-RFC 4122 UUID'
+assert u.fields == (305419896, 4660, 22136, 18, 52, 95073701484152), 'fields'
+
+# all the forms of the constructor give the same UUID
+assert UUID('{12345678-1234-5678-1234-567812345678}') == u, 'braces'
+assert UUID('urn:uuid:12345678-1234-5678-1234-567812345678') == u, 'urn prefix'
+assert UUID('12345678123456781234567812345678') == u, 'no hyphens'
+assert UUID(hex='12345678123456781234567812345678') == u, 'hex keyword'
+assert UUID(bytes=u.bytes) == u, 'bytes'
+assert UUID(bytes_le=u.bytes_le) == u, 'bytes_le'
+assert UUID(fields=u.fields) == u, 'fields'
+assert UUID(int=u.int) == u, 'int'
+assert str(UUID(int=5, version=4)) == '00000000-0000-4000-8000-000000000005', 'version sets the version bits'
+
+# === comparison and hashing ===
+a = UUID(int=1)
+b = UUID(int=2)
+assert a < b and b > a and a <= a and a != b, 'ordering'
+assert sorted([b, a]) == [a, b], 'sorting'
+assert UUID(int=1) == a, 'equality'
+assert a != 'x', 'not equal to other types'
+assert {a: 'one'}[UUID(int=1)] == 'one', 'hashable'
+assert len({a, UUID(int=1), b}) == 2, 'set of UUIDs'
+
+# === namespaces ===
+assert str(uuid.NAMESPACE_DNS) == '6ba7b810-9dad-11d1-80b4-00c04fd430c8', 'NAMESPACE_DNS'
+assert str(uuid.NAMESPACE_URL) == '6ba7b811-9dad-11d1-80b4-00c04fd430c8', 'NAMESPACE_URL'
+assert str(uuid.NAMESPACE_OID) == '6ba7b812-9dad-11d1-80b4-00c04fd430c8', 'NAMESPACE_OID'
+assert str(uuid.NAMESPACE_X500) == '6ba7b814-9dad-11d1-80b4-00c04fd430c8', 'NAMESPACE_X500'
+assert uuid.NAMESPACE_DNS.variant == 'specified in RFC 4122', 'namespace variant'
+assert uuid.NAMESPACE_DNS.version == 1, 'namespace version'
+
+# === uuid3() and uuid5() ===
+u3 = uuid.uuid3(uuid.NAMESPACE_DNS, 'python.org')
+assert str(u3) == '6fa459ea-ee8a-3ca4-894e-db77e160355e', 'uuid3'
+assert u3.version == 3, 'uuid3 version'
+u5 = uuid.uuid5(uuid.NAMESPACE_DNS, 'python.org')
+assert str(u5) == '886313e1-3b8a-5372-9b90-0c9aee199e5d', 'uuid5'
+assert u5.version == 5, 'uuid5 version'
+assert u5.variant == 'specified in RFC 4122', 'uuid5 variant'
+assert str(uuid.uuid5(uuid.NAMESPACE_URL, b'x')) == '4cd605e7-afa2-5360-b5b9-c5e9fb5c76f4', 'uuid5 of bytes'
+
+
+# === errors ===
+def error(exc_type, f, *args, **kwargs):
+    try:
+        f(*args, **kwargs)
+    except exc_type as e:
+        return str(e)
+    assert False, f'{f} should have raised'
+
+
+assert error(ValueError, UUID, 'xyz') == 'badly formed hexadecimal UUID string', 'short hex'
+assert error(ValueError, UUID, 'g' * 32) == "invalid literal for int() with base 16: '" + 'g' * 32 + "'", 'bad hex'
+assert error(ValueError, UUID, bytes=b'a') == 'bytes is not a 16-char string', 'short bytes'
+assert error(ValueError, UUID, int=-1) == 'int is out of range (need a 128-bit value)', 'negative int'
+assert error(TypeError, UUID) == 'one of the hex, bytes, bytes_le, fields, or int arguments must be given', 'none'
+assert error(TypeError, UUID, '0' * 32, int=1) == (
+    'one of the hex, bytes, bytes_le, fields, or int arguments must be given'
+), 'two forms'
+assert error(ValueError, UUID, fields=(1, 2, 3, 4, 5)) == 'fields is not a 6-tuple', 'short fields'
+assert error(ValueError, UUID, fields=(1 << 32, 0, 0, 0, 0, 0)) == (
+    'field 1 out of range (need a 32-bit value)'
+), 'field out of range'
+assert error(ValueError, UUID, '0' * 32, version=9) == 'illegal version number', 'bad version'
+assert error(TypeError, uuid.uuid3, uuid.NAMESPACE_DNS, 1) == "can't concat int to bytes", 'uuid3 of int'
+assert error(AttributeError, uuid.uuid5, 'x', 'a') == "'str' object has no attribute 'bytes'", 'uuid5 of str namespace'
@@ -0,0 +1,13 @@
+# call-external
+# uuid.uuid4() asks the host for random bytes via an OS call,
+# so only properties which hold for any bytes are checked here
+import uuid
+
+u = uuid.uuid4()
+assert type(u) is uuid.UUID, 'uuid4() returns a UUID'
+assert u.version == 4, 'uuid4() sets the version'
+assert u.variant == 'specified in RFC 4122', 'uuid4() sets the variant'
+assert len(str(u)) == 36, 'canonical form'
+assert str(u)[14] == '4', 'version digit'
+assert str(u)[19] in '89ab', 'variant digit'
+assert uuid.UUID(str(u)) == u, 'round trip'
@@ -0,0 +1,35 @@
+# run-async
+events = []
+
+
+class AsyncRecorder:
+    def __init__(self, name, suppress=False):
+        self.name = name
+        self.suppress = suppress
+
+    async def __aenter__(self):
+        events.append(('enter', self.name))
+        return self.name.upper()
+
+    async def __aexit__(self, exc_type, exc, tb):
+        events.append(('exit', self.name, exc_type))
+        return self.suppress
+
+
+async def main():
+    async with AsyncRecorder('a') as value:
+        events.append(('body', value))
+    async with AsyncRecorder('b', suppress=True):
+        raise ValueError('boom')
+    return 'done'
+
+
+result = await main()  # pyright: ignore
+assert result == 'done', 'async with completes'
+assert events == [
+    ('enter', 'a'),
+    ('body', 'A'),
+    ('exit', 'a', None),
+    ('enter', 'b'),
+    ('exit', 'b', ValueError),
+], 'async with awaits __aenter__ and __aexit__'
@@ -0,0 +1,163 @@
+# === enter and exit ===
+events = []
+
+
+class Recorder:
+    def __init__(self, name, suppress=False):
+        self.name = name
+        self.suppress = suppress
+
+    def __enter__(self):
+        events.append(('enter', self.name))
+        return self.name.upper()
+
+    def __exit__(self, exc_type, exc, tb):
+        events.append(('exit', self.name, exc_type, exc))
+        return self.suppress
+
+
+with Recorder('a') as value:
+    events.append(('body', value))
+assert value == 'A', 'as target is bound to the result of __enter__'
+assert events == [('enter', 'a'), ('body', 'A'), ('exit', 'a', None, None)], 'enter, body, exit order'
+
+# === without a target ===
+events = []
+with Recorder('b'):
+    events.append('body')
+assert events == [('enter', 'b'), 'body', ('exit', 'b', None, None)], 'with without as'
+
+# === tuple target ===
+
+
+class Pair:
+    def __enter__(self):
+        return (1, 2)
+
+    def __exit__(self, exc_type, exc, tb):
+        return False
+
+
+with Pair() as (first, second):
+    pass
+assert (first, second) == (1, 2), 'as target can be unpacked'
+
+# === several items ===
+events = []
+with Recorder('outer') as x, Recorder('inner') as y:
+    events.append((x, y))
+assert events == [
+    ('enter', 'outer'),
+    ('enter', 'inner'),
+    ('OUTER', 'INNER'),
+    ('exit', 'inner', None, None),
+    ('exit', 'outer', None, None),
+], 'items are entered in order and exited in reverse'
+
+# === exception passed to __exit__ ===
+events = []
+try:
+    with Recorder('c'):
+        raise ValueError('boom')
+except ValueError as e:
+    events.append(('caught', str(e)))
+assert events[0] == ('enter', 'c'), 'enter runs first'
+assert events[1][:3] == ('exit', 'c', ValueError), '__exit__ gets the exception type'
+assert isinstance(events[1][3], ValueError), '__exit__ gets the exception'
+assert events[2] == ('caught', 'boom'), 'falsy __exit__ result lets the exception propagate'
+
+# === suppressing the exception ===
+events = []
+with Recorder('d', suppress=True):
+    raise KeyError('missing')
+    events.append('unreachable')
+events.append('after')
+assert len(events) == 3, 'body stops at the exception'
+assert events[1][2] is KeyError, '__exit__ gets KeyError'
+assert events[2] == 'after', 'truthy __exit__ result suppresses the exception'
+
+# === exception in __enter__ ===
+
+
+class BadEnter:
+    def __enter__(self):
+        raise RuntimeError('cannot enter')
+
+    def __exit__(self, exc_type, exc, tb):
+        events.append('exit')
+
+
+events = []
+try:
+    with BadEnter():
+        events.append('body')
+except RuntimeError as e:
+    events.append(str(e))
+assert events == ['cannot enter'], '__exit__ is not called when __enter__ fails'
+
+# === exception in __exit__ ===
+
+
+class BadExit:
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc, tb):
+        raise TypeError('cannot exit')
+
+
+try:
+    with BadExit():
+        raise ValueError('original')
+except TypeError as e:
+    assert str(e) == 'cannot exit', 'exception from __exit__ replaces the original'
+
+# === return inside with ===
+events = []
+
+
+def get_value():
+    with Recorder('ret'):
+        return 42
+
+
+assert get_value() == 42, 'return value is kept'
+assert events == [('enter', 'ret'), ('exit', 'ret', None, None)], '__exit__ runs on return'
+
+# === break and continue inside with ===
+events = []
+for i in range(3):
+    with Recorder(str(i)):
+        if i == 0:
+            continue
+        if i == 1:
+            break
+assert events == [
+    ('enter', '0'),
+    ('exit', '0', None, None),
+    ('enter', '1'),
+    ('exit', '1', None, None),
+], '__exit__ runs on continue and break'
+
+# === with inside try/finally ===
+events = []
+
+
+def nested():
+    try:
+        with Recorder('inner'):
+            return 'value'
+    finally:
+        events.append('finally')
+
+
+assert nested() == 'value', 'return through with and finally'
+assert events == [('enter', 'inner'), ('exit', 'inner', None, None), 'finally'], 'exit runs before finally'
+
+# === suppressing inside a loop ===
+count = 0
+for i in range(3):
+    with Recorder('loop', suppress=True):
+        count += 1
+        raise ValueError(i)
+assert count == 3, 'suppressed exceptions do not stop the loop'
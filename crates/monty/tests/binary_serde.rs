@@ -181,3 +181,110 @@ fn run_progress_complete_roundtrip() {
 
     assert_eq!(loaded.into_complete().unwrap(), MontyObject::Int(3));
 }
+
+#[test]
+fn run_progress_dump_load_suspended_generator() {
+    // A generator suspended at a yield lives on the heap and resumes after dump/load
+    let code = "
+def numbers():
+    n = 0
+    while True:
+        n += 1
+        yield n
+
+g = numbers()
+a = next(g)
+b = ext_fn(a)
+[a, b, next(g), next(g)]
+";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec!["ext_fn".to_owned()]).unwrap();
+
+    let progress = runner.start(vec![], NoLimitTracker, &mut StdPrint).unwrap();
+    let bytes = progress.dump().unwrap();
+    let loaded: RunProgress<NoLimitTracker> = RunProgress::load(&bytes).unwrap();
+    let (fn_name, args, _, _call_id, state) = loaded.into_function_call().unwrap();
+    assert_eq!(fn_name, "ext_fn");
+    assert_eq!(args, vec![MontyObject::Int(1)]);
+
+    let result = state.run(MontyObject::Int(10), &mut StdPrint).unwrap();
+    let expected = MontyObject::List(vec![
+        MontyObject::Int(1),
+        MontyObject::Int(10),
+        MontyObject::Int(2),
+        MontyObject::Int(3),
+    ]);
+    assert_eq!(result.into_complete().unwrap(), expected);
+}
+
+#[test]
+fn run_progress_dump_load_inside_generator() {
+    // An external call made by a generator's body suspends its running frame, which is
+    // dumped and loaded along with the loop advancing it
+    let code = "
+def doubled(items):
+    for item in items:
+        yield ext_fn(item) * 2
+
+total = 0
+for value in doubled([1, 2]):
+    total += value
+total
+";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec!["ext_fn".to_owned()]).unwrap();
+
+    let mut progress = runner.start(vec![], NoLimitTracker, &mut StdPrint).unwrap();
+    for expected_arg in [1, 2] {
+        let bytes = progress.dump().unwrap();
+        let loaded: RunProgress<NoLimitTracker> = RunProgress::load(&bytes).unwrap();
+        let (fn_name, args, _, _call_id, state) = loaded.into_function_call().unwrap();
+        assert_eq!(fn_name, "ext_fn");
+        assert_eq!(args, vec![MontyObject::Int(expected_arg)]);
+        progress = state.run(MontyObject::Int(expected_arg * 10), &mut StdPrint).unwrap();
+    }
+    assert_eq!(progress.into_complete().unwrap(), MontyObject::Int(60)); // (10 + 20) * 2
+}
+
+#[test]
+fn run_progress_dump_load_partial_and_lru_cache() {
+    // A partial and a populated lru_cache wrapper live on the heap across dump/load, and
+    // the cache keeps its results and statistics
+    let code = "
+from functools import lru_cache, partial
+
+calls = []
+
+@lru_cache(maxsize=4)
+def square(x):
+    calls.append(x)
+    return x * x
+
+add = partial(lambda a, b: a + b, 100)
+square(2)
+square(3)
+square(2)
+x = ext_fn(add(1))
+y = square(2) + square(3)
+info = square.cache_info()
+[x, y, calls, info.hits, info.misses, info.currsize]
+";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec!["ext_fn".to_owned()]).unwrap();
+
+    let progress = runner.start(vec![], NoLimitTracker, &mut StdPrint).unwrap();
+    let bytes = progress.dump().unwrap();
+    let loaded: RunProgress<NoLimitTracker> = RunProgress::load(&bytes).unwrap();
+    let (fn_name, args, _, _call_id, state) = loaded.into_function_call().unwrap();
+    assert_eq!(fn_name, "ext_fn");
+    assert_eq!(args, vec![MontyObject::Int(101)]);
+
+    let result = state.run(MontyObject::Int(7), &mut StdPrint).unwrap();
+    let expected = MontyObject::List(vec![
+        MontyObject::Int(7),
+        MontyObject::Int(13),
+        // the cached results are used after loading, without calling the function again
+        MontyObject::List(vec![MontyObject::Int(2), MontyObject::Int(3)]),
+        MontyObject::Int(3),
+        MontyObject::Int(2),
+        MontyObject::Int(2),
+    ]);
+    assert_eq!(result.into_complete().unwrap(), expected);
+}
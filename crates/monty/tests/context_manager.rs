@@ -0,0 +1,141 @@
+//! Tests for context managers implemented by the host.
+//!
+//! A dataclass returned by an external function can list `__enter__` and `__exit__` in its
+//! `methods`; a `with` statement on it then yields each call to the host as a
+//! `RunProgress::FunctionCall` named after the method, with the dataclass as first argument.
+
+use monty::{ExcType, MontyObject, MontyRun, NoLimitTracker, RunProgress, Snapshot, StdPrint};
+
+/// Builds a `Session` dataclass whose context manager methods are run by the host.
+fn session(name: &str) -> MontyObject {
+    MontyObject::Dataclass {
+        name: "Session".to_string(),
+        type_id: 0, // Test fixture has no real Python type
+        field_names: vec!["name".to_string()],
+        attrs: vec![(
+            MontyObject::String("name".to_string()),
+            MontyObject::String(name.to_string()),
+        )]
+        .into(),
+        methods: vec!["__enter__".to_string(), "__exit__".to_string()],
+        frozen: true,
+    }
+}
+
+fn runner(code: &str) -> MontyRun {
+    MontyRun::new(code.to_owned(), "test.py", vec![], vec!["open_session".to_owned()]).unwrap()
+}
+
+/// Asserts that execution paused at a call of `expected_name` and returns its arguments and state.
+fn expect_call(
+    progress: RunProgress<NoLimitTracker>,
+    expected_name: &str,
+) -> (Vec<MontyObject>, Snapshot<NoLimitTracker>) {
+    let (function_name, args, _, _, state) = progress.into_function_call().expect("expected a function call");
+    assert_eq!(function_name, expected_name);
+    (args, state)
+}
+
+#[test]
+fn host_enter_and_exit_are_function_calls() {
+    let code = "
+with open_session('db') as s:
+    name = s.name
+name
+";
+    let progress = runner(code).start(vec![], NoLimitTracker, &mut StdPrint).unwrap();
+
+    let (args, state) = expect_call(progress, "open_session");
+    assert_eq!(args, vec![MontyObject::String("db".to_string())]);
+    let progress = state.run(session("db"), &mut StdPrint).unwrap();
+
+    let (args, state) = expect_call(progress, "__enter__");
+    assert_eq!(args, vec![session("db")]);
+    let progress = state.run(session("db"), &mut StdPrint).unwrap();
+
+    let (args, state) = expect_call(progress, "__exit__");
+    assert_eq!(
+        args,
+        vec![session("db"), MontyObject::None, MontyObject::None, MontyObject::None]
+    );
+    let progress = state.run(MontyObject::None, &mut StdPrint).unwrap();
+
+    assert_eq!(progress.into_complete().unwrap(), MontyObject::String("db".to_string()));
+}
+
+#[test]
+fn host_exit_sees_exception_and_can_suppress_it() {
+    let code = "
+with open_session('db'):
+    raise ValueError('boom')
+'suppressed'
+";
+    let progress = runner(code).start(vec![], NoLimitTracker, &mut StdPrint).unwrap();
+
+    let (_, state) = expect_call(progress, "open_session");
+    let progress = state.run(session("db"), &mut StdPrint).unwrap();
+    let (_, state) = expect_call(progress, "__enter__");
+    let progress = state.run(MontyObject::None, &mut StdPrint).unwrap();
+
+    let (args, state) = expect_call(progress, "__exit__");
+    assert_eq!(args.len(), 4);
+    assert_eq!(args[0], session("db"));
+    assert_eq!(
+        args[2],
+        MontyObject::Exception {
+            exc_type: ExcType::ValueError,
+            arg: Some("boom".to_string()),
+        }
+    );
+    assert_eq!(args[3], MontyObject::None);
+    let progress = state.run(MontyObject::Bool(true), &mut StdPrint).unwrap();
+
+    assert_eq!(
+        progress.into_complete().unwrap(),
+        MontyObject::String("suppressed".to_string())
+    );
+}
+
+#[test]
+fn host_exit_returning_false_propagates_exception() {
+    let code = "
+with open_session('db'):
+    raise ValueError('boom')
+";
+    let progress = runner(code).start(vec![], NoLimitTracker, &mut StdPrint).unwrap();
+
+    let (_, state) = expect_call(progress, "open_session");
+    let progress = state.run(session("db"), &mut StdPrint).unwrap();
+    let (_, state) = expect_call(progress, "__enter__");
+    let progress = state.run(MontyObject::None, &mut StdPrint).unwrap();
+    let (_, state) = expect_call(progress, "__exit__");
+
+    let exc = state.run(MontyObject::Bool(false), &mut StdPrint).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::ValueError);
+}
+
+#[test]
+fn host_context_manager_survives_dump_and_load() {
+    let code = "
+def work():
+    with open_session('db') as s:
+        return s.name
+
+work()
+";
+    let progress = runner(code).start(vec![], NoLimitTracker, &mut StdPrint).unwrap();
+    let (_, state) = expect_call(progress, "open_session");
+    let progress = state.run(session("db"), &mut StdPrint).unwrap();
+    let (_, state) = expect_call(progress, "__enter__");
+    let progress = state.run(session("db"), &mut StdPrint).unwrap();
+
+    // Snapshot while paused at `__exit__` for the `return` inside the with block
+    let bytes = progress.dump().unwrap();
+    let loaded: RunProgress<NoLimitTracker> = RunProgress::load(&bytes).unwrap();
+
+    let (args, state) = expect_call(loaded, "__exit__");
+    assert_eq!(args[0], session("db"));
+    let progress = state.run(MontyObject::None, &mut StdPrint).unwrap();
+
+    assert_eq!(progress.into_complete().unwrap(), MontyObject::String("db".to_string()));
+}
@@ -676,6 +676,31 @@ fn dispatch_os_call(
         return MontyObject::Dict(env_dict.into()).into();
     }
 
+    // DateTimeNow takes the requested timezone rather than a path, and the virtual clock is fixed
+    if function == OsFunction::DateTimeNow {
+        return MontyObject::DateTime {
+            year: 2024,
+            month: 1,
+            day: 15,
+            hour: 12,
+            minute: 30,
+            second: 45,
+            microsecond: 123_456,
+            utc_offset: Some(0),
+        }
+        .into();
+    }
+
+    // RandomSeed takes no arguments, and the seed is fixed so runs are reproducible
+    if function == OsFunction::RandomSeed {
+        return MontyObject::Int(12_345).into();
+    }
+
+    // Uuid4 takes no arguments, and the bytes are fixed so runs are reproducible
+    if function == OsFunction::Uuid4 {
+        return MontyObject::Bytes((0..16).collect()).into();
+    }
+
     // Extract path from MontyObject::Path (or String for backwards compatibility)
     let path = match &args[0] {
         MontyObject::Path(p) => p.clone(),
@@ -684,7 +709,9 @@ fn dispatch_os_call(
     };
 
     match function {
-        OsFunction::GetEnviron => unreachable!("handled above"),
+        OsFunction::GetEnviron | OsFunction::DateTimeNow | OsFunction::RandomSeed | OsFunction::Uuid4 => {
+            unreachable!("handled above")
+        }
         OsFunction::Exists => {
             let exists = get_virtual_file(&path).is_some() || is_virtual_dir(&path);
             MontyObject::Bool(exists).into()
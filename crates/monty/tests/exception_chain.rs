@@ -0,0 +1,126 @@
+//! Tests for exception chaining through the public `MontyException` API.
+//!
+//! Chained exceptions raised by Monty code are exposed via `cause()` and `context()`,
+//! and exceptions passed in by the host keep their chain inside Monty.
+
+use monty::{ExcType, ExternalResult, MontyException, MontyObject, MontyRun, NoLimitTracker, StdPrint};
+
+fn run_err(code: &str) -> MontyException {
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+    ex.run_no_limits(vec![]).unwrap_err()
+}
+
+#[test]
+fn raise_from_sets_cause() {
+    let exc = run_err(
+        "
+try:
+    1 / 0
+except ZeroDivisionError as e:
+    raise ValueError('bad') from e
+",
+    );
+    assert_eq!(exc.exc_type(), ExcType::ValueError);
+    assert!(exc.suppress_context());
+
+    let cause = exc.cause().expect("cause should be set");
+    assert_eq!(cause.exc_type(), ExcType::ZeroDivisionError);
+    assert_eq!(cause.message(), Some("division by zero"));
+    assert_eq!(cause.traceback().len(), 1);
+    assert_eq!(cause.traceback()[0].start.line, 3);
+
+    let context = exc.context().expect("context should be set");
+    assert_eq!(context.exc_type(), ExcType::ZeroDivisionError);
+    assert_eq!(exc.displayed_context(), Some(cause));
+}
+
+#[test]
+fn raise_from_none_hides_context() {
+    let exc = run_err(
+        "
+try:
+    1 / 0
+except ZeroDivisionError:
+    raise ValueError('bad') from None
+",
+    );
+    assert!(exc.cause().is_none());
+    assert!(exc.context().is_some());
+    assert!(exc.suppress_context());
+    assert!(exc.displayed_context().is_none());
+    assert!(!exc.to_string().contains("ZeroDivisionError"));
+}
+
+#[test]
+fn implicit_context_display() {
+    let exc = run_err(
+        "
+try:
+    1 / 0
+except ZeroDivisionError:
+    raise ValueError('bad')
+",
+    );
+    assert!(exc.cause().is_none());
+    assert!(!exc.suppress_context());
+    let context = exc.context().expect("context should be set");
+    assert_eq!(context.exc_type(), ExcType::ZeroDivisionError);
+    assert_eq!(exc.displayed_context(), Some(context));
+    assert_eq!(
+        exc.to_string(),
+        r#"Traceback (most recent call last):
+  File "test.py", line 3, in <module>
+    1 / 0
+    ~~~~~
+ZeroDivisionError: division by zero
+
+During handling of the above exception, another exception occurred:
+
+Traceback (most recent call last):
+  File "test.py", line 5, in <module>
+    raise ValueError('bad')
+ValueError: bad"#
+    );
+}
+
+#[test]
+fn cause_without_traceback() {
+    let exc = run_err("raise ValueError('bad') from KeyError('missing')");
+    let cause = exc.cause().expect("cause should be set");
+    assert!(cause.traceback().is_empty());
+    assert!(exc.to_string().starts_with(
+        "KeyError: missing\n\nThe above exception was the direct cause of the following exception:\n\nTraceback"
+    ));
+}
+
+#[test]
+fn host_exception_chain() {
+    let code = "
+try:
+    fetch()
+except RuntimeError as e:
+    result = (type(e.__cause__) is OSError, e.__cause__.args[0], e.__suppress_context__)
+result
+";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec!["fetch".to_owned()]).unwrap();
+    let progress = ex.start(vec![], NoLimitTracker, &mut StdPrint).unwrap();
+    let (function_name, _, _, _, state) = progress.into_function_call().expect("expected a function call");
+    assert_eq!(function_name, "fetch");
+
+    let cause = MontyException::new(ExcType::OSError, Some("connection reset".to_string()));
+    let exc = MontyException::new(ExcType::RuntimeError, Some("fetch failed".to_string())).with_chain(
+        Some(Box::new(cause)),
+        None,
+        true,
+    );
+    let progress = state.run(ExternalResult::Error(exc), &mut StdPrint).unwrap();
+    let result = progress.into_complete().expect("expected completion");
+    assert_eq!(
+        result,
+        MontyObject::Tuple(vec![
+            MontyObject::Bool(true),
+            MontyObject::String("connection reset".to_string()),
+            MontyObject::Bool(true),
+        ])
+    );
+}
@@ -0,0 +1,86 @@
+//! Tests for exception groups through the public `MontyException` API.
+//!
+//! Exception groups raised by Monty code expose their sub-exceptions via `exceptions()`,
+//! and exception groups passed in by the host keep them inside Monty.
+
+use monty::{ExcType, ExternalResult, MontyException, MontyObject, MontyRun, NoLimitTracker, StdPrint};
+
+fn run_err(code: &str) -> MontyException {
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+    ex.run_no_limits(vec![]).unwrap_err()
+}
+
+#[test]
+fn uncaught_group() {
+    let exc = run_err("raise ExceptionGroup('eg', [ValueError('a'), TypeError('b')])");
+    assert_eq!(exc.exc_type(), ExcType::ExceptionGroup);
+    assert_eq!(exc.message(), Some("eg"));
+    assert_eq!(exc.summary(), "ExceptionGroup: eg (2 sub-exceptions)");
+
+    let exceptions = exc.exceptions();
+    assert_eq!(exceptions.len(), 2);
+    assert_eq!(exceptions[0].exc_type(), ExcType::ValueError);
+    assert_eq!(exceptions[0].message(), Some("a"));
+    assert_eq!(exceptions[1].exc_type(), ExcType::TypeError);
+    assert_eq!(exceptions[1].message(), Some("b"));
+
+    assert_eq!(
+        exc.to_string(),
+        r#"  + Exception Group Traceback (most recent call last):
+  |   File "test.py", line 1, in <module>
+  |     raise ExceptionGroup('eg', [ValueError('a'), TypeError('b')])
+  | ExceptionGroup: eg (2 sub-exceptions)
+  +-+---------------- 1 ----------------
+    | ValueError: a
+    +---------------- 2 ----------------
+    | TypeError: b
+    +------------------------------------"#
+    );
+}
+
+#[test]
+fn uncaught_except_star_rest() {
+    let exc = run_err(
+        "
+try:
+    raise ExceptionGroup('eg', [ValueError(1), KeyError(2)])
+except* ValueError:
+    pass
+",
+    );
+    assert_eq!(exc.exc_type(), ExcType::ExceptionGroup);
+    let exceptions = exc.exceptions();
+    assert_eq!(exceptions.len(), 1);
+    assert_eq!(exceptions[0].exc_type(), ExcType::KeyError);
+}
+
+#[test]
+fn host_exception_group() {
+    let code = "
+try:
+    fetch()
+except* OSError as e:
+    result = (type(e) is ExceptionGroup, e.message, len(e.exceptions), e.exceptions[0].args[0])
+result
+";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec!["fetch".to_owned()]).unwrap();
+    let progress = ex.start(vec![], NoLimitTracker, &mut StdPrint).unwrap();
+    let (function_name, _, _, _, state) = progress.into_function_call().expect("expected a function call");
+    assert_eq!(function_name, "fetch");
+
+    let exc = MontyException::new(ExcType::ExceptionGroup, Some("fetch failed".to_string())).with_exceptions(vec![
+        MontyException::new(ExcType::OSError, Some("connection reset".to_string())),
+        MontyException::new(ExcType::OSError, Some("timed out".to_string())),
+    ]);
+    let progress = state.run(ExternalResult::Error(exc), &mut StdPrint).unwrap();
+    let result = progress.into_complete().expect("expected completion");
+    assert_eq!(
+        result,
+        MontyObject::Tuple(vec![
+            MontyObject::Bool(true),
+            MontyObject::String("fetch failed".to_string()),
+            MontyObject::Int(2),
+            MontyObject::String("connection reset".to_string()),
+        ])
+    );
+}
@@ -5,6 +5,7 @@
 
 use indexmap::IndexMap;
 use monty::{ExcType, MontyObject, MontyRun};
+use num_bigint::BigInt;
 
 // === Immediate Value Tests ===
 
@@ -321,6 +322,118 @@ fn invalid_input_repr_nested_in_list() {
     assert!(result.is_err(), "Repr nested in list should be invalid");
 }
 
+#[test]
+fn invalid_input_unknown_function_handle() {
+    let ex = MontyRun::new("x".to_owned(), "test.py", vec!["x".to_owned()], vec![]).unwrap();
+    let result = ex.run_no_limits(vec![MontyObject::Function {
+        name: "f".to_string(),
+        id: 0,
+    }]);
+    assert!(
+        result.is_err(),
+        "a handle which isn't a function's id should be invalid"
+    );
+}
+
+#[test]
+fn function_output_is_handle() {
+    let ex = MontyRun::new(
+        "def add(a, b=1):\n    return a + b\nadd".to_owned(),
+        "test.py",
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let result = ex.run_no_limits(vec![]).unwrap();
+    let MontyObject::Function { name, .. } = result else {
+        panic!("expected a function handle, got {result:?}");
+    };
+    assert_eq!(name, "add");
+}
+
+#[test]
+fn enum_member_output() {
+    let code = "from enum import IntEnum\nclass Level(IntEnum):\n    LOW = 1\n    HIGH = 2\n    MINIMUM = 1\n\
+                class Mode(IntEnum):\n    READ = 1\n(Level.HIGH, Level.MINIMUM, Mode.READ)";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+    let output = ex.run_no_limits(vec![]).unwrap();
+    assert_eq!(output.py_repr(), "(<Level.HIGH: 2>, <Level.LOW: 1>, <Mode.READ: 1>)");
+    let MontyObject::Tuple(items) = output else {
+        panic!("expected a tuple, got {output:?}");
+    };
+    let members: Vec<(&str, u64, &str)> = items
+        .iter()
+        .map(|item| match item {
+            MontyObject::Enum {
+                class_name,
+                class_id,
+                name,
+                ..
+            } => (class_name.as_str(), *class_id, name.as_str()),
+            _ => panic!("expected an enum member, got {item:?}"),
+        })
+        .collect();
+    assert_eq!(
+        members
+            .iter()
+            .map(|(class, _, name)| (*class, *name))
+            .collect::<Vec<_>>(),
+        vec![("Level", "HIGH"), ("Level", "LOW"), ("Mode", "READ")]
+    );
+    assert_eq!(members[0].1, members[1].1, "members of a class share its id");
+    assert_ne!(members[0].1, members[2].1, "classes have different ids");
+    let result = items[0].clone();
+
+    // enum members are output-only
+    let ex = MontyRun::new("x".to_owned(), "test.py", vec!["x".to_owned()], vec![]).unwrap();
+    assert!(
+        ex.run_no_limits(vec![result]).is_err(),
+        "enum members should be invalid inputs"
+    );
+}
+
+#[test]
+fn decimal_input_output() {
+    let ex = MontyRun::new("x + 1".to_owned(), "test.py", vec!["x".to_owned()], vec![]).unwrap();
+    let result = ex.run_no_limits(vec![MontyObject::Decimal("0.10".to_owned())]).unwrap();
+    assert_eq!(result, MontyObject::Decimal("1.10".to_owned()));
+    assert_eq!(result.py_repr(), "Decimal('1.10')");
+}
+
+#[test]
+fn invalid_input_decimal() {
+    let ex = MontyRun::new("x".to_owned(), "test.py", vec!["x".to_owned()], vec![]).unwrap();
+    let result = ex.run_no_limits(vec![MontyObject::Decimal("one".to_owned())]);
+    assert!(result.is_err(), "an unparsable Decimal should be invalid");
+}
+
+#[test]
+fn fraction_input_output() {
+    let ex = MontyRun::new("x * 2".to_owned(), "test.py", vec!["x".to_owned()], vec![]).unwrap();
+    // inputs are reduced to lowest terms
+    let result = ex
+        .run_no_limits(vec![MontyObject::Fraction {
+            numerator: BigInt::from(2),
+            denominator: BigInt::from(-6),
+        }])
+        .unwrap();
+    assert_eq!(
+        result,
+        MontyObject::Fraction {
+            numerator: BigInt::from(-2),
+            denominator: BigInt::from(3),
+        }
+    );
+    assert_eq!(result.py_repr(), "Fraction(-2, 3)");
+
+    let ex = MontyRun::new("x".to_owned(), "test.py", vec!["x".to_owned()], vec![]).unwrap();
+    let result = ex.run_no_limits(vec![MontyObject::Fraction {
+        numerator: BigInt::from(1),
+        denominator: BigInt::from(0),
+    }]);
+    assert!(result.is_err(), "a Fraction with a zero denominator should be invalid");
+}
+
 // === Function Parameter Shadowing Tests ===
 // These tests verify that function parameters properly shadow script inputs with the same name.
 
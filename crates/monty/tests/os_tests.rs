@@ -4,7 +4,7 @@
 //! `RunProgress::OsCall` with the correct `OsFunction` variant and arguments,
 //! and that return values are correctly used by Python code.
 
-use monty::{MontyObject, MontyRun, NoLimitTracker, OsFunction, RunProgress, StdPrint, file_stat};
+use monty::{ExcType, MontyObject, MontyRun, NoLimitTracker, OsFunction, RunProgress, StdPrint, file_stat};
 
 /// Helper to run code and extract the OsCall progress.
 ///
@@ -38,6 +38,9 @@ fn run_to_oscall(code: &str) -> (OsFunction, Vec<MontyObject>) {
                 | OsFunction::Rename => MontyObject::None,
                 OsFunction::Getenv => MontyObject::String("mock_env_value".to_owned()),
                 OsFunction::GetEnviron => MontyObject::Dict(vec![].into()),
+                OsFunction::DateTimeNow => mock_now(),
+                OsFunction::RandomSeed => MontyObject::Int(42),
+                OsFunction::Uuid4 => MontyObject::Bytes(vec![0xab; 16]),
             };
             let _ = state.run(mock_result, &mut StdPrint);
             (function, args)
@@ -46,6 +49,20 @@ fn run_to_oscall(code: &str) -> (OsFunction, Vec<MontyObject>) {
     }
 }
 
+/// The current time the host reports in tests: 2024-01-15 12:30:45.5 UTC.
+fn mock_now() -> MontyObject {
+    MontyObject::DateTime {
+        year: 2024,
+        month: 1,
+        day: 15,
+        hour: 12,
+        minute: 30,
+        second: 45,
+        microsecond: 500_000,
+        utc_offset: Some(0),
+    }
+}
+
 /// Helper to run code, provide an OS call result, and get the final value.
 fn run_oscall_with_result(code: &str, mock_result: MontyObject) -> (OsFunction, Vec<MontyObject>, MontyObject) {
     let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
@@ -457,3 +474,268 @@ import os
     assert_eq!(func, OsFunction::GetEnviron);
     assert_eq!(result, MontyObject::Bool(true));
 }
+
+// =============================================================================
+// datetime.now() and date.today() tests
+// =============================================================================
+
+#[test]
+fn datetime_now_yields_oscall() {
+    let code = r"
+import datetime
+datetime.datetime.now()
+";
+    let (func, args) = run_to_oscall(code);
+    assert_eq!(func, OsFunction::DateTimeNow);
+    assert_eq!(args, vec![MontyObject::None]);
+}
+
+#[test]
+fn datetime_now_naive_is_utc() {
+    let code = r"
+from datetime import datetime
+datetime.now()
+";
+    let (func, _, result) = run_oscall_with_result(code, mock_now());
+    assert_eq!(func, OsFunction::DateTimeNow);
+    assert_eq!(
+        result,
+        MontyObject::DateTime {
+            year: 2024,
+            month: 1,
+            day: 15,
+            hour: 12,
+            minute: 30,
+            second: 45,
+            microsecond: 500_000,
+            utc_offset: None,
+        }
+    );
+}
+
+#[test]
+fn datetime_now_with_timezone() {
+    let code = r"
+from datetime import datetime, timedelta, timezone
+datetime.now(timezone(timedelta(hours=-5)))
+";
+    let (func, args, result) = run_oscall_with_result(code, mock_now());
+    assert_eq!(func, OsFunction::DateTimeNow);
+    assert_eq!(
+        args,
+        vec![MontyObject::TimeZone {
+            utc_offset: -5 * 3600,
+            name: None,
+        }]
+    );
+    assert_eq!(
+        result,
+        MontyObject::DateTime {
+            year: 2024,
+            month: 1,
+            day: 15,
+            hour: 7,
+            minute: 30,
+            second: 45,
+            microsecond: 500_000,
+            utc_offset: Some(-5 * 3600),
+        }
+    );
+}
+
+#[test]
+fn date_today() {
+    let code = r"
+from datetime import date
+date.today()
+";
+    let late_evening = MontyObject::DateTime {
+        year: 2024,
+        month: 12,
+        day: 31,
+        hour: 23,
+        minute: 0,
+        second: 0,
+        microsecond: 0,
+        utc_offset: Some(-3600),
+    };
+    let (func, args, result) = run_oscall_with_result(code, late_evening);
+    assert_eq!(func, OsFunction::DateTimeNow);
+    assert_eq!(args, vec![MontyObject::None]);
+    // the host's aware time is converted to UTC, which is already the next day
+    assert_eq!(
+        result,
+        MontyObject::Date {
+            year: 2025,
+            month: 1,
+            day: 1,
+        }
+    );
+}
+
+#[test]
+fn datetime_now_result_used() {
+    let code = r"
+from datetime import datetime, timezone
+now = datetime.now(timezone.utc)
+f'{now:%Y-%m-%d %H:%M}', now.isoformat()
+";
+    let (_, _, result) = run_oscall_with_result(code, mock_now());
+    assert_eq!(
+        result,
+        MontyObject::Tuple(vec![
+            MontyObject::String("2024-01-15 12:30".to_owned()),
+            MontyObject::String("2024-01-15T12:30:45.500000+00:00".to_owned()),
+        ])
+    );
+}
+
+#[test]
+fn datetime_now_bad_result() {
+    let code = r"
+from datetime import datetime
+datetime.now()
+";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut StdPrint).unwrap();
+    let RunProgress::OsCall { state, .. } = progress else {
+        panic!("expected OsCall, got {progress:?}");
+    };
+    let exc = state.run(MontyObject::Int(0), &mut StdPrint).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::TypeError);
+    assert_eq!(
+        exc.message(),
+        Some("datetime.now() OS call must return a datetime, not 'int'")
+    );
+}
+
+// =============================================================================
+// random seed tests
+// =============================================================================
+
+/// Runs code which doesn't need the host, returning its result.
+fn run_no_oscall(code: &str) -> MontyObject {
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut StdPrint).unwrap();
+    progress.into_complete().expect("expected Complete")
+}
+
+#[test]
+fn random_unseeded_yields_oscall() {
+    let (func, args) = run_to_oscall("import random; random.random()");
+    assert_eq!(func, OsFunction::RandomSeed);
+    assert!(args.is_empty());
+}
+
+#[test]
+fn random_seeded_does_not_yield() {
+    let result = run_no_oscall("import random; random.seed(7); 0 <= random.random() < 1");
+    assert_eq!(result, MontyObject::Bool(true));
+}
+
+#[test]
+fn random_host_seed_matches_explicit_seed() {
+    let code = r"
+import random
+[random.randint(1, 100) for _ in range(5)], random.Random(3).random()
+";
+    let (func, _, result) = run_oscall_with_result(code, MontyObject::Int(42));
+    assert_eq!(func, OsFunction::RandomSeed);
+    let expected = run_no_oscall(&format!("import random\nrandom.seed(42)\n{}", code.trim_start()));
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn random_seed_none_and_instances_yield() {
+    for code in [
+        "import random; random.seed(); random.random()",
+        "import random; random.seed(None); random.random()",
+        "import random; random.Random().random()",
+        "import random; r = random.Random(1); r.seed(); r.random()",
+    ] {
+        let (func, _, result) = run_oscall_with_result(code, MontyObject::Int(42));
+        assert_eq!(func, OsFunction::RandomSeed, "{code}");
+        let expected = run_no_oscall("import random; random.Random(42).random()");
+        assert_eq!(result, expected, "{code}");
+    }
+}
+
+#[test]
+fn random_state_survives_dump_load() {
+    let code = r"
+import random
+from pathlib import Path
+first = random.random()
+Path('/tmp').exists()
+first, random.random(), random.gauss(0, 1)
+";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut StdPrint).unwrap();
+    let RunProgress::OsCall { function, state, .. } = progress else {
+        panic!("expected OsCall, got {progress:?}");
+    };
+    assert_eq!(function, OsFunction::RandomSeed);
+    let progress = state.run(MontyObject::Int(42), &mut StdPrint).unwrap();
+
+    let bytes = progress.dump().unwrap();
+    let progress = RunProgress::<NoLimitTracker>::load(&bytes).unwrap();
+    let RunProgress::OsCall { function, state, .. } = progress else {
+        panic!("expected OsCall, got {progress:?}");
+    };
+    assert_eq!(function, OsFunction::Exists);
+    let result = state
+        .run(MontyObject::Bool(true), &mut StdPrint)
+        .unwrap()
+        .into_complete()
+        .expect("expected Complete after resume");
+
+    let expected =
+        run_no_oscall("import random; random.seed(42); random.random(), random.random(), random.gauss(0, 1)");
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn random_seed_bad_result() {
+    let runner = MontyRun::new("import random; random.random()".to_owned(), "test.py", vec![], vec![]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut StdPrint).unwrap();
+    let RunProgress::OsCall { state, .. } = progress else {
+        panic!("expected OsCall, got {progress:?}");
+    };
+    let exc = state
+        .run(MontyObject::String("seed".to_owned()), &mut StdPrint)
+        .unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::TypeError);
+    assert_eq!(
+        exc.message(),
+        Some("random.seed() OS call must return an int, not 'str'")
+    );
+}
+
+// =============================================================================
+// uuid.uuid4() tests
+// =============================================================================
+
+#[test]
+fn uuid4_yields_oscall() {
+    let (func, args, result) =
+        run_oscall_with_result("import uuid; str(uuid.uuid4())", MontyObject::Bytes(vec![0xff; 16]));
+    assert_eq!(func, OsFunction::Uuid4);
+    assert!(args.is_empty());
+    // The version and variant bits are set on the host's bytes
+    assert_eq!(
+        result,
+        MontyObject::String("ffffffff-ffff-4fff-bfff-ffffffffffff".to_owned())
+    );
+}
+
+#[test]
+fn uuid4_bad_result() {
+    let runner = MontyRun::new("import uuid; uuid.uuid4()".to_owned(), "test.py", vec![], vec![]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut StdPrint).unwrap();
+    let RunProgress::OsCall { state, .. } = progress else {
+        panic!("expected OsCall, got {progress:?}");
+    };
+    let exc = state.run(MontyObject::Bytes(vec![0; 8]), &mut StdPrint).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::ValueError);
+    assert_eq!(exc.message(), Some("uuid.uuid4() OS call must return 16 bytes, not 8"));
+}
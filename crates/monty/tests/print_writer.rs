@@ -1,4 +1,6 @@
-use monty::{CollectStringPrint, MontyRun, NoLimitTracker, NoPrint};
+use std::borrow::Cow;
+
+use monty::{CollectStringPrint, MontyException, MontyObject, MontyRun, NoLimitTracker, NoPrint, PrintWriter};
 
 #[test]
 fn print_single_string() {
@@ -218,3 +220,71 @@ fn print_multiline_sep() {
     ex.run(vec![], NoLimitTracker, &mut writer).unwrap();
     assert_eq!(writer.output(), "1\n2\n3\n");
 }
+
+// === stderr tests ===
+
+#[test]
+fn print_file_stderr() {
+    let code = "import sys\nprint('result')\nprint('warning', 1, file=sys.stderr)\nprint('out', file=sys.stdout)";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+    let mut writer = CollectStringPrint::new();
+    ex.run(vec![], NoLimitTracker, &mut writer).unwrap();
+    assert_eq!(writer.output(), "result\nout\n");
+    assert_eq!(writer.stderr_output(), "warning 1\n");
+}
+
+#[test]
+fn print_file_stderr_custom_sep_and_end() {
+    let code = "import sys\nprint('a', 'b', sep='-', end='!', file=sys.stderr)";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+    let mut writer = CollectStringPrint::new();
+    ex.run(vec![], NoLimitTracker, &mut writer).unwrap();
+    assert_eq!(writer.output(), "");
+    assert_eq!(writer.stderr_output(), "a-b!");
+}
+
+#[test]
+fn print_file_none_uses_stdout() {
+    let ex = MontyRun::new("print('x', file=None)".to_owned(), "test.py", vec![], vec![]).unwrap();
+    let mut writer = CollectStringPrint::new();
+    ex.run(vec![], NoLimitTracker, &mut writer).unwrap();
+    assert_eq!(writer.output(), "x\n");
+}
+
+#[test]
+fn sys_stream_write() {
+    let code = "import sys\nn = sys.stdout.write('out')\nsys.stderr.write('err')\nsys.stderr.flush()\nn";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+    let mut writer = CollectStringPrint::new();
+    let result = ex.run(vec![], NoLimitTracker, &mut writer).unwrap();
+    assert_eq!(result, MontyObject::Int(3));
+    let (stdout, stderr) = writer.into_outputs();
+    assert_eq!(stdout, "out");
+    assert_eq!(stderr, "err");
+}
+
+/// A writer which only implements the `stdout_*` methods, as writers did before stderr
+/// was supported.
+#[derive(Default)]
+struct StdoutOnly(String);
+
+impl PrintWriter for StdoutOnly {
+    fn stdout_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        self.0.push_str(&output);
+        Ok(())
+    }
+
+    fn stdout_push(&mut self, end: char) -> Result<(), MontyException> {
+        self.0.push(end);
+        Ok(())
+    }
+}
+
+#[test]
+fn stderr_defaults_to_stdout() {
+    let code = "import sys\nprint('out')\nprint('err', file=sys.stderr)\nsys.stderr.write('!')";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+    let mut writer = StdoutOnly::default();
+    ex.run(vec![], NoLimitTracker, &mut writer).unwrap();
+    assert_eq!(writer.0, "out\nerr\n!");
+}
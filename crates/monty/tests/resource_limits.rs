@@ -660,3 +660,22 @@ fn bigint_rejected_before_allocation() {
         Some("memory limit exceeded: 250000 bytes > 100000 bytes")
     );
 }
+
+/// Test that `bytes(n)` and `bytearray(n)` check the size before allocating the zeros.
+#[test]
+fn zeroed_bytes_rejected_before_allocation() {
+    for code in ["bytes(10 ** 12)", "bytearray(10 ** 12)"] {
+        let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+
+        let limits = ResourceLimits::new().max_memory(1_000_000);
+        let result = ex.run(vec![], LimitedTracker::new(limits), &mut StdPrint);
+
+        let exc = result.expect_err("should be rejected before allocation");
+        assert_eq!(exc.exc_type(), ExcType::MemoryError, "{code}");
+        assert_eq!(
+            exc.message(),
+            Some("memory limit exceeded: 1000000000000 bytes > 1000000 bytes"),
+            "{code}"
+        );
+    }
+}
@@ -91,6 +91,8 @@ def run_file_and_get_traceback(
                     # Keep the "Traceback (most recent call last):" header
                     if frame.startswith('Traceback'):
                         result_frames.append(frame)
+                        # each exception in a chain has its own traceback
+                        found_user_code = False
                         continue
                     elif '__asy.run(__test_main())' in frame:
                         # Skip the asyncio.run(__test_main()) wrapper frame
@@ -110,7 +112,8 @@ def run_file_and_get_traceback(
                     if not found_user_code and frame.startswith(f'  File "{file_path}"'):
                         found_user_code = True
 
-                    if found_user_code:
+                    # lines which aren't frames are messages and the separators between chained exceptions
+                    if found_user_code or not frame.startswith('  File '):
                         if async_mode:
                             if adjusted_frame := _adjust_async_frame(frame, file_path, file_name, line_offset):
                                 result_frames.append(adjusted_frame)